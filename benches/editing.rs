@@ -0,0 +1,197 @@
+//! Benchmarks for the editing hot paths
+//!
+//! Run with `cargo bench --bench editing`; pass a filter to run a group,
+//! e.g. `cargo bench --bench editing -- markers`. Criterion compares each
+//! run with the previous one, so run it before and after a change.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use fresh::model::buffer::Buffer;
+use fresh::model::marker::MarkerList;
+use fresh::model::piece_tree::Position;
+use fresh::primitives::highlighter::{Highlighter, Language};
+use fresh::view::theme::Theme;
+
+/// Small deterministic generator, so every run edits the same positions
+struct Positions(u64);
+
+impl Positions {
+    fn new() -> Self {
+        Self(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Next position in `0..=max`
+    fn next(&mut self, max: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % (max as u64 + 1)) as usize
+    }
+}
+
+/// `lines` lines of prose
+fn text(lines: usize) -> String {
+    (0..lines)
+        .map(|i| format!("Line {} of the benchmark text, with a few more words.\n", i))
+        .collect()
+}
+
+/// `lines` lines of Rust
+fn rust_source(lines: usize) -> String {
+    let mut source = String::new();
+    while source.lines().count() < lines {
+        let n = source.len();
+        source.push_str(&format!(
+            "/// Adds {n} to the value\nfn add_{n}(value: u64) -> u64 {{\n    let s = \"{n}\";\n    value + {n} // {n}\n}}\n\n"
+        ));
+    }
+    source
+}
+
+fn buffer(text: &str) -> Buffer {
+    Buffer::from_str(text, usize::MAX)
+}
+
+fn bench_buffer_edits(c: &mut Criterion) {
+    let base = text(10_000);
+    let mut group = c.benchmark_group("buffer");
+
+    group.bench_function("insert_random_1000", |b| {
+        b.iter_batched(
+            || buffer(&base),
+            |mut buffer| {
+                let mut positions = Positions::new();
+                for _ in 0..1000 {
+                    let offset = positions.next(buffer.len());
+                    buffer.insert(offset, "x");
+                }
+                buffer
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("delete_random_1000", |b| {
+        b.iter_batched(
+            || buffer(&base),
+            |mut buffer| {
+                let mut positions = Positions::new();
+                for _ in 0..1000 {
+                    let start = positions.next(buffer.len() - 1);
+                    buffer.delete(start..start + 1);
+                }
+                buffer
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_line_lookup(c: &mut Criterion) {
+    let lines = 100_000;
+    let mut edited = buffer(&text(lines));
+    // Edits split the text into many pieces, as in a long editing session
+    let mut positions = Positions::new();
+    for _ in 0..1000 {
+        let offset = positions.next(edited.len());
+        edited.insert(offset, "y");
+    }
+    let mut group = c.benchmark_group("line_lookup");
+
+    group.bench_function("line_start_offset_100k_lines", |b| {
+        let mut positions = Positions::new();
+        b.iter(|| black_box(edited.line_start_offset(positions.next(lines - 1))))
+    });
+
+    group.bench_function("position_to_offset_100k_lines", |b| {
+        let mut positions = Positions::new();
+        b.iter(|| {
+            black_box(edited.position_to_offset(Position {
+                line: positions.next(lines - 1),
+                column: 10,
+            }))
+        })
+    });
+
+    group.bench_function("offset_to_position_100k_lines", |b| {
+        let mut positions = Positions::new();
+        let len = edited.len();
+        b.iter(|| black_box(edited.offset_to_position(positions.next(len))))
+    });
+
+    group.finish();
+}
+
+fn bench_markers(c: &mut Criterion) {
+    let size = 1_000_000;
+    let mut markers = MarkerList::new();
+    let mut positions = Positions::new();
+    for _ in 0..10_000 {
+        markers.create(positions.next(size), false);
+    }
+    let mut group = c.benchmark_group("markers");
+
+    group.bench_function("adjust_for_edit_10k_markers", |b| {
+        let mut positions = Positions::new();
+        b.iter(|| {
+            let position = positions.next(size);
+            markers.adjust_for_insert(position, 5);
+            markers.adjust_for_delete(position, 5);
+        })
+    });
+
+    group.bench_function("query_range_10k_markers", |b| {
+        let mut positions = Positions::new();
+        b.iter(|| {
+            let start = positions.next(size - 5000);
+            black_box(markers.query_range(start, start + 5000))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_highlight(c: &mut Criterion) {
+    let source = buffer(&rust_source(5000));
+    let theme = Theme::dark();
+    let mut group = c.benchmark_group("highlight");
+    group.sample_size(20);
+
+    group.bench_function("rust_5k_lines_full", |b| {
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        b.iter(|| {
+            highlighter.invalidate_all();
+            black_box(highlighter.highlight_viewport(
+                &source,
+                0,
+                source.len(),
+                &theme,
+                source.len(),
+            ))
+        })
+    });
+
+    group.bench_function("rust_5k_lines_viewport", |b| {
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        let mut positions = Positions::new();
+        b.iter(|| {
+            highlighter.invalidate_all();
+            let start = positions.next(source.len() - 4000);
+            black_box(highlighter.highlight_viewport(&source, start, start + 4000, &theme, 10_000))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_buffer_edits,
+    bench_line_lookup,
+    bench_markers,
+    bench_highlight
+);
+criterion_main!(benches);
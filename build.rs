@@ -63,6 +63,16 @@ struct FieldInfo {
 fn rust_type_to_ts(rust_type: &str) -> String {
     let rust_type = rust_type.trim();
 
+    // Handle impl Future<Output = T> of async ops written as plain fns
+    if let Some(output) = rust_type
+        .strip_prefix("impl std::future::Future<Output = ")
+        .or_else(|| rust_type.strip_prefix("impl Future<Output = "))
+    {
+        if let Some(inner) = output.strip_suffix('>') {
+            return rust_type_to_ts(inner);
+        }
+    }
+
     // Handle Option<T>
     if rust_type.starts_with("Option<") && rust_type.ends_with('>') {
         let inner = &rust_type[7..rust_type.len() - 1];
@@ -383,6 +393,11 @@ fn parse_param(param_str: &str) -> Option<ParamInfo> {
         return None;
     }
 
+    // Skip the v8 scope of ops that inspect the caller
+    if param_str.contains("v8::PinScope") {
+        return None;
+    }
+
     // Check for #[string] or #[serde] attribute
     let is_string = param_str.contains("#[string]");
     let is_serde = param_str.contains("#[serde]");
@@ -1021,7 +1036,11 @@ fn categorize_op(js_name: &str, is_async: bool) -> &'static str {
     }
 
     // Environment operations
-    if js_name == "getEnv" || js_name == "getCwd" {
+    if js_name == "getEnv"
+        || js_name == "getCwd"
+        || js_name == "getWorkspaceRoots"
+        || js_name == "filterIgnored"
+    {
         return "environment";
     }
 
@@ -1036,7 +1055,7 @@ fn categorize_op(js_name: &str, is_async: bool) -> &'static str {
     }
 
     // Status ops
-    if js_name == "setStatus" || js_name == "debug" {
+    if js_name == "setStatus" || js_name == "showNotification" || js_name == "debug" {
         return "status";
     }
 
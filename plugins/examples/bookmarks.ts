@@ -45,7 +45,7 @@ function getCurrentLocation(): {
 }
 
 // Helper: Get actual line number using the API
-function getCurrentLineCol(): { line: number; column: number } {
+async function getCurrentLineCol(): Promise<{ line: number; column: number }> {
   // Use the actual getCursorLine API for accurate line number
   const lineNumber = editor.getCursorLine();
 
@@ -59,7 +59,7 @@ function getCurrentLineCol(): { line: number; column: number } {
   if (bufferInfo && cursorPos > 0) {
     // Read a small chunk before cursor to find line start
     const readStart = Math.max(0, cursorPos - 1000);
-    const textBefore = editor.getBufferText(bufferId, readStart, cursorPos);
+    const textBefore = await editor.getBufferText(bufferId, readStart, cursorPos);
     const lastNewline = textBefore.lastIndexOf("\n");
     if (lastNewline !== -1) {
       column = cursorPos - (readStart + lastNewline);
@@ -73,9 +73,9 @@ function getCurrentLineCol(): { line: number; column: number } {
 }
 
 // Action: Add bookmark at current position
-globalThis.bookmark_add = function (): void {
+globalThis.bookmark_add = async function (): Promise<void> {
   const { path, position, splitId } = getCurrentLocation();
-  const { line, column } = getCurrentLineCol();
+  const { line, column } = await getCurrentLineCol();
 
   if (!path) {
     editor.setStatus("Cannot bookmark: buffer has no file path");
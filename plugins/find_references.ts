@@ -3,8 +3,9 @@
 /**
  * Find References Plugin (TypeScript)
  *
- * Displays LSP find references results in a virtual buffer split view.
- * Listens for lsp_references hook from the editor and shows results.
+ * Displays find references results, grouped by file, in a virtual buffer
+ * split view. Listens for lsp_references hook from the editor (fired for
+ * LSP results and for the project search fallback) and shows results.
  * Uses cursor movement for navigation (Up/Down/j/k work naturally).
  */
 
@@ -52,14 +53,7 @@ function getRelativePath(filePath: string): string {
 
 // Format a reference for display with line preview
 function formatReference(item: ReferenceItem): string {
-  const displayPath = getRelativePath(item.file);
-  const location = `${displayPath}:${item.line}:${item.column}`;
-
-  // Truncate location if too long, leaving room for line text
-  const maxLocationLen = 50;
-  const truncatedLocation = location.length > maxLocationLen
-    ? "..." + location.slice(-(maxLocationLen - 3))
-    : location.padEnd(maxLocationLen);
+  const location = `${item.line}:${item.column}`.padEnd(10);
 
   // Get line text preview (truncated)
   const lineText = item.lineText || "";
@@ -69,7 +63,7 @@ function formatReference(item: ReferenceItem): string {
     ? trimmedLine.slice(0, maxLineLen - 3) + "..."
     : trimmedLine;
 
-  return `  ${truncatedLocation}  ${displayLine}\n`;
+  return `    ${location}  ${displayLine}\n`;
 }
 
 // Build entries for the virtual buffer
@@ -91,9 +85,17 @@ function buildPanelEntries(): TextPropertyEntry[] {
       properties: { type: "empty" },
     });
   } else {
-    // Add each reference
+    // Add each reference, with a header line before each file
+    let currentFile: string | null = null;
     for (let i = 0; i < currentReferences.length; i++) {
       const ref = currentReferences[i];
+      if (ref.file !== currentFile) {
+        currentFile = ref.file;
+        entries.push({
+          text: `  ${getRelativePath(ref.file)}\n`,
+          properties: { type: "file" },
+        });
+      }
       entries.push({
         text: formatReference(ref),
         properties: {
@@ -170,8 +172,13 @@ async function showReferencesPanel(symbol: string, references: ReferenceItem[]):
     sourceSplitId = editor.getActiveSplitId();
   }
 
-  // Limit results
-  const limitedRefs = references.slice(0, MAX_RESULTS);
+  // Group by file, then limit results
+  const sortedRefs = [...references].sort((a, b) =>
+    a.file === b.file
+      ? a.line - b.line || a.column - b.column
+      : a.file < b.file ? -1 : 1
+  );
+  const limitedRefs = sortedRefs.slice(0, MAX_RESULTS);
 
   // Set references and symbol
   currentSymbol = symbol;
@@ -246,14 +253,11 @@ globalThis.on_references_cursor_moved = function (data: {
     return;
   }
 
-  // Get cursor line to determine which reference is selected
-  // getCursorLine() returns the line for the active buffer
-  const cursorLine = editor.getCursorLine();
-
-  // Line 0 is header, lines 1 to N are references
-  const refIndex = cursorLine - 1;
+  // File header lines sit between references, so use the index property
+  const props = editor.getTextPropertiesAtCursor(referencesBufferId);
+  const refIndex = props.length > 0 ? props[0].index : undefined;
 
-  if (refIndex >= 0 && refIndex < currentReferences.length) {
+  if (typeof refIndex === "number") {
     editor.setStatus(`Reference ${refIndex + 1}/${currentReferences.length}`);
   }
 };
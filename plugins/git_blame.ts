@@ -110,6 +110,13 @@ editor.defineMode(
 // Git Blame Parsing
 // =============================================================================
 
+/**
+ * Folder to run git in for a file
+ */
+function fileDir(filePath: string): string | undefined {
+  return editor.pathDirname(filePath) || undefined;
+}
+
 /**
  * Parse git blame --porcelain output
  */
@@ -122,7 +129,8 @@ async function fetchGitBlame(filePath: string, commit: string | null): Promise<B
 
   args.push("--", filePath);
 
-  const result = await editor.spawnProcess("git", args);
+  // Run in the file's folder, which may belong to another workspace root
+  const result = await editor.spawnProcess("git", args, fileDir(filePath));
 
   if (result.exit_code !== 0) {
     editor.setStatus(`Git blame error: ${result.stderr}`);
@@ -243,7 +251,11 @@ function formatRelativeDate(timestamp: number): string {
 async function fetchFileContent(filePath: string, commit: string | null): Promise<string> {
   if (commit) {
     // Get historical file content
-    const result = await editor.spawnProcess("git", ["show", `${commit}:${filePath}`]);
+    const result = await editor.spawnProcess(
+      "git",
+      ["show", `${commit}:./${editor.pathBasename(filePath)}`],
+      fileDir(filePath)
+    );
     if (result.exit_code === 0) {
       return result.stdout;
     }
@@ -5,12 +5,18 @@
  *
  * Provides interactive file finding functionality with fuzzy search
  * for git-tracked files. Uses the prompt API for interactive selection.
+ * Every workspace folder is searched; files of folders other than the
+ * first are shown under the folder's name.
  */
 
 // State management
 let allFiles: string[] = [];
 let filteredFiles: string[] = [];
 let isLoading = false;
+// Path to open for each entry of allFiles
+let filePaths = new Map<string, string>();
+// Workspace roots the files were loaded from
+let loadedRoots = "";
 
 // Simple fuzzy filter function
 function fuzzyMatch(str: string, pattern: string): boolean {
@@ -119,17 +125,36 @@ async function loadGitFiles(): Promise<void> {
   editor.setStatus("Loading git files...");
 
   try {
-    const result = await editor.spawnProcess("git", ["ls-files"]);
-
-    if (result.exit_code === 0) {
-      allFiles = result.stdout.split("\n").filter((line) => line.trim() !== "");
+    const roots = editor.getWorkspaceRoots();
+    allFiles = [];
+    filePaths = new Map();
+    loadedRoots = roots.join("\n");
+
+    for (const [index, root] of roots.entries()) {
+      const result = await editor.spawnProcess("git", ["ls-files"], root);
+      if (result.exit_code !== 0) {
+        editor.debug(`Failed to load git files in ${root}: ${result.stderr}`);
+        if (index === 0) {
+          editor.setStatus(`Error loading git files: ${result.stderr}`);
+        }
+        continue;
+      }
+      const files = result.stdout.split("\n").filter((file) => file.trim() !== "");
+      // Files of the first root open relative to the working directory
+      const paths = files.map((file) => (index === 0 ? file : editor.pathJoin(root, file)));
+      // Tracked files can still match .ignore files or the ignore_patterns setting
+      const kept = new Set(editor.filterIgnored(paths));
+      for (const [i, file] of files.entries()) {
+        if (!kept.has(paths[i])) continue;
+        const name = index === 0 ? file : `${editor.pathBasename(root)}/${file}`;
+        allFiles.push(name);
+        filePaths.set(name, paths[i]);
+      }
+    }
 
+    if (allFiles.length > 0) {
       editor.debug(`Loaded ${allFiles.length} git-tracked files`);
       editor.setStatus(`Git Find File: ${allFiles.length} files indexed`);
-    } else {
-      editor.debug(`Failed to load git files: ${result.stderr}`);
-      editor.setStatus(`Error loading git files: ${result.stderr}`);
-      allFiles = [];
     }
   } catch (e) {
     editor.debug(`Exception loading git files: ${e}`);
@@ -154,8 +179,9 @@ function filesToSuggestions(files: string[]): PromptSuggestion[] {
 
 // Global function to start file finder
 globalThis.start_git_find_file = async function (): Promise<void> {
-  // Load files if not already loaded
-  if (allFiles.length === 0 && !isLoading) {
+  // Load files if not already loaded, or the workspace folders changed
+  const roots = editor.getWorkspaceRoots().join("\n");
+  if ((allFiles.length === 0 || roots !== loadedRoots) && !isLoading) {
     await loadGitFiles();
   }
 
@@ -225,7 +251,7 @@ globalThis.onGitFindFilePromptConfirmed = function (args: {
     editor.debug(`Opening file: ${selectedFile}`);
 
     // Open the file at line 1
-    editor.openFile(selectedFile, 1, 1);
+    editor.openFile(filePaths.get(selectedFile) ?? selectedFile, 1, 1);
     editor.setStatus(`Opened ${selectedFile}`);
   } else if (args.input.trim() !== "") {
     // Try to open input directly if it's a valid file path
@@ -233,7 +259,7 @@ globalThis.onGitFindFilePromptConfirmed = function (args: {
 
     // Check if the exact input matches any file
     if (allFiles.includes(inputFile)) {
-      editor.openFile(inputFile, 1, 1);
+      editor.openFile(filePaths.get(inputFile) ?? inputFile, 1, 1);
       editor.setStatus(`Opened ${inputFile}`);
     } else {
       editor.setStatus(`File not found: ${inputFile}`);
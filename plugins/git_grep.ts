@@ -4,6 +4,7 @@
  * Git Grep Plugin
  *
  * Provides interactive git grep functionality with live search results.
+ * Every workspace folder is searched.
  */
 
 interface GrepMatch {
@@ -32,29 +33,41 @@ function parseGitGrepLine(line: string): GrepMatch | null {
 }
 
 // Parse git grep output into suggestions
-function parseGitGrepOutput(stdout: string): {
+// Files are relative to the working directory, or under `root` when given.
+// Matches in files that .ignore files or the ignore_patterns setting exclude
+// are dropped.
+function parseGitGrepOutput(stdout: string, root?: string): {
   results: GrepMatch[];
   suggestions: PromptSuggestion[];
 } {
-  const results: GrepMatch[] = [];
-  const suggestions: PromptSuggestion[] = [];
-
+  const matches: GrepMatch[] = [];
   for (const line of stdout.split("\n")) {
     if (!line.trim()) continue;
     const match = parseGitGrepLine(line);
     if (match) {
-      results.push(match);
-      suggestions.push({
-        text: `${match.file}:${match.line}:${match.column}`,
-        description: match.content,
-        value: `${match.file}:${match.line}:${match.column}`,
-        disabled: false,
-      });
-
-      // Limit to 100 results for performance
-      if (results.length >= 100) {
-        break;
+      if (root) {
+        match.file = editor.pathJoin(root, match.file);
       }
+      matches.push(match);
+    }
+  }
+  const kept = new Set(editor.filterIgnored([...new Set(matches.map((m) => m.file))]));
+
+  const results: GrepMatch[] = [];
+  const suggestions: PromptSuggestion[] = [];
+  for (const match of matches) {
+    if (!kept.has(match.file)) continue;
+    results.push(match);
+    suggestions.push({
+      text: `${match.file}:${match.line}:${match.column}`,
+      description: match.content,
+      value: `${match.file}:${match.line}:${match.column}`,
+      disabled: false,
+    });
+
+    // Limit to 100 results for performance
+    if (results.length >= 100) {
+      break;
     }
   }
 
@@ -88,32 +101,44 @@ globalThis.onGitGrepPromptChanged = function(args: {
     return true;
   }
 
-  // Spawn git grep asynchronously
-  const cwd = editor.getCwd();
-  editor.spawnProcess("git", ["grep", "-n", "--column", "-I", "--", query], cwd)
-    .then((result) => {
-      if (result.exit_code === 0) {
-        // Parse results and update suggestions
-        const { results, suggestions } = parseGitGrepOutput(result.stdout);
-        gitGrepResults = results;
+  // Spawn git grep asynchronously in each workspace folder
+  const roots = editor.getWorkspaceRoots();
+  Promise.all(
+    roots.map((root) =>
+      editor.spawnProcess("git", ["grep", "-n", "--column", "-I", "--", query], root)
+    )
+  )
+    .then((runs) => {
+      // Matches in the first folder stay relative to the working directory
+      const results: GrepMatch[] = [];
+      const suggestions: PromptSuggestion[] = [];
+      for (const [index, run] of runs.entries()) {
+        if (run.exit_code !== 0) continue;
+        const found = parseGitGrepOutput(run.stdout, index === 0 ? undefined : roots[index]);
+        results.push(...found.results);
+        suggestions.push(...found.suggestions);
+      }
+      // git grep exits with 1 when nothing matched
+      const failed = runs.find((run) => run.exit_code !== 0 && run.exit_code !== 1);
+
+      if (results.length > 0 || !failed) {
+        // Limit to 100 results for performance
+        gitGrepResults = results.slice(0, 100);
 
         // Update prompt with suggestions
-        editor.setPromptSuggestions(suggestions);
+        editor.setPromptSuggestions(suggestions.slice(0, 100));
 
         // Update status
-        if (results.length > 0) {
-          editor.setStatus(`Found ${results.length} matches`);
+        if (gitGrepResults.length > 0) {
+          editor.setStatus(`Found ${gitGrepResults.length} matches`);
         } else {
           editor.setStatus("No matches found");
         }
-      } else if (result.exit_code === 1) {
-        // No matches found (git grep returns 1)
-        gitGrepResults = [];
-        editor.setPromptSuggestions([]);
-        editor.setStatus("No matches found");
       } else {
         // Error occurred
-        editor.setStatus(`Git grep error: ${result.stderr}`);
+        gitGrepResults = [];
+        editor.setPromptSuggestions([]);
+        editor.setStatus(`Git grep error: ${failed.stderr}`);
       }
     })
     .catch((e) => {
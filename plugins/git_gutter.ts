@@ -13,6 +13,9 @@
  * - ▾ (red): Deleted line(s) below
  */
 
+import { lineChanges, parseUnifiedDiff } from "./lib/index.ts";
+import type { LineChange } from "./lib/index.ts";
+
 // =============================================================================
 // Constants
 // =============================================================================
@@ -38,20 +41,11 @@ const SYMBOLS = {
 // Types
 // =============================================================================
 
-interface DiffHunk {
-  /** Type of change */
-  type: "added" | "modified" | "deleted";
-  /** Starting line number in the new file (1-indexed) */
-  startLine: number;
-  /** Number of lines affected */
-  lineCount: number;
-}
-
 interface BufferGitState {
   /** File path for this buffer */
   filePath: string;
-  /** Last known hunks for this buffer */
-  hunks: DiffHunk[];
+  /** Last known changed lines for this buffer */
+  hunks: LineChange[];
   /** Whether we're currently updating */
   updating: boolean;
 }
@@ -64,115 +58,6 @@ interface BufferGitState {
 const bufferStates: Map<number, BufferGitState> = new Map();
 
 
-// =============================================================================
-// Git Diff Parsing
-// =============================================================================
-
-/**
- * Parse unified diff output to extract hunks
- * Unified diff format:
- * @@ -start,count +start,count @@
- */
-function parseDiffOutput(diffOutput: string): DiffHunk[] {
-  const hunks: DiffHunk[] = [];
-  const lines = diffOutput.split("\n");
-
-  let currentOldLine = 0;
-  let currentNewLine = 0;
-  let inHunk = false;
-  let addedStart = 0;
-  let addedCount = 0;
-  let modifiedStart = 0;
-  let modifiedCount = 0;
-  let deletedAtLine = 0;
-  let deletedCount = 0;
-
-  const flushAdded = () => {
-    if (addedCount > 0) {
-      hunks.push({ type: "added", startLine: addedStart, lineCount: addedCount });
-      addedCount = 0;
-    }
-  };
-
-  const flushModified = () => {
-    if (modifiedCount > 0) {
-      hunks.push({ type: "modified", startLine: modifiedStart, lineCount: modifiedCount });
-      modifiedCount = 0;
-    }
-  };
-
-  const flushDeleted = () => {
-    if (deletedCount > 0) {
-      // Deleted lines are shown as a marker on the line after the deletion
-      hunks.push({ type: "deleted", startLine: deletedAtLine, lineCount: deletedCount });
-      deletedCount = 0;
-    }
-  };
-
-  for (const line of lines) {
-    // Match hunk header: @@ -old_start,old_count +new_start,new_count @@
-    const hunkMatch = line.match(/^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/);
-    if (hunkMatch) {
-      // Flush any pending changes from previous hunk
-      flushAdded();
-      flushModified();
-      flushDeleted();
-
-      currentOldLine = parseInt(hunkMatch[1], 10);
-      currentNewLine = parseInt(hunkMatch[3], 10);
-      inHunk = true;
-      continue;
-    }
-
-    if (!inHunk) continue;
-
-    if (line.startsWith("+") && !line.startsWith("+++")) {
-      // Added line
-      if (deletedCount > 0) {
-        // If there were deletions right before, this is a modification
-        if (modifiedCount === 0) {
-          modifiedStart = currentNewLine;
-        }
-        modifiedCount++;
-        deletedCount--;
-      } else {
-        // Pure addition
-        if (addedCount === 0) {
-          addedStart = currentNewLine;
-        }
-        addedCount++;
-      }
-      currentNewLine++;
-    } else if (line.startsWith("-") && !line.startsWith("---")) {
-      // Deleted line - flush any pending additions first
-      flushAdded();
-
-      if (deletedCount === 0) {
-        deletedAtLine = currentNewLine;
-      }
-      deletedCount++;
-      currentOldLine++;
-    } else if (line.startsWith(" ")) {
-      // Context line (unchanged)
-      flushAdded();
-      flushModified();
-      flushDeleted();
-      currentOldLine++;
-      currentNewLine++;
-    } else if (line === "\\ No newline at end of file") {
-      // Ignore this marker
-      continue;
-    }
-  }
-
-  // Flush any remaining changes
-  flushAdded();
-  flushModified();
-  flushDeleted();
-
-  return hunks;
-}
-
 // =============================================================================
 // Git Operations
 // =============================================================================
@@ -257,7 +142,7 @@ async function updateGitGutter(bufferId: number): Promise<void> {
     if (diffOutput.length > 0 && diffOutput.length < 500) {
       editor.debug(`Git Gutter: diff = ${diffOutput.replace(/\n/g, "\\n")}`);
     }
-    const hunks = parseDiffOutput(diffOutput);
+    const hunks = lineChanges(parseUnifiedDiff(diffOutput).flatMap((file) => file.hunks));
     editor.debug(`Git Gutter: parsed ${hunks.length} hunks`);
 
     // Clear existing indicators
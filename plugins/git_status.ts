@@ -0,0 +1,778 @@
+/// <reference path="../types/fresh.d.ts" />
+
+/**
+ * Git Status Plugin - Stage, unstage and discard changes
+ *
+ * "Git Status" lists the staged, unstaged and untracked files of the
+ * repository. On a file:
+ * - s stages it, u unstages it, d discards its changes (after asking)
+ * - Enter shows its hunks, where s/u/d act on the hunk under the cursor
+ *   and Enter opens the file at the hunk
+ *
+ * Hunks come from the same diff model as the git gutter (lib/git-hunks.ts).
+ */
+
+import { applyPatch, hunkPatch, parseUnifiedDiff } from "./lib/index.ts";
+import type { DiffHunk, FileDiff } from "./lib/index.ts";
+
+// =============================================================================
+// Types and Interfaces
+// =============================================================================
+
+type Section = "staged" | "unstaged" | "untracked";
+
+interface StatusEntry {
+  section: Section;
+  /** Status letter from `git status` (M, A, D, R, ...; ? for untracked) */
+  status: string;
+  /** Path relative to the repository root */
+  path: string;
+}
+
+interface StatusPanelState {
+  isOpen: boolean;
+  bufferId: number | null;
+  splitId: number | null;
+  sourceBufferId: number | null; // Buffer shown before the panel, restored on close
+  root: string; // Repository root; git status reports paths relative to it
+  branch: string;
+  entries: StatusEntry[];
+  cachedContent: string; // Content for highlighting (getBufferText doesn't work for virtual buffers)
+}
+
+interface HunkViewState {
+  isOpen: boolean;
+  bufferId: number | null;
+  path: string | null;
+  /** Diff of the worktree against the index, and of the index against HEAD */
+  unstaged: FileDiff | null;
+  staged: FileDiff | null;
+  cachedContent: string;
+}
+
+/** What a confirmed discard prompt applies to */
+type PendingDiscard =
+  | { kind: "file"; entry: StatusEntry }
+  | { kind: "hunk"; file: FileDiff; hunk: DiffHunk };
+
+// =============================================================================
+// State Management
+// =============================================================================
+
+const statusState: StatusPanelState = {
+  isOpen: false,
+  bufferId: null,
+  splitId: null,
+  sourceBufferId: null,
+  root: "",
+  branch: "",
+  entries: [],
+  cachedContent: "",
+};
+
+const hunkState: HunkViewState = {
+  isOpen: false,
+  bufferId: null,
+  path: null,
+  unstaged: null,
+  staged: null,
+  cachedContent: "",
+};
+
+let pendingDiscard: PendingDiscard | null = null;
+
+const SECTION_TITLES: Record<Section, string> = {
+  staged: "Staged",
+  unstaged: "Unstaged",
+  untracked: "Untracked",
+};
+
+// =============================================================================
+// Color Definitions
+// =============================================================================
+
+const colors = {
+  header: [255, 200, 100] as [number, number, number],    // Gold
+  staged: [100, 255, 100] as [number, number, number],    // Green
+  unstaged: [255, 184, 108] as [number, number, number],  // Orange
+  untracked: [255, 100, 100] as [number, number, number], // Red
+  help: [120, 120, 120] as [number, number, number],      // Gray
+  diffAdd: [100, 255, 100] as [number, number, number],   // Green for additions
+  diffDel: [255, 100, 100] as [number, number, number],   // Red for deletions
+  diffHunk: [150, 150, 255] as [number, number, number],  // Blue for hunk headers
+};
+
+// =============================================================================
+// Mode Definitions
+// =============================================================================
+
+editor.defineMode(
+  "git-status",
+  "normal", // inherit from normal mode for cursor movement
+  [
+    ["Return", "git_status_show_hunks"],
+    ["Tab", "git_status_show_hunks"],
+    ["s", "git_status_stage"],
+    ["u", "git_status_unstage"],
+    ["d", "git_status_discard"],
+    ["r", "git_status_refresh"],
+    ["q", "git_status_close"],
+    ["Escape", "git_status_close"],
+  ],
+  true // read-only
+);
+
+editor.defineMode(
+  "git-status-hunks",
+  "normal", // inherit from normal mode for cursor movement
+  [
+    ["Return", "git_hunks_open_file"],
+    ["s", "git_hunks_stage"],
+    ["u", "git_hunks_unstage"],
+    ["d", "git_hunks_discard"],
+    ["q", "git_hunks_close"],
+    ["Escape", "git_hunks_close"],
+  ],
+  true // read-only
+);
+
+// =============================================================================
+// Git Command Execution
+// =============================================================================
+
+async function git(args: string[]): Promise<SpawnResult> {
+  return await editor.spawnProcess("git", args, statusState.root || editor.getCwd());
+}
+
+/**
+ * Parse `git status --porcelain=v1 -z --branch` output
+ */
+function parseStatus(output: string): { branch: string; entries: StatusEntry[] } {
+  let branch = "";
+  const entries: StatusEntry[] = [];
+  const records = output.split("\0");
+
+  for (let i = 0; i < records.length; i++) {
+    const record = records[i];
+    if (record.length < 3) continue;
+
+    if (record.startsWith("## ")) {
+      // "## main...origin/main [ahead 1]" or "## No commits yet on main"
+      branch = record.substring(3).split("...")[0].replace("No commits yet on ", "");
+      continue;
+    }
+
+    const x = record[0];
+    const y = record[1];
+    const path = record.substring(3);
+    if (x === "R" || x === "C") {
+      // The original path follows as its own record
+      i++;
+    }
+
+    if (x === "?" && y === "?") {
+      entries.push({ section: "untracked", status: "?", path });
+      continue;
+    }
+    if (x !== " ") {
+      entries.push({ section: "staged", status: x, path });
+    }
+    if (y !== " ") {
+      entries.push({ section: "unstaged", status: y, path });
+    }
+  }
+
+  return { branch, entries };
+}
+
+async function fetchStatus(): Promise<boolean> {
+  const result = await git(["status", "--porcelain=v1", "-z", "--branch"]);
+  if (result.exit_code !== 0) {
+    editor.setStatus(`Git status error: ${result.stderr.trim()}`);
+    return false;
+  }
+  const { branch, entries } = parseStatus(result.stdout);
+  statusState.branch = branch;
+  statusState.entries = entries;
+  return true;
+}
+
+/**
+ * Run a git command that changes the repository, reporting failures
+ */
+async function runGitChange(args: string[], done: string): Promise<boolean> {
+  const result = await git(args);
+  if (result.exit_code !== 0) {
+    editor.setStatus(`Git error: ${result.stderr.trim()}`);
+    return false;
+  }
+  editor.setStatus(done);
+  return true;
+}
+
+// =============================================================================
+// Status Panel
+// =============================================================================
+
+function entriesToContent(entries: TextPropertyEntry[]): string {
+  return entries.map(e => e.text).join("");
+}
+
+function buildStatusEntries(): TextPropertyEntry[] {
+  const entries: TextPropertyEntry[] = [];
+
+  entries.push({
+    text: `Git Status: ${statusState.branch || "(no branch)"}\n`,
+    properties: { type: "header" },
+  });
+  entries.push({
+    text: "s: stage | u: unstage | d: discard | RET: hunks | r: refresh | q: quit\n",
+    properties: { type: "help" },
+  });
+
+  if (statusState.entries.length === 0) {
+    entries.push({ text: "\n", properties: { type: "blank" } });
+    entries.push({
+      text: "Nothing to commit, working tree clean\n",
+      properties: { type: "empty" },
+    });
+    return entries;
+  }
+
+  for (const section of ["staged", "unstaged", "untracked"] as Section[]) {
+    const sectionEntries = statusState.entries
+      .map((entry, index) => ({ entry, index }))
+      .filter(({ entry }) => entry.section === section);
+    if (sectionEntries.length === 0) continue;
+
+    entries.push({ text: "\n", properties: { type: "blank" } });
+    entries.push({
+      text: `${SECTION_TITLES[section]} (${sectionEntries.length}):\n`,
+      properties: { type: "section-header", section },
+    });
+    for (const { entry, index } of sectionEntries) {
+      entries.push({
+        text: `  ${entry.status}  ${entry.path}\n`,
+        properties: { type: "file", index, section, path: entry.path },
+      });
+    }
+  }
+
+  return entries;
+}
+
+function applyStatusHighlighting(): void {
+  if (statusState.bufferId === null) return;
+  const bufferId = statusState.bufferId;
+  editor.clearNamespace(bufferId, "gitstatus");
+
+  const content = statusState.cachedContent;
+  let byteOffset = 0;
+  let section: Section | null = null;
+
+  for (const line of content.split("\n")) {
+    const lineEnd = byteOffset + line.length;
+    let color: [number, number, number] | null = null;
+
+    if (line.startsWith("Git Status:") || line.endsWith("):")) {
+      color = colors.header;
+      for (const s of ["staged", "unstaged", "untracked"] as Section[]) {
+        if (line.startsWith(SECTION_TITLES[s] + " (")) section = s;
+      }
+    } else if (line.startsWith("s: stage")) {
+      color = colors.help;
+    } else if (line.startsWith("  ") && section) {
+      color = colors[section];
+    }
+
+    if (color && line.length > 0) {
+      editor.addOverlay(
+        bufferId,
+        "gitstatus",
+        byteOffset,
+        lineEnd,
+        color[0],
+        color[1],
+        color[2],
+        false, // underline
+        color === colors.header, // bold
+        false  // italic
+      );
+    }
+
+    byteOffset = lineEnd + 1;
+  }
+}
+
+function updateStatusView(): void {
+  if (statusState.bufferId === null) return;
+  const entries = buildStatusEntries();
+  statusState.cachedContent = entriesToContent(entries);
+  editor.setVirtualBufferContent(statusState.bufferId, entries);
+  applyStatusHighlighting();
+}
+
+function getEntryAtCursor(): StatusEntry | null {
+  if (statusState.bufferId === null) return null;
+  const props = editor.getTextPropertiesAtCursor(statusState.bufferId);
+  if (props.length > 0 && props[0].type === "file" && typeof props[0].index === "number") {
+    return statusState.entries[props[0].index as number] || null;
+  }
+  return null;
+}
+
+async function refreshAll(): Promise<void> {
+  if (statusState.isOpen && (await fetchStatus())) {
+    updateStatusView();
+  }
+  if (hunkState.isOpen && hunkState.path) {
+    await loadHunks(hunkState.path);
+    updateHunkView();
+  }
+}
+
+// =============================================================================
+// Public Commands - Status Panel
+// =============================================================================
+
+globalThis.show_git_status = async function(): Promise<void> {
+  if (statusState.isOpen) {
+    editor.setStatus("Git status already open");
+    return;
+  }
+
+  editor.setStatus("Loading git status...");
+  statusState.splitId = editor.getActiveSplitId();
+  statusState.sourceBufferId = editor.getActiveBufferId();
+
+  const root = await editor.spawnProcess("git", ["rev-parse", "--show-toplevel"], editor.getCwd());
+  if (root.exit_code !== 0) {
+    editor.setStatus("Not a git repository");
+    statusState.splitId = null;
+    return;
+  }
+  statusState.root = root.stdout.trim();
+
+  if (!(await fetchStatus())) {
+    statusState.splitId = null;
+    return;
+  }
+
+  const entries = buildStatusEntries();
+  statusState.cachedContent = entriesToContent(entries);
+
+  // Create virtual buffer in the current split (replacing current buffer)
+  const bufferId = await editor.createVirtualBufferInExistingSplit({
+    name: "*Git Status*",
+    mode: "git-status",
+    read_only: true,
+    entries: entries,
+    split_id: statusState.splitId!,
+    show_line_numbers: false,
+    show_cursors: true,
+    editing_disabled: true,
+  });
+
+  if (bufferId !== null) {
+    statusState.isOpen = true;
+    statusState.bufferId = bufferId;
+    applyStatusHighlighting();
+    editor.setStatus(`Git status: ${statusState.entries.length} change(s)`);
+  } else {
+    statusState.splitId = null;
+    editor.setStatus("Failed to open git status panel");
+  }
+};
+
+globalThis.git_status_close = function(): void {
+  if (!statusState.isOpen) {
+    return;
+  }
+
+  // Restore the original buffer in the split
+  if (statusState.splitId !== null && statusState.sourceBufferId !== null) {
+    editor.setSplitBuffer(statusState.splitId, statusState.sourceBufferId);
+  }
+
+  if (statusState.bufferId !== null) {
+    editor.closeBuffer(statusState.bufferId);
+  }
+
+  statusState.isOpen = false;
+  statusState.bufferId = null;
+  statusState.splitId = null;
+  statusState.sourceBufferId = null;
+  statusState.entries = [];
+  editor.setStatus("Git status closed");
+};
+
+globalThis.git_status_refresh = async function(): Promise<void> {
+  if (!statusState.isOpen) return;
+  await refreshAll();
+  editor.setStatus(`Git status refreshed: ${statusState.entries.length} change(s)`);
+};
+
+globalThis.git_status_stage = async function(): Promise<void> {
+  const entry = getEntryAtCursor();
+  if (!entry) {
+    editor.setStatus("Move cursor to a file line");
+    return;
+  }
+  if (entry.section === "staged") {
+    editor.setStatus(`${entry.path} is already staged`);
+    return;
+  }
+  // "-A" also stages deletions
+  if (await runGitChange(["add", "-A", "--", entry.path], `Staged ${entry.path}`)) {
+    await refreshAll();
+  }
+};
+
+globalThis.git_status_unstage = async function(): Promise<void> {
+  const entry = getEntryAtCursor();
+  if (!entry) {
+    editor.setStatus("Move cursor to a file line");
+    return;
+  }
+  if (entry.section !== "staged") {
+    editor.setStatus(`${entry.path} is not staged`);
+    return;
+  }
+  if (await runGitChange(["restore", "--staged", "--", entry.path], `Unstaged ${entry.path}`)) {
+    await refreshAll();
+  }
+};
+
+globalThis.git_status_discard = function(): void {
+  const entry = getEntryAtCursor();
+  if (!entry) {
+    editor.setStatus("Move cursor to a file line");
+    return;
+  }
+  if (entry.section === "staged") {
+    editor.setStatus(`Unstage ${entry.path} before discarding its changes`);
+    return;
+  }
+  pendingDiscard = { kind: "file", entry };
+  const what = entry.section === "untracked" ? `Delete untracked ${entry.path}` : `Discard changes to ${entry.path}`;
+  editor.startPrompt(`${what}? (y/N) `, "git-status-discard");
+};
+
+// =============================================================================
+// Hunk View
+// =============================================================================
+
+async function fetchFileDiff(path: string, cached: boolean): Promise<FileDiff | null> {
+  const args = ["diff", "--no-color", "--no-ext-diff"];
+  if (cached) args.push("--cached");
+  args.push("--", path);
+  const result = await git(args);
+  if (result.exit_code !== 0) {
+    editor.setStatus(`Git diff error: ${result.stderr.trim()}`);
+    return null;
+  }
+  return parseUnifiedDiff(result.stdout)[0] || null;
+}
+
+async function loadHunks(path: string): Promise<void> {
+  hunkState.unstaged = await fetchFileDiff(path, false);
+  hunkState.staged = await fetchFileDiff(path, true);
+}
+
+function buildHunkEntries(): TextPropertyEntry[] {
+  const entries: TextPropertyEntry[] = [];
+  entries.push({
+    text: `Hunks: ${hunkState.path}\n`,
+    properties: { type: "header" },
+  });
+  entries.push({
+    text: "s: stage | u: unstage | d: discard | RET: open file | q: back\n",
+    properties: { type: "help" },
+  });
+
+  const sections: [Section, FileDiff | null][] = [
+    ["unstaged", hunkState.unstaged],
+    ["staged", hunkState.staged],
+  ];
+  let any = false;
+  for (const [section, diff] of sections) {
+    if (!diff || diff.hunks.length === 0) continue;
+    any = true;
+    entries.push({ text: "\n", properties: { type: "blank" } });
+    entries.push({
+      text: `${SECTION_TITLES[section]} (${diff.hunks.length}):\n`,
+      properties: { type: "section-header", section },
+    });
+    diff.hunks.forEach((hunk, index) => {
+      const props = { type: "hunk", section, index, line: hunk.newStart };
+      entries.push({ text: hunk.header + "\n", properties: props });
+      for (const line of hunk.lines) {
+        entries.push({ text: line + "\n", properties: props });
+      }
+    });
+  }
+
+  if (!any) {
+    entries.push({ text: "\n", properties: { type: "blank" } });
+    entries.push({
+      text: "No hunks (untracked files are staged as a whole)\n",
+      properties: { type: "empty" },
+    });
+  }
+
+  return entries;
+}
+
+function applyHunkHighlighting(): void {
+  if (hunkState.bufferId === null) return;
+  const bufferId = hunkState.bufferId;
+  editor.clearNamespace(bufferId, "githunks");
+
+  let byteOffset = 0;
+  let lineIdx = 0;
+  for (const line of hunkState.cachedContent.split("\n")) {
+    const lineEnd = byteOffset + line.length;
+    let color: [number, number, number] | null = null;
+
+    if (lineIdx === 0 || line.endsWith("):")) {
+      color = colors.header;
+    } else if (lineIdx === 1) {
+      color = colors.help;
+    } else if (line.startsWith("@@")) {
+      color = colors.diffHunk;
+    } else if (line.startsWith("+")) {
+      color = colors.diffAdd;
+    } else if (line.startsWith("-")) {
+      color = colors.diffDel;
+    }
+
+    if (color && line.length > 0) {
+      editor.addOverlay(
+        bufferId,
+        "githunks",
+        byteOffset,
+        lineEnd,
+        color[0],
+        color[1],
+        color[2],
+        false, // underline
+        color === colors.header, // bold
+        false  // italic
+      );
+    }
+
+    byteOffset = lineEnd + 1;
+    lineIdx++;
+  }
+}
+
+function updateHunkView(): void {
+  if (hunkState.bufferId === null) return;
+  const entries = buildHunkEntries();
+  hunkState.cachedContent = entriesToContent(entries);
+  editor.setVirtualBufferContent(hunkState.bufferId, entries);
+  applyHunkHighlighting();
+}
+
+function getHunkAtCursor(): { section: Section; file: FileDiff; hunk: DiffHunk } | null {
+  if (hunkState.bufferId === null) return null;
+  const props = editor.getTextPropertiesAtCursor(hunkState.bufferId);
+  if (props.length === 0 || props[0].type !== "hunk") return null;
+
+  const section = props[0].section as Section;
+  const file = section === "staged" ? hunkState.staged : hunkState.unstaged;
+  const hunk = file?.hunks[props[0].index as number];
+  return file && hunk ? { section, file, hunk } : null;
+}
+
+// =============================================================================
+// Public Commands - Hunk View
+// =============================================================================
+
+globalThis.git_status_show_hunks = async function(): Promise<void> {
+  if (!statusState.isOpen || statusState.splitId === null) return;
+
+  const entry = getEntryAtCursor();
+  if (!entry) {
+    editor.setStatus("Move cursor to a file line");
+    return;
+  }
+
+  hunkState.path = entry.path;
+  await loadHunks(entry.path);
+  const entries = buildHunkEntries();
+  hunkState.cachedContent = entriesToContent(entries);
+
+  // Create virtual buffer in the current split (replacing the status panel)
+  const bufferId = await editor.createVirtualBufferInExistingSplit({
+    name: `*Hunks: ${entry.path}*`,
+    mode: "git-status-hunks",
+    read_only: true,
+    entries: entries,
+    split_id: statusState.splitId,
+    show_line_numbers: false,
+    show_cursors: true,
+    editing_disabled: true,
+  });
+
+  if (bufferId !== null) {
+    hunkState.isOpen = true;
+    hunkState.bufferId = bufferId;
+    applyHunkHighlighting();
+    editor.setStatus(`${entry.path} | s: stage hunk | u: unstage hunk | d: discard hunk | q: back`);
+  } else {
+    editor.setStatus("Failed to open hunk view");
+  }
+};
+
+globalThis.git_hunks_close = function(): void {
+  if (!hunkState.isOpen) {
+    return;
+  }
+
+  // Go back to the status panel
+  if (statusState.splitId !== null && statusState.bufferId !== null) {
+    editor.setSplitBuffer(statusState.splitId, statusState.bufferId);
+    applyStatusHighlighting();
+  }
+
+  if (hunkState.bufferId !== null) {
+    editor.closeBuffer(hunkState.bufferId);
+  }
+
+  hunkState.isOpen = false;
+  hunkState.bufferId = null;
+  hunkState.path = null;
+  hunkState.unstaged = null;
+  hunkState.staged = null;
+  editor.setStatus(`Git status: ${statusState.entries.length} change(s)`);
+};
+
+globalThis.git_hunks_stage = async function(): Promise<void> {
+  const target = getHunkAtCursor();
+  if (!target) {
+    editor.setStatus("Move cursor to a hunk");
+    return;
+  }
+  if (target.section === "staged") {
+    editor.setStatus("Hunk is already staged");
+    return;
+  }
+  const error = await applyPatch(hunkPatch(target.file, target.hunk), ["--cached"], statusState.root);
+  editor.setStatus(error ? `Git apply error: ${error}` : "Staged hunk");
+  await refreshAll();
+};
+
+globalThis.git_hunks_unstage = async function(): Promise<void> {
+  const target = getHunkAtCursor();
+  if (!target) {
+    editor.setStatus("Move cursor to a hunk");
+    return;
+  }
+  if (target.section !== "staged") {
+    editor.setStatus("Hunk is not staged");
+    return;
+  }
+  const error = await applyPatch(
+    hunkPatch(target.file, target.hunk),
+    ["--cached", "--reverse"],
+    statusState.root
+  );
+  editor.setStatus(error ? `Git apply error: ${error}` : "Unstaged hunk");
+  await refreshAll();
+};
+
+globalThis.git_hunks_discard = function(): void {
+  const target = getHunkAtCursor();
+  if (!target) {
+    editor.setStatus("Move cursor to a hunk");
+    return;
+  }
+  if (target.section === "staged") {
+    editor.setStatus("Unstage the hunk before discarding it");
+    return;
+  }
+  pendingDiscard = { kind: "hunk", file: target.file, hunk: target.hunk };
+  editor.startPrompt("Discard hunk? (y/N) ", "git-status-discard");
+};
+
+globalThis.git_hunks_open_file = function(): void {
+  if (!hunkState.path) return;
+  const target = getHunkAtCursor();
+  const line = target ? Math.max(1, target.hunk.newStart) : 1;
+  const path = editor.pathJoin(statusState.root, hunkState.path);
+  editor.openFile(path, line, 1);
+};
+
+// =============================================================================
+// Discard Confirmation
+// =============================================================================
+
+globalThis.onGitStatusPromptConfirmed = async function(args: {
+  prompt_type: string;
+  selected_index: number | null;
+  input: string;
+}): Promise<boolean> {
+  if (args.prompt_type !== "git-status-discard") {
+    return true; // Not our prompt
+  }
+
+  const pending = pendingDiscard;
+  pendingDiscard = null;
+  if (!pending || args.input.trim().toLowerCase() !== "y") {
+    editor.setStatus("Discard cancelled");
+    return true;
+  }
+
+  if (pending.kind === "file") {
+    const { entry } = pending;
+    const changed = entry.section === "untracked"
+      ? await runGitChange(["clean", "-f", "--", entry.path], `Deleted ${entry.path}`)
+      : await runGitChange(["restore", "--", entry.path], `Discarded changes to ${entry.path}`);
+    if (!changed) return true;
+  } else {
+    const error = await applyPatch(hunkPatch(pending.file, pending.hunk), ["--reverse"], statusState.root);
+    editor.setStatus(error ? `Git apply error: ${error}` : "Discarded hunk");
+  }
+  await refreshAll();
+  return true;
+};
+
+globalThis.onGitStatusPromptCancelled = function(args: {
+  prompt_type: string;
+}): boolean {
+  if (args.prompt_type === "git-status-discard") {
+    pendingDiscard = null;
+  }
+  return true;
+};
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+editor.on("prompt_confirmed", "onGitStatusPromptConfirmed");
+editor.on("prompt_cancelled", "onGitStatusPromptCancelled");
+
+editor.registerCommand(
+  "Git Status",
+  "Stage, unstage and discard changes by file or hunk",
+  "show_git_status",
+  "normal"
+);
+
+editor.registerCommand(
+  "Git Status: Close",
+  "Close the git status panel",
+  "git_status_close",
+  "normal"
+);
+
+editor.registerCommand(
+  "Git Status: Refresh",
+  "Refresh the git status panel",
+  "git_status_refresh",
+  "normal"
+);
+
+editor.debug("Git Status plugin loaded");
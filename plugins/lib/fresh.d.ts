@@ -91,6 +91,20 @@ interface ProcessHandle extends PromiseLike<SpawnResult> {
   kill(): Promise<boolean>;
 }
 
+/** Options for searching a buffer */
+interface SearchInBufferOptions {
+  /** Whether the pattern is a regular expression (default false) */
+  regex?: boolean | null;
+  /** Whether matching is case sensitive (default true) */
+  case_sensitive?: boolean | null;
+  /** Start of the byte range to search (default 0) */
+  start?: number | null;
+  /** End of the byte range to search (default end of buffer) */
+  end?: number | null;
+  /** Most matches to return (default and maximum 10000) */
+  max_results?: number | null;
+}
+
 /** Result from spawnProcess */
 interface SpawnResult {
   /** Complete stdout as string. Newlines preserved; trailing newline included. */
@@ -101,12 +115,46 @@ interface SpawnResult {
   exit_code: number;
 }
 
-/** Result from spawnBackgroundProcess - just the process ID */
+/** Result from spawnBackgroundProcess and spawnStreamingProcess - just the process ID */
 interface BackgroundProcessResult {
   /** Unique process ID for later reference (kill, status check) */
   process_id: number;
 }
 
+/** Chunk of output from a streaming process */
+interface ProcessOutput {
+  /** "stdout", "stderr" or "exit" */
+  stream: string;
+  /** Text of the chunk; empty for "exit" */
+  data: string;
+  /** Exit code for "exit" (-1 if the process was killed); null otherwise */
+  exit_code?: number | null;
+}
+
+/** Options for an HTTP request */
+interface FetchOptions {
+  /** HTTP method (default "GET") */
+  method?: string | null;
+  /** Request headers */
+  headers?: Record<string, unknown> | null;
+  /** Request body */
+  body?: string | null;
+  /** Timeout in milliseconds (default 30000, at most 120000) */
+  timeout_ms?: number | null;
+  /** Largest response body to accept in bytes (default and at most 10 MiB) */
+  max_bytes?: number | null;
+}
+
+/** Response to an HTTP request */
+interface FetchResult {
+  /** HTTP status code */
+  status: number;
+  /** Response headers, with lowercase names */
+  headers: Record<string, unknown>;
+  /** Response body as text */
+  body: string;
+}
+
 /** File stat information */
 interface FileStat {
   /** Whether the path exists */
@@ -131,6 +179,8 @@ interface BufferInfo {
   modified: boolean;
   /** Buffer length in bytes */
   length: number;
+  /** Version of the buffer content, increased by every edit */
+  version: number;
 }
 
 /** Diff vs last save for a buffer */
@@ -307,6 +357,19 @@ interface EditorAPI {
    * @param message - Text to display; keep short (status bar has limited width)
    */
   setStatus(message: string): void;
+  /**
+   * Show a toast notification
+   *
+   * Notifications stack in the corner of the editor and dismiss themselves after
+   * a timeout. They are also kept in a history the user can review with
+   * "Show Notifications". Prefer setStatus for routine feedback.
+   * @param message - Text to display
+   * @param level - "info" (default), "warning" or "error"
+   * @param source - Name shown alongside the notification (e.g. the plugin name)
+   * @example
+   * editor.showNotification("Formatter not found", "warning", "prettier");
+   */
+  showNotification(message: string, level?: string | null, source?: string | null): boolean;
   /**
    * Log a debug message to the editor's trace output
    *
@@ -335,6 +398,16 @@ interface EditorAPI {
    * @returns User configuration object (sparse - only explicitly set values)
    */
   getUserConfig(): unknown;
+  /**
+   * Get the value of a plugin setting
+   *
+   * Returns the value from the config file, or the default declared with
+   * defineSettings() if it is not set.
+   * @param namespace - Plugin namespace
+   * @param key - Setting key
+   * @returns The setting's value, or null if it is neither set nor declared
+   */
+  getPluginSetting(namespace: string, key: string): unknown;
   /**
    * Get the buffer ID of the focused editor pane
    *
@@ -366,6 +439,42 @@ interface EditorAPI {
    * @param buffer_id - Target buffer ID
    */
   getBufferLength(buffer_id: number): number;
+  /**
+   * Read part of a buffer's text
+   *
+   * Reads only the requested byte range, so plugins never copy the whole
+   * buffer. The range is clamped to the buffer's length, and at most 16 MB
+   * can be read per call. Compare
+   * `getBufferInfo(id).version` before and after to notice edits made while
+   * waiting.
+   * @param buffer_id - Target buffer ID
+   * @param start - Start byte offset (inclusive)
+   * @param end - End byte offset (exclusive)
+   * @returns Promise resolving to the text in the range
+   */
+  getBufferText(buffer_id: number, start: number, end: number): Promise<string>;
+  /**
+   * Get the number of lines in a buffer
+   *
+   * Counted in the editor, without copying the text.
+   * @param buffer_id - Target buffer ID
+   * @returns Promise resolving to the line count, or null while it is unknown (large files)
+   */
+  getLineCount(buffer_id: number): Promise<number | null>;
+  /**
+   * Get the line containing a byte offset
+   * @param buffer_id - Target buffer ID
+   * @param offset - Byte offset
+   * @returns Promise resolving to the 0-indexed line, or null while line numbers are unknown
+   */
+  getLineAtOffset(buffer_id: number, offset: number): Promise<number | null>;
+  /**
+   * Get the byte offset where a line starts
+   * @param buffer_id - Target buffer ID
+   * @param line - 0-indexed line number
+   * @returns Promise resolving to the byte offset, or null if the line does not exist
+   */
+  getLineStartOffset(buffer_id: number, line: number): Promise<number | null>;
   /**
    * Check if a buffer has been modified since last save
    *
@@ -470,6 +579,48 @@ interface EditorAPI {
    * stay in sync with the saved config.
    */
   reloadConfig(): void;
+  /**
+   * Declare a plugin's settings
+   *
+   * The settings get a page of their own in the settings UI, and their values
+   * are saved under `plugins.<namespace>` in the config file.
+   * @param namespace - Plugin namespace, usually the plugin's name (e.g., "todo_highlighter")
+   * @param settings - Object mapping each key to a JSON Schema property with `type`, `default` and `description` (and optionally `enum`, `minimum`, `maximum`)
+   * @returns true if the settings were declared
+   * @example
+   * editor.defineSettings("todo_highlighter", {
+   * keywords: { type: "array", items: { type: "string" }, default: ["TODO", "FIXME"],
+   * description: "Words to highlight" },
+   * });
+   */
+  defineSettings(namespace: string, settings: unknown): boolean;
+  /**
+   * Set a plugin setting and save it to the config file
+   * @param namespace - Plugin namespace
+   * @param key - Setting key
+   * @param value - New value
+   * @returns true if the change was sent to the editor
+   */
+  setPluginSetting(namespace: string, key: string, value: unknown): boolean;
+  /**
+   * Write a line to the plugin log
+   *
+   * The log is shown by the "Show Plugin Log" command. console.log, console.warn
+   * and console.error write here too.
+   * @param level - "debug", "info", "warn" or "error"
+   * @param message - Text of the line
+   */
+  log(level: string, message: string): void;
+  /**
+   * Report an error thrown by an event handler or action
+   *
+   * Errors thrown by handlers and actions are reported automatically; call this
+   * for errors a plugin catches itself. A plugin that keeps failing is disabled
+   * and restarted later.
+   * @param handler_name - Name of the global function that failed
+   * @param message - Error message, ideally with its stack trace
+   */
+  reportError(handler_name: string, message: string): void;
   /**
    * Copy text to the system clipboard
    *
@@ -478,6 +629,17 @@ interface EditorAPI {
    * @param text - Text to copy to clipboard
    */
   setClipboard(text: string): void;
+  /**
+   * Find the matches of a pattern in a buffer
+   *
+   * The search runs in the editor, so plugins don't read the text to search
+   * it. At most 16 MB is searched per call; search larger buffers in parts.
+   * @param buffer_id - Target buffer ID
+   * @param pattern - Text or regular expression to find
+   * @param options - Search options, or null for defaults
+   * @returns Promise resolving to the byte ranges of the matches
+   */
+  searchInBuffer(buffer_id: number, pattern: string, options?: SearchInBufferOptions | null): Promise<SelectionRange[]>;
   /**
    * Insert text at a byte position in a buffer
    *
@@ -500,6 +662,22 @@ interface EditorAPI {
    * @param end - End byte offset (exclusive)
    */
   deleteRange(buffer_id: number, start: number, end: number): boolean;
+  /**
+   * Start grouping a buffer's edits into one undo step
+   *
+   * Every edit the plugin makes to the buffer until the matching endUndoGroup
+   * is undone and redone at once; the user's edits and other plugins' don't
+   * join it, and one made in between starts a new step. Groups nest; always
+   * pair with endUndoGroup (use try/finally). Groups left open are closed when
+   * the plugin throws an error. Returns true if command was sent successfully.
+   * @param buffer_id - Target buffer ID
+   */
+  beginUndoGroup(buffer_id: number): boolean;
+  /**
+   * End the undo group started by the matching beginUndoGroup
+   * @param buffer_id - Target buffer ID
+   */
+  endUndoGroup(buffer_id: number): boolean;
   /**
    * Clear all overlays in a namespace
    * @param buffer_id - The buffer ID
@@ -600,6 +778,51 @@ interface EditorAPI {
    * @returns true if the context was updated
    */
   setContext(name: string, active: boolean): boolean;
+  /**
+   * Add an item to the editor's right-click context menu
+   * Items are shown after the built-in ones; adding a label again replaces it.
+   * @param label - Text shown in the menu
+   * @param action - Command or JavaScript function name to run when picked
+   * @param when - Menu context key the item needs to be enabled (e.g., "has_selection"), or ""
+   * @returns true if the item was added
+   */
+  addContextMenuItem(label: string, action: string, when: string): boolean;
+  /**
+   * Remove an item from the editor's right-click context menu
+   * @param label - Label the item was added with
+   * @returns true if the request was sent
+   */
+  removeContextMenuItem(label: string): boolean;
+  /**
+   * Add a top-level menu to the menu bar, after the built-in menus
+   * Adding a label again replaces that menu.
+   * @param label - Menu label (e.g., "Tools")
+   * @returns true if the menu was added
+   */
+  addMenu(label: string): boolean;
+  /**
+   * Add an item to the end of a menu bar menu
+   * Adding a label again replaces that item.
+   * @param menu_label - Label of the menu to add to (e.g., "Edit", or one added with addMenu)
+   * @param label - Text shown in the menu
+   * @param action - Command or JavaScript function name to run when picked
+   * @param when - Menu context key the item needs to be enabled (e.g., "has_selection"), or ""
+   * @returns true if the item was added
+   */
+  addMenuItem(menu_label: string, label: string, action: string, when: string): boolean;
+  /**
+   * Remove an item from a menu bar menu
+   * @param menu_label - Label of the menu holding the item
+   * @param label - Label the item was added with
+   * @returns true if the request was sent
+   */
+  removeMenuItem(menu_label: string, label: string): boolean;
+  /**
+   * Remove a top-level menu added with addMenu
+   * @param label - Menu label
+   * @returns true if the request was sent
+   */
+  removeMenu(label: string): boolean;
   /**
    * Open a file in the editor, optionally at a specific location
    * @param path - File path to open
@@ -608,6 +831,43 @@ interface EditorAPI {
    * @returns true if file was opened
    */
   openFile(path: string, line: number, column: number): boolean;
+  /**
+   * Open a URL served by a file system provider in a buffer
+   *
+   * Built-in providers serve `git://REV/path`, `zip://archive.zip!/path` and
+   * `cmd://command`; plugins add schemes with registerFileSystemProvider.
+   * The buffer appears once the provider has read the content.
+   * @param url - URL to open (e.g. "git://HEAD~1/src/main.rs")
+   * @returns true if the request was sent
+   */
+  openUrl(url: string): boolean;
+  /**
+   * Serve URLs with a scheme from this plugin
+   *
+   * Opening `<scheme>://...` fires the "vfs_read" hook with { url, scheme, path };
+   * answer it with completeVfsRead. Saving a buffer of a writable provider
+   * fires "vfs_write" with { url, scheme, path, content }; answer it with
+   * completeVfsWrite.
+   * @param scheme - Lowercase URL scheme (e.g. "s3")
+   * @param writable - Whether buffers from this provider can be saved
+   * @returns true if the request was sent
+   */
+  registerFileSystemProvider(scheme: string, writable: boolean): boolean;
+  /**
+   * Answer a "vfs_read" hook with the content of a URL, or an error
+   * @param url - URL from the hook
+   * @param content - Content to open, or null on error
+   * @param error - Error shown to the user, or null on success
+   * @returns true if the answer was sent
+   */
+  completeVfsRead(url: string, content?: string | null, error?: string | null): boolean;
+  /**
+   * Answer a "vfs_write" hook once the content is written, or with an error
+   * @param url - URL from the hook
+   * @param error - Error shown to the user, or null on success
+   * @returns true if the answer was sent
+   */
+  completeVfsWrite(url: string, error?: string | null): boolean;
   /**
    * Open a file in a specific split pane
    * @param split_id - The split ID to open the file in
@@ -636,12 +896,13 @@ interface EditorAPI {
    */
   spawnBackgroundProcess(command: string, args: string[], cwd?: string | null): Promise<BackgroundProcessResult>;
   /**
-   * Kill a background or cancellable process by ID
+   * Kill a background, cancellable or streaming process by ID
    *
    * Sends SIGTERM to gracefully terminate the process.
    * Returns true if the process was found and killed, false if not found.
+   * A streaming process still delivers its remaining output and exit code.
    *
-   * @param process_id - ID returned from spawnBackgroundProcess or spawnProcessStart
+   * @param process_id - ID returned from spawnBackgroundProcess, spawnProcessStart or spawnStreamingProcess
    * @returns true if process was killed, false if not found
    */
   killProcess(#[bigint] process_id: number): Promise<boolean>;
@@ -652,6 +913,69 @@ interface EditorAPI {
    * @returns SpawnResult with stdout, stderr, and exit_code
    */
   spawnProcessWait(#[bigint] process_id: number): Promise<SpawnResult>;
+  /**
+   * Spawn a process whose output is streamed as it arrives
+   *
+   * Read its stdout and stderr chunks and finally its exit code with
+   * readProcessOutput(id), write to it with writeProcessStdin(id, data), and stop
+   * it with killProcess(id). Use it for linters and formatters that report
+   * results while they run.
+   *
+   * @param command - Program name (searched in PATH) or absolute path
+   * @param args - Command arguments (each array element is one argument)
+   * @param cwd - Working directory; null uses editor's cwd
+   * @returns Object with process_id for later reference
+   * @example
+   * const proc = await editor.spawnStreamingProcess("eslint", ["--stdin", "--format", "unix"]);
+   * await editor.writeProcessStdin(proc.process_id, source);
+   * await editor.closeProcessStdin(proc.process_id);
+   * let chunk;
+   * while ((chunk = await editor.readProcessOutput(proc.process_id)) !== null) {
+   * if (chunk.stream === "stdout") showResults(chunk.data);
+   * }
+   */
+  spawnStreamingProcess(command: string, args: string[], cwd?: string | null): Promise<BackgroundProcessResult>;
+  /**
+   * Wait for the next chunk of output from a streaming process
+   *
+   * Chunks of stdout and stderr arrive in the order the process wrote them;
+   * the last chunk has stream "exit" and the exit code.
+   * @param process_id - ID returned from spawnStreamingProcess
+   * @returns The next chunk, or null once the exit code has been read
+   */
+  readProcessOutput(#[bigint] process_id: number): Promise<ProcessOutput | null>;
+  /**
+   * Write text to the stdin of a streaming process
+   *
+   * @param process_id - ID returned from spawnStreamingProcess
+   * @param data - Text to write
+   * @returns true if it was written, false if the process is not found or its stdin is closed
+   */
+  writeProcessStdin(#[bigint] process_id: number, data: string): Promise<boolean>;
+  /**
+   * Close the stdin of a streaming process, so it sees the end of its input
+   *
+   * @param process_id - ID returned from spawnStreamingProcess
+   * @returns true if stdin was open, false if it was already closed or the process is not found
+   */
+  closeProcessStdin(#[bigint] process_id: number): Promise<boolean>;
+  /**
+   * Make an HTTP request
+   *
+   * The first request of a plugin asks the user whether the plugin may access
+   * the network; the answer is saved in `plugin_network_access` in the config.
+   * Responses with error statuses resolve like any other, and redirects are
+   * not followed (the response has the `location` header); the promise rejects
+   * if access is denied, the request fails or times out, or the body is larger
+   * than max_bytes.
+   * @param url - http:// or https:// URL
+   * @param options - Method, headers, body and limits, or null for a GET
+   * @returns Promise resolving to the status, headers and body
+   * @example
+   * const res = await editor.fetch("https://api.dictionaryapi.dev/api/v2/entries/en/fresh");
+   * if (res.status === 200) editor.setStatus(JSON.parse(res.body)[0].meanings[0].definitions[0].definition);
+   */
+  fetch(url: string, options?: FetchOptions | null): Promise<FetchResult>;
   /**
    * Delay execution for a specified number of milliseconds
    *
@@ -865,12 +1189,33 @@ interface EditorAPI {
   /**
    * Get the editor's current working directory
    *
-   * Returns the editor's working directory (set when the editor was started).
+   * Returns the editor's working directory (set when the editor was started),
+   * or the active split's own one if it has been given one.
    * Use as base for resolving relative paths and spawning processes.
    * Note: This returns the editor's stored working_dir, not process CWD,
    * which is important for test isolation.
    */
   getCwd(): string;
+  /**
+   * Get the folders of the workspace
+   *
+   * Returns the working directory (as `getCwd()` does) followed by the
+   * folders added to the workspace. Tools that search the project, such as
+   * file finders and grep, should search each of them.
+   * @returns Array of absolute folder paths
+   */
+  getWorkspaceRoots(): string[];
+  /**
+   * Filter ignored files out of a list of paths
+   *
+   * A file is ignored when a `.gitignore` or `.ignore` file in its folder or
+   * above says so, or it matches the `ignore_patterns` config setting - the
+   * same files the file explorer hides. Relative paths are resolved against
+   * the working directory.
+   * @param paths - File paths to filter
+   * @returns The paths that are not ignored, in their original order
+   */
+  filterIgnored(paths: string[]): string[];
 
   // === Path Operations ===
   /**
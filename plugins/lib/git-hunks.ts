@@ -0,0 +1,218 @@
+/// <reference path="./fresh.d.ts" />
+
+/**
+ * Git Hunks - Shared model of `git diff` output
+ *
+ * Used by the git gutter (to mark changed lines) and the git status panel
+ * (to stage, unstage and discard single hunks).
+ *
+ * @example
+ * ```typescript
+ * const files = parseUnifiedDiff(diffOutput);
+ * const changes = lineChanges(files.flatMap(f => f.hunks));
+ * const error = await applyPatch(hunkPatch(files[0], files[0].hunks[0]), ["--cached"]);
+ * ```
+ */
+
+/**
+ * A hunk of a unified diff
+ */
+export interface DiffHunk {
+  /** Header line, e.g. "@@ -1,2 +1,3 @@ fn main()" */
+  header: string;
+  /** First line in the old file (1-indexed) */
+  oldStart: number;
+  oldCount: number;
+  /** First line in the new file (1-indexed) */
+  newStart: number;
+  newCount: number;
+  /** Body lines, each starting with " ", "+", "-" or "\" */
+  lines: string[];
+}
+
+/**
+ * The changes to one file in a unified diff
+ */
+export interface FileDiff {
+  /** Path relative to the repository root (the old path for deletions) */
+  path: string;
+  /** Lines before the first hunk ("diff --git", "index", "---", "+++") */
+  header: string[];
+  hunks: DiffHunk[];
+}
+
+/**
+ * A run of changed lines, as shown in the gutter
+ */
+export interface LineChange {
+  /** Type of change */
+  type: "added" | "modified" | "deleted";
+  /** Starting line number in the new file (1-indexed) */
+  startLine: number;
+  /** Number of lines affected */
+  lineCount: number;
+}
+
+/**
+ * Parse `git diff` output into files and hunks
+ */
+export function parseUnifiedDiff(diffOutput: string): FileDiff[] {
+  const files: FileDiff[] = [];
+  let file: FileDiff | null = null;
+  let hunk: DiffHunk | null = null;
+
+  for (const line of diffOutput.split("\n")) {
+    if (line.startsWith("diff --git ")) {
+      // "diff --git a/path b/path"; refined by the "---"/"+++" lines below
+      const match = line.match(/^diff --git a\/(.*) b\/(.*)$/);
+      file = { path: match ? match[2] : "", header: [line], hunks: [] };
+      files.push(file);
+      hunk = null;
+      continue;
+    }
+    if (!file) continue;
+
+    // Match hunk header: @@ -old_start,old_count +new_start,new_count @@
+    const hunkMatch = line.match(/^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/);
+    if (hunkMatch) {
+      hunk = {
+        header: line,
+        oldStart: parseInt(hunkMatch[1], 10),
+        oldCount: hunkMatch[2] === undefined ? 1 : parseInt(hunkMatch[2], 10),
+        newStart: parseInt(hunkMatch[3], 10),
+        newCount: hunkMatch[4] === undefined ? 1 : parseInt(hunkMatch[4], 10),
+        lines: [],
+      };
+      file.hunks.push(hunk);
+      continue;
+    }
+
+    if (hunk) {
+      if (line.length > 0 && " +-\\".includes(line[0])) {
+        hunk.lines.push(line);
+      }
+      continue;
+    }
+
+    file.header.push(line);
+    if (line.startsWith("+++ b/")) {
+      file.path = line.substring(6);
+    } else if (line.startsWith("--- a/") && file.path === "") {
+      file.path = line.substring(6);
+    }
+  }
+
+  return files;
+}
+
+/**
+ * Classify the changed lines of hunks for the gutter: additions, deletions,
+ * and deletions followed by additions (modifications)
+ */
+export function lineChanges(hunks: DiffHunk[]): LineChange[] {
+  const changes: LineChange[] = [];
+
+  for (const hunk of hunks) {
+    let currentNewLine = hunk.newStart;
+    let addedStart = 0;
+    let addedCount = 0;
+    let modifiedStart = 0;
+    let modifiedCount = 0;
+    let deletedAtLine = 0;
+    let deletedCount = 0;
+
+    const flush = () => {
+      if (addedCount > 0) {
+        changes.push({ type: "added", startLine: addedStart, lineCount: addedCount });
+        addedCount = 0;
+      }
+      if (modifiedCount > 0) {
+        changes.push({ type: "modified", startLine: modifiedStart, lineCount: modifiedCount });
+        modifiedCount = 0;
+      }
+      if (deletedCount > 0) {
+        // Deleted lines are shown as a marker on the line after the deletion
+        changes.push({ type: "deleted", startLine: deletedAtLine, lineCount: deletedCount });
+        deletedCount = 0;
+      }
+    };
+
+    for (const line of hunk.lines) {
+      if (line.startsWith("+")) {
+        if (deletedCount > 0) {
+          // If there were deletions right before, this is a modification
+          if (modifiedCount === 0) {
+            modifiedStart = currentNewLine;
+          }
+          modifiedCount++;
+          deletedCount--;
+        } else {
+          // Pure addition
+          if (addedCount === 0) {
+            addedStart = currentNewLine;
+          }
+          addedCount++;
+        }
+        currentNewLine++;
+      } else if (line.startsWith("-")) {
+        // Deleted line - flush any pending additions first
+        if (addedCount > 0) {
+          changes.push({ type: "added", startLine: addedStart, lineCount: addedCount });
+          addedCount = 0;
+        }
+        if (deletedCount === 0) {
+          deletedAtLine = currentNewLine;
+        }
+        deletedCount++;
+      } else if (line.startsWith(" ")) {
+        // Context line (unchanged)
+        flush();
+        currentNewLine++;
+      }
+      // "\ No newline at end of file" markers are ignored
+    }
+
+    flush();
+  }
+
+  return changes;
+}
+
+/**
+ * A patch containing only `hunk` of `file`, for `git apply`
+ */
+export function hunkPatch(file: FileDiff, hunk: DiffHunk): string {
+  return [...file.header, hunk.header, ...hunk.lines].join("\n") + "\n";
+}
+
+/**
+ * Run `git apply` with `args` on `patch`
+ *
+ * @param patch - Patch text, e.g. from hunkPatch
+ * @param args - Extra arguments, e.g. ["--cached"] to stage or
+ *   ["--cached", "--reverse"] to unstage
+ * @param cwd - Directory to run git in; null uses editor's cwd
+ * @returns null on success, or git's error message
+ */
+export async function applyPatch(
+  patch: string,
+  args: string[],
+  cwd: string | null = null
+): Promise<string | null> {
+  const proc = await editor.spawnStreamingProcess("git", ["apply", ...args, "-"], cwd);
+  await editor.writeProcessStdin(proc.process_id, patch);
+  await editor.closeProcessStdin(proc.process_id);
+
+  let stderr = "";
+  let exitCode = -1;
+  let chunk;
+  while ((chunk = await editor.readProcessOutput(proc.process_id)) !== null) {
+    if (chunk.stream === "stderr") {
+      stderr += chunk.data;
+    } else if (chunk.stream === "exit") {
+      exitCode = chunk.exit_code ?? -1;
+    }
+  }
+
+  return exitCode === 0 ? null : (stderr.trim() || `git apply exited with ${exitCode}`);
+}
@@ -22,3 +22,7 @@ export { NavigationController } from "./navigation-controller.ts";
 // Buffer Creation
 export { VirtualBufferFactory } from "./virtual-buffer-factory.ts";
 export type { VirtualBufferOptions, SplitBufferOptions } from "./virtual-buffer-factory.ts";
+
+// Git Diffs
+export { parseUnifiedDiff, lineChanges, hunkPatch, applyPatch } from "./git-hunks.ts";
+export type { DiffHunk, FileDiff, LineChange } from "./git-hunks.ts";
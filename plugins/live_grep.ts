@@ -45,36 +45,48 @@ function parseRipgrepLine(line: string): GrepMatch | null {
 }
 
 // Parse ripgrep output into suggestions
+// Matches in files the ignore_patterns setting excludes are dropped
 function parseRipgrepOutput(stdout: string): {
   results: GrepMatch[];
   suggestions: PromptSuggestion[];
 } {
-  const results: GrepMatch[] = [];
-  const suggestions: PromptSuggestion[] = [];
-
+  const matches: GrepMatch[] = [];
   for (const line of stdout.split("\n")) {
     if (!line.trim()) continue;
     const match = parseRipgrepLine(line);
     if (match) {
-      results.push(match);
-
-      // Truncate long content for display
-      const displayContent =
-        match.content.length > 60
-          ? match.content.substring(0, 57) + "..."
-          : match.content;
-
-      suggestions.push({
-        text: `${match.file}:${match.line}`,
-        description: displayContent.trim(),
-        value: `${results.length - 1}`, // Store index as value
-        disabled: false,
-      });
-
-      // Limit to 100 results for performance
-      if (results.length >= 100) {
-        break;
+      // With several search paths, rg prefixes the working directory's
+      // matches with "./"; matches in other workspace roots are absolute
+      if (match.file.startsWith("./")) {
+        match.file = match.file.substring(2);
       }
+      matches.push(match);
+    }
+  }
+  const kept = new Set(editor.filterIgnored([...new Set(matches.map((m) => m.file))]));
+
+  const results: GrepMatch[] = [];
+  const suggestions: PromptSuggestion[] = [];
+  for (const match of matches) {
+    if (!kept.has(match.file)) continue;
+    results.push(match);
+
+    // Truncate long content for display
+    const displayContent =
+      match.content.length > 60
+        ? match.content.substring(0, 57) + "..."
+        : match.content;
+
+    suggestions.push({
+      text: `${match.file}:${match.line}`,
+      description: displayContent.trim(),
+      value: `${results.length - 1}`, // Store index as value
+      disabled: false,
+    });
+
+    // Limit to 100 results for performance
+    if (results.length >= 100) {
+      break;
     }
   }
 
@@ -226,6 +238,9 @@ async function runSearch(query: string): Promise<void> {
 
   try {
     const cwd = editor.getCwd();
+    // Search the other workspace roots along with the working directory
+    const extraRoots = editor.getWorkspaceRoots().filter((root) => root !== cwd);
+    const searchPaths = extraRoots.length > 0 ? [".", ...extraRoots] : [];
     editor.debug(`[live_grep] spawning rg for query="${query}" in cwd="${cwd}"`);
     const searchStartTime = Date.now();
     const search = editor.spawnProcess("rg", [
@@ -241,6 +256,7 @@ async function runSearch(query: string): Promise<void> {
       "-g", "!*.lock",
       "--",
       query,
+      ...searchPaths,
     ], cwd);
 
     currentSearch = search;
@@ -0,0 +1,205 @@
+//! Abbreviation expansion handlers.
+//!
+//! This module provides functionality to:
+//! - Expand abbreviations when a word boundary is typed after them
+//! - Revert the last expansion with an immediate Backspace
+//! - List the abbreviations active in the current buffer
+
+use super::types::AbbreviationExpansion;
+use super::Editor;
+use crate::input::abbreviations::{
+    active_abbreviations, find_abbreviation, parse_expansion, Abbreviation, AbbreviationScope,
+};
+use crate::model::event::Event;
+use crate::services::lsp::manager::detect_language;
+use std::collections::BTreeMap;
+
+/// How far back from the cursor to look for an abbreviation
+const MAX_ABBREVIATION_BYTES: usize = 256;
+
+impl Editor {
+    /// Language of the active buffer and the abbreviations in effect for it
+    fn active_buffer_abbreviations(&self) -> (Option<String>, BTreeMap<String, Abbreviation>) {
+        let language = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(|path| detect_language(path, &self.config.languages));
+        let language_abbreviations = language.as_ref().and_then(|name| {
+            self.config
+                .languages
+                .get(name)
+                .map(|config| (name.as_str(), &config.abbreviations))
+        });
+        let active = active_abbreviations(&self.config.abbreviations, language_abbreviations);
+        (language, active)
+    }
+
+    /// Expand the abbreviation ending at the cursor, if any. Called just
+    /// before a word boundary character is typed.
+    ///
+    /// Returns true if the expansion was a snippet (contains `$0`), in which
+    /// case the boundary character should not be inserted.
+    pub(super) fn expand_abbreviation(&mut self) -> bool {
+        let (_, abbreviations) = self.active_buffer_abbreviations();
+        if abbreviations.is_empty() {
+            return false;
+        }
+
+        let buffer_id = self.active_buffer();
+        let state = self.active_state_mut();
+        if state.cursors.count() != 1 || state.cursors.primary().anchor.is_some() {
+            return false;
+        }
+        let cursor_id = state.cursors.primary_id();
+        let position = state.cursors.primary().position;
+        let before =
+            state.get_text_range(position.saturating_sub(MAX_ABBREVIATION_BYTES), position);
+        let line_before = before.rsplit('\n').next().unwrap_or("");
+
+        let Some((typed, abbreviation)) = find_abbreviation(line_before, &abbreviations) else {
+            return false;
+        };
+        let typed = typed.to_string();
+        let (text, cursor_offset) = parse_expansion(&abbreviation.expansion);
+        let start = position - typed.len();
+        let text_end = start + text.len();
+
+        let mut events = vec![
+            Event::Delete {
+                range: start..position,
+                deleted_text: typed.clone(),
+                cursor_id,
+            },
+            Event::Insert {
+                position: start,
+                text: text.clone(),
+                cursor_id,
+            },
+        ];
+        let cursor = match cursor_offset {
+            Some(offset) => {
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: text_end,
+                    new_position: start + offset,
+                    old_anchor: None,
+                    new_anchor: None,
+                    old_sticky_column: 0,
+                    new_sticky_column: 0,
+                });
+                start + offset
+            }
+            None => text_end,
+        };
+
+        let batch = Event::Batch {
+            events,
+            description: format!("Expand abbreviation '{}'", typed),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        self.last_abbreviation = Some(AbbreviationExpansion {
+            buffer_id,
+            start,
+            inserted: text,
+            restored: typed,
+            cursor,
+        });
+        cursor_offset.is_some()
+    }
+
+    /// Extend the pending expansion over the boundary character typed after
+    /// it, so reverting restores exactly what the user typed
+    pub(super) fn include_abbreviation_trigger(&mut self) {
+        let Some(mut expansion) = self.last_abbreviation.take() else {
+            return;
+        };
+        let state = self.active_state_mut();
+        let cursor = state.cursors.primary().position;
+        let expansion_end = expansion.start + expansion.inserted.len();
+        if cursor < expansion_end {
+            return;
+        }
+        let trigger = state.get_text_range(expansion_end, cursor);
+        expansion.inserted.push_str(&trigger);
+        expansion.restored.push_str(&trigger);
+        expansion.cursor = cursor;
+        self.last_abbreviation = Some(expansion);
+    }
+
+    /// Undo an expansion in place, restoring the typed text. Returns false
+    /// (doing nothing) if the buffer or cursor has changed since.
+    pub(super) fn revert_abbreviation(&mut self, expansion: AbbreviationExpansion) -> bool {
+        if self.active_buffer() != expansion.buffer_id {
+            return false;
+        }
+        let state = self.active_state_mut();
+        if state.cursors.count() != 1
+            || state.cursors.primary().anchor.is_some()
+            || state.cursors.primary().position != expansion.cursor
+        {
+            return false;
+        }
+        let end = expansion.start + expansion.inserted.len();
+        if state.get_text_range(expansion.start, end) != expansion.inserted {
+            return false;
+        }
+
+        let cursor_id = state.cursors.primary_id();
+        let batch = Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: expansion.start..end,
+                    deleted_text: expansion.inserted,
+                    cursor_id,
+                },
+                Event::Insert {
+                    position: expansion.start,
+                    text: expansion.restored.clone(),
+                    cursor_id,
+                },
+            ],
+            description: format!("Revert abbreviation '{}'", expansion.restored.trim_end()),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+        true
+    }
+
+    /// Open the abbreviations active in the current buffer in a read-only buffer
+    pub(super) fn list_abbreviations_in_buffer(&mut self) {
+        let (language, abbreviations) = self.active_buffer_abbreviations();
+        if abbreviations.is_empty() {
+            self.set_status_message("No abbreviations configured".to_string());
+            return;
+        }
+
+        let width = abbreviations
+            .keys()
+            .map(|typed| typed.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut content = match &language {
+            Some(language) => format!("// Abbreviations active for {}\n\n", language),
+            None => "// Abbreviations active for this buffer\n\n".to_string(),
+        };
+        for (typed, abbreviation) in &abbreviations {
+            let scope = match &abbreviation.scope {
+                AbbreviationScope::Global => "global".to_string(),
+                AbbreviationScope::Language(name) => name.clone(),
+            };
+            content.push_str(&format!(
+                "{:<width$}  ->  {}  [{}]\n",
+                typed,
+                abbreviation.expansion.replace('\n', "\\n"),
+                scope,
+                width = width
+            ));
+        }
+
+        self.open_read_only_buffer("*Abbreviations*".to_string(), "abbreviation-list", &content);
+        self.set_status_message(format!("Showing {} abbreviation(s)", abbreviations.len()));
+    }
+}
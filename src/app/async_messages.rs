@@ -13,6 +13,7 @@ use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{Diagnostic, InlayHint};
 use serde_json::Value;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use super::types::{LspMessageEntry, LspProgressInfo};
@@ -451,21 +452,22 @@ impl Editor {
     /// Includes debounce logic to prevent rapid auto-reverts from overwhelming the editor.
     /// This is different from `handle_file_changed` which actually reloads the file.
     pub(super) fn handle_async_file_changed(&mut self, path: String) -> bool {
-        const DEBOUNCE_WINDOW: Duration = Duration::from_secs(10);
-        const RAPID_REVERT_THRESHOLD: u32 = 10; // Require 10 reverts in 10 seconds to disable
+        const RAPID_REVERT_THRESHOLD: u32 = 10; // Require 10 reverts in the window to disable
 
         // Skip if auto-revert is disabled
         if !self.auto_revert_enabled {
             return false;
         }
 
+        let debounce_window = Duration::from_millis(self.config.editor.auto_revert_debounce_ms);
         let path_buf = PathBuf::from(&path);
 
-        // Only track events for files that are actually open in the editor
+        // Only track events for files that are actually open in the editor,
+        // and whose buffers have not had auto-revert turned off
         let is_file_open = self
             .buffers
             .iter()
-            .any(|(_, state)| state.buffer.file_path() == Some(&path_buf));
+            .any(|(_, state)| state.buffer.file_path() == Some(&path_buf) && state.auto_revert);
 
         if !is_file_open {
             tracing::trace!("Ignoring file change event for non-open file: {}", path);
@@ -474,12 +476,13 @@ impl Editor {
 
         // Track rapid file change events - only disable after many reverts in short window
         if let Some((window_start, count)) = self.file_rapid_change_counts.get_mut(&path_buf) {
-            if self.time_source.elapsed_since(*window_start) < DEBOUNCE_WINDOW {
+            if self.time_source.elapsed_since(*window_start) < debounce_window {
                 *count += 1;
 
                 if *count >= RAPID_REVERT_THRESHOLD {
                     // Disable auto-revert
                     self.auto_revert_enabled = false;
+                    self.auto_revert_disabled_by = Some(path_buf.clone());
                     self.status_message = Some(format!(
                         "Auto-revert disabled: {} is updating too frequently (use Ctrl+Shift+R to re-enable)",
                         path_buf.file_name().unwrap_or_default().to_string_lossy()
@@ -488,7 +491,7 @@ impl Editor {
                         "Auto-revert disabled for {:?} ({} reverts in {:?})",
                         path_buf,
                         count,
-                        DEBOUNCE_WINDOW
+                        debounce_window
                     );
                     return false;
                 }
@@ -518,6 +521,13 @@ impl Editor {
     pub(super) fn handle_file_explorer_initialized(&mut self, mut view: FileTreeView) {
         tracing::info!("File explorer initialized");
 
+        let explorer_config = &self.config.file_explorer;
+        let ignore_patterns = view.ignore_patterns_mut();
+        ignore_patterns.set_service(Arc::clone(&self.ignore_service));
+        ignore_patterns.set_show_gitignored(
+            explorer_config.show_gitignored || !explorer_config.respect_gitignore,
+        );
+
         // Load root .gitignore
         let root_id = view.tree().root_id();
         let root_path = view.tree().get_node(root_id).map(|n| n.entry.path.clone());
@@ -531,6 +541,7 @@ impl Editor {
         }
 
         self.file_explorer = Some(view);
+        self.sync_file_explorer_roots();
         self.set_status_message("File explorer ready".to_string());
     }
 
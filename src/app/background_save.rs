@@ -0,0 +1,265 @@
+//! Saving large buffers in the background.
+//!
+//! Buffers of at least `editor.background_save_threshold_bytes` are written
+//! on a background thread, so saving hundreds of megabytes doesn't block the
+//! UI. This module provides functionality to:
+//! - Write a snapshot of the buffer, so it can be edited during the save
+//! - Show the progress of the save in the status bar
+//! - Read the written file back and check its length and checksum before it
+//!   replaces the original, if `editor.verify_background_saves` is set
+//! - Finish the save as a foreground save does once the file is written
+//!
+//! A quit waits for saves in progress, and is called off if one fails.
+//! Buffers with on-save actions and copies of remote files still save in
+//! the foreground, since the actions and the upload work on the saved file.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use sha2::{Digest, Sha256};
+
+use super::Editor;
+use crate::model::buffer::{AtomicWrite, SaveSnapshot};
+use crate::model::event::BufferId;
+
+/// A buffer being written to its file in the background
+pub(crate) struct BackgroundSave {
+    path: PathBuf,
+    snapshot: Arc<SaveSnapshot>,
+    /// Position of the buffer's event log when the snapshot was taken
+    event_index: usize,
+    /// Bytes written so far, counted by the writer thread
+    written: Arc<AtomicUsize>,
+    handle: JoinHandle<io::Result<()>>,
+    /// Progress last shown in the status bar, in percent
+    shown_percent: usize,
+}
+
+impl BackgroundSave {
+    fn percent(&self) -> usize {
+        let written = self.written.load(Ordering::Relaxed);
+        (written * 100)
+            .checked_div(self.snapshot.len())
+            .unwrap_or(100)
+            .min(100)
+    }
+}
+
+impl Editor {
+    /// Whether the active buffer is large enough to save in the background,
+    /// and has nothing to run once it is saved
+    pub(super) fn saves_in_background(&self) -> bool {
+        let threshold = self.config.editor.background_save_threshold_bytes;
+        threshold > 0
+            && self.active_state().buffer.len() as u64 >= threshold
+            && !self.ssh_buffers.contains_key(&self.active_buffer())
+            && !self.has_on_save_actions()
+    }
+
+    /// Start writing the active buffer to its file in the background
+    pub(super) fn start_background_save(&mut self) -> io::Result<()> {
+        let buffer_id = self.active_buffer();
+        if self.background_saves.contains_key(&buffer_id) {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the buffer is still saving",
+            ));
+        }
+        let buffer = &self.active_state().buffer;
+        let path = buffer
+            .file_path()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "No file path associated with buffer",
+                )
+            })?
+            .to_path_buf();
+        let snapshot = Arc::new(buffer.save_snapshot()?);
+        // Creating the temp file here reports errors such as a denied
+        // permission at once, as a foreground save does
+        let write = AtomicWrite::create(&path)?;
+
+        let written = Arc::new(AtomicUsize::new(0));
+        let verify = self.config.editor.verify_background_saves;
+        let handle = {
+            let snapshot = Arc::clone(&snapshot);
+            let written = Arc::clone(&written);
+            std::thread::spawn(move || write_snapshot(&snapshot, write, &written, verify))
+        };
+        tracing::info!(
+            "Saving {} ({} bytes) in the background",
+            path.display(),
+            snapshot.len()
+        );
+
+        // Edits from here on start a new undo step, so undo can get back to
+        // the saved content
+        let event_log = self.active_event_log_mut();
+        event_log.add_undo_boundary();
+        let save = BackgroundSave {
+            path,
+            snapshot,
+            event_index: event_log.current_index(),
+            written,
+            handle,
+            shown_percent: 0,
+        };
+        self.status_message = Some(saving_message(&save));
+        self.background_saves.insert(buffer_id, save);
+        Ok(())
+    }
+
+    /// Show the progress of saves in the background and finish those done.
+    /// Returns true if the screen needs redrawing.
+    pub fn poll_background_saves(&mut self) -> bool {
+        if self.background_saves.is_empty() {
+            return false;
+        }
+        let mut changed = false;
+
+        let finished: Vec<BufferId> = self
+            .background_saves
+            .iter()
+            .filter(|(_, save)| save.handle.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+        for buffer_id in finished {
+            if let Some(save) = self.background_saves.remove(&buffer_id) {
+                self.finish_background_save(buffer_id, save);
+                changed = true;
+            }
+        }
+
+        // Progress of the save of the active buffer, or else of any
+        let active_buffer = self.active_buffer();
+        let shown = if self.background_saves.contains_key(&active_buffer) {
+            Some(active_buffer)
+        } else {
+            self.background_saves.keys().next().copied()
+        };
+        if let Some(save) = shown.and_then(|id| self.background_saves.get_mut(&id)) {
+            let percent = save.percent();
+            if save.shown_percent != percent {
+                save.shown_percent = percent;
+                self.status_message = Some(saving_message(save));
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Whether any buffer is being saved in the background
+    pub fn is_saving_in_background(&self) -> bool {
+        !self.background_saves.is_empty()
+    }
+
+    /// Record a finished save in the background, as a foreground save
+    /// would. Edits made since the snapshot was taken stay modified.
+    fn finish_background_save(&mut self, buffer_id: BufferId, save: BackgroundSave) {
+        let BackgroundSave {
+            path,
+            snapshot,
+            event_index,
+            handle,
+            ..
+        } = save;
+        let result = handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("save thread panicked")));
+        let name = file_name(&path);
+        if let Err(e) = result {
+            tracing::warn!("Background save of {} failed: {}", path.display(), e);
+            self.status_message = Some(format!("Failed to save {}: {}", name, e));
+            // A quit waiting for the save is called off
+            self.should_quit = false;
+            return;
+        }
+        tracing::info!("Saved {} in the background", path.display());
+        if !self.buffers.contains_key(&buffer_id) {
+            self.status_message = Some(format!("Saved {}", name));
+            return;
+        }
+
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.mark_snapshot_saved(&snapshot, &path);
+        }
+        self.finish_save(buffer_id, Some(path));
+        // The content saved is that of the snapshot, not the current one
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.mark_saved_at(event_index);
+        }
+        self.update_modified_from_event_log(buffer_id);
+
+        self.status_message = Some(format!("Saved {}", name));
+    }
+}
+
+/// Write `snapshot` to the file through `write`, counting the bytes in
+/// `written`. With `verify`, the temp file is read back and checked before
+/// it replaces the file; if it doesn't match, the file is left as it was.
+fn write_snapshot(
+    snapshot: &SaveSnapshot,
+    mut write: AtomicWrite,
+    written: &AtomicUsize,
+    verify: bool,
+) -> io::Result<()> {
+    let mut hasher = verify.then(Sha256::new);
+    snapshot.write_to(write.file(), &mut |chunk| {
+        if let Some(hasher) = hasher.as_mut() {
+            hasher.update(chunk);
+        }
+        written.fetch_add(chunk.len(), Ordering::Relaxed);
+    })?;
+    if let Some(hasher) = hasher {
+        write.file().sync_all()?;
+        verify_written(write.temp_path(), snapshot.len(), &hasher.finalize())?;
+    }
+    write.commit()?;
+    Ok(())
+}
+
+/// Check that the file at `path` holds `len` bytes with the SHA-256
+/// checksum `expected`
+fn verify_written(path: &Path, len: usize, expected: &[u8]) -> io::Result<()> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut chunk = vec![0; 64 * 1024];
+    let mut read = 0;
+    loop {
+        let n = match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&chunk[..n]);
+        read += n;
+    }
+    if read != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("verification failed: wrote {} of {} bytes", read, len),
+        ));
+    }
+    if hasher.finalize().as_slice() != expected {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "verification failed: written bytes differ from the buffer",
+        ));
+    }
+    Ok(())
+}
+
+fn saving_message(save: &BackgroundSave) -> String {
+    format!("Saving {}... {}%", file_name(&save.path), save.percent())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
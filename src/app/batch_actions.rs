@@ -0,0 +1,64 @@
+//! Headless batch mode.
+//!
+//! This module provides functionality to:
+//! - Run batch commands (replace, format, save, named actions) on the active buffer
+//! - Run a plugin script and apply the edits it makes
+
+use super::Editor;
+use crate::input::batch_commands::BatchCommand;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long the plugin must stay quiet before a batch script is considered done
+const SCRIPT_IDLE: Duration = Duration::from_millis(100);
+
+/// Upper bound on how long a batch script may keep sending commands
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl Editor {
+    /// Run a batch command on the active buffer
+    pub fn run_batch_command(&mut self, command: &BatchCommand) -> Result<(), String> {
+        match command {
+            BatchCommand::Replace {
+                search,
+                replacement,
+            } => {
+                self.perform_replace(search, replacement);
+                Ok(())
+            }
+            BatchCommand::Format => self.format_buffer(),
+            BatchCommand::Save => {
+                if self.active_state().buffer.file_path().is_none() {
+                    return Err("Buffer has no file to save to".to_string());
+                }
+                self.save().map_err(|e| e.to_string())
+            }
+            BatchCommand::Action(action) => self
+                .handle_action(action.clone())
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Load a plugin script and apply the commands it sends until it goes
+    /// quiet
+    pub fn run_batch_script(&mut self, path: &Path) -> Result<(), String> {
+        self.plugin_manager.load_plugin(path)?;
+
+        let started = Instant::now();
+        let mut last_activity = Instant::now();
+        while last_activity.elapsed() < SCRIPT_IDLE {
+            if started.elapsed() > SCRIPT_TIMEOUT {
+                return Err(format!(
+                    "Script did not finish within {}s",
+                    SCRIPT_TIMEOUT.as_secs()
+                ));
+            }
+            if self.process_async_messages() {
+                last_activity = Instant::now();
+            } else {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        Ok(())
+    }
+}
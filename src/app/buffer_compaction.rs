@@ -0,0 +1,109 @@
+//! Compaction of fragmented buffers.
+//!
+//! This module provides functionality to:
+//! - Merge the small pieces that typing and scattered edits leave in a
+//!   buffer's piece tree, once the editor has been idle for a moment
+//! - Keep totals of what compaction did, shown with the buffer's piece tree
+//!   statistics by the Show Buffer Statistics command
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::model::event::BufferId;
+use std::time::{Duration, Instant};
+
+/// Time without input after which buffers are compacted
+const COMPACT_IDLE_DELAY: Duration = Duration::from_secs(1);
+
+/// Buffers with fewer pieces than this are left alone
+const COMPACT_MIN_PIECES: usize = 64;
+
+/// Totals of the compaction passes run so far
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactionMetrics {
+    /// Buffers compacted
+    pub runs: usize,
+    /// Pieces removed by merging
+    pub pieces_merged: usize,
+    /// Bytes copied from small pieces into new buffers
+    pub bytes_copied: usize,
+    /// Time the last pass took
+    pub last_duration: Duration,
+}
+
+impl Editor {
+    /// Note user input, which postpones compaction until the editor is idle
+    pub(super) fn note_input_for_compaction(&mut self) {
+        self.last_input_time = self.time_source.now();
+        self.compacted_since_input = false;
+    }
+
+    /// Compact fragmented buffers once the editor has been idle for a moment.
+    ///
+    /// Called every frame; runs at most once per idle period.
+    pub fn compact_idle_buffers(&mut self) {
+        if self.compacted_since_input
+            || self.time_source.elapsed_since(self.last_input_time) < COMPACT_IDLE_DELAY
+        {
+            return;
+        }
+        self.compacted_since_input = true;
+
+        let fragmented: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.stats().leaf_count >= COMPACT_MIN_PIECES)
+            .map(|(id, _)| *id)
+            .collect();
+        for buffer_id in fragmented {
+            self.compact_buffer(buffer_id);
+        }
+    }
+
+    /// Compact one buffer and add what it did to the metrics
+    pub fn compact_buffer(&mut self, buffer_id: BufferId) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let start = Instant::now();
+        let stats = state.buffer.compact();
+        let duration = start.elapsed();
+        tracing::debug!(
+            "Compacted buffer {:?}: {} -> {} pieces, {} bytes copied in {:?}",
+            buffer_id,
+            stats.pieces_before,
+            stats.pieces_after,
+            stats.bytes_copied,
+            duration
+        );
+
+        let metrics = &mut self.compaction_metrics;
+        metrics.runs += 1;
+        metrics.pieces_merged += stats.pieces_before - stats.pieces_after;
+        metrics.bytes_copied += stats.bytes_copied;
+        metrics.last_duration = duration;
+    }
+
+    /// Totals of the compaction passes run so far
+    pub fn compaction_metrics(&self) -> CompactionMetrics {
+        self.compaction_metrics
+    }
+
+    /// Show the active buffer's piece tree statistics and the compaction
+    /// totals in the status bar
+    pub fn show_buffer_stats(&mut self) {
+        let buffer = &self.active_state().buffer;
+        let stats = buffer.stats();
+        let string_buffers = buffer.string_buffer_count();
+        let metrics = self.compaction_metrics;
+        self.set_status_message(format!(
+            "{} pieces, depth {}, {} string buffers | compacted {} times: {} pieces merged, {} copied, last took {:.1} ms",
+            stats.leaf_count,
+            stats.depth,
+            string_buffers,
+            metrics.runs,
+            metrics.pieces_merged,
+            format_size(metrics.bytes_copied as u64),
+            metrics.last_duration.as_secs_f64() * 1000.0
+        ));
+    }
+}
@@ -0,0 +1,435 @@
+//! Buffer list.
+//!
+//! This module provides functionality to:
+//! - List every open buffer in a split, with its modified state, size and path
+//! - Mark buffers to close or save several at once, or save all modified ones
+//! - Jump to a buffer in the split the list was opened from
+//! - Close all buffers whose changes are saved
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::{BufferId, SplitDirection, SplitId};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::split::SplitViewState;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashSet;
+
+/// Mode of the buffer list
+const LIST_MODE: &str = "buffer-list";
+
+/// Lines at the top of the list before the first buffer
+const HEADER_LINES: usize = 2;
+
+/// Share of the height kept by the split the list is opened from
+const LIST_SPLIT_RATIO: f32 = 0.6;
+
+/// The list buffer, the buffers it shows and the split it was opened from
+pub(crate) struct BufferListPanel {
+    buffer_id: BufferId,
+    source_split: SplitId,
+    /// Buffer shown on each row
+    rows: Vec<BufferId>,
+    marked: HashSet<BufferId>,
+}
+
+impl Editor {
+    /// Show all open buffers in a split below the current one
+    pub fn show_buffer_list(&mut self) {
+        if let Some(panel) = self.buffer_list_panel.as_ref() {
+            let buffer_id = panel.buffer_id;
+            if let Some(&split_id) = self.split_manager.splits_for_buffer(buffer_id).first() {
+                self.refresh_buffer_list();
+                self.save_current_split_view_state();
+                self.split_manager.set_active_split(split_id);
+                self.restore_current_split_view_state();
+                return;
+            }
+            if self.buffers.contains_key(&buffer_id) {
+                let _ = self.force_close_buffer(buffer_id);
+            }
+            self.buffer_list_panel = None;
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(LIST_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "buffer_list_jump")
+                .with_binding(KeyCode::Char(' '), KeyModifiers::NONE, "buffer_list_mark")
+                .with_binding(KeyCode::Char('d'), KeyModifiers::NONE, "buffer_list_close")
+                .with_binding(KeyCode::Char('s'), KeyModifiers::NONE, "buffer_list_save")
+                .with_binding(
+                    KeyCode::Char('S'),
+                    KeyModifiers::SHIFT,
+                    "buffer_list_save_all",
+                )
+                .with_binding(
+                    KeyCode::Char('S'),
+                    KeyModifiers::NONE,
+                    "buffer_list_save_all",
+                )
+                .with_binding(
+                    KeyCode::Char('g'),
+                    KeyModifiers::NONE,
+                    "buffer_list_refresh",
+                ),
+        );
+        let buffer_id =
+            self.create_virtual_buffer("*Buffers*".to_string(), LIST_MODE.to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        self.buffer_list_panel = Some(BufferListPanel {
+            buffer_id,
+            source_split: self.split_manager.active_split(),
+            rows: Vec::new(),
+            marked: HashSet::new(),
+        });
+        self.refresh_buffer_list();
+
+        self.save_current_split_view_state();
+        match self.split_manager.split_active(
+            SplitDirection::Horizontal,
+            buffer_id,
+            LIST_SPLIT_RATIO,
+        ) {
+            Ok(split_id) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    buffer_id,
+                );
+                view_state.viewport.line_wrap_enabled = false;
+                self.split_view_states.insert(split_id, view_state);
+                self.split_manager.set_active_split(split_id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to create buffer list split: {}", e);
+                self.set_active_buffer(buffer_id);
+            }
+        }
+    }
+
+    /// Show the buffer on the cursor line in the split the list was opened
+    /// from
+    pub fn buffer_list_jump(&mut self) {
+        let Some(buffer_id) = self.buffer_list_row() else {
+            return;
+        };
+        let source_split = self
+            .buffer_list_panel
+            .as_ref()
+            .map(|panel| panel.source_split)
+            .filter(|&split| self.split_manager.get_buffer_id(split).is_some());
+        if let Some(source_split) = source_split {
+            self.save_current_split_view_state();
+            self.split_manager.set_active_split(source_split);
+            self.restore_current_split_view_state();
+        }
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Mark or unmark the buffer on the cursor line and move to the next one
+    pub fn buffer_list_toggle_mark(&mut self) {
+        let Some(buffer_id) = self.buffer_list_row() else {
+            return;
+        };
+        if let Some(panel) = self.buffer_list_panel.as_mut() {
+            if !panel.marked.remove(&buffer_id) {
+                panel.marked.insert(buffer_id);
+            }
+        }
+        self.refresh_buffer_list();
+
+        let state = self.active_state_mut();
+        let line = state
+            .buffer
+            .get_line_number(state.cursors.primary().position);
+        if let Some(next) = state.buffer.line_start_offset(line + 1) {
+            if next < state.buffer.len() {
+                state.cursors.primary_mut().position = next;
+            }
+        }
+    }
+
+    /// Close the marked buffers, or the one on the cursor line. Buffers with
+    /// unsaved changes are kept.
+    pub fn buffer_list_close(&mut self) {
+        let targets = self.buffer_list_targets();
+        if targets.is_empty() {
+            return;
+        }
+        let mut closed = 0;
+        let mut modified = 0;
+        for buffer_id in targets {
+            let is_modified = self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|state| state.buffer.is_modified());
+            if is_modified {
+                modified += 1;
+            } else if self.close_buffer(buffer_id).is_ok() {
+                closed += 1;
+            }
+        }
+        self.focus_buffer_list();
+        self.refresh_buffer_list();
+
+        let mut message = format!("Closed {} buffer(s)", closed);
+        if modified > 0 {
+            message.push_str(&format!(", kept {} with unsaved changes", modified));
+        }
+        self.set_status_message(message);
+    }
+
+    /// Save the marked buffers, or the one on the cursor line
+    pub fn buffer_list_save(&mut self) {
+        let targets = self.buffer_list_targets();
+        if targets.is_empty() {
+            return;
+        }
+        self.save_buffers(targets);
+    }
+
+    /// Save every buffer with unsaved changes
+    pub fn buffer_list_save_all(&mut self) {
+        let mut targets: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified())
+            .map(|(&id, _)| id)
+            .collect();
+        targets.sort_by_key(|id| id.0);
+        self.save_buffers(targets);
+    }
+
+    /// Update the list after buffers were opened, closed or changed
+    pub fn buffer_list_refresh(&mut self) {
+        self.refresh_buffer_list();
+    }
+
+    /// Close every buffer of a file without unsaved changes
+    pub fn close_saved_buffers(&mut self) {
+        let saved: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.file_path().is_some() && !state.buffer.is_modified())
+            .map(|(&id, _)| id)
+            .collect();
+        let mut closed = 0;
+        for buffer_id in saved {
+            if self.close_buffer(buffer_id).is_ok() {
+                closed += 1;
+            }
+        }
+        self.focus_buffer_list();
+        self.refresh_buffer_list();
+        self.set_status_message(format!("Closed {} saved buffer(s)", closed));
+    }
+
+    /// Save `targets`, skipping those without a file or changed on disk
+    fn save_buffers(&mut self, targets: Vec<BufferId>) {
+        let mut saved = 0;
+        let mut skipped = Vec::new();
+        let mut failed = Vec::new();
+        for buffer_id in targets {
+            let Some(state) = self.buffers.get(&buffer_id) else {
+                continue;
+            };
+            if !state.buffer.is_modified() {
+                continue;
+            }
+            let name = self.get_buffer_display_name(buffer_id);
+            match self.save_buffer_by_id(buffer_id) {
+                Ok(()) => saved += 1,
+                Err(None) => skipped.push(name),
+                Err(Some(e)) => failed.push(format!("{} ({})", name, e)),
+            }
+        }
+        self.refresh_buffer_list();
+
+        let mut message = format!("Saved {} buffer(s)", saved);
+        if !skipped.is_empty() {
+            message.push_str(&format!(
+                "; skipped {} (no file or changed on disk)",
+                skipped.join(", ")
+            ));
+        }
+        if !failed.is_empty() {
+            message.push_str(&format!("; failed {}", failed.join(", ")));
+        }
+        self.set_status_message(message);
+    }
+
+    /// The buffer on the cursor line of the list, if the list is active
+    fn buffer_list_row(&self) -> Option<BufferId> {
+        let panel = self
+            .buffer_list_panel
+            .as_ref()
+            .filter(|panel| panel.buffer_id == self.active_buffer())?;
+        let state = self.active_state();
+        state
+            .text_properties
+            .get_at(state.cursors.primary().position)
+            .iter()
+            .find_map(|property| property.get_as::<usize>("row"))
+            .and_then(|row| panel.rows.get(row).copied())
+            .filter(|buffer_id| self.buffers.contains_key(buffer_id))
+    }
+
+    /// The marked buffers that are still open, or else the buffer on the
+    /// cursor line
+    fn buffer_list_targets(&self) -> Vec<BufferId> {
+        let Some(panel) = self
+            .buffer_list_panel
+            .as_ref()
+            .filter(|panel| panel.buffer_id == self.active_buffer())
+        else {
+            return Vec::new();
+        };
+        let marked: Vec<BufferId> = panel
+            .rows
+            .iter()
+            .filter(|id| panel.marked.contains(id) && self.buffers.contains_key(id))
+            .copied()
+            .collect();
+        if marked.is_empty() {
+            self.buffer_list_row().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    /// Keep the list in its own split only: closing the buffer of another
+    /// split may have put the list there in its place
+    fn focus_buffer_list(&mut self) {
+        let Some(list_buffer) = self.buffer_list_panel.as_ref().map(|p| p.buffer_id) else {
+            return;
+        };
+        let splits = self.split_manager.splits_for_buffer(list_buffer);
+        let active = self.split_manager.active_split();
+        let Some(list_split) = splits
+            .iter()
+            .copied()
+            .find(|&split| split == active)
+            .or_else(|| splits.first().copied())
+        else {
+            return;
+        };
+        let replacement = self
+            .buffers
+            .keys()
+            .filter(|&&id| id != list_buffer)
+            .min_by_key(|id| id.0)
+            .copied();
+        if let Some(replacement) = replacement {
+            for &split in splits.iter().filter(|&&split| split != list_split) {
+                let _ = self.split_manager.set_split_buffer(split, replacement);
+            }
+        }
+        if list_split != active {
+            self.save_current_split_view_state();
+            self.split_manager.set_active_split(list_split);
+            self.restore_current_split_view_state();
+        }
+    }
+
+    /// Rewrite the list buffer from the open buffers
+    fn refresh_buffer_list(&mut self) {
+        let Some(panel) = self.buffer_list_panel.as_ref() else {
+            return;
+        };
+        let list_buffer = panel.buffer_id;
+        if !self.buffers.contains_key(&list_buffer) {
+            self.buffer_list_panel = None;
+            return;
+        }
+
+        let mut rows: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .filter(|&&id| id != list_buffer)
+            .copied()
+            .collect();
+        rows.sort_by_key(|id| id.0);
+
+        let names: Vec<String> = rows
+            .iter()
+            .map(|&id| {
+                self.buffers
+                    .get(&id)
+                    .and_then(|state| state.buffer.file_path())
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| self.get_buffer_display_name(id))
+            })
+            .collect();
+        let name_width = names.iter().map(|name| name.chars().count()).max();
+        let name_width = name_width.unwrap_or(0).clamp(10, 40);
+
+        let mut modified_count = 0;
+        let mut lines = Vec::with_capacity(rows.len());
+        for (row, (&buffer_id, name)) in rows.iter().zip(&names).enumerate() {
+            let Some(state) = self.buffers.get(&buffer_id) else {
+                continue;
+            };
+            let modified = state.buffer.is_modified();
+            if modified {
+                modified_count += 1;
+            }
+            let path = state
+                .buffer
+                .file_path()
+                .map(|path| {
+                    path.strip_prefix(&self.working_dir)
+                        .unwrap_or(path)
+                        .display()
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let text = format!(
+                "  [{}] {} {:<name_width$}  {:>9}  {}\n",
+                if panel.marked.contains(&buffer_id) {
+                    'x'
+                } else {
+                    ' '
+                },
+                if modified { '*' } else { ' ' },
+                name,
+                format_size(state.buffer.total_bytes() as u64),
+                path,
+            );
+            lines.push(TextPropertyEntry::text(text).with_property("row", serde_json::json!(row)));
+        }
+
+        let mut entries = vec![TextPropertyEntry::text(format!(
+            "Buffers: {} open, {} modified\n\
+             Enter: jump   Space: mark   d: close   s: save   S: save all modified   \
+             g: refresh   q: close list\n",
+            rows.len(),
+            modified_count
+        ))];
+        entries.extend(lines);
+
+        let row_count = rows.len();
+        if let Some(panel) = self.buffer_list_panel.as_mut() {
+            panel.marked.retain(|id| rows.contains(id));
+            panel.rows = rows;
+        }
+        if let Err(e) = self.set_virtual_buffer_content(list_buffer, entries) {
+            tracing::warn!("Failed to update buffer list: {}", e);
+            return;
+        }
+        // Keep the cursor on a row when rows below it were removed
+        let last_row = HEADER_LINES + row_count - 1;
+        if let Some(state) = self.buffers.get_mut(&list_buffer) {
+            let cursor = state.cursors.primary().position;
+            if row_count > 0 && state.buffer.get_line_number(cursor) > last_row {
+                if let Some(offset) = state.buffer.line_start_offset(last_row) {
+                    state.cursors.primary_mut().position = offset;
+                }
+            }
+        }
+    }
+}
@@ -12,12 +12,14 @@ use std::io;
 use std::path::Path;
 
 use crate::model::event::{BufferId, Event, SplitId};
+use crate::primitives::indent_detection::{detect_buffer_indentation, IndentStyle};
 use crate::services::lsp::manager::detect_language;
 use crate::state::EditorState;
 use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
 
 use super::help;
+use super::protected_mode;
 use super::Editor;
 
 impl Editor {
@@ -55,16 +57,30 @@ impl Editor {
             .map(|m| m.display_name.clone())
             .unwrap_or_else(|| path.display().to_string());
 
-        // Check if buffer is binary for status message
-        let is_binary = self
+        // Check if buffer is binary, minified or over the size limits for
+        // status message
+        let (is_binary, is_protected, auto_revert) = self
             .buffers
             .get(&buffer_id)
-            .map(|s| s.buffer.is_binary())
-            .unwrap_or(false);
-
-        // Show appropriate status message for binary vs regular files
-        if is_binary {
+            .map(|s| (s.buffer.is_binary(), s.protected, s.auto_revert))
+            .unwrap_or((false, false, true));
+
+        // Show appropriate status message for binary, minified, large and
+        // regular files
+        if let Some(message) = self.progressive_load_message(buffer_id) {
+            self.status_message = Some(message);
+        } else if is_binary {
             self.status_message = Some(format!("Opened {} [binary file, read-only]", display_name));
+        } else if !auto_revert {
+            self.status_message = Some(format!(
+                "Opened {} [large file, features limited]",
+                display_name
+            ));
+        } else if is_protected {
+            self.status_message = Some(format!(
+                "Opened {} [minified file, highlighting off]",
+                display_name
+            ));
         } else {
             self.status_message = Some(format!("Opened {}", display_name));
         }
@@ -79,9 +95,10 @@ impl Editor {
     ///
     /// If the file doesn't exist, creates an unsaved buffer with that filename.
     pub fn open_file_no_focus(&mut self, path: &Path) -> io::Result<BufferId> {
-        // Resolve relative paths against working_dir, not process current directory
+        // Resolve relative paths against the split's working directory, not
+        // the process current directory
         let resolved_path = if path.is_relative() {
-            self.working_dir.join(path)
+            self.active_split_working_dir().join(path)
         } else {
             path.to_path_buf()
         };
@@ -147,15 +164,10 @@ impl Editor {
             id
         };
 
-        // Create the editor state - either load from file or create empty buffer
-        let mut state = if file_exists {
-            EditorState::from_file(
-                path,
-                self.terminal_width,
-                self.terminal_height,
-                self.config.editor.large_file_threshold_bytes as usize,
-                &self.grammar_registry,
-            )?
+        // Create the editor state - either load from file or create empty buffer.
+        // A file slow to read opens with what has arrived and keeps loading.
+        let (mut state, load) = if file_exists {
+            self.load_file_state(path)?
         } else {
             // File doesn't exist - create empty buffer with the file path set
             let mut new_state = EditorState::new(
@@ -165,7 +177,7 @@ impl Editor {
             );
             // Set the file path so saving will create the file
             new_state.buffer.set_file_path(path.to_path_buf());
-            new_state
+            (new_state, None)
         };
         // Note: line_wrap_enabled is set on SplitViewState.viewport when the split is created
 
@@ -177,6 +189,35 @@ impl Editor {
             tracing::info!("Detected binary file: {}", path.display());
         }
 
+        // Binary and minified files open in protected mode, so scanning their
+        // text for display cannot freeze the editor
+        let is_minified = file_exists
+            && !is_binary
+            && protected_mode::is_minified(path, self.config.editor.minified_line_length);
+        if is_minified {
+            tracing::info!("Detected minified file: {}", path.display());
+        }
+        if is_binary || is_minified {
+            protected_mode::protect(&mut state);
+        }
+
+        // Files over the size limits also lose auto-revert until the user
+        // chooses otherwise
+        let over_limits = if file_exists && !is_binary {
+            protected_mode::over_size_limits(
+                path,
+                self.config.editor.file_size_warning_bytes,
+                self.config.editor.line_length_warning_bytes,
+            )
+        } else {
+            None
+        };
+        if let Some(reason) = &over_limits {
+            tracing::info!("File over size limits ({}): {}", reason, path.display());
+            protected_mode::protect(&mut state);
+            state.auto_revert = false;
+        }
+
         // Set show_whitespace_tabs, use_tabs, and tab_size based on language config
         // with fallback to global editor config for tab_size
         if let Some(language) = detect_language(path, &self.config.languages) {
@@ -192,9 +233,26 @@ impl Editor {
             state.tab_size = self.config.editor.tab_size;
         }
 
+        // The file's own indentation wins over the language settings
+        if self.config.editor.detect_indentation && file_exists && !is_binary {
+            match detect_buffer_indentation(&mut state.buffer) {
+                Some(IndentStyle::Tabs) => state.use_tabs = true,
+                Some(IndentStyle::Spaces(width)) => {
+                    state.use_tabs = false;
+                    state.tab_size = width;
+                }
+                None => {}
+            }
+        }
+
         self.buffers.insert(buffer_id, state);
         self.event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
+        // Bring back the undo history of an earlier session, once the whole
+        // file is there
+        if file_exists && !is_binary && load.is_none() {
+            self.restore_undo_history(buffer_id, path);
+        }
 
         // Create metadata for this buffer
         let mut metadata =
@@ -207,13 +265,17 @@ impl Editor {
             metadata.disable_lsp("Binary file".to_string());
         }
 
-        // Notify LSP about the newly opened file (skip for binary files)
-        if !is_binary {
+        // Notify LSP about the newly opened file (skip for binary files, and
+        // wait for files still loading)
+        if !is_binary && load.is_none() {
             self.notify_lsp_file_opened(path, buffer_id, &mut metadata);
         }
 
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
+        if let Some(load) = load {
+            self.progressive_loads.insert(buffer_id, load);
+        }
 
         // Add buffer to the active split's tabs (but don't switch to it)
         let active_split = self.split_manager.active_split();
@@ -234,6 +296,18 @@ impl Editor {
             }),
         );
 
+        if is_binary && self.config.editor.binary_hex_view_prompt {
+            self.start_prompt(
+                "Binary file. Show a hex view? (y/N) ".to_string(),
+                PromptType::ConfirmHexView {
+                    path: path.to_path_buf(),
+                },
+            );
+        }
+        if let Some(reason) = over_limits {
+            self.start_limit_file_features_prompt(buffer_id, &reason);
+        }
+
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
 
@@ -420,11 +494,12 @@ impl Editor {
         let buffer_id = BufferId(self.next_buffer_id);
         self.next_buffer_id += 1;
 
-        let state = EditorState::new(
+        let mut state = EditorState::new(
             self.terminal_width,
             self.terminal_height,
             self.config.editor.large_file_threshold_bytes as usize,
         );
+        state.tab_size = self.config.editor.tab_size;
         // Note: line_wrap_enabled is set on SplitViewState.viewport when the split is created
         self.buffers.insert(buffer_id, state);
         self.event_logs
@@ -686,6 +761,26 @@ impl Editor {
         buffer_id
     }
 
+    /// Open `content` in a new read-only virtual buffer and make it the
+    /// active buffer
+    pub(super) fn open_read_only_buffer(
+        &mut self,
+        name: String,
+        mode: &str,
+        content: &str,
+    ) -> BufferId {
+        let buffer_id = self.create_virtual_buffer(name, mode.to_string(), true);
+        let threshold = self.config.editor.large_file_threshold_bytes as usize;
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .buffer
+                .replace(crate::model::buffer::Buffer::from_str(content, threshold));
+            state.editing_disabled = true;
+        }
+        self.set_active_buffer(buffer_id);
+        buffer_id
+    }
+
     /// Set the content of a virtual buffer with text properties
     ///
     /// # Arguments
@@ -870,6 +965,7 @@ impl Editor {
     fn close_buffer_internal(&mut self, id: BufferId) -> io::Result<()> {
         // Save file state before closing (for per-file session persistence)
         self.save_file_state_on_close(id);
+        self.save_undo_history(id);
 
         // If closing a terminal buffer while in terminal mode, exit terminal mode
         if self.terminal_mode && self.is_terminal_buffer(id) {
@@ -896,8 +992,15 @@ impl Editor {
 
         self.buffers.remove(&id);
         self.event_logs.remove(&id);
+        self.autosave_snapshots.remove(&id);
         self.seen_byte_ranges.remove(&id);
         self.buffer_metadata.remove(&id);
+        self.ssh_buffers.remove(&id);
+        self.forget_vfs_buffer(id);
+        self.forget_git_commit_buffer(id);
+        self.forget_collab_buffer(id);
+        #[cfg(unix)]
+        self.release_remote_waiters(id);
 
         // Remove buffer from panel_ids mapping if it was a panel buffer
         // This prevents stale entries when the same panel_id is reused later
@@ -968,6 +1071,7 @@ impl Editor {
 
         if is_last_viewport {
             // Last viewport of this buffer - close the buffer entirely
+            self.persist_scratch_buffer(buffer_id);
             if self.active_state().buffer.is_modified() {
                 // Buffer has unsaved changes - prompt for confirmation
                 let name = self.get_buffer_display_name(buffer_id);
@@ -1038,6 +1142,7 @@ impl Editor {
 
         if is_last_viewport {
             // Last viewport of this buffer - need to close buffer entirely
+            self.persist_scratch_buffer(buffer_id);
             if let Some(state) = self.buffers.get(&buffer_id) {
                 if state.buffer.is_modified() {
                     // Buffer has unsaved changes - prompt for confirmation
@@ -3,15 +3,56 @@
 //! This module contains clipboard operations and multi-cursor actions:
 //! - Copy/cut/paste operations
 //! - Copy with formatting (HTML with syntax highlighting)
+//! - Pasting from the clipboard history
 //! - Multi-cursor add above/below/at next match
 
 use crate::input::multi_cursor::{
     add_cursor_above, add_cursor_at_next_match, add_cursor_below, AddCursorResult,
 };
-use crate::model::event::{CursorId, Event};
+use crate::model::event::{
+    CursorId, Event, PopupContentData, PopupData, PopupListItemData, PopupPositionData,
+};
+use crate::primitives::indent::reindent_pasted_text;
 
 use super::Editor;
 
+/// Title of the clipboard history popup, which tells its confirmations apart
+pub(super) const CLIPBOARD_HISTORY_TITLE: &str = "Clipboard History";
+
+/// Title of the clipboard history popup that pastes entries as they were copied
+pub(super) const CLIPBOARD_HISTORY_PLAIN_TITLE: &str = "Clipboard History (plain text)";
+
+/// Longest preview of an entry shown in the clipboard history popup
+const HISTORY_PREVIEW_CHARS: usize = 40;
+
+/// The popup line for a clipboard history entry: its first non-blank line,
+/// shortened, with how much text the entry holds
+fn history_item(index: usize, text: &str) -> PopupListItemData {
+    let first_line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    let mut preview: String = first_line.chars().take(HISTORY_PREVIEW_CHARS).collect();
+    if first_line.chars().count() > HISTORY_PREVIEW_CHARS {
+        preview.push('…');
+    }
+
+    let line_count = text.lines().count();
+    let detail = if line_count > 1 {
+        format!("{} lines", line_count)
+    } else {
+        format!("{} chars", text.chars().count())
+    };
+
+    PopupListItemData {
+        text: preview,
+        detail: Some(detail),
+        icon: None,
+        data: Some(index.to_string()),
+    }
+}
+
 // These are the clipboard and multi-cursor operations on Editor.
 //
 // MOTIVATION FOR SEPARATION:
@@ -375,6 +416,70 @@ impl Editor {
         self.status_message = Some("Pasted".to_string());
     }
 
+    /// Show the clipboard history, most recent first, to pick an entry to paste
+    ///
+    /// With `plain`, the picked entry is pasted exactly as it was copied;
+    /// otherwise multi-line entries are re-indented to the cursor's line when
+    /// auto-indent is on.
+    pub fn show_clipboard_history(&mut self, plain: bool) {
+        // Pick up text copied in other applications since the last paste
+        let _ = self.clipboard.paste();
+
+        let items: Vec<PopupListItemData> = self
+            .clipboard
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(index, text)| history_item(index, text))
+            .collect();
+        if items.is_empty() {
+            self.set_status_message("Clipboard history is empty".to_string());
+            return;
+        }
+
+        let title = if plain {
+            CLIPBOARD_HISTORY_PLAIN_TITLE
+        } else {
+            CLIPBOARD_HISTORY_TITLE
+        };
+        let height = items.len() as u16 + 2;
+        self.show_popup(PopupData {
+            title: Some(title.to_string()),
+            transient: false,
+            content: PopupContentData::List { items, selected: 0 },
+            position: PopupPositionData::BelowCursor,
+            width: 60,
+            max_height: height.min(12),
+            bordered: true,
+        });
+    }
+
+    /// Paste the clipboard history entry at `index`
+    pub(super) fn paste_from_history(&mut self, index: usize, plain: bool) {
+        let Some(text) = self.clipboard.history().get(index).cloned() else {
+            return;
+        };
+        if plain || !self.config.editor.auto_indent {
+            self.paste_text(text);
+            return;
+        }
+
+        let indent: String = {
+            let state = self.active_state_mut();
+            let position = state.cursors.primary().position;
+            let mut iter = state.buffer.line_iterator(position, 80);
+            iter.next()
+                .map(|(_, line)| {
+                    line.chars()
+                        .take_while(|c| *c == ' ' || *c == '\t')
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+        self.paste_text(reindent_pasted_text(&normalized, &indent));
+    }
+
     /// Set clipboard content for testing purposes
     /// This sets the internal clipboard and enables internal-only mode to avoid
     /// system clipboard interference between parallel tests
@@ -0,0 +1,512 @@
+//! Editing a buffer together with other editors (experimental).
+//!
+//! This module provides functionality to:
+//! - Share the active buffer on an address ("Collab: Share Buffer..."), join
+//!   a buffer shared by another editor with the address and session token
+//!   the host shows ("Collab: Join...") and leave ("Collab: Stop")
+//! - Send the edits of the shared buffer, read from its change log, to the
+//!   other editors as CRDT operations, and apply the operations they send
+//! - Show the cursors of the other editors in distinct colors
+//!
+//! The document model is `model::crdt`; the connections are
+//! `services::collab`.
+
+use super::Editor;
+use crate::model::crdt::{CrdtOp, CrdtText, ElementId, TextEdit};
+use crate::model::event::{BufferId, CursorId, Event, OverlayFace};
+use crate::services::collab::{
+    new_session_token, CollabConnection, CollabEvent, CollabMessage, PeerId, DEFAULT_ADDRESS,
+    HOST_SITE,
+};
+use crate::view::overlay::OverlayNamespace;
+use crate::view::prompt::{Prompt, PromptType};
+use std::collections::HashMap;
+
+/// Largest buffer that can be shared; the document keeps an element per byte
+const MAX_SHARED_BYTES: usize = 1024 * 1024;
+
+/// Overlay namespace of the other editors' cursors
+const CURSOR_NAMESPACE: &str = "collab-cursors";
+
+/// Cursor colors, picked by site
+const CURSOR_COLORS: [(u8, u8, u8); 6] = [
+    (220, 80, 80),
+    (80, 160, 220),
+    (90, 190, 90),
+    (210, 160, 60),
+    (170, 100, 210),
+    (60, 190, 180),
+];
+
+/// A hosted or joined session
+pub(crate) struct CollabSession {
+    connection: CollabConnection,
+    hosting: bool,
+    /// Site of each guest, on the host
+    peer_sites: HashMap<PeerId, u64>,
+    /// Site the host gives the next guest
+    next_site: u64,
+    /// The shared buffer; a guest has none until the host's welcome
+    shared: Option<SharedBuffer>,
+    /// Cursor of each other site, after the element it follows
+    remote_cursors: HashMap<u64, Option<ElementId>>,
+}
+
+struct SharedBuffer {
+    buffer_id: BufferId,
+    doc: CrdtText,
+    /// Name sent to guests
+    name: String,
+    /// Buffer version the document matches
+    synced_version: u64,
+    /// Cursor position last sent to the others
+    sent_cursor: Option<usize>,
+}
+
+impl Editor {
+    /// Prompt for the address to share the active buffer on
+    pub fn start_collab_share_prompt(&mut self) {
+        if self.collab.is_some() {
+            self.set_status_message("Already in a shared session".to_string());
+            return;
+        }
+        self.prompt = Some(Prompt::with_initial_text(
+            "Share buffer on: ".to_string(),
+            PromptType::CollabShare,
+            DEFAULT_ADDRESS.to_string(),
+        ));
+    }
+
+    /// Prompt for the address and session token of a shared buffer to join
+    pub fn start_collab_join_prompt(&mut self) {
+        if self.collab.is_some() {
+            self.set_status_message("Already in a shared session".to_string());
+            return;
+        }
+        self.prompt = Some(Prompt::with_initial_text(
+            "Join shared buffer at (address token): ".to_string(),
+            PromptType::CollabJoin,
+            format!("{} ", DEFAULT_ADDRESS),
+        ));
+    }
+
+    /// Share the active buffer, accepting guests on `address`
+    pub(super) fn collab_share(&mut self, address: &str) {
+        let address = address.trim();
+        if address.is_empty() {
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        if state.buffer.len() > MAX_SHARED_BYTES {
+            self.set_status_message(format!(
+                "Only buffers up to {} KB can be shared",
+                MAX_SHARED_BYTES / 1024
+            ));
+            return;
+        }
+        let Some(text) = state.buffer.to_string() else {
+            self.set_status_message("Buffer is not fully loaded".to_string());
+            return;
+        };
+        let version = state.buffer.version();
+        let name = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|metadata| metadata.display_name.clone())
+            .unwrap_or_else(|| "shared".to_string());
+
+        let token = match new_session_token() {
+            Ok(token) => token,
+            Err(e) => {
+                self.set_status_message(format!("Cannot create a session token: {}", e));
+                return;
+            }
+        };
+        let connection = match CollabConnection::host(address, &token, sender) {
+            Ok(connection) => connection,
+            Err(e) => {
+                self.set_status_message(format!("Cannot share on {}: {}", address, e));
+                return;
+            }
+        };
+        let shown_address = connection
+            .local_addr()
+            .map_or_else(|| address.to_string(), |addr| addr.to_string());
+        self.collab = Some(CollabSession {
+            connection,
+            hosting: true,
+            peer_sites: HashMap::new(),
+            next_site: HOST_SITE + 1,
+            shared: Some(SharedBuffer {
+                buffer_id,
+                doc: CrdtText::new(HOST_SITE, text.as_bytes()),
+                name: name.clone(),
+                synced_version: version,
+                sent_cursor: None,
+            }),
+            remote_cursors: HashMap::new(),
+        });
+        self.set_status_message(format!(
+            "Sharing {}; join with: {} {}",
+            name, shown_address, token
+        ));
+    }
+
+    /// Join the buffer shared as `input` ("ADDRESS TOKEN"). It opens once the
+    /// host sends it.
+    pub(super) fn collab_join(&mut self, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            return;
+        }
+        let Some((address, token)) = input.split_once(char::is_whitespace) else {
+            self.set_status_message(
+                "Enter the address and the session token the host shows".to_string(),
+            );
+            return;
+        };
+        let token = token.trim();
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+        match CollabConnection::join(address, token, sender) {
+            Ok(connection) => {
+                self.collab = Some(CollabSession {
+                    connection,
+                    hosting: false,
+                    peer_sites: HashMap::new(),
+                    next_site: 0,
+                    shared: None,
+                    remote_cursors: HashMap::new(),
+                });
+                self.set_status_message(format!("Joining {}...", address));
+            }
+            Err(e) => self.set_status_message(format!("Cannot join {}: {}", address, e)),
+        }
+    }
+
+    /// Leave the session. The buffer stays open as an ordinary buffer.
+    pub fn stop_collab(&mut self) {
+        if self.collab.is_none() {
+            self.set_status_message("No shared session".to_string());
+            return;
+        }
+        self.end_collab("Stopped sharing");
+    }
+
+    fn end_collab(&mut self, message: &str) {
+        if let Some(session) = self.collab.take() {
+            if let Some(shared) = &session.shared {
+                if let Some(state) = self.buffers.get_mut(&shared.buffer_id) {
+                    state.overlays.clear_namespace(
+                        &OverlayNamespace::from_string(CURSOR_NAMESPACE.to_string()),
+                        &mut state.marker_list,
+                    );
+                }
+            }
+        }
+        self.set_status_message(message.to_string());
+    }
+
+    /// End the session when its buffer is closed
+    pub(super) fn forget_collab_buffer(&mut self, buffer_id: BufferId) {
+        let shared_buffer = self
+            .collab
+            .as_ref()
+            .and_then(|session| session.shared.as_ref())
+            .map(|shared| shared.buffer_id);
+        if shared_buffer == Some(buffer_id) {
+            self.collab = None;
+        }
+    }
+
+    /// Send the edits and cursor moves made in the shared buffer since the
+    /// last call
+    pub(super) fn sync_collab_buffer(&mut self) {
+        let Some(session) = self.collab.as_mut() else {
+            return;
+        };
+        let Some(shared) = session.shared.as_mut() else {
+            return;
+        };
+        let Some(state) = self.buffers.get(&shared.buffer_id) else {
+            return;
+        };
+        let site = shared.doc.site();
+
+        let version = state.buffer.version();
+        if version != shared.synced_version {
+            let Some(changes) = state.buffer.changes_since(shared.synced_version) else {
+                // Reloaded from disk or too many edits at once: the others
+                // can no longer be told what changed
+                self.end_collab("Shared buffer changed too much to sync; session ended");
+                return;
+            };
+            let mut ops: Vec<CrdtOp> = Vec::new();
+            for change in changes {
+                ops.extend(shared.doc.local_delete(change.removed.clone()));
+                ops.extend(
+                    shared
+                        .doc
+                        .local_insert(change.removed.start, &change.inserted),
+                );
+            }
+            shared.synced_version = version;
+            if !ops.is_empty() {
+                session
+                    .connection
+                    .broadcast(&CollabMessage::Ops { site, ops }, None);
+            }
+        }
+
+        let position = state.cursors.primary().position;
+        if shared.sent_cursor != Some(position) {
+            shared.sent_cursor = Some(position);
+            let after = shared.doc.id_before(position);
+            session
+                .connection
+                .broadcast(&CollabMessage::Cursor { site, after }, None);
+        }
+    }
+
+    /// Handle a connection change or a message from another editor
+    pub(super) fn handle_collab_event(&mut self, event: CollabEvent) {
+        if self.collab.is_none() {
+            return;
+        }
+        match event {
+            CollabEvent::Connected(peer) => self.handle_collab_connected(peer),
+            CollabEvent::Message(peer, message) => self.handle_collab_message(peer, message),
+            CollabEvent::Disconnected(peer) => {
+                let Some(session) = self.collab.as_mut() else {
+                    return;
+                };
+                if !session.hosting {
+                    self.end_collab("The host ended the shared session");
+                    return;
+                }
+                if let Some(site) = session.peer_sites.remove(&peer) {
+                    session.remote_cursors.remove(&site);
+                    session
+                        .connection
+                        .broadcast(&CollabMessage::Left { site }, None);
+                }
+                self.refresh_collab_cursors();
+                self.set_status_message("A guest left the shared buffer".to_string());
+            }
+        }
+    }
+
+    /// Welcome a new guest with its site and the document
+    fn handle_collab_connected(&mut self, peer: PeerId) {
+        // Bring the document up to date before sending it
+        self.sync_collab_buffer();
+        let Some(session) = self.collab.as_mut() else {
+            return;
+        };
+        if !session.hosting {
+            return;
+        }
+        let Some(shared) = session.shared.as_ref() else {
+            return;
+        };
+
+        let site = session.next_site;
+        session.next_site += 1;
+        session.peer_sites.insert(peer, site);
+        session.connection.send(
+            peer,
+            &CollabMessage::Welcome {
+                site,
+                name: shared.name.clone(),
+                runs: shared.doc.runs(),
+            },
+        );
+        // Where everyone else's cursor is
+        let host_cursor = shared.sent_cursor.and_then(|pos| shared.doc.id_before(pos));
+        let cursors = std::iter::once((HOST_SITE, host_cursor))
+            .chain(session.remote_cursors.iter().map(|(&s, &a)| (s, a)));
+        for (site, after) in cursors {
+            session
+                .connection
+                .send(peer, &CollabMessage::Cursor { site, after });
+        }
+        self.set_status_message("A guest joined the shared buffer".to_string());
+    }
+
+    fn handle_collab_message(&mut self, peer: PeerId, message: CollabMessage) {
+        let Some(session) = self.collab.as_mut() else {
+            return;
+        };
+        let message = if session.hosting {
+            // A guest only speaks for the site it was given
+            let Some(&site) = session.peer_sites.get(&peer) else {
+                return;
+            };
+            let message = match message {
+                CollabMessage::Ops { ops, .. } => {
+                    let foreign = ops
+                        .iter()
+                        .any(|op| matches!(op, CrdtOp::Insert { id, .. } if id.site != site));
+                    if foreign {
+                        tracing::warn!("Collab: guest {} inserted text of another site", peer);
+                        return;
+                    }
+                    CollabMessage::Ops { site, ops }
+                }
+                CollabMessage::Cursor { after, .. } => CollabMessage::Cursor { site, after },
+                // Only the host welcomes guests and announces who left
+                CollabMessage::Welcome { .. } | CollabMessage::Left { .. } => return,
+            };
+            // The host passes everything from one guest on to the others
+            session.connection.broadcast(&message, Some(peer));
+            message
+        } else {
+            message
+        };
+        self.apply_collab_message(message);
+    }
+
+    /// Apply a message from the host, or from a guest after checking it
+    fn apply_collab_message(&mut self, message: CollabMessage) {
+        let Some(session) = self.collab.as_mut() else {
+            return;
+        };
+
+        match message {
+            CollabMessage::Welcome { site, name, runs } => {
+                if session.hosting || session.shared.is_some() {
+                    return;
+                }
+                let doc = CrdtText::from_runs(site, &runs);
+                let text = String::from_utf8_lossy(&doc.text()).into_owned();
+                let buffer_id =
+                    self.create_virtual_buffer(format!("{} (shared)", name), String::new(), false);
+                let mut version = 0;
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.set_language_from_name(&name, &self.grammar_registry);
+                    state.buffer.insert(0, &text);
+                    state.buffer.clear_modified();
+                    version = state.buffer.version();
+                }
+                if let Some(session) = self.collab.as_mut() {
+                    session.shared = Some(SharedBuffer {
+                        buffer_id,
+                        doc,
+                        name: name.clone(),
+                        synced_version: version,
+                        sent_cursor: None,
+                    });
+                }
+                self.set_active_buffer(buffer_id);
+                self.set_status_message(format!("Joined shared buffer {}", name));
+            }
+            CollabMessage::Ops { ops, .. } => {
+                // Local edits not sent yet must be in the document first
+                self.sync_collab_buffer();
+                let Some(shared) = self
+                    .collab
+                    .as_mut()
+                    .and_then(|session| session.shared.as_mut())
+                else {
+                    return;
+                };
+                let buffer_id = shared.buffer_id;
+                let edits: Vec<TextEdit> = ops.iter().flat_map(|op| shared.doc.apply(op)).collect();
+                self.apply_collab_edits(buffer_id, edits);
+                self.refresh_collab_cursors();
+            }
+            CollabMessage::Cursor { site, after } => {
+                session.remote_cursors.insert(site, after);
+                self.refresh_collab_cursors();
+            }
+            CollabMessage::Left { site } => {
+                session.remote_cursors.remove(&site);
+                self.refresh_collab_cursors();
+            }
+        }
+    }
+
+    /// Apply edits from another editor to the shared buffer, without
+    /// moving the local cursors except to keep them on the same text
+    fn apply_collab_edits(&mut self, buffer_id: BufferId, edits: Vec<TextEdit>) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        for edit in edits {
+            let mut events = Vec::new();
+            if !edit.removed.is_empty() {
+                let deleted_text = state.get_text_range(edit.removed.start, edit.removed.end);
+                events.push(Event::Delete {
+                    range: edit.removed.clone(),
+                    deleted_text,
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            if !edit.inserted.is_empty() {
+                events.push(Event::Insert {
+                    position: edit.removed.start,
+                    text: String::from_utf8_lossy(&edit.inserted).into_owned(),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            for event in events {
+                state.apply(&event);
+                if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+                    log.append(event);
+                }
+            }
+        }
+
+        // These edits are in the document already
+        let version = state.buffer.version();
+        if let Some(shared) = self
+            .collab
+            .as_mut()
+            .and_then(|session| session.shared.as_mut())
+        {
+            shared.synced_version = version;
+        }
+    }
+
+    /// Redraw the other editors' cursors in the shared buffer
+    fn refresh_collab_cursors(&mut self) {
+        let Some(session) = self.collab.as_ref() else {
+            return;
+        };
+        let Some(shared) = session.shared.as_ref() else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&shared.buffer_id) else {
+            return;
+        };
+        let namespace = OverlayNamespace::from_string(CURSOR_NAMESPACE.to_string());
+        state
+            .overlays
+            .clear_namespace(&namespace, &mut state.marker_list);
+
+        let mut cursors: Vec<_> = session.remote_cursors.iter().collect();
+        cursors.sort();
+        for (&site, &after) in cursors {
+            let offset = shared.doc.offset_after(after);
+            if offset >= state.buffer.len() {
+                continue;
+            }
+            let end = state.buffer.next_char_boundary(offset).max(offset + 1);
+            let color = CURSOR_COLORS[(site as usize) % CURSOR_COLORS.len()];
+            state.apply(&Event::AddOverlay {
+                namespace: Some(namespace.clone()),
+                range: offset..end,
+                face: OverlayFace::Background { color },
+                priority: crate::view::overlay::priority::REMOTE_CURSOR,
+                message: None,
+            });
+        }
+    }
+}
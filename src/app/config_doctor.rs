@@ -0,0 +1,284 @@
+//! Checking the configuration files for mistakes.
+//!
+//! This module provides functionality to:
+//! - Parse each config file that applies to the working directory and each
+//!   user theme, reporting syntax and type errors with their line and column
+//! - Report settings that parse but don't take effect: an unknown theme or
+//!   keymap, and keybindings with an unknown action, key or context
+//! - Show the results in a read-only buffer ("Config Doctor")
+//!
+//! A config file that doesn't parse is skipped at startup for the next one
+//! (or the defaults), so its mistakes otherwise go unnoticed. When they make
+//! the editor unusable, `--safe-mode` starts without the user's keybindings,
+//! theme and plugins so this can be run.
+
+use std::path::Path;
+
+use super::Editor;
+use crate::config::{Config, KeybindingMapName};
+use crate::input::keybindings::KeybindingResolver;
+use crate::view::theme::Theme;
+
+/// How bad a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    /// The file is not used
+    Error,
+    /// A setting in it is ignored or falls back to a default
+    Warning,
+}
+
+/// A mistake found in a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Finding {
+    pub severity: Severity,
+    /// Line (1-based), when known
+    pub line: Option<usize>,
+    /// Column (1-based), when known
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Finding {
+    fn warning(line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            line,
+            column: None,
+            message,
+        }
+    }
+
+    /// `path:line:col: severity: message`, as compilers print them
+    fn format(&self, path: &Path) -> String {
+        let mut location = path.display().to_string();
+        if let Some(line) = self.line {
+            location.push_str(&format!(":{}", line));
+            if let Some(column) = self.column {
+                location.push_str(&format!(":{}", column));
+            }
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        format!("{}: {}: {}", location, severity, self.message)
+    }
+}
+
+/// A parse error, with serde's "at line L column C" moved to the location
+fn json_error(error: &serde_json::Error) -> Finding {
+    let message = error.to_string();
+    let message = match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    };
+    Finding {
+        severity: Severity::Error,
+        line: (error.line() > 0).then_some(error.line()),
+        column: (error.column() > 0).then_some(error.column()),
+        message,
+    }
+}
+
+/// Line of the first occurrence of the JSON string `value` in `text`
+fn line_of(text: &str, value: &str) -> Option<usize> {
+    let needle = serde_json::to_string(value).ok()?;
+    text.lines()
+        .position(|line| line.contains(&needle))
+        .map(|index| index + 1)
+}
+
+/// Checks the text of one kind of file
+type Check = fn(&str) -> Vec<Finding>;
+
+/// Mistakes in the config file `text`
+pub(crate) fn check_config(text: &str) -> Vec<Finding> {
+    let config: Config = match serde_json::from_str(text) {
+        Ok(config) => config,
+        Err(e) => return vec![json_error(&e)],
+    };
+    let mut findings = Vec::new();
+
+    if !Theme::exists(&config.theme) {
+        findings.push(Finding::warning(
+            line_of(text, &config.theme),
+            format!(
+                "unknown theme \"{}\" (the dark theme is used)",
+                config.theme.0
+            ),
+        ));
+    }
+
+    let keymap = &config.active_keybinding_map;
+    if !config.keybinding_maps.contains_key(&keymap.0)
+        && !KeybindingMapName::BUILTIN_OPTIONS.contains(&keymap.0.as_str())
+    {
+        findings.push(Finding::warning(
+            line_of(text, keymap),
+            format!("unknown keymap \"{}\" (no default keybindings)", keymap.0),
+        ));
+    }
+    for (name, map) in &config.keybinding_maps {
+        if let Some(parent) = &map.inherits {
+            if !config.keybinding_maps.contains_key(parent)
+                && !KeybindingMapName::BUILTIN_OPTIONS.contains(&parent.as_str())
+            {
+                findings.push(Finding::warning(
+                    line_of(text, parent),
+                    format!("keymap \"{}\" inherits unknown keymap \"{}\"", name, parent),
+                ));
+            }
+        }
+    }
+
+    let bindings = config.keybindings.iter().chain(
+        config
+            .keybinding_maps
+            .values()
+            .flat_map(|map| &map.bindings),
+    );
+    for binding in bindings {
+        if let Err(problem) = KeybindingResolver::check_binding(binding) {
+            findings.push(Finding::warning(
+                line_of(text, &binding.action),
+                format!("keybinding: {}", problem),
+            ));
+        }
+    }
+
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+/// Mistakes in the theme file `text`
+pub(crate) fn check_theme(text: &str) -> Vec<Finding> {
+    match Theme::check_json(text) {
+        Ok(()) => Vec::new(),
+        Err(e) => vec![json_error(&e)],
+    }
+}
+
+impl Editor {
+    /// Check the config and theme files and list the mistakes in a
+    /// read-only buffer
+    pub(super) fn show_config_doctor(&mut self) {
+        let mut files: Vec<(std::path::PathBuf, Check)> =
+            Config::config_search_paths(&self.working_dir)
+                .into_iter()
+                .map(|path| (path, check_config as Check))
+                .collect();
+        if let Some(dir) = Theme::user_themes_dir() {
+            let mut themes: Vec<_> = std::fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            themes.sort();
+            files.extend(themes.into_iter().map(|path| (path, check_theme as _)));
+        }
+
+        let mut content = String::from("// Config doctor\n\n");
+        let (mut errors, mut warnings) = (0, 0);
+        if files.is_empty() {
+            content.push_str("No config or theme files; the defaults are used\n");
+        }
+        for (path, check) in &files {
+            let findings = match std::fs::read_to_string(path) {
+                Ok(text) => check(&text),
+                Err(e) => vec![Finding {
+                    severity: Severity::Error,
+                    line: None,
+                    column: None,
+                    message: format!("can't be read: {}", e),
+                }],
+            };
+            if findings.is_empty() {
+                content.push_str(&format!("{}: ok\n", path.display()));
+            }
+            for finding in &findings {
+                match finding.severity {
+                    Severity::Error => errors += 1,
+                    Severity::Warning => warnings += 1,
+                }
+                content.push_str(&finding.format(path));
+                content.push('\n');
+            }
+        }
+        content.push_str(&format!(
+            "\n{} files checked. Errors: {}, warnings: {}\n",
+            files.len(),
+            errors,
+            warnings
+        ));
+        if errors > 0 {
+            content.push_str(
+                "A config file with errors is skipped for the next one, or the defaults\n",
+            );
+        }
+
+        self.open_read_only_buffer("*Config Doctor*".to_string(), "config-doctor", &content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_error_has_location() {
+        let findings = check_config("{\n  \"theme\": \"dark\"\n  \"editor\": {}\n}");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!((findings[0].line, findings[0].column), (Some(3), Some(3)));
+        assert!(!findings[0].message.contains("at line"));
+        assert!(findings[0]
+            .format(Path::new("/c.json"))
+            .starts_with("/c.json:3:3: error: "));
+
+        // Wrong types are errors too
+        let findings = check_config("{\n  \"editor\": {\"tab_size\": \"four\"}\n}");
+        assert_eq!(findings[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_ignored_settings_are_warnings() {
+        let text = r#"{
+  "theme": "no-such-theme",
+  "active_keybinding_map": "vim",
+  "keybindings": [
+    {"key": "s", "modifiers": ["ctrl"], "action": "save"},
+    {"key": "s", "modifiers": ["alt"], "action": "save_everything"},
+    {"key": "NoSuchKey", "action": "undo"}
+  ]
+}"#;
+        let findings = check_config(text);
+        let summary: Vec<(Option<usize>, &str)> = findings
+            .iter()
+            .map(|f| (f.line, f.message.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Some(2),
+                    "unknown theme \"no-such-theme\" (the dark theme is used)"
+                ),
+                (Some(3), "unknown keymap \"vim\" (no default keybindings)"),
+                (Some(6), "keybinding: unknown action \"save_everything\""),
+                (Some(7), "keybinding: unknown key \"NoSuchKey\""),
+            ]
+        );
+        assert!(findings.iter().all(|f| f.severity == Severity::Warning));
+
+        assert!(check_config("{}").is_empty());
+    }
+
+    #[test]
+    fn test_theme_errors() {
+        assert_eq!(check_theme("{").len(), 1);
+        assert_eq!(check_theme("[]")[0].severity, Severity::Error);
+    }
+}
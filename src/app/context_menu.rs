@@ -0,0 +1,447 @@
+//! Right-click context menus.
+//!
+//! This module provides functionality to:
+//! - Open a context menu on a tab, with Close, Close Others, Close to the
+//!   Right, Pin, Copy Path, Reveal in File Explorer and Split Right
+//! - Open a context menu in the text area, with Cut/Copy/Paste, Go to
+//!   Definition, Find References, Format Selection and plugin-added items
+//! - Route keys and mouse events to the open menu, and run the action picked
+//!
+//! The menu itself is the generic [`ContextMenuState`] control; each area
+//! that opens one adds a [`ContextMenuTarget`] and its actions here.
+
+use super::Editor;
+use crate::config::MenuItem;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::{BufferId, Event, SplitId};
+use crate::types::context_keys;
+use crate::view::controls::{
+    render_context_menu, ContextMenuColors, ContextMenuEvent, ContextMenuItem, ContextMenuLayout,
+    ContextMenuState,
+};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+
+/// What a context menu was opened on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContextMenuTarget {
+    /// A tab of a split
+    Tab {
+        split_id: SplitId,
+        buffer_id: BufferId,
+    },
+    /// The text area of the focused split
+    Editor,
+}
+
+/// Actions offered by context menus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ContextMenuAction {
+    CloseTab,
+    CloseOtherTabs,
+    CloseTabsToRight,
+    TogglePinTab,
+    CopyPath,
+    RevealInFileExplorer,
+    SplitRight,
+    /// Run an editor action, as if its key binding was pressed
+    Run(Action),
+}
+
+/// An open context menu
+pub(crate) struct ContextMenu {
+    target: ContextMenuTarget,
+    state: ContextMenuState<ContextMenuAction>,
+    /// Menu as last rendered, for hit testing
+    layout: ContextMenuLayout,
+}
+
+impl Editor {
+    /// Open the context menu of the tab under a right click. Returns true if
+    /// the click was on a tab.
+    pub(super) fn handle_tab_right_click(&mut self, col: u16, row: u16) -> bool {
+        let Some((split_id, buffer_id)) = self.cached_layout.tab_areas.iter().find_map(
+            |(split_id, buffer_id, tab_row, start_col, end_col, _)| {
+                (row == *tab_row && col >= *start_col && col < *end_col)
+                    .then_some((*split_id, *buffer_id))
+            },
+        ) else {
+            return false;
+        };
+
+        let tabs = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.open_buffers.clone())
+            .unwrap_or_default();
+        let pinned = self
+            .split_view_states
+            .get(&split_id)
+            .is_some_and(|vs| vs.is_pinned(buffer_id));
+        let position = tabs.iter().position(|&id| id == buffer_id).unwrap_or(0);
+        let has_path = self.buffer_file_path(buffer_id).is_some();
+        let shortcut = |action: &str| self.context_menu_shortcut(action);
+
+        let items = vec![
+            ContextMenuItem::action("Close", ContextMenuAction::CloseTab)
+                .with_shortcut(shortcut("close_tab")),
+            ContextMenuItem::action("Close Others", ContextMenuAction::CloseOtherTabs)
+                .with_enabled(tabs.len() > 1),
+            ContextMenuItem::action("Close to the Right", ContextMenuAction::CloseTabsToRight)
+                .with_enabled(position + 1 < tabs.len()),
+            ContextMenuItem::Separator,
+            ContextMenuItem::action(
+                if pinned { "Unpin" } else { "Pin" },
+                ContextMenuAction::TogglePinTab,
+            ),
+            ContextMenuItem::Separator,
+            ContextMenuItem::action("Copy Path", ContextMenuAction::CopyPath)
+                .with_enabled(has_path),
+            ContextMenuItem::action(
+                "Reveal in File Explorer",
+                ContextMenuAction::RevealInFileExplorer,
+            )
+            .with_enabled(has_path),
+            ContextMenuItem::Separator,
+            ContextMenuItem::action("Split Right", ContextMenuAction::SplitRight)
+                .with_shortcut(shortcut("split_vertical")),
+        ];
+
+        self.tab_overflow_menu = None;
+        self.context_menu = Some(ContextMenu {
+            target: ContextMenuTarget::Tab {
+                split_id,
+                buffer_id,
+            },
+            state: ContextMenuState::new(items, (col, row.saturating_add(1))),
+            layout: ContextMenuLayout::default(),
+        });
+        true
+    }
+
+    /// Open the editor context menu under a right click in a text area. The
+    /// cursor moves to the click unless it lands inside the selection, so
+    /// the menu acts on what was clicked. Returns true if the click was in a
+    /// text area.
+    pub(super) fn handle_editor_right_click(&mut self, col: u16, row: u16) -> bool {
+        let Some((split_id, buffer_id, content_rect)) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, content_rect, _, _, _)| {
+                (*split_id, *buffer_id, *content_rect)
+            })
+        else {
+            return false;
+        };
+        if self.is_terminal_buffer(buffer_id) {
+            return false;
+        }
+
+        let cached_mappings = self
+            .cached_layout
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let fallback = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.top_byte)
+            .unwrap_or(0);
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        let gutter_width = state.margins.left_total_width() as u16;
+        let Some(position) = Self::screen_to_buffer_position(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &cached_mappings,
+            fallback,
+            false,
+        ) else {
+            return false;
+        };
+
+        self.focus_split(split_id, buffer_id);
+        self.key_context = KeyContext::Normal;
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let cursor = state.cursors.primary();
+            let in_selection = cursor
+                .selection_range()
+                .is_some_and(|range| range.contains(&position));
+            if !in_selection {
+                let event = Event::MoveCursor {
+                    cursor_id: state.cursors.primary_id(),
+                    old_position: cursor.position,
+                    new_position: position,
+                    old_anchor: cursor.anchor,
+                    new_anchor: None,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0,
+                };
+                if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+                    event_log.append(event.clone());
+                }
+                state.apply(&event);
+            }
+        }
+
+        let has_selection = self.has_active_selection();
+        let editable = !self.is_editing_disabled();
+        let lsp_ready = self.active_buffer_lsp_ready();
+        // Plugin items are enabled by the same context keys as menu bar items
+        self.menu_state
+            .context
+            .set(context_keys::HAS_SELECTION, has_selection)
+            .set(context_keys::LSP_AVAILABLE, lsp_ready);
+
+        let item = |label: &str, name: &str, action: Action| {
+            ContextMenuItem::action(label, ContextMenuAction::Run(action))
+                .with_shortcut(self.context_menu_shortcut(name))
+        };
+        let mut items = vec![
+            item("Cut", "cut", Action::Cut).with_enabled(has_selection && editable),
+            item("Copy", "copy", Action::Copy).with_enabled(has_selection),
+            item("Paste", "paste", Action::Paste).with_enabled(editable),
+            ContextMenuItem::Separator,
+            item(
+                "Go to Definition",
+                "lsp_goto_definition",
+                Action::LspGotoDefinition,
+            )
+            .with_enabled(lsp_ready),
+            item("Find References", "lsp_references", Action::LspReferences)
+                .with_enabled(lsp_ready),
+            ContextMenuItem::Separator,
+            item(
+                "Format Selection",
+                "format_selection",
+                Action::FormatSelection,
+            )
+            .with_enabled(has_selection && editable && self.can_format_selection()),
+        ];
+        if !self.plugin_context_menu_items.is_empty() {
+            items.push(ContextMenuItem::Separator);
+        }
+        for plugin_item in &self.plugin_context_menu_items {
+            let MenuItem::Action {
+                label,
+                action,
+                args,
+                when,
+                ..
+            } = plugin_item
+            else {
+                continue;
+            };
+            let enabled = when
+                .as_deref()
+                .is_none_or(|key| self.menu_state.context.get(key));
+            let parsed = Action::from_str(action, args)
+                .unwrap_or_else(|| Action::PluginAction(action.clone()));
+            items.push(item(label.as_str(), action.as_str(), parsed).with_enabled(enabled));
+        }
+
+        self.tab_overflow_menu = None;
+        self.context_menu = Some(ContextMenu {
+            target: ContextMenuTarget::Editor,
+            state: ContextMenuState::new(items, (col, row)),
+            layout: ContextMenuLayout::default(),
+        });
+        true
+    }
+
+    /// Route a key press to the open context menu. Returns true if a menu was
+    /// open, in which case it consumes the key.
+    pub(super) fn handle_context_menu_key(&mut self, key: KeyEvent) -> bool {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return false;
+        };
+        match menu.state.handle_key(key) {
+            Some(ContextMenuEvent::Activated(action)) => self.run_context_menu_action(action),
+            Some(ContextMenuEvent::HighlightChanged(_)) => {}
+            // Escape, or any key the menu doesn't use, dismisses it
+            Some(ContextMenuEvent::Closed) | None => self.context_menu = None,
+        }
+        true
+    }
+
+    /// Route a mouse event to the open context menu. Returns true if the menu
+    /// consumed it.
+    pub(super) fn handle_context_menu_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return false;
+        };
+        let inside = menu.layout.contains(event.column, event.row);
+        match menu.state.handle_mouse(event, &menu.layout) {
+            Some(ContextMenuEvent::Activated(action)) => {
+                self.run_context_menu_action(action);
+                true
+            }
+            Some(ContextMenuEvent::Closed) => {
+                self.context_menu = None;
+                // A right click elsewhere goes on to open that spot's menu
+                event.kind != MouseEventKind::Down(MouseButton::Right)
+            }
+            Some(ContextMenuEvent::HighlightChanged(_)) | None => {
+                inside || matches!(event.kind, MouseEventKind::Up(_))
+            }
+        }
+    }
+
+    /// Close the open context menu and run the action picked from it
+    fn run_context_menu_action(&mut self, action: ContextMenuAction) {
+        let Some(menu) = self.context_menu.take() else {
+            return;
+        };
+        match menu.target {
+            ContextMenuTarget::Tab {
+                split_id,
+                buffer_id,
+            } => self.run_tab_action(split_id, buffer_id, action),
+            ContextMenuTarget::Editor => {
+                if let ContextMenuAction::Run(action) = action {
+                    if let Err(e) = self.handle_action(action) {
+                        tracing::warn!("Context menu action failed: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run a tab context menu action on `buffer_id`'s tab in `split_id`
+    fn run_tab_action(
+        &mut self,
+        split_id: SplitId,
+        buffer_id: BufferId,
+        action: ContextMenuAction,
+    ) {
+        if !self.buffers.contains_key(&buffer_id) {
+            return;
+        }
+        let tabs = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| (vs.open_buffers.clone(), vs.pinned_buffers.clone()));
+        let Some((tabs, pinned)) = tabs else {
+            return;
+        };
+
+        match action {
+            ContextMenuAction::CloseTab => {
+                self.close_tab_in_split(buffer_id, split_id);
+            }
+            ContextMenuAction::CloseOtherTabs | ContextMenuAction::CloseTabsToRight => {
+                self.focus_split(split_id, buffer_id);
+                let position = tabs.iter().position(|&id| id == buffer_id).unwrap_or(0);
+                let targets: Vec<BufferId> = tabs
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, id)| {
+                        *id != buffer_id
+                            && !pinned.contains(id)
+                            && (action == ContextMenuAction::CloseOtherTabs || i > position)
+                    })
+                    .map(|(_, &id)| id)
+                    .collect();
+                self.close_tabs_in_split(split_id, targets);
+            }
+            ContextMenuAction::TogglePinTab => {
+                let now_pinned = self
+                    .split_view_states
+                    .get_mut(&split_id)
+                    .is_some_and(|vs| vs.toggle_pin(buffer_id));
+                let name = self.get_buffer_display_name(buffer_id);
+                if now_pinned {
+                    self.set_status_message(format!("Pinned {}", name));
+                } else {
+                    self.set_status_message(format!("Unpinned {}", name));
+                }
+            }
+            ContextMenuAction::CopyPath => {
+                if let Some(path) = self.buffer_file_path(buffer_id) {
+                    let text = path.display().to_string();
+                    self.clipboard.copy(text.clone());
+                    self.set_status_message(format!("Copied {}", text));
+                }
+            }
+            ContextMenuAction::RevealInFileExplorer => {
+                self.focus_split(split_id, buffer_id);
+                self.focus_file_explorer();
+            }
+            ContextMenuAction::SplitRight => {
+                self.focus_split(split_id, buffer_id);
+                self.split_pane_vertical();
+            }
+            ContextMenuAction::Run(_) => {}
+        }
+    }
+
+    /// Close `targets` in a split, keeping the tabs that would need to ask
+    /// about unsaved changes
+    fn close_tabs_in_split(&mut self, split_id: SplitId, targets: Vec<BufferId>) {
+        let mut closed = 0;
+        let mut kept = 0;
+        for buffer_id in targets {
+            let shown_elsewhere = self
+                .split_view_states
+                .iter()
+                .any(|(&sid, vs)| sid != split_id && vs.has_buffer(buffer_id));
+            let modified = self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|state| state.buffer.is_modified());
+            if modified && !shown_elsewhere {
+                kept += 1;
+            } else if self.close_tab_in_split(buffer_id, split_id) {
+                closed += 1;
+            }
+        }
+        if kept > 0 {
+            self.set_status_message(format!(
+                "Closed {} tab(s); kept {} with unsaved changes",
+                closed, kept
+            ));
+        } else {
+            self.set_status_message(format!("Closed {} tab(s)", closed));
+        }
+    }
+
+    /// Key binding of an action as shown in a menu, or empty if it has none
+    fn context_menu_shortcut(&self, action: &str) -> String {
+        self.keybindings
+            .find_keybinding_for_action(action, KeyContext::Normal)
+            .unwrap_or_default()
+    }
+
+    /// Path of the file a buffer shows, if it has one
+    fn buffer_file_path(&self, buffer_id: BufferId) -> Option<std::path::PathBuf> {
+        self.buffers
+            .get(&buffer_id)?
+            .buffer
+            .file_path()
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(|path| path.to_path_buf())
+    }
+
+    /// Render the open context menu above everything but notifications
+    pub(super) fn render_context_menu(&mut self, frame: &mut Frame) {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        menu.layout = render_context_menu(
+            frame,
+            frame.area(),
+            &menu.state,
+            &ContextMenuColors::from_theme(&self.theme),
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Offering the report of a crash on the next start.
+//!
+//! This module provides functionality to:
+//! - Keep the list of open files the crash report names up to date
+//! - On start, find a report written when the previous run panicked
+//! - Offer to open it, and to restore the session, which isn't restored
+//!   automatically after a crash in case it is what crashed the editor
+//!
+//! The report itself is written by the panic hook, see
+//! `services::crash_report`.
+
+use std::path::PathBuf;
+
+use super::Editor;
+use crate::services::crash_report;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Give the crash report the files open now, when they changed
+    pub fn record_open_files_for_crash_report(&mut self) {
+        let mut files: Vec<PathBuf> = self
+            .buffer_metadata
+            .values()
+            .filter_map(|metadata| metadata.file_path().cloned())
+            .collect();
+        files.sort();
+        if files == self.crash_report_files {
+            return;
+        }
+        self.crash_report_files = files.clone();
+        crash_report::set_open_files(files);
+    }
+
+    /// The report of a crash of the previous run, if it wasn't offered yet
+    pub fn take_pending_crash_report(&self) -> Option<PathBuf> {
+        crash_report::take_pending_report(&crash_report::report_dir(&self.dir_context.state_dir))
+    }
+
+    /// Ask whether to open `report`, restoring the session too if
+    /// `restore_session`
+    pub fn offer_crash_report(&mut self, report: PathBuf, restore_session: bool) {
+        let message = if restore_session {
+            "Fresh crashed last time. Restore the session and open the crash report? (Y/n) "
+        } else {
+            "Fresh crashed last time. Open the crash report? (Y/n) "
+        };
+        self.start_prompt(
+            message.to_string(),
+            PromptType::ConfirmCrashReport {
+                report,
+                restore_session,
+            },
+        );
+    }
+
+    /// Handle the ConfirmCrashReport prompt
+    pub(super) fn handle_confirm_crash_report(
+        &mut self,
+        input: &str,
+        report: PathBuf,
+        restore_session: bool,
+    ) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower == "n" || input_lower == "no" {
+            self.set_status_message(format!("Crash report saved to {}", report.display()));
+            return;
+        }
+        if restore_session {
+            if let Err(e) = self.try_restore_session() {
+                tracing::warn!("Failed to restore session: {}", e);
+            }
+        }
+        if let Err(e) = self.open_file(&report) {
+            self.set_status_message(format!(
+                "Failed to open crash report {}: {}",
+                report.display(),
+                e
+            ));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Diff viewer.
+//!
+//! This module provides functionality to:
+//! - Compare two files and show the result as a unified diff in a read-only buffer
+//! - Show the same view for any two texts, e.g. a buffer and an earlier
+//!   version of it (see `app/local_history_actions.rs`)
+
+use super::types::{BufferKind, BufferMetadata};
+use super::Editor;
+use crate::model::event::{BufferId, EventLog};
+use crate::model::line_diff::{diff_line_sequence, DiffLine};
+use crate::state::EditorState;
+use crate::view::overlay::{Overlay, OverlayFace};
+use ratatui::style::Color;
+use std::io;
+use std::path::Path;
+
+impl Editor {
+    /// Open a read-only buffer showing the line differences from `old` to `new`
+    pub fn open_diff(&mut self, old: &Path, new: &Path) -> io::Result<BufferId> {
+        let old_text = std::fs::read(old)?;
+        let new_text = std::fs::read(new)?;
+
+        let file_name = |path: &Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        };
+        let (buffer_id, changes) = self.open_text_diff(
+            (&old.display().to_string(), &old_text),
+            (&new.display().to_string(), &new_text),
+            format!("*Diff: {} vs {}*", file_name(old), file_name(new)),
+        );
+        if changes == 0 {
+            self.set_status_message("Files are identical".to_string());
+        } else {
+            self.set_status_message(format!("{} changed line(s)", changes));
+        }
+        Ok(buffer_id)
+    }
+
+    /// Open a read-only diff buffer named `display_name` from the `old` to
+    /// the `new` text, each given with the label shown in the header.
+    /// Returns the buffer and the number of changed lines.
+    pub(super) fn open_text_diff(
+        &mut self,
+        old: (&str, &[u8]),
+        new: (&str, &[u8]),
+        display_name: String,
+    ) -> (BufferId, usize) {
+        let lines = diff_line_sequence(old.1, new.1);
+
+        let mut content = format!("--- {}\n+++ {}\n", old.0, new.0);
+        let mut colored = Vec::new();
+        let mut changes = 0;
+        for line in &lines {
+            let (prefix, text, color) = match line {
+                DiffLine::Unchanged(text) => (' ', text, None),
+                DiffLine::Removed(text) => ('-', text, Some(Color::Red)),
+                DiffLine::Added(text) => ('+', text, Some(Color::Green)),
+            };
+            let start = content.len();
+            content.push(prefix);
+            content.push_str(&String::from_utf8_lossy(text));
+            if let Some(color) = color {
+                colored.push((start..content.len(), color));
+                changes += 1;
+            }
+            content.push('\n');
+        }
+
+        let buffer_id = BufferId(self.next_buffer_id);
+        self.next_buffer_id += 1;
+
+        let mut state = EditorState::new(
+            self.terminal_width,
+            self.terminal_height,
+            self.config.editor.large_file_threshold_bytes as usize,
+        );
+        state.buffer.replace(crate::model::buffer::Buffer::from_str(
+            &content,
+            self.config.editor.large_file_threshold_bytes as usize,
+        ));
+        state.editing_disabled = true;
+        for (range, color) in colored {
+            let overlay = Overlay::new(
+                &mut state.marker_list,
+                range,
+                OverlayFace::Foreground { color },
+            );
+            state.overlays.add(overlay);
+        }
+
+        self.buffers.insert(buffer_id, state);
+        self.event_logs.insert(buffer_id, EventLog::new());
+
+        let metadata = BufferMetadata {
+            kind: BufferKind::Virtual {
+                mode: "diff".to_string(),
+            },
+            display_name,
+            lsp_enabled: false,
+            lsp_disabled_reason: Some("Virtual diff buffer".to_string()),
+            read_only: true,
+            binary: false,
+            lsp_opened_with: std::collections::HashSet::new(),
+        };
+        self.buffer_metadata.insert(buffer_id, metadata);
+
+        self.set_active_buffer(buffer_id);
+        (buffer_id, changes)
+    }
+}
@@ -0,0 +1,89 @@
+//! Buffer export.
+//!
+//! This module provides functionality to:
+//! - Write the active buffer, highlighted with the current theme, to a
+//!   standalone HTML page
+//! - Write it with ANSI color escapes, for `cat` or piping to a pager
+
+use super::normalize_path;
+use super::Editor;
+use crate::services::styled_html::{render_html_document, render_styled_ansi, ExportFormat};
+use crate::view::prompt::PromptType;
+use std::path::Path;
+
+impl Editor {
+    /// Prompt for the file to export the active buffer to as `format`
+    pub fn start_export_prompt(&mut self, format: ExportFormat) {
+        let name = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| {
+                p.strip_prefix(&self.working_dir)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .unwrap_or_else(|| "untitled".to_string());
+        self.start_prompt_with_initial_text(
+            format!("Export as {} to: ", format.display_name()),
+            PromptType::ExportBuffer { format },
+            format!("{}.{}", name, format.extension()),
+        );
+    }
+
+    /// Export the active buffer as `format` to the file at `input`
+    pub(super) fn export_buffer(&mut self, input: &str, format: ExportFormat) {
+        let input = input.trim();
+        if input.is_empty() {
+            self.set_status_message("Export cancelled".to_string());
+            return;
+        }
+        let input_path = Path::new(input);
+        let path = if input_path.is_absolute() {
+            normalize_path(input_path)
+        } else {
+            normalize_path(&self.working_dir.join(input_path))
+        };
+        if self.active_state().buffer.file_path() == Some(path.as_path()) {
+            self.set_status_message("Cannot export a buffer over its own file".to_string());
+            return;
+        }
+        if self.active_state().buffer.is_large_file() {
+            self.set_status_message("Export is not available for large files".to_string());
+            return;
+        }
+
+        let title = match self.active_state().buffer.file_path() {
+            Some(p) => p
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| p.display().to_string()),
+            None => self.get_buffer_display_name(self.active_buffer()),
+        };
+        let theme = self.theme.clone();
+        let (text, spans) = {
+            let state = self.active_state_mut();
+            let len = state.buffer.len();
+            let text = state.get_text_range(0, len);
+            let spans = state
+                .highlighter
+                .highlight_viewport(&state.buffer, 0, len, &theme, 0);
+            (text, spans)
+        };
+        let output = match format {
+            ExportFormat::Html => render_html_document(&title, &text, &spans, &theme),
+            ExportFormat::Ansi => render_styled_ansi(&text, &spans),
+        };
+        match std::fs::write(&path, output) {
+            Ok(()) => self.set_status_message(format!(
+                "Exported as {} to {}",
+                format.display_name(),
+                path.display()
+            )),
+            Err(e) => {
+                self.set_status_message(format!("Failed to export to {}: {}", path.display(), e))
+            }
+        }
+    }
+}
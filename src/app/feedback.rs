@@ -0,0 +1,83 @@
+//! Feedback for rejected input.
+//!
+//! Keys and actions that can't do anything, like moving left at the start
+//! of the buffer or an unknown key in a prompt, call [`Editor::reject`]. How
+//! that shows is set once, by `editor.error_feedback`:
+//! - `none`: nothing happens
+//! - `visual`: the split the key went to flashes briefly
+//! - `bell`: the event loop rings the terminal bell
+
+use std::time::{Duration, Instant};
+
+use super::Editor;
+use crate::config::ErrorFeedback;
+use crate::model::event::SplitId;
+use ratatui::style::{Modifier, Style};
+use ratatui::Frame;
+
+/// How long a visual bell flashes the split
+const FLASH_DURATION: Duration = Duration::from_millis(120);
+
+/// A split flashing after a rejected key
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VisualBell {
+    split_id: SplitId,
+    until: Instant,
+}
+
+impl Editor {
+    /// Signal that a key or action was rejected, as set by
+    /// `editor.error_feedback`
+    pub(crate) fn reject(&mut self) {
+        match self.config.editor.error_feedback {
+            ErrorFeedback::None => {}
+            ErrorFeedback::Visual => {
+                self.visual_bell = Some(VisualBell {
+                    split_id: self.split_manager.active_split(),
+                    until: self.time_source.now() + FLASH_DURATION,
+                });
+            }
+            ErrorFeedback::Bell => self.bell_pending = true,
+        }
+    }
+
+    /// Whether the terminal bell should ring, for the event loop to ring it
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// End a visual bell whose time is up. Returns true if one ended, so the
+    /// screen needs redrawing.
+    pub fn check_visual_bell(&mut self) -> bool {
+        let ended = self
+            .visual_bell
+            .is_some_and(|bell| self.time_source.now() >= bell.until);
+        if ended {
+            self.visual_bell = None;
+        }
+        ended
+    }
+
+    /// Whether a split is flashing after a rejected key
+    pub fn is_visual_bell_on(&self) -> bool {
+        self.visual_bell.is_some()
+    }
+
+    /// Invert the text area of the flashing split, if any
+    pub(super) fn render_visual_bell(&self, frame: &mut Frame) {
+        let Some(bell) = self.visual_bell else {
+            return;
+        };
+        let area = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(split_id, ..)| *split_id == bell.split_id)
+            .map(|(_, _, content_rect, ..)| *content_rect);
+        if let Some(area) = area {
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Opening files dropped on the terminal.
+//!
+//! This module provides functionality to:
+//! - Recognize a terminal paste that is only the paths of existing files, as
+//!   terminals paste them when files are dragged onto the window
+//! - Offer to open them in tabs instead of inserting the text
+//!
+//! Terminals quote dropped paths in different ways: wrapped in single or
+//! double quotes, with spaces escaped by backslashes, or as `file://` URIs.
+//! Several dropped files are separated by spaces or newlines. Only absolute
+//! paths count, so pasting a relative path that happens to exist (`mod.rs`)
+//! still inserts it.
+
+use std::path::{Path, PathBuf};
+
+use super::Editor;
+use crate::view::prompt::PromptType;
+
+/// Pastes longer than this are never taken for dropped files
+const MAX_DROP_LEN: usize = 16 * 1024;
+
+impl Editor {
+    /// Handle text pasted by the terminal (bracketed paste)
+    ///
+    /// Dropped files are offered to open; other text is pasted.
+    pub fn paste_from_terminal(&mut self, text: String) {
+        if self.prompt.is_none() && !self.terminal_mode {
+            if let Some(paths) = parse_dropped_paths(&text) {
+                let message = match paths.as_slice() {
+                    [path] => format!("Open dropped file '{}'? (Y/n) ", path.display()),
+                    _ => format!("Open {} dropped files? (Y/n) ", paths.len()),
+                };
+                self.start_prompt(message, PromptType::ConfirmOpenDroppedFiles { paths, text });
+                return;
+            }
+        }
+        self.paste_text(text);
+    }
+
+    /// Handle the ConfirmOpenDroppedFiles prompt: open the files, or paste
+    /// their paths after all
+    pub(super) fn handle_confirm_open_dropped_files(
+        &mut self,
+        input: &str,
+        paths: Vec<PathBuf>,
+        text: String,
+    ) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower == "n" || input_lower == "no" {
+            self.paste_text(text);
+            return;
+        }
+        let mut opened = 0;
+        for path in &paths {
+            match self.open_file(path) {
+                Ok(_) => opened += 1,
+                Err(e) => {
+                    self.set_status_message(format!("Failed to open {}: {}", path.display(), e));
+                    return;
+                }
+            }
+        }
+        if opened > 1 {
+            self.set_status_message(format!("Opened {} files", opened));
+        }
+    }
+}
+
+/// The files a paste names, if it is nothing but absolute paths of existing
+/// files
+pub(crate) fn parse_dropped_paths(text: &str) -> Option<Vec<PathBuf>> {
+    let text = text.trim();
+    if text.is_empty() || text.len() > MAX_DROP_LEN {
+        return None;
+    }
+    // A single unquoted path may contain spaces
+    let whole = Path::new(text);
+    if whole.is_absolute() && whole.is_file() {
+        return Some(vec![whole.to_path_buf()]);
+    }
+    split_words(text)?
+        .into_iter()
+        .map(|word| {
+            let path = if word.starts_with("file://") {
+                url::Url::parse(&word).ok()?.to_file_path().ok()?
+            } else {
+                PathBuf::from(word)
+            };
+            (path.is_absolute() && path.is_file()).then_some(path)
+        })
+        .collect()
+}
+
+/// Split `text` into words the way a shell would: on unquoted whitespace,
+/// removing quotes and (except on Windows, where it separates paths) the
+/// backslashes escaping a character; None if a quote isn't closed
+fn split_words(text: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' if !cfg!(windows) => match chars.next()? {
+                            c @ ('"' | '\\') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' if !cfg!(windows) => {
+                in_word = true;
+                word.push(chars.next()?);
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"'/a b/c' "/d \"e\"" /f\ g /h"#).unwrap(),
+            vec!["/a b/c", "/d \"e\"", "/f g", "/h"]
+        );
+        assert_eq!(split_words("/a\n/b\n").unwrap(), vec!["/a", "/b"]);
+        assert!(split_words("'/unterminated").is_none());
+    }
+
+    #[test]
+    fn test_parse_dropped_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let spaced = dir.path().join("my notes.txt");
+        let plain = dir.path().join("main.rs");
+        std::fs::write(&spaced, "").unwrap();
+        std::fs::write(&plain, "").unwrap();
+
+        let quoted = format!("'{}'", spaced.display());
+        assert_eq!(parse_dropped_paths(&quoted), Some(vec![spaced.clone()]));
+        let escaped = spaced.display().to_string().replace(' ', "\\ ");
+        let uri = url::Url::from_file_path(&plain).unwrap();
+        assert_eq!(
+            parse_dropped_paths(&format!("{} {}\n", escaped, uri)),
+            Some(vec![spaced.clone(), plain.clone()])
+        );
+        // Unquoted, as some terminals paste a single file
+        assert_eq!(
+            parse_dropped_paths(&spaced.display().to_string()),
+            Some(vec![spaced])
+        );
+
+        // Text, missing files, directories and relative paths are pasted
+        assert_eq!(parse_dropped_paths("hello world"), None);
+        let missing = dir.path().join("missing.txt");
+        let with_missing = format!("{} {}", plain.display(), missing.display());
+        assert_eq!(parse_dropped_paths(&with_missing), None);
+        assert_eq!(parse_dropped_paths(&dir.path().display().to_string()), None);
+        assert_eq!(parse_dropped_paths("main.rs"), None);
+    }
+}
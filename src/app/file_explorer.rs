@@ -442,7 +442,7 @@ impl Editor {
         if let Some(explorer) = &self.file_explorer {
             if let Some(selected_id) = explorer.get_selected() {
                 // Don't allow deleting the root directory
-                if selected_id == explorer.tree().root_id() {
+                if explorer.tree().is_root(selected_id) {
                     self.set_status_message("Cannot delete project root".to_string());
                     return;
                 }
@@ -497,7 +497,7 @@ impl Editor {
         if let Some(explorer) = &self.file_explorer {
             if let Some(selected_id) = explorer.get_selected() {
                 // Don't allow renaming the root directory
-                if selected_id == explorer.tree().root_id() {
+                if explorer.tree().is_root(selected_id) {
                     self.set_status_message("Cannot rename project root".to_string());
                     return;
                 }
@@ -0,0 +1,431 @@
+//! File management for the active buffer.
+//!
+//! This module provides functionality to:
+//! - Rename or move the buffer's file, keeping the buffer, session and LSP in step
+//! - Duplicate the file and open the copy
+//! - Move the file to the system trash and close its buffer
+//! - Undo the most recent of these operations
+
+use super::{normalize_path, BufferMetadata, Editor};
+use crate::model::event::BufferId;
+use crate::services::lsp::manager::detect_language;
+use crate::view::prompt::PromptType;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A file operation that can be undone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FileOperation {
+    /// A file was renamed or moved
+    Moved { from: PathBuf, to: PathBuf },
+    /// A copy of a file was created
+    Duplicated { copy: PathBuf },
+    /// A file was moved to the system trash
+    Trashed { path: PathBuf },
+}
+
+impl Editor {
+    /// Path of the active buffer's file, or a status message explaining why
+    /// it cannot be managed
+    fn managed_file_path(&mut self) -> Option<PathBuf> {
+        if self.ssh_buffers.contains_key(&self.active_buffer()) {
+            self.set_status_message("Remote files cannot be managed locally".to_string());
+            return None;
+        }
+        let path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf);
+        if path.is_none() {
+            self.set_status_message("Buffer has no file".to_string());
+        }
+        path
+    }
+
+    /// Display form of a path: relative to the working directory when inside it
+    fn relative_display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.working_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Resolve a path typed in a prompt against the working directory
+    fn resolve_prompt_path(&self, input: &str) -> PathBuf {
+        let input_path = Path::new(input.trim());
+        if input_path.is_absolute() {
+            normalize_path(input_path)
+        } else {
+            normalize_path(&self.working_dir.join(input_path))
+        }
+    }
+
+    /// Start the prompt for renaming or moving the active buffer's file
+    pub fn rename_file_prompt(&mut self) {
+        let Some(path) = self.managed_file_path() else {
+            return;
+        };
+        if !path.exists() {
+            self.set_status_message("File has not been saved yet".to_string());
+            return;
+        }
+        let current = self.relative_display(&path);
+        self.start_prompt_with_initial_text(
+            "Rename/move to: ".to_string(),
+            PromptType::RenameFile,
+            current,
+        );
+    }
+
+    /// Start the prompt for duplicating the active buffer's file
+    pub fn duplicate_file_prompt(&mut self) {
+        let Some(path) = self.managed_file_path() else {
+            return;
+        };
+        if !path.exists() {
+            self.set_status_message("File has not been saved yet".to_string());
+            return;
+        }
+        let suggestion = self.relative_display(&copy_name(&path));
+        self.start_prompt_with_initial_text(
+            "Duplicate to: ".to_string(),
+            PromptType::DuplicateFile,
+            suggestion,
+        );
+    }
+
+    /// Ask before moving the active buffer's file to the trash
+    pub fn trash_file_prompt(&mut self) {
+        let Some(path) = self.managed_file_path() else {
+            return;
+        };
+        if !path.exists() {
+            self.set_status_message("File has not been saved yet".to_string());
+            return;
+        }
+        let name = self.relative_display(&path);
+        let warning = if self.active_state().buffer.is_modified() {
+            " Unsaved changes will be lost."
+        } else {
+            ""
+        };
+        self.start_prompt(
+            format!("Move {} to trash?{} (y/N) ", name, warning),
+            PromptType::ConfirmTrashFile,
+        );
+    }
+
+    /// Handle the RenameFile prompt
+    pub(super) fn handle_rename_file(&mut self, input: &str) {
+        let buffer_id = self.active_buffer();
+        let Some(from) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            return;
+        };
+        let to = self.resolve_prompt_path(input);
+        if input.trim().is_empty() || to == from {
+            self.set_status_message("Rename cancelled".to_string());
+            return;
+        }
+        match self.move_buffer_file(buffer_id, &from, &to) {
+            Ok(()) => {
+                self.set_status_message(format!(
+                    "Moved {} to {}",
+                    self.relative_display(&from),
+                    self.relative_display(&to)
+                ));
+                self.file_operation_history
+                    .push(FileOperation::Moved { from, to });
+            }
+            Err(e) => self.set_status_message(format!("Error moving file: {}", e)),
+        }
+    }
+
+    /// Handle the DuplicateFile prompt
+    pub(super) fn handle_duplicate_file(&mut self, input: &str) {
+        let Some(from) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            return;
+        };
+        let copy = self.resolve_prompt_path(input);
+        if input.trim().is_empty() || copy == from {
+            self.set_status_message("Duplicate cancelled".to_string());
+            return;
+        }
+        if let Err(e) = copy_file(&from, &copy) {
+            self.set_status_message(format!("Error duplicating file: {}", e));
+            return;
+        }
+        let unsaved = self.active_state().buffer.is_modified();
+        match self.open_file(&copy) {
+            Ok(_) => {
+                let note = if unsaved {
+                    " (unsaved changes not included)"
+                } else {
+                    ""
+                };
+                self.set_status_message(format!(
+                    "Duplicated to {}{}",
+                    self.relative_display(&copy),
+                    note
+                ));
+            }
+            Err(e) => self.set_status_message(format!("Duplicated, but cannot open copy: {}", e)),
+        }
+        self.file_operation_history
+            .push(FileOperation::Duplicated { copy });
+    }
+
+    /// Handle the ConfirmTrashFile prompt
+    pub(super) fn handle_confirm_trash_file(&mut self, input: &str) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower != "y" && input_lower != "yes" {
+            self.set_status_message("Delete cancelled".to_string());
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            return;
+        };
+        if let Err(e) = trash::delete(&path) {
+            self.set_status_message(format!("Error moving to trash: {}", e));
+            return;
+        }
+        self.file_mod_times.remove(&path);
+        if let Err(e) = self.force_close_buffer(buffer_id) {
+            tracing::warn!("Failed to close trashed buffer: {}", e);
+        }
+        self.set_status_message(format!("Moved to trash: {}", self.relative_display(&path)));
+        self.file_operation_history
+            .push(FileOperation::Trashed { path });
+    }
+
+    /// Undo the most recent rename, duplicate or delete
+    pub fn undo_file_operation(&mut self) {
+        let Some(operation) = self.file_operation_history.pop() else {
+            self.set_status_message("No file operation to undo".to_string());
+            return;
+        };
+        let result = match &operation {
+            FileOperation::Moved { from, to } => {
+                let result = match self.buffer_for_path(to) {
+                    Some(buffer_id) => self.move_buffer_file(buffer_id, to, from),
+                    None => move_file(to, from),
+                };
+                result.map(|()| format!("Moved back to {}", self.relative_display(from)))
+            }
+            FileOperation::Duplicated { copy } => {
+                if let Some(buffer_id) = self.buffer_for_path(copy) {
+                    if let Err(e) = self.close_buffer(buffer_id) {
+                        self.file_operation_history.push(operation.clone());
+                        self.set_status_message(format!("Cannot remove copy: {}", e));
+                        return;
+                    }
+                }
+                trash::delete(copy)
+                    .map_err(io::Error::other)
+                    .map(|()| format!("Moved copy to trash: {}", self.relative_display(copy)))
+            }
+            FileOperation::Trashed { path } => restore_from_trash(path).and_then(|()| {
+                self.open_file(path)?;
+                Ok(format!("Restored {}", self.relative_display(path)))
+            }),
+        };
+        match result {
+            Ok(message) => self.set_status_message(message),
+            Err(e) => self.set_status_message(format!("Cannot undo file operation: {}", e)),
+        }
+    }
+
+    /// The open buffer editing `path`, if any
+    pub(super) fn buffer_for_path(&self, path: &Path) -> Option<BufferId> {
+        self.buffers
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id)
+    }
+
+    /// Move the file of `buffer_id` on disk and point the buffer at the new
+    /// location
+    fn move_buffer_file(&mut self, buffer_id: BufferId, from: &Path, to: &Path) -> io::Result<()> {
+        // Lazily loaded regions read from the old path, so load them first
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let total = state.buffer.total_bytes();
+            state
+                .buffer
+                .get_text_range_mut(0, total)
+                .map_err(io::Error::other)?;
+        }
+        move_file(from, to)?;
+
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.set_file_path(to.to_path_buf());
+        }
+        if let Some(mtime) = self.file_mod_times.remove(from) {
+            self.file_mod_times.insert(to.to_path_buf(), mtime);
+        }
+        self.close_lsp_document(buffer_id);
+        let mut metadata = BufferMetadata::with_file(to.to_path_buf(), &self.working_dir);
+        self.notify_lsp_file_opened(to, buffer_id, &mut metadata);
+        self.buffer_metadata.insert(buffer_id, metadata);
+        Ok(())
+    }
+
+    /// Tell the language server that a buffer's document is gone
+    fn close_lsp_document(&mut self, buffer_id: BufferId) {
+        let Some(metadata) = self.buffer_metadata.get(&buffer_id) else {
+            return;
+        };
+        if metadata.lsp_opened_with.is_empty() {
+            return;
+        }
+        let (Some(path), Some(uri)) = (metadata.file_path(), metadata.file_uri().cloned()) else {
+            return;
+        };
+        let Some(language) = detect_language(path, &self.config.languages) else {
+            return;
+        };
+        if let Some(client) = self
+            .lsp
+            .as_mut()
+            .and_then(|lsp| lsp.get_or_spawn(&language))
+        {
+            if let Err(e) = client.did_close(uri) {
+                tracing::warn!("Failed to send didClose to LSP: {}", e);
+            }
+        }
+    }
+}
+
+/// Default name for a copy of `path`: `name copy.ext`
+fn copy_name(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{} copy.{}", stem, ext.to_string_lossy()),
+        None => format!("{} copy", stem),
+    };
+    path.with_file_name(name)
+}
+
+/// Move a file, creating the destination directory. Never overwrites.
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if let Err(e) = std::fs::rename(from, to) {
+        // Renames cannot cross filesystems; fall back to copy and remove
+        if std::fs::copy(from, to).is_err() {
+            return Err(e);
+        }
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Copy a file, creating the destination directory. Never overwrites.
+fn copy_file(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(from, to).map(|_| ())
+}
+
+/// Put the most recently trashed file that was at `path` back
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn restore_from_trash(path: &Path) -> io::Result<()> {
+    let item = trash::os_limited::list()
+        .map_err(io::Error::other)?
+        .into_iter()
+        .filter(|item| item.original_path() == path)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "file is no longer in the trash"))?;
+    trash::os_limited::restore_all([item]).map_err(io::Error::other)
+}
+
+/// The trash cannot be read on this platform
+#[cfg(not(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+fn restore_from_trash(path: &Path) -> io::Result<()> {
+    Err(io::Error::other(format!(
+        "restore {} from the Trash",
+        path.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_name() {
+        assert_eq!(
+            copy_name(Path::new("/src/main.rs")),
+            PathBuf::from("/src/main copy.rs")
+        );
+        assert_eq!(
+            copy_name(Path::new("/src/Makefile")),
+            PathBuf::from("/src/Makefile copy")
+        );
+    }
+
+    #[test]
+    fn test_move_file_never_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("nested/b.txt");
+        std::fs::write(&a, "a").unwrap();
+
+        move_file(&a, &b).unwrap();
+        assert!(!a.exists());
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "a");
+
+        std::fs::write(&a, "new").unwrap();
+        let err = move_file(&a, &b).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "a");
+    }
+}
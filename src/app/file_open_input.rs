@@ -9,25 +9,32 @@ use crate::input::keybindings::Action;
 use crate::view::prompt::PromptType;
 
 impl Editor {
-    /// Check if the file open dialog is active (for both OpenFile and SwitchProject)
+    /// Check if the file open dialog is active (for OpenFile and the folder prompts)
     pub fn is_file_open_active(&self) -> bool {
         self.prompt
             .as_ref()
             .map(|p| {
                 matches!(
                     p.prompt_type,
-                    PromptType::OpenFile | PromptType::SwitchProject
+                    PromptType::OpenFile
+                        | PromptType::SwitchProject
+                        | PromptType::AddWorkspaceFolder
                 )
             })
             .unwrap_or(false)
             && self.file_open_state.is_some()
     }
 
-    /// Check if we're in folder-only selection mode (Switch Project)
+    /// Check if we're in folder-only selection mode (Switch Project, Add Folder to Workspace)
     fn is_folder_open_mode(&self) -> bool {
         self.prompt
             .as_ref()
-            .map(|p| p.prompt_type == PromptType::SwitchProject)
+            .map(|p| {
+                matches!(
+                    p.prompt_type,
+                    PromptType::SwitchProject | PromptType::AddWorkspaceFolder
+                )
+            })
             .unwrap_or(false)
     }
 
@@ -155,6 +162,22 @@ impl Editor {
             .map(|s| s.current_dir.clone())
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
+        // Remote files and provider URLs are fetched in the background
+        if !is_folder_mode {
+            if let Some(url) = crate::services::ssh::SshUrl::parse(&prompt_input) {
+                self.file_open_state = None;
+                self.prompt = None;
+                self.open_ssh_url(url);
+                return;
+            }
+            if let Some(url) = self.vfs.parse(&prompt_input) {
+                self.file_open_state = None;
+                self.prompt = None;
+                self.open_vfs_url(url);
+                return;
+            }
+        }
+
         // If there's any prompt input, try to resolve it as a path
         if !prompt_input.is_empty() {
             let expanded_path = if prompt_input.starts_with('~') {
@@ -232,14 +255,24 @@ impl Editor {
         // In folder mode, selecting a file does nothing
     }
 
-    /// Select a folder as the new project root (for SwitchProject mode)
+    /// Select a folder as the new project root (for SwitchProject mode), or
+    /// add it to the workspace
     fn file_open_select_folder(&mut self, path: std::path::PathBuf) {
+        let add_to_workspace = self
+            .prompt
+            .as_ref()
+            .is_some_and(|p| p.prompt_type == PromptType::AddWorkspaceFolder);
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
 
-        // Change the working directory
-        self.change_working_dir(path);
+        if add_to_workspace {
+            self.add_workspace_folder(path);
+        } else {
+            // Change the working directory
+            self.change_working_dir(path);
+        }
     }
 
     /// Navigate to a directory in the file browser
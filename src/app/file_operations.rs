@@ -11,6 +11,7 @@
 use std::io;
 use std::path::{Path, PathBuf};
 
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use lsp_types::TextDocumentContentChangeEvent;
 
 use crate::model::event::{BufferId, EventLog};
@@ -22,6 +23,18 @@ use super::{BufferMetadata, Editor};
 impl Editor {
     /// Save the active buffer
     pub fn save(&mut self) -> io::Result<()> {
+        // Saving part of a file would cut it short
+        if self.active_state().loading {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "file is still loading",
+            ));
+        }
+        // Large buffers are written in the background, and finish the save
+        // once written
+        if self.saves_in_background() {
+            return self.start_background_save();
+        }
         let path = self
             .active_state()
             .buffer
@@ -29,9 +42,78 @@ impl Editor {
             .map(|p| p.to_path_buf());
         self.active_state_mut().buffer.save()?;
         self.status_message = Some("Saved".to_string());
+        self.finish_save(self.active_buffer(), path);
+
+        // Run on-save actions (formatters, linters, etc.)
+        match self.run_on_save_actions() {
+            Ok(true) => {
+                // Actions ran successfully - if status_message was set by run_on_save_actions
+                // (e.g., for missing optional formatters), keep it. Otherwise update status.
+                if self.status_message.as_deref() == Some("Saved") {
+                    self.status_message = Some("Saved (with on-save actions)".to_string());
+                }
+                // else: keep the message set by run_on_save_actions (e.g., missing formatter)
+            }
+            Ok(false) => {
+                // No actions configured, keep original status
+            }
+            Err(e) => {
+                // Action failed, show error but don't fail the save
+                self.status_message = Some(e);
+            }
+        }
 
+        // Write the local copy of a remote file back to its host
+        self.upload_ssh_buffer(self.active_buffer());
+
+        Ok(())
+    }
+
+    /// Save a buffer without switching to it, as the save command does:
+    /// buffers opened from a provider are written back through it, and a
+    /// commit message is committed. Fails with `None` when the buffer has no
+    /// file or its file changed on disk, since saving it then needs the
+    /// user's say.
+    pub(super) fn save_buffer_by_id(
+        &mut self,
+        buffer_id: BufferId,
+    ) -> Result<(), Option<io::Error>> {
+        if self.is_vfs_buffer(buffer_id) {
+            self.save_vfs_buffer(buffer_id);
+            return Ok(());
+        }
+        if self.is_git_commit_message(buffer_id) {
+            self.finish_git_commit();
+            return Ok(());
+        }
+        let has_path = self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.buffer.file_path().is_some());
+        if !has_path {
+            return Err(None);
+        }
+
+        // Save through the active buffer of the current split, as the save
+        // command does, without showing the buffer there
+        let original_active = self.active_buffer();
+        self.split_manager.set_active_buffer_id(buffer_id);
+        let result = if self.check_save_conflict().is_some() {
+            Err(None)
+        } else {
+            self.save().map_err(Some)
+        };
+        self.split_manager.set_active_buffer_id(original_active);
+        result
+    }
+
+    /// Bookkeeping after `buffer_id` was written to `path`: saved markers,
+    /// modification time, LSP and plugin notifications
+    pub(super) fn finish_save(&mut self, buffer_id: BufferId, path: Option<PathBuf>) {
         // Mark the event log position as saved (for undo modified tracking)
-        self.active_event_log_mut().mark_saved();
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.mark_saved();
+        }
 
         // Update file modification time after save
         if let Some(ref p) = path {
@@ -43,10 +125,10 @@ impl Editor {
         }
 
         // Notify LSP of save
-        self.notify_lsp_save();
+        self.notify_lsp_save(buffer_id);
 
         // Delete recovery file (buffer is now saved)
-        let _ = self.delete_buffer_recovery(self.active_buffer());
+        let _ = self.delete_buffer_recovery(buffer_id);
 
         // Emit control event
         if let Some(ref p) = path {
@@ -60,7 +142,6 @@ impl Editor {
 
         // Fire AfterFileSave hook for plugins
         if let Some(ref p) = path {
-            let buffer_id = self.active_buffer();
             self.plugin_manager.run_hook(
                 "after_file_save",
                 crate::services::plugins::hooks::HookArgs::AfterFileSave {
@@ -68,28 +149,8 @@ impl Editor {
                     path: p.clone(),
                 },
             );
+            self.regenerate_tags_after_save(p);
         }
-
-        // Run on-save actions (formatters, linters, etc.)
-        match self.run_on_save_actions() {
-            Ok(true) => {
-                // Actions ran successfully - if status_message was set by run_on_save_actions
-                // (e.g., for missing optional formatters), keep it. Otherwise update status.
-                if self.status_message.as_deref() == Some("Saved") {
-                    self.status_message = Some("Saved (with on-save actions)".to_string());
-                }
-                // else: keep the message set by run_on_save_actions (e.g., missing formatter)
-            }
-            Ok(false) => {
-                // No actions configured, keep original status
-            }
-            Err(e) => {
-                // Action failed, show error but don't fail the save
-                self.status_message = Some(e);
-            }
-        }
-
-        Ok(())
     }
 
     /// Revert the active buffer to the last saved version on disk
@@ -136,9 +197,16 @@ impl Editor {
         });
         new_state.cursors = restored_cursors;
 
-        // Replace the current buffer with the new state
+        // Replace the current buffer with the new state, keeping its
+        // indentation settings
         let buffer_id = self.active_buffer();
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            new_state.tab_size = state.tab_size;
+            new_state.use_tabs = state.use_tabs;
+            new_state.show_whitespace_tabs = state.show_whitespace_tabs;
+            new_state
+                .buffer
+                .continue_versions_from(state.buffer.version());
             *state = new_state;
             // Note: line_wrap_enabled is now in SplitViewState.viewport
         }
@@ -175,6 +243,7 @@ impl Editor {
     /// Toggle auto-revert mode
     pub fn toggle_auto_revert(&mut self) {
         self.auto_revert_enabled = !self.auto_revert_enabled;
+        self.auto_revert_disabled_by = None;
 
         if self.auto_revert_enabled {
             self.status_message = Some("Auto-revert enabled".to_string());
@@ -193,9 +262,12 @@ impl Editor {
             return false;
         }
 
-        // Check poll interval
-        let poll_interval =
-            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        // Check poll interval, stretched while polling is slow
+        let poll_interval = self
+            .file_poll_stats
+            .interval(std::time::Duration::from_millis(
+                self.config.editor.auto_revert_poll_interval_ms,
+            ));
         let elapsed = self.time_source.elapsed_since(self.last_auto_revert_poll);
         tracing::trace!(
             "poll_file_changes: elapsed={:?}, poll_interval={:?}",
@@ -214,16 +286,31 @@ impl Editor {
             .filter_map(|state| state.buffer.file_path().map(PathBuf::from))
             .collect();
 
+        let uncached_paths = path_patterns(&self.config.editor.auto_revert_uncached_paths);
+        let checked = files_to_check.len();
+        let mut unreadable = Vec::new();
+        let mut stat_time = std::time::Duration::ZERO;
         let mut any_changed = false;
 
         for path in files_to_check {
-            // Get current mtime
-            let current_mtime = match std::fs::metadata(&path) {
-                Ok(meta) => match meta.modified() {
-                    Ok(mtime) => mtime,
-                    Err(_) => continue,
-                },
-                Err(_) => continue, // File might have been deleted
+            // Get current mtime, opening the file first where metadata is cached
+            let stat_start = std::time::Instant::now();
+            let metadata = if uncached_paths
+                .as_ref()
+                .is_some_and(|patterns| matches_path_pattern(patterns, &path))
+            {
+                std::fs::File::open(&path).and_then(|file| file.metadata())
+            } else {
+                std::fs::metadata(&path)
+            };
+            stat_time += stat_start.elapsed();
+            // The file might have been deleted
+            let current_mtime = match metadata.and_then(|meta| meta.modified()) {
+                Ok(mtime) => mtime,
+                Err(e) => {
+                    unreadable.push((path, e.to_string()));
+                    continue;
+                }
             };
 
             // Check if mtime has changed
@@ -243,47 +330,65 @@ impl Editor {
             }
         }
 
+        self.file_poll_stats
+            .record(self.time_source.now(), checked, unreadable, stat_time);
         any_changed
     }
 
     /// Poll for file tree changes (called from main loop)
     ///
-    /// Checks modification times of expanded directories to detect new/deleted files.
-    /// Returns true if any directory was refreshed (requires re-render).
+    /// Checks modification times of expanded directories to detect new/deleted files,
+    /// and reloads ignore files that changed.
+    /// Returns true if anything changed (requires re-render).
     pub fn poll_file_tree_changes(&mut self) -> bool {
-        // Check poll interval
-        let poll_interval =
-            std::time::Duration::from_millis(self.config.editor.file_tree_poll_interval_ms);
+        // Check poll interval, stretched while polling is slow
+        let poll_interval = self
+            .tree_poll_stats
+            .interval(std::time::Duration::from_millis(
+                self.config.editor.file_tree_poll_interval_ms,
+            ));
         if self.time_source.elapsed_since(self.last_file_tree_poll) < poll_interval {
             return false;
         }
         self.last_file_tree_poll = self.time_source.now();
 
+        // Reload ignore files that changed, which changes what the tree shows
+        let ignore_files_changed = self
+            .ignore_service
+            .write()
+            .map(|mut service| !service.poll_changes().is_empty())
+            .unwrap_or(false);
+
         // Get file explorer reference
         let Some(explorer) = &self.file_explorer else {
-            return false;
+            return ignore_files_changed;
         };
 
-        // Collect expanded directories (node_id, path)
+        // Collect expanded directories (node_id, path), skipping ignored
+        // ones the tree does not show
         use crate::view::file_tree::NodeId;
         let expanded_dirs: Vec<(NodeId, PathBuf)> = explorer
             .tree()
             .all_nodes()
             .filter(|node| node.is_dir() && node.is_expanded())
+            .filter(|node| explorer.tree().is_root(node.id) || explorer.is_node_visible(node.id))
             .map(|node| (node.id, node.entry.path.clone()))
             .collect();
 
         // Check mtimes and collect directories that need refresh
         let mut dirs_to_refresh: Vec<NodeId> = Vec::new();
+        let checked = expanded_dirs.len();
+        let mut unreadable = Vec::new();
+        let stat_start = std::time::Instant::now();
 
         for (node_id, path) in expanded_dirs {
-            // Get current mtime
-            let current_mtime = match std::fs::metadata(&path) {
-                Ok(meta) => match meta.modified() {
-                    Ok(mtime) => mtime,
-                    Err(_) => continue,
-                },
-                Err(_) => continue, // Directory might have been deleted
+            // Get current mtime; the directory might have been deleted
+            let current_mtime = match std::fs::metadata(&path).and_then(|meta| meta.modified()) {
+                Ok(mtime) => mtime,
+                Err(e) => {
+                    unreadable.push((path, e.to_string()));
+                    continue;
+                }
             };
 
             // Check if mtime has changed
@@ -300,9 +405,16 @@ impl Editor {
             }
         }
 
+        self.tree_poll_stats.record(
+            self.time_source.now(),
+            checked,
+            unreadable,
+            stat_start.elapsed(),
+        );
+
         // Refresh changed directories
         if dirs_to_refresh.is_empty() {
-            return false;
+            return ignore_files_changed;
         }
 
         // Refresh each changed directory
@@ -575,6 +687,12 @@ impl Editor {
                 None => continue,
             };
 
+            // A file still loading is being read to its end already, and a
+            // file being saved in the background is changed by the save
+            if state.loading || self.background_saves.contains_key(&buffer_id) {
+                continue;
+            }
+
             // Check if the file actually changed (compare mod times)
             // We use optimistic concurrency: check mtime, and if we decide to revert,
             // re-check to handle the race where a save completed between our checks.
@@ -661,3 +779,29 @@ impl Editor {
         }
     }
 }
+
+/// Matcher of absolute paths against patterns in .gitignore syntax, or None
+/// if there are no patterns
+fn path_patterns(patterns: &[String]) -> Option<Gitignore> {
+    if patterns.is_empty() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        if let Err(e) = builder.add_line(None, pattern) {
+            tracing::warn!("Invalid path pattern {:?}: {}", pattern, e);
+        }
+    }
+    builder.build().ok()
+}
+
+/// Whether the absolute `path` or a folder above it matches `patterns`
+fn matches_path_pattern(patterns: &Gitignore, path: &Path) -> bool {
+    // Patterns are relative to the filesystem root
+    let Ok(relative) = path.strip_prefix("/") else {
+        return false;
+    };
+    patterns
+        .matched_path_or_any_parents(relative, false)
+        .is_ignore()
+}
@@ -0,0 +1,200 @@
+//! Health of the polling that notices files changed on disk.
+//!
+//! This module provides functionality to:
+//! - Keep statistics of the last poll of open files (auto-revert) and of the
+//!   folders expanded in the file explorer, including paths that couldn't be
+//!   read
+//! - Poll less often while polling is slow (many files, or a network
+//!   filesystem), so it never takes more than a small share of the time
+//! - Show both in a read-only buffer ("Show File Watcher Status")
+//!
+//! Changes are found by comparing modification times rather than with OS
+//! file notifications, so large sessions don't run into watch limits; their
+//! cost is the time a poll takes, which is what is tracked here.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use super::types::{BufferKind, BufferMetadata};
+use super::Editor;
+use crate::model::event::{BufferId, EventLog};
+use crate::state::EditorState;
+
+/// Polls are spaced at least this many times their duration apart
+const SLOW_POLL_FACTOR: u32 = 20;
+
+/// Unreadable paths listed in the status buffer
+const MAX_UNREADABLE_SHOWN: usize = 20;
+
+/// What the last poll of a set of paths found
+#[derive(Debug, Default)]
+pub(crate) struct PollStats {
+    /// When it ran
+    last_poll: Option<Instant>,
+    /// Paths it checked
+    checked: usize,
+    /// Paths whose modification time couldn't be read, and why
+    unreadable: Vec<(PathBuf, String)>,
+    /// Time spent reading modification times
+    duration: Duration,
+}
+
+impl PollStats {
+    /// Time between polls: the configured interval, stretched while polls
+    /// are slow
+    pub fn interval(&self, configured: Duration) -> Duration {
+        configured.max(self.duration * SLOW_POLL_FACTOR)
+    }
+
+    /// Whether polls are slow enough to run less often than configured
+    pub fn is_slow(&self, configured: Duration) -> bool {
+        self.interval(configured) > configured
+    }
+
+    /// Paths the last poll couldn't read
+    pub fn unreadable_count(&self) -> usize {
+        self.unreadable.len()
+    }
+
+    /// Record a poll run at `now`
+    pub fn record(
+        &mut self,
+        now: Instant,
+        checked: usize,
+        unreadable: Vec<(PathBuf, String)>,
+        duration: Duration,
+    ) {
+        if !unreadable.is_empty() && unreadable.len() != self.unreadable.len() {
+            tracing::debug!("{} watched paths can't be read", unreadable.len());
+        }
+        self.last_poll = Some(now);
+        self.checked = checked;
+        self.unreadable = unreadable;
+        self.duration = duration;
+    }
+
+    /// Describe the last poll, with `what` naming the paths
+    fn describe(&self, what: &str, configured: Duration, now: Instant) -> String {
+        let Some(last_poll) = self.last_poll else {
+            return format!("{}: not polled yet\n", what);
+        };
+        let interval = self.interval(configured);
+        let mut text = format!(
+            "{}: {} checked every {:.1}s, last poll took {:.1} ms ({}s ago)\n",
+            what,
+            self.checked,
+            interval.as_secs_f64(),
+            self.duration.as_secs_f64() * 1000.0,
+            now.saturating_duration_since(last_poll).as_secs()
+        );
+        if self.is_slow(configured) {
+            text.push_str(&format!(
+                "  Polling is slow, so it runs less often than the configured {:.1}s\n",
+                configured.as_secs_f64()
+            ));
+        }
+        if !self.unreadable.is_empty() {
+            text.push_str(&format!("  {} can't be read:\n", self.unreadable.len()));
+            for (path, error) in self.unreadable.iter().take(MAX_UNREADABLE_SHOWN) {
+                text.push_str(&format!("    {}: {}\n", path.display(), error));
+            }
+            if self.unreadable.len() > MAX_UNREADABLE_SHOWN {
+                text.push_str(&format!(
+                    "    ... and {} more\n",
+                    self.unreadable.len() - MAX_UNREADABLE_SHOWN
+                ));
+            }
+        }
+        text
+    }
+}
+
+impl Editor {
+    /// Show what the file watcher polls and how it is doing in a read-only
+    /// buffer
+    pub(super) fn show_file_watcher_status(&mut self) {
+        let now = self.time_source.now();
+        let editor_config = &self.config.editor;
+        let mut content = String::from("// File watcher\n\n");
+
+        let auto_revert = if self.auto_revert_enabled {
+            "on".to_string()
+        } else if let Some(path) = &self.auto_revert_disabled_by {
+            format!("off ({} changed too often)", path.display())
+        } else {
+            "off".to_string()
+        };
+        content.push_str(&format!("Auto-revert: {}\n", auto_revert));
+        if !editor_config.auto_revert_uncached_paths.is_empty() {
+            content.push_str(&format!(
+                "Opened when polled: {}\n",
+                editor_config.auto_revert_uncached_paths.join(", ")
+            ));
+        }
+        content.push('\n');
+        content.push_str(&self.file_poll_stats.describe(
+            "Open files",
+            Duration::from_millis(editor_config.auto_revert_poll_interval_ms),
+            now,
+        ));
+        content.push_str(&self.tree_poll_stats.describe(
+            "File explorer folders",
+            Duration::from_millis(editor_config.file_tree_poll_interval_ms),
+            now,
+        ));
+
+        let buffer_id = BufferId(self.next_buffer_id);
+        self.next_buffer_id += 1;
+
+        let mut state = EditorState::new(
+            self.terminal_width,
+            self.terminal_height,
+            self.config.editor.large_file_threshold_bytes as usize,
+        );
+        state.buffer.replace(crate::model::buffer::Buffer::from_str(
+            &content,
+            self.config.editor.large_file_threshold_bytes as usize,
+        ));
+        self.buffers.insert(buffer_id, state);
+        self.event_logs.insert(buffer_id, EventLog::new());
+
+        let metadata = BufferMetadata {
+            kind: BufferKind::Virtual {
+                mode: "file-watcher-status".to_string(),
+            },
+            display_name: "*File Watcher*".to_string(),
+            lsp_enabled: false,
+            lsp_disabled_reason: Some("Virtual file watcher status buffer".to_string()),
+            read_only: true,
+            binary: false,
+            lsp_opened_with: std::collections::HashSet::new(),
+        };
+        self.buffer_metadata.insert(buffer_id, metadata);
+
+        self.set_active_buffer(buffer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_polls_run_less_often() {
+        let configured = Duration::from_secs(2);
+        let mut stats = PollStats::default();
+        assert_eq!(stats.interval(configured), configured);
+
+        let now = Instant::now();
+        stats.record(now, 100, Vec::new(), Duration::from_millis(50));
+        assert_eq!(stats.interval(configured), configured);
+        stats.record(now, 5000, Vec::new(), Duration::from_millis(500));
+        assert_eq!(stats.interval(configured), Duration::from_secs(10));
+
+        let unreadable = vec![(PathBuf::from("/gone.txt"), "not found".to_string())];
+        stats.record(now, 1, unreadable, Duration::ZERO);
+        let text = stats.describe("Open files", configured, now);
+        assert!(text.contains("1 checked every 2.0s"));
+        assert!(text.contains("/gone.txt: not found"));
+    }
+}
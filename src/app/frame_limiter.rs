@@ -0,0 +1,173 @@
+//! Pacing of screen redraws.
+//!
+//! This module provides functionality to:
+//! - Cap redraws at the configured frames per second (`editor.max_fps`)
+//! - Redraw at once for key presses and pastes, so typing never waits
+//! - Redraw for mouse, scroll and resize events at most once per frame, so a
+//!   burst of them is drawn once
+//! - Redraw for background events (job progress, file watchers, language
+//!   servers) lazily, a quarter as often
+//!
+//! The event loop owns the limiter: it reports why the screen changed with
+//! [`FrameLimiter::request`], asks [`FrameLimiter::should_render`] before
+//! drawing, and waits for input no longer than [`FrameLimiter::timeout`].
+
+use std::time::{Duration, Instant};
+
+/// Background redraws are this many frames apart
+const BACKGROUND_FRAMES: u32 = 4;
+
+/// Why the screen needs redrawing, least urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Redraw {
+    /// Something changed without user input
+    Background,
+    /// Mouse, scroll or resize event
+    Pointer,
+    /// Key press or paste
+    Key,
+}
+
+/// Decides when the event loop redraws the screen
+#[derive(Debug)]
+pub struct FrameLimiter {
+    frame_interval: Duration,
+    last_render: Option<Instant>,
+    /// Most urgent redraw requested since the last one
+    pending: Option<Redraw>,
+}
+
+impl FrameLimiter {
+    /// Limiter drawing at most `max_fps` frames per second (0: no cap)
+    pub fn new(max_fps: u32) -> Self {
+        let mut limiter = Self {
+            frame_interval: Duration::ZERO,
+            last_render: None,
+            pending: Some(Redraw::Key),
+        };
+        limiter.set_max_fps(max_fps);
+        limiter
+    }
+
+    /// Change the cap, e.g. after the configuration was edited
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.frame_interval = Duration::from_secs(1)
+            .checked_div(max_fps)
+            .unwrap_or(Duration::ZERO);
+    }
+
+    /// Ask for a redraw
+    pub fn request(&mut self, redraw: Redraw) {
+        self.pending = self.pending.max(Some(redraw));
+    }
+
+    /// Whether a redraw was asked for and not done yet
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Shortest time between the last frame and a redraw of this kind
+    fn interval(&self, redraw: Redraw) -> Duration {
+        match redraw {
+            Redraw::Key => Duration::ZERO,
+            Redraw::Pointer => self.frame_interval,
+            Redraw::Background => self.frame_interval * BACKGROUND_FRAMES,
+        }
+    }
+
+    /// Time left until the pending redraw is due, or None if none is pending
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        let redraw = self.pending?;
+        let Some(last_render) = self.last_render else {
+            return Some(Duration::ZERO);
+        };
+        Some(
+            self.interval(redraw)
+                .saturating_sub(now.saturating_duration_since(last_render)),
+        )
+    }
+
+    /// Whether to redraw now; `input_queued` tells that more input is
+    /// waiting, which is handled first so a burst is drawn once (but never
+    /// for longer than a background frame)
+    pub fn should_render(&self, now: Instant, input_queued: bool) -> bool {
+        let Some(timeout) = self.timeout(now) else {
+            return false;
+        };
+        if input_queued {
+            let since_last = self
+                .last_render
+                .map_or(Duration::MAX, |last| now.saturating_duration_since(last));
+            return since_last >= self.interval(Redraw::Background);
+        }
+        timeout.is_zero()
+    }
+
+    /// Record a redraw done at `now`
+    pub fn rendered(&mut self, now: Instant) {
+        self.last_render = Some(now);
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter_after_render(now: Instant) -> FrameLimiter {
+        let mut limiter = FrameLimiter::new(50); // 20ms frames
+        limiter.rendered(now);
+        limiter
+    }
+
+    #[test]
+    fn test_redraw_pacing() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+
+        let mut limiter = limiter_after_render(start);
+        assert!(!limiter.should_render(ms(1), false));
+        assert_eq!(limiter.timeout(ms(1)), None);
+
+        // Key presses are drawn at once
+        limiter.request(Redraw::Key);
+        assert!(limiter.should_render(ms(1), false));
+
+        // Pointer events wait for the next frame
+        let mut limiter = limiter_after_render(start);
+        limiter.request(Redraw::Pointer);
+        assert!(!limiter.should_render(ms(5), false));
+        assert_eq!(limiter.timeout(ms(5)), Some(Duration::from_millis(15)));
+        assert!(limiter.should_render(ms(20), false));
+
+        // Background events wait four frames, unless something more urgent comes
+        let mut limiter = limiter_after_render(start);
+        limiter.request(Redraw::Background);
+        assert!(!limiter.should_render(ms(20), false));
+        assert!(limiter.should_render(ms(80), false));
+        limiter.request(Redraw::Pointer);
+        limiter.request(Redraw::Background);
+        assert!(limiter.should_render(ms(20), false));
+    }
+
+    #[test]
+    fn test_queued_input_is_handled_first() {
+        let start = Instant::now();
+        let mut limiter = limiter_after_render(start);
+        limiter.request(Redraw::Key);
+        assert!(!limiter.should_render(start + Duration::from_millis(10), true));
+        // A long burst still shows progress
+        assert!(limiter.should_render(start + Duration::from_millis(80), true));
+        limiter.rendered(start + Duration::from_millis(80));
+        assert!(!limiter.is_pending());
+    }
+
+    #[test]
+    fn test_uncapped() {
+        let start = Instant::now();
+        let mut limiter = FrameLimiter::new(0);
+        limiter.rendered(start);
+        limiter.request(Redraw::Background);
+        assert!(limiter.should_render(start, false));
+    }
+}
@@ -0,0 +1,88 @@
+//! Git branch switching.
+//!
+//! This module provides functionality to:
+//! - List the local branches of the working directory's repository, with
+//!   the current one marked, and check out the chosen one in the background
+//! - Refresh the status bar's git status once the checkout finishes
+//!
+//! The status itself is watched by `services::git_status`.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::git_status;
+use crate::view::prompt::{Prompt, PromptType};
+
+impl Editor {
+    /// Prompt for a local branch to check out
+    pub fn start_git_checkout_prompt(&mut self) {
+        let branches = match git_status::local_branches(&self.working_dir) {
+            Ok(branches) => branches,
+            Err(e) => {
+                self.set_status_message(format!("Cannot list branches: {}", e));
+                return;
+            }
+        };
+        if branches.is_empty() {
+            self.set_status_message("No local branches".to_string());
+            return;
+        }
+
+        let current = self
+            .git_status
+            .as_ref()
+            .map(|status| status.branch.as_str());
+        let suggestions: Vec<Suggestion> = branches
+            .iter()
+            .map(|branch| Suggestion {
+                text: branch.clone(),
+                description: (Some(branch.as_str()) == current).then(|| "current".to_string()),
+                value: Some(branch.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            "Checkout branch: ".to_string(),
+            PromptType::GitCheckoutBranch,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Check out `branch` on a background thread
+    pub(super) fn git_checkout_branch(&mut self, branch: &str) {
+        if branch.is_empty() {
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+        self.set_status_message(format!("Checking out {}...", branch));
+        let dir = self.working_dir.clone();
+        let branch = branch.to_string();
+        std::thread::spawn(move || {
+            let result = git_status::checkout(&dir, &branch);
+            let _ = sender.send(AsyncMessage::GitCheckoutFinished { branch, result });
+        });
+    }
+
+    /// Report a finished checkout and refresh the status bar
+    pub(super) fn handle_git_checkout_finished(
+        &mut self,
+        branch: String,
+        result: Result<(), String>,
+    ) {
+        match result {
+            Ok(()) => self.set_status_message(format!("Switched to branch '{}'", branch)),
+            Err(e) => self.set_status_message(format!("Checkout of '{}' failed: {}", branch, e)),
+        }
+        if let Some(watcher) = &self.git_status_watcher {
+            watcher.refresh();
+        }
+    }
+}
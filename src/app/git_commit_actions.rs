@@ -0,0 +1,287 @@
+//! Git commits from inside the editor.
+//!
+//! This module provides functionality to:
+//! - Open a commit message buffer, with rulers at the configured columns
+//!   (`editor.commit_message_rulers`), above a read-only view of the staged
+//!   changes; when amending, the message starts as the last commit's
+//! - Commit on save, in the background, and close both buffers once done
+//! - Show what git and the hooks printed when the commit is rejected, so a
+//!   failing `pre-commit` or `commit-msg` hook can be fixed without a shell
+//!
+//! Closing the message buffer aborts the commit.
+
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::{BufferId, SplitDirection, SplitId};
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::git_status;
+use crate::view::split::SplitViewState;
+
+/// Mode of the commit message buffer
+const MESSAGE_MODE: &str = "git-commit";
+
+/// Mode of the staged changes and commit output buffers
+const STAGED_MODE: &str = "git-staged";
+
+/// Share of the height kept by the message split
+const STAGED_SPLIT_RATIO: f32 = 0.4;
+
+/// A commit being written
+pub(crate) struct GitCommitSession {
+    message_buffer: BufferId,
+    staged_buffer: BufferId,
+    /// Split below the message showing the staged changes, and the hook
+    /// output after a failed commit
+    staged_split: Option<SplitId>,
+    output_buffer: Option<BufferId>,
+    amend: bool,
+    /// Whether git is running, so saving again doesn't commit twice
+    running: bool,
+}
+
+impl Editor {
+    /// Open a commit message buffer above the staged changes, or, with
+    /// `amend`, one to replace the last commit
+    pub fn start_git_commit(&mut self, amend: bool) {
+        if let Some(session) = &self.git_commit {
+            if self.buffers.contains_key(&session.message_buffer) {
+                let buffer_id = session.message_buffer;
+                self.set_active_buffer(buffer_id);
+                self.set_status_message("A commit is already being written".to_string());
+                return;
+            }
+            self.git_commit = None;
+        }
+
+        let staged = match git_status::staged_changes(&self.working_dir) {
+            Ok(staged) => staged,
+            Err(e) => {
+                self.set_status_message(format!("Cannot commit: {}", e));
+                return;
+            }
+        };
+        let message = if amend {
+            match git_status::last_commit_message(&self.working_dir) {
+                Ok(message) => format!("{}\n", message.trim_end()),
+                Err(e) => {
+                    self.set_status_message(format!("Cannot amend: {}", e));
+                    return;
+                }
+            }
+        } else if staged.is_empty() {
+            self.set_status_message("Nothing staged to commit".to_string());
+            return;
+        } else {
+            String::new()
+        };
+        let staged = if staged.is_empty() {
+            "No staged changes; amending changes only the message.\n".to_string()
+        } else {
+            staged
+        };
+
+        let message_buffer = self.create_virtual_buffer(
+            "*Commit Message*".to_string(),
+            MESSAGE_MODE.to_string(),
+            false,
+        );
+        if let Some(state) = self.buffers.get_mut(&message_buffer) {
+            state.rulers = self.config.editor.commit_message_rulers.clone();
+            state.buffer.insert(0, &message);
+            state.buffer.clear_modified();
+        }
+        self.set_active_buffer(message_buffer);
+
+        self.mode_registry.register(
+            BufferMode::new(STAGED_MODE)
+                .with_parent("special")
+                .with_read_only(true),
+        );
+        let staged_buffer = self.create_read_only_text_buffer("*Staged Changes*", &staged);
+        let message_split = self.split_manager.active_split();
+        let staged_split = self.open_in_split_below(staged_buffer);
+        self.focus_split(message_split, message_buffer);
+
+        self.git_commit = Some(GitCommitSession {
+            message_buffer,
+            staged_buffer,
+            staged_split,
+            output_buffer: None,
+            amend,
+            running: false,
+        });
+        let action = if amend {
+            "amend the last commit"
+        } else {
+            "commit"
+        };
+        self.set_status_message(format!("Save to {}; close the buffer to abort", action));
+    }
+
+    /// Whether `buffer_id` is the message of the commit being written
+    pub(super) fn is_git_commit_message(&self, buffer_id: BufferId) -> bool {
+        self.git_commit
+            .as_ref()
+            .is_some_and(|session| session.message_buffer == buffer_id)
+    }
+
+    /// Commit with the message buffer's content on a background thread
+    pub(super) fn finish_git_commit(&mut self) {
+        let Some(session) = self.git_commit.as_ref() else {
+            return;
+        };
+        if session.running {
+            self.set_status_message("Commit in progress...".to_string());
+            return;
+        }
+        let amend = session.amend;
+        let Some(message) = self
+            .buffers
+            .get(&session.message_buffer)
+            .and_then(|state| state.buffer.to_string())
+        else {
+            return;
+        };
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+
+        if let Some(session) = self.git_commit.as_mut() {
+            session.running = true;
+        }
+        self.set_status_message("Committing...".to_string());
+        let dir = self.working_dir.clone();
+        std::thread::spawn(move || {
+            let result = git_status::commit(&dir, &message, amend);
+            let _ = sender.send(AsyncMessage::GitCommitFinished { result });
+        });
+    }
+
+    /// Close the commit buffers after a commit, or show why it failed
+    pub(super) fn handle_git_commit_finished(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(summary) => {
+                if let Some(session) = self.git_commit.take() {
+                    self.close_git_commit_session(session);
+                }
+                self.set_status_message(summary);
+                if let Some(watcher) = &self.git_status_watcher {
+                    watcher.refresh();
+                }
+            }
+            Err(output) => {
+                let Some(session) = self.git_commit.as_mut() else {
+                    self.set_status_message(format!("Commit failed: {}", output.trim()));
+                    return;
+                };
+                session.running = false;
+                let previous_output = session.output_buffer.take();
+                let staged_split = session.staged_split;
+                if let Some(buffer_id) = previous_output {
+                    let _ = self.force_close_buffer(buffer_id);
+                }
+                self.show_git_commit_output(&output, staged_split);
+
+                let first_line = output
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .unwrap_or("git exited with an error");
+                self.set_status_message(format!(
+                    "Commit failed: {} (see *Commit Output*)",
+                    first_line
+                ));
+            }
+        }
+    }
+
+    /// Forget the commit being written when its message buffer is closed
+    pub(super) fn forget_git_commit_buffer(&mut self, buffer_id: BufferId) {
+        if self.is_git_commit_message(buffer_id) {
+            self.git_commit = None;
+        }
+    }
+
+    /// Show the output of a failed commit in the staged changes split
+    fn show_git_commit_output(&mut self, output: &str, staged_split: Option<SplitId>) {
+        let message_split = self.split_manager.active_split();
+        let staged_split = staged_split.and_then(|split_id| {
+            self.split_manager
+                .get_buffer_id(split_id)
+                .map(|buffer_id| (split_id, buffer_id))
+        });
+        if let Some((split_id, buffer_id)) = staged_split {
+            self.focus_split(split_id, buffer_id);
+        }
+        let output_buffer = self.create_read_only_text_buffer("*Commit Output*", output);
+        self.set_active_buffer(output_buffer);
+
+        let Some(session) = self.git_commit.as_mut() else {
+            return;
+        };
+        session.output_buffer = Some(output_buffer);
+        let message_buffer = session.message_buffer;
+        if staged_split.is_some() {
+            self.focus_split(message_split, message_buffer);
+        }
+    }
+
+    /// Close the buffers and split of a finished commit
+    fn close_git_commit_session(&mut self, session: GitCommitSession) {
+        if let Some(split_id) = session.staged_split {
+            if self.split_manager.get_buffer_id(split_id).is_some() {
+                self.handle_close_split(split_id);
+            }
+        }
+        let buffers = [
+            Some(session.message_buffer),
+            Some(session.staged_buffer),
+            session.output_buffer,
+        ];
+        for buffer_id in buffers.into_iter().flatten() {
+            if self.buffers.contains_key(&buffer_id) {
+                let _ = self.force_close_buffer(buffer_id);
+            }
+        }
+    }
+
+    /// Create a read-only buffer in the staged changes mode holding `text`,
+    /// highlighted as a diff
+    fn create_read_only_text_buffer(&mut self, name: &str, text: &str) -> BufferId {
+        let buffer_id = self.create_virtual_buffer(name.to_string(), STAGED_MODE.to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.set_language_from_name("staged.diff", &self.grammar_registry);
+            state.buffer.insert(0, text);
+            state.buffer.clear_modified();
+            state.editing_disabled = true;
+        }
+        buffer_id
+    }
+
+    /// Show `buffer_id` in a new split below the active one
+    fn open_in_split_below(&mut self, buffer_id: BufferId) -> Option<SplitId> {
+        self.save_current_split_view_state();
+        match self.split_manager.split_active(
+            SplitDirection::Horizontal,
+            buffer_id,
+            STAGED_SPLIT_RATIO,
+        ) {
+            Ok(split_id) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    buffer_id,
+                );
+                view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                self.split_view_states.insert(split_id, view_state);
+                self.split_manager.set_active_split(split_id);
+                Some(split_id)
+            }
+            Err(e) => {
+                tracing::error!("Failed to create staged changes split: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Links to the current file on the repository's web host.
+//!
+//! This module provides functionality to:
+//! - Copy a permalink to the selected lines (or the cursor line) at the
+//!   checked out commit
+//! - Open the same link in the browser
+//!
+//! The links are built by `services::git_remote` from the `origin` remote.
+
+use super::Editor;
+use crate::services::git_remote;
+use std::path::Path;
+
+impl Editor {
+    /// Link to the selected lines of the current file at the checked out
+    /// commit, or `None` after reporting why there is none
+    fn current_file_permalink(&mut self) -> Option<String> {
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            self.set_status_message("Buffer has no file".to_string());
+            return None;
+        };
+
+        let state = self.active_state_mut();
+        let cursor = *state.cursors.primary();
+        let (first, last) = match cursor.selection_range() {
+            Some(range) => {
+                let first = state.buffer.get_line_number(range.start);
+                let mut last = state.buffer.get_line_number(range.end);
+                // A selection ending at the start of a line doesn't include it
+                if last > first && state.buffer.line_start_offset(last) == Some(range.end) {
+                    last -= 1;
+                }
+                (first, last)
+            }
+            None => {
+                let line = state.buffer.get_line_number(cursor.position);
+                (line, line)
+            }
+        };
+
+        match git_remote::file_permalink(&path, (first + 1, last + 1)) {
+            Ok(url) => Some(url),
+            Err(e) => {
+                self.set_status_message(e);
+                None
+            }
+        }
+    }
+
+    /// Copy a permalink to the selected lines to the clipboard
+    pub fn copy_git_permalink(&mut self) {
+        if let Some(url) = self.current_file_permalink() {
+            self.clipboard.copy(url.clone());
+            self.set_status_message(format!("Copied {}", url));
+        }
+    }
+
+    /// Open the selected lines on the repository's web host
+    pub fn open_on_git_remote(&mut self) {
+        if let Some(url) = self.current_file_permalink() {
+            match git_remote::open_in_browser(&url) {
+                Ok(()) => self.set_status_message(format!("Opened {}", url)),
+                Err(e) => self.set_status_message(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Grammar installation handlers.
+//!
+//! This module provides functionality to:
+//! - Prompt for a grammar package and install it in the background
+//! - Rebuild the grammar registry so new grammars apply without a restart
+
+use super::Editor;
+use crate::primitives::grammar_registry::GrammarRegistry;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::grammar_installer::{self, GrammarSource};
+use crate::view::notification::NotificationLevel;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Start the "install grammar" prompt
+    pub fn start_install_grammar_prompt(&mut self) {
+        self.start_prompt(
+            "Install grammar (.vsix URL, owner/repo, or publisher.name): ".to_string(),
+            PromptType::InstallGrammar,
+        );
+    }
+
+    /// Download and install a grammar package on a background thread.
+    /// The result arrives as `AsyncMessage::GrammarInstalled`.
+    pub(super) fn install_grammar(&mut self, input: &str) {
+        let source = match GrammarSource::parse(input) {
+            Ok(source) => source,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
+        let Some(grammars_dir) = GrammarRegistry::grammars_directory() else {
+            self.set_status_message("No config directory for grammars".to_string());
+            return;
+        };
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+
+        self.set_status_message(format!(
+            "Installing grammar from {}...",
+            source.download_url()
+        ));
+        std::thread::spawn(move || {
+            let result = grammar_installer::install(&source, &grammars_dir);
+            let _ = sender.send(AsyncMessage::GrammarInstalled { result });
+        });
+    }
+
+    /// Handle the result of a background grammar install
+    pub(super) fn handle_grammar_installed(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(name) => {
+                self.reload_grammars();
+                self.set_status_message(format!("Installed grammar package '{}'", name));
+                self.notify(
+                    NotificationLevel::Info,
+                    format!("Installed grammar package '{}'", name),
+                );
+            }
+            Err(e) => {
+                tracing::warn!("Grammar install failed: {}", e);
+                self.set_status_message(format!("Grammar install failed: {}", e));
+                self.notify(
+                    NotificationLevel::Error,
+                    format!("Grammar install failed: {}", e),
+                );
+            }
+        }
+    }
+
+    /// Rebuild the grammar registry from disk and re-detect syntax
+    /// highlighting for every open file
+    pub fn reload_grammars(&mut self) {
+        self.grammar_registry = GrammarRegistry::for_editor();
+        for state in self.buffers.values_mut() {
+            // Virtual buffers without a file keep their name-based highlighter
+            if state.syntax_override.is_some() || state.buffer.file_path().is_some() {
+                let name = state.syntax_override.clone();
+                state.set_syntax_override(name, &self.grammar_registry);
+            }
+        }
+    }
+}
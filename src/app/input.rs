@@ -1,5 +1,9 @@
+use super::prompt_actions::DETECT_INDENTATION;
 use super::*;
+use crate::input::abbreviations::is_word_char;
+use crate::primitives::line_operations::LineOperation;
 use crate::services::plugins::hooks::HookArgs;
+use crate::services::styled_html::ExportFormat;
 impl Editor {
     /// Determine the current keybinding context based on UI state
     pub fn get_key_context(&self) -> crate::input::keybindings::KeyContext {
@@ -26,6 +30,18 @@ impl Editor {
         &mut self,
         code: crossterm::event::KeyCode,
         modifiers: crossterm::event::KeyModifiers,
+    ) -> std::io::Result<()> {
+        self.note_input_for_compaction();
+        let result = self.dispatch_key(code, modifiers);
+        // Signature help follows the cursor through the call
+        self.update_signature_help();
+        result
+    }
+
+    fn dispatch_key(
+        &mut self,
+        code: crossterm::event::KeyCode,
+        modifiers: crossterm::event::KeyModifiers,
     ) -> std::io::Result<()> {
         use crate::input::keybindings::Action;
 
@@ -37,9 +53,21 @@ impl Editor {
             modifiers
         );
 
+        // Shifted characters reported by the kitty protocol become their unshifted key
+        let (code, modifiers) = crate::input::keyboard::normalize_key(code, modifiers);
+
         // Create key event for dispatch methods
         let key_event = crossterm::event::KeyEvent::new(code, modifiers);
 
+        // The quit dialog, tab overflow dropdown and context menus take keys
+        // while open
+        if self.handle_quit_dialog_key(key_event)
+            || self.handle_tab_overflow_key(key_event)
+            || self.handle_context_menu_key(key_event)
+        {
+            return Ok(());
+        }
+
         // Try terminal input dispatch first (handles terminal mode and re-entry)
         if self.dispatch_terminal_input(&key_event).is_some() {
             return Ok(());
@@ -64,9 +92,21 @@ impl Editor {
                 .top()
                 .is_some_and(|p| p.transient);
 
+            // The hover key focuses the hover popup instead of dismissing it
+            if is_transient_popup
+                && self.keybindings.resolve(&key_event, self.key_context) == Action::LspHover
+                && self.focus_hover_popup()
+            {
+                return Ok(());
+            }
+
             if is_transient_popup {
-                // Dismiss the popup on any key press
-                self.hide_popup();
+                // Dismiss the popup on any key press; signature help comes
+                // back afterwards while the cursor stays inside the call
+                let dismiss = code == crossterm::event::KeyCode::Esc;
+                if !self.hide_signature_help_popup(dismiss) {
+                    self.hide_popup();
+                }
                 tracing::debug!("Dismissed transient popup on key press");
                 // Recalculate context now that popup is gone
                 context = self.get_key_context();
@@ -158,17 +198,33 @@ impl Editor {
 
         // Record action to macro if recording
         self.record_macro_action(&action);
+        crate::services::crash_report::record_action(&action);
+
+        // Only the keystroke right after an expansion can revert it
+        let last_abbreviation = self.last_abbreviation.take();
 
         match action {
             Action::Quit => self.quit(),
             Action::Save => {
-                // Check if buffer has a file path - if not, redirect to SaveAs
-                if self.active_state().buffer.file_path().is_none() {
+                // Buffers opened from a provider are written back through it
+                if self.is_vfs_buffer(self.active_buffer()) {
+                    self.save_vfs_buffer(self.active_buffer());
+                } else if self.is_git_commit_message(self.active_buffer()) {
+                    // Saving the commit message commits it
+                    self.finish_git_commit();
+                } else if self.active_state().buffer.file_path().is_none() {
+                    // No file path - redirect to SaveAs
                     self.start_prompt_with_initial_text(
                         "Save as: ".to_string(),
                         PromptType::SaveFileAs,
                         String::new(),
                     );
+                } else if self.active_state().loading {
+                    self.set_status_message(
+                        "Can't save while the file is still loading".to_string(),
+                    );
+                } else if self.background_saves.contains_key(&self.active_buffer()) {
+                    self.set_status_message("The buffer is still saving".to_string());
                 } else if self.check_save_conflict().is_some() {
                     // Check if file was modified externally since we opened/saved it
                     self.start_prompt(
@@ -176,7 +232,7 @@ impl Editor {
                         PromptType::ConfirmSaveConflict,
                     );
                 } else {
-                    self.save()?;
+                    self.save_or_offer_root()?;
                 }
             }
             Action::SaveAs => {
@@ -208,6 +264,8 @@ impl Editor {
                 self.start_prompt("Switch project: ".to_string(), PromptType::SwitchProject);
                 self.init_folder_open_state();
             }
+            Action::AddWorkspaceFolder => self.start_add_workspace_folder_prompt(),
+            Action::RemoveWorkspaceFolder => self.start_remove_workspace_folder_prompt(),
             Action::GotoLine => self.start_prompt("Go to line: ".to_string(), PromptType::GotoLine),
             Action::New => {
                 self.new_buffer();
@@ -230,6 +288,16 @@ impl Editor {
             Action::CloseTab => {
                 self.close_tab();
             }
+            Action::CloseSavedBuffers => self.close_saved_buffers(),
+            Action::NewScratchBuffer => self.start_new_scratch_prompt(),
+            Action::OpenScratchBuffer => self.start_open_scratch_prompt(),
+            Action::BufferList => self.show_buffer_list(),
+            Action::BufferListJump => self.buffer_list_jump(),
+            Action::BufferListMark => self.buffer_list_toggle_mark(),
+            Action::BufferListClose => self.buffer_list_close(),
+            Action::BufferListSave => self.buffer_list_save(),
+            Action::BufferListSaveAll => self.buffer_list_save_all(),
+            Action::BufferListRefresh => self.buffer_list_refresh(),
             Action::Revert => {
                 // Check if buffer has unsaved changes - prompt for confirmation
                 if self.active_state().buffer.is_modified() {
@@ -247,13 +315,55 @@ impl Editor {
             Action::ToggleAutoRevert => {
                 self.toggle_auto_revert();
             }
+            Action::ShowFileWatcherStatus => self.show_file_watcher_status(),
+            Action::ShowSystemHealth => self.show_system_health(),
+            Action::RenameFile => self.rename_file_prompt(),
+            Action::DuplicateFile => self.duplicate_file_prompt(),
+            Action::TrashFile => self.trash_file_prompt(),
+            Action::UndoFileOperation => self.undo_file_operation(),
+            Action::RenameSymbol => self.rename_symbol()?,
+            Action::RenamePreviewToggle => self.toggle_rename_preview_change(),
+            Action::RenamePreviewApply => self.apply_rename_preview()?,
+            Action::ReferencesJump => self.jump_to_reference()?,
+            Action::RunTask => self.start_run_task_prompt(),
+            Action::StopTask => self.stop_task(),
+            Action::TaskGotoError => self.goto_task_error_at_cursor(),
+            Action::QuickfixNext => self.cycle_quickfix(true),
+            Action::QuickfixPrevious => self.cycle_quickfix(false),
+            Action::QuickfixShow => self.show_quickfix_list(),
+            Action::QuickfixFromBuffer => self.quickfix_from_buffer(),
+            Action::QuickfixJump => self.quickfix_jump(),
+            Action::ReplStart => self.start_repl_prompt(),
+            Action::ReplSend => self.repl_send_line_or_selection(),
+            Action::ReplSendCell => self.repl_send_cell(),
+            Action::SortLinesAscending => self.apply_line_operation(LineOperation::SortAscending),
+            Action::SortLinesDescending => self.apply_line_operation(LineOperation::SortDescending),
+            Action::SortLinesNumeric => self.apply_line_operation(LineOperation::SortNumeric),
+            Action::SortLinesUnique => self.apply_line_operation(LineOperation::SortUnique),
+            Action::ReverseLines => self.apply_line_operation(LineOperation::Reverse),
+            Action::ShuffleLines => self.apply_line_operation(LineOperation::Shuffle),
+            Action::JoinLines => self.apply_line_operation(LineOperation::Join),
+            Action::AlignOn => self.start_align_prompt(),
+            Action::ReflowParagraph => self.reflow_paragraph(),
+            Action::RemoveDuplicateLines => {
+                self.apply_line_operation(LineOperation::RemoveDuplicates)
+            }
             Action::FormatBuffer => {
                 if let Err(e) = self.format_buffer() {
                     self.set_status_message(format!("Format failed: {}", e));
                 }
             }
+            Action::FormatSelection => {
+                if let Err(e) = self.format_selection() {
+                    self.set_status_message(format!("Format failed: {}", e));
+                }
+            }
             Action::Copy => self.copy_selection(),
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
+            Action::ExportHtml => self.start_export_prompt(ExportFormat::Html),
+            Action::ExportAnsi => self.start_export_prompt(ExportFormat::Ansi),
+            Action::ShowHexView => self.show_hex_view(),
+            Action::ShowBufferStats => self.show_buffer_stats(),
             Action::Cut => {
                 if self.is_editing_disabled() {
                     self.set_status_message("Editing disabled in this buffer".to_string());
@@ -268,18 +378,54 @@ impl Editor {
                 }
                 self.paste()
             }
+            Action::PasteFromHistory | Action::PasteFromHistoryPlain => {
+                if self.is_editing_disabled() {
+                    self.set_status_message("Editing disabled in this buffer".to_string());
+                    return Ok(());
+                }
+                self.show_clipboard_history(action == Action::PasteFromHistoryPlain)
+            }
             Action::Undo => {
                 self.handle_undo();
             }
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::LocalHistory => self.show_local_history(),
+            Action::LocalHistoryDiff => self.local_history_diff(),
+            Action::LocalHistoryRestore => self.local_history_restore(),
+            Action::OpenFileAtRevision => self.start_file_revision_prompt(false),
+            Action::DiffWithRevision => self.start_file_revision_prompt(true),
+            Action::GitCheckoutBranch => self.start_git_checkout_prompt(),
+            Action::GitCommit => self.start_git_commit(false),
+            Action::GitAmendCommit => self.start_git_commit(true),
+            Action::GitCopyPermalink => self.copy_git_permalink(),
+            Action::GitOpenOnRemote => self.open_on_git_remote(),
+            Action::CollabShare => self.start_collab_share_prompt(),
+            Action::CollabJoin => self.start_collab_join_prompt(),
+            Action::CollabStop => self.stop_collab(),
+            Action::GenerateTags => self.generate_tags(),
             Action::ShowHelp => {
                 self.open_help_manual();
             }
             Action::ShowKeyboardShortcuts => {
                 self.open_keyboard_shortcuts();
             }
+            Action::ShowKeyboardProtocol => {
+                self.set_status_message(self.keyboard_protocol.describe());
+            }
+            Action::ShowNotifications => {
+                self.show_notifications_in_buffer();
+            }
+            Action::ShowPlugins => {
+                self.show_plugins_in_buffer();
+            }
+            Action::ShowPluginLog => {
+                self.show_plugin_log_in_buffer();
+            }
+            Action::DismissNotifications => {
+                self.dismiss_notifications();
+            }
             Action::CommandPalette => {
                 // Toggle command palette: close if already open, otherwise open it
                 if let Some(prompt) = &self.prompt {
@@ -289,6 +435,8 @@ impl Editor {
                     }
                 }
 
+                self.palette_cache = Default::default();
+
                 // Use the current context for filtering commands
                 let suggestions = self.command_registry.read().unwrap().filter(
                     "",
@@ -392,12 +540,19 @@ impl Editor {
             Action::DumpConfig => {
                 self.dump_config();
             }
+            Action::ConfigDoctor => self.show_config_doctor(),
             Action::SelectTheme => {
                 self.start_select_theme_prompt();
             }
             Action::SelectKeybindingMap => {
                 self.start_select_keybinding_map_prompt();
             }
+            Action::InstallGrammar => {
+                self.start_install_grammar_prompt();
+            }
+            Action::ChangeLanguageMode => {
+                self.start_change_language_prompt();
+            }
             Action::Search => {
                 // If already in a search-related prompt, Ctrl+F acts like Enter (confirm search)
                 let is_search_prompt = self.prompt.as_ref().is_some_and(|p| {
@@ -437,6 +592,7 @@ impl Editor {
             Action::FindPrevious => {
                 self.find_previous();
             }
+            Action::SelectAllMatches => self.select_all_matches(),
             Action::AddCursorNextMatch => self.add_cursor_at_next_match(),
             Action::AddCursorAbove => self.add_cursor_above(),
             Action::AddCursorBelow => self.add_cursor_below(),
@@ -482,8 +638,11 @@ impl Editor {
             Action::IncreaseSplitSize => self.adjust_split_size(0.05),
             Action::DecreaseSplitSize => self.adjust_split_size(-0.05),
             Action::ToggleMaximizeSplit => self.toggle_maximize_split(),
+            Action::SetSplitWorkingDir => self.set_split_working_dir_from_buffer(),
+            Action::ClearSplitWorkingDir => self.clear_split_working_dir(),
             Action::ToggleFileExplorer => self.toggle_file_explorer(),
             Action::ToggleMenuBar => self.toggle_menu_bar(),
+            Action::ToggleZenMode => self.toggle_zen_mode(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
@@ -504,6 +663,9 @@ impl Editor {
             Action::SetLineEnding => {
                 self.start_set_line_ending_prompt();
             }
+            Action::SetIndentation => {
+                self.start_set_indentation_prompt();
+            }
             Action::ToggleIndentationStyle => {
                 if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
                     state.use_tabs = !state.use_tabs;
@@ -614,9 +776,6 @@ impl Editor {
                 }
             }
 
-            Action::SmartHome => {
-                self.smart_home();
-            }
             Action::ToggleComment => {
                 self.toggle_comment();
             }
@@ -746,6 +905,9 @@ impl Editor {
             Action::ListMacros => {
                 self.list_macros_in_buffer();
             }
+            Action::ListAbbreviations => {
+                self.list_abbreviations_in_buffer();
+            }
             Action::PromptRecordMacro => {
                 self.start_prompt("Record macro (0-9): ".to_string(), PromptType::RecordMacro);
             }
@@ -774,6 +936,12 @@ impl Editor {
                     self.set_status_message("Editing disabled in this buffer".to_string());
                     return Ok(());
                 }
+                // Backspace right after an abbreviation expansion reverts it
+                if let Some(expansion) = last_abbreviation {
+                    if self.revert_abbreviation(expansion) {
+                        return Ok(());
+                    }
+                }
                 // Normal backspace handling
                 if let Some(events) = self.action_to_events(Action::DeleteBackward) {
                     if events.len() > 1 {
@@ -874,6 +1042,12 @@ impl Editor {
                 // Run shell command on buffer/selection, replace content
                 self.start_shell_command_prompt(true);
             }
+            Action::RunShellCommand => {
+                self.start_run_shell_command_prompt();
+            }
+            Action::Suspend => {
+                self.request_suspend();
+            }
             Action::OpenSettings => {
                 self.open_settings();
             }
@@ -947,6 +1121,15 @@ impl Editor {
             Action::PopupCancel => {
                 self.handle_popup_cancel();
             }
+            Action::PopupCopy => {
+                self.handle_popup_copy();
+            }
+            Action::PopupPin => {
+                self.handle_popup_pin();
+            }
+            Action::ClosePinnedPopups => {
+                self.close_pinned_popups();
+            }
             Action::InsertChar(c) => {
                 if self.is_prompting() {
                     return self.handle_insert_char_prompt(c);
@@ -1025,7 +1208,7 @@ impl Editor {
             {
                 // Scroll the file explorer
                 if let Some(explorer) = &mut self.file_explorer {
-                    let visible = explorer.tree().get_visible_nodes();
+                    let visible = explorer.shown_nodes();
                     if visible.is_empty() {
                         return Ok(());
                     }
@@ -1636,6 +1819,13 @@ impl Editor {
             }
             state.apply(&event);
 
+            // A sticky scroll header lies above the viewport: scroll to it
+            if target_position < fallback {
+                if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                    view_state.viewport.clear_skip_ensure_visible();
+                }
+            }
+
             // Track position history
             if !self.in_navigation {
                 self.position_history
@@ -1758,6 +1948,47 @@ impl Editor {
         }
     }
 
+    /// Start the indentation selection prompt
+    fn start_set_indentation_prompt(&mut self) {
+        let state = self.active_state();
+        let current = if state.use_tabs {
+            format!("Tabs: {}", state.tab_size)
+        } else {
+            format!("Spaces: {}", state.tab_size)
+        };
+
+        let mut options: Vec<String> = ["Spaces", "Tabs"]
+            .iter()
+            .flat_map(|style| [2, 4, 8].map(|width| format!("{}: {}", style, width)))
+            .collect();
+        if !options.contains(&current) {
+            options.push(current.clone());
+        }
+        options.push(DETECT_INDENTATION.to_string());
+
+        let suggestions: Vec<crate::input::commands::Suggestion> = options
+            .iter()
+            .map(|option| crate::input::commands::Suggestion {
+                text: option.clone(),
+                description: (*option == current).then(|| "current".to_string()),
+                value: Some(option.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        let mut prompt = crate::view::prompt::Prompt::with_suggestions(
+            "Indentation: ".to_string(),
+            PromptType::SetIndentation,
+            suggestions,
+        );
+        prompt.selected_suggestion = options.iter().position(|option| *option == current);
+        prompt.input = current;
+        prompt.cursor_pos = prompt.input.len();
+        self.prompt = Some(prompt);
+    }
+
     /// Start the theme selection prompt with available themes
     fn start_select_theme_prompt(&mut self) {
         let available_themes = crate::view::theme::Theme::available_themes();
@@ -2112,24 +2343,48 @@ impl Editor {
         // Cancel any pending LSP requests since the text is changing
         self.cancel_pending_lsp_requests();
 
+        // A word boundary expands the abbreviation before it; snippets
+        // consume the boundary character
+        if !is_word_char(c) && self.expand_abbreviation() {
+            return Ok(());
+        }
+
         if let Some(events) = self.action_to_events(Action::InsertChar(c)) {
+            // Typed text joins the undo step of the word being typed
+            let now = self.time_source.now();
+            let timeout =
+                std::time::Duration::from_millis(self.config.editor.undo_group_timeout_ms);
+            let word_char = is_word_char(c);
             // Wrap multiple events (multi-cursor) in a Batch for atomic undo
             if events.len() > 1 {
                 let batch = Event::Batch {
                     events: events.clone(),
                     description: format!("Insert '{}'", c),
                 };
-                self.active_event_log_mut().append(batch.clone());
+                self.active_event_log_mut()
+                    .append_typed(batch.clone(), word_char, now, timeout);
                 self.apply_event_to_active_buffer(&batch);
             } else {
                 // Single cursor - no need for batch
                 for event in events {
-                    self.active_event_log_mut().append(event.clone());
+                    // Replacing a selection is its own undo step
+                    if matches!(event, Event::Insert { .. }) {
+                        self.active_event_log_mut().append_typed(
+                            event.clone(),
+                            word_char,
+                            now,
+                            timeout,
+                        );
+                    } else {
+                        self.active_event_log_mut().append(event.clone());
+                    }
                     self.apply_event_to_active_buffer(&event);
                 }
             }
         }
 
+        self.include_abbreviation_trigger();
+
         // Auto-trigger signature help on '(' and ','
         if c == '(' || c == ',' {
             let _ = self.request_signature_help();
@@ -2165,7 +2420,43 @@ impl Editor {
             return Ok(());
         }
 
-        if let Some(events) = self.action_to_events(action) {
+        // Moves that can run into the start or end of the buffer
+        let is_bounded_move = matches!(
+            action,
+            Action::MoveLeft
+                | Action::MoveRight
+                | Action::MoveUp
+                | Action::MoveDown
+                | Action::MoveWordLeft
+                | Action::MoveWordRight
+                | Action::MovePageUp
+                | Action::MovePageDown
+        );
+
+        // With line wrap, Up and Down move by screen rows
+        let events = self
+            .wrapped_vertical_move_events(&action)
+            .or_else(|| self.action_to_events(action));
+
+        // A move that leaves every cursor where it was hit the buffer's edge
+        let moved = events.as_ref().is_some_and(|events| {
+            events.iter().any(|event| {
+                !matches!(
+                    event,
+                    Event::MoveCursor {
+                        old_position,
+                        new_position,
+                        old_anchor,
+                        new_anchor,
+                        ..
+                    } if old_position == new_position && old_anchor == new_anchor
+                )
+            })
+        });
+        if is_bounded_move && !moved {
+            self.reject();
+        }
+        if let Some(events) = events {
             // Wrap multiple events (multi-cursor) in a Batch for atomic undo
             if events.len() > 1 {
                 let batch = Event::Batch {
@@ -219,6 +219,8 @@ impl Editor {
                 self.update_prompt_suggestions();
             }
 
+            DeferredAction::Reject => self.reject(),
+
             // File browser actions
             DeferredAction::FileBrowserSelectPrev => {
                 if let Some(state) = &mut self.file_open_state {
@@ -0,0 +1,91 @@
+//! Per-buffer language mode selection.
+//!
+//! This module provides functionality to:
+//! - Pick a syntax for the active buffer from all grammars in the registry
+//! - Restore automatic detection from the file path and contents
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Suggestion that clears the override and detects the syntax from the file
+const AUTO_DETECT_LANGUAGE: &str = "Auto Detect";
+
+impl Editor {
+    /// Start the "Change Language Mode" prompt for the active buffer
+    pub fn start_change_language_prompt(&mut self) {
+        let state = self.active_state();
+        let has_override = state.syntax_override.is_some();
+        let current = state.syntax_display_name();
+
+        let mut names: Vec<&str> = self.grammar_registry.available_syntaxes();
+        names.sort_by_key(|name| name.to_lowercase());
+        names.dedup();
+
+        let mut suggestions = vec![Suggestion {
+            text: AUTO_DETECT_LANGUAGE.to_string(),
+            description: Some("Detect from the file name and contents".to_string()),
+            value: Some(AUTO_DETECT_LANGUAGE.to_string()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        }];
+        suggestions.extend(names.iter().map(|name| Suggestion {
+            text: name.to_string(),
+            description: if *name == current {
+                Some("(current)".to_string())
+            } else {
+                None
+            },
+            value: Some(name.to_string()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        }));
+
+        let current_index = if has_override {
+            suggestions
+                .iter()
+                .position(|s| s.text == current)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        self.prompt = Some(Prompt::with_suggestions(
+            "Language mode: ".to_string(),
+            PromptType::SelectLanguage,
+            suggestions,
+        ));
+
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(current_index);
+        }
+    }
+
+    /// Apply a language mode chosen in the prompt to the active buffer
+    pub(super) fn apply_language_override(&mut self, input: &str) {
+        let input = input.trim();
+        let name = if input.is_empty() || input == AUTO_DETECT_LANGUAGE {
+            None
+        } else {
+            Some(input.to_string())
+        };
+
+        let buffer_id = self.active_buffer();
+        let registry = self.grammar_registry.clone();
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if !state.set_syntax_override(name.clone(), &registry) {
+            self.set_status_message(format!("Unknown language: {}", input));
+            return;
+        }
+
+        let display_name = state.syntax_display_name();
+        self.set_status_message(match name {
+            Some(_) => format!("Language mode set to {}", display_name),
+            None => format!("Language mode detected as {}", display_name),
+        });
+    }
+}
@@ -0,0 +1,269 @@
+//! Line operations on the selection.
+//!
+//! Sorting, reversing, shuffling, joining, removing duplicate lines and
+//! aligning on a delimiter work on the lines the selection touches, or on
+//! the whole buffer when nothing is selected ("Join Lines" then joins the
+//! cursor line with the next one). "Reflow Paragraph" rewraps the
+//! paragraphs the selection touches, or the one at the cursor. Each
+//! operation is applied as a single undoable edit.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::Event;
+use crate::primitives::line_operations::{
+    align_lines, apply_line_operation, parse_align_delimiter, LineOperation,
+};
+use crate::primitives::prose::{paragraph_at, reflow_lines};
+use crate::view::prompt::{Prompt, PromptType};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::ops::Range;
+
+/// Number of aligned lines shown while typing the delimiter
+const ALIGN_PREVIEW_LINES: usize = 8;
+
+/// The lines an operation applies to
+struct LineBlock {
+    /// Byte range of the lines, without the final line ending
+    range: Range<usize>,
+    text: String,
+    line_ending: &'static str,
+    /// Whether the lines came from a selection
+    selected: bool,
+}
+
+impl LineBlock {
+    fn lines(&self) -> Vec<&str> {
+        self.text.split(self.line_ending).collect()
+    }
+}
+
+impl Editor {
+    /// The lines the selection touches, or the whole buffer (or the cursor
+    /// line and the next, for `join`) when nothing is selected
+    fn selected_line_block(&mut self, join: bool) -> LineBlock {
+        let state = self.active_state_mut();
+        let cursor = *state.cursors.primary();
+        let selection = cursor.selection_range();
+        let (first_line, last_line) = match &selection {
+            Some(range) => {
+                let first = state.buffer.get_line_number(range.start);
+                let mut last = state.buffer.get_line_number(range.end);
+                // A selection ending at the start of a line doesn't include it
+                if last > first && state.buffer.line_start_offset(last) == Some(range.end) {
+                    last -= 1;
+                }
+                (first, last)
+            }
+            None if join => {
+                let line = state.buffer.get_line_number(cursor.position);
+                (line, line + 1)
+            }
+            None => (0, usize::MAX),
+        };
+        self.line_block(first_line, last_line, selection.is_some())
+    }
+
+    /// Lines `first_line` to `last_line` of the active buffer
+    fn line_block(&mut self, first_line: usize, last_line: usize, selected: bool) -> LineBlock {
+        let state = self.active_state_mut();
+        let line_ending = state.buffer.line_ending().as_str();
+        let buffer_len = state.buffer.len();
+        let start = state.buffer.line_start_offset(first_line).unwrap_or(0);
+        let mut end = match last_line.checked_add(1) {
+            Some(next) => state
+                .buffer
+                .line_start_offset(next)
+                .map_or(buffer_len, |offset| offset - line_ending.len()),
+            None => buffer_len,
+        };
+        let mut text = state.get_text_range(start, end);
+        // The final line ending stays where it is
+        if text.ends_with(line_ending) {
+            text.truncate(text.len() - line_ending.len());
+            end -= line_ending.len();
+        }
+
+        LineBlock {
+            range: start..end,
+            text,
+            line_ending,
+            selected,
+        }
+    }
+
+    /// Replace `block` with `new_text` as one undoable edit
+    ///
+    /// Selected lines stay selected so operations can be chained; otherwise
+    /// the cursor keeps its position.
+    fn replace_line_block(&mut self, block: LineBlock, new_text: String, description: String) {
+        let state = self.active_state_mut();
+        let cursor = *state.cursors.primary();
+        let cursor_id = state.cursors.primary_id();
+        let new_len = state.buffer.len() - block.text.len() + new_text.len();
+
+        let start = block.range.start;
+        let inserted_end = start + new_text.len();
+        let (new_position, new_anchor) = if block.selected {
+            (inserted_end, Some(start))
+        } else {
+            (cursor.position.min(new_len), None)
+        };
+        let mut events = vec![
+            Event::Delete {
+                range: block.range,
+                deleted_text: block.text,
+                cursor_id,
+            },
+            Event::Insert {
+                position: start,
+                text: new_text,
+                cursor_id,
+            },
+        ];
+        if new_position != inserted_end || new_anchor.is_some() {
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: inserted_end,
+                new_position,
+                old_anchor: None,
+                new_anchor,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            });
+        }
+
+        let batch = Event::Batch {
+            events,
+            description,
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    /// Apply `op` to the selected lines of the active buffer
+    pub fn apply_line_operation(&mut self, op: LineOperation) {
+        if self.is_editing_disabled() {
+            self.set_status_message("Editing disabled in this buffer".to_string());
+            return;
+        }
+
+        let block = self.selected_line_block(op == LineOperation::Join);
+        let lines = block.lines();
+        let line_count = lines.len();
+        if line_count < 2 {
+            self.set_status_message("Select at least two lines".to_string());
+            return;
+        }
+        let seed = RandomState::new().hash_one(block.range.start);
+        let new_text = apply_line_operation(op, &lines, seed).join(block.line_ending);
+        if new_text == block.text {
+            self.set_status_message(format!("{} lines unchanged", line_count));
+            return;
+        }
+
+        self.replace_line_block(block, new_text, format!("{} lines", op.verb()));
+        self.set_status_message(format!("{} {} lines", op.verb(), line_count));
+    }
+
+    /// Prompt for the delimiter to align the selected lines on
+    pub fn start_align_prompt(&mut self) {
+        if self.is_editing_disabled() {
+            self.set_status_message("Editing disabled in this buffer".to_string());
+            return;
+        }
+        self.prompt = Some(Prompt::new(
+            "Align on (text or /regex/): ".to_string(),
+            PromptType::AlignOn,
+        ));
+    }
+
+    /// Show the first aligned lines as the prompt's suggestions while the
+    /// delimiter is typed
+    pub(super) fn update_align_preview(&mut self, input: &str) {
+        let suggestions = if input.trim().is_empty() {
+            Vec::new()
+        } else {
+            match parse_align_delimiter(input) {
+                Ok(delimiter) => {
+                    let block = self.selected_line_block(false);
+                    align_lines(&block.lines(), &delimiter)
+                        .into_iter()
+                        .take(ALIGN_PREVIEW_LINES)
+                        .map(Suggestion::new)
+                        .collect()
+                }
+                Err(e) => {
+                    let mut suggestion = Suggestion::new(e);
+                    suggestion.disabled = true;
+                    vec![suggestion]
+                }
+            }
+        };
+        if let Some(prompt) = &mut self.prompt {
+            prompt.suggestions = suggestions;
+            prompt.selected_suggestion = None;
+        }
+    }
+
+    /// Align the selected lines on every occurrence of the delimiter `spec`
+    pub(super) fn align_selection(&mut self, spec: &str) {
+        let delimiter = match parse_align_delimiter(spec) {
+            Ok(delimiter) => delimiter,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
+        let block = self.selected_line_block(false);
+        let lines = block.lines();
+        let line_count = lines.len();
+        let new_text = align_lines(&lines, &delimiter).join(block.line_ending);
+        if new_text == block.text {
+            self.set_status_message(format!("{} lines unchanged", line_count));
+            return;
+        }
+
+        let spec = spec.trim().to_string();
+        self.replace_line_block(block, new_text, format!("Align on '{}'", spec));
+        self.set_status_message(format!("Aligned {} lines on '{}'", line_count, spec));
+    }
+
+    /// Rewrap the paragraphs the selection touches, or the one at the
+    /// cursor, to `editor.reflow_width` columns
+    pub fn reflow_paragraph(&mut self) {
+        if self.is_editing_disabled() {
+            self.set_status_message("Editing disabled in this buffer".to_string());
+            return;
+        }
+
+        let estimated_line_length = self.config.editor.estimated_line_length;
+        let state = self.active_state_mut();
+        let cursor = *state.cursors.primary();
+        let block = if cursor.selection_range().is_some() {
+            self.selected_line_block(false)
+        } else {
+            let Some(paragraph) =
+                paragraph_at(&mut state.buffer, cursor.position, estimated_line_length)
+            else {
+                self.set_status_message("No paragraph at the cursor".to_string());
+                return;
+            };
+            let first_line = state.buffer.get_line_number(paragraph.start);
+            let last_line = state
+                .buffer
+                .get_line_number(paragraph.end.saturating_sub(1).max(paragraph.start));
+            self.line_block(first_line, last_line, false)
+        };
+
+        let width = self.config.editor.reflow_width;
+        let tab_size = self.active_state().tab_size;
+        let new_text = reflow_lines(&block.lines(), width, tab_size).join(block.line_ending);
+        if new_text == block.text {
+            self.set_status_message("Paragraph already fits".to_string());
+            return;
+        }
+        self.replace_line_block(block, new_text, "Reflow paragraph".to_string());
+        self.set_status_message(format!("Reflowed to {} columns", width));
+    }
+}
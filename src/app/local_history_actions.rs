@@ -0,0 +1,532 @@
+//! Local history.
+//!
+//! This module provides functionality to:
+//! - Remember the points a buffer was auto-saved for recovery at
+//! - List earlier versions of a buffer in a split: those points, plus the
+//!   undo checkpoints where editing paused for a few seconds
+//! - Compare a version with the buffer in the diff viewer, or restore it as
+//!   one undoable edit
+//!
+//! Versions are rebuilt from the undo history, so they last as long as it
+//! does (see `EventLog::content_at`).
+
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::{BufferId, Event, EventLog, LogEntry, SplitDirection, SplitId};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::split::SplitViewState;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Mode of the local history list buffer
+const LIST_MODE: &str = "local-history";
+
+/// Share of the height kept by the split the list is opened from
+const LIST_SPLIT_RATIO: f32 = 0.6;
+
+/// Edits further apart than this (in milliseconds) start a new checkpoint
+const CHECKPOINT_PAUSE_MS: u64 = 3000;
+
+/// Auto-save points remembered per buffer; the oldest are dropped first
+const MAX_AUTOSAVE_SNAPSHOTS: usize = 50;
+
+/// A point of a buffer's undo history that was auto-saved for recovery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AutosaveSnapshot {
+    /// Number of events applied to the buffer at that point
+    index: usize,
+    /// Timestamp of the event before the point, to notice when the history
+    /// it was taken from has been replaced by new edits after an undo
+    previous_timestamp: Option<u64>,
+    /// When it was saved (milliseconds since epoch)
+    saved_at: u64,
+}
+
+impl AutosaveSnapshot {
+    fn is_valid(&self, entries: &[LogEntry]) -> bool {
+        match self.index.checked_sub(1) {
+            None => true,
+            Some(previous) => {
+                entries.get(previous).map(|entry| entry.timestamp) == self.previous_timestamp
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionKind {
+    Opened,
+    Checkpoint,
+    Autosave,
+}
+
+/// A version of the buffer shown in the list
+#[derive(Debug, Clone)]
+struct Version {
+    kind: VersionKind,
+    /// Number of events applied to the buffer in this version
+    index: usize,
+    /// Milliseconds since epoch; 0 for the version the buffer was opened with
+    timestamp: u64,
+}
+
+impl Version {
+    fn label(&self) -> String {
+        match self.kind {
+            VersionKind::Opened => "as opened".to_string(),
+            VersionKind::Checkpoint | VersionKind::Autosave => format_timestamp(self.timestamp),
+        }
+    }
+}
+
+/// The list buffer, the buffer whose versions it shows, and the split that
+/// buffer was shown in
+pub(crate) struct LocalHistoryPanel {
+    buffer_id: BufferId,
+    source_buffer: BufferId,
+    source_split: SplitId,
+    versions: Vec<Version>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn format_timestamp(millis: u64) -> String {
+    let datetime: chrono::DateTime<chrono::Local> =
+        (UNIX_EPOCH + Duration::from_millis(millis)).into();
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Versions of a buffer, newest first: the end of every burst of edits, the
+/// still valid auto-save points, and the content it was opened with
+fn collect_versions(entries: &[LogEntry], autosaves: &[AutosaveSnapshot]) -> Vec<Version> {
+    let writes: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].event.modifies_buffer())
+        .collect();
+    if writes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut versions = vec![Version {
+        kind: VersionKind::Opened,
+        index: 0,
+        timestamp: 0,
+    }];
+    for (n, &i) in writes.iter().enumerate() {
+        let timestamp = entries[i].timestamp;
+        let ends_burst = writes.get(n + 1).is_none_or(|&next| {
+            entries[next].timestamp.saturating_sub(timestamp) > CHECKPOINT_PAUSE_MS
+        });
+        if ends_burst {
+            versions.push(Version {
+                kind: VersionKind::Checkpoint,
+                index: i + 1,
+                timestamp,
+            });
+        }
+    }
+    versions.extend(
+        autosaves
+            .iter()
+            .filter(|snapshot| snapshot.is_valid(entries))
+            .map(|snapshot| Version {
+                kind: VersionKind::Autosave,
+                index: snapshot.index,
+                timestamp: snapshot.saved_at,
+            }),
+    );
+    versions.sort_by_key(|version| std::cmp::Reverse((version.timestamp, version.index)));
+    versions
+}
+
+impl Editor {
+    /// Remember the current point of `buffer_id`'s undo history as
+    /// auto-saved
+    pub(super) fn record_autosave_snapshot(&mut self, buffer_id: BufferId) {
+        let Some(log) = self.event_logs.get(&buffer_id) else {
+            return;
+        };
+        let index = log.current_index();
+        let previous_timestamp = index
+            .checked_sub(1)
+            .map(|previous| log.entries()[previous].timestamp);
+        let snapshots = self.autosave_snapshots.entry(buffer_id).or_default();
+        if snapshots.last().is_some_and(|last| {
+            last.index == index && last.previous_timestamp == previous_timestamp
+        }) {
+            return;
+        }
+        snapshots.push(AutosaveSnapshot {
+            index,
+            previous_timestamp,
+            saved_at: now_millis(),
+        });
+        if snapshots.len() > MAX_AUTOSAVE_SNAPSHOTS {
+            snapshots.remove(0);
+        }
+    }
+
+    /// Show the versions of the active buffer in a split below the current
+    /// one
+    pub fn show_local_history(&mut self) {
+        let source_buffer = match &self.local_history_panel {
+            Some(panel) if panel.buffer_id == self.active_buffer() => panel.source_buffer,
+            _ => self.active_buffer(),
+        };
+        let versions = self.buffer_versions(source_buffer);
+        if versions.is_empty() {
+            self.set_status_message("No local history for this buffer".to_string());
+            return;
+        }
+
+        // Reuse the list if it is already shown
+        if let Some(panel) = self.local_history_panel.as_mut() {
+            let buffer_id = panel.buffer_id;
+            if let Some(&split_id) = self.split_manager.splits_for_buffer(buffer_id).first() {
+                if panel.source_buffer != source_buffer {
+                    panel.source_buffer = source_buffer;
+                    panel.source_split = self.split_manager.active_split();
+                }
+                panel.versions = versions;
+                self.refresh_local_history_panel();
+                self.save_current_split_view_state();
+                self.split_manager.set_active_split(split_id);
+                self.restore_current_split_view_state();
+                return;
+            }
+            if self.buffers.contains_key(&buffer_id) {
+                let _ = self.force_close_buffer(buffer_id);
+            }
+            self.local_history_panel = None;
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(LIST_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "local_history_diff")
+                .with_binding(
+                    KeyCode::Char('r'),
+                    KeyModifiers::NONE,
+                    "local_history_restore",
+                ),
+        );
+        let buffer_id =
+            self.create_virtual_buffer("*Local History*".to_string(), LIST_MODE.to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        self.local_history_panel = Some(LocalHistoryPanel {
+            buffer_id,
+            source_buffer,
+            source_split: self.split_manager.active_split(),
+            versions,
+        });
+        self.refresh_local_history_panel();
+
+        self.save_current_split_view_state();
+        match self.split_manager.split_active(
+            SplitDirection::Horizontal,
+            buffer_id,
+            LIST_SPLIT_RATIO,
+        ) {
+            Ok(split_id) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    buffer_id,
+                );
+                view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                self.split_view_states.insert(split_id, view_state);
+                self.split_manager.set_active_split(split_id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to create local history split: {}", e);
+                self.set_active_buffer(buffer_id);
+            }
+        }
+    }
+
+    /// Compare the version on the cursor line with the buffer in the diff
+    /// viewer
+    pub fn local_history_diff(&mut self) {
+        let Some((source_buffer, version)) = self.selected_version() else {
+            return;
+        };
+        let Some((current, old)) = self.version_content(source_buffer, &version) else {
+            return;
+        };
+        let name = self.local_history_name(source_buffer);
+
+        self.focus_local_history_source();
+        let label = version.label();
+        let (_, changes) = self.open_text_diff(
+            (&format!("{} ({})", name, label), &old),
+            (&name, &current),
+            format!("*Diff: {} @ {}*", name, label),
+        );
+        if changes == 0 {
+            self.set_status_message("No differences from the buffer".to_string());
+        } else {
+            self.set_status_message(format!("{} changed line(s)", changes));
+        }
+    }
+
+    /// Replace the buffer content with the version on the cursor line, as
+    /// one undoable edit
+    pub fn local_history_restore(&mut self) {
+        let Some((source_buffer, version)) = self.selected_version() else {
+            return;
+        };
+        let Some((current, old)) = self.version_content(source_buffer, &version) else {
+            return;
+        };
+        let (Ok(current), Ok(old)) = (String::from_utf8(current), String::from_utf8(old)) else {
+            self.set_status_message("Cannot restore a version that isn't valid UTF-8".to_string());
+            return;
+        };
+
+        self.focus_local_history_source();
+        self.set_active_buffer(source_buffer);
+        if self.is_editing_disabled() {
+            self.set_status_message("Editing disabled in this buffer".to_string());
+            return;
+        }
+        let label = version.label();
+        if current == old {
+            self.set_status_message(format!("Buffer already matches the version {}", label));
+            return;
+        }
+
+        let state = self.active_state();
+        let cursor_id = state.cursors.primary_id();
+        let position = state.cursors.primary().position.min(old.len());
+        let mut events = vec![
+            Event::Delete {
+                range: 0..current.len(),
+                deleted_text: current,
+                cursor_id,
+            },
+            Event::Insert {
+                position: 0,
+                text: old.clone(),
+                cursor_id,
+            },
+        ];
+        if position != old.len() {
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: old.len(),
+                new_position: position,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            });
+        }
+        let batch = Event::Batch {
+            events,
+            description: format!("Restore version {}", label),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        let versions = self.buffer_versions(source_buffer);
+        if let Some(panel) = self.local_history_panel.as_mut() {
+            panel.versions = versions;
+        }
+        self.refresh_local_history_panel();
+        self.set_status_message(format!("Restored the version {}", label));
+    }
+
+    fn buffer_versions(&self, buffer_id: BufferId) -> Vec<Version> {
+        let Some(log) = self.event_logs.get(&buffer_id) else {
+            return Vec::new();
+        };
+        let autosaves = self
+            .autosave_snapshots
+            .get(&buffer_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        collect_versions(log.entries(), autosaves)
+    }
+
+    /// File name of the buffer, or its display name when it has no file
+    fn local_history_name(&self, buffer_id: BufferId) -> String {
+        let file_name = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned());
+        file_name
+            .or_else(|| {
+                self.buffer_metadata
+                    .get(&buffer_id)
+                    .map(|meta| meta.display_name.clone())
+            })
+            .unwrap_or_default()
+    }
+
+    /// The buffer of the list and the version on the cursor line of the
+    /// list buffer
+    fn selected_version(&mut self) -> Option<(BufferId, Version)> {
+        let panel = self
+            .local_history_panel
+            .as_ref()
+            .filter(|panel| panel.buffer_id == self.active_buffer())?;
+        let state = self.active_state();
+        let version = state
+            .text_properties
+            .get_at(state.cursors.primary().position)
+            .iter()
+            .find_map(|property| property.get_as::<usize>("index"))
+            .and_then(|index| panel.versions.get(index).cloned());
+        let source_buffer = panel.source_buffer;
+        match version {
+            Some(version) if self.buffers.contains_key(&source_buffer) => {
+                Some((source_buffer, version))
+            }
+            Some(_) => {
+                self.set_status_message("The buffer was closed".to_string());
+                None
+            }
+            None => {
+                self.set_status_message("Move the cursor to a version line".to_string());
+                None
+            }
+        }
+    }
+
+    /// The current content of `buffer_id` and its content in `version`
+    fn version_content(
+        &mut self,
+        buffer_id: BufferId,
+        version: &Version,
+    ) -> Option<(Vec<u8>, Vec<u8>)> {
+        let state = self.buffers.get_mut(&buffer_id)?;
+        let total_bytes = state.buffer.total_bytes();
+        let current = match state.buffer.get_text_range_mut(0, total_bytes) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.set_status_message(format!("Cannot read the buffer: {}", e));
+                return None;
+            }
+        };
+        let old = self
+            .event_logs
+            .get(&buffer_id)?
+            .content_at(&current, version.index);
+        Some((current, old))
+    }
+
+    /// Switch to the split the list was opened from, if it is still open
+    fn focus_local_history_source(&mut self) {
+        let Some(source_split) = self.local_history_panel.as_ref().map(|p| p.source_split) else {
+            return;
+        };
+        if self.split_manager.get_buffer_id(source_split).is_some() {
+            self.save_current_split_view_state();
+            self.split_manager.set_active_split(source_split);
+            self.restore_current_split_view_state();
+        }
+    }
+
+    /// Rewrite the list buffer from the panel's versions
+    fn refresh_local_history_panel(&mut self) {
+        let Some(panel) = self.local_history_panel.as_ref() else {
+            return;
+        };
+        let buffer_id = panel.buffer_id;
+        let name = self.local_history_name(panel.source_buffer);
+        let current_index = self
+            .event_logs
+            .get(&panel.source_buffer)
+            .map(EventLog::current_index)
+            .unwrap_or_default();
+
+        let mut entries = vec![TextPropertyEntry::text(format!(
+            "Local history: {} ({} version(s))\nEnter: diff with buffer   r: restore   q: close\n",
+            name,
+            panel.versions.len()
+        ))];
+        for (index, version) in panel.versions.iter().enumerate() {
+            let kind = match version.kind {
+                VersionKind::Opened => "Opened",
+                VersionKind::Checkpoint => "Undo checkpoint",
+                VersionKind::Autosave => "Autosave",
+            };
+            let note = if version.index > current_index {
+                "  (undone)"
+            } else {
+                ""
+            };
+            let text = format!("  {:<19}  {}{}\n", version.label(), kind, note);
+            entries.push(
+                TextPropertyEntry::text(text).with_property("index", serde_json::json!(index)),
+            );
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to update local history list: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::event::CursorId;
+
+    fn insert_at(timestamp: u64, position: usize, text: &str) -> LogEntry {
+        let mut entry = LogEntry::new(Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        });
+        entry.timestamp = timestamp;
+        entry
+    }
+
+    #[test]
+    fn test_collect_versions() {
+        // A pause after "b"
+        let entries = vec![
+            insert_at(1_000, 0, "a"),
+            insert_at(2_000, 1, "b"),
+            insert_at(9_000, 2, "c"),
+        ];
+        let autosaves = vec![
+            AutosaveSnapshot {
+                index: 1,
+                previous_timestamp: Some(1_000),
+                saved_at: 1_500,
+            },
+            // Taken from a history that was since replaced
+            AutosaveSnapshot {
+                index: 2,
+                previous_timestamp: Some(1_234),
+                saved_at: 2_500,
+            },
+        ];
+        let versions: Vec<(VersionKind, usize)> = collect_versions(&entries, &autosaves)
+            .iter()
+            .map(|version| (version.kind, version.index))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                (VersionKind::Checkpoint, 3),
+                (VersionKind::Checkpoint, 2),
+                (VersionKind::Autosave, 1),
+                (VersionKind::Opened, 0),
+            ]
+        );
+
+        assert!(collect_versions(&[], &autosaves).is_empty());
+    }
+}
@@ -30,13 +30,18 @@ impl Editor {
             return;
         };
 
-        // Attempt restart
+        self.restart_lsp_server(&language);
+    }
+
+    /// Restart (or start) the LSP server for `language` and re-send didOpen
+    /// notifications for all buffers of that language.
+    pub(super) fn restart_lsp_server(&mut self, language: &str) {
         let Some(lsp) = self.lsp.as_mut() else {
             self.set_status_message("No LSP manager available".to_string());
             return;
         };
 
-        let (success, message) = lsp.manual_restart(&language);
+        let (success, message) = lsp.manual_restart(language);
         self.status_message = Some(message);
 
         if !success {
@@ -44,7 +49,7 @@ impl Editor {
         }
 
         // Re-send didOpen for all buffers of this language
-        self.reopen_buffers_for_language(&language);
+        self.reopen_buffers_for_language(language);
     }
 
     /// Re-send didOpen notifications for all buffers of a given language.
@@ -18,6 +18,9 @@ use crate::model::event::{BufferId, Event};
 use crate::services::lsp::manager::detect_language;
 use crate::view::prompt::{Prompt, PromptType};
 
+use super::references_actions::Reference;
+use super::signature_help::{signature_help_lines, SIGNATURE_HELP_TITLE};
+use super::workspace_edit::WorkspaceEdit;
 use super::{uri_to_path, Editor};
 
 impl Editor {
@@ -344,6 +347,11 @@ impl Editor {
 
     /// Request LSP completion at current cursor position
     pub(crate) fn request_completion(&mut self) -> io::Result<()> {
+        // Without a language server, complete from the project's tags file
+        if !self.is_lsp_ready_for_active_buffer() && self.complete_from_tags()? {
+            return Ok(());
+        }
+
         // Get the current buffer and cursor position
         let state = self.active_state();
         let cursor_pos = state.cursors.primary().position;
@@ -381,6 +389,11 @@ impl Editor {
 
     /// Request LSP go-to-definition at current cursor position
     pub(crate) fn request_goto_definition(&mut self) -> io::Result<()> {
+        // Without a language server, look the definition up in the tags file
+        if !self.is_lsp_ready_for_active_buffer() && self.goto_definition_from_tags() {
+            return Ok(());
+        }
+
         // Get the current buffer and cursor position
         let state = self.active_state();
         let cursor_pos = state.cursors.primary().position;
@@ -459,6 +472,8 @@ impl Editor {
             self.next_lsp_request_id += 1;
             self.pending_hover_request = Some(request_id);
             self.lsp_status = "LSP: hover...".to_string();
+            // Shown at the cursor, even if a mouse hover was pending
+            self.mouse_hover_screen_position = None;
         }
 
         Ok(())
@@ -569,7 +584,7 @@ impl Editor {
                 face: crate::model::event::OverlayFace::Background {
                     color: (80, 80, 120), // Subtle highlight for hovered symbol
                 },
-                priority: 90, // Below rename (100) but above syntax (lower)
+                priority: crate::view::overlay::priority::HOVER_SYMBOL,
                 message: None,
             };
             self.apply_event_to_active_buffer(&event);
@@ -588,7 +603,7 @@ impl Editor {
 
         // Use markdown rendering if the content is markdown
         let mut popup = if is_markdown {
-            Popup::markdown(&contents, &self.theme)
+            Popup::markdown(&contents, &self.theme, Some(&self.grammar_registry))
         } else {
             // Plain text - split by lines
             let lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
@@ -740,6 +755,12 @@ impl Editor {
             }
         };
 
+        // Without a running language server, search the project instead
+        if !self.is_lsp_ready_for_active_buffer() {
+            self.find_references_textually(symbol);
+            return Ok(());
+        }
+
         // Convert byte position to LSP position (line, UTF-16 code units)
         let (line, character) = state.buffer.position_to_lsp_position(cursor_pos);
         let buffer_id = self.active_buffer();
@@ -806,6 +827,7 @@ impl Editor {
             self.next_lsp_request_id += 1;
             self.pending_signature_help_request = Some(request_id);
             self.lsp_status = "LSP: signature help...".to_string();
+            self.signature_help_requested(cursor_pos);
         }
 
         Ok(())
@@ -826,90 +848,25 @@ impl Editor {
         self.pending_signature_help_request = None;
         self.lsp_status.clear();
 
-        let signature_help = match signature_help {
-            Some(help) if !help.signatures.is_empty() => help,
+        // Replace the popup for the previous position in the call
+        self.hide_signature_help_popup(true);
+
+        let lines = match signature_help.as_ref().and_then(signature_help_lines) {
+            Some(lines) if self.track_signature_help_call() => lines,
             _ => {
                 tracing::debug!("No signature help available");
+                self.signature_help_call = None;
                 return;
             }
         };
 
-        // Get the active signature
-        let active_signature_idx = signature_help.active_signature.unwrap_or(0) as usize;
-        let signature = match signature_help.signatures.get(active_signature_idx) {
-            Some(sig) => sig,
-            None => return,
-        };
-
-        // Build the display content
-        let mut lines: Vec<String> = Vec::new();
-
-        // Add the signature label (function signature)
-        lines.push(signature.label.clone());
-
-        // Add parameter highlighting info
-        let active_param = signature_help
-            .active_parameter
-            .or(signature.active_parameter)
-            .unwrap_or(0) as usize;
-
-        // If there are parameters, highlight the active one
-        if let Some(params) = &signature.parameters {
-            if let Some(param) = params.get(active_param) {
-                // Get parameter label
-                let param_label = match &param.label {
-                    lsp_types::ParameterLabel::Simple(s) => s.clone(),
-                    lsp_types::ParameterLabel::LabelOffsets(offsets) => {
-                        // Extract substring from signature label
-                        let start = offsets[0] as usize;
-                        let end = offsets[1] as usize;
-                        if end <= signature.label.len() {
-                            signature.label[start..end].to_string()
-                        } else {
-                            String::new()
-                        }
-                    }
-                };
-
-                if !param_label.is_empty() {
-                    lines.push(format!("> {}", param_label));
-                }
-
-                // Add parameter documentation if available
-                if let Some(doc) = &param.documentation {
-                    let doc_text = match doc {
-                        lsp_types::Documentation::String(s) => s.clone(),
-                        lsp_types::Documentation::MarkupContent(m) => m.value.clone(),
-                    };
-                    if !doc_text.is_empty() {
-                        lines.push(String::new());
-                        lines.push(doc_text);
-                    }
-                }
-            }
-        }
-
-        // Add function documentation if available
-        if let Some(doc) = &signature.documentation {
-            let doc_text = match doc {
-                lsp_types::Documentation::String(s) => s.clone(),
-                lsp_types::Documentation::MarkupContent(m) => m.value.clone(),
-            };
-            if !doc_text.is_empty() {
-                if lines.len() > 1 {
-                    lines.push(String::new());
-                    lines.push("---".to_string());
-                }
-                lines.push(doc_text);
-            }
-        }
-
         // Create a popup with the signature help
-        use crate::view::popup::{Popup, PopupPosition};
+        use crate::view::popup::{Popup, PopupContent, PopupPosition};
         use ratatui::style::Style;
 
-        let mut popup = Popup::text(lines, &self.theme);
-        popup.title = Some("Signature Help".to_string());
+        let mut popup = Popup::text(Vec::new(), &self.theme);
+        popup.content = PopupContent::Markdown(lines);
+        popup.title = Some(SIGNATURE_HELP_TITLE.to_string());
         popup.transient = true;
         popup.position = PopupPosition::BelowCursor;
         popup.width = 60;
@@ -922,7 +879,7 @@ impl Editor {
             state.popups.show(popup);
             tracing::info!(
                 "Showing signature help popup for {} signatures",
-                signature_help.signatures.len()
+                signature_help.map_or(0, |help| help.signatures.len())
             );
         }
     }
@@ -1076,146 +1033,30 @@ impl Editor {
             return Ok(());
         }
 
-        // Convert locations to hook args format
-        let lsp_locations: Vec<crate::services::plugins::hooks::LspLocation> = locations
+        let references: Vec<Reference> = locations
             .iter()
-            .map(|loc| {
-                // Convert URI to file path
-                let file = if loc.uri.scheme().map(|s| s.as_str()) == Some("file") {
-                    // Extract path from file:// URI
-                    loc.uri.path().as_str().to_string()
-                } else {
-                    loc.uri.as_str().to_string()
-                };
-
-                crate::services::plugins::hooks::LspLocation {
-                    file,
-                    line: loc.range.start.line + 1, // LSP is 0-based, convert to 1-based
-                    column: loc.range.start.character + 1, // LSP is 0-based
-                }
+            .filter_map(|loc| {
+                let path = uri_to_path(&loc.uri).ok()?;
+                Some(Reference {
+                    path,
+                    line: loc.range.start.line as usize + 1, // LSP is 0-based, convert to 1-based
+                    column: loc.range.start.character as usize + 1, // LSP is 0-based
+                    line_text: None,
+                })
             })
             .collect();
 
-        let count = lsp_locations.len();
+        let count = references.len();
         let symbol = std::mem::take(&mut self.pending_references_symbol);
-        self.set_status_message(format!("Found {} reference(s) for '{}'", count, symbol));
+        tracing::info!("Showing {} references for symbol '{}'", count, symbol);
 
-        // Fire the lsp_references hook so plugins can display the results
-        self.plugin_manager.run_hook(
-            "lsp_references",
-            crate::services::plugins::hooks::HookArgs::LspReferences {
-                symbol: symbol.clone(),
-                locations: lsp_locations,
-            },
-        );
-
-        tracing::info!(
-            "Fired lsp_references hook with {} locations for symbol '{}'",
-            count,
-            symbol
-        );
+        // Plugins display the results if they handle the lsp_references hook
+        self.show_references(symbol.clone(), references);
+        self.set_status_message(format!("Found {} reference(s) for '{}'", count, symbol));
 
         Ok(())
     }
 
-    /// Apply LSP text edits to a buffer and return the number of changes made.
-    /// Edits are sorted in reverse order and applied as a batch.
-    pub(crate) fn apply_lsp_text_edits(
-        &mut self,
-        buffer_id: BufferId,
-        mut edits: Vec<lsp_types::TextEdit>,
-    ) -> io::Result<usize> {
-        if edits.is_empty() {
-            return Ok(0);
-        }
-
-        // Sort edits by position (reverse order to avoid offset issues)
-        edits.sort_by(|a, b| {
-            b.range
-                .start
-                .line
-                .cmp(&a.range.start.line)
-                .then(b.range.start.character.cmp(&a.range.start.character))
-        });
-
-        // Collect all events for this buffer into a batch
-        let mut batch_events = Vec::new();
-        let mut changes = 0;
-
-        // Create events for all edits
-        for edit in edits {
-            let state = self
-                .buffers
-                .get_mut(&buffer_id)
-                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Buffer not found"))?;
-
-            // Convert LSP range to byte positions
-            let start_line = edit.range.start.line as usize;
-            let start_char = edit.range.start.character as usize;
-            let end_line = edit.range.end.line as usize;
-            let end_char = edit.range.end.character as usize;
-
-            let start_pos = state.buffer.lsp_position_to_byte(start_line, start_char);
-            let end_pos = state.buffer.lsp_position_to_byte(end_line, end_char);
-            let buffer_len = state.buffer.len();
-
-            // Log the conversion for debugging
-            let old_text = if start_pos < end_pos && end_pos <= buffer_len {
-                state.get_text_range(start_pos, end_pos)
-            } else {
-                format!(
-                    "<invalid range: start={}, end={}, buffer_len={}>",
-                    start_pos, end_pos, buffer_len
-                )
-            };
-            tracing::debug!(
-                "  Converting LSP range line {}:{}-{}:{} to bytes {}..{} (replacing {:?} with {:?})",
-                start_line, start_char, end_line, end_char,
-                start_pos, end_pos, old_text, edit.new_text
-            );
-
-            // Delete old text
-            if start_pos < end_pos {
-                let deleted_text = state.get_text_range(start_pos, end_pos);
-                let cursor_id = state.cursors.primary_id();
-                let delete_event = Event::Delete {
-                    range: start_pos..end_pos,
-                    deleted_text,
-                    cursor_id,
-                };
-                batch_events.push(delete_event);
-            }
-
-            // Insert new text
-            if !edit.new_text.is_empty() {
-                let state = self
-                    .buffers
-                    .get(&buffer_id)
-                    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Buffer not found"))?;
-                let cursor_id = state.cursors.primary_id();
-                let insert_event = Event::Insert {
-                    position: start_pos,
-                    text: edit.new_text.clone(),
-                    cursor_id,
-                };
-                batch_events.push(insert_event);
-            }
-
-            changes += 1;
-        }
-
-        // Create a batch event for all rename changes
-        if !batch_events.is_empty() {
-            let batch = Event::Batch {
-                events: batch_events,
-                description: "LSP Rename".to_string(),
-            };
-            self.apply_rename_batch_to_buffer(buffer_id, batch)?;
-        }
-
-        Ok(changes)
-    }
-
     /// Handle rename response from LSP
     pub fn handle_rename_response(
         &mut self,
@@ -1237,81 +1078,20 @@ impl Editor {
                     })
                 );
 
-                // Apply the workspace edit
-                let mut total_changes = 0;
-
-                // Handle changes (map of URI -> Vec<TextEdit>)
-                if let Some(changes) = workspace_edit.changes {
-                    for (uri, edits) in changes {
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
+                let edit = WorkspaceEdit::from_lsp(workspace_edit);
+                match self.apply_workspace_edit(edit, "LSP Rename") {
+                    Ok(applied) if applied.files > 0 => {
+                        self.status_message = Some(format!(
+                            "Renamed successfully ({} changes, {} unopened file(s) edited on disk)",
+                            applied.edits, applied.files
+                        ));
                     }
-                }
-
-                // Handle document_changes (TextDocumentEdit[])
-                // This is what rust-analyzer sends instead of changes
-                if let Some(document_changes) = workspace_edit.document_changes {
-                    use lsp_types::DocumentChanges;
-
-                    let text_edits = match document_changes {
-                        DocumentChanges::Edits(edits) => edits,
-                        DocumentChanges::Operations(ops) => {
-                            // Extract TextDocumentEdit from operations
-                            ops.into_iter()
-                                .filter_map(|op| {
-                                    if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
-                                        Some(edit)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        }
-                    };
-
-                    for text_doc_edit in text_edits {
-                        let uri = text_doc_edit.text_document.uri;
-
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-
-                            // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
-                            let edits: Vec<lsp_types::TextEdit> = text_doc_edit
-                                .edits
-                                .into_iter()
-                                .map(|one_of| match one_of {
-                                    lsp_types::OneOf::Left(text_edit) => text_edit,
-                                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
-                                })
-                                .collect();
-
-                            // Log the edits for debugging
-                            tracing::info!(
-                                "Applying {} edits from rust-analyzer for {:?}:",
-                                edits.len(),
-                                path
-                            );
-                            for (i, edit) in edits.iter().enumerate() {
-                                tracing::info!(
-                                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
-                                    i,
-                                    edit.range.start.line,
-                                    edit.range.start.character,
-                                    edit.range.end.line,
-                                    edit.range.end.character,
-                                    edit.new_text
-                                );
-                            }
-
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
+                    Ok(applied) => {
+                        self.status_message =
+                            Some(format!("Renamed successfully ({} changes)", applied.edits));
                     }
+                    Err(e) => self.status_message = Some(format!("Rename failed: {}", e)),
                 }
-
-                self.status_message =
-                    Some(format!("Renamed successfully ({} changes)", total_changes));
             }
             Err(error) => {
                 // Per LSP spec: ContentModified errors (-32801) should NOT be shown to user
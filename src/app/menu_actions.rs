@@ -5,7 +5,67 @@
 use super::Editor;
 use crate::app::types::HoverTarget;
 use crate::config::{Menu, MenuItem};
-use crate::input::keybindings::Action;
+use crate::input::commands::{Command, CommandSource};
+use crate::input::keybindings::{Action, KeyContext};
+use std::collections::{BTreeMap, HashMap};
+
+/// Label of the menu listing the commands plugins register
+pub(crate) const PLUGINS_MENU_LABEL: &str = "Plugins";
+
+/// Build the Plugins menu: a submenu per plugin with the commands it
+/// registered, or None if plugins registered none
+fn plugins_menu(commands: Vec<Command>) -> Option<Menu> {
+    let mut groups: BTreeMap<String, Vec<MenuItem>> = BTreeMap::new();
+    for command in commands {
+        let CommandSource::Plugin(plugin) = command.source else {
+            continue;
+        };
+        let Action::PluginAction(action) = command.action else {
+            continue;
+        };
+        // Commands of a plugin's own modes and panels only apply there
+        let in_editor = command.contexts.is_empty()
+            || command
+                .contexts
+                .iter()
+                .any(|c| matches!(c, KeyContext::Normal | KeyContext::Global));
+        if !in_editor || !command.custom_contexts.is_empty() {
+            continue;
+        }
+        groups.entry(plugin).or_default().push(MenuItem::Action {
+            label: command.name,
+            action,
+            args: HashMap::new(),
+            when: None,
+            checkbox: None,
+        });
+    }
+    if groups.is_empty() {
+        return None;
+    }
+
+    let items = groups
+        .into_iter()
+        .map(|(label, mut items)| {
+            items.sort_by(|a, b| menu_item_label(a).cmp(menu_item_label(b)));
+            MenuItem::Submenu { label, items }
+        })
+        .collect();
+    Some(Menu {
+        label: PLUGINS_MENU_LABEL.to_string(),
+        items,
+    })
+}
+
+fn menu_item_label(item: &MenuItem) -> &str {
+    match item {
+        MenuItem::Action { label, .. }
+        | MenuItem::Submenu { label, .. }
+        | MenuItem::DynamicSubmenu { label, .. } => label,
+        MenuItem::Label { info } => info,
+        MenuItem::Separator { .. } => "",
+    }
+}
 
 impl Editor {
     /// Get all menus (config menus + plugin menus) with DynamicSubmenus expanded.
@@ -23,6 +83,37 @@ impl Editor {
             .collect()
     }
 
+    /// Keep the Plugins menu in step with the commands plugins have
+    /// registered. Cheap when nothing changed, so it runs every frame.
+    pub(super) fn refresh_plugins_menu(&mut self) {
+        let (revision, commands) = {
+            let registry = self.command_registry.read().unwrap();
+            if registry.revision() == self.plugins_menu_revision {
+                return;
+            }
+            (registry.revision(), registry.plugin_commands())
+        };
+        self.plugins_menu_revision = revision;
+
+        let existing = self
+            .menu_state
+            .plugin_menus
+            .iter()
+            .position(|menu| menu.label == PLUGINS_MENU_LABEL);
+        match (existing, plugins_menu(commands)) {
+            (Some(index), Some(menu)) => self.menu_state.plugin_menus[index] = menu,
+            (None, Some(menu)) => self.menu_state.plugin_menus.push(menu),
+            (Some(index), None) => {
+                self.menu_state.plugin_menus.remove(index);
+                // Don't leave a dropdown open on a menu that's gone
+                if self.menu_state.active_menu.is_some() {
+                    self.close_menu_with_auto_hide();
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
     /// Handle MenuActivate action - opens the first menu.
     /// If the menu bar is hidden, it will be temporarily shown.
     pub fn handle_menu_activate(&mut self) {
@@ -1,33 +1,86 @@
+mod abbreviation_actions;
 mod async_messages;
+mod background_save;
+mod batch_actions;
+mod buffer_compaction;
+mod buffer_list_actions;
 mod buffer_management;
 mod clipboard;
+mod collab_actions;
+mod config_doctor;
+mod context_menu;
+mod crash_report_actions;
+mod diff_actions;
+mod export_actions;
+mod feedback;
+mod file_drop;
 mod file_explorer;
+mod file_management_actions;
 pub mod file_open;
 mod file_open_input;
 mod file_operations;
+mod file_watcher;
+pub mod frame_limiter;
+mod git_actions;
+mod git_commit_actions;
+mod git_link_actions;
+mod grammar_actions;
 mod help;
 mod input;
 mod input_dispatch;
+mod language_actions;
+mod line_actions;
+mod local_history_actions;
 mod lsp_actions;
 mod lsp_requests;
 mod menu_actions;
 mod mouse_input;
+mod notification_actions;
 mod on_save_actions;
+mod palette_actions;
 mod plugin_commands;
+mod plugin_fetch;
+mod plugin_health;
+mod plugin_settings;
 mod popup_actions;
+mod progressive_load;
 mod prompt_actions;
+mod protected_mode;
+mod quickfix_actions;
+mod quit_dialog;
 mod recovery_actions;
+mod references_actions;
+#[cfg(unix)]
+mod remote_actions;
+mod rename_symbol_actions;
 mod render;
+mod repl_actions;
+mod revision_actions;
+mod scratch_actions;
 pub mod session;
 mod settings_actions;
 mod shell_command;
+mod signature_help;
 mod split_actions;
+mod ssh_actions;
+mod sudo_actions;
+mod system_health;
+mod tab_overflow;
+mod tags_actions;
+mod task_actions;
 mod terminal;
+pub mod terminal_handoff;
 mod terminal_input;
+mod terminal_title;
 mod toggle_actions;
 pub mod types;
 mod undo_actions;
+mod vfs_actions;
 mod view_actions;
+mod workspace_actions;
+pub mod workspace_edit;
+mod wrapped_movement;
+mod zen_actions;
 
 use std::path::Component;
 
@@ -64,20 +117,24 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
 }
 
 use self::types::{
-    Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
-    LspProgressInfo, MacroRecordingState, MouseState, SearchState, DEFAULT_BACKGROUND_FILE,
+    AbbreviationExpansion, Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState,
+    LspMessageEntry, LspProgressInfo, MacroRecordingState, MouseState, SearchState,
+    DEFAULT_BACKGROUND_FILE,
 };
 use crate::config::Config;
 use crate::config_io::DirectoryContext;
 use crate::input::actions::action_to_events as convert_action_to_events;
 use crate::input::buffer_mode::ModeRegistry;
+use crate::input::command_palette::{self, PaletteMode};
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Suggestion;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
+use crate::input::keyboard::KeyboardProtocol;
 use crate::input::position_history::PositionHistory;
 use crate::model::event::{Event, EventLog, SplitDirection, SplitId};
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
 use crate::services::fs::{FsBackend, FsManager, LocalFsBackend};
+use crate::services::ignore_service::{IgnoreService, SharedIgnoreService};
 use crate::services::lsp::manager::{detect_language, LspManager};
 use crate::services::plugins::api::{BufferSavedDiff, PluginCommand};
 use crate::services::plugins::PluginManager;
@@ -89,12 +146,13 @@ use crate::view::file_tree::{FileTree, FileTreeView};
 use crate::view::prompt::{Prompt, PromptType};
 use crate::view::split::{SplitManager, SplitViewState};
 use crate::view::ui::{
-    FileExplorerRenderer, SplitRenderer, StatusBarRenderer, SuggestionsRenderer,
+    DamageRegion, DamageTracker, FileExplorerRenderer, SplitRenderer, StatusBarRenderer,
+    SuggestionsRenderer,
 };
 use crossterm::event::{KeyCode, KeyModifiers};
 use lsp_types::{Position, Range as LspRange, TextDocumentContentChangeEvent};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
 use std::collections::{HashMap, HashSet};
@@ -170,6 +228,9 @@ pub struct Editor {
     /// Plugin-provided status message (displayed alongside the core status)
     plugin_status_message: Option<String>,
 
+    /// Toast notifications (stacked, auto-dismissing) and their history
+    notifications: crate::view::notification::NotificationManager,
+
     /// Active prompt (minibuffer)
     prompt: Option<Prompt>,
 
@@ -235,15 +296,33 @@ pub struct Editor {
     /// Whether GPM is being used for mouse input (requires software cursor)
     gpm_active: bool,
 
+    /// Keyboard protocol negotiated with the terminal
+    keyboard_protocol: KeyboardProtocol,
+
     /// Current keybinding context
     key_context: KeyContext,
 
     /// Menu state (active menu, highlighted item)
     menu_state: crate::view::ui::MenuState,
 
+    /// Command registry revision the Plugins menu was last built from
+    plugins_menu_revision: u64,
+
     /// Working directory for file explorer (set at initialization)
     working_dir: PathBuf,
 
+    /// Folders added to the workspace besides the working directory
+    workspace_folders: Vec<PathBuf>,
+
+    /// Settings declared by plugins, by plugin namespace
+    plugin_settings_schemas: std::collections::BTreeMap<String, serde_json::Value>,
+
+    /// Errors, restarts and log of plugins
+    plugin_health: crate::services::plugins::health::PluginHealth,
+
+    /// Plugin HTTP requests waiting for the user to allow network access
+    pending_plugin_fetches: Vec<plugin_fetch::PendingPluginFetch>,
+
     /// Position history for back/forward navigation
     pub position_history: PositionHistory,
 
@@ -268,9 +347,15 @@ pub struct Editor {
     /// Symbol name for pending references request
     pending_references_symbol: String,
 
+    /// Pending LSP workspace symbol search request ID (if any)
+    pending_workspace_symbols_request: Option<u64>,
+
     /// Pending LSP signature help request ID (if any)
     pending_signature_help_request: Option<u64>,
 
+    /// The call signature help is shown for, followed as the cursor moves
+    signature_help_call: Option<signature_help::SignatureHelpCall>,
+
     /// Pending LSP code actions request ID (if any)
     pending_code_actions_request: Option<u64>,
 
@@ -312,6 +397,13 @@ pub struct Editor {
     /// Cached layout areas from last render (for mouse hit testing)
     pub(crate) cached_layout: CachedLayout,
 
+    /// Lines rendered in the previous frame, reused while unchanged, per split
+    line_render_caches: HashMap<SplitId, crate::view::ui::LineRenderCache>,
+
+    /// Status bar and tab bars drawn in the previous frame, redrawn only
+    /// when their inputs change
+    damage_tracker: DamageTracker,
+
     /// Command registry for dynamic commands
     command_registry: Arc<RwLock<CommandRegistry>>,
 
@@ -376,6 +468,96 @@ pub struct Editor {
     /// Last recorded macro register (for F12 to replay)
     last_macro_register: Option<char>,
 
+    /// Most recent abbreviation expansion, revertible by the next Backspace
+    last_abbreviation: Option<AbbreviationExpansion>,
+
+    /// Socket accepting `fresh --remote` requests (with `--listen`)
+    #[cfg(unix)]
+    remote_server: Option<crate::services::remote::RemoteServer>,
+
+    /// `--remote --wait` clients waiting for their buffer to be closed
+    #[cfg(unix)]
+    remote_waiters: HashMap<BufferId, Vec<crate::services::remote::RemoteConnection>>,
+
+    /// Buffers editing a local copy of an `ssh://` file
+    ssh_buffers: HashMap<BufferId, crate::services::ssh::SshUrl>,
+
+    /// Providers of `git://`, `zip://`, `cmd://` and plugin URLs
+    vfs: crate::services::vfs::VfsRegistry,
+
+    /// Buffers opened from a provider, by the URL they were read from
+    vfs_buffers: HashMap<BufferId, crate::services::vfs::VfsUrl>,
+
+    /// Buffer versions being written by their provider
+    vfs_saves: HashMap<BufferId, u64>,
+
+    /// Renames, duplicates and deletes that can be undone, most recent last
+    file_operation_history: Vec<file_management_actions::FileOperation>,
+
+    /// Textual rename waiting for review in its preview buffer
+    rename_preview: Option<rename_symbol_actions::RenamePreview>,
+
+    /// Built-in Find References results, shown when no plugin displays them
+    references_panel: Option<references_actions::ReferencesPanel>,
+
+    /// Most recent task run (output buffer and compiler messages)
+    task_run: Option<task_actions::TaskRun>,
+
+    /// Next task run ID (matches output from the background job to its run)
+    next_task_id: u64,
+
+    /// Locations to step through with "Quickfix: Next/Previous Entry"
+    quickfix: Option<quickfix_actions::QuickfixList>,
+
+    /// The quickfix list split, while it is open
+    quickfix_panel: Option<quickfix_actions::QuickfixPanel>,
+
+    /// REPL each buffer sends code to
+    repls: HashMap<BufferId, repl_actions::Repl>,
+
+    /// Points each buffer was auto-saved for recovery at, for local history
+    autosave_snapshots: HashMap<BufferId, Vec<local_history_actions::AutosaveSnapshot>>,
+
+    /// The local history list split, while it is open
+    local_history_panel: Option<local_history_actions::LocalHistoryPanel>,
+
+    /// The buffer list split, while it is open
+    buffer_list_panel: Option<buffer_list_actions::BufferListPanel>,
+
+    /// The layout to restore, while zen mode is on
+    zen_layout: Option<zen_actions::ZenLayout>,
+
+    /// The list of hidden tabs opened from a tab bar's overflow button
+    tab_overflow_menu: Option<tab_overflow::TabOverflowMenu>,
+
+    /// The open right-click context menu
+    context_menu: Option<context_menu::ContextMenu>,
+
+    /// Dialog asking what to do with unsaved buffers on quit, while open
+    quit_dialog: Option<quit_dialog::QuitDialog>,
+
+    /// Items plugins added to the editor's context menu
+    plugin_context_menu_items: Vec<crate::config::MenuItem>,
+
+    /// Terminal handoff requested of the event loop (suspend, or a command
+    /// to run in the terminal)
+    terminal_handoff: Option<terminal_handoff::TerminalHandoff>,
+
+    /// Terminal title as last taken by the event loop
+    last_terminal_title: Option<String>,
+
+    /// Whether the event loop should ring the terminal bell
+    bell_pending: bool,
+
+    /// Split flashing after a rejected key, while it flashes
+    visual_bell: Option<feedback::VisualBell>,
+
+    /// Files still being read in the background, by the buffer they open in
+    progressive_loads: HashMap<BufferId, progressive_load::ProgressiveLoad>,
+
+    /// Buffers being written to their files in the background
+    background_saves: HashMap<BufferId, background_save::BackgroundSave>,
+
     /// Pending plugin action receivers (for async action execution)
     #[cfg(feature = "plugins")]
     pending_plugin_actions: Vec<(
@@ -402,12 +584,19 @@ pub struct Editor {
     /// Whether auto-revert mode is enabled (automatically reload files when changed on disk)
     auto_revert_enabled: bool,
 
+    /// File whose constant changes turned auto-revert off
+    auto_revert_disabled_by: Option<PathBuf>,
+
     /// Last time we polled for file changes (for auto-revert)
     last_auto_revert_poll: std::time::Instant,
 
     /// Last time we polled for directory changes (for file tree refresh)
     last_file_tree_poll: std::time::Instant,
 
+    /// What the last polls of open files and of file explorer folders found
+    file_poll_stats: file_watcher::PollStats,
+    tree_poll_stats: file_watcher::PollStats,
+
     /// Last known modification times for open files (for auto-revert)
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -416,6 +605,9 @@ pub struct Editor {
     /// Maps directory path to last known modification time
     dir_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Ignore files and patterns, shared with the file explorer and plugins
+    ignore_service: SharedIgnoreService,
+
     /// Tracks rapid file change events for debouncing
     /// Maps file path to (last event time, event count)
     file_rapid_change_counts: HashMap<PathBuf, (std::time::Instant, u32)>,
@@ -435,6 +627,25 @@ pub struct Editor {
     /// Last auto-save time for rate limiting
     last_auto_save: std::time::Instant,
 
+    /// Last time modified scratch buffers were written to disk
+    last_scratch_save: std::time::Instant,
+
+    /// When the session is saved outside of quitting
+    session_tracker: session::SessionTracker,
+
+    /// Open files last recorded for a crash report, sorted
+    crash_report_files: Vec<PathBuf>,
+
+    /// Time of the last key press, after which buffers are compacted once
+    /// the editor is idle
+    last_input_time: std::time::Instant,
+
+    /// Whether buffers have been compacted since the last key press
+    compacted_since_input: bool,
+
+    /// Totals of the buffer compaction passes run so far
+    compaction_metrics: buffer_compaction::CompactionMetrics,
+
     /// Active custom contexts for command visibility
     /// Plugin-defined contexts like "config-editor" that control command availability
     active_custom_contexts: HashSet<String>,
@@ -442,9 +653,33 @@ pub struct Editor {
     /// Warning log receiver and path (for opening warning log when warnings occur)
     warning_log: Option<(std::sync::mpsc::Receiver<()>, PathBuf)>,
 
+    /// Recording of the input received, with `--record-session`
+    session_recorder: Option<crate::input::recording::SessionRecorder>,
+
     /// Periodic update checker (checks for new releases every hour)
     update_checker: Option<crate::services::release_checker::PeriodicUpdateChecker>,
 
+    /// Background watcher of the working directory's git status
+    git_status_watcher: Option<crate::services::git_status::GitStatusWatcher>,
+
+    /// Latest git status of the working directory, shown in the status bar
+    git_status: Option<crate::services::git_status::GitStatus>,
+
+    /// The commit being written, while its message buffer is open
+    git_commit: Option<git_commit_actions::GitCommitSession>,
+
+    /// The shared buffer session, while hosting or joined
+    collab: Option<collab_actions::CollabSession>,
+
+    /// The project's tags file and the ctags runs regenerating it
+    tags: tags_actions::TagsState,
+
+    /// Files and symbols listed by the open command palette
+    palette_cache: palette_actions::PaletteCache,
+
+    /// Symbols of the project, searched by the palette's `#` mode
+    symbol_index: palette_actions::SymbolIndex,
+
     /// Terminal manager for built-in terminal support
     terminal_manager: crate::services::terminal::TerminalManager,
 
@@ -477,6 +712,9 @@ pub struct Editor {
     /// Double-click is only detected if both clicks are at the same position
     previous_click_position: Option<(u16, u16)>,
 
+    /// Number of clicks in the current series at the same position (1 to 3)
+    click_count: u8,
+
     /// Settings UI state (when settings modal is open)
     pub(crate) settings_state: Option<crate::view::settings::SettingsState>,
 
@@ -615,11 +853,12 @@ impl Editor {
         let mut event_logs = HashMap::new();
 
         let buffer_id = BufferId(0);
-        let state = EditorState::new(
+        let mut state = EditorState::new(
             width,
             height,
             config.editor.large_file_threshold_bytes as usize,
         );
+        state.tab_size = config.editor.tab_size;
         // Note: line_wrap_enabled is now stored in SplitViewState.viewport
         tracing::info!("EditorState created for buffer {:?}", buffer_id);
         buffers.insert(buffer_id, state);
@@ -681,7 +920,7 @@ impl Editor {
         let command_registry = Arc::new(RwLock::new(CommandRegistry::new()));
 
         // Initialize plugin manager (handles both enabled and disabled cases internally)
-        let plugin_manager = PluginManager::new(enable_plugins, Arc::clone(&command_registry));
+        let mut plugin_manager = PluginManager::new(enable_plugins, Arc::clone(&command_registry));
 
         // Load TypeScript plugins from multiple directories:
         // 1. Next to the executable (for cargo-dist installations)
@@ -747,6 +986,7 @@ impl Editor {
         let file_explorer_width = config.file_explorer.width;
         let recovery_enabled = config.editor.recovery_enabled;
         let auto_save_interval_secs = config.editor.auto_save_interval_secs;
+        let auto_revert_enabled = config.editor.auto_revert;
         let check_for_updates = config.check_for_updates;
 
         // Start periodic update checker if enabled
@@ -762,6 +1002,24 @@ impl Editor {
             None
         };
 
+        // Watch the git branch and working tree for the status bar
+        let git_status_watcher = config.editor.show_git_status.then(|| {
+            let sender = async_bridge.sender();
+            crate::services::git_status::watch(
+                working_dir.clone(),
+                crate::services::git_status::GIT_STATUS_INTERVAL,
+                move |status| {
+                    let _ = sender.send(AsyncMessage::GitStatusChanged { status });
+                },
+            )
+        });
+
+        let zen_mode = config.editor.zen_mode;
+        let ignore_service = IgnoreService::shared(&config.ignore_patterns);
+        if let Ok(mut service) = ignore_service.write() {
+            service.set_roots(vec![working_dir.clone()]);
+        }
+
         Ok(Editor {
             buffers,
             event_logs,
@@ -779,6 +1037,7 @@ impl Editor {
             restart_with_dir: None,
             status_message: None,
             plugin_status_message: None,
+            notifications: crate::view::notification::NotificationManager::new(),
             prompt: None,
             terminal_width: width,
             terminal_height: height,
@@ -794,13 +1053,15 @@ impl Editor {
             file_explorer_visible: false,
             file_explorer_sync_in_progress: false,
             file_explorer_width_percent: file_explorer_width,
-            menu_bar_visible: true,
+            menu_bar_visible: !zen_mode,
             menu_bar_auto_shown: false,
             mouse_enabled: true,
             mouse_cursor_position: None,
             gpm_active: false,
+            keyboard_protocol: KeyboardProtocol::default(),
             key_context: KeyContext::Normal,
             menu_state: crate::view::ui::MenuState::new(),
+            plugins_menu_revision: 0,
             working_dir,
             position_history: PositionHistory::new(),
             in_navigation: false,
@@ -810,7 +1071,9 @@ impl Editor {
             pending_hover_request: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
+            pending_workspace_symbols_request: None,
             pending_signature_help_request: None,
+            signature_help_call: None,
             pending_code_actions_request: None,
             pending_inlay_hints_request: None,
             hover_symbol_range: None,
@@ -828,6 +1091,8 @@ impl Editor {
             lsp_status: String::new(),
             mouse_state: MouseState::default(),
             cached_layout: CachedLayout::default(),
+            line_render_caches: HashMap::new(),
+            damage_tracker: DamageTracker::default(),
             command_registry,
             plugin_manager,
             seen_byte_ranges: HashMap::new(),
@@ -867,6 +1132,41 @@ impl Editor {
             macros: HashMap::new(),
             macro_recording: None,
             last_macro_register: None,
+            last_abbreviation: None,
+            #[cfg(unix)]
+            remote_server: None,
+            #[cfg(unix)]
+            remote_waiters: HashMap::new(),
+            ssh_buffers: HashMap::new(),
+            vfs: crate::services::vfs::VfsRegistry::new(),
+            vfs_buffers: HashMap::new(),
+            vfs_saves: HashMap::new(),
+            file_operation_history: Vec::new(),
+            rename_preview: None,
+            references_panel: None,
+            task_run: None,
+            next_task_id: 0,
+            quickfix: None,
+            quickfix_panel: None,
+            repls: HashMap::new(),
+            autosave_snapshots: HashMap::new(),
+            local_history_panel: None,
+            buffer_list_panel: None,
+            zen_layout: zen_mode.then(|| zen_actions::ZenLayout::new(true)),
+            tab_overflow_menu: None,
+            context_menu: None,
+            quit_dialog: None,
+            plugin_context_menu_items: Vec::new(),
+            terminal_handoff: None,
+            last_terminal_title: None,
+            bell_pending: false,
+            visual_bell: None,
+            progressive_loads: HashMap::new(),
+            background_saves: HashMap::new(),
+            workspace_folders: Vec::new(),
+            plugin_settings_schemas: std::collections::BTreeMap::new(),
+            plugin_health: crate::services::plugins::health::PluginHealth::new(),
+            pending_plugin_fetches: Vec::new(),
             #[cfg(feature = "plugins")]
             pending_plugin_actions: Vec::new(),
             #[cfg(feature = "plugins")]
@@ -874,11 +1174,15 @@ impl Editor {
             chord_state: Vec::new(),
             pending_lsp_confirmation: None,
             pending_close_buffer: None,
-            auto_revert_enabled: true,
+            auto_revert_enabled,
+            auto_revert_disabled_by: None,
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
+            file_poll_stats: file_watcher::PollStats::default(),
+            tree_poll_stats: file_watcher::PollStats::default(),
             file_mod_times: HashMap::new(),
             dir_mod_times: HashMap::new(),
+            ignore_service,
             file_rapid_change_counts: HashMap::new(),
             file_open_state: None,
             file_browser_layout: None,
@@ -892,9 +1196,23 @@ impl Editor {
             },
             time_source: time_source.clone(),
             last_auto_save: time_source.now(),
+            last_scratch_save: time_source.now(),
+            session_tracker: session::SessionTracker::new(false, time_source.now()),
+            crash_report_files: Vec::new(),
+            last_input_time: time_source.now(),
+            compacted_since_input: false,
+            compaction_metrics: Default::default(),
             active_custom_contexts: HashSet::new(),
             warning_log: None,
+            session_recorder: None,
             update_checker,
+            git_status_watcher,
+            git_status: None,
+            git_commit: None,
+            collab: None,
+            tags: Default::default(),
+            palette_cache: Default::default(),
+            symbol_index: Default::default(),
             terminal_manager: crate::services::terminal::TerminalManager::new(),
             terminal_buffers: HashMap::new(),
             terminal_backing_files: HashMap::new(),
@@ -904,6 +1222,7 @@ impl Editor {
             terminal_mode_resume: std::collections::HashSet::new(),
             previous_click_time: None,
             previous_click_position: None,
+            click_count: 0,
             settings_state: None,
             color_capability,
             stdin_streaming: None,
@@ -989,8 +1308,10 @@ impl Editor {
 
     /// Check if editing should be disabled for the active buffer
     /// This returns true when editing_disabled is true (e.g., for read-only virtual buffers)
+    /// or while the file is still loading
     pub fn is_editing_disabled(&self) -> bool {
-        self.active_state().editing_disabled
+        let state = self.active_state();
+        state.editing_disabled || state.loading
     }
 
     /// Resolve a keybinding for the active buffer's mode
@@ -1029,6 +1350,16 @@ impl Editor {
             .unwrap_or(false)
     }
 
+    /// Whether the language server for the active buffer is ready for requests
+    pub fn is_lsp_ready_for_active_buffer(&self) -> bool {
+        self.buffer_metadata
+            .get(&self.active_buffer())
+            .filter(|metadata| metadata.lsp_enabled)
+            .and_then(|metadata| metadata.file_path())
+            .and_then(|path| detect_language(path, &self.config.languages))
+            .is_some_and(|language| self.is_lsp_server_ready(&language))
+    }
+
     /// Get the LSP status string (displayed in status bar)
     pub fn get_lsp_status(&self) -> &str {
         &self.lsp_status
@@ -1111,6 +1442,35 @@ impl Editor {
         Ok(())
     }
 
+    /// Record the input the editor receives to `path`, to be replayed by a
+    /// test (see `input::recording`)
+    pub fn start_session_recording(&mut self, path: &Path) -> io::Result<()> {
+        self.session_recorder = Some(crate::input::recording::SessionRecorder::create(
+            path,
+            self.terminal_width,
+            self.terminal_height,
+        )?);
+        Ok(())
+    }
+
+    /// Record a terminal event about to be handled, if recording. Input to
+    /// a password prompt is redacted.
+    pub fn record_terminal_event(&mut self, event: &crossterm::event::Event) {
+        let password_prompt = self
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.prompt_type == PromptType::SudoPassword);
+        if let Some(recorder) = &mut self.session_recorder {
+            if let Some(event) = crate::input::recording::RecordedEvent::from_terminal(event) {
+                recorder.record(if password_prompt {
+                    event.redacted()
+                } else {
+                    event
+                });
+            }
+        }
+    }
+
     /// Log keystroke for debugging
     pub fn log_keystroke(&mut self, key_code: &str, modifiers: &str) {
         if let Some(event_log) = self.event_logs.get_mut(&self.active_buffer()) {
@@ -1200,6 +1560,31 @@ impl Editor {
         true
     }
 
+    /// Check whether any buffer's cursor has rested long enough to show the
+    /// occurrences of the word (or selection) under it
+    ///
+    /// Highlights are hidden while the cursor moves so that holding an arrow
+    /// key doesn't flash them on every line. Returns true if any became
+    /// visible and a redraw is needed.
+    pub fn check_occurrence_highlight_timer(&mut self) -> bool {
+        let enabled = self.config.editor.highlight_occurrences;
+        let delay =
+            std::time::Duration::from_millis(self.config.editor.highlight_occurrences_delay_ms);
+        let now = self.time_source.now();
+
+        let mut needs_redraw = false;
+        for state in self.buffers.values_mut() {
+            if !enabled {
+                state.semantic_highlighter.hide();
+                continue;
+            }
+            let cursor = state.cursors.primary();
+            let key = (cursor.position, cursor.anchor);
+            needs_redraw |= state.semantic_highlighter.settle(key, now, delay);
+        }
+        needs_redraw
+    }
+
     /// Load an ANSI background image from a user-provided path
     fn load_ansi_background(&mut self, input: &str) -> io::Result<()> {
         let trimmed = input.trim();
@@ -1503,6 +1888,7 @@ impl Editor {
     /// line_info contains pre-calculated line numbers from BEFORE buffer modification
     fn trigger_plugin_hooks_for_event(&mut self, event: &Event, line_info: EventLineInfo) {
         let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
 
         // Convert event to hook args and fire the appropriate hook
         let hook_args = match event {
@@ -1547,6 +1933,7 @@ impl Editor {
                         start_line: line_info.start_line,
                         end_line: line_info.end_line,
                         lines_added: line_info.line_delta.max(0) as usize,
+                        version,
                     },
                 ))
             }
@@ -1596,6 +1983,7 @@ impl Editor {
                         start_line: line_info.start_line,
                         end_line: line_info.end_line,
                         lines_removed: (-line_info.line_delta).max(0) as usize,
+                        version,
                     },
                 ))
             }
@@ -1769,24 +2157,25 @@ impl Editor {
         self.event_logs.get_mut(&self.active_buffer()).unwrap()
     }
 
-    /// Update the buffer's modified flag based on event log position
+    /// Update the modified flag of `buffer_id` based on event log position
     /// Call this after undo/redo to correctly track whether the buffer
     /// has returned to its saved state
-    pub(super) fn update_modified_from_event_log(&mut self) {
+    pub(super) fn update_modified_from_event_log(&mut self, buffer_id: BufferId) {
         let is_at_saved = self
             .event_logs
-            .get(&self.active_buffer())
+            .get(&buffer_id)
             .map(|log| log.is_at_saved_position())
             .unwrap_or(false);
 
-        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
             state.buffer.set_modified(!is_at_saved);
         }
     }
 
-    /// Check if the editor should quit
+    /// Check if the editor should quit. A quit waits for saves in the
+    /// background and writes through providers to finish.
     pub fn should_quit(&self) -> bool {
-        self.should_quit
+        self.should_quit && self.background_saves.is_empty() && self.vfs_saves.is_empty()
     }
 
     /// Check if the editor should restart with a new working directory
@@ -1824,21 +2213,20 @@ impl Editor {
 
     /// Request the editor to quit
     pub fn quit(&mut self) {
-        // Check for unsaved buffers
-        let modified_count = self.count_modified_buffers();
-        if modified_count > 0 {
-            // Prompt user for confirmation
-            let msg = if modified_count == 1 {
-                "1 buffer has unsaved changes. (d)iscard and quit, (C)ancel? ".to_string()
-            } else {
-                format!(
-                    "{} buffers have unsaved changes. (d)iscard and quit, (C)ancel? ",
-                    modified_count
-                )
-            };
-            self.start_prompt(msg, PromptType::ConfirmQuitWithModified);
-        } else {
+        // Scratch buffers are kept on disk rather than discarded
+        self.persist_all_scratch_buffers();
+
+        // Ask about each unsaved buffer
+        let modified: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified())
+            .map(|(&id, _)| id)
+            .collect();
+        if modified.is_empty() {
             self.should_quit = true;
+        } else {
+            self.open_quit_dialog(modified);
         }
     }
 
@@ -1855,16 +2243,49 @@ impl Editor {
         // Update terminal dimensions for future buffer creation
         self.terminal_width = width;
         self.terminal_height = height;
-
-        // Resize all SplitViewState viewports (viewport is now owned by SplitViewState)
-        for view_state in self.split_view_states.values_mut() {
-            view_state.viewport.resize(width, height);
+        self.damage_tracker.invalidate();
+
+        // Resize all SplitViewState viewports (viewport is now owned by SplitViewState).
+        // Visible splits get their share of the new size, as the next render
+        // lays them out; the others (hidden behind a maximized split) get the
+        // whole terminal.
+        let split_areas: HashMap<SplitId, Rect> = self
+            .split_manager
+            .get_visible_buffers(self.estimated_editor_area())
+            .into_iter()
+            .map(|(split_id, _, area)| (split_id, area))
+            .collect();
+        for (split_id, view_state) in self.split_view_states.iter_mut() {
+            match split_areas.get(split_id) {
+                Some(area) => view_state.viewport.resize(
+                    area.width.saturating_sub(1),  // scrollbar
+                    area.height.saturating_sub(1), // tab bar
+                ),
+                None => view_state.viewport.resize(width, height),
+            }
         }
 
         // Resize visible terminal PTYs to match new dimensions
         self.resize_visible_terminals();
     }
 
+    /// Area the splits are laid out in at the current terminal size, without
+    /// the file explorer, menu bar and status bar
+    pub(crate) fn estimated_editor_area(&self) -> Rect {
+        let file_explorer_width = if self.file_explorer_visible {
+            (self.terminal_width as f32 * self.file_explorer_width_percent) as u16
+        } else {
+            0
+        };
+        let editor_width = self.terminal_width.saturating_sub(file_explorer_width);
+        Rect::new(
+            file_explorer_width,
+            1, // menu bar
+            editor_width,
+            self.terminal_height.saturating_sub(2), // menu bar + status bar
+        )
+    }
+
     // Prompt/Minibuffer control methods
 
     /// Start a new prompt (enter minibuffer mode)
@@ -1952,6 +2373,7 @@ impl Editor {
             prompt_type,
             PromptType::OpenFile
                 | PromptType::SwitchProject
+                | PromptType::AddWorkspaceFolder
                 | PromptType::SaveFileAs
                 | PromptType::Command
         );
@@ -2022,7 +2444,7 @@ impl Editor {
     /// Initialize the file open dialog state
     ///
     /// Called when the Open File prompt is started. Determines the initial directory
-    /// (from current buffer's directory or working directory) and triggers async
+    /// (from current buffer's directory or the split's working directory) and triggers async
     /// directory loading.
     fn init_file_open_state(&mut self) {
         // Determine initial directory
@@ -2032,7 +2454,7 @@ impl Editor {
             .file_path()
             .and_then(|path| path.parent())
             .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| self.working_dir.clone());
+            .unwrap_or_else(|| self.active_split_working_dir());
 
         // Create the file open state
         self.file_open_state = Some(file_open::FileOpenState::new(initial_dir.clone()));
@@ -2065,15 +2487,57 @@ impl Editor {
     /// - LSP servers are properly shut down and restarted with new root
     /// - Plugins are cleanly restarted
     /// - No state leaks between projects
+    ///
+    /// The main loop saves this project's session before the restart and
+    /// restores the new project's one after it. Unsaved changes would be
+    /// lost, so with modified buffers the user is asked first.
     pub fn change_working_dir(&mut self, new_path: PathBuf) {
         // Canonicalize the path to resolve symlinks and normalize
         let new_path = new_path.canonicalize().unwrap_or(new_path);
 
+        // Scratch buffers are kept on disk rather than discarded
+        self.persist_all_scratch_buffers();
+
+        let modified_count = self.count_modified_buffers();
+        if modified_count > 0 {
+            let msg = if modified_count == 1 {
+                "1 buffer has unsaved changes. (s)ave, (d)iscard, or (C)ancel switching project? "
+                    .to_string()
+            } else {
+                format!(
+                    "{} buffers have unsaved changes. (s)ave, (d)iscard, or (C)ancel switching project? ",
+                    modified_count
+                )
+            };
+            self.start_prompt(msg, PromptType::ConfirmSwitchProject { path: new_path });
+            return;
+        }
+
         // Request a restart with the new working directory
         // The main loop will handle creating a fresh editor instance
         self.request_restart(new_path);
     }
 
+    /// Handle the ConfirmSwitchProject prompt
+    fn handle_confirm_switch_project(&mut self, input: &str, path: PathBuf) {
+        match input.trim().to_lowercase().as_str() {
+            "s" | "save" => {
+                self.buffer_list_save_all();
+                let remaining = self.count_modified_buffers();
+                if remaining > 0 {
+                    self.set_status_message(format!(
+                        "Not switching project: {} buffer(s) could not be saved",
+                        remaining
+                    ));
+                    return;
+                }
+                self.request_restart(path);
+            }
+            "d" | "discard" => self.request_restart(path),
+            _ => self.set_status_message("Switch project cancelled".to_string()),
+        }
+    }
+
     /// Load directory contents for the file open dialog
     fn load_file_open_directory(&mut self, path: PathBuf) {
         // Update state to loading
@@ -2135,6 +2599,7 @@ impl Editor {
 
     /// Cancel the current prompt and return to normal mode
     pub fn cancel_prompt(&mut self) {
+        let mut network_access_plugin = None;
         // Determine prompt type and reset appropriate history navigation
         if let Some(ref prompt) = self.prompt {
             match &prompt.prompt_type {
@@ -2163,17 +2628,25 @@ impl Editor {
                     };
                     self.apply_event_to_active_buffer(&remove_overlay_event);
                 }
-                PromptType::OpenFile | PromptType::SwitchProject => {
+                PromptType::OpenFile
+                | PromptType::SwitchProject
+                | PromptType::AddWorkspaceFolder => {
                     // Clear file browser state
                     self.file_open_state = None;
                     self.file_browser_layout = None;
                 }
+                PromptType::ConfirmPluginNetworkAccess { plugin } => {
+                    network_access_plugin = Some(plugin.clone());
+                }
                 _ => {}
             }
         }
 
         self.prompt = None;
         self.pending_search_range = None;
+        if let Some(plugin) = network_access_plugin {
+            self.cancel_plugin_network_access(&plugin);
+        }
         self.status_message = Some("Canceled".to_string());
     }
 
@@ -2190,10 +2663,19 @@ impl Editor {
                 PromptType::Command
                     | PromptType::OpenFile
                     | PromptType::SwitchProject
+                    | PromptType::AddWorkspaceFolder
                     | PromptType::SaveFileAs
                     | PromptType::StopLspServer
                     | PromptType::SelectTheme
+                    | PromptType::SelectLanguage
                     | PromptType::SwitchToTab
+                    | PromptType::RunTask
+                    | PromptType::FileRevision { .. }
+                    | PromptType::GitCheckoutBranch
+                    | PromptType::RemoveWorkspaceFolder
+                    | PromptType::NewScratchBuffer
+                    | PromptType::OpenScratchBuffer
+                    | PromptType::LimitFileFeatures { .. }
             ) {
                 // Use the selected suggestion if any
                 if let Some(selected_idx) = prompt.selected_suggestion {
@@ -2280,6 +2762,18 @@ impl Editor {
             .is_some()
     }
 
+    /// Check if LSP is enabled for the active buffer and its server is
+    /// running and ready
+    pub(super) fn active_buffer_lsp_ready(&self) -> bool {
+        self.buffer_metadata
+            .get(&self.active_buffer())
+            .filter(|metadata| metadata.lsp_enabled)
+            .and_then(|metadata| metadata.file_path())
+            .and_then(|path| detect_language(path, &self.config.languages))
+            .zip(self.lsp.as_ref())
+            .is_some_and(|(language, lsp)| lsp.is_server_ready(&language))
+    }
+
     /// Get mutable reference to prompt (for input handling)
     pub fn prompt_mut(&mut self) -> Option<&mut Prompt> {
         self.prompt.as_mut()
@@ -2309,22 +2803,11 @@ impl Editor {
 
         match prompt_type {
             PromptType::Command => {
-                let selection_active = self.has_active_selection();
-                if let Some(prompt) = &mut self.prompt {
-                    // Use the underlying context (not Prompt context) for filtering
-                    prompt.suggestions = self.command_registry.read().unwrap().filter(
-                        &input,
-                        self.key_context,
-                        &self.keybindings,
-                        selection_active,
-                        &self.active_custom_contexts,
-                    );
-                    prompt.selected_suggestion = if prompt.suggestions.is_empty() {
-                        None
-                    } else {
-                        Some(0)
-                    };
+                if let (PaletteMode::WorkspaceSymbols, query) = command_palette::parse_query(&input)
+                {
+                    self.request_workspace_symbols(query);
                 }
+                self.update_command_palette(&input);
             }
             PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                 // Update incremental search highlights as user types
@@ -2336,7 +2819,7 @@ impl Editor {
                 // Reset history navigation when user types - allows Up to navigate history
                 self.replace_history.reset_navigation();
             }
-            PromptType::OpenFile | PromptType::SwitchProject => {
+            PromptType::OpenFile | PromptType::SwitchProject | PromptType::AddWorkspaceFolder => {
                 // For OpenFile/SwitchProject, update the file browser filter (native implementation)
                 self.update_file_open_filter();
             }
@@ -2365,7 +2848,20 @@ impl Editor {
                     },
                 );
             }
-            PromptType::SwitchToTab | PromptType::SelectTheme | PromptType::StopLspServer => {
+            PromptType::AlignOn => {
+                // Preview the alignment as the delimiter is typed
+                self.update_align_preview(&input);
+            }
+            PromptType::SwitchToTab
+            | PromptType::SelectTheme
+            | PromptType::SelectLanguage
+            | PromptType::StopLspServer
+            | PromptType::RunTask
+            | PromptType::FileRevision { .. }
+            | PromptType::GitCheckoutBranch
+            | PromptType::RemoveWorkspaceFolder
+            | PromptType::NewScratchBuffer
+            | PromptType::OpenScratchBuffer => {
                 // Filter suggestions using fuzzy matching
                 use crate::input::fuzzy::fuzzy_match;
 
@@ -2406,7 +2902,7 @@ impl Editor {
     /// - LSP diagnostics
     /// - LSP initialization/errors
     /// - File system changes (future)
-    /// - Git status updates (future)
+    /// - Git status updates
     pub fn process_async_messages(&mut self) -> bool {
         let Some(bridge) = &self.async_bridge else {
             return false;
@@ -2414,6 +2910,13 @@ impl Editor {
 
         let messages = bridge.try_recv_all();
         let needs_render = !messages.is_empty();
+        if needs_render {
+            if let Some(recorder) = &mut self.session_recorder {
+                recorder.record(crate::input::recording::RecordedEvent::from_async_messages(
+                    &messages,
+                ));
+            }
+        }
 
         for message in messages {
             match message {
@@ -2497,6 +3000,12 @@ impl Editor {
                         tracing::error!("Error handling references response: {}", e);
                     }
                 }
+                AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    response,
+                } => {
+                    self.handle_workspace_symbols_response(request_id, response);
+                }
                 AsyncMessage::LspSignatureHelp {
                     request_id,
                     signature_help,
@@ -2532,8 +3041,17 @@ impl Editor {
                     self.handle_async_file_changed(path);
                 }
                 AsyncMessage::GitStatusChanged { status } => {
-                    tracing::info!("Git status changed: {}", status);
-                    // TODO: Handle git status changes
+                    tracing::debug!("Git status changed: {:?}", status);
+                    self.git_status = status;
+                }
+                AsyncMessage::GitCheckoutFinished { branch, result } => {
+                    self.handle_git_checkout_finished(branch, result);
+                }
+                AsyncMessage::GitCommitFinished { result } => {
+                    self.handle_git_commit_finished(result);
+                }
+                AsyncMessage::Collab(event) => {
+                    self.handle_collab_event(event);
                 }
                 AsyncMessage::FileExplorerInitialized(view) => {
                     self.handle_file_explorer_initialized(view);
@@ -2604,6 +3122,24 @@ impl Editor {
                 AsyncMessage::FileOpenDirectoryLoaded(result) => {
                     self.handle_file_open_directory_loaded(result);
                 }
+                AsyncMessage::GrammarInstalled { result } => {
+                    self.handle_grammar_installed(result);
+                }
+                AsyncMessage::SymbolIndexBuilt { root, symbols } => {
+                    self.handle_symbol_index_built(root, symbols);
+                }
+                AsyncMessage::TagsGenerated { root, result } => {
+                    self.handle_tags_generated(root, result);
+                }
+                AsyncMessage::VfsRead { url, result } => {
+                    self.handle_vfs_read(url, result);
+                }
+                AsyncMessage::VfsWritten { url, result } => {
+                    self.handle_vfs_written(url, result);
+                }
+                AsyncMessage::PluginFetchResult { request_id, result } => {
+                    self.respond_plugin_fetch(request_id, result);
+                }
                 AsyncMessage::TerminalOutput { terminal_id } => {
                     // Terminal output received - check if we should auto-jump back to terminal mode
                     tracing::trace!("Terminal output received for {:?}", terminal_id);
@@ -2630,6 +3166,32 @@ impl Editor {
                         }
                     }
                 }
+                AsyncMessage::SshProgress {
+                    url,
+                    received,
+                    total,
+                } => {
+                    self.handle_ssh_progress(url, received, total);
+                }
+                AsyncMessage::SshFetched { url, result } => {
+                    self.handle_ssh_fetched(url, result);
+                }
+                AsyncMessage::SshUploaded { url, result } => {
+                    self.handle_ssh_uploaded(url, result);
+                }
+                AsyncMessage::TaskOutput { task_id, line } => {
+                    self.handle_task_output(task_id, line);
+                }
+                AsyncMessage::TaskFinished { task_id, exit_code } => {
+                    self.handle_task_finished(task_id, exit_code);
+                }
+                #[cfg(unix)]
+                AsyncMessage::RemoteOpen {
+                    request,
+                    connection,
+                } => {
+                    self.handle_remote_open(request, connection);
+                }
                 AsyncMessage::TerminalExited { terminal_id } => {
                     tracing::info!("Terminal {:?} exited", terminal_id);
                     // Find and close the buffer associated with this terminal
@@ -2661,6 +3223,15 @@ impl Editor {
         // Process pending LSP server restarts (with exponential backoff)
         self.process_pending_lsp_restarts();
 
+        // Restart disabled plugins and a stopped plugin host (with exponential backoff)
+        self.process_plugin_restarts();
+
+        // Ask about network access for plugin requests that arrived while a prompt was open
+        self.prompt_plugin_network_access();
+
+        // Send the edits made to a shared buffer to the other editors
+        self.sync_collab_buffer();
+
         // Check and clear the plugin render request flag
         #[cfg(feature = "plugins")]
         let plugin_render = {
@@ -2681,8 +3252,19 @@ impl Editor {
         let file_changes = self.poll_file_changes();
         let tree_changes = self.poll_file_tree_changes();
 
+        // Remove toast notifications whose timeout has elapsed
+        let notifications_expired = self.notifications.expire(
+            self.time_source.now(),
+            std::time::Duration::from_millis(self.config.editor.notification_timeout_ms),
+        );
+
         // Trigger render if any async messages, plugin commands were processed, or plugin requested render
-        needs_render || processed_any_commands || plugin_render || file_changes || tree_changes
+        needs_render
+            || processed_any_commands
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || notifications_expired
     }
 
     /// Update LSP status bar string from active progress operations
@@ -2757,9 +3339,10 @@ impl Editor {
             // Update active split ID
             snapshot.active_split_id = self.split_manager.active_split().0;
 
-            // Clear and update buffer info
-            snapshot.buffers.clear();
-            snapshot.buffer_saved_diffs.clear();
+            // Clear and update buffer info, keeping the diffs of buffers
+            // that were not edited since the last update
+            let previous_buffers = std::mem::take(&mut snapshot.buffers);
+            let mut previous_diffs = std::mem::take(&mut snapshot.buffer_saved_diffs);
             snapshot.buffer_cursor_positions.clear();
             snapshot.buffer_text_properties.clear();
 
@@ -2769,13 +3352,22 @@ impl Editor {
                     path: state.buffer.file_path().map(|p| p.to_path_buf()),
                     modified: state.buffer.is_modified(),
                     length: state.buffer.len(),
+                    version: state.buffer.version(),
                 };
+                let unchanged = previous_buffers.get(buffer_id).is_some_and(|previous| {
+                    previous.version == buffer_info.version
+                        && previous.modified == buffer_info.modified
+                        && previous.length == buffer_info.length
+                });
                 snapshot.buffers.insert(*buffer_id, buffer_info);
 
                 // Skip diffing in large file mode - too expensive
                 // TODO: Enable when we have an efficient streaming diff algorithm
                 let is_large_file = state.buffer.line_count().is_none();
-                let diff = if is_large_file {
+                let previous_diff = previous_diffs.remove(buffer_id).filter(|_| unchanged);
+                let diff = if let Some(diff) = previous_diff {
+                    diff
+                } else if is_large_file {
                     BufferSavedDiff {
                         equal: !state.buffer.is_modified(),
                         byte_ranges: vec![],
@@ -2857,7 +3449,11 @@ impl Editor {
             snapshot.clipboard = self.clipboard.get_internal().to_string();
 
             // Update working directory (for spawning processes in correct directory)
-            snapshot.working_dir = self.working_dir.clone();
+            snapshot.working_dir = self.active_split_working_dir();
+            snapshot.workspace_roots = std::iter::once(snapshot.working_dir.clone())
+                .chain(self.workspace_folders.iter().cloned())
+                .collect();
+            snapshot.ignore_service = Arc::clone(&self.ignore_service);
 
             // Update LSP diagnostics
             snapshot.diagnostics = self.stored_diagnostics.clone();
@@ -2879,11 +3475,22 @@ impl Editor {
                 buffer_id,
                 position,
                 text,
+                plugin,
             } => {
-                self.handle_insert_text(buffer_id, position, text);
+                self.handle_insert_text(buffer_id, position, text, plugin);
+            }
+            PluginCommand::DeleteRange {
+                buffer_id,
+                range,
+                plugin,
+            } => {
+                self.handle_delete_range(buffer_id, range, plugin);
+            }
+            PluginCommand::BeginUndoGroup { buffer_id, plugin } => {
+                self.handle_begin_undo_group(buffer_id, plugin);
             }
-            PluginCommand::DeleteRange { buffer_id, range } => {
-                self.handle_delete_range(buffer_id, range);
+            PluginCommand::EndUndoGroup { buffer_id, plugin } => {
+                self.handle_end_undo_group(buffer_id, plugin);
             }
             PluginCommand::InsertAtCursor { text } => {
                 self.handle_insert_at_cursor(text);
@@ -2999,6 +3606,12 @@ impl Editor {
             PluginCommand::RemoveMenu { menu_label } => {
                 self.handle_remove_menu(menu_label);
             }
+            PluginCommand::AddContextMenuItem { item } => {
+                self.handle_add_context_menu_item(item);
+            }
+            PluginCommand::RemoveContextMenuItem { label } => {
+                self.handle_remove_context_menu_item(label);
+            }
 
             // ==================== Split Commands ====================
             PluginCommand::FocusSplit { split_id } => {
@@ -3075,6 +3688,23 @@ impl Editor {
             PluginCommand::SetStatus { message } => {
                 self.handle_set_status(message);
             }
+            PluginCommand::ShowNotification {
+                level,
+                message,
+                source,
+            } => {
+                self.handle_show_notification(level, message, source);
+            }
+            PluginCommand::PluginLog {
+                plugin,
+                level,
+                message,
+            } => {
+                self.record_plugin_log(plugin, &level, message);
+            }
+            PluginCommand::PluginError { plugin, message } => {
+                self.record_plugin_error(plugin, message);
+            }
             PluginCommand::ApplyTheme { theme_name } => {
                 self.apply_theme(&theme_name);
             }
@@ -3133,6 +3763,40 @@ impl Editor {
                 self.handle_close_buffer(buffer_id);
             }
 
+            PluginCommand::GetBufferText {
+                buffer_id,
+                range,
+                request_id,
+            } => {
+                self.handle_get_buffer_text(buffer_id, range, request_id);
+            }
+            PluginCommand::RegisterFileSystemProvider { scheme, writable } => {
+                self.register_plugin_vfs_provider(&scheme, writable);
+            }
+            PluginCommand::CompleteVfsRead { url, result } => {
+                self.complete_vfs_read(&url, result);
+            }
+            PluginCommand::CompleteVfsWrite { url, result } => {
+                self.complete_vfs_write(&url, result);
+            }
+            PluginCommand::OpenUrl { url } => {
+                self.open_url(&url);
+            }
+            PluginCommand::Fetch {
+                plugin,
+                request,
+                request_id,
+            } => {
+                self.handle_plugin_fetch(plugin, request, request_id);
+            }
+            PluginCommand::QueryBuffer {
+                buffer_id,
+                query,
+                request_id,
+            } => {
+                self.handle_query_buffer(buffer_id, query, request_id);
+            }
+
             // ==================== LSP Commands ====================
             PluginCommand::SendLspRequest {
                 language,
@@ -3143,6 +3807,21 @@ impl Editor {
                 self.handle_send_lsp_request(language, method, params, request_id);
             }
 
+            // ==================== Settings Commands ====================
+            PluginCommand::DefineSettings {
+                namespace,
+                settings,
+            } => {
+                self.define_plugin_settings(&namespace, settings);
+            }
+            PluginCommand::SetPluginSetting {
+                namespace,
+                key,
+                value,
+            } => {
+                self.set_plugin_setting(&namespace, &key, value);
+            }
+
             // ==================== Clipboard Commands ====================
             PluginCommand::SetClipboard { text } => {
                 self.handle_set_clipboard(text);
@@ -1,17 +1,26 @@
 //! Mouse input handling.
 //!
 //! This module contains all mouse event handling logic including:
-//! - Click, double-click, and drag handling
+//! - Click, double-click, triple-click and drag handling
+//! - Ctrl+click to add a cursor
+//! - Auto-scrolling while a drag selection is held beyond a split's edge
 //! - Scrollbar interaction
 //! - Hover target computation
 //! - Split separator dragging
+//! - Popup scrolling, dragging, and resizing
 //! - Text selection via mouse
 
+use super::types::PopupDragKind;
 use super::*;
 use crate::input::keybindings::Action;
-use crate::model::event::{SplitDirection, SplitId};
+use crate::model::event::{CursorId, SplitDirection, SplitId};
+use crate::primitives::word_navigation::is_word_char;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
+use ratatui::layout::Rect;
+
+/// How often a drag selection held beyond a split's edge scrolls it
+const DRAG_AUTOSCROLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
 
 impl Editor {
     /// Handle a mouse event.
@@ -25,11 +34,11 @@ impl Editor {
         let col = mouse_event.column;
         let row = mouse_event.row;
 
-        // Detect double-click for left button down events (used by all handlers)
-        let is_double_click = if matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left))
-        {
+        // Count repeated clicks for left button down events (used by all handlers):
+        // the second is a double-click, the third a triple-click
+        let click_count = if matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left)) {
             let now = self.time_source.now();
-            let is_double = if let (Some(previous_time), Some(previous_pos)) =
+            let repeated = if let (Some(previous_time), Some(previous_pos)) =
                 (self.previous_click_time, self.previous_click_position)
             {
                 let double_click_threshold =
@@ -41,18 +50,19 @@ impl Editor {
                 false
             };
 
-            // Update click tracking
-            if is_double {
-                self.previous_click_time = None;
-                self.previous_click_position = None;
+            // Update click tracking; a fourth click starts a new series
+            self.click_count = if repeated {
+                self.click_count % 3 + 1
             } else {
-                self.previous_click_time = Some(now);
-                self.previous_click_position = Some((col, row));
-            }
-            is_double
+                1
+            };
+            self.previous_click_time = Some(now);
+            self.previous_click_position = Some((col, row));
+            self.click_count
         } else {
-            false
+            0
         };
+        let is_double_click = click_count == 2;
 
         // When settings modal is open, capture all mouse events
         if self.settings_state.as_ref().map_or(false, |s| s.visible) {
@@ -83,6 +93,30 @@ impl Editor {
             row
         );
 
+        // The quit dialog is modal while open
+        if self.handle_quit_dialog_mouse(mouse_event) {
+            return Ok(true);
+        }
+
+        // An open context menu takes the mouse, and a right click on a tab or
+        // in a text area opens one
+        if self.handle_context_menu_mouse(mouse_event)
+            || (mouse_event.kind == MouseEventKind::Down(MouseButton::Right)
+                && (self.handle_tab_right_click(col, row)
+                    || self.handle_editor_right_click(col, row)))
+        {
+            return Ok(true);
+        }
+
+        // The tab overflow dropdown takes clicks while it is open, and opens
+        // from its button however quickly that is clicked again
+        if self.handle_tab_overflow_mouse(mouse_event)
+            || (mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+                && self.handle_tab_overflow_click(col, row))
+        {
+            return Ok(true);
+        }
+
         match mouse_event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 if is_double_click {
@@ -91,7 +125,16 @@ impl Editor {
                     needs_render = true;
                     return Ok(needs_render);
                 }
-                self.handle_mouse_click(col, row)?;
+                if click_count == 3 {
+                    self.handle_mouse_triple_click(col, row)?;
+                    needs_render = true;
+                    return Ok(needs_render);
+                }
+                let add_cursor = self.config.editor.ctrl_click_adds_cursor
+                    && mouse_event.modifiers.contains(KeyModifiers::CONTROL);
+                if !(add_cursor && self.handle_mouse_ctrl_click(col, row)) {
+                    self.handle_mouse_click(col, row)?;
+                }
                 needs_render = true;
             }
             MouseEventKind::Drag(MouseButton::Left) => {
@@ -115,6 +158,10 @@ impl Editor {
                 self.mouse_state.dragging_text_selection = false;
                 self.mouse_state.drag_selection_split = None;
                 self.mouse_state.drag_selection_anchor = None;
+                self.mouse_state.last_drag_autoscroll = None;
+                // Clear popup drag state
+                self.mouse_state.dragging_popup = None;
+                self.mouse_state.drag_start_popup_area = None;
 
                 // If we finished dragging a separator, resize visible terminals
                 if was_dragging_separator {
@@ -161,26 +208,34 @@ impl Editor {
                 self.update_lsp_hover_state(col, row);
             }
             MouseEventKind::ScrollUp => {
-                // Check if file browser is active and should handle scroll
-                if self.is_file_open_active() && self.handle_file_open_scroll(-3) {
+                let lines = -(self.config.editor.mouse_scroll_lines as i32);
+                // Let the file browser or the popup under the mouse handle the scroll first
+                if (self.is_file_open_active() && self.handle_file_open_scroll(lines))
+                    || self.handle_popup_scroll(col, row, lines)
+                    || self.handle_tab_bar_scroll(col, row, lines)
+                {
                     needs_render = true;
                 } else {
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, -3)?;
+                    self.handle_mouse_scroll(col, row, lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     self.sync_split_view_state_to_editor_state();
                     needs_render = true;
                 }
             }
             MouseEventKind::ScrollDown => {
-                // Check if file browser is active and should handle scroll
-                if self.is_file_open_active() && self.handle_file_open_scroll(3) {
+                let lines = self.config.editor.mouse_scroll_lines as i32;
+                // Let the file browser or the popup under the mouse handle the scroll first
+                if (self.is_file_open_active() && self.handle_file_open_scroll(lines))
+                    || self.handle_popup_scroll(col, row, lines)
+                    || self.handle_tab_bar_scroll(col, row, lines)
+                {
                     needs_render = true;
                 } else {
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, 3)?;
+                    self.handle_mouse_scroll(col, row, lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     self.sync_split_view_state_to_editor_state();
                     needs_render = true;
@@ -299,10 +354,11 @@ impl Editor {
     /// Update LSP hover state based on mouse position
     /// Tracks position for debounced hover requests
     ///
-    /// Hover popup stays visible when:
+    /// Only identifiers in the active split are hovered. Hover popup stays visible when:
     /// - Mouse is over the hover popup itself
     /// - Mouse is within the hovered symbol range
-    /// Hover is dismissed when mouse leaves the editor area entirely.
+    ///
+    /// Hover is dismissed when mouse moves anywhere else.
     fn update_lsp_hover_state(&mut self, col: u16, row: u16) {
         // Check if mouse is over a transient popup - if so, keep hover active
         if self.is_mouse_over_transient_popup(col, row) {
@@ -324,13 +380,12 @@ impl Editor {
                 (*split_id, *buffer_id, *content_rect)
             });
 
-        let Some((split_id, buffer_id, content_rect)) = split_info else {
+        // Hover popups belong to the active buffer, so other splits aren't hovered
+        let Some((split_id, buffer_id, content_rect)) =
+            split_info.filter(|(split_id, _, _)| *split_id == self.split_manager.active_split())
+        else {
             // Mouse is not over editor content - clear hover state and dismiss popup
-            if self.mouse_state.lsp_hover_state.is_some() {
-                self.mouse_state.lsp_hover_state = None;
-                self.mouse_state.lsp_hover_request_sent = false;
-                self.dismiss_transient_popups();
-            }
+            self.clear_mouse_hover();
             return;
         };
 
@@ -362,11 +417,7 @@ impl Editor {
             false, // Don't include gutter
         ) else {
             // Mouse is in gutter - clear hover state
-            if self.mouse_state.lsp_hover_state.is_some() {
-                self.mouse_state.lsp_hover_state = None;
-                self.mouse_state.lsp_hover_request_sent = false;
-                self.dismiss_transient_popups();
-            }
+            self.clear_mouse_hover();
             return;
         };
 
@@ -378,6 +429,21 @@ impl Editor {
             }
         }
 
+        // Only identifiers are worth asking the server about (non-ASCII bytes
+        // belong to identifiers in most languages)
+        let over_identifier = self.buffers.get(&buffer_id).is_some_and(|state| {
+            byte_pos < state.buffer.len()
+                && state
+                    .buffer
+                    .slice_bytes(byte_pos..byte_pos + 1)
+                    .first()
+                    .is_some_and(|&byte| is_word_char(byte) || !byte.is_ascii())
+        });
+        if !over_identifier {
+            self.clear_mouse_hover();
+            return;
+        }
+
         // Check if we're still hovering the same position
         if let Some((old_pos, _, _, _)) = self.mouse_state.lsp_hover_state {
             if old_pos == byte_pos {
@@ -385,7 +451,7 @@ impl Editor {
                 return;
             }
             // Position changed outside symbol range - reset state and dismiss popup
-            self.dismiss_transient_popups();
+            self.clear_mouse_hover();
         }
 
         // Start tracking new hover position
@@ -393,6 +459,129 @@ impl Editor {
         self.mouse_state.lsp_hover_request_sent = false;
     }
 
+    /// Stop tracking the mouse hover: dismiss its popup and drop a request
+    /// still in flight, so its answer doesn't show up after the mouse left
+    fn clear_mouse_hover(&mut self) {
+        if self.mouse_state.lsp_hover_state.take().is_none() {
+            return;
+        }
+        if self.mouse_state.lsp_hover_request_sent && self.mouse_hover_screen_position.is_some() {
+            self.pending_hover_request = None;
+            self.mouse_hover_screen_position = None;
+            self.lsp_status.clear();
+        }
+        self.mouse_state.lsp_hover_request_sent = false;
+        self.dismiss_transient_popups();
+    }
+
+    /// Find the topmost popup whose outer area contains the given position.
+    /// Returns (popup_idx, popup_rect, inner_rect).
+    fn popup_at(&self, col: u16, row: u16) -> Option<(usize, Rect, Rect)> {
+        self.cached_layout
+            .popup_areas
+            .iter()
+            .rev()
+            .find(|(_, popup_rect, _, _, _)| {
+                col >= popup_rect.x
+                    && col < popup_rect.x + popup_rect.width
+                    && row >= popup_rect.y
+                    && row < popup_rect.y + popup_rect.height
+            })
+            .map(|(popup_idx, popup_rect, inner_rect, _, _)| (*popup_idx, *popup_rect, *inner_rect))
+    }
+
+    /// Scroll the popup under the mouse, if any.
+    /// Returns true if the event was consumed by a popup.
+    fn handle_popup_scroll(&mut self, col: u16, row: u16, delta: i32) -> bool {
+        let Some((popup_idx, _popup_rect, inner_rect)) = self.popup_at(col, row) else {
+            return false;
+        };
+
+        if let Some(popup) = self.active_state_mut().popups.get_mut(popup_idx) {
+            popup.scroll_by(delta, inner_rect);
+        }
+
+        // The item under the mouse changed; recompute on the next mouse move
+        self.mouse_state.hover_target = None;
+        true
+    }
+
+    /// Start moving or resizing a popup if the click is on the title bar
+    /// (top border) or the bottom-right corner of a draggable popup.
+    /// Returns true if a drag was started.
+    fn start_popup_drag(&mut self, col: u16, row: u16) -> bool {
+        let Some((popup_idx, popup_rect, _inner_rect)) = self.popup_at(col, row) else {
+            return false;
+        };
+
+        let draggable = self
+            .active_state()
+            .popups
+            .all()
+            .get(popup_idx)
+            .is_some_and(|p| p.is_draggable());
+        if !draggable {
+            return false;
+        }
+
+        let kind = if col == popup_rect.x + popup_rect.width - 1
+            && row == popup_rect.y + popup_rect.height - 1
+        {
+            PopupDragKind::Resize
+        } else if row == popup_rect.y {
+            PopupDragKind::Move
+        } else {
+            return false;
+        };
+
+        // Pin the popup where it is so it no longer follows the cursor
+        if let Some(popup) = self.active_state_mut().popups.get_mut(popup_idx) {
+            popup.position = crate::view::popup::PopupPosition::Fixed {
+                x: popup_rect.x,
+                y: popup_rect.y,
+            };
+        }
+
+        self.mouse_state.dragging_popup = Some((popup_idx, kind));
+        self.mouse_state.drag_start_position = Some((col, row));
+        self.mouse_state.drag_start_popup_area = Some(popup_rect);
+        true
+    }
+
+    /// Handle popup drag (move or resize)
+    fn handle_popup_drag(&mut self, col: u16, row: u16, popup_idx: usize, kind: PopupDragKind) {
+        use crate::view::popup::{PopupPosition, MIN_POPUP_HEIGHT, MIN_POPUP_WIDTH};
+
+        let (Some((start_col, start_row)), Some(start_area)) = (
+            self.mouse_state.drag_start_position,
+            self.mouse_state.drag_start_popup_area,
+        ) else {
+            return;
+        };
+
+        let delta_x = col as i32 - start_col as i32;
+        let delta_y = row as i32 - start_row as i32;
+
+        let Some(popup) = self.active_state_mut().popups.get_mut(popup_idx) else {
+            return;
+        };
+
+        match kind {
+            PopupDragKind::Move => {
+                popup.position = PopupPosition::Fixed {
+                    x: (start_area.x as i32 + delta_x).max(0) as u16,
+                    y: (start_area.y as i32 + delta_y).max(0) as u16,
+                };
+            }
+            PopupDragKind::Resize => {
+                popup.width =
+                    (start_area.width as i32 + delta_x).max(MIN_POPUP_WIDTH as i32) as u16;
+                popup.max_height =
+                    (start_area.height as i32 + delta_y).max(MIN_POPUP_HEIGHT as i32) as u16;
+            }
+        }
+    }
+
     /// Check if mouse position is over a transient popup (hover, signature help)
     fn is_mouse_over_transient_popup(&self, col: u16, row: u16) -> bool {
         // Check if there's a transient popup showing
@@ -604,7 +793,23 @@ impl Editor {
             return Ok(());
         }
 
-        // Find which split/buffer was clicked and handle double-click
+        self.handle_mouse_multi_click(col, row, Action::SelectWord)
+    }
+
+    /// Handle mouse triple click (down event)
+    /// Triple-click in editor area selects the line under the cursor.
+    pub(super) fn handle_mouse_triple_click(&mut self, col: u16, row: u16) -> std::io::Result<()> {
+        tracing::debug!("handle_mouse_triple_click at col={}, row={}", col, row);
+        self.handle_mouse_multi_click(col, row, Action::SelectLine)
+    }
+
+    /// Find which split/buffer was clicked and run `action` at the click
+    fn handle_mouse_multi_click(
+        &mut self,
+        col: u16,
+        row: u16,
+        action: Action,
+    ) -> std::io::Result<()> {
         let split_areas = self.cached_layout.split_areas.clone();
         for (split_id, buffer_id, content_rect, _scrollbar_rect, _thumb_start, _thumb_end) in
             &split_areas
@@ -614,17 +819,24 @@ impl Editor {
                 && row >= content_rect.y
                 && row < content_rect.y + content_rect.height
             {
-                // Double-clicked on an editor split
+                // Multi-clicked on an editor split
                 if self.is_terminal_buffer(*buffer_id) {
                     self.key_context = crate::input::keybindings::KeyContext::Terminal;
-                    // Don't select word in terminal buffers
+                    // Don't select in terminal buffers
                     return Ok(());
                 }
 
                 self.key_context = crate::input::keybindings::KeyContext::Normal;
 
-                // Position cursor at click location and select word
-                self.handle_editor_double_click(col, row, *split_id, *buffer_id, *content_rect)?;
+                // Position cursor at click location and select around it
+                self.handle_editor_multi_click(
+                    col,
+                    row,
+                    *split_id,
+                    *buffer_id,
+                    *content_rect,
+                    action,
+                )?;
                 return Ok(());
             }
         }
@@ -632,14 +844,16 @@ impl Editor {
         Ok(())
     }
 
-    /// Handle double-click in editor content area - selects the word under cursor
-    fn handle_editor_double_click(
+    /// Handle a double or triple click in editor content area - moves the
+    /// cursor there and selects the word or line with `action`
+    fn handle_editor_multi_click(
         &mut self,
         col: u16,
         row: u16,
         split_id: crate::model::event::SplitId,
         buffer_id: BufferId,
         content_rect: ratatui::layout::Rect,
+        action: Action,
     ) -> std::io::Result<()> {
         use crate::model::event::Event;
 
@@ -694,11 +908,123 @@ impl Editor {
             state.apply(&event);
         }
 
-        // Now select the word under cursor
-        self.handle_action(Action::SelectWord)?;
+        // Now select the word or line under cursor
+        self.handle_action(action)?;
 
         Ok(())
     }
+
+    /// Handle Ctrl+click: add a cursor at the clicked position in the text
+    ///
+    /// Returns false if the click was not on the text of an editor split, so
+    /// it is handled as a plain click.
+    pub(super) fn handle_mouse_ctrl_click(&mut self, col: u16, row: u16) -> bool {
+        use crate::model::event::Event;
+
+        let Some((split_id, buffer_id, content_rect)) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, content_rect, _, _, _)| {
+                (*split_id, *buffer_id, *content_rect)
+            })
+        else {
+            return false;
+        };
+        if self.is_terminal_buffer(buffer_id) {
+            return false;
+        }
+
+        let cached_mappings = self
+            .cached_layout
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let fallback = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.top_byte)
+            .unwrap_or(0);
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        let gutter_width = state.margins.left_total_width() as u16;
+        let Some(position) = Self::screen_to_buffer_position(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &cached_mappings,
+            fallback,
+            false, // A click in the gutter moves the cursor as usual
+        ) else {
+            return false;
+        };
+
+        self.focus_split(split_id, buffer_id);
+        self.key_context = crate::input::keybindings::KeyContext::Normal;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return true;
+        };
+        // Clicking where a cursor already is doesn't add another one
+        if state.cursors.iter().any(|(_, c)| c.position == position) {
+            return true;
+        }
+        let next_id = state
+            .cursors
+            .iter()
+            .map(|(id, _)| id.0 + 1)
+            .max()
+            .unwrap_or(0);
+        let event = Event::AddCursor {
+            cursor_id: CursorId(next_id),
+            position,
+            anchor: None,
+        };
+        if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+            event_log.append(event.clone());
+        }
+        state.apply(&event);
+        let count = state.cursors.count();
+        self.set_status_message(format!("Added cursor ({} cursors)", count));
+        true
+    }
+
+    /// Keep extending a drag selection while the mouse is held beyond the
+    /// top or bottom edge of its split, scrolling the split
+    ///
+    /// Returns true if the selection was extended.
+    pub fn check_drag_autoscroll(&mut self) -> bool {
+        if !self.mouse_state.dragging_text_selection {
+            return false;
+        }
+        let Some((col, row)) = self.mouse_state.last_position else {
+            return false;
+        };
+        let Some(content_rect) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(sid, _, _, _, _, _)| Some(*sid) == self.mouse_state.drag_selection_split)
+            .map(|(_, _, rect, _, _, _)| *rect)
+        else {
+            return false;
+        };
+        if row >= content_rect.y && row < content_rect.y + content_rect.height {
+            return false;
+        }
+        let due = self.mouse_state.last_drag_autoscroll.is_none_or(|last| {
+            self.time_source.now().duration_since(last) >= DRAG_AUTOSCROLL_INTERVAL
+        });
+        due && self.handle_text_selection_drag(col, row).is_ok()
+    }
+
     /// Handle mouse click (down event)
     pub(super) fn handle_mouse_click(&mut self, col: u16, row: u16) -> std::io::Result<()> {
         // Check if click is on suggestions (command palette, autocomplete)
@@ -724,6 +1050,11 @@ impl Editor {
             }
         }
 
+        // Check if click is on a popup's title bar or resize corner
+        if self.start_popup_drag(col, row) {
+            return Ok(());
+        }
+
         // Check if click is on a popup (they're rendered on top)
         for (_popup_idx, _popup_rect, inner_rect, scroll_offset, num_items) in
             self.cached_layout.popup_areas.iter().rev()
@@ -754,6 +1085,19 @@ impl Editor {
             }
         }
 
+        // Other clicks inside a persistent popup shouldn't reach the text underneath
+        if let Some((popup_idx, _, _)) = self.popup_at(col, row) {
+            let persistent = self
+                .active_state()
+                .popups
+                .all()
+                .get(popup_idx)
+                .is_some_and(|p| !p.transient);
+            if persistent {
+                return Ok(());
+            }
+        }
+
         // Check if click is on the file browser popup
         if self.is_file_open_active() {
             if self.handle_file_open_click(col, row) {
@@ -813,6 +1157,29 @@ impl Editor {
             return Ok(());
         }
 
+        // Check if click is on the status bar language indicator
+        if let Some((lang_row, start_col, end_col)) = self.cached_layout.status_bar_language_area {
+            if row == lang_row && col >= start_col && col < end_col {
+                return self.handle_action(Action::ChangeLanguageMode);
+            }
+        }
+
+        // Check if click is on the status bar indentation indicator
+        if let Some((indent_row, start_col, end_col)) =
+            self.cached_layout.status_bar_indentation_area
+        {
+            if row == indent_row && col >= start_col && col < end_col {
+                return self.handle_action(Action::SetIndentation);
+            }
+        }
+
+        // Check if click is on the status bar health indicator
+        if let Some((health_row, start_col, end_col)) = self.cached_layout.status_bar_health_area {
+            if row == health_row && col >= start_col && col < end_col {
+                return self.handle_action(Action::ShowSystemHealth);
+            }
+        }
+
         // Check if click is on file explorer
         if let Some(explorer_area) = self.cached_layout.file_explorer_area {
             if col >= explorer_area.x
@@ -1009,6 +1376,12 @@ impl Editor {
 
     /// Handle mouse drag event
     pub(super) fn handle_mouse_drag(&mut self, col: u16, row: u16) -> std::io::Result<()> {
+        // If dragging a popup, move or resize it
+        if let Some((popup_idx, kind)) = self.mouse_state.dragging_popup {
+            self.handle_popup_drag(col, row, popup_idx, kind);
+            return Ok(());
+        }
+
         // If dragging scrollbar, update scroll position
         if let Some(dragging_split_id) = self.mouse_state.dragging_scrollbar {
             // Find the buffer and scrollbar rect for this split
@@ -1114,9 +1487,20 @@ impl Editor {
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let gutter_width = state.margins.left_total_width() as u16;
 
-            let Some(target_position) = Self::screen_to_buffer_position(
+            // Beyond the top or bottom edge, select lines past the edge; the
+            // split scrolls to show the cursor
+            let last_row = content_rect.y + content_rect.height.saturating_sub(1);
+            let lines_beyond = if row < content_rect.y {
+                -((content_rect.y - row) as isize)
+            } else if row > last_row {
+                (row - last_row) as isize
+            } else {
+                0
+            };
+
+            let Some(mut target_position) = Self::screen_to_buffer_position(
                 col,
-                row,
+                row.clamp(content_rect.y, last_row),
                 content_rect,
                 gutter_width,
                 &cached_mappings,
@@ -1125,6 +1509,12 @@ impl Editor {
             ) else {
                 return Ok(());
             };
+            if lines_beyond != 0 {
+                let (line, column) = state.buffer.position_to_line_col(target_position);
+                let line = line.saturating_add_signed(lines_beyond);
+                target_position = state.buffer.line_col_to_position(line, column);
+                self.mouse_state.last_drag_autoscroll = Some(self.time_source.now());
+            }
 
             // Move cursor to target position while keeping anchor to create selection
             let primary_cursor_id = state.cursors.primary_id();
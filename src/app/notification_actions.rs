@@ -0,0 +1,66 @@
+//! Toast notification handlers.
+
+use super::Editor;
+use crate::view::notification::{NotificationLevel, NotificationManager};
+
+impl Editor {
+    /// Show a toast notification
+    pub fn notify(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        self.notify_from(level, message, None);
+    }
+
+    /// Show a toast notification attributed to `source` (e.g. a plugin name)
+    pub fn notify_from(
+        &mut self,
+        level: NotificationLevel,
+        message: impl Into<String>,
+        source: Option<String>,
+    ) {
+        let message = message.into();
+        tracing::debug!("Notification ({}): {}", level.label(), message);
+        let now = self.time_source.now();
+        self.notifications.push(level, message, source, now);
+    }
+
+    /// Get the notification manager (visible toasts and history)
+    pub fn notifications(&self) -> &NotificationManager {
+        &self.notifications
+    }
+
+    /// Open the notification history in a read-only buffer, newest first
+    pub(super) fn show_notifications_in_buffer(&mut self) {
+        if self.notifications.history_len() == 0 {
+            self.set_status_message("No notifications".to_string());
+            return;
+        }
+
+        let now = self.time_source.now();
+        let mut content = String::from("// Notifications (newest first)\n\n");
+        for notification in self.notifications.history().rev() {
+            let age = now.saturating_duration_since(notification.created_at);
+            let source = notification
+                .source
+                .as_ref()
+                .map(|s| format!(" [{}]", s))
+                .unwrap_or_default();
+            content.push_str(&format!(
+                "{:<7}{} ({}s ago)\n  {}\n\n",
+                notification.level.label(),
+                source,
+                age.as_secs(),
+                notification.message.replace('\n', "\n  ")
+            ));
+        }
+
+        self.open_read_only_buffer("*Notifications*".to_string(), "notification-list", &content);
+        self.set_status_message(format!(
+            "Showing {} notification(s)",
+            self.notifications.history_len()
+        ));
+    }
+
+    /// Dismiss all visible toasts
+    pub(super) fn dismiss_notifications(&mut self) {
+        self.notifications.dismiss_all();
+    }
+}
@@ -4,7 +4,7 @@
 //! such as formatters, linters, and other tools.
 
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::Duration;
 
@@ -24,6 +24,19 @@ enum ActionResult {
 }
 
 impl Editor {
+    /// Whether saving the active buffer runs a formatter or on-save actions
+    pub(super) fn has_on_save_actions(&self) -> bool {
+        let Some(path) = self.active_state().buffer.file_path() else {
+            return false;
+        };
+        detect_language(path, &self.config.languages)
+            .and_then(|language| self.config.languages.get(&language))
+            .is_some_and(|lang_config| {
+                (lang_config.format_on_save && lang_config.formatter.is_some())
+                    || lang_config.on_save.iter().any(|action| action.enabled)
+            })
+    }
+
     /// Run on-save actions for the active buffer after a successful save.
     /// This includes format-on-save (if enabled) and any on_save actions.
     /// Returns Ok(true) if actions ran successfully, Ok(false) if no actions,
@@ -50,7 +63,7 @@ impl Editor {
         // Run formatter if format_on_save is enabled
         if lang_config.format_on_save {
             if let Some(ref formatter) = lang_config.formatter {
-                match self.run_formatter(formatter, &path) {
+                match self.run_formatter(formatter, &path, None) {
                     ActionResult::Success(output) => {
                         self.replace_buffer_with_output(&output)?;
                         // Re-save after formatting
@@ -101,6 +114,82 @@ impl Editor {
     /// Format the current buffer using the configured formatter.
     /// Returns Ok(()) if formatting succeeded, or Err with an error message.
     pub fn format_buffer(&mut self) -> Result<(), String> {
+        let (path, formatter) = self.active_formatter()?;
+
+        match self.run_formatter(&formatter, &path, None) {
+            ActionResult::Success(output) => {
+                self.replace_buffer_with_output(&output)?;
+                self.set_status_message(format!("Formatted with {}", formatter.command));
+                Ok(())
+            }
+            ActionResult::CommandNotFound(cmd) => Err(format!("Formatter '{}' not found", cmd)),
+            ActionResult::Error(e) => Err(e),
+        }
+    }
+
+    /// Format the primary selection using the configured formatter, which
+    /// must read the text from stdin.
+    /// Returns Ok(()) if formatting succeeded, or Err with an error message.
+    pub fn format_selection(&mut self) -> Result<(), String> {
+        let Some(range) = self.active_state().cursors.primary().selection_range() else {
+            return Err("No selection to format".to_string());
+        };
+        let (path, formatter) = self.active_formatter()?;
+        if !formatter.stdin {
+            return Err(format!(
+                "Formatter '{}' doesn't read stdin, so it can only format the whole buffer",
+                formatter.command
+            ));
+        }
+
+        let text = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        let mut output = match self.run_formatter(&formatter, &path, Some(&text)) {
+            ActionResult::Success(output) => output,
+            ActionResult::CommandNotFound(cmd) => {
+                return Err(format!("Formatter '{}' not found", cmd))
+            }
+            ActionResult::Error(e) => return Err(e),
+        };
+        // Formatters end their output with a newline; keep the selection's own ending
+        if !text.ends_with('\n') {
+            output.truncate(output.trim_end_matches(['\n', '\r']).len());
+        }
+        if output == text {
+            return Ok(());
+        }
+
+        let cursor_id = self.active_state().cursors.primary_id();
+        let batch = Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: range.clone(),
+                    deleted_text: text,
+                    cursor_id,
+                },
+                Event::Insert {
+                    position: range.start,
+                    text: output,
+                    cursor_id,
+                },
+            ],
+            description: "Format selection".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+        self.set_status_message(format!("Formatted selection with {}", formatter.command));
+        Ok(())
+    }
+
+    /// Whether the active buffer's formatter can format a selection
+    pub(super) fn can_format_selection(&self) -> bool {
+        self.active_formatter()
+            .is_ok_and(|(_, formatter)| formatter.stdin)
+    }
+
+    /// The file of the active buffer and the formatter configured for its language
+    fn active_formatter(&self) -> Result<(PathBuf, FormatterConfig), String> {
         let path = match self.active_state().buffer.file_path() {
             Some(p) => p.to_path_buf(),
             None => {
@@ -123,24 +212,19 @@ impl Editor {
             .get(&language)
             .and_then(|lc| lc.formatter.clone());
 
-        let formatter = match formatter {
-            Some(f) => f,
-            None => return Err(format!("No formatter configured for {}", language)),
-        };
-
-        match self.run_formatter(&formatter, &path) {
-            ActionResult::Success(output) => {
-                self.replace_buffer_with_output(&output)?;
-                self.set_status_message(format!("Formatted with {}", formatter.command));
-                Ok(())
-            }
-            ActionResult::CommandNotFound(cmd) => Err(format!("Formatter '{}' not found", cmd)),
-            ActionResult::Error(e) => Err(e),
+        match formatter {
+            Some(f) => Ok((path, f)),
+            None => Err(format!("No formatter configured for {}", language)),
         }
     }
 
-    /// Run a formatter on the current buffer content.
-    fn run_formatter(&mut self, formatter: &FormatterConfig, file_path: &Path) -> ActionResult {
+    /// Run a formatter on `input`, or on the current buffer content if `None`.
+    fn run_formatter(
+        &mut self,
+        formatter: &FormatterConfig,
+        file_path: &Path,
+        input: Option<&str>,
+    ) -> ActionResult {
         let file_path_str = file_path.display().to_string();
 
         // Check if command exists
@@ -187,9 +271,12 @@ impl Editor {
             }
         };
 
-        // Write buffer content to stdin if configured
+        // Write the input to stdin if configured
         if formatter.stdin {
-            let content = self.active_state().buffer.to_string().unwrap_or_default();
+            let content = match input {
+                Some(text) => text.to_string(),
+                None => self.active_state().buffer.to_string().unwrap_or_default(),
+            };
             if let Some(mut stdin) = child.stdin.take() {
                 if let Err(e) = stdin.write_all(content.as_bytes()) {
                     return ActionResult::Error(format!("Failed to write to stdin: {}", e));
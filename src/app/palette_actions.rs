@@ -0,0 +1,274 @@
+//! Command palette modes other than commands.
+//!
+//! This module provides functionality to:
+//! - List the files of the working directory for the `/` mode
+//! - List the symbols of the active buffer for the `@` mode
+//! - Search the symbols of the workspace for the `#` mode, asking the
+//!   language server (`workspace/symbol`) and falling back to an index built
+//!   in the background
+//! - Open the chosen file or jump to the chosen symbol
+
+use super::{uri_to_path, Editor};
+use crate::input::command_palette::{self, PaletteMode};
+use crate::input::commands::Suggestion;
+use crate::primitives::sticky_scroll::all_scope_headers;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::symbol_index::{self, IndexedSymbol};
+use crate::view::prompt::PromptType;
+use lsp_types::{OneOf, SymbolKind, WorkspaceSymbolResponse};
+use std::path::PathBuf;
+
+/// Only symbols in the first this many bytes of a buffer are listed
+const SYMBOL_SCAN_BYTES: usize = 1024 * 1024;
+
+/// Files and symbols listed while the palette is open, so they are not
+/// collected again on every keystroke
+#[derive(Default)]
+pub(crate) struct PaletteCache {
+    files: Option<Vec<String>>,
+    symbols: Option<Vec<(usize, String)>>,
+    /// Latest `workspace/symbol` results of the language server
+    lsp_symbols: Vec<IndexedSymbol>,
+    /// Whether this palette already started refreshing the symbol index
+    index_refreshed: bool,
+}
+
+/// Symbols of the project, rebuilt in the background each time a palette
+/// starts searching them; the previous index is searched meanwhile
+#[derive(Default)]
+pub(crate) struct SymbolIndex {
+    /// Directory the symbols were collected from
+    root: PathBuf,
+    symbols: Vec<IndexedSymbol>,
+    building: bool,
+}
+
+impl Editor {
+    /// Fill the command palette with the suggestions for `input`
+    pub(super) fn update_command_palette(&mut self, input: &str) {
+        let selection_active = self.has_active_selection();
+        let suggestions = match command_palette::parse_query(input) {
+            // Use the underlying context (not Prompt context) for filtering
+            (PaletteMode::Commands, query) => self.command_registry.read().unwrap().filter(
+                query,
+                self.key_context,
+                &self.keybindings,
+                selection_active,
+                &self.active_custom_contexts,
+            ),
+            (mode, query) => self.palette_mode_suggestions(mode, query),
+        };
+        if let Some(prompt) = &mut self.prompt {
+            prompt.suggestions = suggestions;
+            prompt.selected_suggestion = if prompt.suggestions.is_empty() {
+                None
+            } else {
+                Some(0)
+            };
+        }
+    }
+
+    /// Suggestions for the `/`, `@` and `#` palette modes
+    fn palette_mode_suggestions(&mut self, mode: PaletteMode, query: &str) -> Vec<Suggestion> {
+        match mode {
+            PaletteMode::Commands => Vec::new(),
+            PaletteMode::Files => {
+                let working_dir = self.working_dir.clone();
+                let files = self.palette_cache.files.get_or_insert_with(|| {
+                    command_palette::list_files(&working_dir, command_palette::MAX_FILES)
+                });
+                command_palette::file_suggestions(query, files)
+            }
+            PaletteMode::Symbols => {
+                if self.palette_cache.symbols.is_none() {
+                    let state = self.active_state();
+                    let language = state.highlighter.language();
+                    let is_markdown = state.highlighter.syntax_name() == Some("Markdown");
+                    let headers =
+                        all_scope_headers(&state.buffer, language, is_markdown, SYMBOL_SCAN_BYTES);
+                    let source = state
+                        .buffer
+                        .slice_bytes(0..state.buffer.len().min(SYMBOL_SCAN_BYTES));
+                    self.palette_cache.symbols =
+                        Some(command_palette::list_symbols(&source, &headers));
+                }
+                let symbols = self.palette_cache.symbols.as_deref().unwrap_or_default();
+                command_palette::symbol_suggestions(query, symbols)
+            }
+            PaletteMode::WorkspaceSymbols => {
+                if !self.palette_cache.index_refreshed {
+                    self.palette_cache.index_refreshed = true;
+                    self.start_symbol_indexing();
+                }
+                // The language server knows the project best; the index
+                // covers servers that are missing, starting or silent
+                let symbols = if !self.palette_cache.lsp_symbols.is_empty() {
+                    &self.palette_cache.lsp_symbols[..]
+                } else if self.symbol_index.root == self.working_dir {
+                    &self.symbol_index.symbols[..]
+                } else {
+                    &[]
+                };
+                command_palette::workspace_symbol_suggestions(query, symbols, &self.working_dir)
+            }
+        }
+    }
+
+    /// Rebuild the symbol index of the working directory in the background
+    fn start_symbol_indexing(&mut self) {
+        if self.symbol_index.building {
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            return;
+        };
+        self.symbol_index.building = true;
+        let root = self.working_dir.clone();
+        std::thread::spawn(move || {
+            let symbols = symbol_index::index_workspace(&root);
+            let _ = sender.send(AsyncMessage::SymbolIndexBuilt { root, symbols });
+        });
+    }
+
+    /// Handle the symbol index built in the background
+    pub(super) fn handle_symbol_index_built(&mut self, root: PathBuf, symbols: Vec<IndexedSymbol>) {
+        self.symbol_index.building = false;
+        // Dropped if the project was switched while indexing
+        if root != self.working_dir {
+            return;
+        }
+        self.symbol_index.root = root;
+        self.symbol_index.symbols = symbols;
+        self.refresh_workspace_symbol_palette();
+    }
+
+    /// Ask the language server of the active buffer for the workspace symbols
+    /// matching `query`
+    pub(super) fn request_workspace_symbols(&mut self, query: &str) {
+        if query.is_empty() || !self.is_lsp_ready_for_active_buffer() {
+            return;
+        }
+        let request_id = self.next_lsp_request_id;
+        let buffer_id = self.active_buffer();
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, |handle, _uri, _language| {
+                handle
+                    .workspace_symbols(request_id, query.to_string())
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.pending_workspace_symbols_request = Some(request_id);
+        }
+    }
+
+    /// Handle the language server's answer to a workspace symbol search
+    pub(super) fn handle_workspace_symbols_response(
+        &mut self,
+        request_id: u64,
+        response: Option<WorkspaceSymbolResponse>,
+    ) {
+        if self.pending_workspace_symbols_request != Some(request_id) {
+            tracing::debug!("Ignoring stale workspace symbols response: {}", request_id);
+            return;
+        }
+        self.pending_workspace_symbols_request = None;
+
+        let symbols = match response {
+            Some(WorkspaceSymbolResponse::Flat(symbols)) => symbols
+                .into_iter()
+                .filter_map(|symbol| {
+                    Some(IndexedSymbol {
+                        path: uri_to_path(&symbol.location.uri).ok()?,
+                        line: symbol.location.range.start.line as usize + 1,
+                        kind: lsp_symbol_kind(symbol.kind),
+                        name: symbol.name,
+                    })
+                })
+                .collect(),
+            Some(WorkspaceSymbolResponse::Nested(symbols)) => symbols
+                .into_iter()
+                .filter_map(|symbol| {
+                    let (uri, line) = match &symbol.location {
+                        OneOf::Left(location) => (&location.uri, location.range.start.line),
+                        OneOf::Right(location) => (&location.uri, 0),
+                    };
+                    Some(IndexedSymbol {
+                        path: uri_to_path(uri).ok()?,
+                        line: line as usize + 1,
+                        kind: lsp_symbol_kind(symbol.kind),
+                        name: symbol.name,
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        self.palette_cache.lsp_symbols = symbols;
+        self.refresh_workspace_symbol_palette();
+    }
+
+    /// Re-filter the palette if it is showing workspace symbols
+    fn refresh_workspace_symbol_palette(&mut self) {
+        let Some(prompt) = &self.prompt else {
+            return;
+        };
+        if prompt.prompt_type != PromptType::Command {
+            return;
+        }
+        let input = prompt.input.clone();
+        if command_palette::parse_query(&input).0 == PaletteMode::WorkspaceSymbols {
+            self.update_command_palette(&input);
+        }
+    }
+
+    /// Open a file chosen in the `/` palette mode, relative to the working directory
+    pub(super) fn open_palette_file(&mut self, relative: &str) {
+        let path = self.working_dir.join(relative);
+        if relative.is_empty() || !path.is_file() {
+            self.set_status_message(format!("No file matches '{}'", relative));
+            return;
+        }
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(format!("Error opening file: {e}"));
+        }
+    }
+
+    /// Jump to a symbol chosen in the `@` palette mode
+    pub(super) fn goto_palette_symbol(&mut self, line: &str) {
+        match line.parse::<usize>() {
+            Ok(line) if line > 0 => self.goto_line_col(line, None),
+            _ => self.set_status_message(format!("No symbol matches '{}'", line)),
+        }
+    }
+
+    /// Open the file of a symbol chosen in the `#` palette mode at its line
+    pub(super) fn goto_workspace_symbol(&mut self, value: &str) {
+        let Some((line, path)) = command_palette::parse_workspace_symbol(value) else {
+            self.set_status_message(format!("No symbol matches '{}'", value));
+            return;
+        };
+        match self.open_file(&path) {
+            Ok(_) => self.goto_line_col(line, None),
+            Err(e) => self.set_status_message(format!("Error opening file: {e}")),
+        }
+    }
+}
+
+/// Short name of an LSP symbol kind, as shown next to the symbol
+fn lsp_symbol_kind(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::FUNCTION => "function",
+        SymbolKind::METHOD | SymbolKind::CONSTRUCTOR => "method",
+        SymbolKind::CLASS => "class",
+        SymbolKind::STRUCT => "struct",
+        SymbolKind::ENUM => "enum",
+        SymbolKind::INTERFACE => "interface",
+        SymbolKind::MODULE | SymbolKind::NAMESPACE | SymbolKind::PACKAGE => "module",
+        SymbolKind::CONSTANT => "constant",
+        SymbolKind::VARIABLE => "variable",
+        SymbolKind::FIELD | SymbolKind::PROPERTY => "field",
+        SymbolKind::TYPE_PARAMETER => "type",
+        _ => "symbol",
+    }
+}
@@ -4,14 +4,91 @@
 
 use crate::model::event::{BufferId, CursorId, Event, SplitId};
 use crate::services::plugins::api::{
-    LayoutHints, MenuPosition, PluginResponse, ViewTransformPayload,
+    BufferQuery, LayoutHints, MenuPosition, PluginResponse, ViewTransformPayload,
 };
+use crate::state::EditorState;
 use crate::view::overlay::{OverlayHandle, OverlayNamespace};
 use crate::view::split::SplitViewState;
 use std::io;
 
 use super::Editor;
 
+/// Most bytes a plugin can read or search in one request
+const PLUGIN_READ_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Most matches a plugin search returns
+const PLUGIN_SEARCH_MAX_RESULTS: usize = 10_000;
+
+/// `range` clamped to a buffer of `len` bytes, or an error if it is over
+/// the limit for one request
+fn plugin_read_range(
+    range: std::ops::Range<usize>,
+    len: usize,
+) -> Result<std::ops::Range<usize>, String> {
+    let end = range.end.min(len);
+    let start = range.start.min(end);
+    if end - start > PLUGIN_READ_MAX_BYTES {
+        return Err(format!(
+            "Range of {} bytes is over the limit of {} bytes per request",
+            end - start,
+            PLUGIN_READ_MAX_BYTES
+        ));
+    }
+    Ok(start..end)
+}
+
+/// Answer a plugin's question about a buffer
+fn answer_buffer_query(
+    state: &mut EditorState,
+    query: BufferQuery,
+) -> Result<serde_json::Value, String> {
+    match query {
+        BufferQuery::LineCount => Ok(serde_json::json!(state.buffer.line_count())),
+        BufferQuery::LineAtOffset { offset } => {
+            let offset = offset.min(state.buffer.len());
+            let line = state.buffer.offset_to_position(offset).map(|p| p.line);
+            Ok(serde_json::json!(line))
+        }
+        BufferQuery::LineStartOffset { line } => {
+            Ok(serde_json::json!(state.buffer.line_start_offset(line)))
+        }
+        BufferQuery::Search {
+            pattern,
+            regex,
+            case_sensitive,
+            range,
+            max_results,
+        } => {
+            let len = state.buffer.len();
+            let range = plugin_read_range(range.unwrap_or(0..len), len)?;
+            let pattern = if regex {
+                pattern
+            } else {
+                regex::escape(&pattern)
+            };
+            let regex = regex::bytes::RegexBuilder::new(&pattern)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map_err(|e| format!("Invalid regex: {}", e))?;
+            let text = state
+                .buffer
+                .get_text_range_mut(range.start, range.len())
+                .map_err(|e| e.to_string())?;
+            let matches: Vec<serde_json::Value> = regex
+                .find_iter(&text)
+                .take(max_results.min(PLUGIN_SEARCH_MAX_RESULTS))
+                .map(|m| {
+                    serde_json::json!({
+                        "start": range.start + m.start(),
+                        "end": range.start + m.end(),
+                    })
+                })
+                .collect();
+            Ok(serde_json::Value::Array(matches))
+        }
+    }
+}
+
 impl Editor {
     // ==================== Menu Helpers ====================
 
@@ -52,7 +129,7 @@ impl Editor {
                 namespace,
                 range,
                 face,
-                priority: 10,
+                priority: crate::view::overlay::priority::PLUGIN_HIGHLIGHT,
                 message: None,
             };
             state.apply(&event);
@@ -257,6 +334,16 @@ impl Editor {
         position: MenuPosition,
     ) {
         if let Some(menu) = self.find_menu_by_label_mut(&menu_label) {
+            // Adding an item again (e.g. when its plugin reloads) replaces it
+            if let crate::config::MenuItem::Action {
+                label: new_label, ..
+            } = &item
+            {
+                menu.items.retain(|i| {
+                    !matches!(i, crate::config::MenuItem::Action { label, .. } if label == new_label)
+                });
+            }
+
             // Insert at the specified position
             let insert_idx = match position {
                 MenuPosition::Top => 0,
@@ -295,6 +382,11 @@ impl Editor {
 
     /// Handle AddMenu command
     pub(super) fn handle_add_menu(&mut self, menu: crate::config::Menu, position: MenuPosition) {
+        // Adding a menu again (e.g. when its plugin reloads) replaces it
+        self.menu_state
+            .plugin_menus
+            .retain(|m| m.label != menu.label);
+
         // Calculate insert index based on position
         let total_menus = self.config.menu.menus.len() + self.menu_state.plugin_menus.len();
 
@@ -397,6 +489,28 @@ impl Editor {
         }
     }
 
+    /// Handle AddContextMenuItem command
+    pub(super) fn handle_add_context_menu_item(&mut self, item: crate::config::MenuItem) {
+        let crate::config::MenuItem::Action { label, .. } = &item else {
+            tracing::warn!("Only actions can be added to the context menu");
+            return;
+        };
+        let existing = self.plugin_context_menu_items.iter().position(
+            |other| matches!(other, crate::config::MenuItem::Action { label: l, .. } if l == label),
+        );
+        match existing {
+            Some(index) => self.plugin_context_menu_items[index] = item,
+            None => self.plugin_context_menu_items.push(item),
+        }
+    }
+
+    /// Handle RemoveContextMenuItem command
+    pub(super) fn handle_remove_context_menu_item(&mut self, item_label: String) {
+        self.plugin_context_menu_items.retain(|item| {
+            !matches!(item, crate::config::MenuItem::Action { label, .. } if *label == item_label)
+        });
+    }
+
     // ==================== Split Commands ====================
 
     /// Handle FocusSplit command
@@ -540,6 +654,7 @@ impl Editor {
         buffer_id: BufferId,
         position: usize,
         text: String,
+        plugin: Option<String>,
     ) {
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let event = Event::Insert {
@@ -549,7 +664,7 @@ impl Editor {
             };
             state.apply(&event);
             if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                log.append(event);
+                log.append_from(event, plugin.as_deref().unwrap_or_default());
             }
         }
     }
@@ -559,6 +674,7 @@ impl Editor {
         &mut self,
         buffer_id: BufferId,
         range: std::ops::Range<usize>,
+        plugin: Option<String>,
     ) {
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let deleted_text = state.get_text_range(range.start, range.end);
@@ -569,11 +685,63 @@ impl Editor {
             };
             state.apply(&event);
             if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                log.append(event);
+                log.append_from(event, plugin.as_deref().unwrap_or_default());
             }
         }
     }
 
+    /// Handle BeginUndoGroup command. The group is the plugin's: only its
+    /// own edits join it.
+    pub(super) fn handle_begin_undo_group(&mut self, buffer_id: BufferId, plugin: Option<String>) {
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.begin_group(plugin.as_deref().unwrap_or_default());
+        }
+    }
+
+    /// Handle EndUndoGroup command
+    pub(super) fn handle_end_undo_group(&mut self, buffer_id: BufferId, plugin: Option<String>) {
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.end_group(plugin.as_deref().unwrap_or_default());
+        }
+    }
+
+    /// Close the undo groups a plugin left open, e.g. when it throws an
+    /// error or stops, so its later edits start new undo steps
+    pub(super) fn close_plugin_undo_groups(&mut self, plugin: &str) {
+        for log in self.event_logs.values_mut() {
+            log.close_groups(plugin);
+        }
+    }
+
+    /// Handle GetBufferText command. The range is clamped to the buffer.
+    pub(super) fn handle_get_buffer_text(
+        &mut self,
+        buffer_id: BufferId,
+        range: std::ops::Range<usize>,
+        request_id: u64,
+    ) {
+        let result = match self.buffers.get_mut(&buffer_id) {
+            Some(state) => plugin_read_range(range, state.buffer.len())
+                .map(|range| state.get_text_range(range.start, range.end)),
+            None => Err(format!("Buffer {} not found", buffer_id.0)),
+        };
+        self.send_plugin_response(PluginResponse::BufferText { request_id, result });
+    }
+
+    /// Handle QueryBuffer command
+    pub(super) fn handle_query_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        query: BufferQuery,
+        request_id: u64,
+    ) {
+        let result = match self.buffers.get_mut(&buffer_id) {
+            Some(state) => answer_buffer_query(state, query),
+            None => Err(format!("Buffer {} not found", buffer_id.0)),
+        };
+        self.send_plugin_response(PluginResponse::BufferQuery { request_id, result });
+    }
+
     /// Handle InsertAtCursor command
     pub(super) fn handle_insert_at_cursor(&mut self, text: String) {
         // Insert text at current cursor position in active buffer
@@ -862,6 +1030,21 @@ impl Editor {
         }
     }
 
+    /// Handle ShowNotification command
+    pub(super) fn handle_show_notification(
+        &mut self,
+        level: String,
+        message: String,
+        source: Option<String>,
+    ) {
+        use crate::view::notification::NotificationLevel;
+        if message.trim().is_empty() {
+            return;
+        }
+        let source = source.filter(|s| !s.is_empty());
+        self.notify_from(NotificationLevel::from_name(&level), message, source);
+    }
+
     /// Handle StartPrompt command
     pub(super) fn handle_start_prompt(&mut self, label: String, prompt_type: String) {
         // Create a plugin-controlled prompt
@@ -1015,3 +1198,61 @@ impl Editor {
         self.clipboard.copy(text);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn state_with(text: &str) -> EditorState {
+        let mut state = EditorState::new(80, 24, 1024 * 1024);
+        state.buffer = crate::model::buffer::Buffer::from_str(text, 1024 * 1024);
+        state
+    }
+
+    #[test]
+    fn test_plugin_read_range_is_clamped_and_limited() {
+        assert_eq!(plugin_read_range(2..100, 10), Ok(2..10));
+        assert_eq!(plugin_read_range(20..30, 10), Ok(10..10));
+        assert!(plugin_read_range(0..usize::MAX, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_answer_buffer_query() {
+        let mut state = state_with("fn a() {}\nfn B() {}\nlet c = 1;\n");
+
+        assert_eq!(
+            answer_buffer_query(&mut state, BufferQuery::LineCount),
+            Ok(json!(4))
+        );
+        assert_eq!(
+            answer_buffer_query(&mut state, BufferQuery::LineAtOffset { offset: 12 }),
+            Ok(json!(1))
+        );
+        assert_eq!(
+            answer_buffer_query(&mut state, BufferQuery::LineStartOffset { line: 2 }),
+            Ok(json!(20))
+        );
+        assert_eq!(
+            answer_buffer_query(&mut state, BufferQuery::LineStartOffset { line: 9 }),
+            Ok(json!(null))
+        );
+
+        let search = |pattern: &str, regex, case_sensitive, max_results| BufferQuery::Search {
+            pattern: pattern.to_string(),
+            regex,
+            case_sensitive,
+            range: None,
+            max_results,
+        };
+        assert_eq!(
+            answer_buffer_query(&mut state, search("fn b", false, false, 10)),
+            Ok(json!([{ "start": 10, "end": 14 }]))
+        );
+        assert_eq!(
+            answer_buffer_query(&mut state, search(r"\(\)", true, true, 1)),
+            Ok(json!([{ "start": 4, "end": 6 }]))
+        );
+        assert!(answer_buffer_query(&mut state, search("(", true, true, 1)).is_err());
+    }
+}
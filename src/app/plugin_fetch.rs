@@ -0,0 +1,152 @@
+//! HTTP requests made by plugins.
+//!
+//! This module provides functionality to:
+//! - Check whether a plugin may access the network before making its request
+//! - Ask the user on a plugin's first request, saving the answer in
+//!   `plugin_network_access` in the config
+//! - Make the request on a background thread and send the response back to
+//!   the plugin
+//!
+//! Requests themselves are made by `services::plugins::fetch`.
+
+use super::Editor;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::plugins::api::PluginResponse;
+use crate::services::plugins::fetch::{self, FetchRequest, FetchResponse};
+use crate::view::prompt::PromptType;
+
+/// A plugin's request waiting for the user to allow network access
+#[derive(Debug)]
+pub(super) struct PendingPluginFetch {
+    plugin: String,
+    host: String,
+    request: FetchRequest,
+    request_id: u64,
+}
+
+impl Editor {
+    /// Make an HTTP request for a plugin, asking the user first if the
+    /// config does not say whether the plugin may access the network
+    pub fn handle_plugin_fetch(
+        &mut self,
+        plugin: Option<String>,
+        request: FetchRequest,
+        request_id: u64,
+    ) {
+        let Some(plugin) = plugin else {
+            self.respond_plugin_fetch(
+                request_id,
+                Err("Network access is only available to plugins".to_string()),
+            );
+            return;
+        };
+        let host = match fetch::request_host(&request.url) {
+            Ok(host) => host,
+            Err(e) => {
+                self.respond_plugin_fetch(request_id, Err(e));
+                return;
+            }
+        };
+
+        match self.config.plugin_network_access.get(&plugin) {
+            Some(true) => self.start_plugin_fetch(request, request_id),
+            Some(false) => self.respond_plugin_fetch(request_id, Err(access_denied(&plugin))),
+            None => {
+                self.pending_plugin_fetches.push(PendingPluginFetch {
+                    plugin,
+                    host,
+                    request,
+                    request_id,
+                });
+                self.prompt_plugin_network_access();
+            }
+        }
+    }
+
+    /// Ask whether the plugin of the first waiting request may access the
+    /// network, unless another prompt is open
+    pub(super) fn prompt_plugin_network_access(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+        let Some(pending) = self.pending_plugin_fetches.first() else {
+            return;
+        };
+        let message = format!(
+            "Allow plugin '{}' to access the network ({})? (y/n) ",
+            pending.plugin, pending.host
+        );
+        let plugin = pending.plugin.clone();
+        self.start_prompt(message, PromptType::ConfirmPluginNetworkAccess { plugin });
+    }
+
+    /// Handle the ConfirmPluginNetworkAccess prompt. The answer is saved to
+    /// the config.
+    pub(super) fn handle_confirm_plugin_network_access(&mut self, input: &str, plugin: String) {
+        let input_lower = input.trim().to_lowercase();
+        let allowed = input_lower == "y" || input_lower == "yes";
+        self.config
+            .plugin_network_access
+            .insert(plugin.clone(), allowed);
+        self.set_status_message(if allowed {
+            format!("Plugin '{}' may access the network", plugin)
+        } else {
+            format!("Plugin '{}' may not access the network", plugin)
+        });
+        if let Err(e) = self.save_config() {
+            self.set_status_message(e);
+        }
+
+        for pending in self.take_pending_plugin_fetches(&plugin) {
+            if allowed {
+                self.start_plugin_fetch(pending.request, pending.request_id);
+            } else {
+                self.respond_plugin_fetch(pending.request_id, Err(access_denied(&plugin)));
+            }
+        }
+        self.prompt_plugin_network_access();
+    }
+
+    /// Fail the waiting requests of a plugin whose prompt was canceled. The
+    /// plugin is asked again on its next request.
+    pub(super) fn cancel_plugin_network_access(&mut self, plugin: &str) {
+        for pending in self.take_pending_plugin_fetches(plugin) {
+            self.respond_plugin_fetch(pending.request_id, Err(access_denied(plugin)));
+        }
+    }
+
+    /// Send the response to a plugin's request
+    pub(super) fn respond_plugin_fetch(
+        &mut self,
+        request_id: u64,
+        result: Result<FetchResponse, String>,
+    ) {
+        self.send_plugin_response(PluginResponse::Fetch { request_id, result });
+    }
+
+    /// Remove and return the waiting requests of `plugin`
+    fn take_pending_plugin_fetches(&mut self, plugin: &str) -> Vec<PendingPluginFetch> {
+        let (taken, rest) = std::mem::take(&mut self.pending_plugin_fetches)
+            .into_iter()
+            .partition(|pending| pending.plugin == plugin);
+        self.pending_plugin_fetches = rest;
+        taken
+    }
+
+    /// Make a request on a background thread. The response arrives as
+    /// `AsyncMessage::PluginFetchResult`.
+    fn start_plugin_fetch(&mut self, request: FetchRequest, request_id: u64) {
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.respond_plugin_fetch(request_id, Err("Async runtime not available".to_string()));
+            return;
+        };
+        std::thread::spawn(move || {
+            let result = fetch::fetch(&request);
+            let _ = sender.send(AsyncMessage::PluginFetchResult { request_id, result });
+        });
+    }
+}
+
+fn access_denied(plugin: &str) -> String {
+    format!("Plugin '{}' may not access the network", plugin)
+}
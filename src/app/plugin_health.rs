@@ -0,0 +1,221 @@
+//! Plugin errors, restarts and log.
+//!
+//! This module provides functionality to:
+//! - Record what plugins print and the errors they throw in the plugin log,
+//!   shown by the Show Plugin Log command
+//! - Disable a plugin that keeps throwing errors, and restart it later with
+//!   exponential backoff
+//! - Respawn the plugin host when its thread stops, say after a panic
+//! - List the plugins and whether they are running with Show Plugins
+//!
+//! The policy lives in `services::plugins::health`.
+
+use super::Editor;
+use crate::services::plugins::health::{PluginHealth, PluginLogLevel, PluginState};
+use crate::view::notification::NotificationLevel;
+use std::time::Instant;
+
+/// How a plugin state reads in the plugin list
+pub(super) fn describe_state(state: &PluginState, now: Instant) -> String {
+    match state {
+        PluginState::Running => "running".to_string(),
+        PluginState::Disabled {
+            restart_at: Some(at),
+            ..
+        } => format!(
+            "disabled, restarting in {}s",
+            at.saturating_duration_since(now).as_secs_f64().ceil()
+        ),
+        PluginState::Disabled {
+            restart_at: None, ..
+        } => "disabled, restarted too often".to_string(),
+    }
+}
+
+impl Editor {
+    /// The plugin log and the states of plugins that have thrown errors
+    pub fn plugin_health(&self) -> &PluginHealth {
+        &self.plugin_health
+    }
+
+    /// Add a line a plugin printed to the plugin log
+    pub fn record_plugin_log(&mut self, plugin: Option<String>, level: &str, message: String) {
+        let now = self.time_source.now();
+        self.plugin_health.log(
+            plugin.as_deref(),
+            PluginLogLevel::parse(level),
+            message,
+            now,
+        );
+    }
+
+    /// Record an error thrown by a plugin, closing the undo groups it left
+    /// open and disabling the plugin if it keeps throwing
+    pub fn record_plugin_error(&mut self, plugin: Option<String>, message: String) {
+        let now = self.time_source.now();
+        tracing::warn!("Plugin error ({:?}): {}", plugin, message);
+        self.close_plugin_undo_groups(plugin.as_deref().unwrap_or_default());
+        let Some(plugin) = plugin else {
+            self.plugin_health
+                .log(None, PluginLogLevel::Error, message, now);
+            return;
+        };
+        if !self.plugin_health.record_error(&plugin, &message, now) {
+            return;
+        }
+
+        if let Err(e) = self.plugin_manager.disable_plugin(&plugin) {
+            tracing::warn!("Failed to disable plugin '{}': {}", plugin, e);
+        }
+        let state = self.plugin_health.state(&plugin);
+        self.notify_from(
+            NotificationLevel::Error,
+            format!(
+                "Plugin '{}' {} after repeated errors: {}",
+                plugin,
+                describe_state(&state, now),
+                message.lines().next().unwrap_or_default()
+            ),
+            Some(plugin),
+        );
+    }
+
+    /// Restart the plugin host if it stopped, and plugins whose restart is
+    /// due
+    pub(super) fn process_plugin_restarts(&mut self) {
+        let now = self.time_source.now();
+
+        if self.plugin_manager.host_stopped()
+            && *self.plugin_health.host_state() == PluginState::Running
+        {
+            self.plugin_health
+                .host_stopped("Plugin host stopped".to_string(), now);
+            // Every plugin is started again with the host
+            for log in self.event_logs.values_mut() {
+                log.close_all_groups();
+            }
+            let state = self.plugin_health.host_state().clone();
+            self.notify(
+                NotificationLevel::Error,
+                format!("Plugin host stopped ({})", describe_state(&state, now)),
+            );
+        }
+        if self.plugin_health.take_host_restart(now) {
+            match self.plugin_manager.restart_host() {
+                Ok(()) => self.set_status_message("Plugin host restarted".to_string()),
+                Err(e) => {
+                    tracing::error!("Failed to restart plugin host: {}", e);
+                    self.plugin_health.host_restart_failed(e, now);
+                }
+            }
+        }
+
+        for (plugin, reason) in self.plugin_manager.take_stopped_remote_plugins() {
+            self.close_plugin_undo_groups(&plugin);
+            self.plugin_health.plugin_stopped(
+                &plugin,
+                reason.unwrap_or_else(|| "The plugin's process exited".to_string()),
+                now,
+            );
+            let state = self.plugin_health.state(&plugin);
+            self.notify_from(
+                NotificationLevel::Error,
+                format!(
+                    "Plugin '{}' stopped ({})",
+                    plugin,
+                    describe_state(&state, now)
+                ),
+                Some(plugin),
+            );
+        }
+
+        for plugin in self.plugin_health.take_due_restarts(now) {
+            self.close_plugin_undo_groups(&plugin);
+            match self.plugin_manager.reload_plugin(&plugin) {
+                Ok(()) => self.set_status_message(format!("Plugin '{}' restarted", plugin)),
+                Err(e) => {
+                    tracing::error!("Failed to restart plugin '{}': {}", plugin, e);
+                    self.plugin_health.restart_failed(&plugin, e, now);
+                }
+            }
+        }
+    }
+
+    /// List the plugins and their states in a read-only buffer
+    pub(super) fn show_plugins_in_buffer(&mut self) {
+        let now = self.time_source.now();
+        let mut plugins: Vec<(String, String)> = self
+            .plugin_manager
+            .loaded_plugins()
+            .into_iter()
+            .map(|(name, path)| (name, path.display().to_string()))
+            .collect();
+        for (name, _) in self.plugin_health.states() {
+            if !plugins.iter().any(|(loaded, _)| loaded == name) {
+                plugins.push((name.to_string(), String::new()));
+            }
+        }
+        plugins.sort();
+
+        let mut content = String::from("// Plugins\n\n");
+        content.push_str(&format!(
+            "Plugin host: {}\n\n",
+            if self.plugin_manager.is_active() {
+                describe_state(self.plugin_health.host_state(), now)
+            } else {
+                "not running".to_string()
+            }
+        ));
+        if plugins.is_empty() {
+            content.push_str("No plugins loaded\n");
+        }
+        for (name, path) in &plugins {
+            let state = self.plugin_health.state(name);
+            content.push_str(&format!("{}: {}\n", name, describe_state(&state, now)));
+            if !path.is_empty() {
+                content.push_str(&format!("  {}\n", path));
+            }
+            if let PluginState::Disabled { error, .. } = &state {
+                content.push_str(&format!("  {}\n", error.replace('\n', "\n  ")));
+            }
+            content.push('\n');
+        }
+
+        self.open_plugin_info_buffer(content, "plugin-list", "*Plugins*");
+        self.set_status_message(format!("Showing {} plugin(s)", plugins.len()));
+    }
+
+    /// Open the plugin log in a read-only buffer, oldest first
+    pub(super) fn show_plugin_log_in_buffer(&mut self) {
+        let now = self.time_source.now();
+        let mut content = String::from("// Plugin log (oldest first)\n\n");
+        let mut count = 0;
+        for entry in self.plugin_health.log_entries() {
+            let age = now.saturating_duration_since(entry.time);
+            let plugin = entry
+                .plugin
+                .as_ref()
+                .map(|p| format!(" [{}]", p))
+                .unwrap_or_default();
+            content.push_str(&format!(
+                "{:<5}{} ({}s ago) {}\n",
+                entry.level.label(),
+                plugin,
+                age.as_secs(),
+                entry.message.replace('\n', "\n  ")
+            ));
+            count += 1;
+        }
+        if count == 0 {
+            content.push_str("Nothing logged\n");
+        }
+
+        self.open_plugin_info_buffer(content, "plugin-log", "*Plugin Log*");
+        self.set_status_message(format!("Showing {} plugin log line(s)", count));
+    }
+
+    /// Open `content` in a new read-only virtual buffer
+    fn open_plugin_info_buffer(&mut self, content: String, mode: &str, name: &str) {
+        self.open_read_only_buffer(name.to_string(), mode, &content);
+    }
+}
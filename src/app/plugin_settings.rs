@@ -0,0 +1,71 @@
+//! Settings declared by plugins.
+//!
+//! This module provides functionality to:
+//! - Record the settings a plugin declares with `editor.defineSettings()`,
+//!   which then get a page of their own in the settings UI
+//! - Look up a plugin setting, falling back to its declared default
+//! - Set a plugin setting from `editor.setPluginSetting()` and save it
+//!
+//! Values are stored under `plugins.<namespace>` in the config file.
+
+use super::Editor;
+
+impl Editor {
+    /// Record the settings a plugin declared. `settings` maps each key to a
+    /// JSON Schema property with its type, default and description.
+    pub fn define_plugin_settings(&mut self, namespace: &str, settings: serde_json::Value) {
+        if !settings.is_object() {
+            tracing::warn!(
+                "Plugin '{}' declared settings that are not an object",
+                namespace
+            );
+            return;
+        }
+        self.plugin_settings_schemas
+            .insert(namespace.to_string(), settings);
+        self.drop_hidden_settings_state();
+    }
+
+    /// The value of a plugin setting, or its declared default if it is not
+    /// set
+    pub fn plugin_setting(&self, namespace: &str, key: &str) -> Option<serde_json::Value> {
+        if let Some(value) = self
+            .config
+            .plugins
+            .get(namespace)
+            .and_then(|values| values.get(key))
+        {
+            return Some(value.clone());
+        }
+        self.plugin_settings_schemas
+            .get(namespace)
+            .and_then(|settings| settings.get(key))
+            .and_then(|property| property.get("default"))
+            .cloned()
+    }
+
+    /// Set a plugin setting and save it to the config file
+    pub fn set_plugin_setting(&mut self, namespace: &str, key: &str, value: serde_json::Value) {
+        self.config
+            .plugins
+            .entry(namespace.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+        self.drop_hidden_settings_state();
+        if let Err(e) = self.save_config() {
+            self.set_status_message(e);
+        }
+    }
+
+    /// Drop the settings modal, unless it is open, so it is rebuilt with the
+    /// current plugin settings when next opened
+    fn drop_hidden_settings_state(&mut self) {
+        if self
+            .settings_state
+            .as_ref()
+            .is_some_and(|state| !state.visible)
+        {
+            self.settings_state = None;
+        }
+    }
+}
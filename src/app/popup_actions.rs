@@ -2,9 +2,12 @@
 //!
 //! This module contains handlers for popup-related actions like confirmation and cancellation.
 
+use super::clipboard::{CLIPBOARD_HISTORY_PLAIN_TITLE, CLIPBOARD_HISTORY_TITLE};
+use super::system_health::SYSTEM_HEALTH_TITLE;
 use super::Editor;
 use crate::model::event::Event;
 use crate::primitives::word_navigation::find_completion_word_start;
+use crate::view::popup::PopupPosition;
 
 /// Result of handling a popup confirmation.
 pub enum PopupConfirmResult {
@@ -41,6 +44,41 @@ impl Editor {
             return PopupConfirmResult::EarlyReturn;
         }
 
+        // Run the action of a System Health popup line, if it has one
+        let system_health_action = self
+            .active_state()
+            .popups
+            .top()
+            .filter(|popup| popup.title.as_deref() == Some(SYSTEM_HEALTH_TITLE))
+            .map(|popup| popup.selected_item().and_then(|item| item.data.clone()));
+        if let Some(action) = system_health_action {
+            self.hide_popup();
+            if let Some(action) = action {
+                self.handle_system_health_action(&action);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
+        // Paste the clipboard history entry that was picked
+        let clipboard_pick = self.active_state().popups.top().and_then(|popup| {
+            let plain = match popup.title.as_deref() {
+                Some(CLIPBOARD_HISTORY_TITLE) => false,
+                Some(CLIPBOARD_HISTORY_PLAIN_TITLE) => true,
+                _ => return None,
+            };
+            let index = popup
+                .selected_item()
+                .and_then(|item| item.data.as_deref()?.parse::<usize>().ok());
+            Some((index, plain))
+        });
+        if let Some((index, plain)) = clipboard_pick {
+            self.hide_popup();
+            if let Some(index) = index {
+                self.paste_from_history(index, plain);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
         // If it's a completion popup, insert the selected item
         let completion_text = if let Some(popup) = self.active_state().popups.top() {
             if let Some(title) = &popup.title {
@@ -123,4 +161,62 @@ impl Editor {
         }
         self.hide_popup();
     }
+
+    /// Focus the hover popup, so keys scroll, copy or pin it instead of
+    /// dismissing it. Returns false if the top popup is not a hover popup.
+    pub(super) fn focus_hover_popup(&mut self) -> bool {
+        let Some(popup) = self.active_state_mut().popups.top_mut() else {
+            return false;
+        };
+        if !popup.transient || popup.title.as_deref() != Some("Hover") {
+            return false;
+        }
+        popup.transient = false;
+        self.set_status_message("Hover: ↑/↓ scroll, y copy, p pin, Esc close".to_string());
+        true
+    }
+
+    /// Handle PopupCopy action: copy the top popup's text to the clipboard
+    pub(super) fn handle_popup_copy(&mut self) {
+        let Some(text) = self.active_state().popups.top().map(|p| p.plain_text()) else {
+            return;
+        };
+        self.clipboard.copy(text);
+        self.set_status_message("Copied".to_string());
+    }
+
+    /// Handle PopupPin action: keep the top popup open where it is drawn
+    /// while the cursor moves on, until closed with Close Pinned Popups
+    pub(super) fn handle_popup_pin(&mut self) {
+        let state = self.active_state();
+        let Some(top) = state.popups.all().len().checked_sub(1) else {
+            return;
+        };
+        let position = self
+            .cached_layout
+            .popup_areas
+            .iter()
+            .find(|(popup_idx, ..)| *popup_idx == top)
+            .map(|(_, area, ..)| PopupPosition::Fixed {
+                x: area.x,
+                y: area.y,
+            })
+            .unwrap_or(PopupPosition::Centered);
+        self.active_state_mut().popups.pin_top(position);
+
+        // The hovered symbol is no longer highlighted once the cursor moves on
+        if let Some(handle) = self.hover_symbol_overlay.take() {
+            self.apply_event_to_active_buffer(&Event::RemoveOverlay { handle });
+        }
+        self.hover_symbol_range = None;
+        self.set_status_message("Popup pinned".to_string());
+    }
+
+    /// Handle ClosePinnedPopups action
+    pub(super) fn close_pinned_popups(&mut self) {
+        let closed = self.active_state_mut().popups.close_pinned();
+        if closed == 0 {
+            self.set_status_message("No pinned popups".to_string());
+        }
+    }
 }
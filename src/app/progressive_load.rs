@@ -0,0 +1,249 @@
+//! Progressive file loading.
+//!
+//! Files on slow filesystems, like network drives, can take a while to read.
+//! Files below the large file threshold are read on a background thread. If
+//! the whole file arrives within [`LOAD_WAIT`], it opens as usual. Otherwise
+//! the buffer opens with the part read so far and the rest is appended as it
+//! arrives, with a "loading…" marker after the last loaded line. Until then
+//! the buffer can be navigated but not edited or saved.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use super::Editor;
+use crate::model::buffer::TextBuffer;
+use crate::model::event::BufferId;
+use crate::state::EditorState;
+
+/// How long opening a file waits for all of it before showing what it has
+const LOAD_WAIT: Duration = Duration::from_millis(100);
+
+/// Bytes read at a time by the loader thread
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// What the loader thread sends
+enum LoadMessage {
+    Chunk(Vec<u8>),
+    Done,
+    Failed(io::Error),
+}
+
+/// A file still being read in the background
+pub(crate) struct ProgressiveLoad {
+    path: PathBuf,
+    receiver: mpsc::Receiver<LoadMessage>,
+    /// Bytes loaded so far
+    loaded: usize,
+    /// Size of the file when it was opened, 0 if unknown
+    file_size: usize,
+}
+
+impl Editor {
+    /// Create the editor state for an existing file. A file that takes
+    /// longer than [`LOAD_WAIT`] to read comes with the load to finish.
+    pub(super) fn load_file_state(
+        &self,
+        path: &Path,
+    ) -> io::Result<(EditorState, Option<ProgressiveLoad>)> {
+        let threshold = self.config.editor.large_file_threshold_bytes as usize;
+        let file_size = std::fs::metadata(path)?.len() as usize;
+        // Large files are loaded lazily already
+        if TextBuffer::is_large_file_size(file_size, threshold) {
+            let state = EditorState::from_file(
+                path,
+                self.terminal_width,
+                self.terminal_height,
+                threshold,
+                &self.grammar_registry,
+            )?;
+            return Ok((state, None));
+        }
+
+        let receiver = spawn_loader(path.to_path_buf());
+        let deadline = Instant::now() + LOAD_WAIT;
+        let mut contents = Vec::new();
+        let finished = loop {
+            let Some(wait) = deadline.checked_duration_since(Instant::now()) else {
+                break false;
+            };
+            match receiver.recv_timeout(wait) {
+                Ok(LoadMessage::Chunk(chunk)) => contents.extend_from_slice(&chunk),
+                Ok(LoadMessage::Done) => break true,
+                Ok(LoadMessage::Failed(e)) => return Err(e),
+                Err(mpsc::RecvTimeoutError::Timeout) => break false,
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::other("file loader stopped"))
+                }
+            }
+        };
+
+        let loaded = contents.len();
+        let buffer = TextBuffer::from_file_contents(path, contents);
+        let mut state = EditorState::from_file_buffer(path, buffer, &self.grammar_registry);
+        if finished {
+            return Ok((state, None));
+        }
+        tracing::info!(
+            "Loading {} in the background after {} bytes",
+            path.display(),
+            loaded
+        );
+        state.loading = true;
+        let load = ProgressiveLoad {
+            path: path.to_path_buf(),
+            receiver,
+            loaded,
+            file_size,
+        };
+        Ok((state, Some(load)))
+    }
+
+    /// Append what arrived of the files loading in the background. Returns
+    /// true if any buffer grew or finished loading, so the screen needs
+    /// redrawing.
+    pub fn poll_progressive_loads(&mut self) -> bool {
+        if self.progressive_loads.is_empty() {
+            return false;
+        }
+        // Drop the loads of buffers closed or reverted in the meantime
+        self.progressive_loads
+            .retain(|id, _| self.buffers.get(id).is_some_and(|state| state.loading));
+
+        let mut changed = false;
+        let mut finished = Vec::new();
+        for (&buffer_id, load) in self.progressive_loads.iter_mut() {
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            loop {
+                match load.receiver.try_recv() {
+                    Ok(LoadMessage::Chunk(chunk)) => {
+                        let start = state.buffer.len();
+                        load.loaded += chunk.len();
+                        state.buffer.append_loaded(chunk);
+                        state
+                            .highlighter
+                            .invalidate_range(start..state.buffer.len());
+                        changed = true;
+                    }
+                    Ok(LoadMessage::Done) => {
+                        finished.push((buffer_id, None));
+                        break;
+                    }
+                    Ok(LoadMessage::Failed(e)) => {
+                        finished.push((buffer_id, Some(e)));
+                        break;
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        finished.push((buffer_id, Some(io::Error::other("file loader stopped"))));
+                        break;
+                    }
+                }
+            }
+        }
+
+        if changed && finished.is_empty() {
+            if let Some(load) = self.progressive_loads.get(&self.active_buffer()) {
+                self.status_message = Some(loading_message(load));
+            }
+        }
+        for (buffer_id, error) in finished {
+            self.finish_progressive_load(buffer_id, error);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Whether any file is still loading in the background
+    pub fn is_loading_files(&self) -> bool {
+        !self.progressive_loads.is_empty()
+    }
+
+    /// Status message for a buffer opened while its file is still loading
+    pub(super) fn progressive_load_message(&self, buffer_id: BufferId) -> Option<String> {
+        self.progressive_loads.get(&buffer_id).map(loading_message)
+    }
+
+    /// Make a buffer whose file finished loading editable, and do what
+    /// opening the file left for then. A buffer whose file failed to load
+    /// part way stays read-only, so saving it cannot cut the file short.
+    fn finish_progressive_load(&mut self, buffer_id: BufferId, error: Option<io::Error>) {
+        let Some(load) = self.progressive_loads.remove(&buffer_id) else {
+            return;
+        };
+        let name = self.get_buffer_display_name(buffer_id);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state.loading = false;
+        if let Some(e) = error {
+            tracing::warn!("Failed to load {}: {}", load.path.display(), e);
+            state.editing_disabled = true;
+            if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                metadata.read_only = true;
+            }
+            self.status_message = Some(format!(
+                "Failed to load {} after {} bytes: {} [read-only]",
+                name, load.loaded, e
+            ));
+            return;
+        }
+
+        tracing::info!("Loaded {} ({} bytes)", load.path.display(), load.loaded);
+        self.restore_undo_history(buffer_id, &load.path);
+        if let Some(mut metadata) = self.buffer_metadata.remove(&buffer_id) {
+            self.notify_lsp_file_opened(&load.path, buffer_id, &mut metadata);
+            self.buffer_metadata.insert(buffer_id, metadata);
+        }
+        self.status_message = Some(format!("Loaded {}", name));
+    }
+}
+
+/// Read the file at `path` on a new thread, sending it in chunks. The thread
+/// stops early once the receiver is gone.
+fn spawn_loader(path: PathBuf) -> mpsc::Receiver<LoadMessage> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                let _ = sender.send(LoadMessage::Failed(e));
+                return;
+            }
+        };
+        let mut chunk = vec![0; CHUNK_SIZE];
+        loop {
+            let message = match file.read(&mut chunk) {
+                Ok(0) => LoadMessage::Done,
+                Ok(n) => LoadMessage::Chunk(chunk[..n].to_vec()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => LoadMessage::Failed(e),
+            };
+            let last = !matches!(message, LoadMessage::Chunk(_));
+            if sender.send(message).is_err() || last {
+                return;
+            }
+        }
+    });
+    receiver
+}
+
+fn loading_message(load: &ProgressiveLoad) -> String {
+    let name = load
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| load.path.display().to_string());
+    if load.file_size > 0 {
+        format!(
+            "Loading {}... {}%",
+            name,
+            (load.loaded * 100 / load.file_size).min(100)
+        )
+    } else {
+        format!("Loading {}... {} bytes", name, load.loaded)
+    }
+}
@@ -6,11 +6,15 @@ use super::normalize_path;
 use super::BufferId;
 use super::BufferMetadata;
 use super::Editor;
+use crate::input::command_palette::{self, PaletteMode};
 use crate::input::keybindings::Action;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
 use std::path::Path;
 
+/// Choice of the indentation prompt that guesses it from the buffer
+pub(super) const DETECT_INDENTATION: &str = "Detect from file";
+
 /// Result of handling a prompt confirmation.
 pub enum PromptResult {
     /// Prompt handled, continue normally
@@ -63,6 +67,18 @@ impl Editor {
                     ));
                 }
             }
+            PromptType::AddWorkspaceFolder => {
+                let input_path = Path::new(&input);
+                let resolved_path = if input_path.is_absolute() {
+                    normalize_path(input_path)
+                } else {
+                    normalize_path(&self.working_dir.join(input_path))
+                };
+                self.add_workspace_folder(resolved_path);
+            }
+            PromptType::RemoveWorkspaceFolder => {
+                self.remove_workspace_folder(input.trim());
+            }
             PromptType::SaveFileAs => {
                 self.handle_save_file_as(&input);
             }
@@ -101,21 +117,26 @@ impl Editor {
                     self.perform_replace(&search, &input);
                 }
             }
-            PromptType::Command => {
-                let commands = self.command_registry.read().unwrap().get_all();
-                if let Some(cmd) = commands.iter().find(|c| c.name == input) {
-                    let action = cmd.action.clone();
-                    let cmd_name = cmd.name.clone();
-                    self.set_status_message(format!("Executing: {}", cmd_name));
-                    self.command_registry
-                        .write()
-                        .unwrap()
-                        .record_usage(&cmd_name);
-                    return PromptResult::ExecuteAction(action);
-                } else {
-                    self.set_status_message(format!("Unknown command: {input}"));
+            PromptType::Command => match command_palette::parse_query(&input) {
+                (PaletteMode::Files, path) => self.open_palette_file(path),
+                (PaletteMode::Symbols, line) => self.goto_palette_symbol(line),
+                (PaletteMode::WorkspaceSymbols, symbol) => self.goto_workspace_symbol(symbol),
+                (PaletteMode::Commands, name) => {
+                    let commands = self.command_registry.read().unwrap().get_all();
+                    if let Some(cmd) = commands.iter().find(|c| c.name == name) {
+                        let action = cmd.action.clone();
+                        let cmd_name = cmd.name.clone();
+                        self.set_status_message(format!("Executing: {}", cmd_name));
+                        self.command_registry
+                            .write()
+                            .unwrap()
+                            .record_usage(&cmd_name);
+                        return PromptResult::ExecuteAction(action);
+                    } else {
+                        self.set_status_message(format!("Unknown command: {name}"));
+                    }
                 }
-            }
+            },
             PromptType::GotoLine => match input.trim().parse::<usize>() {
                 Ok(line_num) if line_num > 0 => {
                     self.goto_line_col(line_num, None);
@@ -189,13 +210,31 @@ impl Editor {
             PromptType::ConfirmSaveConflict => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "o" || input_lower == "overwrite" {
-                    if let Err(e) = self.save() {
+                    if let Err(e) = self.save_or_offer_root() {
                         self.set_status_message(format!("Failed to save: {}", e));
                     }
                 } else {
                     self.set_status_message("Save cancelled".to_string());
                 }
             }
+            PromptType::RenameSymbolText { original } => {
+                self.preview_textual_rename(original, input);
+            }
+            PromptType::RenameFile => {
+                self.handle_rename_file(&input);
+            }
+            PromptType::DuplicateFile => {
+                self.handle_duplicate_file(&input);
+            }
+            PromptType::ConfirmTrashFile => {
+                self.handle_confirm_trash_file(&input);
+            }
+            PromptType::ConfirmSaveAsRoot => {
+                self.handle_confirm_save_as_root(&input);
+            }
+            PromptType::SudoPassword => {
+                self.handle_sudo_password(input);
+            }
             PromptType::ConfirmOverwriteFile { path } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "o" || input_lower == "overwrite" {
@@ -209,13 +248,8 @@ impl Editor {
                     return PromptResult::EarlyReturn;
                 }
             }
-            PromptType::ConfirmQuitWithModified => {
-                let input_lower = input.trim().to_lowercase();
-                if input_lower == "d" || input_lower == "discard" {
-                    self.should_quit = true;
-                } else {
-                    self.set_status_message("Quit cancelled".to_string());
-                }
+            PromptType::ConfirmSwitchProject { path } => {
+                self.handle_confirm_switch_project(&input, path);
             }
             PromptType::LspRename {
                 original_text,
@@ -245,6 +279,36 @@ impl Editor {
             PromptType::SelectTheme => {
                 self.apply_theme(input.trim());
             }
+            PromptType::RunTask => {
+                self.run_task(input.trim());
+            }
+            PromptType::GitCheckoutBranch => {
+                self.git_checkout_branch(input.trim());
+            }
+            PromptType::FileRevision { path, diff } => {
+                self.open_file_revision(&path, input.trim(), diff);
+            }
+            PromptType::NewScratchBuffer => {
+                self.new_scratch_buffer(&input);
+            }
+            PromptType::OpenScratchBuffer => {
+                self.open_scratch_buffer(&input);
+            }
+            PromptType::ExportBuffer { format } => {
+                self.export_buffer(&input, format);
+            }
+            PromptType::StartRepl => {
+                self.start_repl(&input);
+            }
+            PromptType::AlignOn => {
+                self.align_selection(&input);
+            }
+            PromptType::CollabShare => {
+                self.collab_share(&input);
+            }
+            PromptType::CollabJoin => {
+                self.collab_join(&input);
+            }
             PromptType::SelectKeybindingMap => {
                 self.apply_keybinding_map(input.trim());
             }
@@ -269,9 +333,42 @@ impl Editor {
             PromptType::SetLineEnding => {
                 self.handle_set_line_ending(&input);
             }
+            PromptType::SetIndentation => {
+                self.handle_set_indentation(&input);
+            }
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
+            PromptType::RunShellCommand => {
+                self.handle_run_shell_command(&input);
+            }
+            PromptType::ConfirmInsertCommandOutput { output } => {
+                self.handle_confirm_insert_command_output(&input, output);
+            }
+            PromptType::ConfirmHexView { path } => {
+                self.handle_confirm_hex_view(&input, path);
+            }
+            PromptType::ConfirmOpenDroppedFiles { paths, text } => {
+                self.handle_confirm_open_dropped_files(&input, paths, text);
+            }
+            PromptType::ConfirmPluginNetworkAccess { plugin } => {
+                self.handle_confirm_plugin_network_access(&input, plugin);
+            }
+            PromptType::ConfirmCrashReport {
+                report,
+                restore_session,
+            } => {
+                self.handle_confirm_crash_report(&input, report, restore_session);
+            }
+            PromptType::LimitFileFeatures { buffer_id } => {
+                self.handle_limit_file_features(&input, buffer_id);
+            }
+            PromptType::InstallGrammar => {
+                self.install_grammar(&input);
+            }
+            PromptType::SelectLanguage => {
+                self.apply_language_override(&input);
+            }
         }
         PromptResult::Done
     }
@@ -346,7 +443,7 @@ impl Editor {
                     }
                 }
 
-                self.notify_lsp_save();
+                self.notify_lsp_save(self.active_buffer());
 
                 self.emit_event(
                     crate::model::control_event::events::FILE_SAVED.name,
@@ -457,6 +554,50 @@ impl Editor {
         }
     }
 
+    /// Handle SetIndentation prompt confirmation.
+    fn handle_set_indentation(&mut self, input: &str) {
+        use crate::primitives::indent_detection::{detect_buffer_indentation, IndentStyle};
+
+        let trimmed = input.trim();
+        // Whether to use tabs, and the indent width
+        let indentation = if trimmed.eq_ignore_ascii_case(DETECT_INDENTATION) {
+            let state = self.active_state_mut();
+            match detect_buffer_indentation(&mut state.buffer) {
+                Some(IndentStyle::Tabs) => Some((true, state.tab_size)),
+                Some(IndentStyle::Spaces(width)) => Some((false, width)),
+                None => {
+                    self.set_status_message("Not enough indentation to tell".to_string());
+                    return;
+                }
+            }
+        } else {
+            // "Spaces: 4", "tabs 8", ...
+            let mut words = trimmed
+                .split(|c: char| c == ':' || c.is_whitespace())
+                .filter(|word| !word.is_empty());
+            let style = words.next().unwrap_or_default().to_lowercase();
+            let width = words.next().and_then(|width| width.parse::<usize>().ok());
+            match (style.as_str(), width) {
+                ("tabs", Some(width)) if width > 0 => Some((true, width)),
+                ("spaces", Some(width)) if width > 0 => Some((false, width)),
+                _ => None,
+            }
+        };
+
+        match indentation {
+            Some((use_tabs, width)) => {
+                let state = self.active_state_mut();
+                state.use_tabs = use_tabs;
+                state.tab_size = width;
+                let style = if use_tabs { "Tabs" } else { "Spaces" };
+                self.set_status_message(format!("Indentation: {}: {}", style, width));
+            }
+            None => {
+                self.set_status_message(format!("Unknown indentation: {}", input));
+            }
+        }
+    }
+
     /// Handle register-based input (macros, bookmarks).
     fn handle_register_input<F>(&mut self, input: &str, action: F, register_type: &str)
     where
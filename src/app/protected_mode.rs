@@ -0,0 +1,248 @@
+//! Safeguards for binary, minified and very large files.
+//!
+//! This module provides functionality to:
+//! - Detect minified files, whose very long lines would make highlighting and
+//!   word scans freeze the editor
+//! - Open binary and minified files in protected mode, without syntax
+//!   highlighting or highlighting of the word under the cursor
+//! - Open files over the size limits (`editor.file_size_warning_bytes` and
+//!   `editor.line_length_warning_bytes`) in protected mode without auto-revert,
+//!   and ask which features to keep
+//! - Show the bytes of a file as a hex dump in a read-only buffer, offered
+//!   when a binary file is opened if `editor.binary_hex_view_prompt` is on
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::primitives::highlight_engine::HighlightEngine;
+use crate::state::EditorState;
+use crate::view::prompt::{Prompt, PromptType};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Bytes read from the start of a file when looking for minified lines
+const MINIFIED_SAMPLE_BYTES: u64 = 1024 * 1024;
+
+/// Multiple of `editor.line_length_warning_bytes` read from the start of a
+/// file when looking for lines over the limit
+const LINE_WARNING_SAMPLE_FACTOR: u64 = 4;
+
+/// Bytes of a file shown in the hex view
+const HEX_VIEW_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Bytes per line of the hex view
+const HEX_BYTES_PER_LINE: usize = 16;
+
+/// Whether `bytes` contain a line longer than `max_len` bytes
+pub fn has_long_line(bytes: &[u8], max_len: usize) -> bool {
+    bytes
+        .split(|&b| b == b'\n')
+        .any(|line| line.len() > max_len)
+}
+
+/// Whether the file at `path` looks minified: a line in its first megabyte
+/// is longer than `max_len` bytes. A `max_len` of 0 turns detection off.
+pub fn is_minified(path: &Path, max_len: usize) -> bool {
+    max_len > 0 && sample_has_long_line(path, MINIFIED_SAMPLE_BYTES, max_len)
+}
+
+/// Why the file at `path` is over the configured size limits, if it is
+pub fn over_size_limits(path: &Path, max_size: u64, max_line_len: usize) -> Option<String> {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if max_size > 0 && size > max_size {
+        return Some(format!("file of {}", format_size(size)));
+    }
+    let sample_bytes = (max_line_len as u64).saturating_mul(LINE_WARNING_SAMPLE_FACTOR);
+    if max_line_len > 0 && sample_has_long_line(path, sample_bytes, max_line_len) {
+        return Some(format!("line over {}", format_size(max_line_len as u64)));
+    }
+    None
+}
+
+/// Whether the first `sample_bytes` of the file at `path` contain a line
+/// longer than `max_len` bytes
+fn sample_has_long_line(path: &Path, sample_bytes: u64, max_len: usize) -> bool {
+    let mut sample = Vec::new();
+    match std::fs::File::open(path) {
+        Ok(file) => {
+            if file.take(sample_bytes).read_to_end(&mut sample).is_err() {
+                return false;
+            }
+        }
+        Err(_) => return false,
+    }
+    has_long_line(&sample, max_len)
+}
+
+/// Hex dump of `bytes`: offset, 16 bytes in hex, and the printable ASCII
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() / HEX_BYTES_PER_LINE * 80 + 80);
+    for (i, chunk) in bytes.chunks(HEX_BYTES_PER_LINE).enumerate() {
+        out.push_str(&format!("{:08x} ", i * HEX_BYTES_PER_LINE));
+        for j in 0..HEX_BYTES_PER_LINE {
+            if j % 8 == 0 {
+                out.push(' ');
+            }
+            match chunk.get(j) {
+                Some(byte) => out.push_str(&format!("{:02x} ", byte)),
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str(" |");
+        out.extend(chunk.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+    }
+    out
+}
+
+/// Turn off the features of a buffer that scan its text for display
+pub(super) fn protect(state: &mut EditorState) {
+    state.highlighter = HighlightEngine::None;
+    state.semantic_highlighter.enabled = false;
+    state.protected = true;
+}
+
+impl Editor {
+    /// Ask which features to keep for a buffer whose file is over the size
+    /// limits. It has already been put in protected mode without auto-revert.
+    pub(super) fn start_limit_file_features_prompt(&mut self, buffer_id: BufferId, reason: &str) {
+        let choices = [
+            ("limit", "Limit features", "No highlighting or auto-revert"),
+            (
+                "read-only",
+                "Limit features, read-only",
+                "No highlighting, auto-revert or editing",
+            ),
+            ("full", "Use all features", "May be slow"),
+        ];
+        let suggestions: Vec<Suggestion> = choices
+            .iter()
+            .map(|(value, text, description)| Suggestion {
+                text: text.to_string(),
+                description: Some(description.to_string()),
+                value: Some(value.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            format!("Large file ({}): ", reason),
+            PromptType::LimitFileFeatures { buffer_id },
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Handle the LimitFileFeatures prompt
+    pub(super) fn handle_limit_file_features(&mut self, input: &str, buffer_id: BufferId) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        match input {
+            "read-only" => {
+                state.editing_disabled = true;
+                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                    metadata.read_only = true;
+                }
+                self.set_status_message(
+                    "Read-only, with highlighting and auto-revert off".to_string(),
+                );
+            }
+            "full" => {
+                state.set_syntax_override(None, &self.grammar_registry);
+                state.semantic_highlighter.enabled = true;
+                state.protected = false;
+                state.auto_revert = true;
+                self.set_status_message("All features turned back on".to_string());
+            }
+            _ => {
+                self.set_status_message("Highlighting and auto-revert are off".to_string());
+            }
+        }
+    }
+
+    /// Handle the ConfirmHexView prompt
+    pub(super) fn handle_confirm_hex_view(&mut self, input: &str, path: PathBuf) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower != "y" && input_lower != "yes" {
+            return;
+        }
+        if let Err(e) = self.open_hex_view(&path) {
+            self.set_status_message(format!("Failed to read {}: {}", path.display(), e));
+        }
+    }
+
+    /// Show a hex view of the current buffer's file
+    pub fn show_hex_view(&mut self) {
+        let path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let Some(path) = path else {
+            self.set_status_message("Buffer has no file to show in hex".to_string());
+            return;
+        };
+        if let Err(e) = self.open_hex_view(&path) {
+            self.set_status_message(format!("Failed to read {}: {}", path.display(), e));
+        }
+    }
+
+    /// Open a read-only buffer with a hex dump of the file at `path`
+    pub fn open_hex_view(&mut self, path: &Path) -> io::Result<BufferId> {
+        let file = std::fs::File::open(path)?;
+        let size = file.metadata()?.len();
+        let mut bytes = Vec::new();
+        file.take(HEX_VIEW_MAX_BYTES).read_to_end(&mut bytes)?;
+
+        let mut content = hex_dump(&bytes);
+        if size > HEX_VIEW_MAX_BYTES {
+            content.push_str(&format!(
+                "... {} more bytes not shown\n",
+                size - HEX_VIEW_MAX_BYTES
+            ));
+        }
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let buffer_id = self.open_read_only_buffer(format!("*Hex: {}*", name), "hex", &content);
+        protect(self.active_state_mut());
+        self.set_status_message(format!("Hex view of {} ({} bytes)", name, size));
+        Ok(buffer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_long_line() {
+        assert!(!has_long_line(b"short\nlines\n", 10));
+        assert!(has_long_line(b"short\nthis one is long\n", 10));
+        assert!(has_long_line(b"no newline at all", 10));
+        assert!(!has_long_line(b"", 10));
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let dump = hex_dump(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR!");
+        assert_eq!(
+            dump,
+            "00000000  89 50 4e 47 0d 0a 1a 0a  00 00 00 0d 49 48 44 52  |.PNG........IHDR|\n\
+             00000010  21                                                |!|\n"
+        );
+    }
+}
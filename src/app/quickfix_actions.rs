@@ -0,0 +1,334 @@
+//! Quickfix list.
+//!
+//! This module provides functionality to:
+//! - Collect file locations from task output, or from text in a buffer such
+//!   as a pasted build log
+//! - Step through them with "Quickfix: Next Entry" / "Quickfix: Previous Entry"
+//! - List them in a split below the current one and jump from there
+//!
+//! The list is saved with the session (see `app/session.rs`).
+
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::{BufferId, SplitDirection, SplitId};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::tasks::{self, CompilerMessage, MessageSeverity};
+use crate::view::split::SplitViewState;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::{Path, PathBuf};
+
+/// Mode of the quickfix list buffer
+const LIST_MODE: &str = "quickfix-list";
+
+/// Share of the height kept by the split the list is opened from
+const LIST_SPLIT_RATIO: f32 = 0.7;
+
+/// One location in the quickfix list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QuickfixEntry {
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, if known
+    pub column: Option<usize>,
+    pub severity: MessageSeverity,
+    pub message: String,
+}
+
+impl QuickfixEntry {
+    /// Entry for a message whose relative path starts at `base_dir`
+    pub fn from_message(message: CompilerMessage, base_dir: &Path) -> Self {
+        Self {
+            path: base_dir.join(&message.path),
+            line: message.line,
+            column: message.column,
+            severity: message.severity,
+            message: message.message,
+        }
+    }
+}
+
+/// Locations to step through, and the one last jumped to
+pub(crate) struct QuickfixList {
+    /// Where the entries came from, e.g. "Task: build"
+    pub title: String,
+    pub entries: Vec<QuickfixEntry>,
+    pub current: Option<usize>,
+    /// Task run whose output the entries come from
+    pub task_id: Option<u64>,
+}
+
+/// The list buffer and the split files are opened in from it
+pub(crate) struct QuickfixPanel {
+    buffer_id: BufferId,
+    source_split: SplitId,
+}
+
+impl Editor {
+    /// Replace the quickfix list, updating the list split if it is shown
+    pub(super) fn set_quickfix_list(&mut self, list: QuickfixList) {
+        self.quickfix = Some(list);
+        self.refresh_quickfix_panel();
+    }
+
+    /// Add an entry found in the output of task `task_id`, if the list
+    /// belongs to that task
+    pub(super) fn push_quickfix_entry(&mut self, task_id: u64, entry: QuickfixEntry) {
+        let Some(list) = self
+            .quickfix
+            .as_mut()
+            .filter(|list| list.task_id == Some(task_id))
+        else {
+            return;
+        };
+        list.entries.push(entry);
+        self.refresh_quickfix_panel();
+    }
+
+    /// Build the quickfix list from the selection, or the whole buffer when
+    /// nothing is selected
+    pub fn quickfix_from_buffer(&mut self) {
+        let buffer_id = self.active_buffer();
+        let name = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|meta| meta.display_name.clone())
+            .unwrap_or_default();
+        let text = {
+            let state = self.active_state_mut();
+            match state.cursors.primary().selection_range() {
+                Some(range) => state.get_text_range(range.start, range.end),
+                None => state.buffer.to_string().unwrap_or_default(),
+            }
+        };
+
+        let entries: Vec<QuickfixEntry> = tasks::parse_locations(&text)
+            .into_iter()
+            .map(|message| QuickfixEntry::from_message(message, &self.working_dir))
+            .collect();
+        if entries.is_empty() {
+            self.set_status_message("No file locations found".to_string());
+            return;
+        }
+        self.set_status_message(format!(
+            "Quickfix: {} location(s) from {}",
+            entries.len(),
+            name
+        ));
+        self.set_quickfix_list(QuickfixList {
+            title: name,
+            entries,
+            current: None,
+            task_id: None,
+        });
+    }
+
+    /// Jump to the next (or previous) quickfix entry, wrapping around
+    pub fn cycle_quickfix(&mut self, forward: bool) {
+        let Some(list) = self
+            .quickfix
+            .as_ref()
+            .filter(|list| !list.entries.is_empty())
+        else {
+            self.set_status_message("Quickfix list is empty".to_string());
+            return;
+        };
+        let count = list.entries.len();
+        let index = match (list.current, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        self.goto_quickfix_entry(index);
+    }
+
+    /// Make entry `index` current and open its location
+    pub(super) fn goto_quickfix_entry(&mut self, index: usize) {
+        let Some(list) = self.quickfix.as_mut() else {
+            return;
+        };
+        let Some(entry) = list.entries.get(index).cloned() else {
+            return;
+        };
+        list.current = Some(index);
+        let count = list.entries.len();
+        self.refresh_quickfix_panel();
+
+        if self.open_quickfix_location(&entry) {
+            self.set_status_message(format!(
+                "{}/{} {}: {}",
+                index + 1,
+                count,
+                entry.severity.label(),
+                entry.message
+            ));
+        }
+    }
+
+    /// Open the file of `entry` at its line and column
+    ///
+    /// From the list split, the file opens in the split the list was opened
+    /// from. Returns false (with a status message) if the file can't be opened.
+    pub(super) fn open_quickfix_location(&mut self, entry: &QuickfixEntry) -> bool {
+        if let Some(panel) = self
+            .quickfix_panel
+            .as_ref()
+            .filter(|panel| panel.buffer_id == self.active_buffer())
+        {
+            let source_split = panel.source_split;
+            if self.split_manager.get_buffer_id(source_split).is_some() {
+                self.save_current_split_view_state();
+                self.split_manager.set_active_split(source_split);
+                self.restore_current_split_view_state();
+            }
+        }
+
+        if let Err(e) = self.open_file(&entry.path) {
+            let display = self.quickfix_display_path(&entry.path);
+            self.set_status_message(format!("Cannot open {}: {}", display, e));
+            return false;
+        }
+        self.goto_line_col(entry.line, entry.column);
+        true
+    }
+
+    /// Show the quickfix list in a split below the current one
+    pub fn show_quickfix_list(&mut self) {
+        if self.quickfix.is_none() {
+            self.set_status_message("Quickfix list is empty".to_string());
+            return;
+        }
+
+        // Focus the list if it is already shown
+        if let Some(panel) = self.quickfix_panel.as_ref() {
+            let buffer_id = panel.buffer_id;
+            if let Some(&split_id) = self.split_manager.splits_for_buffer(buffer_id).first() {
+                self.refresh_quickfix_panel();
+                self.save_current_split_view_state();
+                self.split_manager.set_active_split(split_id);
+                self.restore_current_split_view_state();
+                return;
+            }
+            if self.buffers.contains_key(&buffer_id) {
+                let _ = self.force_close_buffer(buffer_id);
+            }
+            self.quickfix_panel = None;
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(LIST_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "quickfix_jump"),
+        );
+        let buffer_id =
+            self.create_virtual_buffer("*Quickfix*".to_string(), LIST_MODE.to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        let source_split = self.split_manager.active_split();
+        self.quickfix_panel = Some(QuickfixPanel {
+            buffer_id,
+            source_split,
+        });
+        self.refresh_quickfix_panel();
+
+        self.save_current_split_view_state();
+        match self.split_manager.split_active(
+            SplitDirection::Horizontal,
+            buffer_id,
+            LIST_SPLIT_RATIO,
+        ) {
+            Ok(split_id) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    buffer_id,
+                );
+                view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                self.split_view_states.insert(split_id, view_state);
+                self.split_manager.set_active_split(split_id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to create quickfix split: {}", e);
+                self.set_active_buffer(buffer_id);
+            }
+        }
+    }
+
+    /// Jump to the entry on the cursor line of the list buffer
+    pub fn quickfix_jump(&mut self) {
+        let index = {
+            let state = self.active_state();
+            state
+                .text_properties
+                .get_at(state.cursors.primary().position)
+                .iter()
+                .find_map(|property| property.get_as::<usize>("index"))
+        };
+        match index {
+            Some(index) => self.goto_quickfix_entry(index),
+            None => self.set_status_message("Move the cursor to a location line".to_string()),
+        }
+    }
+
+    /// Rewrite the list buffer from the current list, keeping the cursor on
+    /// the current entry
+    fn refresh_quickfix_panel(&mut self) {
+        let (Some(panel), Some(list)) = (self.quickfix_panel.as_ref(), self.quickfix.as_ref())
+        else {
+            return;
+        };
+        let buffer_id = panel.buffer_id;
+
+        let mut entries = vec![TextPropertyEntry::text(format!(
+            "Quickfix: {} ({} location(s))\nEnter: jump to location   q: close\n",
+            list.title,
+            list.entries.len()
+        ))];
+        let mut current_offset = None;
+        let mut offset = entries[0].text.len();
+        for (index, entry) in list.entries.iter().enumerate() {
+            let is_current = list.current == Some(index);
+            if is_current {
+                current_offset = Some(offset);
+            }
+            let location = match entry.column {
+                Some(column) => format!("{}:{}", entry.line, column),
+                None => entry.line.to_string(),
+            };
+            let text = format!(
+                "{} {}:{}: {}: {}\n",
+                if is_current { ">" } else { " " },
+                self.quickfix_display_path(&entry.path),
+                location,
+                entry.severity.label(),
+                entry.message
+            );
+            offset += text.len();
+            entries.push(
+                TextPropertyEntry::text(text).with_property("index", serde_json::json!(index)),
+            );
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to update quickfix list: {}", e);
+            return;
+        }
+        if let Some(position) = current_offset {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.cursors.primary_mut().position = position;
+            }
+        }
+    }
+
+    /// `path` relative to the working directory when it is inside it
+    fn quickfix_display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.working_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
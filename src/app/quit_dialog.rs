@@ -0,0 +1,187 @@
+//! Confirm-on-quit dialog.
+//!
+//! Quitting with unsaved changes opens a modal listing each modified buffer.
+//! This module provides functionality to:
+//! - Save or discard each buffer on its own row, or all of them at once
+//! - Quit once every listed buffer is dealt with, or cancel the quit
+//! - Route keys and mouse events to the dialog while it is open
+//!
+//! The dialog itself is the generic [`ChoiceDialogState`] control.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::view::controls::{
+    render_choice_dialog, ChoiceDialogColors, ChoiceDialogEvent, ChoiceDialogLayout,
+    ChoiceDialogState,
+};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::Frame;
+
+/// Choices offered on each buffer's row
+const SAVE: usize = 0;
+const DISCARD: usize = 1;
+
+/// Buttons of the dialog
+const SAVE_ALL: usize = 0;
+const DISCARD_ALL: usize = 1;
+const CANCEL: usize = 2;
+
+/// Open confirm-on-quit dialog
+pub(crate) struct QuitDialog {
+    /// Buffers in the order of the dialog's rows
+    buffers: Vec<BufferId>,
+    state: ChoiceDialogState,
+    /// Dialog as last rendered, for hit testing
+    layout: ChoiceDialogLayout,
+}
+
+impl Editor {
+    /// Open the dialog listing `buffers`, the modified buffers
+    pub(super) fn open_quit_dialog(&mut self, mut buffers: Vec<BufferId>) {
+        buffers.sort_by_key(|id| id.0);
+        let rows = buffers
+            .iter()
+            .map(|&id| self.get_buffer_display_name(id))
+            .collect();
+        let state = ChoiceDialogState::new(
+            "Unsaved Changes",
+            unsaved_message(buffers.len()),
+            rows,
+            vec!["Save".to_string(), "Discard".to_string()],
+            vec![
+                "Save All".to_string(),
+                "Discard All".to_string(),
+                "Cancel".to_string(),
+            ],
+        );
+        self.context_menu = None;
+        self.tab_overflow_menu = None;
+        self.quit_dialog = Some(QuitDialog {
+            buffers,
+            state,
+            layout: ChoiceDialogLayout::default(),
+        });
+    }
+
+    /// Route a key press to the open quit dialog. Returns true if the dialog
+    /// was open, in which case it consumes the key.
+    pub(super) fn handle_quit_dialog_key(&mut self, key: KeyEvent) -> bool {
+        let Some(dialog) = self.quit_dialog.as_mut() else {
+            return false;
+        };
+        if let Some(event) = dialog.state.handle_key(key) {
+            self.run_quit_dialog_event(event);
+        }
+        true
+    }
+
+    /// Route a mouse event to the open quit dialog. Returns true if the
+    /// dialog was open, in which case it consumes the event.
+    pub(super) fn handle_quit_dialog_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some(dialog) = self.quit_dialog.as_mut() else {
+            return false;
+        };
+        if let Some(event) = dialog.state.handle_mouse(event, &dialog.layout) {
+            self.run_quit_dialog_event(event);
+        }
+        true
+    }
+
+    fn run_quit_dialog_event(&mut self, event: ChoiceDialogEvent) {
+        match event {
+            ChoiceDialogEvent::Chosen { row, choice } => {
+                let Some(&buffer_id) = self.quit_dialog.as_ref().and_then(|d| d.buffers.get(row))
+                else {
+                    return;
+                };
+                match choice {
+                    SAVE => self.save_from_quit_dialog(&[buffer_id]),
+                    DISCARD => self.remove_quit_dialog_rows(&[buffer_id]),
+                    _ => {}
+                }
+            }
+            ChoiceDialogEvent::Button(SAVE_ALL) => {
+                let buffers = self
+                    .quit_dialog
+                    .as_ref()
+                    .map(|d| d.buffers.clone())
+                    .unwrap_or_default();
+                self.save_from_quit_dialog(&buffers);
+            }
+            ChoiceDialogEvent::Button(DISCARD_ALL) => {
+                self.quit_dialog = None;
+                self.should_quit = true;
+            }
+            ChoiceDialogEvent::Button(CANCEL) | ChoiceDialogEvent::Cancelled => {
+                self.quit_dialog = None;
+                self.set_status_message("Quit cancelled".to_string());
+            }
+            ChoiceDialogEvent::Button(_) | ChoiceDialogEvent::FocusChanged(_) => {}
+        }
+    }
+
+    /// Save `buffers`, dropping the rows of those saved. The rows of buffers
+    /// that could not be saved stay, and the dialog says why.
+    fn save_from_quit_dialog(&mut self, buffers: &[BufferId]) {
+        let mut saved = Vec::new();
+        let mut problems = Vec::new();
+        for &buffer_id in buffers {
+            let name = self.get_buffer_display_name(buffer_id);
+            match self.save_buffer_by_id(buffer_id) {
+                Ok(()) => saved.push(buffer_id),
+                Err(None) => problems.push(format!("{} has no file or changed on disk", name)),
+                Err(Some(e)) => problems.push(format!("{} failed to save: {}", name, e)),
+            }
+        }
+        self.remove_quit_dialog_rows(&saved);
+        if problems.is_empty() {
+            return;
+        }
+        if let Some(dialog) = self.quit_dialog.as_mut() {
+            dialog.state.message = problems.join("; ");
+        }
+    }
+
+    /// Drop the rows of `buffers`, and quit once no rows are left
+    fn remove_quit_dialog_rows(&mut self, buffers: &[BufferId]) {
+        let Some(dialog) = self.quit_dialog.as_mut() else {
+            return;
+        };
+        for buffer_id in buffers {
+            if let Some(row) = dialog.buffers.iter().position(|id| id == buffer_id) {
+                dialog.buffers.remove(row);
+                dialog.state.remove_row(row);
+            }
+        }
+        if dialog.buffers.is_empty() {
+            self.quit_dialog = None;
+            self.should_quit = true;
+        } else {
+            dialog.state.message = unsaved_message(dialog.buffers.len());
+        }
+    }
+
+    /// Render the open quit dialog above the editor and notifications
+    pub(super) fn render_quit_dialog(&mut self, frame: &mut Frame) {
+        let Some(dialog) = self.quit_dialog.as_mut() else {
+            return;
+        };
+        dialog.layout = render_choice_dialog(
+            frame,
+            frame.area(),
+            &dialog.state,
+            &ChoiceDialogColors::from_theme(&self.theme),
+        );
+    }
+}
+
+fn unsaved_message(count: usize) -> String {
+    if count == 1 {
+        "1 buffer has unsaved changes. Save before quitting?".to_string()
+    } else {
+        format!(
+            "{} buffers have unsaved changes. Save before quitting?",
+            count
+        )
+    }
+}
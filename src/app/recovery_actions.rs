@@ -264,6 +264,7 @@ impl Editor {
                 // Clear recovery_pending flag after successful save
                 state.buffer.set_recovery_pending(false);
                 saved_count += 1;
+                self.record_autosave_snapshot(buffer_id);
             }
         }
 
@@ -0,0 +1,223 @@
+//! Find References results.
+//!
+//! This module provides functionality to:
+//! - Find whole-word occurrences of the symbol at the cursor across the
+//!   project when no language server can answer the request
+//! - Hand references to plugins through the `lsp_references` hook, or list
+//!   them grouped by file in a results buffer when no plugin displays them
+//! - Jump from a result line to its location
+
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::BufferId;
+use crate::services::plugins::hooks::{HookArgs, LspLocation};
+use crate::services::project_rename;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Mode of the references results buffer
+const RESULTS_MODE: &str = "references-results";
+
+/// Hook fired with the references so plugins can display them
+const REFERENCES_HOOK: &str = "lsp_references";
+
+/// One reference to a symbol
+pub(crate) struct Reference {
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column
+    pub column: usize,
+    /// The line containing the reference, loaded when the results are shown
+    pub line_text: Option<String>,
+}
+
+/// The references results buffer and the location shown on each line
+pub(crate) struct ReferencesPanel {
+    buffer_id: BufferId,
+    line_references: Vec<Option<Reference>>,
+}
+
+impl Editor {
+    /// Find whole-word occurrences of `symbol` in the project and show them
+    pub(super) fn find_references_textually(&mut self, symbol: String) {
+        if symbol.is_empty() {
+            self.set_status_message("No symbol at cursor".to_string());
+            return;
+        }
+
+        let open_buffers: HashMap<PathBuf, String> = self
+            .buffers
+            .values()
+            .filter_map(|state| {
+                let path = state.buffer.file_path()?.to_path_buf();
+                Some((path, state.buffer.to_string()?))
+            })
+            .collect();
+        let ignore = self
+            .ignore_service
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        let references: Vec<Reference> =
+            project_rename::scan_project(&self.working_dir, &symbol, &open_buffers, &ignore)
+                .into_iter()
+                .map(|occurrence| Reference {
+                    path: occurrence.path,
+                    line: occurrence.line,
+                    column: occurrence.column,
+                    line_text: Some(occurrence.line_text),
+                })
+                .collect();
+        drop(ignore);
+        if references.is_empty() {
+            self.set_status_message(format!("No references found for '{}'", symbol));
+            return;
+        }
+
+        let message = format!(
+            "Found {} textual reference(s) for '{}' (no language server)",
+            references.len(),
+            symbol
+        );
+        self.show_references(symbol, references);
+        self.set_status_message(message);
+    }
+
+    /// Display references through a plugin if one handles the hook,
+    /// otherwise in the built-in results buffer
+    pub(super) fn show_references(&mut self, symbol: String, references: Vec<Reference>) {
+        if self.plugin_manager.has_hook_handlers(REFERENCES_HOOK) {
+            let locations = references
+                .iter()
+                .map(|reference| LspLocation {
+                    file: reference.path.to_string_lossy().into_owned(),
+                    line: reference.line as u32,
+                    column: reference.column as u32,
+                })
+                .collect();
+            self.plugin_manager.run_hook(
+                REFERENCES_HOOK,
+                HookArgs::LspReferences { symbol, locations },
+            );
+            return;
+        }
+        self.show_references_panel(&symbol, references);
+    }
+
+    /// List references grouped by file in a read-only results buffer
+    fn show_references_panel(&mut self, symbol: &str, mut references: Vec<Reference>) {
+        references.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.line.cmp(&b.line))
+                .then(a.column.cmp(&b.column))
+        });
+        self.load_reference_line_texts(&mut references);
+
+        let mut content = format!(
+            "References to '{}': {}\nEnter: jump to reference   q: close\n\n",
+            symbol,
+            references.len()
+        );
+        let mut line_references: Vec<Option<Reference>> = vec![None, None, None];
+        let mut current_path: Option<PathBuf> = None;
+        for reference in references {
+            if current_path.as_ref() != Some(&reference.path) {
+                let display = reference
+                    .path
+                    .strip_prefix(&self.working_dir)
+                    .unwrap_or(&reference.path);
+                content.push_str(&format!("{}\n", display.display()));
+                line_references.push(None);
+                current_path = Some(reference.path.clone());
+            }
+            content.push_str(&format!(
+                "  {}:{}: {}\n",
+                reference.line,
+                reference.column,
+                reference.line_text.as_deref().unwrap_or("").trim()
+            ));
+            line_references.push(Some(reference));
+        }
+
+        // A new search replaces the previous results
+        if let Some(previous) = self.references_panel.take() {
+            if self.buffers.contains_key(&previous.buffer_id) {
+                let _ = self.force_close_buffer(previous.buffer_id);
+            }
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(RESULTS_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "references_jump"),
+        );
+
+        let buffer_id =
+            self.open_read_only_buffer(format!("*References: {}*", symbol), RESULTS_MODE, &content);
+        // Start on the first reference, below the first file name
+        if let Some(first) = line_references.iter().position(Option::is_some) {
+            let state = self.active_state_mut();
+            if let Some(offset) = state.buffer.line_start_offset(first) {
+                state.cursors.primary_mut().position = offset;
+            }
+        }
+        self.references_panel = Some(ReferencesPanel {
+            buffer_id,
+            line_references,
+        });
+    }
+
+    /// Fill in the line text of references from open buffers or from disk
+    fn load_reference_line_texts(&self, references: &mut [Reference]) {
+        let mut file_lines: HashMap<PathBuf, Vec<String>> = HashMap::new();
+        for reference in references.iter_mut().filter(|r| r.line_text.is_none()) {
+            let lines = file_lines.entry(reference.path.clone()).or_insert_with(|| {
+                let open = self
+                    .buffers
+                    .values()
+                    .find(|state| state.buffer.file_path() == Some(reference.path.as_path()))
+                    .and_then(|state| state.buffer.to_string());
+                open.or_else(|| std::fs::read_to_string(&reference.path).ok())
+                    .map(|text| text.lines().map(str::to_string).collect())
+                    .unwrap_or_default()
+            });
+            reference.line_text = lines.get(reference.line.saturating_sub(1)).cloned();
+        }
+    }
+
+    /// Open the reference on the cursor line of the results buffer
+    pub fn jump_to_reference(&mut self) -> std::io::Result<()> {
+        let active = self.active_buffer();
+        let line = {
+            let state = self.active_state();
+            state
+                .buffer
+                .get_line_number(state.cursors.primary().position)
+        };
+        let Some(panel) = self
+            .references_panel
+            .as_ref()
+            .filter(|panel| panel.buffer_id == active)
+        else {
+            return Ok(());
+        };
+        let Some(Some(reference)) = panel.line_references.get(line) else {
+            self.set_status_message("Move the cursor to a reference line".to_string());
+            return Ok(());
+        };
+        let (path, line, column) = (reference.path.clone(), reference.line, reference.column);
+
+        self.open_file(&path)?;
+        self.goto_line_col(line, Some(column));
+        let display = path
+            .strip_prefix(&self.working_dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        self.set_status_message(format!("Jumped to {}:{}", display, line));
+        Ok(())
+    }
+}
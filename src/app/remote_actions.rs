@@ -0,0 +1,64 @@
+//! Server side of `fresh --remote`.
+//!
+//! This module provides functionality to:
+//! - Listen for open requests from other `fresh` processes
+//! - Open requested files and answer the client
+//! - Release `--wait` clients when their buffer is closed
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::remote::{OpenRequest, RemoteConnection, RemoteServer};
+use std::io;
+use std::path::Path;
+
+impl Editor {
+    /// Listen on `path` for files to open sent with `fresh --remote open`
+    pub fn start_remote_server(&mut self, path: &Path) -> io::Result<()> {
+        let sender = self
+            .async_bridge
+            .as_ref()
+            .map(|bridge| bridge.sender())
+            .ok_or_else(|| io::Error::other("Async runtime not available"))?;
+        self.remote_server = Some(RemoteServer::listen(path, sender)?);
+        Ok(())
+    }
+
+    /// Open a file requested by a remote client
+    pub(super) fn handle_remote_open(
+        &mut self,
+        request: OpenRequest,
+        mut connection: RemoteConnection,
+    ) {
+        let buffer_id = match self.open_file(&request.path) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                connection.reply_error(&e.to_string());
+                return;
+            }
+        };
+        if let Some(line) = request.line {
+            self.goto_line_col(line, request.column);
+        }
+
+        connection.reply_ok();
+        if request.wait {
+            self.remote_waiters
+                .entry(buffer_id)
+                .or_default()
+                .push(connection);
+            self.set_status_message(format!(
+                "Opened {} (remote client waits until it is closed)",
+                request.path.display()
+            ));
+        } else {
+            self.set_status_message(format!("Opened {}", request.path.display()));
+        }
+    }
+
+    /// Tell clients waiting on a closed buffer that they can continue
+    pub(super) fn release_remote_waiters(&mut self, buffer_id: BufferId) {
+        for connection in self.remote_waiters.remove(&buffer_id).unwrap_or_default() {
+            connection.reply_closed();
+        }
+    }
+}
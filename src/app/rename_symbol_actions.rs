@@ -0,0 +1,312 @@
+//! Project-wide symbol rename.
+//!
+//! This module provides functionality to:
+//! - Rename the symbol at the cursor through the language server when one is running
+//! - Otherwise find whole-word occurrences across the project and list them
+//!   in a preview buffer where each change can be toggled
+//! - Apply the selected changes, one undoable batch per file
+
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::model::event::{BufferId, Event};
+use crate::services::project_rename::{self, Occurrence};
+use crate::view::prompt::PromptType;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Mode of the rename preview buffer
+const PREVIEW_MODE: &str = "rename-preview";
+
+/// Lines at the top of the preview before the first file
+const PREVIEW_HEADER_LINES: usize = 3;
+
+/// A textual rename waiting for review
+pub(crate) struct RenamePreview {
+    buffer_id: BufferId,
+    old_name: String,
+    new_name: String,
+    occurrences: Vec<Occurrence>,
+    selected: Vec<bool>,
+    /// Occurrence shown on each line of the preview buffer
+    line_occurrences: Vec<Option<usize>>,
+}
+
+impl RenamePreview {
+    /// Build the preview text and record which occurrence each line shows
+    fn render(&mut self, working_dir: &std::path::Path) -> String {
+        let count = self.selected.iter().filter(|s| **s).count();
+        let mut content = format!(
+            "Rename '{}' to '{}': {} of {} changes selected\n\
+             Space/Enter: toggle change   a: apply selected   q: cancel\n\n",
+            self.old_name,
+            self.new_name,
+            count,
+            self.occurrences.len()
+        );
+        self.line_occurrences = vec![None; PREVIEW_HEADER_LINES];
+        let mut current_path = None;
+        for (i, occurrence) in self.occurrences.iter().enumerate() {
+            if current_path != Some(&occurrence.path) {
+                current_path = Some(&occurrence.path);
+                let display = occurrence
+                    .path
+                    .strip_prefix(working_dir)
+                    .unwrap_or(&occurrence.path);
+                content.push_str(&format!("{}\n", display.display()));
+                self.line_occurrences.push(None);
+            }
+            let mark = if self.selected[i] { 'x' } else { ' ' };
+            content.push_str(&format!(
+                "  [{}] {}: {}\n",
+                mark,
+                occurrence.line,
+                occurrence.line_text.trim()
+            ));
+            self.line_occurrences.push(Some(i));
+        }
+        content
+    }
+}
+
+impl Editor {
+    /// Rename the symbol at the cursor: through the language server when it
+    /// is running, otherwise textually across the project
+    pub fn rename_symbol(&mut self) -> std::io::Result<()> {
+        if self.is_lsp_ready_for_active_buffer() {
+            return self.start_rename();
+        }
+
+        use crate::primitives::word_navigation::{find_word_end, find_word_start};
+        let (word_start, word_end) = {
+            let state = self.active_state();
+            let cursor_pos = state.cursors.primary().position;
+            (
+                find_word_start(&state.buffer, cursor_pos),
+                find_word_end(&state.buffer, cursor_pos),
+            )
+        };
+        if word_start >= word_end {
+            self.set_status_message("No symbol at cursor".to_string());
+            return Ok(());
+        }
+        let word = self.active_state_mut().get_text_range(word_start, word_end);
+        self.start_prompt_with_initial_text(
+            format!("Rename '{}' in project to: ", word),
+            PromptType::RenameSymbolText {
+                original: word.clone(),
+            },
+            word,
+        );
+        Ok(())
+    }
+
+    /// Find the occurrences for a textual rename and show them for review
+    pub(super) fn preview_textual_rename(&mut self, old_name: String, new_name: String) {
+        let new_name = new_name.trim().to_string();
+        if new_name.is_empty() || new_name == old_name {
+            self.set_status_message("Name unchanged".to_string());
+            return;
+        }
+
+        let open_buffers: HashMap<PathBuf, String> = self
+            .buffers
+            .values()
+            .filter_map(|state| {
+                let path = state.buffer.file_path()?.to_path_buf();
+                Some((path, state.buffer.to_string()?))
+            })
+            .collect();
+        let ignore = self
+            .ignore_service
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        let occurrences =
+            project_rename::scan_project(&self.working_dir, &old_name, &open_buffers, &ignore);
+        drop(ignore);
+        if occurrences.is_empty() {
+            self.set_status_message(format!("No occurrences of '{}'", old_name));
+            return;
+        }
+
+        // A new rename replaces any preview still open
+        if let Some(previous) = self.rename_preview.take() {
+            if self.buffers.contains_key(&previous.buffer_id) {
+                let _ = self.force_close_buffer(previous.buffer_id);
+            }
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(PREVIEW_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(
+                    KeyCode::Char(' '),
+                    KeyModifiers::NONE,
+                    "rename_preview_toggle",
+                )
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "rename_preview_toggle")
+                .with_binding(
+                    KeyCode::Char('a'),
+                    KeyModifiers::NONE,
+                    "rename_preview_apply",
+                ),
+        );
+
+        let buffer_id =
+            self.open_read_only_buffer(format!("*Rename: {}*", old_name), PREVIEW_MODE, "");
+        let mut preview = RenamePreview {
+            buffer_id,
+            old_name,
+            new_name,
+            selected: vec![true; occurrences.len()],
+            occurrences,
+            line_occurrences: Vec::new(),
+        };
+        let content = preview.render(&self.working_dir);
+
+        let threshold = self.config.editor.large_file_threshold_bytes as usize;
+        let state = self.active_state_mut();
+        state
+            .buffer
+            .replace(crate::model::buffer::Buffer::from_str(&content, threshold));
+        // Start on the first change
+        if let Some(offset) = state.buffer.line_start_offset(PREVIEW_HEADER_LINES + 1) {
+            state.cursors.primary_mut().position = offset;
+        }
+
+        let file_count = {
+            let mut paths: Vec<_> = preview.occurrences.iter().map(|o| &o.path).collect();
+            paths.dedup();
+            paths.len()
+        };
+        self.set_status_message(format!(
+            "{} occurrence(s) in {} file(s). Review and press 'a' to apply",
+            preview.occurrences.len(),
+            file_count
+        ));
+        self.rename_preview = Some(preview);
+    }
+
+    /// The rename preview, if it is the active buffer
+    fn active_rename_preview(&mut self) -> Option<&mut RenamePreview> {
+        let active = self.active_buffer();
+        self.rename_preview
+            .as_mut()
+            .filter(|preview| preview.buffer_id == active)
+    }
+
+    /// Toggle the change on the cursor line of the rename preview
+    pub fn toggle_rename_preview_change(&mut self) {
+        let (cursor, line) = {
+            let state = self.active_state();
+            let cursor = state.cursors.primary().position;
+            (cursor, state.buffer.get_line_number(cursor))
+        };
+        let working_dir = self.working_dir.clone();
+        let Some(preview) = self.active_rename_preview() else {
+            return;
+        };
+        let Some(Some(index)) = preview.line_occurrences.get(line).copied() else {
+            return;
+        };
+        preview.selected[index] = !preview.selected[index];
+        let content = preview.render(&working_dir);
+        // Move to the next change so repeated toggles walk down the list
+        let next_line = (line + 1..preview.line_occurrences.len())
+            .find(|&l| preview.line_occurrences[l].is_some());
+
+        let threshold = self.config.editor.large_file_threshold_bytes as usize;
+        let state = self.active_state_mut();
+        state
+            .buffer
+            .replace(crate::model::buffer::Buffer::from_str(&content, threshold));
+        state.cursors.primary_mut().position = next_line
+            .and_then(|l| state.buffer.line_start_offset(l))
+            .unwrap_or(cursor);
+    }
+
+    /// Apply the selected changes of the rename preview and close it
+    pub fn apply_rename_preview(&mut self) -> std::io::Result<()> {
+        if self.active_rename_preview().is_none() {
+            return Ok(());
+        }
+        let Some(preview) = self.rename_preview.take() else {
+            return Ok(());
+        };
+        self.force_close_buffer(preview.buffer_id)?;
+
+        let mut by_path: BTreeMap<&PathBuf, Vec<usize>> = BTreeMap::new();
+        for (occurrence, _) in preview
+            .occurrences
+            .iter()
+            .zip(&preview.selected)
+            .filter(|(_, selected)| **selected)
+        {
+            by_path
+                .entry(&occurrence.path)
+                .or_default()
+                .push(occurrence.offset);
+        }
+
+        let mut changed = 0;
+        let mut skipped = 0;
+        let mut files = 0;
+        for (path, offsets) in by_path {
+            let buffer_id = match self.open_file(path) {
+                Ok(id) => id,
+                Err(e) => {
+                    tracing::warn!("Rename: cannot open {}: {}", path.display(), e);
+                    skipped += offsets.len();
+                    continue;
+                }
+            };
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            let cursor_id = state.cursors.primary_id();
+            let mut events = Vec::new();
+            // Last occurrence first so earlier offsets stay valid
+            for &offset in offsets.iter().rev() {
+                let end = offset + preview.old_name.len();
+                // The file may have changed since the preview was built
+                if end > state.buffer.len() || state.get_text_range(offset, end) != preview.old_name
+                {
+                    skipped += 1;
+                    continue;
+                }
+                events.push(Event::Delete {
+                    range: offset..end,
+                    deleted_text: preview.old_name.clone(),
+                    cursor_id,
+                });
+                events.push(Event::Insert {
+                    position: offset,
+                    text: preview.new_name.clone(),
+                    cursor_id,
+                });
+                changed += 1;
+            }
+            if !events.is_empty() {
+                files += 1;
+                self.apply_rename_batch_to_buffer(
+                    buffer_id,
+                    Event::Batch {
+                        events,
+                        description: "Rename Symbol".to_string(),
+                    },
+                )?;
+            }
+        }
+
+        let mut message = format!(
+            "Renamed '{}' to '{}': {} change(s) in {} file(s)",
+            preview.old_name, preview.new_name, changed, files
+        );
+        if skipped > 0 {
+            message.push_str(&format!(", {} skipped (file changed)", skipped));
+        }
+        self.set_status_message(message);
+        Ok(())
+    }
+}
@@ -1,4 +1,14 @@
 use super::*;
+use crate::view::margin::{LineIndicator, BOOKMARK_INDICATOR_NAMESPACE};
+use crate::view::ui::{SplitRenderContext, StatusBarContent, StatusBarContext};
+use ratatui::style::Color;
+
+/// Smallest terminal width the editor lays itself out in
+pub const MIN_TERMINAL_WIDTH: u16 = 20;
+
+/// Smallest terminal height the editor lays itself out in: one content line
+/// plus the menu, tab, status and prompt lines
+pub const MIN_TERMINAL_HEIGHT: u16 = 5;
 
 impl Editor {
     /// Render the editor to the terminal
@@ -6,6 +16,15 @@ impl Editor {
         let _span = tracing::trace_span!("render").entered();
         let size = frame.area();
 
+        if size.width < MIN_TERMINAL_WIDTH || size.height < MIN_TERMINAL_HEIGHT {
+            self.render_too_small(frame);
+            return;
+        }
+
+        self.apply_zen_mode_to_new_buffers();
+        let zen_mode = self.is_zen_mode();
+        self.damage_tracker.begin_frame();
+
         // NOTE: Viewport sync with cursor is handled by split_rendering.rs which knows the
         // correct content area dimensions. Don't sync here with incorrect EditorState viewport size.
 
@@ -24,6 +43,9 @@ impl Editor {
             }
         }
 
+        // Hide occurrence highlights for cursors that moved since the last frame
+        self.check_occurrence_highlight_timer();
+
         // Refresh search highlights for the current viewport if we have an active search
         // This ensures highlights update when scrolling to show matches in the new viewport
         if let Some(ref search_state) = self.search_state {
@@ -51,7 +73,7 @@ impl Editor {
         let has_file_browser = self.prompt.as_ref().map_or(false, |p| {
             matches!(
                 p.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject
+                PromptType::OpenFile | PromptType::SwitchProject | PromptType::AddWorkspaceFolder
             )
         }) && self.file_open_state.is_some();
 
@@ -61,11 +83,11 @@ impl Editor {
         let constraints = vec![
             Constraint::Length(if self.menu_bar_visible { 1 } else { 0 }), // Menu bar
             Constraint::Min(0),                                            // Main content area
-            Constraint::Length(if has_suggestions || has_file_browser {
+            Constraint::Length(if has_suggestions || has_file_browser || zen_mode {
                 0
             } else {
                 1
-            }), // Status bar (hidden with popups)
+            }), // Status bar (hidden with popups and in zen mode)
             Constraint::Length(if show_search_options { 1 } else { 0 }),   // Search options bar
             Constraint::Length(1), // Prompt line (always reserved)
         ];
@@ -312,30 +334,43 @@ impl Editor {
 
         let is_maximized = self.split_manager.is_maximized();
 
-        let (split_areas, tab_areas, close_split_areas, maximize_split_areas, view_line_mappings) =
-            SplitRenderer::render_content(
-                frame,
-                editor_content_area,
-                &self.split_manager,
-                &mut self.buffers,
-                &self.buffer_metadata,
-                &mut self.event_logs,
-                &self.theme,
-                self.ansi_background.as_ref(),
-                self.background_fade,
+        let (
+            split_areas,
+            tab_areas,
+            close_split_areas,
+            maximize_split_areas,
+            tab_bars,
+            view_line_mappings,
+        ) = SplitRenderer::render_content(
+            frame,
+            editor_content_area,
+            &SplitRenderContext {
+                split_manager: &self.split_manager,
+                buffer_metadata: &self.buffer_metadata,
+                theme: &self.theme,
+                ansi_background: self.ansi_background.as_ref(),
+                background_fade: self.background_fade,
                 lsp_waiting,
-                self.config.editor.large_file_threshold_bytes,
-                self.config.editor.line_wrap,
-                self.config.editor.estimated_line_length,
-                self.config.editor.highlight_context_bytes,
-                Some(&mut self.split_view_states),
+                large_file_threshold_bytes: self.config.editor.large_file_threshold_bytes,
+                estimated_line_length: self.config.editor.estimated_line_length,
+                highlight_context_bytes: self.config.editor.highlight_context_bytes,
                 hide_cursor,
                 hovered_tab,
                 hovered_close_split,
                 hovered_maximize_split,
                 is_maximized,
-                self.config.editor.relative_line_numbers,
-            );
+                line_number_mode: self.config.editor.effective_line_number_mode(),
+                gutter_components: &self.config.editor.gutter,
+                indent_guides: self.config.editor.indent_guides,
+                sticky_scroll: self.config.editor.sticky_scroll,
+                zen_width: zen_mode.then_some(self.config.editor.zen_mode_width),
+            },
+            &mut self.buffers,
+            &mut self.event_logs,
+            Some(&mut self.split_view_states),
+            &mut self.line_render_caches,
+            &mut self.damage_tracker,
+        );
 
         // Render terminal content on top of split content for terminal buffers
         self.render_terminal_splits(frame, &split_areas);
@@ -344,11 +379,13 @@ impl Editor {
         self.cached_layout.tab_areas = tab_areas;
         self.cached_layout.close_split_areas = close_split_areas;
         self.cached_layout.maximize_split_areas = maximize_split_areas;
+        self.cached_layout.tab_bars = tab_bars;
         self.cached_layout.view_line_mappings = view_line_mappings;
         self.cached_layout.separator_areas = self
             .split_manager
             .get_separators_with_ids(editor_content_area);
         self.cached_layout.editor_content_area = Some(editor_content_area);
+        self.render_visual_bell(frame);
 
         // Render hover highlights for separators and scrollbars
         self.render_hover_highlights(frame);
@@ -360,7 +397,7 @@ impl Editor {
             // For OpenFile/SwitchProject prompt, render the file browser popup
             if matches!(
                 prompt.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject
+                PromptType::OpenFile | PromptType::SwitchProject | PromptType::AddWorkspaceFolder
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     // Calculate popup area: position above prompt line, covering status bar
@@ -384,7 +421,8 @@ impl Editor {
                 // For other prompts, render suggestions as before
                 // Calculate overlay area: position above prompt line (which is below status bar)
                 let suggestion_count = prompt.suggestions.len().min(10);
-                let height = suggestion_count as u16 + 2; // +2 for borders
+                // +2 for borders, and no taller than the space above the prompt
+                let height = (suggestion_count as u16 + 2).min(main_chunks[prompt_line_idx].y);
 
                 // Position suggestions above the prompt line
                 // The prompt line is at main_chunks[3], so suggestions go above it
@@ -409,15 +447,25 @@ impl Editor {
         }
 
         // Clone all immutable values before the mutable borrow
-        let display_name = self
+        let mut display_name = self
             .buffer_metadata
             .get(&self.active_buffer())
             .map(|m| m.display_name.clone())
             .unwrap_or_else(|| "[No Name]".to_string());
+        if let Some(dir) = self.split_working_dir_label() {
+            display_name = format!("{} [cwd: {}]", display_name, dir);
+        }
         let status_message = self.status_message.clone();
         let plugin_status_message = self.plugin_status_message.clone();
         let prompt = self.prompt.clone();
         let lsp_status = self.lsp_status.clone();
+        let git_status = self
+            .git_status
+            .as_ref()
+            .filter(|_| self.config.editor.show_git_status)
+            .map(|status| status.label())
+            .unwrap_or_default();
+        let health_summary = self.health_summary();
         let theme = self.theme.clone();
         let keybindings_cloned = self.keybindings.clone(); // Clone the keybindings
         let chord_state_cloned = self.chord_state.clone(); // Clone the chord state
@@ -426,20 +474,41 @@ impl Editor {
         let update_available = self.latest_version().map(|v| v.to_string());
 
         // Render status bar (hidden when suggestions or file browser popup is shown)
-        if !has_suggestions && !has_file_browser {
-            StatusBarRenderer::render_status_bar(
+        self.cached_layout.status_bar_language_area = None;
+        self.cached_layout.status_bar_indentation_area = None;
+        self.cached_layout.status_bar_health_area = None;
+        if !has_suggestions && !has_file_browser && !zen_mode {
+            let content = StatusBarContent::new(
+                self.active_state_mut(),
+                &StatusBarContext {
+                    status_message: &status_message,
+                    plugin_status_message: &plugin_status_message,
+                    lsp_status: &lsp_status,
+                    git_status: &git_status,
+                    health_summary: &health_summary,
+                    theme: &theme,
+                    display_name: &display_name,
+                    keybindings: &keybindings_cloned,
+                    chord_state: &chord_state_cloned,
+                    update_available: update_available.as_deref(),
+                },
+            );
+            let areas = self.damage_tracker.draw(
                 frame,
+                DamageRegion::StatusBar,
                 main_chunks[status_bar_idx],
-                self.active_state_mut(), // Use the mutable reference
-                &status_message,
-                &plugin_status_message,
-                &lsp_status,
-                &theme,
-                &display_name,
-                &keybindings_cloned,         // Pass the cloned keybindings
-                &chord_state_cloned,         // Pass the cloned chord state
-                update_available.as_deref(), // Pass update availability
+                content.fingerprint(),
+                |frame| {
+                    StatusBarRenderer::render_status_bar(
+                        frame,
+                        main_chunks[status_bar_idx],
+                        &content,
+                    )
+                },
             );
+            self.cached_layout.status_bar_language_area = areas.language;
+            self.cached_layout.status_bar_indentation_area = areas.indentation;
+            self.cached_layout.status_bar_health_area = areas.health;
         }
 
         // Render search options bar when in search prompt
@@ -478,6 +547,7 @@ impl Editor {
                 prompt.prompt_type,
                 crate::view::prompt::PromptType::OpenFile
                     | crate::view::prompt::PromptType::SwitchProject
+                    | crate::view::prompt::PromptType::AddWorkspaceFolder
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     StatusBarRenderer::render_file_open_prompt(
@@ -577,8 +647,11 @@ impl Editor {
         // Store popup areas for mouse hit testing
         self.cached_layout.popup_areas = popup_info.clone();
 
-        // Now render popups
+        // Now render popups, pinned ones below the others
         let state = self.active_state_mut();
+        for popup in state.popups.pinned() {
+            popup.render(frame, popup.calculate_area(size, None), &theme_clone);
+        }
         if state.popups.is_visible() {
             for (popup_idx, popup) in state.popups.all().iter().enumerate() {
                 if let Some((_, popup_area, _, _, _)) = popup_info.get(popup_idx) {
@@ -592,6 +665,15 @@ impl Editor {
             }
         }
 
+        // Render the tab overflow dropdown above popups
+        self.render_tab_overflow_menu(frame);
+        self.render_context_menu(frame);
+
+        // Render toast notifications above popups, in the bottom-right of the editor area
+        self.notifications
+            .render(frame, editor_content_area, &self.theme);
+        self.render_quit_dialog(frame);
+
         // Render menu bar last so dropdown appears on top of all other content
         // Update menu context with current editor state
         // Collect values first to avoid borrow conflicts
@@ -619,22 +701,7 @@ impl Editor {
         let mouse_capture = self.mouse_enabled;
         let mouse_hover = self.config.editor.mouse_hover_enabled;
         // Check if LSP is enabled for this buffer AND the server is running and ready
-        let lsp_available = self
-            .buffer_metadata
-            .get(&self.active_buffer())
-            .and_then(|metadata| {
-                if !metadata.lsp_enabled {
-                    return None;
-                }
-                // Get file path and detect language
-                metadata.file_path().and_then(|path| {
-                    detect_language(path, &self.config.languages).and_then(|language| {
-                        // Check if LSP server for this language is ready
-                        self.lsp.as_ref().map(|lsp| lsp.is_server_ready(&language))
-                    })
-                })
-            })
-            .unwrap_or(false);
+        let lsp_available = self.active_buffer_lsp_ready();
         let show_hidden = self
             .file_explorer
             .as_ref()
@@ -646,6 +713,7 @@ impl Editor {
             .map(|fe| fe.ignore_patterns().show_gitignored())
             .unwrap_or(false);
         let has_selection = self.has_active_selection();
+        self.refresh_plugins_menu();
 
         self.menu_state
             .context
@@ -660,7 +728,9 @@ impl Editor {
             .set(context_keys::FILE_EXPLORER_SHOW_HIDDEN, show_hidden)
             .set(context_keys::FILE_EXPLORER_SHOW_GITIGNORED, show_gitignored)
             .set(context_keys::HAS_SELECTION, has_selection)
-            .set(context_keys::MENU_BAR, self.menu_bar_visible);
+            .set(context_keys::MENU_BAR, self.menu_bar_visible)
+            .set(context_keys::AUTO_REVERT, self.auto_revert_enabled)
+            .set(context_keys::ZEN_MODE, zen_mode);
 
         // Render settings modal (before menu bar so menus can overlay)
         // Check visibility first to avoid borrow conflict with dimming
@@ -740,6 +810,51 @@ impl Editor {
             frame.buffer_mut(),
             self.color_capability,
         );
+
+        tracing::trace!("Redrawn regions: {:?}", self.damage_tracker.damaged());
+    }
+
+    /// Render the notice shown instead of the editor when the terminal is
+    /// smaller than the minimum size
+    fn render_too_small(&mut self, frame: &mut Frame) {
+        use ratatui::style::Style;
+        use ratatui::text::Line;
+        use ratatui::widgets::{Paragraph, Wrap};
+
+        // Nothing on screen can be clicked
+        self.cached_layout = CachedLayout::default();
+
+        let size = frame.area();
+        let mut lines = vec![Line::from("Window too small")];
+        if size.height > 1 {
+            lines.push(Line::from(format!(
+                "Need {}x{}, have {}x{}",
+                MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT, size.width, size.height
+            )));
+        }
+        let top = size.height.saturating_sub(lines.len() as u16) / 2;
+        let area = ratatui::layout::Rect::new(
+            size.x,
+            size.y + top,
+            size.width,
+            size.height.saturating_sub(top),
+        );
+        let style = Style::default()
+            .fg(self.theme.editor_fg)
+            .bg(self.theme.editor_bg);
+        frame.render_widget(Paragraph::new("").style(style), size);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(style)
+                .alignment(ratatui::layout::Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+
+        crate::view::color_support::convert_buffer_colors(
+            frame.buffer_mut(),
+            self.color_capability,
+        );
     }
 
     /// Apply dimming effect to UI elements outside the focused terminal area
@@ -948,6 +1063,7 @@ impl Editor {
 
         if is_transient_popup {
             self.hide_popup();
+            self.signature_help_call = None;
             tracing::debug!("Dismissed transient popup");
         }
     }
@@ -959,9 +1075,15 @@ impl Editor {
     /// - Dismisses transient popups (Hover, Signature Help)
     /// - Clears LSP hover state and pending requests
     /// - Removes hover symbol highlighting
+    /// - Ends the undo step of the text being typed
     pub(super) fn on_editor_focus_lost(&mut self) {
         // Dismiss transient popups via EditorState
         self.active_state_mut().on_focus_lost();
+        self.signature_help_call = None;
+        let buffer_id = self.active_buffer();
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.add_undo_boundary();
+        }
 
         // Clear hover state
         self.mouse_state.lsp_hover_state = None;
@@ -1435,15 +1557,12 @@ impl Editor {
     }
 
     /// Notify LSP of a file save
-    pub(super) fn notify_lsp_save(&mut self) {
+    pub(super) fn notify_lsp_save(&mut self, buffer_id: BufferId) {
         // Check if LSP is enabled for this buffer
-        let metadata = match self.buffer_metadata.get(&self.active_buffer()) {
+        let metadata = match self.buffer_metadata.get(&buffer_id) {
             Some(m) => m,
             None => {
-                tracing::debug!(
-                    "notify_lsp_save: no metadata for buffer {:?}",
-                    self.active_buffer()
-                );
+                tracing::debug!("notify_lsp_save: no metadata for buffer {:?}", buffer_id);
                 return;
             }
         };
@@ -1480,7 +1599,11 @@ impl Editor {
         };
 
         // Get the full text to send with didSave
-        let full_text = match self.active_state().buffer.to_string() {
+        let full_text = match self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string())
+        {
             Some(t) => t,
             None => {
                 tracing::debug!("notify_lsp_save: buffer not fully loaded");
@@ -1515,7 +1638,8 @@ impl Editor {
     /// Convert an action into a list of events to apply to the active buffer
     /// Returns None for actions that don't generate events (like Quit)
     pub fn action_to_events(&mut self, action: Action) -> Option<Vec<Event>> {
-        let tab_size = self.config.editor.tab_size;
+        // The buffer's own indent width (language settings, detected or set)
+        let tab_size = self.active_state().tab_size;
         let auto_indent = self.config.editor.auto_indent;
         let estimated_line_length = self.config.editor.estimated_line_length;
 
@@ -1558,36 +1682,12 @@ impl Editor {
             return;
         }
 
-        // Get theme colors and search settings before borrowing state
+        // Get theme colors before borrowing state
         let search_bg = self.theme.search_match_bg;
         let search_fg = self.theme.search_match_fg;
-        let case_sensitive = self.search_case_sensitive;
-        let whole_word = self.search_whole_word;
-        let use_regex = self.search_use_regex;
         let ns = self.search_namespace.clone();
 
-        // Build regex pattern if regex mode is enabled, or escape for literal search
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
-
-        // Build regex with case sensitivity
-        let regex = regex::RegexBuilder::new(&regex_pattern)
-            .case_insensitive(!case_sensitive)
-            .build();
-
-        let regex = match regex {
+        let regex = match self.search_regex(query) {
             Ok(r) => r,
             Err(_) => {
                 // Invalid regex, clear highlights and return
@@ -1645,12 +1745,32 @@ impl Editor {
                 },
                 ns.clone(),
             )
-            .with_priority_value(10); // Priority - above syntax highlighting
+            .with_priority_value(crate::view::overlay::priority::SEARCH_MATCH);
 
             state.overlays.add(overlay);
         }
     }
 
+    /// Build the regex for a search query from the current search options
+    ///
+    /// Literal queries are escaped; whole-word mode wraps the pattern in word
+    /// boundaries, and matching is case-insensitive unless case sensitivity is on.
+    fn search_regex(&self, query: &str) -> Result<regex::Regex, regex::Error> {
+        let pattern = if self.search_use_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let pattern = if self.search_whole_word {
+            format!(r"\b{}\b", pattern)
+        } else {
+            pattern
+        };
+        regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!self.search_case_sensitive)
+            .build()
+    }
+
     /// Perform a search and update search state
     pub(super) fn perform_search(&mut self, query: &str) {
         // Don't clear search highlights here - keep them from incremental search
@@ -1678,11 +1798,6 @@ impl Editor {
             }
         };
 
-        // Get search settings
-        let case_sensitive = self.search_case_sensitive;
-        let whole_word = self.search_whole_word;
-        let use_regex = self.search_use_regex;
-
         // Determine search boundaries
         let (search_start, search_end) = if let Some(ref range) = search_range {
             (range.start, range.end)
@@ -1690,27 +1805,7 @@ impl Editor {
             (0, buffer_content.len())
         };
 
-        // Build regex pattern
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
-
-        // Build regex with case sensitivity
-        let regex = match regex::RegexBuilder::new(&regex_pattern)
-            .case_insensitive(!case_sensitive)
-            .build()
-        {
+        let regex = match self.search_regex(query) {
             Ok(r) => r,
             Err(e) => {
                 self.search_state = None;
@@ -1877,6 +1972,115 @@ impl Editor {
         }
     }
 
+    /// Select every match of the current search, one cursor per match
+    ///
+    /// Matches are limited to the search range when searching in a selection.
+    /// The cursor on the current match becomes the primary cursor, so the view
+    /// stays where it is; undo restores the previous cursors.
+    pub(super) fn select_all_matches(&mut self) {
+        let Some(search_state) = self.search_state.clone() else {
+            self.set_status_message("No active search. Press Ctrl+F to search.".to_string());
+            return;
+        };
+
+        let regex = match self.search_regex(&search_state.query) {
+            Ok(r) => r,
+            Err(e) => {
+                self.set_status_message(format!("Invalid regex: {}", e));
+                return;
+            }
+        };
+
+        let Some(buffer_content) = self.active_state().buffer.to_string() else {
+            self.set_status_message("Buffer not fully loaded".to_string());
+            return;
+        };
+
+        // The buffer may have been edited since the search ran, so find the
+        // matches again rather than trusting the stored positions
+        let (search_start, search_end) = match search_state.search_range {
+            Some(ref range) => (
+                range.start.min(buffer_content.len()),
+                range.end.min(buffer_content.len()),
+            ),
+            None => (0, buffer_content.len()),
+        };
+        let Some(search_slice) = buffer_content.get(search_start..search_end) else {
+            return;
+        };
+        let mut matches: Vec<Range<usize>> = regex
+            .find_iter(search_slice)
+            .filter(|m| !m.is_empty())
+            .map(|m| search_start + m.start()..search_start + m.end())
+            .collect();
+
+        if matches.is_empty() {
+            self.set_status_message(format!("No matches found for '{}'", search_state.query));
+            return;
+        }
+
+        // Cursors added later become primary, so put the current match last
+        let current_start = search_state
+            .current_match_index
+            .and_then(|i| search_state.matches.get(i).copied());
+        if let Some(index) = matches.iter().position(|m| Some(m.start) == current_start) {
+            let current = matches.remove(index);
+            matches.push(current);
+        }
+
+        let state = self.active_state();
+        let first_id = state
+            .cursors
+            .iter()
+            .map(|(id, _)| id)
+            .min_by_key(|id| id.0)
+            .expect("Should have at least one cursor");
+        let first_cursor = *state.cursors.get(first_id).unwrap();
+        let mut next_id = state.cursors.iter().map(|(id, _)| id.0).max().unwrap_or(0) + 1;
+
+        let mut events: Vec<Event> = state
+            .cursors
+            .iter()
+            .filter(|(id, _)| *id != first_id)
+            .map(|(cursor_id, cursor)| Event::RemoveCursor {
+                cursor_id,
+                position: cursor.position,
+                anchor: cursor.anchor,
+            })
+            .collect();
+        events.push(Event::MoveCursor {
+            cursor_id: first_id,
+            old_position: first_cursor.position,
+            new_position: matches[0].end,
+            old_anchor: first_cursor.anchor,
+            new_anchor: Some(matches[0].start),
+            old_sticky_column: first_cursor.sticky_column,
+            new_sticky_column: 0,
+        });
+        for range in &matches[1..] {
+            events.push(Event::AddCursor {
+                cursor_id: crate::model::event::CursorId(next_id),
+                position: range.end,
+                anchor: Some(range.start),
+            });
+            next_id += 1;
+        }
+
+        let batch = Event::Batch {
+            events,
+            description: "Select all matches".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        let count = matches.len();
+        self.set_status_message(format!(
+            "Selected {} match{}",
+            count,
+            if count == 1 { "" } else { "es" }
+        ));
+    }
+
     /// Perform a replace-all operation
     /// Replaces all occurrences of the search query with the replacement text
     pub(super) fn perform_replace(&mut self, search: &str, replacement: &str) {
@@ -2347,48 +2551,6 @@ impl Editor {
         ));
     }
 
-    /// Smart home: toggle between line start and first non-whitespace character
-    pub(super) fn smart_home(&mut self) {
-        let estimated_line_length = self.config.editor.estimated_line_length;
-        let state = self.active_state_mut();
-        let cursor = state.cursors.primary().clone();
-        let cursor_id = state.cursors.primary_id();
-
-        // Get line information
-        let mut iter = state
-            .buffer
-            .line_iterator(cursor.position, estimated_line_length);
-        if let Some((line_start, line_content)) = iter.next() {
-            // Find first non-whitespace character
-            let first_non_ws = line_content
-                .chars()
-                .take_while(|c| *c != '\n')
-                .position(|c| !c.is_whitespace())
-                .map(|offset| line_start + offset)
-                .unwrap_or(line_start);
-
-            // Toggle: if at first non-ws, go to line start; otherwise go to first non-ws
-            let new_pos = if cursor.position == first_non_ws {
-                line_start
-            } else {
-                first_non_ws
-            };
-
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
-            };
-
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
-        }
-    }
-
     /// Toggle comment on the current line or selection
     pub(super) fn toggle_comment(&mut self) {
         // Determine comment prefix based on file extension
@@ -2899,10 +3061,10 @@ impl Editor {
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            state.buffer = crate::model::buffer::Buffer::from_str(
+            state.buffer.replace(crate::model::buffer::Buffer::from_str(
                 &content,
                 self.config.editor.large_file_threshold_bytes as usize,
-            );
+            ));
         }
 
         // Set metadata
@@ -2971,10 +3133,10 @@ impl Editor {
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            state.buffer = crate::model::buffer::Buffer::from_str(
+            state.buffer.replace(crate::model::buffer::Buffer::from_str(
                 &content,
                 self.config.editor.large_file_threshold_bytes as usize,
-            );
+            ));
         }
 
         // Set metadata
@@ -3000,14 +3162,43 @@ impl Editor {
     pub(super) fn set_bookmark(&mut self, key: char) {
         let buffer_id = self.active_buffer();
         let position = self.active_state().cursors.primary().position;
+        self.insert_bookmark(key, buffer_id, position);
+        self.set_status_message(format!("Bookmark '{}' set", key));
+    }
+
+    /// Record bookmark `key` at `position` in `buffer_id`, marking its line
+    /// in the gutter
+    pub(super) fn insert_bookmark(&mut self, key: char, buffer_id: BufferId, position: usize) {
+        self.remove_bookmark(key);
+        let indicator = self.buffers.get_mut(&buffer_id).map(|state| {
+            state.margins.set_line_indicator(
+                position,
+                BOOKMARK_INDICATOR_NAMESPACE.to_string(),
+                LineIndicator::new(key.to_string(), Color::Cyan, 0),
+            )
+        });
         self.bookmarks.insert(
             key,
             Bookmark {
                 buffer_id,
                 position,
+                indicator,
             },
         );
-        self.set_status_message(format!("Bookmark '{}' set", key));
+    }
+
+    /// Remove bookmark `key` and its gutter indicator
+    fn remove_bookmark(&mut self, key: char) -> Option<Bookmark> {
+        let bookmark = self.bookmarks.remove(&key)?;
+        if let (Some(marker_id), Some(state)) = (
+            bookmark.indicator,
+            self.buffers.get_mut(&bookmark.buffer_id),
+        ) {
+            state
+                .margins
+                .remove_line_indicator(marker_id, BOOKMARK_INDICATOR_NAMESPACE);
+        }
+        Some(bookmark)
     }
 
     /// Jump to a bookmark
@@ -3028,7 +3219,11 @@ impl Editor {
             let state = self.active_state_mut();
             let cursor_id = state.cursors.primary_id();
             let old_pos = state.cursors.primary().position;
-            let new_pos = bookmark.position.min(state.buffer.len());
+            let new_pos = bookmark
+                .indicator
+                .and_then(|marker_id| state.margins.line_indicator_position(marker_id))
+                .unwrap_or(bookmark.position)
+                .min(state.buffer.len());
 
             let event = Event::MoveCursor {
                 cursor_id,
@@ -3050,7 +3245,7 @@ impl Editor {
 
     /// Clear a bookmark
     pub(super) fn clear_bookmark(&mut self, key: char) {
-        if self.bookmarks.remove(&key).is_some() {
+        if self.remove_bookmark(key).is_some() {
             self.set_status_message(format!("Bookmark '{}' cleared", key));
         } else {
             self.set_status_message(format!("Bookmark '{}' not set", key));
@@ -0,0 +1,268 @@
+//! Sending code to a REPL.
+//!
+//! This module provides functionality to:
+//! - Attach a buffer to a REPL running in a terminal split below it, started
+//!   from the `repl` command of the buffer's language or from "REPL: Start"
+//! - Send the current line, the selection or the current `%%` cell to it
+//!
+//! Buffers whose language uses the same REPL command share one REPL.
+
+use super::Editor;
+use crate::model::event::{BufferId, SplitDirection};
+use crate::services::lsp::manager::detect_language;
+use crate::services::repl;
+use crate::services::terminal::TerminalId;
+use crate::view::prompt::{Prompt, PromptType};
+use crate::view::split::SplitViewState;
+
+/// Share of the height kept by the code when the REPL split opens
+const REPL_SPLIT_RATIO: f32 = 0.6;
+
+/// A REPL process a buffer sends code to
+#[derive(Debug, Clone)]
+pub(crate) struct Repl {
+    terminal_id: TerminalId,
+    /// Command the REPL was started with
+    command: String,
+}
+
+impl Editor {
+    /// The `repl` command configured for the language of a buffer, found
+    /// from its file name or its "Change Language Mode" override
+    fn configured_repl_command(&self, buffer_id: BufferId) -> Option<String> {
+        let state = self.buffers.get(&buffer_id)?;
+        let from_path = state
+            .buffer
+            .file_path()
+            .and_then(|path| detect_language(path, &self.config.languages))
+            .and_then(|language| self.config.languages.get(&language));
+        let from_override = state.syntax_override.as_ref().and_then(|syntax| {
+            let syntax = syntax.to_lowercase();
+            self.config
+                .languages
+                .iter()
+                .find(|(name, config)| **name == syntax || config.grammar == syntax)
+                .map(|(_, config)| config)
+        });
+        from_override.or(from_path)?.repl.clone()
+    }
+
+    /// Prompt for the command of a REPL to attach the active buffer to
+    pub fn start_repl_prompt(&mut self) {
+        let command = self
+            .configured_repl_command(self.active_buffer())
+            .unwrap_or_default();
+        self.prompt = Some(Prompt::with_initial_text(
+            "REPL command: ".to_string(),
+            PromptType::StartRepl,
+            command,
+        ));
+    }
+
+    /// Start `command` in a terminal split and attach the active buffer to it
+    pub(super) fn start_repl(&mut self, command: &str) -> Option<TerminalId> {
+        let command = command.trim();
+        if command.is_empty() {
+            self.set_status_message("No REPL command given".to_string());
+            return None;
+        }
+        let source_buffer = self.active_buffer();
+        if self.is_terminal_buffer(source_buffer) {
+            self.set_status_message("Cannot attach a terminal to a REPL".to_string());
+            return None;
+        }
+
+        let source_split = self.split_manager.active_split();
+        let (terminal_id, terminal_buffer) = match self.spawn_terminal_buffer(source_split) {
+            Ok(ids) => ids,
+            Err(e) => {
+                self.set_status_message(format!("Failed to start REPL: {}", e));
+                return None;
+            }
+        };
+        // The REPL gets its own split rather than a tab next to the code
+        if let Some(view_state) = self.split_view_states.get_mut(&source_split) {
+            view_state.remove_buffer(terminal_buffer);
+        }
+        self.show_repl_split(terminal_buffer);
+
+        // Replace the shell so the terminal closes when the REPL exits
+        #[cfg(unix)]
+        let line = format!("exec {}\r", command);
+        #[cfg(not(unix))]
+        let line = format!("{}\r", command);
+        if let Some(handle) = self.terminal_manager.get(terminal_id) {
+            handle.write(line.as_bytes());
+        }
+
+        self.repls.insert(
+            source_buffer,
+            Repl {
+                terminal_id,
+                command: command.to_string(),
+            },
+        );
+        self.set_status_message(format!(
+            "REPL '{}' started in terminal {}",
+            command, terminal_id
+        ));
+        Some(terminal_id)
+    }
+
+    /// Show a REPL terminal in a split below the active one, keeping the
+    /// focus on the code
+    fn show_repl_split(&mut self, terminal_buffer: BufferId) {
+        let source_split = self.split_manager.active_split();
+        self.save_current_split_view_state();
+        match self.split_manager.split_active(
+            SplitDirection::Horizontal,
+            terminal_buffer,
+            REPL_SPLIT_RATIO,
+        ) {
+            Ok(split_id) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    terminal_buffer,
+                );
+                // Terminal buffers should not wrap lines so escape sequences stay intact
+                view_state.viewport.line_wrap_enabled = false;
+                self.split_view_states.insert(split_id, view_state);
+                self.split_manager.set_active_split(source_split);
+                self.restore_current_split_view_state();
+            }
+            Err(e) => tracing::error!("Failed to create REPL split: {}", e),
+        }
+        self.resize_visible_terminals();
+    }
+
+    /// The running REPL of the active buffer, starting (or sharing) the one
+    /// configured for its language if it has none
+    fn active_buffer_repl(&mut self) -> Option<TerminalId> {
+        let buffer_id = self.active_buffer();
+        let is_alive = |editor: &Editor, repl: &Repl| {
+            editor
+                .terminal_manager
+                .get(repl.terminal_id)
+                .is_some_and(|handle| handle.is_alive())
+        };
+
+        let attached = self
+            .repls
+            .get(&buffer_id)
+            .filter(|repl| is_alive(self, repl))
+            .cloned();
+        let repl = match attached {
+            Some(repl) => repl,
+            None => {
+                let Some(command) = self.configured_repl_command(buffer_id) else {
+                    self.set_status_message(
+                        "No REPL for this buffer (run REPL: Start, or set \"repl\" for its language)"
+                            .to_string(),
+                    );
+                    return None;
+                };
+                let shared = self
+                    .repls
+                    .values()
+                    .find(|repl| repl.command == command && is_alive(self, repl))
+                    .cloned();
+                match shared {
+                    Some(repl) => {
+                        self.repls.insert(buffer_id, repl.clone());
+                        repl
+                    }
+                    None => return self.start_repl(&command),
+                }
+            }
+        };
+
+        // Reopen the REPL's split if it was closed
+        if let Some(terminal_buffer) = self
+            .terminal_buffers
+            .iter()
+            .find(|(_, id)| **id == repl.terminal_id)
+            .map(|(buffer, _)| *buffer)
+        {
+            if self
+                .split_manager
+                .splits_for_buffer(terminal_buffer)
+                .is_empty()
+            {
+                self.show_repl_split(terminal_buffer);
+            }
+        }
+        Some(repl.terminal_id)
+    }
+
+    /// Send `code` to the active buffer's REPL. Returns false if there is none.
+    fn send_to_repl(&mut self, code: &str) -> bool {
+        let Some(terminal_id) = self.active_buffer_repl() else {
+            return false;
+        };
+        if let Some(handle) = self.terminal_manager.get(terminal_id) {
+            handle.write(&repl::repl_input(code));
+        }
+        true
+    }
+
+    /// Send the selection, or the cursor line, to the REPL
+    ///
+    /// After sending a line the cursor moves to the next one, so that lines
+    /// can be sent one after another.
+    pub fn repl_send_line_or_selection(&mut self) {
+        let (code, next_line) = {
+            let state = self.active_state_mut();
+            let cursor = *state.cursors.primary();
+            match cursor.selection_range() {
+                Some(range) => (state.get_text_range(range.start, range.end), None),
+                None => {
+                    let line = state.buffer.get_line_number(cursor.position);
+                    let text = state.buffer.to_string().unwrap_or_default();
+                    let lines: Vec<&str> = text.lines().collect();
+                    let code = lines.get(line).copied().unwrap_or("").to_string();
+                    let next_line = (line + 1 < lines.len()).then_some(line + 1);
+                    (code, next_line)
+                }
+            }
+        };
+        let line_count = code.lines().count().max(1);
+        if !self.send_to_repl(&code) {
+            return;
+        }
+        if let Some(line) = next_line {
+            self.goto_line_col(line + 1, None);
+        }
+        self.set_status_message(format!("Sent {} line(s) to the REPL", line_count));
+    }
+
+    /// Send the `%%` cell around the cursor to the REPL and move to the next
+    /// cell
+    pub fn repl_send_cell(&mut self) {
+        let (code, next_line) = {
+            let state = self.active_state();
+            let line = state
+                .buffer
+                .get_line_number(state.cursors.primary().position);
+            let text = state.buffer.to_string().unwrap_or_default();
+            let lines: Vec<&str> = text.lines().collect();
+            let range = repl::cell_line_range(&lines, line);
+            let code = lines[range.clone()].join("\n");
+            // Skip the next cell's marker line
+            let next_line = (range.end + 1 < lines.len()).then_some(range.end + 1);
+            (code, next_line)
+        };
+        if code.trim().is_empty() {
+            self.set_status_message("The cell is empty".to_string());
+            return;
+        }
+        let line_count = code.lines().count();
+        if !self.send_to_repl(&code) {
+            return;
+        }
+        if let Some(line) = next_line {
+            self.goto_line_col(line + 1, None);
+        }
+        self.set_status_message(format!("Sent cell ({} line(s)) to the REPL", line_count));
+    }
+}
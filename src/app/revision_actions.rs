@@ -0,0 +1,106 @@
+//! Earlier git revisions of the current file.
+//!
+//! This module provides functionality to:
+//! - List the commits that changed the current file (from `git log`)
+//! - Open the file at the chosen commit in a read-only `file@abc1234`
+//!   buffer, through the `git://` provider
+//! - Compare the buffer with the file at the chosen commit in the diff viewer
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::vfs::{self, Provider};
+use crate::view::prompt::{Prompt, PromptType};
+use std::path::Path;
+
+impl Editor {
+    /// Prompt for a commit that changed the current file, to open it at that
+    /// commit or, with `diff`, to compare the buffer with it
+    pub fn start_file_revision_prompt(&mut self, diff: bool) {
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            self.set_status_message("Buffer has no file".to_string());
+            return;
+        };
+        let revisions = match vfs::git_file_revisions(&path) {
+            Ok(revisions) => revisions,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
+        if revisions.is_empty() {
+            self.set_status_message(format!("No commits changed {}", path.display()));
+            return;
+        }
+
+        let suggestions: Vec<Suggestion> = revisions
+            .into_iter()
+            .map(|revision| Suggestion {
+                text: format!("{} {}", revision.hash, revision.summary),
+                description: Some(format!("{}, {}", revision.author, revision.date)),
+                value: Some(revision.hash),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        let message = if diff {
+            "Compare with revision: "
+        } else {
+            "Open revision: "
+        };
+        self.prompt = Some(Prompt::with_suggestions(
+            message.to_string(),
+            PromptType::FileRevision { path, diff },
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Open `path` at commit `hash`, or compare the buffer with it
+    pub(super) fn open_file_revision(&mut self, path: &Path, hash: &str, diff: bool) {
+        if hash.is_empty() {
+            return;
+        }
+        let url = vfs::git_revision_url(hash, path);
+        if !diff {
+            self.open_vfs_url(url);
+            return;
+        }
+
+        let Some(Provider::BuiltIn(provider)) = self.vfs.get(&url.scheme).cloned() else {
+            self.set_status_message("git:// is not available".to_string());
+            return;
+        };
+        let old = match provider.read(&url.path, &self.working_dir) {
+            Ok(old) => old,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
+        let current = self.active_state().buffer.to_string().unwrap_or_default();
+        let name = self.vfs.display_name(&url);
+        let current_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let (_, changes) = self.open_text_diff(
+            (&name, old.as_bytes()),
+            (&current_name, current.as_bytes()),
+            format!("*Diff: {} vs {}*", name, current_name),
+        );
+        if changes == 0 {
+            self.set_status_message(format!("No differences from {}", name));
+        } else {
+            self.set_status_message(format!("{} changed line(s)", changes));
+        }
+    }
+}
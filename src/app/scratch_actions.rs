@@ -0,0 +1,292 @@
+//! Scratch buffers.
+//!
+//! This module provides functionality to:
+//! - Create a scratch buffer with a chosen syntax, backed by a file in the
+//!   data directory so it needs no name
+//! - Write modified scratch buffers back to disk periodically, on close and
+//!   on quit, without asking
+//! - List existing scratch buffers to reopen them
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::view::prompt::{Prompt, PromptType};
+use std::path::{Path, PathBuf};
+
+/// Language suggestion for a scratch buffer without highlighting
+const PLAIN_TEXT: &str = "Plain Text";
+
+/// Extension of scratch files whose syntax has none
+const PLAIN_TEXT_EXTENSION: &str = "txt";
+
+/// Longest preview of a scratch buffer's first line in the open prompt
+const PREVIEW_CHARS: usize = 60;
+
+/// File name of scratch buffer `number` with extension `ext`
+fn scratch_file_name(number: usize, ext: &str) -> String {
+    format!("scratch-{}.{}", number, ext)
+}
+
+/// Number of a scratch file name, whatever its extension
+fn scratch_number(file_name: &str) -> Option<usize> {
+    let stem = file_name.split('.').next()?;
+    stem.strip_prefix("scratch-")?.parse().ok()
+}
+
+/// First non-blank line of `content`, shortened for a suggestion description
+fn preview_line(content: &str) -> Option<String> {
+    let line = content.lines().map(str::trim).find(|l| !l.is_empty())?;
+    if line.chars().count() > PREVIEW_CHARS {
+        let short: String = line.chars().take(PREVIEW_CHARS).collect();
+        Some(format!("{}…", short))
+    } else {
+        Some(line.to_string())
+    }
+}
+
+/// Scratch files in `dir`, most recently modified first
+fn list_scratch_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(scratch_number)
+                .is_some()
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((metadata.modified().ok()?, entry.path()))
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+impl Editor {
+    /// Prompt for the language of a new scratch buffer
+    pub fn start_new_scratch_prompt(&mut self) {
+        let mut names: Vec<&str> = self.grammar_registry.available_syntaxes();
+        names.retain(|name| *name != PLAIN_TEXT);
+        names.sort_by_key(|name| name.to_lowercase());
+        names.dedup();
+
+        let suggestions: Vec<Suggestion> = std::iter::once(PLAIN_TEXT)
+            .chain(names)
+            .map(|name| Suggestion {
+                text: name.to_string(),
+                description: None,
+                value: Some(name.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            "Scratch buffer language: ".to_string(),
+            PromptType::NewScratchBuffer,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Create a scratch buffer with the syntax called `language`
+    pub(super) fn new_scratch_buffer(&mut self, language: &str) {
+        let language = language.trim();
+        let language = if language.is_empty() {
+            PLAIN_TEXT
+        } else {
+            language
+        };
+        let override_name = if language == PLAIN_TEXT {
+            None
+        } else {
+            match self.grammar_registry.find_syntax_by_name(language) {
+                Some(syntax) => Some(syntax.name.clone()),
+                None => {
+                    self.set_status_message(format!("Unknown language: {}", language));
+                    return;
+                }
+            }
+        };
+        let ext = override_name
+            .as_deref()
+            .and_then(|name| self.grammar_registry.find_syntax_by_name(name))
+            .and_then(|syntax| syntax.file_extensions.first().cloned())
+            .unwrap_or_else(|| PLAIN_TEXT_EXTENSION.to_string());
+
+        let dir = self.dir_context.scratch_dir();
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            self.set_status_message(format!("Cannot create {}: {}", dir.display(), e));
+            return;
+        }
+        let next = list_scratch_files(&dir)
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str().and_then(scratch_number))
+            .max()
+            .map_or(1, |n| n + 1);
+        let path = dir.join(scratch_file_name(next, &ext));
+        if let Err(e) = std::fs::write(&path, "") {
+            self.set_status_message(format!("Cannot create {}: {}", path.display(), e));
+            return;
+        }
+
+        let buffer_id = match self.open_file(&path) {
+            Ok(id) => id,
+            Err(e) => {
+                self.set_status_message(format!("Cannot open scratch buffer: {}", e));
+                return;
+            }
+        };
+        // The extension may belong to another syntax as well; keep the one chosen
+        if override_name.is_some() {
+            let registry = self.grammar_registry.clone();
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.set_syntax_override(override_name, &registry);
+            }
+        }
+        self.set_status_message(format!("New {} scratch buffer", language));
+    }
+
+    /// Prompt for an existing scratch buffer to reopen
+    pub fn start_open_scratch_prompt(&mut self) {
+        let files = list_scratch_files(&self.dir_context.scratch_dir());
+        if files.is_empty() {
+            self.set_status_message("No scratch buffers".to_string());
+            return;
+        }
+        let suggestions: Vec<Suggestion> = files
+            .iter()
+            .map(|path| Suggestion {
+                text: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                description: std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| preview_line(&content)),
+                value: Some(path.display().to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            "Open scratch buffer: ".to_string(),
+            PromptType::OpenScratchBuffer,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Reopen the scratch buffer at `input`, a path or a scratch file name
+    pub(super) fn open_scratch_buffer(&mut self, input: &str) {
+        let input = input.trim();
+        let path = if Path::new(input).is_absolute() {
+            PathBuf::from(input)
+        } else {
+            self.dir_context.scratch_dir().join(input)
+        };
+        if !path.is_file() {
+            self.set_status_message(format!("No scratch buffer '{}'", input));
+            return;
+        }
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(format!("Cannot open scratch buffer: {}", e));
+        }
+    }
+
+    /// Whether `buffer_id` is a scratch buffer
+    pub(super) fn is_scratch_buffer(&self, buffer_id: BufferId) -> bool {
+        let dir = self.dir_context.scratch_dir();
+        self.buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .is_some_and(|path| path.parent() == Some(dir.as_path()))
+    }
+
+    /// Write `buffer_id` to its scratch file if it is a modified scratch buffer
+    pub(super) fn persist_scratch_buffer(&mut self, buffer_id: BufferId) {
+        if !self.is_scratch_buffer(buffer_id) {
+            return;
+        }
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if !state.buffer.is_modified() {
+            return;
+        }
+        if let Err(e) = state.buffer.save() {
+            tracing::warn!("Failed to save scratch buffer: {}", e);
+            return;
+        }
+        if let Some(path) = state.buffer.file_path().map(|p| p.to_path_buf()) {
+            if let Ok(mtime) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+                self.file_mod_times.insert(path, mtime);
+            }
+        }
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.mark_saved();
+        }
+        let _ = self.delete_buffer_recovery(buffer_id);
+    }
+
+    /// Write every modified scratch buffer to disk
+    ///
+    /// Called every frame; writes at most once per auto-save interval.
+    pub fn persist_scratch_buffers(&mut self) {
+        let interval =
+            std::time::Duration::from_secs(self.config.editor.auto_save_interval_secs as u64);
+        if self.time_source.elapsed_since(self.last_scratch_save) < interval {
+            return;
+        }
+        self.last_scratch_save = self.time_source.now();
+        self.persist_all_scratch_buffers();
+    }
+
+    /// Write every modified scratch buffer to disk now
+    pub(super) fn persist_all_scratch_buffers(&mut self) {
+        let ids: Vec<BufferId> = self.buffers.keys().copied().collect();
+        for buffer_id in ids {
+            self.persist_scratch_buffer(buffer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratch_number() {
+        assert_eq!(scratch_number("scratch-3.md"), Some(3));
+        assert_eq!(scratch_number("scratch-12.tar.gz"), Some(12));
+        assert_eq!(scratch_number("scratch-x.md"), None);
+        assert_eq!(scratch_number("notes.md"), None);
+        assert_eq!(scratch_file_name(4, "rs"), "scratch-4.rs");
+    }
+
+    #[test]
+    fn test_preview_line() {
+        assert_eq!(
+            preview_line("\n  \n  hello  \nworld"),
+            Some("hello".to_string())
+        );
+        assert_eq!(preview_line("   "), None);
+        let long = "x".repeat(PREVIEW_CHARS + 5);
+        assert_eq!(
+            preview_line(&long),
+            Some(format!("{}…", "x".repeat(PREVIEW_CHARS)))
+        );
+    }
+}
@@ -28,17 +28,20 @@ use std::time::Instant;
 
 use crate::state::EditorState;
 
+use crate::model::cursor::{Cursor, Cursors};
 use crate::model::event::{BufferId, SplitDirection, SplitId};
 use crate::services::terminal::TerminalId;
 use crate::session::{
-    FileExplorerState, PersistedFileSession, SearchOptions, SerializedBookmark, SerializedCursor,
-    SerializedFileState, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
+    FileExplorerState, PersistedFileSession, SearchOptions, SerializedBookmark,
+    SerializedBufferState, SerializedCursor, SerializedFileState, SerializedQuickfixEntry,
+    SerializedQuickfixList, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
     SerializedSplitViewState, SerializedTabRef, SerializedTerminalSession, SerializedViewMode,
     Session, SessionConfigOverrides, SessionError, SessionHistories, SESSION_VERSION,
 };
 use crate::state::ViewMode;
 use crate::view::split::{SplitNode, SplitViewState};
 
+use super::quickfix_actions::{QuickfixEntry, QuickfixList};
 use super::types::Bookmark;
 use super::Editor;
 
@@ -48,22 +51,43 @@ use super::Editor;
 pub struct SessionTracker {
     /// Whether session has unsaved changes
     dirty: bool,
+    /// When the layout last changed
+    changed_at: Instant,
     /// Last save time
     last_save: Instant,
-    /// Minimum interval between saves (debounce)
-    save_interval: std::time::Duration,
+    /// Quiet time after a change before saving (debounce)
+    save_delay: std::time::Duration,
+    /// Longest time between saves, so cursor and scroll positions are kept
+    max_interval: std::time::Duration,
     /// Whether session persistence is enabled
     enabled: bool,
+    /// Layout seen on the last check
+    layout: Option<SessionLayout>,
+}
+
+/// The parts of the session that change the window arrangement: the split
+/// tree, the tabs of each split and the file explorer
+#[derive(PartialEq)]
+struct SessionLayout {
+    root: SplitNode,
+    active_split: SplitId,
+    /// Buffers of each split's tabs, with their files: opening a file can
+    /// load it into the empty buffer already open
+    tabs: HashMap<SplitId, Vec<(BufferId, Option<PathBuf>)>>,
+    file_explorer_visible: bool,
 }
 
 impl SessionTracker {
     /// Create a new session tracker
-    pub fn new(enabled: bool) -> Self {
+    pub fn new(enabled: bool, now: Instant) -> Self {
         Self {
             dirty: false,
-            last_save: Instant::now(),
-            save_interval: std::time::Duration::from_secs(5),
+            changed_at: now,
+            last_save: now,
+            save_delay: std::time::Duration::from_secs(2),
+            max_interval: std::time::Duration::from_secs(60),
             enabled,
+            layout: None,
         }
     }
 
@@ -73,21 +97,38 @@ impl SessionTracker {
     }
 
     /// Mark session as needing save
-    pub fn mark_dirty(&mut self) {
+    pub fn mark_dirty(&mut self, now: Instant) {
         if self.enabled {
             self.dirty = true;
+            self.changed_at = now;
         }
     }
 
-    /// Check if a save is needed and enough time has passed
-    pub fn should_save(&self) -> bool {
-        self.enabled && self.dirty && self.last_save.elapsed() >= self.save_interval
+    /// Mark the session dirty if `layout` differs from the last one seen
+    fn observe_layout(&mut self, layout: SessionLayout, now: Instant) {
+        match &self.layout {
+            Some(previous) if *previous == layout => {}
+            Some(_) => {
+                self.mark_dirty(now);
+                self.layout = Some(layout);
+            }
+            // The first layout is the restored (or empty) one
+            None => self.layout = Some(layout),
+        }
+    }
+
+    /// Check if a save is needed: changes have settled, or the last save
+    /// is too old
+    pub fn should_save(&self, now: Instant) -> bool {
+        self.enabled
+            && ((self.dirty && now.saturating_duration_since(self.changed_at) >= self.save_delay)
+                || now.saturating_duration_since(self.last_save) >= self.max_interval)
     }
 
     /// Record that a save was performed
-    pub fn record_save(&mut self) {
+    pub fn record_save(&mut self, now: Instant) {
         self.dirty = false;
-        self.last_save = Instant::now();
+        self.last_save = now;
     }
 
     /// Check if there are unsaved changes (for shutdown)
@@ -217,7 +258,7 @@ impl Editor {
             syntax_highlighting: Some(self.config.editor.syntax_highlighting),
             enable_inlay_hints: Some(self.config.editor.enable_inlay_hints),
             mouse_enabled: Some(self.mouse_enabled),
-            menu_bar_hidden: Some(!self.menu_bar_visible),
+            menu_bar_hidden: Some(!self.menu_bar_preference()),
         };
 
         // Capture histories using the items() accessor
@@ -248,6 +289,16 @@ impl Editor {
         let bookmarks =
             serialize_bookmarks(&self.bookmarks, &self.buffer_metadata, &self.working_dir);
 
+        // Capture per-file extras (language override, read-only)
+        let buffer_states =
+            serialize_buffer_states(&self.buffers, &self.buffer_metadata, &self.working_dir);
+
+        // Capture the quickfix list
+        let quickfix = self
+            .quickfix
+            .as_ref()
+            .map(|list| serialize_quickfix(list, &self.working_dir));
+
         Session {
             version: SESSION_VERSION,
             working_dir: self.working_dir.clone(),
@@ -259,7 +310,11 @@ impl Editor {
             histories,
             search_options,
             bookmarks,
+            buffer_states,
+            language_overrides: HashMap::new(),
+            quickfix,
             terminals,
+            workspace_folders: self.workspace_folders.clone(),
             saved_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -280,7 +335,76 @@ impl Editor {
         self.save_all_global_file_states();
 
         let session = self.capture_session();
-        session.save()
+        session.save()?;
+        self.session_tracker.record_save(self.time_source.now());
+        Ok(())
+    }
+
+    /// Save the session from now on when the layout or tabs change, and
+    /// periodically (see [`Editor::auto_save_session`])
+    pub fn enable_session_auto_save(&mut self) {
+        self.session_tracker = SessionTracker::new(true, self.time_source.now());
+    }
+
+    /// Save the session if the layout or tabs changed and then settled, or
+    /// if it was not saved for a while; called from the event loop
+    pub fn auto_save_session(&mut self) {
+        if !self.session_tracker.is_enabled() {
+            return;
+        }
+        let now = self.time_source.now();
+        let layout = self.session_layout();
+        self.session_tracker.observe_layout(layout, now);
+        if self.session_tracker.should_save(now) {
+            self.auto_save_session_now();
+        }
+    }
+
+    /// Save the session when the terminal loses focus: the user may be
+    /// about to close it
+    pub fn on_terminal_focus_lost(&mut self) {
+        if self.session_tracker.is_enabled() {
+            self.auto_save_session_now();
+        }
+    }
+
+    /// Save the session outside of an explicit request, logging failures
+    fn auto_save_session_now(&mut self) {
+        match self.save_session() {
+            Ok(()) => tracing::debug!("Session auto-saved"),
+            Err(e) => {
+                tracing::warn!("Failed to auto-save session: {}", e);
+                // Retry after the next change or interval, not every tick
+                self.session_tracker.record_save(self.time_source.now());
+            }
+        }
+    }
+
+    /// The window arrangement, compared between event loop ticks
+    fn session_layout(&self) -> SessionLayout {
+        SessionLayout {
+            root: self.split_manager.root().clone(),
+            active_split: self.split_manager.active_split(),
+            tabs: self
+                .split_view_states
+                .iter()
+                .map(|(split_id, view_state)| {
+                    let tabs = view_state
+                        .open_buffers
+                        .iter()
+                        .map(|id| {
+                            let path = self
+                                .buffer_metadata
+                                .get(id)
+                                .and_then(|metadata| metadata.file_path().cloned());
+                            (*id, path)
+                        })
+                        .collect();
+                    (*split_id, tabs)
+                })
+                .collect(),
+            file_explorer_visible: self.file_explorer_visible,
+        }
     }
 
     /// Save global file states for all open file buffers
@@ -324,7 +448,7 @@ impl Editor {
             additional_cursors: view_state
                 .cursors
                 .iter()
-                .skip(1)
+                .filter(|(id, _)| *id != view_state.cursors.primary_id())
                 .map(|(_, cursor)| SerializedCursor {
                     position: cursor.position,
                     anchor: cursor.anchor,
@@ -429,7 +553,7 @@ impl Editor {
             self.mouse_enabled = mouse_enabled;
         }
         if let Some(menu_bar_hidden) = session.config_overrides.menu_bar_hidden {
-            self.menu_bar_visible = !menu_bar_hidden;
+            self.set_menu_bar_preference(!menu_bar_hidden);
         }
 
         // 2. Restore search options
@@ -460,6 +584,14 @@ impl Editor {
         if self.file_explorer_visible && self.file_explorer.is_none() {
             self.init_file_explorer();
         }
+        self.set_workspace_folders(
+            session
+                .workspace_folders
+                .iter()
+                .filter(|folder| folder.is_dir())
+                .cloned()
+                .collect(),
+        );
 
         // 5. Open files from the session and build buffer mappings
         // Collect all unique file paths from split_states (which tracks all open files per split)
@@ -534,17 +666,53 @@ impl Editor {
                 // Verify position is valid
                 if let Some(buffer) = self.buffers.get(&buffer_id) {
                     let pos = bookmark.position.min(buffer.buffer.len());
-                    self.bookmarks.insert(
-                        *key,
-                        Bookmark {
-                            buffer_id,
-                            position: pos,
-                        },
-                    );
+                    self.insert_bookmark(*key, buffer_id, pos);
                 }
             }
         }
 
+        // 8. Restore per-file extras (language override, read-only)
+        for (rel_path, buffer_state) in &session.buffer_states {
+            let Some(&buffer_id) = path_to_buffer.get(rel_path) else {
+                continue;
+            };
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                if let Some(syntax_name) = &buffer_state.language {
+                    state.set_syntax_override(Some(syntax_name.clone()), &self.grammar_registry);
+                }
+                if buffer_state.read_only {
+                    state.editing_disabled = true;
+                }
+            }
+            if buffer_state.read_only {
+                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                    metadata.read_only = true;
+                }
+            }
+        }
+
+        // 9. Restore the quickfix list
+        if let Some(quickfix) = &session.quickfix {
+            let entries: Vec<QuickfixEntry> = quickfix
+                .entries
+                .iter()
+                .map(|entry| QuickfixEntry {
+                    path: self.working_dir.join(&entry.file_path),
+                    line: entry.line,
+                    column: entry.column,
+                    severity: entry.severity,
+                    message: entry.message.clone(),
+                })
+                .collect();
+            let current = quickfix.current.filter(|&i| i < entries.len());
+            self.quickfix = Some(QuickfixList {
+                title: quickfix.title.clone(),
+                entries,
+                current,
+                task_id: None,
+            });
+        }
+
         tracing::debug!(
             "Session restore complete: {} splits, {} buffers",
             self.split_view_states.len(),
@@ -643,7 +811,7 @@ impl Editor {
         }
 
         let large_file_threshold = self.config.editor.large_file_threshold_bytes as usize;
-        if let Ok(new_state) = EditorState::from_file(
+        if let Ok(mut new_state) = EditorState::from_file(
             backing_path,
             self.terminal_width,
             self.terminal_height,
@@ -651,6 +819,9 @@ impl Editor {
             &self.grammar_registry,
         ) {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                new_state
+                    .buffer
+                    .continue_versions_from(state.buffer.version());
                 *state = new_state;
                 // Move cursor to end of buffer
                 let total = state.buffer.total_bytes();
@@ -894,12 +1065,8 @@ impl Editor {
                         let max_pos = buffer.buffer.len();
                         let cursor_pos = file_state.cursor.position.min(max_pos);
 
-                        // Set cursor in SplitViewState
-                        view_state.cursors.primary_mut().position = cursor_pos;
-                        view_state.cursors.primary_mut().anchor =
-                            file_state.cursor.anchor.map(|a| a.min(max_pos));
-                        view_state.cursors.primary_mut().sticky_column =
-                            file_state.cursor.sticky_column;
+                        // Set cursors (and selections) in SplitViewState
+                        view_state.cursors = restore_cursors(file_state, max_pos);
 
                         // Set scroll position
                         view_state.viewport.top_byte = file_state.scroll.top_byte.min(max_pos);
@@ -921,12 +1088,7 @@ impl Editor {
                     // Also set cursor in EditorState (authoritative for cursors)
                     if let Some(editor_state) = self.buffers.get_mut(&active_id) {
                         let max_pos = editor_state.buffer.len();
-                        let cursor_pos = file_state.cursor.position.min(max_pos);
-                        editor_state.cursors.primary_mut().position = cursor_pos;
-                        editor_state.cursors.primary_mut().anchor =
-                            file_state.cursor.anchor.map(|a| a.min(max_pos));
-                        editor_state.cursors.primary_mut().sticky_column =
-                            file_state.cursor.sticky_column;
+                        editor_state.cursors = restore_cursors(file_state, max_pos);
                         // Note: viewport is now exclusively owned by SplitViewState (restored above)
                     }
                     break;
@@ -945,6 +1107,7 @@ impl Editor {
             SerializedViewMode::Compose => ViewMode::Compose,
         };
         view_state.compose_width = split_state.compose_width;
+        view_state.working_dir = split_state.working_dir.clone().filter(|dir| dir.is_dir());
         view_state.tab_scroll_offset = split_state.tab_scroll_offset;
     }
 }
@@ -1106,7 +1269,7 @@ fn serialize_split_view_state(
                             additional_cursors: view_state
                                 .cursors
                                 .iter()
-                                .skip(1) // Skip primary
+                                .filter(|(id, _)| *id != view_state.cursors.primary_id())
                                 .map(|(_, cursor)| SerializedCursor {
                                     position: cursor.position,
                                     anchor: cursor.anchor,
@@ -1137,6 +1300,7 @@ fn serialize_split_view_state(
             ViewMode::Compose => SerializedViewMode::Compose,
         },
         compose_width: view_state.compose_width,
+        working_dir: view_state.working_dir.clone(),
     }
 }
 
@@ -1166,6 +1330,75 @@ fn serialize_bookmarks(
         .collect()
 }
 
+/// The saved primary and secondary cursors of a file, clamped to its length
+fn restore_cursors(file_state: &SerializedFileState, max_pos: usize) -> Cursors {
+    let restore = |saved: &SerializedCursor| {
+        let mut cursor = Cursor::new(saved.position.min(max_pos));
+        cursor.anchor = saved.anchor.map(|a| a.min(max_pos));
+        cursor.sticky_column = saved.sticky_column;
+        cursor
+    };
+    let mut cursors = Cursors::new();
+    // The last cursor added becomes the primary one
+    let mut saved = file_state
+        .additional_cursors
+        .iter()
+        .chain(std::iter::once(&file_state.cursor));
+    if let Some(first) = saved.next() {
+        *cursors.primary_mut() = restore(first);
+    }
+    for cursor in saved {
+        cursors.add(restore(cursor));
+    }
+    cursors.normalize();
+    cursors
+}
+
+fn serialize_buffer_states(
+    buffers: &HashMap<BufferId, EditorState>,
+    buffer_metadata: &HashMap<BufferId, super::types::BufferMetadata>,
+    working_dir: &Path,
+) -> HashMap<PathBuf, SerializedBufferState> {
+    buffers
+        .iter()
+        .filter_map(|(buffer_id, state)| {
+            let metadata = buffer_metadata.get(buffer_id)?;
+            let buffer_state = SerializedBufferState {
+                language: state.syntax_override.clone(),
+                // Binary files are read-only whenever they are opened
+                read_only: metadata.read_only && !metadata.binary,
+            };
+            if buffer_state == SerializedBufferState::default() {
+                return None;
+            }
+            let rel_path = metadata.file_path()?.strip_prefix(working_dir).ok()?;
+            Some((rel_path.to_path_buf(), buffer_state))
+        })
+        .collect()
+}
+
+fn serialize_quickfix(list: &QuickfixList, working_dir: &Path) -> SerializedQuickfixList {
+    SerializedQuickfixList {
+        title: list.title.clone(),
+        entries: list
+            .entries
+            .iter()
+            .map(|entry| SerializedQuickfixEntry {
+                file_path: entry
+                    .path
+                    .strip_prefix(working_dir)
+                    .unwrap_or(&entry.path)
+                    .to_path_buf(),
+                line: entry.line,
+                column: entry.column,
+                severity: entry.severity,
+                message: entry.message.clone(),
+            })
+            .collect(),
+        current: list.current,
+    }
+}
+
 /// Collect all unique file paths from split_states
 fn collect_file_paths_from_states(
     split_states: &HashMap<usize, SerializedSplitViewState>,
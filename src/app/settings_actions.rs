@@ -21,6 +21,11 @@ impl Editor {
         if self.settings_state.is_none() {
             match crate::view::settings::SettingsState::new(SCHEMA_JSON, &self.config) {
                 Ok(mut state) => {
+                    for (namespace, settings) in &self.plugin_settings_schemas {
+                        if let Err(e) = state.add_plugin_settings(namespace, settings) {
+                            tracing::warn!("Invalid settings of plugin '{}': {}", namespace, e);
+                        }
+                    }
                     state.show();
                     self.settings_state = Some(state);
                 }
@@ -83,6 +88,10 @@ impl Editor {
         // Update keybindings
         self.keybindings = KeybindingResolver::new(&self.config);
 
+        if let Ok(mut service) = self.ignore_service.write() {
+            service.set_global_patterns(&self.config.ignore_patterns);
+        }
+
         // Save to disk
         if let Err(e) = std::fs::create_dir_all(&self.dir_context.config_dir) {
             self.set_status_message(format!("Failed to create config directory: {}", e));
@@ -93,11 +102,8 @@ impl Editor {
         match self.config.save_to_file(&config_path) {
             Ok(()) => {
                 self.set_status_message("Settings saved".to_string());
-                // Clear pending changes and hide
-                if let Some(ref mut state) = self.settings_state {
-                    state.discard_changes();
-                    state.hide();
-                }
+                // Drop the modal so it is rebuilt from the saved config when reopened
+                self.settings_state = None;
             }
             Err(e) => {
                 self.set_status_message(format!("Failed to save settings: {}", e));
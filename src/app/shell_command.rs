@@ -247,7 +247,7 @@ impl Editor {
 }
 
 /// Detect the shell to use for executing commands.
-fn detect_shell() -> String {
+pub(super) fn detect_shell() -> String {
     // Try SHELL environment variable first
     if let Ok(shell) = std::env::var("SHELL") {
         if !shell.is_empty() {
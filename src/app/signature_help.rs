@@ -0,0 +1,303 @@
+//! Signature help that follows the cursor through a call.
+//!
+//! Typing "(" or "," requests signature help. While its popup is up, every
+//! key press resolves the call around the cursor again: inside the same
+//! call the popup stays and is re-requested when the cursor moved, so the
+//! highlighted parameter follows the argument being typed. Leaving the call,
+//! switching buffers or pressing Esc closes it.
+
+use lsp_types::{Documentation, ParameterInformation, ParameterLabel, SignatureHelp};
+use ratatui::style::{Modifier, Style};
+use std::ops::Range;
+
+use crate::model::event::BufferId;
+use crate::view::popup::{Popup, StyledLine};
+
+use super::Editor;
+
+/// Title of the signature help popup
+pub(super) const SIGNATURE_HELP_TITLE: &str = "Signature Help";
+
+/// How far back to look for the open paren of the call around the cursor
+const MAX_CALL_SCAN: usize = 4096;
+
+/// The call signature help is being shown for
+pub(super) struct SignatureHelpCall {
+    buffer_id: BufferId,
+    /// Byte offset of the call's open paren
+    open_paren: usize,
+    /// Cursor position signature help was last requested for
+    cursor: usize,
+    /// The popup, while it is hidden so a key press reaches the buffer
+    hidden_popup: Option<Popup>,
+}
+
+impl Editor {
+    /// Start tracking the call around the cursor after a signature help
+    /// response, returning false when the cursor is not inside a call
+    pub(super) fn track_signature_help_call(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let cursor = self.active_state().cursors.primary().position;
+        self.signature_help_call =
+            self.enclosing_call_start(cursor)
+                .map(|open_paren| SignatureHelpCall {
+                    buffer_id,
+                    open_paren,
+                    cursor,
+                    hidden_popup: None,
+                });
+        self.signature_help_call.is_some()
+    }
+
+    /// Hide the signature help popup, returning false if it's not on top
+    ///
+    /// Unless `dismiss` is set, the popup is kept to be shown again after
+    /// the key press being handled if the cursor is still inside the call.
+    pub(super) fn hide_signature_help_popup(&mut self, dismiss: bool) -> bool {
+        let is_signature_help = self
+            .active_state()
+            .popups
+            .top()
+            .is_some_and(|popup| popup.title.as_deref() == Some(SIGNATURE_HELP_TITLE));
+        if !is_signature_help {
+            return false;
+        }
+        let popup = self.active_state_mut().popups.hide();
+        match self.signature_help_call.as_mut() {
+            Some(call) if !dismiss => call.hidden_popup = popup,
+            _ => self.signature_help_call = None,
+        }
+        true
+    }
+
+    /// Record that signature help was requested with the cursor at `cursor`
+    pub(super) fn signature_help_requested(&mut self, cursor: usize) {
+        if let Some(call) = self.signature_help_call.as_mut() {
+            call.cursor = cursor;
+        }
+    }
+
+    /// Resolve the call around the cursor again after a key press
+    pub(super) fn update_signature_help(&mut self) {
+        let Some(call) = self.signature_help_call.as_mut() else {
+            return;
+        };
+        let hidden_popup = call.hidden_popup.take();
+        let (buffer_id, open_paren, last_cursor) = (call.buffer_id, call.open_paren, call.cursor);
+
+        if buffer_id != self.active_buffer() {
+            self.signature_help_call = None;
+            return;
+        }
+        let cursor = self.active_state().cursors.primary().position;
+        if self.enclosing_call_start(cursor) != Some(open_paren) {
+            self.signature_help_call = None;
+            return;
+        }
+
+        if let Some(popup) = hidden_popup {
+            let popups = &mut self.active_state_mut().popups;
+            if !popups.is_visible() {
+                popups.show(popup);
+            }
+        }
+        if cursor != last_cursor {
+            let _ = self.request_signature_help();
+        }
+    }
+
+    /// Byte offset of the open paren of the call around `cursor`
+    fn enclosing_call_start(&mut self, cursor: usize) -> Option<usize> {
+        let start = cursor.saturating_sub(MAX_CALL_SCAN);
+        let text = self
+            .active_state_mut()
+            .buffer
+            .get_text_range_mut(start, cursor - start)
+            .ok()?;
+        enclosing_call_start(&text).map(|offset| start + offset)
+    }
+}
+
+/// Offset of the unmatched "(" closest to the end of `text`
+fn enclosing_call_start(text: &[u8]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, &byte) in text.iter().enumerate().rev() {
+        match byte {
+            b')' => depth += 1,
+            b'(' if depth == 0 => return Some(i),
+            b'(' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Popup lines for the active signature, with the active parameter in bold
+pub(super) fn signature_help_lines(help: &SignatureHelp) -> Option<Vec<StyledLine>> {
+    let last = help.signatures.len().checked_sub(1)?;
+    let active = (help.active_signature.unwrap_or(0) as usize).min(last);
+    let signature = help.signatures.get(active)?;
+    let label = &signature.label;
+
+    let active_param = help
+        .active_parameter
+        .or(signature.active_parameter)
+        .unwrap_or(0) as usize;
+    let param = signature
+        .parameters
+        .as_ref()
+        .and_then(|params| params.get(active_param));
+    let param_range = param.and_then(|param| parameter_range(label, param));
+
+    let mut lines = Vec::new();
+    let mut signature_line = StyledLine::new();
+    match param_range {
+        Some(range) => {
+            signature_line.push(label[..range.start].to_string(), Style::default());
+            signature_line.push(
+                label[range.clone()].to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            );
+            signature_line.push(label[range.end..].to_string(), Style::default());
+        }
+        None => signature_line.push(label.clone(), Style::default()),
+    }
+    if help.signatures.len() > 1 {
+        signature_line.push(
+            format!("  ({}/{})", active + 1, help.signatures.len()),
+            Style::default().add_modifier(Modifier::DIM),
+        );
+    }
+    lines.push(signature_line);
+
+    let docs = [
+        param.and_then(|param| param.documentation.as_ref()),
+        signature.documentation.as_ref(),
+    ];
+    for doc in docs.into_iter().flatten() {
+        let text = match doc {
+            Documentation::String(s) => s,
+            Documentation::MarkupContent(m) => &m.value,
+        };
+        if text.is_empty() {
+            continue;
+        }
+        lines.push(StyledLine::new());
+        for doc_line in text.lines() {
+            let mut line = StyledLine::new();
+            line.push(doc_line.to_string(), Style::default());
+            lines.push(line);
+        }
+    }
+    Some(lines)
+}
+
+/// Byte range of `param` in the signature `label`
+fn parameter_range(label: &str, param: &ParameterInformation) -> Option<Range<usize>> {
+    match &param.label {
+        ParameterLabel::Simple(name) => {
+            // Look after the open paren so the function name can't match
+            let from = label.find('(').unwrap_or(0);
+            let start = from + label[from..].find(name.as_str())?;
+            Some(start..start + name.len())
+        }
+        // Offsets are in UTF-16 code units
+        ParameterLabel::LabelOffsets([start, end]) => {
+            let byte_offset = |utf16: u32| {
+                let mut units = 0;
+                for (i, c) in label.char_indices() {
+                    if units >= utf16 as usize {
+                        return Some(i);
+                    }
+                    units += c.len_utf16();
+                }
+                (units == utf16 as usize).then_some(label.len())
+            };
+            let range = byte_offset(*start)?..byte_offset(*end)?;
+            (range.start <= range.end).then_some(range)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::SignatureInformation;
+
+    fn text(line: &StyledLine) -> Vec<(&str, bool)> {
+        line.spans
+            .iter()
+            .map(|span| {
+                (
+                    span.text.as_str(),
+                    span.style.add_modifier.contains(Modifier::BOLD),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_enclosing_call_start() {
+        assert_eq!(enclosing_call_start(b"foo(a, "), Some(3));
+        assert_eq!(enclosing_call_start(b"foo(a, bar(b), "), Some(3));
+        assert_eq!(enclosing_call_start(b"foo(a, bar(b"), Some(10));
+        assert_eq!(enclosing_call_start(b"foo(a)"), None);
+        assert_eq!(enclosing_call_start(b"let x = 1;"), None);
+    }
+
+    #[test]
+    fn test_active_parameter_bold() {
+        let parameter = |label| ParameterInformation {
+            label,
+            documentation: None,
+        };
+        let help = SignatureHelp {
+            signatures: vec![
+                SignatureInformation {
+                    label: "add(a: i32)".to_string(),
+                    documentation: None,
+                    parameters: None,
+                    active_parameter: None,
+                },
+                SignatureInformation {
+                    label: "add(a: i32, b: i32)".to_string(),
+                    documentation: Some(Documentation::String("Adds".to_string())),
+                    parameters: Some(vec![
+                        parameter(ParameterLabel::Simple("a: i32".to_string())),
+                        parameter(ParameterLabel::LabelOffsets([12, 18])),
+                    ]),
+                    active_parameter: None,
+                },
+            ],
+            active_signature: Some(1),
+            active_parameter: Some(1),
+        };
+
+        let lines = signature_help_lines(&help).unwrap();
+        assert_eq!(
+            text(&lines[0]),
+            vec![
+                ("add(a: i32, ", false),
+                ("b: i32", true),
+                (")", false),
+                ("  (2/2)", false)
+            ]
+        );
+        assert_eq!(text(&lines[2]), vec![("Adds", false)]);
+    }
+
+    #[test]
+    fn test_parameter_range_utf16() {
+        // "é" is one UTF-16 unit but two bytes
+        let param = ParameterInformation {
+            label: ParameterLabel::LabelOffsets([6, 7]),
+            documentation: None,
+        };
+        assert_eq!(parameter_range("fé(x, y)", &param), Some(7..8));
+        let param = ParameterInformation {
+            label: ParameterLabel::Simple("f".to_string()),
+            documentation: None,
+        };
+        assert_eq!(parameter_range("f(f: u8)", &param), Some(2..3));
+    }
+}
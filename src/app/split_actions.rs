@@ -6,9 +6,11 @@
 //! - Navigating between splits
 //! - Managing per-split view states (cursors, viewport)
 //! - Split size adjustment and maximize
+//! - Per-split working directories
 
 use crate::model::event::{Event, SplitDirection, SplitId};
 use crate::view::split::SplitViewState;
+use std::path::PathBuf;
 
 use super::Editor;
 
@@ -20,6 +22,8 @@ impl Editor {
 
         // Share the current buffer with the new split (Emacs-style)
         let current_buffer_id = self.active_buffer();
+        // The new split starts in the same working directory
+        let working_dir = self.split_own_working_dir(self.split_manager.active_split());
 
         // Split the pane
         match self.split_manager.split_active(
@@ -35,6 +39,7 @@ impl Editor {
                     current_buffer_id,
                 );
                 view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                view_state.working_dir = working_dir.clone();
                 self.split_view_states.insert(new_split_id, view_state);
                 // Restore the new split's view state to the buffer
                 self.restore_current_split_view_state();
@@ -53,6 +58,8 @@ impl Editor {
 
         // Share the current buffer with the new split (Emacs-style)
         let current_buffer_id = self.active_buffer();
+        // The new split starts in the same working directory
+        let working_dir = self.split_own_working_dir(self.split_manager.active_split());
 
         // Split the pane
         match self.split_manager.split_active(
@@ -68,6 +75,7 @@ impl Editor {
                     current_buffer_id,
                 );
                 view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                view_state.working_dir = working_dir.clone();
                 self.split_view_states.insert(new_split_id, view_state);
                 // Restore the new split's view state to the buffer
                 self.restore_current_split_view_state();
@@ -289,4 +297,64 @@ impl Editor {
             }
         }
     }
+
+    /// The working directory of `split_id`: its own, or the project's
+    pub fn split_working_dir(&self, split_id: SplitId) -> PathBuf {
+        self.split_own_working_dir(split_id)
+            .unwrap_or_else(|| self.working_dir.clone())
+    }
+
+    /// The working directory of the active split
+    pub fn active_split_working_dir(&self) -> PathBuf {
+        self.split_working_dir(self.split_manager.active_split())
+    }
+
+    fn split_own_working_dir(&self, split_id: SplitId) -> Option<PathBuf> {
+        self.split_view_states
+            .get(&split_id)
+            .and_then(|vs| vs.working_dir.clone())
+    }
+
+    /// Give the active split the folder of its buffer as working directory
+    pub fn set_split_working_dir_from_buffer(&mut self) {
+        let Some(dir) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.to_path_buf())
+        else {
+            self.set_status_message("Buffer has no folder to use".to_string());
+            return;
+        };
+        let split_id = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+            view_state.working_dir = Some(dir.clone());
+        }
+        self.set_status_message(format!("Split working directory: {}", dir.display()));
+    }
+
+    /// Return the active split to the project's working directory
+    pub fn clear_split_working_dir(&mut self) {
+        let split_id = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+            view_state.working_dir = None;
+        }
+        self.set_status_message(format!(
+            "Split working directory: {} (project)",
+            self.working_dir.display()
+        ));
+    }
+
+    /// Label for the active split's own working directory in the status bar,
+    /// relative to the project when inside it
+    pub(crate) fn split_working_dir_label(&self) -> Option<String> {
+        let dir = self.split_own_working_dir(self.split_manager.active_split())?;
+        let label = match dir.strip_prefix(&self.working_dir) {
+            Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+            Ok(rel) => rel.display().to_string(),
+            Err(_) => dir.display().to_string(),
+        };
+        Some(label)
+    }
 }
@@ -0,0 +1,112 @@
+//! Remote file editing over SSH.
+//!
+//! This module provides functionality to:
+//! - Fetch `ssh://` and `sftp://` files in the background, showing progress
+//! - Open the local copy as a buffer named after the URL
+//! - Write the copy back to the host whenever the buffer is saved
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::ssh::{self, SshUrl};
+use std::path::PathBuf;
+
+impl Editor {
+    /// Fetch a remote file on a background thread and open it once it
+    /// arrives (`AsyncMessage::SshFetched`)
+    pub fn open_ssh_url(&mut self, url: SshUrl) {
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+
+        self.set_status_message(format!("Fetching {}...", url));
+        std::thread::spawn(move || {
+            let mut last_percent = None;
+            let result = ssh::fetch(&url, |received, total| {
+                // One update per percent (or per chunk while the size is unknown)
+                let percent = total.filter(|&t| t > 0).map(|t| received * 100 / t);
+                if percent.is_none() || percent != last_percent {
+                    last_percent = percent;
+                    let _ = sender.send(AsyncMessage::SshProgress {
+                        url: url.clone(),
+                        received,
+                        total,
+                    });
+                }
+            });
+            let _ = sender.send(AsyncMessage::SshFetched {
+                url,
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Show the progress of a remote transfer
+    pub(super) fn handle_ssh_progress(&mut self, url: SshUrl, received: u64, total: Option<u64>) {
+        let progress = match total {
+            Some(total) if total > 0 => {
+                format!("{}% of {}", received * 100 / total, format_size(total))
+            }
+            _ => format_size(received),
+        };
+        self.set_status_message(format!("Fetching {}... {}", url, progress));
+    }
+
+    /// Open a fetched remote file
+    pub(super) fn handle_ssh_fetched(&mut self, url: SshUrl, result: Result<PathBuf, String>) {
+        let path = match result {
+            Ok(path) => path,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
+        match self.open_file(&path) {
+            Ok(buffer_id) => {
+                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                    metadata.display_name = url.to_string();
+                }
+                self.set_status_message(format!("Opened {}", url));
+                self.ssh_buffers.insert(buffer_id, url);
+            }
+            Err(e) => self.set_status_message(format!("Error opening {}: {}", url, e)),
+        }
+    }
+
+    /// Write a saved remote buffer back to its host in the background
+    pub(super) fn upload_ssh_buffer(&mut self, buffer_id: BufferId) {
+        let Some(url) = self.ssh_buffers.get(&buffer_id).cloned() else {
+            return;
+        };
+        // "Save As" to a local path detaches the buffer from the host
+        let cache_path = url.cache_path();
+        let saved_path = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path());
+        if saved_path != Some(cache_path.as_path()) {
+            self.ssh_buffers.remove(&buffer_id);
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+
+        self.set_status_message(format!("Writing {}...", url));
+        std::thread::spawn(move || {
+            let result = ssh::upload(&url, &cache_path).map_err(|e| e.to_string());
+            let _ = sender.send(AsyncMessage::SshUploaded { url, result });
+        });
+    }
+
+    /// Report the result of writing a remote file
+    pub(super) fn handle_ssh_uploaded(&mut self, url: SshUrl, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.set_status_message(format!("Saved {}", url)),
+            Err(e) => self.set_status_message(e),
+        }
+    }
+}
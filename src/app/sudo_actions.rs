@@ -0,0 +1,82 @@
+//! Saving files that need root.
+//!
+//! This module provides functionality to:
+//! - Offer to retry as root when a save is denied
+//! - Ask for the sudo password when the helper needs one
+//! - Write the buffer through the helper and finish the save as usual
+
+use super::Editor;
+use crate::services::sudo::Elevation;
+use crate::view::prompt::PromptType;
+use std::io;
+
+impl Editor {
+    /// Save the active buffer, offering to retry as root when the file is
+    /// not writable
+    pub fn save_or_offer_root(&mut self) -> io::Result<()> {
+        match self.save() {
+            Err(e) if cfg!(unix) && e.kind() == io::ErrorKind::PermissionDenied => {
+                let path = self
+                    .active_state()
+                    .buffer
+                    .file_path()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                self.start_prompt(
+                    format!("Permission denied writing {}. Retry as root? (y/N) ", path),
+                    PromptType::ConfirmSaveAsRoot,
+                );
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Handle the answer to the "Retry as root?" prompt
+    pub(super) fn handle_confirm_save_as_root(&mut self, input: &str) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower != "y" && input_lower != "yes" {
+            self.set_status_message("Save cancelled".to_string());
+            return;
+        }
+        let elevation = Elevation::detect();
+        if elevation.needs_password() {
+            self.start_prompt("[sudo] password: ".to_string(), PromptType::SudoPassword);
+        } else {
+            self.save_as_root(&elevation, None);
+        }
+    }
+
+    /// Handle the password entered for saving as root
+    pub(super) fn handle_sudo_password(&mut self, password: String) {
+        self.save_as_root(&Elevation::detect(), Some(&password));
+    }
+
+    /// Write the active buffer to its file through the privilege helper
+    fn save_as_root(&mut self, elevation: &Elevation, password: Option<&str>) {
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf())
+        else {
+            return;
+        };
+        let total = self.active_state().buffer.total_bytes();
+        let content = match self.active_state_mut().buffer.get_text_range_mut(0, total) {
+            Ok(content) => content,
+            Err(e) => {
+                self.set_status_message(format!("Failed to save: {}", e));
+                return;
+            }
+        };
+        match elevation.write(&path, &content, password) {
+            Ok(()) => {
+                self.active_state_mut().buffer.mark_written_externally();
+                self.set_status_message(format!("Saved {} as root", path.display()));
+                self.finish_save(self.active_buffer(), Some(path));
+            }
+            Err(e) => self.set_status_message(format!("Failed to save as root: {}", e)),
+        }
+    }
+}
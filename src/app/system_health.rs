@@ -0,0 +1,254 @@
+//! Health of the editor's background services.
+//!
+//! This module provides functionality to:
+//! - Summarize running jobs, language servers that went down, plugins that
+//!   failed and file watcher problems in a status bar segment, shown only
+//!   while there is something to report
+//! - List every service and its state in the System Health popup (the Show
+//!   System Health command, or a click on the segment), with an action to
+//!   restart or stop each
+//!
+//! The services keep their own state (`LspManager`, `PluginHealth`, the task
+//! runner and the file poll statistics); this only reads it and calls their
+//! existing restart paths.
+
+use std::time::Duration;
+
+use super::plugin_health::describe_state;
+use super::Editor;
+use crate::model::event::{PopupContentData, PopupData, PopupListItemData, PopupPositionData};
+use crate::services::async_bridge::LspServerStatus;
+use crate::services::plugins::health::PluginState;
+
+/// Title of the popup, which tells its confirmations apart
+pub(super) const SYSTEM_HEALTH_TITLE: &str = "System Health";
+
+/// "1 job", "2 jobs"
+fn count(n: usize, what: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, what)
+    } else {
+        format!("{} {}s", n, what)
+    }
+}
+
+/// A popup line, selecting which runs `action`
+fn item(text: String, detail: Option<&str>, action: Option<String>) -> PopupListItemData {
+    PopupListItemData {
+        text,
+        detail: detail.map(str::to_string),
+        icon: None,
+        data: action,
+    }
+}
+
+impl Editor {
+    /// Whether the LSP server for `language` went down without the user
+    /// stopping it
+    fn is_lsp_down(&self, language: &str, status: LspServerStatus) -> bool {
+        status == LspServerStatus::Error
+            || self.lsp.as_ref().is_some_and(|lsp| {
+                lsp.is_in_cooldown(language) || lsp.has_pending_restart(language)
+            })
+    }
+
+    /// Short summary for the status bar; empty when no job is running and
+    /// every service is healthy
+    pub(super) fn health_summary(&self) -> String {
+        let mut parts = Vec::new();
+
+        let jobs = usize::from(self.running_task_name().is_some()) + self.lsp_progress.len();
+        if jobs > 0 {
+            parts.push(count(jobs, "job"));
+        }
+
+        let lsp_down = self
+            .lsp_server_statuses
+            .iter()
+            .filter(|(language, status)| self.is_lsp_down(language, **status))
+            .count();
+        if lsp_down > 0 {
+            parts.push(format!("{} LSP down", lsp_down));
+        }
+
+        if *self.plugin_health.host_state() != PluginState::Running {
+            parts.push("plugins stopped".to_string());
+        } else {
+            let failed = self
+                .plugin_health
+                .states()
+                .filter(|(_, state)| **state != PluginState::Running)
+                .count();
+            if failed > 0 {
+                parts.push(format!("{} failed", count(failed, "plugin")));
+            }
+        }
+
+        let editor_config = &self.config.editor;
+        let file_interval = Duration::from_millis(editor_config.auto_revert_poll_interval_ms);
+        let tree_interval = Duration::from_millis(editor_config.file_tree_poll_interval_ms);
+        if self.auto_revert_disabled_by.is_some() {
+            parts.push("auto-revert off".to_string());
+        } else if self.file_poll_stats.is_slow(file_interval)
+            || self.tree_poll_stats.is_slow(tree_interval)
+        {
+            parts.push("watcher slow".to_string());
+        } else if self.file_poll_stats.unreadable_count() > 0 {
+            parts.push(format!(
+                "{} unreadable",
+                count(self.file_poll_stats.unreadable_count(), "file")
+            ));
+        }
+
+        parts.join(", ")
+    }
+
+    /// Show every background service, its state and what can be done about
+    /// it in a popup
+    pub(super) fn show_system_health(&mut self) {
+        let now = self.time_source.now();
+        let mut items = Vec::new();
+
+        if let Some(name) = self.running_task_name() {
+            items.push(item(
+                format!("Task '{}': running", name),
+                Some("Stop it"),
+                Some("stop_task".to_string()),
+            ));
+        }
+        let mut progress: Vec<_> = self.lsp_progress.values().collect();
+        progress.sort_by(|a, b| (&a.language, &a.title).cmp(&(&b.language, &b.title)));
+        for info in progress {
+            let mut text = format!("LSP {}: {}", info.language, info.title);
+            if let Some(pct) = info.percentage {
+                text.push_str(&format!(" ({}%)", pct));
+            }
+            items.push(item(text, None, None));
+        }
+
+        let mut statuses: Vec<(String, LspServerStatus)> = self
+            .lsp_server_statuses
+            .iter()
+            .map(|(language, status)| (language.clone(), *status))
+            .collect();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        for (language, status) in statuses {
+            let state = if self.is_lsp_down(&language, status) {
+                "down"
+            } else {
+                match status {
+                    LspServerStatus::Starting => "starting",
+                    LspServerStatus::Initializing => "initializing",
+                    LspServerStatus::Running => "ready",
+                    LspServerStatus::Error => "error",
+                    LspServerStatus::Shutdown => "stopped",
+                }
+            };
+            items.push(item(
+                format!("LSP {}: {}", language, state),
+                Some("Restart it"),
+                Some(format!("restart_lsp:{}", language)),
+            ));
+        }
+
+        let host_state = self.plugin_health.host_state();
+        if *host_state != PluginState::Running {
+            items.push(item(
+                format!("Plugins: {}", describe_state(host_state, now)),
+                Some("Restart the plugin host"),
+                Some("restart_plugin_host".to_string()),
+            ));
+        }
+        let failed: Vec<(String, String)> = self
+            .plugin_health
+            .states()
+            .filter(|(_, state)| **state != PluginState::Running)
+            .map(|(name, state)| (name.to_string(), describe_state(state, now)))
+            .collect();
+        if *host_state == PluginState::Running && failed.is_empty() {
+            items.push(item(
+                format!(
+                    "Plugins: {} running",
+                    self.plugin_manager.loaded_plugins().len()
+                ),
+                None,
+                None,
+            ));
+        }
+        for (name, state) in failed {
+            items.push(item(
+                format!("Plugin {}: {}", name, state),
+                Some("Restart it"),
+                Some(format!("restart_plugin:{}", name)),
+            ));
+        }
+
+        if self.auto_revert_enabled {
+            items.push(item(
+                format!("File watcher: {}", self.health_watcher_state()),
+                Some("Show details"),
+                Some("file_watcher_status".to_string()),
+            ));
+        } else {
+            let reason = match &self.auto_revert_disabled_by {
+                Some(path) => format!(" ({} changed too often)", path.display()),
+                None => String::new(),
+            };
+            items.push(item(
+                format!("File watcher: auto-revert off{}", reason),
+                Some("Turn auto-revert on"),
+                Some("enable_auto_revert".to_string()),
+            ));
+        }
+
+        let height = items.len() as u16 + 2;
+        self.show_popup(PopupData {
+            title: Some(SYSTEM_HEALTH_TITLE.to_string()),
+            transient: false,
+            content: PopupContentData::List { items, selected: 0 },
+            position: PopupPositionData::Centered,
+            width: 60,
+            max_height: height.min(20),
+            bordered: true,
+        });
+    }
+
+    /// How the file watcher is doing, for the popup
+    fn health_watcher_state(&self) -> String {
+        let configured = Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        let unreadable = self.file_poll_stats.unreadable_count();
+        if self.file_poll_stats.is_slow(configured) {
+            "slow, polling less often".to_string()
+        } else if unreadable > 0 {
+            format!("{} can't be read", count(unreadable, "file"))
+        } else {
+            "ok".to_string()
+        }
+    }
+
+    /// Run the action of the System Health popup line the user confirmed
+    pub(super) fn handle_system_health_action(&mut self, action: &str) {
+        let now = self.time_source.now();
+        match action.split_once(':') {
+            Some(("restart_lsp", language)) => self.restart_lsp_server(language),
+            Some(("restart_plugin", plugin)) => {
+                self.plugin_health.restart_now(Some(plugin), now);
+                self.process_plugin_restarts();
+            }
+            _ => match action {
+                "stop_task" => self.stop_task(),
+                "restart_plugin_host" => {
+                    self.plugin_health.restart_now(None, now);
+                    self.process_plugin_restarts();
+                }
+                "enable_auto_revert" => {
+                    if !self.auto_revert_enabled {
+                        self.toggle_auto_revert();
+                    }
+                }
+                "file_watcher_status" => self.show_file_watcher_status(),
+                _ => tracing::warn!("Unknown system health action: {}", action),
+            },
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Tab bar overflow.
+//!
+//! When a split has more tabs than fit, its tab bar scrolls to keep the
+//! active tab in view and shows an overflow button ("⋯") at its right end.
+//! This module provides functionality to:
+//! - Open a dropdown from that button listing the tabs that are scrolled out
+//!   of view or cut off, and switch the split to the one picked
+//! - Cycle through a split's tabs with the mouse wheel over its tab bar
+
+use super::Editor;
+use crate::model::event::{BufferId, SplitId};
+use crate::view::controls::{DropdownEvent, DropdownLayout, DropdownState, FocusState};
+use crate::view::ui::tabs::TabsRenderer;
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+/// Open list of a split's hidden tabs
+pub(crate) struct TabOverflowMenu {
+    split_id: SplitId,
+    /// Buffers in the order of the dropdown's options
+    buffers: Vec<BufferId>,
+    dropdown: DropdownState,
+    /// Overflow button and list as last rendered, for hit testing
+    layout: DropdownLayout,
+}
+
+impl Editor {
+    /// Open or close the overflow dropdown when a click lands on a tab bar's
+    /// overflow button. Returns true if it did.
+    pub(super) fn handle_tab_overflow_click(&mut self, col: u16, row: u16) -> bool {
+        let Some((split_id, button, hidden)) = self.cached_layout.tab_bars.iter().find_map(|bar| {
+            let (start, end) = bar.overflow_button?;
+            (row == bar.rect.y && col >= start && col < end).then(|| {
+                (
+                    bar.split_id,
+                    Rect::new(start, bar.rect.y, end - start, 1),
+                    bar.hidden.clone(),
+                )
+            })
+        }) else {
+            return false;
+        };
+
+        // A second click on the same button closes the dropdown
+        if self
+            .tab_overflow_menu
+            .take()
+            .is_some_and(|menu| menu.split_id == split_id)
+        {
+            return true;
+        }
+
+        let (buffers, options): (Vec<_>, Vec<_>) = hidden
+            .into_iter()
+            .filter_map(|id| {
+                TabsRenderer::tab_title(id, &self.buffers, &self.buffer_metadata)
+                    .map(|title| (id, title))
+            })
+            .unzip();
+        if buffers.is_empty() {
+            return true;
+        }
+
+        let mut dropdown =
+            DropdownState::new(options, "Hidden tabs").with_focus(FocusState::Focused);
+        dropdown.toggle_open();
+        self.tab_overflow_menu = Some(TabOverflowMenu {
+            split_id,
+            buffers,
+            dropdown,
+            layout: DropdownLayout {
+                button_area: button,
+                ..Default::default()
+            },
+        });
+        true
+    }
+
+    /// Route a key press to the open overflow dropdown. Returns true if the
+    /// dropdown was open, in which case it consumes the key.
+    pub(super) fn handle_tab_overflow_key(&mut self, key: KeyEvent) -> bool {
+        let Some(menu) = self.tab_overflow_menu.as_mut() else {
+            return false;
+        };
+        match menu.dropdown.handle_key(key) {
+            Some(DropdownEvent::Closed) => self.pick_tab_overflow_item(),
+            Some(DropdownEvent::SelectionChanged(_)) => {}
+            // Escape, or any key the dropdown doesn't use, dismisses it
+            _ => self.tab_overflow_menu = None,
+        }
+        true
+    }
+
+    /// Route a mouse event to the open overflow dropdown. Returns true if the
+    /// dropdown consumed it.
+    pub(super) fn handle_tab_overflow_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some(menu) = self.tab_overflow_menu.as_mut() else {
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Moved => {
+                if let Some(index) = menu.layout.option_at(event.column, event.row) {
+                    menu.dropdown.selected = index;
+                }
+                menu.layout.contains(event.column, event.row)
+            }
+            MouseEventKind::Down(_) => {
+                match menu.dropdown.handle_mouse(event, &menu.layout) {
+                    Some(DropdownEvent::SelectionChanged(_)) => self.pick_tab_overflow_item(),
+                    // A click on the button or outside the list dismisses it
+                    _ => self.tab_overflow_menu = None,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Switch the split to the tab selected in the overflow dropdown, and close it
+    fn pick_tab_overflow_item(&mut self) {
+        let Some(menu) = self.tab_overflow_menu.take() else {
+            return;
+        };
+        if let Some(&buffer_id) = menu.buffers.get(menu.dropdown.selected) {
+            if self.buffers.contains_key(&buffer_id) {
+                self.focus_split(menu.split_id, buffer_id);
+            }
+        }
+    }
+
+    /// Render the open overflow dropdown under its button
+    pub(super) fn render_tab_overflow_menu(&mut self, frame: &mut Frame) {
+        let Some(menu) = self.tab_overflow_menu.as_mut() else {
+            return;
+        };
+        // The split was closed, or its tabs fit again
+        let button_shown = self
+            .cached_layout
+            .tab_bars
+            .iter()
+            .any(|bar| bar.split_id == menu.split_id && bar.overflow_button.is_some());
+        if !button_shown {
+            self.tab_overflow_menu = None;
+            return;
+        }
+        menu.layout = TabsRenderer::render_overflow_menu(
+            frame,
+            menu.layout.button_area,
+            &menu.dropdown,
+            &self.theme,
+        );
+    }
+
+    /// Cycle through the tabs of the split whose tab bar is under the mouse
+    /// wheel. Returns true if the wheel was over a tab bar.
+    pub(super) fn handle_tab_bar_scroll(&mut self, col: u16, row: u16, delta: i32) -> bool {
+        let Some(split_id) = self
+            .cached_layout
+            .tab_bars
+            .iter()
+            .find(|bar| row == bar.rect.y && col >= bar.rect.x && col < bar.rect.x + bar.rect.width)
+            .map(|bar| bar.split_id)
+        else {
+            return false;
+        };
+        let Some(buffer_id) = self.split_manager.get_buffer_id(split_id) else {
+            return false;
+        };
+
+        self.tab_overflow_menu = None;
+        self.focus_split(split_id, buffer_id);
+        if delta < 0 {
+            self.prev_buffer();
+        } else {
+            self.next_buffer();
+        }
+        true
+    }
+}
@@ -0,0 +1,240 @@
+//! ctags integration.
+//!
+//! This module provides functionality to:
+//! - Generate the project's tags file in the background ("Tags: Generate"),
+//!   and regenerate it when a file in the project is saved
+//! - Jump to a definition and complete symbols from the tags file when no
+//!   language server is running for the buffer
+
+use super::Editor;
+use crate::primitives::word_navigation::{
+    find_completion_word_start, find_word_end, find_word_start,
+};
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::tags::{self, Tag, TagIndex};
+use crate::view::notification::NotificationLevel;
+use lsp_types::{CompletionItem, CompletionItemKind};
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Most completions offered from the tags file
+const MAX_TAG_COMPLETIONS: usize = 100;
+
+/// The loaded tags file and the ctags runs writing it
+#[derive(Default)]
+pub(crate) struct TagsState {
+    /// Path and modification time of the loaded tags file, and its tags
+    loaded: Option<(PathBuf, SystemTime, TagIndex)>,
+    /// A ctags run is in progress
+    generating: bool,
+    /// Another run was asked for while one was in progress
+    regenerate_again: bool,
+    /// Report the outcome of the current run (it was started by the user)
+    announce: bool,
+}
+
+impl Editor {
+    /// Generate the project's tags file, reporting the outcome
+    pub(super) fn generate_tags(&mut self) {
+        self.start_tags_generation(true);
+    }
+
+    /// Run ctags over the working directory in the background
+    fn start_tags_generation(&mut self, announce: bool) {
+        if self.tags.generating {
+            self.tags.regenerate_again = true;
+            self.tags.announce |= announce;
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            return;
+        };
+        let command = self.config.editor.ctags_command.clone();
+        let root = self.working_dir.clone();
+        if announce {
+            self.set_status_message(format!("Generating tags with '{}'...", command));
+        }
+        self.tags.generating = true;
+        self.tags.announce = announce;
+        std::thread::spawn(move || {
+            let result = tags::generate(&command, &root);
+            let _ = sender.send(AsyncMessage::TagsGenerated { root, result });
+        });
+    }
+
+    /// Handle the end of a background ctags run
+    pub(super) fn handle_tags_generated(&mut self, root: PathBuf, result: Result<PathBuf, String>) {
+        self.tags.generating = false;
+        let announce = std::mem::take(&mut self.tags.announce);
+        match result {
+            Ok(path) => {
+                if announce && root == self.working_dir {
+                    let count = self.tag_index().map_or(0, TagIndex::len);
+                    self.set_status_message(format!(
+                        "Generated {} ({} tags)",
+                        path.display(),
+                        count
+                    ));
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Generating tags failed: {}", e);
+                if announce {
+                    self.set_status_message(e.clone());
+                    self.notify(NotificationLevel::Error, e);
+                }
+            }
+        }
+        if std::mem::take(&mut self.tags.regenerate_again) {
+            self.start_tags_generation(announce);
+        }
+    }
+
+    /// Regenerate the tags file after `path` was saved, if the project has one
+    pub(super) fn regenerate_tags_after_save(&mut self, path: &Path) {
+        if self.config.editor.regenerate_tags_on_save
+            && path.starts_with(&self.working_dir)
+            && tags::find_tags_file(&self.working_dir).is_some()
+        {
+            self.start_tags_generation(false);
+        }
+    }
+
+    /// Tags of the project's tags file, reloaded when the file changed
+    fn tag_index(&mut self) -> Option<&TagIndex> {
+        let path = tags::find_tags_file(&self.working_dir)?;
+        let mtime = std::fs::metadata(&path).ok()?.modified().ok()?;
+        let fresh = matches!(
+            &self.tags.loaded,
+            Some((loaded_path, loaded_mtime, _)) if *loaded_path == path && *loaded_mtime == mtime
+        );
+        if !fresh {
+            match TagIndex::load(&path) {
+                Ok(index) => self.tags.loaded = Some((path, mtime, index)),
+                Err(e) => {
+                    tracing::warn!("Failed to read tags file {}: {}", path.display(), e);
+                    self.tags.loaded = None;
+                }
+            }
+        }
+        self.tags.loaded.as_ref().map(|(_, _, index)| index)
+    }
+
+    /// Jump to the definition of the word under the cursor from the tags file
+    ///
+    /// Returns false if the project has no tags file.
+    pub(super) fn goto_definition_from_tags(&mut self) -> bool {
+        let word = {
+            let cursor_pos = self.active_state().cursors.primary().position;
+            let start = find_word_start(&self.active_state().buffer, cursor_pos);
+            let end = find_word_end(&self.active_state().buffer, cursor_pos);
+            self.active_state_mut().get_text_range(start, end)
+        };
+        let current_path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let Some(index) = self.tag_index() else {
+            return false;
+        };
+        let definitions = index.definitions(&word);
+        let count = definitions.len();
+        // Prefer a definition in the current file
+        let Some(tag): Option<Tag> = definitions
+            .iter()
+            .find(|tag| Some(&tag.path) == current_path.as_ref())
+            .or(definitions.first())
+            .cloned()
+        else {
+            if word.is_empty() {
+                self.set_status_message("No symbol at cursor".to_string());
+            } else {
+                self.set_status_message(format!("No tag for '{}'", word));
+            }
+            return true;
+        };
+
+        let text = std::fs::read_to_string(&tag.path).unwrap_or_default();
+        let line = tags::resolve_line(&tag.address, &text).unwrap_or(1);
+        match self.open_file(&tag.path) {
+            Ok(_) => {
+                self.goto_line_col(line, None);
+                let others = if count > 1 {
+                    format!(" (1 of {})", count)
+                } else {
+                    String::new()
+                };
+                self.set_status_message(format!(
+                    "Jumped to definition of '{}' from tags{}",
+                    word, others
+                ));
+            }
+            Err(e) => self.set_status_message(format!("Error opening file: {e}")),
+        }
+        true
+    }
+
+    /// Offer the tags starting with the word before the cursor as completions
+    ///
+    /// Returns false if there is no word before the cursor or the project
+    /// has no tags file.
+    pub(super) fn complete_from_tags(&mut self) -> io::Result<bool> {
+        let prefix = {
+            let cursor_pos = self.active_state().cursors.primary().position;
+            let start = find_completion_word_start(&self.active_state().buffer, cursor_pos);
+            self.active_state_mut().get_text_range(start, cursor_pos)
+        };
+        if prefix.is_empty() {
+            return Ok(false);
+        }
+        let working_dir = self.working_dir.clone();
+        let Some(index) = self.tag_index() else {
+            return Ok(false);
+        };
+        let mut seen = HashSet::new();
+        let items: Vec<CompletionItem> = index
+            .with_prefix(&prefix)
+            .filter(|tag| seen.insert(tag.name.as_str()))
+            .take(MAX_TAG_COMPLETIONS)
+            .map(|tag| CompletionItem {
+                label: tag.name.clone(),
+                kind: tag.kind.as_deref().and_then(tag_completion_kind),
+                detail: Some(
+                    tag.path
+                        .strip_prefix(&working_dir)
+                        .unwrap_or(&tag.path)
+                        .display()
+                        .to_string(),
+                ),
+                ..Default::default()
+            })
+            .collect();
+        if items.is_empty() {
+            self.set_status_message(format!("No tags start with '{}'", prefix));
+            return Ok(true);
+        }
+
+        // Shown like a language server's answer
+        let request_id = self.next_lsp_request_id;
+        self.next_lsp_request_id += 1;
+        self.pending_completion_request = Some(request_id);
+        self.handle_completion_response(request_id, items)?;
+        Ok(true)
+    }
+}
+
+/// Completion kind of a ctags kind, in its long or one-letter form
+fn tag_completion_kind(kind: &str) -> Option<CompletionItemKind> {
+    Some(match kind {
+        "f" | "function" => CompletionItemKind::FUNCTION,
+        "m" | "method" => CompletionItemKind::METHOD,
+        "c" | "class" => CompletionItemKind::CLASS,
+        "s" | "struct" => CompletionItemKind::STRUCT,
+        "v" | "variable" => CompletionItemKind::VARIABLE,
+        "d" | "macro" | "constant" => CompletionItemKind::CONSTANT,
+        _ => return None,
+    })
+}
@@ -0,0 +1,269 @@
+//! Project task runner.
+//!
+//! This module provides functionality to:
+//! - Choose one of the configured `tasks` and run it in the background
+//! - Stream its output into a read-only `*Task: name*` buffer
+//! - Collect compiler errors and warnings from the output into the quickfix
+//!   list, and jump to them from the output buffer
+
+use super::quickfix_actions::{QuickfixEntry, QuickfixList};
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::tasks::{self, MessageSeverity, OutputParser, RunningTask};
+use crate::view::prompt::{Prompt, PromptType};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::PathBuf;
+
+/// Mode of the task output buffer
+const OUTPUT_MODE: &str = "task-output";
+
+/// The most recent task run and the messages found in its output
+pub(crate) struct TaskRun {
+    id: u64,
+    name: String,
+    buffer_id: BufferId,
+    /// Directory the task ran in; relative paths in its output start here
+    working_dir: PathBuf,
+    /// The process, until it exits
+    process: Option<RunningTask>,
+    parser: OutputParser,
+    /// Compiler messages, with the output buffer line each ends on
+    messages: Vec<(usize, QuickfixEntry)>,
+    /// Lines in the output buffer
+    line_count: usize,
+}
+
+impl Editor {
+    /// Prompt for one of the configured tasks to run
+    pub fn start_run_task_prompt(&mut self) {
+        if self.config.tasks.is_empty() {
+            self.set_status_message(
+                "No tasks configured (add \"tasks\" to the project's config.json)".to_string(),
+            );
+            return;
+        }
+        let suggestions: Vec<Suggestion> = self
+            .config
+            .tasks
+            .iter()
+            .map(|task| Suggestion {
+                text: task.name.clone(),
+                description: Some(task.command.clone()),
+                value: Some(task.name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            "Run task: ".to_string(),
+            PromptType::RunTask,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Run the configured task called `name`
+    pub(super) fn run_task(&mut self, name: &str) {
+        let Some(task) = self.config.tasks.iter().find(|t| t.name == name).cloned() else {
+            self.set_status_message(format!("No task named '{}'", name));
+            return;
+        };
+        if let Some(run) = self.task_run.as_ref().filter(|run| run.process.is_some()) {
+            self.set_status_message(format!(
+                "Task '{}' is still running (use Stop Task first)",
+                run.name
+            ));
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.set_status_message("Async runtime not available".to_string());
+            return;
+        };
+
+        let working_dir = match &task.working_dir {
+            Some(dir) => self.working_dir.join(dir),
+            None => self.active_split_working_dir(),
+        };
+        let id = self.next_task_id;
+        self.next_task_id += 1;
+        let line_sender = sender.clone();
+        let process = match tasks::spawn(
+            &task.command,
+            &working_dir,
+            move |line| {
+                let _ = line_sender.send(AsyncMessage::TaskOutput { task_id: id, line });
+            },
+            move |exit_code| {
+                let _ = sender.send(AsyncMessage::TaskFinished {
+                    task_id: id,
+                    exit_code,
+                });
+            },
+        ) {
+            Ok(process) => process,
+            Err(e) => {
+                self.set_status_message(format!("Failed to run task '{}': {}", task.name, e));
+                return;
+            }
+        };
+
+        // The new run replaces the previous output
+        if let Some(previous) = self.task_run.take() {
+            if self.buffers.contains_key(&previous.buffer_id) {
+                let _ = self.force_close_buffer(previous.buffer_id);
+            }
+        }
+
+        self.mode_registry.register(
+            BufferMode::new(OUTPUT_MODE)
+                .with_parent("special")
+                .with_read_only(true)
+                .with_binding(KeyCode::Enter, KeyModifiers::NONE, "task_goto_error"),
+        );
+
+        let buffer_id = self.open_read_only_buffer(
+            format!("*Task: {}*", task.name),
+            OUTPUT_MODE,
+            &format!("$ {}\n", task.command),
+        );
+
+        self.set_status_message(format!("Running task '{}'...", task.name));
+        self.set_quickfix_list(QuickfixList {
+            title: format!("Task: {}", task.name),
+            entries: Vec::new(),
+            current: None,
+            task_id: Some(id),
+        });
+        self.task_run = Some(TaskRun {
+            id,
+            name: task.name,
+            buffer_id,
+            working_dir,
+            process: Some(process),
+            parser: OutputParser::default(),
+            messages: Vec::new(),
+            line_count: 1,
+        });
+    }
+
+    /// Name of the running task, if any
+    pub(super) fn running_task_name(&self) -> Option<&str> {
+        self.task_run
+            .as_ref()
+            .filter(|run| run.process.is_some())
+            .map(|run| run.name.as_str())
+    }
+
+    /// Stop the running task
+    pub fn stop_task(&mut self) {
+        match self.task_run.as_ref() {
+            Some(TaskRun {
+                process: Some(process),
+                name,
+                ..
+            }) => {
+                process.stop();
+                self.set_status_message(format!("Stopping task '{}'...", name));
+            }
+            _ => self.set_status_message("No task is running".to_string()),
+        }
+    }
+
+    /// Append a line of task output and collect any compiler message in it
+    pub(super) fn handle_task_output(&mut self, task_id: u64, line: String) {
+        let Some(run) = self.task_run.as_mut().filter(|run| run.id == task_id) else {
+            return;
+        };
+        let entry = run
+            .parser
+            .parse_line(&line)
+            .map(|message| QuickfixEntry::from_message(message, &run.working_dir));
+        if let Some(entry) = &entry {
+            run.messages.push((run.line_count, entry.clone()));
+        }
+        run.line_count += 1;
+        let buffer_id = run.buffer_id;
+        self.append_task_output(buffer_id, &format!("{}\n", line));
+        if let Some(entry) = entry {
+            self.push_quickfix_entry(task_id, entry);
+        }
+    }
+
+    /// Report how the task ended
+    pub(super) fn handle_task_finished(&mut self, task_id: u64, exit_code: Option<i32>) {
+        let Some(run) = self.task_run.as_mut().filter(|run| run.id == task_id) else {
+            return;
+        };
+        run.process = None;
+        let errors = run
+            .messages
+            .iter()
+            .filter(|(_, m)| m.severity == MessageSeverity::Error)
+            .count();
+        let warnings = run.messages.len() - errors;
+        let outcome = match exit_code {
+            Some(0) => "succeeded".to_string(),
+            Some(code) => format!("failed (exit code {})", code),
+            None => "was stopped".to_string(),
+        };
+        let mut status = format!("Task '{}' {}", run.name, outcome);
+        if !run.messages.is_empty() {
+            status.push_str(&format!(": {} error(s), {} warning(s)", errors, warnings));
+        }
+        run.line_count += 2;
+        let buffer_id = run.buffer_id;
+        self.append_task_output(buffer_id, &format!("\n[{}]\n", status));
+        self.set_status_message(status);
+    }
+
+    /// Append text to the output buffer, following it if the cursor is at
+    /// the end
+    fn append_task_output(&mut self, buffer_id: BufferId, text: &str) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let end = state.buffer.len();
+        let follow = state.cursors.primary().position == end;
+        state.buffer.insert(end, text);
+        if follow {
+            state.cursors.primary_mut().position = state.buffer.len();
+        }
+    }
+
+    /// Jump to the compiler message on the cursor line of the task output
+    pub fn goto_task_error_at_cursor(&mut self) {
+        let active = self.active_buffer();
+        let line = {
+            let state = self.active_state();
+            state
+                .buffer
+                .get_line_number(state.cursors.primary().position)
+        };
+        let Some(run) = self.task_run.as_ref().filter(|run| run.buffer_id == active) else {
+            return;
+        };
+        let Some(index) = run.messages.iter().position(|(l, _)| *l == line) else {
+            self.set_status_message("No error or warning on this line".to_string());
+            return;
+        };
+        // The quickfix list holds the same messages unless it was replaced
+        let from_this_run = self
+            .quickfix
+            .as_ref()
+            .is_some_and(|list| list.task_id == Some(run.id));
+        if from_this_run {
+            self.goto_quickfix_entry(index);
+            return;
+        }
+        let entry = run.messages[index].1.clone();
+        if self.open_quickfix_location(&entry) {
+            self.set_status_message(format!("{}: {}", entry.severity.label(), entry.message));
+        }
+    }
+}
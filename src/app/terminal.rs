@@ -30,6 +30,40 @@ use crate::state::EditorState;
 impl Editor {
     /// Open a new terminal in the current split
     pub fn open_terminal(&mut self) {
+        match self.spawn_terminal_buffer(self.split_manager.active_split()) {
+            Ok((terminal_id, buffer_id)) => {
+                // Switch to the terminal buffer
+                self.set_active_buffer(buffer_id);
+
+                // Enable terminal mode
+                self.terminal_mode = true;
+                self.key_context = crate::input::keybindings::KeyContext::Terminal;
+
+                // Resize terminal to match actual split content area
+                self.resize_visible_terminals();
+
+                self.set_status_message(format!(
+                    "Terminal {} opened (Ctrl+Space to exit)",
+                    terminal_id
+                ));
+                tracing::info!(
+                    "Opened terminal {:?} with buffer {:?}",
+                    terminal_id,
+                    buffer_id
+                );
+            }
+            Err(e) => {
+                self.set_status_message(format!("Failed to open terminal: {}", e));
+                tracing::error!("Failed to open terminal: {}", e);
+            }
+        }
+    }
+
+    /// Start a shell in a new terminal and add its buffer to `split_id`'s tabs
+    pub(crate) fn spawn_terminal_buffer(
+        &mut self,
+        split_id: crate::model::event::SplitId,
+    ) -> Result<(TerminalId, BufferId), String> {
         // Get the current split dimensions for the terminal size
         let (cols, rows) = self.get_terminal_dimensions();
 
@@ -56,58 +90,27 @@ impl Editor {
             .terminal_backing_files
             .get(&predicted_terminal_id)
             .cloned();
-        match self.terminal_manager.spawn(
+        let terminal_id = self.terminal_manager.spawn(
             cols,
             rows,
-            Some(self.working_dir.clone()),
+            Some(self.split_working_dir(split_id)),
             Some(log_path.clone()),
             backing_path_for_spawn,
-        ) {
-            Ok(terminal_id) => {
-                // Track log file path (use actual ID in case it differs)
-                let actual_log_path = log_path.clone();
-                self.terminal_log_files
-                    .insert(terminal_id, actual_log_path.clone());
-                // If predicted differs, move backing path entry
-                if terminal_id != predicted_terminal_id {
-                    self.terminal_backing_files.remove(&predicted_terminal_id);
-                    let backing_path =
-                        terminal_root.join(format!("fresh-terminal-{}.txt", terminal_id.0));
-                    self.terminal_backing_files
-                        .insert(terminal_id, backing_path);
-                }
-
-                // Create a buffer for this terminal
-                let buffer_id = self.create_terminal_buffer_attached(
-                    terminal_id,
-                    self.split_manager.active_split(),
-                );
-
-                // Switch to the terminal buffer
-                self.set_active_buffer(buffer_id);
-
-                // Enable terminal mode
-                self.terminal_mode = true;
-                self.key_context = crate::input::keybindings::KeyContext::Terminal;
-
-                // Resize terminal to match actual split content area
-                self.resize_visible_terminals();
-
-                self.set_status_message(format!(
-                    "Terminal {} opened (Ctrl+Space to exit)",
-                    terminal_id
-                ));
-                tracing::info!(
-                    "Opened terminal {:?} with buffer {:?}",
-                    terminal_id,
-                    buffer_id
-                );
-            }
-            Err(e) => {
-                self.set_status_message(format!("Failed to open terminal: {}", e));
-                tracing::error!("Failed to open terminal: {}", e);
-            }
+        )?;
+
+        // Track log file path (use actual ID in case it differs)
+        self.terminal_log_files.insert(terminal_id, log_path);
+        // If predicted differs, move backing path entry
+        if terminal_id != predicted_terminal_id {
+            self.terminal_backing_files.remove(&predicted_terminal_id);
+            let backing_path = terminal_root.join(format!("fresh-terminal-{}.txt", terminal_id.0));
+            self.terminal_backing_files
+                .insert(terminal_id, backing_path);
         }
+
+        // Create a buffer for this terminal
+        let buffer_id = self.create_terminal_buffer_attached(terminal_id, split_id);
+        Ok((terminal_id, buffer_id))
     }
 
     /// Create a buffer for a terminal session
@@ -321,22 +324,10 @@ impl Editor {
     /// Resize all visible terminal PTYs to match their current split dimensions.
     /// Call this after operations that change split layout (maximize, resize, etc.)
     pub fn resize_visible_terminals(&mut self) {
-        // Get the content area excluding file explorer
-        let file_explorer_width = if self.file_explorer_visible {
-            (self.terminal_width as f32 * self.file_explorer_width_percent) as u16
-        } else {
-            0
-        };
-        let editor_width = self.terminal_width.saturating_sub(file_explorer_width);
-        let editor_area = ratatui::layout::Rect::new(
-            file_explorer_width,
-            1, // menu bar
-            editor_width,
-            self.terminal_height.saturating_sub(2), // menu bar + status bar
-        );
-
         // Get visible buffers with their areas
-        let visible_buffers = self.split_manager.get_visible_buffers(editor_area);
+        let visible_buffers = self
+            .split_manager
+            .get_visible_buffers(self.estimated_editor_area());
 
         // Resize each terminal buffer to match its split content area
         for (_split_id, buffer_id, split_area) in visible_buffers {
@@ -430,7 +421,7 @@ impl Editor {
 
             // Reload buffer from the backing file (reusing existing file loading)
             let large_file_threshold = self.config.editor.large_file_threshold_bytes as usize;
-            if let Ok(new_state) = EditorState::from_file(
+            if let Ok(mut new_state) = EditorState::from_file(
                 &backing_file,
                 self.terminal_width,
                 self.terminal_height,
@@ -439,6 +430,9 @@ impl Editor {
             ) {
                 // Replace buffer state
                 if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    new_state
+                        .buffer
+                        .continue_versions_from(state.buffer.version());
                     *state = new_state;
                     // Move cursor to end of buffer
                     let total = state.buffer.total_bytes();
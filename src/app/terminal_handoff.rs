@@ -0,0 +1,172 @@
+//! Handing the terminal back to the shell.
+//!
+//! This module provides functionality to:
+//! - Suspend the editor to the shell, resuming with `fg`
+//! - Run a shell command in the terminal the editor was started from, so it
+//!   can print and read input there, then offer to insert what it printed at
+//!   the cursor
+//!
+//! The editor only records the request. The event loop owns the terminal:
+//! it leaves raw mode and the alternate screen, carries the request out, and
+//! sets the terminal up again afterwards.
+
+use std::io::{self, BufRead, Read, Write};
+use std::process::{Command, Stdio};
+
+use super::shell_command::detect_shell;
+use super::Editor;
+use crate::view::prompt::PromptType;
+
+/// Request for the event loop to give the terminal up for a while
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalHandoff {
+    /// Stop the process until the shell resumes it
+    Suspend,
+    /// Run a shell command with the terminal
+    RunCommand(String),
+}
+
+/// Result of a command run with [`run_in_terminal`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// Everything the command wrote to stdout
+    pub stdout: String,
+    /// Whether the command exited successfully
+    pub success: bool,
+}
+
+/// Run `command` through the shell, copying its stdout to `sink` as it
+/// arrives and returning it. Stdin and stderr are inherited.
+pub fn run_passthrough(command: &str, sink: &mut dyn Write) -> io::Result<CommandOutput> {
+    let mut child = Command::new(detect_shell())
+        .args(["-c", command])
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let mut captured = Vec::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = match stdout.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            sink.write_all(&chunk[..n])?;
+            sink.flush()?;
+            captured.extend_from_slice(&chunk[..n]);
+        }
+    }
+    let status = child.wait()?;
+
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&captured).into_owned(),
+        success: status.success(),
+    })
+}
+
+/// Run `command` in the terminal, which must be out of raw mode, and wait
+/// for Enter before returning so its output can be read
+pub fn run_in_terminal(command: &str) -> io::Result<CommandOutput> {
+    let mut stdout = io::stdout();
+    writeln!(stdout, "$ {}", command)?;
+    let output = run_passthrough(command, &mut stdout)?;
+    if !output.stdout.is_empty() && !output.stdout.ends_with('\n') {
+        writeln!(stdout)?;
+    }
+    write!(stdout, "[Press Enter to return to the editor]")?;
+    stdout.flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(output)
+}
+
+impl Editor {
+    /// Ask the event loop to suspend the editor
+    pub fn request_suspend(&mut self) {
+        if cfg!(unix) {
+            self.terminal_handoff = Some(TerminalHandoff::Suspend);
+        } else {
+            self.set_status_message("Suspend is not supported on this platform".to_string());
+        }
+    }
+
+    /// Prompt for a shell command to run in the terminal
+    pub fn start_run_shell_command_prompt(&mut self) {
+        self.start_prompt("Run in terminal: ".to_string(), PromptType::RunShellCommand);
+    }
+
+    /// Handle the RunShellCommand prompt
+    pub(super) fn handle_run_shell_command(&mut self, input: &str) {
+        let command = input.trim();
+        if command.is_empty() {
+            return;
+        }
+        self.terminal_handoff = Some(TerminalHandoff::RunCommand(command.to_string()));
+    }
+
+    /// Take the pending terminal handoff, if any, for the event loop to
+    /// carry out
+    pub fn take_terminal_handoff(&mut self) -> Option<TerminalHandoff> {
+        self.terminal_handoff.take()
+    }
+
+    /// Report the result of a command run in the terminal, and offer to
+    /// insert its output at the cursor
+    pub fn finish_run_shell_command(&mut self, command: &str, result: io::Result<CommandOutput>) {
+        let output = match result {
+            Ok(output) => output,
+            Err(e) => {
+                self.set_status_message(format!("Failed to run '{}': {}", command, e));
+                return;
+            }
+        };
+        let status = if output.success { "" } else { " (failed)" };
+        if output.stdout.is_empty() {
+            self.set_status_message(format!("'{}' printed nothing{}", command, status));
+            return;
+        }
+        let lines = output.stdout.lines().count();
+        self.start_prompt(
+            format!(
+                "Insert output of '{}'{} ({} line{}) at cursor? (y/N) ",
+                command,
+                status,
+                lines,
+                if lines == 1 { "" } else { "s" }
+            ),
+            PromptType::ConfirmInsertCommandOutput {
+                output: output.stdout,
+            },
+        );
+    }
+
+    /// Handle the ConfirmInsertCommandOutput prompt
+    pub(super) fn handle_confirm_insert_command_output(&mut self, input: &str, output: String) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower != "y" && input_lower != "yes" {
+            return;
+        }
+        self.paste_text(output);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_passthrough_copies_and_captures_stdout() {
+        let mut sink = Vec::new();
+        let output = run_passthrough("printf 'one\\ntwo'", &mut sink).unwrap();
+        assert_eq!(output.stdout, "one\ntwo");
+        assert!(output.success);
+        assert_eq!(sink, b"one\ntwo");
+
+        let output = run_passthrough("exit 3", &mut Vec::new()).unwrap();
+        assert!(!output.success);
+    }
+}
@@ -0,0 +1,95 @@
+//! Terminal title.
+//!
+//! The title of the terminal's window or tab follows the active buffer, in
+//! the format set by `editor.terminal_title`. The editor only works out the
+//! title; the event loop writes it to the terminal when it changes, and
+//! restores the terminal's own title on exit.
+
+use super::Editor;
+
+impl Editor {
+    /// Title for the terminal, or None if `editor.terminal_title` is empty
+    pub fn terminal_title(&self) -> Option<String> {
+        let format = &self.config.editor.terminal_title;
+        if format.is_empty() {
+            return None;
+        }
+        let buffer_id = self.active_buffer();
+        let state = self.buffers.get(&buffer_id)?;
+        let path = state
+            .buffer
+            .file_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        let project = self
+            .working_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.working_dir.display().to_string());
+        Some(format_terminal_title(
+            format,
+            &self.get_buffer_display_name(buffer_id),
+            state.buffer.is_modified(),
+            &path,
+            &project,
+        ))
+    }
+
+    /// The terminal title, if it changed since it was last taken
+    pub fn take_terminal_title_change(&mut self) -> Option<String> {
+        let title = self.terminal_title()?;
+        if self.last_terminal_title.as_ref() == Some(&title) {
+            return None;
+        }
+        self.last_terminal_title = Some(title.clone());
+        Some(title)
+    }
+
+    /// Forget the title last taken, so it is written again after the
+    /// terminal was given up for a while
+    pub fn forget_terminal_title(&mut self) {
+        self.last_terminal_title = None;
+    }
+}
+
+/// Fill in the `{file}`, `{modified}`, `{path}` and `{project}` fields of a
+/// title format. Control characters are dropped, since they would end the
+/// escape sequence that sets the title.
+fn format_terminal_title(
+    format: &str,
+    file: &str,
+    modified: bool,
+    path: &str,
+    project: &str,
+) -> String {
+    format
+        .replace("{file}", file)
+        .replace("{modified}", if modified { "*" } else { "" })
+        .replace("{path}", path)
+        .replace("{project}", project)
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_terminal_title() {
+        let format = "{file}{modified} — {project} — fresh";
+        assert_eq!(
+            format_terminal_title(format, "main.rs", true, "/src/main.rs", "app"),
+            "main.rs* — app — fresh"
+        );
+        assert_eq!(
+            format_terminal_title(format, "main.rs", false, "/src/main.rs", "app"),
+            "main.rs — app — fresh"
+        );
+        assert_eq!(
+            format_terminal_title("{path}", "a\u{7}b", false, "/tmp/a\u{7}b", ""),
+            "/tmp/ab"
+        );
+    }
+}
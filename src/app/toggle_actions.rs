@@ -8,6 +8,7 @@
 
 use crate::config::Config;
 use crate::input::keybindings::KeybindingResolver;
+use crate::input::keyboard::KeyboardProtocol;
 use crate::services::lsp::manager::detect_language;
 
 use super::Editor;
@@ -148,6 +149,16 @@ impl Editor {
         self.gpm_active = active;
     }
 
+    /// Record the keyboard protocol negotiated with the terminal at startup
+    pub fn set_keyboard_protocol(&mut self, protocol: KeyboardProtocol) {
+        self.keyboard_protocol = protocol;
+    }
+
+    /// Keyboard protocol negotiated with the terminal
+    pub fn keyboard_protocol(&self) -> KeyboardProtocol {
+        self.keyboard_protocol
+    }
+
     /// Toggle inlay hints visibility
     pub fn toggle_inlay_hints(&mut self) {
         self.config.editor.enable_inlay_hints = !self.config.editor.enable_inlay_hints;
@@ -229,6 +240,10 @@ impl Editor {
         // Always reload keybindings (complex types don't implement PartialEq)
         self.keybindings = KeybindingResolver::new(&self.config);
 
+        if let Ok(mut service) = self.ignore_service.write() {
+            service.set_global_patterns(&self.config.ignore_patterns);
+        }
+
         // Update LSP configs
         if let Some(ref mut lsp) = self.lsp {
             for (language, lsp_config) in &self.config.lsp {
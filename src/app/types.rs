@@ -1,6 +1,7 @@
 use crate::app::file_open::SortMode;
 use crate::input::keybindings::Action;
 use crate::model::event::{BufferId, SplitDirection, SplitId};
+use crate::model::marker::MarkerId;
 use crate::services::async_bridge::LspMessageType;
 use ratatui::layout::Rect;
 use std::collections::{HashMap, HashSet};
@@ -43,6 +44,8 @@ pub(super) struct Bookmark {
     pub buffer_id: BufferId,
     /// Byte offset position in the buffer
     pub position: usize,
+    /// Gutter indicator marking the bookmark's line, which follows edits
+    pub indicator: Option<MarkerId>,
 }
 
 /// State for interactive replace (query-replace)
@@ -267,6 +270,22 @@ pub(super) struct MacroRecordingState {
     pub actions: Vec<Action>,
 }
 
+/// An abbreviation expansion that an immediate Backspace can revert
+#[derive(Debug, Clone)]
+pub(super) struct AbbreviationExpansion {
+    /// Buffer the expansion happened in
+    pub buffer_id: BufferId,
+    /// Start of the inserted text
+    pub start: usize,
+    /// Text inserted in place of the abbreviation (plus the typed boundary
+    /// character, unless a snippet consumed it)
+    pub inserted: String,
+    /// Text restored when the expansion is reverted
+    pub restored: String,
+    /// Cursor position right after the expansion
+    pub cursor: usize,
+}
+
 /// LSP progress information
 #[derive(Debug, Clone)]
 pub(super) struct LspProgressInfo {
@@ -327,6 +346,13 @@ pub enum HoverTarget {
     FileExplorerCloseButton,
 }
 
+/// What a popup drag does: move the popup (title bar) or resize it (bottom-right corner)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PopupDragKind {
+    Move,
+    Resize,
+}
+
 /// Mouse state tracking
 #[derive(Debug, Clone, Default)]
 pub(super) struct MouseState {
@@ -363,6 +389,24 @@ pub(super) struct MouseState {
     pub drag_selection_split: Option<SplitId>,
     /// The buffer byte position where the selection anchor is
     pub drag_selection_anchor: Option<usize>,
+    /// When a drag selection held beyond the split's edge last scrolled it
+    pub last_drag_autoscroll: Option<std::time::Instant>,
+    /// Popup being dragged (index in the popup stack) and whether it is moved or resized
+    pub dragging_popup: Option<(usize, PopupDragKind)>,
+    /// Popup area when the popup drag started
+    pub drag_start_popup_area: Option<Rect>,
+}
+
+/// A split's tab bar, for the overflow button and mouse wheel handling
+#[derive(Debug, Clone)]
+pub struct TabBarArea {
+    pub split_id: SplitId,
+    /// The tab bar row
+    pub rect: Rect,
+    /// (start_col, end_col) of the overflow button, when the tabs don't fit
+    pub overflow_button: Option<(u16, u16)>,
+    /// Tabs scrolled out of view or cut off, in tab order
+    pub hidden: Vec<BufferId>,
 }
 
 /// Mapping from visual row to buffer positions for mouse click handling
@@ -416,10 +460,21 @@ pub(crate) struct CachedLayout {
     /// Maximize split button hit areas
     /// (split_id, row, start_col, end_col)
     pub maximize_split_areas: Vec<(SplitId, u16, u16, u16)>,
+    /// Tab bars for the overflow button and mouse wheel handling
+    pub tab_bars: Vec<TabBarArea>,
     /// View line mappings for accurate mouse click positioning per split
     /// Maps visual row index to character position mappings
     /// Used to translate screen coordinates to buffer byte positions
     pub view_line_mappings: HashMap<SplitId, Vec<ViewLineMapping>>,
     /// Settings modal layout for hit testing
     pub settings_layout: Option<crate::view::settings::SettingsLayout>,
+    /// Status bar language indicator for mouse hit testing
+    /// (row, start_col, end_col)
+    pub status_bar_language_area: Option<(u16, u16, u16)>,
+    /// Status bar indentation indicator for mouse hit testing
+    /// (row, start_col, end_col)
+    pub status_bar_indentation_area: Option<(u16, u16, u16)>,
+    /// Status bar health indicator for mouse hit testing
+    /// (row, start_col, end_col)
+    pub status_bar_health_area: Option<(u16, u16, u16)>,
 }
@@ -1,6 +1,11 @@
-//! Undo and redo action handlers.
+//! Undo and redo action handlers, and undo history kept across sessions
+//! (see `services/undo_history.rs`).
 
 use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::undo_history::{PersistedUndoHistory, UndoHistoryStore};
+use std::path::Path;
+use std::time::Duration;
 
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
@@ -31,7 +36,7 @@ impl Editor {
         }
 
         // Update modified status based on event log position
-        self.update_modified_from_event_log();
+        self.update_modified_from_event_log(self.active_buffer());
     }
 
     /// Handle Redo action - reapply an undone edit operation.
@@ -49,6 +54,91 @@ impl Editor {
         }
 
         // Update modified status based on event log position
-        self.update_modified_from_event_log();
+        self.update_modified_from_event_log(self.active_buffer());
+    }
+
+    fn undo_history_store(&self) -> UndoHistoryStore {
+        UndoHistoryStore::new(self.dir_context.undo_history_dir())
+    }
+
+    fn undo_history_max_age(&self) -> Duration {
+        Duration::from_secs(
+            u64::from(self.config.editor.persistent_undo_max_age_days) * 24 * 60 * 60,
+        )
+    }
+
+    /// Restore the undo history saved for `path` into a freshly opened buffer,
+    /// if its content is what the history was saved with
+    pub(super) fn restore_undo_history(&mut self, buffer_id: BufferId, path: &Path) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        // Large files are not fully loaded, so they can't be checked
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+        let cursor_id = state.cursors.primary_id();
+        let Some(history) =
+            self.undo_history_store()
+                .load(path, content.as_bytes(), self.undo_history_max_age())
+        else {
+            return;
+        };
+        tracing::debug!(
+            "Restored {} undo step(s) for {:?}",
+            history.steps.len(),
+            path
+        );
+        self.event_logs
+            .insert(buffer_id, history.to_event_log(cursor_id));
+    }
+
+    /// Save the undo history of a file buffer, or forget it if there is
+    /// nothing to undo
+    pub(super) fn save_undo_history(&self, buffer_id: BufferId) {
+        if !self.config.editor.persistent_undo || self.is_terminal_buffer(buffer_id) {
+            return;
+        }
+        let (Some(path), Some(state), Some(log)) = (
+            self.buffer_metadata
+                .get(&buffer_id)
+                .and_then(|metadata| metadata.file_path()),
+            self.buffers.get(&buffer_id),
+            self.event_logs.get(&buffer_id),
+        ) else {
+            return;
+        };
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+
+        let store = self.undo_history_store();
+        match PersistedUndoHistory::from_event_log(path, log, content.as_bytes()) {
+            Some(mut history) => {
+                history.truncate_to_size(self.config.editor.persistent_undo_max_bytes as usize);
+                if let Err(e) = store.save(&history) {
+                    tracing::warn!("Failed to save undo history for {:?}: {}", path, e);
+                }
+            }
+            None => store.remove(path),
+        }
+    }
+
+    /// Save the undo history of every open file and remove expired ones
+    /// (call on shutdown)
+    pub fn save_all_undo_histories(&self) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        for &buffer_id in self.buffers.keys() {
+            self.save_undo_history(buffer_id);
+        }
+        let removed = self.undo_history_store().prune(self.undo_history_max_age());
+        if removed > 0 {
+            tracing::debug!("Removed {} expired undo histories", removed);
+        }
     }
 }
@@ -0,0 +1,225 @@
+//! Buffers backed by virtual filesystem providers.
+//!
+//! This module provides functionality to:
+//! - Open URLs like `git://HEAD~1/src/main.rs` as buffers named after the URL,
+//!   reading them on a background thread (built-in providers) or through the
+//!   `vfs_read` hook (plugin providers)
+//! - Save those buffers back through their provider, if it is writable
+//! - Let plugins register providers for their own schemes
+//!
+//! The content is kept in memory; nothing is written to temp files.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::plugins::hooks::HookArgs;
+use crate::services::vfs::{Provider, VfsUrl};
+
+/// Buffer mode of buffers opened from a provider
+const VFS_BUFFER_MODE: &str = "vfs";
+
+impl Editor {
+    /// Parse `input` as a URL of a registered provider
+    pub fn parse_vfs_url(&self, input: &str) -> Option<VfsUrl> {
+        self.vfs.parse(input)
+    }
+
+    /// Open `input` if it is a URL of a registered provider, returning
+    /// whether it was one
+    pub fn open_url(&mut self, input: &str) -> bool {
+        match self.vfs.parse(input) {
+            Some(url) => {
+                self.open_vfs_url(url);
+                true
+            }
+            None => {
+                self.set_status_message(format!("Unknown URL: {}", input));
+                false
+            }
+        }
+    }
+
+    /// Serve URLs with `scheme` from a plugin, through the `vfs_read` and
+    /// `vfs_write` hooks
+    pub fn register_plugin_vfs_provider(&mut self, scheme: &str, writable: bool) {
+        if VfsUrl::parse(&format!("{}://x", scheme)).is_none() {
+            tracing::warn!("Invalid file system provider scheme '{}'", scheme);
+            return;
+        }
+        self.vfs.register(scheme, Provider::Plugin { writable });
+    }
+
+    /// Open `url` in a buffer, or switch to it if it is already open. The
+    /// buffer appears once its provider has read it.
+    pub fn open_vfs_url(&mut self, url: VfsUrl) {
+        if let Some(buffer_id) = self.vfs_buffer_for(&url) {
+            self.set_active_buffer(buffer_id);
+            return;
+        }
+        let Some(provider) = self.vfs.get(&url.scheme).cloned() else {
+            self.set_status_message(format!("No provider for {}://", url.scheme));
+            return;
+        };
+
+        self.set_status_message(format!("Opening {}...", url));
+        match provider {
+            Provider::BuiltIn(provider) => {
+                let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+                    self.set_status_message("Async runtime not available".to_string());
+                    return;
+                };
+                let working_dir = self.working_dir.clone();
+                std::thread::spawn(move || {
+                    let result = provider.read(&url.path, &working_dir);
+                    let _ = sender.send(AsyncMessage::VfsRead { url, result });
+                });
+            }
+            Provider::Plugin { .. } => {
+                self.plugin_manager.run_hook(
+                    "vfs_read",
+                    HookArgs::VfsRead {
+                        url: url.to_string(),
+                        scheme: url.scheme.clone(),
+                        path: url.path.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Open a buffer with the content a provider read for `url`
+    pub fn handle_vfs_read(&mut self, url: VfsUrl, result: Result<String, String>) {
+        let content = match result {
+            Ok(content) => content,
+            Err(e) => {
+                self.set_status_message(format!("Error opening {}: {}", url, e));
+                return;
+            }
+        };
+        if let Some(buffer_id) = self.vfs_buffer_for(&url) {
+            self.set_active_buffer(buffer_id);
+            return;
+        }
+
+        let writable = self.vfs.get(&url.scheme).is_some_and(Provider::writable);
+        let name = self.vfs.display_name(&url);
+        let buffer_id = self.create_virtual_buffer(name, VFS_BUFFER_MODE.to_string(), !writable);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            // Highlight by the source's extension, which the name may not end with
+            state.set_language_from_name(&url.path, &self.grammar_registry);
+            state.buffer.insert(0, &content);
+            state.buffer.clear_modified();
+        }
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.mark_saved();
+        }
+        self.vfs_buffers.insert(buffer_id, url.clone());
+        self.set_active_buffer(buffer_id);
+        self.set_status_message(format!("Opened {}", url));
+    }
+
+    /// Open the content a plugin provider read for `url`
+    pub fn complete_vfs_read(&mut self, url: &str, result: Result<String, String>) {
+        match VfsUrl::parse(url) {
+            Some(url) => self.handle_vfs_read(url, result),
+            None => tracing::warn!("Plugin completed a read of invalid URL '{}'", url),
+        }
+    }
+
+    /// Finish saving a buffer a plugin provider has written
+    pub fn complete_vfs_write(&mut self, url: &str, result: Result<(), String>) {
+        match VfsUrl::parse(url) {
+            Some(url) => self.handle_vfs_written(url, result),
+            None => tracing::warn!("Plugin completed a write of invalid URL '{}'", url),
+        }
+    }
+
+    /// Whether `buffer_id` was opened from a provider
+    pub(super) fn is_vfs_buffer(&self, buffer_id: BufferId) -> bool {
+        self.vfs_buffers.contains_key(&buffer_id)
+    }
+
+    /// Write a buffer back through its provider. It is marked saved once the
+    /// provider reports success, unless it was edited in the meantime.
+    pub(super) fn save_vfs_buffer(&mut self, buffer_id: BufferId) {
+        let Some(url) = self.vfs_buffers.get(&buffer_id).cloned() else {
+            return;
+        };
+        let Some(provider) = self.vfs.get(&url.scheme).cloned() else {
+            self.set_status_message(format!("No provider for {}://", url.scheme));
+            return;
+        };
+        if !provider.writable() {
+            self.set_status_message(format!("{} is read-only", url));
+            return;
+        }
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let content = state.buffer.to_string().unwrap_or_default();
+        self.vfs_saves.insert(buffer_id, state.buffer.version());
+
+        self.set_status_message(format!("Writing {}...", url));
+        match provider {
+            Provider::BuiltIn(provider) => {
+                let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+                    self.set_status_message("Async runtime not available".to_string());
+                    return;
+                };
+                let working_dir = self.working_dir.clone();
+                std::thread::spawn(move || {
+                    let result = provider.write(&url.path, &content, &working_dir);
+                    let _ = sender.send(AsyncMessage::VfsWritten { url, result });
+                });
+            }
+            Provider::Plugin { .. } => {
+                self.plugin_manager.run_hook(
+                    "vfs_write",
+                    HookArgs::VfsWrite {
+                        url: url.to_string(),
+                        scheme: url.scheme.clone(),
+                        path: url.path.clone(),
+                        content,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Mark a buffer saved once its provider has written it
+    pub fn handle_vfs_written(&mut self, url: VfsUrl, result: Result<(), String>) {
+        let Some(buffer_id) = self.vfs_buffer_for(&url) else {
+            return;
+        };
+        let saved_version = self.vfs_saves.remove(&buffer_id);
+        if let Err(e) = result {
+            self.set_status_message(format!("Error saving {}: {}", url, e));
+            // A quit waiting for the write is called off
+            self.should_quit = false;
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            if Some(state.buffer.version()) == saved_version {
+                state.buffer.clear_modified();
+                if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+                    log.mark_saved();
+                }
+            }
+        }
+        self.set_status_message(format!("Saved {}", url));
+    }
+
+    /// Forget a closed buffer
+    pub(super) fn forget_vfs_buffer(&mut self, buffer_id: BufferId) {
+        self.vfs_buffers.remove(&buffer_id);
+        self.vfs_saves.remove(&buffer_id);
+    }
+
+    /// The open buffer of `url`
+    fn vfs_buffer_for(&self, url: &VfsUrl) -> Option<BufferId> {
+        self.vfs_buffers
+            .iter()
+            .find(|(_, open)| *open == url)
+            .map(|(&buffer_id, _)| buffer_id)
+    }
+}
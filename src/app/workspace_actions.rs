@@ -0,0 +1,163 @@
+//! Workspace folders.
+//!
+//! This module provides functionality to:
+//! - Add folders to the workspace besides the project root, and remove them
+//! - Show them as top-level folders in the file explorer
+//!
+//! Workspace folders are saved with the session. Plugins get them from
+//! `editor.getWorkspaceRoots()`, so the file finder and grep search them too.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::view::prompt::{Prompt, PromptType};
+use std::path::{Path, PathBuf};
+
+impl Editor {
+    /// Folders added to the workspace, besides the working directory
+    pub fn workspace_folders(&self) -> &[PathBuf] {
+        &self.workspace_folders
+    }
+
+    /// The working directory followed by the workspace folders
+    pub fn workspace_roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.working_dir.clone())
+            .chain(self.workspace_folders.iter().cloned())
+            .collect()
+    }
+
+    /// Prompt for a folder to add to the workspace
+    pub fn start_add_workspace_folder_prompt(&mut self) {
+        self.start_prompt(
+            "Add folder to workspace: ".to_string(),
+            PromptType::AddWorkspaceFolder,
+        );
+        self.init_folder_open_state();
+    }
+
+    /// Add `path` to the workspace folders
+    pub fn add_workspace_folder(&mut self, path: PathBuf) {
+        if !path.is_dir() {
+            self.set_status_message(format!("Not a directory: {}", path.display()));
+            return;
+        }
+        let path = path.canonicalize().unwrap_or(path);
+        if let Some(root) = self
+            .workspace_roots()
+            .into_iter()
+            .find(|root| path.starts_with(root) || root.starts_with(&path))
+        {
+            self.set_status_message(format!(
+                "{} overlaps workspace folder {}",
+                path.display(),
+                root.display()
+            ));
+            return;
+        }
+
+        self.workspace_folders.push(path.clone());
+        self.workspace_folders_changed();
+        self.set_status_message(format!("Added to workspace: {}", path.display()));
+    }
+
+    /// Prompt for one of the workspace folders to remove
+    pub fn start_remove_workspace_folder_prompt(&mut self) {
+        if self.workspace_folders.is_empty() {
+            self.set_status_message("No folders have been added to the workspace".to_string());
+            return;
+        }
+        let suggestions: Vec<Suggestion> = self
+            .workspace_folders
+            .iter()
+            .map(|folder| {
+                let path = folder.display().to_string();
+                Suggestion {
+                    text: path.clone(),
+                    description: None,
+                    value: Some(path),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            "Remove folder from workspace: ".to_string(),
+            PromptType::RemoveWorkspaceFolder,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Remove the workspace folder at `input`
+    pub(super) fn remove_workspace_folder(&mut self, input: &str) {
+        let Some(index) = self
+            .workspace_folders
+            .iter()
+            .position(|folder| folder == Path::new(input))
+        else {
+            self.set_status_message(format!("Not a workspace folder: {}", input));
+            return;
+        };
+        let folder = self.workspace_folders.remove(index);
+        self.workspace_folders_changed();
+        self.set_status_message(format!("Removed from workspace: {}", folder.display()));
+    }
+
+    /// Replace the workspace folders, as when restoring a session
+    pub(super) fn set_workspace_folders(&mut self, folders: Vec<PathBuf>) {
+        self.workspace_folders = folders;
+        self.workspace_folders_changed();
+    }
+
+    /// Update the file explorer and the ignore service after the workspace
+    /// folders changed
+    fn workspace_folders_changed(&mut self) {
+        let roots = self.workspace_roots();
+        if let Ok(mut service) = self.ignore_service.write() {
+            service.set_roots(roots);
+        }
+        self.sync_file_explorer_roots();
+    }
+
+    /// Make the file explorer's extra roots match the workspace folders
+    pub(super) fn sync_file_explorer_roots(&mut self) {
+        let (Some(runtime), Some(explorer)) = (&self.tokio_runtime, &mut self.file_explorer) else {
+            return;
+        };
+
+        let shown: Vec<PathBuf> = explorer
+            .tree()
+            .extra_roots()
+            .iter()
+            .filter_map(|&id| explorer.tree().get_node(id))
+            .map(|node| node.entry.path.clone())
+            .collect();
+        for path in &shown {
+            if !self.workspace_folders.contains(path) {
+                explorer.tree_mut().remove_root(path);
+            }
+        }
+        let selection_removed = explorer
+            .get_selected()
+            .is_some_and(|id| explorer.tree().get_node(id).is_none());
+        if selection_removed {
+            let root_id = explorer.tree().root_id();
+            explorer.set_selected(Some(root_id));
+        }
+        for folder in &self.workspace_folders {
+            if shown.contains(folder) {
+                continue;
+            }
+            match runtime.block_on(explorer.tree_mut().add_root(folder.clone())) {
+                Ok(_) => {
+                    if let Err(e) = explorer.load_gitignore_for_dir(folder) {
+                        tracing::warn!("Failed to load .gitignore from {:?}: {}", folder, e);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to show {:?} in the file explorer: {}", folder, e),
+            }
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Applying a set of edits that spans several files.
+//!
+//! Rename, code actions and project-wide replace change many files at once.
+//! [`Editor::apply_workspace_edit`] applies such a set all or nothing: every
+//! edit is resolved and checked before anything changes, then files that
+//! aren't open are rewritten on disk (putting back the ones already written
+//! if a write fails) and each open buffer gets its edits as one undoable
+//! batch, which moves cursors, markers and overlays along with the text.
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::model::buffer::{AtomicWrite, Buffer};
+use crate::model::event::{BufferId, Event};
+
+use super::{uri_to_path, Editor};
+
+/// Where a [`TextEdit`] applies
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditRange {
+    /// Byte offsets
+    Bytes(Range<usize>),
+    /// LSP line and UTF-16 character positions
+    Lsp(lsp_types::Range),
+}
+
+/// Replace a range of a file with new text
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub range: EditRange,
+    pub new_text: String,
+}
+
+/// Edits to several files, applied together
+///
+/// Edits of a file are given against its current content and must not
+/// overlap. Insertions at the same position are applied in the order given.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkspaceEdit {
+    pub files: Vec<(PathBuf, Vec<TextEdit>)>,
+}
+
+/// What [`Editor::apply_workspace_edit`] changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AppliedWorkspaceEdit {
+    /// Open buffers edited in memory
+    pub buffers: usize,
+    /// Files that aren't open, edited on disk
+    pub files: usize,
+    /// Edits applied
+    pub edits: usize,
+}
+
+impl WorkspaceEdit {
+    /// Add `edits` to the file at `path`
+    pub fn add(&mut self, path: PathBuf, edits: impl IntoIterator<Item = TextEdit>) {
+        let edits = edits.into_iter();
+        match self.files.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, existing)) => existing.extend(edits),
+            None => self.files.push((path, edits.collect())),
+        }
+    }
+
+    /// Text edits of an LSP workspace edit, from either `changes` or
+    /// `document_changes`. Edits to non-file URIs and resource operations
+    /// (create, rename, delete) are skipped.
+    pub fn from_lsp(edit: lsp_types::WorkspaceEdit) -> Self {
+        let convert = |edit: lsp_types::TextEdit| TextEdit {
+            range: EditRange::Lsp(edit.range),
+            new_text: edit.new_text,
+        };
+
+        let mut result = Self::default();
+        for (uri, edits) in edit.changes.into_iter().flatten() {
+            if let Ok(path) = uri_to_path(&uri) {
+                result.add(path, edits.into_iter().map(convert));
+            }
+        }
+
+        let document_edits = match edit.document_changes {
+            Some(lsp_types::DocumentChanges::Edits(edits)) => edits,
+            Some(lsp_types::DocumentChanges::Operations(ops)) => ops
+                .into_iter()
+                .filter_map(|op| match op {
+                    lsp_types::DocumentChangeOperation::Edit(edit) => Some(edit),
+                    lsp_types::DocumentChangeOperation::Op(_) => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        for document_edit in document_edits {
+            if let Ok(path) = uri_to_path(&document_edit.text_document.uri) {
+                let edits = document_edit.edits.into_iter().map(|edit| match edit {
+                    lsp_types::OneOf::Left(edit) => edit,
+                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+                });
+                result.add(path, edits.map(convert));
+            }
+        }
+        result
+    }
+
+    /// Whether there is nothing to apply
+    pub fn is_empty(&self) -> bool {
+        self.files.iter().all(|(_, edits)| edits.is_empty())
+    }
+}
+
+/// A file's edits as byte ranges, checked against its content
+enum ResolvedFile {
+    Buffer {
+        buffer_id: BufferId,
+        edits: Vec<(Range<usize>, String)>,
+    },
+    Disk {
+        path: PathBuf,
+        original: Vec<u8>,
+        edited: Vec<u8>,
+        edits: usize,
+    },
+}
+
+impl Editor {
+    /// Apply edits to several files at once
+    ///
+    /// Open buffers are edited in memory, one undo step per buffer, and
+    /// their language servers are notified. Other files are edited on disk
+    /// without being opened. Nothing changes if any edit is out of range,
+    /// edits of a file overlap, or a file can't be read; if writing a file
+    /// fails, the files already written are restored.
+    pub fn apply_workspace_edit(
+        &mut self,
+        edit: WorkspaceEdit,
+        description: &str,
+    ) -> io::Result<AppliedWorkspaceEdit> {
+        // Resolve everything before changing anything
+        let mut resolved = Vec::new();
+        for (path, edits) in edit.files {
+            if edits.is_empty() {
+                continue;
+            }
+            let file = match self.workspace_edit_buffer(&path) {
+                Some(buffer_id) => {
+                    let state = self.buffers.get_mut(&buffer_id).ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::NotFound, "Buffer not found")
+                    })?;
+                    // Lazily loaded regions must be in memory to convert positions
+                    let total = state.buffer.total_bytes();
+                    state
+                        .buffer
+                        .get_text_range_mut(0, total)
+                        .map_err(io::Error::other)?;
+                    let edits =
+                        resolve_edits(&state.buffer, edits).map_err(|e| edit_error(&path, e))?;
+                    ResolvedFile::Buffer { buffer_id, edits }
+                }
+                None => {
+                    let original = std::fs::read(&path).map_err(|e| edit_error(&path, e))?;
+                    let buffer = Buffer::from_bytes(original.clone());
+                    let edits = resolve_edits(&buffer, edits).map_err(|e| edit_error(&path, e))?;
+                    ResolvedFile::Disk {
+                        edited: apply_edits(&original, &edits),
+                        edits: edits.len(),
+                        path,
+                        original,
+                    }
+                }
+            };
+            resolved.push(file);
+        }
+
+        // Files on disk first: they're the only part that can fail
+        let mut written: Vec<(&Path, &[u8])> = Vec::new();
+        for file in &resolved {
+            if let ResolvedFile::Disk {
+                path,
+                original,
+                edited,
+                ..
+            } = file
+            {
+                if let Err(e) = write_file(path, edited) {
+                    for (path, original) in written {
+                        if let Err(e) = write_file(path, original) {
+                            tracing::error!("Failed to restore {}: {}", path.display(), e);
+                        }
+                    }
+                    return Err(edit_error(path, e));
+                }
+                written.push((path, original));
+            }
+        }
+
+        let mut applied = AppliedWorkspaceEdit::default();
+        for file in resolved {
+            match file {
+                ResolvedFile::Disk { edits, .. } => {
+                    applied.files += 1;
+                    applied.edits += edits;
+                }
+                ResolvedFile::Buffer { buffer_id, edits } => {
+                    applied.buffers += 1;
+                    applied.edits += edits.len();
+                    self.apply_edits_to_buffer(buffer_id, edits, description)?;
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    /// The open buffer editing `path`, also matching it canonicalized
+    fn workspace_edit_buffer(&self, path: &Path) -> Option<BufferId> {
+        self.buffer_for_path(path).or_else(|| {
+            let canonical = path.canonicalize().ok()?;
+            self.buffers
+                .iter()
+                .find(|(_, state)| {
+                    state
+                        .buffer
+                        .file_path()
+                        .and_then(|p| p.canonicalize().ok())
+                        .is_some_and(|p| p == canonical)
+                })
+                .map(|(id, _)| *id)
+        })
+    }
+
+    /// Apply resolved edits to an open buffer as one batch
+    fn apply_edits_to_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        edits: Vec<(Range<usize>, String)>,
+        description: &str,
+    ) -> io::Result<()> {
+        let state = self
+            .buffers
+            .get_mut(&buffer_id)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Buffer not found"))?;
+        let cursor_id = state.cursors.primary_id();
+
+        // Last edit first, so earlier offsets stay valid
+        let mut events = Vec::new();
+        for (range, new_text) in edits.into_iter().rev() {
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    deleted_text: state.get_text_range(range.start, range.end),
+                    range: range.clone(),
+                    cursor_id,
+                });
+            }
+            if !new_text.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: new_text,
+                    cursor_id,
+                });
+            }
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.apply_rename_batch_to_buffer(
+            buffer_id,
+            Event::Batch {
+                events,
+                description: description.to_string(),
+            },
+        )
+    }
+}
+
+/// Byte ranges of `edits` in `buffer`, in order, checked to be in range and
+/// not to overlap
+fn resolve_edits(buffer: &Buffer, edits: Vec<TextEdit>) -> io::Result<Vec<(Range<usize>, String)>> {
+    let len = buffer.len();
+    let mut resolved: Vec<(Range<usize>, String)> = edits
+        .into_iter()
+        .map(|edit| {
+            let range = match edit.range {
+                EditRange::Bytes(range) => range,
+                EditRange::Lsp(range) => {
+                    let byte = |position: lsp_types::Position| {
+                        buffer.lsp_position_to_byte(
+                            position.line as usize,
+                            position.character as usize,
+                        )
+                    };
+                    byte(range.start)..byte(range.end)
+                }
+            };
+            (range, edit.new_text)
+        })
+        .collect();
+
+    if let Some((range, _)) = resolved
+        .iter()
+        .find(|(range, _)| range.start > range.end || range.end > len)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "edit {}..{} is outside the file ({} bytes)",
+                range.start, range.end, len
+            ),
+        ));
+    }
+    // Stable, so insertions at one position keep their order
+    resolved.sort_by_key(|(range, _)| range.start);
+    if let Some(pair) = resolved
+        .windows(2)
+        .find(|pair| pair[0].0.end > pair[1].0.start)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "edits {}..{} and {}..{} overlap",
+                pair[0].0.start, pair[0].0.end, pair[1].0.start, pair[1].0.end
+            ),
+        ));
+    }
+    Ok(resolved)
+}
+
+/// `content` with resolved edits applied
+fn apply_edits(content: &[u8], edits: &[(Range<usize>, String)]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(content.len());
+    let mut copied = 0;
+    for (range, new_text) in edits {
+        result.extend_from_slice(&content[copied..range.start]);
+        result.extend_from_slice(new_text.as_bytes());
+        copied = range.end;
+    }
+    result.extend_from_slice(&content[copied..]);
+    result
+}
+
+/// Replace the contents of `path` through a temp file, as buffer saves do,
+/// so a crash never leaves it half written
+fn write_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut write = AtomicWrite::create(path)?;
+    write.file().write_all(contents)?;
+    write.commit()?;
+    Ok(())
+}
+
+fn edit_error(path: &Path, error: io::Error) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(range: Range<usize>, new_text: &str) -> TextEdit {
+        TextEdit {
+            range: EditRange::Bytes(range),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    fn lsp(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+        let position = |(line, character)| lsp_types::Position { line, character };
+        TextEdit {
+            range: EditRange::Lsp(lsp_types::Range {
+                start: position(start),
+                end: position(end),
+            }),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let content = "fn é(value: i32) {\n    value\n}\n";
+        let buffer = Buffer::from_bytes(content.as_bytes().to_vec());
+        let edits = resolve_edits(
+            &buffer,
+            vec![
+                lsp((1, 4), (1, 9), "amount"),
+                // "é" is one UTF-16 unit
+                lsp((0, 5), (0, 10), "amount"),
+                bytes(0..0, "// a\n"),
+                bytes(0..0, "// b\n"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(apply_edits(content.as_bytes(), &edits)).unwrap(),
+            "// a\n// b\nfn é(amount: i32) {\n    amount\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_edits_rejected() {
+        let buffer = Buffer::from_bytes(b"hello world".to_vec());
+        let err = resolve_edits(&buffer, vec![bytes(6..20, "")]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = resolve_edits(&buffer, vec![bytes(0..5, "a"), bytes(4..8, "b")]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        // Adjacent edits don't overlap
+        assert!(resolve_edits(&buffer, vec![bytes(0..5, "a"), bytes(5..6, "b")]).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_replaces_file_keeping_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.sh");
+        std::fs::write(&path, "old contents").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        write_file(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        // The temp file was moved into place
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
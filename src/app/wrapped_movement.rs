@@ -0,0 +1,258 @@
+//! Moving the cursor up and down by screen rows when lines wrap.
+//!
+//! With line wrap on, a long line takes several rows, and Up/Down move to the
+//! row above or below instead of the previous or next line. The goal column
+//! is then a column of the row, kept through short rows as the goal column
+//! of a line is (see `vertical_move_target` in `input::actions`). Lines are
+//! split into rows by the renderer, at the width of the last render, so the
+//! cursor moves over the rows on screen.
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::model::buffer::Buffer;
+use crate::model::event::Event;
+use crate::view::ui::split_rendering::SplitRenderer;
+
+/// A line of the buffer split into rows
+struct WrappedLine {
+    /// Byte and visual column of each position the cursor can take on each
+    /// row; the last row ends with the end of the line
+    rows: Vec<Vec<(usize, usize)>>,
+}
+
+impl WrappedLine {
+    /// The line at `position`, or the one before or after it
+    fn new(
+        buffer: &mut Buffer,
+        position: usize,
+        line_offset: isize,
+        wrap_width: usize,
+        tab_size: usize,
+        estimated_line_length: usize,
+    ) -> Option<Self> {
+        let mut iter = buffer.line_iterator(position, estimated_line_length);
+        let (start, text) = match line_offset {
+            -1 => iter.prev()?,
+            0 => {
+                let start = iter.current_position();
+                // The empty line after a final newline has no content
+                iter.next().unwrap_or((start, String::new()))
+            }
+            _ => {
+                iter.next();
+                iter.next()?
+            }
+        };
+        let end = start + text.trim_end_matches(['\r', '\n']).len();
+
+        let mut rows: Vec<Vec<(usize, usize)>> = Vec::new();
+        let mut width = 0;
+        let view_lines = SplitRenderer::wrapped_source_line(
+            buffer,
+            start,
+            wrap_width,
+            tab_size,
+            estimated_line_length,
+        );
+        for view_line in &view_lines {
+            let mut positions: Vec<(usize, usize)> = Vec::new();
+            let chars = view_line.char_source_bytes.iter();
+            for (byte, column) in chars.zip(&view_line.char_visual_cols) {
+                // A tab shows as several characters from the same byte
+                match byte {
+                    Some(byte) if positions.last().is_none_or(|&(last, _)| last != *byte) => {
+                        positions.push((*byte, *column))
+                    }
+                    _ => {}
+                }
+            }
+            if !positions.is_empty() {
+                rows.push(positions);
+                width = view_line.visual_to_char.len();
+            }
+        }
+
+        // The end of a line without a newline follows its last character
+        match rows.last_mut() {
+            Some(row) if row.last().is_some_and(|&(byte, _)| byte >= end) => {}
+            Some(row) => row.push((end, width)),
+            None => rows.push(vec![(end, 0)]),
+        }
+        Some(Self { rows })
+    }
+
+    /// The row showing `position`, and its visual column there
+    fn row_and_column(&self, position: usize) -> (usize, usize) {
+        // At a row boundary the cursor is at the start of the next row
+        let row = self
+            .rows
+            .iter()
+            .rposition(|row| row[0].0 <= position)
+            .unwrap_or(0);
+        let column = self.rows[row]
+            .iter()
+            .rev()
+            .find(|&&(byte, _)| byte <= position)
+            .map_or(0, |&(_, column)| column);
+        (row, column)
+    }
+
+    /// Position at the visual column `goal_column` of `row`, or after it
+    /// when it falls inside a character; the end of the row if it is shorter.
+    /// Past the end of a row that continues on the next one, the cursor
+    /// stays on its last character: its end is the start of the next row.
+    fn position_in_row(&self, row: usize, goal_column: usize) -> usize {
+        let positions = &self.rows[row];
+        positions
+            .iter()
+            .find(|&&(_, column)| column >= goal_column)
+            .or(positions.last())
+            .map_or(0, |&(byte, _)| byte)
+    }
+}
+
+/// Where moving the cursor at `position` a row up (or down) lands, with the
+/// goal column to keep, like `vertical_move_target` for lines. `None` on the
+/// first (last) row of the buffer.
+fn row_move_target(
+    buffer: &mut Buffer,
+    position: usize,
+    sticky_column: usize,
+    up: bool,
+    wrap_width: usize,
+    tab_size: usize,
+    estimated_line_length: usize,
+) -> Option<(usize, usize)> {
+    let line = |buffer: &mut Buffer, line_offset| {
+        WrappedLine::new(
+            buffer,
+            position,
+            line_offset,
+            wrap_width,
+            tab_size,
+            estimated_line_length,
+        )
+    };
+    let current = line(buffer, 0)?;
+    let (row, column) = current.row_and_column(position);
+    let goal_column = if sticky_column > 0 {
+        sticky_column
+    } else {
+        column
+    };
+
+    let new_position = if up && row > 0 {
+        current.position_in_row(row - 1, goal_column)
+    } else if up {
+        let above = line(buffer, -1)?;
+        above.position_in_row(above.rows.len() - 1, goal_column)
+    } else if row + 1 < current.rows.len() {
+        current.position_in_row(row + 1, goal_column)
+    } else {
+        line(buffer, 1)?.position_in_row(0, goal_column)
+    };
+    Some((new_position, goal_column))
+}
+
+impl Editor {
+    /// Events moving the active split's cursors a row up or down for
+    /// MoveUp, MoveDown, SelectUp and SelectDown; `None` for other actions
+    /// or when line wrap is off, to move by lines
+    pub(super) fn wrapped_vertical_move_events(&mut self, action: &Action) -> Option<Vec<Event>> {
+        let (up, select) = match action {
+            Action::MoveUp => (true, false),
+            Action::MoveDown => (false, false),
+            Action::SelectUp => (true, true),
+            Action::SelectDown => (false, true),
+            _ => return None,
+        };
+        let split = self
+            .split_view_states
+            .get(&self.split_manager.active_split())?;
+        let viewport = &split.viewport;
+        // Not rendered yet, or laid out by a plugin: move by lines
+        if !viewport.line_wrap_enabled || viewport.wrap_width == 0 || split.view_transform.is_some()
+        {
+            return None;
+        }
+        let wrap_width = viewport.wrap_width;
+        let estimated_line_length = self.config.editor.estimated_line_length;
+        let buffer_id = self.active_buffer();
+        let state = self.buffers.get_mut(&buffer_id)?;
+        if state.buffer.is_binary() {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        for (cursor_id, cursor) in state.cursors.iter() {
+            let Some((new_position, goal_column)) = row_move_target(
+                &mut state.buffer,
+                cursor.position,
+                cursor.sticky_column,
+                up,
+                wrap_width,
+                state.tab_size,
+                estimated_line_length,
+            ) else {
+                continue;
+            };
+
+            let new_anchor = if select {
+                Some(cursor.anchor.unwrap_or(cursor.position))
+            } else if cursor.deselect_on_move {
+                None
+            } else {
+                // Emacs mark mode keeps the selection
+                cursor.anchor
+            };
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position,
+                old_anchor: cursor.anchor,
+                new_anchor,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: goal_column,
+            });
+        }
+        Some(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Moves on rows of 10 columns
+    fn move_from(text: &str, position: usize, sticky: usize, up: bool) -> Option<(usize, usize)> {
+        let mut buffer = Buffer::from_str(text, usize::MAX);
+        row_move_target(&mut buffer, position, sticky, up, 10, 4, 80)
+    }
+
+    #[test]
+    fn test_moves_between_rows_of_a_line() {
+        let text = "0123456789abcdefghijKLM\nxy\n";
+        // Row 0 column 3 -> row 1 column 3
+        assert_eq!(move_from(text, 3, 0, false), Some((13, 3)));
+        // Row 2 is short: the cursor goes to its end, and the goal stays
+        assert_eq!(move_from(text, 15, 0, false), Some((23, 5)));
+        assert_eq!(move_from(text, 23, 5, false), Some((26, 5)));
+        // Up from the next line lands on the last row of the wrapped one
+        assert_eq!(move_from(text, 24, 0, true), Some((20, 0)));
+        assert_eq!(move_from(text, 13, 0, true), Some((3, 3)));
+        assert_eq!(move_from(text, 2, 0, true), None);
+    }
+
+    #[test]
+    fn test_rows_break_between_words_and_expand_tabs() {
+        // Rows "ab cdefgh " and "ij\tk", the tab taking columns 2 and 3
+        let text = "ab cdefgh ij\tk\nxyz";
+        assert_eq!(move_from(text, 8, 0, false), Some((14, 8)));
+        assert_eq!(move_from(text, 14, 8, true), Some((8, 8)));
+        // Column 3 is inside the tab: the cursor goes after it
+        assert_eq!(move_from(text, 3, 0, false), Some((13, 3)));
+        // The last line has no newline: its end follows "xyz"
+        assert_eq!(move_from(text, 13, 3, false), Some((18, 3)));
+        assert_eq!(move_from(text, 18, 0, true), Some((13, 3)));
+    }
+}
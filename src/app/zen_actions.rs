@@ -0,0 +1,116 @@
+//! Zen (distraction-free) mode.
+//!
+//! This module provides functionality to:
+//! - Hide the menu bar and the line numbers and gutters of every buffer,
+//!   remembering how they were so turning zen mode off restores them
+//! - Keep buffers opened while zen mode is on free of gutters too
+//! - Save the preference in the config file
+//!
+//! The tab bars and status bar are hidden, and the text centered, while
+//! rendering.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Layout to restore when zen mode is turned off
+pub(crate) struct ZenLayout {
+    menu_bar_visible: bool,
+    /// Whether each buffer showed line numbers before zen mode hid them
+    line_numbers: HashMap<BufferId, bool>,
+}
+
+impl ZenLayout {
+    pub(crate) fn new(menu_bar_visible: bool) -> Self {
+        Self {
+            menu_bar_visible,
+            line_numbers: HashMap::new(),
+        }
+    }
+}
+
+impl Editor {
+    /// Whether zen mode is on
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_layout.is_some()
+    }
+
+    /// Turn zen mode on or off, and save the preference
+    pub fn toggle_zen_mode(&mut self) {
+        if self.zen_layout.is_some() {
+            self.exit_zen_mode();
+            self.set_status_message("Zen mode off".to_string());
+        } else {
+            self.enter_zen_mode();
+            self.set_status_message("Zen mode on".to_string());
+        }
+
+        self.config.editor.zen_mode = self.zen_layout.is_some();
+        if let Err(e) = self.save_config() {
+            tracing::warn!("Failed to save config after toggling zen mode: {}", e);
+        }
+    }
+
+    fn enter_zen_mode(&mut self) {
+        let mut layout = ZenLayout::new(self.menu_bar_visible);
+        self.menu_bar_visible = false;
+        self.menu_bar_auto_shown = false;
+        self.menu_state.close_menu();
+        for (&buffer_id, state) in self.buffers.iter_mut() {
+            layout
+                .line_numbers
+                .insert(buffer_id, state.margins.show_line_numbers);
+            state.margins.set_line_numbers(false);
+        }
+        self.zen_layout = Some(layout);
+    }
+
+    fn exit_zen_mode(&mut self) {
+        let Some(layout) = self.zen_layout.take() else {
+            return;
+        };
+        self.menu_bar_visible = layout.menu_bar_visible;
+        for (buffer_id, shown) in layout.line_numbers {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.margins.set_line_numbers(shown);
+                if shown {
+                    let total_lines = state.buffer.line_count().unwrap_or(1);
+                    state.margins.update_width_for_buffer(total_lines);
+                }
+            }
+        }
+    }
+
+    /// Hide the gutters of buffers opened since zen mode was turned on
+    pub(super) fn apply_zen_mode_to_new_buffers(&mut self) {
+        let Some(layout) = self.zen_layout.as_mut() else {
+            return;
+        };
+        layout
+            .line_numbers
+            .retain(|buffer_id, _| self.buffers.contains_key(buffer_id));
+        for (&buffer_id, state) in self.buffers.iter_mut() {
+            if let Entry::Vacant(entry) = layout.line_numbers.entry(buffer_id) {
+                entry.insert(state.margins.show_line_numbers);
+                state.margins.set_line_numbers(false);
+            }
+        }
+    }
+
+    /// Whether the menu bar is shown outside zen mode
+    pub(super) fn menu_bar_preference(&self) -> bool {
+        match &self.zen_layout {
+            Some(layout) => layout.menu_bar_visible,
+            None => self.menu_bar_visible,
+        }
+    }
+
+    /// Show or hide the menu bar, or only remember it for when zen mode ends
+    pub(super) fn set_menu_bar_preference(&mut self, visible: bool) {
+        match self.zen_layout.as_mut() {
+            Some(layout) => layout.menu_bar_visible = visible,
+            None => self.menu_bar_visible = visible,
+        }
+    }
+}
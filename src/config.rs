@@ -167,9 +167,40 @@ pub struct Config {
     #[serde(default)]
     pub lsp: HashMap<String, LspServerConfig>,
 
+    /// Abbreviations expanded when typed before a space or punctuation
+    /// (e.g., "teh" -> "the"). `$0` in an expansion marks where the cursor goes.
+    /// Language-specific abbreviations go in `languages`.
+    #[serde(default)]
+    pub abbreviations: HashMap<String, String>,
+
+    /// Project tasks (e.g., "cargo build", "npm test"), run with "Run Task".
+    /// Usually defined in the project's `config.json`.
+    #[serde(default)]
+    pub tasks: Vec<TaskConfig>,
+
+    /// Files and folders to ignore everywhere, in .gitignore syntax (e.g.,
+    /// "*.log", "dist/"), on top of `.gitignore` and `.ignore` files. The
+    /// file explorer, file finder, project search and file watcher skip them.
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+
     /// Menu bar configuration
     #[serde(default)]
     pub menu: MenuConfig,
+
+    /// Settings of plugins, by plugin namespace. Plugins declare their
+    /// settings with `editor.defineSettings()`, which also adds them to the
+    /// settings UI.
+    #[serde(default)]
+    #[schemars(skip)]
+    pub plugins: HashMap<String, serde_json::Map<String, serde_json::Value>>,
+
+    /// Whether plugins may make HTTP requests with `editor.fetch()`, by
+    /// plugin name. A plugin that is not listed asks on its first request,
+    /// and the answer is saved here.
+    #[serde(default)]
+    #[schemars(skip)]
+    pub plugin_network_access: HashMap<String, bool>,
 }
 
 fn default_keybinding_map_name() -> KeybindingMapName {
@@ -191,14 +222,89 @@ pub struct EditorConfig {
     #[serde(default = "default_true")]
     pub auto_indent: bool,
 
+    /// Guess whether a file is indented with tabs or spaces, and how wide,
+    /// from its leading whitespace when it is opened. The language settings
+    /// apply to files with too little indentation to tell.
+    #[serde(default = "default_true")]
+    pub detect_indentation: bool,
+
     /// Show line numbers in the gutter
     #[serde(default = "default_true")]
     pub line_numbers: bool,
 
     /// Show line numbers relative to cursor position
+    /// (same as `line_number_mode` "hybrid")
     #[serde(default = "default_false")]
     pub relative_line_numbers: bool,
 
+    /// How the line numbers in the gutter count: "absolute", "relative"
+    /// (distance from the cursor line) or "hybrid" (distance, with the
+    /// cursor line's own number)
+    #[serde(default)]
+    pub line_number_mode: LineNumberMode,
+
+    /// Components of the gutter, left to right. Sign components next to each
+    /// other share one column, where the first with a sign on a line shows it.
+    #[serde(default = "default_gutter")]
+    pub gutter: Vec<GutterComponent>,
+
+    /// Draw vertical guides at each indentation level in leading whitespace
+    #[serde(default = "default_false")]
+    pub indent_guides: bool,
+
+    /// Pin the header lines of enclosing scopes (functions, classes, markdown
+    /// headings) at the top of the view while scrolling through them
+    #[serde(default = "default_false")]
+    pub sticky_scroll: bool,
+
+    /// Use the kitty keyboard protocol when the terminal supports it, so
+    /// keys like Ctrl+Shift+letter, Ctrl+Enter and Super combinations can be
+    /// bound. Takes effect on restart.
+    #[serde(default = "default_true")]
+    pub kitty_keyboard: bool,
+
+    /// Title shown by the terminal (its window or tab), updated as the
+    /// active buffer changes. `{file}` is the active buffer's name,
+    /// `{modified}` is `*` when it has unsaved changes, `{path}` its full
+    /// path and `{project}` the name of the working directory. The
+    /// terminal's own title comes back on exit. Empty leaves the title alone.
+    #[serde(default = "default_terminal_title")]
+    pub terminal_title: String,
+
+    /// How a key or action that can't do anything is signalled, such as
+    /// moving past the start or end of the buffer or an unknown key in a
+    /// prompt: "none", "visual" (flash the split) or "bell"
+    #[serde(default)]
+    pub error_feedback: ErrorFeedback,
+
+    /// Distraction-free mode: hide the menu bar, tab bars, status bar and
+    /// gutters, and center the text in a column of `zen_mode_width`
+    #[serde(default = "default_false")]
+    pub zen_mode: bool,
+
+    /// Width in columns of the centered text column in zen mode
+    #[serde(default = "default_zen_mode_width")]
+    pub zen_mode_width: u16,
+
+    /// Show the git branch, ahead/behind counts and uncommitted changes in
+    /// the status bar
+    #[serde(default = "default_true")]
+    pub show_git_status: bool,
+
+    /// Columns highlighted as rulers in the commit message buffer
+    #[serde(default = "default_commit_message_rulers")]
+    pub commit_message_rulers: Vec<usize>,
+
+    /// Command run by "Tags: Generate" to write the project's tags file
+    /// (universal-ctags)
+    #[serde(default = "default_ctags_command")]
+    pub ctags_command: String,
+
+    /// Regenerate the project's tags file in the background when a file in
+    /// the project is saved, if the project has one
+    #[serde(default = "default_true")]
+    pub regenerate_tags_on_save: bool,
+
     /// Minimum lines to keep visible above/below cursor when scrolling
     #[serde(default = "default_scroll_offset")]
     pub scroll_offset: usize,
@@ -211,6 +317,10 @@ pub struct EditorConfig {
     #[serde(default = "default_true")]
     pub line_wrap: bool,
 
+    /// Column "Reflow Paragraph" wraps text at
+    #[serde(default = "default_reflow_width")]
+    pub reflow_width: usize,
+
     /// Maximum time in milliseconds for syntax highlighting per frame
     #[serde(default = "default_highlight_timeout")]
     pub highlight_timeout_ms: u64,
@@ -233,6 +343,38 @@ pub struct EditorConfig {
     #[serde(default = "default_estimated_line_length")]
     pub estimated_line_length: usize,
 
+    /// Files with a line longer than this many bytes are treated as minified
+    /// and open without syntax highlighting or word highlighting, which would
+    /// be slow on such lines. 0 turns the check off.
+    #[serde(default = "default_minified_line_length")]
+    pub minified_line_length: usize,
+
+    /// When a binary file is opened, ask whether to show a hex view of it
+    #[serde(default = "default_false")]
+    pub binary_hex_view_prompt: bool,
+
+    /// Files larger than this many bytes open with syntax highlighting and
+    /// auto-revert turned off, and a prompt offers to make them read-only or
+    /// to turn the features back on. 0 turns the check off.
+    #[serde(default = "default_file_size_warning")]
+    pub file_size_warning_bytes: u64,
+
+    /// Like `file_size_warning_bytes`, for files with a line longer than this
+    /// many bytes. 0 turns the check off.
+    #[serde(default = "default_line_length_warning")]
+    pub line_length_warning_bytes: usize,
+
+    /// Buffers of at least this many bytes save in the background, with the
+    /// progress in the status bar, and can be edited while they save. 0 saves
+    /// every buffer in the foreground.
+    #[serde(default = "default_background_save_threshold")]
+    pub background_save_threshold_bytes: u64,
+
+    /// Read back what a save in the background wrote, and check its length
+    /// and checksum before it replaces the file
+    #[serde(default = "default_false")]
+    pub verify_background_saves: bool,
+
     /// Whether to enable LSP inlay hints (type hints, parameter hints, etc.)
     #[serde(default = "default_true")]
     pub enable_inlay_hints: bool,
@@ -257,6 +399,15 @@ pub struct EditorConfig {
     #[serde(default = "default_highlight_context_bytes")]
     pub highlight_context_bytes: usize,
 
+    /// Highlight the other occurrences of the word under the cursor, or of
+    /// the selected text, in the visible part of the buffer
+    #[serde(default = "default_true")]
+    pub highlight_occurrences: bool,
+
+    /// Milliseconds the cursor has to rest before occurrences are highlighted
+    #[serde(default = "default_highlight_occurrences_delay")]
+    pub highlight_occurrences_delay_ms: u64,
+
     /// Whether mouse hover triggers LSP hover requests.
     /// When enabled, hovering over code with the mouse will show documentation.
     /// Default: true
@@ -270,11 +421,35 @@ pub struct EditorConfig {
     pub mouse_hover_delay_ms: u64,
 
     /// Time window in milliseconds for detecting double-clicks.
-    /// Two clicks within this time are treated as a double-click (word selection).
+    /// Two clicks within this time are treated as a double-click (word selection),
+    /// three as a triple-click (line selection).
     /// Default: 500ms
     #[serde(default = "default_double_click_time")]
     pub double_click_time_ms: u64,
 
+    /// Lines scrolled per notch of the mouse wheel.
+    /// Default: 3
+    #[serde(default = "default_mouse_scroll_lines")]
+    pub mouse_scroll_lines: usize,
+
+    /// Whether Ctrl+click in the text adds a cursor instead of moving it.
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub ctrl_click_adds_cursor: bool,
+
+    /// Time window in milliseconds within which typed characters are undone
+    /// together. Each word typed is its own undo step; moving the cursor
+    /// also starts a new one. 0 undoes every character separately.
+    /// Default: 1000ms
+    #[serde(default = "default_undo_group_timeout")]
+    pub undo_group_timeout_ms: u64,
+
+    /// Whether unmodified buffers are reloaded when their file changes on
+    /// disk. Can be toggled while editing with "Toggle Auto-Revert".
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub auto_revert: bool,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -282,12 +457,61 @@ pub struct EditorConfig {
     #[serde(default = "default_auto_revert_poll_interval")]
     pub auto_revert_poll_interval_ms: u64,
 
+    /// Window in milliseconds for detecting files that change constantly
+    /// (e.g., logs being written). A file reverted 10 times within this
+    /// window turns auto-revert off.
+    /// Default: 10000ms (10 seconds)
+    #[serde(default = "default_auto_revert_debounce")]
+    pub auto_revert_debounce_ms: u64,
+
+    /// Files whose changes are polled by opening them rather than only
+    /// reading their metadata, in .gitignore syntax against absolute paths
+    /// (e.g., "/mnt/nfs/"). Network filesystems such as NFS, and some
+    /// container mounts, cache file metadata, so changes made elsewhere can
+    /// go unnoticed for a minute; opening the file makes them look again.
+    /// Default: none
+    #[serde(default)]
+    pub auto_revert_uncached_paths: Vec<String>,
+
     /// Poll interval in milliseconds for refreshing expanded directories in the file explorer.
     /// Directory modification times are checked at this interval to detect new/deleted files.
     /// Lower values detect changes faster but use more CPU.
     /// Default: 3000ms (3 seconds)
     #[serde(default = "default_file_tree_poll_interval")]
     pub file_tree_poll_interval_ms: u64,
+
+    /// How long toast notifications stay on screen, in milliseconds.
+    /// Error notifications stay twice as long.
+    /// Default: 4000ms (4 seconds)
+    #[serde(default = "default_notification_timeout")]
+    pub notification_timeout_ms: u64,
+
+    /// Most times per second the screen is redrawn. Key presses are shown
+    /// at once; mouse, scroll and resize events are drawn at most this
+    /// often, and background updates (job progress, file watchers, language
+    /// servers) a quarter as often. Lower values help over slow remote
+    /// connections. 0 removes the cap.
+    /// Default: 60
+    #[serde(default = "default_max_fps")]
+    pub max_fps: u32,
+
+    /// Whether to keep the undo history of files across sessions.
+    /// The history is saved when a file is closed or the editor exits, and
+    /// restored when the file is opened again unchanged.
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub persistent_undo: bool,
+
+    /// Maximum size of the undo history kept for a file, in bytes.
+    /// The oldest edits are dropped first.
+    /// Default: 1MB
+    #[serde(default = "default_persistent_undo_max_bytes")]
+    pub persistent_undo_max_bytes: u64,
+
+    /// Number of days a file's undo history is kept after it was last saved.
+    /// Default: 30 days
+    #[serde(default = "default_persistent_undo_max_age_days")]
+    pub persistent_undo_max_age_days: u32,
 }
 
 fn default_tab_size() -> usize {
@@ -303,6 +527,22 @@ fn default_large_file_threshold() -> u64 {
     LARGE_FILE_THRESHOLD_BYTES
 }
 
+fn default_minified_line_length() -> usize {
+    10_000
+}
+
+fn default_file_size_warning() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn default_line_length_warning() -> usize {
+    1024 * 1024
+}
+
+fn default_background_save_threshold() -> u64 {
+    10 * 1024 * 1024
+}
+
 fn default_true() -> bool {
     true
 }
@@ -315,6 +555,31 @@ fn default_scroll_offset() -> usize {
     3
 }
 
+fn default_gutter() -> Vec<GutterComponent> {
+    vec![
+        GutterComponent::DiagnosticSigns,
+        GutterComponent::GitSigns,
+        GutterComponent::Bookmarks,
+        GutterComponent::LineNumbers,
+    ]
+}
+
+fn default_terminal_title() -> String {
+    "{file}{modified} — {project} — fresh".to_string()
+}
+
+fn default_zen_mode_width() -> u16 {
+    100
+}
+
+fn default_commit_message_rulers() -> Vec<usize> {
+    vec![50, 72]
+}
+
+fn default_ctags_command() -> String {
+    "ctags".to_string()
+}
+
 fn default_highlight_timeout() -> u64 {
     5
 }
@@ -323,6 +588,10 @@ fn default_snapshot_interval() -> usize {
     100
 }
 
+fn default_reflow_width() -> usize {
+    80
+}
+
 fn default_estimated_line_length() -> usize {
     80
 }
@@ -331,6 +600,10 @@ fn default_auto_save_interval() -> u32 {
     2 // Auto-save every 2 seconds for fast recovery
 }
 
+fn default_highlight_occurrences_delay() -> u64 {
+    150
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
@@ -343,37 +616,110 @@ fn default_double_click_time() -> u64 {
     500 // 500ms window for detecting double-clicks
 }
 
+fn default_mouse_scroll_lines() -> usize {
+    3
+}
+
+fn default_undo_group_timeout() -> u64 {
+    1000 // typing pauses longer than 1 second start a new undo step
+}
+
+fn default_max_fps() -> u32 {
+    60
+}
+
 fn default_auto_revert_poll_interval() -> u64 {
     2000 // 2 seconds between file mtime checks
 }
 
+fn default_auto_revert_debounce() -> u64 {
+    10_000 // 10 seconds to count reverts in
+}
+
 fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
 
+fn default_notification_timeout() -> u64 {
+    4000 // 4 seconds before a toast disappears
+}
+
+fn default_persistent_undo_max_bytes() -> u64 {
+    1024 * 1024 // 1MB of edits per file
+}
+
+fn default_persistent_undo_max_age_days() -> u32 {
+    30
+}
+
 impl Default for EditorConfig {
     fn default() -> Self {
         Self {
             tab_size: default_tab_size(),
             auto_indent: true,
+            detect_indentation: true,
             line_numbers: true,
             relative_line_numbers: false,
+            line_number_mode: LineNumberMode::default(),
+            gutter: default_gutter(),
+            indent_guides: false,
+            sticky_scroll: false,
+            kitty_keyboard: true,
+            terminal_title: default_terminal_title(),
+            error_feedback: ErrorFeedback::default(),
+            zen_mode: false,
+            zen_mode_width: default_zen_mode_width(),
+            show_git_status: true,
+            commit_message_rulers: default_commit_message_rulers(),
+            ctags_command: default_ctags_command(),
+            regenerate_tags_on_save: true,
             scroll_offset: default_scroll_offset(),
             syntax_highlighting: true,
             line_wrap: true,
+            reflow_width: default_reflow_width(),
             highlight_timeout_ms: default_highlight_timeout(),
             snapshot_interval: default_snapshot_interval(),
             large_file_threshold_bytes: default_large_file_threshold(),
             estimated_line_length: default_estimated_line_length(),
+            minified_line_length: default_minified_line_length(),
+            binary_hex_view_prompt: false,
+            file_size_warning_bytes: default_file_size_warning(),
+            line_length_warning_bytes: default_line_length_warning(),
+            background_save_threshold_bytes: default_background_save_threshold(),
+            verify_background_saves: false,
             enable_inlay_hints: true,
             recovery_enabled: true,
             auto_save_interval_secs: default_auto_save_interval(),
             highlight_context_bytes: default_highlight_context_bytes(),
+            highlight_occurrences: true,
+            highlight_occurrences_delay_ms: default_highlight_occurrences_delay(),
             mouse_hover_enabled: true,
             mouse_hover_delay_ms: default_mouse_hover_delay(),
             double_click_time_ms: default_double_click_time(),
+            mouse_scroll_lines: default_mouse_scroll_lines(),
+            ctrl_click_adds_cursor: true,
+            undo_group_timeout_ms: default_undo_group_timeout(),
+            auto_revert: true,
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
+            auto_revert_debounce_ms: default_auto_revert_debounce(),
+            auto_revert_uncached_paths: Vec::new(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
+            notification_timeout_ms: default_notification_timeout(),
+            max_fps: default_max_fps(),
+            persistent_undo: true,
+            persistent_undo_max_bytes: default_persistent_undo_max_bytes(),
+            persistent_undo_max_age_days: default_persistent_undo_max_age_days(),
+        }
+    }
+}
+
+impl EditorConfig {
+    /// Line number mode to render with, honoring `relative_line_numbers`
+    pub fn effective_line_number_mode(&self) -> LineNumberMode {
+        if self.relative_line_numbers && self.line_number_mode == LineNumberMode::Absolute {
+            LineNumberMode::Hybrid
+        } else {
+            self.line_number_mode
         }
     }
 }
@@ -540,6 +886,22 @@ pub struct OnSaveAction {
     pub enabled: bool,
 }
 
+/// A named project command, run with "Run Task"
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/name"))]
+pub struct TaskConfig {
+    /// Name shown when choosing a task (e.g., "build")
+    pub name: String,
+
+    /// Command line run through the shell (e.g., "cargo build")
+    pub command: String,
+
+    /// Working directory for the command, relative to the project root
+    /// (defaults to the project root)
+    #[serde(default)]
+    pub working_dir: Option<String>,
+}
+
 fn default_on_save_timeout() -> u64 {
     10000
 }
@@ -606,6 +968,63 @@ pub struct LanguageConfig {
     /// Note: Use `formatter` + `format_on_save` for formatting, not on_save
     #[serde(default)]
     pub on_save: Vec<OnSaveAction>,
+
+    /// Abbreviations for this language, merged with (and overriding) the
+    /// global `abbreviations`
+    #[serde(default)]
+    pub abbreviations: HashMap<String, String>,
+
+    /// Command that starts an interactive REPL for this language (e.g.
+    /// "python3"), used by the "REPL: Send ..." commands
+    #[serde(default)]
+    pub repl: Option<String>,
+}
+
+/// How line numbers in the gutter count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LineNumberMode {
+    /// Every line shows its own number
+    #[default]
+    Absolute,
+    /// Every line shows its distance from the cursor line
+    Relative,
+    /// The cursor line shows its own number, the others their distance
+    Hybrid,
+}
+
+/// A component of the gutter, the margin left of the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GutterComponent {
+    /// Line numbers, counted as set by `line_number_mode`
+    LineNumbers,
+    /// Markers on lines with diagnostics
+    DiagnosticSigns,
+    /// Line indicators set by plugins, such as the git gutter's change markers
+    GitSigns,
+    /// Bookmarks, shown by their key
+    Bookmarks,
+}
+
+impl GutterComponent {
+    /// Whether the component draws a one-character sign rather than numbers
+    pub fn is_sign(self) -> bool {
+        !matches!(self, Self::LineNumbers)
+    }
+}
+
+/// How rejected keys and actions are signalled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorFeedback {
+    /// Nothing happens
+    #[default]
+    None,
+    /// The split the key went to flashes
+    Visual,
+    /// The terminal rings its bell
+    Bell,
 }
 
 /// Preference for which syntax highlighting backend to use
@@ -736,7 +1155,12 @@ impl Default for Config {
             active_keybinding_map: default_keybinding_map_name(),
             languages: Self::default_languages(),
             lsp: Self::default_lsp_config(),
+            abbreviations: HashMap::new(),
+            tasks: vec![],
+            ignore_patterns: vec![],
             menu: MenuConfig::default(),
+            plugins: HashMap::new(),
+            plugin_network_access: HashMap::new(),
         }
     }
 }
@@ -777,6 +1201,16 @@ impl Config {
         Ok(config)
     }
 
+    /// Drop the user's keybindings, keymaps and theme, which `--safe-mode`
+    /// starts without
+    pub fn apply_safe_mode(&mut self) {
+        let defaults = Self::default();
+        self.keybindings = defaults.keybindings;
+        self.keybinding_maps = defaults.keybinding_maps;
+        self.active_keybinding_map = defaults.active_keybinding_map;
+        self.theme = defaults.theme;
+    }
+
     /// Merge default values for HashMap fields that should combine user entries with defaults.
     ///
     /// This is called after deserializing user config to ensure that:
@@ -889,6 +1323,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -913,6 +1349,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: Some("node".to_string()),
             },
         );
 
@@ -937,6 +1375,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -965,6 +1405,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: Some("python3".to_string()),
             },
         );
 
@@ -989,6 +1431,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1020,6 +1464,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1039,6 +1485,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1069,6 +1517,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1092,6 +1542,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1111,6 +1563,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1135,6 +1589,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1154,6 +1610,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1178,6 +1636,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1197,6 +1657,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1222,6 +1684,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
 
@@ -1403,6 +1867,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "New Scratch Buffer".to_string(),
+                        action: "new_scratch_buffer".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: "Open File...".to_string(),
                         action: "open".to_string(),
@@ -1432,6 +1903,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Auto-Revert".to_string(),
+                        action: "toggle_auto_revert".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::AUTO_REVERT.to_string()),
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: "Close Buffer".to_string(),
@@ -1440,6 +1918,28 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Close All Saved Buffers".to_string(),
+                        action: "close_saved_buffers".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Separator { separator: true },
+                    MenuItem::Action {
+                        label: "Rename File...".to_string(),
+                        action: "rename_file".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Duplicate File".to_string(),
+                        action: "duplicate_file".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: "Switch Project...".to_string(),
@@ -1448,6 +1948,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Add Folder to Workspace...".to_string(),
+                        action: "add_workspace_folder".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: "Quit".to_string(),
                         action: "quit".to_string(),
@@ -1501,6 +2008,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Paste from History...".to_string(),
+                        action: "paste_from_history".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: "Select All".to_string(),
@@ -1553,6 +2067,20 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Toggle Comment".to_string(),
+                        action: "toggle_comment".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Join Lines".to_string(),
+                        action: "join_lines".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: "Format Buffer".to_string(),
                         action: "format_buffer".to_string(),
@@ -1560,6 +2088,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Format Selection".to_string(),
+                        action: "format_selection".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::HAS_SELECTION.to_string()),
+                        checkbox: None,
+                    },
                 ],
             },
             // View menu
@@ -1595,6 +2130,13 @@ impl Config {
                         when: None,
                         checkbox: Some(context_keys::MOUSE_CAPTURE.to_string()),
                     },
+                    MenuItem::Action {
+                        label: "Zen Mode".to_string(),
+                        action: "toggle_zen_mode".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::ZEN_MODE.to_string()),
+                    },
                     // Note: Compose Mode removed from menu - markdown_compose plugin provides this
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
@@ -1799,6 +2341,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Select All Matches".to_string(),
+                        action: "select_all_matches".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: "Remove Secondary Cursors".to_string(),
                         action: "remove_secondary_cursors".to_string(),
@@ -1819,6 +2368,13 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: "Go to Matching Bracket".to_string(),
+                        action: "goto_matching_bracket".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: "Go to Definition".to_string(),
                         action: "lsp_goto_definition".to_string(),
@@ -1834,6 +2390,36 @@ impl Config {
                         checkbox: None,
                     },
                     MenuItem::Separator { separator: true },
+                    MenuItem::Action {
+                        label: "Next Error".to_string(),
+                        action: "jump_to_next_error".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Previous Error".to_string(),
+                        action: "jump_to_previous_error".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Separator { separator: true },
+                    MenuItem::Action {
+                        label: "Back".to_string(),
+                        action: "navigate_back".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Forward".to_string(),
+                        action: "navigate_forward".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: "Next Buffer".to_string(),
                         action: "next_buffer".to_string(),
@@ -1849,6 +2435,21 @@ impl Config {
                         checkbox: None,
                     },
                     MenuItem::Separator { separator: true },
+                    MenuItem::Action {
+                        label: "Set Bookmark...".to_string(),
+                        action: "prompt_set_bookmark".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Jump to Bookmark...".to_string(),
+                        action: "prompt_jump_to_bookmark".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: "Command Palette...".to_string(),
                         action: "command_palette".to_string(),
@@ -2027,6 +2628,35 @@ impl Config {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Separator { separator: true },
+                    MenuItem::Action {
+                        label: "Notifications".to_string(),
+                        action: "show_notifications".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Installed Plugins".to_string(),
+                        action: "show_plugins".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "System Health".to_string(),
+                        action: "show_system_health".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: "Config Doctor".to_string(),
+                        action: "config_doctor".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                 ],
             },
         ]
@@ -2256,6 +2886,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_default_menu_actions_are_known() {
+        // Every default menu item should run a built-in action rather than
+        // falling through to a plugin action of the same name
+        fn check(items: &[MenuItem], path: &str) {
+            for item in items {
+                match item.expand_dynamic() {
+                    MenuItem::Action {
+                        label,
+                        action,
+                        args,
+                        ..
+                    } => assert!(
+                        crate::input::keybindings::Action::from_str(&action, &args).is_some(),
+                        "{path} > {label}: unknown action {action:?}"
+                    ),
+                    MenuItem::Submenu { label, items } => {
+                        check(&items, &format!("{path} > {label}"))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for menu in Config::default_menus() {
+            check(&menu.items, &menu.label);
+        }
+    }
+
     #[test]
     fn test_non_dynamic_item_unchanged() {
         // Non-DynamicSubmenu items should be unchanged by expand_dynamic
@@ -42,7 +42,7 @@ impl Config {
     /// 2. System config paths (see `system_config_paths()`)
     ///
     /// Only returns paths that exist on disk.
-    fn config_search_paths(working_dir: &Path) -> Vec<PathBuf> {
+    pub(crate) fn config_search_paths(working_dir: &Path) -> Vec<PathBuf> {
         let local = Self::local_config_path(working_dir);
         let mut paths = Vec::with_capacity(3);
 
@@ -187,6 +187,10 @@ pub struct DirectoryContext {
     /// e.g., ~/.local/share/fresh on Linux, ~/Library/Application Support/fresh on macOS
     pub data_dir: std::path::PathBuf,
 
+    /// State directory for history that can be lost without harm (undo history)
+    /// e.g., ~/.local/state/fresh on Linux; the data directory elsewhere
+    pub state_dir: std::path::PathBuf,
+
     /// Config directory for user configuration
     /// e.g., ~/.config/fresh on Linux, ~/Library/Application Support/fresh on macOS
     pub config_dir: std::path::PathBuf,
@@ -214,6 +218,11 @@ impl DirectoryContext {
             })?
             .join("fresh");
 
+        // Only Linux has an XDG state directory
+        let state_dir = dirs::state_dir()
+            .map(|dir| dir.join("fresh"))
+            .unwrap_or_else(|| data_dir.clone());
+
         #[allow(unused_mut)] // mut needed on macOS only
         let mut config_dir = dirs::config_dir()
             .ok_or_else(|| {
@@ -235,6 +244,7 @@ impl DirectoryContext {
 
         Ok(Self {
             data_dir,
+            state_dir,
             config_dir,
             home_dir: dirs::home_dir(),
             documents_dir: dirs::document_dir(),
@@ -247,6 +257,7 @@ impl DirectoryContext {
     pub fn for_testing(temp_dir: &std::path::Path) -> Self {
         Self {
             data_dir: temp_dir.join("data"),
+            state_dir: temp_dir.join("state"),
             config_dir: temp_dir.join("config"),
             home_dir: Some(temp_dir.join("home")),
             documents_dir: Some(temp_dir.join("documents")),
@@ -264,6 +275,16 @@ impl DirectoryContext {
         self.data_dir.join("sessions")
     }
 
+    /// Get the scratch buffers directory path
+    pub fn scratch_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("scratch")
+    }
+
+    /// Get the undo history directory path
+    pub fn undo_history_dir(&self) -> std::path::PathBuf {
+        self.state_dir.join("undo")
+    }
+
     /// Get the search history file path
     pub fn search_history_path(&self) -> std::path::PathBuf {
         self.data_dir.join("search_history.json")
@@ -0,0 +1,165 @@
+//! Abbreviation expansion
+//!
+//! Abbreviations are configured as `typed -> expansion` pairs, globally and
+//! per language. When the user types a word boundary (space or punctuation)
+//! right after an abbreviation, the abbreviation is replaced by its
+//! expansion. An expansion containing `$0` is a snippet: the cursor moves
+//! to the `$0` marker and the boundary character is not inserted.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Marker for the cursor position inside an expansion
+const CURSOR_MARKER: &str = "$0";
+
+/// Where an active abbreviation was configured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbbreviationScope {
+    Global,
+    Language(String),
+}
+
+/// An abbreviation in effect for a buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Abbreviation {
+    pub expansion: String,
+    pub scope: AbbreviationScope,
+}
+
+/// Merge global and language abbreviations; language entries win
+pub fn active_abbreviations(
+    global: &HashMap<String, String>,
+    language: Option<(&str, &HashMap<String, String>)>,
+) -> BTreeMap<String, Abbreviation> {
+    let mut active: BTreeMap<String, Abbreviation> = global
+        .iter()
+        .map(|(typed, expansion)| {
+            (
+                typed.clone(),
+                Abbreviation {
+                    expansion: expansion.clone(),
+                    scope: AbbreviationScope::Global,
+                },
+            )
+        })
+        .collect();
+
+    if let Some((name, abbreviations)) = language {
+        for (typed, expansion) in abbreviations {
+            active.insert(
+                typed.clone(),
+                Abbreviation {
+                    expansion: expansion.clone(),
+                    scope: AbbreviationScope::Language(name.to_string()),
+                },
+            );
+        }
+    }
+
+    active.retain(|typed, _| !typed.is_empty() && !typed.chars().any(char::is_whitespace));
+    active
+}
+
+/// Whether a character is part of a word (typing anything else is a word
+/// boundary that triggers expansion)
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Find the abbreviation that `text_before_cursor` ends with.
+///
+/// The abbreviation must start at a word boundary: at the start of the text,
+/// after whitespace, or (for abbreviations starting with a word character)
+/// after punctuation. The longest match wins.
+pub fn find_abbreviation<'a>(
+    text_before_cursor: &str,
+    abbreviations: &'a BTreeMap<String, Abbreviation>,
+) -> Option<(&'a str, &'a Abbreviation)> {
+    abbreviations
+        .iter()
+        .filter(|(typed, _)| {
+            let Some(prefix) = text_before_cursor.strip_suffix(typed.as_str()) else {
+                return false;
+            };
+            match prefix.chars().next_back() {
+                None => true,
+                Some(prev) if prev.is_whitespace() => true,
+                Some(prev) => typed.chars().next().is_some_and(is_word_char) && !is_word_char(prev),
+            }
+        })
+        .max_by_key(|(typed, _)| typed.len())
+        .map(|(typed, abbreviation)| (typed.as_str(), abbreviation))
+}
+
+/// Split an expansion into the text to insert and the cursor offset within
+/// it (`Some` only when the expansion contains `$0`)
+pub fn parse_expansion(expansion: &str) -> (String, Option<usize>) {
+    match expansion.find(CURSOR_MARKER) {
+        Some(offset) => (expansion.replacen(CURSOR_MARKER, "", 1), Some(offset)),
+        None => (expansion.to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abbreviations(pairs: &[(&str, &str)]) -> BTreeMap<String, Abbreviation> {
+        let global = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        active_abbreviations(&global, None)
+    }
+
+    #[test]
+    fn test_language_overrides_global() {
+        let global = HashMap::from([
+            ("teh".to_string(), "the".to_string()),
+            ("fn-".to_string(), "function".to_string()),
+        ]);
+        let rust = HashMap::from([("fn-".to_string(), "fn $0() {}".to_string())]);
+        let active = active_abbreviations(&global, Some(("rust", &rust)));
+
+        assert_eq!(active["teh"].scope, AbbreviationScope::Global);
+        assert_eq!(active["fn-"].expansion, "fn $0() {}");
+        assert_eq!(
+            active["fn-"].scope,
+            AbbreviationScope::Language("rust".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_abbreviation_at_word_boundary() {
+        let active = abbreviations(&[("teh", "the"), ("fn-", "function")]);
+
+        assert_eq!(find_abbreviation("teh", &active).unwrap().0, "teh");
+        assert_eq!(find_abbreviation("see teh", &active).unwrap().0, "teh");
+        assert_eq!(find_abbreviation("(teh", &active).unwrap().0, "teh");
+        assert_eq!(find_abbreviation("  fn-", &active).unwrap().0, "fn-");
+        // Part of a longer word
+        assert!(find_abbreviation("poteh", &active).is_none());
+        assert!(find_abbreviation("xfn-", &active).is_none());
+        assert!(find_abbreviation("", &active).is_none());
+    }
+
+    #[test]
+    fn test_find_abbreviation_prefers_longest() {
+        let active = abbreviations(&[("s", "short"), ("-s", "dash")]);
+        assert_eq!(find_abbreviation("a -s", &active).unwrap().0, "-s");
+    }
+
+    #[test]
+    fn test_parse_expansion() {
+        assert_eq!(parse_expansion("the"), ("the".to_string(), None));
+        assert_eq!(
+            parse_expansion("fn $0() {}"),
+            ("fn () {}".to_string(), Some(3))
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys_ignored() {
+        let active = abbreviations(&[("", "empty"), ("a b", "space")]);
+        assert!(active.is_empty());
+    }
+}
@@ -4,7 +4,12 @@ use crate::input::keybindings::Action;
 use crate::model::buffer::{Buffer, LineEnding};
 use crate::model::cursor::{Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
-use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
+use crate::primitives::display_width::{
+    byte_offset_at_visual_column_with_tabs, visual_column_at_byte_with_tabs,
+};
+use crate::primitives::prose::{
+    find_paragraph_end, find_paragraph_start, find_sentence_start_left, find_sentence_start_right,
+};
 use crate::primitives::word_navigation::{
     find_word_end, find_word_start, find_word_start_left, find_word_start_right,
 };
@@ -43,26 +48,41 @@ fn pos_2d_to_byte(buffer: &Buffer, pos: Position2D) -> usize {
     line_start + clamped_col
 }
 
-/// Calculate the visual column (display width) at the cursor position.
-/// Returns (visual_column, byte_column_within_line).
-fn calculate_visual_column(
+/// Where moving the cursor at `position` a line up (or down) lands, with the
+/// goal visual column to keep for the next vertical move.
+///
+/// The goal is the cursor's sticky column if set (vertical moves keep it, so
+/// the cursor returns to it after passing short lines), else its current
+/// visual column. Columns count tabs and wide characters as drawn. `None` on
+/// the first (last) line.
+fn vertical_move_target(
     buffer: &mut Buffer,
-    cursor_position: usize,
+    position: usize,
+    sticky_column: usize,
+    up: bool,
+    tab_size: usize,
     estimated_line_length: usize,
-) -> (usize, usize) {
-    let mut iter = buffer.line_iterator(cursor_position, estimated_line_length);
-    let current_line_start = iter.current_position();
-    let byte_column = cursor_position.saturating_sub(current_line_start);
-
-    if let Some((_, line_content)) = iter.next() {
-        if byte_column > 0 && byte_column <= line_content.len() {
-            (str_width(&line_content[..byte_column]), byte_column)
-        } else {
-            (byte_column, byte_column) // Fallback for edge cases
-        }
+) -> Option<(usize, usize)> {
+    let goal_column = if sticky_column > 0 {
+        sticky_column
     } else {
-        (byte_column, byte_column) // Fallback
-    }
+        let mut iter = buffer.line_iterator(position, estimated_line_length);
+        let line_start = iter.current_position();
+        let line = iter.next().map(|(_, line)| line).unwrap_or_default();
+        visual_column_at_byte_with_tabs(&line, position - line_start, tab_size)
+    };
+
+    let mut iter = buffer.line_iterator(position, estimated_line_length);
+    let (target_start, target_line) = if up {
+        iter.prev()?
+    } else {
+        // Skip the current line
+        iter.next();
+        iter.next()?
+    };
+    let target_line = target_line.trim_end_matches(LINE_ENDING_CHARS);
+    let byte_offset = byte_offset_at_visual_column_with_tabs(target_line, goal_column, tab_size);
+    Some((target_start + byte_offset, goal_column))
 }
 
 /// Pattern for matching line ending characters (\r and \n)
@@ -74,6 +94,34 @@ fn content_len_without_line_ending(content: &str) -> usize {
     content.trim_end_matches(LINE_ENDING_CHARS).len()
 }
 
+/// Where smart home moves a cursor on the line starting at `line_start`:
+/// the first non-whitespace character, or the line start if already there.
+/// On a blank line the end of its whitespace takes the place of the first
+/// non-whitespace character.
+fn smart_home_position(line_start: usize, line_content: &str, position: usize) -> usize {
+    let text = line_content.trim_end_matches(LINE_ENDING_CHARS);
+    let indent_end = line_start + (text.len() - text.trim_start().len());
+    if position == indent_end {
+        line_start
+    } else {
+        indent_end
+    }
+}
+
+/// Where smart end moves a cursor on the line starting at `line_start`:
+/// after the last non-whitespace character, or the line end if already
+/// there. Lines without trailing whitespace go straight to the line end.
+fn smart_end_position(line_start: usize, line_content: &str, position: usize) -> usize {
+    let text = line_content.trim_end_matches(LINE_ENDING_CHARS);
+    let line_end = line_start + text.len();
+    let text_end = line_start + text.trim_end().len();
+    if position == text_end || text_end == line_start {
+        line_end
+    } else {
+        text_end
+    }
+}
+
 /// Adjust position after moving left in CRLF mode.
 /// If we land on \n that's preceded by \r, skip back to the \r.
 /// This ensures the cursor never sits between \r and \n.
@@ -1049,100 +1097,35 @@ pub fn action_to_events(
             }
         }
 
-        Action::MoveUp => {
+        Action::MoveUp | Action::MoveDown => {
+            let up = matches!(action, Action::MoveUp);
             for (cursor_id, cursor) in state.cursors.iter() {
-                // Calculate visual column first (iterator is dropped after this call)
-                let (current_visual_column, _) = calculate_visual_column(
+                let Some((new_pos, goal_column)) = vertical_move_target(
                     &mut state.buffer,
                     cursor.position,
+                    cursor.sticky_column,
+                    up,
+                    state.tab_size,
                     estimated_line_length,
-                );
-
-                // Use sticky_column if set (now stores visual column), otherwise use current visual column
-                let goal_visual_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_visual_column
+                ) else {
+                    continue;
                 };
 
-                // Now create iterator for navigation
-                let mut iter = state
-                    .buffer
-                    .line_iterator(cursor.position, estimated_line_length);
-
-                if let Some((prev_line_start, prev_line_content)) = iter.prev() {
-                    // Calculate byte offset from visual column, ensuring valid character boundary
-                    let prev_line_text = prev_line_content.trim_end_matches('\n');
-                    let byte_offset =
-                        byte_offset_at_visual_column(prev_line_text, goal_visual_column);
-                    let new_pos = prev_line_start + byte_offset;
-
-                    // Preserve anchor if deselect_on_move is false (Emacs mark mode)
-                    let new_anchor = if cursor.deselect_on_move {
-                        None
-                    } else {
-                        cursor.anchor
-                    };
-                    events.push(Event::MoveCursor {
-                        cursor_id,
-                        old_position: cursor.position,
-                        new_position: new_pos,
-                        old_anchor: cursor.anchor,
-                        new_anchor,
-                        old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_visual_column, // Preserve the goal visual column
-                    });
-                }
-            }
-        }
-
-        Action::MoveDown => {
-            for (cursor_id, cursor) in state.cursors.iter() {
-                // Calculate visual column first (iterator is dropped after this call)
-                let (current_visual_column, _) = calculate_visual_column(
-                    &mut state.buffer,
-                    cursor.position,
-                    estimated_line_length,
-                );
-
-                // Use sticky_column if set (now stores visual column), otherwise use current visual column
-                let goal_visual_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
+                // Preserve anchor if deselect_on_move is false (Emacs mark mode)
+                let new_anchor = if cursor.deselect_on_move {
+                    None
                 } else {
-                    current_visual_column
+                    cursor.anchor
                 };
-
-                // Now create iterator for navigation
-                let mut iter = state
-                    .buffer
-                    .line_iterator(cursor.position, estimated_line_length);
-
-                // Consume current line
-                iter.next();
-
-                if let Some((next_line_start, next_line_content)) = iter.next() {
-                    // Calculate byte offset from visual column, ensuring valid character boundary
-                    let next_line_text = next_line_content.trim_end_matches('\n');
-                    let byte_offset =
-                        byte_offset_at_visual_column(next_line_text, goal_visual_column);
-                    let new_pos = next_line_start + byte_offset;
-
-                    // Preserve anchor if deselect_on_move is false (Emacs mark mode)
-                    let new_anchor = if cursor.deselect_on_move {
-                        None
-                    } else {
-                        cursor.anchor
-                    };
-                    events.push(Event::MoveCursor {
-                        cursor_id,
-                        old_position: cursor.position,
-                        new_position: new_pos,
-                        old_anchor: cursor.anchor,
-                        new_anchor,
-                        old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_visual_column, // Preserve the goal visual column
-                    });
-                }
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: goal_column, // Preserve the goal visual column
+                });
             }
         }
 
@@ -1200,6 +1183,40 @@ pub fn action_to_events(
             }
         }
 
+        Action::SmartHome | Action::SmartEnd | Action::SelectSmartHome | Action::SelectSmartEnd => {
+            let select = matches!(action, Action::SelectSmartHome | Action::SelectSmartEnd);
+            let home = matches!(action, Action::SmartHome | Action::SelectSmartHome);
+            for (cursor_id, cursor) in state.cursors.iter() {
+                let position = cursor.position;
+                let mut iter = state.buffer.line_iterator(position, estimated_line_length);
+                let Some((line_start, line_content)) = iter.next() else {
+                    continue;
+                };
+                let new_pos = if home {
+                    smart_home_position(line_start, &line_content, position)
+                } else {
+                    smart_end_position(line_start, &line_content, position)
+                };
+                let new_anchor = if select {
+                    Some(cursor.anchor.unwrap_or(position))
+                } else if cursor.deselect_on_move {
+                    None
+                } else {
+                    // Emacs mark mode keeps the selection
+                    cursor.anchor
+                };
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
         Action::MoveWordLeft => {
             for (cursor_id, cursor) in state.cursors.iter() {
                 let new_pos = find_word_start_left(&state.buffer, cursor.position);
@@ -1242,6 +1259,55 @@ pub fn action_to_events(
             }
         }
 
+        Action::MoveSentenceLeft
+        | Action::MoveSentenceRight
+        | Action::MoveParagraphUp
+        | Action::MoveParagraphDown
+        | Action::SelectSentenceLeft
+        | Action::SelectSentenceRight
+        | Action::SelectParagraphUp
+        | Action::SelectParagraphDown => {
+            let select = matches!(
+                action,
+                Action::SelectSentenceLeft
+                    | Action::SelectSentenceRight
+                    | Action::SelectParagraphUp
+                    | Action::SelectParagraphDown
+            );
+            for (cursor_id, cursor) in state.cursors.iter() {
+                let position = cursor.position;
+                let new_pos = match action {
+                    Action::MoveSentenceLeft | Action::SelectSentenceLeft => {
+                        find_sentence_start_left(&state.buffer, position)
+                    }
+                    Action::MoveSentenceRight | Action::SelectSentenceRight => {
+                        find_sentence_start_right(&state.buffer, position)
+                    }
+                    Action::MoveParagraphUp | Action::SelectParagraphUp => {
+                        find_paragraph_start(&mut state.buffer, position, estimated_line_length)
+                    }
+                    _ => find_paragraph_end(&mut state.buffer, position, estimated_line_length),
+                };
+                let new_anchor = if select {
+                    Some(cursor.anchor.unwrap_or(position))
+                } else if cursor.deselect_on_move {
+                    None
+                } else {
+                    // Emacs mark mode keeps the selection
+                    cursor.anchor
+                };
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
         Action::MoveDocumentStart => {
             for (cursor_id, cursor) in state.cursors.iter() {
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
@@ -1416,71 +1482,30 @@ pub fn action_to_events(
             }
         }
 
-        Action::SelectUp => {
-            for (cursor_id, cursor) in state.cursors.iter() {
-                let mut iter = state
-                    .buffer
-                    .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
-                let anchor = cursor.anchor.unwrap_or(cursor.position);
-
-                // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
-                };
-
-                if let Some((prev_line_start, prev_line_content)) = iter.prev() {
-                    let prev_line_len = prev_line_content.trim_end_matches('\n').len();
-                    let new_pos = prev_line_start + goal_column.min(prev_line_len);
-
-                    events.push(Event::MoveCursor {
-                        cursor_id,
-                        old_position: cursor.position,
-                        new_position: new_pos,
-                        old_anchor: cursor.anchor,
-                        new_anchor: Some(anchor),
-                        old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_column, // Preserve the goal column
-                    });
-                }
-            }
-        }
-
-        Action::SelectDown => {
+        Action::SelectUp | Action::SelectDown => {
+            let up = matches!(action, Action::SelectUp);
             for (cursor_id, cursor) in state.cursors.iter() {
-                let mut iter = state
-                    .buffer
-                    .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
-
-                // Use sticky_column if set, otherwise use current column
-                let goal_column = if cursor.sticky_column > 0 {
-                    cursor.sticky_column
-                } else {
-                    current_column
+                let Some((new_pos, goal_column)) = vertical_move_target(
+                    &mut state.buffer,
+                    cursor.position,
+                    cursor.sticky_column,
+                    up,
+                    state.tab_size,
+                    estimated_line_length,
+                ) else {
+                    continue;
                 };
 
-                // Skip current line, then get next line
-                iter.next();
-                if let Some((next_line_start, next_line_content)) = iter.next() {
-                    let next_line_len = next_line_content.trim_end_matches('\n').len();
-                    let new_pos = next_line_start + goal_column.min(next_line_len);
-
-                    events.push(Event::MoveCursor {
-                        cursor_id,
-                        old_position: cursor.position,
-                        new_position: new_pos,
-                        old_anchor: cursor.anchor,
-                        new_anchor: Some(anchor),
-                        old_sticky_column: cursor.sticky_column,
-                        new_sticky_column: goal_column, // Preserve the goal column
-                    });
-                }
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor: Some(anchor),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: goal_column, // Preserve the goal column
+                });
             }
         }
 
@@ -2006,9 +2031,21 @@ pub fn action_to_events(
         | Action::SaveAs
         | Action::Open
         | Action::SwitchProject
+        | Action::AddWorkspaceFolder
+        | Action::RemoveWorkspaceFolder
         | Action::New
+        | Action::NewScratchBuffer
+        | Action::OpenScratchBuffer
         | Action::Close
         | Action::CloseTab
+        | Action::CloseSavedBuffers
+        | Action::BufferList
+        | Action::BufferListJump
+        | Action::BufferListMark
+        | Action::BufferListClose
+        | Action::BufferListSave
+        | Action::BufferListSaveAll
+        | Action::BufferListRefresh
         | Action::GotoLine
         | Action::NextBuffer
         | Action::PrevBuffer
@@ -2023,8 +2060,14 @@ pub fn action_to_events(
         | Action::PrevSplit
         | Action::Copy
         | Action::CopyWithTheme(_)
+        | Action::ExportHtml
+        | Action::ExportAnsi
+        | Action::ShowHexView
+        | Action::ShowBufferStats
         | Action::Cut
         | Action::Paste
+        | Action::PasteFromHistory
+        | Action::PasteFromHistoryPlain
         | Action::AddCursorNextMatch
         | Action::AddCursorAbove
         | Action::AddCursorBelow
@@ -2036,13 +2079,32 @@ pub fn action_to_events(
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
+        | Action::SetSplitWorkingDir
+        | Action::ClearSplitWorkingDir
         | Action::Undo
         | Action::Redo
+        | Action::LocalHistory
+        | Action::LocalHistoryDiff
+        | Action::LocalHistoryRestore
+        | Action::OpenFileAtRevision
+        | Action::DiffWithRevision
+        | Action::GitCheckoutBranch
+        | Action::GitCommit
+        | Action::GitAmendCommit
+        | Action::GitCopyPermalink
+        | Action::GitOpenOnRemote
+        | Action::CollabShare
+        | Action::CollabJoin
+        | Action::CollabStop
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
         | Action::ShowKeyboardShortcuts
-        | Action::SmartHome
+        | Action::ShowKeyboardProtocol
+        | Action::ShowNotifications
+        | Action::DismissNotifications
+        | Action::ShowPlugins
+        | Action::ShowPluginLog
         | Action::ToggleComment
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
@@ -2097,8 +2159,12 @@ pub fn action_to_events(
         | Action::PopupPageDown
         | Action::PopupConfirm
         | Action::PopupCancel
+        | Action::PopupCopy
+        | Action::PopupPin
+        | Action::ClosePinnedPopups
         | Action::ToggleFileExplorer
         | Action::ToggleMenuBar
+        | Action::ToggleZenMode
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::SetBackground
@@ -2128,13 +2194,16 @@ pub fn action_to_events(
         | Action::LspStop
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
+        | Action::GenerateTags
         | Action::ToggleLineNumbers
         | Action::ToggleMouseCapture
         | Action::DumpConfig
+        | Action::ConfigDoctor
         | Action::Search
         | Action::FindInSelection
         | Action::FindNext
         | Action::FindPrevious
+        | Action::SelectAllMatches
         | Action::Replace
         | Action::QueryReplace
         | Action::MenuActivate
@@ -2152,9 +2221,44 @@ pub fn action_to_events(
         | Action::ScrollTabsRight
         | Action::SelectTheme
         | Action::SelectKeybindingMap
+        | Action::InstallGrammar
+        | Action::ChangeLanguageMode
+        | Action::ListAbbreviations
         | Action::Revert
         | Action::ToggleAutoRevert
+        | Action::ShowFileWatcherStatus
+        | Action::ShowSystemHealth
         | Action::FormatBuffer
+        | Action::FormatSelection
+        | Action::RenameFile
+        | Action::DuplicateFile
+        | Action::TrashFile
+        | Action::UndoFileOperation
+        | Action::RenameSymbol
+        | Action::RenamePreviewToggle
+        | Action::RenamePreviewApply
+        | Action::ReferencesJump
+        | Action::RunTask
+        | Action::StopTask
+        | Action::TaskGotoError
+        | Action::QuickfixNext
+        | Action::QuickfixPrevious
+        | Action::QuickfixShow
+        | Action::QuickfixFromBuffer
+        | Action::QuickfixJump
+        | Action::ReplStart
+        | Action::ReplSend
+        | Action::ReplSendCell
+        | Action::SortLinesAscending
+        | Action::SortLinesDescending
+        | Action::SortLinesNumeric
+        | Action::SortLinesUnique
+        | Action::ReverseLines
+        | Action::ShuffleLines
+        | Action::JoinLines
+        | Action::RemoveDuplicateLines
+        | Action::AlignOn
+        | Action::ReflowParagraph
         | Action::OpenTerminal
         | Action::CloseTerminal
         | Action::FocusTerminal
@@ -2173,12 +2277,15 @@ pub fn action_to_events(
         | Action::SettingsDecrement
         | Action::SetTabSize
         | Action::SetLineEnding
+        | Action::SetIndentation
         | Action::ToggleIndentationStyle
         | Action::ToggleTabIndicators
         | Action::ToggleDebugHighlights
         | Action::ResetBufferSettings
         | Action::ShellCommand
-        | Action::ShellCommandReplace => return None,
+        | Action::ShellCommandReplace
+        | Action::RunShellCommand
+        | Action::Suspend => return None,
 
         // Block/rectangular selection actions
         Action::BlockSelectLeft => {
@@ -2667,6 +2774,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_goal_column_through_short_lines_tabs_and_wide_chars() {
+        let mut state =
+            EditorState::new(80, 24, crate::config::LARGE_FILE_THRESHOLD_BYTES as usize);
+        state.tab_size = 4;
+        state.apply(&Event::Insert {
+            position: 0,
+            text: "abcdefgh\nab\n\tx\n你好世界z\n".to_string(),
+            cursor_id: CursorId(0),
+        });
+        state.apply(&Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: state.cursors.primary().position,
+            new_position: 6,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+
+        let vertical_move = |state: &mut EditorState, action: Action| {
+            let events = action_to_events(state, action, 4, false, 80, 24).unwrap();
+            for event in &events {
+                state.apply(event);
+            }
+            state.cursors.primary().position
+        };
+
+        // The end of "ab", the end of the tab-indented "x" (column 5), then
+        // column 6 of the wide characters, which is the start of "界"
+        assert_eq!(vertical_move(&mut state, Action::MoveDown), 11);
+        assert_eq!(vertical_move(&mut state, Action::MoveDown), 14);
+        assert_eq!(vertical_move(&mut state, Action::MoveDown), 24);
+        // And back up to column 6
+        assert_eq!(vertical_move(&mut state, Action::MoveUp), 14);
+        assert_eq!(vertical_move(&mut state, Action::MoveUp), 11);
+        assert_eq!(vertical_move(&mut state, Action::MoveUp), 6);
+
+        // Typing starts a new goal column: after "abc", column 3
+        vertical_move(&mut state, Action::MoveDown);
+        state.apply(&Event::Insert {
+            position: 11,
+            text: "c".to_string(),
+            cursor_id: CursorId(0),
+        });
+        assert_eq!(vertical_move(&mut state, Action::MoveUp), 3);
+
+        // Moving sideways does too: column 2 is inside the tab, which the
+        // cursor goes past as past a wide character
+        assert_eq!(vertical_move(&mut state, Action::MoveDown), 12);
+        vertical_move(&mut state, Action::MoveLeft);
+        assert_eq!(vertical_move(&mut state, Action::SelectDown), 14);
+    }
+
     #[test]
     fn test_column_calculation_doesnt_underflow() {
         let mut state =
@@ -2863,6 +3024,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_smart_home_and_end_toggle() {
+        let mut state =
+            EditorState::new(80, 24, crate::config::LARGE_FILE_THRESHOLD_BYTES as usize);
+
+        // "    let x = 1;  " - indent ends at 4, text ends at 14, line ends at 16
+        state.apply(&Event::Insert {
+            position: 0,
+            text: "    let x = 1;  \nnext".to_string(),
+            cursor_id: CursorId(0),
+        });
+        state.apply(&Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 21,
+            new_position: 8,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+
+        fn press(state: &mut EditorState, action: Action) -> usize {
+            for event in action_to_events(state, action, 4, false, 80, 24).unwrap() {
+                state.apply(&event);
+            }
+            state.cursors.primary().position
+        }
+
+        assert_eq!(press(&mut state, Action::SmartHome), 4);
+        assert_eq!(press(&mut state, Action::SmartHome), 0);
+        assert_eq!(press(&mut state, Action::SmartHome), 4);
+        assert_eq!(press(&mut state, Action::SmartEnd), 14);
+        assert_eq!(press(&mut state, Action::SmartEnd), 16);
+        assert_eq!(press(&mut state, Action::SmartEnd), 14);
+
+        assert_eq!(press(&mut state, Action::SelectSmartHome), 4);
+        assert_eq!(
+            state.cursors.primary().selection_range(),
+            Some(4..14),
+            "SelectSmartHome should extend the selection from the old position"
+        );
+    }
+
+    #[test]
+    fn test_smart_home_on_blank_line_and_unindented_line() {
+        assert_eq!(smart_home_position(10, "   \n", 10), 13);
+        assert_eq!(smart_home_position(10, "   \n", 13), 10);
+        assert_eq!(smart_home_position(10, "word\n", 12), 10);
+        assert_eq!(smart_home_position(10, "word\n", 10), 10);
+        assert_eq!(smart_end_position(10, "word\r\n", 11), 14);
+        assert_eq!(smart_end_position(10, "   \n", 10), 13);
+    }
+
     #[test]
     fn test_move_up_with_unloaded_chunks() {
         // Test MoveUp when the chunk containing the cursor hasn't been loaded yet
@@ -0,0 +1,186 @@
+//! Commands for headless batch mode
+//!
+//! Each command is one line: a command name followed by arguments, separated
+//! by whitespace. Arguments containing whitespace are quoted with `"` or
+//! `'`; inside double quotes `\n`, `\t`, `\"` and `\\` are escapes. Blank
+//! lines and lines starting with `#` are ignored.
+//!
+//! Besides the built-in commands, any action name usable in keybindings
+//! (for example `select_all` or `delete_line`) runs that action.
+
+use crate::input::keybindings::Action;
+use std::collections::HashMap;
+
+/// A command run against a buffer in batch mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchCommand {
+    /// Replace every occurrence of `search` (literal text) with `replacement`
+    Replace { search: String, replacement: String },
+    /// Run the formatter configured for the buffer's language
+    Format,
+    /// Save the buffer to its file
+    Save,
+    /// Run an editor action by name
+    Action(Action),
+}
+
+/// Parse one line of a batch script. Returns `Ok(None)` for blank lines and
+/// comments.
+pub fn parse_batch_command(line: &str) -> Result<Option<BatchCommand>, String> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+
+    let words = split_words(trimmed)?;
+    let (name, args) = words.split_first().expect("non-empty line has a word");
+    let expect_args = |count: usize| {
+        if args.len() == count {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' takes {} argument(s), got {}",
+                name,
+                count,
+                args.len()
+            ))
+        }
+    };
+
+    let command = match name.as_str() {
+        "replace" => {
+            expect_args(2)?;
+            if args[0].is_empty() {
+                return Err("'replace' needs a non-empty search text".to_string());
+            }
+            BatchCommand::Replace {
+                search: args[0].clone(),
+                replacement: args[1].clone(),
+            }
+        }
+        "format" => {
+            expect_args(0)?;
+            BatchCommand::Format
+        }
+        "save" => {
+            expect_args(0)?;
+            BatchCommand::Save
+        }
+        _ => {
+            expect_args(0)?;
+            match Action::from_str(name, &HashMap::new()) {
+                Some(action) => BatchCommand::Action(action),
+                None => return Err(format!("Unknown command '{}'", name)),
+            }
+        }
+    };
+    Ok(Some(command))
+}
+
+/// Split a line into whitespace-separated words, honoring quotes
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(words);
+        }
+
+        let mut word = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                '\'' => loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated ' quote".to_string()),
+                    }
+                },
+                '"' => loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => word.push('\n'),
+                            Some('t') => word.push('\t'),
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("Unterminated \" quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated \" quote".to_string()),
+                    }
+                },
+                c => word.push(c),
+            }
+        }
+        words.push(word);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> BatchCommand {
+        parse_batch_command(line).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_parse_builtin_commands() {
+        assert_eq!(parse("save"), BatchCommand::Save);
+        assert_eq!(parse("  format  "), BatchCommand::Format);
+        assert_eq!(
+            parse("replace foo bar"),
+            BatchCommand::Replace {
+                search: "foo".to_string(),
+                replacement: "bar".to_string()
+            }
+        );
+        assert_eq!(parse("select_all"), BatchCommand::Action(Action::SelectAll));
+    }
+
+    #[test]
+    fn test_parse_quoted_arguments() {
+        assert_eq!(
+            parse(r#"replace "a b" 'it''s'"#),
+            BatchCommand::Replace {
+                search: "a b".to_string(),
+                replacement: "its".to_string()
+            }
+        );
+        assert_eq!(
+            parse(r#"replace "\t" "line\n\"x\"\\""#),
+            BatchCommand::Replace {
+                search: "\t".to_string(),
+                replacement: "line\n\"x\"\\".to_string()
+            }
+        );
+        assert_eq!(
+            parse(r#"replace x """#),
+            BatchCommand::Replace {
+                search: "x".to_string(),
+                replacement: String::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_and_comments() {
+        assert_eq!(parse_batch_command("").unwrap(), None);
+        assert_eq!(parse_batch_command("   ").unwrap(), None);
+        assert_eq!(parse_batch_command("# save").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_batch_command("frobnicate").is_err());
+        assert!(parse_batch_command("replace foo").is_err());
+        assert!(parse_batch_command("replace '' bar").is_err());
+        assert!(parse_batch_command("save now").is_err());
+        assert!(parse_batch_command("replace \"foo bar").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Command palette modes
+//!
+//! The first character of the palette input picks what it lists, like
+//! VSCode's quick pick:
+//! - no prefix or `>`: commands
+//! - `/`: files in the working directory
+//! - `@`: symbols (scope headers) in the active buffer
+//! - `#`: symbols in the whole workspace
+//!
+//! Suggestion values keep the mode prefix so that confirming can tell the
+//! modes apart: `/src/main.rs` for a file, `@12` for the symbol on line 12,
+//! `#12:/path/to/lib.rs` for a workspace symbol.
+
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::fuzzy_filter;
+use crate::services::symbol_index::IndexedSymbol;
+use std::path::{Path, PathBuf};
+
+/// Most files listed by the `/` mode
+pub const MAX_FILES: usize = 20_000;
+
+/// Most suggestions shown by the `/`, `@` and `#` modes
+const MAX_SUGGESTIONS: usize = 200;
+
+/// What the command palette lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteMode {
+    Commands,
+    Files,
+    Symbols,
+    WorkspaceSymbols,
+}
+
+/// Split the palette input into its mode and the query to match
+pub fn parse_query(input: &str) -> (PaletteMode, &str) {
+    let mode = match input.chars().next() {
+        Some('>') => PaletteMode::Commands,
+        Some('/') => PaletteMode::Files,
+        Some('@') => PaletteMode::Symbols,
+        Some('#') => PaletteMode::WorkspaceSymbols,
+        _ => return (PaletteMode::Commands, input),
+    };
+    (mode, input[1..].trim_start())
+}
+
+/// Paths of the files under `root`, relative to it, skipping what
+/// `.gitignore` and hidden-file rules exclude
+pub fn list_files(root: &Path, limit: usize) -> Vec<String> {
+    let walker = ignore::WalkBuilder::new(root).require_git(false).build();
+    walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Suggestions for the files matching `query`, best first
+pub fn file_suggestions(query: &str, files: &[String]) -> Vec<Suggestion> {
+    fuzzy_filter(query, files, |file| file.as_str())
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(idx, _)| {
+            let file = &files[idx];
+            Suggestion {
+                text: file.clone(),
+                description: None,
+                value: Some(format!("/{}", file)),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            }
+        })
+        .collect()
+}
+
+/// Line numbers and trimmed text of the symbols whose header lines start at
+/// `headers` in `source`
+pub fn list_symbols(source: &[u8], headers: &[usize]) -> Vec<(usize, String)> {
+    let mut symbols = Vec::with_capacity(headers.len());
+    let mut line = 1;
+    let mut counted = 0;
+    for &offset in headers {
+        line += source[counted..offset]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        counted = offset;
+        let end = source[offset..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(source.len(), |i| offset + i);
+        let text = String::from_utf8_lossy(&source[offset..end]);
+        symbols.push((line, text.trim().to_string()));
+    }
+    symbols
+}
+
+/// Suggestions for the symbols matching `query`, in document order when
+/// the query is empty and best first otherwise
+pub fn symbol_suggestions(query: &str, symbols: &[(usize, String)]) -> Vec<Suggestion> {
+    fuzzy_filter(query, symbols, |(_, text)| text.as_str())
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(idx, _)| {
+            let (line, text) = &symbols[idx];
+            Suggestion {
+                text: text.clone(),
+                description: Some(format!("line {}", line)),
+                value: Some(format!("@{}", line)),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            }
+        })
+        .collect()
+}
+
+/// Suggestions for the workspace symbols matching `query`, best first, with
+/// their paths shown relative to `root`
+pub fn workspace_symbol_suggestions(
+    query: &str,
+    symbols: &[IndexedSymbol],
+    root: &Path,
+) -> Vec<Suggestion> {
+    fuzzy_filter(query, symbols, |symbol| symbol.name.as_str())
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(idx, _)| {
+            let symbol = &symbols[idx];
+            let path = symbol.path.strip_prefix(root).unwrap_or(&symbol.path);
+            Suggestion {
+                text: symbol.name.clone(),
+                description: Some(format!(
+                    "{}  {}:{}",
+                    symbol.kind,
+                    path.display(),
+                    symbol.line
+                )),
+                value: Some(format!("#{}:{}", symbol.line, symbol.path.display())),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            }
+        })
+        .collect()
+}
+
+/// Line and path of a workspace symbol suggestion value, without its `#`
+pub fn parse_workspace_symbol(value: &str) -> Option<(usize, PathBuf)> {
+    let (line, path) = value.split_once(':')?;
+    Some((line.parse().ok()?, PathBuf::from(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("save"), (PaletteMode::Commands, "save"));
+        assert_eq!(parse_query("> save"), (PaletteMode::Commands, "save"));
+        assert_eq!(parse_query("/main"), (PaletteMode::Files, "main"));
+        assert_eq!(parse_query("@ fn"), (PaletteMode::Symbols, "fn"));
+        assert_eq!(
+            parse_query("#Point"),
+            (PaletteMode::WorkspaceSymbols, "Point")
+        );
+        assert_eq!(parse_query(""), (PaletteMode::Commands, ""));
+    }
+
+    #[test]
+    fn test_file_suggestions() {
+        let files = vec![
+            "README.md".to_string(),
+            "src/main.rs".to_string(),
+            "src/model/buffer.rs".to_string(),
+        ];
+        let suggestions = file_suggestions("main", &files);
+        assert_eq!(suggestions[0].text, "src/main.rs");
+        assert_eq!(suggestions[0].get_value(), "/src/main.rs");
+        assert!(file_suggestions("xyz", &files).is_empty());
+    }
+
+    #[test]
+    fn test_symbol_suggestions() {
+        let source = b"fn a() {}\n\nstruct B {\n    x: u8,\n}\nfn c() {}\n";
+        let symbols = list_symbols(source, &[0, 11, 35]);
+        let suggestions = symbol_suggestions("", &symbols);
+        let listed: Vec<_> = suggestions
+            .iter()
+            .map(|s| (s.text.as_str(), s.get_value()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("fn a() {}", "@1"),
+                ("struct B {", "@3"),
+                ("fn c() {}", "@6")
+            ]
+        );
+        let suggestions = symbol_suggestions("struct", &symbols);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].get_value(), "@3");
+    }
+
+    #[test]
+    fn test_workspace_symbol_suggestions() {
+        let root = Path::new("/project");
+        let symbols = vec![
+            IndexedSymbol {
+                name: "parse_args".to_string(),
+                kind: "function",
+                path: root.join("src/cli.rs"),
+                line: 12,
+            },
+            IndexedSymbol {
+                name: "Parser".to_string(),
+                kind: "struct",
+                path: root.join("src/parser.rs"),
+                line: 3,
+            },
+        ];
+        let suggestions = workspace_symbol_suggestions("Parser", &symbols, root);
+        assert_eq!(suggestions[0].text, "Parser");
+        assert_eq!(
+            suggestions[0].description.as_deref(),
+            Some("struct  src/parser.rs:3")
+        );
+        let value = suggestions[0].get_value();
+        assert_eq!(
+            parse_workspace_symbol(&value[1..]),
+            Some((3, root.join("src/parser.rs")))
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::input::commands::{get_all_commands, Command, Suggestion};
 use crate::input::fuzzy::fuzzy_match;
 use crate::input::keybindings::Action;
 use crate::input::keybindings::KeyContext;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 /// Registry for managing editor commands
@@ -20,6 +21,10 @@ pub struct CommandRegistry {
     /// Plugin-registered commands (dynamically added/removed)
     plugin_commands: Arc<RwLock<Vec<Command>>>,
 
+    /// Bumped whenever plugin commands are added or removed, so views built
+    /// from them (like the Plugins menu) know to rebuild
+    revision: AtomicU64,
+
     /// Command usage history (most recent first)
     /// Used to sort command palette suggestions by recency
     command_history: Vec<String>,
@@ -34,6 +39,7 @@ impl CommandRegistry {
         Self {
             builtin_commands: get_all_commands(),
             plugin_commands: Arc::new(RwLock::new(Vec::new())),
+            revision: AtomicU64::new(0),
             command_history: Vec::new(),
         }
     }
@@ -75,18 +81,31 @@ impl CommandRegistry {
 
         // Add new command
         commands.push(command);
+        self.revision.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Unregister a command by name
     pub fn unregister(&self, name: &str) {
         let mut commands = self.plugin_commands.write().unwrap();
         commands.retain(|c| c.name != name);
+        self.revision.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Unregister all commands registered by a specific plugin
     pub fn unregister_by_prefix(&self, prefix: &str) {
         let mut commands = self.plugin_commands.write().unwrap();
         commands.retain(|c| !c.name.starts_with(prefix));
+        self.revision.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Revision of the plugin commands, which changes whenever they do
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Relaxed)
+    }
+
+    /// Get the commands registered by plugins
+    pub fn plugin_commands(&self) -> Vec<Command> {
+        self.plugin_commands.read().unwrap().clone()
     }
 
     /// Get all commands (built-in + plugin)
@@ -279,6 +298,28 @@ mod tests {
         assert_eq!(registry.plugin_command_count(), 0);
     }
 
+    #[test]
+    fn test_revision_tracks_plugin_commands() {
+        let registry = CommandRegistry::new();
+        let initial = registry.revision();
+
+        registry.register(Command {
+            name: "Test Command".to_string(),
+            description: "A test command".to_string(),
+            action: Action::PluginAction("test_action".to_string()),
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Plugin("test".to_string()),
+        });
+        let registered = registry.revision();
+        assert_ne!(registered, initial);
+        assert_eq!(registry.plugin_commands().len(), 1);
+
+        registry.unregister("Test Command");
+        assert_ne!(registry.revision(), registered);
+        assert!(registry.plugin_commands().is_empty());
+    }
+
     #[test]
     fn test_register_replaces_existing() {
         let registry = CommandRegistry::new();
@@ -142,6 +142,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Add Folder to Workspace".to_string(),
+            description: "Add another root folder to the workspace".to_string(),
+            action: Action::AddWorkspaceFolder,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Remove Folder from Workspace".to_string(),
+            description: "Remove a root folder added to the workspace".to_string(),
+            action: Action::RemoveWorkspaceFolder,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Save File".to_string(),
             description: "Save the current buffer to disk".to_string(),
@@ -166,6 +182,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "New Scratch Buffer".to_string(),
+            description: "Create a buffer for notes in a chosen language, kept on disk".to_string(),
+            action: Action::NewScratchBuffer,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Open Scratch Buffer".to_string(),
+            description: "Reopen a scratch buffer".to_string(),
+            action: Action::OpenScratchBuffer,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Close Buffer".to_string(),
             description: "Close the current buffer".to_string(),
@@ -182,6 +214,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Close All Saved Buffers".to_string(),
+            description: "Close every buffer without unsaved changes".to_string(),
+            action: Action::CloseSavedBuffers,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Buffers".to_string(),
+            description: "List open buffers to jump to, save or close".to_string(),
+            action: Action::BufferList,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Revert File".to_string(),
             description: "Discard changes and reload from disk".to_string(),
@@ -190,6 +238,38 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Rename/Move File".to_string(),
+            description: "Rename or move the current file on disk".to_string(),
+            action: Action::RenameFile,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Duplicate File".to_string(),
+            description: "Copy the current file and open the copy".to_string(),
+            action: Action::DuplicateFile,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Delete File (Move to Trash)".to_string(),
+            description: "Move the current file to the system trash and close it".to_string(),
+            action: Action::TrashFile,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Undo File Operation".to_string(),
+            description: "Undo the last rename, duplicate or delete".to_string(),
+            action: Action::UndoFileOperation,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Toggle Auto-Revert".to_string(),
             description: "Toggle automatic reloading when files change on disk".to_string(),
@@ -198,6 +278,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Show File Watcher Status".to_string(),
+            description: "Show which files are checked for changes on disk, how long it takes and which can't be read".to_string(),
+            action: Action::ShowFileWatcherStatus,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show System Health".to_string(),
+            description: "Show running jobs and the state of language servers, plugins and the file watcher, with actions to restart them".to_string(),
+            action: Action::ShowSystemHealth,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Quit".to_string(),
             description: "Exit the editor".to_string(),
@@ -223,6 +319,97 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Local History".to_string(),
+            description: "List earlier versions of the buffer to compare with or restore"
+                .to_string(),
+            action: Action::LocalHistory,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Open File @ Revision...".to_string(),
+            description: "Open the file as it was at a commit that changed it".to_string(),
+            action: Action::OpenFileAtRevision,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Compare with Revision...".to_string(),
+            description: "Compare the buffer with the file at a commit that changed it".to_string(),
+            action: Action::DiffWithRevision,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Git: Checkout Branch...".to_string(),
+            description: "Switch to another local branch".to_string(),
+            action: Action::GitCheckoutBranch,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Git: Commit".to_string(),
+            description: "Write a message for the staged changes and commit them on save"
+                .to_string(),
+            action: Action::GitCommit,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Git: Amend Commit".to_string(),
+            description: "Edit the last commit's message and add the staged changes to it"
+                .to_string(),
+            action: Action::GitAmendCommit,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Git: Copy Permalink".to_string(),
+            description: "Copy a link to the selected lines on GitHub or GitLab".to_string(),
+            action: Action::GitCopyPermalink,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Git: Open on Remote".to_string(),
+            description: "Open the selected lines on GitHub or GitLab in the browser".to_string(),
+            action: Action::GitOpenOnRemote,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Collab: Share Buffer...".to_string(),
+            description: "Let other editors join and edit this buffer (experimental)".to_string(),
+            action: Action::CollabShare,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Collab: Join...".to_string(),
+            description: "Open a buffer shared by another editor (experimental)".to_string(),
+            action: Action::CollabJoin,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Collab: Stop".to_string(),
+            description: "Stop sharing or leave the shared buffer".to_string(),
+            action: Action::CollabStop,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Copy".to_string(),
             description: "Copy selection to clipboard".to_string(),
@@ -240,6 +427,40 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Export as HTML".to_string(),
+            description: "Write the buffer with the theme's syntax colors to an HTML page"
+                .to_string(),
+            action: Action::ExportHtml,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Export as ANSI".to_string(),
+            description: "Write the buffer with syntax colors as ANSI escape sequences".to_string(),
+            action: Action::ExportAnsi,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show Hex View".to_string(),
+            description: "Show the bytes of the file in hex, in a read-only buffer".to_string(),
+            action: Action::ShowHexView,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show Buffer Statistics".to_string(),
+            description: "Show the piece tree of the buffer and how much it has been compacted"
+                .to_string(),
+            action: Action::ShowBufferStats,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Cut".to_string(),
             description: "Cut selection to clipboard".to_string(),
@@ -256,6 +477,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Paste from History".to_string(),
+            description: "Pick a recently copied text to paste".to_string(),
+            action: Action::PasteFromHistory,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Paste from History as Plain Text".to_string(),
+            description: "Pick a recently copied text to paste exactly as it was copied".to_string(),
+            action: Action::PasteFromHistoryPlain,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Delete Line".to_string(),
             description: "Delete the current line".to_string(),
@@ -296,6 +533,131 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Sort Lines Ascending".to_string(),
+            description: "Sort the selected lines (or the buffer) in ascending order".to_string(),
+            action: Action::SortLinesAscending,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Sort Lines Descending".to_string(),
+            description: "Sort the selected lines (or the buffer) in descending order".to_string(),
+            action: Action::SortLinesDescending,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Sort Lines Numerically".to_string(),
+            description: "Sort the selected lines (or the buffer) by their leading number"
+                .to_string(),
+            action: Action::SortLinesNumeric,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Sort Lines Unique".to_string(),
+            description: "Sort the selected lines (or the buffer) and drop duplicates".to_string(),
+            action: Action::SortLinesUnique,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Reverse Lines".to_string(),
+            description: "Reverse the order of the selected lines (or the buffer)".to_string(),
+            action: Action::ReverseLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Shuffle Lines".to_string(),
+            description: "Put the selected lines (or the buffer) in random order".to_string(),
+            action: Action::ShuffleLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Join Lines".to_string(),
+            description: "Join the selected lines, or the cursor line and the next, into one"
+                .to_string(),
+            action: Action::JoinLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Remove Duplicate Lines".to_string(),
+            description:
+                "Remove repeated lines from the selection (or the buffer), keeping the first"
+                    .to_string(),
+            action: Action::RemoveDuplicateLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Align On...".to_string(),
+            description: "Line up a delimiter (text or /regex/) across the selected lines"
+                .to_string(),
+            action: Action::AlignOn,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Reflow Paragraph".to_string(),
+            description: "Rewrap the paragraph (or the selected lines) to the reflow width".to_string(),
+            action: Action::ReflowParagraph,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Format Selection".to_string(),
+            description: "Format the selected text with the language's formatter".to_string(),
+            action: Action::FormatSelection,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Next Paragraph".to_string(),
+            description: "Move to the blank line after the paragraph".to_string(),
+            action: Action::MoveParagraphDown,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Previous Paragraph".to_string(),
+            description: "Move to the blank line before the paragraph".to_string(),
+            action: Action::MoveParagraphUp,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Next Sentence".to_string(),
+            description: "Move to the start of the next sentence".to_string(),
+            action: Action::MoveSentenceRight,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Previous Sentence".to_string(),
+            description: "Move to the start of the sentence".to_string(),
+            action: Action::MoveSentenceLeft,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Open Line".to_string(),
             description: "Insert newline at cursor without moving cursor".to_string(),
@@ -484,6 +846,23 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Set Split Working Directory".to_string(),
+            description: "Use the current buffer's folder as this split's working directory"
+                .to_string(),
+            action: Action::SetSplitWorkingDir,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Clear Split Working Directory".to_string(),
+            description: "Return this split to the project's working directory".to_string(),
+            action: Action::ClearSplitWorkingDir,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // View toggles
         Command {
             name: "Toggle Line Numbers".to_string(),
@@ -519,6 +898,15 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Set Indentation".to_string(),
+            description: "Indent with spaces or tabs of a given width, or detect it from the file"
+                .to_string(),
+            action: Action::SetIndentation,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Toggle Indentation: Spaces ↔ Tabs".to_string(),
             description: "Switch between spaces and tabs for indentation".to_string(),
@@ -609,6 +997,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Toggle Zen Mode".to_string(),
+            description: "Hide bars and gutters and center the text".to_string(),
+            action: Action::ToggleZenMode,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Focus File Explorer".to_string(),
             description: "Move focus to the file explorer".to_string(),
@@ -742,6 +1138,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Select All Matches".to_string(),
+            description: "Put a cursor on every match of the current search".to_string(),
+            action: Action::SelectAllMatches,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Replace".to_string(),
             description: "Replace text in the current buffer".to_string(),
@@ -776,6 +1180,15 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Smart End".to_string(),
+            description: "Move after the last non-whitespace character, or line end if already there"
+                .to_string(),
+            action: Action::SmartEnd,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Show Completions".to_string(),
             description: "Trigger autocomplete suggestions at cursor".to_string(),
@@ -800,6 +1213,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Close Pinned Popups".to_string(),
+            description: "Close hover popups pinned in place".to_string(),
+            action: Action::ClosePinnedPopups,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Find References".to_string(),
             description: "Find all references to the symbol under cursor".to_string(),
@@ -808,6 +1229,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Tags: Generate".to_string(),
+            description: "Write the project's tags file with ctags".to_string(),
+            action: Action::GenerateTags,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Show Signature Help".to_string(),
             description: "Show function parameter hints".to_string(),
@@ -906,11 +1335,90 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        // Tasks
+        Command {
+            name: "Run Task".to_string(),
+            description: "Run a task from the project's config (build, test, ...)".to_string(),
+            action: Action::RunTask,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Stop Task".to_string(),
+            description: "Stop the running task".to_string(),
+            action: Action::StopTask,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // Quickfix
+        Command {
+            name: "Quickfix: Next Entry".to_string(),
+            description: "Go to the next location in the quickfix list".to_string(),
+            action: Action::QuickfixNext,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Quickfix: Previous Entry".to_string(),
+            description: "Go to the previous location in the quickfix list".to_string(),
+            action: Action::QuickfixPrevious,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Quickfix: Show List".to_string(),
+            description: "Show the quickfix list in a split".to_string(),
+            action: Action::QuickfixShow,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Quickfix: Load from Buffer".to_string(),
+            description:
+                "Build the quickfix list from file:line:col locations in the selection or buffer"
+                    .to_string(),
+            action: Action::QuickfixFromBuffer,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // REPL
+        Command {
+            name: "REPL: Start".to_string(),
+            description: "Start a REPL in a split below and send this buffer's code to it"
+                .to_string(),
+            action: Action::ReplStart,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "REPL: Send Line or Selection".to_string(),
+            description: "Send the selection, or the current line, to the buffer's REPL"
+                .to_string(),
+            action: Action::ReplSend,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "REPL: Send Cell".to_string(),
+            description: "Send the code between `%%` cell markers to the buffer's REPL".to_string(),
+            action: Action::ReplSendCell,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // LSP
         Command {
             name: "Rename Symbol".to_string(),
             description: "Rename the symbol under cursor across the project".to_string(),
-            action: Action::LspRename,
+            action: Action::RenameSymbol,
             contexts: vec![KeyContext::Normal],
             custom_contexts: vec![],
             source: CommandSource::Builtin,
@@ -924,6 +1432,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "List Abbreviations".to_string(),
+            description: "Show the abbreviations active in the current buffer".to_string(),
+            action: Action::ListAbbreviations,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "List Macros".to_string(),
             description: "Show all recorded macros".to_string(),
@@ -997,6 +1513,46 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Show Keyboard Protocol".to_string(),
+            description: "Show whether the kitty keyboard protocol is in use".to_string(),
+            action: Action::ShowKeyboardProtocol,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show Notifications".to_string(),
+            description: "Show the history of notifications".to_string(),
+            action: Action::ShowNotifications,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show Plugins".to_string(),
+            description: "List the plugins and whether they are running".to_string(),
+            action: Action::ShowPlugins,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Show Plugin Log".to_string(),
+            description: "Show what plugins printed and the errors they threw".to_string(),
+            action: Action::ShowPluginLog,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Dismiss Notifications".to_string(),
+            description: "Hide all visible notifications".to_string(),
+            action: Action::DismissNotifications,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Config
         Command {
             name: "Dump Config".to_string(),
@@ -1006,6 +1562,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Config Doctor".to_string(),
+            description: "Check the config, theme and keybinding files and list their mistakes with line numbers".to_string(),
+            action: Action::ConfigDoctor,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: "Toggle Inlay Hints".to_string(),
             description: "Show or hide LSP inlay hints (type hints, parameter hints)".to_string(),
@@ -1032,6 +1596,24 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Change Language Mode".to_string(),
+            description: "Choose the syntax used to highlight the current buffer".to_string(),
+            action: Action::ChangeLanguageMode,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // Grammar installation
+        Command {
+            name: "Grammar: Install...".to_string(),
+            description: "Install a grammar from a .vsix URL, GitHub repo, or marketplace ID"
+                .to_string(),
+            action: Action::InstallGrammar,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Settings
         Command {
             name: "Open Settings".to_string(),
@@ -1092,6 +1674,23 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: "Run Shell Command".to_string(),
+            description: "Run shell command in the terminal, then offer to insert its output"
+                .to_string(),
+            action: Action::RunShellCommand,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: "Suspend".to_string(),
+            description: "Suspend the editor to the shell (resume with fg)".to_string(),
+            action: Action::Suspend,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
     ]
 }
 
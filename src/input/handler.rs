@@ -142,6 +142,9 @@ pub enum DeferredAction {
 
     // Insert character (for prompts that need to update suggestions)
     InsertCharAndUpdate(char),
+
+    // Signal a key that can't do anything, as set by `editor.error_feedback`
+    Reject,
 }
 
 /// Trait for elements that can handle input events.
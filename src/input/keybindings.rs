@@ -28,10 +28,10 @@ pub fn format_keybinding(keycode: &KeyCode, modifiers: &KeyModifiers) -> String
 
     // Control key is Ctrl on all platforms (crossterm's CONTROL modifier is the physical Ctrl key)
     // On macOS, use native symbols for Alt and Shift only
-    let (ctrl_label, alt_label, shift_label) = if use_macos_symbols() {
-        ("Ctrl", "⌥", "⇧")
+    let (ctrl_label, alt_label, shift_label, super_label) = if use_macos_symbols() {
+        ("Ctrl", "⌥", "⇧", "⌘")
     } else {
-        ("Ctrl", "Alt", "Shift")
+        ("Ctrl", "Alt", "Shift", "Super")
     };
 
     if modifiers.contains(KeyModifiers::CONTROL) {
@@ -46,6 +46,10 @@ pub fn format_keybinding(keycode: &KeyCode, modifiers: &KeyModifiers) -> String
         result.push_str(shift_label);
         result.push('+');
     }
+    if modifiers.contains(KeyModifiers::SUPER) {
+        result.push_str(super_label);
+        result.push('+');
+    }
 
     match keycode {
         KeyCode::Enter => result.push_str("Enter"),
@@ -218,6 +222,10 @@ pub enum Action {
     MoveDown,
     MoveWordLeft,
     MoveWordRight,
+    MoveSentenceLeft,
+    MoveSentenceRight,
+    MoveParagraphUp,
+    MoveParagraphDown,
     MoveLineStart,
     MoveLineEnd,
     MovePageUp,
@@ -232,6 +240,10 @@ pub enum Action {
     SelectDown,
     SelectWordLeft,
     SelectWordRight,
+    SelectSentenceLeft,
+    SelectSentenceRight,
+    SelectParagraphUp,
+    SelectParagraphDown,
     SelectLineStart,
     SelectLineEnd,
     SelectDocumentStart,
@@ -268,8 +280,14 @@ pub enum Action {
     // Clipboard
     Copy,
     CopyWithTheme(String),
+    ExportHtml,
+    ExportAnsi,
+    ShowHexView,
+    ShowBufferStats,
     Cut,
     Paste,
+    PasteFromHistory,
+    PasteFromHistoryPlain,
 
     // Multi-cursor
     AddCursorAbove,
@@ -282,13 +300,57 @@ pub enum Action {
     SaveAs,
     Open,
     SwitchProject,
+    AddWorkspaceFolder,
+    RemoveWorkspaceFolder,
     New,
+    NewScratchBuffer,
+    OpenScratchBuffer,
     Close,
     CloseTab,
+    CloseSavedBuffers,
+    BufferList,
+    BufferListJump,
+    BufferListMark,
+    BufferListClose,
+    BufferListSave,
+    BufferListSaveAll,
+    BufferListRefresh,
     Quit,
     Revert,
     ToggleAutoRevert,
+    ShowFileWatcherStatus,
+    ShowSystemHealth,
     FormatBuffer,
+    FormatSelection,
+    RenameFile,
+    DuplicateFile,
+    TrashFile,
+    UndoFileOperation,
+    RenameSymbol,
+    RenamePreviewToggle,
+    RenamePreviewApply,
+    ReferencesJump,
+    RunTask,
+    StopTask,
+    TaskGotoError,
+    QuickfixNext,
+    QuickfixPrevious,
+    QuickfixShow,
+    QuickfixFromBuffer,
+    QuickfixJump,
+    ReplStart,
+    ReplSend,
+    ReplSendCell,
+    SortLinesAscending,
+    SortLinesDescending,
+    SortLinesNumeric,
+    SortLinesUnique,
+    ReverseLines,
+    ShuffleLines,
+    JoinLines,
+    RemoveDuplicateLines,
+    AlignOn,
+    ReflowParagraph,
 
     // Navigation
     GotoLine,
@@ -298,6 +360,9 @@ pub enum Action {
 
     // Smart editing
     SmartHome,
+    SmartEnd,
+    SelectSmartHome,
+    SelectSmartEnd,
     DedentSelection,
     ToggleComment,
 
@@ -331,18 +396,39 @@ pub enum Action {
     // Undo/redo
     Undo,
     Redo,
+    LocalHistory,
+    LocalHistoryDiff,
+    LocalHistoryRestore,
+    OpenFileAtRevision,
+    DiffWithRevision,
+    GitCheckoutBranch,
+    GitCommit,
+    GitAmendCommit,
+    GitCopyPermalink,
+    GitOpenOnRemote,
+    CollabShare,
+    CollabJoin,
+    CollabStop,
 
     // View
     ScrollUp,
     ScrollDown,
     ShowHelp,
     ShowKeyboardShortcuts,
+    ShowKeyboardProtocol,
+    ShowNotifications,
+    DismissNotifications,
+    ShowPlugins,
+    ShowPluginLog,
     CommandPalette,
     ToggleLineWrap,
     ToggleComposeMode,
     SetComposeWidth,
     SelectTheme,
     SelectKeybindingMap,
+    InstallGrammar,
+    ChangeLanguageMode,
+    ListAbbreviations,
 
     // Buffer/tab navigation
     NextBuffer,
@@ -367,6 +453,8 @@ pub enum Action {
     IncreaseSplitSize,
     DecreaseSplitSize,
     ToggleMaximizeSplit,
+    SetSplitWorkingDir, // Use the buffer's folder as the split's working directory
+    ClearSplitWorkingDir, // Return the split to the project's working directory
 
     // Prompt mode actions
     PromptConfirm,
@@ -407,11 +495,15 @@ pub enum Action {
     PopupPageDown,
     PopupConfirm,
     PopupCancel,
+    PopupCopy,
+    PopupPin,
+    ClosePinnedPopups,
 
     // File explorer operations
     ToggleFileExplorer,
     // Menu bar visibility
     ToggleMenuBar,
+    ToggleZenMode,
     FocusFileExplorer,
     FocusEditor,
     FileExplorerUp,
@@ -441,6 +533,7 @@ pub enum Action {
     LspStop,
     ToggleInlayHints,
     ToggleMouseHover,
+    GenerateTags,
 
     // View toggles
     ToggleLineNumbers,
@@ -452,18 +545,21 @@ pub enum Action {
     // Buffer settings (per-buffer overrides)
     SetTabSize,
     SetLineEnding,
+    SetIndentation,
     ToggleIndentationStyle,
     ToggleTabIndicators,
     ResetBufferSettings,
 
     // Config operations
     DumpConfig,
+    ConfigDoctor,
 
     // Search and replace
     Search,
     FindInSelection,
     FindNext,
     FindPrevious,
+    SelectAllMatches,
     Replace,
     QueryReplace, // Interactive replace (y/n/!/q for each match)
 
@@ -506,6 +602,8 @@ pub enum Action {
     // Shell command operations
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
+    RunShellCommand,     // Run shell command in the terminal, offer to insert its output
+    Suspend,             // Suspend the editor to the shell
 
     // No-op
     None,
@@ -531,6 +629,10 @@ impl Action {
             "move_down" => Some(Action::MoveDown),
             "move_word_left" => Some(Action::MoveWordLeft),
             "move_word_right" => Some(Action::MoveWordRight),
+            "move_sentence_left" => Some(Action::MoveSentenceLeft),
+            "move_sentence_right" => Some(Action::MoveSentenceRight),
+            "move_paragraph_up" => Some(Action::MoveParagraphUp),
+            "move_paragraph_down" => Some(Action::MoveParagraphDown),
             "move_line_start" => Some(Action::MoveLineStart),
             "move_line_end" => Some(Action::MoveLineEnd),
             "move_page_up" => Some(Action::MovePageUp),
@@ -544,6 +646,10 @@ impl Action {
             "select_down" => Some(Action::SelectDown),
             "select_word_left" => Some(Action::SelectWordLeft),
             "select_word_right" => Some(Action::SelectWordRight),
+            "select_sentence_left" => Some(Action::SelectSentenceLeft),
+            "select_sentence_right" => Some(Action::SelectSentenceRight),
+            "select_paragraph_up" => Some(Action::SelectParagraphUp),
+            "select_paragraph_down" => Some(Action::SelectParagraphDown),
             "select_line_start" => Some(Action::SelectLineStart),
             "select_line_end" => Some(Action::SelectLineEnd),
             "select_document_start" => Some(Action::SelectDocumentStart),
@@ -578,8 +684,14 @@ impl Action {
                 let theme = args.get("theme").and_then(|v| v.as_str()).unwrap_or("");
                 Some(Action::CopyWithTheme(theme.to_string()))
             }
+            "export_html" => Some(Action::ExportHtml),
+            "export_ansi" => Some(Action::ExportAnsi),
+            "show_hex_view" => Some(Action::ShowHexView),
+            "show_buffer_stats" => Some(Action::ShowBufferStats),
             "cut" => Some(Action::Cut),
             "paste" => Some(Action::Paste),
+            "paste_from_history" => Some(Action::PasteFromHistory),
+            "paste_from_history_plain" => Some(Action::PasteFromHistoryPlain),
 
             "add_cursor_above" => Some(Action::AddCursorAbove),
             "add_cursor_below" => Some(Action::AddCursorBelow),
@@ -590,19 +702,66 @@ impl Action {
             "save_as" => Some(Action::SaveAs),
             "open" => Some(Action::Open),
             "switch_project" => Some(Action::SwitchProject),
+            "add_workspace_folder" => Some(Action::AddWorkspaceFolder),
+            "remove_workspace_folder" => Some(Action::RemoveWorkspaceFolder),
             "new" => Some(Action::New),
+            "new_scratch_buffer" => Some(Action::NewScratchBuffer),
+            "open_scratch_buffer" => Some(Action::OpenScratchBuffer),
             "close" => Some(Action::Close),
             "close_tab" => Some(Action::CloseTab),
+            "close_saved_buffers" => Some(Action::CloseSavedBuffers),
+            "buffer_list" => Some(Action::BufferList),
+            "buffer_list_jump" => Some(Action::BufferListJump),
+            "buffer_list_mark" => Some(Action::BufferListMark),
+            "buffer_list_close" => Some(Action::BufferListClose),
+            "buffer_list_save" => Some(Action::BufferListSave),
+            "buffer_list_save_all" => Some(Action::BufferListSaveAll),
+            "buffer_list_refresh" => Some(Action::BufferListRefresh),
             "quit" => Some(Action::Quit),
             "revert" => Some(Action::Revert),
             "toggle_auto_revert" => Some(Action::ToggleAutoRevert),
+            "show_file_watcher_status" => Some(Action::ShowFileWatcherStatus),
+            "show_system_health" => Some(Action::ShowSystemHealth),
             "format_buffer" => Some(Action::FormatBuffer),
+            "format_selection" => Some(Action::FormatSelection),
+            "rename_file" => Some(Action::RenameFile),
+            "duplicate_file" => Some(Action::DuplicateFile),
+            "trash_file" => Some(Action::TrashFile),
+            "undo_file_operation" => Some(Action::UndoFileOperation),
+            "rename_symbol" => Some(Action::RenameSymbol),
+            "rename_preview_toggle" => Some(Action::RenamePreviewToggle),
+            "rename_preview_apply" => Some(Action::RenamePreviewApply),
+            "references_jump" => Some(Action::ReferencesJump),
+            "run_task" => Some(Action::RunTask),
+            "stop_task" => Some(Action::StopTask),
+            "task_goto_error" => Some(Action::TaskGotoError),
+            "quickfix_next" => Some(Action::QuickfixNext),
+            "quickfix_previous" => Some(Action::QuickfixPrevious),
+            "quickfix_show" => Some(Action::QuickfixShow),
+            "quickfix_from_buffer" => Some(Action::QuickfixFromBuffer),
+            "quickfix_jump" => Some(Action::QuickfixJump),
+            "repl_start" => Some(Action::ReplStart),
+            "repl_send" => Some(Action::ReplSend),
+            "repl_send_cell" => Some(Action::ReplSendCell),
+            "sort_lines_ascending" => Some(Action::SortLinesAscending),
+            "sort_lines_descending" => Some(Action::SortLinesDescending),
+            "sort_lines_numeric" => Some(Action::SortLinesNumeric),
+            "sort_lines_unique" => Some(Action::SortLinesUnique),
+            "reverse_lines" => Some(Action::ReverseLines),
+            "shuffle_lines" => Some(Action::ShuffleLines),
+            "join_lines" => Some(Action::JoinLines),
+            "remove_duplicate_lines" => Some(Action::RemoveDuplicateLines),
+            "align_on" => Some(Action::AlignOn),
+            "reflow_paragraph" => Some(Action::ReflowParagraph),
             "goto_line" => Some(Action::GotoLine),
             "goto_matching_bracket" => Some(Action::GoToMatchingBracket),
             "jump_to_next_error" => Some(Action::JumpToNextError),
             "jump_to_previous_error" => Some(Action::JumpToPreviousError),
 
             "smart_home" => Some(Action::SmartHome),
+            "smart_end" => Some(Action::SmartEnd),
+            "select_smart_home" => Some(Action::SelectSmartHome),
+            "select_smart_end" => Some(Action::SelectSmartEnd),
             "dedent_selection" => Some(Action::DedentSelection),
             "toggle_comment" => Some(Action::ToggleComment),
 
@@ -666,11 +825,29 @@ impl Action {
 
             "undo" => Some(Action::Undo),
             "redo" => Some(Action::Redo),
+            "local_history" => Some(Action::LocalHistory),
+            "local_history_diff" => Some(Action::LocalHistoryDiff),
+            "local_history_restore" => Some(Action::LocalHistoryRestore),
+            "open_file_at_revision" => Some(Action::OpenFileAtRevision),
+            "diff_with_revision" => Some(Action::DiffWithRevision),
+            "git_checkout_branch" => Some(Action::GitCheckoutBranch),
+            "git_commit" => Some(Action::GitCommit),
+            "git_amend_commit" => Some(Action::GitAmendCommit),
+            "git_copy_permalink" => Some(Action::GitCopyPermalink),
+            "git_open_on_remote" => Some(Action::GitOpenOnRemote),
+            "collab_share" => Some(Action::CollabShare),
+            "collab_join" => Some(Action::CollabJoin),
+            "collab_stop" => Some(Action::CollabStop),
 
             "scroll_up" => Some(Action::ScrollUp),
             "scroll_down" => Some(Action::ScrollDown),
             "show_help" => Some(Action::ShowHelp),
             "keyboard_shortcuts" => Some(Action::ShowKeyboardShortcuts),
+            "show_keyboard_protocol" => Some(Action::ShowKeyboardProtocol),
+            "show_notifications" => Some(Action::ShowNotifications),
+            "dismiss_notifications" => Some(Action::DismissNotifications),
+            "show_plugins" => Some(Action::ShowPlugins),
+            "show_plugin_log" => Some(Action::ShowPluginLog),
             "command_palette" => Some(Action::CommandPalette),
             "toggle_line_wrap" => Some(Action::ToggleLineWrap),
             "toggle_compose_mode" => Some(Action::ToggleComposeMode),
@@ -690,6 +867,8 @@ impl Action {
             "increase_split_size" => Some(Action::IncreaseSplitSize),
             "decrease_split_size" => Some(Action::DecreaseSplitSize),
             "toggle_maximize_split" => Some(Action::ToggleMaximizeSplit),
+            "set_split_working_dir" => Some(Action::SetSplitWorkingDir),
+            "clear_split_working_dir" => Some(Action::ClearSplitWorkingDir),
 
             "prompt_confirm" => Some(Action::PromptConfirm),
             "prompt_cancel" => Some(Action::PromptCancel),
@@ -726,9 +905,13 @@ impl Action {
             "popup_page_down" => Some(Action::PopupPageDown),
             "popup_confirm" => Some(Action::PopupConfirm),
             "popup_cancel" => Some(Action::PopupCancel),
+            "popup_copy" => Some(Action::PopupCopy),
+            "popup_pin" => Some(Action::PopupPin),
+            "close_pinned_popups" => Some(Action::ClosePinnedPopups),
 
             "toggle_file_explorer" => Some(Action::ToggleFileExplorer),
             "toggle_menu_bar" => Some(Action::ToggleMenuBar),
+            "toggle_zen_mode" => Some(Action::ToggleZenMode),
             "focus_file_explorer" => Some(Action::FocusFileExplorer),
             "focus_editor" => Some(Action::FocusEditor),
             "file_explorer_up" => Some(Action::FileExplorerUp),
@@ -757,6 +940,7 @@ impl Action {
             "lsp_stop" => Some(Action::LspStop),
             "toggle_inlay_hints" => Some(Action::ToggleInlayHints),
             "toggle_mouse_hover" => Some(Action::ToggleMouseHover),
+            "generate_tags" => Some(Action::GenerateTags),
 
             "toggle_line_numbers" => Some(Action::ToggleLineNumbers),
             "toggle_mouse_capture" => Some(Action::ToggleMouseCapture),
@@ -765,20 +949,26 @@ impl Action {
             "set_background_blend" => Some(Action::SetBackgroundBlend),
             "select_theme" => Some(Action::SelectTheme),
             "select_keybinding_map" => Some(Action::SelectKeybindingMap),
+            "install_grammar" => Some(Action::InstallGrammar),
+            "change_language_mode" => Some(Action::ChangeLanguageMode),
+            "list_abbreviations" => Some(Action::ListAbbreviations),
 
             // Buffer settings
             "set_tab_size" => Some(Action::SetTabSize),
             "set_line_ending" => Some(Action::SetLineEnding),
+            "set_indentation" => Some(Action::SetIndentation),
             "toggle_indentation_style" => Some(Action::ToggleIndentationStyle),
             "toggle_tab_indicators" => Some(Action::ToggleTabIndicators),
             "reset_buffer_settings" => Some(Action::ResetBufferSettings),
 
             "dump_config" => Some(Action::DumpConfig),
+            "config_doctor" => Some(Action::ConfigDoctor),
 
             "search" => Some(Action::Search),
             "find_in_selection" => Some(Action::FindInSelection),
             "find_next" => Some(Action::FindNext),
             "find_previous" => Some(Action::FindPrevious),
+            "select_all_matches" => Some(Action::SelectAllMatches),
             "replace" => Some(Action::Replace),
             "query_replace" => Some(Action::QueryReplace),
 
@@ -810,6 +1000,8 @@ impl Action {
             // Shell command actions
             "shell_command" => Some(Action::ShellCommand),
             "shell_command_replace" => Some(Action::ShellCommandReplace),
+            "run_shell_command" => Some(Action::RunShellCommand),
+            "suspend" => Some(Action::Suspend),
 
             // Settings actions
             "open_settings" => Some(Action::OpenSettings),
@@ -1395,6 +1587,9 @@ impl KeybindingResolver {
         if modifiers.contains(KeyModifiers::SHIFT) {
             parts.push("Shift");
         }
+        if modifiers.contains(KeyModifiers::SUPER) {
+            parts.push("Super");
+        }
 
         // Format the key
         let key_str = match key_code {
@@ -1421,6 +1616,37 @@ impl KeybindingResolver {
         parts.join("+")
     }
 
+    /// What is wrong with `binding`, if anything: an unknown action or key
+    /// gets it ignored, and an unknown context binds it in the editor
+    pub fn check_binding(binding: &crate::config::Keybinding) -> Result<(), String> {
+        if Action::from_str(&binding.action, &binding.args).is_none() {
+            return Err(format!("unknown action \"{}\"", binding.action));
+        }
+        if let Some(when) = &binding.when {
+            if KeyContext::from_when_clause(when).is_none() {
+                return Err(format!(
+                    "unknown context \"{}\" (bound in the editor)",
+                    when
+                ));
+            }
+        }
+        let keys: Vec<&str> = if binding.keys.is_empty() {
+            vec![binding.key.as_str()]
+        } else {
+            binding
+                .keys
+                .iter()
+                .map(|press| press.key.as_str())
+                .collect()
+        };
+        for key in keys {
+            if Self::parse_key(key).is_none() {
+                return Err(format!("unknown key \"{}\"", key));
+            }
+        }
+        Ok(())
+    }
+
     /// Parse a key string to KeyCode
     fn parse_key(key: &str) -> Option<KeyCode> {
         let lower = key.to_lowercase();
@@ -1457,6 +1683,7 @@ impl KeybindingResolver {
                 "ctrl" | "control" => result |= KeyModifiers::CONTROL,
                 "shift" => result |= KeyModifiers::SHIFT,
                 "alt" => result |= KeyModifiers::ALT,
+                "super" | "cmd" | "win" => result |= KeyModifiers::SUPER,
                 _ => {}
             }
         }
@@ -1531,6 +1758,10 @@ impl KeybindingResolver {
             Action::MoveDown => "Move cursor down".to_string(),
             Action::MoveWordLeft => "Move word left".to_string(),
             Action::MoveWordRight => "Move word right".to_string(),
+            Action::MoveSentenceLeft => "Move to sentence start".to_string(),
+            Action::MoveSentenceRight => "Move to next sentence".to_string(),
+            Action::MoveParagraphUp => "Move to previous paragraph".to_string(),
+            Action::MoveParagraphDown => "Move to next paragraph".to_string(),
             Action::MoveLineStart => "Move to line start".to_string(),
             Action::MoveLineEnd => "Move to line end".to_string(),
             Action::MovePageUp => "Move page up".to_string(),
@@ -1543,6 +1774,10 @@ impl KeybindingResolver {
             Action::SelectDown => "Select down".to_string(),
             Action::SelectWordLeft => "Select word left".to_string(),
             Action::SelectWordRight => "Select word right".to_string(),
+            Action::SelectSentenceLeft => "Select to sentence start".to_string(),
+            Action::SelectSentenceRight => "Select to next sentence".to_string(),
+            Action::SelectParagraphUp => "Select to previous paragraph".to_string(),
+            Action::SelectParagraphDown => "Select to next paragraph".to_string(),
             Action::SelectLineStart => "Select to line start".to_string(),
             Action::SelectLineEnd => "Select to line end".to_string(),
             Action::SelectDocumentStart => "Select to document start".to_string(),
@@ -1570,8 +1805,18 @@ impl KeybindingResolver {
             Action::Copy => "Copy".to_string(),
             Action::CopyWithTheme(theme) if theme.is_empty() => "Copy with formatting".to_string(),
             Action::CopyWithTheme(theme) => format!("Copy with {} theme", theme),
+            Action::ExportHtml => "Export buffer as HTML".to_string(),
+            Action::ExportAnsi => "Export buffer as ANSI".to_string(),
+            Action::ShowHexView => "Show a hex view of the file".to_string(),
+            Action::ShowBufferStats => "Show buffer statistics".to_string(),
             Action::Cut => "Cut".to_string(),
             Action::Paste => "Paste".to_string(),
+            Action::PasteFromHistory => {
+                "Pick an entry of the clipboard history to paste".to_string()
+            }
+            Action::PasteFromHistoryPlain => {
+                "Pick an entry of the clipboard history to paste without re-indenting".to_string()
+            }
             Action::AddCursorAbove => "Add cursor above".to_string(),
             Action::AddCursorBelow => "Add cursor below".to_string(),
             Action::AddCursorNextMatch => "Add cursor at next match".to_string(),
@@ -1580,13 +1825,65 @@ impl KeybindingResolver {
             Action::SaveAs => "Save file as...".to_string(),
             Action::Open => "Open file".to_string(),
             Action::SwitchProject => "Switch project".to_string(),
+            Action::AddWorkspaceFolder => "Add folder to workspace".to_string(),
+            Action::RemoveWorkspaceFolder => "Remove folder from workspace".to_string(),
             Action::New => "New file".to_string(),
+            Action::NewScratchBuffer => "New scratch buffer".to_string(),
+            Action::OpenScratchBuffer => "Open scratch buffer".to_string(),
             Action::Close => "Close file".to_string(),
             Action::CloseTab => "Close tab".to_string(),
+            Action::CloseSavedBuffers => "Close all buffers without unsaved changes".to_string(),
+            Action::BufferList => "List open buffers".to_string(),
+            Action::BufferListJump => "Show the selected buffer".to_string(),
+            Action::BufferListMark => "Mark or unmark the selected buffer".to_string(),
+            Action::BufferListClose => "Close the marked buffers".to_string(),
+            Action::BufferListSave => "Save the marked buffers".to_string(),
+            Action::BufferListSaveAll => "Save all modified buffers".to_string(),
+            Action::BufferListRefresh => "Refresh the buffer list".to_string(),
             Action::Quit => "Quit editor".to_string(),
             Action::Revert => "Revert to saved file".to_string(),
             Action::ToggleAutoRevert => "Toggle auto-revert mode".to_string(),
+            Action::ShowFileWatcherStatus => "Show what is polled for changes on disk".to_string(),
+            Action::ShowSystemHealth => "Show background jobs and services".to_string(),
             Action::FormatBuffer => "Format buffer with configured formatter".to_string(),
+            Action::FormatSelection => "Format selection with configured formatter".to_string(),
+            Action::RenameFile => "Rename or move the current file".to_string(),
+            Action::DuplicateFile => "Duplicate the current file".to_string(),
+            Action::TrashFile => "Move the current file to the trash".to_string(),
+            Action::UndoFileOperation => "Undo the last rename, duplicate or delete".to_string(),
+            Action::RenameSymbol => "Rename symbol across the project".to_string(),
+            Action::RenamePreviewToggle => "Toggle a change in the rename preview".to_string(),
+            Action::RenamePreviewApply => "Apply the selected rename changes".to_string(),
+            Action::ReferencesJump => "Jump to the selected reference".to_string(),
+            Action::RunTask => "Run a project task".to_string(),
+            Action::StopTask => "Stop the running task".to_string(),
+            Action::TaskGotoError => "Go to the error on this line of task output".to_string(),
+            Action::QuickfixNext => "Go to the next quickfix location".to_string(),
+            Action::QuickfixPrevious => "Go to the previous quickfix location".to_string(),
+            Action::QuickfixShow => "Show the quickfix list in a split".to_string(),
+            Action::QuickfixFromBuffer => {
+                "Build the quickfix list from file locations in the buffer".to_string()
+            }
+            Action::QuickfixJump => "Jump to the selected quickfix location".to_string(),
+            Action::ReplStart => "Start a REPL for the current buffer".to_string(),
+            Action::ReplSend => "Send the line or selection to the REPL".to_string(),
+            Action::ReplSendCell => "Send the current cell to the REPL".to_string(),
+            Action::SortLinesAscending => "Sort the selected lines in ascending order".to_string(),
+            Action::SortLinesDescending => {
+                "Sort the selected lines in descending order".to_string()
+            }
+            Action::SortLinesNumeric => {
+                "Sort the selected lines by their leading number".to_string()
+            }
+            Action::SortLinesUnique => "Sort the selected lines, dropping duplicates".to_string(),
+            Action::ReverseLines => "Reverse the order of the selected lines".to_string(),
+            Action::ShuffleLines => "Shuffle the selected lines".to_string(),
+            Action::JoinLines => "Join the selected lines into one".to_string(),
+            Action::RemoveDuplicateLines => "Remove duplicate lines from the selection".to_string(),
+            Action::AlignOn => "Align the selected lines on a delimiter".to_string(),
+            Action::ReflowParagraph => {
+                "Rewrap the paragraph at the cursor to the reflow width".to_string()
+            }
             Action::GotoLine => "Go to line number".to_string(),
             Action::GoToMatchingBracket => "Go to matching bracket".to_string(),
             Action::JumpToNextError => "Jump to next error/diagnostic".to_string(),
@@ -1594,6 +1891,9 @@ impl KeybindingResolver {
             Action::SmartHome => {
                 "Smart home (toggle line start / first non-whitespace)".to_string()
             }
+            Action::SmartEnd => "Smart end (toggle line end / last non-whitespace)".to_string(),
+            Action::SelectSmartHome => "Select to first non-whitespace / line start".to_string(),
+            Action::SelectSmartEnd => "Select to last non-whitespace / line end".to_string(),
             Action::DedentSelection => "Dedent selection".to_string(),
             Action::ToggleComment => "Toggle comment".to_string(),
             Action::SetBookmark(c) => format!("Set bookmark '{}'", c),
@@ -1617,10 +1917,32 @@ impl KeybindingResolver {
             Action::PromptJumpToBookmark => "Jump to bookmark (prompts for register)".to_string(),
             Action::Undo => "Undo".to_string(),
             Action::Redo => "Redo".to_string(),
+            Action::LocalHistory => "Show earlier versions of the buffer".to_string(),
+            Action::LocalHistoryDiff => "Compare the selected version with the buffer".to_string(),
+            Action::LocalHistoryRestore => "Restore the selected version of the buffer".to_string(),
+            Action::OpenFileAtRevision => "Open the file at an earlier git commit".to_string(),
+            Action::DiffWithRevision => {
+                "Compare the buffer with the file at an earlier git commit".to_string()
+            }
+            Action::GitCheckoutBranch => "Check out a local git branch".to_string(),
+            Action::GitCommit => "Commit the staged changes".to_string(),
+            Action::GitAmendCommit => "Amend the last commit".to_string(),
+            Action::GitCopyPermalink => {
+                "Copy a link to the selected lines on the git remote".to_string()
+            }
+            Action::GitOpenOnRemote => "Open the selected lines on the git remote".to_string(),
+            Action::CollabShare => "Share the buffer with other editors".to_string(),
+            Action::CollabJoin => "Join a buffer shared by another editor".to_string(),
+            Action::CollabStop => "Leave the shared buffer session".to_string(),
             Action::ScrollUp => "Scroll up".to_string(),
             Action::ScrollDown => "Scroll down".to_string(),
             Action::ShowHelp => "Show manual".to_string(),
             Action::ShowKeyboardShortcuts => "Show keyboard shortcuts".to_string(),
+            Action::ShowKeyboardProtocol => "Show the keyboard protocol in use".to_string(),
+            Action::ShowNotifications => "Show notification history".to_string(),
+            Action::DismissNotifications => "Dismiss notifications".to_string(),
+            Action::ShowPlugins => "Show plugins".to_string(),
+            Action::ShowPluginLog => "Show plugin log".to_string(),
             Action::CommandPalette => "Command palette".to_string(),
             Action::ToggleLineWrap => "Toggle line wrap".to_string(),
             Action::ToggleComposeMode => "Toggle compose mode".to_string(),
@@ -1637,6 +1959,8 @@ impl KeybindingResolver {
             Action::IncreaseSplitSize => "Increase split size".to_string(),
             Action::DecreaseSplitSize => "Decrease split size".to_string(),
             Action::ToggleMaximizeSplit => "Toggle maximize split".to_string(),
+            Action::SetSplitWorkingDir => "Set split working directory".to_string(),
+            Action::ClearSplitWorkingDir => "Clear split working directory".to_string(),
             Action::PromptConfirm => "Confirm prompt".to_string(),
             Action::PromptCancel => "Cancel prompt".to_string(),
             Action::PromptBackspace => "Prompt backspace".to_string(),
@@ -1671,8 +1995,12 @@ impl KeybindingResolver {
             Action::PopupPageDown => "Popup page down".to_string(),
             Action::PopupConfirm => "Popup confirm".to_string(),
             Action::PopupCancel => "Popup cancel".to_string(),
+            Action::PopupCopy => "Popup copy text".to_string(),
+            Action::PopupPin => "Popup pin in place".to_string(),
+            Action::ClosePinnedPopups => "Close pinned popups".to_string(),
             Action::ToggleFileExplorer => "Toggle file explorer".to_string(),
             Action::ToggleMenuBar => "Toggle menu bar visibility".to_string(),
+            Action::ToggleZenMode => "Toggle zen (distraction-free) mode".to_string(),
             Action::FocusFileExplorer => "Focus file explorer".to_string(),
             Action::FocusEditor => "Focus editor".to_string(),
             Action::FileExplorerUp => "File explorer: navigate up".to_string(),
@@ -1702,6 +2030,7 @@ impl KeybindingResolver {
             Action::LspStop => "LSP: Stop a running server".to_string(),
             Action::ToggleInlayHints => "Toggle inlay hints".to_string(),
             Action::ToggleMouseHover => "Toggle LSP hover on mouse".to_string(),
+            Action::GenerateTags => "Generate the project's tags file with ctags".to_string(),
             Action::ToggleLineNumbers => "Toggle line numbers".to_string(),
             Action::ToggleMouseCapture => "Toggle mouse support".to_string(),
             Action::ToggleDebugHighlights => {
@@ -1711,14 +2040,21 @@ impl KeybindingResolver {
             Action::SetBackgroundBlend => "Set background blend ratio".to_string(),
             Action::SetTabSize => "Set tab size for current buffer".to_string(),
             Action::SetLineEnding => "Set line ending format (LF/CRLF)".to_string(),
+            Action::SetIndentation => {
+                "Set indentation (spaces/tabs and width) for current buffer".to_string()
+            }
             Action::ToggleIndentationStyle => "Toggle indentation style (spaces/tabs)".to_string(),
             Action::ToggleTabIndicators => "Toggle tab indicator visibility".to_string(),
             Action::ResetBufferSettings => "Reset buffer settings to config".to_string(),
             Action::DumpConfig => "Dump config to file".to_string(),
+            Action::ConfigDoctor => "Check config and theme files for mistakes".to_string(),
             Action::Search => "Search for text in buffer".to_string(),
             Action::FindInSelection => "Search within selection".to_string(),
             Action::FindNext => "Find next search match".to_string(),
             Action::FindPrevious => "Find previous search match".to_string(),
+            Action::SelectAllMatches => {
+                "Select all search matches with multiple cursors".to_string()
+            }
             Action::Replace => "Replace text in buffer".to_string(),
             Action::QueryReplace => "Interactive replace (y/n/!/q for each match)".to_string(),
             Action::MenuActivate => "Activate menu bar".to_string(),
@@ -1735,6 +2071,9 @@ impl KeybindingResolver {
             Action::ScrollTabsRight => "Scroll tabs right".to_string(),
             Action::SelectTheme => "Select theme".to_string(),
             Action::SelectKeybindingMap => "Select keybinding map".to_string(),
+            Action::InstallGrammar => "Install a syntax grammar package".to_string(),
+            Action::ChangeLanguageMode => "Change language mode for current buffer".to_string(),
+            Action::ListAbbreviations => "List active abbreviations".to_string(),
             Action::SwitchToPreviousTab => "Switch to previous tab".to_string(),
             Action::SwitchToTabByName => "Switch to tab by name".to_string(),
             Action::OpenTerminal => "Open terminal".to_string(),
@@ -1755,6 +2094,8 @@ impl KeybindingResolver {
             Action::SettingsDecrement => "Decrement value".to_string(),
             Action::ShellCommand => "Run shell command on buffer/selection".to_string(),
             Action::ShellCommandReplace => "Run shell command and replace".to_string(),
+            Action::RunShellCommand => "Run shell command in terminal".to_string(),
+            Action::Suspend => "Suspend to the shell".to_string(),
             Action::None => "No action".to_string(),
         }
     }
@@ -1899,6 +2240,12 @@ mod tests {
             KeybindingResolver::parse_modifiers(&mods),
             KeyModifiers::CONTROL | KeyModifiers::SHIFT
         );
+
+        let mods = vec!["super".to_string(), "alt".to_string()];
+        assert_eq!(
+            KeybindingResolver::parse_modifiers(&mods),
+            KeyModifiers::SUPER | KeyModifiers::ALT
+        );
     }
 
     #[test]
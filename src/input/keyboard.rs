@@ -0,0 +1,140 @@
+//! Terminal keyboard protocol
+//!
+//! Terminals that support the kitty keyboard protocol (CSI-u) report keys
+//! that legacy escape sequences cannot tell apart, such as Ctrl+Shift+letter,
+//! Ctrl+Enter and Super-modified keys. The protocol is enabled at startup when
+//! the terminal says it supports it; otherwise keys are parsed the legacy way.
+//!
+//! With Shift held, the protocol reports the shifted character ("A", "!")
+//! rather than the key pressed, so key events are normalized back to the
+//! unshifted key before bindings are looked up.
+
+use crossterm::event::{KeyCode, KeyModifiers, KeyboardEnhancementFlags};
+
+/// Keyboard protocol in use between the terminal and the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyboardProtocol {
+    /// Legacy escape sequences, because the terminal did not report support
+    /// for the kitty protocol
+    #[default]
+    Legacy,
+    /// Legacy escape sequences, because `editor.kitty_keyboard` is off
+    Disabled,
+    /// The kitty keyboard protocol (CSI-u)
+    Kitty,
+}
+
+impl KeyboardProtocol {
+    /// One-line description for the diagnostics command
+    pub fn describe(self) -> String {
+        match self {
+            Self::Kitty => format!(
+                "Keyboard protocol: kitty (CSI-u), flags {:?}",
+                enhancement_flags()
+            ),
+            Self::Legacy => {
+                "Keyboard protocol: legacy (the terminal does not support the kitty protocol)"
+                    .to_string()
+            }
+            Self::Disabled => {
+                "Keyboard protocol: legacy (kitty protocol disabled by editor.kitty_keyboard)"
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Flags pushed to terminals that support the kitty protocol
+pub fn enhancement_flags() -> KeyboardEnhancementFlags {
+    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+}
+
+/// Unshifted key of a shifted character on a US layout
+fn unshifted(c: char) -> Option<char> {
+    if c.is_ascii_uppercase() {
+        return Some(c.to_ascii_lowercase());
+    }
+    let base = match c {
+        '!' => '1',
+        '@' => '2',
+        '#' => '3',
+        '$' => '4',
+        '%' => '5',
+        '^' => '6',
+        '&' => '7',
+        '*' => '8',
+        '(' => '9',
+        ')' => '0',
+        '_' => '-',
+        '+' => '=',
+        '{' => '[',
+        '}' => ']',
+        '|' => '\\',
+        ':' => ';',
+        '"' => '\'',
+        '<' => ',',
+        '>' => '.',
+        '?' => '/',
+        '~' => '`',
+        _ => return None,
+    };
+    Some(base)
+}
+
+/// Normalize a key event for binding lookup
+///
+/// A shifted character combined with Ctrl, Alt or Super becomes the
+/// unshifted key with Shift, so `Ctrl+Shift+A` and `Ctrl+Shift+!` match
+/// bindings written as `ctrl+shift+a` and `ctrl+shift+1`. Text typed with
+/// Shift alone is left as is.
+pub fn normalize_key(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    let chord = KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER;
+    match code {
+        KeyCode::Char(c)
+            if modifiers.contains(KeyModifiers::SHIFT) && modifiers.intersects(chord) =>
+        {
+            (KeyCode::Char(unshifted(c).unwrap_or(c)), modifiers)
+        }
+        _ => (code, modifiers),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_shifted_chords() {
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        assert_eq!(
+            normalize_key(KeyCode::Char('A'), ctrl_shift),
+            (KeyCode::Char('a'), ctrl_shift)
+        );
+        assert_eq!(
+            normalize_key(KeyCode::Char('!'), ctrl_shift),
+            (KeyCode::Char('1'), ctrl_shift)
+        );
+        let super_shift = KeyModifiers::SUPER | KeyModifiers::SHIFT;
+        assert_eq!(
+            normalize_key(KeyCode::Char('?'), super_shift),
+            (KeyCode::Char('/'), super_shift)
+        );
+    }
+
+    #[test]
+    fn test_normalize_leaves_typed_text() {
+        assert_eq!(
+            normalize_key(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            (KeyCode::Char('A'), KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            normalize_key(KeyCode::Char('!'), KeyModifiers::NONE),
+            (KeyCode::Char('!'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            normalize_key(KeyCode::Enter, KeyModifiers::CONTROL),
+            (KeyCode::Enter, KeyModifiers::CONTROL)
+        );
+    }
+}
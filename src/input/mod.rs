@@ -2,13 +2,18 @@
 //!
 //! This module handles the input-to-action-to-event translation.
 
+pub mod abbreviations;
 pub mod actions;
+pub mod batch_commands;
 pub mod buffer_mode;
+pub mod command_palette;
 pub mod command_registry;
 pub mod commands;
 pub mod fuzzy;
 pub mod handler;
 pub mod input_history;
 pub mod keybindings;
+pub mod keyboard;
 pub mod multi_cursor;
 pub mod position_history;
+pub mod recording;
@@ -0,0 +1,468 @@
+//! Recording of the input the editor receives, for bug reports.
+//!
+//! With `--record-session FILE` every key press, mouse event, paste, resize
+//! and batch of async messages (LSP responses, file changes, plugin
+//! commands) is appended to FILE as a line of JSON, after a header giving
+//! the terminal size. Test code reads the file back with [`Recording::load`]
+//! and replays it against an editor (`EditorTestHarness::replay`), turning a
+//! bug that is hard to reproduce by hand into an e2e test.
+//!
+//! Each batch of async messages is recorded with the messages that can be
+//! serialized (LSP responses and notifications, file changes, plugin
+//! process output and responses, task output). On replay the harness
+//! injects them in order at the point they were handled, followed by
+//! whatever async messages the replaying editor has pending, so the others
+//! (file explorer listings, terminal output) are deterministic as long as
+//! the test sets up the same files.
+//!
+//! Lines are flushed as they are written, so a recording of a session that
+//! crashed ends with the event that crashed it.
+//!
+//! Text typed or pasted into a password prompt is recorded as `*`, so a
+//! recording never holds a password.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::services::async_bridge::AsyncMessage;
+
+/// Version of the file format, in the header
+const FORMAT_VERSION: u32 = 1;
+
+/// First line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Header {
+    fresh_recording: u32,
+    width: u16,
+    height: u16,
+}
+
+/// Something the editor received
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedEvent {
+    /// A key press; `key` is the character, or a name like "Enter" or "F5"
+    Key { key: String, modifiers: u8 },
+    /// A mouse event; `kind` is like "down_left", "drag_left", "scroll_up"
+    Mouse {
+        kind: String,
+        column: u16,
+        row: u16,
+        modifiers: u8,
+    },
+    /// Text pasted by the terminal
+    Paste { text: String },
+    /// The terminal was resized
+    Resize { width: u16, height: u16 },
+    /// The terminal lost focus
+    FocusLost,
+    /// Async messages were handled; `messages` holds the ones that could
+    /// be serialized, in order
+    Async {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        messages: Vec<serde_json::Value>,
+    },
+}
+
+/// A recorded event and when it happened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedEntry {
+    /// Milliseconds since the recording started
+    pub ms: u64,
+    #[serde(flatten)]
+    pub event: RecordedEvent,
+}
+
+/// Names of the keys that aren't characters
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::BackTab, "BackTab"),
+    (KeyCode::Esc, "Esc"),
+    (KeyCode::Left, "Left"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+];
+
+fn key_name(code: KeyCode) -> Option<String> {
+    match code {
+        KeyCode::Char(c) => Some(c.to_string()),
+        KeyCode::F(n) => Some(format!("F{}", n)),
+        code => KEY_NAMES
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map(|(_, name)| name.to_string()),
+    }
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    if let Some(n) = name.strip_prefix('F').and_then(|n| n.parse().ok()) {
+        return Some(KeyCode::F(n));
+    }
+    KEY_NAMES
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map(|(code, _)| *code)
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    }
+}
+
+fn mouse_kind_name(kind: MouseEventKind) -> String {
+    match kind {
+        MouseEventKind::Down(button) => format!("down_{}", button_name(button)),
+        MouseEventKind::Up(button) => format!("up_{}", button_name(button)),
+        MouseEventKind::Drag(button) => format!("drag_{}", button_name(button)),
+        MouseEventKind::Moved => "moved".to_string(),
+        MouseEventKind::ScrollDown => "scroll_down".to_string(),
+        MouseEventKind::ScrollUp => "scroll_up".to_string(),
+        MouseEventKind::ScrollLeft => "scroll_left".to_string(),
+        MouseEventKind::ScrollRight => "scroll_right".to_string(),
+    }
+}
+
+fn parse_mouse_kind(name: &str) -> Option<MouseEventKind> {
+    let button = |name: &str| match name {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        "middle" => Some(MouseButton::Middle),
+        _ => None,
+    };
+    match name.split_once('_') {
+        Some(("down", b)) => button(b).map(MouseEventKind::Down),
+        Some(("up", b)) => button(b).map(MouseEventKind::Up),
+        Some(("drag", b)) => button(b).map(MouseEventKind::Drag),
+        _ => match name {
+            "moved" => Some(MouseEventKind::Moved),
+            "scroll_down" => Some(MouseEventKind::ScrollDown),
+            "scroll_up" => Some(MouseEventKind::ScrollUp),
+            "scroll_left" => Some(MouseEventKind::ScrollLeft),
+            "scroll_right" => Some(MouseEventKind::ScrollRight),
+            _ => None,
+        },
+    }
+}
+
+impl RecordedEvent {
+    /// The recorded form of a terminal event; None for events the editor
+    /// ignores, like key releases
+    pub fn from_terminal(event: &CrosstermEvent) -> Option<Self> {
+        match event {
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(Self::Key {
+                key: key_name(key.code)?,
+                modifiers: key.modifiers.bits(),
+            }),
+            CrosstermEvent::Mouse(mouse) => Some(Self::Mouse {
+                kind: mouse_kind_name(mouse.kind),
+                column: mouse.column,
+                row: mouse.row,
+                modifiers: mouse.modifiers.bits(),
+            }),
+            CrosstermEvent::Paste(text) => Some(Self::Paste { text: text.clone() }),
+            CrosstermEvent::Resize(width, height) => Some(Self::Resize {
+                width: *width,
+                height: *height,
+            }),
+            CrosstermEvent::FocusLost => Some(Self::FocusLost),
+            _ => None,
+        }
+    }
+
+    /// The recorded form of a batch of handled async messages
+    pub fn from_async_messages(messages: &[AsyncMessage]) -> Self {
+        Self::Async {
+            messages: messages
+                .iter()
+                .filter_map(|message| serde_json::to_value(message).ok())
+                .collect(),
+        }
+    }
+
+    /// The recorded async messages, if this is a batch of them. Messages
+    /// this version can't read are left out.
+    pub fn async_messages(&self) -> Vec<AsyncMessage> {
+        match self {
+            Self::Async { messages } => messages
+                .iter()
+                .filter_map(|message| match serde_json::from_value(message.clone()) {
+                    Ok(message) => Some(message),
+                    Err(e) => {
+                        tracing::warn!("Skipping recorded async message: {}", e);
+                        None
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The event with its typed or pasted text replaced by `*`, for input
+    /// that must not be written to disk
+    pub fn redacted(self) -> Self {
+        match self {
+            Self::Key { key, modifiers } if key.chars().count() == 1 => Self::Key {
+                key: "*".to_string(),
+                modifiers,
+            },
+            Self::Paste { .. } => Self::Paste {
+                text: "*".to_string(),
+            },
+            event => event,
+        }
+    }
+
+    /// The key press, if this is one with a known key
+    pub fn key(&self) -> Option<(KeyCode, KeyModifiers)> {
+        match self {
+            Self::Key { key, modifiers } => Some((
+                parse_key_name(key)?,
+                KeyModifiers::from_bits_truncate(*modifiers),
+            )),
+            _ => None,
+        }
+    }
+
+    /// The mouse event, if this is one of a known kind
+    pub fn mouse(&self) -> Option<MouseEvent> {
+        match self {
+            Self::Mouse {
+                kind,
+                column,
+                row,
+                modifiers,
+            } => Some(MouseEvent {
+                kind: parse_mouse_kind(kind)?,
+                column: *column,
+                row: *row,
+                modifiers: KeyModifiers::from_bits_truncate(*modifiers),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Writes a recording as the editor runs
+#[derive(Debug)]
+pub struct SessionRecorder {
+    file: File,
+    start: Instant,
+}
+
+impl SessionRecorder {
+    /// Start recording to `path`, replacing it, for a terminal of the given
+    /// size
+    pub fn create(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        let header = Header {
+            fresh_recording: FORMAT_VERSION,
+            width,
+            height,
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+        Ok(Self {
+            file,
+            start: Instant::now(),
+        })
+    }
+
+    /// Append `event`
+    pub fn record(&mut self, event: RecordedEvent) {
+        let entry = RecordedEntry {
+            ms: self.start.elapsed().as_millis() as u64,
+            event,
+        };
+        let written = serde_json::to_string(&entry)
+            .map_err(io::Error::from)
+            .and_then(|line| writeln!(self.file, "{}", line));
+        if let Err(e) = written {
+            tracing::warn!("Failed to record event: {}", e);
+        }
+    }
+}
+
+/// A recording read back
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// Terminal size when the recording started
+    pub width: u16,
+    pub height: u16,
+    pub entries: Vec<RecordedEntry>,
+}
+
+impl Recording {
+    /// Parse a recording
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let header: Header = lines
+            .next()
+            .ok_or_else(|| invalid("empty recording".to_string()))
+            .and_then(|line| serde_json::from_str(line).map_err(io::Error::from))?;
+        if header.fresh_recording != FORMAT_VERSION {
+            return Err(invalid(format!(
+                "unsupported recording version {}",
+                header.fresh_recording
+            )));
+        }
+        let entries = lines
+            .enumerate()
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| invalid(format!("line {}: {}", index + 2, e)))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self {
+            width: header.width,
+            height: header.height,
+            entries,
+        })
+    }
+
+    /// Read the recording at `path`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    #[test]
+    fn test_recording_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let events = [
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE)),
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL)),
+            CrosstermEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Left),
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::ALT,
+            }),
+            CrosstermEvent::Paste("two\nlines".to_string()),
+            CrosstermEvent::Resize(100, 40),
+        ];
+
+        let mut recorder = SessionRecorder::create(&path, 80, 24).unwrap();
+        for event in &events {
+            recorder.record(RecordedEvent::from_terminal(event).unwrap());
+        }
+        recorder.record(RecordedEvent::Async {
+            messages: Vec::new(),
+        });
+
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!((recording.width, recording.height), (80, 24));
+        assert_eq!(recording.entries.len(), events.len() + 1);
+        let recorded: Vec<_> = recording.entries.iter().map(|e| &e.event).collect();
+        assert_eq!(
+            recorded[0].key(),
+            Some((KeyCode::Char('A'), KeyModifiers::SHIFT))
+        );
+        assert_eq!(recorded[1].key(), Some((KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(
+            recorded[2].key(),
+            Some((KeyCode::Enter, KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            recorded[3]
+                .mouse()
+                .map(|m| (m.kind, m.column, m.row, m.modifiers)),
+            Some((
+                MouseEventKind::Drag(MouseButton::Left),
+                3,
+                4,
+                KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            *recorded[4],
+            RecordedEvent::Paste {
+                text: "two\nlines".to_string()
+            }
+        );
+        assert_eq!(
+            *recorded[6],
+            RecordedEvent::Async {
+                messages: Vec::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_async_message_payloads_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let mut recorder = SessionRecorder::create(&path, 80, 24).unwrap();
+        recorder.record(RecordedEvent::from_async_messages(&[
+            AsyncMessage::FileChanged {
+                path: "/tmp/a.txt".to_string(),
+            },
+            // A file explorer listing holds this process's state
+            AsyncMessage::FileExplorerToggleNode(crate::view::file_tree::NodeId(1)),
+            AsyncMessage::PluginLspResponse {
+                language: "rust".to_string(),
+                request_id: 7,
+                result: Ok(serde_json::json!({ "items": [] })),
+            },
+        ]));
+
+        let recording = Recording::load(&path).unwrap();
+        let messages = recording.entries[0].event.async_messages();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(
+            &messages[0],
+            AsyncMessage::FileChanged { path } if path == "/tmp/a.txt"
+        ));
+        assert!(matches!(
+            &messages[1],
+            AsyncMessage::PluginLspResponse {
+                request_id: 7,
+                result: Ok(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_key_releases_are_not_recorded() {
+        let mut release = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(
+            RecordedEvent::from_terminal(&CrosstermEvent::Key(release)),
+            None
+        );
+        assert!(
+            Recording::parse("{\"fresh_recording\": 99, \"width\": 1, \"height\": 1}").is_err()
+        );
+    }
+}
@@ -28,3 +28,7 @@ pub mod primitives;
 pub mod services;
 #[cfg(feature = "runtime")]
 pub mod view;
+
+// Test harness for driving the editor from tests, also in other crates
+#[cfg(feature = "test-harness")]
+pub mod testing;
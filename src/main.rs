@@ -2,24 +2,36 @@ use clap::Parser;
 use crossterm::{
     cursor::SetCursorStyle,
     event::{
-        poll as event_poll, read as event_read, DisableBracketedPaste, EnableBracketedPaste,
-        Event as CrosstermEvent, KeyEvent, KeyEventKind, KeyboardEnhancementFlags, MouseEvent,
-        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        poll as event_poll, read as event_read, DisableBracketedPaste, DisableFocusChange,
+        EnableBracketedPaste, EnableFocusChange, Event as CrosstermEvent, KeyEvent, KeyEventKind,
+        MouseEvent, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    style::Print,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen, SetTitle,
     },
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
+use fresh::input::keyboard::KeyboardProtocol;
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
 use fresh::services::tracing_setup;
+use fresh::view::underline::UnderlineBackend;
 use fresh::{
-    app::Editor, config, config_io::DirectoryContext, services::release_checker,
-    services::signal_handler, services::warning_log::WarningLogHandle,
+    app::frame_limiter::{FrameLimiter, Redraw},
+    app::terminal_handoff::{self, TerminalHandoff},
+    app::Editor,
+    config,
+    config_io::DirectoryContext,
+    services::release_checker,
+    services::signal_handler,
+    services::warning_log::WarningLogHandle,
 };
 use ratatui::Terminal;
 use std::{
-    io::{self, stdout},
-    path::PathBuf,
+    io::{self, stdout, BufRead},
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -29,7 +41,8 @@ use std::{
 #[command(about = "A terminal text editor with multi-cursor support", long_about = None)]
 #[command(version)]
 struct Args {
-    /// Files to open (use "-" to read from stdin)
+    /// Files to open (use "-" to read from stdin). Open at a position with
+    /// `file:line:col`, or with `+line` before the file
     #[arg(value_name = "FILES")]
     files: Vec<String>,
 
@@ -41,6 +54,11 @@ struct Args {
     #[arg(long)]
     no_plugins: bool,
 
+    /// Start without plugins and without the configured keybindings and
+    /// theme, to recover from a configuration that makes the editor unusable
+    #[arg(long)]
+    safe_mode: bool,
+
     /// Path to configuration file
     #[arg(long, value_name = "PATH")]
     config: Option<PathBuf>,
@@ -53,13 +71,63 @@ struct Args {
     #[arg(long, value_name = "LOG_FILE")]
     event_log: Option<PathBuf>,
 
+    /// Record key presses, mouse events and pastes to the specified file,
+    /// to be replayed in a test when reporting a bug
+    #[arg(long, value_name = "FILE")]
+    record_session: Option<PathBuf>,
+
     /// Don't restore previous session (start fresh)
     #[arg(long)]
     no_session: bool,
 
+    /// Compare two files in the diff viewer
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    diff: Option<Vec<PathBuf>>,
+
     /// Print the effective configuration as JSON and exit
     #[arg(long)]
     dump_config: bool,
+
+    /// Edit FILES without the terminal UI and exit. Commands come from
+    /// --command, or from stdin one per line
+    #[arg(long)]
+    batch: bool,
+
+    /// Batch command to run on each file, e.g. `replace old new`, `format`
+    /// or `save` (repeatable, implies --batch)
+    #[arg(short = 'c', long = "command", value_name = "CMD")]
+    commands: Vec<String>,
+
+    /// Plugin script to run on the opened files (implies --batch)
+    #[arg(long, value_name = "PATH")]
+    script: Option<PathBuf>,
+
+    /// Accept files sent with `--remote open` from other terminals
+    /// (socket: $FRESH_SOCKET, else one in $XDG_RUNTIME_DIR or a private temp directory)
+    #[arg(long)]
+    listen: bool,
+
+    /// Send FILES to the `--listen` instance instead of starting an editor.
+    /// The only command is `open`
+    #[arg(long, value_name = "COMMAND", value_parser = ["open"])]
+    remote: Option<String>,
+
+    /// With --remote, exit only once the opened buffers are closed (for
+    /// use as $EDITOR)
+    #[arg(long, requires = "remote")]
+    wait: bool,
+}
+
+impl Args {
+    /// Whether a buffer should be read from stdin (`--stdin` or a "-" file)
+    fn reads_stdin(&self) -> bool {
+        self.stdin || self.files.iter().any(|f| f == "-")
+    }
+
+    /// Whether to run headless instead of starting the terminal UI
+    fn is_batch(&self) -> bool {
+        self.batch || self.script.is_some() || !self.commands.is_empty()
+    }
 }
 
 /// Parsed file location from CLI argument in file:line:col format
@@ -79,7 +147,7 @@ struct IterationOutcome {
 struct SetupState {
     config: config::Config,
     warning_log_handle: Option<WarningLogHandle>,
-    terminal: Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: Terminal<UnderlineBackend<io::Stdout>>,
     terminal_size: (u16, u16),
     file_locations: Vec<FileLocation>,
     show_file_explorer: bool,
@@ -92,6 +160,8 @@ struct SetupState {
     gpm_client: Option<GpmClient>,
     #[cfg(not(target_os = "linux"))]
     gpm_client: Option<()>,
+    /// Keyboard protocol negotiated with the terminal
+    keyboard_protocol: KeyboardProtocol,
 }
 
 /// State for stdin streaming in background
@@ -237,11 +307,19 @@ fn handle_first_run_setup(
         editor.enable_event_streaming(log_path)?;
     }
 
+    if let Some(path) = &args.record_session {
+        editor.start_session_recording(path)?;
+    }
+
     if let Some(handle) = warning_log_handle.take() {
         editor.set_warning_log(handle.receiver, handle.path);
     }
 
-    if session_enabled {
+    // After a crash the session is restored only if the user agrees, in
+    // case it is what crashed the editor
+    let crash_report = editor.take_pending_crash_report();
+
+    if session_enabled && crash_report.is_none() {
         match editor.try_restore_session() {
             Ok(true) => {
                 tracing::info!("Session restored successfully");
@@ -263,6 +341,18 @@ fn handle_first_run_setup(
     }
 
     for loc in file_locations {
+        if let Some(url) = loc
+            .path
+            .to_str()
+            .and_then(fresh::services::ssh::SshUrl::parse)
+        {
+            editor.open_ssh_url(url);
+            continue;
+        }
+        if let Some(url) = loc.path.to_str().and_then(|p| editor.parse_vfs_url(p)) {
+            editor.open_vfs_url(url);
+            continue;
+        }
         if loc.path.is_dir() {
             continue;
         }
@@ -273,6 +363,10 @@ fn handle_first_run_setup(
         }
     }
 
+    if let Some([old, new]) = args.diff.as_deref() {
+        editor.open_diff(old, new)?;
+    }
+
     if show_file_explorer {
         editor.show_file_explorer();
     }
@@ -292,9 +386,37 @@ fn handle_first_run_setup(
         }
     }
 
+    if let Some(report) = crash_report {
+        editor.offer_crash_report(report, session_enabled);
+    }
+
     Ok(())
 }
 
+/// Parse the FILES arguments: "-" (stdin) is skipped, and a `+line`
+/// argument sets the line for the file that follows it (unless that file
+/// has its own `:line` suffix).
+fn parse_file_arguments(files: &[String]) -> Vec<FileLocation> {
+    let mut locations = Vec::new();
+    let mut pending_line = None;
+    for arg in files {
+        if arg == "-" {
+            continue;
+        }
+        if let Some(line) = arg.strip_prefix('+').and_then(|n| n.parse::<usize>().ok()) {
+            pending_line = Some(line);
+            continue;
+        }
+        let mut location = parse_file_location(arg);
+        if location.line.is_none() {
+            location.line = pending_line;
+        }
+        pending_line = None;
+        locations.push(location);
+    }
+    locations
+}
+
 /// Parse a file path that may include line and column information.
 /// Supports formats:
 /// - file.txt
@@ -390,6 +512,27 @@ fn parse_file_location(input: &str) -> FileLocation {
     }
 }
 
+/// Load the config from `--config`, or the one that applies to `working_dir`
+fn load_config(args: &Args, working_dir: &Path) -> io::Result<config::Config> {
+    let mut config = match &args.config {
+        None => config::Config::load_for_working_dir(working_dir),
+        Some(config_path) => config::Config::load_from_file(config_path).map_err(|e| {
+            eprintln!(
+                "Error: Failed to load config from {}: {}",
+                config_path.display(),
+                e
+            );
+            io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+        })?,
+    };
+    if args.safe_mode {
+        config.apply_safe_mode();
+        // A user theme file can shadow the default theme's name
+        fresh::view::theme::set_skip_user_themes(true);
+    }
+    Ok(config)
+}
+
 fn initialize_app(args: &Args) -> io::Result<SetupState> {
     let log_file = args
         .log_file
@@ -406,16 +549,21 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
     std::panic::set_hook(Box::new(move |panic| {
         let _ = crossterm::execute!(stdout(), crossterm::event::DisableMouseCapture);
         let _ = stdout().execute(DisableBracketedPaste);
+        let _ = stdout().execute(DisableFocusChange);
         let _ = stdout().execute(SetCursorStyle::DefaultUserShape);
         let _ = stdout().execute(PopKeyboardEnhancementFlags);
+        let _ = stdout().execute(Print(POP_TITLE));
         let _ = disable_raw_mode();
         let _ = stdout().execute(LeaveAlternateScreen);
         original_hook(panic);
+        if let Some(path) = fresh::services::crash_report::write_report(panic) {
+            eprintln!("Crash report written to {}", path.display());
+        }
     }));
 
     // Check if we should read from stdin
     // This can be triggered by --stdin flag or by using "-" as a file argument
-    let stdin_requested = args.stdin || args.files.iter().any(|f| f == "-");
+    let stdin_requested = args.reads_stdin();
 
     // Start stdin streaming in background BEFORE entering raw mode
     // This is critical - once raw mode is enabled, stdin is used for terminal events
@@ -448,13 +596,8 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
     };
 
     // Determine working directory early for config loading
-    // Filter out "-" from files list since it's handled via stdin_stream
-    let file_locations: Vec<FileLocation> = args
-        .files
-        .iter()
-        .filter(|f| *f != "-")
-        .map(|f| parse_file_location(f))
-        .collect();
+    // "-" is handled via stdin_stream
+    let file_locations = parse_file_arguments(&args.files);
 
     let mut working_dir = None;
     let mut show_file_explorer = false;
@@ -475,29 +618,26 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
         .cloned()
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-    let config = if let Some(config_path) = &args.config {
-        match config::Config::load_from_file(config_path) {
-            Ok(cfg) => cfg,
-            Err(e) => {
-                eprintln!(
-                    "Error: Failed to load config from {}: {}",
-                    config_path.display(),
-                    e
-                );
-                return Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string()));
-            }
-        }
-    } else {
-        config::Config::load_for_working_dir(&effective_working_dir)
-    };
+    let config = load_config(args, &effective_working_dir)?;
 
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
-
-    let keyboard_flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
-        | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS;
-    let _ = stdout().execute(PushKeyboardEnhancementFlags(keyboard_flags));
-    tracing::info!("Enabled keyboard enhancement flags: {:?}", keyboard_flags);
+    // The title follows the active buffer; the terminal's own comes back on exit
+    let _ = stdout().execute(Print(PUSH_TITLE));
+
+    // Only terminals that answer the kitty protocol query get its flags;
+    // the rest keep the legacy escape sequences
+    let keyboard_protocol = if !config.editor.kitty_keyboard {
+        KeyboardProtocol::Disabled
+    } else if matches!(supports_keyboard_enhancement(), Ok(true)) {
+        let keyboard_flags = fresh::input::keyboard::enhancement_flags();
+        let _ = stdout().execute(PushKeyboardEnhancementFlags(keyboard_flags));
+        tracing::info!("Enabled keyboard enhancement flags: {:?}", keyboard_flags);
+        KeyboardProtocol::Kitty
+    } else {
+        KeyboardProtocol::Legacy
+    };
+    tracing::info!("Keyboard protocol: {:?}", keyboard_protocol);
 
     #[cfg(target_os = "linux")]
     let gpm_client = match GpmClient::connect() {
@@ -521,10 +661,15 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
     let _ = stdout().execute(EnableBracketedPaste);
     tracing::info!("Enabled bracketed paste mode");
 
+    // Report focus changes so the session is saved when the terminal loses focus
+    let _ = stdout().execute(EnableFocusChange);
+
     let _ = stdout().execute(SetCursorStyle::BlinkingBlock);
     tracing::info!("Enabled blinking block cursor");
 
-    let backend = ratatui::backend::CrosstermBackend::new(stdout());
+    let underline_styles = fresh::view::underline::detect_underline_styles();
+    tracing::info!("Styled underlines: {}", underline_styles);
+    let backend = UnderlineBackend::new(stdout(), underline_styles);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
@@ -532,6 +677,9 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
     tracing::info!("Terminal size: {}x{}", size.width, size.height);
 
     let dir_context = DirectoryContext::from_system()?;
+    fresh::services::crash_report::set_report_dir(fresh::services::crash_report::report_dir(
+        &dir_context.state_dir,
+    ));
     let current_working_dir = working_dir;
 
     Ok(SetupState {
@@ -545,6 +693,7 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
         current_working_dir,
         stdin_stream,
         gpm_client,
+        keyboard_protocol,
     })
 }
 
@@ -552,7 +701,7 @@ fn initialize_app(args: &Args) -> io::Result<SetupState> {
 fn run_editor_iteration(
     editor: &mut Editor,
     session_enabled: bool,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
     #[cfg(target_os = "linux")] gpm_client: &Option<GpmClient>,
 ) -> io::Result<IterationOutcome> {
     #[cfg(target_os = "linux")]
@@ -563,6 +712,7 @@ fn run_editor_iteration(
     if let Err(e) = editor.end_recovery_session() {
         tracing::warn!("Failed to end recovery session: {}", e);
     }
+    editor.save_all_undo_histories();
 
     let update_result = editor.get_update_result().cloned();
     let restart_dir = editor.take_restart_dir();
@@ -574,27 +724,163 @@ fn run_editor_iteration(
     })
 }
 
+/// Run `--batch`/`--script` mode: open the files, run the script and the
+/// commands on each file, then exit. Returns the process exit code: 0 on
+/// success, 1 if a command failed and 2 for invalid arguments.
+fn run_batch(args: &Args) -> io::Result<i32> {
+    let log_file = args
+        .log_file
+        .clone()
+        .unwrap_or_else(|| std::env::temp_dir().join("fresh.log"));
+    let _ = tracing_setup::init_global(&log_file);
+
+    if args.reads_stdin() {
+        eprintln!("Error: batch mode edits files, not stdin");
+        return Ok(2);
+    }
+    let file_locations = parse_file_arguments(&args.files);
+    if file_locations.is_empty() {
+        eprintln!("Error: batch mode needs at least one file");
+        return Ok(2);
+    }
+
+    // Without --command or --script, the commands are piped in on stdin
+    let command_lines = if args.commands.is_empty() && args.script.is_none() {
+        if !stdin_has_data() {
+            eprintln!("Error: --batch needs --command, --script or commands piped to stdin");
+            return Ok(2);
+        }
+        io::stdin().lock().lines().collect::<io::Result<Vec<_>>>()?
+    } else {
+        args.commands.clone()
+    };
+    let mut commands = Vec::new();
+    for line in &command_lines {
+        match fresh::input::batch_commands::parse_batch_command(line) {
+            Ok(Some(command)) => commands.push(command),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Error: {}: {}", line.trim(), e);
+                return Ok(2);
+            }
+        }
+    }
+
+    let working_dir = std::env::current_dir().unwrap_or_default();
+    let config = load_config(args, &working_dir)?;
+    let mut editor = Editor::with_working_dir(
+        config,
+        80,
+        24,
+        None,
+        DirectoryContext::from_system()?,
+        args.script.is_some() && !args.no_plugins,
+        fresh::view::color_support::ColorCapability::TrueColor,
+    )?;
+
+    for loc in &file_locations {
+        editor.open_file(&loc.path)?;
+        if let Some(line) = loc.line {
+            editor.goto_line_col(line, loc.column);
+        }
+    }
+
+    if let Some(script) = &args.script {
+        if let Err(e) = editor.run_batch_script(script) {
+            eprintln!("Error: {}: {}", script.display(), e);
+            return Ok(1);
+        }
+    }
+
+    for loc in &file_locations {
+        editor.open_file(&loc.path)?;
+        for command in &commands {
+            if let Err(e) = editor.run_batch_command(command) {
+                eprintln!("Error: {}: {}", loc.path.display(), e);
+                return Ok(1);
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// Run `--remote open`: ask the `--listen` instance to open FILES.
+/// Returns the process exit code.
+#[cfg(unix)]
+fn run_remote(args: &Args) -> i32 {
+    use fresh::services::remote::{default_socket_path, send_open, OpenRequest};
+
+    let file_locations = parse_file_arguments(&args.files);
+    if file_locations.is_empty() {
+        eprintln!("Error: --remote open needs at least one file");
+        return 2;
+    }
+    let socket = match default_socket_path() {
+        Ok(socket) => socket,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+
+    // The editor runs in another directory, so send absolute paths. Each
+    // file gets its own connection so `--wait` waits for all of them.
+    let failed = std::thread::scope(|scope| {
+        let handles: Vec<_> = file_locations
+            .iter()
+            .map(|loc| {
+                let socket = &socket;
+                scope.spawn(move || {
+                    let request = OpenRequest {
+                        path: std::path::absolute(&loc.path)?,
+                        line: loc.line,
+                        column: loc.column,
+                        wait: args.wait,
+                    };
+                    send_open(socket, &request)
+                })
+            })
+            .collect();
+        let mut failed = false;
+        for (handle, loc) in handles.into_iter().zip(&file_locations) {
+            match handle.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    eprintln!("Error: {}: {}", loc.path.display(), e);
+                    failed = true;
+                }
+                Err(_) => failed = true,
+            }
+        }
+        failed
+    });
+    i32::from(failed)
+}
+
+#[cfg(not(unix))]
+fn run_remote(_args: &Args) -> i32 {
+    eprintln!("Error: --remote is only supported on unix");
+    2
+}
+
 fn main() -> io::Result<()> {
     // Parse command-line arguments
     let args = Args::parse();
 
+    // Remote mode hands the files to another instance
+    if args.remote.is_some() {
+        std::process::exit(run_remote(&args));
+    }
+
+    // Batch mode runs without a terminal
+    if args.is_batch() {
+        let code = run_batch(&args)?;
+        std::process::exit(code);
+    }
+
     // Handle --dump-config early (no terminal setup needed)
     if args.dump_config {
-        let config = if let Some(config_path) = &args.config {
-            match config::Config::load_from_file(config_path) {
-                Ok(cfg) => cfg,
-                Err(e) => {
-                    eprintln!(
-                        "Error: Failed to load config from {}: {}",
-                        config_path.display(),
-                        e
-                    );
-                    return Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string()));
-                }
-            }
-        } else {
-            config::Config::load_for_working_dir(&std::env::current_dir().unwrap_or_default())
-        };
+        let config = load_config(&args, &std::env::current_dir().unwrap_or_default())?;
 
         // Pretty-print the config as JSON
         match serde_json::to_string_pretty(&config) {
@@ -623,6 +909,7 @@ fn main() -> io::Result<()> {
         gpm_client,
         #[cfg(not(target_os = "linux"))]
         gpm_client,
+        keyboard_protocol,
     } = initialize_app(&args)?;
 
     let mut current_working_dir = initial_working_dir;
@@ -638,7 +925,11 @@ fn main() -> io::Result<()> {
     // Returns (loop_result, last_update_result) tuple
     let (result, last_update_result) = loop {
         let first_run = is_first_run;
-        let session_enabled = !args.no_session && file_locations.is_empty();
+        // Opening files, stdin or a diff starts a one-off editor, not the saved session.
+        // A project switched to afterwards has its session saved and restored.
+        let session_enabled = !args.no_session
+            && (!first_run
+                || (file_locations.is_empty() && !args.reads_stdin() && args.diff.is_none()));
 
         // Detect terminal color capability
         let color_capability = fresh::view::color_support::ColorCapability::detect();
@@ -649,7 +940,7 @@ fn main() -> io::Result<()> {
             terminal_height,
             current_working_dir.clone(),
             dir_context.clone(),
-            !args.no_plugins,
+            !args.no_plugins && !args.safe_mode,
             color_capability,
         )?;
 
@@ -657,6 +948,20 @@ fn main() -> io::Result<()> {
         if gpm_client.is_some() {
             editor.set_gpm_active(true);
         }
+        editor.set_keyboard_protocol(keyboard_protocol);
+
+        #[cfg(unix)]
+        if args.listen {
+            let result = fresh::services::remote::default_socket_path().and_then(|socket| {
+                editor
+                    .start_remote_server(&socket)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", socket.display(), e)))
+            });
+            if let Err(e) = result {
+                tracing::warn!("Failed to listen for --remote: {}", e);
+                editor.set_status_message(format!("Not listening for --remote: {}", e));
+            }
+        }
 
         if first_run {
             handle_first_run_setup(
@@ -669,10 +974,12 @@ fn main() -> io::Result<()> {
                 session_enabled,
             )?;
         } else {
-            if restore_session_on_restart {
+            let mut session_restored = false;
+            if restore_session_on_restart && session_enabled {
                 match editor.try_restore_session() {
                     Ok(true) => {
                         tracing::info!("Session restored successfully");
+                        session_restored = true;
                     }
                     Ok(false) => {
                         tracing::debug!("No previous session found");
@@ -685,14 +992,25 @@ fn main() -> io::Result<()> {
 
             editor.show_file_explorer();
             editor.set_status_message(format!(
-                "Switched to project: {}",
+                "Switched to project: {}{}",
                 current_working_dir
                     .as_ref()
                     .map(|p| p.display().to_string())
-                    .unwrap_or_else(|| ".".to_string())
+                    .unwrap_or_else(|| ".".to_string()),
+                if session_restored {
+                    " (session restored)"
+                } else {
+                    ""
+                }
             ));
         }
 
+        if args.safe_mode {
+            editor.set_status_message(
+                "Safe mode: plugins, keybindings and theme are off. Run Config Doctor to find mistakes in the config".to_string(),
+            );
+        }
+
         if let Err(e) = editor.start_recovery_session() {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
@@ -716,6 +1034,11 @@ fn main() -> io::Result<()> {
                 "Restarting editor with new working directory: {}",
                 new_dir.display()
             );
+            // Shell commands, terminals and tools started from the editor
+            // run in the new project too
+            if let Err(e) = std::env::set_current_dir(&new_dir) {
+                tracing::warn!("Failed to change directory to {}: {}", new_dir.display(), e);
+            }
             current_working_dir = Some(new_dir);
             is_first_run = false;
             restore_session_on_restart = true; // Restore session for the new project
@@ -727,12 +1050,7 @@ fn main() -> io::Result<()> {
     };
 
     // Clean up terminal
-    let _ = crossterm::execute!(stdout(), crossterm::event::DisableMouseCapture);
-    let _ = stdout().execute(DisableBracketedPaste);
-    let _ = stdout().execute(SetCursorStyle::DefaultUserShape);
-    let _ = stdout().execute(PopKeyboardEnhancementFlags);
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
+    release_terminal()?;
 
     // Check for updates after terminal is restored (using cached result)
     if let Some(update_result) = last_update_result {
@@ -758,27 +1076,99 @@ fn main() -> io::Result<()> {
     result
 }
 
+/// Save the terminal's title on its title stack (xterm's, which most
+/// terminals support), so it can be put back on exit
+const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// Restore the title saved by `PUSH_TITLE`
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Put the terminal back the way the shell expects it
+fn release_terminal() -> io::Result<()> {
+    let _ = crossterm::execute!(stdout(), crossterm::event::DisableMouseCapture);
+    let _ = stdout().execute(DisableBracketedPaste);
+    let _ = stdout().execute(DisableFocusChange);
+    let _ = stdout().execute(SetCursorStyle::DefaultUserShape);
+    let _ = stdout().execute(PopKeyboardEnhancementFlags);
+    let _ = stdout().execute(Print(POP_TITLE));
+    disable_raw_mode()?;
+    stdout().execute(LeaveAlternateScreen)?;
+    Ok(())
+}
+
+/// Set the terminal up again after `release_terminal`, the way startup did
+fn reclaim_terminal(
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
+    keyboard_protocol: KeyboardProtocol,
+    mouse_capture: bool,
+) -> io::Result<()> {
+    enable_raw_mode()?;
+    stdout().execute(EnterAlternateScreen)?;
+    if keyboard_protocol == KeyboardProtocol::Kitty {
+        let _ = stdout().execute(PushKeyboardEnhancementFlags(
+            fresh::input::keyboard::enhancement_flags(),
+        ));
+    }
+    if mouse_capture {
+        let _ = crossterm::execute!(stdout(), crossterm::event::EnableMouseCapture);
+    }
+    let _ = stdout().execute(EnableBracketedPaste);
+    let _ = stdout().execute(EnableFocusChange);
+    let _ = stdout().execute(SetCursorStyle::BlinkingBlock);
+    terminal.clear()
+}
+
+/// Give the terminal up for a suspend or a command run in it, then take it
+/// back and redraw everything
+fn hand_off_terminal(
+    editor: &mut Editor,
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
+    handoff: TerminalHandoff,
+    mouse_capture: bool,
+) -> io::Result<()> {
+    tracing::info!("Handing off the terminal: {:?}", handoff);
+    release_terminal()?;
+    match handoff {
+        TerminalHandoff::Suspend => signal_handler::stop_process(),
+        TerminalHandoff::RunCommand(command) => {
+            let result = terminal_handoff::run_in_terminal(&command);
+            editor.finish_run_shell_command(&command, result);
+        }
+    }
+    reclaim_terminal(terminal, editor.keyboard_protocol(), mouse_capture)?;
+    let _ = stdout().execute(Print(PUSH_TITLE));
+    editor.forget_terminal_title();
+    let size = terminal.size()?;
+    editor.resize(size.width, size.height);
+    Ok(())
+}
+
 /// Main event loop
 #[cfg(target_os = "linux")]
 fn run_event_loop(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
     session_enabled: bool,
     gpm_client: &Option<GpmClient>,
 ) -> io::Result<()> {
-    run_event_loop_common(editor, terminal, session_enabled, |timeout| {
-        poll_with_gpm(gpm_client.as_ref(), timeout)
-    })
+    let mouse_capture = gpm_client.is_none();
+    run_event_loop_common(
+        editor,
+        terminal,
+        session_enabled,
+        mouse_capture,
+        |timeout| poll_with_gpm(gpm_client.as_ref(), timeout),
+    )
 }
 
 /// Main event loop (non-Linux version without GPM)
 #[cfg(not(target_os = "linux"))]
 fn run_event_loop(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
     session_enabled: bool,
 ) -> io::Result<()> {
-    run_event_loop_common(editor, terminal, session_enabled, |timeout| {
+    run_event_loop_common(editor, terminal, session_enabled, true, |timeout| {
         if event_poll(timeout)? {
             Ok(Some(event_read()?))
         } else {
@@ -789,8 +1179,9 @@ fn run_event_loop(
 
 fn run_event_loop_common<F>(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<UnderlineBackend<io::Stdout>>,
     session_enabled: bool,
+    mouse_capture: bool,
     mut poll_event: F,
 ) -> io::Result<()>
 where
@@ -798,35 +1189,66 @@ where
 {
     use std::time::Instant;
 
-    const FRAME_DURATION: Duration = Duration::from_millis(16); // 60fps
-    let mut last_render = Instant::now();
-    let mut needs_render = true;
+    let mut frames = FrameLimiter::new(editor.config().editor.max_fps);
     let mut pending_event: Option<CrosstermEvent> = None;
 
+    if session_enabled {
+        editor.enable_session_auto_save();
+    }
+
     loop {
         // Process async messages and poll for file changes (auto-revert, file tree)
         if editor.process_async_messages() {
-            needs_render = true;
+            frames.request(Redraw::Background);
         }
 
         // Check mouse hover timer for LSP hover requests
         if editor.check_mouse_hover_timer() {
-            needs_render = true;
+            frames.request(Redraw::Background);
+        }
+
+        // Show word occurrence highlights once the cursor has rested
+        if editor.check_occurrence_highlight_timer() {
+            frames.request(Redraw::Background);
+        }
+
+        // Keep scrolling while a drag selection is held beyond a split's edge
+        if editor.check_drag_autoscroll() {
+            frames.request(Redraw::Pointer);
+        }
+
+        // End the flash of a rejected key
+        if editor.check_visual_bell() {
+            frames.request(Redraw::Background);
         }
 
         // Check for warnings and open warning log if any occurred
         if editor.check_warning_log() {
-            needs_render = true;
+            frames.request(Redraw::Background);
         }
 
         // Poll stdin streaming progress (if active)
         if editor.poll_stdin_streaming() {
-            needs_render = true;
+            frames.request(Redraw::Background);
+        }
+
+        // Append what arrived of files loading in the background
+        if editor.poll_progressive_loads() {
+            frames.request(Redraw::Background);
+        }
+
+        // Show the progress of saves in the background, and finish them
+        if editor.poll_background_saves() {
+            frames.request(Redraw::Background);
         }
 
         if let Err(e) = editor.auto_save_dirty_buffers() {
             tracing::debug!("Auto-save error: {}", e);
         }
+        editor.persist_scratch_buffers();
+        editor.auto_save_session();
+        editor.record_open_files_for_crash_report();
+        editor.compact_idle_buffers();
 
         if editor.should_quit() {
             if session_enabled {
@@ -839,20 +1261,42 @@ where
             break;
         }
 
-        if needs_render && last_render.elapsed() >= FRAME_DURATION {
+        // Ctrl+Z from outside the editor (kill -TSTP) suspends like the action
+        if signal_handler::take_suspend_request() {
+            editor.request_suspend();
+        }
+        if let Some(handoff) = editor.take_terminal_handoff() {
+            hand_off_terminal(editor, terminal, handoff, mouse_capture)?;
+            frames.request(Redraw::Key);
+        } else if signal_handler::take_resumed() {
+            // Stopped and continued by someone else: the shell may have reset
+            // the terminal in between
+            reclaim_terminal(terminal, editor.keyboard_protocol(), mouse_capture)?;
+            editor.forget_terminal_title();
+            frames.request(Redraw::Key);
+        }
+
+        // Input already waiting is handled before drawing, so a burst of
+        // key presses or scroll events is drawn once
+        frames.set_max_fps(editor.config().editor.max_fps);
+        let input_queued = pending_event.is_some() || event_poll(Duration::ZERO)?;
+        if frames.should_render(Instant::now(), input_queued) {
             terminal.draw(|frame| editor.render(frame))?;
-            last_render = Instant::now();
-            needs_render = false;
+            frames.rendered(Instant::now());
+            if let Some(title) = editor.take_terminal_title_change() {
+                let _ = stdout().execute(SetTitle(title));
+            }
+        }
+        if editor.take_bell() {
+            let _ = stdout().execute(Print('\x07'));
         }
 
         let event = if let Some(e) = pending_event.take() {
             Some(e)
         } else {
-            let timeout = if needs_render {
-                FRAME_DURATION.saturating_sub(last_render.elapsed())
-            } else {
-                Duration::from_millis(50)
-            };
+            let timeout = frames
+                .timeout(Instant::now())
+                .unwrap_or(Duration::from_millis(50));
 
             poll_event(timeout)?
         };
@@ -861,28 +1305,31 @@ where
 
         let (event, next) = coalesce_mouse_moves(event)?;
         pending_event = next;
+        editor.record_terminal_event(&event);
 
         match event {
             CrosstermEvent::Key(key_event) => {
                 if key_event.kind == KeyEventKind::Press {
                     handle_key_event(editor, key_event)?;
-                    needs_render = true;
+                    frames.request(Redraw::Key);
                 }
             }
             CrosstermEvent::Mouse(mouse_event) => {
                 if handle_mouse_event(editor, mouse_event)? {
-                    needs_render = true;
+                    frames.request(Redraw::Pointer);
                 }
             }
             CrosstermEvent::Resize(w, h) => {
                 editor.resize(w, h);
-                needs_render = true;
+                frames.request(Redraw::Pointer);
             }
             CrosstermEvent::Paste(text) => {
-                // External paste from terminal (bracketed paste mode)
-                editor.paste_text(text);
-                needs_render = true;
+                // External paste from terminal (bracketed paste mode), which
+                // is also how files dropped on the terminal arrive
+                editor.paste_from_terminal(text);
+                frames.request(Redraw::Key);
             }
+            CrosstermEvent::FocusLost => editor.on_terminal_focus_lost(),
             _ => {}
         }
     }
@@ -1069,6 +1516,67 @@ mod tests {
         assert_eq!(locs[2].column, Some(5));
     }
 
+    #[test]
+    fn test_parse_file_arguments_plus_line() {
+        let args: Vec<String> = ["+42", "a.txt", "b.txt", "+3", "c.txt:7:2", "-"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let locs = parse_file_arguments(&args);
+
+        assert_eq!(locs.len(), 3);
+        assert_eq!(locs[0].path, PathBuf::from("a.txt"));
+        assert_eq!(locs[0].line, Some(42));
+        assert_eq!(locs[1].path, PathBuf::from("b.txt"));
+        assert_eq!(locs[1].line, None);
+        // An explicit :line suffix wins over +line
+        assert_eq!(locs[2].path, PathBuf::from("c.txt"));
+        assert_eq!(locs[2].line, Some(7));
+        assert_eq!(locs[2].column, Some(2));
+    }
+
+    #[test]
+    fn test_diff_flag_takes_two_paths() {
+        let args = Args::try_parse_from(["fresh", "--diff", "old.txt", "new.txt"]).unwrap();
+        assert_eq!(
+            args.diff,
+            Some(vec![PathBuf::from("old.txt"), PathBuf::from("new.txt")])
+        );
+        assert!(args.files.is_empty());
+        assert!(Args::try_parse_from(["fresh", "--diff", "old.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_remote_flags() {
+        let args = Args::try_parse_from(["fresh", "--remote", "open", "--wait", "COMMIT_EDITMSG"])
+            .unwrap();
+        assert_eq!(args.remote.as_deref(), Some("open"));
+        assert!(args.wait);
+        assert_eq!(args.files, vec!["COMMIT_EDITMSG"]);
+
+        assert!(Args::try_parse_from(["fresh", "--remote", "close", "a.txt"]).is_err());
+        assert!(Args::try_parse_from(["fresh", "--wait", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_batch_flags() {
+        let args = Args::try_parse_from(["fresh", "a.txt"]).unwrap();
+        assert!(!args.is_batch());
+
+        let args =
+            Args::try_parse_from(["fresh", "-c", "replace a b", "--command", "save", "a.txt"])
+                .unwrap();
+        assert!(args.is_batch());
+        assert_eq!(args.commands, vec!["replace a b", "save"]);
+        assert_eq!(args.files, vec!["a.txt"]);
+
+        let args = Args::try_parse_from(["fresh", "--script", "edit.ts", "a.txt"]).unwrap();
+        assert!(args.is_batch());
+        assert!(Args::try_parse_from(["fresh", "--batch", "a.txt"])
+            .unwrap()
+            .is_batch());
+    }
+
     #[test]
     fn test_parse_file_location_with_line() {
         let loc = parse_file_location("foo.txt:42");
@@ -1,12 +1,13 @@
 /// Text buffer that uses PieceTree with integrated line tracking
 /// Architecture where the tree is the single source of truth for text and line information
 use crate::model::piece_tree::{
-    BufferData, BufferLocation, Cursor, PieceInfo, PieceRangeIter, PieceTree, Position,
-    StringBuffer, TreeStats,
+    merge_adjacent_leaves, BufferData, BufferLocation, Cursor, LeafData, PieceInfo, PieceRangeIter,
+    PieceTree, PieceTreeNode, PieceView, Position, StringBuffer, TreeStats,
 };
 use crate::model::piece_tree_diff::PieceTreeDiff;
 use anyhow::{Context, Result};
 use regex::bytes::Regex;
+use std::collections::VecDeque;
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -22,6 +23,50 @@ pub const LOAD_CHUNK_SIZE: usize = 1024 * 1024;
 /// Chunk alignment for lazy loading (64 KB)
 pub const CHUNK_ALIGNMENT: usize = 64 * 1024;
 
+/// Inserts up to this size are stored in the append buffer, which grows up
+/// to this size before a new one is started (1 MB)
+const APPEND_BUFFER_MAX_BYTES: usize = 1024 * 1024;
+
+/// Number of recent changes kept for `changes_since`
+const CHANGE_LOG_CAPACITY: usize = 1024;
+
+/// Most inserted bytes kept in the change log, so large pastes are not kept
+/// twice (1 MB)
+const CHANGE_LOG_MAX_BYTES: usize = 1024 * 1024;
+
+/// Pieces shorter than this are copied together when compacting
+const SMALL_PIECE_BYTES: usize = 256;
+
+/// Largest buffer created when compacting copies small pieces together (64 KB)
+const COMPACT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// One edit of a buffer, as recorded in its change log.
+///
+/// Applying the changes returned by `TextBuffer::changes_since(v)` in order
+/// to the content at version `v` gives the current content, so consumers
+/// (language servers, highlighters, plugins) can follow the buffer
+/// incrementally instead of copying all of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferChange {
+    /// Version of the buffer after this edit
+    pub version: u64,
+    /// Byte range removed, in the content before this edit
+    pub removed: Range<usize>,
+    /// Bytes inserted at `removed.start`
+    pub inserted: Vec<u8>,
+}
+
+/// What compacting a buffer did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionStats {
+    /// Pieces before compacting
+    pub pieces_before: usize,
+    /// Pieces after compacting
+    pub pieces_after: usize,
+    /// Bytes copied from small pieces into new buffers
+    pub bytes_copied: usize,
+}
+
 /// Line ending format used in the file
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LineEnding {
@@ -118,6 +163,22 @@ pub struct TextBuffer {
     /// Next buffer ID to assign
     next_buffer_id: usize,
 
+    /// Buffer that inserted text is appended to, so typing does not allocate
+    /// a buffer per keystroke
+    append_buffer: Option<usize>,
+
+    /// Version of the content, increased by every edit
+    version: u64,
+
+    /// The most recent edits, oldest first
+    change_log: VecDeque<BufferChange>,
+
+    /// Oldest version the change log reaches back to
+    change_log_start: u64,
+
+    /// Bytes inserted by the changes in the change log
+    change_log_bytes: usize,
+
     /// Optional file path for persistence
     file_path: Option<PathBuf>,
 
@@ -155,6 +216,11 @@ impl TextBuffer {
             piece_tree,
             buffers: vec![StringBuffer::new(0, Vec::new())],
             next_buffer_id: 1,
+            append_buffer: None,
+            version: 0,
+            change_log: VecDeque::new(),
+            change_log_start: 0,
+            change_log_bytes: 0,
             file_path: None,
             modified: false,
             recovery_pending: false,
@@ -190,6 +256,11 @@ impl TextBuffer {
             saved_root,
             buffers: vec![buffer],
             next_buffer_id: 1,
+            append_buffer: None,
+            version: 0,
+            change_log: VecDeque::new(),
+            change_log_start: 0,
+            change_log_bytes: 0,
             file_path: None,
             modified: false,
             recovery_pending: false,
@@ -213,6 +284,11 @@ impl TextBuffer {
             saved_root,
             buffers: vec![StringBuffer::new(0, Vec::new())],
             next_buffer_id: 1,
+            append_buffer: None,
+            version: 0,
+            change_log: VecDeque::new(),
+            change_log_start: 0,
+            change_log_bytes: 0,
             file_path: None,
             modified: false,
             recovery_pending: false,
@@ -234,19 +310,23 @@ impl TextBuffer {
         let metadata = std::fs::metadata(path)?;
         let file_size = metadata.len() as usize;
 
+        // Choose loading strategy based on file size
+        if Self::is_large_file_size(file_size, large_file_threshold) {
+            Self::load_large_file(path, file_size)
+        } else {
+            Self::load_small_file(path)
+        }
+    }
+
+    /// Whether a file of `file_size` bytes is loaded lazily, as a large file
+    pub fn is_large_file_size(file_size: usize, large_file_threshold: usize) -> bool {
         // Use threshold parameter or default
         let threshold = if large_file_threshold > 0 {
             large_file_threshold
         } else {
             DEFAULT_LARGE_FILE_THRESHOLD
         };
-
-        // Choose loading strategy based on file size
-        if file_size >= threshold {
-            Self::load_large_file(path, file_size)
-        } else {
-            Self::load_small_file(path)
-        }
+        file_size >= threshold
     }
 
     /// Load a small file with full eager loading and line indexing
@@ -255,7 +335,13 @@ impl TextBuffer {
         let mut file = std::fs::File::open(path)?;
         let mut contents = Vec::new();
         file.read_to_end(&mut contents)?;
+        Ok(Self::from_file_contents(path, contents))
+    }
 
+    /// Create a buffer for the file at `path` from contents already read,
+    /// with full line indexing. The contents may be just the start of the
+    /// file, with the rest added by [`Self::append_loaded`].
+    pub fn from_file_contents(path: &Path, contents: Vec<u8>) -> Self {
         // Detect if this is a binary file
         let is_binary = Self::detect_binary(&contents);
 
@@ -269,7 +355,7 @@ impl TextBuffer {
         buffer.large_file = false;
         buffer.is_binary = is_binary;
         buffer.line_ending = line_ending;
-        Ok(buffer)
+        buffer
     }
 
     /// Load a large file with unloaded buffer (no line indexing, lazy loading)
@@ -320,6 +406,11 @@ impl TextBuffer {
             saved_root,
             buffers: vec![buffer],
             next_buffer_id: 1,
+            append_buffer: None,
+            version: 0,
+            change_log: VecDeque::new(),
+            change_log_start: 0,
+            change_log_bytes: 0,
             file_path: Some(path.to_path_buf()),
             modified: false,
             recovery_pending: false,
@@ -351,11 +442,11 @@ impl TextBuffer {
         let dest_path = path.as_ref();
         let total = self.total_bytes();
 
-        // Get original file metadata (permissions, owner, etc.) before writing
-        // so we can preserve it after creating/renaming the temp file
-        let original_metadata = std::fs::metadata(dest_path).ok();
-
         if total == 0 {
+            // Get original file metadata (permissions, owner, etc.) before
+            // writing so we can preserve it
+            let original_metadata = std::fs::metadata(dest_path).ok();
+
             // Empty file - just create it
             std::fs::File::create(dest_path)?;
             if let Some(ref meta) = original_metadata {
@@ -368,80 +459,21 @@ impl TextBuffer {
         }
 
         // Use a temp file to avoid corrupting the original if something goes wrong
-        let temp_path = dest_path.with_extension("tmp");
-        let mut out_file = std::fs::File::create(&temp_path)?;
+        let mut write = AtomicWrite::create(dest_path)?;
 
         // Cache for open source files (for streaming unloaded regions)
         let mut source_file_cache: Option<(PathBuf, std::fs::File)> = None;
 
         // Iterate through all pieces and write them
         for piece_view in self.piece_tree.iter_pieces_in_range(0, total) {
-            let buffer_id = piece_view.location.buffer_id();
-            let buffer = self.buffers.get(buffer_id).ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("Buffer {} not found", buffer_id),
-                )
-            })?;
-
-            match &buffer.data {
-                BufferData::Loaded { data, .. } => {
-                    // Write from memory (line endings are already correct in buffer)
-                    let start = piece_view.buffer_offset;
-                    let end = start + piece_view.bytes;
-                    let chunk = &data[start..end];
-                    out_file.write_all(chunk)?;
-                }
-                BufferData::Unloaded {
-                    file_path,
-                    file_offset,
-                    ..
-                } => {
-                    // Stream from source file without loading into memory
-                    // NOTE: Unloaded regions come directly from the original file and already
-                    // have the correct line endings, so we don't need to convert them
-                    let source_file = match &mut source_file_cache {
-                        Some((cached_path, file)) if cached_path == file_path => file,
-                        _ => {
-                            let file = std::fs::File::open(file_path)?;
-                            source_file_cache = Some((file_path.clone(), file));
-                            &mut source_file_cache.as_mut().unwrap().1
-                        }
-                    };
-
-                    // Seek to the right position in source file
-                    let read_offset = *file_offset + piece_view.buffer_offset;
-                    source_file.seek(SeekFrom::Start(read_offset as u64))?;
-
-                    // Stream in chunks to avoid large memory allocation
-                    const STREAM_CHUNK_SIZE: usize = 64 * 1024; // 64KB chunks
-                    let mut remaining = piece_view.bytes;
-                    let mut chunk_buf = vec![0u8; STREAM_CHUNK_SIZE.min(remaining)];
-
-                    while remaining > 0 {
-                        let to_read = remaining.min(chunk_buf.len());
-                        source_file.read_exact(&mut chunk_buf[..to_read])?;
-                        out_file.write_all(&chunk_buf[..to_read])?;
-                        remaining -= to_read;
-                    }
-                }
-            }
-        }
-
-        // Ensure all data is written
-        out_file.sync_all()?;
-        drop(out_file);
-
-        // Restore original file permissions/owner before renaming
-        if let Some(ref meta) = original_metadata {
-            Self::restore_file_metadata(&temp_path, meta)?;
+            let piece = self.piece_bytes(&piece_view)?;
+            write_piece(write.file(), piece, &mut source_file_cache, &mut |_| {})?;
         }
 
         // Atomically replace the original file
-        std::fs::rename(&temp_path, dest_path)?;
+        let new_size = write.commit()?;
 
         // Update saved file size to match the file on disk
-        let new_size = std::fs::metadata(dest_path)?.len() as usize;
         tracing::debug!(
             "Buffer::save: updating saved_file_size from {:?} to {}",
             self.saved_file_size,
@@ -454,6 +486,79 @@ impl TextBuffer {
         Ok(())
     }
 
+    /// Where the bytes of a piece are: in memory, or still in its source file
+    fn piece_bytes(&self, piece_view: &PieceView) -> io::Result<PieceBytes<'_>> {
+        let buffer_id = piece_view.location.buffer_id();
+        let buffer = self.buffers.get(buffer_id).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Buffer {} not found", buffer_id),
+            )
+        })?;
+
+        Ok(match &buffer.data {
+            BufferData::Loaded { data, .. } => {
+                // Line endings are already correct in buffer
+                let start = piece_view.buffer_offset;
+                PieceBytes::Loaded(&data[start..start + piece_view.bytes])
+            }
+            // NOTE: Unloaded regions come directly from the original file and already
+            // have the correct line endings, so we don't need to convert them
+            BufferData::Unloaded {
+                file_path,
+                file_offset,
+                ..
+            } => PieceBytes::Unloaded {
+                file_path,
+                offset: *file_offset + piece_view.buffer_offset,
+                bytes: piece_view.bytes,
+            },
+        })
+    }
+
+    /// Snapshot the content, to write it out while the buffer goes on
+    /// changing. Loaded text is copied; unloaded text stays in its source
+    /// file until written.
+    pub fn save_snapshot(&self) -> io::Result<SaveSnapshot> {
+        let total = self.total_bytes();
+        let mut pieces = Vec::new();
+        for piece_view in self.piece_tree.iter_pieces_in_range(0, total) {
+            pieces.push(match self.piece_bytes(&piece_view)? {
+                PieceBytes::Loaded(data) => SnapshotPiece::Loaded(data.to_vec()),
+                PieceBytes::Unloaded {
+                    file_path,
+                    offset,
+                    bytes,
+                } => SnapshotPiece::Unloaded {
+                    file_path: file_path.to_path_buf(),
+                    offset,
+                    bytes,
+                },
+            });
+        }
+        Ok(SaveSnapshot {
+            root: self.piece_tree.root(),
+            pieces,
+            len: total,
+        })
+    }
+
+    /// Record that `snapshot` was written to `path`. Edits made since the
+    /// snapshot was taken are left modified.
+    pub fn mark_snapshot_saved(&mut self, snapshot: &SaveSnapshot, path: &Path) {
+        self.saved_root = snapshot.root.clone();
+        self.saved_file_size = Some(snapshot.len);
+        self.file_path = Some(path.to_path_buf());
+        self.modified = !Arc::ptr_eq(&self.saved_root, &self.piece_tree.root());
+    }
+
+    /// Record that the full content was written to the buffer's file by
+    /// another process (e.g. a privileged helper)
+    pub fn mark_written_externally(&mut self) {
+        self.saved_file_size = Some(self.total_bytes());
+        self.mark_saved_snapshot();
+    }
+
     /// Restore file metadata (permissions, owner/group) from original file
     fn restore_file_metadata(path: &Path, original_meta: &std::fs::Metadata) -> io::Result<()> {
         // Restore permissions (works cross-platform)
@@ -728,21 +833,37 @@ impl TextBuffer {
         self.modified = true;
         self.recovery_pending = true;
 
+        self.record_change(offset..offset, &text);
+
         // Count line feeds in the text to insert
         let line_feed_cnt = Some(text.iter().filter(|&&b| b == b'\n').count());
 
-        // Optimization: try to append to existing buffer if insertion is at piece boundary
-        let (buffer_location, buffer_offset, text_len) =
-            if let Some(append_info) = self.try_append_to_existing_buffer(offset, &text) {
-                append_info
-            } else {
-                // Create a new StringBuffer for this insertion
-                let buffer_id = self.next_buffer_id;
-                self.next_buffer_id += 1;
-                let buffer = StringBuffer::new(buffer_id, text.clone());
-                self.buffers.push(buffer);
-                (BufferLocation::Added(buffer_id), 0, text.len())
-            };
+        // Text inserted right after the text last inserted extends its piece
+        if let Some((location, buffer_offset, text_len)) =
+            self.try_append_to_existing_buffer(offset, &text)
+        {
+            if self.piece_tree.extend_piece(
+                offset,
+                location,
+                buffer_offset,
+                text_len,
+                line_feed_cnt,
+            ) {
+                return self.piece_tree.cursor_at_offset(offset + text_len);
+            }
+            return self.piece_tree.insert(
+                offset,
+                location,
+                buffer_offset,
+                text_len,
+                line_feed_cnt,
+                &self.buffers,
+            );
+        }
+
+        // Other text goes at the end of the append buffer, in a new piece
+        let text_len = text.len();
+        let (buffer_location, buffer_offset) = self.store_inserted_text(text);
 
         // Update piece tree (need to pass buffers reference)
         self.piece_tree.insert(
@@ -805,6 +926,32 @@ impl TextBuffer {
         Some((piece_info.location, append_offset, text.len()))
     }
 
+    /// Store inserted text at the end of the append buffer, or in a buffer
+    /// of its own if it is large or the append buffer is full.
+    /// Returns where the text is stored.
+    fn store_inserted_text(&mut self, text: Vec<u8>) -> (BufferLocation, usize) {
+        if let Some(buffer) = self
+            .append_buffer
+            .and_then(|buffer_id| self.buffers.get_mut(buffer_id))
+        {
+            let fits = buffer
+                .get_data()
+                .is_some_and(|data| data.len() + text.len() <= APPEND_BUFFER_MAX_BYTES);
+            if fits {
+                let buffer_offset = buffer.append(&text);
+                return (BufferLocation::Added(buffer.id), buffer_offset);
+            }
+        }
+
+        let buffer_id = self.next_buffer_id;
+        self.next_buffer_id += 1;
+        if text.len() < APPEND_BUFFER_MAX_BYTES {
+            self.append_buffer = Some(buffer_id);
+        }
+        self.buffers.push(StringBuffer::new(buffer_id, text));
+        (BufferLocation::Added(buffer_id), 0)
+    }
+
     /// Insert text (from &str) at the given byte offset
     pub fn insert(&mut self, offset: usize, text: &str) {
         self.insert_bytes(offset, text.as_bytes().to_vec());
@@ -822,22 +969,22 @@ impl TextBuffer {
         self.modified = true;
         self.recovery_pending = true;
 
+        let offset = self.position_to_offset(position);
+        self.record_change(offset..offset, &text);
+
         // Count line feeds in the text to insert
         let line_feed_cnt = text.iter().filter(|&&b| b == b'\n').count();
 
-        // Create a new StringBuffer for this insertion
-        let buffer_id = self.next_buffer_id;
-        self.next_buffer_id += 1;
-        let buffer = StringBuffer::new(buffer_id, text.clone());
-        self.buffers.push(buffer);
+        let text_len = text.len();
+        let (buffer_location, buffer_offset) = self.store_inserted_text(text);
 
         // Use the optimized position-based insertion (single traversal)
         self.piece_tree.insert_at_position(
             position.line,
             position.column,
-            BufferLocation::Added(buffer_id),
-            0,
-            text.len(),
+            buffer_location,
+            buffer_offset,
+            text_len,
             line_feed_cnt,
             &self.buffers,
         )
@@ -849,6 +996,9 @@ impl TextBuffer {
             return;
         }
 
+        let end = (offset + bytes).min(self.total_bytes());
+        self.record_change(offset..end, &[]);
+
         // Update piece tree
         self.piece_tree.delete(offset, bytes, &self.buffers);
 
@@ -868,13 +1018,15 @@ impl TextBuffer {
     /// This now uses the optimized piece_tree.delete_position_range() for a single traversal
     pub fn delete_range(&mut self, start: Position, end: Position) {
         // Use the optimized position-based deletion
-        self.piece_tree.delete_position_range(
+        if let Some(removed) = self.piece_tree.delete_position_range(
             start.line,
             start.column,
             end.line,
             end.column,
             &self.buffers,
-        );
+        ) {
+            self.record_change(removed, &[]);
+        }
         // Mark as modified and needing recovery
         self.modified = true;
         self.recovery_pending = true;
@@ -1221,6 +1373,115 @@ impl TextBuffer {
             None, // line_feed_cnt unknown for unloaded chunk
             &self.buffers,
         );
+
+        // The appended text is not loaded, so it cannot be logged as a change
+        self.reset_change_log();
+    }
+
+    /// Append the next part of a file that is still being read, with line
+    /// indexing. Like [`Self::extend_streaming`], the appended text is part of
+    /// the file, so it neither modifies the buffer nor is logged as a change.
+    pub fn append_loaded(&mut self, data: Vec<u8>) {
+        if data.is_empty() {
+            return;
+        }
+        let old_size = self.total_bytes();
+        let bytes = data.len();
+
+        let buffer_id = self.next_buffer_id;
+        self.next_buffer_id += 1;
+        let new_buffer = StringBuffer::new(buffer_id, data);
+        let line_feed_cnt = new_buffer.line_feed_count();
+        self.buffers.push(new_buffer);
+
+        let was_saved = Arc::ptr_eq(&self.saved_root, &self.piece_tree.root());
+        self.piece_tree.insert(
+            old_size,
+            BufferLocation::Stored(buffer_id),
+            0,
+            bytes,
+            line_feed_cnt,
+            &self.buffers,
+        );
+        if was_saved {
+            self.saved_root = self.piece_tree.root();
+        }
+        if let Some(size) = self.saved_file_size.as_mut() {
+            *size += bytes;
+        }
+
+        self.reset_change_log();
+    }
+
+    /// Version of the content. Every edit increases it by one.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The edits made since `version`, oldest first, or None if the change
+    /// log no longer reaches back that far (the caller should then read the
+    /// whole content again)
+    pub fn changes_since(&self, version: u64) -> Option<Vec<BufferChange>> {
+        if version > self.version || version < self.change_log_start {
+            return None;
+        }
+        Some(
+            self.change_log
+                .iter()
+                .filter(|change| change.version > version)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Bump the version and log an edit. The oldest changes are dropped to
+    /// keep the log within [`CHANGE_LOG_CAPACITY`] changes and
+    /// [`CHANGE_LOG_MAX_BYTES`] inserted bytes.
+    fn record_change(&mut self, removed: Range<usize>, inserted: &[u8]) {
+        if inserted.len() > CHANGE_LOG_MAX_BYTES {
+            self.reset_change_log();
+            return;
+        }
+        self.version += 1;
+        while self.change_log.len() == CHANGE_LOG_CAPACITY
+            || self.change_log_bytes + inserted.len() > CHANGE_LOG_MAX_BYTES
+        {
+            let Some(oldest) = self.change_log.pop_front() else {
+                break;
+            };
+            self.change_log_start = oldest.version;
+            self.change_log_bytes -= oldest.inserted.len();
+        }
+        self.change_log_bytes += inserted.len();
+        self.change_log.push_back(BufferChange {
+            version: self.version,
+            removed,
+            inserted: inserted.to_vec(),
+        });
+    }
+
+    /// Bump the version after a change that cannot be logged, so earlier
+    /// versions can no longer be followed incrementally
+    fn reset_change_log(&mut self) {
+        self.version += 1;
+        self.change_log.clear();
+        self.change_log_start = self.version;
+        self.change_log_bytes = 0;
+    }
+
+    /// Replace the content with `buffer`, keeping the version increasing.
+    /// Earlier versions can no longer be followed incrementally.
+    pub fn replace(&mut self, buffer: TextBuffer) {
+        let version = self.version;
+        *self = buffer;
+        self.continue_versions_from(version);
+    }
+
+    /// Continue the versions of a buffer this one replaces, whose version
+    /// was `version`, so consumers following it see a new version
+    pub fn continue_versions_from(&mut self, version: u64) {
+        self.version = self.version.max(version);
+        self.reset_change_log();
     }
 
     /// Check if the buffer has been modified since last save
@@ -1526,6 +1787,88 @@ impl TextBuffer {
         self.piece_tree.stats()
     }
 
+    /// Number of string buffers pieces can refer to
+    pub fn string_buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Merge the small pieces left behind by many small edits.
+    ///
+    /// Adjacent pieces over consecutive bytes of one buffer are merged as
+    /// they are; runs of other small pieces are copied into a new buffer.
+    /// The content is unchanged, so the buffer's modified state is too.
+    pub fn compact(&mut self) -> CompactionStats {
+        let leaves = self.piece_tree.get_leaves();
+        let pieces_before = leaves.len();
+        let merged = merge_adjacent_leaves(leaves);
+
+        let mut compacted = Vec::with_capacity(merged.len());
+        let mut run: Vec<LeafData> = Vec::new();
+        let mut run_bytes = 0;
+        let mut bytes_copied = 0;
+        for leaf in merged {
+            let small = leaf.bytes < SMALL_PIECE_BYTES && self.leaf_data(&leaf).is_some();
+            if !small || run_bytes + leaf.bytes > COMPACT_CHUNK_BYTES {
+                bytes_copied += self.flush_compaction_run(&mut run, &mut compacted);
+                run_bytes = 0;
+            }
+            if small {
+                run_bytes += leaf.bytes;
+                run.push(leaf);
+            } else {
+                compacted.push(leaf);
+            }
+        }
+        bytes_copied += self.flush_compaction_run(&mut run, &mut compacted);
+
+        let stats = CompactionStats {
+            pieces_before,
+            pieces_after: compacted.len().max(1),
+            bytes_copied,
+        };
+        if stats.pieces_after < pieces_before {
+            let was_saved = Arc::ptr_eq(&self.saved_root, &self.piece_tree.root());
+            self.piece_tree.set_leaves(&compacted);
+            if was_saved {
+                self.saved_root = self.piece_tree.root();
+            }
+        }
+        stats
+    }
+
+    /// Bytes of a piece, if its buffer is loaded
+    fn leaf_data(&self, leaf: &LeafData) -> Option<&[u8]> {
+        let data = self.buffers.get(leaf.location.buffer_id())?.get_data()?;
+        data.get(leaf.offset..leaf.offset + leaf.bytes)
+    }
+
+    /// Copy a run of small pieces into one new buffer and add its piece to
+    /// `out`. A run of one piece is kept as it is. Returns the bytes copied.
+    fn flush_compaction_run(&mut self, run: &mut Vec<LeafData>, out: &mut Vec<LeafData>) -> usize {
+        if run.len() < 2 {
+            out.append(run);
+            return 0;
+        }
+        let mut data = Vec::new();
+        for leaf in run.iter() {
+            data.extend_from_slice(self.leaf_data(leaf).unwrap_or_default());
+        }
+        run.clear();
+
+        let buffer_id = self.next_buffer_id;
+        self.next_buffer_id += 1;
+        let bytes = data.len();
+        let line_feed_cnt = Some(data.iter().filter(|&&b| b == b'\n').count());
+        self.buffers.push(StringBuffer::new(buffer_id, data));
+        out.push(LeafData::new(
+            BufferLocation::Added(buffer_id),
+            0,
+            bytes,
+            line_feed_cnt,
+        ));
+        bytes
+    }
+
     // Search and Replace Operations
 
     /// Find the next occurrence of a pattern, with wrap-around
@@ -2423,6 +2766,187 @@ impl<'a> Iterator for OverlappingChunks<'a> {
     }
 }
 
+/// Chunk size for writing a buffer out (64 KB)
+const WRITE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The bytes of one piece of a buffer, to write out
+enum PieceBytes<'a> {
+    /// In memory
+    Loaded(&'a [u8]),
+    /// Still only in the file the buffer was loaded from
+    Unloaded {
+        file_path: &'a Path,
+        offset: usize,
+        bytes: usize,
+    },
+}
+
+/// Write one piece to `out` in chunks, streaming unloaded pieces from their
+/// source file without loading them into memory. `source_file_cache` keeps
+/// the source file last opened. `written` is called with each chunk written.
+fn write_piece(
+    out: &mut impl Write,
+    piece: PieceBytes<'_>,
+    source_file_cache: &mut Option<(PathBuf, std::fs::File)>,
+    written: &mut dyn FnMut(&[u8]),
+) -> io::Result<()> {
+    match piece {
+        PieceBytes::Loaded(data) => {
+            for chunk in data.chunks(WRITE_CHUNK_SIZE) {
+                out.write_all(chunk)?;
+                written(chunk);
+            }
+        }
+        PieceBytes::Unloaded {
+            file_path,
+            offset,
+            bytes,
+        } => {
+            let source_file = match &mut *source_file_cache {
+                Some((cached_path, file)) if cached_path == file_path => file,
+                _ => {
+                    let file = std::fs::File::open(file_path)?;
+                    &mut source_file_cache.insert((file_path.to_path_buf(), file)).1
+                }
+            };
+
+            // Seek to the right position in source file
+            source_file.seek(SeekFrom::Start(offset as u64))?;
+
+            // Stream in chunks to avoid large memory allocation
+            let mut remaining = bytes;
+            let mut chunk_buf = vec![0u8; WRITE_CHUNK_SIZE.min(remaining)];
+            while remaining > 0 {
+                let to_read = remaining.min(chunk_buf.len());
+                source_file.read_exact(&mut chunk_buf[..to_read])?;
+                out.write_all(&chunk_buf[..to_read])?;
+                written(&chunk_buf[..to_read]);
+                remaining -= to_read;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A piece of a [`SaveSnapshot`]
+enum SnapshotPiece {
+    Loaded(Vec<u8>),
+    Unloaded {
+        file_path: PathBuf,
+        offset: usize,
+        bytes: usize,
+    },
+}
+
+/// The content of a buffer at one moment, from [`TextBuffer::save_snapshot`]
+pub struct SaveSnapshot {
+    root: Arc<PieceTreeNode>,
+    pieces: Vec<SnapshotPiece>,
+    len: usize,
+}
+
+impl SaveSnapshot {
+    /// Size of the content in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the content is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Write the content to `out`, calling `written` with each chunk written
+    pub fn write_to(&self, out: &mut impl Write, written: &mut dyn FnMut(&[u8])) -> io::Result<()> {
+        let mut source_file_cache = None;
+        for piece in &self.pieces {
+            let piece = match piece {
+                SnapshotPiece::Loaded(data) => PieceBytes::Loaded(data),
+                SnapshotPiece::Unloaded {
+                    file_path,
+                    offset,
+                    bytes,
+                } => PieceBytes::Unloaded {
+                    file_path,
+                    offset: *offset,
+                    bytes: *bytes,
+                },
+            };
+            write_piece(&mut *out, piece, &mut source_file_cache, written)?;
+        }
+        Ok(())
+    }
+}
+
+/// A file written through a temp file next to it, which replaces the file
+/// once complete. Dropped before then, it removes the temp file and leaves
+/// the file as it was.
+pub struct AtomicWrite {
+    dest_path: PathBuf,
+    temp_path: PathBuf,
+    /// The temp file, until committed
+    file: Option<std::fs::File>,
+    /// Permissions and owner of the file being replaced
+    original_metadata: Option<std::fs::Metadata>,
+    committed: bool,
+}
+
+impl AtomicWrite {
+    /// Create the temp file for writing `dest_path`
+    pub fn create(dest_path: &Path) -> io::Result<Self> {
+        let original_metadata = std::fs::metadata(dest_path).ok();
+        let temp_path = dest_path.with_extension("tmp");
+        let file = std::fs::File::create(&temp_path)?;
+        Ok(Self {
+            dest_path: dest_path.to_path_buf(),
+            temp_path,
+            file: Some(file),
+            original_metadata,
+            committed: false,
+        })
+    }
+
+    /// The temp file to write to
+    pub fn file(&mut self) -> &mut std::fs::File {
+        self.file
+            .as_mut()
+            .expect("temp file is open until committed")
+    }
+
+    /// Path of the temp file
+    pub fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    /// Sync the temp file and move it over the file, with the original
+    /// file's permissions and owner. Returns the size of the new file.
+    pub fn commit(mut self) -> io::Result<usize> {
+        // Ensure all data is written
+        if let Some(file) = self.file.take() {
+            file.sync_all()?;
+        }
+
+        // Restore original file permissions/owner before renaming
+        if let Some(ref meta) = self.original_metadata {
+            TextBuffer::restore_file_metadata(&self.temp_path, meta)?;
+        }
+
+        std::fs::rename(&self.temp_path, &self.dest_path)?;
+        self.committed = true;
+        Ok(std::fs::metadata(&self.dest_path)?.len() as usize)
+    }
+}
+
+impl Drop for AtomicWrite {
+    fn drop(&mut self) {
+        if !self.committed {
+            // Close the temp file first, as some platforms can't remove open files
+            drop(self.file.take());
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2473,6 +2997,150 @@ mod tests {
         assert_eq!(buffer.get_all_text().unwrap(), b"hello\nworld");
     }
 
+    #[test]
+    fn test_typing_coalesces_into_append_buffer() {
+        let mut buffer = TextBuffer::from_bytes(b"hello\nworld\n".to_vec());
+
+        // Consecutive typing grows a single piece
+        for (i, ch) in "abc\ndef".chars().enumerate() {
+            buffer.insert(6 + i, &ch.to_string());
+        }
+        assert_eq!(buffer.stats().leaf_count, 3);
+        assert_eq!(buffer.line_count(), Some(4));
+
+        // Typing elsewhere adds pieces but no more buffers
+        buffer.insert(0, ">");
+        buffer.insert(buffer.len(), "<");
+        buffer.insert_at_position(Position { line: 1, column: 0 }, b"~".to_vec());
+        assert_eq!(buffer.string_buffer_count(), 2);
+        assert_eq!(
+            buffer.get_all_text().unwrap(),
+            b">hello\n~abc\ndefworld\n<".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_changes_since_replays_edits() {
+        let mut buffer = TextBuffer::from_bytes(b"hello\nworld\n".to_vec());
+        assert_eq!(buffer.version(), 0);
+        let mut copy = buffer.get_all_text().unwrap();
+
+        buffer.insert(5, ",");
+        buffer.delete_bytes(0, 1);
+        buffer.insert_at_position(Position { line: 1, column: 0 }, b"big ".to_vec());
+        buffer.delete_range(
+            Position { line: 0, column: 4 },
+            Position { line: 1, column: 0 },
+        );
+        buffer.delete_bytes(buffer.len(), 3);
+        assert_eq!(buffer.version(), 4);
+
+        let changes = buffer.changes_since(0).unwrap();
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[0].removed, 5..5);
+        assert_eq!(changes[1].removed, 0..1);
+        for change in changes {
+            copy.splice(change.removed, change.inserted);
+        }
+        assert_eq!(copy, buffer.get_all_text().unwrap());
+
+        assert_eq!(buffer.changes_since(4), Some(Vec::new()));
+        assert_eq!(buffer.changes_since(5), None);
+    }
+
+    #[test]
+    fn test_change_log_is_bounded() {
+        let mut buffer = TextBuffer::from_bytes(Vec::new());
+        for i in 0..CHANGE_LOG_CAPACITY + 10 {
+            buffer.insert(i, "x");
+        }
+        assert_eq!(buffer.changes_since(0), None);
+        assert_eq!(buffer.changes_since(9), None);
+        assert_eq!(
+            buffer.changes_since(10).map(|changes| changes.len()),
+            Some(CHANGE_LOG_CAPACITY)
+        );
+    }
+
+    #[test]
+    fn test_change_log_bytes_are_bounded() {
+        let mut buffer = TextBuffer::from_bytes(Vec::new());
+        let half = "x".repeat(CHANGE_LOG_MAX_BYTES / 2);
+        buffer.insert(0, &half);
+        buffer.insert(0, &half);
+        assert_eq!(
+            buffer.changes_since(0).map(|changes| changes.len()),
+            Some(2)
+        );
+
+        // The oldest change is dropped to make room
+        buffer.insert(0, "y");
+        assert_eq!(buffer.changes_since(0), None);
+        assert_eq!(
+            buffer.changes_since(1).map(|changes| changes.len()),
+            Some(2)
+        );
+
+        // A change too large to keep is not logged, but still versioned
+        buffer.insert(0, &"z".repeat(CHANGE_LOG_MAX_BYTES + 1));
+        assert_eq!(buffer.version(), 4);
+        assert_eq!(buffer.changes_since(3), None);
+        assert_eq!(buffer.changes_since(4), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_replace_keeps_version_increasing() {
+        let mut buffer = TextBuffer::from_bytes(b"hello".to_vec());
+        buffer.insert(5, "!");
+        buffer.insert(6, "!");
+        buffer.replace(TextBuffer::from_bytes(b"other".to_vec()));
+        assert_eq!(buffer.version(), 3);
+        assert_eq!(buffer.changes_since(2), None);
+        assert_eq!(buffer.get_all_text().unwrap(), b"other".to_vec());
+    }
+
+    #[test]
+    fn test_compact_merges_small_pieces() {
+        let mut buffer = TextBuffer::from_bytes(b"hello\nworld\n".to_vec());
+        // Type at the end one character at a time, then scatter edits in the
+        // middle, each in its own piece
+        for (i, ch) in "abc\ndef".chars().enumerate() {
+            buffer.insert(12 + i, &ch.to_string());
+        }
+        for i in 0..5 {
+            buffer.insert(2 + i * 2, "x");
+        }
+        let expected = buffer.get_all_text().unwrap();
+        let pieces = buffer.stats().leaf_count;
+
+        let stats = buffer.compact();
+        assert_eq!(stats.pieces_before, pieces);
+        assert!(stats.pieces_after < 4, "{:?}", stats);
+        assert_eq!(buffer.stats().leaf_count, stats.pieces_after);
+        assert_eq!(buffer.get_all_text().unwrap(), expected);
+        assert_eq!(buffer.line_count(), Some(4));
+        let last_line = expected.iter().rposition(|&b| b == b'\n').unwrap() + 1;
+        assert_eq!(buffer.line_start_offset(3), Some(last_line));
+
+        // Compacting again has nothing left to do
+        let again = buffer.compact();
+        assert_eq!(again.pieces_before, again.pieces_after);
+        assert_eq!(again.bytes_copied, 0);
+    }
+
+    #[test]
+    fn test_compact_keeps_saved_state() {
+        let mut buffer = TextBuffer::from_bytes(b"hello".to_vec());
+        for (i, ch) in " world".chars().enumerate() {
+            buffer.insert(5 + i, &ch.to_string());
+        }
+        buffer.mark_saved_snapshot();
+
+        buffer.compact();
+        assert!(!buffer.is_modified());
+        assert!(buffer.diff_since_saved().equal);
+    }
+
     #[test]
     fn test_insert_at_start() {
         let mut buffer = TextBuffer::from_bytes(b"world".to_vec());
@@ -2791,6 +3459,48 @@ mod tests {
             assert!(buffer.buffers[0].is_loaded());
         }
 
+        #[test]
+        fn test_append_loaded_keeps_buffer_saved() {
+            let mut buffer =
+                TextBuffer::from_file_contents(Path::new("/tmp/slow.txt"), b"hello\nwo".to_vec());
+            buffer.append_loaded(b"rld\nagain\n".to_vec());
+
+            assert_eq!(buffer.get_all_text().unwrap(), b"hello\nworld\nagain\n");
+            assert_eq!(buffer.line_count(), Some(4));
+            assert!(!buffer.is_modified());
+            assert!(buffer.diff_since_saved().equal);
+            assert_eq!(buffer.file_path(), Some(Path::new("/tmp/slow.txt")));
+        }
+
+        #[test]
+        fn test_save_snapshot_ignores_later_edits() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("large.txt");
+            File::create(&file_path)
+                .unwrap()
+                .write_all(b"hello world\n")
+                .unwrap();
+
+            // Large file, so the text stays unloaded apart from the edit
+            let mut buffer = TextBuffer::load_from_file(&file_path, 5).unwrap();
+            buffer.insert(0, "> ");
+            let snapshot = buffer.save_snapshot().unwrap();
+            buffer.insert(buffer.len(), "more\n");
+
+            let mut written = Vec::new();
+            let mut reported = 0;
+            snapshot
+                .write_to(&mut written, &mut |chunk| reported += chunk.len())
+                .unwrap();
+            assert_eq!(written, b"> hello world\n");
+            assert_eq!(snapshot.len(), written.len());
+            assert_eq!(reported, written.len());
+
+            buffer.mark_snapshot_saved(&snapshot, &file_path);
+            assert!(buffer.is_modified());
+            assert!(!buffer.diff_since_saved().equal);
+        }
+
         #[test]
         fn test_load_large_file_lazy_loading() {
             let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,434 @@
+//! Sequence CRDT for buffers edited by several editors at once.
+//!
+//! Every byte of a shared buffer is an element with a unique [`ElementId`].
+//! Inserting puts new elements after the element they follow, and deleting
+//! only marks elements as deleted (tombstones), so operations from other
+//! sites can still refer to them. Concurrent inserts after the same element
+//! are ordered by id (RGA), so sites that have applied the same operations
+//! have the same text, whatever order the operations arrived in.
+//!
+//! The elements are kept in a flat vector: fine for the small buffers this
+//! is meant for, not for large files.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Site of the elements of the initial text, shared by all sites
+pub const INITIAL_SITE: u64 = 0;
+
+/// Identity of an element: a Lamport clock and the site that made it.
+/// Later inserts have higher clocks, so they sort first among elements
+/// inserted after the same one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ElementId {
+    pub clock: u64,
+    pub site: u64,
+}
+
+/// An edit made at one site, to be applied at the others
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CrdtOp {
+    /// `bytes` inserted after the element `after` (`None`: at the start).
+    /// Byte `i` gets the id `(id.clock + i, id.site)` and follows byte `i - 1`.
+    Insert {
+        id: ElementId,
+        after: Option<ElementId>,
+        bytes: Vec<u8>,
+    },
+    /// Elements deleted
+    Delete { ids: Vec<ElementId> },
+}
+
+/// Consecutive elements from one site with consecutive clocks, used to
+/// send a whole document compactly
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementRun {
+    /// Id of the first element
+    pub id: ElementId,
+    pub bytes: Vec<u8>,
+    pub deleted: bool,
+}
+
+/// A change to the visible text made by applying an operation. Edits are
+/// applied in order, each to the text left by the previous one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range removed
+    pub removed: Range<usize>,
+    /// Bytes inserted at `removed.start`
+    pub inserted: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Element {
+    id: ElementId,
+    byte: u8,
+    deleted: bool,
+}
+
+/// The text of a shared buffer as seen by one site
+#[derive(Debug, Clone)]
+pub struct CrdtText {
+    site: u64,
+    /// Highest clock seen; new elements get higher ones
+    clock: u64,
+    elements: Vec<Element>,
+}
+
+impl CrdtText {
+    /// A document holding `text`, edited by `site`. All sites must start
+    /// from the same text (or from the runs of another site).
+    pub fn new(site: u64, text: &[u8]) -> Self {
+        let elements = text
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| Element {
+                id: ElementId {
+                    clock: i as u64 + 1,
+                    site: INITIAL_SITE,
+                },
+                byte,
+                deleted: false,
+            })
+            .collect();
+        Self {
+            site,
+            clock: text.len() as u64,
+            elements,
+        }
+    }
+
+    /// A document received from another site as `runs`, edited by `site`
+    pub fn from_runs(site: u64, runs: &[ElementRun]) -> Self {
+        let mut elements = Vec::new();
+        let mut clock = 0;
+        for run in runs {
+            for (i, &byte) in run.bytes.iter().enumerate() {
+                let id = ElementId {
+                    clock: run.id.clock + i as u64,
+                    site: run.id.site,
+                };
+                clock = clock.max(id.clock);
+                elements.push(Element {
+                    id,
+                    byte,
+                    deleted: run.deleted,
+                });
+            }
+        }
+        Self {
+            site,
+            clock,
+            elements,
+        }
+    }
+
+    /// All elements, including deleted ones, as runs
+    pub fn runs(&self) -> Vec<ElementRun> {
+        let mut runs: Vec<ElementRun> = Vec::new();
+        for element in &self.elements {
+            if let Some(run) = runs.last_mut() {
+                let continues = run.id.site == element.id.site
+                    && run.id.clock + run.bytes.len() as u64 == element.id.clock
+                    && run.deleted == element.deleted;
+                if continues {
+                    run.bytes.push(element.byte);
+                    continue;
+                }
+            }
+            runs.push(ElementRun {
+                id: element.id,
+                bytes: vec![element.byte],
+                deleted: element.deleted,
+            });
+        }
+        runs
+    }
+
+    /// Site this document makes edits as
+    pub fn site(&self) -> u64 {
+        self.site
+    }
+
+    /// The visible text
+    pub fn text(&self) -> Vec<u8> {
+        self.elements
+            .iter()
+            .filter(|element| !element.deleted)
+            .map(|element| element.byte)
+            .collect()
+    }
+
+    /// Index of the visible element at `offset`, or the end
+    fn index_of_offset(&self, offset: usize) -> usize {
+        let mut visible = 0;
+        for (index, element) in self.elements.iter().enumerate() {
+            if !element.deleted {
+                if visible == offset {
+                    return index;
+                }
+                visible += 1;
+            }
+        }
+        self.elements.len()
+    }
+
+    /// Number of visible elements before `index`
+    fn offset_of_index(&self, index: usize) -> usize {
+        self.elements[..index]
+            .iter()
+            .filter(|element| !element.deleted)
+            .count()
+    }
+
+    fn index_of_id(&self, id: ElementId) -> Option<usize> {
+        self.elements.iter().position(|element| element.id == id)
+    }
+
+    /// Id of the visible element before `offset`, to anchor a position
+    /// that stays put when others edit the text
+    pub fn id_before(&self, offset: usize) -> Option<ElementId> {
+        let index = self.index_of_offset(offset.checked_sub(1)?);
+        self.elements.get(index).map(|element| element.id)
+    }
+
+    /// Offset just after the element `id` (`None`: the start). For a
+    /// deleted element, the offset where it was.
+    pub fn offset_after(&self, id: Option<ElementId>) -> usize {
+        match id.and_then(|id| self.index_of_id(id)) {
+            Some(index) => self.offset_of_index(index + 1),
+            None => 0,
+        }
+    }
+
+    /// Insert `bytes` at `offset` of the visible text
+    pub fn local_insert(&mut self, offset: usize, bytes: &[u8]) -> Option<CrdtOp> {
+        if bytes.is_empty() {
+            return None;
+        }
+        let after = self.id_before(offset);
+        // New ids are higher than any seen, so they go right after `after`
+        let mut index = match after {
+            Some(id) => self.index_of_id(id)? + 1,
+            None => 0,
+        };
+        let id = ElementId {
+            clock: self.clock + 1,
+            site: self.site,
+        };
+        for (i, &byte) in bytes.iter().enumerate() {
+            self.elements.insert(
+                index,
+                Element {
+                    id: ElementId {
+                        clock: id.clock + i as u64,
+                        site: self.site,
+                    },
+                    byte,
+                    deleted: false,
+                },
+            );
+            index += 1;
+        }
+        self.clock += bytes.len() as u64;
+        Some(CrdtOp::Insert {
+            id,
+            after,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Delete `range` of the visible text
+    pub fn local_delete(&mut self, range: Range<usize>) -> Option<CrdtOp> {
+        let mut ids = Vec::new();
+        let mut index = self.index_of_offset(range.start);
+        while ids.len() < range.len() && index < self.elements.len() {
+            let element = &mut self.elements[index];
+            if !element.deleted {
+                element.deleted = true;
+                ids.push(element.id);
+            }
+            index += 1;
+        }
+        (!ids.is_empty()).then_some(CrdtOp::Delete { ids })
+    }
+
+    /// Apply an operation from another site, returning the changes to the
+    /// visible text. Operations already applied change nothing.
+    pub fn apply(&mut self, op: &CrdtOp) -> Vec<TextEdit> {
+        let mut edits: Vec<TextEdit> = Vec::new();
+        match op {
+            CrdtOp::Insert { id, after, bytes } => {
+                // Where the scan for the next byte starts: after its parent
+                let mut start = match after {
+                    Some(after) => match self.index_of_id(*after) {
+                        Some(index) => index + 1,
+                        // Its parent never arrived; nothing to anchor to
+                        None => return edits,
+                    },
+                    None => 0,
+                };
+                for (i, &byte) in bytes.iter().enumerate() {
+                    let id = ElementId {
+                        clock: id.clock + i as u64,
+                        site: id.site,
+                    };
+                    self.clock = self.clock.max(id.clock);
+                    if let Some(index) = self.index_of_id(id) {
+                        start = index + 1;
+                        continue;
+                    }
+                    // Skip elements inserted after the same parent by later
+                    // or higher-site inserts, and everything inserted after them
+                    let mut index = start;
+                    while index < self.elements.len() && self.elements[index].id > id {
+                        index += 1;
+                    }
+                    self.elements.insert(
+                        index,
+                        Element {
+                            id,
+                            byte,
+                            deleted: false,
+                        },
+                    );
+                    start = index + 1;
+
+                    let offset = self.offset_of_index(index);
+                    match edits.last_mut() {
+                        Some(edit)
+                            if edit.removed.is_empty()
+                                && edit.removed.start + edit.inserted.len() == offset =>
+                        {
+                            edit.inserted.push(byte);
+                        }
+                        _ => edits.push(TextEdit {
+                            removed: offset..offset,
+                            inserted: vec![byte],
+                        }),
+                    }
+                }
+            }
+            CrdtOp::Delete { ids } => {
+                let mut hint = 0;
+                for id in ids {
+                    // Deleted runs are usually adjacent, so try the next element first
+                    let index = match self.elements.get(hint) {
+                        Some(element) if element.id == *id => Some(hint),
+                        _ => self.index_of_id(*id),
+                    };
+                    let Some(index) = index else { continue };
+                    hint = index + 1;
+                    if self.elements[index].deleted {
+                        continue;
+                    }
+                    self.elements[index].deleted = true;
+
+                    let offset = self.offset_of_index(index);
+                    match edits.last_mut() {
+                        Some(edit) if edit.removed.start == offset => edit.removed.end += 1,
+                        _ => edits.push(TextEdit {
+                            removed: offset..offset + 1,
+                            inserted: Vec::new(),
+                        }),
+                    }
+                }
+            }
+        }
+        edits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_edits(text: &mut Vec<u8>, edits: Vec<TextEdit>) {
+        for edit in edits {
+            text.splice(edit.removed, edit.inserted);
+        }
+    }
+
+    #[test]
+    fn test_local_edits() {
+        let mut doc = CrdtText::new(1, b"hello world");
+        doc.local_insert(5, b",").unwrap();
+        doc.local_delete(0..1).unwrap();
+        doc.local_insert(0, b"J").unwrap();
+        assert_eq!(doc.text(), b"Jello, world");
+        assert!(doc.local_delete(3..3).is_none());
+        assert!(doc.local_insert(3, b"").is_none());
+    }
+
+    #[test]
+    fn test_concurrent_edits_converge() {
+        let mut a = CrdtText::new(1, b"abc");
+        let mut b = CrdtText::new(2, b"abc");
+
+        // Both insert at the same place, and each deletes something
+        let a_ops = vec![
+            a.local_insert(1, b"XY").unwrap(),
+            a.local_delete(3..4).unwrap(),
+        ];
+        let b_ops = vec![
+            b.local_insert(1, b"12").unwrap(),
+            b.local_delete(0..1).unwrap(),
+        ];
+        let mut a_text = a.text();
+        let mut b_text = b.text();
+        for op in &b_ops {
+            apply_edits(&mut a_text, a.apply(op));
+        }
+        for op in &a_ops {
+            apply_edits(&mut b_text, b.apply(op));
+        }
+
+        assert_eq!(a.text(), b.text());
+        assert_eq!(a_text, a.text());
+        assert_eq!(b_text, b.text());
+        assert_eq!(a.text(), b"12XYc");
+    }
+
+    #[test]
+    fn test_apply_is_idempotent() {
+        let mut a = CrdtText::new(1, b"abc");
+        let mut b = CrdtText::new(2, b"abc");
+        let insert = a.local_insert(3, b"d").unwrap();
+        let delete = a.local_delete(0..1).unwrap();
+        assert_eq!(b.apply(&insert).len(), 1);
+        assert!(b.apply(&insert).is_empty());
+        assert_eq!(b.apply(&delete).len(), 1);
+        assert!(b.apply(&delete).is_empty());
+        assert_eq!(b.text(), b"bcd");
+    }
+
+    #[test]
+    fn test_runs_round_trip() {
+        let mut a = CrdtText::new(1, b"hello");
+        a.local_insert(5, b" world").unwrap();
+        a.local_delete(0..2).unwrap();
+        let runs = a.runs();
+        assert_eq!(runs.len(), 3);
+
+        let mut b = CrdtText::from_runs(2, &runs);
+        assert_eq!(b.text(), a.text());
+        let op = b.local_insert(0, b"J").unwrap();
+        a.apply(&op);
+        assert_eq!(a.text(), b"Jllo world");
+    }
+
+    #[test]
+    fn test_anchors_follow_remote_edits() {
+        let mut a = CrdtText::new(1, b"abc");
+        let mut b = CrdtText::new(2, b"abc");
+        // b's cursor is after "b"
+        let anchor = b.id_before(2);
+        assert_eq!(b.offset_after(anchor), 2);
+
+        let op = a.local_insert(0, b"xx").unwrap();
+        b.apply(&op);
+        assert_eq!(b.offset_after(anchor), 4);
+        assert_eq!(b.offset_after(None), 0);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::view::overlay::{OverlayHandle, OverlayNamespace};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ops::Range;
+use std::time::{Duration, Instant};
 
 /// Unique identifier for a cursor
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -475,6 +477,10 @@ pub struct LogEntry {
 
     /// Optional description for debugging
     pub description: Option<String>,
+
+    /// Undone and redone together with the write action before it
+    #[serde(default)]
+    pub joined: bool,
 }
 
 impl LogEntry {
@@ -486,6 +492,7 @@ impl LogEntry {
                 .unwrap()
                 .as_millis() as u64,
             description: None,
+            joined: false,
         }
     }
 
@@ -529,6 +536,17 @@ pub struct EventLog {
     /// Index at which the buffer was last saved (for tracking modified status)
     /// When current_index equals saved_at_index, the buffer is not modified
     saved_at_index: Option<usize>,
+
+    /// When the last typed text was appended and whether it was a word
+    /// character, while the next typed text may join its undo step
+    typing: Option<(Instant, bool)>,
+
+    /// Open undo groups: owner -> number of nested groups (see `begin_group`)
+    groups: HashMap<String, usize>,
+
+    /// Owner of the undo group the last grouped write action joined, and
+    /// that action's index
+    group_tail: Option<(String, usize)>,
 }
 
 impl EventLog {
@@ -541,13 +559,41 @@ impl EventLog {
             snapshot_interval: 100,
             stream_file: None,
             saved_at_index: Some(0), // New buffer starts at "saved" state (index 0)
+            typing: None,
+            groups: HashMap::new(),
+            group_tail: None,
         }
     }
 
+    /// Create a log from previously recorded events, each with whether it is
+    /// undone together with the one before it, positioned after the first
+    /// `current_index` of them, which is taken as the saved point
+    pub fn restore(events: Vec<(Event, bool)>, current_index: usize) -> Self {
+        let mut log = Self::new();
+        log.entries = events
+            .into_iter()
+            .map(|(event, joined)| LogEntry {
+                joined,
+                ..LogEntry::new(event)
+            })
+            .collect();
+        log.current_index = current_index.min(log.entries.len());
+        log.saved_at_index = Some(log.current_index);
+        log
+    }
+
     /// Mark the current position as the saved point
-    /// Call this when the buffer is saved to disk
+    /// Call this when the buffer is saved to disk. Text typed afterwards
+    /// starts a new undo step, so undo can get back to the saved content.
     pub fn mark_saved(&mut self) {
         self.saved_at_index = Some(self.current_index);
+        self.typing = None;
+    }
+
+    /// Mark an earlier position as the saved point, for a save of the
+    /// content as it was there
+    pub fn mark_saved_at(&mut self, index: usize) {
+        self.saved_at_index = Some(index);
     }
 
     /// Check if the buffer is at the saved position (not modified)
@@ -649,7 +695,116 @@ impl EventLog {
     }
 
     /// Append an event to the log
+    ///
+    /// Cursor moves and edits other than typing end the current typing undo
+    /// step.
     pub fn append(&mut self, event: Event) -> usize {
+        if event.is_write_action() || matches!(event, Event::MoveCursor { .. }) {
+            self.typing = None;
+        }
+        self.push(event, false)
+    }
+
+    /// Append text typed by the user, joining the undo step of the text
+    /// typed before it unless `timeout` passed since, a boundary was added or
+    /// this character starts a new word. A zero `timeout` never joins.
+    pub fn append_typed(
+        &mut self,
+        event: Event,
+        word_char: bool,
+        now: Instant,
+        timeout: Duration,
+    ) -> usize {
+        let joins = self.typing.is_some_and(|(typed_at, after_word_char)| {
+            !timeout.is_zero()
+                && now.saturating_duration_since(typed_at) <= timeout
+                && (after_word_char || !word_char)
+        });
+        self.typing = Some((now, word_char));
+        self.push(event, joins)
+    }
+
+    /// End the current typing undo step, e.g. on a mode switch
+    pub fn add_undo_boundary(&mut self) {
+        self.typing = None;
+    }
+
+    /// Start an undo group of `owner`, e.g. a plugin: the write actions it
+    /// appends with `append_from` until the matching `end_group` are undone
+    /// and redone as one step, as long as no other write action comes in
+    /// between. Groups nest.
+    pub fn begin_group(&mut self, owner: &str) {
+        if !self.groups.contains_key(owner) {
+            self.end_group_tail(owner);
+        }
+        *self.groups.entry(owner.to_string()).or_insert(0) += 1;
+    }
+
+    /// End the undo group `owner` started by the matching `begin_group`
+    pub fn end_group(&mut self, owner: &str) {
+        let Some(depth) = self.groups.get_mut(owner) else {
+            return;
+        };
+        *depth -= 1;
+        if *depth == 0 {
+            self.close_groups(owner);
+        }
+    }
+
+    /// End all the undo groups `owner` has open, e.g. when it stops
+    pub fn close_groups(&mut self, owner: &str) {
+        if self.groups.remove(owner).is_some() {
+            self.end_group_tail(owner);
+            self.typing = None;
+        }
+    }
+
+    /// End the undo groups of all owners
+    pub fn close_all_groups(&mut self) {
+        if !self.groups.is_empty() {
+            self.groups.clear();
+            self.group_tail = None;
+            self.typing = None;
+        }
+    }
+
+    /// Forget where the last write action of `owner`'s undo group is, so
+    /// its next one starts a new undo step
+    fn end_group_tail(&mut self, owner: &str) {
+        if self
+            .group_tail
+            .as_ref()
+            .is_some_and(|(tail_owner, _)| tail_owner == owner)
+        {
+            self.group_tail = None;
+        }
+    }
+
+    /// Append an event made by `owner`, joining the undo step of its
+    /// previous write action if both are in an undo group of `owner` and no
+    /// other write action came in between
+    pub fn append_from(&mut self, event: Event, owner: &str) -> usize {
+        let grouped = event.is_write_action() && self.groups.contains_key(owner);
+        let joins = grouped
+            && self.group_tail.as_ref().is_some_and(|(tail_owner, index)| {
+                tail_owner == owner
+                    && *index < self.current_index
+                    && self.entries[index + 1..self.current_index]
+                        .iter()
+                        .all(|entry| !entry.event.is_write_action())
+            });
+        let index = self.append(event);
+        if joins {
+            self.entries[index].joined = true;
+        }
+        if grouped {
+            self.group_tail = Some((owner.to_string(), index));
+        }
+        index
+    }
+
+    /// Push an event, joining it to the previous write action if asked to
+    fn push(&mut self, event: Event, joined: bool) -> usize {
         // If we're not at the end, truncate future events
         if self.current_index < self.entries.len() {
             self.entries.truncate(self.current_index);
@@ -674,7 +829,9 @@ impl EventLog {
             }
         }
 
-        let entry = LogEntry::new(event);
+        let is_write_action = event.is_write_action();
+        let mut entry = LogEntry::new(event);
+        entry.joined = joined && is_write_action;
         self.entries.push(entry);
         self.current_index = self.entries.len();
 
@@ -710,17 +867,20 @@ impl EventLog {
     /// Move back through events (for undo)
     /// Collects all events up to and including the first write action, returns their inverses
     /// This processes readonly events (like scrolling) and stops at write events (like Insert/Delete)
+    /// Write actions joined to the one before them are undone with it.
     pub fn undo(&mut self) -> Vec<Event> {
         let mut inverse_events = Vec::new();
         let mut found_write_action = false;
+        self.typing = None;
 
         // Keep moving backward until we find a write action
         while self.can_undo() && !found_write_action {
             self.current_index -= 1;
-            let event = &self.entries[self.current_index].event;
+            let entry = &self.entries[self.current_index];
+            let event = &entry.event;
 
             // Check if this is a write action - we'll stop after processing it
-            if event.is_write_action() {
+            if event.is_write_action() && !entry.joined {
                 found_write_action = true;
             }
 
@@ -740,13 +900,17 @@ impl EventLog {
     pub fn redo(&mut self) -> Vec<Event> {
         let mut events = Vec::new();
         let mut found_write_action = false;
+        self.typing = None;
 
         // Keep moving forward to collect write action and subsequent readonly events
         while self.can_redo() {
-            let event = self.entries[self.current_index].event.clone();
+            let entry = &self.entries[self.current_index];
+            let joined = entry.joined;
+            let event = entry.event.clone();
 
-            // If we've already found a write action and this is another write action, stop
-            if found_write_action && event.is_write_action() {
+            // If we've already found a write action and this is another write
+            // action (not joined to it), stop
+            if found_write_action && event.is_write_action() && !joined {
                 // Don't include this event, it's the next write action
                 break;
             }
@@ -774,6 +938,29 @@ impl EventLog {
         &self.entries[range]
     }
 
+    /// The buffer content after the first `index` events, given the content
+    /// at the current position
+    ///
+    /// Undone events are reverted and redoable ones replayed on a copy of
+    /// the text, so any point of the history can be shown without moving
+    /// the buffer there.
+    pub fn content_at(&self, current_content: &[u8], index: usize) -> Vec<u8> {
+        let index = index.min(self.entries.len());
+        let mut content = current_content.to_vec();
+        if index < self.current_index {
+            for entry in self.entries[index..self.current_index].iter().rev() {
+                if let Some(inverse) = entry.event.inverse() {
+                    apply_text_event(&mut content, &inverse);
+                }
+            }
+        } else {
+            for entry in &self.entries[self.current_index..index] {
+                apply_text_event(&mut content, &entry.event);
+            }
+        }
+        content
+    }
+
     /// Get the most recent event
     pub fn last_event(&self) -> Option<&Event> {
         if self.current_index > 0 {
@@ -832,6 +1019,26 @@ impl EventLog {
     }
 }
 
+/// Apply the text edits of `event` to `content`
+fn apply_text_event(content: &mut Vec<u8>, event: &Event) {
+    match event {
+        Event::Insert { position, text, .. } => {
+            let position = (*position).min(content.len());
+            content.splice(position..position, text.bytes());
+        }
+        Event::Delete { range, .. } => {
+            let end = range.end.min(content.len());
+            content.drain(range.start.min(end)..end);
+        }
+        Event::Batch { events, .. } => {
+            for event in events {
+                apply_text_event(content, event);
+            }
+        }
+        _ => {}
+    }
+}
+
 impl Default for EventLog {
     fn default() -> Self {
         Self::new()
@@ -1050,4 +1257,186 @@ mod tests {
         assert_eq!(log.entries().len(), 2);
         assert_eq!(log.current_index(), 2);
     }
+
+    #[test]
+    fn test_content_at() {
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "hello".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.append(Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: 0..1,
+                    deleted_text: "h".to_string(),
+                    cursor_id: CursorId(0),
+                },
+                Event::Insert {
+                    position: 0,
+                    text: "J".to_string(),
+                    cursor_id: CursorId(0),
+                },
+            ],
+            description: "Replace".to_string(),
+        });
+        log.append(Event::Insert {
+            position: 5,
+            text: "!".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.undo();
+
+        let current = b"Jello";
+        assert_eq!(log.content_at(current, 0), b"");
+        assert_eq!(log.content_at(current, 1), b"hello");
+        assert_eq!(log.content_at(current, 2), b"Jello");
+        // Redoable events are replayed
+        assert_eq!(log.content_at(current, 3), b"Jello!");
+        assert_eq!(log.content_at(current, 10), b"Jello!");
+    }
+
+    fn typed(position: usize, c: char) -> Event {
+        Event::Insert {
+            position,
+            text: c.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    #[test]
+    fn test_typing_groups_words() {
+        let mut log = EventLog::new();
+        let timeout = Duration::from_millis(1000);
+        let now = Instant::now();
+        for (i, c) in "ab cd".chars().enumerate() {
+            log.append_typed(typed(i, c), c.is_alphanumeric(), now, timeout);
+        }
+
+        // "cd" starts a new word; the space joins the word before it
+        assert_eq!(log.undo().len(), 2);
+        assert_eq!(log.undo().len(), 3);
+        assert!(!log.can_undo());
+        assert_eq!(log.redo().len(), 3);
+        assert_eq!(log.redo().len(), 2);
+        assert!(!log.can_redo());
+    }
+
+    #[test]
+    fn test_typing_boundaries() {
+        let mut log = EventLog::new();
+        let timeout = Duration::from_millis(1000);
+        let now = Instant::now();
+        log.append_typed(typed(0, 'a'), true, now, timeout);
+        // Too long after the previous character
+        log.append_typed(typed(1, 'b'), true, now + timeout * 2, timeout);
+        // After a cursor move
+        log.append(Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 2,
+            new_position: 0,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+        log.append_typed(typed(0, 'c'), true, now + timeout * 2, timeout);
+        // After an explicit boundary
+        log.add_undo_boundary();
+        log.append_typed(typed(1, 'd'), true, now + timeout * 2, timeout);
+        // Grouping disabled
+        log.append_typed(typed(2, 'e'), true, now + timeout * 2, Duration::ZERO);
+
+        let mut steps = 0;
+        while log.can_undo() {
+            log.undo();
+            steps += 1;
+        }
+        assert_eq!(steps, 5);
+    }
+
+    #[test]
+    fn test_save_ends_typing_step() {
+        let mut log = EventLog::new();
+        let timeout = Duration::from_millis(1000);
+        let now = Instant::now();
+        log.append_typed(typed(0, 'a'), true, now, timeout);
+        log.mark_saved();
+        log.append_typed(typed(1, 'b'), true, now, timeout);
+        assert!(!log.is_at_saved_position());
+
+        // One undo gets back to the saved content
+        assert_eq!(log.undo().len(), 1);
+        assert!(log.is_at_saved_position());
+        assert_eq!(log.undo().len(), 1);
+    }
+
+    #[test]
+    fn test_undo_group() {
+        let mut log = EventLog::new();
+        log.append(typed(0, 'a'));
+        log.begin_group("plugin");
+        log.append_from(typed(1, 'b'), "plugin");
+        log.begin_group("plugin");
+        log.append_from(typed(2, 'c'), "plugin");
+        log.end_group("plugin");
+        log.append_from(
+            Event::Delete {
+                range: 0..1,
+                deleted_text: "a".to_string(),
+                cursor_id: CursorId(0),
+            },
+            "plugin",
+        );
+        log.end_group("plugin");
+        log.append_from(typed(0, 'd'), "plugin");
+
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.undo().len(), 3);
+        assert_eq!(log.undo().len(), 1);
+        assert!(!log.can_undo());
+        log.redo();
+        assert_eq!(log.redo().len(), 3);
+        assert_eq!(log.current_index(), 4);
+    }
+
+    #[test]
+    fn test_undo_group_holds_only_its_owners_edits() {
+        let mut log = EventLog::new();
+        let timeout = Duration::from_millis(1000);
+        let now = Instant::now();
+        log.begin_group("plugin");
+        log.append_from(typed(0, 'a'), "plugin");
+        log.append_from(typed(1, 'b'), "plugin");
+        // Neither the user's typing nor another plugin's edit joins the group
+        log.append_typed(typed(2, 'x'), true, now, timeout);
+        log.append_from(typed(3, 'y'), "other");
+        log.append_from(typed(4, 'c'), "plugin");
+        log.append_from(typed(5, 'd'), "plugin");
+
+        assert_eq!(log.undo().len(), 2);
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.undo().len(), 2);
+        assert!(!log.can_undo());
+    }
+
+    #[test]
+    fn test_close_groups_ends_open_group() {
+        let mut log = EventLog::new();
+        log.begin_group("plugin");
+        log.begin_group("plugin");
+        log.append_from(typed(0, 'a'), "plugin");
+        log.close_groups("plugin");
+        log.append_from(typed(1, 'b'), "plugin");
+        // An unmatched end after the groups were closed is ignored
+        log.end_group("plugin");
+        log.append_from(typed(2, 'c'), "plugin");
+
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.undo().len(), 1);
+        assert_eq!(log.undo().len(), 1);
+        assert!(!log.can_undo());
+    }
 }
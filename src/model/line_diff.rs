@@ -82,6 +82,58 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// A line of a unified diff between two texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// Line present in both texts
+    Unchanged(&'a [u8]),
+    /// Line only in the old text
+    Removed(&'a [u8]),
+    /// Line only in the new text
+    Added(&'a [u8]),
+}
+
+/// Align two texts line by line for display, unified-diff style: unchanged
+/// lines appear once, and removed lines come before the lines added in
+/// their place.
+pub fn diff_line_sequence<'a>(old: &'a [u8], new: &'a [u8]) -> Vec<DiffLine<'a>> {
+    fn split_lines(text: &[u8]) -> Vec<&[u8]> {
+        if text.is_empty() {
+            return vec![];
+        }
+        let text = text.strip_suffix(b"\n").unwrap_or(text);
+        text.split(|&b| b == b'\n').collect()
+    }
+
+    let old_lines = split_lines(old);
+    let new_lines = split_lines(new);
+    let lcs = longest_common_subsequence(&old_lines, &new_lines);
+
+    let mut sequence = Vec::with_capacity(old_lines.len().max(new_lines.len()));
+    let (mut old_idx, mut new_idx) = (0, 0);
+    for m in lcs.iter().chain(std::iter::once(&LineMatch {
+        saved_idx: old_lines.len(),
+        current_idx: new_lines.len(),
+    })) {
+        sequence.extend(
+            old_lines[old_idx..m.saved_idx]
+                .iter()
+                .map(|l| DiffLine::Removed(l)),
+        );
+        sequence.extend(
+            new_lines[new_idx..m.current_idx]
+                .iter()
+                .map(|l| DiffLine::Added(l)),
+        );
+        if m.saved_idx < old_lines.len() {
+            sequence.push(DiffLine::Unchanged(old_lines[m.saved_idx]));
+        }
+        old_idx = m.saved_idx + 1;
+        new_idx = m.current_idx + 1;
+    }
+    sequence
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -486,6 +538,33 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    #[test]
+    fn test_diff_line_sequence() {
+        let old = b"a\nb\nc\nd\n";
+        let new = b"a\nB\nc\nd\ne\n";
+        assert_eq!(
+            diff_line_sequence(old, new),
+            vec![
+                DiffLine::Unchanged(b"a"),
+                DiffLine::Removed(b"b"),
+                DiffLine::Added(b"B"),
+                DiffLine::Unchanged(b"c"),
+                DiffLine::Unchanged(b"d"),
+                DiffLine::Added(b"e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_line_sequence_empty_side() {
+        assert_eq!(diff_line_sequence(b"", b"x\n"), vec![DiffLine::Added(b"x")]);
+        assert_eq!(
+            diff_line_sequence(b"x\ny", b""),
+            vec![DiffLine::Removed(b"x"), DiffLine::Removed(b"y")]
+        );
+        assert!(diff_line_sequence(b"", b"").is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,7 @@
 
 pub mod buffer;
 pub mod control_event;
+pub mod crdt;
 pub mod cursor;
 pub mod document_model;
 pub mod edit;
@@ -1,4 +1,5 @@
 use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -310,6 +311,26 @@ pub struct TreeStats {
     pub line_feed_count: Option<usize>,
 }
 
+/// Merge adjacent leaves that refer to consecutive bytes of the same buffer,
+/// as typing one character at a time leaves behind, and drop empty leaves
+pub fn merge_adjacent_leaves(leaves: Vec<LeafData>) -> Vec<LeafData> {
+    let mut merged: Vec<LeafData> = Vec::with_capacity(leaves.len());
+    for leaf in leaves.into_iter().filter(|leaf| leaf.bytes > 0) {
+        if let Some(last) = merged.last_mut() {
+            if last.location == leaf.location && last.offset + last.bytes == leaf.offset {
+                last.bytes += leaf.bytes;
+                last.line_feed_cnt = match (last.line_feed_cnt, leaf.line_feed_cnt) {
+                    (Some(a), Some(b)) => Some(a + b),
+                    _ => None,
+                };
+                continue;
+            }
+        }
+        merged.push(leaf);
+    }
+    merged
+}
+
 // Line iteration can be implemented by:
 // 1. Maintaining a cursor position (current piece + offset within piece)
 // 2. For next_line(): scan forward in the current piece's buffer until '\n',
@@ -786,6 +807,114 @@ impl PieceTree {
         self.cursor_at_offset(offset + bytes)
     }
 
+    /// Lengthen the piece that ends at document `offset` by `bytes`, if it
+    /// refers to `location` and ends at `buffer_offset` in it, so text typed
+    /// right after the text last typed needs no new piece.
+    /// Returns false (leaving the tree unchanged) if no piece fits.
+    pub fn extend_piece(
+        &mut self,
+        offset: usize,
+        location: BufferLocation,
+        buffer_offset: usize,
+        bytes: usize,
+        line_feed_cnt: Option<usize>,
+    ) -> bool {
+        if offset == 0 || bytes == 0 {
+            return false;
+        }
+        match Self::extend_leaf_ending_at(
+            &self.root,
+            offset,
+            location,
+            buffer_offset,
+            bytes,
+            line_feed_cnt,
+        ) {
+            Some(root) => {
+                self.root = root;
+                self.total_bytes += bytes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Copy of `node` with the leaf ending at `offset` (relative to the node)
+    /// lengthened, copying only the nodes on the path to it so snapshots of
+    /// the tree are unaffected
+    fn extend_leaf_ending_at(
+        node: &Arc<PieceTreeNode>,
+        offset: usize,
+        location: BufferLocation,
+        buffer_offset: usize,
+        bytes: usize,
+        line_feed_cnt: Option<usize>,
+    ) -> Option<Arc<PieceTreeNode>> {
+        let add_line_feeds = |count: Option<usize>| match (count, line_feed_cnt) {
+            (Some(a), Some(b)) => Some(a + b),
+            _ => None,
+        };
+        match node.as_ref() {
+            PieceTreeNode::Leaf {
+                location: leaf_location,
+                offset: leaf_offset,
+                bytes: leaf_bytes,
+                line_feed_cnt: leaf_line_feeds,
+            } => {
+                if *leaf_bytes != offset
+                    || *leaf_location != location
+                    || leaf_offset + leaf_bytes != buffer_offset
+                {
+                    return None;
+                }
+                Some(Arc::new(PieceTreeNode::Leaf {
+                    location,
+                    offset: *leaf_offset,
+                    bytes: leaf_bytes + bytes,
+                    line_feed_cnt: add_line_feeds(*leaf_line_feeds),
+                }))
+            }
+            PieceTreeNode::Internal {
+                left_bytes,
+                lf_left,
+                left,
+                right,
+            } => {
+                if offset <= *left_bytes {
+                    let left = Self::extend_leaf_ending_at(
+                        left,
+                        offset,
+                        location,
+                        buffer_offset,
+                        bytes,
+                        line_feed_cnt,
+                    )?;
+                    Some(Arc::new(PieceTreeNode::Internal {
+                        left_bytes: left_bytes + bytes,
+                        lf_left: add_line_feeds(*lf_left),
+                        left,
+                        right: Arc::clone(right),
+                    }))
+                } else {
+                    let right = Self::extend_leaf_ending_at(
+                        right,
+                        offset - left_bytes,
+                        location,
+                        buffer_offset,
+                        bytes,
+                        line_feed_cnt,
+                    )?;
+                    Some(Arc::new(PieceTreeNode::Internal {
+                        left_bytes: *left_bytes,
+                        lf_left: *lf_left,
+                        left: Arc::clone(left),
+                        right,
+                    }))
+                }
+            }
+        }
+    }
+
     /// Get a clone of the root node (shared via Arc)
     pub fn root(&self) -> Arc<PieceTreeNode> {
         Arc::clone(&self.root)
@@ -939,9 +1068,10 @@ impl PieceTree {
                             let mut lines_seen = 0;
                             let mut found_line_start = *offset;
 
+                            // A line can start right after the piece, when it ends with a newline
                             if let Some(line_starts) = buffer.get_line_starts() {
                                 for &ls in line_starts.iter() {
-                                    if ls > *offset && ls < *offset + *bytes {
+                                    if ls > *offset && ls <= *offset + *bytes {
                                         if lines_seen == line_in_piece - 1 {
                                             found_line_start = ls;
                                             break;
@@ -1232,7 +1362,8 @@ impl PieceTree {
     }
 
     /// Delete text in a range specified by positions (start_line, start_col) to (end_line, end_col)
-    /// This performs a more efficient traversal than converting positions to offsets separately
+    /// This performs a more efficient traversal than converting positions to offsets separately.
+    /// Returns the byte range deleted, if any.
     pub fn delete_position_range(
         &mut self,
         start_line: usize,
@@ -1240,10 +1371,10 @@ impl PieceTree {
         end_line: usize,
         end_column: usize,
         buffers: &[StringBuffer],
-    ) {
+    ) -> Option<Range<usize>> {
         // Edge case: empty range
         if start_line == end_line && start_column == end_column {
-            return;
+            return None;
         }
 
         // Find both positions in a single traversal and collect leaves
@@ -1272,8 +1403,10 @@ impl PieceTree {
                 self.root = Self::build_balanced(&leaves);
                 self.total_bytes = self.total_bytes.saturating_sub(deleted_bytes);
                 self.check_and_rebalance();
+                return Some(start..end);
             }
         }
+        None
     }
 
     /// Helper to collect leaves while deleting a range specified by positions
@@ -1597,6 +1730,13 @@ impl PieceTree {
         leaves
     }
 
+    /// Replace the pieces of the tree with `leaves`, which must hold the same
+    /// content, as a balanced tree
+    pub fn set_leaves(&mut self, leaves: &[LeafData]) {
+        self.root = Self::build_balanced(leaves);
+        self.total_bytes = self.root.total_bytes();
+    }
+
     /// Convert byte offset to line/column position using tree's line metadata
     pub fn offset_to_position(
         &self,
@@ -1973,6 +2113,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extend_piece() {
+        let buffers = test_buffers();
+        let mut tree = PieceTree::new(BufferLocation::Stored(0), 0, 10, Some(0));
+        tree.insert(5, BufferLocation::Added(1), 0, 2, Some(0), &buffers);
+        let snapshot = tree.root();
+        assert_eq!(tree.stats().leaf_count, 3);
+
+        // Only the piece ending at the offset, at the given buffer offset, grows
+        assert!(!tree.extend_piece(5, BufferLocation::Added(1), 2, 1, Some(0)));
+        assert!(!tree.extend_piece(7, BufferLocation::Added(1), 3, 1, Some(0)));
+        assert!(tree.extend_piece(7, BufferLocation::Added(1), 2, 3, Some(1)));
+
+        assert_eq!(tree.stats().leaf_count, 3);
+        assert_eq!(tree.total_bytes(), 15);
+        assert_eq!(tree.line_count(), Some(2));
+        let info = tree.find_by_offset(9).unwrap();
+        assert_eq!(info.location, BufferLocation::Added(1));
+        assert_eq!((info.offset, info.bytes), (0, 5));
+
+        // Earlier snapshots keep their content
+        assert_eq!(snapshot.total_bytes(), 12);
+    }
+
+    #[test]
+    fn test_merge_adjacent_leaves() {
+        let buffers = test_buffers();
+        let mut tree = PieceTree::new(BufferLocation::Stored(0), 0, 10, Some(0));
+
+        // Typing one byte at a time appends consecutive bytes of one buffer
+        for i in 0..5 {
+            tree.insert(10 + i, BufferLocation::Added(1), i, 1, Some(0), &buffers);
+        }
+        assert_eq!(tree.stats().leaf_count, 6);
+
+        let leaves = merge_adjacent_leaves(tree.get_leaves());
+        assert_eq!(leaves.len(), 2);
+        assert_eq!(leaves[1].location, BufferLocation::Added(1));
+        assert_eq!((leaves[1].offset, leaves[1].bytes), (0, 5));
+
+        tree.set_leaves(&leaves);
+        assert_eq!(tree.stats().leaf_count, 2);
+        assert_eq!(tree.total_bytes(), 15);
+    }
+
     #[test]
     fn test_find_by_offset() {
         let tree = PieceTree::new(BufferLocation::Stored(0), 0, 100, Some(0));
@@ -72,6 +72,42 @@ pub fn byte_offset_at_visual_column(s: &str, visual_col: usize) -> usize {
     s.len()
 }
 
+/// Width of `ch` drawn at visual column `col`: tabs extend to the next
+/// multiple of `tab_size`, as the editor draws them.
+#[inline]
+fn char_width_at(ch: char, col: usize, tab_size: usize) -> usize {
+    if ch == '\t' {
+        let tab_size = tab_size.max(1);
+        tab_size - col % tab_size
+    } else {
+        char_width(ch)
+    }
+}
+
+/// Like [`visual_column_at_byte`], with tabs expanded to `tab_size` columns.
+pub fn visual_column_at_byte_with_tabs(s: &str, byte_offset: usize, tab_size: usize) -> usize {
+    s[..byte_offset.min(s.len())]
+        .chars()
+        .fold(0, |col, ch| col + char_width_at(ch, col, tab_size))
+}
+
+/// Like [`byte_offset_at_visual_column`], with tabs expanded to `tab_size`
+/// columns.
+pub fn byte_offset_at_visual_column_with_tabs(
+    s: &str,
+    visual_col: usize,
+    tab_size: usize,
+) -> usize {
+    let mut current_col = 0;
+    for (byte_idx, ch) in s.char_indices() {
+        if current_col >= visual_col {
+            return byte_idx;
+        }
+        current_col += char_width_at(ch, current_col, tab_size);
+    }
+    s.len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +176,17 @@ mod tests {
         let string = String::from("Hello🚀");
         assert_eq!(string.display_width(), 7);
     }
+
+    #[test]
+    fn test_columns_with_tabs() {
+        // The tab ends at the next tab stop
+        assert_eq!(visual_column_at_byte_with_tabs("\tx", 1, 4), 4);
+        assert_eq!(visual_column_at_byte_with_tabs("ab\tx", 3, 4), 4);
+        assert_eq!(visual_column_at_byte_with_tabs("你\tx", 4, 4), 4);
+        assert_eq!(byte_offset_at_visual_column_with_tabs("ab\tx", 4, 4), 3);
+        // A column inside the tab is after it, as inside a wide character
+        assert_eq!(byte_offset_at_visual_column_with_tabs("ab\tx", 3, 4), 3);
+        assert_eq!(byte_offset_at_visual_column_with_tabs("你x", 1, 4), 3);
+        assert_eq!(byte_offset_at_visual_column_with_tabs("\tx", 9, 4), 2);
+    }
 }
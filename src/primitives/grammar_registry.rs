@@ -261,12 +261,68 @@ impl GrammarRegistry {
         self.syntax_set.find_syntax_by_first_line(first_line)
     }
 
+    /// Find syntax from buffer content when the file name gives no hint.
+    ///
+    /// `head` is the first few lines of the file and `tail` the last few.
+    /// Checks in order:
+    /// 1. Vim modelines (`vim: ft=python`) in the head or tail
+    /// 2. Emacs modelines (`-*- mode: ruby -*-`) in the first two lines
+    /// 3. First-line patterns from the grammars (shebangs, `<?xml`, etc.)
+    /// 4. The shebang interpreter name (`#!/usr/bin/env node`)
+    pub fn find_syntax_by_content(&self, head: &[&str], tail: &[&str]) -> Option<&SyntaxReference> {
+        let modeline = head
+            .iter()
+            .chain(tail.iter())
+            .find_map(|line| parse_vim_modeline(line))
+            .or_else(|| {
+                head.iter()
+                    .take(2)
+                    .find_map(|line| parse_emacs_modeline(line))
+            });
+        if let Some(syntax) = modeline.and_then(|token| self.find_syntax_by_modeline_token(&token))
+        {
+            return Some(syntax);
+        }
+
+        let first_line = head.first()?;
+        if let Some(syntax) = self.find_syntax_by_first_line(first_line) {
+            return Some(syntax);
+        }
+
+        shebang_interpreter(first_line).and_then(|name| self.find_syntax_by_modeline_token(name))
+    }
+
+    /// Resolve a language token from a modeline or shebang, including
+    /// common aliases that don't match a syntax name or extension
+    fn find_syntax_by_modeline_token(&self, token: &str) -> Option<&SyntaxReference> {
+        let token = match token {
+            "sh" | "zsh" | "ksh" | "dash" | "shell-script" => "bash",
+            "node" | "nodejs" | "deno" => "js",
+            "pypy" => "python",
+            other => other,
+        };
+        self.find_syntax_by_token(token)
+    }
+
     /// Find syntax by scope name
     pub fn find_syntax_by_scope(&self, scope: &str) -> Option<&SyntaxReference> {
         let scope = syntect::parsing::Scope::new(scope).ok()?;
         self.syntax_set.find_syntax_by_scope(scope)
     }
 
+    /// Find syntax by a language token, such as a markdown code fence tag
+    /// ("rust", "py", "JavaScript"); matches names and file extensions
+    pub fn find_syntax_by_token(&self, token: &str) -> Option<&SyntaxReference> {
+        if let Some(syntax) = self
+            .user_extensions
+            .get(token)
+            .and_then(|scope| self.find_syntax_by_scope(scope))
+        {
+            return Some(syntax);
+        }
+        self.syntax_set.find_syntax_by_token(token)
+    }
+
     /// Find syntax by name
     pub fn find_syntax_by_name(&self, name: &str) -> Option<&SyntaxReference> {
         self.syntax_set.find_syntax_by_name(name)
@@ -313,20 +369,84 @@ impl Default for GrammarRegistry {
     }
 }
 
+/// Extract the file type from a vim modeline, e.g.
+/// `# vim: set ft=python ts=4:` or `// vi: filetype=rust`
+fn parse_vim_modeline(line: &str) -> Option<String> {
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .filter_map(|marker| {
+            line.find(marker).and_then(|pos| {
+                // The marker must start the line or follow whitespace
+                let preceded_by_space = line[..pos]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| c.is_whitespace());
+                preceded_by_space.then_some(pos + marker.len())
+            })
+        })
+        .min()?;
+
+    line[start..]
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .find_map(|option| {
+            let (key, value) = option.split_once('=')?;
+            matches!(key, "ft" | "filetype" | "syntax" | "syn").then_some(value)
+        })
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_lowercase())
+}
+
+/// Extract the mode from an emacs modeline, e.g.
+/// `# -*- mode: ruby; coding: utf-8 -*-` or `/* -*- c++ -*- */`
+fn parse_emacs_modeline(line: &str) -> Option<String> {
+    let start = line.find("-*-")? + 3;
+    let end = line[start..].find("-*-")? + start;
+    let vars = line[start..end].trim();
+
+    let mode = if vars.contains(':') {
+        vars.split(';').find_map(|var| {
+            let (key, value) = var.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case("mode")
+                .then(|| value.trim())
+        })?
+    } else {
+        vars
+    };
+
+    let mode = mode.strip_suffix("-mode").unwrap_or(mode).to_lowercase();
+    (!mode.is_empty()).then_some(mode)
+}
+
+/// Extract the interpreter name from a shebang line, skipping `env` and
+/// its flags: `#!/usr/bin/env -S node --flag` yields `node`
+fn shebang_interpreter(line: &str) -> Option<&str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    // "python3.11" -> "python"
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    (!program.is_empty()).then_some(program)
+}
+
 // VSCode package.json structures
 
 #[derive(Debug, Deserialize)]
-struct PackageManifest {
+pub(crate) struct PackageManifest {
     #[serde(default)]
-    contributes: Option<Contributes>,
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) contributes: Option<Contributes>,
 }
 
 #[derive(Debug, Deserialize, Default)]
-struct Contributes {
+pub(crate) struct Contributes {
     #[serde(default)]
     languages: Vec<LanguageContribution>,
     #[serde(default)]
-    grammars: Vec<GrammarContribution>,
+    pub(crate) grammars: Vec<GrammarContribution>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -337,11 +457,13 @@ struct LanguageContribution {
 }
 
 #[derive(Debug, Deserialize)]
-struct GrammarContribution {
+pub(crate) struct GrammarContribution {
+    /// Injection grammars don't belong to a language
+    #[serde(default)]
     language: String,
     #[serde(rename = "scopeName")]
     scope_name: String,
-    path: String,
+    pub(crate) path: String,
 }
 
 #[cfg(test)]
@@ -384,6 +506,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_syntax_by_token() {
+        let registry = GrammarRegistry::load();
+        assert!(registry.find_syntax_by_token("rust").is_some());
+        assert!(registry.find_syntax_by_token("py").is_some());
+        assert!(registry.find_syntax_by_token("toml").is_some());
+        assert!(registry.find_syntax_by_token("not-a-language").is_none());
+    }
+
+    #[test]
+    fn test_parse_vim_modeline() {
+        assert_eq!(
+            parse_vim_modeline("# vim: set ft=python ts=4:"),
+            Some("python".to_string())
+        );
+        assert_eq!(
+            parse_vim_modeline("// vi: filetype=Rust"),
+            Some("rust".to_string())
+        );
+        assert_eq!(
+            parse_vim_modeline("/* vim:syntax=c */"),
+            Some("c".to_string())
+        );
+        assert_eq!(parse_vim_modeline("# vim: ts=4 sw=4"), None);
+        assert_eq!(parse_vim_modeline("let x = navim: ft=python"), None);
+    }
+
+    #[test]
+    fn test_parse_emacs_modeline() {
+        assert_eq!(
+            parse_emacs_modeline("# -*- mode: ruby; coding: utf-8 -*-"),
+            Some("ruby".to_string())
+        );
+        assert_eq!(
+            parse_emacs_modeline("/* -*- C++ -*- */"),
+            Some("c++".to_string())
+        );
+        assert_eq!(
+            parse_emacs_modeline(";; -*- mode: lisp-mode -*-"),
+            Some("lisp".to_string())
+        );
+        assert_eq!(parse_emacs_modeline("# -*- coding: utf-8 -*-"), None);
+        assert_eq!(parse_emacs_modeline("no modeline here"), None);
+    }
+
+    #[test]
+    fn test_shebang_interpreter() {
+        assert_eq!(shebang_interpreter("#!/bin/sh"), Some("sh"));
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env python3"),
+            Some("python")
+        );
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env -S node --no-warnings"),
+            Some("node")
+        );
+        assert_eq!(shebang_interpreter("#!/usr/bin/env"), None);
+        assert_eq!(shebang_interpreter("print('hi')"), None);
+    }
+
+    #[test]
+    fn test_find_syntax_by_content() {
+        let registry = GrammarRegistry::load();
+        let name = |head: &[&str], tail: &[&str]| {
+            registry
+                .find_syntax_by_content(head, tail)
+                .map(|s| s.name.clone())
+        };
+
+        assert_eq!(
+            name(&["#!/usr/bin/env python3", "print('hi')"], &[]),
+            Some("Python".to_string())
+        );
+        assert_eq!(
+            name(&["#!/usr/bin/env node", "console.log(1)"], &[]),
+            Some("JavaScript".to_string())
+        );
+        assert_eq!(
+            name(&["#!/bin/sh"], &[]),
+            Some("Bourne Again Shell (bash)".to_string())
+        );
+        // A modeline wins over the shebang
+        assert_eq!(
+            name(&["#!/bin/sh", "# vim: ft=ruby"], &[]),
+            Some("Ruby".to_string())
+        );
+        assert_eq!(
+            name(&["some text"], &["", "# vim: set filetype=python:"]),
+            Some("Python".to_string())
+        );
+        assert_eq!(
+            name(&["# -*- mode: ruby -*-"], &[]),
+            Some("Ruby".to_string())
+        );
+        assert_eq!(name(&["just some prose"], &[]), None);
+        assert_eq!(name(&[], &[]), None);
+    }
+
     #[test]
     fn test_syntax_set_arc() {
         let registry = GrammarRegistry::load();
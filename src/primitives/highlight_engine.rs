@@ -19,11 +19,12 @@
 use crate::model::buffer::Buffer;
 use crate::primitives::grammar_registry::GrammarRegistry;
 use crate::primitives::highlighter::{HighlightCategory, HighlightSpan, Highlighter, Language};
+use crate::primitives::injection::{find_injections, InjectionHost};
 use crate::view::theme::Theme;
 use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
-use syntect::parsing::SyntaxSet;
+use syntect::parsing::{SyntaxReference, SyntaxSet};
 
 /// Map TextMate scope to highlight category
 fn scope_to_category(scope: &str) -> Option<HighlightCategory> {
@@ -167,6 +168,53 @@ fn scope_to_category(scope: &str) -> Option<HighlightCategory> {
     None
 }
 
+/// Highlight a standalone code snippet, such as a fenced code block in hover docs.
+///
+/// Returns one entry per line, each a list of `(text, category)` segments that
+/// together cover the whole line. Text without a recognised scope has no category.
+pub fn highlight_snippet(
+    code: &str,
+    syntax: &SyntaxReference,
+    syntax_set: &SyntaxSet,
+) -> Vec<Vec<(String, Option<HighlightCategory>)>> {
+    use syntect::parsing::{ParseState, ScopeStack};
+
+    let mut state = ParseState::new(syntax);
+    let mut scopes = ScopeStack::new();
+    let mut lines = Vec::new();
+
+    for line in code.lines() {
+        let mut segments = Vec::new();
+        let ops = state
+            .parse_line(&format!("{}\n", line), syntax_set)
+            .unwrap_or_default();
+
+        let mut offset = 0;
+        for (op_offset, op) in ops {
+            // Offsets past the line content point at the added newline
+            let op_offset = op_offset.min(line.len());
+            if op_offset > offset {
+                segments.push((
+                    line[offset..op_offset].to_string(),
+                    TextMateEngine::scope_stack_to_category(&scopes),
+                ));
+            }
+            offset = op_offset;
+            let _ = scopes.apply(&op);
+        }
+        if offset < line.len() {
+            segments.push((
+                line[offset..].to_string(),
+                TextMateEngine::scope_stack_to_category(&scopes),
+            ));
+        }
+
+        lines.push(segments);
+    }
+
+    lines
+}
+
 /// Preference for which highlighting backend to use
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum HighlighterPreference {
@@ -263,8 +311,6 @@ impl TextMateEngine {
         theme: &Theme,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        use syntect::parsing::{ParseState, ScopeStack};
-
         // Check cache validity
         if let Some(cache) = &self.cache {
             if cache.range.start <= viewport_start
@@ -294,8 +340,6 @@ impl TextMateEngine {
         }
 
         let syntax = &self.syntax_set.syntaxes()[self.syntax_index];
-        let mut state = ParseState::new(syntax);
-        let mut spans = Vec::new();
 
         // Get content
         let content = buffer.slice_bytes(parse_start..parse_end);
@@ -304,11 +348,67 @@ impl TextMateEngine {
             Err(_) => return Vec::new(),
         };
 
+        let mut spans = Self::parse_spans(&self.syntax_set, syntax, content_str, parse_start);
+
+        // Re-highlight embedded code (fenced blocks, <script>, annotated
+        // strings) with its own grammar
+        let host = InjectionHost::from_scope(&syntax.scope.build_string());
+        for region in find_injections(host, content_str) {
+            let Some(injected) = self.syntax_set.find_syntax_by_token(&region.language) else {
+                continue;
+            };
+            if injected.name == syntax.name {
+                continue;
+            }
+            let region_spans = Self::parse_spans(
+                &self.syntax_set,
+                injected,
+                &content_str[region.range.clone()],
+                parse_start + region.range.start,
+            );
+            let range = parse_start + region.range.start..parse_start + region.range.end;
+            Self::replace_region(&mut spans, range, region_spans);
+        }
+
+        // Merge adjacent spans
+        Self::merge_adjacent_spans(&mut spans);
+
+        // Update cache
+        self.cache = Some(TextMateCache {
+            range: parse_start..parse_end,
+            spans: spans.clone(),
+        });
+        self.last_buffer_len = buffer.len();
+
+        // Filter and resolve colors
+        spans
+            .into_iter()
+            .filter(|span| span.range.start < viewport_end && span.range.end > viewport_start)
+            .map(|span| HighlightSpan {
+                range: span.range,
+                color: span.category.color(theme),
+            })
+            .collect()
+    }
+
+    /// Parse `text` with `syntax` into category spans, offsetting byte
+    /// ranges by `base_offset`
+    fn parse_spans(
+        syntax_set: &SyntaxSet,
+        syntax: &SyntaxReference,
+        text: &str,
+        base_offset: usize,
+    ) -> Vec<CachedSpan> {
+        use syntect::parsing::{ParseState, ScopeStack};
+
+        let mut state = ParseState::new(syntax);
+        let mut spans = Vec::new();
+
         // Parse line by line - manually track line boundaries to handle CRLF correctly
         // str::lines() strips both \n and \r\n, losing the distinction
-        let content_bytes = content_str.as_bytes();
+        let content_bytes = text.as_bytes();
         let mut pos = 0;
-        let mut current_offset = parse_start;
+        let mut current_offset = base_offset;
         let mut current_scopes = ScopeStack::new();
 
         while pos < content_bytes.len() {
@@ -353,7 +453,7 @@ impl TextMateEngine {
                 line_content.to_string()
             };
 
-            let ops = match state.parse_line(&line_for_syntect, &self.syntax_set) {
+            let ops = match state.parse_line(&line_for_syntect, syntax_set) {
                 Ok(ops) => ops,
                 Err(_) => {
                     pos = line_end;
@@ -407,25 +507,34 @@ impl TextMateEngine {
             current_offset += actual_line_byte_len;
         }
 
-        // Merge adjacent spans
-        Self::merge_adjacent_spans(&mut spans);
-
-        // Update cache
-        self.cache = Some(TextMateCache {
-            range: parse_start..parse_end,
-            spans: spans.clone(),
-        });
-        self.last_buffer_len = buffer.len();
-
-        // Filter and resolve colors
         spans
-            .into_iter()
-            .filter(|span| span.range.start < viewport_end && span.range.end > viewport_start)
-            .map(|span| HighlightSpan {
-                range: span.range,
-                color: span.category.color(theme),
-            })
-            .collect()
+    }
+
+    /// Replace the spans inside `range` with `injected`, trimming host
+    /// spans that cross its edges
+    fn replace_region(spans: &mut Vec<CachedSpan>, range: Range<usize>, injected: Vec<CachedSpan>) {
+        let mut result = Vec::with_capacity(spans.len() + injected.len());
+        for span in spans.drain(..) {
+            if span.range.end <= range.start || span.range.start >= range.end {
+                result.push(span);
+                continue;
+            }
+            if span.range.start < range.start {
+                result.push(CachedSpan {
+                    range: span.range.start..range.start,
+                    category: span.category,
+                });
+            }
+            if span.range.end > range.end {
+                result.push(CachedSpan {
+                    range: range.end..span.range.end,
+                    category: span.category,
+                });
+            }
+        }
+        result.extend(injected);
+        result.sort_by_key(|span| span.range.start);
+        *spans = result;
     }
 
     /// Map scope stack to highlight category
@@ -513,6 +622,25 @@ impl HighlightEngine {
         }
     }
 
+    /// Create a TextMate engine for a syntax chosen by name (e.g. "Python").
+    /// Returns `None` if the registry has no syntax with that name.
+    pub fn for_syntax_name(name: &str, registry: &GrammarRegistry) -> Option<Self> {
+        let syntax_set = registry.syntax_set_arc();
+        let index = syntax_set.syntaxes().iter().position(|s| s.name == name)?;
+
+        // Detect tree-sitter language from the syntax's extensions for non-highlighting features
+        let ts_language = syntax_set.syntaxes()[index]
+            .file_extensions
+            .iter()
+            .find_map(|ext| Language::from_path(Path::new(&format!("file.{}", ext))));
+
+        Some(Self::TextMate(TextMateEngine::with_language(
+            syntax_set,
+            index,
+            ts_language,
+        )))
+    }
+
     /// Create a TextMate engine for a file, falling back to tree-sitter if no TextMate grammar
     fn textmate_for_file(path: &Path, registry: &GrammarRegistry) -> Self {
         let syntax_set = registry.syntax_set_arc();
@@ -677,6 +805,19 @@ mod tests {
         assert!(engine.language().is_some());
     }
 
+    #[test]
+    fn test_engine_for_syntax_name() {
+        let registry = GrammarRegistry::load();
+
+        let engine = HighlightEngine::for_syntax_name("Python", &registry).unwrap();
+        assert_eq!(engine.backend_name(), "textmate");
+        assert_eq!(engine.syntax_name(), Some("Python"));
+        // Tree-sitter language is derived from the syntax's extensions
+        assert!(matches!(engine.language(), Some(Language::Python)));
+
+        assert!(HighlightEngine::for_syntax_name("No Such Language", &registry).is_none());
+    }
+
     #[test]
     fn test_tree_sitter_explicit_preference() {
         let registry = GrammarRegistry::load();
@@ -797,4 +938,92 @@ mod tests {
             panic!("Expected TextMate engine for .java file");
         }
     }
+
+    /// Category of the span covering `needle` in `text`, if any
+    fn category_at(
+        registry: &GrammarRegistry,
+        file: &str,
+        text: &str,
+        needle: &str,
+    ) -> Option<HighlightCategory> {
+        let mut engine = HighlightEngine::for_file(Path::new(file), registry);
+        let HighlightEngine::TextMate(ref mut tm) = engine else {
+            panic!("Expected TextMate engine for {}", file);
+        };
+        let buffer = Buffer::from_str(text, 0);
+        let theme = Theme::default();
+        tm.highlight_viewport(&buffer, 0, text.len(), &theme, 0);
+
+        let start = text.find(needle).unwrap();
+        let end = start + needle.len();
+        tm.cache
+            .as_ref()
+            .unwrap()
+            .spans
+            .iter()
+            .find(|span| span.range.start <= start && span.range.end >= end)
+            .map(|span| span.category)
+    }
+
+    #[test]
+    fn test_injection_markdown_fenced_block() {
+        let registry = GrammarRegistry::load();
+        // TOML comes from an embedded grammar the markdown grammar doesn't
+        // know about, so without injection the block would be plain raw text
+        let text = "# Notes\n\n```toml\nenabled = true\n```\n";
+        assert_eq!(
+            category_at(&registry, "notes.md", text, "true"),
+            Some(HighlightCategory::Number)
+        );
+        // Text after the block is markdown again
+        assert_eq!(
+            category_at(&registry, "notes.md", text, "Notes"),
+            Some(HighlightCategory::Keyword)
+        );
+    }
+
+    #[test]
+    fn test_injection_annotated_sql_string() {
+        let registry = GrammarRegistry::load();
+        let text = "fn q() -> &'static str {\n    /* sql */ \"SELECT id FROM users\"\n}\n";
+        assert_eq!(
+            category_at(&registry, "query.rs", text, "SELECT"),
+            Some(HighlightCategory::Keyword)
+        );
+
+        // Without the annotation the whole literal is a string
+        let text = "fn q() -> &'static str {\n    \"SELECT id FROM users\"\n}\n";
+        assert_eq!(
+            category_at(&registry, "query.rs", text, "SELECT"),
+            Some(HighlightCategory::String)
+        );
+    }
+
+    #[test]
+    fn test_injection_html_script() {
+        let registry = GrammarRegistry::load();
+        let text = "<html>\n<script>\nfunction greet() { return 1; }\n</script>\n</html>\n";
+        assert_eq!(
+            category_at(&registry, "index.html", text, "function"),
+            Some(HighlightCategory::Type)
+        );
+    }
+
+    #[test]
+    fn test_highlight_snippet() {
+        let registry = GrammarRegistry::load();
+        let syntax = registry.find_syntax_by_token("rust").unwrap();
+        let lines = highlight_snippet("fn main() {\n    // hi\n}", syntax, registry.syntax_set());
+
+        assert_eq!(lines.len(), 3);
+        // Segments cover each line exactly
+        let line0: String = lines[0].iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(line0, "fn main() {");
+        assert!(lines[0]
+            .iter()
+            .any(|(text, cat)| text == "fn" && cat.is_some()));
+        assert!(lines[1]
+            .iter()
+            .any(|(text, cat)| text.contains("hi") && *cat == Some(HighlightCategory::Comment)));
+    }
 }
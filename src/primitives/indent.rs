@@ -965,11 +965,63 @@ impl Default for IndentCalculator {
     }
 }
 
+/// Re-indent multi-line pasted text to fit the line it's pasted into
+///
+/// The text's own indentation is measured on its lines after the first (the
+/// first line usually starts mid-line, or at the cursor). That common
+/// indentation is replaced by `indent` on those lines and stripped from the
+/// start of the first line, so a copied block keeps its shape but lines up
+/// with the cursor's line. Blank lines are left empty.
+pub fn reindent_pasted_text(text: &str, indent: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let leading = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let Some(base) = lines[1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| leading(line))
+        .min()
+    else {
+        return text.to_string();
+    };
+
+    let mut result = String::with_capacity(text.len());
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        let strip = leading(line).min(base);
+        if i == 0 {
+            result.push_str(&line[strip..]);
+        } else if !line.trim().is_empty() {
+            result.push_str(indent);
+            result.push_str(&line[strip..]);
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::model::buffer::Buffer;
 
+    #[test]
+    fn test_reindent_pasted_text() {
+        // A whole block copied from deeper indentation
+        assert_eq!(
+            reindent_pasted_text("        if x {\n            y();\n        }\n", "  "),
+            "if x {\n      y();\n  }\n"
+        );
+        // Copied from mid-line, with a blank line inside
+        assert_eq!(
+            reindent_pasted_text("x {\n\ty();\n\n}", "    "),
+            "x {\n    \ty();\n\n    }"
+        );
+        // Single lines are pasted as they are
+        assert_eq!(reindent_pasted_text("  abc", "    "), "  abc");
+    }
+
     #[test]
     fn test_current_and_previous_line_indent() {
         let buffer = Buffer::from_str_test("fn main() {\n    let x = 1;");
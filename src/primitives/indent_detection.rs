@@ -0,0 +1,100 @@
+//! Guessing how a file is indented from its leading whitespace
+//!
+//! Lines indented with a tab are counted against lines indented with spaces.
+//! For spaces, the width is the most common change of indentation between
+//! consecutive lines: a 4-space file mostly steps by 4, and only sometimes
+//! by 8 when a block closes two levels at once. Steps of one column (the
+//! ` * ` of block comments) don't count.
+
+use crate::model::buffer::Buffer;
+
+/// Bytes looked at from the start of the file
+const SCAN_BYTES: usize = 64 * 1024;
+
+/// Widths a space indentation can be guessed as
+const WIDTHS: std::ops::RangeInclusive<usize> = 2..=8;
+
+/// How a file is indented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(usize),
+}
+
+/// Guess the indentation of `text`; `None` when it has too little
+/// indentation to tell
+pub fn detect_indentation(text: &str) -> Option<IndentStyle> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    // Number of times the indentation changed by each width
+    let mut steps = [0usize; 9];
+    let mut previous_spaces = 0;
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            previous_spaces = 0;
+            continue;
+        }
+        let spaces = line.len() - line.trim_start_matches(' ').len();
+        if spaces >= 2 {
+            space_lines += 1;
+        }
+        let step = spaces.abs_diff(previous_spaces);
+        if WIDTHS.contains(&step) {
+            steps[step] += 1;
+        }
+        previous_spaces = spaces;
+    }
+
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+    if tab_lines > space_lines {
+        return Some(IndentStyle::Tabs);
+    }
+    // The most common step, the narrower on a tie
+    let width = WIDTHS
+        .filter(|&width| steps[width] > 0)
+        .max_by_key(|&width| (steps[width], std::cmp::Reverse(width)))?;
+    Some(IndentStyle::Spaces(width))
+}
+
+/// Guess the indentation of the start of `buffer`
+pub fn detect_buffer_indentation(buffer: &mut Buffer) -> Option<IndentStyle> {
+    let bytes = buffer
+        .get_text_range_mut(0, buffer.len().min(SCAN_BYTES))
+        .ok()?;
+    detect_indentation(&String::from_utf8_lossy(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_space_width_from_steps() {
+        let four = "fn main() {\n    if x {\n        y();\n    }\n}\n\nfn f() {\n    g();\n}\n";
+        assert_eq!(detect_indentation(four), Some(IndentStyle::Spaces(4)));
+
+        // Closing two levels at once steps by 4 in a 2-space file
+        let two = "a:\n  b:\n    c: 1\n    d: 2\n  e:\n    f: 3\ng: 4\n";
+        assert_eq!(detect_indentation(two), Some(IndentStyle::Spaces(2)));
+    }
+
+    #[test]
+    fn test_detects_tabs() {
+        let text = "func main() {\n\tif x {\n\t\ty()\n\t}\n}\n";
+        assert_eq!(detect_indentation(text), Some(IndentStyle::Tabs));
+    }
+
+    #[test]
+    fn test_block_comments_and_unindented_text_tell_nothing() {
+        assert_eq!(detect_indentation("/**\n * Docs\n */\nint x;\n"), None);
+        assert_eq!(detect_indentation("one\ntwo\n\nthree\n"), None);
+        assert_eq!(detect_indentation(""), None);
+    }
+}
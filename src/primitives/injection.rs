@@ -0,0 +1,407 @@
+//! Language injection detection
+//!
+//! Finds regions of a buffer written in a different language than the
+//! buffer itself, so the highlight engine can highlight them with their
+//! own grammar:
+//! - Fenced code blocks in markdown (```` ```rust ````)
+//! - `<script>` and `<style>` elements in HTML
+//! - String literals annotated with a language comment, in any language:
+//!   `/* sql */ "SELECT ..."` or `// language=sql` on the line before
+
+use std::ops::Range;
+
+/// A region of text in another language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectionRegion {
+    /// Byte range of the embedded code, relative to the scanned text
+    pub range: Range<usize>,
+    /// Language token for the region ("rust", "js", "sql", ...), resolved
+    /// against the grammar registry by the caller
+    pub language: String,
+}
+
+/// Kind of host document, which decides the injection rules that apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InjectionHost {
+    Markdown,
+    Html,
+    Other,
+}
+
+impl InjectionHost {
+    /// Classify a host grammar by its top-level TextMate scope
+    pub fn from_scope(scope: &str) -> Self {
+        if scope.starts_with("text.html.markdown") {
+            Self::Markdown
+        } else if scope.starts_with("text.html") {
+            Self::Html
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Find all injection regions in `text`, sorted by start offset and
+/// non-overlapping
+pub fn find_injections(host: InjectionHost, text: &str) -> Vec<InjectionRegion> {
+    let mut regions = match host {
+        InjectionHost::Markdown => find_fenced_blocks(text),
+        InjectionHost::Html => {
+            let mut regions = find_html_elements(text);
+            regions.extend(find_annotated_strings(text));
+            regions
+        }
+        InjectionHost::Other => find_annotated_strings(text),
+    };
+
+    regions.sort_by_key(|r| r.range.start);
+    let mut end = 0;
+    regions.retain(|r| {
+        let keep = r.range.start >= end && !r.range.is_empty();
+        if keep {
+            end = r.range.end;
+        }
+        keep
+    });
+    regions
+}
+
+/// Iterate over lines with their starting byte offsets, keeping line endings
+fn lines_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line))
+    })
+}
+
+/// Fenced code blocks with an info string: the region covers the lines
+/// between the fences. An unclosed block runs to the end of the text.
+fn find_fenced_blocks(text: &str) -> Vec<InjectionRegion> {
+    let mut regions = Vec::new();
+    // (fence char, fence length, language, content start)
+    let mut open: Option<(char, usize, String, usize)> = None;
+
+    for (offset, line) in lines_with_offsets(text) {
+        let trimmed = line.trim_start_matches(' ');
+        // Fences may be indented by at most three spaces
+        if line.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let Some(fence_char) = trimmed.chars().next().filter(|c| *c == '`' || *c == '~') else {
+            continue;
+        };
+        let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+        if fence_len < 3 {
+            continue;
+        }
+        let info = trimmed[fence_len..].trim();
+
+        match &open {
+            Some((open_char, open_len, language, content_start)) => {
+                if fence_char == *open_char && fence_len >= *open_len && info.is_empty() {
+                    regions.push(InjectionRegion {
+                        range: *content_start..offset,
+                        language: language.clone(),
+                    });
+                    open = None;
+                }
+            }
+            None => {
+                let language = info
+                    .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+                    .next()
+                    .unwrap_or("")
+                    .to_lowercase();
+                open = Some((fence_char, fence_len, language, offset + line.len()));
+            }
+        }
+    }
+
+    if let Some((_, _, language, content_start)) = open {
+        regions.push(InjectionRegion {
+            range: content_start..text.len(),
+            language,
+        });
+    }
+
+    // Blocks without a language tag stay plain
+    regions.retain(|r| !r.language.is_empty());
+    regions
+}
+
+/// Contents of `<script>` and `<style>` elements
+fn find_html_elements(text: &str) -> Vec<InjectionRegion> {
+    let lower = text.to_ascii_lowercase();
+    let mut regions = Vec::new();
+
+    for (tag, default_language) in [("script", "js"), ("style", "css")] {
+        let open_tag = format!("<{}", tag);
+        let close_tag = format!("</{}", tag);
+        let mut search_from = 0;
+
+        while let Some(found) = lower[search_from..].find(&open_tag) {
+            let tag_start = search_from + found;
+            let after_name = tag_start + open_tag.len();
+            // Reject longer tag names like <scripts>
+            if lower[after_name..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                search_from = after_name;
+                continue;
+            }
+            let Some(tag_end) = lower[after_name..].find('>').map(|i| after_name + i + 1) else {
+                break;
+            };
+            let content_end = lower[tag_end..]
+                .find(&close_tag)
+                .map_or(text.len(), |i| tag_end + i);
+
+            let language =
+                html_type_language(&lower[after_name..tag_end - 1]).unwrap_or(default_language);
+            if !language.is_empty() {
+                regions.push(InjectionRegion {
+                    range: tag_end..content_end,
+                    language: language.to_string(),
+                });
+            }
+            search_from = content_end;
+        }
+    }
+
+    regions
+}
+
+/// Language from a `type`/`lang` attribute on a script or style tag.
+/// Returns an empty string for types that aren't code (JSON data blocks
+/// are still code; templates are not).
+fn html_type_language(attributes: &str) -> Option<&'static str> {
+    let value = ["lang=", "type="].iter().find_map(|attr| {
+        let start = attributes.find(attr)? + attr.len();
+        let rest = attributes[start..].trim_start_matches(['"', '\'']);
+        let end = rest
+            .find(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        Some(&rest[..end])
+    })?;
+
+    Some(match value {
+        "ts" | "typescript" | "text/typescript" => "ts",
+        "json" | "application/json" | "application/ld+json" | "importmap" => "json",
+        "scss" | "text/scss" => "scss",
+        "sass" | "text/sass" => "sass",
+        "less" | "text/less" => "less",
+        "module" | "text/javascript" | "application/javascript" | "js" | "javascript" => "js",
+        "text/css" | "css" => "css",
+        _ => "",
+    })
+}
+
+/// String literals annotated with the language they contain
+fn find_annotated_strings(text: &str) -> Vec<InjectionRegion> {
+    let mut candidates: Vec<usize> = text
+        .match_indices("/*")
+        .chain(text.match_indices("language="))
+        .map(|(i, _)| i)
+        .collect();
+    candidates.sort_unstable();
+
+    let mut regions = Vec::new();
+    let mut search_from = 0;
+    for start in candidates {
+        if start < search_from {
+            continue;
+        }
+        let Some((language, annotation_end, next_line)) = annotation_at(text, start) else {
+            continue;
+        };
+        search_from = annotation_end;
+
+        // A block comment annotates the string right after it; a line
+        // comment annotates the first string on the following line
+        let literal = if next_line {
+            text[annotation_end..]
+                .find('\n')
+                .map(|i| annotation_end + i + 1)
+                .and_then(|line_start| {
+                    let line_end = text[line_start..]
+                        .find('\n')
+                        .map_or(text.len(), |i| line_start + i);
+                    let quote = text[line_start..line_end].find(['"', '\'', '`'])?;
+                    string_literal_after(text, line_start + quote)
+                })
+        } else {
+            string_literal_after(text, annotation_end)
+        };
+
+        if let Some(range) = literal {
+            search_from = range.end;
+            regions.push(InjectionRegion { range, language });
+        }
+    }
+
+    regions
+}
+
+/// Parse a language annotation at `start`, which points at either `/*`
+/// (`/* sql */`, `/* language=sql */`) or `language=` inside a line comment
+/// (`//`, `#`, `--`). Returns the language, the offset just past the
+/// annotation, and whether it is a line comment.
+fn annotation_at(text: &str, start: usize) -> Option<(String, usize, bool)> {
+    if text[start..].starts_with("/*") {
+        let body_start = start + 2;
+        let body_len = text[body_start..].find("*/")?;
+        let body = text[body_start..body_start + body_len].trim();
+        let name = body.strip_prefix("language=").unwrap_or(body);
+        return is_language_token(name)
+            .then(|| (name.to_lowercase(), body_start + body_len + 2, false));
+    }
+
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let prefix = text[line_start..start].trim();
+    let in_line_comment = ["//", "#", "--"]
+        .iter()
+        .any(|c| prefix.strip_prefix(c).is_some_and(|p| p.trim().is_empty()));
+    if !in_line_comment {
+        return None;
+    }
+
+    let name_start = start + "language=".len();
+    let name_end = text[name_start..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |i| name_start + i);
+    let name = &text[name_start..name_end];
+    is_language_token(name).then(|| (name.to_lowercase(), name_end, true))
+}
+
+/// Whether an annotation body looks like a language name rather than prose
+fn is_language_token(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 20
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '#' | '-' | '_'))
+}
+
+/// The contents of a string literal starting after optional whitespace at
+/// `from`. Handles `"`, `'`, backtick, and triple-quoted strings, with
+/// backslash escapes.
+fn string_literal_after(text: &str, from: usize) -> Option<Range<usize>> {
+    let rest = &text[from..];
+    let skipped = rest.len() - rest.trim_start().len();
+    let start = from + skipped;
+    let rest = &text[start..];
+
+    let quote = ["\"\"\"", "'''", "\"", "'", "`"]
+        .into_iter()
+        .find(|q| rest.starts_with(q))?;
+    let content_start = start + quote.len();
+
+    let bytes = text.as_bytes();
+    let mut i = content_start;
+    while i < text.len() {
+        if bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if bytes[i..].starts_with(quote.as_bytes()) {
+            return Some(content_start..i);
+        }
+        // Only triple-quoted and template strings span lines
+        if bytes[i] == b'\n' && quote.len() == 1 && quote != "`" {
+            return None;
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regions(host: InjectionHost, text: &str) -> Vec<(&str, String)> {
+        find_injections(host, text)
+            .into_iter()
+            .map(|r| (&text[r.range], r.language))
+            .collect()
+    }
+
+    #[test]
+    fn test_host_from_scope() {
+        assert_eq!(
+            InjectionHost::from_scope("text.html.markdown"),
+            InjectionHost::Markdown
+        );
+        assert_eq!(
+            InjectionHost::from_scope("text.html.basic"),
+            InjectionHost::Html
+        );
+        assert_eq!(
+            InjectionHost::from_scope("source.rust"),
+            InjectionHost::Other
+        );
+    }
+
+    #[test]
+    fn test_markdown_fenced_blocks() {
+        let text = "# Title\n\n```rust\nfn main() {}\n```\n\n~~~ Python {.numberLines}\nx = 1\n~~~\n\n```\nplain\n```\n";
+        assert_eq!(
+            regions(InjectionHost::Markdown, text),
+            vec![
+                ("fn main() {}\n", "rust".to_string()),
+                ("x = 1\n", "python".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_unclosed_fence_runs_to_end() {
+        let text = "````js\nlet a = 1;\n```\nstill code\n";
+        assert_eq!(
+            regions(InjectionHost::Markdown, text),
+            vec![("let a = 1;\n```\nstill code\n", "js".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_html_script_and_style() {
+        let text = "<html><STYLE>p { color: red; }</STYLE>\n<script type=\"module\">let x = 1;</script><scripts>no</scripts><script type=\"text/template\"><b></b></script></html>";
+        assert_eq!(
+            regions(InjectionHost::Html, text),
+            vec![
+                ("p { color: red; }", "css".to_string()),
+                ("let x = 1;", "js".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_annotated_strings() {
+        let text = "let q = /* sql */ \"SELECT * FROM t WHERE a = \\\"x\\\"\";\n\
+                    // language=SQL\n\
+                    let r = 'DELETE FROM t';\n\
+                    /* not a language annotation */ \"text\";\n\
+                    query = \"\"\"\nSELECT 1\n\"\"\"  # nothing\n";
+        assert_eq!(
+            regions(InjectionHost::Other, text),
+            vec![
+                ("SELECT * FROM t WHERE a = \\\"x\\\"", "sql".to_string()),
+                ("DELETE FROM t", "sql".to_string()),
+            ]
+        );
+
+        let text = "q = /*language=sql*/ \"\"\"\nSELECT 1\n\"\"\"\n";
+        assert_eq!(
+            regions(InjectionHost::Other, text),
+            vec![("\nSELECT 1\n", "sql".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_annotation_requires_string() {
+        assert!(regions(InjectionHost::Other, "/* sql */ foo();\nlanguage=sql \"x\"").is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+//! Line operations: sorting, reversing, shuffling, joining and removing
+//! duplicate lines, and aligning lines on a delimiter.
+//!
+//! These are pure transformations of a list of lines; the editor applies
+//! them to the selected lines as one undoable edit (see
+//! `app/line_actions.rs`).
+
+use crate::primitives::display_width::str_width;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// An operation on a block of lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineOperation {
+    SortAscending,
+    SortDescending,
+    /// Sort by the number each line starts with
+    SortNumeric,
+    /// Sort ascending, dropping repeated lines
+    SortUnique,
+    Reverse,
+    Shuffle,
+    /// Join the lines into one, separated by single spaces
+    Join,
+    /// Drop repeated lines, keeping the first of each
+    RemoveDuplicates,
+}
+
+impl LineOperation {
+    /// Past-tense verb for status messages, e.g. "Sorted 3 lines"
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::SortAscending | Self::SortDescending | Self::SortNumeric | Self::SortUnique => {
+                "Sorted"
+            }
+            Self::Reverse => "Reversed",
+            Self::Shuffle => "Shuffled",
+            Self::Join => "Joined",
+            Self::RemoveDuplicates => "Removed duplicates from",
+        }
+    }
+}
+
+/// Apply `op` to `lines`
+///
+/// `seed` only matters for [`LineOperation::Shuffle`].
+pub fn apply_line_operation(op: LineOperation, lines: &[&str], seed: u64) -> Vec<String> {
+    let mut result: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    match op {
+        LineOperation::SortAscending => result.sort(),
+        LineOperation::SortDescending => result.sort_by(|a, b| b.cmp(a)),
+        LineOperation::SortNumeric => result.sort_by(|a, b| compare_numeric(a, b)),
+        LineOperation::SortUnique => {
+            result.sort();
+            result.dedup();
+        }
+        LineOperation::Reverse => result.reverse(),
+        LineOperation::Shuffle => shuffle(&mut result, seed),
+        LineOperation::Join => {
+            let joined = lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| if i == 0 { line.trim_end() } else { line.trim() })
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            result = vec![joined];
+        }
+        LineOperation::RemoveDuplicates => {
+            let mut seen = HashSet::new();
+            result.retain(|line| seen.insert(line.clone()));
+        }
+    }
+    result
+}
+
+/// The number a line starts with, ignoring leading whitespace
+fn leading_number(line: &str) -> Option<f64> {
+    let line = line.trim_start();
+    let mut end = 0;
+    for (i, c) in line.char_indices() {
+        let sign = i == 0 && (c == '-' || c == '+');
+        if !(sign || c.is_ascii_digit() || c == '.') {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    line[..end].parse().ok()
+}
+
+/// Lines without a number come first, in their original order
+fn compare_numeric(a: &str, b: &str) -> Ordering {
+    match (leading_number(a), leading_number(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Fisher-Yates shuffle driven by a xorshift generator
+fn shuffle(lines: &mut [String], seed: u64) {
+    let mut state = seed | 1;
+    for i in (1..lines.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = (state % (i as u64 + 1)) as usize;
+        lines.swap(i, j);
+    }
+}
+
+/// Parse the delimiter typed for "Align On...": literal text, or a regex
+/// between slashes (`/\s+-\s+/`)
+pub fn parse_align_delimiter(spec: &str) -> Result<Regex, String> {
+    let spec = spec.trim();
+    let pattern = match spec.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+        Some(pattern) if !pattern.is_empty() => pattern.to_string(),
+        _ if spec.is_empty() => return Err("No delimiter given".to_string()),
+        _ => regex::escape(spec),
+    };
+    Regex::new(&pattern).map_err(|e| format!("Invalid regex: {}", e))
+}
+
+/// Pad `lines` so every occurrence of `delimiter` lines up in columns
+///
+/// Lines are split into fields at each match; the n-th delimiters of all
+/// lines are aligned with each other. A delimiter written against the text
+/// before it on every line (`key: value`, `a, b`) stays attached and the
+/// fields after it are aligned instead. Lines without a delimiter are left
+/// as they are.
+pub fn align_lines(lines: &[&str], delimiter: &Regex) -> Vec<String> {
+    struct Split<'a> {
+        fields: Vec<&'a str>,
+        delimiters: Vec<&'a str>,
+    }
+
+    let splits: Vec<Option<Split>> = lines
+        .iter()
+        .map(|line| {
+            let mut fields = Vec::new();
+            let mut delimiters = Vec::new();
+            let mut last = 0;
+            for m in delimiter.find_iter(line).filter(|m| !m.is_empty()) {
+                fields.push(&line[last..m.start()]);
+                let text = m.as_str().trim();
+                delimiters.push(if text.is_empty() { m.as_str() } else { text });
+                last = m.end();
+            }
+            fields.push(&line[last..]);
+            (!delimiters.is_empty()).then_some(Split { fields, delimiters })
+        })
+        .collect();
+    let columns = splits
+        .iter()
+        .flatten()
+        .map(|split| split.delimiters.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output: Vec<String> = splits
+        .iter()
+        .map(|split| match split {
+            Some(split) => split.fields[0].trim_end().to_string(),
+            None => String::new(),
+        })
+        .collect();
+    for column in 0..columns {
+        let rows: Vec<usize> = (0..lines.len())
+            .filter(|&i| {
+                splits[i]
+                    .as_ref()
+                    .is_some_and(|split| split.delimiters.len() > column)
+            })
+            .collect();
+        let attached = rows.iter().all(|&i| {
+            let field = splits[i].as_ref().unwrap().fields[column];
+            field.ends_with(|c: char| !c.is_whitespace())
+        });
+        let pad = |output: &mut Vec<String>| {
+            let width = rows
+                .iter()
+                .map(|&i| str_width(&output[i]))
+                .max()
+                .unwrap_or(0);
+            for &i in &rows {
+                let padding = width - str_width(&output[i]);
+                output[i].push_str(&" ".repeat(padding));
+            }
+        };
+
+        if !attached {
+            pad(&mut output);
+        }
+        for &i in &rows {
+            let split = splits[i].as_ref().unwrap();
+            if !attached && !output[i].is_empty() {
+                output[i].push(' ');
+            }
+            output[i].push_str(split.delimiters[column]);
+        }
+        if attached {
+            pad(&mut output);
+        }
+        for &i in &rows {
+            let field = splits[i].as_ref().unwrap().fields[column + 1].trim();
+            if !field.is_empty() {
+                output[i].push(' ');
+                output[i].push_str(field);
+            }
+        }
+    }
+
+    output
+        .into_iter()
+        .zip(lines)
+        .zip(&splits)
+        .map(|((aligned, line), split)| match split {
+            Some(_) => aligned.trim_end().to_string(),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(op: LineOperation, lines: &[&str]) -> Vec<String> {
+        apply_line_operation(op, lines, 42)
+    }
+
+    #[test]
+    fn test_sorts() {
+        let lines = ["pear", "apple", "Zebra", "apple"];
+        assert_eq!(
+            apply(LineOperation::SortAscending, &lines),
+            ["Zebra", "apple", "apple", "pear"]
+        );
+        assert_eq!(
+            apply(LineOperation::SortDescending, &lines),
+            ["pear", "apple", "apple", "Zebra"]
+        );
+        assert_eq!(
+            apply(LineOperation::SortUnique, &lines),
+            ["Zebra", "apple", "pear"]
+        );
+        assert_eq!(
+            apply(
+                LineOperation::SortNumeric,
+                &["10 ten", "2 two", "x", "  -1.5", "2.5"]
+            ),
+            ["x", "  -1.5", "2 two", "2.5", "10 ten"]
+        );
+    }
+
+    #[test]
+    fn test_reverse_and_remove_duplicates() {
+        assert_eq!(
+            apply(LineOperation::Reverse, &["a", "b", "c"]),
+            ["c", "b", "a"]
+        );
+        assert_eq!(
+            apply(
+                LineOperation::RemoveDuplicates,
+                &["b", "a", "b", "", "a", ""]
+            ),
+            ["b", "a", ""]
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            apply(LineOperation::Join, &["  fn f(", "    a,", "", "    b) "]),
+            ["  fn f( a, b)"]
+        );
+    }
+
+    #[test]
+    fn test_shuffle_keeps_lines() {
+        let lines: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut shuffled = apply(LineOperation::Shuffle, &refs);
+        assert_ne!(shuffled, lines);
+        shuffled.sort_by_key(|line| line.parse::<u32>().unwrap());
+        assert_eq!(shuffled, lines);
+    }
+
+    fn align(lines: &[&str], spec: &str) -> Vec<String> {
+        align_lines(lines, &parse_align_delimiter(spec).unwrap())
+    }
+
+    #[test]
+    fn test_align_on_delimiter() {
+        assert_eq!(
+            align(&["a = 1", "long_name=2", "  # comment", "b  =  3"], "="),
+            [
+                "a         = 1",
+                "long_name = 2",
+                "  # comment",
+                "b         = 3"
+            ]
+        );
+        // Attached delimiters stay attached; the values line up
+        assert_eq!(
+            align(&["name: x", "description: y"], ":"),
+            ["name:        x", "description: y"]
+        );
+    }
+
+    #[test]
+    fn test_align_every_occurrence() {
+        assert_eq!(
+            align(&["a,bb,c", "long,x,y", "k,v"], ","),
+            ["a,    bb, c", "long, x,  y", "k,    v"]
+        );
+        assert_eq!(
+            align(&["x -> 1 -> a", "yyy ->  22 -> b"], "/-+>/"),
+            ["x   -> 1  -> a", "yyy -> 22 -> b"]
+        );
+    }
+
+    #[test]
+    fn test_parse_align_delimiter() {
+        assert!(parse_align_delimiter("  ").is_err());
+        assert!(parse_align_delimiter("/(/").is_err());
+        // Literal text is not a regex
+        assert!(parse_align_delimiter("(").unwrap().is_match("f(x)"));
+        assert!(parse_align_delimiter("/").unwrap().is_match("a/b"));
+    }
+}
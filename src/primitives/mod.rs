@@ -10,9 +10,14 @@ pub mod grammar_registry;
 pub mod highlight_engine;
 pub mod highlighter;
 pub mod indent;
+pub mod indent_detection;
+pub mod injection;
 pub mod line_iterator;
+pub mod line_operations;
 pub mod line_wrapping;
+pub mod prose;
 pub mod semantic_highlight;
+pub mod sticky_scroll;
 pub mod text_property;
 pub mod visual_layout;
 pub mod word_navigation;
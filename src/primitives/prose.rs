@@ -0,0 +1,385 @@
+//! Paragraphs and sentences of prose.
+//!
+//! A paragraph is a run of non-blank lines. A line holding nothing but a
+//! comment marker (`//`, `#`, `>`, ...) counts as blank, so the paragraphs of
+//! a comment are found the same way as those of a markdown file. Sentences
+//! end with `.`, `!` or `?` (and any closing quotes or brackets) followed by
+//! whitespace, or with their paragraph.
+//!
+//! [`reflow_lines`] rewraps paragraphs to a width for "Reflow Paragraph",
+//! keeping the comment prefix of each paragraph and the bullet of each list
+//! item.
+
+use std::ops::Range;
+
+use crate::model::buffer::Buffer;
+use crate::primitives::display_width::visual_column_at_byte_with_tabs;
+
+/// Comment markers kept at the start of reflowed lines, longest first
+const COMMENT_MARKERS: &[&str] = &["///", "//!", "//", "--", "#", ";", ">", "*"];
+
+/// Bytes looked at on each side of the cursor to find sentences
+const SENTENCE_WINDOW: usize = 4000;
+
+/// Split `line` into its prefix (indentation, a comment marker and the
+/// whitespace after it) and the text after it
+fn split_prefix(line: &str) -> (&str, &str) {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let rest = &line[indent..];
+    let marker = COMMENT_MARKERS
+        .iter()
+        .find(|marker| rest.starts_with(*marker))
+        .map_or(0, |marker| {
+            // `;;`, `##` and `>>` are one marker
+            let repeat = if marker.len() == 1 { *marker } else { "" };
+            let repeated = rest[marker.len()..].trim_start_matches(repeat);
+            rest.len() - repeated.len()
+        });
+    let text = rest[marker..].trim_start_matches([' ', '\t']);
+    line.split_at(line.len() - text.len())
+}
+
+/// Whether `line` has no text besides whitespace and a comment marker
+pub fn is_blank_line(line: &str) -> bool {
+    split_prefix(line).1.trim().is_empty()
+}
+
+/// The list bullet `text` starts with (`- `, `* `, `+ `, `1. ` or `1) `),
+/// with the whitespace after it
+fn list_bullet(text: &str) -> Option<&str> {
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let marker = match text[digits..].chars().next()? {
+        '.' | ')' if digits > 0 => digits + 1,
+        '-' | '*' | '+' if digits == 0 => 1,
+        _ => return None,
+    };
+    let after = &text[marker..];
+    let text_start = after.trim_start_matches([' ', '\t']);
+    (text_start.len() < after.len()).then(|| &text[..text.len() - text_start.len()])
+}
+
+/// Start of the paragraph before `pos`: the blank line above the paragraph
+/// the cursor is in, or above the previous one if it is at its start or
+/// between paragraphs. The start of the buffer if there is none.
+pub fn find_paragraph_start(
+    buffer: &mut Buffer,
+    pos: usize,
+    estimated_line_length: usize,
+) -> usize {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let line_start = iter.current_position();
+    // Inside a line of text, its own paragraph is the one to go to the start of
+    let mut in_paragraph =
+        pos > line_start && iter.next().is_some_and(|(_, line)| !is_blank_line(&line));
+
+    let mut iter = buffer.line_iterator(line_start, estimated_line_length);
+    while let Some((start, line)) = iter.prev() {
+        let blank = is_blank_line(&line);
+        if blank && in_paragraph {
+            return start;
+        }
+        in_paragraph |= !blank;
+    }
+    0
+}
+
+/// End of the paragraph after `pos`: the blank line below the paragraph the
+/// cursor is in, or below the next one if it is between paragraphs. The end
+/// of the buffer if there is none.
+pub fn find_paragraph_end(buffer: &mut Buffer, pos: usize, estimated_line_length: usize) -> usize {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let mut in_paragraph = false;
+    while let Some((start, line)) = iter.next() {
+        let blank = is_blank_line(&line);
+        if blank && in_paragraph && start > pos {
+            return start;
+        }
+        in_paragraph |= !blank;
+    }
+    buffer.len()
+}
+
+/// Lines of the paragraph at `pos`: from the start of its first line to the
+/// start of the line after it. `None` on a blank line.
+pub fn paragraph_at(
+    buffer: &mut Buffer,
+    pos: usize,
+    estimated_line_length: usize,
+) -> Option<Range<usize>> {
+    let mut end = buffer.len();
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let line_start = iter.current_position();
+    let (_, line) = iter.next()?;
+    if is_blank_line(&line) {
+        return None;
+    }
+    while let Some((start, line)) = iter.next() {
+        if is_blank_line(&line) {
+            end = start;
+            break;
+        }
+    }
+
+    let mut start = line_start;
+    let mut iter = buffer.line_iterator(line_start, estimated_line_length);
+    while let Some((line_start, line)) = iter.prev() {
+        if is_blank_line(&line) {
+            break;
+        }
+        start = line_start;
+    }
+    Some(start..end)
+}
+
+/// Offsets in `bytes` where sentences start. `at_text_start` says whether
+/// `bytes` starts at the start of the text, so that its first word starts a
+/// sentence.
+fn sentence_starts(bytes: &[u8], at_text_start: bool) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut pending = at_text_start;
+    let mut after_end = false;
+    let mut newlines = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        if b.is_ascii_whitespace() {
+            pending |= after_end;
+            after_end = false;
+            if b == b'\n' {
+                newlines += 1;
+                // A blank line ends the paragraph, and its last sentence
+                pending |= newlines >= 2;
+            }
+            continue;
+        }
+        newlines = 0;
+        if pending {
+            starts.push(i);
+            pending = false;
+        }
+        match b {
+            b'.' | b'!' | b'?' => after_end = true,
+            b'"' | b'\'' | b')' | b']' => {}
+            _ => after_end = false,
+        }
+    }
+    starts
+}
+
+/// Sentence starts around `pos`, with the range of the buffer looked at
+fn sentence_starts_around(buffer: &Buffer, pos: usize) -> (Range<usize>, Vec<usize>) {
+    let start = pos.saturating_sub(SENTENCE_WINDOW);
+    let end = (pos + SENTENCE_WINDOW).min(buffer.len());
+    let bytes = buffer.slice_bytes(start..end);
+    let starts = sentence_starts(&bytes, start == 0)
+        .into_iter()
+        .map(|offset| start + offset)
+        .collect();
+    (start..end, starts)
+}
+
+/// Start of the sentence before `pos`
+pub fn find_sentence_start_left(buffer: &Buffer, pos: usize) -> usize {
+    let (window, starts) = sentence_starts_around(buffer, pos);
+    starts
+        .into_iter()
+        .rev()
+        .find(|&start| start < pos)
+        .unwrap_or(window.start)
+}
+
+/// Start of the sentence after `pos`, or the end of the buffer
+pub fn find_sentence_start_right(buffer: &Buffer, pos: usize) -> usize {
+    let (window, starts) = sentence_starts_around(buffer, pos);
+    starts
+        .into_iter()
+        .find(|&start| start > pos)
+        .unwrap_or(window.end)
+}
+
+/// Fill `words` into lines of at most `width` columns, the first starting
+/// with `first_prefix` and the others with `prefix`. A word longer than the
+/// width gets a line of its own.
+fn fill(
+    words: &[&str],
+    first_prefix: &str,
+    prefix: &str,
+    width: usize,
+    tab_size: usize,
+) -> Vec<String> {
+    let columns = |s: &str| visual_column_at_byte_with_tabs(s, s.len(), tab_size);
+    let mut lines = Vec::new();
+    let mut line = first_prefix.to_string();
+    let mut has_words = false;
+    for word in words {
+        if has_words && columns(&line) + 1 + columns(word) > width {
+            lines.push(std::mem::replace(&mut line, prefix.to_string()));
+            has_words = false;
+        }
+        if has_words {
+            line.push(' ');
+        }
+        line.push_str(word);
+        has_words = true;
+    }
+    lines.push(line.trim_end().to_string());
+    lines
+}
+
+/// Rewrap one paragraph
+fn reflow_paragraph(lines: &[&str], width: usize, tab_size: usize) -> Vec<String> {
+    let (prefix, _) = split_prefix(lines[0]);
+    let marker = prefix.trim();
+    // ` * ` starts the lines of a block comment; otherwise `*` is a bullet
+    let is_comment = match marker {
+        "" => false,
+        "*" => {
+            prefix.starts_with([' ', '\t'])
+                && lines.iter().all(|line| line.trim_start().starts_with('*'))
+        }
+        _ => true,
+    };
+    let (prefix, texts): (&str, Vec<&str>) = if is_comment {
+        let texts = lines.iter().map(|line| split_prefix(line).1).collect();
+        (prefix, texts)
+    } else {
+        let indent = &lines[0][..lines[0].len() - lines[0].trim_start().len()];
+        (indent, lines.iter().map(|line| line.trim_start()).collect())
+    };
+
+    // Each list item is filled on its own, its lines lined up after the bullet
+    let mut result = Vec::new();
+    let mut bullet = "";
+    let mut words: Vec<&str> = Vec::new();
+    let mut flush = |bullet: &str, words: &mut Vec<&str>| {
+        if words.is_empty() {
+            return;
+        }
+        let first_prefix = format!("{}{}", prefix, bullet);
+        let indent = visual_column_at_byte_with_tabs(bullet, bullet.len(), tab_size);
+        let rest_prefix = format!("{}{}", prefix, " ".repeat(indent));
+        result.extend(fill(words, &first_prefix, &rest_prefix, width, tab_size));
+        words.clear();
+    };
+    for text in texts {
+        let text = text.trim_end();
+        let text = match list_bullet(text) {
+            Some(item_bullet) => {
+                flush(bullet, &mut words);
+                bullet = item_bullet;
+                &text[item_bullet.len()..]
+            }
+            None => text,
+        };
+        words.extend(text.split_whitespace());
+    }
+    flush(bullet, &mut words);
+    result
+}
+
+/// Rewrap the paragraphs of `lines` to `width` columns
+///
+/// Blank lines separate paragraphs and are kept as they are. Each paragraph
+/// keeps the indentation and comment marker of its first line, and list
+/// items are filled one by one, their lines indented past the bullet.
+pub fn reflow_lines(lines: &[&str], width: usize, tab_size: usize) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in lines {
+        if is_blank_line(line) {
+            if !paragraph.is_empty() {
+                result.extend(reflow_paragraph(&paragraph, width, tab_size));
+                paragraph.clear();
+            }
+            result.push(line.to_string());
+        } else {
+            paragraph.push(line);
+        }
+    }
+    if !paragraph.is_empty() {
+        result.extend(reflow_paragraph(&paragraph, width, tab_size));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reflow(text: &str, width: usize) -> String {
+        let lines: Vec<&str> = text.split('\n').collect();
+        reflow_lines(&lines, width, 4).join("\n")
+    }
+
+    #[test]
+    fn test_split_prefix() {
+        assert_eq!(split_prefix("    /// Docs"), ("    /// ", "Docs"));
+        assert_eq!(split_prefix(";; Lisp"), (";; ", "Lisp"));
+        assert_eq!(split_prefix("> > quote"), ("> ", "> quote"));
+        assert_eq!(split_prefix("  text"), ("  ", "text"));
+        assert!(is_blank_line("  //  \n"));
+        assert!(!is_blank_line("// a\n"));
+    }
+
+    #[test]
+    fn test_reflow_joins_and_splits_lines() {
+        assert_eq!(
+            reflow("one two\nthree four five six seven", 15),
+            "one two three\nfour five six\nseven"
+        );
+        // A word longer than the width stays whole
+        assert_eq!(reflow("a verylongword b", 5), "a\nverylongword\nb");
+    }
+
+    #[test]
+    fn test_reflow_keeps_comment_prefixes() {
+        assert_eq!(
+            reflow(
+                "    // one two three\n    // four\n    //\n    // five six",
+                18
+            ),
+            "    // one two\n    // three four\n    //\n    // five six"
+        );
+        assert_eq!(reflow(" * one two\n * three", 12), " * one two\n * three");
+    }
+
+    #[test]
+    fn test_reflow_list_items() {
+        assert_eq!(
+            reflow("- one two three\n- four\n  five\n10. six seven", 11),
+            "- one two\n  three\n- four five\n10. six\n    seven"
+        );
+        // A `*` bullet on its own is a list item, not a comment
+        assert_eq!(reflow("* one two three", 10), "* one two\n  three");
+    }
+
+    #[test]
+    fn test_paragraph_motions() {
+        let text = "one\ntwo\n\n\nthree\n//\nfour";
+        let mut buffer = Buffer::from_str(text, usize::MAX);
+        // From inside the first paragraph to the blank line after it, then
+        // past the comment-only line to the end
+        assert_eq!(find_paragraph_end(&mut buffer, 1, 80), 8);
+        assert_eq!(find_paragraph_end(&mut buffer, 8, 80), 16);
+        assert_eq!(find_paragraph_end(&mut buffer, 16, 80), text.len());
+        assert_eq!(find_paragraph_start(&mut buffer, text.len(), 80), 16);
+        assert_eq!(find_paragraph_start(&mut buffer, 16, 80), 9);
+        assert_eq!(find_paragraph_start(&mut buffer, 5, 80), 0);
+
+        assert_eq!(paragraph_at(&mut buffer, 5, 80), Some(0..8));
+        assert_eq!(paragraph_at(&mut buffer, 8, 80), None);
+        assert_eq!(paragraph_at(&mut buffer, 19, 80), Some(19..text.len()));
+    }
+
+    #[test]
+    fn test_sentence_motions() {
+        let text = "One. \"Two?\" Three\nfour\n\nFive";
+        let buffer = Buffer::from_str(text, usize::MAX);
+        assert_eq!(find_sentence_start_right(&buffer, 0), 5);
+        assert_eq!(find_sentence_start_right(&buffer, 5), 12);
+        // A blank line ends a sentence without punctuation
+        assert_eq!(find_sentence_start_right(&buffer, 12), 24);
+        assert_eq!(find_sentence_start_right(&buffer, 24), text.len());
+        assert_eq!(find_sentence_start_left(&buffer, 14), 12);
+        assert_eq!(find_sentence_start_left(&buffer, 12), 5);
+        assert_eq!(find_sentence_start_left(&buffer, 3), 0);
+    }
+}
@@ -7,6 +7,9 @@
 //! - Uses the same `HighlightSpan` approach as syntax highlighting for efficiency
 //! - Computed on-demand during rendering (no persistent markers)
 //! - Only highlights occurrences within the visible viewport
+//! - Debounced: highlights are hidden while the cursor moves and shown once it
+//!   has rested (see [`SemanticHighlighter::settle`])
+//! - With a selection, matches the selected text literally instead of the word
 //!
 //! # Three modes of operation (in order of preference):
 //! 1. **Locals mode** (scope-aware): Uses tree-sitter "locals" queries to track
@@ -26,6 +29,7 @@ use crate::primitives::highlighter::{HighlightSpan, Language};
 use crate::primitives::word_navigation::{find_word_end, find_word_start, is_word_char};
 use ratatui::style::Color;
 use std::ops::Range;
+use std::time::{Duration, Instant};
 use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
 
 /// Default subtle background color for occurrence highlights
@@ -48,6 +52,12 @@ pub struct SemanticHighlighter {
     locals_query: Option<Query>,
     /// Capture indices for locals query
     locals_captures: LocalsCaptures,
+    /// Whether highlights are currently shown (false while the cursor moves)
+    visible: bool,
+    /// Cursor position and selection anchor the highlights were settled for
+    settled_for: Option<(usize, Option<usize>)>,
+    /// When the cursor last moved, while waiting for it to rest
+    moved_at: Option<Instant>,
 }
 
 /// Capture indices for the locals query
@@ -183,6 +193,9 @@ impl SemanticHighlighter {
             identifier_query: None,
             locals_query: None,
             locals_captures: LocalsCaptures::default(),
+            visible: true,
+            settled_for: None,
+            moved_at: None,
         }
     }
 
@@ -305,6 +318,85 @@ impl SemanticHighlighter {
         self.parser.is_some() && self.identifier_query.is_some()
     }
 
+    /// Track the cursor and report when its highlights should appear
+    ///
+    /// Highlights are hidden as soon as `cursor` (position and selection
+    /// anchor) differs from the last call, and shown again once it has stayed
+    /// put for `delay`. Returns true when they become visible, so the caller
+    /// knows to redraw.
+    pub fn settle(
+        &mut self,
+        cursor: (usize, Option<usize>),
+        now: Instant,
+        delay: Duration,
+    ) -> bool {
+        if self.settled_for != Some(cursor) {
+            self.settled_for = Some(cursor);
+            self.moved_at = Some(now);
+            self.visible = false;
+        }
+
+        match self.moved_at {
+            Some(moved_at) if now.saturating_duration_since(moved_at) >= delay => {
+                self.moved_at = None;
+                self.visible = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Hide highlights until the next [`Self::settle`] sees the cursor rest
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.settled_for = None;
+        self.moved_at = None;
+    }
+
+    /// Get highlights for other occurrences of the selected text
+    ///
+    /// Matches the selection literally (not just whole words) within the
+    /// viewport. Selections spanning lines or containing only whitespace
+    /// produce no highlights, and the selection itself is not highlighted.
+    pub fn highlight_selection_occurrences(
+        &self,
+        buffer: &Buffer,
+        selection: Range<usize>,
+        viewport_start: usize,
+        viewport_end: usize,
+    ) -> Vec<HighlightSpan> {
+        if !self.enabled || !self.visible || selection.len() < self.min_word_length {
+            return Vec::new();
+        }
+
+        let selected_bytes = buffer.slice_bytes(selection.clone());
+        let needle = match std::str::from_utf8(&selected_bytes) {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+        if needle.contains('\n') || needle.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let search_start = viewport_start.saturating_sub(needle.len());
+        let search_end = (viewport_end + needle.len()).min(buffer.len());
+        let bytes = buffer.slice_bytes(search_start..search_end);
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        text.match_indices(needle)
+            .map(|(rel, _)| search_start + rel..search_start + rel + needle.len())
+            .filter(|range| range.start < viewport_end && range.end > viewport_start)
+            .filter(|range| *range != selection)
+            .map(|range| HighlightSpan {
+                range,
+                color: self.highlight_color,
+            })
+            .collect()
+    }
+
     /// Get highlights for word occurrences in the viewport
     ///
     /// # Arguments
@@ -324,7 +416,7 @@ impl SemanticHighlighter {
         viewport_end: usize,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        if !self.enabled {
+        if !self.enabled || !self.visible {
             return Vec::new();
         }
 
@@ -911,6 +1003,53 @@ mod tests {
         assert_eq!(spans.len(), 0);
     }
 
+    #[test]
+    fn test_settle_hides_highlights_until_cursor_rests() {
+        let buffer = Buffer::from_str_test("foo bar foo");
+        let mut highlighter = SemanticHighlighter::new();
+        let delay = Duration::from_millis(150);
+        let start = Instant::now();
+
+        assert!(!highlighter.settle((0, None), start, delay));
+        let spans = highlighter.highlight_occurrences(&buffer, 0, 0, buffer.len(), 100_000);
+        assert!(spans.is_empty());
+
+        // Still resting on the same spot, but not long enough yet
+        assert!(!highlighter.settle((0, None), start + Duration::from_millis(100), delay));
+
+        assert!(highlighter.settle((0, None), start + delay, delay));
+        let spans = highlighter.highlight_occurrences(&buffer, 0, 0, buffer.len(), 100_000);
+        assert_eq!(spans.len(), 2);
+
+        // Already visible: no further redraw needed
+        assert!(!highlighter.settle((0, None), start + delay * 2, delay));
+
+        // Moving the cursor hides them again
+        assert!(!highlighter.settle((8, None), start + delay * 2, delay));
+        let spans = highlighter.highlight_occurrences(&buffer, 8, 0, buffer.len(), 100_000);
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn test_selection_occurrences() {
+        let buffer = Buffer::from_str_test("a.b(x); a.b(y);\na.bc");
+        let highlighter = SemanticHighlighter::new();
+
+        // Selecting "a.b" matches literally, including inside "a.bc"
+        let spans = highlighter.highlight_selection_occurrences(&buffer, 0..3, 0, buffer.len());
+        let ranges: Vec<_> = spans.into_iter().map(|s| s.range).collect();
+        assert_eq!(ranges, vec![8..11, 16..19]);
+
+        // Multi-line and whitespace-only selections are ignored
+        assert!(highlighter
+            .highlight_selection_occurrences(&buffer, 12..18, 0, buffer.len())
+            .is_empty());
+        let buffer = Buffer::from_str_test("foo  bar  baz");
+        assert!(highlighter
+            .highlight_selection_occurrences(&buffer, 3..5, 0, buffer.len())
+            .is_empty());
+    }
+
     #[test]
     fn test_cursor_at_end_of_buffer() {
         let buffer = Buffer::from_str_test("foo bar foo");
@@ -0,0 +1,312 @@
+//! Sticky scroll: headers of the scopes enclosing the top of the viewport
+//!
+//! When the view is scrolled into the middle of a function, class or
+//! markdown section, the lines that open the enclosing scopes are pinned at
+//! the top of the editor. Scopes come from the tree-sitter parse tree for
+//! built-in languages and from headings for markdown.
+//!
+//! Only `context_bytes` around the position are parsed, so scopes starting
+//! further back are not found (same trade-off as viewport highlighting).
+//!
+//! The same scopes are listed as the symbols of a file by the command
+//! palette's `@` mode.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use tree_sitter::Parser;
+
+/// Parse tree node kinds that open a sticky scope
+const SCOPE_KINDS: &[&str] = &[
+    // Rust
+    "function_item",
+    "impl_item",
+    "trait_item",
+    "mod_item",
+    "struct_item",
+    "enum_item",
+    // Python, C, C++, PHP, Bash, Lua
+    "function_definition",
+    "class_definition",
+    // JavaScript, TypeScript, Go, Java, C#, PHP, Lua
+    "function_declaration",
+    "generator_function_declaration",
+    "class_declaration",
+    "abstract_class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "method_definition",
+    "method_declaration",
+    "constructor_declaration",
+    "namespace_declaration",
+    "type_declaration",
+    // C, C++
+    "struct_specifier",
+    "class_specifier",
+    "enum_specifier",
+    "namespace_definition",
+    // Ruby
+    "class",
+    "module",
+    "method",
+    "singleton_method",
+    // CSS
+    "rule_set",
+    "media_statement",
+];
+
+/// Line start offsets of the headers of the scopes enclosing the line that
+/// starts at `position`, outermost first
+pub fn enclosing_scope_headers(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    is_markdown: bool,
+    position: usize,
+    context_bytes: usize,
+) -> Vec<usize> {
+    let mut start = position.saturating_sub(context_bytes);
+    let end = position.saturating_add(context_bytes).min(buffer.len());
+    let source = buffer.slice_bytes(start..end);
+
+    // Start the parse on a line boundary
+    let skip = if start > 0 {
+        source
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(0, |i| i + 1)
+            .min(position - start)
+    } else {
+        0
+    };
+    start += skip;
+    let source = &source[skip..];
+
+    let headers = if is_markdown {
+        heading_headers(source, position - start)
+    } else if let Some(language) = language {
+        syntax_headers(language, source, position - start)
+    } else {
+        Vec::new()
+    };
+    headers.into_iter().map(|offset| offset + start).collect()
+}
+
+/// Line start offsets of every scope header in the first `max_bytes` of the
+/// buffer, in document order
+pub fn all_scope_headers(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    is_markdown: bool,
+    max_bytes: usize,
+) -> Vec<usize> {
+    let source = buffer.slice_bytes(0..buffer.len().min(max_bytes));
+    if is_markdown {
+        let mut headers = Vec::new();
+        let mut in_fence = false;
+        let mut offset = 0;
+        for line in source.split(|&b| b == b'\n') {
+            let trimmed = line.trim_ascii_start();
+            if trimmed.starts_with(b"```") || trimmed.starts_with(b"~~~") {
+                in_fence = !in_fence;
+            } else if !in_fence && heading_level(line).is_some() {
+                headers.push(offset);
+            }
+            offset += line.len() + 1;
+        }
+        return headers;
+    }
+
+    let Some(language) = language else {
+        return Vec::new();
+    };
+    let mut parser = Parser::new();
+    if parser
+        .set_language(&language.tree_sitter_language())
+        .is_err()
+    {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(&source, None) else {
+        return Vec::new();
+    };
+
+    // Pre-order walk, so headers come out sorted by start offset
+    let mut headers: Vec<usize> = Vec::new();
+    let mut cursor = tree.walk();
+    'walk: loop {
+        let node = cursor.node();
+        if SCOPE_KINDS.contains(&node.kind()) {
+            let header = line_start(&source, node.start_byte());
+            if headers.last() != Some(&header) {
+                headers.push(header);
+            }
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+    headers
+}
+
+/// Scope headers from the tree-sitter parse tree
+fn syntax_headers(language: &Language, source: &[u8], position: usize) -> Vec<usize> {
+    let mut parser = Parser::new();
+    if parser
+        .set_language(&language.tree_sitter_language())
+        .is_err()
+    {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    // Look up the first non-blank character of the line, not its indentation
+    let target = source[position..]
+        .iter()
+        .position(|&b| !matches!(b, b' ' | b'\t'))
+        .map_or(position, |i| position + i);
+    let Some(mut node) = tree.root_node().descendant_for_byte_range(target, target) else {
+        return Vec::new();
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        if SCOPE_KINDS.contains(&node.kind())
+            && node.start_byte() < position
+            && node.end_byte() > target
+        {
+            let header = line_start(source, node.start_byte());
+            if headers.last() != Some(&header) {
+                headers.push(header);
+            }
+        }
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => break,
+        }
+    }
+    headers.reverse();
+    headers
+}
+
+/// Headings of the markdown sections containing `position`
+fn heading_headers(source: &[u8], position: usize) -> Vec<usize> {
+    let mut sections: Vec<(usize, usize)> = Vec::new(); // (level, offset)
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in source.split(|&b| b == b'\n') {
+        if offset > position {
+            break;
+        }
+        let trimmed = line.trim_ascii_start();
+        if trimmed.starts_with(b"```") || trimmed.starts_with(b"~~~") {
+            in_fence = !in_fence;
+        } else if let Some(level) = heading_level(line).filter(|_| !in_fence) {
+            sections.retain(|(outer, _)| *outer < level);
+            if offset < position {
+                sections.push((level, offset));
+            }
+        }
+        offset += line.len() + 1;
+    }
+    sections.into_iter().map(|(_, offset)| offset).collect()
+}
+
+/// Level of an ATX heading line (`# Title` is 1)
+fn heading_level(line: &[u8]) -> Option<usize> {
+    let level = line.iter().take_while(|&&b| b == b'#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest[0] == b' ' || rest[0] == b'\t'))
+        .then_some(level)
+}
+
+fn line_start(source: &[u8], offset: usize) -> usize {
+    source[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(
+        text: &str,
+        language: Option<Language>,
+        is_markdown: bool,
+        line: &str,
+    ) -> Vec<String> {
+        let buffer = Buffer::from_str_test(text);
+        let position = text.find(line).unwrap();
+        enclosing_scope_headers(&buffer, language.as_ref(), is_markdown, position, 10_000)
+            .into_iter()
+            .map(|offset| text[offset..].lines().next().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_scopes() {
+        let text = "mod a {\n    impl S {\n        fn f() {\n            let x = 1;\n        }\n    }\n}\nfn g() {}\n";
+        assert_eq!(
+            headers(text, Some(Language::Rust), false, "            let x"),
+            vec!["mod a {", "    impl S {", "        fn f() {"]
+        );
+        // A scope that starts on the line itself is not a header for it
+        assert_eq!(
+            headers(text, Some(Language::Rust), false, "        fn f()"),
+            vec!["mod a {", "    impl S {"]
+        );
+        assert!(headers(text, Some(Language::Rust), false, "fn g()").is_empty());
+    }
+
+    #[test]
+    fn test_python_scopes() {
+        let text = "class A:\n    def f(self):\n        x = 1\n        return x\n";
+        assert_eq!(
+            headers(text, Some(Language::Python), false, "        return"),
+            vec!["class A:", "    def f(self):"]
+        );
+    }
+
+    #[test]
+    fn test_markdown_headings() {
+        let text = "# Title\n\n## One\n\ntext\n\n```\n# not a heading\n```\n\n### Deep\n\nmore\n\n## Two\n\nlast\n";
+        assert_eq!(
+            headers(text, None, true, "more"),
+            vec!["# Title", "## One", "### Deep"]
+        );
+        assert_eq!(headers(text, None, true, "last"), vec!["# Title", "## Two"]);
+        // A heading line closes sections of the same or deeper level
+        assert_eq!(headers(text, None, true, "## Two"), vec!["# Title"]);
+    }
+
+    fn all_headers(text: &str, language: Option<Language>, is_markdown: bool) -> Vec<String> {
+        let buffer = Buffer::from_str_test(text);
+        all_scope_headers(&buffer, language.as_ref(), is_markdown, 10_000)
+            .into_iter()
+            .map(|offset| text[offset..].lines().next().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_all_scope_headers() {
+        let text = "mod a {\n    impl S {\n        fn f() {\n            let x = 1;\n        }\n    }\n}\nfn g() {}\n";
+        assert_eq!(
+            all_headers(text, Some(Language::Rust), false),
+            vec!["mod a {", "    impl S {", "        fn f() {", "fn g() {}"]
+        );
+        let text = "# Title\n\n```\n# not a heading\n```\n\n## One\n";
+        assert_eq!(all_headers(text, None, true), vec!["# Title", "## One"]);
+    }
+
+    #[test]
+    fn test_no_language() {
+        assert!(headers("fn f() {\n    x\n}\n", None, false, "    x").is_empty());
+    }
+}
@@ -14,12 +14,19 @@ use crate::services::terminal::TerminalId;
 use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{
     CodeActionOrCommand, CompletionItem, Diagnostic, InlayHint, Location, SignatureHelp,
+    WorkspaceSymbolResponse,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::mpsc;
 
 /// Messages sent from async tasks to the synchronous main loop
-#[derive(Debug)]
+///
+/// `--record-session` records the messages that can be serialized with the
+/// input, so a replay handles them too (see `input::recording`); the ones
+/// holding state of this process or types without a serialized form are
+/// skipped.
+#[derive(Debug, Serialize, Deserialize)]
 pub enum AsyncMessage {
     /// LSP diagnostics received for a file
     LspDiagnostics {
@@ -74,6 +81,12 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP workspace symbol search response
+    LspWorkspaceSymbols {
+        request_id: u64,
+        response: Option<WorkspaceSymbolResponse>,
+    },
+
     /// LSP signature help response
     LspSignatureHelp {
         request_id: u64,
@@ -113,20 +126,38 @@ pub enum AsyncMessage {
     /// File changed externally (future: file watching)
     FileChanged { path: String },
 
-    /// Git status updated (future: git integration)
-    GitStatusChanged { status: String },
+    /// Git branch and working tree status changed (`None` outside a
+    /// repository)
+    #[serde(skip)]
+    GitStatusChanged {
+        status: Option<crate::services::git_status::GitStatus>,
+    },
+
+    /// A branch checkout finished
+    GitCheckoutFinished {
+        branch: String,
+        result: Result<(), String>,
+    },
+
+    /// A commit finished, with git's summary line or everything git and
+    /// the hooks printed on failure
+    GitCommitFinished { result: Result<String, String> },
 
     /// File explorer initialized with tree view
+    #[serde(skip)]
     FileExplorerInitialized(FileTreeView),
 
     /// File explorer node toggle completed
+    #[serde(skip)]
     FileExplorerToggleNode(NodeId),
 
     /// File explorer node refresh completed
+    #[serde(skip)]
     FileExplorerRefreshNode(NodeId),
 
     /// File explorer expand to path completed
     /// Contains the updated FileTreeView with the path expanded and selected
+    #[serde(skip)]
     FileExplorerExpandedToPath(FileTreeView),
 
     /// Plugin process completed with output
@@ -190,17 +221,99 @@ pub enum AsyncMessage {
     },
 
     /// File open dialog: directory listing completed
+    #[serde(skip)]
     FileOpenDirectoryLoaded(std::io::Result<Vec<crate::services::fs::FsEntry>>),
 
+    /// Grammar package installation finished (package name or error)
+    GrammarInstalled { result: Result<String, String> },
+
+    /// Background indexing of the symbols of the project under `root` finished
+    #[serde(skip)]
+    SymbolIndexBuilt {
+        root: std::path::PathBuf,
+        symbols: Vec<crate::services::symbol_index::IndexedSymbol>,
+    },
+
+    /// A ctags run over `root` finished, writing the returned tags file
+    TagsGenerated {
+        root: std::path::PathBuf,
+        result: Result<std::path::PathBuf, String>,
+    },
+
+    /// Content read by a file system provider for a buffer being opened
+    #[serde(skip)]
+    VfsRead {
+        url: crate::services::vfs::VfsUrl,
+        result: Result<String, String>,
+    },
+
+    /// A file system provider finished writing a saved buffer
+    #[serde(skip)]
+    VfsWritten {
+        url: crate::services::vfs::VfsUrl,
+        result: Result<(), String>,
+    },
+
+    /// HTTP request made by a plugin finished
+    PluginFetchResult {
+        request_id: u64,
+        result: Result<crate::services::plugins::fetch::FetchResponse, String>,
+    },
+
     /// Terminal output received (triggers redraw)
+    #[serde(skip)]
     TerminalOutput { terminal_id: TerminalId },
 
     /// Terminal process exited
+    #[serde(skip)]
     TerminalExited { terminal_id: TerminalId },
+
+    /// Bytes of a remote file fetched over SSH so far
+    #[serde(skip)]
+    SshProgress {
+        url: crate::services::ssh::SshUrl,
+        received: u64,
+        total: Option<u64>,
+    },
+
+    /// Remote file fetched into its local copy (path) or failed
+    #[serde(skip)]
+    SshFetched {
+        url: crate::services::ssh::SshUrl,
+        result: Result<std::path::PathBuf, String>,
+    },
+
+    /// Saved remote file written back to its host
+    #[serde(skip)]
+    SshUploaded {
+        url: crate::services::ssh::SshUrl,
+        result: Result<(), String>,
+    },
+
+    /// A line of output from a running task
+    TaskOutput { task_id: u64, line: String },
+
+    /// A task's process exited (`None` if it was killed by a signal)
+    TaskFinished {
+        task_id: u64,
+        exit_code: Option<i32>,
+    },
+
+    /// A `fresh --remote open` client asked to open a file
+    #[cfg(unix)]
+    #[serde(skip)]
+    RemoteOpen {
+        request: crate::services::remote::OpenRequest,
+        connection: crate::services::remote::RemoteConnection,
+    },
+
+    /// A peer of the shared buffer connected, sent a message or left
+    #[serde(skip)]
+    Collab(crate::services::collab::CollabEvent),
 }
 
 /// LSP progress value types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LspProgressValue {
     Begin {
         title: String,
@@ -217,7 +330,7 @@ pub enum LspProgressValue {
 }
 
 /// LSP message type (corresponds to MessageType in LSP spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LspMessageType {
     Error = 1,
     Warning = 2,
@@ -226,7 +339,7 @@ pub enum LspMessageType {
 }
 
 /// LSP server status
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LspServerStatus {
     Starting,
     Initializing,
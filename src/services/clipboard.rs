@@ -6,12 +6,17 @@
 //! - Uses arboard crate for reading from system clipboard
 //! - Supports copying HTML-formatted text for rich text editors
 //! - Gracefully falls back to internal clipboard if system clipboard is unavailable
+//! - Keeps a history of recently copied text to pick from when pasting
 
 use crossterm::clipboard::CopyToClipboard;
 use crossterm::execute;
+use std::collections::VecDeque;
 use std::io::{stdout, Write};
 use std::sync::Mutex;
 
+/// Number of entries kept in the clipboard history
+pub const CLIPBOARD_HISTORY_LIMIT: usize = 20;
+
 /// Global clipboard holder to maintain X11 clipboard ownership for the application lifetime.
 /// On X11, the clipboard owner must stay alive to respond to paste requests from other apps.
 static SYSTEM_CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
@@ -23,6 +28,8 @@ pub struct Clipboard {
     internal: String,
     /// When true, paste() uses internal clipboard only (for testing)
     internal_only: bool,
+    /// Recently copied text, most recent first, without duplicates
+    history: VecDeque<String>,
 }
 
 impl Clipboard {
@@ -31,9 +38,26 @@ impl Clipboard {
         Self {
             internal: String::new(),
             internal_only: false,
+            history: VecDeque::new(),
         }
     }
 
+    /// Recently copied text, most recent first
+    pub fn history(&self) -> &VecDeque<String> {
+        &self.history
+    }
+
+    /// Put `text` at the front of the history, dropping an older copy of it
+    /// and the oldest entry once the history is full
+    fn remember(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.history.retain(|entry| entry != text);
+        self.history.push_front(text.to_string());
+        self.history.truncate(CLIPBOARD_HISTORY_LIMIT);
+    }
+
     /// Enable internal-only mode (for testing)
     /// When enabled, paste() uses internal clipboard only, ignoring system clipboard
     pub fn set_internal_only(&mut self, enabled: bool) {
@@ -47,6 +71,7 @@ impl Clipboard {
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
         self.internal = plain_text.to_string();
+        self.remember(plain_text);
 
         if let Ok(mut guard) = SYSTEM_CLIPBOARD.lock() {
             // Create clipboard if it doesn't exist yet
@@ -84,6 +109,7 @@ impl Clipboard {
     /// 2. arboard crate (works via X11/Wayland APIs in Gnome Console, XFCE Terminal, etc.)
     pub fn copy(&mut self, text: String) {
         self.internal = text.clone();
+        self.remember(&text);
 
         // Try OSC 52 first (works in modern terminals)
         // Note: This doesn't "fail" in a detectable way - it just sends escape sequences
@@ -152,6 +178,10 @@ impl Clipboard {
             if let Some(clipboard) = guard.as_mut() {
                 if let Ok(text) = clipboard.get_text() {
                     if !text.is_empty() {
+                        // Text copied in other applications joins the history too
+                        if text != self.internal {
+                            self.remember(&text);
+                        }
                         self.internal = text.clone();
                         return Some(text);
                     }
@@ -174,6 +204,7 @@ impl Clipboard {
 
     /// Set the internal clipboard content without updating system clipboard
     pub fn set_internal(&mut self, text: String) {
+        self.remember(&text);
         self.internal = text;
     }
 
@@ -231,4 +262,23 @@ mod tests {
         clipboard.copy("hello".to_string());
         assert_eq!(clipboard.get_internal(), "hello");
     }
+
+    #[test]
+    fn test_clipboard_history() {
+        let mut clipboard = Clipboard::new();
+        clipboard.set_internal("one".to_string());
+        clipboard.set_internal("two".to_string());
+        clipboard.set_internal("one".to_string());
+        clipboard.set_internal(String::new());
+        assert_eq!(clipboard.history(), &["one", "two"]);
+
+        for i in 0..CLIPBOARD_HISTORY_LIMIT {
+            clipboard.set_internal(i.to_string());
+        }
+        assert_eq!(clipboard.history().len(), CLIPBOARD_HISTORY_LIMIT);
+        assert_eq!(
+            clipboard.history()[0],
+            (CLIPBOARD_HISTORY_LIMIT - 1).to_string()
+        );
+    }
 }
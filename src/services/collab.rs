@@ -0,0 +1,475 @@
+//! Sharing a buffer with other editors over TCP.
+//!
+//! This module provides functionality to:
+//! - Host a shared buffer: listen on an address and accept any number of
+//!   guests (`Collab: Share Buffer`)
+//! - Join a buffer shared by another editor (`Collab: Join`)
+//! - Send and receive `CollabMessage`s, forwarded to the main loop as
+//!   `AsyncMessage::Collab`
+//!
+//! The protocol is line based: every message is one line of JSON. A guest
+//! first sends the session token the host showed when it started sharing;
+//! connections without it are closed. The host then gives each guest a site
+//! id and the document in a `Welcome`, and relays the operations and cursors
+//! of each guest to the others.
+//!
+//! Sending never blocks the editor: every peer has a writer thread with a
+//! bounded queue, and a peer that stops reading is disconnected once its
+//! queue is full.
+
+use crate::model::crdt::{CrdtOp, ElementId, ElementRun};
+use crate::services::async_bridge::AsyncMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Address offered when sharing a buffer
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// How long joining waits for the host to answer, and the host for a new
+/// connection's session token
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the host checks whether it should stop accepting guests
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Longest message line accepted; a longer one ends the connection
+const MAX_MESSAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Messages waiting to be written to one peer before it is disconnected
+const SEND_QUEUE_LEN: usize = 1024;
+
+/// How long a write to a peer may block before the peer is disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Site id of the host; guests get the following ones
+pub const HOST_SITE: u64 = 1;
+
+/// Connection to another editor: a guest for the host, the host for a guest
+pub type PeerId = u64;
+
+/// A message between the editors sharing a buffer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabMessage {
+    /// From the host to a new guest: the guest's site, the buffer's name
+    /// and its document
+    Welcome {
+        site: u64,
+        name: String,
+        runs: Vec<ElementRun>,
+    },
+    /// Edits made at `site`
+    Ops { site: u64, ops: Vec<CrdtOp> },
+    /// The cursor of `site` is now after the element `after`
+    /// (`None`: at the start)
+    Cursor { site: u64, after: Option<ElementId> },
+    /// `site` left the session
+    Left { site: u64 },
+}
+
+/// First line a guest sends
+#[derive(Debug, Serialize, Deserialize)]
+struct Hello {
+    token: String,
+}
+
+/// Something that happened on a connection
+#[derive(Debug)]
+pub enum CollabEvent {
+    Connected(PeerId),
+    Message(PeerId, CollabMessage),
+    Disconnected(PeerId),
+}
+
+/// A connected peer
+struct Peer {
+    /// Lines for its writer thread
+    queue: mpsc::SyncSender<Arc<str>>,
+    /// Shut down to disconnect it
+    stream: TcpStream,
+}
+
+type Peers = Arc<Mutex<HashMap<PeerId, Peer>>>;
+
+/// A random token guests need to join a hosted session
+pub fn new_session_token() -> io::Result<String> {
+    let mut bytes = [0u8; 8];
+    getrandom::fill(&mut bytes).map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// The connections of a hosted or joined session. Dropping it closes them.
+pub struct CollabConnection {
+    peers: Peers,
+    stop: Arc<AtomicBool>,
+    local_addr: Option<SocketAddr>,
+}
+
+impl CollabConnection {
+    /// Listen on `address` and accept guests that send `token` on a
+    /// background thread
+    pub fn host(
+        address: &str,
+        token: &str,
+        sender: mpsc::Sender<AsyncMessage>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        // Non-blocking, so the thread notices when the session ends
+        listener.set_nonblocking(true)?;
+        let connection = Self {
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            stop: Arc::new(AtomicBool::new(false)),
+            local_addr: listener.local_addr().ok(),
+        };
+
+        let peers = Arc::clone(&connection.peers);
+        let stop = Arc::clone(&connection.stop);
+        let token: Arc<str> = token.into();
+        thread::spawn(move || {
+            let mut next_peer: PeerId = 0;
+            while !stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, addr)) => {
+                        next_peer += 1;
+                        let peer = next_peer;
+                        let (token, peers, stop, sender) = (
+                            Arc::clone(&token),
+                            Arc::clone(&peers),
+                            Arc::clone(&stop),
+                            sender.clone(),
+                        );
+                        // Waiting for the token must not hold up other guests
+                        thread::spawn(move || {
+                            if let Err(e) =
+                                accept_guest(peer, stream, &token, &peers, &stop, &sender)
+                            {
+                                tracing::warn!("Collab: refused connection from {}: {}", addr, e);
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(ACCEPT_POLL_INTERVAL);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Collab: accept failed: {}", e);
+                        thread::sleep(ACCEPT_POLL_INTERVAL);
+                    }
+                }
+            }
+        });
+        Ok(connection)
+    }
+
+    /// Connect to the host at `address` with the session `token`. The host
+    /// is peer 0.
+    pub fn join(
+        address: &str,
+        token: &str,
+        sender: mpsc::Sender<AsyncMessage>,
+    ) -> io::Result<Self> {
+        let addr = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no address to connect to")
+        })?;
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut hello = serde_json::to_string(&Hello {
+            token: token.to_string(),
+        })?;
+        hello.push('\n');
+        stream.write_all(hello.as_bytes())?;
+
+        let connection = Self {
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            stop: Arc::new(AtomicBool::new(false)),
+            local_addr: stream.local_addr().ok(),
+        };
+        let reader = BufReader::new(stream.try_clone()?);
+        add_peer(
+            0,
+            stream,
+            reader,
+            &connection.peers,
+            &connection.stop,
+            &sender,
+        )?;
+        Ok(connection)
+    }
+
+    /// Address the host listens on (useful when binding port 0)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Number of connected peers
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().map(|peers| peers.len()).unwrap_or(0)
+    }
+
+    /// Send `message` to `peer`
+    pub fn send(&self, peer: PeerId, message: &CollabMessage) {
+        self.send_where(message, |id| id == peer);
+    }
+
+    /// Send `message` to every peer except `except`
+    pub fn broadcast(&self, message: &CollabMessage, except: Option<PeerId>) {
+        self.send_where(message, |id| Some(id) != except);
+    }
+
+    fn send_where(&self, message: &CollabMessage, to: impl Fn(PeerId) -> bool) {
+        let Ok(mut line) = serde_json::to_string(message) else {
+            return;
+        };
+        line.push('\n');
+        let line: Arc<str> = line.into();
+        let Ok(peers) = self.peers.lock() else {
+            return;
+        };
+        // A peer that is too far behind or gone is disconnected; its reader
+        // reports it
+        for (&id, peer) in peers.iter() {
+            if to(id) {
+                if let Err(e) = peer.queue.try_send(Arc::clone(&line)) {
+                    tracing::warn!("Collab: disconnecting peer {}: {}", id, e);
+                    let _ = peer.stream.shutdown(Shutdown::Both);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for CollabConnection {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Ok(mut peers) = self.peers.lock() {
+            for peer in peers.values() {
+                let _ = peer.stream.shutdown(Shutdown::Both);
+            }
+            peers.clear();
+        }
+    }
+}
+
+/// Check a new connection's session token, then add it as a guest
+fn accept_guest(
+    peer: PeerId,
+    stream: TcpStream,
+    token: &str,
+    peers: &Peers,
+    stop: &Arc<AtomicBool>,
+    sender: &mpsc::Sender<AsyncMessage>,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let hello =
+        read_line(&mut reader)?.ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+    let hello: Hello = serde_json::from_str(&hello)?;
+    if hello.token != token {
+        let _ = stream.shutdown(Shutdown::Both);
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "wrong session token",
+        ));
+    }
+    stream.set_read_timeout(None)?;
+    add_peer(peer, stream, reader, peers, stop, sender)
+}
+
+/// Read one message line, without its newline. `None` at the end of the
+/// stream; an error if the line is longer than [`MAX_MESSAGE_BYTES`].
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_MESSAGE_BYTES + 1)
+        .read_until(b'\n', &mut line)?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    } else if line.len() as u64 > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message too long",
+        ));
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Register a connected peer, writing to it and reading its messages on
+/// background threads
+fn add_peer(
+    peer: PeerId,
+    stream: TcpStream,
+    mut reader: BufReader<TcpStream>,
+    peers: &Peers,
+    stop: &Arc<AtomicBool>,
+    sender: &mpsc::Sender<AsyncMessage>,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let (queue, lines) = mpsc::sync_channel::<Arc<str>>(SEND_QUEUE_LEN);
+    if let Ok(mut peers) = peers.lock() {
+        peers.insert(peer, Peer { queue, stream });
+    }
+    let _ = sender.send(AsyncMessage::Collab(CollabEvent::Connected(peer)));
+
+    // Ends when the peer is removed (dropping the queue) or a write fails
+    thread::spawn(move || {
+        for line in lines {
+            if let Err(e) = writer.write_all(line.as_bytes()) {
+                tracing::debug!("Collab: write to peer {} failed: {}", peer, e);
+                let _ = writer.shutdown(Shutdown::Both);
+                break;
+            }
+        }
+    });
+
+    let peers = Arc::clone(peers);
+    let stop = Arc::clone(stop);
+    let sender = sender.clone();
+    thread::spawn(move || {
+        loop {
+            let line = match read_line(&mut reader) {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Collab: dropping peer {}: {}", peer, e);
+                    let _ = reader.get_ref().shutdown(Shutdown::Both);
+                    break;
+                }
+            };
+            match serde_json::from_str::<CollabMessage>(&line) {
+                Ok(message) => {
+                    let event = CollabEvent::Message(peer, message);
+                    if sender.send(AsyncMessage::Collab(event)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => tracing::warn!("Collab: invalid message from peer {}: {}", peer, e),
+            }
+        }
+        if let Ok(mut peers) = peers.lock() {
+            peers.remove(&peer);
+        }
+        if !stop.load(Ordering::SeqCst) {
+            let _ = sender.send(AsyncMessage::Collab(CollabEvent::Disconnected(peer)));
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recv(receiver: &mpsc::Receiver<AsyncMessage>) -> CollabEvent {
+        match receiver.recv_timeout(Duration::from_secs(5)).unwrap() {
+            AsyncMessage::Collab(event) => event,
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_host_and_join_exchange_messages() {
+        let (host_sender, host_receiver) = mpsc::channel();
+        let host = CollabConnection::host("127.0.0.1:0", "secret", host_sender).unwrap();
+        let address = host.local_addr().unwrap().to_string();
+
+        let (guest_sender, guest_receiver) = mpsc::channel();
+        let guest = CollabConnection::join(&address, "secret", guest_sender).unwrap();
+        assert!(matches!(recv(&guest_receiver), CollabEvent::Connected(0)));
+        let CollabEvent::Connected(peer) = recv(&host_receiver) else {
+            panic!("guest did not connect");
+        };
+
+        let message = CollabMessage::Cursor {
+            site: 2,
+            after: None,
+        };
+        guest.send(0, &message);
+        match recv(&host_receiver) {
+            CollabEvent::Message(from, received) => {
+                assert_eq!(from, peer);
+                assert_eq!(received, message);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        drop(guest);
+        assert!(matches!(
+            recv(&host_receiver),
+            CollabEvent::Disconnected(from) if from == peer
+        ));
+        assert_eq!(host.peer_count(), 0);
+    }
+
+    #[test]
+    fn test_host_refuses_wrong_token() {
+        let (host_sender, host_receiver) = mpsc::channel();
+        let host = CollabConnection::host("127.0.0.1:0", "secret", host_sender).unwrap();
+        let address = host.local_addr().unwrap().to_string();
+
+        let (guest_sender, guest_receiver) = mpsc::channel();
+        let _guest = CollabConnection::join(&address, "guess", guest_sender).unwrap();
+        assert!(matches!(recv(&guest_receiver), CollabEvent::Connected(0)));
+        // The host closes the connection without telling the editor
+        assert!(matches!(
+            recv(&guest_receiver),
+            CollabEvent::Disconnected(0)
+        ));
+        assert!(host_receiver
+            .recv_timeout(Duration::from_millis(200))
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_line_limits_length() {
+        let mut reader = io::Cursor::new(b"{}\nlast".to_vec());
+        assert_eq!(read_line(&mut reader).unwrap().as_deref(), Some("{}"));
+        assert_eq!(read_line(&mut reader).unwrap().as_deref(), Some("last"));
+        assert_eq!(read_line(&mut reader).unwrap(), None);
+
+        let long = vec![b'x'; MAX_MESSAGE_BYTES as usize + 1];
+        let err = read_line(&mut io::Cursor::new(long)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_peer_that_stops_reading_is_disconnected() {
+        let (host_sender, host_receiver) = mpsc::channel();
+        let host = CollabConnection::host("127.0.0.1:0", "secret", host_sender).unwrap();
+        let address = host.local_addr().unwrap();
+
+        // A guest that sends its token and then never reads
+        let mut stalled = TcpStream::connect(address).unwrap();
+        stalled.write_all(b"{\"token\":\"secret\"}\n").unwrap();
+        let CollabEvent::Connected(peer) = recv(&host_receiver) else {
+            panic!("guest did not connect");
+        };
+
+        let message = CollabMessage::Welcome {
+            site: 2,
+            name: "x".repeat(64 * 1024),
+            runs: Vec::new(),
+        };
+        let started = std::time::Instant::now();
+        while host.peer_count() > 0 && started.elapsed() < Duration::from_secs(5) {
+            host.send(peer, &message);
+        }
+        assert!(matches!(
+            recv(&host_receiver),
+            CollabEvent::Disconnected(from) if from == peer
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+//! Crash reports written when the editor panics.
+//!
+//! The panic hook can't reach the editor, which is borrowed by the code that
+//! panicked, so the editor keeps what a report needs here as it runs: the
+//! names of the last actions it handled and the files it has open. On a
+//! panic of the main thread, [`write_report`] saves them with the panic
+//! message and a backtrace in the crash report directory and marks the
+//! report pending; the next start takes it with [`take_pending_report`] and
+//! offers to open it.
+//!
+//! Only action names are kept (`InsertChar`, not the character), so a report
+//! can be shared without leaking what was typed.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Actions kept for the report
+const RECENT_ACTIONS: usize = 50;
+
+/// File in the report directory naming the report not offered yet
+const PENDING_FILE: &str = "pending";
+
+/// What the editor last did, for the report
+struct CrashContext {
+    report_dir: Option<PathBuf>,
+    recent_actions: VecDeque<String>,
+    open_files: Vec<PathBuf>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    report_dir: None,
+    recent_actions: VecDeque::new(),
+    open_files: Vec::new(),
+});
+
+/// Directory crash reports go to under the state directory
+pub fn report_dir(state_dir: &Path) -> PathBuf {
+    state_dir.join("crash-reports")
+}
+
+/// Write reports to `dir` from now on
+pub fn set_report_dir(dir: PathBuf) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.report_dir = Some(dir);
+    }
+}
+
+/// Record that the editor handled `action`, given by its `Debug` form
+pub fn record_action(action: &impl std::fmt::Debug) {
+    let debug = format!("{:?}", action);
+    let name = debug.split(['(', ' ', '{']).next().unwrap_or_default();
+    if let Ok(mut context) = CONTEXT.lock() {
+        if context.recent_actions.len() >= RECENT_ACTIONS {
+            context.recent_actions.pop_front();
+        }
+        context.recent_actions.push_back(name.to_string());
+    }
+}
+
+/// Record the files the editor has open
+pub fn set_open_files(files: Vec<PathBuf>) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.open_files = files;
+    }
+}
+
+/// The text of a report on `panic`
+fn format_report(panic: &PanicHookInfo<'_>, context: &CrashContext, now_secs: u64) -> String {
+    let message = panic
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".to_string());
+    let location = panic
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    let mut report = String::new();
+    let _ = writeln!(report, "Fresh {} crashed", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {} (seconds since 1970)", now_secs);
+    let _ = writeln!(
+        report,
+        "Platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(report, "\nPanic: {}\nAt: {}", message, location);
+
+    let _ = writeln!(report, "\nOpen files:");
+    for file in &context.open_files {
+        let _ = writeln!(report, "  {}", file.display());
+    }
+    let _ = writeln!(report, "\nLast actions, oldest first:");
+    for action in &context.recent_actions {
+        let _ = writeln!(report, "  {}", action);
+    }
+    let _ = writeln!(
+        report,
+        "\nBacktrace:\n{}",
+        std::backtrace::Backtrace::force_capture()
+    );
+    report
+}
+
+/// Write a report on `panic` and mark it pending; returns its path. Only
+/// panics of the main thread crash the editor: other threads (plugins,
+/// language servers) are restarted, so nothing is written for them.
+pub fn write_report(panic: &PanicHookInfo<'_>) -> Option<PathBuf> {
+    if std::thread::current().name() != Some("main") {
+        return None;
+    }
+    // The panic may have happened while the context was locked
+    let context = CONTEXT.try_lock().ok()?;
+    let dir = context.report_dir.clone()?;
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let report = format_report(panic, &context, now_secs);
+
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("crash-{}.txt", now_secs));
+    std::fs::write(&path, report).ok()?;
+    std::fs::write(dir.join(PENDING_FILE), path.to_string_lossy().as_bytes()).ok()?;
+    Some(path)
+}
+
+/// The report of a crash of the previous run, if it wasn't offered yet;
+/// it won't be returned again
+pub fn take_pending_report(dir: &Path) -> Option<PathBuf> {
+    let pending = dir.join(PENDING_FILE);
+    let path = std::fs::read_to_string(&pending).ok()?;
+    let _ = std::fs::remove_file(&pending);
+    let path = PathBuf::from(path.trim());
+    path.is_file().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_report_is_taken_once() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(take_pending_report(dir.path()), None);
+
+        let report = dir.path().join("crash-1.txt");
+        std::fs::write(&report, "Fresh crashed").unwrap();
+        std::fs::write(
+            dir.path().join(PENDING_FILE),
+            report.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(take_pending_report(dir.path()), Some(report));
+        assert_eq!(take_pending_report(dir.path()), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Links to files on a repository's web host.
+//!
+//! This module provides functionality to:
+//! - Turn the `origin` remote URL (https, ssh or `git@host:path`) into the
+//!   web URL of the repository on GitHub, GitLab or a host like them
+//! - Build the URL of a file at a commit, with the line range
+//! - Open URLs in the system's browser
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// How a host lays out file URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteKind {
+    /// `/blob/<commit>/<path>#L1-L2`, also used by Gitea and other hosts
+    GitHub,
+    /// `/-/blob/<commit>/<path>#L1-2`
+    GitLab,
+}
+
+/// A repository on a web host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    pub kind: RemoteKind,
+    /// Web URL of the repository, e.g. `https://github.com/owner/repo`
+    pub base: String,
+}
+
+impl RemoteRepo {
+    /// URL of `path` (relative to the repository root, with `/` separators)
+    /// at `commit`, pointing at the 1-indexed, inclusive `lines`
+    pub fn file_url(&self, commit: &str, path: &str, lines: (usize, usize)) -> String {
+        let path = encode_path(path);
+        let (first, last) = lines;
+        match self.kind {
+            RemoteKind::GitHub if first == last => {
+                format!("{}/blob/{}/{}#L{}", self.base, commit, path, first)
+            }
+            RemoteKind::GitHub => {
+                format!(
+                    "{}/blob/{}/{}#L{}-L{}",
+                    self.base, commit, path, first, last
+                )
+            }
+            RemoteKind::GitLab if first == last => {
+                format!("{}/-/blob/{}/{}#L{}", self.base, commit, path, first)
+            }
+            RemoteKind::GitLab => {
+                format!(
+                    "{}/-/blob/{}/{}#L{}-{}",
+                    self.base, commit, path, first, last
+                )
+            }
+        }
+    }
+}
+
+/// Parse a remote URL such as `git@github.com:owner/repo.git`,
+/// `ssh://git@gitlab.com:2222/group/repo.git` or
+/// `https://user@github.com/owner/repo`
+pub fn parse_remote_url(url: &str) -> Option<RemoteRepo> {
+    let url = url.trim();
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        // Drop the port: ssh ports don't apply to the web host
+        let host = host.split(':').next()?;
+        (host, path)
+    } else {
+        // scp-like syntax: [user@]host:path
+        let (authority, path) = url.split_once(':')?;
+        let host = authority.rsplit('@').next()?;
+        // "C:\repo" is a Windows path, not a host
+        if host.len() == 1 {
+            return None;
+        }
+        (host, path)
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    let kind = if host.contains("gitlab") {
+        RemoteKind::GitLab
+    } else {
+        RemoteKind::GitHub
+    };
+    Some(RemoteRepo {
+        kind,
+        base: format!("https://{}/{}", host, path),
+    })
+}
+
+/// Percent-encode a path for a URL, keeping the `/` separators
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// URL of `file` at the checked out commit on the `origin` remote's web
+/// host, pointing at the 1-indexed, inclusive `lines`
+pub fn file_permalink(file: &Path, lines: (usize, usize)) -> Result<String, String> {
+    let dir = file.parent().ok_or("File has no directory")?;
+    let name = file
+        .file_name()
+        .ok_or("File has no name")?
+        .to_string_lossy();
+
+    let remote = run_git(dir, &["remote", "get-url", "origin"])
+        .map_err(|_| "No 'origin' remote".to_string())?;
+    let repo = parse_remote_url(&remote)
+        .ok_or_else(|| format!("Unrecognized remote URL: {}", remote.trim()))?;
+    let commit = run_git(dir, &["rev-parse", "HEAD"])?;
+    // The directory relative to the repository root, e.g. "src/"
+    let prefix = run_git(dir, &["rev-parse", "--show-prefix"])?;
+
+    Ok(repo.file_url(
+        commit.trim(),
+        &format!("{}{}", prefix.trim_end_matches('\n'), name),
+        lines,
+    ))
+}
+
+/// Open `url` in the system's default browser
+pub fn open_in_browser(url: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open browser: {}", e))
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_url() {
+        let github = RemoteRepo {
+            kind: RemoteKind::GitHub,
+            base: "https://github.com/owner/repo".to_string(),
+        };
+        assert_eq!(
+            parse_remote_url("git@github.com:owner/repo.git"),
+            Some(github.clone())
+        );
+        assert_eq!(
+            parse_remote_url("https://github.com/owner/repo.git\n"),
+            Some(github.clone())
+        );
+        assert_eq!(
+            parse_remote_url("https://user@github.com/owner/repo/"),
+            Some(github)
+        );
+        assert_eq!(
+            parse_remote_url("ssh://git@gitlab.example.com:2222/group/sub/repo.git"),
+            Some(RemoteRepo {
+                kind: RemoteKind::GitLab,
+                base: "https://gitlab.example.com/group/sub/repo".to_string(),
+            })
+        );
+        assert_eq!(parse_remote_url("/srv/git/repo.git"), None);
+        assert_eq!(parse_remote_url("C:\\git\\repo"), None);
+        assert_eq!(parse_remote_url(""), None);
+    }
+
+    #[test]
+    fn test_file_url() {
+        let github = parse_remote_url("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(
+            github.file_url("abc123", "src/main.rs", (3, 3)),
+            "https://github.com/owner/repo/blob/abc123/src/main.rs#L3"
+        );
+        assert_eq!(
+            github.file_url("abc123", "docs/read me.md", (3, 5)),
+            "https://github.com/owner/repo/blob/abc123/docs/read%20me.md#L3-L5"
+        );
+
+        let gitlab = parse_remote_url("git@gitlab.com:group/repo.git").unwrap();
+        assert_eq!(
+            gitlab.file_url("abc123", "src/main.rs", (3, 5)),
+            "https://gitlab.com/group/repo/-/blob/abc123/src/main.rs#L3-5"
+        );
+    }
+}
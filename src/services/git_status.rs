@@ -0,0 +1,323 @@
+//! Git branch and working tree status.
+//!
+//! This module provides functionality to:
+//! - Read the current branch, its ahead/behind counts against the upstream
+//!   and whether the working tree has changes (`git status --porcelain=v2`)
+//! - Watch them from a background thread, reporting only changes
+//! - List and check out local branches
+//! - Show the staged changes and commit them, running the repository's hooks
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the watcher reads the status
+pub const GIT_STATUS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Branch and working tree state of a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Branch name, or the abbreviated commit when detached
+    pub branch: String,
+    /// Commits not yet pushed to the upstream
+    pub ahead: usize,
+    /// Upstream commits not yet pulled
+    pub behind: usize,
+    /// Whether there are uncommitted or untracked changes
+    pub dirty: bool,
+}
+
+impl GitStatus {
+    /// Short form for the status bar, e.g. `main ↑1 ↓2 *`
+    pub fn label(&self) -> String {
+        let mut label = self.branch.clone();
+        if self.ahead > 0 {
+            label.push_str(&format!(" ↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            label.push_str(&format!(" ↓{}", self.behind));
+        }
+        if self.dirty {
+            label.push_str(" *");
+        }
+        label
+    }
+}
+
+/// Parse the output of `git status --porcelain=v2 --branch`
+fn parse_status(output: &str) -> Option<GitStatus> {
+    let mut branch = None;
+    let mut oid = None;
+    let mut ahead = 0;
+    let mut behind = 0;
+    let mut dirty = false;
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            branch = Some(head.to_string());
+        } else if let Some(commit) = line.strip_prefix("# branch.oid ") {
+            oid = Some(commit.chars().take(7).collect::<String>());
+        } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+            for count in counts.split_whitespace() {
+                if let Some(n) = count.strip_prefix('+') {
+                    ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = count.strip_prefix('-') {
+                    behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.is_empty() {
+            dirty = true;
+        }
+    }
+    let branch = match branch? {
+        detached if detached == "(detached)" => format!("({})", oid.unwrap_or(detached)),
+        branch => branch,
+    };
+    Some(GitStatus {
+        branch,
+        ahead,
+        behind,
+        dirty,
+    })
+}
+
+/// The status of the repository containing `dir`, or `None` outside a
+/// repository
+pub fn read_status(dir: &Path) -> Option<GitStatus> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v2", "--branch"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_status(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Local branch names, sorted
+pub fn local_branches(dir: &Path) -> Result<Vec<String>, String> {
+    let output = run_git(
+        dir,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+    )?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Check out `branch`
+pub fn checkout(dir: &Path, branch: &str) -> Result<(), String> {
+    run_git(dir, &["checkout", "--quiet", branch, "--"]).map(|_| ())
+}
+
+/// The staged changes: a summary of the files followed by the diff
+pub fn staged_changes(dir: &Path) -> Result<String, String> {
+    let stat = run_git(dir, &["diff", "--cached", "--stat"])?;
+    let diff = run_git(dir, &["diff", "--cached"])?;
+    if diff.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(format!("{}\n{}", stat, diff))
+}
+
+/// The full message of the last commit
+pub fn last_commit_message(dir: &Path) -> Result<String, String> {
+    run_git(dir, &["log", "-1", "--format=%B"])
+}
+
+/// Commit the staged changes with `message`, or replace the last commit
+/// with `amend`. Comment lines and surrounding blank lines are stripped.
+///
+/// Returns git's summary line (e.g. `[main 1234567] Fix typo`). On failure,
+/// returns everything git and the hooks printed, since a rejecting
+/// `pre-commit` or `commit-msg` hook explains itself there.
+pub fn commit(dir: &Path, message: &str, amend: bool) -> Result<String, String> {
+    let mut args = vec!["commit", "--cleanup=strip", "--file=-"];
+    if amend {
+        args.push("--amend");
+    }
+    let mut child = Command::new("git")
+        .args(&args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(message.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut log = stderr.into_owned();
+        log.push_str(&stdout);
+        return Err(log);
+    }
+    Ok(stdout
+        .lines()
+        .find(|line| line.starts_with('['))
+        .unwrap_or("Committed")
+        .to_string())
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        // The first line says what went wrong; the rest lists files and advice
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Handle to a background thread watching the status of a repository. The
+/// thread stops when the handle is dropped.
+pub struct GitStatusWatcher {
+    stop_signal: Arc<AtomicBool>,
+    refresh_signal: Arc<AtomicBool>,
+}
+
+impl GitStatusWatcher {
+    /// Read the status now instead of at the next interval
+    pub fn refresh(&self) {
+        self.refresh_signal.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for GitStatusWatcher {
+    fn drop(&mut self) {
+        self.stop_signal.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Read the status of the repository containing `dir` every `interval`,
+/// calling `on_change` with it the first time and whenever it changes
+pub fn watch(
+    dir: PathBuf,
+    interval: Duration,
+    on_change: impl Fn(Option<GitStatus>) + Send + 'static,
+) -> GitStatusWatcher {
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let refresh_signal = Arc::new(AtomicBool::new(false));
+    let stop = stop_signal.clone();
+    let refresh = refresh_signal.clone();
+
+    thread::spawn(move || {
+        let mut last = None;
+        while !stop.load(Ordering::SeqCst) {
+            let status = read_status(&dir);
+            if last.as_ref() != Some(&status) {
+                on_change(status.clone());
+                last = Some(status);
+            }
+
+            // Sleep in small increments to allow quick shutdown and refresh
+            let sleep_end = Instant::now() + interval;
+            while Instant::now() < sleep_end
+                && !stop.load(Ordering::SeqCst)
+                && !refresh.swap(false, Ordering::SeqCst)
+            {
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+    });
+
+    GitStatusWatcher {
+        stop_signal,
+        refresh_signal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let status = parse_status(
+            "# branch.oid 1234567890abcdef\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +2 -1\n1 .M N... 100644 100644 100644 a b src/main.rs\n",
+        )
+        .unwrap();
+        assert_eq!(
+            status,
+            GitStatus {
+                branch: "main".to_string(),
+                ahead: 2,
+                behind: 1,
+                dirty: true,
+            }
+        );
+        assert_eq!(status.label(), "main ↑2 ↓1 *");
+
+        let detached =
+            parse_status("# branch.oid 1234567890abcdef\n# branch.head (detached)\n").unwrap();
+        assert_eq!(detached.label(), "(1234567)");
+
+        assert!(parse_status("").is_none());
+    }
+
+    #[test]
+    fn test_read_status_and_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| run_git(dir.path(), args).unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "first"]);
+        git(&["branch", "feature"]);
+
+        assert_eq!(read_status(dir.path()).unwrap().label(), "main");
+        std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        assert_eq!(read_status(dir.path()).unwrap().label(), "main *");
+
+        assert_eq!(local_branches(dir.path()).unwrap(), ["feature", "main"]);
+        checkout(dir.path(), "feature").unwrap();
+        assert_eq!(read_status(dir.path()).unwrap().branch, "feature");
+        assert!(checkout(dir.path(), "missing").is_err());
+
+        let outside = tempfile::tempdir().unwrap();
+        assert!(read_status(outside.path()).is_none());
+    }
+
+    #[test]
+    fn test_commit_and_amend() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| run_git(dir.path(), args).unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+
+        assert_eq!(staged_changes(dir.path()).unwrap(), "");
+        git(&["add", "."]);
+        let staged = staged_changes(dir.path()).unwrap();
+        assert!(staged.contains("a.txt | 1 +"));
+        assert!(staged.contains("+a"));
+
+        let summary = commit(dir.path(), "First\n\n# comment\n", false).unwrap();
+        assert!(summary.starts_with("[main (root-commit) "));
+        assert!(summary.ends_with("] First"));
+        assert_eq!(last_commit_message(dir.path()).unwrap(), "First\n\n");
+
+        commit(dir.path(), "Second", true).unwrap();
+        assert_eq!(last_commit_message(dir.path()).unwrap(), "Second\n\n");
+        assert_eq!(git(&["rev-list", "--count", "HEAD"]).trim(), "1");
+
+        // Nothing staged: git explains on stdout
+        let error = commit(dir.path(), "Third", false).unwrap_err();
+        assert!(error.contains("nothing to commit"));
+    }
+}
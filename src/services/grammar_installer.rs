@@ -0,0 +1,575 @@
+//! Grammar installer for VSCode-format grammar extensions.
+//!
+//! This module provides functionality to:
+//! - Resolve an install source (a .vsix URL, a GitHub repository, or a
+//!   marketplace extension ID) to a download URL
+//! - Download the extension package (a zip archive)
+//! - Unpack the package.json and the grammar files it contributes into the
+//!   user grammars directory, where `GrammarRegistry` picks them up
+
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::primitives::grammar_registry::PackageManifest;
+
+/// Maximum size of a downloaded extension package, and of the package or
+/// any file in it once decompressed
+const MAX_DOWNLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Where to install a grammar extension from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarSource {
+    /// Direct URL to a .vsix (or any zip) package
+    Url(String),
+    /// GitHub repository containing a VSCode extension
+    GitHub { owner: String, repo: String },
+    /// VSCode marketplace extension ID (`publisher.name`)
+    Marketplace { publisher: String, name: String },
+}
+
+impl GrammarSource {
+    /// Parse user input into a grammar source.
+    ///
+    /// Accepts `https://…` URLs (GitHub repository URLs are recognized),
+    /// `owner/repo` for GitHub, and `publisher.name` for the marketplace.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+
+        if let Some(rest) = input
+            .strip_prefix("https://github.com/")
+            .or_else(|| input.strip_prefix("http://github.com/"))
+        {
+            let parts: Vec<&str> = rest.trim_end_matches('/').split('/').collect();
+            if parts.len() == 2 && !parts[1].ends_with(".vsix") && !parts[1].ends_with(".zip") {
+                return Self::github(parts[0], parts[1]);
+            }
+        }
+
+        if input.starts_with("https://") || input.starts_with("http://") {
+            return Ok(Self::Url(input.to_string()));
+        }
+
+        if let Some((owner, repo)) = input.split_once('/') {
+            return Self::github(owner, repo);
+        }
+
+        if let Some((publisher, name)) = input.split_once('.') {
+            if is_identifier(publisher) && is_identifier(name) {
+                return Ok(Self::Marketplace {
+                    publisher: publisher.to_string(),
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        Err(format!(
+            "Expected a .vsix URL, a GitHub repository (owner/repo), or a marketplace ID (publisher.name), got '{}'",
+            input
+        ))
+    }
+
+    fn github(owner: &str, repo: &str) -> Result<Self, String> {
+        let repo = repo.trim_end_matches(".git");
+        if !is_identifier(owner) || !is_identifier(repo) {
+            return Err(format!("Invalid GitHub repository: {}/{}", owner, repo));
+        }
+        Ok(Self::GitHub {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// URL of the zip archive for this source
+    pub fn download_url(&self) -> String {
+        match self {
+            Self::Url(url) => url.clone(),
+            Self::GitHub { owner, repo } => {
+                format!("https://github.com/{}/{}/archive/HEAD.zip", owner, repo)
+            }
+            Self::Marketplace { publisher, name } => format!(
+                "https://marketplace.visualstudio.com/_apis/public/gallery/publishers/{}/vsextensions/{}/latest/vspackage",
+                publisher, name
+            ),
+        }
+    }
+
+    /// Directory name to use when the package.json doesn't declare a name
+    fn fallback_name(&self) -> String {
+        match self {
+            Self::Url(url) => {
+                let file = url.rsplit('/').next().unwrap_or(url);
+                let file = file.split(['?', '#']).next().unwrap_or(file);
+                file.trim_end_matches(".vsix")
+                    .trim_end_matches(".zip")
+                    .to_string()
+            }
+            Self::GitHub { repo, .. } => repo.clone(),
+            Self::Marketplace { publisher, name } => format!("{}.{}", publisher, name),
+        }
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// Download and install a grammar extension into `grammars_dir`.
+/// Returns the name of the installed package directory.
+pub fn install(source: &GrammarSource, grammars_dir: &Path) -> Result<String, String> {
+    let archive = download(&source.download_url())?;
+    install_from_archive(&archive, &source.fallback_name(), grammars_dir)
+}
+
+/// Fetch a URL into memory
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    tracing::info!("Downloading grammar package from {}", url);
+    let response = ureq::get(url)
+        .set("User-Agent", "fresh-editor-grammar-installer")
+        .timeout(Duration::from_secs(60))
+        .call()
+        .map_err(|e| format!("Download failed: {}", e))?;
+
+    read_limited(response.into_reader(), MAX_DOWNLOAD_BYTES)
+        .map_err(|e| format!("Failed to read download: {}", e))
+}
+
+/// Read all of `reader`, failing instead of stopping short when it holds
+/// more than `limit` bytes
+fn read_limited(reader: impl Read, limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("larger than {} bytes", limit),
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Unpack the grammar contribution of an extension package into `grammars_dir`.
+///
+/// Only the package.json and the grammar files it lists are extracted, so
+/// the rest of the extension (code, images, tests) never touches the disk.
+/// An existing installation with the same name is replaced.
+pub fn install_from_archive(
+    archive: &[u8],
+    fallback_name: &str,
+    grammars_dir: &Path,
+) -> Result<String, String> {
+    // The marketplace serves packages gzip-encoded
+    let archive = if archive.starts_with(&[0x1f, 0x8b]) {
+        read_limited(flate2::read::GzDecoder::new(archive), MAX_DOWNLOAD_BYTES)
+            .map_err(|e| format!("Failed to decompress package: {}", e))?
+    } else {
+        archive.to_vec()
+    };
+    let zip = ZipArchive::parse(&archive)?;
+
+    // Prefer the manifest closest to the archive root
+    let mut manifests: Vec<&str> = zip
+        .names()
+        .filter(|name| *name == "package.json" || name.ends_with("/package.json"))
+        .collect();
+    manifests.sort_by_key(|name| name.matches('/').count());
+
+    for manifest_path in manifests {
+        let manifest_bytes = zip.read(manifest_path)?;
+        let Ok(manifest) = serde_json::from_slice::<PackageManifest>(&manifest_bytes) else {
+            continue;
+        };
+        let grammars = manifest.contributes.map(|c| c.grammars).unwrap_or_default();
+        if grammars.is_empty() {
+            continue;
+        }
+
+        let root = &manifest_path[..manifest_path.len() - "package.json".len()];
+        let mut files = vec![("package.json".to_string(), manifest_bytes)];
+        for grammar in &grammars {
+            let relative = safe_relative_path(&grammar.path)?;
+            let data = zip.read(&format!("{}{}", root, relative))?;
+            files.push((relative, data));
+        }
+
+        let name = sanitize_name(manifest.name.as_deref().unwrap_or(fallback_name));
+        if name.is_empty() {
+            return Err("Could not determine a name for the grammar package".to_string());
+        }
+        let target = grammars_dir.join(&name);
+        if target.exists() {
+            std::fs::remove_dir_all(&target)
+                .map_err(|e| format!("Failed to remove old {:?}: {}", target, e))?;
+        }
+        for (relative, data) in files {
+            let path = target.join(&relative);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+            }
+            std::fs::write(&path, data)
+                .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        }
+
+        tracing::info!(
+            "Installed {} grammar(s) from package '{}' into {:?}",
+            grammars.len(),
+            name,
+            target
+        );
+        return Ok(name);
+    }
+
+    Err("Package does not contribute any grammars".to_string())
+}
+
+/// Normalize a manifest-relative path, rejecting paths that escape the package
+fn safe_relative_path(path: &str) -> Result<String, String> {
+    let mut parts = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => return Err(format!("Grammar path escapes the package: {}", path)),
+            _ if part.contains(':') => {
+                return Err(format!("Grammar path escapes the package: {}", path))
+            }
+            _ => parts.push(part),
+        }
+    }
+    if parts.is_empty() || path.starts_with('/') {
+        return Err(format!("Invalid grammar path: {}", path));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Make a package name safe to use as a directory name
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+/// A zip entry located via the central directory
+struct ZipEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    local_header_offset: usize,
+}
+
+/// Minimal read-only zip reader (stored and deflated entries, no zip64)
+pub(crate) struct ZipArchive<'a> {
+    data: &'a [u8],
+    entries: Vec<ZipEntry>,
+}
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+
+impl<'a> ZipArchive<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Result<Self, String> {
+        let invalid = || "Package is not a valid zip archive".to_string();
+
+        // The end-of-central-directory record sits at the end, before an optional comment
+        let eocd = (0..=data.len().saturating_sub(22))
+            .rev()
+            .take(u16::MAX as usize + 1)
+            .find(|&pos| read_u32(data, pos) == Some(EOCD_SIGNATURE))
+            .ok_or_else(invalid)?;
+        let count = read_u16(data, eocd + 10).ok_or_else(invalid)? as usize;
+        let mut pos = read_u32(data, eocd + 16).ok_or_else(invalid)? as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if read_u32(data, pos) != Some(CENTRAL_HEADER_SIGNATURE) {
+                return Err(invalid());
+            }
+            let method = read_u16(data, pos + 10).ok_or_else(invalid)?;
+            let compressed_size = read_u32(data, pos + 20).ok_or_else(invalid)? as usize;
+            let name_len = read_u16(data, pos + 28).ok_or_else(invalid)? as usize;
+            let extra_len = read_u16(data, pos + 30).ok_or_else(invalid)? as usize;
+            let comment_len = read_u16(data, pos + 32).ok_or_else(invalid)? as usize;
+            let local_header_offset = read_u32(data, pos + 42).ok_or_else(invalid)? as usize;
+            let name = data
+                .get(pos + 46..pos + 46 + name_len)
+                .ok_or_else(invalid)?;
+
+            entries.push(ZipEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                local_header_offset,
+            });
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+
+        Ok(Self { data, entries })
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Read and decompress an entry by name
+    pub(crate) fn read(&self, name: &str) -> Result<Vec<u8>, String> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| format!("Package is missing {}", name))?;
+        let corrupt = || format!("Corrupt zip entry: {}", name);
+
+        let pos = entry.local_header_offset;
+        if read_u32(self.data, pos) != Some(LOCAL_HEADER_SIGNATURE) {
+            return Err(corrupt());
+        }
+        let name_len = read_u16(self.data, pos + 26).ok_or_else(corrupt)? as usize;
+        let extra_len = read_u16(self.data, pos + 28).ok_or_else(corrupt)? as usize;
+        let start = pos + 30 + name_len + extra_len;
+        let compressed = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(corrupt)?;
+
+        match entry.method {
+            0 => Ok(compressed.to_vec()),
+            8 => read_limited(
+                flate2::read::DeflateDecoder::new(compressed),
+                MAX_DOWNLOAD_BYTES,
+            )
+            .map_err(|e| format!("Failed to unpack {}: {}", name, e)),
+            method => Err(format!(
+                "Unsupported compression method {} for {}",
+                method, name
+            )),
+        }
+    }
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    data.get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    data.get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Build a zip archive; entries are deflated when `deflate` is set
+    fn build_zip(files: &[(&str, &str)], deflate: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, content) in files {
+            let (method, data) = if deflate {
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content.as_bytes()).unwrap();
+                (8u16, encoder.finish().unwrap())
+            } else {
+                (0u16, content.as_bytes().to_vec())
+            };
+            let offset = out.len() as u32;
+
+            out.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+            out.extend_from_slice(&[20, 0, 0, 0]);
+            out.extend_from_slice(&method.to_le_bytes());
+            out.extend_from_slice(&[0; 8]); // time, date, crc
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&[0, 0]);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&data);
+
+            central.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            central.extend_from_slice(&method.to_le_bytes());
+            central.extend_from_slice(&[0; 8]);
+            central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central.extend_from_slice(&[0; 12]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let central_offset = out.len() as u32;
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        out.extend_from_slice(&central_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    const MANIFEST: &str = r#"{
+        "name": "vscode-foo",
+        "contributes": {
+            "languages": [{ "id": "foo", "extensions": [".foo"] }],
+            "grammars": [
+                { "language": "foo", "scopeName": "source.foo", "path": "./syntaxes/foo.tmLanguage.json" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_sources() {
+        assert_eq!(
+            GrammarSource::parse("https://example.com/foo-1.0.vsix").unwrap(),
+            GrammarSource::Url("https://example.com/foo-1.0.vsix".to_string())
+        );
+        let github = GrammarSource::GitHub {
+            owner: "someone".to_string(),
+            repo: "vscode-foo".to_string(),
+        };
+        assert_eq!(GrammarSource::parse("someone/vscode-foo").unwrap(), github);
+        assert_eq!(
+            GrammarSource::parse("https://github.com/someone/vscode-foo.git/").unwrap(),
+            github
+        );
+        assert_eq!(
+            GrammarSource::parse(" someone.foo ").unwrap(),
+            GrammarSource::Marketplace {
+                publisher: "someone".to_string(),
+                name: "foo".to_string(),
+            }
+        );
+        assert!(GrammarSource::parse("").is_err());
+        assert!(GrammarSource::parse("not a source").is_err());
+    }
+
+    #[test]
+    fn test_download_urls() {
+        let github = GrammarSource::parse("someone/vscode-foo").unwrap();
+        assert_eq!(
+            github.download_url(),
+            "https://github.com/someone/vscode-foo/archive/HEAD.zip"
+        );
+        assert_eq!(github.fallback_name(), "vscode-foo");
+
+        let url = GrammarSource::parse("https://example.com/dl/foo-1.0.vsix?x=1").unwrap();
+        assert_eq!(url.fallback_name(), "foo-1.0");
+    }
+
+    #[test]
+    fn test_install_vsix_extracts_only_grammar_contribution() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = build_zip(
+            &[
+                ("extension.vsixmanifest", "<xml/>"),
+                ("extension/package.json", MANIFEST),
+                (
+                    "extension/syntaxes/foo.tmLanguage.json",
+                    "{\"scopeName\":\"source.foo\"}",
+                ),
+                ("extension/out/extension.js", "console.log('hi')"),
+            ],
+            true,
+        );
+
+        let name = install_from_archive(&archive, "fallback", dir.path()).unwrap();
+        assert_eq!(name, "vscode-foo");
+
+        let installed = dir.path().join("vscode-foo");
+        assert!(installed.join("package.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(installed.join("syntaxes/foo.tmLanguage.json")).unwrap(),
+            "{\"scopeName\":\"source.foo\"}"
+        );
+        assert!(!installed.join("out").exists());
+    }
+
+    #[test]
+    fn test_install_github_archive_and_gzip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = build_zip(
+            &[
+                ("vscode-foo-HEAD/test/package.json", "{}"),
+                ("vscode-foo-HEAD/package.json", MANIFEST),
+                ("vscode-foo-HEAD/syntaxes/foo.tmLanguage.json", "{}"),
+            ],
+            false,
+        );
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&archive).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let name = install_from_archive(&gzipped, "fallback", dir.path()).unwrap();
+        assert_eq!(name, "vscode-foo");
+        assert!(dir
+            .path()
+            .join("vscode-foo/syntaxes/foo.tmLanguage.json")
+            .exists());
+    }
+
+    #[test]
+    fn test_install_rejects_bad_packages() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let no_grammars = build_zip(&[("extension/package.json", "{\"name\":\"x\"}")], false);
+        assert!(install_from_archive(&no_grammars, "x", dir.path()).is_err());
+
+        let escaping = MANIFEST.replace("./syntaxes/foo.tmLanguage.json", "../../evil.json");
+        let archive = build_zip(&[("extension/package.json", &escaping)], false);
+        let err = install_from_archive(&archive, "x", dir.path()).unwrap_err();
+        assert!(err.contains("escapes"));
+
+        assert!(install_from_archive(b"not a zip", "x", dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_read_limited_fails_past_limit() {
+        assert_eq!(read_limited(&b"12345"[..], 5).unwrap(), b"12345");
+        let err = read_limited(&b"123456"[..], 5).unwrap_err();
+        assert_eq!(err.to_string(), "larger than 5 bytes");
+
+        // A small gzip stream that decompresses past the limit
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        std::io::Write::write_all(&mut encoder, &[0; 64 * 1024]).unwrap();
+        let bomb = encoder.finish().unwrap();
+        assert!(bomb.len() < 1024);
+        assert!(read_limited(flate2::read::GzDecoder::new(&bomb[..]), 1024).is_err());
+    }
+
+    #[test]
+    fn test_install_downloads_from_url() {
+        let archive = build_zip(
+            &[
+                ("extension/package.json", MANIFEST),
+                ("extension/syntaxes/foo.tmLanguage.json", "{}"),
+            ],
+            true,
+        );
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/foo.vsix", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            if let Ok(request) = server.recv() {
+                let _ = request.respond(tiny_http::Response::from_data(archive));
+            }
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = GrammarSource::parse(&url).unwrap();
+        assert_eq!(install(&source, dir.path()).unwrap(), "vscode-foo");
+        handle.join().unwrap();
+    }
+}
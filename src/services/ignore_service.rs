@@ -0,0 +1,352 @@
+//! Shared handling of ignore files.
+//!
+//! This module provides functionality to:
+//! - Load the `.gitignore` and `.ignore` files of directories and decide
+//!   whether a path is ignored, the way git and ripgrep do
+//! - Apply global ignore patterns from the `ignore_patterns` config setting
+//! - Notice ignore files that were created, changed or deleted, and reload them
+//!
+//! One service is shared by the file explorer, the file watcher, project-wide
+//! search and plugins (the file finder and grep plugins filter their results
+//! with `editor.filterIgnored()`).
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+/// Ignore files read in every directory, lowest precedence first
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// The ignore service, shared between the editor and the plugin thread
+pub type SharedIgnoreService = Arc<RwLock<IgnoreService>>;
+
+/// Ignore rules of one directory
+#[derive(Debug)]
+struct DirRules {
+    /// Rules from the directory's ignore files, or None if it has none
+    rules: Option<Gitignore>,
+    /// Modification times of the ignore files when they were loaded
+    mtimes: [Option<SystemTime>; 2],
+    /// Whether the directory is the root of a git repository, above which
+    /// ignore files no longer apply
+    is_repo_root: bool,
+}
+
+/// Why a path is ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IgnoreReason {
+    /// A `.gitignore` or `.ignore` file
+    IgnoreFile,
+    /// The `ignore_patterns` config setting
+    GlobalPattern,
+}
+
+/// Ignore files and global patterns, loaded per directory as needed
+#[derive(Debug)]
+pub struct IgnoreService {
+    /// Loaded directories
+    dirs: HashMap<PathBuf, DirRules>,
+    /// Global patterns, in .gitignore syntax
+    global_patterns: Vec<String>,
+    /// Matcher for the global patterns
+    global: Gitignore,
+    /// Workspace roots, which anchored global patterns ("/build") are
+    /// relative to
+    roots: Vec<PathBuf>,
+}
+
+impl IgnoreService {
+    /// Create a service with the given global patterns
+    pub fn new(global_patterns: &[String]) -> Self {
+        let mut service = Self {
+            dirs: HashMap::new(),
+            global_patterns: Vec::new(),
+            global: Gitignore::empty(),
+            roots: Vec::new(),
+        };
+        service.set_global_patterns(global_patterns);
+        service
+    }
+
+    /// Create a service that can be shared
+    pub fn shared(global_patterns: &[String]) -> SharedIgnoreService {
+        Arc::new(RwLock::new(Self::new(global_patterns)))
+    }
+
+    /// Replace the global patterns
+    pub fn set_global_patterns(&mut self, patterns: &[String]) {
+        if self.global_patterns == patterns {
+            return;
+        }
+        let mut builder = GitignoreBuilder::new("");
+        for pattern in patterns {
+            if let Err(e) = builder.add_line(None, pattern) {
+                tracing::warn!("Invalid ignore pattern {:?}: {}", pattern, e);
+            }
+        }
+        self.global = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to build ignore patterns: {}", e);
+            Gitignore::empty()
+        });
+        self.global_patterns = patterns.to_vec();
+    }
+
+    /// The global patterns
+    pub fn global_patterns(&self) -> &[String] {
+        &self.global_patterns
+    }
+
+    /// Set the workspace roots
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots = roots;
+    }
+
+    /// Load the ignore files of `dir`, replacing any loaded before
+    pub fn load_dir(&mut self, dir: &Path) {
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut mtimes = [None; 2];
+        let mut has_rules = false;
+        for (i, name) in IGNORE_FILES.iter().enumerate() {
+            let path = dir.join(name);
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            mtimes[i] = Some(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+            if let Some(e) = builder.add(&path) {
+                tracing::warn!("Failed to read {:?}: {}", path, e);
+            }
+            has_rules = true;
+        }
+        let rules = if has_rules {
+            match builder.build() {
+                Ok(rules) => Some(rules),
+                Err(e) => {
+                    tracing::warn!("Failed to load ignore files in {:?}: {}", dir, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        self.dirs.insert(
+            dir.to_path_buf(),
+            DirRules {
+                rules,
+                mtimes,
+                is_repo_root: dir.join(".git").exists(),
+            },
+        );
+    }
+
+    /// Load the ignore files of `dir` and of the directories above it, up to
+    /// the root of its git repository. Directories already loaded are skipped.
+    pub fn load_dir_and_ancestors(&mut self, dir: &Path) {
+        for ancestor in dir.ancestors() {
+            if !self.dirs.contains_key(ancestor) {
+                self.load_dir(ancestor);
+            }
+            if self.dirs.get(ancestor).is_some_and(|d| d.is_repo_root) {
+                break;
+            }
+        }
+    }
+
+    /// Number of loaded directories that have ignore files
+    pub fn ignore_file_count(&self) -> usize {
+        self.dirs.values().filter(|d| d.rules.is_some()).count()
+    }
+
+    /// Why `path` is ignored, if it is. Only ignore files that have been
+    /// loaded are consulted.
+    pub fn ignore_reason(&self, path: &Path, is_dir: bool) -> Option<IgnoreReason> {
+        if self.matches_global(path, is_dir) {
+            return Some(IgnoreReason::GlobalPattern);
+        }
+        // Outside a git repository, ignore files above the workspace roots
+        // (say, in the home folder) do not apply
+        let in_repo = path
+            .ancestors()
+            .any(|dir| self.dirs.get(dir).is_some_and(|d| d.is_repo_root));
+        // The deepest directory with a rule for the path decides, so a
+        // nested ignore file can re-include what a parent one ignores
+        for dir in path.ancestors().skip(1) {
+            if !in_repo
+                && !self.roots.is_empty()
+                && !self.roots.iter().any(|root| dir.starts_with(root))
+            {
+                break;
+            }
+            let Some(dir_rules) = self.dirs.get(dir) else {
+                continue;
+            };
+            if let Some(rules) = &dir_rules.rules {
+                match rules.matched_path_or_any_parents(path, is_dir) {
+                    Match::Ignore(_) => return Some(IgnoreReason::IgnoreFile),
+                    Match::Whitelist(_) => return None,
+                    Match::None => {}
+                }
+            }
+            if dir_rules.is_repo_root {
+                break;
+            }
+        }
+        None
+    }
+
+    /// Whether `path` is ignored by a loaded ignore file or a global pattern
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignore_reason(path, is_dir).is_some()
+    }
+
+    /// Whether `path` matches a global pattern
+    pub fn matches_global(&self, path: &Path, is_dir: bool) -> bool {
+        if self.global.is_empty() {
+            return false;
+        }
+        let relative = self.relative_path(path);
+        self.global
+            .matched_path_or_any_parents(&relative, is_dir)
+            .is_ignore()
+    }
+
+    /// Whether the file at `path` is ignored, loading the ignore files above
+    /// it first
+    pub fn check_file(&mut self, path: &Path) -> bool {
+        if let Some(parent) = path.parent() {
+            self.load_dir_and_ancestors(parent);
+        }
+        self.is_ignored(path, false)
+    }
+
+    /// Reload the ignore files that were created, changed or deleted since
+    /// they were loaded. Returns the directories that were reloaded.
+    pub fn poll_changes(&mut self) -> Vec<PathBuf> {
+        let changed: Vec<PathBuf> = self
+            .dirs
+            .iter()
+            .filter(|(dir, rules)| {
+                IGNORE_FILES.iter().enumerate().any(|(i, name)| {
+                    let mtime = std::fs::metadata(dir.join(name))
+                        .ok()
+                        .map(|m| m.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+                    mtime != rules.mtimes[i]
+                })
+            })
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        for dir in &changed {
+            if dir.is_dir() {
+                tracing::debug!("Reloading ignore files in {:?}", dir);
+                self.load_dir(dir);
+            } else {
+                self.dirs.remove(dir);
+            }
+        }
+        changed
+    }
+
+    /// `path` relative to the workspace root containing it, or with its
+    /// root component removed
+    fn relative_path(&self, path: &Path) -> PathBuf {
+        if let Some(relative) = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+        {
+            return relative.to_path_buf();
+        }
+        path.components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .collect()
+    }
+}
+
+impl Default for IgnoreService {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+        std::fs::write(root.join("sub/.ignore"), "!keep.log\n*.tmp\n").unwrap();
+        (temp_dir, root)
+    }
+
+    #[test]
+    fn test_ignore_files_apply_deepest_first() {
+        let (_temp_dir, root) = project();
+        let mut service = IgnoreService::default();
+
+        assert!(service.check_file(&root.join("a.log")));
+        assert!(service.check_file(&root.join("build/out/main.o")));
+        assert!(!service.check_file(&root.join("src/main.rs")));
+        assert!(service.check_file(&root.join("sub/x.tmp")));
+        assert!(service.check_file(&root.join("sub/other.log")));
+        assert!(!service.check_file(&root.join("sub/keep.log")));
+        assert_eq!(service.ignore_file_count(), 2);
+    }
+
+    #[test]
+    fn test_ignore_files_above_workspace_outside_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let home = temp_dir.path().canonicalize().unwrap();
+        let project = home.join("project");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(home.join(".gitignore"), "*\n").unwrap();
+
+        let mut service = IgnoreService::default();
+        service.set_roots(vec![project.clone()]);
+        assert!(!service.check_file(&project.join("main.rs")));
+
+        std::fs::create_dir(home.join(".git")).unwrap();
+        let mut service = IgnoreService::default();
+        service.set_roots(vec![project.clone()]);
+        assert!(service.check_file(&project.join("main.rs")));
+    }
+
+    #[test]
+    fn test_global_patterns() {
+        let (_temp_dir, root) = project();
+        let mut service = IgnoreService::new(&["*.bak".to_string(), "/dist".to_string()]);
+        service.set_roots(vec![root.clone()]);
+
+        assert_eq!(
+            service.ignore_reason(&root.join("sub/a.bak"), false),
+            Some(IgnoreReason::GlobalPattern)
+        );
+        assert!(service.is_ignored(&root.join("dist/app.js"), false));
+        assert!(!service.is_ignored(&root.join("sub/dist/app.js"), false));
+
+        service.set_global_patterns(&[]);
+        assert!(!service.is_ignored(&root.join("sub/a.bak"), false));
+    }
+
+    #[test]
+    fn test_poll_changes_reloads_ignore_files() {
+        let (_temp_dir, root) = project();
+        let mut service = IgnoreService::default();
+        assert!(!service.check_file(&root.join("notes.txt")));
+        assert!(service.poll_changes().is_empty());
+
+        std::fs::write(root.join(".ignore"), "*.txt\n").unwrap();
+        assert_eq!(service.poll_changes(), vec![root.clone()]);
+        assert!(service.is_ignored(&root.join("notes.txt"), false));
+
+        std::fs::remove_file(root.join(".ignore")).unwrap();
+        service.poll_changes();
+        assert!(!service.is_ignored(&root.join("notes.txt"), false));
+    }
+}
@@ -17,15 +17,15 @@ use crate::services::async_bridge::{
 use crate::services::process_limits::ProcessLimits;
 use lsp_types::{
     notification::{
-        DidChangeTextDocument, DidOpenTextDocument, DidSaveTextDocument, Initialized, Notification,
-        PublishDiagnostics,
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Initialized, Notification, PublishDiagnostics,
     },
     request::{Initialize, Request, Shutdown},
-    ClientCapabilities, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializeParams, InitializeResult, InitializedParams,
-    PublishDiagnosticsParams, ServerCapabilities, TextDocumentContentChangeEvent,
-    TextDocumentIdentifier, TextDocumentItem, Uri, VersionedTextDocumentIdentifier,
-    WindowClientCapabilities, WorkspaceFolder,
+    ClientCapabilities, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
+    InitializedParams, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem, Uri,
+    VersionedTextDocumentIdentifier, WindowClientCapabilities, WorkspaceFolder,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -42,6 +42,9 @@ use tokio::sync::{mpsc, oneshot};
 /// This gives the LSP server time to process didOpen before receiving changes
 const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 
+/// Response senders for in-flight requests, keyed by JSON-RPC request id
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>;
+
 /// Check if a document is already open and should skip didOpen.
 /// Returns true if the document is already open (should skip), false if it should proceed.
 fn should_skip_did_open(
@@ -201,6 +204,7 @@ fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
         GeneralClientCapabilities, RenameClientCapabilities, TextDocumentClientCapabilities,
         WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
@@ -214,6 +218,7 @@ fn create_client_capabilities() -> ClientCapabilities {
                 document_changes: Some(true),
                 ..Default::default()
             }),
+            symbol: Some(WorkspaceSymbolClientCapabilities::default()),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
@@ -262,6 +267,9 @@ enum LspCommand {
     /// Notify document saved
     DidSave { uri: Uri, text: Option<String> },
 
+    /// Notify document closed
+    DidClose { uri: Uri },
+
     /// Request completion at position
     Completion {
         request_id: u64,
@@ -303,6 +311,9 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Search the symbols of the whole workspace
+    WorkspaceSymbols { request_id: u64, query: String },
+
     /// Request signature help
     SignatureHelp {
         request_id: u64,
@@ -395,7 +406,7 @@ impl LspState {
     async fn replay_pending_commands(
         &mut self,
         commands: Vec<LspCommand>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) {
         if commands.is_empty() {
             return;
@@ -429,6 +440,10 @@ impl LspState {
                     tracing::info!("Replaying DidSave for {}", uri.as_str());
                     let _ = self.handle_did_save(uri, text).await;
                 }
+                LspCommand::DidClose { uri } => {
+                    tracing::info!("Replaying DidClose for {}", uri.as_str());
+                    let _ = self.handle_did_close(uri).await;
+                }
                 _ => {}
             }
         }
@@ -480,7 +495,7 @@ impl LspState {
         &mut self,
         method: &str,
         params: Option<P>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<R, String> {
         self.send_request_sequential_tracked(method, params, pending, None)
             .await
@@ -491,7 +506,7 @@ impl LspState {
         &mut self,
         method: &str,
         params: Option<P>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
         editor_request_id: Option<u64>,
     ) -> Result<R, String> {
         let id = self.next_id;
@@ -542,7 +557,7 @@ impl LspState {
         &mut self,
         root_uri: Option<Uri>,
         initialization_options: Option<Value>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<InitializeResult, String> {
         tracing::info!(
             "Initializing async LSP server with root_uri: {:?}, initialization_options: {:?}",
@@ -605,7 +620,7 @@ impl LspState {
         uri: Uri,
         text: String,
         language_id: String,
-        _pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        _pending: &PendingRequests,
     ) -> Result<(), String> {
         let path = PathBuf::from(uri.path().as_str());
 
@@ -637,7 +652,7 @@ impl LspState {
         &mut self,
         uri: Uri,
         content_changes: Vec<TextDocumentContentChangeEvent>,
-        _pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        _pending: &PendingRequests,
     ) -> Result<(), String> {
         tracing::trace!("LSP: did_change for {}", uri.as_str());
 
@@ -699,6 +714,21 @@ impl LspState {
         self.send_notification::<DidSaveTextDocument>(params).await
     }
 
+    /// Handle did_close command
+    async fn handle_did_close(&mut self, uri: Uri) -> Result<(), String> {
+        tracing::trace!("LSP: did_close for {}", uri.as_str());
+
+        let path = PathBuf::from(uri.path().as_str());
+        self.document_versions.remove(&path);
+        self.pending_opens.remove(&path);
+
+        let params = DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri },
+        };
+
+        self.send_notification::<DidCloseTextDocument>(params).await
+    }
+
     /// Handle completion request
     async fn handle_completion(
         &mut self,
@@ -706,7 +736,7 @@ impl LspState {
         uri: Uri,
         line: u32,
         character: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             CompletionParams, PartialResultParams, Position, TextDocumentIdentifier,
@@ -779,7 +809,7 @@ impl LspState {
         uri: Uri,
         line: u32,
         character: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             GotoDefinitionParams, PartialResultParams, Position, TextDocumentIdentifier,
@@ -859,7 +889,7 @@ impl LspState {
         line: u32,
         character: u32,
         new_name: String,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             Position, RenameParams, TextDocumentIdentifier, TextDocumentPositionParams,
@@ -928,7 +958,7 @@ impl LspState {
         uri: Uri,
         line: u32,
         character: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             HoverParams, Position, TextDocumentIdentifier, TextDocumentPositionParams,
@@ -1047,7 +1077,7 @@ impl LspState {
         uri: Uri,
         line: u32,
         character: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             PartialResultParams, Position, ReferenceContext, ReferenceParams,
@@ -1107,6 +1137,49 @@ impl LspState {
         }
     }
 
+    /// Handle workspace symbol search request
+    async fn handle_workspace_symbols(
+        &mut self,
+        request_id: u64,
+        query: String,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{PartialResultParams, WorkDoneProgressParams, WorkspaceSymbolParams};
+
+        tracing::trace!("LSP: workspace symbol request for '{}'", query);
+
+        let params = WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Value>("workspace/symbol", Some(params), pending)
+            .await
+        {
+            Ok(result) => {
+                // Flat or nested symbol list, or null
+                let response =
+                    serde_json::from_value::<Option<lsp_types::WorkspaceSymbolResponse>>(result)
+                        .unwrap_or_default();
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    response,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Workspace symbol request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    response: None,
+                });
+                Err(e)
+            }
+        }
+    }
+
     /// Handle signature help request
     async fn handle_signature_help(
         &mut self,
@@ -1114,7 +1187,7 @@ impl LspState {
         uri: Uri,
         line: u32,
         character: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             Position, SignatureHelpParams, TextDocumentIdentifier, TextDocumentPositionParams,
@@ -1191,7 +1264,7 @@ impl LspState {
         end_line: u32,
         end_char: u32,
         diagnostics: Vec<lsp_types::Diagnostic>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             CodeActionContext, CodeActionParams, PartialResultParams, Position, Range,
@@ -1269,7 +1342,7 @@ impl LspState {
         request_id: u64,
         uri: Uri,
         previous_result_id: Option<String>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             DocumentDiagnosticParams, PartialResultParams, TextDocumentIdentifier,
@@ -1398,7 +1471,7 @@ impl LspState {
         start_char: u32,
         end_line: u32,
         end_char: u32,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<(), String> {
         use lsp_types::{
             InlayHintParams, Position, Range, TextDocumentIdentifier, WorkDoneProgressParams,
@@ -1473,7 +1546,7 @@ impl LspState {
         request_id: u64,
         method: String,
         params: Option<Value>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) {
         tracing::trace!(
             "Plugin request {} => method={} params={:?}",
@@ -1723,7 +1796,7 @@ impl LspTask {
     /// Spawn the stdout reader task that continuously reads and dispatches LSP messages
     fn spawn_stdout_reader(
         mut stdout: BufReader<ChildStdout>,
-        pending: Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: PendingRequests,
         async_tx: std_mpsc::Sender<AsyncMessage>,
         language: String,
         server_command: String,
@@ -1903,6 +1976,18 @@ impl LspTask {
                                 pending_commands.push(LspCommand::DidSave { uri, text });
                             }
                         }
+                        LspCommand::DidClose { uri } => {
+                            if state.initialized {
+                                tracing::info!("Processing DidClose for {}", uri.as_str());
+                                let _ = state.handle_did_close(uri).await;
+                            } else {
+                                tracing::trace!(
+                                    "Queueing DidClose for {} until initialization completes",
+                                    uri.as_str()
+                                );
+                                pending_commands.push(LspCommand::DidClose { uri });
+                            }
+                        }
                         LspCommand::Completion {
                             request_id,
                             uri,
@@ -2011,6 +2096,19 @@ impl LspTask {
                                 });
                             }
                         }
+                        LspCommand::WorkspaceSymbols { request_id, query } => {
+                            if state.initialized {
+                                let _ = state
+                                    .handle_workspace_symbols(request_id, query, &pending)
+                                    .await;
+                            } else {
+                                tracing::trace!("LSP not initialized, cannot search symbols");
+                                let _ = state.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                                    request_id,
+                                    response: None,
+                                });
+                            }
+                        }
                         LspCommand::SignatureHelp {
                             request_id,
                             uri,
@@ -2190,7 +2288,7 @@ impl LspTask {
         &mut self,
         root_uri: Option<Uri>,
         initialization_options: Option<Value>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<InitializeResult, String> {
         tracing::info!(
             "Initializing async LSP server with root_uri: {:?}, initialization_options: {:?}",
@@ -2253,7 +2351,7 @@ impl LspTask {
         uri: Uri,
         text: String,
         language_id: String,
-        _pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        _pending: &PendingRequests,
     ) -> Result<(), String> {
         let path = PathBuf::from(uri.path().as_str());
 
@@ -2285,7 +2383,7 @@ impl LspTask {
         &mut self,
         uri: Uri,
         content_changes: Vec<TextDocumentContentChangeEvent>,
-        _pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        _pending: &PendingRequests,
     ) -> Result<(), String> {
         tracing::trace!("LSP: did_change for {}", uri.as_str());
 
@@ -2330,7 +2428,7 @@ impl LspTask {
         &mut self,
         method: &str,
         params: Option<P>,
-        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+        pending: &PendingRequests,
     ) -> Result<R, String> {
         let id = self.next_id;
         self.next_id += 1;
@@ -2798,7 +2896,7 @@ async fn read_message_from_stdout(
 /// Standalone function to handle and dispatch messages (for reader task)
 async fn handle_message_dispatch(
     message: JsonRpcMessage,
-    pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    pending: &PendingRequests,
     async_tx: &std_mpsc::Sender<AsyncMessage>,
     language: &str,
     server_command: &str,
@@ -3373,6 +3471,13 @@ impl LspHandle {
             .map_err(|_| "Failed to send did_save command".to_string())
     }
 
+    /// Send didClose notification
+    pub fn did_close(&self, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DidClose { uri })
+            .map_err(|_| "Failed to send did_close command".to_string())
+    }
+
     /// Request completion at position
     pub fn completion(
         &self,
@@ -3465,6 +3570,13 @@ impl LspHandle {
             .map_err(|_| "Failed to send references command".to_string())
     }
 
+    /// Search the symbols of the whole workspace
+    pub fn workspace_symbols(&self, request_id: u64, query: String) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceSymbols { request_id, query })
+            .map_err(|_| "Failed to send workspace_symbols command".to_string())
+    }
+
     /// Request signature help
     pub fn signature_help(
         &self,
@@ -4,8 +4,10 @@
 ///! Diagnostics are displayed as colored underlines (red for errors, yellow for warnings, etc.)
 use crate::model::buffer::Buffer;
 use crate::state::EditorState;
-use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::overlay::{priority, Overlay, OverlayFace, OverlayNamespace, UnderlineStyle};
+use crate::view::underline::underline_modifier;
 use lsp_types::{Diagnostic, DiagnosticSeverity};
+use ratatui::style::{Color, Style};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::ops::Range;
@@ -112,28 +114,36 @@ pub fn diagnostic_to_overlay(
     // Determine overlay face based on diagnostic severity using theme colors
     let (face, priority) = match diagnostic.severity {
         Some(DiagnosticSeverity::ERROR) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_error_bg,
-            },
-            100, // Highest priority
+            severity_face(
+                theme.diagnostic_error_bg,
+                theme.diagnostic_error_fg,
+                UnderlineStyle::Wavy,
+            ),
+            priority::DIAGNOSTIC_ERROR,
         ),
         Some(DiagnosticSeverity::WARNING) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_warning_bg,
-            },
-            50, // Medium priority
+            severity_face(
+                theme.diagnostic_warning_bg,
+                theme.diagnostic_warning_fg,
+                UnderlineStyle::Wavy,
+            ),
+            priority::DIAGNOSTIC_WARNING,
         ),
         Some(DiagnosticSeverity::INFORMATION) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_info_bg,
-            },
-            30, // Lower priority
+            severity_face(
+                theme.diagnostic_info_bg,
+                theme.diagnostic_info_fg,
+                UnderlineStyle::Dotted,
+            ),
+            priority::DIAGNOSTIC_INFO,
         ),
         Some(DiagnosticSeverity::HINT) | None => (
-            OverlayFace::Background {
-                color: theme.diagnostic_hint_bg,
-            },
-            10, // Lowest priority
+            severity_face(
+                theme.diagnostic_hint_bg,
+                theme.diagnostic_hint_fg,
+                UnderlineStyle::Dashed,
+            ),
+            priority::DIAGNOSTIC_HINT,
         ),
         _ => return None, // Unknown severity
     };
@@ -141,6 +151,17 @@ pub fn diagnostic_to_overlay(
     Some((start_byte..end_byte, face, priority))
 }
 
+/// Face of a diagnostic: the severity's background, underlined in the
+/// severity's color. The text keeps its own color.
+fn severity_face(bg: Color, underline_color: Color, underline: UnderlineStyle) -> OverlayFace {
+    OverlayFace::Style {
+        style: Style::default()
+            .bg(bg)
+            .add_modifier(underline_modifier(underline))
+            .underline_color(underline_color),
+    }
+}
+
 /// Apply LSP diagnostics to editor state as overlays
 ///
 /// This function:
@@ -241,10 +262,13 @@ mod tests {
         assert_eq!(priority, 100); // Error has highest priority
 
         match face {
-            OverlayFace::Background { color } => {
-                assert_eq!(color, theme.diagnostic_error_bg);
+            OverlayFace::Style { style } => {
+                assert_eq!(style.bg, Some(theme.diagnostic_error_bg));
+                assert_eq!(style.underline_color, Some(theme.diagnostic_error_fg));
+                assert_eq!(style.fg, None);
+                assert_eq!(style.add_modifier, underline_modifier(UnderlineStyle::Wavy));
             }
-            _ => panic!("Expected Background face"),
+            _ => panic!("Expected Style face"),
         }
     }
 
@@ -282,10 +306,12 @@ mod tests {
         assert_eq!(priority, 50); // Warning has medium priority
 
         match face {
-            OverlayFace::Background { color } => {
-                assert_eq!(color, theme.diagnostic_warning_bg);
+            OverlayFace::Style { style } => {
+                assert_eq!(style.bg, Some(theme.diagnostic_warning_bg));
+                assert_eq!(style.underline_color, Some(theme.diagnostic_warning_fg));
+                assert_eq!(style.fg, None);
             }
-            _ => panic!("Expected Background face"),
+            _ => panic!("Expected Style face"),
         }
     }
 
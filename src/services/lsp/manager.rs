@@ -647,6 +647,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
         languages.insert(
@@ -665,6 +667,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
         languages.insert(
@@ -683,6 +687,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                abbreviations: HashMap::new(),
+                repl: None,
             },
         );
         languages
@@ -5,17 +5,35 @@
 
 pub mod async_bridge;
 pub mod clipboard;
+pub mod collab;
+pub mod crash_report;
 pub mod fs;
+pub mod git_remote;
+pub mod git_status;
 #[cfg(target_os = "linux")]
 pub mod gpm;
+pub mod grammar_installer;
+pub mod ignore_service;
 pub mod lsp;
 pub mod plugins;
+pub mod private_dir;
 pub mod process_limits;
+pub mod project_rename;
 pub mod recovery;
 pub mod release_checker;
+#[cfg(unix)]
+pub mod remote;
+pub mod repl;
 pub mod signal_handler;
+pub mod ssh;
 pub mod styled_html;
+pub mod sudo;
+pub mod symbol_index;
+pub mod tags;
+pub mod tasks;
 pub mod terminal;
 pub mod time_source;
 pub mod tracing_setup;
+pub mod undo_history;
+pub mod vfs;
 pub mod warning_log;
@@ -6,6 +6,8 @@
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Command;
 use crate::model::event::{BufferId, SplitId};
+use crate::services::ignore_service::{IgnoreService, SharedIgnoreService};
+use crate::services::plugins::fetch::{FetchRequest, FetchResponse};
 use crate::services::plugins::hooks::{HookCallback, HookRegistry};
 use crate::view::overlay::{OverlayHandle, OverlayNamespace};
 use serde::{Deserialize, Serialize};
@@ -29,6 +31,43 @@ pub enum PluginResponse {
         request_id: u64,
         result: Result<Value, String>,
     },
+    /// Response to GetBufferText with the text of the requested range
+    BufferText {
+        request_id: u64,
+        result: Result<String, String>,
+    },
+    /// Response to QueryBuffer with the answer as JSON
+    BufferQuery {
+        request_id: u64,
+        result: Result<Value, String>,
+    },
+    /// Response to Fetch with the HTTP response
+    Fetch {
+        request_id: u64,
+        result: Result<FetchResponse, String>,
+    },
+}
+
+/// A question about a buffer's content, answered in the editor so plugins
+/// don't have to read the text and count lines themselves
+#[derive(Debug, Clone)]
+pub enum BufferQuery {
+    /// Number of lines (null while unknown, as in large files)
+    LineCount,
+    /// Line (0-indexed) containing a byte offset
+    LineAtOffset { offset: usize },
+    /// Byte offset where a line (0-indexed) starts
+    LineStartOffset { line: usize },
+    /// Byte ranges of the matches of a pattern
+    Search {
+        pattern: String,
+        /// Whether the pattern is a regular expression
+        regex: bool,
+        case_sensitive: bool,
+        /// Byte range to search (the whole buffer if None)
+        range: Option<Range<usize>>,
+        max_results: usize,
+    },
 }
 
 /// Information about a cursor in the editor
@@ -51,6 +90,8 @@ pub struct BufferInfo {
     pub modified: bool,
     /// Length of buffer in bytes
     pub length: usize,
+    /// Version of the buffer content, increased by every edit
+    pub version: u64,
 }
 
 /// Diff between current buffer content and last saved snapshot
@@ -172,6 +213,10 @@ pub struct EditorStateSnapshot {
     pub clipboard: String,
     /// Editor's working directory (for file operations and spawning processes)
     pub working_dir: PathBuf,
+    /// The working directory followed by the other workspace folders
+    pub workspace_roots: Vec<PathBuf>,
+    /// The editor's ignore service, for filtering ignored files
+    pub ignore_service: SharedIgnoreService,
     /// LSP diagnostics per file URI
     /// Maps file URI string to Vec of diagnostics for that file
     pub diagnostics: HashMap<String, Vec<lsp_types::Diagnostic>>,
@@ -198,6 +243,8 @@ impl EditorStateSnapshot {
             selected_text: None,
             clipboard: String::new(),
             working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            workspace_roots: Vec::new(),
+            ignore_service: IgnoreService::shared(&[]),
             diagnostics: HashMap::new(),
             config: serde_json::Value::Null,
             user_config: serde_json::Value::Null,
@@ -221,17 +268,32 @@ pub enum MenuPosition {
 /// Plugin command - allows plugins to send commands to the editor
 #[derive(Debug, Clone)]
 pub enum PluginCommand {
-    /// Insert text at a position in a buffer
+    /// Insert text at a position in a buffer. `plugin` names the plugin
+    /// making the edit, whose undo group it may join.
     InsertText {
         buffer_id: BufferId,
         position: usize,
         text: String,
+        plugin: Option<String>,
     },
 
     /// Delete a range of text from a buffer
     DeleteRange {
         buffer_id: BufferId,
         range: Range<usize>,
+        plugin: Option<String>,
+    },
+
+    /// Start grouping a plugin's edits of a buffer into one undo step
+    BeginUndoGroup {
+        buffer_id: BufferId,
+        plugin: Option<String>,
+    },
+
+    /// End the undo group started by the matching BeginUndoGroup
+    EndUndoGroup {
+        buffer_id: BufferId,
+        plugin: Option<String>,
     },
 
     /// Add an overlay to a buffer, returns handle via response channel
@@ -254,6 +316,29 @@ pub enum PluginCommand {
     /// Set status message
     SetStatus { message: String },
 
+    /// Show a toast notification
+    /// `level` is "info", "warning" or "error"; `source` names the sender (e.g. plugin name)
+    ShowNotification {
+        level: String,
+        message: String,
+        source: Option<String>,
+    },
+
+    /// Add a line to the plugin log
+    /// `level` is "debug", "info", "warn" or "error"; `plugin` is the writer, if known
+    PluginLog {
+        plugin: Option<String>,
+        level: String,
+        message: String,
+    },
+
+    /// An error thrown by a plugin's handler or action
+    /// Plugins that keep throwing are disabled and restarted later
+    PluginError {
+        plugin: Option<String>,
+        message: String,
+    },
+
     /// Apply a theme by name
     ApplyTheme { theme_name: String },
 
@@ -459,6 +544,13 @@ pub enum PluginCommand {
     /// Remove a top-level menu
     RemoveMenu { menu_label: String },
 
+    /// Add an item to the editor's right-click context menu, replacing any
+    /// item with the same label
+    AddContextMenuItem { item: crate::config::MenuItem },
+
+    /// Remove an item from the editor's right-click context menu
+    RemoveContextMenuItem { label: String },
+
     /// Create a new virtual buffer (not backed by a file)
     CreateVirtualBuffer {
         /// Display name (e.g., "*Diagnostics*")
@@ -596,6 +688,49 @@ pub enum PluginCommand {
         position: usize,
     },
 
+    /// Read a byte range of a buffer's text. Plugins ask for the ranges they
+    /// need instead of the snapshot carrying whole buffers.
+    GetBufferText {
+        buffer_id: BufferId,
+        range: Range<usize>,
+        request_id: u64,
+    },
+
+    /// Serve buffers for URLs with `scheme` through the `vfs_read` and
+    /// `vfs_write` hooks
+    RegisterFileSystemProvider { scheme: String, writable: bool },
+
+    /// Content read for a `vfs_read` hook, or why it could not be read
+    CompleteVfsRead {
+        url: String,
+        result: Result<String, String>,
+    },
+
+    /// Outcome of writing for a `vfs_write` hook
+    CompleteVfsWrite {
+        url: String,
+        result: Result<(), String>,
+    },
+
+    /// Open a URL served by a file system provider (e.g. `git://HEAD~1/src/main.rs`)
+    OpenUrl { url: String },
+
+    /// Make an HTTP request for a plugin. The editor asks the user whether
+    /// the plugin may access the network, unless the config says so.
+    Fetch {
+        /// Plugin making the request, if known
+        plugin: Option<String>,
+        request: FetchRequest,
+        request_id: u64,
+    },
+
+    /// Ask a question about a buffer's content
+    QueryBuffer {
+        buffer_id: BufferId,
+        query: BufferQuery,
+        request_id: u64,
+    },
+
     /// Send an arbitrary LSP request and return the raw JSON response
     SendLspRequest {
         language: String,
@@ -604,6 +739,17 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Declare a plugin's settings: a JSON object mapping each key to a JSON
+    /// Schema property with its type, default and description
+    DefineSettings { namespace: String, settings: Value },
+
+    /// Set a plugin setting and save it to the config file
+    SetPluginSetting {
+        namespace: String,
+        key: String,
+        value: Value,
+    },
+
     /// Set the internal clipboard content
     SetClipboard { text: String },
 
@@ -684,23 +830,53 @@ impl PluginApi {
             .map_err(|e| format!("Failed to send command: {}", e))
     }
 
-    /// Insert text at a position in a buffer
+    /// Insert text at a position in a buffer; `plugin` names the plugin
+    /// making the edit
     pub fn insert_text(
         &self,
         buffer_id: BufferId,
         position: usize,
         text: String,
+        plugin: Option<String>,
     ) -> Result<(), String> {
         self.send_command(PluginCommand::InsertText {
             buffer_id,
             position,
             text,
+            plugin,
         })
     }
 
     /// Delete a range of text from a buffer
-    pub fn delete_range(&self, buffer_id: BufferId, range: Range<usize>) -> Result<(), String> {
-        self.send_command(PluginCommand::DeleteRange { buffer_id, range })
+    pub fn delete_range(
+        &self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        plugin: Option<String>,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::DeleteRange {
+            buffer_id,
+            range,
+            plugin,
+        })
+    }
+
+    /// Start grouping a plugin's edits of a buffer into one undo step
+    pub fn begin_undo_group(
+        &self,
+        buffer_id: BufferId,
+        plugin: Option<String>,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::BeginUndoGroup { buffer_id, plugin })
+    }
+
+    /// End the undo group started by the matching `begin_undo_group`
+    pub fn end_undo_group(
+        &self,
+        buffer_id: BufferId,
+        plugin: Option<String>,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::EndUndoGroup { buffer_id, plugin })
     }
 
     /// Add an overlay (decoration) to a buffer
@@ -762,6 +938,20 @@ impl PluginApi {
         self.send_command(PluginCommand::SetStatus { message })
     }
 
+    /// Show a toast notification ("info", "warning" or "error")
+    pub fn show_notification(
+        &self,
+        level: String,
+        message: String,
+        source: Option<String>,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::ShowNotification {
+            level,
+            message,
+            source,
+        })
+    }
+
     /// Open a file at a specific line and column (1-indexed)
     /// This is useful for jumping to locations from git grep, LSP definitions, etc.
     pub fn open_file_at_location(
@@ -843,6 +1033,16 @@ impl PluginApi {
         self.send_command(PluginCommand::RemoveMenu { menu_label })
     }
 
+    /// Add an item to the editor's right-click context menu
+    pub fn add_context_menu_item(&self, item: crate::config::MenuItem) -> Result<(), String> {
+        self.send_command(PluginCommand::AddContextMenuItem { item })
+    }
+
+    /// Remove an item from the editor's right-click context menu
+    pub fn remove_context_menu_item(&self, label: String) -> Result<(), String> {
+        self.send_command(PluginCommand::RemoveContextMenuItem { label })
+    }
+
     // === Virtual Buffer Methods ===
 
     /// Create a new virtual buffer (not backed by a file)
@@ -1030,7 +1230,7 @@ mod tests {
 
         let api = PluginApi::new(hooks, commands, tx, state_snapshot);
 
-        let result = api.insert_text(BufferId(1), 0, "test".to_string());
+        let result = api.insert_text(BufferId(1), 0, "test".to_string(), None);
         assert!(result.is_ok());
 
         // Verify command was sent
@@ -1042,10 +1242,12 @@ mod tests {
                 buffer_id,
                 position,
                 text,
+                plugin,
             } => {
                 assert_eq!(buffer_id.0, 1);
                 assert_eq!(position, 0);
                 assert_eq!(text, "test");
+                assert_eq!(plugin, None);
             }
             _ => panic!("Wrong command type"),
         }
@@ -1115,6 +1317,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_show_notification() {
+        let hooks = Arc::new(RwLock::new(HookRegistry::new()));
+        let commands = Arc::new(RwLock::new(CommandRegistry::new()));
+        let (tx, rx) = std::sync::mpsc::channel();
+        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
+
+        let api = PluginApi::new(hooks, commands, tx, state_snapshot);
+
+        let result = api.show_notification(
+            "warning".to_string(),
+            "Disk almost full".to_string(),
+            Some("my-plugin".to_string()),
+        );
+        assert!(result.is_ok());
+
+        let received = rx.try_recv().unwrap();
+        match received {
+            PluginCommand::ShowNotification {
+                level,
+                message,
+                source,
+            } => {
+                assert_eq!(level, "warning");
+                assert_eq!(message, "Disk almost full");
+                assert_eq!(source.as_deref(), Some("my-plugin"));
+            }
+            _ => panic!("Wrong command type"),
+        }
+    }
+
     #[test]
     fn test_get_active_buffer_id() {
         let hooks = Arc::new(RwLock::new(HookRegistry::new()));
@@ -1149,6 +1382,7 @@ mod tests {
                 path: Some(std::path::PathBuf::from("/test/file.txt")),
                 modified: true,
                 length: 100,
+                version: 0,
             };
             snapshot.buffers.insert(BufferId(1), buffer_info);
         }
@@ -1188,6 +1422,7 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file1.txt")),
                     modified: false,
                     length: 50,
+                    version: 0,
                 },
             );
             snapshot.buffers.insert(
@@ -1197,6 +1432,7 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file2.txt")),
                     modified: true,
                     length: 100,
+                    version: 0,
                 },
             );
             snapshot.buffers.insert(
@@ -1206,6 +1442,7 @@ mod tests {
                     path: None,
                     modified: false,
                     length: 0,
+                    version: 0,
                 },
             );
         }
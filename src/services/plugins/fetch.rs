@@ -0,0 +1,233 @@
+//! HTTP requests made by plugins.
+//!
+//! This module provides functionality to:
+//! - Run a plugin's HTTP request with a timeout and a cap on the response size
+//! - Find the host a request goes to, to ask the user whether the plugin may
+//!   access the network
+//!
+//! Requests run on a background thread started by the editor, which also
+//! checks the plugin's permission first (see `app::plugin_fetch`).
+//! Redirects are not followed: the user approves the host in the request,
+//! so a redirect response is returned to the plugin as it is.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+
+/// Timeout of a request that sets none
+pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+/// Longest timeout a request may set
+pub const MAX_TIMEOUT_MS: u64 = 120_000;
+/// Largest response body a request may read
+pub const MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// An HTTP request made by a plugin
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchRequest {
+    /// http:// or https:// URL
+    pub url: String,
+    /// HTTP method ("GET" if None)
+    pub method: Option<String>,
+    /// Request headers
+    pub headers: HashMap<String, String>,
+    /// Request body
+    pub body: Option<String>,
+    /// Timeout in milliseconds (`DEFAULT_TIMEOUT_MS` if None, at most
+    /// `MAX_TIMEOUT_MS`)
+    pub timeout_ms: Option<u64>,
+    /// Largest response body to accept in bytes (at most
+    /// `MAX_RESPONSE_BYTES`)
+    pub max_bytes: Option<u64>,
+}
+
+/// Response to a plugin's HTTP request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers, with lowercase names
+    pub headers: HashMap<String, String>,
+    /// Response body as text
+    pub body: String,
+}
+
+/// The host a request goes to. Fails for URLs that are not http or https.
+pub fn request_host(url: &str) -> Result<String, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Only http and https URLs can be fetched: {}", url));
+    }
+    parsed
+        .host_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("URL has no host: {}", url))
+}
+
+/// Run `request`, blocking until the response has been read. Responses with
+/// error statuses and redirects are returned like any other; only transport
+/// errors, timeouts and bodies over the size limit fail.
+pub fn fetch(request: &FetchRequest) -> Result<FetchResponse, String> {
+    request_host(&request.url)?;
+    let method = request.method.as_deref().unwrap_or("GET").to_uppercase();
+    let timeout_ms = request
+        .timeout_ms
+        .unwrap_or(DEFAULT_TIMEOUT_MS)
+        .min(MAX_TIMEOUT_MS);
+    let max_bytes = request
+        .max_bytes
+        .unwrap_or(MAX_RESPONSE_BYTES)
+        .min(MAX_RESPONSE_BYTES);
+
+    let agent = ureq::AgentBuilder::new().redirects(0).build();
+    let mut call = agent
+        .request(&method, &request.url)
+        .set("User-Agent", "fresh-editor-plugin")
+        .timeout(Duration::from_millis(timeout_ms));
+    for (name, value) in &request.headers {
+        call = call.set(name, value);
+    }
+    let result = match &request.body {
+        Some(body) => call.send_string(body),
+        None => call.call(),
+    };
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(format!("Request to {} failed: {}", request.url, e)),
+    };
+
+    let status = response.status();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name.to_lowercase(), value))
+        })
+        .collect();
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(max_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read response from {}: {}", request.url, e))?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(format!(
+            "Response from {} is larger than {} bytes",
+            request.url, max_bytes
+        ));
+    }
+
+    Ok(FetchResponse {
+        status,
+        headers,
+        body: String::from_utf8_lossy(&bytes).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve one request with `body` and `status`, returning the server URL
+    /// and the request's method and body
+    fn serve_once(
+        body: &'static str,
+        status: u16,
+    ) -> (String, std::thread::JoinHandle<(String, String)>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/lookup", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let method = request.method().to_string();
+            let mut received = String::new();
+            request.as_reader().read_to_string(&mut received).unwrap();
+            let response = tiny_http::Response::from_string(body)
+                .with_status_code(status)
+                .with_header(tiny_http::Header::from_bytes(&b"X-Word"[..], &b"fresh"[..]).unwrap());
+            request.respond(response).unwrap();
+            (method, received)
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_request_host() {
+        assert_eq!(
+            request_host("https://api.example.com/v1?q=1").unwrap(),
+            "api.example.com"
+        );
+        assert!(request_host("file:///etc/passwd").is_err());
+        assert!(request_host("not a url").is_err());
+    }
+
+    #[test]
+    fn test_fetch_returns_status_headers_and_body() {
+        let (url, handle) = serve_once("a new start", 200);
+        let response = fetch(&FetchRequest {
+            url,
+            method: Some("post".to_string()),
+            body: Some("word=fresh".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "a new start");
+        assert_eq!(response.headers.get("x-word").unwrap(), "fresh");
+        assert_eq!(
+            handle.join().unwrap(),
+            ("POST".to_string(), "word=fresh".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fetch_returns_error_statuses() {
+        let (url, handle) = serve_once("no such word", 404);
+        let response = fetch(&FetchRequest {
+            url,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "no such word");
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_does_not_follow_redirects() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/lookup", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let location = &b"http://other.invalid/lookup"[..];
+            let response = tiny_http::Response::empty(302)
+                .with_header(tiny_http::Header::from_bytes(&b"Location"[..], location).unwrap());
+            request.respond(response).unwrap();
+        });
+        let response = fetch(&FetchRequest {
+            url,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(response.status, 302);
+        assert_eq!(
+            response.headers.get("location").unwrap(),
+            "http://other.invalid/lookup"
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_rejects_large_responses() {
+        let (url, handle) = serve_once("0123456789", 200);
+        let err = fetch(&FetchRequest {
+            url,
+            max_bytes: Some(4),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.contains("larger than 4 bytes"), "{}", err);
+        let _ = handle.join();
+    }
+}
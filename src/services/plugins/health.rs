@@ -0,0 +1,394 @@
+//! Plugin health tracking.
+//!
+//! This module provides functionality to:
+//! - Keep a log of what plugins print and the errors they throw
+//! - Disable a plugin that keeps throwing errors, and restart it with
+//!   exponential backoff
+//! - Restart the plugin host, the thread that runs all plugins, with
+//!   exponential backoff when it stops
+//!
+//! It only decides what should happen and when; the editor asks the
+//! `PluginManager` to disable, reload or respawn.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Errors within `ERROR_WINDOW_SECS` after which a plugin is disabled
+const MAX_ERRORS_IN_WINDOW: usize = 3;
+const ERROR_WINDOW_SECS: u64 = 60;
+
+/// Restarts within `RESTART_WINDOW_SECS` after which a plugin (or the host)
+/// stays disabled
+const MAX_RESTARTS_IN_WINDOW: usize = 5;
+const RESTART_WINDOW_SECS: u64 = 600; // 10 minutes
+const RESTART_BACKOFF_BASE_MS: u64 = 1000; // 1s, 2s, 4s, 8s...
+
+/// Lines kept in the plugin log
+const LOG_CAPACITY: usize = 1000;
+
+/// Severity of a plugin log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginLogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl PluginLogLevel {
+    /// Parse a level name as used by `console` ("log" counts as info)
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "debug" | "trace" => Self::Debug,
+            "warn" => Self::Warn,
+            "error" => Self::Error,
+            _ => Self::Info,
+        }
+    }
+
+    /// Label shown in the plugin log
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+/// A line of the plugin log
+#[derive(Debug, Clone)]
+pub struct PluginLogEntry {
+    pub time: Instant,
+    /// Plugin that wrote the line, if known
+    pub plugin: Option<String>,
+    pub level: PluginLogLevel,
+    pub message: String,
+}
+
+/// Whether a plugin (or the host) is running
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginState {
+    Running,
+    /// Stopped after errors. It is restarted at `restart_at`, or stays
+    /// stopped if it has been restarted too often.
+    Disabled {
+        error: String,
+        restart_at: Option<Instant>,
+    },
+}
+
+/// Errors and restarts of one plugin or the host
+#[derive(Debug, Clone)]
+struct Tracked {
+    state: PluginState,
+    errors: Vec<Instant>,
+    restarts: Vec<Instant>,
+}
+
+impl Default for Tracked {
+    fn default() -> Self {
+        Self {
+            state: PluginState::Running,
+            errors: Vec::new(),
+            restarts: Vec::new(),
+        }
+    }
+}
+
+impl Tracked {
+    /// Stop it and schedule a restart with exponential backoff
+    fn disable(&mut self, error: String, now: Instant) {
+        let window = Duration::from_secs(RESTART_WINDOW_SECS);
+        self.restarts.retain(|t| now.duration_since(*t) < window);
+        let restart_at = (self.restarts.len() < MAX_RESTARTS_IN_WINDOW)
+            .then(|| now + Duration::from_millis(RESTART_BACKOFF_BASE_MS << self.restarts.len()));
+        self.state = PluginState::Disabled { error, restart_at };
+        self.errors.clear();
+    }
+
+    /// Restart it at `now`, forgetting earlier restarts; does nothing if it
+    /// is running
+    fn restart_now(&mut self, now: Instant) {
+        if let PluginState::Disabled { restart_at, .. } = &mut self.state {
+            *restart_at = Some(now);
+            self.restarts.clear();
+        }
+    }
+
+    /// Whether its restart is due, marking it running again if so
+    fn take_restart(&mut self, now: Instant) -> bool {
+        match self.state {
+            PluginState::Disabled {
+                restart_at: Some(at),
+                ..
+            } if at <= now => {
+                self.state = PluginState::Running;
+                self.restarts.push(now);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Errors, restarts and log lines of all plugins
+#[derive(Debug, Default)]
+pub struct PluginHealth {
+    plugins: BTreeMap<String, Tracked>,
+    host: Tracked,
+    log: VecDeque<PluginLogEntry>,
+}
+
+impl PluginHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a line to the plugin log
+    pub fn log(
+        &mut self,
+        plugin: Option<&str>,
+        level: PluginLogLevel,
+        message: impl Into<String>,
+        now: Instant,
+    ) {
+        if self.log.len() >= LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back(PluginLogEntry {
+            time: now,
+            plugin: plugin.map(str::to_string),
+            level,
+            message: message.into(),
+        });
+    }
+
+    /// The plugin log, oldest first
+    pub fn log_entries(&self) -> impl DoubleEndedIterator<Item = &PluginLogEntry> {
+        self.log.iter()
+    }
+
+    /// Record an error thrown by `plugin`. Returns true if the plugin has
+    /// now thrown too many and should be disabled.
+    pub fn record_error(&mut self, plugin: &str, message: &str, now: Instant) -> bool {
+        self.log(Some(plugin), PluginLogLevel::Error, message, now);
+        let tracked = self.plugins.entry(plugin.to_string()).or_default();
+        if tracked.state != PluginState::Running {
+            return false;
+        }
+        let window = Duration::from_secs(ERROR_WINDOW_SECS);
+        tracked.errors.retain(|t| now.duration_since(*t) < window);
+        tracked.errors.push(now);
+        if tracked.errors.len() < MAX_ERRORS_IN_WINDOW {
+            return false;
+        }
+        tracked.disable(message.to_string(), now);
+        true
+    }
+
+    /// Record that restarting `plugin` failed, scheduling another attempt
+    pub fn restart_failed(&mut self, plugin: &str, error: String, now: Instant) {
+        self.log(Some(plugin), PluginLogLevel::Error, error.clone(), now);
+        self.plugins
+            .entry(plugin.to_string())
+            .or_default()
+            .disable(error, now);
+    }
+
+    /// Record that `plugin`'s process stopped, scheduling a restart
+    pub fn plugin_stopped(&mut self, plugin: &str, error: String, now: Instant) {
+        self.log(Some(plugin), PluginLogLevel::Error, error.clone(), now);
+        self.plugins
+            .entry(plugin.to_string())
+            .or_default()
+            .disable(error, now);
+    }
+
+    /// State of `plugin`
+    pub fn state(&self, plugin: &str) -> PluginState {
+        self.plugins
+            .get(plugin)
+            .map(|tracked| tracked.state.clone())
+            .unwrap_or(PluginState::Running)
+    }
+
+    /// Plugins that have logged errors, and their states
+    pub fn states(&self) -> impl Iterator<Item = (&str, &PluginState)> {
+        self.plugins
+            .iter()
+            .map(|(name, tracked)| (name.as_str(), &tracked.state))
+    }
+
+    /// Plugins whose restart is due. They are marked running again.
+    pub fn take_due_restarts(&mut self, now: Instant) -> Vec<String> {
+        self.plugins
+            .iter_mut()
+            .filter_map(|(name, tracked)| tracked.take_restart(now).then(|| name.clone()))
+            .collect()
+    }
+
+    /// Restart a disabled `plugin` (or the host, if None) when restarts are
+    /// next processed, even if it was restarted too often: the user asked
+    pub fn restart_now(&mut self, plugin: Option<&str>, now: Instant) {
+        match plugin {
+            Some(plugin) => {
+                if let Some(tracked) = self.plugins.get_mut(plugin) {
+                    tracked.restart_now(now);
+                }
+            }
+            None => self.host.restart_now(now),
+        }
+    }
+
+    /// Record that the plugin host stopped. Does nothing if it is already
+    /// known to be stopped.
+    pub fn host_stopped(&mut self, error: String, now: Instant) {
+        if self.host.state != PluginState::Running {
+            return;
+        }
+        self.log(None, PluginLogLevel::Error, error.clone(), now);
+        self.host.disable(error, now);
+    }
+
+    /// Record that respawning the plugin host failed, scheduling another
+    /// attempt
+    pub fn host_restart_failed(&mut self, error: String, now: Instant) {
+        self.log(None, PluginLogLevel::Error, error.clone(), now);
+        self.host.disable(error, now);
+    }
+
+    /// State of the plugin host
+    pub fn host_state(&self) -> &PluginState {
+        &self.host.state
+    }
+
+    /// Whether the restart of the plugin host is due. If so, it is marked
+    /// running again, and so are all plugins, since they are all reloaded.
+    pub fn take_host_restart(&mut self, now: Instant) -> bool {
+        if !self.host.take_restart(now) {
+            return false;
+        }
+        for tracked in self.plugins.values_mut() {
+            tracked.state = PluginState::Running;
+            tracked.errors.clear();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_disabled_after_repeated_errors() {
+        let mut health = PluginHealth::new();
+        let start = Instant::now();
+
+        assert!(!health.record_error("todo", "boom", start));
+        // An old error falls out of the window
+        let later = start + Duration::from_secs(ERROR_WINDOW_SECS + 1);
+        assert!(!health.record_error("todo", "boom", later));
+        assert!(!health.record_error("todo", "boom", later));
+        assert_eq!(health.state("todo"), PluginState::Running);
+
+        assert!(health.record_error("todo", "boom", later));
+        assert_eq!(
+            health.state("todo"),
+            PluginState::Disabled {
+                error: "boom".to_string(),
+                restart_at: Some(later + Duration::from_millis(RESTART_BACKOFF_BASE_MS)),
+            }
+        );
+        // Errors from a disabled plugin do not count
+        assert!(!health.record_error("todo", "boom", later));
+        assert_eq!(health.log_entries().count(), 5);
+    }
+
+    #[test]
+    fn test_restart_backoff_doubles_then_gives_up() {
+        let mut health = PluginHealth::new();
+        let mut now = Instant::now();
+
+        for attempt in 0..MAX_RESTARTS_IN_WINDOW {
+            health.restart_failed("todo", "boom".to_string(), now);
+            let delay = Duration::from_millis(RESTART_BACKOFF_BASE_MS << attempt);
+            assert!(health.take_due_restarts(now + delay / 2).is_empty());
+            now += delay;
+            assert_eq!(health.take_due_restarts(now), vec!["todo".to_string()]);
+            assert_eq!(health.state("todo"), PluginState::Running);
+        }
+
+        health.restart_failed("todo", "boom".to_string(), now);
+        assert_eq!(
+            health.state("todo"),
+            PluginState::Disabled {
+                error: "boom".to_string(),
+                restart_at: None,
+            }
+        );
+        assert!(health
+            .take_due_restarts(now + Duration::from_secs(3600))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_restart_now_after_giving_up() {
+        let mut health = PluginHealth::new();
+        let now = Instant::now();
+        health.restart_now(Some("todo"), now);
+        assert!(health.take_due_restarts(now).is_empty());
+
+        for _ in 0..=MAX_RESTARTS_IN_WINDOW {
+            health.restart_failed("todo", "boom".to_string(), now);
+            health.take_due_restarts(now + Duration::from_secs(60));
+        }
+        assert!(matches!(
+            health.state("todo"),
+            PluginState::Disabled {
+                restart_at: None,
+                ..
+            }
+        ));
+        health.restart_now(Some("todo"), now);
+        assert_eq!(health.take_due_restarts(now), vec!["todo".to_string()]);
+        // Its restarts were forgotten, so the next failure backs off again
+        health.restart_failed("todo", "boom".to_string(), now);
+        assert!(matches!(
+            health.state("todo"),
+            PluginState::Disabled {
+                restart_at: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_host_restart_reenables_plugins() {
+        let mut health = PluginHealth::new();
+        let now = Instant::now();
+        health.restart_failed("todo", "boom".to_string(), now);
+
+        health.host_stopped("host panicked".to_string(), now);
+        health.host_stopped("again".to_string(), now);
+        assert!(!health.take_host_restart(now));
+
+        let due = now + Duration::from_millis(RESTART_BACKOFF_BASE_MS);
+        assert!(health.take_host_restart(due));
+        assert_eq!(health.host_state(), &PluginState::Running);
+        assert_eq!(health.state("todo"), PluginState::Running);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut health = PluginHealth::new();
+        let now = Instant::now();
+        for i in 0..LOG_CAPACITY + 10 {
+            health.log(None, PluginLogLevel::Info, i.to_string(), now);
+        }
+        assert_eq!(health.log_entries().count(), LOG_CAPACITY);
+        assert_eq!(health.log_entries().next().unwrap().message, "10");
+    }
+}
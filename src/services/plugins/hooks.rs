@@ -52,6 +52,8 @@ pub enum HookArgs {
         end_line: usize,
         /// Number of lines added by this insertion
         lines_added: usize,
+        /// Version of the buffer after the insertion
+        version: u64,
     },
 
     /// Before text is deleted
@@ -75,6 +77,8 @@ pub enum HookArgs {
         end_line: usize,
         /// Number of lines removed by this deletion
         lines_removed: usize,
+        /// Version of the buffer after the deletion
+        version: u64,
     },
 
     /// Cursor moved to a new position
@@ -227,6 +231,23 @@ pub enum HookArgs {
         /// The request parameters as a JSON string (may be null)
         params: Option<String>,
     },
+
+    /// A buffer is being opened from a URL whose scheme a plugin provides.
+    /// The plugin answers with `editor.completeVfsRead()`.
+    VfsRead {
+        url: String,
+        scheme: String,
+        path: String,
+    },
+
+    /// A buffer opened from a plugin's URL is being saved. The plugin
+    /// answers with `editor.completeVfsWrite()`.
+    VfsWrite {
+        url: String,
+        scheme: String,
+        path: String,
+        content: String,
+    },
 }
 
 /// Information about a single line for the LinesChanged hook
@@ -427,6 +448,7 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
             start_line,
             end_line,
             lines_added,
+            version,
         } => {
             serde_json::json!({
                 "buffer_id": buffer_id.0,
@@ -437,6 +459,7 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "start_line": start_line,
                 "end_line": end_line,
                 "lines_added": lines_added,
+                "version": version,
             })
         }
         HookArgs::BeforeDelete { buffer_id, range } => {
@@ -455,6 +478,7 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
             start_line,
             end_line,
             lines_removed,
+            version,
         } => {
             serde_json::json!({
                 "buffer_id": buffer_id.0,
@@ -466,6 +490,7 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "start_line": start_line,
                 "end_line": end_line,
                 "lines_removed": lines_removed,
+                "version": version,
             })
         }
         HookArgs::BeforeFileOpen { path } => {
@@ -644,6 +669,26 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "params": params,
             })
         }
+        HookArgs::VfsRead { url, scheme, path } => {
+            serde_json::json!({
+                "url": url,
+                "scheme": scheme,
+                "path": path,
+            })
+        }
+        HookArgs::VfsWrite {
+            url,
+            scheme,
+            path,
+            content,
+        } => {
+            serde_json::json!({
+                "url": url,
+                "scheme": scheme,
+                "path": path,
+                "content": content,
+            })
+        }
     };
 
     serde_json::to_string(&json_value).map_err(|e| anyhow!("Failed to serialize hook args: {}", e))
@@ -773,6 +818,7 @@ mod tests {
                 start_line: 0,
                 end_line: 0,
                 lines_added: 0,
+                version: 1,
             },
             HookArgs::BeforeDelete {
                 buffer_id: BufferId(1),
@@ -787,6 +833,7 @@ mod tests {
                 start_line: 0,
                 end_line: 0,
                 lines_removed: 0,
+                version: 2,
             },
             HookArgs::CursorMoved {
                 buffer_id: BufferId(1),
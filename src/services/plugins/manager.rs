@@ -6,19 +6,41 @@
 //! scattered throughout the codebase.
 
 use crate::input::command_registry::CommandRegistry;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+#[cfg(feature = "plugins")]
+use super::remote::{self, RemotePluginHost};
 #[cfg(feature = "plugins")]
 use super::thread::PluginThreadHandle;
 
 /// Unified plugin manager that abstracts over the plugin system.
 ///
-/// When the `plugins` feature is enabled, this wraps `PluginThreadHandle`.
-/// When disabled, all methods are no-ops.
+/// When the `plugins` feature is enabled, this wraps `PluginThreadHandle`
+/// and the remote plugins' processes. When disabled, all methods are no-ops.
 pub struct PluginManager {
     #[cfg(feature = "plugins")]
     inner: Option<PluginThreadHandle>,
+    /// Command registry, kept to respawn the plugin thread
+    #[cfg(feature = "plugins")]
+    command_registry: Arc<RwLock<CommandRegistry>>,
+    /// Plugin directories and files loaded, to load again after a respawn
+    #[cfg(feature = "plugins")]
+    loaded_dirs: Vec<PathBuf>,
+    #[cfg(feature = "plugins")]
+    loaded_files: Vec<PathBuf>,
+    /// Manifests of the remote plugins found, running or not
+    #[cfg(feature = "plugins")]
+    remote_manifests: Vec<PathBuf>,
+    /// Running remote plugins
+    #[cfg(feature = "plugins")]
+    remote_hosts: Vec<RemotePluginHost>,
+    /// Commands sent by the remote plugins
+    #[cfg(feature = "plugins")]
+    remote_commands: (
+        std::sync::mpsc::Sender<super::api::PluginCommand>,
+        std::sync::mpsc::Receiver<super::api::PluginCommand>,
+    ),
     #[cfg(not(feature = "plugins"))]
     _phantom: std::marker::PhantomData<()>,
 }
@@ -32,10 +54,16 @@ impl PluginManager {
         #[cfg(feature = "plugins")]
         {
             if enable {
-                match PluginThreadHandle::spawn(command_registry) {
+                match PluginThreadHandle::spawn(Arc::clone(&command_registry)) {
                     Ok(handle) => {
                         return Self {
                             inner: Some(handle),
+                            command_registry,
+                            loaded_dirs: Vec::new(),
+                            loaded_files: Vec::new(),
+                            remote_manifests: Vec::new(),
+                            remote_hosts: Vec::new(),
+                            remote_commands: std::sync::mpsc::channel(),
                         }
                     }
                     Err(e) => {
@@ -47,7 +75,15 @@ impl PluginManager {
             } else {
                 tracing::info!("Plugins disabled via --no-plugins flag");
             }
-            Self { inner: None }
+            Self {
+                inner: None,
+                command_registry,
+                loaded_dirs: Vec::new(),
+                loaded_files: Vec::new(),
+                remote_manifests: Vec::new(),
+                remote_hosts: Vec::new(),
+                remote_commands: std::sync::mpsc::channel(),
+            }
         }
 
         #[cfg(not(feature = "plugins"))]
@@ -74,14 +110,26 @@ impl PluginManager {
         }
     }
 
-    /// Load plugins from a directory.
-    pub fn load_plugins_from_dir(&self, dir: &Path) -> Vec<String> {
+    /// Load plugins from a directory: the TypeScript plugins, and the remote
+    /// plugins declared by `<name>.remote.json` manifests.
+    pub fn load_plugins_from_dir(&mut self, dir: &Path) -> Vec<String> {
         #[cfg(feature = "plugins")]
         {
-            if let Some(ref manager) = self.inner {
-                return manager.load_plugins_from_dir(dir);
+            let Some(ref manager) = self.inner else {
+                return Vec::new();
+            };
+            self.loaded_dirs.push(dir.to_path_buf());
+            let mut errors = manager.load_plugins_from_dir(dir);
+            for manifest in remote::find_manifests(dir) {
+                if self.remote_manifests.contains(&manifest) {
+                    continue;
+                }
+                self.remote_manifests.push(manifest.clone());
+                if let Err(e) = self.start_remote_plugin(&manifest) {
+                    errors.push(e);
+                }
             }
-            Vec::new()
+            errors
         }
         #[cfg(not(feature = "plugins"))]
         {
@@ -90,10 +138,51 @@ impl PluginManager {
         }
     }
 
+    /// Load and run a single plugin file.
+    pub fn load_plugin(&mut self, path: &Path) -> Result<(), String> {
+        #[cfg(feature = "plugins")]
+        {
+            match self.inner {
+                Some(ref manager) => {
+                    manager.load_plugin(path).map_err(|e| e.to_string())?;
+                    self.loaded_files.push(path.to_path_buf());
+                    Ok(())
+                }
+                None => Err("Plugin system not active".to_string()),
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = path;
+            Err("Compiled without plugin support".to_string())
+        }
+    }
+
+    /// Start the remote plugin declared by `manifest`.
+    #[cfg(feature = "plugins")]
+    fn start_remote_plugin(&mut self, manifest: &Path) -> Result<(), String> {
+        let Some(ref manager) = self.inner else {
+            return Err("Plugin system not active".to_string());
+        };
+        let api = super::api::PluginApi::new(
+            Arc::new(RwLock::new(super::hooks::HookRegistry::new())),
+            Arc::clone(&self.command_registry),
+            self.remote_commands.0.clone(),
+            manager.state_snapshot_handle(),
+        );
+        let host = RemotePluginHost::start(manifest, api)
+            .map_err(|e| format!("Failed to start remote plugin {:?}: {}", manifest, e))?;
+        self.remote_hosts.push(host);
+        Ok(())
+    }
+
     /// Run a hook (fire-and-forget).
     pub fn run_hook(&self, hook_name: &str, args: super::hooks::HookArgs) {
         #[cfg(feature = "plugins")]
         {
+            for host in &self.remote_hosts {
+                host.run_hook(hook_name, &args);
+            }
             if let Some(ref manager) = self.inner {
                 manager.run_hook(hook_name, args);
             }
@@ -122,10 +211,12 @@ impl PluginManager {
     pub fn process_commands(&mut self) -> Vec<super::api::PluginCommand> {
         #[cfg(feature = "plugins")]
         {
-            if let Some(ref mut manager) = self.inner {
-                return manager.process_commands();
-            }
-            Vec::new()
+            let mut commands = match self.inner {
+                Some(ref mut manager) => manager.process_commands(),
+                None => Vec::new(),
+            };
+            commands.extend(self.remote_commands.1.try_iter());
+            commands
         }
         #[cfg(not(feature = "plugins"))]
         {
@@ -145,6 +236,13 @@ impl PluginManager {
         &self,
         action_name: &str,
     ) -> Option<anyhow::Result<super::thread::oneshot::Receiver<anyhow::Result<()>>>> {
+        if let Some(host) = self
+            .remote_hosts
+            .iter()
+            .find(|host| host.has_action(action_name))
+        {
+            return Some(host.execute_action(action_name));
+        }
         self.inner
             .as_ref()
             .map(|m| m.execute_action_async(action_name))
@@ -153,29 +251,190 @@ impl PluginManager {
     /// List all loaded plugins.
     #[cfg(feature = "plugins")]
     pub fn list_plugins(&self) -> Vec<super::runtime::TsPluginInfo> {
-        self.inner
+        let mut plugins = self
+            .inner
             .as_ref()
             .map(|m| m.list_plugins())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        for manifest in &self.remote_manifests {
+            let Some(name) = remote::manifest_plugin_name(manifest) else {
+                continue;
+            };
+            plugins.push(super::runtime::TsPluginInfo {
+                enabled: self.remote_host_index(&name).is_some(),
+                name,
+                path: manifest.clone(),
+            });
+        }
+        plugins
     }
 
-    /// Reload a plugin by name.
+    /// Position of the running remote plugin named `name`.
     #[cfg(feature = "plugins")]
-    pub fn reload_plugin(&self, name: &str) -> anyhow::Result<()> {
-        self.inner
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Plugin system not active"))?
-            .reload_plugin(name)
+    fn remote_host_index(&self, name: &str) -> Option<usize> {
+        self.remote_hosts
+            .iter()
+            .position(|host| host.name() == name)
+    }
+
+    /// Manifest of the remote plugin named `name`, running or not.
+    #[cfg(feature = "plugins")]
+    fn remote_manifest(&self, name: &str) -> Option<PathBuf> {
+        self.remote_manifests
+            .iter()
+            .find(|manifest| remote::manifest_plugin_name(manifest).as_deref() == Some(name))
+            .cloned()
+    }
+
+    /// Names and paths of the loaded plugins, sorted by name.
+    pub fn loaded_plugins(&self) -> Vec<(String, PathBuf)> {
+        #[cfg(feature = "plugins")]
+        {
+            let mut plugins: Vec<(String, PathBuf)> = self
+                .list_plugins()
+                .into_iter()
+                .map(|info| (info.name, info.path))
+                .collect();
+            plugins.sort();
+            plugins
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Reload a plugin by name. A remote plugin's process is restarted.
+    pub fn reload_plugin(&mut self, name: &str) -> Result<(), String> {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(manifest) = self.remote_manifest(name) {
+                if let Some(index) = self.remote_host_index(name) {
+                    self.remote_hosts.remove(index);
+                }
+                return self.start_remote_plugin(&manifest);
+            }
+            match self.inner {
+                Some(ref manager) => manager.reload_plugin(name).map_err(|e| e.to_string()),
+                None => Err("Plugin system not active".to_string()),
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = name;
+            Err("Compiled without plugin support".to_string())
+        }
+    }
+
+    /// Disable a plugin: its event handlers and commands are removed until
+    /// it is reloaded. A remote plugin's process is stopped.
+    pub fn disable_plugin(&mut self, name: &str) -> Result<(), String> {
+        #[cfg(feature = "plugins")]
+        {
+            if self.remote_manifest(name).is_some() {
+                if let Some(index) = self.remote_host_index(name) {
+                    self.remote_hosts.remove(index);
+                }
+                return Ok(());
+            }
+            match self.inner {
+                Some(ref manager) => manager.disable_plugin(name).map_err(|e| e.to_string()),
+                None => Err("Plugin system not active".to_string()),
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = name;
+            Err("Compiled without plugin support".to_string())
+        }
+    }
+
+    /// Check if the plugin thread was started but has stopped, say after a
+    /// panic.
+    pub fn host_stopped(&self) -> bool {
+        #[cfg(feature = "plugins")]
+        {
+            self.inner.as_ref().is_some_and(|m| !m.is_alive())
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            false
+        }
+    }
+
+    /// Respawn the plugin thread and load the plugins again.
+    pub fn restart_host(&mut self) -> Result<(), String> {
+        #[cfg(feature = "plugins")]
+        {
+            if self.inner.is_none() {
+                return Err("Plugin system not active".to_string());
+            }
+            // Dropping the old handle joins its thread
+            self.inner = None;
+            let handle = PluginThreadHandle::spawn(Arc::clone(&self.command_registry))
+                .map_err(|e| format!("Failed to respawn plugin thread: {}", e))?;
+            let mut errors = Vec::new();
+            for dir in &self.loaded_dirs {
+                errors.extend(handle.load_plugins_from_dir(dir));
+            }
+            for path in &self.loaded_files {
+                if let Err(e) = handle.load_plugin(path) {
+                    errors.push(format!("Failed to load {:?}: {}", path, e));
+                }
+            }
+            self.inner = Some(handle);
+            // The remote plugins read the old thread's state snapshot
+            self.remote_hosts.clear();
+            for manifest in self.remote_manifests.clone() {
+                if let Err(e) = self.start_remote_plugin(&manifest) {
+                    errors.push(e);
+                }
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors.join("; "))
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            Err("Compiled without plugin support".to_string())
+        }
+    }
+
+    /// Remove the remote plugins whose process exited, returning their
+    /// names and, if the editor stopped them, why.
+    pub fn take_stopped_remote_plugins(&mut self) -> Vec<(String, Option<String>)> {
+        #[cfg(feature = "plugins")]
+        {
+            let mut stopped = Vec::new();
+            self.remote_hosts.retain(|host| {
+                if host.has_exited() {
+                    stopped.push((host.name().to_string(), host.stop_reason()));
+                    return false;
+                }
+                true
+            });
+            stopped
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            Vec::new()
+        }
     }
 
     /// Check if any handlers are registered for a hook.
     pub fn has_hook_handlers(&self, hook_name: &str) -> bool {
         #[cfg(feature = "plugins")]
         {
-            self.inner
-                .as_ref()
-                .map(|m| m.has_hook_handlers(hook_name))
-                .unwrap_or(false)
+            self.remote_hosts
+                .iter()
+                .any(|host| host.has_hook(hook_name))
+                || self
+                    .inner
+                    .as_ref()
+                    .map(|m| m.has_hook_handlers(hook_name))
+                    .unwrap_or(false)
         }
         #[cfg(not(feature = "plugins"))]
         {
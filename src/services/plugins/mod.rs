@@ -1,6 +1,7 @@
 //! Plugin system
 //!
-//! The plugin system provides TypeScript/JavaScript plugin support using deno_core.
+//! The plugin system provides TypeScript/JavaScript plugin support using deno_core,
+//! and runs plugins written in other languages in their own process (`remote`).
 //! When the `plugins` feature is disabled, only the type definitions (api, hooks, event_hooks)
 //! are available - the actual runtime is excluded to avoid deno dependencies.
 //!
@@ -8,12 +9,16 @@
 
 pub mod api;
 pub mod event_hooks;
+pub mod fetch;
+pub mod health;
 pub mod hooks;
 pub mod manager;
 
 #[cfg(feature = "plugins")]
 pub mod process;
 #[cfg(feature = "plugins")]
+pub mod remote;
+#[cfg(feature = "plugins")]
 pub mod runtime;
 #[cfg(feature = "plugins")]
 pub mod thread;
@@ -2,12 +2,178 @@
 //!
 //! This module enables plugins to spawn external processes asynchronously,
 //! capturing stdout/stderr and notifying via callbacks when complete.
+//!
+//! Streaming processes deliver their stdout and stderr in chunks as they
+//! arrive, followed by the exit code, and take input on stdin. Linters and
+//! formatters use them to show results before the process finishes.
 
 use crate::services::async_bridge::AsyncMessage;
 use std::process::Stdio;
-use std::sync::mpsc;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use std::sync::{mpsc, Arc};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, Command};
+use tokio::sync::Mutex;
+
+/// Bytes read from a streaming process's output at a time
+const STREAM_CHUNK_BYTES: usize = 8192;
+
+/// Something a streaming process did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessEvent {
+    /// A chunk of stdout
+    Stdout(String),
+    /// A chunk of stderr
+    Stderr(String),
+    /// The process exited with this code; -1 if it was killed by a signal.
+    /// Always the last event.
+    Exit(i32),
+}
+
+/// A process whose output is delivered in chunks as it arrives.
+/// Clones are handles to the same process.
+#[derive(Debug, Clone)]
+pub struct StreamingProcess {
+    /// Output chunks and the exit code
+    events: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<ProcessEvent>>>,
+    /// Input of the process, until it is closed
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+    /// OS process ID, to kill the process
+    pid: Option<u32>,
+}
+
+impl StreamingProcess {
+    /// Wait for the next chunk of output, or the exit code. Returns None
+    /// after the exit code has been returned.
+    pub async fn next_event(&self) -> Option<ProcessEvent> {
+        self.events.lock().await.recv().await
+    }
+
+    /// Write `data` to the process's stdin
+    pub async fn write_stdin(&self, data: &[u8]) -> std::io::Result<()> {
+        let mut stdin = self.stdin.lock().await;
+        let stdin = stdin.as_mut().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "stdin is closed")
+        })?;
+        stdin.write_all(data).await?;
+        stdin.flush().await
+    }
+
+    /// Close the process's stdin, so it sees the end of its input. Returns
+    /// false if it was already closed.
+    pub async fn close_stdin(&self) -> bool {
+        self.stdin.lock().await.take().is_some()
+    }
+
+    /// Kill the process. Its remaining output and exit code are still
+    /// delivered.
+    pub fn kill(&self) -> bool {
+        match self.pid {
+            Some(pid) => {
+                kill_pid(pid);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Kill the process with OS process ID `pid`
+pub fn kill_pid(pid: u32) {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+        let _ = kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
+    }
+    #[cfg(not(unix))]
+    {
+        // On non-Unix, try using taskkill
+        let _ = std::process::Command::new("taskkill")
+            .args(["/F", "/PID", &pid.to_string()])
+            .status();
+    }
+}
+
+/// Spawn a process whose stdout and stderr are delivered in chunks as they
+/// arrive, followed by its exit code. Must be called within a tokio runtime.
+pub fn spawn_streaming_process(
+    command: &str,
+    args: &[String],
+    cwd: Option<&str>,
+) -> std::io::Result<StreamingProcess> {
+    let mut cmd = Command::new(command);
+    cmd.args(args);
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.kill_on_drop(true);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+
+    let mut child = cmd.spawn()?;
+    let pid = child.id();
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let (sender, events) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        tokio::join!(
+            forward_output(stdout, &sender, ProcessEvent::Stdout),
+            forward_output(stderr, &sender, ProcessEvent::Stderr),
+        );
+        let exit_code = match child.wait().await {
+            Ok(status) => status.code().unwrap_or(-1),
+            Err(_) => -1,
+        };
+        let _ = sender.send(ProcessEvent::Exit(exit_code));
+    });
+
+    Ok(StreamingProcess {
+        events: Arc::new(Mutex::new(events)),
+        stdin: Arc::new(Mutex::new(stdin)),
+        pid,
+    })
+}
+
+/// Send what `output` produces as events until it closes. A UTF-8 sequence
+/// split between reads is held back until it is complete.
+async fn forward_output<R: AsyncRead + Unpin>(
+    output: Option<R>,
+    sender: &tokio::sync::mpsc::UnboundedSender<ProcessEvent>,
+    event: fn(String) -> ProcessEvent,
+) {
+    let Some(mut output) = output else {
+        return;
+    };
+    let mut buf = vec![0u8; STREAM_CHUNK_BYTES];
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let n = match output.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let complete = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => pending.len(),
+        };
+        if complete == 0 {
+            continue;
+        }
+        let rest = pending.split_off(complete);
+        let chunk = String::from_utf8_lossy(&pending).into_owned();
+        pending = rest;
+        if sender.send(event(chunk)).is_err() {
+            return;
+        }
+    }
+    if !pending.is_empty() {
+        let _ = sender.send(event(String::from_utf8_lossy(&pending).into_owned()));
+    }
+}
 
 /// Spawn an external process for a plugin
 ///
@@ -209,6 +375,68 @@ mod tests {
         }
     }
 
+    /// Collect the events of a streaming process until it exits
+    async fn collect_events(process: StreamingProcess) -> (String, String, i32) {
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        while let Some(event) = process.next_event().await {
+            match event {
+                ProcessEvent::Stdout(chunk) => stdout.push_str(&chunk),
+                ProcessEvent::Stderr(chunk) => stderr.push_str(&chunk),
+                ProcessEvent::Exit(code) => return (stdout, stderr, code),
+            }
+        }
+        panic!("Process ended without an exit event");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_process_output_and_exit() {
+        let process = spawn_streaming_process(
+            "sh",
+            &[
+                "-c".to_string(),
+                "echo out; echo err >&2; exit 3".to_string(),
+            ],
+            None,
+        )
+        .unwrap();
+
+        let (stdout, stderr, exit_code) = collect_events(process).await;
+        assert_eq!(stdout, "out\n");
+        assert_eq!(stderr, "err\n");
+        assert_eq!(exit_code, 3);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_process_stdin() {
+        let process = spawn_streaming_process("cat", &[], None).unwrap();
+        process.write_stdin(b"hello ").await.unwrap();
+        process.write_stdin("wörld".as_bytes()).await.unwrap();
+        assert!(process.close_stdin().await);
+        assert!(!process.close_stdin().await);
+
+        let (stdout, _, exit_code) = collect_events(process).await;
+        assert_eq!(stdout, "hello wörld");
+        assert_eq!(exit_code, 0);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_process_kill() {
+        let process = spawn_streaming_process(
+            "sh",
+            &["-c".to_string(), "echo started; exec sleep 30".to_string()],
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            process.next_event().await,
+            Some(ProcessEvent::Stdout("started\n".to_string()))
+        );
+        assert!(process.kill());
+
+        let (_, _, exit_code) = collect_events(process).await;
+        assert_eq!(exit_code, -1);
+    }
+
     #[tokio::test]
     async fn test_spawn_failing_command() {
         let (sender, receiver) = mpsc::channel();
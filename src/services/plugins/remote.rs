@@ -0,0 +1,820 @@
+//! Remote Plugin Hosts: plugins running in their own process
+//!
+//! A remote plugin is any program that speaks JSON-RPC 2.0 on its stdin and
+//! stdout, one message per line; what it writes to stderr goes to the log.
+//! It is declared by a `<name>.remote.json` manifest in a plugins directory:
+//!
+//! ```json
+//! { "command": "python3", "args": ["word_count.py"] }
+//! ```
+//!
+//! The command runs in the manifest's directory. The editor starts with an
+//! `initialize` request carrying the protocol version and the methods it
+//! offers; the plugin answers with the hooks it wants to receive. After that
+//! the editor sends `hook` notifications and `executeAction` requests, and
+//! the plugin calls the methods of `EDITOR_METHODS`, which map onto
+//! `PluginApi`. The protocol is described in `docs/remote-plugins.md`.
+//!
+//! Nothing here blocks the editor on the plugin: messages go through a
+//! bounded queue to a writer thread, and a plugin that stops reading them
+//! or doesn't answer `initialize` in time is stopped.
+
+use crate::input::commands::{Command, CommandSource};
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::services::plugins::api::{PluginApi, PluginCommand};
+use crate::services::plugins::hooks::{hook_args_to_json, HookArgs};
+use crate::services::plugins::thread::oneshot;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command as ProcessCommand, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Version of the protocol; the plugin must answer `initialize` with it
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// File name suffix of remote plugin manifests
+pub const MANIFEST_SUFFIX: &str = ".remote.json";
+
+/// How long a plugin has to answer `initialize`
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Messages waiting to be written to a plugin before it is stopped
+const WRITE_QUEUE_LEN: usize = 1024;
+
+/// Request id of `initialize`; the editor's other requests count up from 1
+const INITIALIZE_ID: u64 = 0;
+
+/// Methods a remote plugin can call, sent to it in `initialize`
+pub const EDITOR_METHODS: &[&str] = &[
+    "getActiveBuffer",
+    "listBuffers",
+    "getCursor",
+    "getSelectedText",
+    "insertText",
+    "deleteRange",
+    "beginUndoGroup",
+    "endUndoGroup",
+    "insertAtCursor",
+    "openFile",
+    "setStatus",
+    "showNotification",
+    "log",
+    "setClipboard",
+    "registerCommand",
+    "unregisterCommand",
+    "clearNamespace",
+];
+
+/// How to start a remote plugin
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemotePluginManifest {
+    /// Program to run, looked up in PATH or relative to the manifest
+    pub command: String,
+    /// Arguments of the program
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl RemotePluginManifest {
+    /// Read the manifest at `path`
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|e| anyhow!("Invalid manifest {:?}: {}", path, e))
+    }
+}
+
+/// Name of the plugin declared by the manifest at `path`, if it is one
+pub fn manifest_plugin_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    file_name
+        .strip_suffix(MANIFEST_SUFFIX)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// The remote plugin manifests in `dir`, sorted by name
+pub fn find_manifests(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && manifest_plugin_name(path).is_some())
+        .collect();
+    manifests.sort();
+    manifests
+}
+
+/// What the plugin answered to `initialize`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeResult {
+    protocol_version: u64,
+    /// Hooks the plugin wants `hook` notifications for
+    #[serde(default)]
+    hooks: Vec<String>,
+}
+
+/// State shared with the threads reading and writing the plugin's pipes
+struct Shared {
+    name: String,
+    api: PluginApi,
+    child: Mutex<Child>,
+    /// Lines for the writer thread
+    queue: mpsc::SyncSender<String>,
+    /// Editor requests waiting for an answer, by id
+    pending: Mutex<HashMap<u64, oneshot::Sender<Result<()>>>>,
+    /// Hooks the plugin asked for in its answer to `initialize`
+    hooks: Mutex<HashSet<String>>,
+    /// Actions of the commands the plugin registered
+    actions: Mutex<HashSet<String>>,
+    /// Names of the commands the plugin registered
+    commands: Mutex<HashSet<String>>,
+    /// Why the editor stopped the plugin
+    stop_reason: Mutex<Option<String>>,
+}
+
+impl Shared {
+    /// Queue `message` for the plugin, stopping the plugin if it is too far
+    /// behind reading its input
+    fn write(&self, message: &Value) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        match self.queue.try_send(line) {
+            Ok(()) => Ok(()),
+            Err(mpsc::TrySendError::Full(_)) => {
+                self.stop("The plugin stopped reading its input".to_string());
+                Err(anyhow!("Plugin '{}' is not reading its input", self.name))
+            }
+            Err(mpsc::TrySendError::Disconnected(_)) => {
+                Err(anyhow!("Plugin '{}' closed its input", self.name))
+            }
+        }
+    }
+
+    /// Kill the process, remembering `reason` for the editor
+    fn stop(&self, reason: String) {
+        tracing::warn!("Stopping remote plugin '{}': {}", self.name, reason);
+        if let Ok(mut stop_reason) = self.stop_reason.lock() {
+            stop_reason.get_or_insert(reason);
+        }
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.kill();
+        }
+    }
+
+    /// Take the hooks from the answer to `initialize`
+    fn initialize(&self, result: Result<Value>) -> Result<()> {
+        let result: InitializeResult = serde_json::from_value(result?)
+            .map_err(|e| anyhow!("Invalid initialize result: {}", e))?;
+        if result.protocol_version != PROTOCOL_VERSION {
+            return Err(anyhow!(
+                "The plugin speaks protocol version {}, the editor {}",
+                result.protocol_version,
+                PROTOCOL_VERSION
+            ));
+        }
+        if let Ok(mut hooks) = self.hooks.lock() {
+            hooks.extend(result.hooks);
+        }
+        tracing::info!("Started remote plugin '{}'", self.name);
+        Ok(())
+    }
+}
+
+/// A running remote plugin. Dropping it stops the process.
+pub struct RemotePluginHost {
+    shared: Arc<Shared>,
+    next_id: AtomicU64,
+}
+
+impl RemotePluginHost {
+    /// Start the plugin declared by the manifest at `manifest_path` and send
+    /// it `initialize`. Returns without waiting for the answer: the plugin
+    /// gets hooks once it has answered, and is stopped if it doesn't answer
+    /// within `INITIALIZE_TIMEOUT`.
+    pub fn start(manifest_path: &Path, api: PluginApi) -> Result<Self> {
+        let name = manifest_plugin_name(manifest_path)
+            .ok_or_else(|| anyhow!("Not a remote plugin manifest: {:?}", manifest_path))?;
+        let manifest = RemotePluginManifest::read(manifest_path)?;
+        let dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+        // A command like "./server" is relative to the manifest
+        let program = if manifest.command.contains('/') || manifest.command.contains('\\') {
+            dir.join(&manifest.command)
+        } else {
+            PathBuf::from(&manifest.command)
+        };
+        let mut child = ProcessCommand::new(&program)
+            .args(&manifest.args)
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to start {:?}: {}", program, e))?;
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("No stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("No stdout"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("No stderr"))?;
+
+        let (queue, lines) = mpsc::sync_channel(WRITE_QUEUE_LEN);
+        let shared = Arc::new(Shared {
+            name: name.clone(),
+            api,
+            child: Mutex::new(child),
+            queue,
+            pending: Mutex::new(HashMap::new()),
+            hooks: Mutex::new(HashSet::new()),
+            actions: Mutex::new(HashSet::new()),
+            commands: Mutex::new(HashSet::new()),
+            stop_reason: Mutex::new(None),
+        });
+
+        thread::spawn(move || write_messages(stdin, lines));
+        let (init_sender, init_receiver) = mpsc::channel();
+        {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || read_messages(stdout, &shared, init_sender));
+        }
+        {
+            let shared = Arc::clone(&shared);
+            thread::spawn(
+                move || match init_receiver.recv_timeout(INITIALIZE_TIMEOUT) {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => shared.stop(e.to_string()),
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        shared.stop("The plugin did not answer initialize".to_string())
+                    }
+                    // The plugin exited first
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {}
+                },
+            );
+        }
+        {
+            let name = name.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(|line| line.ok()) {
+                    tracing::debug!("Remote plugin '{}': {}", name, line);
+                }
+            });
+        }
+
+        let host = Self {
+            shared,
+            next_id: AtomicU64::new(INITIALIZE_ID + 1),
+        };
+        host.shared.write(&json!({
+            "jsonrpc": "2.0",
+            "id": INITIALIZE_ID,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": PROTOCOL_VERSION,
+                "editorVersion": env!("CARGO_PKG_VERSION"),
+                "name": name,
+                "methods": EDITOR_METHODS,
+            },
+        }))?;
+        Ok(host)
+    }
+
+    /// Name of the plugin, from its manifest's file name
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// Check if the plugin's process has exited
+    pub fn has_exited(&self) -> bool {
+        self.shared
+            .child
+            .lock()
+            .is_ok_and(|mut child| matches!(child.try_wait(), Ok(Some(_))))
+    }
+
+    /// Why the editor stopped the plugin, if it did
+    pub fn stop_reason(&self) -> Option<String> {
+        self.shared.stop_reason.lock().ok()?.clone()
+    }
+
+    /// Check if the plugin asked for `hook_name`
+    pub fn has_hook(&self, hook_name: &str) -> bool {
+        self.shared
+            .hooks
+            .lock()
+            .is_ok_and(|hooks| hooks.contains(hook_name))
+    }
+
+    /// Check if `action_name` belongs to a command the plugin registered
+    pub fn has_action(&self, action_name: &str) -> bool {
+        self.shared
+            .actions
+            .lock()
+            .is_ok_and(|actions| actions.contains(action_name))
+    }
+
+    /// Send a hook to the plugin if it asked for it
+    pub fn run_hook(&self, hook_name: &str, args: &HookArgs) {
+        if !self.has_hook(hook_name) {
+            return;
+        }
+        let args = hook_args_to_json(args)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+            .unwrap_or(Value::Null);
+        let message = json!({
+            "jsonrpc": "2.0",
+            "method": "hook",
+            "params": { "name": hook_name, "args": args },
+        });
+        if let Err(e) = self.shared.write(&message) {
+            tracing::warn!("Failed to send hook to '{}': {}", self.name(), e);
+        }
+    }
+
+    /// Run one of the plugin's actions. The receiver gets the result when
+    /// the plugin answers.
+    pub fn execute_action(&self, action_name: &str) -> Result<oneshot::Receiver<Result<()>>> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = self.shared.pending.lock() {
+            pending.insert(id, sender);
+        }
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "executeAction",
+            "params": { "action": action_name },
+        });
+        if let Err(e) = self.shared.write(&message) {
+            if let Ok(mut pending) = self.shared.pending.lock() {
+                pending.remove(&id);
+            }
+            return Err(anyhow!("Plugin '{}' is not running: {}", self.name(), e));
+        }
+        Ok(receiver)
+    }
+}
+
+impl Drop for RemotePluginHost {
+    fn drop(&mut self) {
+        // Remove the commands it registered, like a disabled TypeScript plugin
+        if let Ok(commands) = self.shared.commands.lock() {
+            for name in commands.iter() {
+                let _ = self
+                    .shared
+                    .api
+                    .send_command(PluginCommand::UnregisterCommand { name: name.clone() });
+            }
+        }
+        let _ = self
+            .shared
+            .write(&json!({ "jsonrpc": "2.0", "method": "shutdown" }));
+        if let Ok(mut child) = self.shared.child.lock() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Write the queued lines to the plugin's stdin until it closes or the
+/// queue is dropped
+fn write_messages(mut stdin: ChildStdin, lines: mpsc::Receiver<String>) {
+    for line in lines {
+        if stdin
+            .write_all(line.as_bytes())
+            .and_then(|()| stdin.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Read the plugin's messages until it exits
+fn read_messages(stdout: impl Read, shared: &Shared, init_sender: mpsc::Sender<Result<()>>) {
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::warn!(
+                    "Invalid message from remote plugin '{}': {}",
+                    shared.name,
+                    e
+                );
+                continue;
+            }
+        };
+
+        if let Some(method) = message.get("method").and_then(Value::as_str) {
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            let result = call_editor(shared, method, params);
+            // Requests have an id and get an answer; notifications don't
+            if let Some(id) = message.get("id") {
+                let response = match result {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err(e) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": -32000, "message": e },
+                    }),
+                };
+                let _ = shared.write(&response);
+            } else if let Err(e) = result {
+                tracing::warn!("Remote plugin '{}' {}: {}", shared.name, method, e);
+            }
+            continue;
+        }
+
+        // An answer to one of the editor's requests
+        let Some(id) = message.get("id").and_then(Value::as_u64) else {
+            continue;
+        };
+        let result = match message.get("error") {
+            Some(error) => Err(anyhow!(
+                "{}",
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            )),
+            None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+        };
+        if id == INITIALIZE_ID {
+            // Before reading further, so the hooks are known when the
+            // plugin's next messages are handled
+            let _ = init_sender.send(shared.initialize(result));
+        } else if let Some(sender) = shared.pending.lock().ok().and_then(|mut p| p.remove(&id)) {
+            let _ = sender.send(result.map(|_| ()));
+        }
+    }
+
+    // Dropping the senders tells whoever waits on them
+    if let Ok(mut pending) = shared.pending.lock() {
+        pending.clear();
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BufferParams {
+    buffer_id: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InsertTextParams {
+    buffer_id: Option<usize>,
+    position: usize,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteRangeParams {
+    buffer_id: Option<usize>,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Deserialize)]
+struct TextParams {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct MessageParams {
+    message: String,
+    #[serde(default = "default_level")]
+    level: String,
+}
+
+fn default_level() -> String {
+    "info".to_string()
+}
+
+#[derive(Deserialize)]
+struct OpenFileParams {
+    path: PathBuf,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct RegisterCommandParams {
+    name: String,
+    #[serde(default)]
+    description: String,
+    action: String,
+}
+
+#[derive(Deserialize)]
+struct NameParams {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClearNamespaceParams {
+    buffer_id: Option<usize>,
+    namespace: String,
+}
+
+fn parse<T: serde::de::DeserializeOwned>(params: Value) -> std::result::Result<T, String> {
+    serde_json::from_value(params).map_err(|e| format!("Invalid params: {}", e))
+}
+
+/// Run a method the plugin called, returning its result
+fn call_editor(shared: &Shared, method: &str, params: Value) -> std::result::Result<Value, String> {
+    let api = &shared.api;
+    // Buffer methods default to the active buffer
+    let buffer = |id: Option<usize>| id.map_or_else(|| api.get_active_buffer_id(), BufferId);
+
+    match method {
+        "getActiveBuffer" => {
+            let id = api.get_active_buffer_id();
+            Ok(api.get_buffer_info(id).map_or(Value::Null, buffer_json))
+        }
+        "listBuffers" => {
+            let mut buffers = api.list_buffers();
+            buffers.sort_by_key(|info| info.id.0);
+            Ok(Value::Array(buffers.into_iter().map(buffer_json).collect()))
+        }
+        "getCursor" => Ok(api.get_primary_cursor().map_or(Value::Null, |cursor| {
+            json!({
+                "position": cursor.position,
+                "selection": cursor.selection.map(|s| json!({ "start": s.start, "end": s.end })),
+            })
+        })),
+        "getSelectedText" => {
+            let snapshot = api.state_snapshot_handle();
+            let text = snapshot.read().ok().and_then(|s| s.selected_text.clone());
+            Ok(text.map_or(Value::Null, Value::String))
+        }
+        "insertText" => {
+            let p: InsertTextParams = parse(params)?;
+            api.insert_text(
+                buffer(p.buffer_id),
+                p.position,
+                p.text,
+                Some(shared.name.clone()),
+            )
+            .map(|_| Value::Null)
+        }
+        "deleteRange" => {
+            let p: DeleteRangeParams = parse(params)?;
+            if p.start > p.end {
+                return Err("start is after end".to_string());
+            }
+            api.delete_range(
+                buffer(p.buffer_id),
+                p.start..p.end,
+                Some(shared.name.clone()),
+            )
+            .map(|_| Value::Null)
+        }
+        "beginUndoGroup" => {
+            let p: BufferParams = parse(params)?;
+            api.begin_undo_group(buffer(p.buffer_id), Some(shared.name.clone()))
+                .map(|_| Value::Null)
+        }
+        "endUndoGroup" => {
+            let p: BufferParams = parse(params)?;
+            api.end_undo_group(buffer(p.buffer_id), Some(shared.name.clone()))
+                .map(|_| Value::Null)
+        }
+        "insertAtCursor" => {
+            let p: TextParams = parse(params)?;
+            api.send_command(PluginCommand::InsertAtCursor { text: p.text })
+                .map(|_| Value::Null)
+        }
+        "openFile" => {
+            let p: OpenFileParams = parse(params)?;
+            api.open_file_at_location(p.path, p.line, p.column)
+                .map(|_| Value::Null)
+        }
+        "setStatus" => {
+            let p: MessageParams = parse(params)?;
+            api.set_status(p.message).map(|_| Value::Null)
+        }
+        "showNotification" => {
+            let p: MessageParams = parse(params)?;
+            api.send_command(PluginCommand::ShowNotification {
+                level: p.level,
+                message: p.message,
+                source: Some(shared.name.clone()),
+            })
+            .map(|_| Value::Null)
+        }
+        "log" => {
+            let p: MessageParams = parse(params)?;
+            api.send_command(PluginCommand::PluginLog {
+                plugin: Some(shared.name.clone()),
+                level: p.level,
+                message: p.message,
+            })
+            .map(|_| Value::Null)
+        }
+        "setClipboard" => {
+            let p: TextParams = parse(params)?;
+            api.send_command(PluginCommand::SetClipboard { text: p.text })
+                .map(|_| Value::Null)
+        }
+        "registerCommand" => {
+            let p: RegisterCommandParams = parse(params)?;
+            if let Ok(mut actions) = shared.actions.lock() {
+                actions.insert(p.action.clone());
+            }
+            if let Ok(mut commands) = shared.commands.lock() {
+                commands.insert(p.name.clone());
+            }
+            api.send_command(PluginCommand::RegisterCommand {
+                command: Command {
+                    name: p.name,
+                    description: p.description,
+                    action: Action::PluginAction(p.action),
+                    contexts: vec![],
+                    custom_contexts: vec![],
+                    source: CommandSource::Plugin(shared.name.clone()),
+                },
+            })
+            .map(|_| Value::Null)
+        }
+        "unregisterCommand" => {
+            let p: NameParams = parse(params)?;
+            if let Ok(mut commands) = shared.commands.lock() {
+                commands.remove(&p.name);
+            }
+            api.send_command(PluginCommand::UnregisterCommand { name: p.name })
+                .map(|_| Value::Null)
+        }
+        "clearNamespace" => {
+            let p: ClearNamespaceParams = parse(params)?;
+            api.clear_namespace(buffer(p.buffer_id), p.namespace)
+                .map(|_| Value::Null)
+        }
+        _ => Err(format!("Unknown method: {}", method)),
+    }
+}
+
+fn buffer_json(info: crate::services::plugins::api::BufferInfo) -> Value {
+    json!({
+        "id": info.id.0,
+        "path": info.path,
+        "modified": info.modified,
+        "length": info.length,
+        "version": info.version,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::command_registry::CommandRegistry;
+    use crate::services::plugins::api::EditorStateSnapshot;
+    use crate::services::plugins::hooks::HookRegistry;
+    use std::sync::RwLock;
+
+    #[test]
+    fn test_manifest_plugin_name() {
+        assert_eq!(
+            manifest_plugin_name(Path::new("/p/word_count.remote.json")),
+            Some("word_count".to_string())
+        );
+        assert_eq!(manifest_plugin_name(Path::new("/p/.remote.json")), None);
+        assert_eq!(manifest_plugin_name(Path::new("/p/word_count.ts")), None);
+    }
+
+    fn test_api() -> PluginApi {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        PluginApi::new(
+            Arc::new(RwLock::new(HookRegistry::new())),
+            Arc::new(RwLock::new(CommandRegistry::new())),
+            tx,
+            Arc::new(RwLock::new(EditorStateSnapshot::new())),
+        )
+    }
+
+    /// A plugin that answers initialize and then never reads its input is
+    /// stopped instead of blocking the editor
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_that_stops_reading_is_stopped() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("stuck.sh"),
+            r#"read -r line
+echo '{"jsonrpc":"2.0","id":0,"result":{"protocolVersion":1,"hooks":["after_file_save"]}}'
+sleep 60
+"#,
+        )
+        .unwrap();
+        let manifest = dir.path().join("stuck.remote.json");
+        std::fs::write(&manifest, r#"{ "command": "sh", "args": ["stuck.sh"] }"#).unwrap();
+
+        let host = RemotePluginHost::start(&manifest, test_api()).unwrap();
+        let started = std::time::Instant::now();
+        while !host.has_hook("after_file_save") {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+        let args = HookArgs::AfterFileSave {
+            buffer_id: BufferId(1),
+            path: PathBuf::from("/tmp/a-file-with-a-rather-long-name.txt"),
+        };
+        while !host.has_exited() {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            host.run_hook("after_file_save", &args);
+        }
+        assert_eq!(
+            host.stop_reason().as_deref(),
+            Some("The plugin stopped reading its input")
+        );
+    }
+
+    /// A plugin that never answers initialize doesn't hold up `start`
+    #[cfg(unix)]
+    #[test]
+    fn test_start_does_not_wait_for_initialize() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manifest = dir.path().join("silent.remote.json");
+        std::fs::write(&manifest, r#"{ "command": "sleep", "args": ["60"] }"#).unwrap();
+
+        let started = std::time::Instant::now();
+        let host = RemotePluginHost::start(&manifest, test_api()).unwrap();
+        assert!(started.elapsed() < INITIALIZE_TIMEOUT);
+        assert!(!host.has_hook("after_file_save"));
+    }
+
+    /// A plugin in shell that asks for `after_file_save`, registers a command
+    /// on `initialize` and sets the status when its action runs
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_plugin_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("echo.sh"),
+            r#"while read -r line; do
+  case "$line" in
+    *'"initialize"'*)
+      echo '{"jsonrpc":"2.0","id":0,"result":{"protocolVersion":1,"hooks":["after_file_save"]}}'
+      echo '{"jsonrpc":"2.0","method":"registerCommand","params":{"name":"Echo","action":"echo_action"}}' ;;
+    *'"executeAction"'*)
+      id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+      echo '{"jsonrpc":"2.0","method":"setStatus","params":{"message":"echoed"}}'
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":null}" ;;
+  esac
+done
+"#,
+        )
+        .unwrap();
+        let manifest = dir.path().join("echo.remote.json");
+        std::fs::write(&manifest, r#"{ "command": "sh", "args": ["echo.sh"] }"#).unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let api = PluginApi::new(
+            Arc::new(RwLock::new(HookRegistry::new())),
+            Arc::new(RwLock::new(CommandRegistry::new())),
+            tx,
+            Arc::new(RwLock::new(EditorStateSnapshot::new())),
+        );
+        let host = RemotePluginHost::start(&manifest, api).unwrap();
+        assert_eq!(host.name(), "echo");
+
+        // Sent after the answer to initialize, so the hooks are known by now
+        match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+            PluginCommand::RegisterCommand { command } => {
+                assert_eq!(command.name, "Echo");
+                assert_eq!(command.source, CommandSource::Plugin("echo".to_string()));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(host.has_hook("after_file_save"));
+        assert!(!host.has_hook("buffer_closed"));
+        assert!(host.has_action("echo_action"));
+
+        let done = host.execute_action("echo_action").unwrap();
+        assert!(done.recv_timeout(Duration::from_secs(5)).unwrap().is_ok());
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            PluginCommand::SetStatus { message } if message == "echoed"
+        ));
+
+        // Stopping the plugin removes its command
+        drop(host);
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            PluginCommand::UnregisterCommand { name } if name == "Echo"
+        ));
+    }
+}
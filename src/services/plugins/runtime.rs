@@ -43,11 +43,12 @@ use crate::input::commands::Suggestion;
 use crate::model::event::BufferId;
 use crate::model::event::SplitId;
 use crate::services::plugins::api::{
-    EditorStateSnapshot, LayoutHints, PluginCommand, ViewTokenWire,
+    EditorStateSnapshot, LayoutHints, MenuPosition, PluginCommand, ViewTokenWire,
 };
+use crate::services::plugins::process::StreamingProcess;
 use anyhow::{anyhow, Result};
 use deno_core::{
-    error::ModuleLoaderError, extension, op2, FastString, JsRuntime, ModuleLoadOptions,
+    error::ModuleLoaderError, extension, op2, v8, FastString, JsRuntime, ModuleLoadOptions,
     ModuleLoadReferrer, ModuleLoadResponse, ModuleSource, ModuleSourceCode, ModuleSpecifier,
     ModuleType, OpState, ResolutionKind, RuntimeOptions,
 };
@@ -60,7 +61,10 @@ use std::sync::{Arc, RwLock};
 // LayoutHints and ViewTransform are defined in plugin_api
 
 /// Custom module loader that transpiles TypeScript to JavaScript
-struct TypeScriptModuleLoader;
+struct TypeScriptModuleLoader {
+    /// Plugin each plugin module file belongs to, given its own `editor`
+    plugin_modules: Rc<RefCell<HashMap<std::path::PathBuf, String>>>,
+}
 
 impl deno_core::ModuleLoader for TypeScriptModuleLoader {
     fn resolve(
@@ -80,6 +84,10 @@ impl deno_core::ModuleLoader for TypeScriptModuleLoader {
         _options: ModuleLoadOptions,
     ) -> ModuleLoadResponse {
         let specifier = module_specifier.clone();
+        let plugin = specifier
+            .to_file_path()
+            .ok()
+            .and_then(|path| self.plugin_modules.borrow().get(&path).cloned());
         let module_load = async move {
             let path = specifier
                 .to_file_path()
@@ -97,6 +105,16 @@ impl deno_core::ModuleLoader for TypeScriptModuleLoader {
             } else {
                 (code, ModuleType::JavaScript)
             };
+            // Shadow the global editor with the plugin's own, on the first
+            // line so line numbers in errors stay the same
+            let code = match plugin {
+                Some(plugin) => format!(
+                    "const editor = globalThis.__pluginEditor({});{}",
+                    serde_json::to_string(&plugin).unwrap_or_default(),
+                    code
+                ),
+                None => code,
+            };
 
             let module_source = ModuleSource::new(
                 module_type,
@@ -170,8 +188,16 @@ struct TsRuntimeState {
     cancellable_processes: Rc<RefCell<HashMap<u64, CancellableProcess>>>,
     /// Process PIDs: process_id -> OS PID (for killing processes that are being waited on)
     process_pids: Rc<RefCell<HashMap<u64, u32>>>,
+    /// Streaming processes: process_id -> handle
+    streaming_processes: Rc<RefCell<HashMap<u64, StreamingProcess>>>,
     /// Next process ID for background processes
     next_process_id: Rc<RefCell<u64>>,
+    /// Declared plugin settings: namespace -> key -> property schema
+    plugin_settings: Rc<RefCell<HashMap<String, serde_json::Value>>>,
+    /// Plugin that registered each event handler and action: function name -> plugin
+    handler_owners: Rc<RefCell<HashMap<String, String>>>,
+    /// Plugin whose module is being loaded or that is making an edit, if any
+    current_plugin: Rc<RefCell<Option<String>>>,
 }
 
 /// Display a transient message in the editor's status bar
@@ -190,6 +216,37 @@ fn op_fresh_set_status(state: &mut OpState, #[string] message: String) {
     tracing::info!("TypeScript plugin set_status: {}", message);
 }
 
+/// Show a toast notification
+///
+/// Notifications stack in the corner of the editor and dismiss themselves after
+/// a timeout. They are also kept in a history the user can review with
+/// "Show Notifications". Prefer setStatus for routine feedback.
+/// @param message - Text to display
+/// @param level - "info" (default), "warning" or "error"
+/// @param source - Name shown alongside the notification (e.g. the plugin name)
+/// @example
+/// editor.showNotification("Formatter not found", "warning", "prettier");
+#[op2]
+fn op_fresh_show_notification(
+    state: &mut OpState,
+    #[string] message: String,
+    #[string] level: Option<String>,
+    #[string] source: Option<String>,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::ShowNotification {
+                level: level.unwrap_or_else(|| "info".to_string()),
+                message,
+                source,
+            })
+            .is_ok();
+    }
+    false
+}
+
 /// Apply a theme by name
 ///
 /// Loads and applies the specified theme immediately. The theme can be a built-in
@@ -262,6 +319,110 @@ fn op_fresh_get_user_config(state: &mut OpState) -> serde_json::Value {
     serde_json::Value::Object(serde_json::Map::new())
 }
 
+/// Declare a plugin's settings
+///
+/// The settings get a page of their own in the settings UI, and their values
+/// are saved under `plugins.<namespace>` in the config file.
+/// @param namespace - Plugin namespace, usually the plugin's name (e.g., "todo_highlighter")
+/// @param settings - Object mapping each key to a JSON Schema property with `type`, `default` and `description` (and optionally `enum`, `minimum`, `maximum`)
+/// @returns true if the settings were declared
+/// @example
+/// editor.defineSettings("todo_highlighter", {
+///   keywords: { type: "array", items: { type: "string" }, default: ["TODO", "FIXME"],
+///               description: "Words to highlight" },
+/// });
+#[op2]
+fn op_fresh_define_settings(
+    state: &mut OpState,
+    #[string] namespace: String,
+    #[serde] settings: serde_json::Value,
+) -> bool {
+    if !settings.is_object() {
+        return false;
+    }
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        runtime_state
+            .plugin_settings
+            .borrow_mut()
+            .insert(namespace.clone(), settings.clone());
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::DefineSettings {
+                namespace,
+                settings,
+            })
+            .is_ok();
+    }
+    false
+}
+
+/// Get the value of a plugin setting
+///
+/// Returns the value from the config file, or the default declared with
+/// defineSettings() if it is not set.
+/// @param namespace - Plugin namespace
+/// @param key - Setting key
+/// @returns The setting's value, or null if it is neither set nor declared
+#[op2]
+#[serde]
+fn op_fresh_get_plugin_setting(
+    state: &mut OpState,
+    #[string] namespace: String,
+    #[string] key: String,
+) -> serde_json::Value {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        if let Ok(snapshot) = runtime_state.state_snapshot.read() {
+            if let Some(value) = snapshot
+                .config
+                .get("plugins")
+                .and_then(|plugins| plugins.get(&namespace))
+                .and_then(|values| values.get(&key))
+            {
+                return value.clone();
+            }
+        }
+        let default = runtime_state
+            .plugin_settings
+            .borrow()
+            .get(&namespace)
+            .and_then(|settings| settings.get(&key))
+            .and_then(|property| property.get("default"))
+            .cloned();
+        if let Some(default) = default {
+            return default;
+        }
+    }
+    serde_json::Value::Null
+}
+
+/// Set a plugin setting and save it to the config file
+/// @param namespace - Plugin namespace
+/// @param key - Setting key
+/// @param value - New value
+/// @returns true if the change was sent to the editor
+#[op2]
+fn op_fresh_set_plugin_setting(
+    state: &mut OpState,
+    #[string] namespace: String,
+    #[string] key: String,
+    #[serde] value: serde_json::Value,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::SetPluginSetting {
+                namespace,
+                key,
+                value,
+            })
+            .is_ok();
+    }
+    false
+}
+
 /// Log a debug message to the editor's trace output
 ///
 /// Messages appear in stderr when running with RUST_LOG=debug.
@@ -272,6 +433,56 @@ fn op_fresh_debug(#[string] message: String) {
     tracing::debug!("TypeScript plugin: {}", message);
 }
 
+/// Write a line to the plugin log
+///
+/// The log is shown by the "Show Plugin Log" command. console.log, console.warn
+/// and console.error write here too.
+/// @param level - "debug", "info", "warn" or "error"
+/// @param message - Text of the line
+#[op2(fast)]
+fn op_fresh_log(state: &mut OpState, #[string] level: String, #[string] message: String) {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let plugin = runtime_state.current_plugin.borrow().clone();
+        let _ = runtime_state.command_sender.send(PluginCommand::PluginLog {
+            plugin,
+            level,
+            message,
+        });
+    }
+}
+
+/// Report an error thrown by an event handler or action
+///
+/// Errors thrown by handlers and actions are reported automatically; call this
+/// for errors a plugin catches itself. A plugin that keeps failing is disabled
+/// and restarted later.
+/// @param handler_name - Name of the global function that failed
+/// @param message - Error message, ideally with its stack trace
+#[op2(fast)]
+fn op_fresh_report_error(
+    state: &mut OpState,
+    #[string] handler_name: String,
+    #[string] message: String,
+) {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let plugin = runtime_state
+            .handler_owners
+            .borrow()
+            .get(&handler_name)
+            .cloned()
+            .or_else(|| runtime_state.current_plugin.borrow().clone());
+        tracing::warn!("Plugin handler '{}' failed: {}", handler_name, message);
+        let _ = runtime_state
+            .command_sender
+            .send(PluginCommand::PluginError {
+                plugin,
+                message: format!("{}: {}", handler_name, message),
+            });
+    }
+}
+
 /// Copy text to the system clipboard
 ///
 /// Copies the provided text to both the internal and system clipboard.
@@ -360,6 +571,244 @@ fn op_fresh_get_buffer_length(state: &mut OpState, buffer_id: u32) -> u32 {
     0
 }
 
+/// Read part of a buffer's text
+///
+/// Reads only the requested byte range, so plugins never copy the whole
+/// buffer. The range is clamped to the buffer's length, and at most 16 MB
+/// can be read per call. Compare
+/// `getBufferInfo(id).version` before and after to notice edits made while
+/// waiting.
+/// @param buffer_id - Target buffer ID
+/// @param start - Start byte offset (inclusive)
+/// @param end - End byte offset (exclusive)
+/// @returns Promise resolving to the text in the range
+#[op2(async)]
+#[string]
+async fn op_fresh_get_buffer_text(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    start: f64,
+    end: f64,
+) -> Result<String, JsErrorBox> {
+    let receiver = {
+        let state = state.borrow();
+        let runtime_state = state
+            .try_borrow::<Rc<RefCell<TsRuntimeState>>>()
+            .ok_or_else(|| JsErrorBox::generic("Failed to get runtime state"))?;
+        let runtime_state = runtime_state.borrow();
+
+        let request_id = {
+            let mut id = runtime_state.next_request_id.borrow_mut();
+            let current = *id;
+            *id += 1;
+            current
+        };
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        {
+            let mut pending = runtime_state.pending_responses.lock().unwrap();
+            pending.insert(request_id, tx);
+        }
+
+        if runtime_state
+            .command_sender
+            .send(PluginCommand::GetBufferText {
+                buffer_id: BufferId(buffer_id as usize),
+                // `as` saturates: a negative offset is 0, and offsets past 4 GiB
+                // are kept instead of wrapping
+                range: start as usize..end as usize,
+                request_id,
+            })
+            .is_err()
+        {
+            let mut pending = runtime_state.pending_responses.lock().unwrap();
+            pending.remove(&request_id);
+            return Err(JsErrorBox::generic("Failed to send command"));
+        }
+
+        rx
+    };
+
+    let response = receiver
+        .await
+        .map_err(|_| JsErrorBox::generic("Response channel closed"))?;
+
+    match response {
+        crate::services::plugins::api::PluginResponse::BufferText { result, .. } => {
+            result.map_err(JsErrorBox::generic)
+        }
+        _ => Err(JsErrorBox::generic(
+            "Unexpected plugin response for buffer text",
+        )),
+    }
+}
+
+/// Send a query about a buffer to the editor and wait for the answer
+async fn query_buffer<T: serde::de::DeserializeOwned>(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    query: crate::services::plugins::api::BufferQuery,
+) -> Result<T, JsErrorBox> {
+    let receiver = {
+        let state = state.borrow();
+        let runtime_state = state
+            .try_borrow::<Rc<RefCell<TsRuntimeState>>>()
+            .ok_or_else(|| JsErrorBox::generic("Failed to get runtime state"))?;
+        let runtime_state = runtime_state.borrow();
+
+        let request_id = {
+            let mut id = runtime_state.next_request_id.borrow_mut();
+            let current = *id;
+            *id += 1;
+            current
+        };
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        {
+            let mut pending = runtime_state.pending_responses.lock().unwrap();
+            pending.insert(request_id, tx);
+        }
+
+        if runtime_state
+            .command_sender
+            .send(PluginCommand::QueryBuffer {
+                buffer_id: BufferId(buffer_id as usize),
+                query,
+                request_id,
+            })
+            .is_err()
+        {
+            let mut pending = runtime_state.pending_responses.lock().unwrap();
+            pending.remove(&request_id);
+            return Err(JsErrorBox::generic("Failed to send command"));
+        }
+
+        rx
+    };
+
+    let response = receiver
+        .await
+        .map_err(|_| JsErrorBox::generic("Response channel closed"))?;
+
+    match response {
+        crate::services::plugins::api::PluginResponse::BufferQuery { result, .. } => {
+            let value = result.map_err(JsErrorBox::generic)?;
+            serde_json::from_value(value).map_err(|e| JsErrorBox::generic(e.to_string()))
+        }
+        _ => Err(JsErrorBox::generic(
+            "Unexpected plugin response for buffer query",
+        )),
+    }
+}
+
+/// Get the number of lines in a buffer
+///
+/// Counted in the editor, without copying the text.
+/// @param buffer_id - Target buffer ID
+/// @returns Promise resolving to the line count, or null while it is unknown (large files)
+#[op2(async)]
+async fn op_fresh_get_line_count(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+) -> Result<Option<u32>, JsErrorBox> {
+    query_buffer(
+        state,
+        buffer_id,
+        crate::services::plugins::api::BufferQuery::LineCount,
+    )
+    .await
+}
+
+/// Get the line containing a byte offset
+/// @param buffer_id - Target buffer ID
+/// @param offset - Byte offset
+/// @returns Promise resolving to the 0-indexed line, or null while line numbers are unknown
+#[op2(async)]
+async fn op_fresh_get_line_at_offset(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    offset: u32,
+) -> Result<Option<u32>, JsErrorBox> {
+    query_buffer(
+        state,
+        buffer_id,
+        crate::services::plugins::api::BufferQuery::LineAtOffset {
+            offset: offset as usize,
+        },
+    )
+    .await
+}
+
+/// Get the byte offset where a line starts
+/// @param buffer_id - Target buffer ID
+/// @param line - 0-indexed line number
+/// @returns Promise resolving to the byte offset, or null if the line does not exist
+#[op2(async)]
+async fn op_fresh_get_line_start_offset(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    line: u32,
+) -> Result<Option<u32>, JsErrorBox> {
+    query_buffer(
+        state,
+        buffer_id,
+        crate::services::plugins::api::BufferQuery::LineStartOffset {
+            line: line as usize,
+        },
+    )
+    .await
+}
+
+/// Options for searching a buffer
+#[derive(Default, serde::Deserialize)]
+struct SearchInBufferOptions {
+    /// Whether the pattern is a regular expression (default false)
+    regex: Option<bool>,
+    /// Whether matching is case sensitive (default true)
+    case_sensitive: Option<bool>,
+    /// Start of the byte range to search (default 0)
+    start: Option<u32>,
+    /// End of the byte range to search (default end of buffer)
+    end: Option<u32>,
+    /// Most matches to return (default and maximum 10000)
+    max_results: Option<u32>,
+}
+
+/// Find the matches of a pattern in a buffer
+///
+/// The search runs in the editor, so plugins don't read the text to search
+/// it. At most 16 MB is searched per call; search larger buffers in parts.
+/// @param buffer_id - Target buffer ID
+/// @param pattern - Text or regular expression to find
+/// @param options - Search options, or null for defaults
+/// @returns Promise resolving to the byte ranges of the matches
+#[op2(async)]
+#[serde]
+async fn op_fresh_search_in_buffer(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    #[string] pattern: String,
+    #[serde] options: Option<SearchInBufferOptions>,
+) -> Result<Vec<TsSelectionRange>, JsErrorBox> {
+    let options = options.unwrap_or_default();
+    let range = match (options.start, options.end) {
+        (None, None) => None,
+        (start, end) => Some(start.unwrap_or(0) as usize..end.map_or(usize::MAX, |e| e as usize)),
+    };
+    query_buffer(
+        state,
+        buffer_id,
+        crate::services::plugins::api::BufferQuery::Search {
+            pattern,
+            regex: options.regex.unwrap_or(false),
+            case_sensitive: options.case_sensitive.unwrap_or(true),
+            range,
+            max_results: options.max_results.map_or(usize::MAX, |n| n as usize),
+        },
+    )
+    .await
+}
+
 /// Check if a buffer has been modified since last save
 ///
 /// Returns false if buffer doesn't exist or has never been saved.
@@ -401,6 +850,7 @@ fn op_fresh_insert_text(
                 buffer_id: BufferId(buffer_id as usize),
                 position: position as usize,
                 text,
+                plugin: runtime_state.current_plugin.borrow().clone(),
             });
         return result.is_ok();
     }
@@ -424,12 +874,68 @@ fn op_fresh_delete_range(state: &mut OpState, buffer_id: u32, start: u32, end: u
             .send(PluginCommand::DeleteRange {
                 buffer_id: BufferId(buffer_id as usize),
                 range: (start as usize)..(end as usize),
+                plugin: runtime_state.current_plugin.borrow().clone(),
+            });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Start grouping a buffer's edits into one undo step
+///
+/// Every edit the plugin makes to the buffer until the matching endUndoGroup
+/// is undone and redone at once; the user's edits and other plugins' don't
+/// join it, and one made in between starts a new step. Groups nest; always
+/// pair with endUndoGroup (use try/finally). Groups left open are closed when
+/// the plugin throws an error. Returns true if command was sent successfully.
+/// @param buffer_id - Target buffer ID
+#[op2(fast)]
+fn op_fresh_begin_undo_group(state: &mut OpState, buffer_id: u32) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::BeginUndoGroup {
+                buffer_id: BufferId(buffer_id as usize),
+                plugin: runtime_state.current_plugin.borrow().clone(),
+            });
+        return result.is_ok();
+    }
+    false
+}
+
+/// End the undo group started by the matching beginUndoGroup
+/// @param buffer_id - Target buffer ID
+#[op2(fast)]
+fn op_fresh_end_undo_group(state: &mut OpState, buffer_id: u32) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::EndUndoGroup {
+                buffer_id: BufferId(buffer_id as usize),
+                plugin: runtime_state.current_plugin.borrow().clone(),
             });
         return result.is_ok();
     }
     false
 }
 
+/// Make the following edits as `plugin` ("" for none), returning the plugin
+/// they were made as before. Used by each plugin's own editor object.
+#[op2]
+#[string]
+fn op_swap_current_plugin(state: &mut OpState, #[string] plugin: String) -> String {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let previous = runtime_state
+            .current_plugin
+            .replace((!plugin.is_empty()).then_some(plugin));
+        return previous.unwrap_or_default();
+    }
+    String::new()
+}
+
 /// Add a colored highlight overlay to text without modifying content
 ///
 /// Overlays are visual decorations that persist until explicitly removed.
@@ -996,6 +1502,10 @@ fn op_fresh_register_command(
         let command_source = if source.is_empty() {
             crate::input::commands::CommandSource::Builtin
         } else {
+            runtime_state
+                .handler_owners
+                .borrow_mut()
+                .insert(action.clone(), source.clone());
             crate::input::commands::CommandSource::Plugin(source)
         };
 
@@ -1049,6 +1559,146 @@ fn op_fresh_set_context(state: &mut OpState, #[string] name: String, active: boo
     false
 }
 
+/// Add an item to the editor's right-click context menu
+/// Items are shown after the built-in ones; adding a label again replaces it.
+/// @param label - Text shown in the menu
+/// @param action - Command or JavaScript function name to run when picked
+/// @param when - Menu context key the item needs to be enabled (e.g., "has_selection"), or ""
+/// @returns true if the item was added
+#[op2(fast)]
+fn op_fresh_add_context_menu_item(
+    state: &mut OpState,
+    #[string] label: String,
+    #[string] action: String,
+    #[string] when: String,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let item = crate::config::MenuItem::Action {
+            label,
+            action,
+            args: HashMap::new(),
+            when: (!when.is_empty()).then_some(when),
+            checkbox: None,
+        };
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::AddContextMenuItem { item });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Remove an item from the editor's right-click context menu
+/// @param label - Label the item was added with
+/// @returns true if the request was sent
+#[op2(fast)]
+fn op_fresh_remove_context_menu_item(state: &mut OpState, #[string] label: String) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::RemoveContextMenuItem { label });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Add a top-level menu to the menu bar, after the built-in menus
+/// Adding a label again replaces that menu.
+/// @param label - Menu label (e.g., "Tools")
+/// @returns true if the menu was added
+#[op2(fast)]
+fn op_fresh_add_menu(state: &mut OpState, #[string] label: String) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let menu = crate::config::Menu {
+            label,
+            items: Vec::new(),
+        };
+        let result = runtime_state.command_sender.send(PluginCommand::AddMenu {
+            menu,
+            position: MenuPosition::Bottom,
+        });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Add an item to the end of a menu bar menu
+/// Adding a label again replaces that item.
+/// @param menu_label - Label of the menu to add to (e.g., "Edit", or one added with addMenu)
+/// @param label - Text shown in the menu
+/// @param action - Command or JavaScript function name to run when picked
+/// @param when - Menu context key the item needs to be enabled (e.g., "has_selection"), or ""
+/// @returns true if the item was added
+#[op2(fast)]
+fn op_fresh_add_menu_item(
+    state: &mut OpState,
+    #[string] menu_label: String,
+    #[string] label: String,
+    #[string] action: String,
+    #[string] when: String,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let item = crate::config::MenuItem::Action {
+            label,
+            action,
+            args: HashMap::new(),
+            when: (!when.is_empty()).then_some(when),
+            checkbox: None,
+        };
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::AddMenuItem {
+                menu_label,
+                item,
+                position: MenuPosition::Bottom,
+            });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Remove an item from a menu bar menu
+/// @param menu_label - Label of the menu holding the item
+/// @param label - Label the item was added with
+/// @returns true if the request was sent
+#[op2(fast)]
+fn op_fresh_remove_menu_item(
+    state: &mut OpState,
+    #[string] menu_label: String,
+    #[string] label: String,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::RemoveMenuItem {
+                menu_label,
+                item_label: label,
+            });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Remove a top-level menu added with addMenu
+/// @param label - Menu label
+/// @returns true if the request was sent
+#[op2(fast)]
+fn op_fresh_remove_menu(state: &mut OpState, #[string] label: String) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::RemoveMenu { menu_label: label });
+        return result.is_ok();
+    }
+    false
+}
+
 /// Open a file in the editor, optionally at a specific location
 /// @param path - File path to open
 /// @param line - Line number to jump to (0 for no jump)
@@ -1058,18 +1708,112 @@ fn op_fresh_set_context(state: &mut OpState, #[string] name: String, active: boo
 fn op_fresh_open_file(state: &mut OpState, #[string] path: String, line: u32, column: u32) -> bool {
     if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
         let runtime_state = runtime_state.borrow();
-        let result = runtime_state
+        let result = runtime_state
+            .command_sender
+            .send(PluginCommand::OpenFileAtLocation {
+                path: std::path::PathBuf::from(path),
+                line: if line == 0 { None } else { Some(line as usize) },
+                column: if column == 0 {
+                    None
+                } else {
+                    Some(column as usize)
+                },
+            });
+        return result.is_ok();
+    }
+    false
+}
+
+/// Open a URL served by a file system provider in a buffer
+///
+/// Built-in providers serve `git://REV/path`, `zip://archive.zip!/path` and
+/// `cmd://command`; plugins add schemes with registerFileSystemProvider.
+/// The buffer appears once the provider has read the content.
+/// @param url - URL to open (e.g. "git://HEAD~1/src/main.rs")
+/// @returns true if the request was sent
+#[op2(fast)]
+fn op_fresh_open_url(state: &mut OpState, #[string] url: String) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::OpenUrl { url })
+            .is_ok();
+    }
+    false
+}
+
+/// Serve URLs with a scheme from this plugin
+///
+/// Opening `<scheme>://...` fires the "vfs_read" hook with { url, scheme, path };
+/// answer it with completeVfsRead. Saving a buffer of a writable provider
+/// fires "vfs_write" with { url, scheme, path, content }; answer it with
+/// completeVfsWrite.
+/// @param scheme - Lowercase URL scheme (e.g. "s3")
+/// @param writable - Whether buffers from this provider can be saved
+/// @returns true if the request was sent
+#[op2(fast)]
+fn op_fresh_register_file_system_provider(
+    state: &mut OpState,
+    #[string] scheme: String,
+    writable: bool,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::RegisterFileSystemProvider { scheme, writable })
+            .is_ok();
+    }
+    false
+}
+
+/// Answer a "vfs_read" hook with the content of a URL, or an error
+/// @param url - URL from the hook
+/// @param content - Content to open, or null on error
+/// @param error - Error shown to the user, or null on success
+/// @returns true if the answer was sent
+#[op2]
+fn op_fresh_complete_vfs_read(
+    state: &mut OpState,
+    #[string] url: String,
+    #[string] content: Option<String>,
+    #[string] error: Option<String>,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = match error {
+            Some(error) => Err(error),
+            None => Ok(content.unwrap_or_default()),
+        };
+        return runtime_state
+            .command_sender
+            .send(PluginCommand::CompleteVfsRead { url, result })
+            .is_ok();
+    }
+    false
+}
+
+/// Answer a "vfs_write" hook once the content is written, or with an error
+/// @param url - URL from the hook
+/// @param error - Error shown to the user, or null on success
+/// @returns true if the answer was sent
+#[op2]
+fn op_fresh_complete_vfs_write(
+    state: &mut OpState,
+    #[string] url: String,
+    #[string] error: Option<String>,
+) -> bool {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        let result = match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        };
+        return runtime_state
             .command_sender
-            .send(PluginCommand::OpenFileAtLocation {
-                path: std::path::PathBuf::from(path),
-                line: if line == 0 { None } else { Some(line as usize) },
-                column: if column == 0 {
-                    None
-                } else {
-                    Some(column as usize)
-                },
-            });
-        return result.is_ok();
+            .send(PluginCommand::CompleteVfsWrite { url, result })
+            .is_ok();
     }
     false
 }
@@ -1174,7 +1918,7 @@ struct SpawnResult {
     exit_code: i32,
 }
 
-/// Result from spawnBackgroundProcess - just the process ID
+/// Result from spawnBackgroundProcess and spawnStreamingProcess - just the process ID
 #[derive(serde::Serialize)]
 struct BackgroundProcessResult {
     /// Unique process ID for later reference (kill, status check)
@@ -1248,12 +1992,13 @@ async fn op_fresh_spawn_background_process(
     Ok(BackgroundProcessResult { process_id })
 }
 
-/// Kill a background or cancellable process by ID
+/// Kill a background, cancellable or streaming process by ID
 ///
 /// Sends SIGTERM to gracefully terminate the process.
 /// Returns true if the process was found and killed, false if not found.
+/// A streaming process still delivers its remaining output and exit code.
 ///
-/// @param process_id - ID returned from spawnBackgroundProcess or spawnProcessStart
+/// @param process_id - ID returned from spawnBackgroundProcess, spawnProcessStart or spawnStreamingProcess
 /// @returns true if process was killed, false if not found
 #[op2(async)]
 async fn op_fresh_kill_process(
@@ -1261,7 +2006,7 @@ async fn op_fresh_kill_process(
     #[bigint] process_id: u64,
 ) -> Result<bool, JsErrorBox> {
     // Try to find and remove from either background_processes or cancellable_processes
-    let (bg_child, cancellable, os_pid) = {
+    let (bg_child, cancellable, os_pid, streaming) = {
         let op_state = state.borrow();
         if let Some(runtime_state) = op_state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
             let runtime_state = runtime_state.borrow();
@@ -1275,7 +2020,13 @@ async fn op_fresh_kill_process(
                 .remove(&process_id);
             // Also get OS PID for fallback kill-by-pid
             let os_pid = runtime_state.process_pids.borrow_mut().remove(&process_id);
-            (bg, cancellable, os_pid)
+            // Streaming processes stay until their exit code has been read
+            let streaming = runtime_state
+                .streaming_processes
+                .borrow()
+                .get(&process_id)
+                .cloned();
+            (bg, cancellable, os_pid, streaming)
         } else {
             return Ok(false);
         }
@@ -1292,20 +2043,10 @@ async fn op_fresh_kill_process(
         // Fallback: kill by OS PID when spawn_process_wait has taken ownership
         // This happens when await-ing the process while trying to kill it
         tracing::trace!(process_id, pid, "killing process by OS PID (fallback)");
-        #[cfg(unix)]
-        {
-            use nix::sys::signal::{kill, Signal};
-            use nix::unistd::Pid;
-            let _ = kill(Pid::from_raw(pid as i32), Signal::SIGKILL);
-        }
-        #[cfg(not(unix))]
-        {
-            // On non-Unix, try using taskkill
-            let _ = std::process::Command::new("taskkill")
-                .args(["/F", "/PID", &pid.to_string()])
-                .status();
-        }
+        crate::services::plugins::process::kill_pid(pid);
         Ok(true)
+    } else if let Some(process) = streaming {
+        Ok(process.kill())
     } else {
         Ok(false)
     }
@@ -1563,6 +2304,306 @@ async fn op_fresh_spawn_process_wait(
     })
 }
 
+/// Chunk of output from a streaming process
+#[derive(serde::Serialize)]
+struct ProcessOutput {
+    /// "stdout", "stderr" or "exit"
+    stream: String,
+    /// Text of the chunk; empty for "exit"
+    data: String,
+    /// Exit code for "exit" (-1 if the process was killed); null otherwise
+    exit_code: Option<i32>,
+}
+
+/// Spawn a process whose output is streamed as it arrives
+///
+/// Read its stdout and stderr chunks and finally its exit code with
+/// readProcessOutput(id), write to it with writeProcessStdin(id, data), and stop
+/// it with killProcess(id). Use it for linters and formatters that report
+/// results while they run.
+///
+/// @param command - Program name (searched in PATH) or absolute path
+/// @param args - Command arguments (each array element is one argument)
+/// @param cwd - Working directory; null uses editor's cwd
+/// @returns Object with process_id for later reference
+/// @example
+/// const proc = await editor.spawnStreamingProcess("eslint", ["--stdin", "--format", "unix"]);
+/// await editor.writeProcessStdin(proc.process_id, source);
+/// await editor.closeProcessStdin(proc.process_id);
+/// let chunk;
+/// while ((chunk = await editor.readProcessOutput(proc.process_id)) !== null) {
+///   if (chunk.stream === "stdout") showResults(chunk.data);
+/// }
+#[op2(async)]
+#[serde]
+async fn op_fresh_spawn_streaming_process(
+    state: Rc<RefCell<OpState>>,
+    #[string] command: String,
+    #[serde] args: Vec<String>,
+    #[string] cwd: Option<String>,
+) -> Result<BackgroundProcessResult, JsErrorBox> {
+    let process =
+        crate::services::plugins::process::spawn_streaming_process(&command, &args, cwd.as_deref())
+            .map_err(|e| JsErrorBox::generic(format!("Failed to spawn process: {}", e)))?;
+
+    let op_state = state.borrow();
+    let Some(runtime_state) = op_state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() else {
+        return Err(JsErrorBox::generic("Runtime state not available"));
+    };
+    let runtime_state = runtime_state.borrow();
+    let mut id = runtime_state.next_process_id.borrow_mut();
+    let process_id = *id;
+    *id += 1;
+    runtime_state
+        .streaming_processes
+        .borrow_mut()
+        .insert(process_id, process);
+    Ok(BackgroundProcessResult { process_id })
+}
+
+/// Get a streaming process's handle by ID
+fn streaming_process(state: &Rc<RefCell<OpState>>, process_id: u64) -> Option<StreamingProcess> {
+    let op_state = state.borrow();
+    let runtime_state = op_state.try_borrow::<Rc<RefCell<TsRuntimeState>>>()?;
+    let runtime_state = runtime_state.borrow();
+    let process = runtime_state
+        .streaming_processes
+        .borrow()
+        .get(&process_id)
+        .cloned();
+    process
+}
+
+/// Wait for the next chunk of output from a streaming process
+///
+/// Chunks of stdout and stderr arrive in the order the process wrote them;
+/// the last chunk has stream "exit" and the exit code.
+/// @param process_id - ID returned from spawnStreamingProcess
+/// @returns The next chunk, or null once the exit code has been read
+#[op2(async)]
+#[serde]
+async fn op_fresh_read_process_output(
+    state: Rc<RefCell<OpState>>,
+    #[bigint] process_id: u64,
+) -> Result<Option<ProcessOutput>, JsErrorBox> {
+    use crate::services::plugins::process::ProcessEvent;
+
+    let Some(process) = streaming_process(&state, process_id) else {
+        return Ok(None);
+    };
+    let output = match process.next_event().await {
+        Some(ProcessEvent::Stdout(data)) => ProcessOutput {
+            stream: "stdout".to_string(),
+            data,
+            exit_code: None,
+        },
+        Some(ProcessEvent::Stderr(data)) => ProcessOutput {
+            stream: "stderr".to_string(),
+            data,
+            exit_code: None,
+        },
+        Some(ProcessEvent::Exit(code)) => ProcessOutput {
+            stream: "exit".to_string(),
+            data: String::new(),
+            exit_code: Some(code),
+        },
+        None => return Ok(None),
+    };
+    if output.exit_code.is_some() {
+        let op_state = state.borrow();
+        if let Some(runtime_state) = op_state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+            let runtime_state = runtime_state.borrow();
+            runtime_state
+                .streaming_processes
+                .borrow_mut()
+                .remove(&process_id);
+        }
+    }
+    Ok(Some(output))
+}
+
+/// Write text to the stdin of a streaming process
+///
+/// @param process_id - ID returned from spawnStreamingProcess
+/// @param data - Text to write
+/// @returns true if it was written, false if the process is not found or its stdin is closed
+#[op2(async)]
+async fn op_fresh_write_process_stdin(
+    state: Rc<RefCell<OpState>>,
+    #[bigint] process_id: u64,
+    #[string] data: String,
+) -> Result<bool, JsErrorBox> {
+    let Some(process) = streaming_process(&state, process_id) else {
+        return Ok(false);
+    };
+    Ok(process.write_stdin(data.as_bytes()).await.is_ok())
+}
+
+/// Close the stdin of a streaming process, so it sees the end of its input
+///
+/// @param process_id - ID returned from spawnStreamingProcess
+/// @returns true if stdin was open, false if it was already closed or the process is not found
+#[op2(async)]
+async fn op_fresh_close_process_stdin(
+    state: Rc<RefCell<OpState>>,
+    #[bigint] process_id: u64,
+) -> Result<bool, JsErrorBox> {
+    let Some(process) = streaming_process(&state, process_id) else {
+        return Ok(false);
+    };
+    Ok(process.close_stdin().await)
+}
+
+/// Options for an HTTP request
+#[derive(Default, serde::Deserialize)]
+struct FetchOptions {
+    /// HTTP method (default "GET")
+    method: Option<String>,
+    /// Request headers
+    headers: Option<HashMap<String, String>>,
+    /// Request body
+    body: Option<String>,
+    /// Timeout in milliseconds (default 30000, at most 120000)
+    timeout_ms: Option<u64>,
+    /// Largest response body to accept in bytes (default and at most 10 MiB)
+    max_bytes: Option<u64>,
+}
+
+/// Response to an HTTP request
+#[derive(serde::Serialize)]
+struct FetchResult {
+    /// HTTP status code
+    status: u32,
+    /// Response headers, with lowercase names
+    headers: HashMap<String, String>,
+    /// Response body as text
+    body: String,
+}
+
+/// Name of the plugin whose module is at `script`, a module URL as it
+/// appears in a stack frame (e.g. "file:///plugins/todo.ts?reload=1")
+fn caller_plugin(script: &str) -> Option<String> {
+    let path = script.strip_prefix("file://")?;
+    let path = path.split('?').next()?;
+    std::path::Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string)
+}
+
+/// Plugin whose code is making the current op call: the innermost stack
+/// frame in a plugin module. Frames of eval'd code are skipped, since such
+/// code can name itself after any module with `//# sourceURL`.
+fn calling_plugin(scope: &mut v8::PinScope) -> Option<String> {
+    let stack = v8::StackTrace::current_stack_trace(scope, CALLER_STACK_FRAMES)?;
+    for index in 0..stack.get_frame_count() {
+        let Some(frame) = stack.get_frame(scope, index) else {
+            continue;
+        };
+        if frame.is_eval() {
+            continue;
+        }
+        let Some(script) = frame.get_script_name(scope) else {
+            continue;
+        };
+        if let Some(plugin) = caller_plugin(&script.to_rust_string_lossy(scope)) {
+            return Some(plugin);
+        }
+    }
+    None
+}
+
+/// Stack frames searched for the plugin making a request
+const CALLER_STACK_FRAMES: usize = 64;
+
+/// Make an HTTP request
+///
+/// The first request of a plugin asks the user whether the plugin may access
+/// the network; the answer is saved in `plugin_network_access` in the config.
+/// Responses with error statuses resolve like any other, and redirects are
+/// not followed (the response has the `location` header); the promise rejects
+/// if access is denied, the request fails or times out, or the body is larger
+/// than max_bytes.
+/// @param url - http:// or https:// URL
+/// @param options - Method, headers, body and limits, or null for a GET
+/// @returns Promise resolving to the status, headers and body
+/// @example
+/// const res = await editor.fetch("https://api.dictionaryapi.dev/api/v2/entries/en/fresh");
+/// if (res.status === 200) editor.setStatus(JSON.parse(res.body)[0].meanings[0].definitions[0].definition);
+#[op2(async)]
+#[serde]
+fn op_fresh_fetch(
+    scope: &mut v8::PinScope,
+    state: Rc<RefCell<OpState>>,
+    #[string] url: String,
+    #[serde] options: Option<FetchOptions>,
+) -> impl std::future::Future<Output = Result<FetchResult, JsErrorBox>> {
+    // Found before the first await, while the caller is still on the stack
+    let plugin = calling_plugin(scope);
+    async move {
+        let options = options.unwrap_or_default();
+        let receiver = {
+            let state = state.borrow();
+            let runtime_state = state
+                .try_borrow::<Rc<RefCell<TsRuntimeState>>>()
+                .ok_or_else(|| JsErrorBox::generic("Failed to get runtime state"))?;
+            let runtime_state = runtime_state.borrow();
+
+            let request_id = {
+                let mut id = runtime_state.next_request_id.borrow_mut();
+                let current = *id;
+                *id += 1;
+                current
+            };
+
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            {
+                let mut pending = runtime_state.pending_responses.lock().unwrap();
+                pending.insert(request_id, tx);
+            }
+
+            let request = crate::services::plugins::fetch::FetchRequest {
+                url,
+                method: options.method,
+                headers: options.headers.unwrap_or_default(),
+                body: options.body,
+                timeout_ms: options.timeout_ms,
+                max_bytes: options.max_bytes,
+            };
+            if runtime_state
+                .command_sender
+                .send(PluginCommand::Fetch {
+                    plugin,
+                    request,
+                    request_id,
+                })
+                .is_err()
+            {
+                let mut pending = runtime_state.pending_responses.lock().unwrap();
+                pending.remove(&request_id);
+                return Err(JsErrorBox::generic("Failed to send command"));
+            }
+
+            rx
+        };
+
+        let response = receiver
+            .await
+            .map_err(|_| JsErrorBox::generic("Response channel closed"))?;
+
+        match response {
+            crate::services::plugins::api::PluginResponse::Fetch { result, .. } => result
+                .map(|response| FetchResult {
+                    status: response.status as u32,
+                    headers: response.headers,
+                    body: response.body,
+                })
+                .map_err(JsErrorBox::generic),
+            _ => Err(JsErrorBox::generic("Unexpected plugin response for fetch")),
+        }
+    }
+}
+
 /// Delay execution for a specified number of milliseconds
 ///
 /// Useful for debouncing user input or adding delays between operations.
@@ -1600,6 +2641,12 @@ fn op_fresh_on(
             .entry(event_name.clone())
             .or_insert_with(Vec::new)
             .push(handler_name.clone());
+        if let Some(plugin) = runtime_state.current_plugin.borrow().clone() {
+            runtime_state
+                .handler_owners
+                .borrow_mut()
+                .insert(handler_name.clone(), plugin);
+        }
         tracing::debug!(
             "Registered event handler '{}' for '{}'",
             handler_name,
@@ -1680,6 +2727,8 @@ struct TsBufferInfo {
     modified: bool,
     /// Buffer length in bytes
     length: u32,
+    /// Version of the buffer content, increased by every edit
+    version: u32,
 }
 
 /// Diff vs last save for a buffer
@@ -1691,7 +2740,7 @@ struct TsBufferSavedDiff {
 }
 
 /// Selection range
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 struct TsSelectionRange {
     /// Start byte position
     start: u32,
@@ -1769,6 +2818,7 @@ fn op_fresh_get_buffer_info(state: &mut OpState, buffer_id: u32) -> Option<TsBuf
                         .unwrap_or_default(),
                     modified: info.modified,
                     length: info.length as u32,
+                    version: info.version as u32,
                 });
             }
         };
@@ -1829,6 +2879,7 @@ fn op_fresh_list_buffers(state: &mut OpState) -> Vec<TsBufferInfo> {
                         .unwrap_or_default(),
                     modified: info.modified,
                     length: info.length as u32,
+                    version: info.version as u32,
                 })
                 .collect();
         };
@@ -2114,7 +3165,8 @@ fn op_fresh_get_env(#[string] name: String) -> Option<String> {
 
 /// Get the editor's current working directory
 ///
-/// Returns the editor's working directory (set when the editor was started).
+/// Returns the editor's working directory (set when the editor was started),
+/// or the active split's own one if it has been given one.
 /// Use as base for resolving relative paths and spawning processes.
 /// Note: This returns the editor's stored working_dir, not process CWD,
 /// which is important for test isolation.
@@ -2133,6 +3185,60 @@ fn op_fresh_get_cwd(state: &mut OpState) -> String {
         .unwrap_or_else(|_| ".".to_string())
 }
 
+/// Get the folders of the workspace
+///
+/// Returns the working directory (as `getCwd()` does) followed by the
+/// folders added to the workspace. Tools that search the project, such as
+/// file finders and grep, should search each of them.
+/// @returns Array of absolute folder paths
+#[op2]
+#[serde]
+fn op_fresh_get_workspace_roots(state: &mut OpState) -> Vec<String> {
+    if let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() {
+        let runtime_state = runtime_state.borrow();
+        if let Ok(snapshot) = runtime_state.state_snapshot.read() {
+            if !snapshot.workspace_roots.is_empty() {
+                return snapshot
+                    .workspace_roots
+                    .iter()
+                    .map(|root| root.to_string_lossy().to_string())
+                    .collect();
+            }
+            return vec![snapshot.working_dir.to_string_lossy().to_string()];
+        };
+    }
+    vec![std::env::current_dir()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| ".".to_string())]
+}
+
+/// Filter ignored files out of a list of paths
+///
+/// A file is ignored when a `.gitignore` or `.ignore` file in its folder or
+/// above says so, or it matches the `ignore_patterns` config setting - the
+/// same files the file explorer hides. Relative paths are resolved against
+/// the working directory.
+/// @param paths - File paths to filter
+/// @returns The paths that are not ignored, in their original order
+#[op2]
+#[serde]
+fn op_fresh_filter_ignored(state: &mut OpState, #[serde] paths: Vec<String>) -> Vec<String> {
+    let Some(runtime_state) = state.try_borrow::<Rc<RefCell<TsRuntimeState>>>() else {
+        return paths;
+    };
+    let runtime_state = runtime_state.borrow();
+    let Ok(snapshot) = runtime_state.state_snapshot.read() else {
+        return paths;
+    };
+    let Ok(mut ignore) = snapshot.ignore_service.write() else {
+        return paths;
+    };
+    paths
+        .into_iter()
+        .filter(|path| !ignore.check_file(&snapshot.working_dir.join(path)))
+        .collect()
+}
+
 /// Join path segments using the OS path separator
 ///
 /// Handles empty segments and normalizes separators.
@@ -2963,20 +4069,34 @@ extension!(
     fresh_runtime,
     ops = [
         op_fresh_set_status,
+        op_fresh_show_notification,
         op_fresh_apply_theme,
         op_fresh_reload_config,
         op_fresh_get_config,
         op_fresh_get_user_config,
+        op_fresh_define_settings,
+        op_fresh_get_plugin_setting,
+        op_fresh_set_plugin_setting,
         op_fresh_debug,
+        op_fresh_log,
+        op_fresh_report_error,
         op_fresh_set_clipboard,
         op_fresh_get_active_buffer_id,
         op_fresh_get_cursor_position,
         op_fresh_get_buffer_path,
         op_fresh_get_buffer_length,
+        op_fresh_get_buffer_text,
+        op_fresh_get_line_count,
+        op_fresh_get_line_at_offset,
+        op_fresh_get_line_start_offset,
+        op_fresh_search_in_buffer,
         op_fresh_get_buffer_saved_diff,
         op_fresh_is_buffer_modified,
         op_fresh_insert_text,
         op_fresh_delete_range,
+        op_fresh_begin_undo_group,
+        op_fresh_end_undo_group,
+        op_swap_current_plugin,
         op_fresh_add_overlay,
         op_fresh_remove_overlay,
         op_fresh_clear_namespace,
@@ -2998,7 +4118,17 @@ extension!(
         op_fresh_register_command,
         op_fresh_unregister_command,
         op_fresh_set_context,
+        op_fresh_add_context_menu_item,
+        op_fresh_remove_context_menu_item,
+        op_fresh_add_menu,
+        op_fresh_add_menu_item,
+        op_fresh_remove_menu_item,
+        op_fresh_remove_menu,
         op_fresh_open_file,
+        op_fresh_open_url,
+        op_fresh_register_file_system_provider,
+        op_fresh_complete_vfs_read,
+        op_fresh_complete_vfs_write,
         op_fresh_get_active_split_id,
         op_fresh_open_file_in_split,
         op_fresh_get_cursor_line,
@@ -3009,6 +4139,11 @@ extension!(
         op_fresh_spawn_background_process,
         op_fresh_kill_process,
         op_fresh_is_process_running,
+        op_fresh_spawn_streaming_process,
+        op_fresh_read_process_output,
+        op_fresh_write_process_stdin,
+        op_fresh_close_process_stdin,
+        op_fresh_fetch,
         op_fresh_get_buffer_info,
         op_fresh_list_buffers,
         op_fresh_get_all_diagnostics,
@@ -3024,6 +4159,8 @@ extension!(
         op_fresh_file_stat,
         op_fresh_get_env,
         op_fresh_get_cwd,
+        op_fresh_get_workspace_roots,
+        op_fresh_filter_ignored,
         op_fresh_path_join,
         op_fresh_path_dirname,
         op_fresh_path_basename,
@@ -3066,6 +4203,16 @@ pub struct TypeScriptRuntime {
     event_handlers: Rc<RefCell<HashMap<String, Vec<String>>>>,
     /// Pending response senders (shared with runtime state for delivering responses)
     pending_responses: PendingResponses,
+    /// Plugin that registered each event handler and action (shared with runtime state)
+    handler_owners: Rc<RefCell<HashMap<String, String>>>,
+    /// Plugin whose module is being loaded (shared with runtime state)
+    current_plugin: Rc<RefCell<Option<String>>>,
+    /// Plugin each plugin module file belongs to (shared with the module loader)
+    plugin_modules: Rc<RefCell<HashMap<std::path::PathBuf, String>>>,
+    /// Module URLs loaded so far, and how many times
+    module_loads: HashMap<String, u32>,
+    /// Plugins disabled after errors, whose actions are not run
+    disabled_plugins: std::collections::HashSet<String>,
 }
 
 impl TypeScriptRuntime {
@@ -3099,6 +4246,9 @@ impl TypeScriptRuntime {
 
         tracing::debug!("TypeScriptRuntime::with_state_and_responses: creating runtime state");
         let event_handlers = Rc::new(RefCell::new(HashMap::new()));
+        let handler_owners = Rc::new(RefCell::new(HashMap::new()));
+        let current_plugin = Rc::new(RefCell::new(None));
+        let plugin_modules = Rc::new(RefCell::new(HashMap::new()));
         let runtime_state = Rc::new(RefCell::new(TsRuntimeState {
             state_snapshot,
             command_sender,
@@ -3108,7 +4258,11 @@ impl TypeScriptRuntime {
             background_processes: Rc::new(RefCell::new(HashMap::new())),
             cancellable_processes: Rc::new(RefCell::new(HashMap::new())),
             process_pids: Rc::new(RefCell::new(HashMap::new())),
+            streaming_processes: Rc::new(RefCell::new(HashMap::new())),
             next_process_id: Rc::new(RefCell::new(1)),
+            plugin_settings: Rc::new(RefCell::new(HashMap::new())),
+            handler_owners: handler_owners.clone(),
+            current_plugin: current_plugin.clone(),
         }));
 
         tracing::debug!(
@@ -3116,7 +4270,9 @@ impl TypeScriptRuntime {
         );
         let js_runtime_start = std::time::Instant::now();
         let mut js_runtime = JsRuntime::new(RuntimeOptions {
-            module_loader: Some(Rc::new(TypeScriptModuleLoader)),
+            module_loader: Some(Rc::new(TypeScriptModuleLoader {
+                plugin_modules: Rc::clone(&plugin_modules),
+            })),
             extensions: vec![fresh_runtime::init()],
             ..Default::default()
         });
@@ -3141,9 +4297,18 @@ impl TypeScriptRuntime {
                     setStatus(message) {
                         core.ops.op_fresh_set_status(message);
                     },
+                    showNotification(message, level = null, source = null) {
+                        return core.ops.op_fresh_show_notification(message, level, source);
+                    },
                     debug(message) {
                         core.ops.op_fresh_debug(message);
                     },
+                    log(level, message) {
+                        core.ops.op_fresh_log(level, message);
+                    },
+                    reportError(handlerName, message) {
+                        core.ops.op_fresh_report_error(handlerName, message);
+                    },
 
                     // Theme operations
                     applyTheme(themeName) {
@@ -3160,6 +4325,15 @@ impl TypeScriptRuntime {
                     getUserConfig() {
                         return core.ops.op_fresh_get_user_config();
                     },
+                    defineSettings(namespace, settings) {
+                        return core.ops.op_fresh_define_settings(namespace, settings);
+                    },
+                    getPluginSetting(namespace, key) {
+                        return core.ops.op_fresh_get_plugin_setting(namespace, key);
+                    },
+                    setPluginSetting(namespace, key, value) {
+                        return core.ops.op_fresh_set_plugin_setting(namespace, key, value);
+                    },
 
                     // Clipboard
                     copyToClipboard(text) {
@@ -3179,6 +4353,21 @@ impl TypeScriptRuntime {
                     getBufferLength(bufferId) {
                         return core.ops.op_fresh_get_buffer_length(bufferId);
                     },
+                    getBufferText(bufferId, start, end) {
+                        return core.ops.op_fresh_get_buffer_text(bufferId, start, end);
+                    },
+                    getLineCount(bufferId) {
+                        return core.ops.op_fresh_get_line_count(bufferId);
+                    },
+                    getLineAtOffset(bufferId, offset) {
+                        return core.ops.op_fresh_get_line_at_offset(bufferId, offset);
+                    },
+                    getLineStartOffset(bufferId, line) {
+                        return core.ops.op_fresh_get_line_start_offset(bufferId, line);
+                    },
+                    searchInBuffer(bufferId, pattern, options = null) {
+                        return core.ops.op_fresh_search_in_buffer(bufferId, pattern, options);
+                    },
                     getBufferSavedDiff(bufferId) {
                         return core.ops.op_fresh_get_buffer_saved_diff(bufferId);
                     },
@@ -3193,6 +4382,12 @@ impl TypeScriptRuntime {
                     deleteRange(bufferId, start, end) {
                         return core.ops.op_fresh_delete_range(bufferId, start, end);
                     },
+                    beginUndoGroup(bufferId) {
+                        return core.ops.op_fresh_begin_undo_group(bufferId);
+                    },
+                    endUndoGroup(bufferId) {
+                        return core.ops.op_fresh_end_undo_group(bufferId);
+                    },
 
                     // Overlays
                     // namespace: group overlays together for efficient batch removal
@@ -3281,10 +4476,50 @@ impl TypeScriptRuntime {
                         return core.ops.op_fresh_set_context(name, active);
                     },
 
+                    // Context menu
+                    addContextMenuItem(label, action, when = "") {
+                        return core.ops.op_fresh_add_context_menu_item(label, action, when);
+                    },
+
+                    removeContextMenuItem(label) {
+                        return core.ops.op_fresh_remove_context_menu_item(label);
+                    },
+
+                    // Menu bar
+                    addMenu(label) {
+                        return core.ops.op_fresh_add_menu(label);
+                    },
+
+                    addMenuItem(menu_label, label, action, when = "") {
+                        return core.ops.op_fresh_add_menu_item(menu_label, label, action, when);
+                    },
+
+                    removeMenuItem(menu_label, label) {
+                        return core.ops.op_fresh_remove_menu_item(menu_label, label);
+                    },
+
+                    removeMenu(label) {
+                        return core.ops.op_fresh_remove_menu(label);
+                    },
+
                     // File operations
                     openFile(path, line = 0, column = 0) {
                         return core.ops.op_fresh_open_file(path, line, column);
                     },
+                    openUrl(url) {
+                        return core.ops.op_fresh_open_url(url);
+                    },
+
+                    // File system providers
+                    registerFileSystemProvider(scheme, writable = false) {
+                        return core.ops.op_fresh_register_file_system_provider(scheme, writable);
+                    },
+                    completeVfsRead(url, content, error = null) {
+                        return core.ops.op_fresh_complete_vfs_read(url, content ?? null, error);
+                    },
+                    completeVfsWrite(url, error = null) {
+                        return core.ops.op_fresh_complete_vfs_write(url, error);
+                    },
 
                     // Split operations
                     getActiveSplitId() {
@@ -3365,6 +4600,21 @@ impl TypeScriptRuntime {
                     isProcessRunning(processId) {
                         return core.ops.op_fresh_is_process_running(processId);
                     },
+                    spawnStreamingProcess(command, args = [], cwd = null) {
+                        return core.ops.op_fresh_spawn_streaming_process(command, args, cwd);
+                    },
+                    readProcessOutput(processId) {
+                        return core.ops.op_fresh_read_process_output(processId);
+                    },
+                    writeProcessStdin(processId, data) {
+                        return core.ops.op_fresh_write_process_stdin(processId, data);
+                    },
+                    closeProcessStdin(processId) {
+                        return core.ops.op_fresh_close_process_stdin(processId);
+                    },
+                    fetch(url, options = null) {
+                        return core.ops.op_fresh_fetch(url, options);
+                    },
                     sendLspRequest(language, method, params = null) {
                         return core.ops.op_fresh_send_lsp_request(language, method, params);
                     },
@@ -3390,6 +4640,12 @@ impl TypeScriptRuntime {
                     getCwd() {
                         return core.ops.op_fresh_get_cwd();
                     },
+                    getWorkspaceRoots() {
+                        return core.ops.op_fresh_get_workspace_roots();
+                    },
+                    filterIgnored(paths) {
+                        return core.ops.op_fresh_filter_ignored(paths);
+                    },
 
                     // Path operations
                     pathJoin(...parts) {
@@ -3470,15 +4726,55 @@ impl TypeScriptRuntime {
                 // Make editor globally available
                 globalThis.editor = editor;
 
+                // Each plugin module gets its own editor, whose edits are
+                // made as the plugin's so that they join only its undo groups
+                const asPlugin = (plugin, edit) => {
+                    const previous = core.ops.op_swap_current_plugin(plugin);
+                    try {
+                        return edit();
+                    } finally {
+                        core.ops.op_swap_current_plugin(previous);
+                    }
+                };
+                globalThis.__pluginEditor = (plugin) => Object.assign(Object.create(editor), {
+                    insertText: (...args) => asPlugin(plugin, () => editor.insertText(...args)),
+                    deleteRange: (...args) => asPlugin(plugin, () => editor.deleteRange(...args)),
+                    beginUndoGroup: (...args) =>
+                        asPlugin(plugin, () => editor.beginUndoGroup(...args)),
+                    endUndoGroup: (...args) => asPlugin(plugin, () => editor.endUndoGroup(...args)),
+                });
+
+                // Send console output to the plugin log
+                const formatLogArgs = (args) => args
+                    .map((arg) => {
+                        if (typeof arg === 'string') return arg;
+                        if (arg instanceof Error) return arg.stack || String(arg);
+                        try { return JSON.stringify(arg); } catch { return String(arg); }
+                    })
+                    .join(' ');
+                for (const level of ['log', 'info', 'warn', 'error', 'debug']) {
+                    globalThis.console[level] = (...args) =>
+                        core.ops.op_fresh_log(level, formatLogArgs(args));
+                }
+                globalThis.__reportError = function(handlerName, error) {
+                    const message = (error && error.stack) || String(error);
+                    core.ops.op_fresh_report_error(handlerName, message);
+                };
+
                 // Pre-compiled event dispatcher for performance
                 // This avoids recompiling JavaScript code for each event emission
                 globalThis.__eventDispatcher = async function(handlerName, eventData) {
                     const handler = globalThis[handlerName];
                     if (typeof handler === 'function') {
-                        const result = handler(eventData);
-                        const finalResult = (result instanceof Promise) ? await result : result;
-                        // Return true by default if handler doesn't return anything
-                        return finalResult !== false;
+                        try {
+                            const result = handler(eventData);
+                            const finalResult = (result instanceof Promise) ? await result : result;
+                            // Return true by default if handler doesn't return anything
+                            return finalResult !== false;
+                        } catch (error) {
+                            globalThis.__reportError(handlerName, error);
+                            return true;
+                        }
                     } else {
                         console.warn('Event handler "' + handlerName + '" is not defined');
                         return true;
@@ -3497,6 +4793,11 @@ impl TypeScriptRuntime {
             js_runtime,
             event_handlers,
             pending_responses,
+            handler_owners,
+            current_plugin,
+            plugin_modules,
+            module_loads: HashMap::new(),
+            disabled_plugins: std::collections::HashSet::new(),
         })
     }
 
@@ -3512,6 +4813,13 @@ impl TypeScriptRuntime {
             crate::services::plugins::api::PluginResponse::LspRequest { request_id, .. } => {
                 *request_id
             }
+            crate::services::plugins::api::PluginResponse::BufferText { request_id, .. } => {
+                *request_id
+            }
+            crate::services::plugins::api::PluginResponse::BufferQuery { request_id, .. } => {
+                *request_id
+            }
+            crate::services::plugins::api::PluginResponse::Fetch { request_id, .. } => *request_id,
         };
 
         let sender = {
@@ -3569,16 +4877,54 @@ impl TypeScriptRuntime {
             .execute_script("<set_plugin_source>", set_source)
             .map_err(|e| anyhow!("Failed to set plugin source: {}", e))?;
 
-        let module_specifier = deno_core::resolve_path(
+        let mut module_specifier = deno_core::resolve_path(
             path,
             &std::env::current_dir().map_err(|e| anyhow!("Failed to get cwd: {}", e))?,
         )
         .map_err(|e| anyhow!("Failed to resolve module path '{}': {}", path, e))?;
 
+        // A module is only evaluated once per URL, so a reload needs a new one
+        let loads = self
+            .module_loads
+            .entry(module_specifier.to_string())
+            .or_insert(0);
+        if *loads > 0 {
+            module_specifier.set_query(Some(&format!("reload={}", loads)));
+        }
+        *loads += 1;
+
+        self.disabled_plugins.remove(plugin_source);
+        if !plugin_source.is_empty() {
+            if let Ok(path) = module_specifier.to_file_path() {
+                self.plugin_modules
+                    .borrow_mut()
+                    .insert(path, plugin_source.to_string());
+            }
+        }
+        *self.current_plugin.borrow_mut() =
+            (!plugin_source.is_empty()).then(|| plugin_source.to_string());
+        let result = self.evaluate_module(&module_specifier, path).await;
+        *self.current_plugin.borrow_mut() = None;
+
+        // Clear the plugin source after loading
+        let clear_source: FastString = "globalThis.__PLUGIN_SOURCE__ = null;".to_string().into();
+        self.js_runtime
+            .execute_script("<clear_plugin_source>", clear_source)
+            .map_err(|e| anyhow!("Failed to clear plugin source: {}", e))?;
+
+        result
+    }
+
+    /// Load and evaluate the module at `module_specifier`
+    async fn evaluate_module(
+        &mut self,
+        module_specifier: &deno_core::ModuleSpecifier,
+        path: &str,
+    ) -> Result<()> {
         // Use load_side_es_module for plugins (allows multiple modules to be loaded)
         let mod_id = self
             .js_runtime
-            .load_side_es_module(&module_specifier)
+            .load_side_es_module(module_specifier)
             .await
             .map_err(|e| anyhow!("Failed to load module '{}': {}", path, e))?;
 
@@ -3591,33 +4937,49 @@ impl TypeScriptRuntime {
 
         result
             .await
-            .map_err(|e| anyhow!("Module evaluation error: {}", e))?;
+            .map_err(|e| anyhow!("Module evaluation error: {}", e))
+    }
 
-        // Clear the plugin source after loading
-        let clear_source: FastString = "globalThis.__PLUGIN_SOURCE__ = null;".to_string().into();
-        self.js_runtime
-            .execute_script("<clear_plugin_source>", clear_source)
-            .map_err(|e| anyhow!("Failed to clear plugin source: {}", e))?;
+    /// Remove the event handlers a plugin registered
+    pub fn remove_plugin_handlers(&mut self, plugin: &str) {
+        let owners = self.handler_owners.borrow();
+        for handlers in self.event_handlers.borrow_mut().values_mut() {
+            handlers.retain(|handler| owners.get(handler).map(String::as_str) != Some(plugin));
+        }
+    }
 
-        Ok(())
+    /// Remove the event handlers of a plugin and stop running its actions,
+    /// until it is loaded again
+    pub fn disable_plugin(&mut self, plugin: &str) {
+        self.remove_plugin_handlers(plugin);
+        self.disabled_plugins.insert(plugin.to_string());
     }
 
     /// Execute a global function by name (for plugin actions)
     pub async fn execute_action(&mut self, action_name: &str) -> Result<()> {
+        if let Some(plugin) = self.handler_owners.borrow().get(action_name) {
+            if self.disabled_plugins.contains(plugin) {
+                return Err(anyhow!("Plugin '{}' is disabled after errors", plugin));
+            }
+        }
         let code = format!(
             r#"
             (async () => {{
-                if (typeof globalThis.{} === 'function') {{
-                    const result = globalThis.{}();
-                    if (result instanceof Promise) {{
-                        await result;
+                if (typeof globalThis.{0} === 'function') {{
+                    try {{
+                        const result = globalThis.{0}();
+                        if (result instanceof Promise) {{
+                            await result;
+                        }}
+                    }} catch (error) {{
+                        globalThis.__reportError("{0}", error);
                     }}
                 }} else {{
-                    throw new Error('Action "{}" is not defined as a global function');
+                    throw new Error('Action "{0}" is not defined as a global function');
                 }}
             }})();
             "#,
-            action_name, action_name, action_name
+            action_name
         );
 
         self.execute_script("<action>", &code).await
@@ -4027,6 +5389,19 @@ mod tests {
         assert!(runtime.is_ok(), "Failed to create TypeScript runtime");
     }
 
+    #[test]
+    fn test_caller_plugin() {
+        assert_eq!(
+            caller_plugin("file:///home/me/.config/fresh/plugins/dictionary.ts"),
+            Some("dictionary".to_string())
+        );
+        assert_eq!(
+            caller_plugin("file:///plugins/dictionary.ts?reload=2"),
+            Some("dictionary".to_string())
+        );
+        assert_eq!(caller_plugin("ext:core/01_core.js"), None);
+    }
+
     #[tokio::test]
     async fn test_execute_simple_script() {
         let mut runtime = TypeScriptRuntime::new().unwrap();
@@ -4145,6 +5520,7 @@ mod tests {
                     path: Some(PathBuf::from("/test/file.rs")),
                     modified: true,
                     length: 1000,
+                    version: 0,
                 },
             );
             snapshot.primary_cursor = Some(CursorInfo {
@@ -4247,6 +5623,7 @@ mod tests {
                 buffer_id,
                 position,
                 text,
+                ..
             } => {
                 assert_eq!(buffer_id.0, 42);
                 assert_eq!(*position, 50);
@@ -4256,7 +5633,9 @@ mod tests {
         }
 
         match &commands[2] {
-            PluginCommand::DeleteRange { buffer_id, range } => {
+            PluginCommand::DeleteRange {
+                buffer_id, range, ..
+            } => {
                 assert_eq!(buffer_id.0, 42);
                 assert_eq!(range.start, 10);
                 assert_eq!(range.end, 20);
@@ -4308,7 +5687,8 @@ mod tests {
                 const methods = [
                     'setStatus', 'debug', 'getActiveBufferId', 'getCursorPosition',
                     'getBufferPath', 'getBufferLength', 'isBufferModified',
-                    'insertText', 'deleteRange', 'addOverlay', 'removeOverlay'
+                    'insertText', 'deleteRange', 'beginUndoGroup', 'endUndoGroup',
+                    'addOverlay', 'removeOverlay'
                 ];
 
                 for (const method of methods) {
@@ -5762,6 +7142,46 @@ mod tests {
         assert!(has_status, "Expected SetStatus with imported MESSAGE");
     }
 
+    #[tokio::test]
+    async fn test_ts_plugin_edits_are_made_as_the_plugin() {
+        use tempfile::TempDir;
+
+        let hooks = Arc::new(RwLock::new(HookRegistry::new()));
+        let commands = Arc::new(RwLock::new(CommandRegistry::new()));
+        let mut manager = TypeScriptPluginManager::new(hooks, commands).unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_path = temp_dir.path().join("grouper.ts");
+        std::fs::write(
+            &plugin_path,
+            r#"
+            globalThis.grouper_edit = async function(): Promise<void> {
+                editor.beginUndoGroup(1);
+                await Promise.resolve();
+                editor.insertText(1, 0, "x");
+                editor.deleteRange(1, 0, 1);
+                editor.endUndoGroup(1);
+            };
+            "#,
+        )
+        .unwrap();
+        manager.load_plugin(&plugin_path).await.unwrap();
+        manager.execute_action("grouper_edit").await.unwrap();
+
+        let plugins: Vec<_> = manager
+            .process_commands()
+            .into_iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::InsertText { plugin, .. }
+                | PluginCommand::DeleteRange { plugin, .. }
+                | PluginCommand::BeginUndoGroup { plugin, .. }
+                | PluginCommand::EndUndoGroup { plugin, .. } => Some(plugin),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(plugins, vec![Some("grouper".to_string()); 4]);
+    }
+
     #[test]
     fn test_plugin_thread_load_plugin_with_import() {
         use crate::services::plugins::thread::PluginThreadHandle;
@@ -49,6 +49,12 @@ pub enum PluginRequest {
         response: oneshot::Sender<Result<()>>,
     },
 
+    /// Disable a plugin by name until it is reloaded
+    DisablePlugin {
+        name: String,
+        response: oneshot::Sender<Result<()>>,
+    },
+
     /// Execute a plugin action
     ExecuteAction {
         action_name: String,
@@ -205,15 +211,28 @@ impl PluginThreadHandle {
             // Create internal manager state
             let mut plugins: HashMap<String, TsPluginInfo> = HashMap::new();
 
-            // Run the event loop with a LocalSet to allow concurrent task execution
+            // Run the event loop with a LocalSet to allow concurrent task execution.
+            // A panic ends the thread; the editor notices and spawns a new one.
             tracing::debug!("Plugin thread: starting event loop with LocalSet");
             let local = tokio::task::LocalSet::new();
-            local.block_on(&rt, async {
-                // Wrap runtime in RefCell for interior mutability during concurrent operations
-                let runtime = Rc::new(RefCell::new(runtime));
-                tracing::debug!("Plugin thread: entering plugin_thread_loop");
-                plugin_thread_loop(runtime, &mut plugins, &thread_commands, request_receiver).await;
-            });
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                local.block_on(&rt, async {
+                    // Wrap runtime in RefCell for interior mutability during concurrent operations
+                    let runtime = Rc::new(RefCell::new(runtime));
+                    tracing::debug!("Plugin thread: entering plugin_thread_loop");
+                    plugin_thread_loop(runtime, &mut plugins, &thread_commands, request_receiver)
+                        .await;
+                })
+            }));
+            if let Err(panic) = result {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                tracing::error!("Plugin thread panicked: {}", message);
+                return;
+            }
 
             tracing::info!("Plugin thread shutting down");
         });
@@ -295,6 +314,26 @@ impl PluginThreadHandle {
         rx.recv().map_err(|_| anyhow!("Plugin thread closed"))?
     }
 
+    /// Disable a plugin until it is reloaded (blocking)
+    pub fn disable_plugin(&self, name: &str) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.request_sender
+            .send(PluginRequest::DisablePlugin {
+                name: name.to_string(),
+                response: tx,
+            })
+            .map_err(|_| anyhow!("Plugin thread not responding"))?;
+
+        rx.recv().map_err(|_| anyhow!("Plugin thread closed"))?
+    }
+
+    /// Check if the plugin thread is still running
+    pub fn is_alive(&self) -> bool {
+        self.thread_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
     /// Execute a plugin action (non-blocking)
     ///
     /// Returns a receiver that will receive the result when the action completes.
@@ -404,6 +443,11 @@ fn respond_to_pending(
             request_id, ..
         } => *request_id,
         crate::services::plugins::api::PluginResponse::LspRequest { request_id, .. } => *request_id,
+        crate::services::plugins::api::PluginResponse::BufferText { request_id, .. } => *request_id,
+        crate::services::plugins::api::PluginResponse::BufferQuery { request_id, .. } => {
+            *request_id
+        }
+        crate::services::plugins::api::PluginResponse::Fetch { request_id, .. } => *request_id,
     };
 
     let sender = {
@@ -617,7 +661,7 @@ async fn handle_request(
         }
 
         PluginRequest::UnloadPlugin { name, response } => {
-            let result = unload_plugin_internal(plugins, commands, &name);
+            let result = unload_plugin_internal(Rc::clone(&runtime), plugins, commands, &name);
             let _ = response.send(result);
         }
 
@@ -627,6 +671,11 @@ async fn handle_request(
             let _ = response.send(result);
         }
 
+        PluginRequest::DisablePlugin { name, response } => {
+            let result = disable_plugin_internal(Rc::clone(&runtime), plugins, &name);
+            let _ = response.send(result);
+        }
+
         PluginRequest::ExecuteAction {
             action_name,
             response,
@@ -791,12 +840,14 @@ async fn load_plugins_from_dir_internal(
 
 /// Unload a plugin
 fn unload_plugin_internal(
+    runtime: Rc<RefCell<TypeScriptRuntime>>,
     plugins: &mut HashMap<String, TsPluginInfo>,
     commands: &Arc<RwLock<CommandRegistry>>,
     name: &str,
 ) -> Result<()> {
     if plugins.remove(name).is_some() {
         tracing::info!("Unloading TypeScript plugin: {}", name);
+        runtime.borrow_mut().remove_plugin_handlers(name);
 
         // Remove plugin's commands (assuming they're prefixed with plugin name)
         let prefix = format!("{}:", name);
@@ -808,6 +859,21 @@ fn unload_plugin_internal(
     }
 }
 
+/// Disable a plugin, keeping it listed so it can be reloaded
+fn disable_plugin_internal(
+    runtime: Rc<RefCell<TypeScriptRuntime>>,
+    plugins: &mut HashMap<String, TsPluginInfo>,
+    name: &str,
+) -> Result<()> {
+    let info = plugins
+        .get_mut(name)
+        .ok_or_else(|| anyhow!("Plugin '{}' not found", name))?;
+    tracing::info!("Disabling TypeScript plugin: {}", name);
+    info.enabled = false;
+    runtime.borrow_mut().disable_plugin(name);
+    Ok(())
+}
+
 /// Reload a plugin
 async fn reload_plugin_internal(
     runtime: Rc<RefCell<TypeScriptRuntime>>,
@@ -821,8 +887,20 @@ async fn reload_plugin_internal(
         .path
         .clone();
 
-    unload_plugin_internal(plugins, commands, name)?;
-    load_plugin_internal(runtime, plugins, &path).await?;
+    unload_plugin_internal(Rc::clone(&runtime), plugins, commands, name)?;
+    if let Err(e) = load_plugin_internal(Rc::clone(&runtime), plugins, &path).await {
+        // Keep it listed, disabled, so it can be reloaded again
+        plugins.insert(
+            name.to_string(),
+            TsPluginInfo {
+                name: name.to_string(),
+                path,
+                enabled: false,
+            },
+        );
+        runtime.borrow_mut().disable_plugin(name);
+        return Err(e);
+    }
 
     Ok(())
 }
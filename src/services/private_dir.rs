@@ -0,0 +1,96 @@
+//! Per-user private directories in the shared temp directory.
+//!
+//! Sockets and cached copies of files must not be reachable by other users,
+//! and their paths must not be claimable in advance in a world-writable
+//! directory. A directory from [`user_temp_dir`] is created with mode 0700
+//! and, if it already exists, is only used when it is a real directory
+//! owned by the current user that nobody else can access.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// `<temp>/<prefix>-<uid>`, created if needed and checked to be private
+pub fn user_temp_dir(prefix: &str) -> io::Result<PathBuf> {
+    let dir = user_temp_path(prefix);
+    ensure_private_dir(&dir)?;
+    Ok(dir)
+}
+
+/// Path of [`user_temp_dir`], without creating or checking it
+pub fn user_temp_path(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", prefix, current_uid()))
+}
+
+/// Create `dir` with mode 0700, or check that the existing one is a
+/// directory owned by the current user with no group or other access
+#[cfg(unix)]
+pub fn ensure_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    // symlink_metadata: a symlink planted in place of the directory is refused
+    let metadata = std::fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() || metadata.uid() != current_uid() || metadata.mode() & 0o077 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{} is not a private directory owned by the current user",
+                dir.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn ensure_private_dir(dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)
+}
+
+#[cfg(unix)]
+fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> u32 {
+    0
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_creates_private_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("private");
+        ensure_private_dir(&dir).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        // Existing private directory is accepted
+        ensure_private_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_shared_dir_and_symlink() {
+        let temp = tempfile::tempdir().unwrap();
+        let shared = temp.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(ensure_private_dir(&shared).is_err());
+
+        let target = temp.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o700)).unwrap();
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        assert!(ensure_private_dir(&link).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Project-wide textual rename and references.
+//!
+//! This module provides functionality to:
+//! - Find whole-word occurrences of an identifier in a text
+//! - Scan the files of a project (respecting `.gitignore`, `.ignore` and the
+//!   global `ignore_patterns`) for occurrences
+//!
+//! "Rename Symbol" and "Find References" fall back to this when no language
+//! server is running for the buffer.
+
+use crate::services::ignore_service::IgnoreService;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Files larger than this are not scanned
+const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Bytes inspected when deciding whether a file is binary
+const BINARY_CHECK_BYTES: usize = 8 * 1024;
+
+/// One whole-word occurrence of an identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub path: PathBuf,
+    /// Byte offset of the occurrence in the file
+    pub offset: usize,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based byte column within the line
+    pub column: usize,
+    /// The line containing the occurrence, without its line ending
+    pub line_text: String,
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Byte offsets of `word` in `text` that are not part of a longer identifier
+pub fn find_word(text: &str, word: &str) -> Vec<usize> {
+    if word.is_empty() {
+        return Vec::new();
+    }
+    text.match_indices(word)
+        .filter(|(start, _)| {
+            let before = text[..*start].chars().next_back();
+            let after = text[start + word.len()..].chars().next();
+            !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+        })
+        .map(|(start, _)| start)
+        .collect()
+}
+
+/// Occurrences of `word` in the contents of one file
+pub fn occurrences_in(path: &Path, text: &str, word: &str) -> Vec<Occurrence> {
+    let mut occurrences = Vec::new();
+    let mut line = 1;
+    let mut line_start = 0;
+    for offset in find_word(text, word) {
+        line += text[line_start..offset].matches('\n').count();
+        line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+        occurrences.push(Occurrence {
+            path: path.to_path_buf(),
+            offset,
+            line,
+            column: offset - line_start + 1,
+            line_text: text[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        });
+    }
+    occurrences
+}
+
+/// Find `word` in every text file under `root`, sorted by path
+///
+/// `open_buffers` maps paths to the current (possibly unsaved) content of
+/// open buffers, which is searched instead of the file on disk. Files matching
+/// the global patterns of `ignore` are skipped.
+pub fn scan_project(
+    root: &Path,
+    word: &str,
+    open_buffers: &HashMap<PathBuf, String>,
+    ignore: &IgnoreService,
+) -> Vec<Occurrence> {
+    let mut seen = HashSet::new();
+    let mut occurrences = Vec::new();
+    let walker = ignore::WalkBuilder::new(root).require_git(false).build();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let path = entry.into_path();
+        if ignore.matches_global(&path, false) {
+            continue;
+        }
+        seen.insert(path.clone());
+        if let Some(text) = open_buffers.get(&path) {
+            occurrences.extend(occurrences_in(&path, text, word));
+        } else if let Some(text) = read_text_file(&path) {
+            occurrences.extend(occurrences_in(&path, &text, word));
+        }
+    }
+    // Open files outside the project are searched too
+    for (path, text) in open_buffers {
+        if !seen.contains(path) {
+            occurrences.extend(occurrences_in(path, text, word));
+        }
+    }
+    occurrences.sort_by(|a, b| a.path.cmp(&b.path).then(a.offset.cmp(&b.offset)));
+    occurrences
+}
+
+/// Contents of a file if it is small, valid UTF-8 and not binary
+fn read_text_file(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(path).ok()?;
+    if bytes[..bytes.len().min(BINARY_CHECK_BYTES)].contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_word_respects_boundaries() {
+        let text = "foo foo_bar barfoo foo() xfoo foo.len émfoo";
+        assert_eq!(find_word(text, "foo"), vec![0, 19, 30]);
+        assert_eq!(find_word(text, ""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_occurrences_in_reports_lines() {
+        let text = "let count = 1;\r\n\nprint(count + count)\n";
+        let found = occurrences_in(Path::new("a.py"), text, "count");
+        let lines: Vec<_> = found
+            .iter()
+            .map(|o| (o.line, o.column, o.line_text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, 5, "let count = 1;"),
+                (3, 7, "print(count + count)"),
+                (3, 15, "print(count + count)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_project_skips_ignored_and_binary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir(root.join("target")).unwrap();
+        std::fs::write(root.join("target/out.rs"), "old").unwrap();
+        std::fs::write(root.join("data.bin"), b"old\0").unwrap();
+        std::fs::write(root.join("b.rs"), "old();\n").unwrap();
+        std::fs::write(root.join("a.rs"), "fn old() {}\n").unwrap();
+        std::fs::write(root.join("c.gen.rs"), "old\n").unwrap();
+
+        let mut open = HashMap::new();
+        open.insert(root.join("b.rs"), "// unsaved\nold(); old();\n".to_string());
+
+        let ignore = IgnoreService::new(&["*.gen.rs".to_string()]);
+        let found = scan_project(root, "old", &open, &ignore);
+        let summary: Vec<_> = found
+            .iter()
+            .map(|o| (o.path.file_name().unwrap().to_str().unwrap(), o.line))
+            .collect();
+        assert_eq!(summary, vec![("a.rs", 1), ("b.rs", 2), ("b.rs", 2)]);
+    }
+}
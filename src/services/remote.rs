@@ -0,0 +1,285 @@
+//! Server/client mode over a unix socket.
+//!
+//! This module provides functionality to:
+//! - Listen on a socket in a running editor (`fresh --listen`) and forward
+//!   open requests to the main loop as `AsyncMessage::RemoteOpen`
+//! - Send open requests to that instance (`fresh --remote open FILE`),
+//!   optionally waiting until the opened buffer is closed
+//!
+//! The protocol is line based: the client sends one JSON `OpenRequest`, the
+//! server answers `ok` (or `error: <message>`) once the file is open and,
+//! for waiting requests, `closed` when the buffer is closed.
+
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::private_dir::user_temp_dir;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Environment variable overriding the socket path
+pub const SOCKET_ENV: &str = "FRESH_SOCKET";
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Socket path used by `--listen` and `--remote`: `$FRESH_SOCKET`, else a
+/// socket in `$XDG_RUNTIME_DIR`, else one in a private per-user directory
+/// under the temp directory
+pub fn default_socket_path() -> io::Result<PathBuf> {
+    if let Some(path) = std::env::var_os(SOCKET_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute());
+    let dir = match runtime_dir {
+        Some(dir) => dir,
+        None => user_temp_dir("fresh")?,
+    };
+    Ok(dir.join("fresh.sock"))
+}
+
+/// Refuse a socket created by another user, who would receive the request
+fn check_socket_owner(socket: &Path) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::symlink_metadata(socket)?;
+    // SAFETY: getuid has no preconditions and cannot fail
+    if metadata.uid() != unsafe { libc::getuid() } {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is owned by another user", socket.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Request to open a file in the listening instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenRequest {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Keep the connection open until the buffer is closed
+    pub wait: bool,
+}
+
+/// Client connection, held by the editor until the request is answered
+#[derive(Debug)]
+pub struct RemoteConnection(UnixStream);
+
+impl RemoteConnection {
+    /// Tell the client the file is open
+    pub fn reply_ok(&mut self) {
+        let _ = self.0.write_all(b"ok\n");
+    }
+
+    /// Tell the client the request failed
+    pub fn reply_error(mut self, message: &str) {
+        let _ = writeln!(self.0, "error: {}", message.replace('\n', " "));
+    }
+
+    /// Tell a waiting client its buffer was closed
+    pub fn reply_closed(mut self) {
+        let _ = self.0.write_all(b"closed\n");
+    }
+}
+
+/// Listening socket; the socket file is removed when this is dropped
+#[derive(Debug)]
+pub struct RemoteServer {
+    path: PathBuf,
+}
+
+impl RemoteServer {
+    /// Bind the socket and accept connections on a background thread.
+    ///
+    /// A stale socket left by an instance that exited is replaced; a socket
+    /// another instance is still listening on is an error.
+    pub fn listen(path: &Path, sender: mpsc::Sender<AsyncMessage>) -> io::Result<Self> {
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("another instance is listening on {}", path.display()),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let request = read_request(&stream);
+                let connection = RemoteConnection(stream);
+                match request {
+                    Ok(request) => {
+                        if sender
+                            .send(AsyncMessage::RemoteOpen {
+                                request,
+                                connection,
+                            })
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(e) => connection.reply_error(&e.to_string()),
+                }
+            }
+        });
+
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn read_request(stream: &UnixStream) -> io::Result<OpenRequest> {
+    // Don't let a silent client block the accept loop
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Ask the instance listening on `socket` to open a file. Returns once the
+/// file is open, or, for a waiting request, once its buffer is closed (or
+/// the editor exits).
+pub fn send_open(socket: &Path, request: &OpenRequest) -> io::Result<()> {
+    let no_listener = |e: io::Error| {
+        io::Error::new(
+            e.kind(),
+            format!("no editor listening on {}: {}", socket.display(), e),
+        )
+    };
+    match check_socket_owner(socket) {
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => return Err(e),
+        Err(e) => return Err(no_listener(e)),
+        Ok(()) => {}
+    }
+    let mut stream = UnixStream::connect(socket).map_err(no_listener)?;
+    let json = serde_json::to_string(request)?;
+    writeln!(stream, "{}", json)?;
+
+    let mut reader = BufReader::new(stream);
+    let mut reply = String::new();
+    reader.read_line(&mut reply)?;
+    match reply.trim_end() {
+        "ok" => {}
+        "" => return Err(io::Error::other("editor closed the connection")),
+        other => {
+            return Err(io::Error::other(
+                other.strip_prefix("error: ").unwrap_or(other).to_string(),
+            ))
+        }
+    }
+
+    if request.wait {
+        // Either `closed` or end of stream when the editor exits
+        reply.clear();
+        reader.read_line(&mut reply)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(path: &str, wait: bool) -> OpenRequest {
+        OpenRequest {
+            path: PathBuf::from(path),
+            line: Some(3),
+            column: None,
+            wait,
+        }
+    }
+
+    fn receive(receiver: &mpsc::Receiver<AsyncMessage>) -> (OpenRequest, RemoteConnection) {
+        match receiver.recv().unwrap() {
+            AsyncMessage::RemoteOpen {
+                request,
+                connection,
+            } => (request, connection),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_open_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("fresh.sock");
+        let (sender, receiver) = mpsc::channel();
+        let _server = RemoteServer::listen(&socket, sender).unwrap();
+
+        let client_socket = socket.clone();
+        let client =
+            std::thread::spawn(move || send_open(&client_socket, &request("/tmp/a.txt", false)));
+        let (received, mut connection) = receive(&receiver);
+        assert_eq!(received, request("/tmp/a.txt", false));
+        connection.reply_ok();
+        client.join().unwrap().unwrap();
+
+        let client_socket = socket.clone();
+        let client =
+            std::thread::spawn(move || send_open(&client_socket, &request("/tmp/b.txt", false)));
+        let (_, connection) = receive(&receiver);
+        connection.reply_error("No such file");
+        let error = client.join().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "No such file");
+    }
+
+    #[test]
+    fn test_wait_returns_when_closed() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("fresh.sock");
+        let (sender, receiver) = mpsc::channel();
+        let _server = RemoteServer::listen(&socket, sender).unwrap();
+
+        let client_socket = socket.clone();
+        let client =
+            std::thread::spawn(move || send_open(&client_socket, &request("/tmp/a.txt", true)));
+        let (_, mut connection) = receive(&receiver);
+        connection.reply_ok();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!client.is_finished());
+
+        connection.reply_closed();
+        client.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_listen_replaces_stale_socket_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("fresh.sock");
+
+        let (sender, _receiver) = mpsc::channel();
+        let server = RemoteServer::listen(&socket, sender.clone()).unwrap();
+        assert!(RemoteServer::listen(&socket, sender.clone()).is_err());
+        drop(server);
+        assert!(!socket.exists());
+
+        // A socket file nobody listens on is left over from a crash
+        drop(UnixListener::bind(&socket).unwrap());
+        assert!(socket.exists());
+        let _server = RemoteServer::listen(&socket, sender).unwrap();
+    }
+
+    #[test]
+    fn test_no_listener() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("missing.sock");
+        assert!(send_open(&socket, &request("/tmp/a.txt", false)).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Sending code to a REPL.
+//!
+//! This module provides functionality to:
+//! - Find the cell around a line, where cells are separated by `%%` marker
+//!   comments (`# %%`, `// %%`, `-- %%`)
+//! - Turn code into the keystrokes a REPL in a terminal expects
+
+use std::ops::Range;
+
+/// Whether `line` starts a new cell (a comment whose text starts with `%%`)
+pub fn is_cell_marker(line: &str) -> bool {
+    line.trim_start()
+        .trim_start_matches(['#', '/', '-', ';'])
+        .trim_start()
+        .starts_with("%%")
+}
+
+/// Lines of the cell containing `line` (0-based, end exclusive)
+///
+/// A cell runs from the line after the previous marker to the line before
+/// the next one; without markers the whole text is one cell.
+pub fn cell_line_range(lines: &[&str], line: usize) -> Range<usize> {
+    let line = line.min(lines.len().saturating_sub(1));
+    let start = if lines.get(line).is_some_and(|l| is_cell_marker(l)) {
+        line + 1
+    } else {
+        (0..line)
+            .rev()
+            .find(|&i| is_cell_marker(lines[i]))
+            .map_or(0, |i| i + 1)
+    };
+    let end = (start..lines.len())
+        .find(|&i| is_cell_marker(lines[i]))
+        .unwrap_or(lines.len());
+    start..end.max(start)
+}
+
+/// Bytes to type into a REPL to evaluate `code`
+///
+/// Each line is entered with a carriage return. Blank lines are dropped from
+/// multi-line code, which is followed by an empty line, so that indented
+/// blocks (in Python, for instance) are neither cut short nor left open.
+pub fn repl_input(code: &str) -> Vec<u8> {
+    let lines: Vec<&str> = code
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    let mut input = String::new();
+    if lines.len() <= 1 {
+        input.push_str(lines.first().copied().unwrap_or(""));
+        input.push('\r');
+    } else {
+        for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+            input.push_str(line);
+            input.push('\r');
+        }
+        input.push('\r');
+    }
+    input.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_markers() {
+        assert!(is_cell_marker("# %%"));
+        assert!(is_cell_marker("  // %% Load data"));
+        assert!(is_cell_marker("-- %%"));
+        assert!(is_cell_marker("#%%"));
+        assert!(is_cell_marker("%%"));
+        assert!(!is_cell_marker(""));
+        assert!(!is_cell_marker("x = 1 # %%"));
+        assert!(!is_cell_marker("# comment"));
+        assert!(!is_cell_marker("print('%%')"));
+    }
+
+    #[test]
+    fn test_cell_line_range() {
+        let lines = vec!["import os", "# %%", "a = 1", "b = 2", "# %% next", "c = 3"];
+        assert_eq!(cell_line_range(&lines, 0), 0..1);
+        assert_eq!(cell_line_range(&lines, 3), 2..4);
+        // A marker line belongs to the cell it starts
+        assert_eq!(cell_line_range(&lines, 1), 2..4);
+        assert_eq!(cell_line_range(&lines, 5), 5..6);
+        assert_eq!(cell_line_range(&["x", "y"], 1), 0..2);
+        assert_eq!(cell_line_range(&["x", "# %%"], 1), 2..2);
+    }
+
+    #[test]
+    fn test_repl_input() {
+        assert_eq!(repl_input("print(1)\n"), b"print(1)\r");
+        assert_eq!(
+            repl_input("def f():\n    x = 1\n\n    return x\n"),
+            b"def f():\r    x = 1\r    return x\r\r"
+        );
+        assert_eq!(repl_input(""), b"\r");
+    }
+}
@@ -1,9 +1,80 @@
 /// Initialize signal handlers for SIGTERM and SIGINT.
 /// On Linux, dumps thread backtraces before terminating.
 /// On other platforms, this is a no-op (default terminal behavior applies).
+/// On Unix, SIGTSTP and SIGCONT are also caught so the event loop can
+/// restore the terminal before stopping and set it up again after resuming.
 pub fn install_signal_handlers() {
     #[cfg(target_os = "linux")]
     linux::install_signal_handlers_with_backtrace();
+    #[cfg(unix)]
+    job_control::install_job_control_handlers();
+}
+
+/// Whether a SIGTSTP arrived since the last call
+pub fn take_suspend_request() -> bool {
+    #[cfg(unix)]
+    return job_control::SUSPEND_REQUESTED.swap(false, std::sync::atomic::Ordering::SeqCst);
+    #[cfg(not(unix))]
+    false
+}
+
+/// Whether the process was continued (SIGCONT) since the last call
+pub fn take_resumed() -> bool {
+    #[cfg(unix)]
+    return job_control::RESUMED.swap(false, std::sync::atomic::Ordering::SeqCst);
+    #[cfg(not(unix))]
+    false
+}
+
+/// Stop the process until the shell continues it with `fg`.
+/// The terminal must already be restored. No-op on non-Unix platforms.
+pub fn stop_process() {
+    #[cfg(unix)]
+    {
+        let _ = nix::sys::signal::raise(nix::sys::signal::Signal::SIGSTOP);
+        // Consume the SIGCONT of this resume; the caller sets the terminal up
+        take_resumed();
+    }
+}
+
+/// SIGTSTP and SIGCONT handling: the handlers only set flags that the event
+/// loop polls, as the terminal can't be touched from a signal handler
+#[cfg(unix)]
+mod job_control {
+    use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    pub(super) static SUSPEND_REQUESTED: AtomicBool = AtomicBool::new(false);
+    pub(super) static RESUMED: AtomicBool = AtomicBool::new(false);
+
+    pub fn install_job_control_handlers() {
+        extern "C" fn suspend_handler(_: libc::c_int) {
+            SUSPEND_REQUESTED.store(true, Ordering::SeqCst);
+        }
+        extern "C" fn resume_handler(_: libc::c_int) {
+            RESUMED.store(true, Ordering::SeqCst);
+        }
+
+        let suspend = SigAction::new(
+            SigHandler::Handler(suspend_handler),
+            SaFlags::SA_RESTART,
+            SigSet::empty(),
+        );
+        let resume = SigAction::new(
+            SigHandler::Handler(resume_handler),
+            SaFlags::SA_RESTART,
+            SigSet::empty(),
+        );
+
+        unsafe {
+            if let Err(e) = sigaction(Signal::SIGTSTP, &suspend) {
+                tracing::error!("Failed to set SIGTSTP handler: {}", e);
+            }
+            if let Err(e) = sigaction(Signal::SIGCONT, &resume) {
+                tracing::error!("Failed to set SIGCONT handler: {}", e);
+            }
+        }
+    }
 }
 
 /// Linux-specific implementation with thread backtrace dumping
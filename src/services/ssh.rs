@@ -0,0 +1,364 @@
+//! Remote files over SSH.
+//!
+//! This module provides functionality to:
+//! - Parse `ssh://[user@]host[:port]/path` and `sftp://` URLs
+//! - Fetch a remote file into a local cache copy, reporting progress
+//! - Write the cache copy back to the remote file
+//!
+//! Transfers run the system `ssh` client (or `$FRESH_SSH`), so keys, agents
+//! and `~/.ssh/config` apply as usual. Connections are shared through an
+//! OpenSSH control master, so only the first transfer to a host pays for
+//! the handshake. `BatchMode` is on because the terminal belongs to the
+//! editor: hosts must be reachable without a password prompt.
+//!
+//! Cache copies and control sockets live in a private per-user directory,
+//! so other local users can neither read the copies nor plant files there.
+
+use crate::services::private_dir::{ensure_private_dir, user_temp_dir, user_temp_path};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Environment variable naming the ssh program to run
+pub const SSH_PROGRAM_ENV: &str = "FRESH_SSH";
+
+/// How long an idle shared connection is kept open
+const CONTROL_PERSIST_SECS: u64 = 600;
+
+/// Prefix of the per-user directory holding cache copies and control sockets
+const PRIVATE_DIR_PREFIX: &str = "fresh-ssh";
+
+/// Size of reads while fetching (progress is reported per chunk)
+const FETCH_CHUNK_BYTES: usize = 64 * 1024;
+
+/// A file on a remote host
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SshUrl {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    /// Path on the host; starts with `~/` for paths relative to the home
+    /// directory
+    pub path: String,
+}
+
+impl SshUrl {
+    /// Parse an `ssh://` or `sftp://` URL. Returns `None` for anything else.
+    ///
+    /// `ssh://host/~/notes.txt` names a file relative to the home directory.
+    /// A user or host starting with `-` is rejected, since ssh would read it
+    /// as an option.
+    pub fn parse(input: &str) -> Option<Self> {
+        let rest = input
+            .strip_prefix("ssh://")
+            .or_else(|| input.strip_prefix("sftp://"))?;
+        let (authority, path) = rest.split_at(rest.find('/')?);
+        let (user, host_port) = match authority.rsplit_once('@') {
+            Some((user, host_port)) => (Some(user.to_string()), host_port),
+            None => (None, authority),
+        };
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.parse().ok()?)),
+            None => (host_port, None),
+        };
+        if host.is_empty() || path.len() < 2 || user.as_deref() == Some("") {
+            return None;
+        }
+        if host.starts_with('-') || user.as_deref().is_some_and(|u| u.starts_with('-')) {
+            return None;
+        }
+        let path = match path.strip_prefix("/~/") {
+            Some(relative) => format!("~/{}", relative),
+            None => path.to_string(),
+        };
+        Some(Self {
+            user,
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    /// File name of the remote path
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// Where the local copy of this file is kept while it is edited. The
+    /// file name is kept so language detection works on the copy.
+    pub fn cache_path(&self) -> PathBuf {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        user_temp_path(PRIVATE_DIR_PREFIX)
+            .join(format!("{:016x}", hasher.finish()))
+            .join(self.file_name())
+    }
+
+    /// `ssh` invocation running `remote_command` on the host
+    fn command(&self, remote_command: &str) -> io::Result<Command> {
+        let program = std::env::var_os(SSH_PROGRAM_ENV).unwrap_or_else(|| "ssh".into());
+        let control_path = user_temp_dir(PRIVATE_DIR_PREFIX)?.join("control-%C");
+        let mut command = Command::new(program);
+        command
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg("-o")
+            .arg("ControlMaster=auto")
+            .arg("-o")
+            .arg(format!("ControlPath={}", control_path.display()))
+            .arg("-o")
+            .arg(format!("ControlPersist={}", CONTROL_PERSIST_SECS));
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        let destination = match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        };
+        command.arg("--").arg(destination).arg(remote_command);
+        Ok(command)
+    }
+
+    /// The remote path quoted for the remote shell
+    fn quoted_path(&self) -> String {
+        match self.path.strip_prefix("~/") {
+            Some(relative) => format!("~/{}", shell_quote(relative)),
+            None => shell_quote(&self.path),
+        }
+    }
+}
+
+impl fmt::Display for SshUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ssh://")?;
+        if let Some(user) = &self.user {
+            write!(f, "{}@", user)?;
+        }
+        write!(f, "{}", self.host)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        match self.path.strip_prefix("~/") {
+            Some(relative) => write!(f, "/~/{}", relative),
+            None => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// Quote a string for a POSIX shell
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Copy the remote file to its cache path, calling `progress(received,
+/// total)` as data arrives. Returns the cache path.
+pub fn fetch(url: &SshUrl, mut progress: impl FnMut(u64, Option<u64>)) -> io::Result<PathBuf> {
+    let dest = url.cache_path();
+    user_temp_dir(PRIVATE_DIR_PREFIX)?;
+    if let Some(parent) = dest.parent() {
+        ensure_private_dir(parent)?;
+    }
+    let path = url.quoted_path();
+    let mut child = url
+        .command(&format!("wc -c < {0} && cat {0}", path))?
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+    let mut size_line = String::new();
+    stdout.read_line(&mut size_line)?;
+    let total = size_line.trim().parse().ok();
+
+    let mut file = create_private_file(&dest)?;
+    let mut received = 0;
+    let mut chunk = vec![0; FETCH_CHUNK_BYTES];
+    loop {
+        let n = stdout.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&chunk[..n])?;
+        received += n as u64;
+        progress(received, total);
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() || total.is_none() {
+        return Err(transfer_error("fetch", url, &output.stderr));
+    }
+    Ok(dest)
+}
+
+/// Replace `path` with a new file only the current user can read
+fn create_private_file(path: &Path) -> io::Result<std::fs::File> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Overwrite the remote file with the contents of `src`
+///
+/// The data goes to a temporary file next to the remote file, which only
+/// replaces it once all of it has arrived, so a failed transfer leaves the
+/// remote file as it was.
+pub fn upload(url: &SshUrl, src: &Path) -> io::Result<()> {
+    let mut file = std::fs::File::open(src)?;
+    let size = file.metadata()?.len();
+    let mut child = url
+        .command(&upload_script(&url.quoted_path(), size))?
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    {
+        let mut stdin = child.stdin.take().expect("piped stdin");
+        io::copy(&mut file, &mut stdin)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(transfer_error("write", url, &output.stderr));
+    }
+    Ok(())
+}
+
+/// Remote shell script writing `size` bytes from stdin to `quoted_path`
+///
+/// The temporary file starts as a copy of the existing file (`cp -p`), so
+/// it keeps the file's mode, and is only moved over the file when exactly
+/// `size` bytes arrived.
+fn upload_script(quoted_path: &str, size: u64) -> String {
+    format!(
+        concat!(
+            "f={path}; t=$(mktemp \"$(dirname -- \"$f\")/.fresh-upload.XXXXXX\") || exit 1; ",
+            "trap 'rm -f -- \"$t\"' EXIT HUP INT TERM; ",
+            "if [ -e \"$f\" ]; then cp -p -- \"$f\" \"$t\" || exit 1; fi; ",
+            "cat > \"$t\" || exit 1; ",
+            "if [ $(wc -c < \"$t\") -ne {size} ]; then echo 'incomplete transfer' >&2; exit 1; fi; ",
+            "mv -f -- \"$t\" \"$f\""
+        ),
+        path = quoted_path,
+        size = size
+    )
+}
+
+fn transfer_error(action: &str, url: &SshUrl, stderr: &[u8]) -> io::Error {
+    let stderr = String::from_utf8_lossy(stderr);
+    let reason = stderr.lines().last().unwrap_or("ssh failed").trim();
+    io::Error::other(format!("Could not {} {}: {}", action, url, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_urls() {
+        assert_eq!(
+            SshUrl::parse("ssh://alice@example.com:2222/etc/hosts"),
+            Some(SshUrl {
+                user: Some("alice".to_string()),
+                host: "example.com".to_string(),
+                port: Some(2222),
+                path: "/etc/hosts".to_string(),
+            })
+        );
+        let url = SshUrl::parse("sftp://box/~/notes.md").unwrap();
+        assert_eq!(url.user, None);
+        assert_eq!(url.path, "~/notes.md");
+        assert_eq!(url.file_name(), "notes.md");
+
+        assert_eq!(SshUrl::parse("/etc/hosts"), None);
+        assert_eq!(SshUrl::parse("ssh://host"), None);
+        assert_eq!(SshUrl::parse("ssh://host/"), None);
+        assert_eq!(SshUrl::parse("ssh:///etc/hosts"), None);
+        assert_eq!(SshUrl::parse("ssh://host:port/x"), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_option_like_destination() {
+        assert_eq!(SshUrl::parse("ssh://-oProxyCommand=sh -c id/x"), None);
+        assert_eq!(SshUrl::parse("ssh://-oProxyCommand=x@host/x"), None);
+        assert_eq!(SshUrl::parse("ssh://user@-host/x"), None);
+        assert!(SshUrl::parse("ssh://user-1@host-1/x").is_some());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for input in [
+            "ssh://alice@example.com:2222/etc/hosts",
+            "ssh://box/~/notes.md",
+        ] {
+            assert_eq!(SshUrl::parse(input).unwrap().to_string(), input);
+        }
+        assert_eq!(
+            SshUrl::parse("sftp://box/a").unwrap().to_string(),
+            "ssh://box/a"
+        );
+    }
+
+    #[test]
+    fn test_quoted_path() {
+        let url = SshUrl::parse("ssh://h/~/it's here.txt").unwrap();
+        assert_eq!(url.quoted_path(), r"~/'it'\''s here.txt'");
+        let url = SshUrl::parse("ssh://h/tmp/$x").unwrap();
+        assert_eq!(url.quoted_path(), "'/tmp/$x'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_upload_script_replaces_file_only_when_complete() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("it's here.sh");
+        std::fs::write(&path, "old\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o751)).unwrap();
+        let run = |data: &str, size: u64| {
+            let mut child = Command::new("sh")
+                .arg("-c")
+                .arg(upload_script(&shell_quote(&path.to_string_lossy()), size))
+                .stdin(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap();
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(data.as_bytes())
+                .unwrap();
+            child.wait().unwrap().success()
+        };
+
+        // Cut short: the file is left alone
+        assert!(!run("ne", 4));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old\n");
+
+        assert!(run("new\n", 4));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o751);
+        // No temporary files are left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_cache_path_keeps_file_name() {
+        let a = SshUrl::parse("ssh://a/src/main.rs").unwrap();
+        let b = SshUrl::parse("ssh://b/src/main.rs").unwrap();
+        assert!(a.cache_path().ends_with("main.rs"));
+        assert_ne!(a.cache_path(), b.cache_path());
+    }
+}
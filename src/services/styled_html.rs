@@ -1,12 +1,40 @@
-//! Styled text rendering for clipboard copy feature
+//! Styled text rendering for clipboard copy and export
 //!
 //! This module renders styled text with syntax highlighting as HTML
-//! for pasting into rich text editors (Google Docs, Word, etc.)
+//! for pasting into rich text editors (Google Docs, Word, etc.), as a
+//! standalone HTML page, or as ANSI escape sequences for terminals
 
 use crate::primitives::highlighter::HighlightSpan;
 use crate::view::theme::Theme;
 use ratatui::style::Color;
 
+/// Output format for exporting a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Standalone HTML page
+    Html,
+    /// Text with ANSI color escape sequences
+    Ansi,
+}
+
+impl ExportFormat {
+    /// File extension appended to the buffer's name by default
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Html => "html",
+            ExportFormat::Ansi => "ans",
+        }
+    }
+
+    /// Name shown in prompts and messages
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ExportFormat::Html => "HTML",
+            ExportFormat::Ansi => "ANSI",
+        }
+    }
+}
+
 /// Convert a ratatui Color to a CSS hex color string
 fn color_to_css(color: Color, default: &str) -> String {
     match color {
@@ -31,6 +59,40 @@ fn color_to_css(color: Color, default: &str) -> String {
     }
 }
 
+/// Map each byte offset of `text` to the color of the span covering it
+fn build_color_map(text: &str, highlight_spans: &[HighlightSpan]) -> Vec<Option<Color>> {
+    let mut color_map: Vec<Option<Color>> = vec![None; text.len()];
+    for span in highlight_spans {
+        let start = span.range.start.min(text.len());
+        let end = span.range.end.min(text.len());
+        for slot in &mut color_map[start..end] {
+            *slot = Some(span.color);
+        }
+    }
+    color_map
+}
+
+/// Append `ch` to `out`, escaping HTML special characters
+fn push_escaped(out: &mut String, ch: char) {
+    match ch {
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '&' => out.push_str("&amp;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        _ => out.push(ch),
+    }
+}
+
+/// Escape HTML special characters
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        push_escaped(&mut escaped, ch);
+    }
+    escaped
+}
+
 /// Render styled text with syntax highlighting to HTML with inline CSS
 ///
 /// The generated HTML uses a `<pre>` block with inline styles for each
@@ -48,15 +110,7 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
     let bg_color = color_to_css(theme.editor_bg, "#1e1e1e");
     let fg_color = color_to_css(theme.editor_fg, "#d4d4d4");
 
-    // Build a map of byte offset to color for quick lookup
-    let mut color_map: Vec<Option<Color>> = vec![None; text.len()];
-    for span in highlight_spans {
-        let start = span.range.start.min(text.len());
-        let end = span.range.end.min(text.len());
-        for i in start..end {
-            color_map[i] = Some(span.color);
-        }
-    }
+    let color_map = build_color_map(text, highlight_spans);
 
     // Build HTML with spans for colored regions
     let mut html = String::new();
@@ -98,14 +152,7 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
         }
 
         // Escape HTML special characters and add the character
-        match ch {
-            '<' => html.push_str("&lt;"),
-            '>' => html.push_str("&gt;"),
-            '&' => html.push_str("&amp;"),
-            '"' => html.push_str("&quot;"),
-            '\'' => html.push_str("&#39;"),
-            _ => html.push(ch),
-        }
+        push_escaped(&mut html, ch);
 
         byte_offset += char_byte_len;
     }
@@ -119,6 +166,87 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
     html
 }
 
+/// Render styled text as a standalone HTML page titled `title`
+///
+/// The page holds the same `<pre>` block as [`render_styled_html`] on the
+/// theme's background, so it can be opened in a browser or attached to a
+/// code review as is.
+pub fn render_html_document(
+    title: &str,
+    text: &str,
+    highlight_spans: &[HighlightSpan],
+    theme: &Theme,
+) -> String {
+    let bg_color = color_to_css(theme.editor_bg, "#1e1e1e");
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body style=\"margin:0;padding:16px;background-color:{};\">\n{}\n</body>\n</html>\n",
+        escape_html(title),
+        bg_color,
+        render_styled_html(text, highlight_spans, theme)
+    )
+}
+
+/// SGR parameters that set the foreground to `color`
+fn color_to_sgr(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+        Color::Indexed(i) => format!("38;5;{}", i),
+        Color::Black => "30".to_string(),
+        Color::Red => "31".to_string(),
+        Color::Green => "32".to_string(),
+        Color::Yellow => "33".to_string(),
+        Color::Blue => "34".to_string(),
+        Color::Magenta => "35".to_string(),
+        Color::Cyan => "36".to_string(),
+        Color::Gray => "37".to_string(),
+        Color::DarkGray => "90".to_string(),
+        Color::LightRed => "91".to_string(),
+        Color::LightGreen => "92".to_string(),
+        Color::LightYellow => "93".to_string(),
+        Color::LightBlue => "94".to_string(),
+        Color::LightMagenta => "95".to_string(),
+        Color::LightCyan => "96".to_string(),
+        Color::White => "97".to_string(),
+        Color::Reset => "39".to_string(),
+    }
+}
+
+/// Render styled text with syntax highlighting as ANSI escape sequences
+///
+/// Only foreground colors are set, so the output keeps the background of
+/// the terminal it is printed in. Colors are reset at the end of every
+/// line, so the output can be piped through `head`, `grep` or a pager.
+pub fn render_styled_ansi(text: &str, highlight_spans: &[HighlightSpan]) -> String {
+    let color_map = build_color_map(text, highlight_spans);
+
+    let mut ansi = String::with_capacity(text.len());
+    let mut current_color: Option<Color> = None;
+    for (byte_offset, ch) in text.char_indices() {
+        if ch == '\n' || ch == '\r' {
+            if current_color.take().is_some() {
+                ansi.push_str("\x1b[0m");
+            }
+            ansi.push(ch);
+            continue;
+        }
+
+        let char_color = color_map.get(byte_offset).copied().flatten();
+        if char_color != current_color {
+            match char_color {
+                Some(color) => ansi.push_str(&format!("\x1b[{}m", color_to_sgr(color))),
+                None => ansi.push_str("\x1b[0m"),
+            }
+            current_color = char_color;
+        }
+        ansi.push(ch);
+    }
+
+    if current_color.is_some() {
+        ansi.push_str("\x1b[0m");
+    }
+    ansi
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +295,37 @@ mod tests {
         assert!(html.contains("main()"));
     }
 
+    #[test]
+    fn test_render_html_document() {
+        let theme = Theme::dark();
+
+        let html = render_html_document("a<b>.rs", "x", &[], &theme);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>a&lt;b&gt;.rs</title>"));
+        assert!(html.contains("<pre style=\""));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_render_ansi_resets_at_line_end() {
+        use std::ops::Range;
+
+        let text = "fn a\nb";
+        let spans = vec![HighlightSpan {
+            range: Range { start: 0, end: 6 },
+            color: Color::Rgb(1, 2, 3),
+        }];
+
+        let ansi = render_styled_ansi(text, &spans);
+
+        assert_eq!(
+            ansi,
+            "\x1b[38;2;1;2;3mfn a\x1b[0m\n\x1b[38;2;1;2;3mb\x1b[0m"
+        );
+        assert_eq!(render_styled_ansi("plain", &[]), "plain");
+    }
+
     #[test]
     fn test_color_to_css() {
         assert_eq!(color_to_css(Color::Black, "#fff"), "#000000");
@@ -0,0 +1,196 @@
+//! Writing files with elevated privileges.
+//!
+//! This module provides functionality to:
+//! - Pick a privilege helper (`pkexec` in graphical sessions, `sudo` otherwise)
+//! - Check whether the helper needs a password from the editor
+//! - Write buffer content to a file as root
+//!
+//! The content is piped to `tee` through the helper's stdin, so it never
+//! passes through a temporary file other users could read. With `sudo` the
+//! password (when needed) is checked first by `sudo -v` on a pipe of its
+//! own, and the write then runs with `-n` on the cached credentials. A wrong
+//! password can't make sudo read the content as further attempts.
+
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Environment variable naming a sudo-compatible program to use instead of
+/// detecting one
+pub const SUDO_PROGRAM_ENV: &str = "FRESH_SUDO";
+
+/// How to gain root for a write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Elevation {
+    /// `pkexec`, which asks for authentication through the desktop's agent
+    Pkexec,
+    /// `sudo` (or a compatible program), which reads a password on stdin
+    /// for `-v`
+    Sudo(OsString),
+}
+
+impl Elevation {
+    /// Choose the helper for this session
+    pub fn detect() -> Self {
+        if let Some(program) = std::env::var_os(SUDO_PROGRAM_ENV) {
+            return Self::Sudo(program);
+        }
+        let graphical =
+            std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+        if graphical && on_path("pkexec") {
+            Self::Pkexec
+        } else {
+            Self::Sudo("sudo".into())
+        }
+    }
+
+    /// Whether the editor has to ask for a password before writing
+    ///
+    /// `pkexec` asks on its own; `sudo` does not need one while its
+    /// credentials are cached.
+    pub fn needs_password(&self) -> bool {
+        match self {
+            Self::Pkexec => false,
+            Self::Sudo(program) => !Command::new(program)
+                .args(["-n", "true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false),
+        }
+    }
+
+    /// Overwrite `path` with `content` as root
+    pub fn write(&self, path: &Path, content: &[u8], password: Option<&str>) -> io::Result<()> {
+        let mut command = match self {
+            Self::Pkexec => Command::new("pkexec"),
+            Self::Sudo(program) => {
+                if let Some(password) = password {
+                    validate_password(program, password)?;
+                }
+                let mut command = Command::new(program);
+                command.args(["-n", "--"]);
+                command
+            }
+        };
+        let mut child = command
+            .arg("tee")
+            .arg("--")
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let written = child.stdin.take().expect("piped stdin").write_all(content);
+        // A helper that gives up early closes the pipe; its stderr says why
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(helper_error(&output.stderr));
+        }
+        written
+    }
+}
+
+/// Check `password` with `sudo -v`, which caches the credentials for the
+/// write. Only the password is sent on its stdin.
+fn validate_password(program: &OsStr, password: &str) -> io::Result<()> {
+    let mut child = Command::new(program)
+        .args(["-S", "-v", "-p", ""])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let written = child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(format!("{}\n", password).as_bytes());
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(helper_error(&output.stderr));
+    }
+    written
+}
+
+/// The error for a helper that failed, from the last line of its stderr
+fn helper_error(stderr: &[u8]) -> io::Error {
+    let stderr = String::from_utf8_lossy(stderr);
+    let reason = stderr
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .unwrap_or("authentication failed")
+        .trim();
+    io::Error::new(io::ErrorKind::PermissionDenied, reason)
+}
+
+/// Whether `program` is an executable on `$PATH`
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// A fake sudo that accepts the password "secret", caching it for `-n`,
+    /// and keeps what `-S` read after the password in `dir/rest`
+    fn fake_sudo(dir: &Path) -> OsString {
+        let script = dir.join("sudo");
+        std::fs::write(
+            &script,
+            r#"#!/bin/sh
+dir=$(dirname "$0")
+if [ "$1" = "-n" ]; then
+  shift; [ "$1" = "--" ] && shift
+  [ -e "$dir/validated" ] || { echo "a password is required" >&2; exit 1; }
+  [ "$1" = "true" ] && exit 0; exec "$@"
+fi
+if [ "$1" = "-S" ]; then
+  read -r pw; cat > "$dir/rest"
+  [ "$pw" = "secret" ] || { echo "Sorry, try again." >&2; exit 1; }
+  touch "$dir/validated"; exit 0
+fi
+exit 2
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script.into_os_string()
+    }
+
+    #[test]
+    fn test_write_with_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let sudo = Elevation::Sudo(fake_sudo(dir.path()));
+        let target = dir.path().join("hosts");
+        assert!(sudo.needs_password());
+
+        sudo.write(&target, b"127.0.0.1 box\n", Some("secret"))
+            .unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"127.0.0.1 box\n");
+    }
+
+    #[test]
+    fn test_wrong_password_reports_helper_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let sudo = Elevation::Sudo(fake_sudo(dir.path()));
+        let target = dir.path().join("hosts");
+        std::fs::write(&target, "old").unwrap();
+
+        let err = sudo.write(&target, b"new", Some("guess")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(err.to_string(), "Sorry, try again.");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "old");
+        // The content never reached the password prompt
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("rest")).unwrap(),
+            ""
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Workspace symbol index.
+//!
+//! This module provides functionality to:
+//! - Collect the named definitions (functions, types, modules, ...) of a
+//!   source file from its tree-sitter parse tree
+//! - Index every source file of a project (respecting `.gitignore`)
+//!
+//! "Go to Symbol in Workspace" (`#` in the command palette) searches this
+//! index when no language server answers `workspace/symbol`.
+
+use crate::primitives::highlighter::Language;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Node, Parser};
+
+/// Files larger than this are not indexed
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Most files indexed in one project
+const MAX_FILES: usize = 20_000;
+
+/// A named definition in a project file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedSymbol {
+    pub name: String,
+    /// What the symbol is ("function", "struct", "class", ...)
+    pub kind: &'static str,
+    pub path: PathBuf,
+    /// 1-based line number of the definition
+    pub line: usize,
+}
+
+/// Kind of symbol a parse tree node defines, if any
+fn symbol_kind(node_kind: &str) -> Option<&'static str> {
+    Some(match node_kind {
+        "function_item"
+        | "function_definition"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "method"
+        | "singleton_method" => "function",
+        "method_definition" | "method_declaration" | "constructor_declaration" => "method",
+        "class_definition"
+        | "class_declaration"
+        | "abstract_class_declaration"
+        | "class_specifier"
+        | "class" => "class",
+        "struct_item" | "struct_specifier" => "struct",
+        "enum_item" | "enum_declaration" | "enum_specifier" => "enum",
+        "trait_item" | "interface_declaration" => "interface",
+        "mod_item" | "module" | "namespace_declaration" | "namespace_definition" => "module",
+        "impl_item" => "impl",
+        "type_item" | "type_spec" | "type_alias_declaration" => "type",
+        _ => return None,
+    })
+}
+
+/// Name of the symbol a node defines: its `name` field, the identifier at
+/// the end of its `declarator` chain (C functions) or the implemented type
+fn symbol_name<'a>(node: Node, source: &'a [u8]) -> Option<&'a str> {
+    let name = node
+        .child_by_field_name("name")
+        .or_else(|| {
+            let mut declarator = node.child_by_field_name("declarator")?;
+            while let Some(inner) = declarator.child_by_field_name("declarator") {
+                declarator = inner;
+            }
+            Some(declarator)
+        })
+        .or_else(|| node.child_by_field_name("type"))?;
+    let text = std::str::from_utf8(&source[name.byte_range()]).ok()?;
+    (!text.is_empty() && !text.contains('\n')).then_some(text)
+}
+
+/// Definitions in `source`, in document order, as (name, kind, 1-based line)
+pub fn index_source(language: Language, source: &[u8]) -> Vec<(String, &'static str, usize)> {
+    let mut parser = Parser::new();
+    if parser
+        .set_language(&language.tree_sitter_language())
+        .is_err()
+    {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let mut symbols = Vec::new();
+    let mut cursor = tree.walk();
+    'walk: loop {
+        let node = cursor.node();
+        // Keywords share their kind with the nodes they open, and a C
+        // `struct name` in a declaration is a use, not a definition
+        let is_definition = node.is_named()
+            && (!node.kind().ends_with("_specifier") || node.child_by_field_name("body").is_some());
+        if let Some(kind) = symbol_kind(node.kind()).filter(|_| is_definition) {
+            if let Some(name) = symbol_name(node, source) {
+                symbols.push((name.to_string(), kind, node.start_position().row + 1));
+            }
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+    symbols
+}
+
+/// Definitions in the source files under `root`
+pub fn index_workspace(root: &Path) -> Vec<IndexedSymbol> {
+    let walker = ignore::WalkBuilder::new(root).require_git(false).build();
+    let mut symbols = Vec::new();
+    for entry in walker
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .take(MAX_FILES)
+    {
+        let path = entry.into_path();
+        let Some(language) = Language::from_path(&path) else {
+            continue;
+        };
+        if !std::fs::metadata(&path).is_ok_and(|m| m.len() <= MAX_FILE_BYTES) {
+            continue;
+        }
+        let Ok(source) = std::fs::read(&path) else {
+            continue;
+        };
+        symbols.extend(
+            index_source(language, &source)
+                .into_iter()
+                .map(|(name, kind, line)| IndexedSymbol {
+                    name,
+                    kind,
+                    path: path.clone(),
+                    line,
+                }),
+        );
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_rust_source() {
+        let source = b"mod a {\n    pub struct Point { x: i32 }\n    impl Point {\n        fn new() -> Self { todo!() }\n    }\n}\nenum Shape {}\n";
+        assert_eq!(
+            index_source(Language::Rust, source),
+            vec![
+                ("a".to_string(), "module", 1),
+                ("Point".to_string(), "struct", 2),
+                ("Point".to_string(), "impl", 3),
+                ("new".to_string(), "function", 4),
+                ("Shape".to_string(), "enum", 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_c_source() {
+        let source = b"struct node { int value; };\n\nstatic int count(struct node *n) {\n    return 0;\n}\n";
+        assert_eq!(
+            index_source(Language::C, source),
+            vec![
+                ("node".to_string(), "struct", 1),
+                ("count".to_string(), "function", 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_workspace() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("lib.py"),
+            "class Parser:\n    def run(self):\n        pass\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "def not_code():\n").unwrap();
+        let symbols = index_workspace(dir.path());
+        let names: Vec<_> = symbols.iter().map(|s| (s.name.as_str(), s.line)).collect();
+        assert_eq!(names, vec![("Parser", 1), ("run", 2)]);
+        assert_eq!(symbols[0].path, dir.path().join("lib.py"));
+    }
+}
@@ -0,0 +1,262 @@
+//! ctags tags files.
+//!
+//! This module provides functionality to:
+//! - Generate a `tags` file for a project with universal-ctags
+//! - Parse tags files and look up tags by name or prefix
+//! - Find the line a tag points at, from its line number or search pattern
+//!
+//! Go to Definition and completion fall back to the project's tags file when
+//! no language server is running for the buffer.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Names of tags files looked up in the project root, in order
+pub const TAGS_FILE_NAMES: &[&str] = &["tags", ".tags"];
+
+/// Where a tag's definition is in its file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagAddress {
+    /// 1-based line number
+    Line(usize),
+    /// Search pattern, with `^` and `$` anchors and without the delimiters
+    Pattern(String),
+}
+
+/// One entry of a tags file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub path: PathBuf,
+    pub address: TagAddress,
+    /// Kind of definition (`f`, `function`, `s`, ...), as written by ctags
+    pub kind: Option<String>,
+}
+
+/// The tags of a tags file, sorted by name
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    tags: Vec<Tag>,
+}
+
+impl TagIndex {
+    /// Parse the contents of a tags file; relative paths are relative to `base_dir`
+    pub fn parse(text: &str, base_dir: &Path) -> Self {
+        let mut tags: Vec<Tag> = text
+            .lines()
+            .filter(|line| !line.starts_with("!_TAG_"))
+            .filter_map(|line| parse_line(line, base_dir))
+            .collect();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { tags }
+    }
+
+    /// Read and parse the tags file at `path`
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        Ok(Self::parse(&String::from_utf8_lossy(&bytes), base_dir))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Tags named exactly `name`
+    pub fn definitions(&self, name: &str) -> &[Tag] {
+        let start = self.tags.partition_point(|tag| tag.name.as_str() < name);
+        let end = start + self.tags[start..].partition_point(|tag| tag.name == name);
+        &self.tags[start..end]
+    }
+
+    /// Tags whose names start with `prefix`, in name order
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Tag> + 'a {
+        let start = self.tags.partition_point(|tag| tag.name.as_str() < prefix);
+        self.tags[start..]
+            .iter()
+            .take_while(move |tag| tag.name.starts_with(prefix))
+    }
+}
+
+/// Parse one `name<TAB>file<TAB>address;"<TAB>fields` line
+fn parse_line(line: &str, base_dir: &Path) -> Option<Tag> {
+    let mut parts = line.splitn(3, '\t');
+    let name = parts.next()?;
+    let file = parts.next()?;
+    let rest = parts.next()?;
+    if name.is_empty() || file.is_empty() {
+        return None;
+    }
+
+    // The address ends at `;"`, followed by the extension fields
+    let (address, fields) = match rest.rfind(";\"") {
+        Some(end) => (&rest[..end], rest[end + 2..].trim_start_matches('\t')),
+        None => (rest, ""),
+    };
+    let mut kind = None;
+    let mut line_field = None;
+    for field in fields.split('\t').filter(|field| !field.is_empty()) {
+        match field.split_once(':') {
+            Some(("kind", value)) => kind = Some(value.to_string()),
+            Some(("line", value)) => line_field = value.parse().ok(),
+            Some(_) => {}
+            // A field without a name is the kind
+            None => kind = Some(field.to_string()),
+        }
+    }
+
+    let address = if let Some(line) = line_field {
+        TagAddress::Line(line)
+    } else if let Ok(line) = address.parse() {
+        TagAddress::Line(line)
+    } else {
+        let delimiter = address.chars().next().filter(|c| *c == '/' || *c == '?')?;
+        let pattern = address[1..]
+            .strip_suffix(delimiter)
+            .unwrap_or(&address[1..]);
+        TagAddress::Pattern(unescape_pattern(pattern, delimiter))
+    };
+
+    Some(Tag {
+        name: name.to_string(),
+        path: base_dir.join(file),
+        address,
+        kind,
+    })
+}
+
+/// Remove the backslashes ctags puts before the delimiter and backslashes
+fn unescape_pattern(pattern: &str, delimiter: char) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(next) if next == delimiter || next == '\\' => result.push(next),
+                Some(next) => {
+                    result.push('\\');
+                    result.push(next);
+                }
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// 1-based line of `text` a tag points at
+pub fn resolve_line(address: &TagAddress, text: &str) -> Option<usize> {
+    let pattern = match address {
+        TagAddress::Line(line) => return Some(*line),
+        TagAddress::Pattern(pattern) => pattern,
+    };
+    let (anchored_start, pattern) = match pattern.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, pattern.as_str()),
+    };
+    let (anchored_end, pattern) = match pattern.strip_suffix('$') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    text.lines()
+        .position(|line| match (anchored_start, anchored_end) {
+            (true, true) => line == pattern,
+            (true, false) => line.starts_with(pattern),
+            (false, true) => line.ends_with(pattern),
+            (false, false) => line.contains(pattern),
+        })
+        .map(|index| index + 1)
+}
+
+/// The tags file of the project at `root`, if it has one
+pub fn find_tags_file(root: &Path) -> Option<PathBuf> {
+    TAGS_FILE_NAMES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Run `command` (universal-ctags) over `root`, writing its tags file; an
+/// existing `.tags` is regenerated in place, otherwise `tags` is written
+pub fn generate(command: &str, root: &Path) -> Result<PathBuf, String> {
+    let path = find_tags_file(root).unwrap_or_else(|| root.join(TAGS_FILE_NAMES[0]));
+    let output = Command::new(command)
+        .args(["-R", "--fields=+n", "-f"])
+        .arg(&path)
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "'{}' failed: {}",
+            command,
+            stderr.lines().next().unwrap_or("unknown error")
+        ));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAGS: &str = "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+        !_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n\
+        Point\tsrc/geo.rs\t/^pub struct Point {$/;\"\ts\tline:3\n\
+        parse\tsrc/parse.c\t/^int parse(const char *s)$/;\"\tkind:function\n\
+        parse_args\tsrc/cli.c\t42;\"\tf\n\
+        path\tsrc/paths.py\t/^def path(a, b):  # a\\/b$/;\"\tf\n";
+
+    #[test]
+    fn test_parse_tags() {
+        let index = TagIndex::parse(TAGS, Path::new("/project"));
+        assert_eq!(index.len(), 4);
+
+        let point = &index.definitions("Point")[0];
+        assert_eq!(point.path, PathBuf::from("/project/src/geo.rs"));
+        assert_eq!(point.address, TagAddress::Line(3));
+        assert_eq!(point.kind.as_deref(), Some("s"));
+
+        let parse = &index.definitions("parse")[0];
+        assert_eq!(
+            parse.address,
+            TagAddress::Pattern("^int parse(const char *s)$".to_string())
+        );
+        assert_eq!(parse.kind.as_deref(), Some("function"));
+
+        assert_eq!(
+            index.definitions("parse_args")[0].address,
+            TagAddress::Line(42)
+        );
+        assert_eq!(
+            index.definitions("path")[0].address,
+            TagAddress::Pattern("^def path(a, b):  # a/b$".to_string())
+        );
+        assert!(index.definitions("missing").is_empty());
+    }
+
+    #[test]
+    fn test_with_prefix() {
+        let index = TagIndex::parse(TAGS, Path::new("/project"));
+        let names: Vec<_> = index.with_prefix("pa").map(|tag| &tag.name).collect();
+        assert_eq!(names, vec!["parse", "parse_args", "path"]);
+        assert_eq!(index.with_prefix("x").count(), 0);
+    }
+
+    #[test]
+    fn test_resolve_line() {
+        let text = "#include <stdio.h>\n\nint parse(const char *s)\n{\n}\n";
+        let address = TagAddress::Pattern("^int parse(const char *s)$".to_string());
+        assert_eq!(resolve_line(&address, text), Some(3));
+        let address = TagAddress::Pattern("^int missing()$".to_string());
+        assert_eq!(resolve_line(&address, text), None);
+        assert_eq!(resolve_line(&TagAddress::Line(7), text), Some(7));
+    }
+}
@@ -0,0 +1,442 @@
+//! Project tasks (build and test commands).
+//!
+//! This module provides functionality to:
+//! - Run a task's command line through the user's shell in the background,
+//!   reporting its output line by line
+//! - Stop a running task together with the processes it started
+//! - Recognise compiler messages (rustc, gcc/clang, tsc) in task output
+//! - Recognise plain `path:line:col` locations in pasted logs
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// How often the waiting thread checks whether the task has exited
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A task process started by [`spawn`]
+pub struct RunningTask {
+    child: Arc<Mutex<Child>>,
+}
+
+impl RunningTask {
+    /// Stop the task and everything it started
+    pub fn stop(&self) {
+        let Ok(mut child) = self.child.lock() else {
+            return;
+        };
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{killpg, Signal};
+            use nix::unistd::Pid;
+            // The shell leads its own process group, so signal the group
+            // to also stop the compiler it is waiting on
+            let _ = killpg(Pid::from_raw(child.id() as i32), Signal::SIGTERM);
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = child.kill();
+        }
+    }
+}
+
+impl Drop for RunningTask {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Run `command` through the user's shell in `working_dir`
+///
+/// `on_line` is called from background threads with each line of output
+/// (stdout and stderr), and `on_exit` once the process has exited with its
+/// exit code (`None` if it was killed by a signal).
+pub fn spawn(
+    command: &str,
+    working_dir: &Path,
+    on_line: impl Fn(String) + Send + Clone + 'static,
+    on_exit: impl FnOnce(Option<i32>) + Send + 'static,
+) -> io::Result<RunningTask> {
+    let shell = crate::services::terminal::detect_shell();
+    let mut cmd = Command::new(shell);
+    #[cfg(windows)]
+    cmd.args(["/C", command]);
+    #[cfg(not(windows))]
+    cmd.args(["-c", command]);
+    cmd.current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn()?;
+
+    let readers = [
+        child
+            .stdout
+            .take()
+            .map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|stream| {
+        let on_line = on_line.clone();
+        std::thread::spawn(move || read_lines(stream, on_line))
+    })
+    .collect::<Vec<_>>();
+
+    let child = Arc::new(Mutex::new(child));
+    let waiter = Arc::clone(&child);
+    std::thread::spawn(move || {
+        // Deliver all output before reporting the exit
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let status = loop {
+            let status = match waiter.lock() {
+                Ok(mut child) => child.try_wait(),
+                Err(_) => break None,
+            };
+            match status {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) => std::thread::sleep(EXIT_POLL_INTERVAL),
+                Err(_) => break None,
+            }
+        };
+        on_exit(status);
+    });
+
+    Ok(RunningTask { child })
+}
+
+/// Call `on_line` for each line of `stream`, replacing invalid UTF-8
+fn read_lines(stream: Box<dyn Read + Send>, on_line: impl Fn(String)) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let text = String::from_utf8_lossy(&line);
+                on_line(text.trim_end_matches(['\n', '\r']).to_string());
+            }
+        }
+    }
+}
+
+/// Severity of a compiler message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageSeverity {
+    Error,
+    Warning,
+    /// A location without a severity, such as a `grep -n` match
+    Note,
+}
+
+impl MessageSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageSeverity::Error => "error",
+            MessageSeverity::Warning => "warning",
+            MessageSeverity::Note => "note",
+        }
+    }
+}
+
+/// An error or warning found in task output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerMessage {
+    pub severity: MessageSeverity,
+    /// File path as printed by the compiler (often relative to the task's
+    /// working directory)
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, if the compiler printed one
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+struct Patterns {
+    /// rustc: `error[E0308]: mismatched types`
+    rustc_header: Regex,
+    /// rustc: `  --> src/main.rs:4:5`, on the line after the header
+    rustc_location: Regex,
+    /// gcc/clang: `main.c:10:5: error: expected ';'`
+    gcc: Regex,
+    /// tsc: `src/a.ts(10,5): error TS2322: ...`
+    tsc: Regex,
+    /// tsc --pretty: `src/a.ts:10:5 - error TS2322: ...`
+    tsc_pretty: Regex,
+    /// grep -n, ripgrep --vimgrep, test runners: `src/a.py:10:5: message`
+    location: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        rustc_header: Regex::new(r"^(error|warning)(?:\[\w+\])?: (.+)$").unwrap(),
+        rustc_location: Regex::new(r"^\s*--> (.+?):(\d+):(\d+)$").unwrap(),
+        gcc: Regex::new(r"^(.+?):(\d+):(?:(\d+):)? (?:fatal )?(error|warning): (.+)$").unwrap(),
+        tsc: Regex::new(r"^(.+?)\((\d+),(\d+)\): (error|warning) (.+)$").unwrap(),
+        tsc_pretty: Regex::new(r"^(.+?):(\d+):(\d+) - (error|warning) (.+)$").unwrap(),
+        location: Regex::new(r"^\s*([^\s:]+):(\d+):(?:(\d+):)?\s*(.*)$").unwrap(),
+    })
+}
+
+fn severity(s: &str) -> MessageSeverity {
+    if s == "warning" {
+        MessageSeverity::Warning
+    } else {
+        MessageSeverity::Error
+    }
+}
+
+/// Recognises compiler messages in task output, one line at a time
+#[derive(Debug, Default)]
+pub struct OutputParser {
+    /// rustc message waiting for its `-->` location line
+    rustc_header: Option<(MessageSeverity, String)>,
+}
+
+impl OutputParser {
+    /// The compiler message that ends with `line`, if any
+    pub fn parse_line(&mut self, line: &str) -> Option<CompilerMessage> {
+        let patterns = patterns();
+
+        if let Some(caps) = patterns.rustc_location.captures(line) {
+            let (severity, message) = self.rustc_header.take()?;
+            return Some(CompilerMessage {
+                severity,
+                path: caps[1].to_string(),
+                line: caps[2].parse().ok()?,
+                column: caps[3].parse().ok(),
+                message,
+            });
+        }
+        if let Some(caps) = patterns.rustc_header.captures(line) {
+            self.rustc_header = Some((severity(&caps[1]), caps[2].to_string()));
+            return None;
+        }
+
+        if let Some(caps) = patterns.gcc.captures(line) {
+            return Some(CompilerMessage {
+                severity: severity(&caps[4]),
+                path: caps[1].to_string(),
+                line: caps[2].parse().ok()?,
+                column: caps.get(3).and_then(|c| c.as_str().parse().ok()),
+                message: caps[5].to_string(),
+            });
+        }
+        let caps = patterns
+            .tsc
+            .captures(line)
+            .or_else(|| patterns.tsc_pretty.captures(line))?;
+        Some(CompilerMessage {
+            severity: severity(&caps[4]),
+            path: caps[1].to_string(),
+            line: caps[2].parse().ok()?,
+            column: caps[3].parse().ok(),
+            message: caps[5].to_string(),
+        })
+    }
+}
+
+/// Compiler messages and plain `path:line[:col]: text` locations in `text`
+///
+/// Used for logs that did not come from a task, where any location is
+/// worth jumping to.
+pub fn parse_locations(text: &str) -> Vec<CompilerMessage> {
+    let location = &patterns().location;
+    let mut parser = OutputParser::default();
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim_end_matches('\r');
+            if let Some(message) = parser.parse_line(line) {
+                return Some(message);
+            }
+            let caps = location.captures(line)?;
+            Some(CompilerMessage {
+                severity: MessageSeverity::Note,
+                path: caps[1].to_string(),
+                line: caps[2].parse().ok()?,
+                column: caps.get(3).and_then(|c| c.as_str().parse().ok()),
+                message: caps[4].to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_all(output: &str) -> Vec<CompilerMessage> {
+        let mut parser = OutputParser::default();
+        output
+            .lines()
+            .filter_map(|line| parser.parse_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_rustc_messages() {
+        let output = "\
+   Compiling demo v0.1.0
+warning: unused variable: `x`
+ --> src/lib.rs:2:9
+  |
+2 |     let x = 1;
+  |         ^ help: prefix it with an underscore: `_x`
+error[E0308]: mismatched types
+  --> src/main.rs:14:5
+error: could not compile `demo` (bin \"demo\") due to 1 previous error
+";
+        let messages = parse_all(output);
+        assert_eq!(
+            messages,
+            vec![
+                CompilerMessage {
+                    severity: MessageSeverity::Warning,
+                    path: "src/lib.rs".to_string(),
+                    line: 2,
+                    column: Some(9),
+                    message: "unused variable: `x`".to_string(),
+                },
+                CompilerMessage {
+                    severity: MessageSeverity::Error,
+                    path: "src/main.rs".to_string(),
+                    line: 14,
+                    column: Some(5),
+                    message: "mismatched types".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gcc_and_tsc_messages() {
+        let output = "\
+main.c:10:5: error: expected ';' before '}' token
+util.h:3: warning: no newline at end of file
+main.c: In function 'main':
+src/app.ts(7,13): error TS2322: Type 'string' is not assignable to type 'number'.
+src/app.ts:9:1 - warning TS6133: 'y' is declared but never read.
+";
+        let summary: Vec<_> = parse_all(output)
+            .into_iter()
+            .map(|m| (m.severity, m.path, m.line, m.column))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (MessageSeverity::Error, "main.c".to_string(), 10, Some(5)),
+                (MessageSeverity::Warning, "util.h".to_string(), 3, None),
+                (
+                    MessageSeverity::Error,
+                    "src/app.ts".to_string(),
+                    7,
+                    Some(13)
+                ),
+                (
+                    MessageSeverity::Warning,
+                    "src/app.ts".to_string(),
+                    9,
+                    Some(1)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_locations_in_pasted_log() {
+        let log = "\
+src/lib.rs:12:5: let total = 0;\r
+docs/notes.md:3:TODO: write this
+main.c:10:5: error: expected ';'
+error[E0425]: cannot find value `y` in this scope
+ --> src/main.rs:4:13
+Finished in 0.2s: 3 passed
+";
+        let summary: Vec<_> = parse_locations(log)
+            .into_iter()
+            .map(|m| (m.severity, m.path, m.line, m.column, m.message))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    MessageSeverity::Note,
+                    "src/lib.rs".to_string(),
+                    12,
+                    Some(5),
+                    "let total = 0;".to_string()
+                ),
+                (
+                    MessageSeverity::Note,
+                    "docs/notes.md".to_string(),
+                    3,
+                    None,
+                    "TODO: write this".to_string()
+                ),
+                (
+                    MessageSeverity::Error,
+                    "main.c".to_string(),
+                    10,
+                    Some(5),
+                    "expected ';'".to_string()
+                ),
+                (
+                    MessageSeverity::Error,
+                    "src/main.rs".to_string(),
+                    4,
+                    Some(13),
+                    "cannot find value `y` in this scope".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_streams_output_and_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let exit_tx = tx.clone();
+        // Dropping the task would stop it
+        let _task = spawn(
+            "echo out; echo err >&2; exit 3",
+            dir.path(),
+            move |line| tx.send(Ok(line)).unwrap(),
+            move |code| exit_tx.send(Err(code)).unwrap(),
+        )
+        .unwrap();
+
+        let mut lines = Vec::new();
+        let exit = loop {
+            match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+                Ok(line) => lines.push(line),
+                Err(code) => break code,
+            }
+        };
+        lines.sort();
+        assert_eq!(lines, vec!["err", "out"]);
+        assert_eq!(exit, Some(3));
+    }
+}
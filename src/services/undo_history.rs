@@ -0,0 +1,435 @@
+//! Undo history persisted across sessions.
+//!
+//! When a file buffer is closed (or the editor exits) its undo history is
+//! written to `$XDG_STATE_HOME/fresh/undo/{path_hash}.json`, keeping only
+//! the text edits of each undo step. The file also records a checksum of
+//! the buffer content at that point: when the file is opened again and its
+//! content still matches, the history is restored so edits from an earlier
+//! session can be undone (and redone).
+//!
+//! Histories are capped in size (the oldest steps are dropped first) and
+//! expire after a number of days; both limits are set in `EditorConfig`.
+//!
+//! ## Crash Resistance
+//!
+//! Uses atomic writes: write to temp file, then rename.
+
+use crate::model::event::{CursorId, Event, EventLog};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Current undo history file format version
+const UNDO_HISTORY_VERSION: u32 = 1;
+
+/// A text edit of an undo step: `I` inserts text at a byte offset and `D`
+/// deletes the given text from it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersistedEdit {
+    I(usize, String),
+    D(usize, String),
+}
+
+impl PersistedEdit {
+    fn event(&self, cursor_id: CursorId) -> Event {
+        match self {
+            Self::I(position, text) => Event::Insert {
+                position: *position,
+                text: text.clone(),
+                cursor_id,
+            },
+            Self::D(position, text) => Event::Delete {
+                range: *position..position + text.len(),
+                deleted_text: text.clone(),
+                cursor_id,
+            },
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::I(_, text) | Self::D(_, text) => text.len() + 16,
+        }
+    }
+}
+
+/// Undo history of one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedUndoHistory {
+    /// Schema version for future migrations
+    pub version: u32,
+
+    /// The file the history belongs to
+    pub path: PathBuf,
+
+    /// SHA-256 of the buffer content after the first `current` steps
+    pub checksum: String,
+
+    /// Timestamp when last saved (Unix epoch seconds)
+    pub saved_at: u64,
+
+    /// Undo steps, oldest first; each is undone as one unit
+    pub steps: Vec<Vec<PersistedEdit>>,
+
+    /// Whether each step is undone together with the step before it, as
+    /// the characters of a typed word are; empty in older histories
+    #[serde(default)]
+    pub joined: Vec<bool>,
+
+    /// Number of steps applied to the content (the rest can be redone)
+    pub current: usize,
+}
+
+impl PersistedUndoHistory {
+    /// Capture the text edits of `log` for a buffer whose content is
+    /// `content`. Returns None when there is nothing to undo or redo.
+    pub fn from_event_log(path: &Path, log: &EventLog, content: &[u8]) -> Option<Self> {
+        let mut steps = Vec::new();
+        let mut joined = Vec::new();
+        let mut current = 0;
+        for (index, entry) in log.entries().iter().enumerate() {
+            let mut edits = Vec::new();
+            collect_edits(&entry.event, &mut edits);
+            if edits.is_empty() {
+                continue;
+            }
+            // The first step has no step before it to be undone with
+            joined.push(entry.joined && !steps.is_empty());
+            steps.push(edits);
+            if index < log.current_index() {
+                current = steps.len();
+            }
+        }
+        if steps.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            version: UNDO_HISTORY_VERSION,
+            path: path.to_path_buf(),
+            checksum: content_checksum(content),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            steps,
+            joined,
+            current,
+        })
+    }
+
+    /// Drop the oldest steps (then the newest redo steps) until the edits
+    /// take at most `max_bytes`
+    pub fn truncate_to_size(&mut self, max_bytes: usize) {
+        let step_size = |step: &Vec<PersistedEdit>| step.iter().map(|e| e.size()).sum::<usize>();
+        let mut total: usize = self.steps.iter().map(step_size).sum();
+        let mut drop_front = 0;
+        while total > max_bytes && drop_front < self.current {
+            total -= step_size(&self.steps[drop_front]);
+            drop_front += 1;
+        }
+        self.steps.drain(..drop_front);
+        self.joined.drain(..drop_front.min(self.joined.len()));
+        if let Some(first) = self.joined.first_mut() {
+            *first = false;
+        }
+        self.current -= drop_front;
+        while total > max_bytes && self.steps.len() > self.current {
+            total -= step_size(&self.steps.pop().unwrap());
+        }
+        self.joined.truncate(self.steps.len());
+    }
+
+    /// Rebuild an event log positioned at `current`, with the restored edits
+    /// applied to `cursor_id` when redone
+    pub fn to_event_log(&self, cursor_id: CursorId) -> EventLog {
+        let events = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let event = match step.as_slice() {
+                    [edit] => edit.event(cursor_id),
+                    edits => Event::Batch {
+                        events: edits.iter().map(|edit| edit.event(cursor_id)).collect(),
+                        description: "Restored edit".to_string(),
+                    },
+                };
+                (event, self.joined.get(index).copied().unwrap_or(false))
+            })
+            .collect();
+        EventLog::restore(events, self.current)
+    }
+}
+
+/// The text edits of an event, in the order they are applied
+fn collect_edits(event: &Event, edits: &mut Vec<PersistedEdit>) {
+    match event {
+        Event::Insert { position, text, .. } => {
+            edits.push(PersistedEdit::I(*position, text.clone()))
+        }
+        Event::Delete {
+            range,
+            deleted_text,
+            ..
+        } => edits.push(PersistedEdit::D(range.start, deleted_text.clone())),
+        Event::Batch { events, .. } => {
+            for event in events {
+                collect_edits(event, edits);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// SHA-256 of file content, as hex
+pub fn content_checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Undo history files in a directory, one per file path
+#[derive(Debug, Clone)]
+pub struct UndoHistoryStore {
+    dir: PathBuf,
+}
+
+impl UndoHistoryStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// History file for a source file
+    fn history_path(&self, source_path: &Path) -> PathBuf {
+        let canonical = source_path
+            .canonicalize()
+            .unwrap_or_else(|_| source_path.to_path_buf());
+        let hash = content_checksum(canonical.to_string_lossy().as_bytes());
+        self.dir.join(format!("{}.json", &hash[..16]))
+    }
+
+    /// Load the history of `path` if it was saved for `content` and is not
+    /// older than `max_age`
+    pub fn load(
+        &self,
+        path: &Path,
+        content: &[u8],
+        max_age: Duration,
+    ) -> Option<PersistedUndoHistory> {
+        let history_path = self.history_path(path);
+        let json = std::fs::read_to_string(&history_path).ok()?;
+        let history: PersistedUndoHistory = serde_json::from_str(&json).ok()?;
+        if history.version > UNDO_HISTORY_VERSION || is_expired(history.saved_at, max_age) {
+            return None;
+        }
+        if history.checksum != content_checksum(content) {
+            tracing::debug!("Undo history for {:?} doesn't match its content", path);
+            return None;
+        }
+        Some(history)
+    }
+
+    /// Save the history of a file (atomic write)
+    pub fn save(&self, history: &PersistedUndoHistory) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let history_path = self.history_path(&history.path);
+        let json = serde_json::to_string(history).map_err(io::Error::other)?;
+
+        let temp_path = history_path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, &history_path)
+    }
+
+    /// Remove the saved history of a file
+    pub fn remove(&self, path: &Path) {
+        let _ = std::fs::remove_file(self.history_path(path));
+    }
+
+    /// Remove history files not written to for longer than `max_age`.
+    /// Returns the number removed.
+    pub fn prune(&self, max_age: Duration) -> usize {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return 0;
+        };
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let expired = entry
+                .metadata()
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > max_age);
+            if expired && std::fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+        removed
+    }
+}
+
+fn is_expired(saved_at: u64, max_age: Duration) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    now.saturating_sub(saved_at) > max_age.as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn log_with_edits() -> EventLog {
+        let cursor_id = CursorId(0);
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "hello".to_string(),
+            cursor_id,
+        });
+        // Not a text edit: not persisted
+        log.append(Event::MoveCursor {
+            cursor_id,
+            old_position: 5,
+            new_position: 0,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+        log.append(Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: 0..1,
+                    deleted_text: "h".to_string(),
+                    cursor_id,
+                },
+                Event::Insert {
+                    position: 0,
+                    text: "J".to_string(),
+                    cursor_id,
+                },
+            ],
+            description: "Replace".to_string(),
+        });
+        log.append(Event::Insert {
+            position: 5,
+            text: "!".to_string(),
+            cursor_id,
+        });
+        log.undo();
+        log
+    }
+
+    #[test]
+    fn test_history_from_event_log() {
+        let history =
+            PersistedUndoHistory::from_event_log(Path::new("/a.txt"), &log_with_edits(), b"Jello")
+                .unwrap();
+        assert_eq!(
+            history.steps,
+            vec![
+                vec![PersistedEdit::I(0, "hello".to_string())],
+                vec![
+                    PersistedEdit::D(0, "h".to_string()),
+                    PersistedEdit::I(0, "J".to_string())
+                ],
+                vec![PersistedEdit::I(5, "!".to_string())],
+            ]
+        );
+        assert_eq!(history.current, 2);
+        assert_eq!(history.checksum, content_checksum(b"Jello"));
+
+        let log = history.to_event_log(CursorId(0));
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.current_index(), 2);
+        assert!(log.can_redo());
+        assert!(log.is_at_saved_position());
+
+        assert!(
+            PersistedUndoHistory::from_event_log(Path::new("/a.txt"), &EventLog::new(), b"")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_typed_words_together() {
+        let cursor_id = CursorId(0);
+        let mut log = EventLog::new();
+        let now = std::time::Instant::now();
+        for (position, c) in "ab cd".chars().enumerate() {
+            let event = Event::Insert {
+                position,
+                text: c.to_string(),
+                cursor_id,
+            };
+            log.append_typed(event, c.is_alphanumeric(), now, Duration::from_secs(1));
+        }
+        let history =
+            PersistedUndoHistory::from_event_log(Path::new("/a.txt"), &log, b"ab cd").unwrap();
+        assert_eq!(history.joined, vec![false, true, true, false, true]);
+
+        let mut log = history.to_event_log(cursor_id);
+        assert_eq!(log.undo().len(), 2);
+        assert_eq!(log.undo().len(), 3);
+        assert!(!log.can_undo());
+
+        // Histories from before words were grouped undo a step at a time
+        let old: PersistedUndoHistory = serde_json::from_str(
+            r#"{"version":1,"path":"/a.txt","checksum":"","saved_at":0,"steps":[[{"I":[0,"a"]}],[{"I":[1,"b"]}]],"current":2}"#,
+        )
+        .unwrap();
+        assert!(old.joined.is_empty());
+        let mut log = old.to_event_log(cursor_id);
+        assert_eq!(log.undo().len(), 1);
+        assert!(log.can_undo());
+    }
+
+    #[test]
+    fn test_truncate_to_size() {
+        let mut history =
+            PersistedUndoHistory::from_event_log(Path::new("/a.txt"), &log_with_edits(), b"Jello")
+                .unwrap();
+        // Room for two of the three steps: the oldest goes first
+        history.truncate_to_size(60);
+        assert_eq!(history.steps.len(), 2);
+        assert_eq!(history.current, 1);
+
+        history.truncate_to_size(10);
+        assert_eq!(history.steps.len(), 0);
+        assert_eq!(history.current, 0);
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = UndoHistoryStore::new(temp_dir.path().join("undo"));
+        let path = temp_dir.path().join("a.txt");
+        let history =
+            PersistedUndoHistory::from_event_log(&path, &log_with_edits(), b"Jello").unwrap();
+        store.save(&history).unwrap();
+
+        assert_eq!(store.load(&path, b"Jello", DAY), Some(history.clone()));
+        // Changed outside the editor: the edits no longer apply
+        assert_eq!(store.load(&path, b"Jello!", DAY), None);
+        assert_eq!(
+            store.load(&temp_dir.path().join("b.txt"), b"Jello", DAY),
+            None
+        );
+
+        let mut old = history;
+        old.saved_at -= 3 * DAY.as_secs();
+        store.save(&old).unwrap();
+        assert_eq!(store.load(&path, b"Jello", 2 * DAY), None);
+
+        assert_eq!(store.prune(DAY), 0);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(store.prune(Duration::ZERO), 1);
+        assert_eq!(store.load(&path, b"Jello", 5 * DAY), None);
+    }
+}
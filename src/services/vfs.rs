@@ -0,0 +1,364 @@
+//! Virtual filesystem providers.
+//!
+//! This module provides functionality to:
+//! - Parse URLs like `git://HEAD~1/src/main.rs` into a scheme and a path
+//! - Keep the providers that back buffers with non-file sources, by scheme
+//! - Read files from git revisions (`git://REV/path`), zip archives
+//!   (`zip://archive.zip!/path`) and command output (`cmd://command`)
+//!
+//! Plugins register further schemes; their reads and writes go through the
+//! `vfs_read` and `vfs_write` hooks instead of a `VfsProvider`. Buffers opened
+//! from a provider keep their content in memory, so no temp files are written.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+
+/// A location served by a provider: `<scheme>://<path>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VfsUrl {
+    pub scheme: String,
+    /// Everything after `://`, interpreted by the provider
+    pub path: String,
+}
+
+impl VfsUrl {
+    /// Split `scheme://path`. Any scheme is accepted; use
+    /// `VfsRegistry::parse` to only accept registered ones.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (scheme, path) = input.split_once("://")?;
+        let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_lowercase())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c));
+        if !valid_scheme || path.is_empty() {
+            return None;
+        }
+        Some(Self {
+            scheme: scheme.to_string(),
+            path: path.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for VfsUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.path)
+    }
+}
+
+/// A built-in source of buffer content. Reads and writes run on a
+/// background thread.
+pub trait VfsProvider: Send + Sync {
+    /// Read the content at `path`, resolving relative paths against
+    /// `working_dir`
+    fn read(&self, path: &str, working_dir: &Path) -> Result<String, String>;
+
+    /// Whether buffers from this provider can be saved
+    fn writable(&self) -> bool {
+        false
+    }
+
+    /// Name of the buffer showing `path`, if not the URL itself
+    fn display_name(&self, _path: &str) -> Option<String> {
+        None
+    }
+
+    /// Write `content` back to `path`
+    fn write(&self, path: &str, _content: &str, _working_dir: &Path) -> Result<(), String> {
+        Err(format!("{} is read-only", path))
+    }
+}
+
+/// Files at a git revision: `git://REV/path`, with `path` relative to the
+/// working directory or absolute (e.g. `git://HEAD~1/src/main.rs`)
+pub struct GitProvider;
+
+impl VfsProvider for GitProvider {
+    fn read(&self, path: &str, working_dir: &Path) -> Result<String, String> {
+        let (revision, file) = split_git_path(path)?;
+        // Run git next to the file, so it is found in the right repository
+        let file = working_dir.join(file);
+        let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+            return Err(format!("Expected git://REVISION/PATH, got git://{}", path));
+        };
+        let output = Command::new("git")
+            .arg("show")
+            .arg(format!("{}:./{}", revision, name.to_string_lossy()))
+            .current_dir(dir)
+            .output()
+            .map_err(|e| format!("Failed to run git: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git show failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn display_name(&self, path: &str) -> Option<String> {
+        let (revision, file) = split_git_path(path).ok()?;
+        let name = Path::new(file).file_name()?.to_string_lossy();
+        Some(format!("{}@{}", name, revision))
+    }
+}
+
+/// Split `REV/path` into the revision and the path
+fn split_git_path(path: &str) -> Result<(&str, &str), String> {
+    let (revision, file) = path
+        .split_once('/')
+        .filter(|(revision, file)| !revision.is_empty() && !file.is_empty())
+        .ok_or_else(|| format!("Expected git://REVISION/PATH, got git://{}", path))?;
+    // git would parse it as an option
+    if revision.starts_with('-') {
+        return Err(format!("Invalid git revision: {}", revision));
+    }
+    Ok((revision, file))
+}
+
+/// A commit that changed a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRevision {
+    /// Abbreviated commit hash
+    pub hash: String,
+    /// First line of the commit message
+    pub summary: String,
+    pub author: String,
+    /// Relative commit date (e.g. "3 days ago")
+    pub date: String,
+}
+
+/// URL of `file` at `revision`
+pub fn git_revision_url(revision: &str, file: &Path) -> VfsUrl {
+    VfsUrl {
+        scheme: "git".to_string(),
+        path: format!("{}/{}", revision, file.display()),
+    }
+}
+
+/// The commits that changed `file`, newest first
+pub fn git_file_revisions(file: &Path) -> Result<Vec<GitRevision>, String> {
+    let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+        return Err(format!("{} is not a file", file.display()));
+    };
+    let output = Command::new("git")
+        .args(["log", "--format=%h%x09%an%x09%ar%x09%s", "--"])
+        .arg(name)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            Some(GitRevision {
+                hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                summary: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Files inside a zip archive: `zip://archive.zip!/path/in/archive`
+pub struct ZipProvider;
+
+impl VfsProvider for ZipProvider {
+    fn read(&self, path: &str, working_dir: &Path) -> Result<String, String> {
+        let (archive, entry) = path
+            .split_once("!/")
+            .ok_or_else(|| format!("Expected zip://ARCHIVE!/PATH, got zip://{}", path))?;
+        let data = std::fs::read(working_dir.join(archive))
+            .map_err(|e| format!("Failed to read {}: {}", archive, e))?;
+        let zip = crate::services::grammar_installer::ZipArchive::parse(&data)
+            .map_err(|e| format!("{}: {}", archive, e))?;
+        let content = zip
+            .read(entry)
+            .map_err(|_| format!("No {} in {}", entry, archive))?;
+        Ok(String::from_utf8_lossy(&content).into_owned())
+    }
+}
+
+/// Output of a shell command run in the working directory:
+/// `cmd://git log --oneline`
+pub struct CommandProvider;
+
+impl VfsProvider for CommandProvider {
+    fn read(&self, path: &str, working_dir: &Path) -> Result<String, String> {
+        #[cfg(unix)]
+        let mut command = {
+            let mut command = Command::new("sh");
+            command.args(["-c", path]);
+            command
+        };
+        #[cfg(not(unix))]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.args(["/C", path]);
+            command
+        };
+        let output = command
+            .current_dir(working_dir)
+            .output()
+            .map_err(|e| format!("Failed to run '{}': {}", path, e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "'{}' failed: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// How a scheme is served
+#[derive(Clone)]
+pub enum Provider {
+    BuiltIn(Arc<dyn VfsProvider>),
+    /// Served by a plugin through the `vfs_read` and `vfs_write` hooks
+    Plugin {
+        writable: bool,
+    },
+}
+
+impl Provider {
+    /// Whether buffers from this provider can be saved
+    pub fn writable(&self) -> bool {
+        match self {
+            Self::BuiltIn(provider) => provider.writable(),
+            Self::Plugin { writable } => *writable,
+        }
+    }
+}
+
+/// Providers by scheme
+pub struct VfsRegistry {
+    providers: HashMap<String, Provider>,
+}
+
+impl Default for VfsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VfsRegistry {
+    /// A registry with the built-in `git`, `zip` and `cmd` providers
+    pub fn new() -> Self {
+        let mut registry = Self {
+            providers: HashMap::new(),
+        };
+        registry.register("git", Provider::BuiltIn(Arc::new(GitProvider)));
+        registry.register("zip", Provider::BuiltIn(Arc::new(ZipProvider)));
+        registry.register("cmd", Provider::BuiltIn(Arc::new(CommandProvider)));
+        registry
+    }
+
+    /// Serve `scheme` with `provider`, replacing any previous provider
+    pub fn register(&mut self, scheme: &str, provider: Provider) {
+        self.providers.insert(scheme.to_string(), provider);
+    }
+
+    /// The provider of `scheme`
+    pub fn get(&self, scheme: &str) -> Option<&Provider> {
+        self.providers.get(scheme)
+    }
+
+    /// Name of the buffer showing `url`
+    pub fn display_name(&self, url: &VfsUrl) -> String {
+        match self.providers.get(&url.scheme) {
+            Some(Provider::BuiltIn(provider)) => provider.display_name(&url.path),
+            _ => None,
+        }
+        .unwrap_or_else(|| url.to_string())
+    }
+
+    /// Parse `input` as a URL with a registered scheme
+    pub fn parse(&self, input: &str) -> Option<VfsUrl> {
+        VfsUrl::parse(input).filter(|url| self.providers.contains_key(&url.scheme))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let url = VfsUrl::parse("git://HEAD~1/src/main.rs").unwrap();
+        assert_eq!(url.scheme, "git");
+        assert_eq!(url.path, "HEAD~1/src/main.rs");
+        assert_eq!(url.to_string(), "git://HEAD~1/src/main.rs");
+
+        assert!(VfsUrl::parse("src/main.rs").is_none());
+        assert!(VfsUrl::parse("Git://HEAD/a").is_none());
+        assert!(VfsUrl::parse("git://").is_none());
+
+        let registry = VfsRegistry::new();
+        assert!(registry.parse("zip://a.zip!/b").is_some());
+        assert!(registry.parse("s3://bucket/key").is_none());
+    }
+
+    #[test]
+    fn test_git_provider_reads_old_revision() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.txt"), "first\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "first"]);
+        std::fs::write(dir.path().join("src/a.txt"), "second\n").unwrap();
+        git(&["commit", "-q", "-am", "second"]);
+
+        assert_eq!(
+            GitProvider.read("HEAD~1/src/a.txt", dir.path()).unwrap(),
+            "first\n"
+        );
+        assert_eq!(
+            GitProvider.read("HEAD/src/a.txt", dir.path()).unwrap(),
+            "second\n"
+        );
+        assert!(GitProvider
+            .read("HEAD/src/missing.txt", dir.path())
+            .is_err());
+        assert!(GitProvider.read("HEAD", dir.path()).is_err());
+        assert!(GitProvider
+            .read("--output=out.txt/src/a.txt", dir.path())
+            .is_err());
+        assert!(!dir.path().join("src/out.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            CommandProvider.read("echo hello", dir.path()).unwrap(),
+            "hello\n"
+        );
+        assert!(CommandProvider.read("exit 3", dir.path()).is_err());
+        assert!(!CommandProvider.writable());
+    }
+}
@@ -6,6 +6,7 @@
 //! - File explorer state
 //! - Search/replace history and options
 //! - Bookmarks
+//! - Per-file extras: language override and read-only flag
 //!
 //! ## Storage
 //!
@@ -30,9 +31,13 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::input::input_history::get_data_dir;
+use crate::services::tasks::MessageSeverity;
 
 /// Current session file format version
-pub const SESSION_VERSION: u32 = 1;
+///
+/// - 1: initial format
+/// - 2: per-file `buffer_states` replace `language_overrides`
+pub const SESSION_VERSION: u32 = 2;
 
 /// Current per-file session version
 pub const FILE_SESSION_VERSION: u32 = 1;
@@ -74,10 +79,29 @@ pub struct Session {
     #[serde(default)]
     pub bookmarks: HashMap<char, SerializedBookmark>,
 
+    /// Per-file state that is not per-split (file path relative to
+    /// working_dir -> state)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub buffer_states: HashMap<PathBuf, SerializedBufferState>,
+
+    /// Syntax overrides from "Change Language Mode" (file path relative to
+    /// working_dir -> syntax name)
+    /// Deprecated (version 1); moved into `buffer_states` when loaded.
+    #[serde(default, skip_serializing)]
+    pub language_overrides: HashMap<PathBuf, String>,
+
+    /// Quickfix list (locations from a task or a pasted log)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quickfix: Option<SerializedQuickfixList>,
+
     /// Open terminal sessions (for restoration)
     #[serde(default)]
     pub terminals: Vec<SerializedTerminalSession>,
 
+    /// Folders added to the workspace besides the working directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_folders: Vec<PathBuf>,
+
     /// Timestamp when session was saved (Unix epoch seconds)
     pub saved_at: u64,
 }
@@ -144,6 +168,10 @@ pub struct SerializedSplitViewState {
     /// Compose width if in compose mode
     #[serde(default)]
     pub compose_width: Option<u16>,
+
+    /// The split's own working directory, if it has one
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
 }
 
 /// Per-file state within a split
@@ -262,6 +290,21 @@ pub struct SearchOptions {
     pub confirm_each: bool,
 }
 
+/// Per-file state shared by every split showing the file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializedBufferState {
+    /// Syntax chosen with "Change Language Mode"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Editing was turned off (e.g. in the large file prompt)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub read_only: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
 /// Serialized bookmark (file path + byte offset)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedBookmark {
@@ -271,6 +314,31 @@ pub struct SerializedBookmark {
     pub position: usize,
 }
 
+/// Serialized quickfix list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedQuickfixList {
+    /// Where the entries came from, e.g. "Task: build"
+    pub title: String,
+    pub entries: Vec<SerializedQuickfixEntry>,
+    /// Entry last jumped to
+    #[serde(default)]
+    pub current: Option<usize>,
+}
+
+/// Serialized quickfix location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedQuickfixEntry {
+    /// File path (relative to working_dir when inside it)
+    pub file_path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column
+    #[serde(default)]
+    pub column: Option<usize>,
+    pub severity: MessageSeverity,
+    pub message: String,
+}
+
 /// Reference to an open tab (file path or terminal index)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SerializedTabRef {
@@ -602,7 +670,7 @@ impl Session {
 
         tracing::debug!("Loading session from {:?}", path);
         let content = std::fs::read_to_string(&path)?;
-        let session: Session = serde_json::from_str(&content)?;
+        let mut session: Session = serde_json::from_str(&content)?;
 
         tracing::debug!(
             "Loaded session: version={}, split_states={}, active_split={}",
@@ -641,10 +709,21 @@ impl Session {
                 max_supported: SESSION_VERSION,
             });
         }
+        session.migrate();
 
         Ok(Some(session))
     }
 
+    /// Bring a session written in an older format up to the current one
+    fn migrate(&mut self) {
+        if self.version < 2 {
+            for (path, language) in self.language_overrides.drain() {
+                self.buffer_states.entry(path).or_default().language = Some(language);
+            }
+        }
+        self.version = SESSION_VERSION;
+    }
+
     /// Save session to file using atomic write (temp file + rename)
     ///
     /// This ensures the session file is never left in a corrupted state:
@@ -706,7 +785,11 @@ impl Session {
             histories: SessionHistories::default(),
             search_options: SearchOptions::default(),
             bookmarks: HashMap::new(),
+            buffer_states: HashMap::new(),
+            language_overrides: HashMap::new(),
+            quickfix: None,
             terminals: Vec::new(),
+            workspace_folders: Vec::new(),
             saved_at: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -786,6 +869,34 @@ mod tests {
         assert_eq!(session.working_dir, restored.working_dir);
     }
 
+    #[test]
+    fn test_session_quickfix_serialization() {
+        let mut session = Session::new(PathBuf::from("/home/user/test"));
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(!json.contains("quickfix"));
+
+        session.quickfix = Some(SerializedQuickfixList {
+            title: "Task: build".to_string(),
+            entries: vec![SerializedQuickfixEntry {
+                file_path: PathBuf::from("src/main.c"),
+                line: 2,
+                column: Some(11),
+                severity: MessageSeverity::Error,
+                message: "expected expression".to_string(),
+            }],
+            current: Some(0),
+        });
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(json.contains("\"severity\":\"error\""));
+
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        let quickfix = restored.quickfix.unwrap();
+        assert_eq!(quickfix.title, "Task: build");
+        assert_eq!(quickfix.current, Some(0));
+        assert_eq!(quickfix.entries[0].file_path, PathBuf::from("src/main.c"));
+        assert_eq!(quickfix.entries[0].column, Some(11));
+    }
+
     #[test]
     fn test_session_config_overrides_skip_none() {
         let overrides = SessionConfigOverrides::default();
@@ -956,6 +1067,7 @@ mod tests {
                 tab_scroll_offset: 0,
                 view_mode: SerializedViewMode::Source,
                 compose_width: None,
+                working_dir: None,
             },
         );
 
@@ -1049,6 +1161,30 @@ mod tests {
         assert_eq!(restored.version, 999);
     }
 
+    #[test]
+    fn test_session_migrates_language_overrides() {
+        // A version 1 session kept syntax overrides in their own map
+        let mut json_value = serde_json::to_value(Session::new(PathBuf::from("/test"))).unwrap();
+        json_value["version"] = serde_json::json!(1);
+        json_value["language_overrides"] = serde_json::json!({ "build.conf": "ini" });
+        let mut session: Session = serde_json::from_value(json_value).unwrap();
+
+        session.migrate();
+        assert_eq!(session.version, SESSION_VERSION);
+        assert!(session.language_overrides.is_empty());
+        assert_eq!(
+            session.buffer_states.get(Path::new("build.conf")),
+            Some(&SerializedBufferState {
+                language: Some("ini".to_string()),
+                read_only: false,
+            })
+        );
+
+        // The old map is not written again
+        let json = serde_json::to_value(&session).unwrap();
+        assert!(json.get("language_overrides").is_none());
+    }
+
     #[test]
     fn test_empty_session_histories() {
         let histories = SessionHistories::default();
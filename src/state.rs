@@ -46,6 +46,9 @@ pub struct EditorState {
     /// Syntax highlighter (tree-sitter or TextMate based on language)
     pub highlighter: HighlightEngine,
 
+    /// Syntax chosen with "Change Language Mode" (None = detect from the file)
+    pub syntax_override: Option<String>,
+
     /// Auto-indent calculator for smart indentation (RefCell for interior mutability)
     pub indent_calculator: RefCell<IndentCalculator>,
 
@@ -84,6 +87,10 @@ pub struct EditorState {
     /// but navigation, selection, and copy are still allowed
     pub editing_disabled: bool,
 
+    /// Whether the rest of the file is still being read in the background.
+    /// Until it is, the buffer can be navigated but not edited or saved.
+    pub loading: bool,
+
     /// Whether to show whitespace tab indicators (→) for this buffer
     /// Set based on language config; defaults to true
     pub show_whitespace_tabs: bool,
@@ -99,6 +106,13 @@ pub struct EditorState {
     /// Semantic highlighter for word occurrence highlighting
     pub semantic_highlighter: SemanticHighlighter,
 
+    /// Whether the buffer is in protected mode (a binary or minified file),
+    /// with syntax and word highlighting turned off
+    pub protected: bool,
+
+    /// Whether the buffer is reloaded when its file changes on disk
+    pub auto_revert: bool,
+
     /// View mode for this buffer (Source or Compose)
     pub view_mode: ViewMode,
 
@@ -115,6 +129,9 @@ pub struct EditorState {
     /// Optional column guides (e.g., for tables) supplied by layout hints
     pub compose_column_guides: Option<Vec<u16>>,
 
+    /// Columns drawn with the ruler background (e.g., 50 and 72 in commit messages)
+    pub rulers: Vec<usize>,
+
     /// Optional transformed view payload for current viewport (tokens + map)
     pub view_transform: Option<crate::services::plugins::api::ViewTransformPayload>,
 }
@@ -129,6 +146,7 @@ impl EditorState {
             buffer: Buffer::new(large_file_threshold),
             cursors: Cursors::new(),
             highlighter: HighlightEngine::None, // No file path, so no syntax highlighting
+            syntax_override: None,
             indent_calculator: RefCell::new(IndentCalculator::new()),
             overlays: OverlayManager::new(),
             marker_list: MarkerList::new(),
@@ -140,15 +158,19 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            loading: false,
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4, // Default tab size
             semantic_highlighter: SemanticHighlighter::new(),
+            protected: false,
+            auto_revert: true,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
             compose_width: None,
             compose_prev_line_numbers: None,
             compose_column_guides: None,
+            rulers: Vec::new(),
             view_transform: None,
         }
     }
@@ -168,6 +190,84 @@ impl EditorState {
         );
     }
 
+    /// Override the syntax used for this buffer, or restore detection from
+    /// the file path when `name` is `None`.
+    /// Returns false (leaving the buffer unchanged) if no syntax has that name.
+    pub fn set_syntax_override(
+        &mut self,
+        name: Option<String>,
+        registry: &GrammarRegistry,
+    ) -> bool {
+        let highlighter = match &name {
+            Some(name) => match HighlightEngine::for_syntax_name(name, registry) {
+                Some(engine) => engine,
+                None => return false,
+            },
+            None => match self.buffer.file_path() {
+                Some(path) => Self::detect_highlighter(path, &self.buffer, registry),
+                None => HighlightEngine::None,
+            },
+        };
+
+        if let Some(language) = highlighter.language() {
+            self.semantic_highlighter.set_language(language);
+        }
+        self.highlighter = highlighter;
+        self.syntax_override = name;
+        true
+    }
+
+    /// Detect the highlighter for a buffer from its file name, falling back
+    /// to modelines and the shebang line when the name gives no syntax
+    fn detect_highlighter(
+        path: &std::path::Path,
+        buffer: &Buffer,
+        registry: &GrammarRegistry,
+    ) -> HighlightEngine {
+        /// Lines scanned at each end of the file for modelines
+        const MODELINE_SCAN_LINES: usize = 5;
+
+        let highlighter = HighlightEngine::for_file(path, registry);
+        let is_plain = highlighter.language().is_none()
+            && highlighter
+                .syntax_name()
+                .is_none_or(|name| name == "Plain Text");
+        if !is_plain {
+            return highlighter;
+        }
+
+        let read_line = |line: usize| {
+            buffer
+                .get_line(line)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        };
+        let head: Vec<String> = (0..MODELINE_SCAN_LINES).map_while(read_line).collect();
+        let tail: Vec<String> = match buffer.line_count() {
+            Some(count) if count > MODELINE_SCAN_LINES => (count - MODELINE_SCAN_LINES..count)
+                .filter_map(read_line)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let head: Vec<&str> = head.iter().map(|l| l.trim_end()).collect();
+        let tail: Vec<&str> = tail.iter().map(|l| l.trim_end()).collect();
+
+        registry
+            .find_syntax_by_content(&head, &tail)
+            .and_then(|syntax| HighlightEngine::for_syntax_name(&syntax.name, registry))
+            .unwrap_or(highlighter)
+    }
+
+    /// Display name of the buffer's syntax, for the status bar
+    pub fn syntax_display_name(&self) -> String {
+        if let Some(name) = self.highlighter.syntax_name() {
+            return name.to_string();
+        }
+        match self.highlighter.language() {
+            Some(language) => format!("{:?}", language),
+            None => "Plain Text".to_string(),
+        }
+    }
+
     /// Create an editor state from a file
     ///
     /// Note: width/height parameters are kept for backward compatibility but
@@ -180,9 +280,18 @@ impl EditorState {
         registry: &GrammarRegistry,
     ) -> std::io::Result<Self> {
         let buffer = Buffer::load_from_file(path, large_file_threshold)?;
+        Ok(Self::from_file_buffer(path, buffer, registry))
+    }
 
+    /// Create an editor state for the file at `path` from its buffer,
+    /// already loaded or still loading
+    pub fn from_file_buffer(
+        path: &std::path::Path,
+        buffer: Buffer,
+        registry: &GrammarRegistry,
+    ) -> Self {
         // Create highlighter using HighlightEngine (tree-sitter preferred, TextMate fallback)
-        let highlighter = HighlightEngine::for_file(path, registry);
+        let highlighter = Self::detect_highlighter(path, &buffer, registry);
         tracing::debug!(
             "Created highlighter for {:?} (backend: {})",
             path,
@@ -190,10 +299,9 @@ impl EditorState {
         );
 
         // Initialize semantic highlighter with language if available
-        let language = Language::from_path(path);
         let mut semantic_highlighter = SemanticHighlighter::new();
-        if let Some(lang) = language {
-            semantic_highlighter.set_language(&lang);
+        if let Some(lang) = highlighter.language() {
+            semantic_highlighter.set_language(lang);
         }
 
         // Initialize marker list with buffer size
@@ -206,10 +314,11 @@ impl EditorState {
             marker_list.adjust_for_insert(0, buffer.len());
         }
 
-        Ok(Self {
+        Self {
             buffer,
             cursors: Cursors::new(),
             highlighter,
+            syntax_override: None,
             indent_calculator: RefCell::new(IndentCalculator::new()),
             overlays: OverlayManager::new(),
             marker_list,
@@ -221,17 +330,21 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            loading: false,
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4, // Default tab size
             semantic_highlighter,
+            protected: false,
+            auto_revert: true,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
             compose_width: None,
             compose_prev_line_numbers: None,
             compose_column_guides: None,
+            rulers: Vec::new(),
             view_transform: None,
-        })
+        }
     }
 
     /// Handle an Insert event - adjusts markers, buffer, highlighter, cursors, and line numbers
@@ -261,6 +374,9 @@ impl EditorState {
         if let Some(cursor) = self.cursors.get_mut(cursor_id) {
             cursor.position = position + text.len();
             cursor.clear_selection();
+            // Typing moves the cursor sideways: the next vertical move starts
+            // from the new column
+            cursor.sticky_column = 0;
         }
 
         // Update primary cursor line number if this was the primary cursor
@@ -305,6 +421,7 @@ impl EditorState {
         if let Some(cursor) = self.cursors.get_mut(cursor_id) {
             cursor.position = range.start;
             cursor.clear_selection();
+            cursor.sticky_column = 0;
         }
 
         // Update primary cursor line number if this was the primary cursor
@@ -1164,6 +1281,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_file_detects_language_from_content() {
+        let registry = GrammarRegistry::load();
+        let dir = tempfile::tempdir().unwrap();
+        let threshold = crate::config::LARGE_FILE_THRESHOLD_BYTES as usize;
+
+        let script = dir.path().join("deploy");
+        std::fs::write(&script, "#!/usr/bin/env node\nconsole.log('hi');\n").unwrap();
+        let mut state = EditorState::from_file(&script, 80, 24, threshold, &registry).unwrap();
+        assert_eq!(state.syntax_display_name(), "JavaScript");
+
+        // Detection stays overridable, and Auto Detect re-runs it
+        assert!(state.set_syntax_override(Some("Plain Text".to_string()), &registry));
+        assert_eq!(state.syntax_display_name(), "Plain Text");
+        assert!(state.set_syntax_override(None, &registry));
+        assert_eq!(state.syntax_display_name(), "JavaScript");
+
+        let notes = dir.path().join("NOTES");
+        std::fs::write(&notes, "line 1\nline 2\n\n# vim: ft=ruby\n").unwrap();
+        let state = EditorState::from_file(&notes, 80, 24, threshold, &registry).unwrap();
+        assert_eq!(state.syntax_display_name(), "Ruby");
+    }
+
     // DocumentModel trait tests
     mod document_model_tests {
         use super::*;
@@ -0,0 +1,5 @@
+//! Utilities for testing code that drives the editor
+//!
+//! Only built with the `test-harness` feature.
+
+pub mod harness;
@@ -14,6 +14,7 @@ pub mod context_keys {
     pub const COMPOSE_MODE: &str = "compose_mode";
     pub const FILE_EXPLORER: &str = "file_explorer";
     pub const MENU_BAR: &str = "menu_bar";
+    pub const ZEN_MODE: &str = "zen_mode";
     pub const FILE_EXPLORER_FOCUSED: &str = "file_explorer_focused";
     pub const MOUSE_CAPTURE: &str = "mouse_capture";
     pub const MOUSE_HOVER: &str = "mouse_hover";
@@ -21,6 +22,7 @@ pub mod context_keys {
     pub const FILE_EXPLORER_SHOW_HIDDEN: &str = "file_explorer_show_hidden";
     pub const FILE_EXPLORER_SHOW_GITIGNORED: &str = "file_explorer_show_gitignored";
     pub const HAS_SELECTION: &str = "has_selection";
+    pub const AUTO_REVERT: &str = "auto_revert";
 }
 
 /// Configuration for process resource limits
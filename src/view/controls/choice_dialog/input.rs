@@ -0,0 +1,66 @@
+//! Choice dialog input handling
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use super::{ChoiceDialogFocus, ChoiceDialogLayout, ChoiceDialogState};
+
+/// Events that can be returned from choice dialog input handling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceDialogEvent {
+    /// A choice of a row was picked
+    Chosen { row: usize, choice: usize },
+    /// A button of the bottom line was picked
+    Button(usize),
+    /// Focus moved
+    FocusChanged(ChoiceDialogFocus),
+    /// The dialog was dismissed without picking anything
+    Cancelled,
+}
+
+impl ChoiceDialogState {
+    /// Handle a mouse event for this dialog
+    ///
+    /// # Arguments
+    /// * `event` - The mouse event to handle
+    /// * `layout` - The dialog's rendered layout for hit testing
+    ///
+    /// # Returns
+    /// * `Some(ChoiceDialogEvent)` if the event changed the dialog
+    /// * `None` if the event was not on a choice or button
+    pub fn handle_mouse(
+        &mut self,
+        event: MouseEvent,
+        layout: &ChoiceDialogLayout,
+    ) -> Option<ChoiceDialogEvent> {
+        let target = layout.target_at(event.column, event.row)?;
+        match event.kind {
+            MouseEventKind::Moved => (self.focus != target).then(|| {
+                self.focus = target;
+                ChoiceDialogEvent::FocusChanged(target)
+            }),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.focus = target;
+                Some(self.activate())
+            }
+            _ => None,
+        }
+    }
+
+    /// Handle a key event for this dialog
+    ///
+    /// # Returns
+    /// * `Some(ChoiceDialogEvent)` if the key was used
+    /// * `None` if the key is not one the dialog uses
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ChoiceDialogEvent> {
+        match key.code {
+            KeyCode::Up => self.focus_up(),
+            KeyCode::Down => self.focus_down(),
+            KeyCode::Left | KeyCode::BackTab => self.focus_prev(),
+            KeyCode::Right | KeyCode::Tab => self.focus_next(),
+            KeyCode::Enter | KeyCode::Char(' ') => return Some(self.activate()),
+            KeyCode::Esc => return Some(ChoiceDialogEvent::Cancelled),
+            _ => return None,
+        }
+        Some(ChoiceDialogEvent::FocusChanged(self.focus))
+    }
+}
@@ -0,0 +1,361 @@
+//! Choice dialog control
+//!
+//! Renders as a centered modal listing rows that each offer the same choices,
+//! above a line of buttons that act on the whole dialog:
+//! ```text
+//! ┌ Unsaved Changes ───────────────────────────┐
+//! │ 2 buffers have unsaved changes             │
+//! │                                            │
+//! │ main.rs              [ Save ] [ Discard ]  │
+//! │ notes.txt            [ Save ] [ Discard ]  │
+//! │                                            │
+//! │ [ Save All ] [ Discard All ] [ Cancel ]    │
+//! └────────────────────────────────────────────┘
+//! ```
+//!
+//! This module provides a complete choice dialog component with:
+//! - State management (`ChoiceDialogState`)
+//! - Rendering (`render_choice_dialog`)
+//! - Input handling (`ChoiceDialogState::handle_mouse`, `handle_key`)
+//! - Layout/hit testing (`ChoiceDialogLayout`)
+//!
+//! The dialog only reports what was picked; the owner decides what a choice
+//! does, and removes rows once they are dealt with.
+
+mod input;
+mod render;
+
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+pub use input::ChoiceDialogEvent;
+pub use render::render_choice_dialog;
+
+/// What has keyboard focus in a choice dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceDialogFocus {
+    /// A choice of a row
+    Row { row: usize, choice: usize },
+    /// A button of the bottom line
+    Button(usize),
+}
+
+/// State for a choice dialog control
+#[derive(Debug, Clone)]
+pub struct ChoiceDialogState {
+    /// Title shown in the top border
+    pub title: String,
+    /// Line shown above the rows
+    pub message: String,
+    /// Label of each row
+    pub rows: Vec<String>,
+    /// Choices offered on every row
+    pub row_choices: Vec<String>,
+    /// Buttons of the bottom line
+    pub buttons: Vec<String>,
+    /// What has keyboard focus
+    pub focus: ChoiceDialogFocus,
+}
+
+impl ChoiceDialogState {
+    /// Create a dialog with the first choice of the first row focused, or the
+    /// first button if there are no rows
+    pub fn new(
+        title: impl Into<String>,
+        message: impl Into<String>,
+        rows: Vec<String>,
+        row_choices: Vec<String>,
+        buttons: Vec<String>,
+    ) -> Self {
+        let mut state = Self {
+            title: title.into(),
+            message: message.into(),
+            rows,
+            row_choices,
+            buttons,
+            focus: ChoiceDialogFocus::Row { row: 0, choice: 0 },
+        };
+        if !state.has_row_choices() {
+            state.focus = ChoiceDialogFocus::Button(0);
+        }
+        state
+    }
+
+    /// Remove a row, keeping focus on a row that still exists
+    pub fn remove_row(&mut self, index: usize) {
+        if index >= self.rows.len() {
+            return;
+        }
+        self.rows.remove(index);
+        if let ChoiceDialogFocus::Row { row, choice } = self.focus {
+            if !self.has_row_choices() {
+                self.focus = ChoiceDialogFocus::Button(0);
+            } else if row >= self.rows.len() || row > index {
+                self.focus = ChoiceDialogFocus::Row {
+                    row: row.saturating_sub(1),
+                    choice,
+                };
+            }
+        }
+    }
+
+    /// Move focus to the row below, then to the buttons, wrapping around
+    pub fn focus_down(&mut self) {
+        self.focus = match self.focus {
+            ChoiceDialogFocus::Row { row, choice } if row + 1 < self.rows.len() => {
+                ChoiceDialogFocus::Row {
+                    row: row + 1,
+                    choice,
+                }
+            }
+            ChoiceDialogFocus::Row { .. } => ChoiceDialogFocus::Button(0),
+            ChoiceDialogFocus::Button(_) if self.has_row_choices() => {
+                ChoiceDialogFocus::Row { row: 0, choice: 0 }
+            }
+            focus @ ChoiceDialogFocus::Button(_) => focus,
+        };
+    }
+
+    /// Move focus to the row above, wrapping around to the buttons
+    pub fn focus_up(&mut self) {
+        self.focus = match self.focus {
+            ChoiceDialogFocus::Row { row, choice } if row > 0 => ChoiceDialogFocus::Row {
+                row: row - 1,
+                choice,
+            },
+            ChoiceDialogFocus::Row { .. } => ChoiceDialogFocus::Button(0),
+            ChoiceDialogFocus::Button(_) if self.has_row_choices() => ChoiceDialogFocus::Row {
+                row: self.rows.len() - 1,
+                choice: 0,
+            },
+            focus @ ChoiceDialogFocus::Button(_) => focus,
+        };
+    }
+
+    /// Move focus to the next choice of the row, or the next button, wrapping
+    /// around
+    pub fn focus_next(&mut self) {
+        self.step(true);
+    }
+
+    /// Move focus to the previous choice of the row, or the previous button,
+    /// wrapping around
+    pub fn focus_prev(&mut self) {
+        self.step(false);
+    }
+
+    /// Event for activating what has focus
+    pub fn activate(&self) -> ChoiceDialogEvent {
+        match self.focus {
+            ChoiceDialogFocus::Row { row, choice } => ChoiceDialogEvent::Chosen { row, choice },
+            ChoiceDialogFocus::Button(index) => ChoiceDialogEvent::Button(index),
+        }
+    }
+
+    fn has_row_choices(&self) -> bool {
+        !self.rows.is_empty() && !self.row_choices.is_empty()
+    }
+
+    /// Move focus one place along the focused line, wrapping around
+    fn step(&mut self, forward: bool) {
+        let wrap = |current: usize, len: usize| match len {
+            0 => 0,
+            _ if forward => (current + 1) % len,
+            _ => (current + len - 1) % len,
+        };
+        self.focus = match self.focus {
+            ChoiceDialogFocus::Row { row, choice } => ChoiceDialogFocus::Row {
+                row,
+                choice: wrap(choice, self.row_choices.len()),
+            },
+            ChoiceDialogFocus::Button(index) => {
+                ChoiceDialogFocus::Button(wrap(index, self.buttons.len()))
+            }
+        };
+    }
+}
+
+/// Colors for the choice dialog control
+#[derive(Debug, Clone, Copy)]
+pub struct ChoiceDialogColors {
+    /// Text color
+    pub fg: Color,
+    /// Dialog background color
+    pub bg: Color,
+    /// Border and title color
+    pub border: Color,
+    /// Focused choice text color
+    pub focused_fg: Color,
+    /// Focused choice background color
+    pub focused_bg: Color,
+}
+
+impl Default for ChoiceDialogColors {
+    fn default() -> Self {
+        Self {
+            fg: Color::White,
+            bg: Color::Black,
+            border: Color::Gray,
+            focused_fg: Color::Black,
+            focused_bg: Color::Cyan,
+        }
+    }
+}
+
+impl ChoiceDialogColors {
+    /// Create colors from theme, matching popups
+    pub fn from_theme(theme: &crate::view::theme::Theme) -> Self {
+        Self {
+            fg: theme.popup_text_fg,
+            bg: theme.popup_bg,
+            border: theme.popup_border_fg,
+            focused_fg: theme.menu_highlight_fg,
+            focused_bg: theme.menu_highlight_bg,
+        }
+    }
+}
+
+/// Layout information returned after rendering for hit testing
+#[derive(Debug, Clone, Default)]
+pub struct ChoiceDialogLayout {
+    /// The dialog including its border
+    pub area: Rect,
+    /// Area of each visible choice, with the row and choice it stands for
+    pub choices: Vec<(usize, usize, Rect)>,
+    /// Area of each button
+    pub buttons: Vec<Rect>,
+}
+
+impl ChoiceDialogLayout {
+    /// Check if a point is within the dialog
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        rect_contains(self.area, x, y)
+    }
+
+    /// Get the choice or button at a point, if any
+    pub fn target_at(&self, x: u16, y: u16) -> Option<ChoiceDialogFocus> {
+        if let Some((row, choice, _)) = self
+            .choices
+            .iter()
+            .find(|(_, _, area)| rect_contains(*area, x, y))
+        {
+            return Some(ChoiceDialogFocus::Row {
+                row: *row,
+                choice: *choice,
+            });
+        }
+        self.buttons
+            .iter()
+            .position(|area| rect_contains(*area, x, y))
+            .map(ChoiceDialogFocus::Button)
+    }
+}
+
+fn rect_contains(area: Rect, x: u16, y: u16) -> bool {
+    x >= area.x && x < area.x + area.width && y >= area.y && y < area.y + area.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn test_frame<F>(width: u16, height: u16, f: F)
+    where
+        F: FnOnce(&mut ratatui::Frame, Rect),
+    {
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = Rect::new(0, 0, width, height);
+                f(frame, area);
+            })
+            .unwrap();
+    }
+
+    fn sample_state(rows: usize) -> ChoiceDialogState {
+        ChoiceDialogState::new(
+            "Unsaved Changes",
+            "Save changes before quitting?",
+            (0..rows).map(|i| format!("file{}.txt", i)).collect(),
+            vec!["Save".to_string(), "Discard".to_string()],
+            vec!["Save All".to_string(), "Cancel".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_choice_dialog_navigation_wraps_through_buttons() {
+        let mut state = sample_state(2);
+        assert_eq!(state.focus, ChoiceDialogFocus::Row { row: 0, choice: 0 });
+
+        state.focus_next();
+        assert_eq!(
+            state.activate(),
+            ChoiceDialogEvent::Chosen { row: 0, choice: 1 }
+        );
+        state.focus_next();
+        assert_eq!(state.focus, ChoiceDialogFocus::Row { row: 0, choice: 0 });
+
+        state.focus_down();
+        state.focus_down();
+        assert_eq!(state.focus, ChoiceDialogFocus::Button(0));
+        state.focus_prev();
+        assert_eq!(state.activate(), ChoiceDialogEvent::Button(1));
+        state.focus_down();
+        assert_eq!(state.focus, ChoiceDialogFocus::Row { row: 0, choice: 0 });
+        state.focus_up();
+        assert_eq!(state.focus, ChoiceDialogFocus::Button(0));
+    }
+
+    #[test]
+    fn test_choice_dialog_remove_row_keeps_focus_valid() {
+        let mut state = sample_state(3);
+        state.focus = ChoiceDialogFocus::Row { row: 2, choice: 1 };
+
+        state.remove_row(0);
+        assert_eq!(state.focus, ChoiceDialogFocus::Row { row: 1, choice: 1 });
+        state.remove_row(1);
+        assert_eq!(state.focus, ChoiceDialogFocus::Row { row: 0, choice: 1 });
+        state.remove_row(0);
+        assert!(state.rows.is_empty());
+        assert_eq!(state.focus, ChoiceDialogFocus::Button(0));
+    }
+
+    #[test]
+    fn test_choice_dialog_renders_hit_areas() {
+        test_frame(80, 20, |frame, area| {
+            let state = sample_state(2);
+            let layout = render_choice_dialog(frame, area, &state, &ChoiceDialogColors::default());
+
+            assert!(layout.area.width > 0);
+            assert_eq!(layout.choices.len(), 4);
+            assert_eq!(layout.buttons.len(), 2);
+            let (_, _, discard) = layout.choices[1];
+            assert_eq!(
+                layout.target_at(discard.x, discard.y),
+                Some(ChoiceDialogFocus::Row { row: 0, choice: 1 })
+            );
+            let cancel = layout.buttons[1];
+            assert_eq!(
+                layout.target_at(cancel.x + 1, cancel.y),
+                Some(ChoiceDialogFocus::Button(1))
+            );
+            assert_eq!(layout.target_at(layout.area.x, layout.area.y), None);
+        });
+    }
+
+    #[test]
+    fn test_choice_dialog_scrolls_to_focused_row() {
+        test_frame(60, 10, |frame, area| {
+            let mut state = sample_state(20);
+            state.focus = ChoiceDialogFocus::Row { row: 15, choice: 0 };
+            let layout = render_choice_dialog(frame, area, &state, &ChoiceDialogColors::default());
+
+            assert!(layout.area.height <= area.height);
+            assert!(layout.choices.iter().any(|(row, _, _)| *row == 15));
+            assert!(!layout.choices.iter().any(|(row, _, _)| *row == 0));
+        });
+    }
+}
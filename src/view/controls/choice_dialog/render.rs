@@ -0,0 +1,178 @@
+//! Choice dialog rendering functions
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use super::{ChoiceDialogColors, ChoiceDialogFocus, ChoiceDialogLayout, ChoiceDialogState};
+use crate::primitives::display_width::{char_width, str_width};
+
+/// Narrowest the dialog gets, unless the screen is narrower
+const MIN_WIDTH: u16 = 40;
+
+/// Render a choice dialog centered in `bounds`. When not every row fits, the
+/// rows scroll to keep the focused one visible.
+///
+/// # Arguments
+/// * `frame` - The ratatui frame to render to
+/// * `bounds` - Rectangle to center the dialog in (usually the whole screen)
+/// * `state` - The choice dialog state
+/// * `colors` - Colors for rendering
+///
+/// # Returns
+/// Layout information for hit testing
+pub fn render_choice_dialog(
+    frame: &mut Frame,
+    bounds: Rect,
+    state: &ChoiceDialogState,
+    colors: &ChoiceDialogColors,
+) -> ChoiceDialogLayout {
+    let button_width = |label: &String| str_width(label) + 4;
+    let line_width = |labels: &[String]| {
+        labels.iter().map(button_width).sum::<usize>() + labels.len().saturating_sub(1)
+    };
+    let choices_width = line_width(&state.row_choices);
+    let label_width = state.rows.iter().map(|row| str_width(row)).max();
+    // One column of padding on each side of the content, inside the border
+    let content_width = [
+        str_width(&state.message),
+        str_width(&state.title) + 2,
+        line_width(&state.buttons),
+        label_width.map_or(0, |width| width + 2 + choices_width),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0);
+    let width = ((content_width + 4) as u16)
+        .max(MIN_WIDTH)
+        .min(bounds.width);
+    // Message, blank line, rows, blank line, buttons
+    let visible_rows = state
+        .rows
+        .len()
+        .min(bounds.height.saturating_sub(6) as usize);
+    let height = (visible_rows as u16 + 6).min(bounds.height);
+    if width < 8 || height < 6 {
+        return ChoiceDialogLayout::default();
+    }
+
+    let area = Rect::new(
+        bounds.x + (bounds.width - width) / 2,
+        bounds.y + (bounds.height - height) / 2,
+        width,
+        height,
+    );
+    let content_x = area.x + 2;
+    let content_width = width.saturating_sub(4) as usize;
+    let rows_y = area.y + 3;
+    let first_row = match state.focus {
+        ChoiceDialogFocus::Row { row, .. } if row >= visible_rows => row + 1 - visible_rows,
+        _ => 0,
+    }
+    .min(state.rows.len() - visible_rows);
+
+    let normal = Style::default().fg(colors.fg).bg(colors.bg);
+    let focused = Style::default()
+        .fg(colors.focused_fg)
+        .bg(colors.focused_bg)
+        .add_modifier(Modifier::BOLD);
+    let mut layout = ChoiceDialogLayout {
+        area,
+        ..Default::default()
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(" {}", truncate(&state.message, content_width)),
+            normal,
+        )),
+        Line::default(),
+    ];
+
+    let choices_x = content_x + content_width.saturating_sub(choices_width) as u16;
+    let label_room = (choices_x - content_x).saturating_sub(1) as usize;
+    for (offset, label) in state.rows[first_row..first_row + visible_rows]
+        .iter()
+        .enumerate()
+    {
+        let row = first_row + offset;
+        let y = rows_y + offset as u16;
+        let label = truncate(label, label_room);
+        let mut spans = vec![Span::styled(
+            format!(
+                " {}{}",
+                label,
+                " ".repeat((choices_x - content_x) as usize - str_width(&label))
+            ),
+            normal,
+        )];
+        let mut x = choices_x;
+        for (choice, text) in state.row_choices.iter().enumerate() {
+            let is_focused = state.focus == ChoiceDialogFocus::Row { row, choice };
+            let text_width = button_width(text) as u16;
+            spans.push(Span::styled(
+                format!("[ {} ]", text),
+                if is_focused { focused } else { normal },
+            ));
+            spans.push(Span::styled(" ", normal));
+            layout
+                .choices
+                .push((row, choice, Rect::new(x, y, text_width, 1)));
+            x += text_width + 1;
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::default());
+
+    let buttons_y = rows_y + visible_rows as u16 + 1;
+    let mut spans = vec![Span::styled(" ", normal)];
+    let mut x = content_x;
+    for (index, text) in state.buttons.iter().enumerate() {
+        let text_width = button_width(text) as u16;
+        if x + text_width > area.x + area.width - 1 {
+            break;
+        }
+        let is_focused = state.focus == ChoiceDialogFocus::Button(index);
+        spans.push(Span::styled(
+            format!("[ {} ]", text),
+            if is_focused { focused } else { normal },
+        ));
+        spans.push(Span::styled(" ", normal));
+        layout.buttons.push(Rect::new(x, buttons_y, text_width, 1));
+        x += text_width + 1;
+    }
+    lines.push(Line::from(spans));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", state.title))
+        .border_style(Style::default().fg(colors.border))
+        .style(normal);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    layout
+}
+
+/// Cut `text` down to at most `width` columns, marking the cut with `…`
+fn truncate(text: &str, width: usize) -> String {
+    if str_width(text) <= width {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if used + w + 1 > width {
+            break;
+        }
+        result.push(c);
+        used += w;
+    }
+    if width > 0 {
+        result.push('…');
+    }
+    result
+}
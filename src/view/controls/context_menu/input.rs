@@ -0,0 +1,79 @@
+//! Context menu input handling
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use super::{ContextMenuLayout, ContextMenuState};
+
+/// Events that can be returned from context menu input handling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMenuEvent<T> {
+    /// An item was picked; the menu should close and run its action
+    Activated(T),
+    /// The highlight moved
+    HighlightChanged(usize),
+    /// The menu was dismissed without picking anything
+    Closed,
+}
+
+impl<T: Clone> ContextMenuState<T> {
+    /// Handle a mouse event for this context menu
+    ///
+    /// # Arguments
+    /// * `event` - The mouse event to handle
+    /// * `layout` - The menu's rendered layout for hit testing
+    ///
+    /// # Returns
+    /// * `Some(ContextMenuEvent)` if the event changed the menu
+    /// * `None` if the event was not relevant, or landed on a separator or
+    ///   disabled item
+    pub fn handle_mouse(
+        &mut self,
+        event: MouseEvent,
+        layout: &ContextMenuLayout,
+    ) -> Option<ContextMenuEvent<T>> {
+        let item = layout.item_at(event.column, event.row);
+        match event.kind {
+            MouseEventKind::Moved => {
+                let index = item?;
+                (self.highlighted != Some(index) && self.highlight(index))
+                    .then_some(ContextMenuEvent::HighlightChanged(index))
+            }
+            MouseEventKind::Down(MouseButton::Left | MouseButton::Right) => {
+                if !layout.contains(event.column, event.row) {
+                    return Some(ContextMenuEvent::Closed);
+                }
+                let index = item?;
+                if !self.highlight(index) {
+                    return None;
+                }
+                self.highlighted_action()
+                    .cloned()
+                    .map(ContextMenuEvent::Activated)
+            }
+            _ => None,
+        }
+    }
+
+    /// Handle a key event for this context menu
+    ///
+    /// # Returns
+    /// * `Some(ContextMenuEvent)` if the key was used
+    /// * `None` if the key is not one the menu uses
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ContextMenuEvent<T>> {
+        match key.code {
+            KeyCode::Up => self.highlight_prev(),
+            KeyCode::Down | KeyCode::Tab => self.highlight_next(),
+            KeyCode::Home | KeyCode::PageUp => self.highlight_first(),
+            KeyCode::End | KeyCode::PageDown => self.highlight_last(),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                return self
+                    .highlighted_action()
+                    .cloned()
+                    .map(ContextMenuEvent::Activated);
+            }
+            KeyCode::Esc => return Some(ContextMenuEvent::Closed),
+            _ => return None,
+        }
+        self.highlighted.map(ContextMenuEvent::HighlightChanged)
+    }
+}
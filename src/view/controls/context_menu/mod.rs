@@ -0,0 +1,317 @@
+//! Context menu control
+//!
+//! Renders as a bordered list of actions opened at the mouse:
+//! ```text
+//! ┌──────────────────────┐
+//! │ Close          Ctrl+W│
+//! │ Close Others         │
+//! │──────────────────────│
+//! │ Copy Path            │
+//! └──────────────────────┘
+//! ```
+//!
+//! This module provides a complete context menu component with:
+//! - State management (`ContextMenuState`)
+//! - Rendering (`render_context_menu`)
+//! - Input handling (`ContextMenuState::handle_mouse`, `handle_key`)
+//! - Layout/hit testing (`ContextMenuLayout`)
+//!
+//! The menu is generic over the action carried by its items, so each area
+//! that opens one (tabs, editor, file tree) uses its own action type.
+
+mod input;
+mod render;
+
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+pub use input::ContextMenuEvent;
+pub use render::render_context_menu;
+
+/// An entry of a context menu
+#[derive(Debug, Clone)]
+pub enum ContextMenuItem<T> {
+    /// An action that can be picked
+    Action {
+        label: String,
+        action: T,
+        /// Key binding shown at the right end, if any
+        shortcut: Option<String>,
+        enabled: bool,
+    },
+    /// A line between groups of actions
+    Separator,
+}
+
+impl<T> ContextMenuItem<T> {
+    /// Create an enabled action item
+    pub fn action(label: impl Into<String>, action: T) -> Self {
+        ContextMenuItem::Action {
+            label: label.into(),
+            action,
+            shortcut: None,
+            enabled: true,
+        }
+    }
+
+    /// Show a key binding next to the label
+    pub fn with_shortcut(mut self, text: impl Into<String>) -> Self {
+        if let ContextMenuItem::Action { shortcut, .. } = &mut self {
+            let text = text.into();
+            *shortcut = (!text.is_empty()).then_some(text);
+        }
+        self
+    }
+
+    /// Set whether the item can be picked
+    pub fn with_enabled(mut self, value: bool) -> Self {
+        if let ContextMenuItem::Action { enabled, .. } = &mut self {
+            *enabled = value;
+        }
+        self
+    }
+
+    /// Check if the item is an action that can be picked
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, ContextMenuItem::Action { enabled: true, .. })
+    }
+}
+
+/// State for a context menu control
+#[derive(Debug, Clone)]
+pub struct ContextMenuState<T> {
+    /// Entries in display order
+    pub items: Vec<ContextMenuItem<T>>,
+    /// Index of the highlighted item (always an enabled action)
+    pub highlighted: Option<usize>,
+    /// Screen position the menu was opened at (its top-left corner, unless
+    /// it has to flip to fit)
+    pub anchor: (u16, u16),
+}
+
+impl<T> ContextMenuState<T> {
+    /// Create a context menu opened at `anchor`, with nothing highlighted
+    pub fn new(items: Vec<ContextMenuItem<T>>, anchor: (u16, u16)) -> Self {
+        Self {
+            items,
+            highlighted: None,
+            anchor,
+        }
+    }
+
+    /// Highlight the next enabled item, wrapping around
+    pub fn highlight_next(&mut self) {
+        self.step(1);
+    }
+
+    /// Highlight the previous enabled item, wrapping around
+    pub fn highlight_prev(&mut self) {
+        self.step(self.items.len().saturating_sub(1));
+    }
+
+    /// Highlight the first enabled item
+    pub fn highlight_first(&mut self) {
+        self.highlighted = self.items.iter().position(|item| item.is_enabled());
+    }
+
+    /// Highlight the last enabled item
+    pub fn highlight_last(&mut self) {
+        self.highlighted = self.items.iter().rposition(|item| item.is_enabled());
+    }
+
+    /// Highlight the item at `index` if it can be picked. Returns true if it was.
+    pub fn highlight(&mut self, index: usize) -> bool {
+        let enabled = self.items.get(index).is_some_and(|item| item.is_enabled());
+        if enabled {
+            self.highlighted = Some(index);
+        }
+        enabled
+    }
+
+    /// Action of the highlighted item
+    pub fn highlighted_action(&self) -> Option<&T> {
+        match self.items.get(self.highlighted?)? {
+            ContextMenuItem::Action {
+                action,
+                enabled: true,
+                ..
+            } => Some(action),
+            _ => None,
+        }
+    }
+
+    /// Move the highlight by `offset` (mod the item count) until it lands on
+    /// an enabled item
+    fn step(&mut self, offset: usize) {
+        let len = self.items.len();
+        let Some(current) = self.highlighted else {
+            if offset == 1 {
+                self.highlight_first();
+            } else {
+                self.highlight_last();
+            }
+            return;
+        };
+        for i in 1..=len {
+            let index = (current + i * offset) % len;
+            if self.items[index].is_enabled() {
+                self.highlighted = Some(index);
+                return;
+            }
+        }
+    }
+}
+
+/// Colors for the context menu control
+#[derive(Debug, Clone, Copy)]
+pub struct ContextMenuColors {
+    /// Item text color
+    pub fg: Color,
+    /// Menu background color
+    pub bg: Color,
+    /// Border color
+    pub border: Color,
+    /// Highlighted item text color
+    pub highlight_fg: Color,
+    /// Highlighted item background color
+    pub highlight_bg: Color,
+    /// Disabled item text color
+    pub disabled: Color,
+    /// Separator line color
+    pub separator: Color,
+}
+
+impl Default for ContextMenuColors {
+    fn default() -> Self {
+        Self {
+            fg: Color::White,
+            bg: Color::Black,
+            border: Color::Gray,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::Cyan,
+            disabled: Color::DarkGray,
+            separator: Color::DarkGray,
+        }
+    }
+}
+
+impl ContextMenuColors {
+    /// Create colors from theme, matching the menu bar's dropdowns
+    pub fn from_theme(theme: &crate::view::theme::Theme) -> Self {
+        Self {
+            fg: theme.menu_dropdown_fg,
+            bg: theme.menu_dropdown_bg,
+            border: theme.menu_border_fg,
+            highlight_fg: theme.menu_highlight_fg,
+            highlight_bg: theme.menu_highlight_bg,
+            disabled: theme.menu_disabled_fg,
+            separator: theme.menu_separator_fg,
+        }
+    }
+}
+
+/// Layout information returned after rendering for hit testing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContextMenuLayout {
+    /// The menu including its border
+    pub area: Rect,
+}
+
+impl ContextMenuLayout {
+    /// Check if a point is within the menu
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.area.x
+            && x < self.area.x + self.area.width
+            && y >= self.area.y
+            && y < self.area.y + self.area.height
+    }
+
+    /// Get the index of the item at a point, if any
+    pub fn item_at(&self, x: u16, y: u16) -> Option<usize> {
+        let inside_border = x > self.area.x
+            && x + 1 < self.area.x + self.area.width
+            && y > self.area.y
+            && y + 1 < self.area.y + self.area.height;
+        inside_border.then(|| (y - self.area.y - 1) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn test_frame<F>(width: u16, height: u16, f: F)
+    where
+        F: FnOnce(&mut ratatui::Frame, Rect),
+    {
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = Rect::new(0, 0, width, height);
+                f(frame, area);
+            })
+            .unwrap();
+    }
+
+    fn sample_items() -> Vec<ContextMenuItem<u8>> {
+        vec![
+            ContextMenuItem::action("Close", 0),
+            ContextMenuItem::action("Close Others", 1).with_enabled(false),
+            ContextMenuItem::Separator,
+            ContextMenuItem::action("Copy Path", 2).with_shortcut("Ctrl+C"),
+        ]
+    }
+
+    #[test]
+    fn test_context_menu_navigation_skips_separators_and_disabled() {
+        let mut state = ContextMenuState::new(sample_items(), (0, 0));
+        assert_eq!(state.highlighted_action(), None);
+
+        state.highlight_next();
+        assert_eq!(state.highlighted_action(), Some(&0));
+        state.highlight_next();
+        assert_eq!(state.highlighted_action(), Some(&2));
+        state.highlight_next();
+        assert_eq!(state.highlighted_action(), Some(&0));
+        state.highlight_prev();
+        assert_eq!(state.highlighted_action(), Some(&2));
+    }
+
+    #[test]
+    fn test_context_menu_highlight_rejects_disabled() {
+        let mut state = ContextMenuState::new(sample_items(), (0, 0));
+        assert!(!state.highlight(1));
+        assert!(!state.highlight(2));
+        assert!(state.highlight(3));
+        assert_eq!(state.highlighted, Some(3));
+    }
+
+    #[test]
+    fn test_context_menu_renders_at_anchor() {
+        test_frame(40, 10, |frame, area| {
+            let state = ContextMenuState::new(sample_items(), (5, 2));
+            let layout = render_context_menu(frame, area, &state, &ContextMenuColors::default());
+
+            assert_eq!((layout.area.x, layout.area.y), (5, 2));
+            assert_eq!(layout.area.height, 6); // 4 items + borders
+            assert_eq!(layout.item_at(6, 3), Some(0));
+            assert_eq!(layout.item_at(6, 6), Some(3));
+            assert_eq!(layout.item_at(5, 3), None); // border
+        });
+    }
+
+    #[test]
+    fn test_context_menu_flips_to_fit() {
+        test_frame(30, 8, |frame, area| {
+            let state = ContextMenuState::new(sample_items(), (28, 7));
+            let layout = render_context_menu(frame, area, &state, &ContextMenuColors::default());
+
+            assert!(layout.area.x + layout.area.width <= area.width);
+            assert!(layout.area.y + layout.area.height <= area.height);
+            assert!(layout.contains(27, 6));
+        });
+    }
+}
@@ -0,0 +1,111 @@
+//! Context menu rendering functions
+
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use super::{ContextMenuColors, ContextMenuItem, ContextMenuLayout, ContextMenuState};
+use crate::primitives::display_width::str_width;
+
+/// Render a context menu at its anchor, flipped left and/or up when it would
+/// not fit in `bounds` otherwise
+///
+/// # Arguments
+/// * `frame` - The ratatui frame to render to
+/// * `bounds` - Rectangle the menu must stay within (usually the whole screen)
+/// * `state` - The context menu state
+/// * `colors` - Colors for rendering
+///
+/// # Returns
+/// Layout information for hit testing
+pub fn render_context_menu<T>(
+    frame: &mut Frame,
+    bounds: Rect,
+    state: &ContextMenuState<T>,
+    colors: &ContextMenuColors,
+) -> ContextMenuLayout {
+    // " label  shortcut " inside the border
+    let text_width = state
+        .items
+        .iter()
+        .map(|item| match item {
+            ContextMenuItem::Action {
+                label, shortcut, ..
+            } => str_width(label) + shortcut.as_deref().map_or(0, |s| str_width(s) + 2),
+            ContextMenuItem::Separator => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    let width = ((text_width + 4) as u16).min(bounds.width);
+    let height = (state.items.len() as u16 + 2).min(bounds.height);
+    if width < 4 || height < 3 {
+        return ContextMenuLayout::default();
+    }
+
+    let (anchor_x, anchor_y) = state.anchor;
+    let right = bounds.x + bounds.width;
+    let bottom = bounds.y + bounds.height;
+    let x = if anchor_x + width <= right {
+        anchor_x
+    } else {
+        (anchor_x + 1)
+            .min(right)
+            .saturating_sub(width)
+            .max(bounds.x)
+    };
+    let y = if anchor_y + height <= bottom {
+        anchor_y
+    } else {
+        (anchor_y + 1)
+            .min(bottom)
+            .saturating_sub(height)
+            .max(bounds.y)
+    };
+    let area = Rect::new(x, y, width, height);
+
+    let content_width = width.saturating_sub(2) as usize;
+    let lines: Vec<Line> = state
+        .items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| match item {
+            ContextMenuItem::Action {
+                label,
+                shortcut,
+                enabled,
+                ..
+            } => {
+                let style = if !enabled {
+                    Style::default().fg(colors.disabled).bg(colors.bg)
+                } else if state.highlighted == Some(idx) {
+                    Style::default()
+                        .fg(colors.highlight_fg)
+                        .bg(colors.highlight_bg)
+                } else {
+                    Style::default().fg(colors.fg).bg(colors.bg)
+                };
+                let shortcut = shortcut.as_deref().unwrap_or("");
+                let pad = content_width.saturating_sub(str_width(label) + str_width(shortcut) + 2);
+                Line::from(Span::styled(
+                    format!(" {}{}{} ", label, " ".repeat(pad), shortcut),
+                    style,
+                ))
+            }
+            ContextMenuItem::Separator => Line::from(Span::styled(
+                "─".repeat(content_width),
+                Style::default().fg(colors.separator).bg(colors.bg),
+            )),
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.border))
+        .style(Style::default().bg(colors.bg));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    ContextMenuLayout { area }
+}
@@ -11,6 +11,8 @@
 //! - `TextList` - List of strings with add/remove
 //! - `MapInput` - Key-value map with expandable entries
 //! - `Button` - Clickable action button
+//! - `ContextMenu` - List of actions opened at the mouse
+//! - `ChoiceDialog` - Modal with per-row choices and dialog-wide buttons
 //!
 //! ## Pattern
 //! Each control follows a consistent pattern:
@@ -19,6 +21,8 @@
 //! - `render_*` function that renders to a frame and returns hit areas
 
 pub mod button;
+pub mod choice_dialog;
+pub mod context_menu;
 pub mod dropdown;
 pub mod keybinding_list;
 pub mod map_input;
@@ -30,6 +34,14 @@ pub mod toggle;
 pub use button::{
     render_button, render_button_row, ButtonColors, ButtonEvent, ButtonLayout, ButtonState,
 };
+pub use choice_dialog::{
+    render_choice_dialog, ChoiceDialogColors, ChoiceDialogEvent, ChoiceDialogFocus,
+    ChoiceDialogLayout, ChoiceDialogState,
+};
+pub use context_menu::{
+    render_context_menu, ContextMenuColors, ContextMenuEvent, ContextMenuItem, ContextMenuLayout,
+    ContextMenuState,
+};
 pub use dropdown::{
     render_dropdown, render_dropdown_aligned, DropdownColors, DropdownEvent, DropdownLayout,
     DropdownState,
@@ -0,0 +1,258 @@
+//! Resolved overlay styles for the visible part of a buffer.
+//!
+//! Search matches, diagnostics, hover highlights and plugin overlays can all
+//! cover the same text. Instead of collecting and layering the overlays at
+//! every character, the overlays in the viewport are swept once per frame:
+//! their boundaries split the viewport into segments, and each segment gets
+//! the style of its overlays applied lowest priority first (overlays of equal
+//! priority in the order they were added). Looking up the style of a byte is
+//! then a binary search.
+//!
+//! Syntax highlighting is drawn below these styles, and the selection and
+//! cursors above them (see `compute_char_style` in `ui/split_rendering.rs`).
+
+use crate::view::overlay::{Overlay, OverlayFace};
+use crate::view::underline::underline_modifier;
+use ratatui::style::Style;
+use std::ops::Range;
+
+/// Overlay styles of a viewport, by byte range
+#[derive(Debug, Clone, Default)]
+pub struct DecorationIndex {
+    /// Segment start offsets, ascending; a segment ends where the next starts
+    starts: Vec<usize>,
+    /// Combined overlay style of each segment, None where no overlay applies
+    styles: Vec<Option<Style>>,
+}
+
+impl DecorationIndex {
+    /// Resolve the overlays of a viewport, given with their byte ranges
+    pub fn build(overlays: &[(Overlay, Range<usize>)]) -> Self {
+        let mut boundaries: Vec<usize> = overlays
+            .iter()
+            .filter(|(_, range)| !range.is_empty())
+            .flat_map(|(_, range)| [range.start, range.end])
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        // Overlays by start, so each is added to the active set once
+        let mut by_start: Vec<usize> = (0..overlays.len())
+            .filter(|&i| !overlays[i].1.is_empty())
+            .collect();
+        by_start.sort_by_key(|&i| overlays[i].1.start);
+
+        let mut next = 0;
+        let mut active: Vec<usize> = Vec::new();
+        let mut starts = Vec::with_capacity(boundaries.len());
+        let mut styles = Vec::with_capacity(boundaries.len());
+        for &position in &boundaries {
+            active.retain(|&i| overlays[i].1.end > position);
+            while next < by_start.len() && overlays[by_start[next]].1.start == position {
+                active.push(by_start[next]);
+                next += 1;
+            }
+            active.sort_by_key(|&i| (overlays[i].0.priority, i));
+
+            let style = (!active.is_empty()).then(|| {
+                active.iter().fold(Style::default(), |style, &i| {
+                    apply_face(style, &overlays[i].0.face)
+                })
+            });
+            // Merge with the previous segment when nothing changes
+            if styles.last() != Some(&style) {
+                starts.push(position);
+                styles.push(style);
+            }
+        }
+
+        Self { starts, styles }
+    }
+
+    /// Combined style of the overlays covering `position`
+    pub fn style_at(&self, position: usize) -> Option<Style> {
+        let segment = self.starts.partition_point(|&start| start <= position);
+        segment
+            .checked_sub(1)
+            .and_then(|segment| self.styles[segment])
+    }
+
+    /// Segments overlapping `range` as (start, style), the first one's start
+    /// moved up to the start of the range
+    pub fn segments(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (usize, Option<Style>)> + '_ {
+        let first = self
+            .starts
+            .partition_point(|&start| start <= range.start)
+            .saturating_sub(1);
+        self.starts[first..]
+            .iter()
+            .zip(&self.styles[first..])
+            .take_while(move |(&start, _)| start < range.end)
+            .map(move |(&start, &style)| (start.max(range.start), style))
+    }
+}
+
+/// Draw `face` over `style`
+///
+/// Underlined text and its underline take the face's color. Curly, dotted
+/// and dashed underlines are drawn by the terminal backend where supported
+/// (see `view/underline.rs`).
+pub fn apply_face(style: Style, face: &OverlayFace) -> Style {
+    match face {
+        OverlayFace::Underline {
+            color,
+            style: underline,
+        } => style
+            .add_modifier(underline_modifier(*underline))
+            .fg(*color)
+            .underline_color(*color),
+        OverlayFace::Background { color } => style.bg(*color),
+        OverlayFace::Foreground { color } => style.fg(*color),
+        OverlayFace::Style { style: face_style } => style.patch(*face_style),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::marker::MarkerList;
+    use ratatui::style::{Color, Modifier};
+
+    fn overlay(
+        marker_list: &mut MarkerList,
+        range: Range<usize>,
+        face: OverlayFace,
+        priority: i32,
+    ) -> (Overlay, Range<usize>) {
+        let overlay = Overlay::with_priority(marker_list, range.clone(), face, priority);
+        (overlay, range)
+    }
+
+    #[test]
+    fn test_styles_layered_by_priority() {
+        let mut marker_list = MarkerList::new();
+        marker_list.set_buffer_size(100);
+        // Added out of priority order: the higher priority still wins
+        let overlays = vec![
+            overlay(
+                &mut marker_list,
+                5..15,
+                OverlayFace::Background { color: Color::Red },
+                100,
+            ),
+            overlay(
+                &mut marker_list,
+                0..10,
+                OverlayFace::Background { color: Color::Blue },
+                10,
+            ),
+            overlay(
+                &mut marker_list,
+                8..12,
+                OverlayFace::Foreground {
+                    color: Color::Green,
+                },
+                10,
+            ),
+        ];
+        let index = DecorationIndex::build(&overlays);
+
+        assert_eq!(index.style_at(0), Some(Style::default().bg(Color::Blue)));
+        assert_eq!(index.style_at(5), Some(Style::default().bg(Color::Red)));
+        assert_eq!(
+            index.style_at(9),
+            Some(Style::default().bg(Color::Red).fg(Color::Green))
+        );
+        assert_eq!(index.style_at(14), Some(Style::default().bg(Color::Red)));
+        assert_eq!(index.style_at(15), None);
+        assert_eq!(index.style_at(50), None);
+    }
+
+    #[test]
+    fn test_equal_priority_in_order_added() {
+        let mut marker_list = MarkerList::new();
+        marker_list.set_buffer_size(100);
+        let overlays = vec![
+            overlay(
+                &mut marker_list,
+                0..4,
+                OverlayFace::Background { color: Color::Red },
+                10,
+            ),
+            overlay(
+                &mut marker_list,
+                0..4,
+                OverlayFace::Background { color: Color::Blue },
+                10,
+            ),
+            // Empty ranges cover nothing
+            overlay(
+                &mut marker_list,
+                2..2,
+                OverlayFace::Background {
+                    color: Color::Green,
+                },
+                50,
+            ),
+        ];
+        let index = DecorationIndex::build(&overlays);
+        assert_eq!(index.style_at(2), Some(Style::default().bg(Color::Blue)));
+        assert_eq!(DecorationIndex::build(&[]).style_at(0), None);
+    }
+
+    #[test]
+    fn test_segments() {
+        let mut marker_list = MarkerList::new();
+        marker_list.set_buffer_size(100);
+        let overlays = vec![
+            overlay(
+                &mut marker_list,
+                5..15,
+                OverlayFace::Background { color: Color::Red },
+                10,
+            ),
+            overlay(
+                &mut marker_list,
+                20..30,
+                OverlayFace::Background { color: Color::Blue },
+                10,
+            ),
+        ];
+        let index = DecorationIndex::build(&overlays);
+        let segments: Vec<_> = index.segments(10..22).collect();
+        assert_eq!(
+            segments,
+            vec![
+                (10, Some(Style::default().bg(Color::Red))),
+                (15, None),
+                (20, Some(Style::default().bg(Color::Blue))),
+            ]
+        );
+        assert_eq!(index.segments(0..5).collect::<Vec<_>>(), vec![]);
+        assert!(DecorationIndex::build(&[]).segments(0..10).next().is_none());
+    }
+
+    #[test]
+    fn test_underline_color() {
+        let style = apply_face(
+            Style::default().fg(Color::White),
+            &OverlayFace::Underline {
+                color: Color::Red,
+                style: crate::view::overlay::UnderlineStyle::Wavy,
+            },
+        );
+        assert_eq!(style.fg, Some(Color::Red));
+        assert_eq!(style.underline_color, Some(Color::Red));
+        assert!(style.add_modifier.contains(underline_modifier(
+            crate::view::overlay::UnderlineStyle::Wavy
+        )));
+        assert_ne!(
+            style.add_modifier,
+            Modifier::UNDERLINED,
+            "keeps the underline style"
+        );
+    }
+}
@@ -1,35 +1,35 @@
 //! Ignore pattern matching for file tree filtering
 //!
 //! This module provides functionality to filter files and directories based on:
-//! - .gitignore patterns
+//! - .gitignore and .ignore files, and the global `ignore_patterns`
 //! - Custom glob patterns
 //! - Hidden file detection
 //!
-//! Uses the `ignore` crate which provides robust .gitignore parsing
-//! compatible with git's ignore rules.
+//! Ignore files are handled by the editor's shared
+//! [`IgnoreService`](crate::services::ignore_service::IgnoreService), so the
+//! tree agrees with the file finder and project search.
 
-use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use std::path::{Path, PathBuf};
+use crate::services::ignore_service::{IgnoreReason, IgnoreService, SharedIgnoreService};
+use std::path::Path;
 
 /// Status of a file/directory with respect to ignore patterns
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IgnoreStatus {
     /// File is visible and not ignored
     Visible,
-    /// File is ignored by .gitignore
+    /// File is ignored by a .gitignore or .ignore file
     GitIgnored,
     /// File is hidden (starts with .)
     Hidden,
-    /// File is ignored by custom pattern
+    /// File is ignored by a custom or global pattern
     CustomIgnored,
 }
 
 /// Manages ignore patterns for file filtering
 #[derive(Debug)]
 pub struct IgnorePatterns {
-    /// Gitignore matchers per directory
-    /// Key: directory path, Value: gitignore rules for that directory
-    gitignores: Vec<(PathBuf, Gitignore)>,
+    /// Ignore files and global patterns
+    service: SharedIgnoreService,
 
     /// Custom glob patterns to ignore
     custom_patterns: Vec<String>,
@@ -48,7 +48,7 @@ impl IgnorePatterns {
     /// Create a new ignore pattern matcher
     pub fn new() -> Self {
         Self {
-            gitignores: Vec::new(),
+            service: IgnoreService::shared(&[]),
             custom_patterns: Vec::new(),
             show_hidden: false,
             show_gitignored: false,
@@ -56,32 +56,20 @@ impl IgnorePatterns {
         }
     }
 
-    /// Load .gitignore file from a directory
+    /// Use the editor's shared ignore service instead of a private one
+    pub fn set_service(&mut self, service: SharedIgnoreService) {
+        self.service = service;
+    }
+
+    /// Load the .gitignore and .ignore files of a directory
     ///
-    /// This should be called when expanding a directory to load its .gitignore
+    /// This should be called when expanding a directory to load its ignore
+    /// files. Those of the directories above it are loaded too.
     pub fn load_gitignore(&mut self, dir: &Path) -> std::io::Result<()> {
-        let gitignore_path = dir.join(".gitignore");
-
-        if !gitignore_path.exists() {
-            return Ok(()); // No .gitignore, nothing to load
-        }
-
-        let mut builder = GitignoreBuilder::new(dir);
-        builder.add(&gitignore_path);
-
-        match builder.build() {
-            Ok(gitignore) => {
-                // Remove any existing gitignore for this directory
-                self.gitignores.retain(|(path, _)| path != dir);
-                // Add new gitignore
-                self.gitignores.push((dir.to_path_buf(), gitignore));
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load .gitignore from {:?}: {}", gitignore_path, e);
-                Ok(()) // Don't fail if .gitignore is malformed
-            }
+        if let Ok(mut service) = self.service.write() {
+            service.load_dir_and_ancestors(dir);
         }
+        Ok(())
     }
 
     /// Add a custom glob pattern to ignore
@@ -112,6 +100,18 @@ impl IgnorePatterns {
         }
     }
 
+    /// Check if a path is left out of the tree by ignore files or patterns
+    ///
+    /// Unlike [`is_ignored`](Self::is_ignored), hidden files are not left out;
+    /// the tree shows them dimmed instead.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        match self.get_status(path, is_dir) {
+            IgnoreStatus::GitIgnored => !self.show_gitignored,
+            IgnoreStatus::CustomIgnored => !self.show_custom_ignored,
+            IgnoreStatus::Visible | IgnoreStatus::Hidden => false,
+        }
+    }
+
     /// Get the ignore status of a path
     ///
     /// This is useful for rendering (e.g., gray out ignored files)
@@ -130,30 +130,17 @@ impl IgnorePatterns {
             return IgnoreStatus::CustomIgnored;
         }
 
-        // Check gitignore
-        if self.matches_gitignore(path, is_dir) {
-            return IgnoreStatus::GitIgnored;
+        // Check ignore files and global patterns
+        let reason = self
+            .service
+            .read()
+            .ok()
+            .and_then(|service| service.ignore_reason(path, is_dir));
+        match reason {
+            Some(IgnoreReason::IgnoreFile) => IgnoreStatus::GitIgnored,
+            Some(IgnoreReason::GlobalPattern) => IgnoreStatus::CustomIgnored,
+            None => IgnoreStatus::Visible,
         }
-
-        IgnoreStatus::Visible
-    }
-
-    /// Check if path matches any .gitignore rules
-    fn matches_gitignore(&self, path: &Path, is_dir: bool) -> bool {
-        // Find the most specific .gitignore (deepest directory)
-        // that could apply to this path
-        for (gitignore_dir, gitignore) in &self.gitignores {
-            if path.starts_with(gitignore_dir) {
-                let relative_path = path.strip_prefix(gitignore_dir).unwrap_or(path);
-                let matched = gitignore.matched(relative_path, is_dir);
-
-                if matched.is_ignore() {
-                    return true;
-                }
-            }
-        }
-
-        false
     }
 
     /// Check if path matches any custom patterns
@@ -219,19 +206,17 @@ impl IgnorePatterns {
         self.show_hidden = !self.show_hidden;
     }
 
-    /// Clear all gitignore rules
-    pub fn clear_gitignores(&mut self) {
-        self.gitignores.clear();
-    }
-
     /// Clear all custom patterns
     pub fn clear_custom_patterns(&mut self) {
         self.custom_patterns.clear();
     }
 
-    /// Get number of loaded .gitignore files
+    /// Get number of directories with loaded ignore files
     pub fn gitignore_count(&self) -> usize {
-        self.gitignores.len()
+        self.service
+            .read()
+            .map(|service| service.ignore_file_count())
+            .unwrap_or(0)
     }
 }
 
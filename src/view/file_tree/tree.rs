@@ -10,6 +10,9 @@ use std::sync::Arc;
 /// The tree starts with just the root node. Directories are only read
 /// when explicitly expanded via `expand_node()`. This makes the tree
 /// efficient even for very large directory structures.
+///
+/// Further root folders can be added with `add_root()`. They are shown as
+/// top-level nodes after the root, for workspaces with several folders.
 #[derive(Debug)]
 pub struct FileTree {
     /// Root directory path
@@ -20,6 +23,8 @@ pub struct FileTree {
     path_to_node: HashMap<PathBuf, NodeId>,
     /// Root node ID
     root_id: NodeId,
+    /// Additional top-level root nodes, in the order they were added
+    extra_roots: Vec<NodeId>,
     /// Next node ID to assign
     next_id: usize,
     /// Filesystem manager for async operations
@@ -66,6 +71,7 @@ impl FileTree {
             nodes,
             path_to_node,
             root_id,
+            extra_roots: Vec::new(),
             next_id: 1,
             fs_manager,
         })
@@ -81,6 +87,71 @@ impl FileTree {
         &self.root_path
     }
 
+    /// Get the IDs of the roots added with `add_root()`
+    pub fn extra_roots(&self) -> &[NodeId] {
+        &self.extra_roots
+    }
+
+    /// Whether `id` is the root or one of the added roots
+    pub fn is_root(&self, id: NodeId) -> bool {
+        id == self.root_id || self.extra_roots.contains(&id)
+    }
+
+    /// Add a directory as another top-level root, expanded
+    ///
+    /// Returns the existing node if the directory is already a root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path isn't a directory, or is already shown
+    /// under another root.
+    pub async fn add_root(&mut self, path: PathBuf) -> io::Result<NodeId> {
+        if let Some(&id) = self.path_to_node.get(&path) {
+            if self.is_root(id) {
+                return Ok(id);
+            }
+        }
+        if !self.fs_manager.is_dir(&path).await? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Path is not a directory: {:?}", path),
+            ));
+        }
+        let overlaps = std::iter::once(self.root_id)
+            .chain(self.extra_roots.iter().copied())
+            .filter_map(|id| self.get_node(id))
+            .any(|node| path.starts_with(&node.entry.path) || node.entry.path.starts_with(&path));
+        if overlaps {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Path overlaps an existing root: {:?}", path),
+            ));
+        }
+
+        let entry = self.fs_manager.get_entry(&path).await?;
+        let id = self.add_node(entry, None);
+        self.extra_roots.push(id);
+        if let Err(e) = self.expand_node(id).await {
+            tracing::warn!("Failed to expand root {:?}: {}", path, e);
+        }
+        Ok(id)
+    }
+
+    /// Remove a root added with `add_root()`
+    ///
+    /// Returns false if `path` is not such a root.
+    pub fn remove_root(&mut self, path: &Path) -> bool {
+        let Some(&id) = self.path_to_node.get(path) else {
+            return false;
+        };
+        let Some(index) = self.extra_roots.iter().position(|&root| root == id) else {
+            return false;
+        };
+        self.extra_roots.remove(index);
+        self.remove_node_recursive(id);
+        true
+    }
+
     /// Get a node by ID
     pub fn get_node(&self, id: NodeId) -> Option<&TreeNode> {
         self.nodes.get(&id)
@@ -233,6 +304,9 @@ impl FileTree {
     pub fn get_visible_nodes(&self) -> Vec<NodeId> {
         let mut visible = Vec::new();
         self.collect_visible_recursive(self.root_id, &mut visible);
+        for &root_id in &self.extra_roots {
+            self.collect_visible_recursive(root_id, &mut visible);
+        }
         visible
     }
 
@@ -323,7 +397,7 @@ impl FileTree {
     /// # Returns
     ///
     /// Returns the NodeId of the target if found, or None if:
-    /// - The path is not under the root directory
+    /// - The path is not under any root directory
     /// - The path doesn't exist
     /// - There was an error expanding intermediate directories
     ///
@@ -337,11 +411,17 @@ impl FileTree {
     /// }
     /// ```
     pub async fn expand_to_path(&mut self, path: &Path) -> Option<NodeId> {
-        // Check if path is under root
-        let relative_path = path.strip_prefix(&self.root_path).ok()?;
+        // Find the root the path is under
+        let (root_id, relative_path) = std::iter::once(self.root_id)
+            .chain(self.extra_roots.iter().copied())
+            .find_map(|id| {
+                let root_path = &self.get_node(id)?.entry.path;
+                let relative = path.strip_prefix(root_path).ok()?;
+                Some((id, relative.to_path_buf()))
+            })?;
 
         // Start from root
-        let mut current_id = self.root_id;
+        let mut current_id = root_id;
 
         // Walk through each component of the path
         for component in relative_path.components() {
@@ -722,4 +802,31 @@ mod tests {
 
         assert!(result.is_none(), "Should return None for nonexistent paths");
     }
+
+    #[tokio::test]
+    async fn test_extra_roots() {
+        let (_temp_dir, mut tree) = create_test_tree().await;
+        let other_dir = TempDir::new().unwrap();
+        std_fs::write(other_dir.path().join("other.txt"), "other").unwrap();
+        let other_path = other_dir.path().to_path_buf();
+
+        let other_id = tree.add_root(other_path.clone()).await.unwrap();
+        assert!(tree.is_root(other_id));
+        assert_eq!(tree.add_root(other_path.clone()).await.unwrap(), other_id);
+        assert!(tree.add_root(tree.root_path().join("dir1")).await.is_err());
+
+        // Shown after the root, at the top level and expanded
+        let visible = tree.get_visible_nodes();
+        assert_eq!(visible, vec![tree.root_id(), other_id, visible[2]]);
+        assert_eq!(tree.get_depth(other_id), 0);
+
+        let file = other_path.join("other.txt");
+        let file_id = tree.expand_to_path(&file).await.unwrap();
+        assert_eq!(tree.get_node(file_id).unwrap().parent, Some(other_id));
+
+        assert!(tree.remove_root(&other_path));
+        assert!(!tree.remove_root(&other_path));
+        assert!(tree.get_node_by_path(&file).is_none());
+        assert_eq!(tree.get_visible_nodes(), vec![tree.root_id()]);
+    }
 }
@@ -60,11 +60,30 @@ impl FileTreeView {
         &mut self.tree
     }
 
+    /// Nodes the tree shows: those under expanded directories, except ones
+    /// left out by ignore files or patterns. Hidden files are shown dimmed,
+    /// and roots are always shown.
+    pub fn shown_nodes(&self) -> Vec<NodeId> {
+        self.tree
+            .get_visible_nodes()
+            .into_iter()
+            .filter(|&id| self.tree.is_root(id) || !self.is_node_excluded(id))
+            .collect()
+    }
+
+    /// Whether ignore files or patterns leave a node out of the tree
+    fn is_node_excluded(&self, node_id: NodeId) -> bool {
+        self.tree.get_node(node_id).is_some_and(|node| {
+            self.ignore_patterns
+                .is_excluded(&node.entry.path, node.is_dir())
+        })
+    }
+
     /// Get currently visible nodes with their indent levels
     ///
     /// Returns a list of (NodeId, indent_level) tuples for rendering.
     pub fn get_display_nodes(&self) -> Vec<(NodeId, usize)> {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         visible
             .into_iter()
             .map(|id| {
@@ -86,7 +105,7 @@ impl FileTreeView {
 
     /// Select the next visible node
     pub fn select_next(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if visible.is_empty() {
             return;
         }
@@ -104,7 +123,7 @@ impl FileTreeView {
 
     /// Select the previous visible node
     pub fn select_prev(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if visible.is_empty() {
             return;
         }
@@ -126,7 +145,7 @@ impl FileTreeView {
             return;
         }
 
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if visible.is_empty() {
             return;
         }
@@ -147,7 +166,7 @@ impl FileTreeView {
             return;
         }
 
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if visible.is_empty() {
             return;
         }
@@ -175,7 +194,7 @@ impl FileTreeView {
         }
 
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.shown_nodes();
             if let Some(pos) = visible.iter().position(|&id| id == selected) {
                 // Only scroll if cursor goes PAST the viewport edges
                 // This implements symmetric scrolling behavior
@@ -195,7 +214,7 @@ impl FileTreeView {
 
     /// Select the first visible node
     pub fn select_first(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if !visible.is_empty() {
             self.selected_node = Some(visible[0]);
         }
@@ -203,7 +222,7 @@ impl FileTreeView {
 
     /// Select the last visible node
     pub fn select_last(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         if !visible.is_empty() {
             self.selected_node = Some(*visible.last().unwrap());
         }
@@ -243,7 +262,7 @@ impl FileTreeView {
         }
 
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.shown_nodes();
             if let Some(pos) = visible.iter().position(|&id| id == selected) {
                 // If selection is above viewport, scroll up
                 if pos < self.scroll_offset {
@@ -285,7 +304,7 @@ impl FileTreeView {
     /// Get the index of the selected node in the visible list
     pub fn get_selected_index(&self) -> Option<usize> {
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.shown_nodes();
             visible.iter().position(|&id| id == selected)
         } else {
             None
@@ -294,13 +313,13 @@ impl FileTreeView {
 
     /// Get visible node at index (accounting for scroll offset)
     pub fn get_node_at_index(&self, index: usize) -> Option<NodeId> {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.shown_nodes();
         visible.get(index).copied()
     }
 
     /// Get the number of visible nodes
     pub fn visible_count(&self) -> usize {
-        self.tree.get_visible_nodes().len()
+        self.shown_nodes().len()
     }
 
     /// Get reference to ignore patterns
@@ -1,3 +1,4 @@
+use crate::config::GutterComponent;
 use crate::model::marker::{MarkerId, MarkerList};
 use ratatui::style::{Color, Style};
 use std::collections::BTreeMap;
@@ -11,6 +12,43 @@ pub enum MarginPosition {
     Right,
 }
 
+/// Namespace of the line indicators that mark bookmarks
+pub const BOOKMARK_INDICATOR_NAMESPACE: &str = "bookmark";
+
+/// A column of the left margin, as laid out from the gutter components
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GutterColumn {
+    /// A one-character column showing the first of these components with a
+    /// sign on the line
+    Signs(Vec<GutterComponent>),
+    /// The line numbers
+    LineNumbers,
+}
+
+/// Group gutter components into columns, merging adjacent sign components
+pub fn layout_gutter(components: &[GutterComponent]) -> Vec<GutterColumn> {
+    let mut columns: Vec<GutterColumn> = Vec::new();
+    for &component in components {
+        if !component.is_sign() {
+            if !columns.contains(&GutterColumn::LineNumbers) {
+                columns.push(GutterColumn::LineNumbers);
+            }
+            continue;
+        }
+        if columns.iter().any(|column| match column {
+            GutterColumn::Signs(signs) => signs.contains(&component),
+            GutterColumn::LineNumbers => false,
+        }) {
+            continue;
+        }
+        match columns.last_mut() {
+            Some(GutterColumn::Signs(signs)) => signs.push(component),
+            _ => columns.push(GutterColumn::Signs(vec![component])),
+        }
+    }
+    columns
+}
+
 /// A line indicator displayed in the gutter's indicator column
 /// Can be used for git status, breakpoints, bookmarks, etc.
 ///
@@ -314,11 +352,23 @@ pub struct MarginManager {
     /// Maps marker_id -> (namespace -> indicator)
     /// The line number is computed at render time from the marker's byte position
     line_indicators: BTreeMap<u64, BTreeMap<String, LineIndicator>>,
+
+    /// Gutter components the columns were laid out from
+    gutter_components: Vec<GutterComponent>,
+
+    /// Columns of the left margin, before the separator
+    gutter_columns: Vec<GutterColumn>,
 }
 
 impl MarginManager {
     /// Create a new margin manager with default settings
     pub fn new() -> Self {
+        let gutter_components = vec![
+            GutterComponent::DiagnosticSigns,
+            GutterComponent::GitSigns,
+            GutterComponent::Bookmarks,
+            GutterComponent::LineNumbers,
+        ];
         Self {
             left_config: MarginConfig::left_default(),
             right_config: MarginConfig::right_default(),
@@ -328,6 +378,8 @@ impl MarginManager {
             diagnostic_indicators: BTreeMap::new(),
             indicator_markers: MarkerList::new(),
             line_indicators: BTreeMap::new(),
+            gutter_columns: layout_gutter(&gutter_components),
+            gutter_components,
         }
     }
 
@@ -409,6 +461,11 @@ impl MarginManager {
         }
     }
 
+    /// Current byte position of the line indicator at `marker_id`
+    pub fn line_indicator_position(&self, marker_id: MarkerId) -> Option<usize> {
+        self.indicator_markers.get_position(marker_id)
+    }
+
     /// Clear all line indicators for a specific namespace
     pub fn clear_line_indicators_for_namespace(&mut self, namespace: &str) {
         // Collect marker IDs to delete (can't modify while iterating)
@@ -472,6 +529,20 @@ impl MarginManager {
         viewport_start: usize,
         viewport_end: usize,
         get_line_fn: impl Fn(usize) -> usize,
+    ) -> BTreeMap<usize, LineIndicator> {
+        self.get_indicators_for_viewport_in(viewport_start, viewport_end, get_line_fn, |_| true)
+    }
+
+    /// Get indicators within a viewport byte range from the namespaces
+    /// accepted by `include_namespace`
+    ///
+    /// Returns a map of line_number -> highest priority indicator for that line.
+    pub fn get_indicators_for_viewport_in(
+        &self,
+        viewport_start: usize,
+        viewport_end: usize,
+        get_line_fn: impl Fn(usize) -> usize,
+        include_namespace: impl Fn(&str) -> bool,
     ) -> BTreeMap<usize, LineIndicator> {
         let mut by_line: BTreeMap<usize, LineIndicator> = BTreeMap::new();
 
@@ -485,7 +556,12 @@ impl MarginManager {
                 let line = get_line_fn(byte_pos);
 
                 // Get highest priority indicator for this marker
-                if let Some(indicator) = indicators.values().max_by_key(|ind| ind.priority) {
+                if let Some(indicator) = indicators
+                    .iter()
+                    .filter(|(namespace, _)| include_namespace(namespace))
+                    .map(|(_, ind)| ind)
+                    .max_by_key(|ind| ind.priority)
+                {
                     // Check if this is higher priority than existing indicator on this line
                     if let Some(existing) = by_line.get(&line) {
                         if indicator.priority > existing.priority {
@@ -618,10 +694,43 @@ impl MarginManager {
         }
     }
 
+    /// Lay out the gutter columns from `components`, if they changed
+    pub fn set_gutter_components(&mut self, components: &[GutterComponent]) {
+        if self.gutter_components != components {
+            self.gutter_components = components.to_vec();
+            self.gutter_columns = layout_gutter(components);
+        }
+    }
+
+    /// Columns of the left margin, before the separator
+    pub fn gutter_columns(&self) -> &[GutterColumn] {
+        &self.gutter_columns
+    }
+
+    /// Width of a gutter column
+    pub fn gutter_column_width(&self, column: &GutterColumn) -> usize {
+        match column {
+            GutterColumn::Signs(_) => 1,
+            GutterColumn::LineNumbers => self.left_config.width,
+        }
+    }
+
     /// Get the total width of the left margin (including separator)
-    /// The separator includes the diagnostic indicator when present
     pub fn left_total_width(&self) -> usize {
-        self.left_config.total_width()
+        if !self.left_config.enabled || self.gutter_columns.is_empty() {
+            return 0;
+        }
+        let columns: usize = self
+            .gutter_columns
+            .iter()
+            .map(|column| self.gutter_column_width(column))
+            .sum();
+        let separator = if self.left_config.show_separator {
+            self.left_config.separator.chars().count()
+        } else {
+            0
+        };
+        columns + separator
     }
 
     /// Get the total width of the right margin (including separator)
@@ -1003,4 +1112,68 @@ mod tests {
         assert!(manager.get_line_indicator(7, byte_to_line).is_some());
         assert!(manager.get_line_indicator(9, byte_to_line).is_some());
     }
+
+    #[test]
+    fn test_layout_gutter_merges_adjacent_signs() {
+        use GutterComponent::*;
+
+        assert_eq!(
+            layout_gutter(&[DiagnosticSigns, GitSigns, LineNumbers, Bookmarks]),
+            vec![
+                GutterColumn::Signs(vec![DiagnosticSigns, GitSigns]),
+                GutterColumn::LineNumbers,
+                GutterColumn::Signs(vec![Bookmarks]),
+            ]
+        );
+        // Repeated components only appear once
+        assert_eq!(
+            layout_gutter(&[LineNumbers, GitSigns, LineNumbers, GitSigns]),
+            vec![
+                GutterColumn::LineNumbers,
+                GutterColumn::Signs(vec![GitSigns]),
+            ]
+        );
+        assert!(layout_gutter(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_left_total_width_follows_gutter_columns() {
+        use GutterComponent::*;
+
+        let mut manager = MarginManager::new();
+        manager.update_width_for_buffer(10);
+        assert_eq!(manager.left_total_width(), 8); // 1 (signs) + 4 (line num) + 3 (separator)
+
+        manager.set_gutter_components(&[GitSigns, LineNumbers, Bookmarks]);
+        assert_eq!(manager.left_total_width(), 9);
+
+        manager.set_gutter_components(&[LineNumbers]);
+        assert_eq!(manager.left_total_width(), 7);
+
+        manager.set_gutter_components(&[]);
+        assert_eq!(manager.left_total_width(), 0);
+    }
+
+    #[test]
+    fn test_indicators_for_viewport_by_namespace() {
+        let mut manager = MarginManager::new();
+        manager.set_line_indicator(
+            line_to_byte(2),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+        manager.set_line_indicator(
+            line_to_byte(2),
+            BOOKMARK_INDICATOR_NAMESPACE.to_string(),
+            LineIndicator::new("a", Color::Cyan, 5),
+        );
+
+        let all = manager.get_indicators_for_viewport(0, 100, byte_to_line);
+        assert_eq!(all.get(&2).map(|i| i.symbol.as_str()), Some("│"));
+
+        let bookmarks = manager.get_indicators_for_viewport_in(0, 100, byte_to_line, |ns| {
+            ns == BOOKMARK_INDICATOR_NAMESPACE
+        });
+        assert_eq!(bookmarks.get(&2).map(|i| i.symbol.as_str()), Some("a"));
+    }
 }
@@ -4,10 +4,12 @@
 
 pub mod color_support;
 pub mod controls;
+pub mod decoration;
 pub mod dimming;
 pub mod file_browser_input;
 pub mod file_tree;
 pub mod margin;
+pub mod notification;
 pub mod overlay;
 pub mod popup;
 pub mod popup_input;
@@ -19,5 +21,6 @@ pub mod split;
 pub mod stream;
 pub mod theme;
 pub mod ui;
+pub mod underline;
 pub mod viewport;
 pub mod virtual_text;
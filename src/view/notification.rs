@@ -0,0 +1,324 @@
+//! Toast notifications
+//!
+//! Notifications are short, non-blocking messages that stack in the bottom-right
+//! corner of the editor area and dismiss themselves after a timeout. Unlike the
+//! status bar, several notifications can be visible at once, and every
+//! notification is kept in a bounded history so it can be reviewed later.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::primitives::display_width::str_width;
+
+/// Maximum number of notifications kept in the history
+pub const MAX_NOTIFICATION_HISTORY: usize = 100;
+
+/// Maximum number of toasts shown on screen at the same time
+pub const MAX_VISIBLE_NOTIFICATIONS: usize = 4;
+
+/// Maximum width of a toast (in columns, including borders)
+const MAX_TOAST_WIDTH: u16 = 50;
+
+/// Maximum number of message lines shown inside a single toast
+const MAX_TOAST_LINES: usize = 3;
+
+/// Severity of a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Parse a level name (as used by plugins); unknown names map to `Info`
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "warn" | "warning" => Self::Warning,
+            "error" => Self::Error,
+            _ => Self::Info,
+        }
+    }
+
+    /// Human-readable label, used for toast titles and the history panel
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+
+    /// Foreground color for this level
+    fn color(&self, theme: &crate::view::theme::Theme) -> ratatui::style::Color {
+        match self {
+            Self::Info => theme.diagnostic_info_fg,
+            Self::Warning => theme.diagnostic_warning_fg,
+            Self::Error => theme.diagnostic_error_fg,
+        }
+    }
+}
+
+/// A single notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    /// Unique, monotonically increasing ID
+    pub id: u64,
+    /// Severity
+    pub level: NotificationLevel,
+    /// Message text
+    pub message: String,
+    /// Where the notification came from (e.g. a plugin name), if known
+    pub source: Option<String>,
+    /// When the notification was posted
+    pub created_at: Instant,
+}
+
+/// Tracks visible toasts and the notification history
+#[derive(Debug, Clone)]
+pub struct NotificationManager {
+    /// Toasts currently on screen (oldest first)
+    active: Vec<Notification>,
+    /// All notifications posted so far (oldest first, bounded)
+    history: VecDeque<Notification>,
+    /// Next notification ID
+    next_id: u64,
+}
+
+impl NotificationManager {
+    pub fn new() -> Self {
+        Self {
+            active: Vec::new(),
+            history: VecDeque::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Post a notification. Returns its ID.
+    pub fn push(
+        &mut self,
+        level: NotificationLevel,
+        message: String,
+        source: Option<String>,
+        now: Instant,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let notification = Notification {
+            id,
+            level,
+            message,
+            source,
+            created_at: now,
+        };
+
+        self.history.push_back(notification.clone());
+        while self.history.len() > MAX_NOTIFICATION_HISTORY {
+            self.history.pop_front();
+        }
+
+        self.active.push(notification);
+        // Drop the oldest toasts when the stack overflows
+        if self.active.len() > MAX_VISIBLE_NOTIFICATIONS {
+            let overflow = self.active.len() - MAX_VISIBLE_NOTIFICATIONS;
+            self.active.drain(..overflow);
+        }
+
+        id
+    }
+
+    /// Remove toasts that have been on screen longer than `timeout`.
+    /// Errors stay on screen twice as long as other levels.
+    /// Returns true if any toast was removed (i.e. a re-render is needed).
+    pub fn expire(&mut self, now: Instant, timeout: Duration) -> bool {
+        let before = self.active.len();
+        self.active.retain(|n| {
+            let lifetime = if n.level == NotificationLevel::Error {
+                timeout * 2
+            } else {
+                timeout
+            };
+            now.saturating_duration_since(n.created_at) < lifetime
+        });
+        self.active.len() != before
+    }
+
+    /// Dismiss all visible toasts (history is kept)
+    pub fn dismiss_all(&mut self) -> bool {
+        let had_any = !self.active.is_empty();
+        self.active.clear();
+        had_any
+    }
+
+    /// Toasts currently on screen (oldest first)
+    pub fn active(&self) -> &[Notification] {
+        &self.active
+    }
+
+    /// Notification history (oldest first)
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Number of notifications in the history
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Render visible toasts stacked upwards from the bottom-right corner of `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &crate::view::theme::Theme) {
+        if self.active.is_empty() || area.width < 10 || area.height < 3 {
+            return;
+        }
+
+        let width = MAX_TOAST_WIDTH.min(area.width.saturating_sub(2));
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let mut bottom = area.y + area.height;
+
+        // Newest toast sits closest to the corner
+        for notification in self.active.iter().rev() {
+            let text_width = str_width(&notification.message);
+            let lines = text_width.div_ceil(inner_width).clamp(1, MAX_TOAST_LINES);
+            let height = lines as u16 + 2;
+
+            if bottom < area.y + height {
+                break;
+            }
+            let toast_area = Rect {
+                x: area.x + area.width - width - 1,
+                y: bottom - height,
+                width,
+                height,
+            };
+            bottom -= height;
+
+            let color = notification.level.color(theme);
+            let mut title = vec![Span::styled(
+                format!(" {} ", notification.level.label()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )];
+            if let Some(source) = &notification.source {
+                title.push(Span::styled(
+                    format!("{} ", source),
+                    Style::default().fg(theme.help_separator_fg),
+                ));
+            }
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(theme.popup_bg).fg(theme.popup_text_fg))
+                .title(Line::from(title));
+
+            let paragraph = Paragraph::new(notification.message.as_str())
+                .block(block)
+                .wrap(Wrap { trim: true });
+
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(paragraph, toast_area);
+        }
+    }
+}
+
+impl Default for NotificationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_name() {
+        assert_eq!(
+            NotificationLevel::from_name("info"),
+            NotificationLevel::Info
+        );
+        assert_eq!(
+            NotificationLevel::from_name("WARN"),
+            NotificationLevel::Warning
+        );
+        assert_eq!(
+            NotificationLevel::from_name("warning"),
+            NotificationLevel::Warning
+        );
+        assert_eq!(
+            NotificationLevel::from_name("error"),
+            NotificationLevel::Error
+        );
+        assert_eq!(
+            NotificationLevel::from_name("bogus"),
+            NotificationLevel::Info
+        );
+    }
+
+    #[test]
+    fn test_push_and_expire() {
+        let mut manager = NotificationManager::new();
+        let timeout = Duration::from_secs(1);
+        let start = Instant::now();
+
+        manager.push(NotificationLevel::Info, "hello".into(), None, start);
+        manager.push(NotificationLevel::Error, "boom".into(), None, start);
+        assert_eq!(manager.active().len(), 2);
+
+        // Nothing expires before the timeout
+        assert!(!manager.expire(start + Duration::from_millis(500), timeout));
+        assert_eq!(manager.active().len(), 2);
+
+        // Info expires after the timeout, error lingers for twice as long
+        assert!(manager.expire(start + Duration::from_millis(1500), timeout));
+        assert_eq!(manager.active().len(), 1);
+        assert_eq!(manager.active()[0].message, "boom");
+
+        assert!(manager.expire(start + Duration::from_millis(2500), timeout));
+        assert!(manager.active().is_empty());
+
+        // History keeps everything
+        assert_eq!(manager.history_len(), 2);
+    }
+
+    #[test]
+    fn test_visible_stack_is_bounded() {
+        let mut manager = NotificationManager::default();
+        let now = Instant::now();
+        for i in 0..(MAX_VISIBLE_NOTIFICATIONS + 2) {
+            manager.push(NotificationLevel::Info, format!("n{}", i), None, now);
+        }
+        assert_eq!(manager.active().len(), MAX_VISIBLE_NOTIFICATIONS);
+        // Oldest toasts were dropped first
+        assert_eq!(manager.active()[0].message, "n2");
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut manager = NotificationManager::default();
+        let now = Instant::now();
+        for i in 0..(MAX_NOTIFICATION_HISTORY + 5) {
+            manager.push(NotificationLevel::Info, format!("n{}", i), None, now);
+        }
+        assert_eq!(manager.history_len(), MAX_NOTIFICATION_HISTORY);
+        assert_eq!(manager.history().next().unwrap().message, "n5");
+    }
+
+    #[test]
+    fn test_dismiss_all_keeps_history() {
+        let mut manager = NotificationManager::default();
+        manager.push(NotificationLevel::Warning, "w".into(), None, Instant::now());
+        assert!(manager.dismiss_all());
+        assert!(manager.active().is_empty());
+        assert_eq!(manager.history_len(), 1);
+        assert!(!manager.dismiss_all());
+    }
+}
@@ -98,6 +98,27 @@ pub enum UnderlineStyle {
 /// Higher priority overlays are rendered on top of lower priority ones
 pub type Priority = i32;
 
+/// Priorities of the editor's own overlays
+///
+/// Overlays of equal priority are drawn in the order they were added.
+/// Plugins choose their own priorities relative to these.
+pub mod priority {
+    use super::Priority;
+
+    /// Highlights added by plugins without a priority of their own
+    pub const PLUGIN_HIGHLIGHT: Priority = 10;
+    /// Search and replace matches
+    pub const SEARCH_MATCH: Priority = 10;
+    /// The symbol under the mouse while hovering
+    pub const HOVER_SYMBOL: Priority = 90;
+    /// Cursors of other editors in a shared buffer
+    pub const REMOTE_CURSOR: Priority = 95;
+    pub const DIAGNOSTIC_HINT: Priority = 10;
+    pub const DIAGNOSTIC_INFO: Priority = 30;
+    pub const DIAGNOSTIC_WARNING: Priority = 50;
+    pub const DIAGNOSTIC_ERROR: Priority = 100;
+}
+
 /// An overlay represents a visual decoration over a range of text
 /// Uses markers for content-anchored positions that automatically adjust with edits
 #[derive(Debug, Clone)]
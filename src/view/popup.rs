@@ -8,6 +8,10 @@ use ratatui::{
 
 use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 
+use crate::primitives::display_width::{char_width, str_width};
+use crate::primitives::grammar_registry::GrammarRegistry;
+use crate::primitives::highlight_engine::highlight_snippet;
+
 /// Clamp a rectangle to fit within bounds, preventing out-of-bounds rendering panics.
 /// Returns a rectangle that is guaranteed to be fully contained within `bounds`.
 fn clamp_rect_to_bounds(rect: Rect, bounds: Rect) -> Rect {
@@ -28,6 +32,12 @@ fn clamp_rect_to_bounds(rect: Rect, bounds: Rect) -> Rect {
     }
 }
 
+/// Minimum width a popup can be resized to with the mouse (including borders)
+pub const MIN_POPUP_WIDTH: u16 = 10;
+
+/// Minimum height a popup can be resized to with the mouse (including borders)
+pub const MIN_POPUP_HEIGHT: u16 = 3;
+
 /// Position of a popup relative to a point in the buffer
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PopupPosition {
@@ -88,152 +98,643 @@ pub enum PopupContent {
     Custom(Vec<String>),
 }
 
-/// Parse markdown text into styled lines for terminal rendering
-pub fn parse_markdown(text: &str, theme: &crate::view::theme::Theme) -> Vec<StyledLine> {
+/// Parse markdown text into styled lines for terminal rendering.
+/// Fenced code blocks are syntax highlighted when `grammar_registry` knows their language.
+pub fn parse_markdown(
+    text: &str,
+    theme: &crate::view::theme::Theme,
+    grammar_registry: Option<&GrammarRegistry>,
+) -> Vec<StyledLine> {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
 
     let parser = Parser::new_ext(text, options);
-    let mut lines: Vec<StyledLine> = vec![StyledLine::new()];
+    let mut renderer = MarkdownRenderer::new(theme, grammar_registry);
+    for event in parser {
+        renderer.handle_event(event);
+    }
+    renderer.finish()
+}
 
-    // Style stack for nested formatting
-    let mut style_stack: Vec<Style> = vec![Style::default()];
-    let mut in_code_block = false;
-    let mut code_block_lang = String::new();
+/// Bullet characters for unordered lists, cycling with nesting depth
+const LIST_BULLETS: [&str; 3] = ["•", "◦", "▪"];
 
-    for event in parser {
-        match event {
-            Event::Start(tag) => {
-                match tag {
-                    Tag::Strong => {
-                        let current = *style_stack.last().unwrap_or(&Style::default());
-                        style_stack.push(current.add_modifier(Modifier::BOLD));
-                    }
-                    Tag::Emphasis => {
-                        let current = *style_stack.last().unwrap_or(&Style::default());
-                        style_stack.push(current.add_modifier(Modifier::ITALIC));
-                    }
-                    Tag::Strikethrough => {
-                        let current = *style_stack.last().unwrap_or(&Style::default());
-                        style_stack.push(current.add_modifier(Modifier::CROSSED_OUT));
-                    }
-                    Tag::CodeBlock(kind) => {
-                        in_code_block = true;
-                        code_block_lang = match kind {
-                            pulldown_cmark::CodeBlockKind::Fenced(lang) => lang.to_string(),
-                            pulldown_cmark::CodeBlockKind::Indented => String::new(),
-                        };
-                        // Start new line for code block
-                        if !lines.last().map(|l| l.spans.is_empty()).unwrap_or(true) {
-                            lines.push(StyledLine::new());
-                        }
-                    }
-                    Tag::Heading { .. } => {
-                        let current = *style_stack.last().unwrap_or(&Style::default());
-                        style_stack
-                            .push(current.add_modifier(Modifier::BOLD).fg(theme.help_key_fg));
-                    }
-                    Tag::Link { .. } | Tag::Image { .. } => {
-                        let current = *style_stack.last().unwrap_or(&Style::default());
-                        style_stack
-                            .push(current.add_modifier(Modifier::UNDERLINED).fg(Color::Cyan));
-                    }
-                    Tag::List(_) | Tag::Item => {
-                        // Start list items on new line
-                        if !lines.last().map(|l| l.spans.is_empty()).unwrap_or(true) {
-                            lines.push(StyledLine::new());
-                        }
-                    }
-                    Tag::Paragraph => {
-                        // Start paragraphs on new line if we have content
-                        if !lines.last().map(|l| l.spans.is_empty()).unwrap_or(true) {
-                            lines.push(StyledLine::new());
-                        }
-                    }
-                    _ => {}
-                }
+/// One level of (possibly nested) list
+struct ListLevel {
+    /// Number of the next item for ordered lists, `None` for bullet lists
+    next_number: Option<u64>,
+    /// Width of the current item's marker, used to indent continuation lines
+    indent: usize,
+}
+
+/// A table whose cells are collected first and laid out once the table ends
+struct TableBuilder {
+    alignments: Vec<pulldown_cmark::Alignment>,
+    /// Rows of cells; each cell is a list of styled spans
+    rows: Vec<Vec<Vec<StyledSpan>>>,
+    /// Number of header rows at the start of `rows`
+    header_rows: usize,
+}
+
+/// Turns pulldown-cmark events into styled lines
+struct MarkdownRenderer<'a> {
+    theme: &'a crate::view::theme::Theme,
+    grammar_registry: Option<&'a GrammarRegistry>,
+    lines: Vec<StyledLine>,
+    /// Style stack for nested formatting
+    style_stack: Vec<Style>,
+    in_code_block: bool,
+    code_block_lang: String,
+    /// Text of the current code block, highlighted as a whole when the block ends
+    code_block_text: String,
+    list_stack: Vec<ListLevel>,
+    /// Marker of the list item being started, emitted with its first content
+    pending_marker: Option<String>,
+    blockquote_depth: usize,
+    table: Option<TableBuilder>,
+}
+
+impl<'a> MarkdownRenderer<'a> {
+    fn new(
+        theme: &'a crate::view::theme::Theme,
+        grammar_registry: Option<&'a GrammarRegistry>,
+    ) -> Self {
+        Self {
+            theme,
+            grammar_registry,
+            lines: vec![StyledLine::new()],
+            style_stack: vec![Style::default()],
+            in_code_block: false,
+            code_block_lang: String::new(),
+            code_block_text: String::new(),
+            list_stack: Vec::new(),
+            pending_marker: None,
+            blockquote_depth: 0,
+            table: None,
+        }
+    }
+
+    fn current_style(&self) -> Style {
+        *self.style_stack.last().unwrap_or(&Style::default())
+    }
+
+    /// Start a new line unless the current one is still empty
+    fn start_new_line(&mut self) {
+        if !self
+            .lines
+            .last()
+            .map(|l| l.spans.is_empty())
+            .unwrap_or(true)
+        {
+            self.lines.push(StyledLine::new());
+        }
+    }
+
+    /// Spans that start every line at the current nesting: block quote bars,
+    /// list indentation, and the marker of a freshly started list item
+    fn line_prefix(&mut self) -> Vec<StyledSpan> {
+        let mut prefix = Vec::new();
+        for _ in 0..self.blockquote_depth {
+            prefix.push(StyledSpan {
+                text: "│ ".to_string(),
+                style: Style::default().fg(self.theme.help_separator_fg),
+            });
+        }
+
+        if let Some(marker) = self.pending_marker.take() {
+            // Marker replaces the indentation of the innermost list level
+            let outer = self.list_stack.len().saturating_sub(1);
+            let indent: usize = self.list_stack[..outer].iter().map(|l| l.indent).sum();
+            if indent > 0 {
+                prefix.push(StyledSpan {
+                    text: " ".repeat(indent),
+                    style: Style::default(),
+                });
             }
-            Event::End(tag_end) => {
-                match tag_end {
-                    TagEnd::Strong
-                    | TagEnd::Emphasis
-                    | TagEnd::Strikethrough
-                    | TagEnd::Heading(_)
-                    | TagEnd::Link
-                    | TagEnd::Image => {
-                        style_stack.pop();
-                    }
-                    TagEnd::CodeBlock => {
-                        in_code_block = false;
-                        code_block_lang.clear();
-                        // End code block with new line
-                        lines.push(StyledLine::new());
-                    }
-                    TagEnd::Paragraph => {
-                        // Add blank line after paragraph
-                        lines.push(StyledLine::new());
-                    }
-                    TagEnd::Item => {
-                        // Items end naturally
-                    }
-                    _ => {}
-                }
+            prefix.push(StyledSpan {
+                text: marker,
+                style: Style::default().fg(self.theme.help_key_fg),
+            });
+        } else {
+            let indent: usize = self.list_stack.iter().map(|l| l.indent).sum();
+            if indent > 0 {
+                prefix.push(StyledSpan {
+                    text: " ".repeat(indent),
+                    style: Style::default(),
+                });
+            }
+        }
+        prefix
+    }
+
+    /// Append text to the current table cell, or to the current line
+    fn push_text(&mut self, text: String, style: Style) {
+        if let Some(table) = &mut self.table {
+            if let Some(cell) = table.rows.last_mut().and_then(|row| row.last_mut()) {
+                cell.push(StyledSpan { text, style });
+            }
+            return;
+        }
+
+        if self
+            .lines
+            .last()
+            .map(|l| l.spans.is_empty())
+            .unwrap_or(true)
+        {
+            let prefix = self.line_prefix();
+            if let Some(line) = self.lines.last_mut() {
+                line.spans.extend(prefix);
+            }
+        }
+        if !text.is_empty() {
+            if let Some(line) = self.lines.last_mut() {
+                line.push(text, style);
             }
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag_end) => self.end_tag(tag_end),
             Event::Text(text) => {
-                let current_style = if in_code_block {
-                    Style::default()
-                        .fg(theme.help_key_fg)
-                        .bg(theme.inline_code_bg)
-                } else {
-                    *style_stack.last().unwrap_or(&Style::default())
-                };
+                if self.in_code_block {
+                    self.code_block_text.push_str(&text);
+                    return;
+                }
+                let current_style = self.current_style();
 
                 // Split text by newlines and add to lines
                 for (i, part) in text.split('\n').enumerate() {
                     if i > 0 {
-                        lines.push(StyledLine::new());
+                        if self.table.is_some() {
+                            continue;
+                        }
+                        self.lines.push(StyledLine::new());
                     }
                     if !part.is_empty() {
-                        if let Some(line) = lines.last_mut() {
-                            line.push(part.to_string(), current_style);
-                        }
+                        self.push_text(part.to_string(), current_style);
                     }
                 }
             }
             Event::Code(code) => {
                 // Inline code
                 let style = Style::default()
-                    .fg(theme.help_key_fg)
-                    .bg(theme.inline_code_bg);
-                if let Some(line) = lines.last_mut() {
-                    line.push(format!("`{}`", code), style);
-                }
+                    .fg(self.theme.help_key_fg)
+                    .bg(self.theme.inline_code_bg);
+                self.push_text(format!("`{}`", code), style);
             }
             Event::SoftBreak => {
                 // Soft break - add space
-                if let Some(line) = lines.last_mut() {
-                    line.push(" ".to_string(), Style::default());
-                }
+                self.push_text(" ".to_string(), Style::default());
             }
             Event::HardBreak => {
-                // Hard break - new line
-                lines.push(StyledLine::new());
+                // Hard break - new line (cells stay on one line)
+                if self.table.is_some() {
+                    self.push_text(" ".to_string(), Style::default());
+                } else {
+                    self.lines.push(StyledLine::new());
+                }
             }
             Event::Rule => {
                 // Horizontal rule
-                lines.push(StyledLine::new());
-                if let Some(line) = lines.last_mut() {
+                self.lines.push(StyledLine::new());
+                if let Some(line) = self.lines.last_mut() {
                     line.push("─".repeat(40), Style::default().fg(Color::DarkGray));
                 }
-                lines.push(StyledLine::new());
+                self.lines.push(StyledLine::new());
             }
             _ => {}
         }
     }
 
-    // Remove trailing empty lines
-    while lines.last().map(|l| l.spans.is_empty()).unwrap_or(false) {
-        lines.pop();
+    fn start_tag(&mut self, tag: Tag) {
+        let current = self.current_style();
+        match tag {
+            Tag::Strong => {
+                self.style_stack.push(current.add_modifier(Modifier::BOLD));
+            }
+            Tag::Emphasis => {
+                self.style_stack
+                    .push(current.add_modifier(Modifier::ITALIC));
+            }
+            Tag::Strikethrough => {
+                self.style_stack
+                    .push(current.add_modifier(Modifier::CROSSED_OUT));
+            }
+            Tag::CodeBlock(kind) => {
+                self.in_code_block = true;
+                self.code_block_lang = match kind {
+                    pulldown_cmark::CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    pulldown_cmark::CodeBlockKind::Indented => String::new(),
+                };
+                // Start new line for code block
+                self.start_new_line();
+            }
+            Tag::Heading { .. } => {
+                self.style_stack.push(
+                    current
+                        .add_modifier(Modifier::BOLD)
+                        .fg(self.theme.help_key_fg),
+                );
+            }
+            Tag::Link { .. } | Tag::Image { .. } => {
+                self.style_stack
+                    .push(current.add_modifier(Modifier::UNDERLINED).fg(Color::Cyan));
+            }
+            Tag::BlockQuote(_) => {
+                self.start_new_line();
+                self.blockquote_depth += 1;
+                self.style_stack.push(
+                    current
+                        .add_modifier(Modifier::ITALIC)
+                        .fg(self.theme.help_separator_fg),
+                );
+            }
+            Tag::List(start) => {
+                // Start lists on new line
+                self.start_new_line();
+                self.list_stack.push(ListLevel {
+                    next_number: start,
+                    indent: 0,
+                });
+            }
+            Tag::Item => {
+                // Start list items on new line
+                self.start_new_line();
+                let depth = self.list_stack.len().saturating_sub(1);
+                if let Some(level) = self.list_stack.last_mut() {
+                    let marker = match level.next_number.as_mut() {
+                        Some(number) => {
+                            let marker = format!("{}. ", number);
+                            *number += 1;
+                            marker
+                        }
+                        None => format!("{} ", LIST_BULLETS[depth % LIST_BULLETS.len()]),
+                    };
+                    level.indent = str_width(&marker);
+                    self.pending_marker = Some(marker);
+                }
+            }
+            Tag::Paragraph => {
+                // Start paragraphs on new line if we have content
+                self.start_new_line();
+            }
+            Tag::Table(alignments) => {
+                self.start_new_line();
+                self.table = Some(TableBuilder {
+                    alignments,
+                    rows: Vec::new(),
+                    header_rows: 0,
+                });
+            }
+            Tag::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                    table.header_rows += 1;
+                }
+                self.style_stack.push(
+                    current
+                        .add_modifier(Modifier::BOLD)
+                        .fg(self.theme.help_key_fg),
+                );
+            }
+            Tag::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableCell => {
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(Vec::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag_end: TagEnd) {
+        match tag_end {
+            TagEnd::Strong
+            | TagEnd::Emphasis
+            | TagEnd::Strikethrough
+            | TagEnd::Heading(_)
+            | TagEnd::Link
+            | TagEnd::Image
+            | TagEnd::TableHead => {
+                self.style_stack.pop();
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.render_code_block();
+                self.code_block_lang.clear();
+                self.code_block_text.clear();
+                // End code block with new line
+                self.lines.push(StyledLine::new());
+            }
+            TagEnd::Paragraph => {
+                // Add blank line after paragraph
+                self.lines.push(StyledLine::new());
+            }
+            TagEnd::BlockQuote(_) => {
+                self.style_stack.pop();
+                self.blockquote_depth = self.blockquote_depth.saturating_sub(1);
+                self.start_new_line();
+            }
+            TagEnd::Item => {
+                // An empty item still shows its marker
+                if self.pending_marker.is_some() {
+                    self.push_text(String::new(), Style::default());
+                }
+            }
+            TagEnd::List(_) => {
+                self.list_stack.pop();
+                self.start_new_line();
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Emit the collected code block, highlighting it when its language is known
+    fn render_code_block(&mut self) {
+        let code_style = Style::default()
+            .fg(self.theme.help_key_fg)
+            .bg(self.theme.inline_code_bg);
+
+        // Fence info can carry extra attributes ("rust,ignore", "python title=x")
+        let token = self
+            .code_block_lang
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .next()
+            .unwrap_or("");
+        let highlighted = self.grammar_registry.and_then(|registry| {
+            if token.is_empty() {
+                return None;
+            }
+            let syntax = registry.find_syntax_by_token(token)?;
+            Some(highlight_snippet(
+                &self.code_block_text,
+                syntax,
+                registry.syntax_set(),
+            ))
+        });
+
+        let code = std::mem::take(&mut self.code_block_text);
+        for (i, part) in code.split('\n').enumerate() {
+            if i > 0 {
+                self.lines.push(StyledLine::new());
+            }
+            match highlighted.as_ref().and_then(|lines| lines.get(i)) {
+                Some(segments) => {
+                    for (text, category) in segments {
+                        let style = match category {
+                            Some(category) => code_style.fg(category.color(self.theme)),
+                            None => code_style,
+                        };
+                        self.push_text(text.clone(), style);
+                    }
+                }
+                None if !part.is_empty() => self.push_text(part.to_string(), code_style),
+                None => {}
+            }
+        }
+    }
+
+    /// Lay out a collected table with aligned columns
+    fn render_table(&mut self, table: TableBuilder) {
+        use pulldown_cmark::Alignment;
+
+        let cell_width =
+            |cell: &Vec<StyledSpan>| -> usize { cell.iter().map(|s| str_width(&s.text)).sum() };
+
+        let num_columns = table
+            .rows
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0)
+            .max(table.alignments.len());
+        let mut widths = vec![0usize; num_columns];
+        for row in &table.rows {
+            for (col, cell) in row.iter().enumerate() {
+                widths[col] = widths[col].max(cell_width(cell));
+            }
+        }
+
+        let border_style = Style::default().fg(self.theme.help_separator_fg);
+        for (row_idx, row) in table.rows.iter().enumerate() {
+            self.start_new_line();
+            for (col, width) in widths.iter().enumerate() {
+                if col > 0 {
+                    self.push_text("│".to_string(), border_style);
+                }
+                let empty = Vec::new();
+                let cell = row.get(col).unwrap_or(&empty);
+                let padding = width - cell_width(cell);
+                let (left, right) = match table.alignments.get(col) {
+                    Some(Alignment::Right) => (padding, 0),
+                    Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                    _ => (0, padding),
+                };
+                self.push_text(" ".repeat(left + 1), Style::default());
+                for span in cell {
+                    self.push_text(span.text.clone(), span.style);
+                }
+                self.push_text(" ".repeat(right + 1), Style::default());
+            }
+
+            // Separate the header from the body
+            if row_idx + 1 == table.header_rows {
+                self.lines.push(StyledLine::new());
+                let separator = widths
+                    .iter()
+                    .map(|w| "─".repeat(w + 2))
+                    .collect::<Vec<_>>()
+                    .join("┼");
+                self.push_text(separator, border_style);
+            }
+            self.lines.push(StyledLine::new());
+        }
+    }
+
+    fn finish(mut self) -> Vec<StyledLine> {
+        // Remove trailing empty lines
+        while self
+            .lines
+            .last()
+            .map(|l| l.spans.is_empty())
+            .unwrap_or(false)
+        {
+            self.lines.pop();
+        }
+
+        self.lines
+    }
+}
+
+/// Continuation prefix for a wrapped line: block quote bars are repeated and
+/// indentation plus any list marker ("• ", "- ", "1. ") become spaces, so
+/// wrapped list items hang under their text instead of under the marker.
+fn hanging_indent(text: &str) -> String {
+    let mut prefix = String::new();
+    let mut chars = text.chars().peekable();
+
+    // Leading indentation and block quote bars
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '│' => prefix.push(c),
+            _ => break,
+        }
+        chars.next();
+    }
+
+    // List marker: a bullet or a number followed by ". "
+    let rest: String = chars.collect();
+    let marker_len = if let Some(c) = rest
+        .chars()
+        .next()
+        .filter(|c| LIST_BULLETS.iter().any(|b| b.starts_with(*c)) || *c == '-' || *c == '*')
+    {
+        if rest[c.len_utf8()..].starts_with(' ') {
+            c.len_utf8() + 1
+        } else {
+            0
+        }
+    } else {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits > 0 && rest[digits..].starts_with(". ") {
+            digits + 2
+        } else {
+            0
+        }
+    };
+
+    let mut indent: String = prefix
+        .chars()
+        .map(|c| if c == '│' { '│' } else { ' ' })
+        .collect();
+    indent.push_str(&" ".repeat(str_width(&rest[..marker_len])));
+    indent
+}
+
+/// Word-wrap a styled line to `width` columns.
+///
+/// Breaks at whitespace where possible and splits words that are wider than a
+/// whole line. Continuation lines start with the line's hanging indent.
+pub fn wrap_styled_line(line: &StyledLine, width: usize) -> Vec<StyledLine> {
+    let text: String = line.spans.iter().map(|s| s.text.as_str()).collect();
+    if width == 0 || str_width(&text) <= width {
+        return vec![line.clone()];
+    }
+
+    let mut indent = hanging_indent(&text);
+    // Don't let the indent swallow most of the line
+    if str_width(&indent) > width / 2 {
+        indent.clear();
+    }
+    let indent_width = str_width(&indent);
+    let indent_style = line.spans.first().map(|s| s.style).unwrap_or_default();
+
+    // Split spans into words and whitespace runs, keeping their styles
+    let mut tokens: Vec<(String, Style)> = Vec::new();
+    for span in &line.spans {
+        let mut current = String::new();
+        let mut current_is_space = None;
+        for c in span.text.chars() {
+            let is_space = c.is_whitespace();
+            if current_is_space.is_some_and(|s| s != is_space) {
+                tokens.push((std::mem::take(&mut current), span.style));
+            }
+            current.push(c);
+            current_is_space = Some(is_space);
+        }
+        if !current.is_empty() {
+            tokens.push((current, span.style));
+        }
+    }
+
+    let mut lines = vec![StyledLine::new()];
+    let mut line_width = 0;
+    // True while the current line holds nothing but its indent
+    let mut at_line_start = true;
+
+    for (token, style) in tokens {
+        let token_width = str_width(&token);
+        let is_space = token.chars().all(char::is_whitespace);
+
+        if line_width + token_width <= width {
+            if !(is_space && at_line_start && lines.len() > 1) {
+                lines.last_mut().unwrap().push(token, style);
+                line_width += token_width;
+                at_line_start = at_line_start && is_space;
+            }
+            continue;
+        }
+
+        if is_space {
+            // Break at the whitespace and drop it
+            lines.push(StyledLine::new());
+            if indent_width > 0 {
+                lines.last_mut().unwrap().push(indent.clone(), indent_style);
+            }
+            line_width = indent_width;
+            at_line_start = true;
+            continue;
+        }
+
+        if !at_line_start {
+            lines.push(StyledLine::new());
+            if indent_width > 0 {
+                lines.last_mut().unwrap().push(indent.clone(), indent_style);
+            }
+            line_width = indent_width;
+            at_line_start = true;
+        }
+
+        // Place the word, splitting it if it's wider than the remaining space
+        let mut chunk = String::new();
+        for c in token.chars() {
+            let c_width = char_width(c);
+            if line_width + c_width > width && (!chunk.is_empty() || !at_line_start) {
+                if !chunk.is_empty() {
+                    lines
+                        .last_mut()
+                        .unwrap()
+                        .push(std::mem::take(&mut chunk), style);
+                }
+                lines.push(StyledLine::new());
+                if indent_width > 0 {
+                    lines.last_mut().unwrap().push(indent.clone(), indent_style);
+                }
+                line_width = indent_width;
+            }
+            chunk.push(c);
+            line_width += c_width;
+            at_line_start = false;
+        }
+        if !chunk.is_empty() {
+            lines.last_mut().unwrap().push(chunk, style);
+        }
+    }
+
+    // Whitespace before a break would only pad the line out
+    let last = lines.len() - 1;
+    for line in &mut lines[..last] {
+        while let Some(span) = line.spans.last_mut() {
+            let trimmed_len = span.text.trim_end().len();
+            if trimmed_len > 0 {
+                span.text.truncate(trimmed_len);
+                break;
+            }
+            line.spans.pop();
+        }
     }
 
     lines
@@ -336,9 +837,14 @@ impl Popup {
         }
     }
 
-    /// Create a new popup with markdown content using theme colors
-    pub fn markdown(markdown_text: &str, theme: &crate::view::theme::Theme) -> Self {
-        let styled_lines = parse_markdown(markdown_text, theme);
+    /// Create a new popup with markdown content using theme colors.
+    /// Code blocks are highlighted with `grammar_registry` when given.
+    pub fn markdown(
+        markdown_text: &str,
+        theme: &crate::view::theme::Theme,
+        grammar_registry: Option<&GrammarRegistry>,
+    ) -> Self {
+        let styled_lines = parse_markdown(markdown_text, theme, grammar_registry);
         Self {
             title: None,
             transient: false,
@@ -413,7 +919,7 @@ impl Popup {
         }
     }
 
-    /// Move selection down (for list popups)
+    /// Move selection down (for list popups), or scroll text down a line
     pub fn select_next(&mut self) {
         if let PopupContent::List { items, selected } = &mut self.content {
             if *selected < items.len().saturating_sub(1) {
@@ -423,10 +929,12 @@ impl Popup {
                     self.scroll_offset = (*selected + 1).saturating_sub(self.max_height as usize);
                 }
             }
+        } else {
+            self.scroll_by(1, self.max_inner_area());
         }
     }
 
-    /// Move selection up (for list popups)
+    /// Move selection up (for list popups), or scroll text up a line
     pub fn select_prev(&mut self) {
         if let PopupContent::List { items: _, selected } = &mut self.content {
             if *selected > 0 {
@@ -436,9 +944,37 @@ impl Popup {
                     self.scroll_offset = *selected;
                 }
             }
+        } else {
+            self.scroll_by(-1, self.max_inner_area());
         }
     }
 
+    /// Content area at the popup's full size
+    fn max_inner_area(&self) -> Rect {
+        let border = if self.bordered { 2 } else { 0 };
+        Rect::new(
+            0,
+            0,
+            self.width.saturating_sub(border),
+            self.max_height.saturating_sub(border),
+        )
+    }
+
+    /// The popup's content as plain text, one line per content line
+    pub fn plain_text(&self) -> String {
+        let lines: Vec<String> = match &self.content {
+            PopupContent::Text(lines) | PopupContent::Custom(lines) => lines.clone(),
+            PopupContent::Markdown(lines) => lines
+                .iter()
+                .map(|line| line.spans.iter().map(|span| span.text.as_str()).collect())
+                .collect(),
+            PopupContent::List { items, .. } => {
+                items.iter().map(|item| item.text.clone()).collect()
+            }
+        };
+        lines.join("\n")
+    }
+
     /// Scroll down by one page
     pub fn page_down(&mut self) {
         if let PopupContent::List { items, selected } = &mut self.content {
@@ -461,14 +997,64 @@ impl Popup {
         }
     }
 
-    /// Calculate the actual content height based on the popup content
-    fn content_height(&self) -> u16 {
-        let content_lines = match &self.content {
-            PopupContent::Text(lines) => lines.len() as u16,
-            PopupContent::Markdown(lines) => lines.len() as u16,
-            PopupContent::List { items, .. } => items.len() as u16,
-            PopupContent::Custom(lines) => lines.len() as u16,
+    /// Scroll the content by `delta` rows without changing the selection.
+    /// `inner_area` is the on-screen content area; its width determines how
+    /// lines wrap and its height stops scrolling once the last row is visible.
+    pub fn scroll_by(&mut self, delta: i32, inner_area: Rect) {
+        let max_offset = self
+            .line_count(inner_area.width)
+            .saturating_sub(inner_area.height as usize);
+        let offset = (self.scroll_offset as i64 + delta as i64).max(0) as usize;
+        self.scroll_offset = offset.min(max_offset);
+    }
+
+    /// Whether this popup can be moved and resized with the mouse.
+    /// Only persistent, bordered popups are draggable; transient ones (hover,
+    /// signature help) disappear as soon as focus moves.
+    pub fn is_draggable(&self) -> bool {
+        self.bordered && !self.transient
+    }
+
+    /// Text content as styled lines, word-wrapped to `width` columns.
+    /// Returns `None` for list content, which is never wrapped.
+    fn wrapped_lines(&self, width: u16) -> Option<Vec<StyledLine>> {
+        let plain = |lines: &Vec<String>| -> Vec<StyledLine> {
+            lines
+                .iter()
+                .map(|line| {
+                    let mut styled = StyledLine::new();
+                    styled.push(line.clone(), Style::default());
+                    styled
+                })
+                .collect()
         };
+        let lines = match &self.content {
+            PopupContent::Text(lines) | PopupContent::Custom(lines) => plain(lines),
+            PopupContent::Markdown(lines) => lines.clone(),
+            PopupContent::List { .. } => return None,
+        };
+        Some(
+            lines
+                .iter()
+                .flat_map(|line| wrap_styled_line(line, width as usize))
+                .collect(),
+        )
+    }
+
+    /// Number of content rows (or list items) when the content is `width` columns wide
+    fn line_count(&self, width: u16) -> usize {
+        match &self.content {
+            PopupContent::List { items, .. } => items.len(),
+            _ => self.wrapped_lines(width).map_or(0, |lines| lines.len()),
+        }
+    }
+
+    /// Calculate the actual content height for a popup `width` columns wide
+    fn content_height(&self, width: u16) -> u16 {
+        let border_width = if self.bordered { 2 } else { 0 };
+        let content_lines = self
+            .line_count(width.saturating_sub(border_width))
+            .min(u16::MAX as usize) as u16;
 
         // Add border lines if bordered
         let border_height = if self.bordered { 2 } else { 0 };
@@ -486,7 +1072,7 @@ impl Popup {
                 let width = self.width.min(terminal_area.width);
                 // Use the minimum of max_height, actual content height, and terminal height
                 let height = self
-                    .content_height()
+                    .content_height(width)
                     .min(self.max_height)
                     .min(terminal_area.height);
 
@@ -525,7 +1111,7 @@ impl Popup {
             PopupPosition::Fixed { x, y } => {
                 let width = self.width.min(terminal_area.width);
                 let height = self
-                    .content_height()
+                    .content_height(width)
                     .min(self.max_height)
                     .min(terminal_area.height);
                 // Clamp x and y to ensure popup stays within terminal bounds
@@ -549,7 +1135,7 @@ impl Popup {
             PopupPosition::Centered => {
                 let width = self.width.min(terminal_area.width);
                 let height = self
-                    .content_height()
+                    .content_height(width)
                     .min(self.max_height)
                     .min(terminal_area.height);
                 let x = (terminal_area.width.saturating_sub(width)) / 2;
@@ -608,21 +1194,15 @@ impl Popup {
         frame.render_widget(block, area);
 
         match &self.content {
-            PopupContent::Text(lines) => {
-                let visible_lines: Vec<Line> = lines
-                    .iter()
-                    .skip(self.scroll_offset)
-                    .take(inner_area.height as usize)
-                    .map(|line| Line::from(line.as_str()))
-                    .collect();
-
-                let paragraph = Paragraph::new(visible_lines);
-                frame.render_widget(paragraph, inner_area);
-            }
-            PopupContent::Markdown(styled_lines) => {
-                let visible_lines: Vec<Line> = styled_lines
+            PopupContent::Text(_) | PopupContent::Markdown(_) | PopupContent::Custom(_) => {
+                let wrapped = self.wrapped_lines(inner_area.width).unwrap_or_default();
+                // Clamp in case the popup was resized since it was last scrolled
+                let offset = self
+                    .scroll_offset
+                    .min(wrapped.len().saturating_sub(inner_area.height as usize));
+                let visible_lines: Vec<Line> = wrapped
                     .iter()
-                    .skip(self.scroll_offset)
+                    .skip(offset)
                     .take(inner_area.height as usize)
                     .map(|styled_line| {
                         let spans: Vec<Span> = styled_line
@@ -687,17 +1267,6 @@ impl Popup {
                 let list = List::new(list_items);
                 frame.render_widget(list, inner_area);
             }
-            PopupContent::Custom(lines) => {
-                let visible_lines: Vec<Line> = lines
-                    .iter()
-                    .skip(self.scroll_offset)
-                    .take(inner_area.height as usize)
-                    .map(|line| Line::from(line.as_str()))
-                    .collect();
-
-                let paragraph = Paragraph::new(visible_lines);
-                frame.render_widget(paragraph, inner_area);
-            }
         }
     }
 }
@@ -707,11 +1276,17 @@ impl Popup {
 pub struct PopupManager {
     /// Stack of active popups (top of stack = topmost popup)
     popups: Vec<Popup>,
+    /// Popups pinned in place, drawn below the stack; they don't take
+    /// input and stay open until closed with `close_pinned`
+    pinned: Vec<Popup>,
 }
 
 impl PopupManager {
     pub fn new() -> Self {
-        Self { popups: Vec::new() }
+        Self {
+            popups: Vec::new(),
+            pinned: Vec::new(),
+        }
     }
 
     /// Show a popup (adds to top of stack)
@@ -734,6 +1309,11 @@ impl PopupManager {
         self.popups.last()
     }
 
+    /// Get a popup by its index in the stack (mutable)
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Popup> {
+        self.popups.get_mut(index)
+    }
+
     /// Get mutable reference to topmost popup
     pub fn top_mut(&mut self) -> Option<&mut Popup> {
         self.popups.last_mut()
@@ -749,6 +1329,29 @@ impl PopupManager {
         &self.popups
     }
 
+    /// Pin the topmost popup at `position`. Returns false if there is none.
+    pub fn pin_top(&mut self, position: PopupPosition) -> bool {
+        let Some(mut popup) = self.popups.pop() else {
+            return false;
+        };
+        popup.transient = false;
+        popup.position = position;
+        self.pinned.push(popup);
+        true
+    }
+
+    /// Pinned popups, oldest first (for rendering)
+    pub fn pinned(&self) -> &[Popup] {
+        &self.pinned
+    }
+
+    /// Close all pinned popups, returning how many there were
+    pub fn close_pinned(&mut self) -> usize {
+        let count = self.pinned.len();
+        self.pinned.clear();
+        count
+    }
+
     /// Dismiss transient popups if present at the top.
     /// These popups should be dismissed when the buffer loses focus.
     /// Returns true if a popup was dismissed.
@@ -774,6 +1377,112 @@ impl Default for PopupManager {
 mod tests {
     use super::*;
 
+    /// Plain text of each rendered markdown line
+    fn markdown_lines(text: &str) -> Vec<String> {
+        let theme = crate::view::theme::Theme::dark();
+        parse_markdown(text, &theme, None)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_markdown_ordered_list_numbering() {
+        let lines = markdown_lines("3. three\n4. four\n5. five");
+        assert_eq!(lines, vec!["3. three", "4. four", "5. five"]);
+    }
+
+    #[test]
+    fn test_markdown_nested_list_indentation() {
+        let lines = markdown_lines("- outer\n  - inner\n    - innermost\n- second");
+        assert_eq!(
+            lines,
+            vec!["• outer", "  ◦ inner", "    ▪ innermost", "• second"]
+        );
+    }
+
+    #[test]
+    fn test_markdown_list_continuation_indent() {
+        let lines = markdown_lines("1. first\n\n   more text\n2. second");
+        assert_eq!(lines[0], "1. first");
+        assert!(lines.contains(&"   more text".to_string()));
+        assert!(lines.contains(&"2. second".to_string()));
+    }
+
+    #[test]
+    fn test_markdown_blockquote() {
+        let lines = markdown_lines("> quoted\n> > nested\n\nafter");
+        assert_eq!(lines[0], "│ quoted");
+        assert!(lines.contains(&"│ │ nested".to_string()));
+        assert_eq!(lines.last().unwrap(), "after");
+    }
+
+    #[test]
+    fn test_markdown_table_alignment() {
+        let lines = markdown_lines(
+            "| Name | Size | Kind |\n|:-----|-----:|:----:|\n| a | 1 | x |\n| long | 200 | yy |",
+        );
+        assert_eq!(
+            lines,
+            vec![
+                " Name │ Size │ Kind ",
+                "──────┼──────┼──────",
+                " a    │    1 │  x   ",
+                " long │  200 │  yy  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_table_header_style() {
+        let theme = crate::view::theme::Theme::dark();
+        let lines = parse_markdown("| H |\n|---|\n| v |", &theme, None);
+        let header = lines[0].spans.iter().find(|s| s.text == "H").unwrap();
+        assert!(header.style.add_modifier.contains(Modifier::BOLD));
+        let body = lines[2].spans.iter().find(|s| s.text == "v").unwrap();
+        assert!(!body.style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_markdown_code_block_highlighting() {
+        let theme = crate::view::theme::Theme::dark();
+        let registry = GrammarRegistry::load();
+        let text = "```rust\nlet x = 1; // note\n```";
+
+        let lines = parse_markdown(text, &theme, Some(&registry));
+        assert!(lines[0].spans.len() > 1);
+        let comment = lines[0]
+            .spans
+            .iter()
+            .find(|s| s.text.contains("note"))
+            .unwrap();
+        assert_eq!(comment.style.fg, Some(theme.syntax_comment));
+        assert_eq!(comment.style.bg, Some(theme.inline_code_bg));
+
+        // Without a registry the block keeps a single flat style
+        let lines = parse_markdown(text, &theme, None);
+        assert_eq!(lines[0].spans.len(), 1);
+        assert_eq!(lines[0].spans[0].text, "let x = 1; // note");
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme.help_key_fg));
+    }
+
+    #[test]
+    fn test_markdown_code_block_unknown_language() {
+        let theme = crate::view::theme::Theme::dark();
+        let registry = GrammarRegistry::load();
+        let lines = parse_markdown(
+            "```nosuchlang\nline one\n\nline three\n```",
+            &theme,
+            Some(&registry),
+        );
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.text.as_str()).collect())
+            .collect();
+        assert_eq!(text, vec!["line one", "", "line three"]);
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme.help_key_fg));
+    }
+
     #[test]
     fn test_popup_list_item() {
         let item = PopupListItem::new("test".to_string())
@@ -967,4 +1676,174 @@ mod tests {
         assert_eq!(clamped.width, 1); // width clamped to fit
         assert_eq!(clamped.height, 1); // height clamped to fit
     }
+
+    #[test]
+    fn test_popup_scroll_by_clamps() {
+        let theme = crate::view::theme::Theme::dark();
+        let lines: Vec<String> = (0..10).map(|i| format!("line {}", i)).collect();
+        let mut popup = Popup::text(lines, &theme);
+
+        // 10 lines, 4 visible: offset can range from 0 to 6
+        popup.scroll_by(3, Rect::new(0, 0, 40, 4));
+        assert_eq!(popup.scroll_offset, 3);
+        popup.scroll_by(30, Rect::new(0, 0, 40, 4));
+        assert_eq!(popup.scroll_offset, 6);
+        popup.scroll_by(-4, Rect::new(0, 0, 40, 4));
+        assert_eq!(popup.scroll_offset, 2);
+        popup.scroll_by(-30, Rect::new(0, 0, 40, 4));
+        assert_eq!(popup.scroll_offset, 0);
+
+        // Content that fits entirely never scrolls
+        popup.scroll_by(3, Rect::new(0, 0, 40, 20));
+        assert_eq!(popup.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_popup_scroll_by_keeps_selection() {
+        let theme = crate::view::theme::Theme::dark();
+        let items = (0..10)
+            .map(|i| PopupListItem::new(format!("item{}", i)))
+            .collect();
+        let mut popup = Popup::list(items, &theme);
+
+        popup.scroll_by(3, Rect::new(0, 0, 40, 5));
+        assert_eq!(popup.scroll_offset, 3);
+        assert_eq!(popup.selected_item().unwrap().text, "item0");
+    }
+
+    #[test]
+    fn test_popup_scroll_by_counts_wrapped_rows() {
+        let theme = crate::view::theme::Theme::dark();
+        // Each line wraps onto two rows at width 10
+        let lines: Vec<String> = (0..3).map(|i| format!("word{} and more", i)).collect();
+        let mut popup = Popup::text(lines, &theme);
+
+        // 6 wrapped rows, 4 visible: offset can range from 0 to 2
+        popup.scroll_by(30, Rect::new(0, 0, 10, 4));
+        assert_eq!(popup.scroll_offset, 2);
+
+        // Wide enough for every line to fit: nothing to scroll
+        popup.scroll_by(30, Rect::new(0, 0, 40, 4));
+        assert_eq!(popup.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_popup_height_includes_wrapped_rows() {
+        let theme = crate::view::theme::Theme::dark();
+        let popup = Popup::text(vec!["aaaa bbbb cccc".to_string()], &theme)
+            .with_position(PopupPosition::Fixed { x: 0, y: 0 })
+            .with_width(8)
+            .with_max_height(10);
+
+        // Inner width 6 fits one word per row, plus two border rows
+        let area = popup.calculate_area(Rect::new(0, 0, 80, 24), None);
+        assert_eq!(area.height, 5);
+    }
+
+    fn wrap_text(text: &str, width: usize) -> Vec<String> {
+        let mut line = StyledLine::new();
+        line.push(text.to_string(), Style::default());
+        wrap_styled_line(&line, width)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_styled_line_fits() {
+        assert_eq!(wrap_text("short line", 20), vec!["short line"]);
+        assert_eq!(wrap_text("", 20), vec![""]);
+    }
+
+    #[test]
+    fn test_wrap_styled_line_breaks_at_words() {
+        assert_eq!(
+            wrap_text("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_styled_line_splits_long_words() {
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_wrap_styled_line_hanging_indent() {
+        assert_eq!(
+            wrap_text("  • alpha beta gamma", 12),
+            vec!["  • alpha", "    beta", "    gamma"]
+        );
+        assert_eq!(
+            wrap_text("12. alpha beta", 10),
+            vec!["12. alpha", "    beta"]
+        );
+        assert_eq!(
+            wrap_text("│ quoted text here", 10),
+            vec!["│ quoted", "│ text", "│ here"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_styled_line_keeps_span_styles() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut line = StyledLine::new();
+        line.push("plain words ".to_string(), Style::default());
+        line.push("bold words".to_string(), bold);
+
+        let wrapped = wrap_styled_line(&line, 12);
+        assert_eq!(wrapped.len(), 2);
+        let last = &wrapped[1];
+        assert!(last.spans.iter().all(|s| s.style == bold));
+    }
+
+    #[test]
+    fn test_popup_is_draggable() {
+        let theme = crate::view::theme::Theme::dark();
+        let popup = Popup::text(vec!["docs".to_string()], &theme);
+        assert!(popup.is_draggable());
+        assert!(!popup.clone().with_transient(true).is_draggable());
+
+        let mut borderless = popup;
+        borderless.bordered = false;
+        assert!(!borderless.is_draggable());
+    }
+
+    #[test]
+    fn test_pinned_popups() {
+        let theme = crate::view::theme::Theme::dark();
+        let mut manager = PopupManager::new();
+        manager.show(Popup::text(vec!["docs".to_string()], &theme).with_transient(true));
+
+        assert!(manager.pin_top(PopupPosition::Fixed { x: 5, y: 3 }));
+        // Pinned popups don't take input and survive clearing the stack
+        assert!(!manager.is_visible());
+        manager.clear();
+        assert_eq!(manager.pinned().len(), 1);
+        let pinned = &manager.pinned()[0];
+        assert!(!pinned.transient);
+        assert_eq!(pinned.position, PopupPosition::Fixed { x: 5, y: 3 });
+        assert_eq!(pinned.plain_text(), "docs");
+
+        assert!(!manager.pin_top(PopupPosition::Centered));
+        assert_eq!(manager.close_pinned(), 1);
+        assert!(manager.pinned().is_empty());
+    }
+
+    #[test]
+    fn test_text_popup_scrolls_by_line() {
+        let theme = crate::view::theme::Theme::dark();
+        let lines = (0..30).map(|i| format!("line {}", i)).collect();
+        let mut popup = Popup::text(lines, &theme);
+        popup.select_next();
+        popup.select_next();
+        assert_eq!(popup.scroll_offset, 2);
+        popup.select_prev();
+        assert_eq!(popup.scroll_offset, 1);
+        for _ in 0..100 {
+            popup.select_next();
+        }
+        // Stops with the last line at the bottom: 30 lines, 13 rows inside the border
+        assert_eq!(popup.scroll_offset, 17);
+    }
 }
@@ -3,9 +3,10 @@
 //! Implements the InputHandler trait for PopupManager, handling
 //! selection navigation and confirmation/cancellation.
 
-use super::popup::PopupManager;
+use super::popup::{Popup, PopupContent, PopupManager};
 use crate::input::handler::{DeferredAction, InputContext, InputHandler, InputResult};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::input::keybindings::Action;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 impl InputHandler for PopupManager {
     fn handle_key_event(&mut self, event: &KeyEvent, ctx: &mut InputContext) -> InputResult {
@@ -65,6 +66,22 @@ impl InputHandler for PopupManager {
                 InputResult::Consumed
             }
 
+            // Text popups (a focused hover) can be copied and pinned
+            KeyCode::Char('y') if event.modifiers.is_empty() && self.top().is_some_and(is_text) => {
+                ctx.defer(DeferredAction::ExecuteAction(Action::PopupCopy));
+                InputResult::Consumed
+            }
+            KeyCode::Char('c')
+                if event.modifiers == KeyModifiers::CONTROL && self.top().is_some_and(is_text) =>
+            {
+                ctx.defer(DeferredAction::ExecuteAction(Action::PopupCopy));
+                InputResult::Consumed
+            }
+            KeyCode::Char('p') if event.modifiers.is_empty() && self.top().is_some_and(is_text) => {
+                ctx.defer(DeferredAction::ExecuteAction(Action::PopupPin));
+                InputResult::Consumed
+            }
+
             // Consume all other keys (modal behavior)
             _ => InputResult::Consumed,
         }
@@ -75,6 +92,11 @@ impl InputHandler for PopupManager {
     }
 }
 
+/// Whether `popup` shows text rather than a list of items
+fn is_text(popup: &Popup) -> bool {
+    !matches!(popup.content, PopupContent::List { .. })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +188,29 @@ mod tests {
             .any(|a| matches!(a, DeferredAction::ClosePopup)));
     }
 
+    #[test]
+    fn test_text_popup_copy_and_pin() {
+        let mut manager = PopupManager::new();
+        manager.show(Popup::text(vec!["docs".to_string()], &Theme::dark()));
+        let mut ctx = InputContext::new();
+
+        manager.handle_key_event(&key(KeyCode::Char('y')), &mut ctx);
+        manager.handle_key_event(&key(KeyCode::Char('p')), &mut ctx);
+        assert!(matches!(
+            ctx.deferred_actions.as_slice(),
+            [
+                DeferredAction::ExecuteAction(Action::PopupCopy),
+                DeferredAction::ExecuteAction(Action::PopupPin)
+            ]
+        ));
+
+        // List popups just consume the keys
+        let mut manager = create_popup_with_items(3);
+        let mut ctx = InputContext::new();
+        manager.handle_key_event(&key(KeyCode::Char('p')), &mut ctx);
+        assert!(ctx.deferred_actions.is_empty());
+    }
+
     #[test]
     fn test_popup_is_modal_when_visible() {
         let mut manager = PopupManager::new();
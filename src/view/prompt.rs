@@ -12,6 +12,10 @@ pub enum PromptType {
     OpenFile,
     /// Switch to a different project folder (change working directory)
     SwitchProject,
+    /// Add a folder to the workspace
+    AddWorkspaceFolder,
+    /// Remove a folder from the workspace (select from list)
+    RemoveWorkspaceFolder,
     /// Save current buffer to a new file
     SaveFileAs,
     /// Search for text in buffer
@@ -45,6 +49,8 @@ pub enum PromptType {
         end_pos: usize,
         overlay_handle: crate::view::overlay::OverlayHandle,
     },
+    /// Project-wide textual rename of `original` (when no LSP is running)
+    RenameSymbolText { original: String },
     /// Record a macro - prompts for register (0-9)
     RecordMacro,
     /// Play a macro - prompts for register (0-9)
@@ -59,6 +65,8 @@ pub enum PromptType {
     SetTabSize,
     /// Set line ending format for current buffer
     SetLineEnding,
+    /// Set indentation style and width for current buffer
+    SetIndentation,
     /// Stop a running LSP server (select from list)
     StopLspServer,
     /// Select a theme (select from list)
@@ -71,6 +79,10 @@ pub enum PromptType {
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
     ConfirmSaveConflict,
+    /// Confirm retrying a save that was denied with root privileges
+    ConfirmSaveAsRoot,
+    /// Password for saving as root (input is masked and never kept in history)
+    SudoPassword,
     /// Confirm overwriting an existing file during SaveAs
     ConfirmOverwriteFile { path: std::path::PathBuf },
     /// Confirm closing a modified buffer (save/discard/cancel)
@@ -78,14 +90,20 @@ pub enum PromptType {
     ConfirmCloseBuffer {
         buffer_id: crate::model::event::BufferId,
     },
-    /// Confirm quitting with modified buffers
-    ConfirmQuitWithModified,
+    /// Confirm switching project with modified buffers
+    ConfirmSwitchProject { path: std::path::PathBuf },
     /// File Explorer rename operation
     /// Stores the original path and name for the file/directory being renamed
     FileExplorerRename {
         original_path: std::path::PathBuf,
         original_name: String,
     },
+    /// Rename or move the active buffer's file
+    RenameFile,
+    /// Copy the active buffer's file to a new path
+    DuplicateFile,
+    /// Confirm moving the active buffer's file to the trash
+    ConfirmTrashFile,
     /// Confirm deleting a file or directory in the file explorer
     ConfirmDeleteFile {
         path: std::path::PathBuf,
@@ -97,6 +115,61 @@ pub enum PromptType {
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
     ShellCommand { replace: bool },
+    /// Shell command to run in the terminal the editor was started from
+    RunShellCommand,
+    /// Confirm inserting the output of a command run in the terminal
+    ConfirmInsertCommandOutput { output: String },
+    /// Confirm showing a hex view of a binary file that was just opened
+    ConfirmHexView { path: std::path::PathBuf },
+    /// Confirm opening files dropped on the terminal instead of pasting
+    /// their paths (`text`)
+    ConfirmOpenDroppedFiles {
+        paths: Vec<std::path::PathBuf>,
+        text: String,
+    },
+    /// Confirm letting a plugin make HTTP requests
+    ConfirmPluginNetworkAccess { plugin: String },
+    /// Confirm opening the report of a crash of the previous run, and
+    /// restoring the session if `restore_session`
+    ConfirmCrashReport {
+        report: std::path::PathBuf,
+        restore_session: bool,
+    },
+    /// Choose which features to keep for a file over the size limits
+    /// (select from list)
+    LimitFileFeatures {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Install a grammar package (.vsix URL, GitHub repo, or marketplace ID)
+    InstallGrammar,
+    /// Select the syntax for the current buffer (select from list)
+    SelectLanguage,
+    /// Choose a configured task to run (select from list)
+    RunTask,
+    /// Local git branch to check out (select from list)
+    GitCheckoutBranch,
+    /// Commit of a file to open, or compare with the buffer when `diff` is
+    /// set (select from list)
+    FileRevision {
+        path: std::path::PathBuf,
+        diff: bool,
+    },
+    /// Language of a new scratch buffer (select from list)
+    NewScratchBuffer,
+    /// Existing scratch buffer to reopen (select from list)
+    OpenScratchBuffer,
+    /// File to export the current buffer to
+    ExportBuffer {
+        format: crate::services::styled_html::ExportFormat,
+    },
+    /// Command of a REPL to attach the active buffer to
+    StartRepl,
+    /// Delimiter to align the selected lines on
+    AlignOn,
+    /// Address to share the active buffer on
+    CollabShare,
+    /// Address of a shared buffer to join
+    CollabJoin,
 }
 
 /// Prompt state for the minibuffer
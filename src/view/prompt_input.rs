@@ -204,7 +204,11 @@ impl InputHandler for Prompt {
                 InputResult::Consumed
             }
 
-            _ => InputResult::Consumed, // Modal - consume all unhandled keys
+            // Modal - consume all unhandled keys
+            _ => {
+                ctx.defer(DeferredAction::Reject);
+                InputResult::Consumed
+            }
         }
     }
 
@@ -249,7 +253,10 @@ impl Prompt {
                 ctx.defer(DeferredAction::UpdatePromptSuggestions);
                 InputResult::Consumed
             }
-            _ => InputResult::Consumed,
+            _ => {
+                ctx.defer(DeferredAction::Reject);
+                InputResult::Consumed
+            }
         }
     }
 }
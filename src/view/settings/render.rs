@@ -18,6 +18,11 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
+/// Smallest modal the settings layout fits in: the footer buttons need
+/// about 36 columns, and the footer, its separator and the borders 4 rows
+const MIN_MODAL_WIDTH: u16 = 40;
+const MIN_MODAL_HEIGHT: u16 = 8;
+
 /// Build spans for a text line with selection highlighting
 ///
 /// Returns a vector of spans where selected portions are highlighted.
@@ -118,6 +123,16 @@ pub fn render_settings(
     // Clear the modal area and draw border
     frame.render_widget(Clear, modal_area);
 
+    if modal_width < MIN_MODAL_WIDTH || modal_height < MIN_MODAL_HEIGHT {
+        frame.render_widget(
+            Paragraph::new("Window too small for settings")
+                .style(Style::default().fg(theme.popup_text_fg).bg(theme.popup_bg))
+                .wrap(ratatui::widgets::Wrap { trim: true }),
+            modal_area,
+        );
+        return SettingsLayout::new(modal_area);
+    }
+
     let title = if state.has_changes() {
         " Settings • (modified) "
     } else {
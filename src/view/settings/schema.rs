@@ -269,6 +269,27 @@ pub fn parse_schema(schema_json: &str) -> Result<Vec<SettingCategory>, serde_jso
     Ok(categories)
 }
 
+/// Parse the settings a plugin declared into a category of their own.
+///
+/// `settings` maps each setting's key to a JSON Schema property (`type`,
+/// `default`, `description`, `enum`, `minimum`, `maximum`). Values live under
+/// `/plugins/<namespace>` in the config.
+pub fn parse_plugin_settings(
+    namespace: &str,
+    settings: &serde_json::Value,
+) -> Result<SettingCategory, serde_json::Error> {
+    let properties: HashMap<String, RawSchema> = serde_json::from_value(settings.clone())?;
+    let path = format!("/plugins/{}", namespace);
+    let settings = parse_properties(&properties, &path, &HashMap::new(), &HashMap::new());
+    Ok(SettingCategory {
+        name: format!("Plugin: {}", humanize_name(namespace)),
+        path,
+        description: Some(format!("Settings of the {} plugin", namespace)),
+        settings,
+        subcategories: Vec::new(),
+    })
+}
+
 /// Build a map from $ref paths to their enum options
 fn build_enum_values_map(entries: &[EnumValueEntry]) -> EnumValuesMap {
     let mut map: EnumValuesMap = HashMap::new();
@@ -584,6 +605,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_plugin_settings_category() {
+        let settings = serde_json::json!({
+            "keywords": {
+                "description": "Words to highlight",
+                "type": "array",
+                "items": { "type": "string" },
+                "default": ["TODO"]
+            },
+            "style": { "type": "string", "enum": ["bold", "underline"] }
+        });
+        let category = parse_plugin_settings("todo_highlighter", &settings).unwrap();
+
+        assert_eq!(category.name, "Plugin: Todo Highlighter");
+        assert_eq!(category.path, "/plugins/todo_highlighter");
+        assert_eq!(
+            category.settings[0].path,
+            "/plugins/todo_highlighter/keywords"
+        );
+        assert!(matches!(
+            category.settings[0].setting_type,
+            SettingType::StringArray
+        ));
+        assert!(matches!(
+            category.settings[1].setting_type,
+            SettingType::Enum { .. }
+        ));
+        assert!(parse_plugin_settings("bad", &serde_json::json!([1])).is_err());
+    }
+
     #[test]
     fn test_humanize_name() {
         assert_eq!(humanize_name("tab_size"), "Tab Size");
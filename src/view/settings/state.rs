@@ -6,7 +6,7 @@
 use super::entry_dialog::EntryDialogState;
 use super::items::{control_to_value, SettingControl, SettingItem, SettingsPage};
 use super::layout::SettingsHit;
-use super::schema::{parse_schema, SettingCategory, SettingSchema};
+use super::schema::{parse_plugin_settings, parse_schema, SettingCategory, SettingSchema};
 use super::search::{search_settings, SearchResult};
 use crate::config::Config;
 use crate::view::controls::FocusState;
@@ -126,6 +126,21 @@ impl SettingsState {
         })
     }
 
+    /// Add a category for the settings a plugin declared
+    pub fn add_plugin_settings(
+        &mut self,
+        namespace: &str,
+        settings: &serde_json::Value,
+    ) -> Result<(), serde_json::Error> {
+        let category = parse_plugin_settings(namespace, settings)?;
+        self.pages.push(
+            super::items::build_pages(std::slice::from_ref(&category), &self.original_config)
+                .remove(0),
+        );
+        self.categories.push(category);
+        Ok(())
+    }
+
     /// Show the settings panel
     pub fn show(&mut self) {
         self.visible = true;
@@ -359,6 +374,9 @@ impl SettingsState {
         for (path, value) in &self.pending_changes {
             if let Some(target) = config_value.pointer_mut(path) {
                 *target = value.clone();
+            } else if path.starts_with("/plugins/") {
+                // Plugin settings are only in the config once they are set
+                set_missing_value(&mut config_value, path, value.clone());
             }
         }
 
@@ -1437,6 +1455,20 @@ impl SettingsState {
     }
 }
 
+/// Set the value at a JSON pointer `path`, creating the objects on the way
+fn set_missing_value(root: &mut serde_json::Value, path: &str, value: serde_json::Value) {
+    let mut target = root;
+    for key in path.trim_start_matches('/').split('/') {
+        let Some(object) = target.as_object_mut() else {
+            return;
+        };
+        target = object
+            .entry(key.to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+    }
+    *target = value;
+}
+
 /// Update a control's state from a JSON value
 fn update_control_from_value(control: &mut SettingControl, value: &serde_json::Value) {
     match control {
@@ -1801,4 +1833,24 @@ mod tests {
 
         state.number_cancel();
     }
+
+    #[test]
+    fn test_plugin_settings_are_applied_to_config() {
+        let config = test_config();
+        let mut state = SettingsState::new(TEST_SCHEMA, &config).unwrap();
+        state
+            .add_plugin_settings(
+                "todo",
+                &serde_json::json!({ "enabled": { "type": "boolean", "default": true } }),
+            )
+            .unwrap();
+        assert_eq!(state.pages.last().unwrap().name, "Plugin: Todo");
+
+        state.set_pending_change("/plugins/todo/enabled", serde_json::json!(false));
+        let new_config = state.apply_changes(&config).unwrap();
+        assert_eq!(
+            new_config.plugins["todo"].get("enabled"),
+            Some(&serde_json::json!(false))
+        );
+    }
 }
@@ -30,6 +30,7 @@ use crate::view::viewport::Viewport;
 use crate::{services::plugins::api::ViewTransformPayload, state::ViewMode};
 use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// A node in the split tree
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -78,6 +79,10 @@ pub struct SplitViewState {
     /// The currently displayed buffer is tracked in the SplitNode::Leaf
     pub open_buffers: Vec<BufferId>,
 
+    /// Pinned tabs, which stay at the front of `open_buffers` and are left
+    /// open by "Close Others" and "Close to the Right"
+    pub pinned_buffers: Vec<BufferId>,
+
     /// Horizontal scroll offset for the tabs in this split
     pub tab_scroll_offset: usize,
 
@@ -105,6 +110,10 @@ pub struct SplitViewState {
 
     /// Previously active buffer in this split (for "Switch to Previous Tab" command)
     pub previous_buffer: Option<BufferId>,
+
+    /// Working directory of this split, if it has its own rather than the
+    /// project's (used by its terminals, the file finder and tasks)
+    pub working_dir: Option<PathBuf>,
 }
 
 impl SplitViewState {
@@ -114,6 +123,7 @@ impl SplitViewState {
             cursors: Cursors::new(),
             viewport: Viewport::new(width, height),
             open_buffers: Vec::new(),
+            pinned_buffers: Vec::new(),
             tab_scroll_offset: 0,
             view_mode: ViewMode::Source,
             compose_width: None,
@@ -123,6 +133,7 @@ impl SplitViewState {
             layout: None,
             layout_dirty: true, // Start dirty so first operation builds layout
             previous_buffer: None,
+            working_dir: None,
         }
     }
 
@@ -132,6 +143,7 @@ impl SplitViewState {
             cursors: Cursors::new(),
             viewport: Viewport::new(width, height),
             open_buffers: vec![buffer_id],
+            pinned_buffers: Vec::new(),
             tab_scroll_offset: 0,
             view_mode: ViewMode::Source,
             compose_width: None,
@@ -141,6 +153,7 @@ impl SplitViewState {
             layout: None,
             layout_dirty: true, // Start dirty so first operation builds layout
             previous_buffer: None,
+            working_dir: None,
         }
     }
 
@@ -188,6 +201,35 @@ impl SplitViewState {
     /// Remove a buffer from this split's tabs
     pub fn remove_buffer(&mut self, buffer_id: BufferId) {
         self.open_buffers.retain(|&id| id != buffer_id);
+        self.pinned_buffers.retain(|&id| id != buffer_id);
+    }
+
+    /// Check if a tab is pinned in this split
+    pub fn is_pinned(&self, buffer_id: BufferId) -> bool {
+        self.pinned_buffers.contains(&buffer_id)
+    }
+
+    /// Pin or unpin a tab, moving it to the end of the pinned tabs at the
+    /// front of the tab bar. Returns whether the tab is now pinned.
+    pub fn toggle_pin(&mut self, buffer_id: BufferId) -> bool {
+        if !self.has_buffer(buffer_id) {
+            return false;
+        }
+        let pinned = if self.is_pinned(buffer_id) {
+            self.pinned_buffers.retain(|&id| id != buffer_id);
+            false
+        } else {
+            self.pinned_buffers.push(buffer_id);
+            true
+        };
+        self.open_buffers.retain(|&id| id != buffer_id);
+        let index = self
+            .open_buffers
+            .iter()
+            .take_while(|id| self.pinned_buffers.contains(id))
+            .count();
+        self.open_buffers.insert(index, buffer_id);
+        pinned
     }
 
     /// Check if a buffer is open in this split
@@ -317,8 +359,11 @@ impl SplitNode {
                 let (first_rect, second_rect) = split_rect(rect, *direction, *ratio);
                 let mut separators = Vec::new();
 
-                // Add separator for this split (in the 1-char gap between first and second)
+                // Add separator for this split (in the 1-char gap between first and second),
+                // unless the split is too small to have a gap
                 match direction {
+                    SplitDirection::Horizontal if rect.height == 0 => {}
+                    SplitDirection::Vertical if rect.width == 0 => {}
                     SplitDirection::Horizontal => {
                         // Horizontal split: separator line is between first and second
                         // y position is at the end of first rect (the gap line)
@@ -386,7 +431,8 @@ impl SplitNode {
 }
 
 /// Split a rectangle into two parts based on direction and ratio
-/// Leaves 1 character space for the separator line between splits
+/// Leaves 1 character space for the separator line between splits.
+/// Both parts stay inside `rect`, even when it is too small to hold them.
 fn split_rect(rect: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect) {
     match direction {
         SplitDirection::Horizontal => {
@@ -404,7 +450,7 @@ fn split_rect(rect: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect)
 
             let second = Rect {
                 x: rect.x,
-                y: rect.y + first_height + 1, // +1 for separator
+                y: rect.y + (first_height + 1).min(rect.height), // +1 for separator
                 width: rect.width,
                 height: second_height,
             };
@@ -425,7 +471,7 @@ fn split_rect(rect: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect)
             };
 
             let second = Rect {
-                x: rect.x + first_width + 1, // +1 for separator
+                x: rect.x + (first_width + 1).min(rect.width), // +1 for separator
                 y: rect.y,
                 width: second_width,
                 height: rect.height,
@@ -1008,4 +1054,42 @@ mod tests {
         assert_eq!(first.x, 0);
         assert_eq!(second.x, 51); // first.x + first.width + 1 (separator)
     }
+
+    #[test]
+    fn test_split_rect_stays_inside_tiny_rect() {
+        for (width, height) in [(0, 0), (1, 1), (2, 2), (3, 0)] {
+            let rect = Rect::new(10, 10, width, height);
+            for direction in [SplitDirection::Horizontal, SplitDirection::Vertical] {
+                let (first, second) = split_rect(rect, direction, 0.9);
+                for part in [first, second] {
+                    assert!(part.x >= rect.x && part.right() <= rect.right());
+                    assert!(part.y >= rect.y && part.bottom() <= rect.bottom());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pinned_tabs_stay_at_front() {
+        let mut state = SplitViewState::with_buffer(80, 24, BufferId(0));
+        state.add_buffer(BufferId(1));
+        state.add_buffer(BufferId(2));
+
+        assert!(state.toggle_pin(BufferId(2)));
+        assert!(state.toggle_pin(BufferId(1)));
+        assert_eq!(
+            state.open_buffers,
+            vec![BufferId(2), BufferId(1), BufferId(0)]
+        );
+
+        // Unpinning moves the tab just after the pinned ones
+        assert!(!state.toggle_pin(BufferId(2)));
+        assert_eq!(
+            state.open_buffers,
+            vec![BufferId(1), BufferId(2), BufferId(0)]
+        );
+
+        state.remove_buffer(BufferId(1));
+        assert!(!state.is_pinned(BufferId(1)));
+    }
 }
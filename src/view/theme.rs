@@ -1,6 +1,15 @@
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global flag to ignore the user's theme files (set by `--safe-mode`)
+static SKIP_USER_THEMES: AtomicBool = AtomicBool::new(false);
+
+/// Load themes only from the builtin themes, ignoring the user themes directory
+pub fn set_skip_user_themes(skip: bool) {
+    SKIP_USER_THEMES.store(skip, Ordering::SeqCst);
+}
 
 /// Serializable color representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,11 +72,26 @@ struct EditorColors {
     current_line_bg: ColorDef,
     line_number_fg: ColorDef,
     line_number_bg: ColorDef,
+    #[serde(default = "default_indent_guide_fg")]
+    indent_guide_fg: ColorDef,
+    #[serde(default = "default_indent_guide_active_fg")]
+    indent_guide_active_fg: ColorDef,
+    #[serde(default = "default_ruler_bg")]
+    ruler_bg: ColorDef,
 }
 
 fn default_inactive_cursor() -> ColorDef {
     ColorDef::Named("DarkGray".to_string())
 }
+fn default_indent_guide_fg() -> ColorDef {
+    ColorDef::Named("DarkGray".to_string())
+}
+fn default_indent_guide_active_fg() -> ColorDef {
+    ColorDef::Named("Gray".to_string())
+}
+fn default_ruler_bg() -> ColorDef {
+    ColorDef::Rgb(48, 48, 48)
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct UiColors {
@@ -277,6 +301,9 @@ pub struct Theme {
     pub current_line_bg: Color,
     pub line_number_fg: Color,
     pub line_number_bg: Color,
+    pub indent_guide_fg: Color,
+    pub indent_guide_active_fg: Color,
+    pub ruler_bg: Color,
 
     // UI element colors
     pub tab_active_fg: Color,
@@ -385,6 +412,9 @@ impl From<ThemeFile> for Theme {
             current_line_bg: file.editor.current_line_bg.into(),
             line_number_fg: file.editor.line_number_fg.into(),
             line_number_bg: file.editor.line_number_bg.into(),
+            indent_guide_fg: file.editor.indent_guide_fg.into(),
+            indent_guide_active_fg: file.editor.indent_guide_active_fg.into(),
+            ruler_bg: file.editor.ruler_bg.into(),
             tab_active_fg: file.ui.tab_active_fg.into(),
             tab_active_bg: file.ui.tab_active_bg.into(),
             tab_inactive_fg: file.ui.tab_inactive_fg.into(),
@@ -467,6 +497,24 @@ impl Theme {
         Ok(theme_file.into())
     }
 
+    /// Check that `content` is a valid theme file
+    pub fn check_json(content: &str) -> Result<(), serde_json::Error> {
+        serde_json::from_str::<ThemeFile>(content).map(|_| ())
+    }
+
+    /// Directory of the user's theme files
+    pub fn user_themes_dir() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("fresh").join("themes"))
+    }
+
+    /// [`Self::user_themes_dir`], unless user themes are skipped
+    fn loadable_user_themes_dir() -> Option<std::path::PathBuf> {
+        if SKIP_USER_THEMES.load(Ordering::SeqCst) {
+            return None;
+        }
+        Self::user_themes_dir()
+    }
+
     /// Load builtin theme from the themes directory
     fn load_builtin_theme(name: &str) -> Option<Self> {
         // Build list of paths to search
@@ -477,11 +525,8 @@ impl Theme {
         ];
 
         // Also check user config themes directory
-        if let Some(config_dir) = dirs::config_dir() {
-            let user_theme_path = config_dir
-                .join("fresh")
-                .join("themes")
-                .join(format!("{}.json", name));
+        if let Some(user_themes_dir) = Self::loadable_user_themes_dir() {
+            let user_theme_path = user_themes_dir.join(format!("{}.json", name));
             theme_paths.insert(0, user_theme_path.to_string_lossy().to_string());
         }
 
@@ -509,6 +554,9 @@ impl Theme {
             current_line_bg: Color::Rgb(40, 40, 40),
             line_number_fg: Color::Rgb(100, 100, 100),
             line_number_bg: Color::Rgb(30, 30, 30),
+            indent_guide_fg: Color::Rgb(64, 64, 64),
+            indent_guide_active_fg: Color::Rgb(112, 112, 112),
+            ruler_bg: Color::Rgb(48, 48, 48),
 
             // UI element colors
             tab_active_fg: Color::Yellow,
@@ -619,6 +667,9 @@ impl Theme {
             current_line_bg: Color::Rgb(245, 245, 245),
             line_number_fg: Color::Rgb(140, 140, 140),
             line_number_bg: Color::Rgb(255, 255, 255),
+            indent_guide_fg: Color::Rgb(211, 211, 211),
+            indent_guide_active_fg: Color::Rgb(147, 147, 147),
+            ruler_bg: Color::Rgb(235, 235, 235),
 
             // UI element colors
             tab_active_fg: Color::Rgb(40, 40, 40),
@@ -729,6 +780,9 @@ impl Theme {
             current_line_bg: Color::Rgb(20, 20, 20),
             line_number_fg: Color::Rgb(140, 140, 140),
             line_number_bg: Color::Black,
+            indent_guide_fg: Color::DarkGray,
+            indent_guide_active_fg: Color::White,
+            ruler_bg: Color::Rgb(28, 28, 28),
 
             // UI element colors
             tab_active_fg: Color::Black,
@@ -844,6 +898,16 @@ impl Theme {
         }
     }
 
+    /// Whether [`Self::from_name`] finds a theme called `name`, rather than
+    /// falling back to the dark theme
+    pub fn exists(name: &str) -> bool {
+        let normalized_name = name.to_lowercase().replace('_', "-");
+        matches!(
+            normalized_name.as_str(),
+            "dark" | "light" | "high-contrast" | "nostalgia"
+        ) || Self::load_builtin_theme(&normalized_name).is_some()
+    }
+
     /// Get all available theme names (builtin + user themes)
     pub fn available_themes() -> Vec<String> {
         let mut themes: Vec<String> = vec![
@@ -854,8 +918,7 @@ impl Theme {
         ];
 
         // Scan user themes directory
-        if let Some(config_dir) = dirs::config_dir() {
-            let user_themes_dir = config_dir.join("fresh").join("themes");
+        if let Some(user_themes_dir) = Self::loadable_user_themes_dir() {
             if let Ok(entries) = std::fs::read_dir(&user_themes_dir) {
                 for entry in entries.flatten() {
                     let path = entry.path();
@@ -889,6 +952,9 @@ impl Theme {
             current_line_bg: Color::Rgb(0, 0, 128),  // Slightly darker blue
             line_number_fg: Color::Rgb(85, 255, 255), // Cyan
             line_number_bg: Color::Rgb(0, 0, 170),
+            indent_guide_fg: Color::Rgb(0, 0, 255),
+            indent_guide_active_fg: Color::Rgb(85, 255, 255),
+            ruler_bg: Color::Rgb(0, 0, 128),
 
             // UI element colors
             tab_active_fg: Color::Rgb(0, 0, 0),
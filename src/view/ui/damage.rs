@@ -0,0 +1,167 @@
+//! Damage tracking of widgets between frames.
+//!
+//! Ratatui rebuilds the whole screen buffer every frame and then compares it
+//! with the previous one, so a widget whose state did not change is still
+//! laid out and styled again. Widgets with a cheap summary of their inputs
+//! (the status bar and the tab bars) are drawn through a [`DamageTracker`]:
+//! each region keeps a fingerprint of the inputs it was drawn from and the
+//! cells it produced, and when neither the fingerprint nor the area changed
+//! the cells are copied back instead of drawing the widget again. Only the
+//! damaged regions are rebuilt, which keeps frames cheap for remote sessions
+//! where every redraw competes with the connection.
+//!
+//! Buffer contents (text and gutters) are reused line by line through
+//! [`LineRenderCache`](super::LineRenderCache) instead: their inputs change
+//! as highlighting completes in the background, so a single fingerprint per
+//! split would rarely match.
+
+use crate::model::event::SplitId;
+use ratatui::buffer::Cell;
+use ratatui::layout::Rect;
+use ratatui::Frame;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// A part of the screen drawn by one widget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageRegion {
+    StatusBar,
+    Tabs(SplitId),
+}
+
+/// What a region looked like when it was last drawn
+struct DrawnRegion {
+    area: Rect,
+    fingerprint: u64,
+    cells: Vec<Cell>,
+    /// Value returned by the widget (e.g. its hit areas)
+    output: Box<dyn Any + Send>,
+}
+
+/// Regions drawn in previous frames, redrawn only when their inputs change
+#[derive(Default)]
+pub struct DamageTracker {
+    regions: HashMap<DamageRegion, DrawnRegion>,
+    /// Regions redrawn in the current frame
+    damaged: Vec<DamageRegion>,
+}
+
+impl DamageTracker {
+    /// Start a frame
+    pub fn begin_frame(&mut self) {
+        self.damaged.clear();
+    }
+
+    /// Forget every region, so the next frame draws all of them
+    pub fn invalidate(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Drop the regions not in `keep` (e.g. the tabs of closed splits)
+    pub fn retain(&mut self, keep: impl Fn(&DamageRegion) -> bool) {
+        self.regions.retain(|region, _| keep(region));
+    }
+
+    /// Draw `region` into `area` with `draw`, unless it was last drawn into
+    /// the same area from inputs with the same `fingerprint`; then its cells
+    /// and the value `draw` returned are reused
+    pub fn draw<T: Clone + Send + 'static>(
+        &mut self,
+        frame: &mut Frame,
+        region: DamageRegion,
+        area: Rect,
+        fingerprint: u64,
+        draw: impl FnOnce(&mut Frame) -> T,
+    ) -> T {
+        let area = area.intersection(frame.area());
+        if let Some(drawn) = self.regions.get(&region) {
+            if drawn.area == area && drawn.fingerprint == fingerprint {
+                if let Some(output) = drawn.output.downcast_ref::<T>() {
+                    let buf = frame.buffer_mut();
+                    for (cell, position) in drawn.cells.iter().zip(area.positions()) {
+                        if let Some(target) = buf.cell_mut(position) {
+                            *target = cell.clone();
+                        }
+                    }
+                    return output.clone();
+                }
+            }
+        }
+
+        let output = draw(frame);
+        let buf = frame.buffer_mut();
+        let cells = area
+            .positions()
+            .filter_map(|position| buf.cell(position).cloned())
+            .collect();
+        self.regions.insert(
+            region,
+            DrawnRegion {
+                area,
+                fingerprint,
+                cells,
+                output: Box::new(output.clone()),
+            },
+        );
+        self.damaged.push(region);
+        output
+    }
+
+    /// Regions redrawn in the current frame
+    pub fn damaged(&self) -> &[DamageRegion] {
+        &self.damaged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Paragraph;
+    use ratatui::Terminal;
+
+    #[test]
+    fn test_unchanged_region_is_reused() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 2)).unwrap();
+        let mut tracker = DamageTracker::default();
+        let area = Rect::new(0, 1, 10, 1);
+        let mut draws = 0;
+        let mut frame_with = |tracker: &mut DamageTracker, text: &str, fingerprint: u64| {
+            let mut output = 0;
+            let completed = terminal
+                .draw(|frame| {
+                    tracker.begin_frame();
+                    output =
+                        tracker.draw(frame, DamageRegion::StatusBar, area, fingerprint, |frame| {
+                            frame.render_widget(Paragraph::new(text.to_string()), area);
+                            draws += 1;
+                            draws
+                        });
+                })
+                .unwrap();
+            let row: String = (0..4)
+                .map(|x| completed.buffer[(x, 1)].symbol().to_string())
+                .collect();
+            (row, output, tracker.damaged().len())
+        };
+
+        assert_eq!(
+            frame_with(&mut tracker, "one", 1),
+            ("one ".to_string(), 1, 1)
+        );
+        // Same inputs: the cells and the returned value are reused
+        assert_eq!(
+            frame_with(&mut tracker, "two", 1),
+            ("one ".to_string(), 1, 0)
+        );
+        assert_eq!(
+            frame_with(&mut tracker, "two", 2),
+            ("two ".to_string(), 2, 1)
+        );
+        tracker.invalidate();
+        assert_eq!(
+            frame_with(&mut tracker, "two", 2),
+            ("two ".to_string(), 3, 1)
+        );
+    }
+}
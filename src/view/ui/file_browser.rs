@@ -444,7 +444,7 @@ impl FileBrowserRenderer {
                 // Truncate with ellipsis
                 let truncated: String = name_with_indicator
                     .chars()
-                    .take(name_col_width - 3)
+                    .take(name_col_width.saturating_sub(3))
                     .collect();
                 format!("{}...", truncated)
             };
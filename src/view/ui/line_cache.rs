@@ -0,0 +1,109 @@
+//! Cache of rendered buffer lines.
+//!
+//! Styling a line looks up the syntax, semantic and overlay styles of every
+//! character, which dominates the cost of a frame on large highlighted
+//! files. Most lines look the same from one frame to the next, so each split
+//! keeps the styled content of the lines it drew in the previous frame,
+//! keyed on a hash of everything the line's appearance depends on: its text,
+//! the styles covering it, the render width and horizontal scroll. Byte
+//! offsets are stored relative to the start of the line, so a line moved by
+//! an edit above it is still found.
+//!
+//! Lines holding a cursor or a selection are always rebuilt, and so are the
+//! gutters (line numbers change when lines are inserted above).
+
+use ratatui::text::Span;
+use std::collections::HashMap;
+
+/// Styled content of a rendered line, without its gutter
+#[derive(Debug, Clone)]
+struct CachedLine {
+    spans: Vec<Span<'static>>,
+    /// Source byte of each screen column, relative to the line's first byte
+    view_map: Vec<Option<usize>>,
+}
+
+/// Rendered lines of a split, reused while their inputs are unchanged
+#[derive(Debug, Default)]
+pub struct LineRenderCache {
+    /// Hash of the inputs shared by all lines of the previous frame
+    frame_key: u64,
+    /// Lines drawn in the previous frame
+    previous: HashMap<u64, CachedLine>,
+    /// Lines drawn in the current frame
+    current: HashMap<u64, CachedLine>,
+    /// Lines reused and rebuilt in the current frame
+    reused: usize,
+    rebuilt: usize,
+}
+
+impl LineRenderCache {
+    /// Start a frame; `frame_key` hashes the inputs shared by all its lines,
+    /// and the cache is emptied when they change
+    pub fn begin_frame(&mut self, frame_key: u64) {
+        if frame_key != self.frame_key {
+            self.frame_key = frame_key;
+            self.previous.clear();
+        }
+        self.current.clear();
+        self.reused = 0;
+        self.rebuilt = 0;
+    }
+
+    /// Append the cached content of the line with `key` starting at byte
+    /// `base` to `spans` and `view_map`; returns false if it isn't cached
+    pub fn reuse(
+        &mut self,
+        key: u64,
+        base: usize,
+        spans: &mut Vec<Span<'static>>,
+        view_map: &mut Vec<Option<usize>>,
+    ) -> bool {
+        if !self.current.contains_key(&key) {
+            let Some(line) = self.previous.remove(&key) else {
+                return false;
+            };
+            self.current.insert(key, line);
+        }
+        let line = &self.current[&key];
+        spans.extend(line.spans.iter().cloned());
+        view_map.extend(
+            line.view_map
+                .iter()
+                .map(|byte| byte.map(|byte| byte + base)),
+        );
+        self.reused += 1;
+        true
+    }
+
+    /// Remember the content of the line with `key` starting at byte `base`
+    pub fn insert(
+        &mut self,
+        key: u64,
+        base: usize,
+        spans: &[Span<'static>],
+        view_map: &[Option<usize>],
+    ) {
+        self.rebuilt += 1;
+        self.current.insert(
+            key,
+            CachedLine {
+                spans: spans.to_vec(),
+                view_map: view_map
+                    .iter()
+                    .map(|byte| byte.map(|byte| byte.saturating_sub(base)))
+                    .collect(),
+            },
+        );
+    }
+
+    /// End a frame, dropping the lines it didn't draw
+    pub fn end_frame(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Number of cacheable lines reused and rebuilt in the last frame
+    pub fn stats(&self) -> (usize, usize) {
+        (self.reused, self.rebuilt)
+    }
+}
@@ -7,13 +7,17 @@
 //! - `status_bar` - Status bar and prompt/minibuffer display
 //! - `suggestions` - Autocomplete and command palette UI
 //! - `split_rendering` - Split pane layout and rendering
+//! - `line_cache` - Rendered lines reused between frames
+//! - `damage` - Widgets redrawn only when their inputs change
 //! - `file_explorer` - File tree explorer rendering
 //! - `scrollbar` - Reusable scrollbar widget
 //! - `scroll_panel` - Reusable scrollable panel for variable-height items
 //! - `file_browser` - File open dialog popup
 
+pub mod damage;
 pub mod file_browser;
 pub mod file_explorer;
+pub mod line_cache;
 pub mod menu;
 pub mod menu_input;
 pub mod scroll_panel;
@@ -26,16 +30,21 @@ pub mod text_edit;
 pub mod view_pipeline;
 
 // Re-export main types for convenience
+pub use damage::{DamageRegion, DamageTracker};
 pub use file_browser::{FileBrowserLayout, FileBrowserRenderer};
 pub use file_explorer::FileExplorerRenderer;
+pub use line_cache::LineRenderCache;
 pub use menu::{context_keys, MenuContext, MenuRenderer, MenuState};
 pub use menu_input::MenuInputHandler;
 pub use scroll_panel::{
     FocusRegion, RenderInfo, ScrollItem, ScrollState, ScrollablePanel, ScrollablePanelLayout,
 };
 pub use scrollbar::{render_scrollbar, ScrollbarColors, ScrollbarState};
-pub use split_rendering::SplitRenderer;
-pub use status_bar::{truncate_path, StatusBarRenderer, TruncatedPath};
+pub use split_rendering::{SplitRenderContext, SplitRenderer};
+pub use status_bar::{
+    truncate_path, StatusBarAreas, StatusBarContent, StatusBarContext, StatusBarRenderer,
+    TruncatedPath,
+};
 pub use suggestions::SuggestionsRenderer;
-pub use tabs::TabsRenderer;
+pub use tabs::{TabBarContext, TabsRenderer};
 pub use text_edit::TextEdit;
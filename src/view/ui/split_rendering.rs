@@ -2,18 +2,24 @@
 
 use std::collections::BTreeMap;
 
-use crate::app::types::ViewLineMapping;
+use crate::app::types::{TabBarArea, ViewLineMapping};
 use crate::app::BufferMetadata;
+use crate::config::{GutterComponent, LineNumberMode};
 use crate::model::buffer::Buffer;
 use crate::model::cursor::SelectionMode;
 use crate::model::event::{BufferId, EventLog, SplitDirection};
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::ansi_background::AnsiBackground;
 use crate::primitives::display_width::char_width;
+use crate::primitives::sticky_scroll::enclosing_scope_headers;
 use crate::services::plugins::api::ViewTransformPayload;
 use crate::state::{EditorState, ViewMode};
+use crate::view::decoration::DecorationIndex;
+use crate::view::margin::{GutterColumn, BOOKMARK_INDICATOR_NAMESPACE};
 use crate::view::split::SplitManager;
-use crate::view::ui::tabs::TabsRenderer;
+use crate::view::ui::damage::{DamageRegion, DamageTracker};
+use crate::view::ui::line_cache::LineRenderCache;
+use crate::view::ui::tabs::{TabBarContext, TabBarHitAreas, TabsRenderer};
 use crate::view::ui::view_pipeline::{
     should_show_line_number, LineStart, ViewLine, ViewLineIterator,
 };
@@ -23,9 +29,17 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
+/// Maximum number of header lines in the sticky scroll band
+const MAX_STICKY_SCROLL_LINES: usize = 5;
+
+/// Bytes of a header line read for the sticky scroll band
+const STICKY_SCROLL_MAX_LINE_BYTES: usize = 1024;
+
 fn push_span_with_map(
     spans: &mut Vec<Span<'static>>,
     map: &mut Vec<Option<usize>>,
@@ -173,10 +187,14 @@ struct DecorationContext {
     highlight_spans: Vec<crate::primitives::highlighter::HighlightSpan>,
     semantic_spans: Vec<crate::primitives::highlighter::HighlightSpan>,
     viewport_overlays: Vec<(crate::view::overlay::Overlay, Range<usize>)>,
+    /// Combined style of the viewport overlays by byte position
+    overlay_styles: DecorationIndex,
     virtual_text_lookup: HashMap<usize, Vec<crate::view::virtual_text::VirtualText>>,
     diagnostic_lines: HashSet<usize>,
     /// Line indicators indexed by line number (highest priority indicator per line)
     line_indicators: BTreeMap<usize, crate::view::margin::LineIndicator>,
+    /// Bookmark indicators indexed by line number
+    bookmark_indicators: BTreeMap<usize, crate::view::margin::LineIndicator>,
 }
 
 struct LineRenderOutput {
@@ -223,8 +241,12 @@ struct LineRenderInput<'a> {
     estimated_lines: usize,
     /// Left column offset for horizontal scrolling
     left_column: usize,
-    /// Whether to show relative line numbers (distance from cursor)
-    relative_line_numbers: bool,
+    /// How line numbers count
+    line_number_mode: LineNumberMode,
+    /// Whether to draw indent guides in leading whitespace
+    indent_guides: bool,
+    /// Lines of the previous frame to reuse, if the split keeps any
+    line_cache: Option<&'a mut LineRenderCache>,
 }
 
 /// Context for computing the style of a single character
@@ -237,7 +259,7 @@ struct CharStyleContext<'a> {
     theme: &'a crate::view::theme::Theme,
     highlight_spans: &'a [crate::primitives::highlighter::HighlightSpan],
     semantic_spans: &'a [crate::primitives::highlighter::HighlightSpan],
-    viewport_overlays: &'a [(crate::view::overlay::Overlay, Range<usize>)],
+    overlay_styles: &'a DecorationIndex,
     primary_cursor_position: usize,
     is_active: bool,
 }
@@ -258,124 +280,336 @@ struct LeftMarginContext<'a> {
     diagnostic_lines: &'a HashSet<usize>,
     /// Pre-computed line indicators (line_num -> indicator)
     line_indicators: &'a BTreeMap<usize, crate::view::margin::LineIndicator>,
+    /// Pre-computed bookmark indicators (line_num -> indicator)
+    bookmark_indicators: &'a BTreeMap<usize, crate::view::margin::LineIndicator>,
     /// Line number where the primary cursor is located (for relative line numbers)
     cursor_line: usize,
-    /// Whether to show relative line numbers
-    relative_line_numbers: bool,
+    /// How line numbers count
+    line_number_mode: LineNumberMode,
 }
 
-/// Render the left margin (indicators + line numbers + separator) to line_spans
-fn render_left_margin(
-    ctx: &LeftMarginContext,
-    line_spans: &mut Vec<Span<'static>>,
-    line_view_map: &mut Vec<Option<usize>>,
-) {
-    if !ctx.state.margins.left_config.enabled {
-        return;
+/// Indent guide layout for the view lines being rendered
+struct IndentGuides {
+    /// Indentation width of each view line; None for blank lines, wrapped
+    /// continuations and injected content
+    indents: Vec<Option<usize>>,
+    tab_size: usize,
+    /// Guide column and view line range of the block containing the cursor
+    active: Option<(usize, Range<usize>)>,
+}
+
+impl IndentGuides {
+    fn compute(view_lines: &[ViewLine], cursor_position: usize, tab_size: usize) -> Self {
+        let tab_size = tab_size.max(1);
+        let indents: Vec<Option<usize>> = view_lines
+            .iter()
+            .map(|line| {
+                if !should_show_line_number(line) {
+                    return None;
+                }
+                let content = line.text.trim_end_matches(['\n', '\r']);
+                let indent = content.chars().take_while(|&c| c == ' ').count();
+                (indent < content.chars().count()).then_some(indent)
+            })
+            .collect();
+
+        let active = view_lines
+            .iter()
+            .position(|line| line.char_source_bytes.contains(&Some(cursor_position)))
+            .and_then(|idx| {
+                // Walk back from a wrapped continuation to its source line
+                let line_idx = (0..=idx)
+                    .rev()
+                    .find(|&i| should_show_line_number(&view_lines[i]))?;
+                let indent = indents[line_idx].filter(|&indent| indent > 0)?;
+                let column = (indent - 1) / tab_size * tab_size;
+                let inside = |i: usize| indents[i].is_none_or(|indent| indent > column);
+                let start = (0..line_idx)
+                    .rev()
+                    .take_while(|&i| inside(i))
+                    .last()
+                    .unwrap_or(line_idx);
+                let end = (line_idx + 1..indents.len())
+                    .take_while(|&i| inside(i))
+                    .last()
+                    .unwrap_or(line_idx);
+                Some((column, start..end + 1))
+            });
+
+        Self {
+            indents,
+            tab_size,
+            active,
+        }
     }
 
-    // For continuation lines, don't show any indicators
-    if ctx.is_continuation {
-        push_span_with_map(
-            line_spans,
-            line_view_map,
-            " ".to_string(),
-            Style::default(),
-            None,
-        );
-    } else if ctx.diagnostic_lines.contains(&ctx.current_source_line_num) {
-        // Diagnostic indicators have highest priority
-        push_span_with_map(
-            line_spans,
-            line_view_map,
-            "●".to_string(),
-            Style::default().fg(ratatui::style::Color::Red),
-            None,
-        );
-    } else if let Some(indicator) = ctx.line_indicators.get(&ctx.current_source_line_num) {
-        // Show line indicator (git gutter, breakpoints, etc.)
-        push_span_with_map(
-            line_spans,
-            line_view_map,
-            indicator.symbol.clone(),
-            Style::default().fg(indicator.color),
-            None,
-        );
-    } else {
-        // Show space (no indicator)
-        push_span_with_map(
-            line_spans,
-            line_view_map,
-            " ".to_string(),
-            Style::default(),
-            None,
-        );
+    /// Guide at a visual column of a view line: Some(true) for the guide of
+    /// the block containing the cursor, Some(false) for other guides
+    fn guide_at(&self, line_idx: usize, column: usize) -> Option<bool> {
+        let indent = self.indents.get(line_idx).copied().flatten()?;
+        if column >= indent || !column.is_multiple_of(self.tab_size) {
+            return None;
+        }
+        Some(
+            self.active
+                .as_ref()
+                .is_some_and(|(active, lines)| *active == column && lines.contains(&line_idx)),
+        )
     }
 
-    // Render line number (right-aligned) or blank for continuations
-    if ctx.is_continuation {
-        // For wrapped continuation lines, render blank space
-        let blank = " ".repeat(ctx.state.margins.left_config.width);
-        push_span_with_map(
-            line_spans,
-            line_view_map,
-            blank,
-            Style::default().fg(ctx.theme.line_number_fg),
-            None,
-        );
-    } else if ctx.relative_line_numbers {
-        // Relative line numbers: show distance from cursor, or absolute for cursor line
-        let display_num = if ctx.current_source_line_num == ctx.cursor_line {
-            // Show absolute line number for the cursor line (1-indexed)
-            ctx.current_source_line_num + 1
-        } else {
-            // Show relative distance for other lines
-            ctx.current_source_line_num.abs_diff(ctx.cursor_line)
+    /// Indentation of a view line and the column of the active guide on it,
+    /// which together decide the line's guides
+    fn line_key(&self, line_idx: usize) -> (Option<usize>, Option<usize>) {
+        let active = self
+            .active
+            .as_ref()
+            .filter(|(_, lines)| lines.contains(&line_idx))
+            .map(|(column, _)| *column);
+        (self.indents.get(line_idx).copied().flatten(), active)
+    }
+}
+
+/// Cache key of a view line's styled content and the byte it starts at, or
+/// None if the line must be rebuilt: it has no source text, or a cursor or
+/// selection touches it
+fn line_cache_key(
+    line: &ViewLine,
+    line_idx: usize,
+    source_line_num: usize,
+    selection: &SelectionContext,
+    decorations: &DecorationContext,
+    indent_guides: Option<&IndentGuides>,
+) -> Option<(u64, usize)> {
+    let (start, last) = line
+        .char_source_bytes
+        .iter()
+        .flatten()
+        .fold(None, |bounds, &byte| match bounds {
+            None => Some((byte, byte)),
+            Some((start, last)) => Some((byte.min(start), byte.max(last))),
+        })?;
+    let end = last + 1;
+    let touched = selection
+        .cursor_positions
+        .iter()
+        .chain([&selection.primary_cursor_position])
+        .any(|&pos| pos >= start && pos <= end)
+        || selection
+            .ranges
+            .iter()
+            .any(|range| range.start <= end && range.end >= start)
+        || selection
+            .block_rects
+            .iter()
+            .any(|&(start_line, _, end_line, _)| {
+                source_line_num >= start_line && source_line_num <= end_line
+            });
+    if touched {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    line.text.hash(&mut hasher);
+    line.ends_with_newline.hash(&mut hasher);
+    for byte in &line.char_source_bytes {
+        byte.map(|byte| byte - start).hash(&mut hasher);
+    }
+    for style in &line.char_styles {
+        style
+            .as_ref()
+            .map(|s| (s.fg, s.bg, s.bold, s.italic))
+            .hash(&mut hasher);
+    }
+    for spans in [&decorations.highlight_spans, &decorations.semantic_spans] {
+        for span in spans
+            .iter()
+            .filter(|span| span.range.start < end && span.range.end > start)
+        {
+            span.range.start.saturating_sub(start).hash(&mut hasher);
+            (span.range.end.min(end) - start).hash(&mut hasher);
+            span.color.hash(&mut hasher);
+        }
+        // Separates the syntax spans from the semantic ones
+        usize::MAX.hash(&mut hasher);
+    }
+    for (segment_start, style) in decorations.overlay_styles.segments(start..end) {
+        (segment_start - start).hash(&mut hasher);
+        style.hash(&mut hasher);
+    }
+    let mut virtual_texts: Vec<_> = decorations
+        .virtual_text_lookup
+        .iter()
+        .filter(|(&pos, _)| pos >= start && pos < end)
+        .collect();
+    virtual_texts.sort_by_key(|(&pos, _)| pos);
+    for (pos, vtexts) in virtual_texts {
+        (pos - start).hash(&mut hasher);
+        for vtext in vtexts {
+            (&vtext.text, vtext.style, vtext.position).hash(&mut hasher);
+        }
+    }
+    indent_guides
+        .map(|guides| guides.line_key(line_idx))
+        .hash(&mut hasher);
+    Some((hasher.finish(), start))
+}
+
+/// Sign of the first of `components` with one on the margin's line
+fn gutter_sign(ctx: &LeftMarginContext, components: &[GutterComponent]) -> Option<(String, Style)> {
+    let line = ctx.current_source_line_num;
+    components.iter().find_map(|component| match component {
+        GutterComponent::DiagnosticSigns => ctx.diagnostic_lines.contains(&line).then(|| {
+            (
+                "●".to_string(),
+                Style::default().fg(ratatui::style::Color::Red),
+            )
+        }),
+        GutterComponent::GitSigns => ctx.line_indicators.get(&line).map(|indicator| {
+            (
+                indicator.symbol.clone(),
+                Style::default().fg(indicator.color),
+            )
+        }),
+        GutterComponent::Bookmarks => ctx.bookmark_indicators.get(&line).map(|indicator| {
+            (
+                indicator.symbol.clone(),
+                Style::default().fg(indicator.color),
+            )
+        }),
+        GutterComponent::LineNumbers => None,
+    })
+}
+
+/// Number the margin shows for the source line holding `cursor`, counting
+/// source lines from `starting_line_num` the way the margin does
+///
+/// In large file mode the numbers shown are estimates that the cursor's own
+/// line number need not agree with, so relative numbers are measured from
+/// this one. None when the cursor is not on one of `view_lines`.
+fn displayed_cursor_line(
+    view_lines: &[ViewLine],
+    starting_line_num: usize,
+    cursor: usize,
+) -> Option<usize> {
+    let mut line_num = starting_line_num;
+    let mut prev_was_source_line = false;
+    let mut last_end: Option<(usize, bool)> = None;
+    for line in view_lines {
+        let is_source_line = should_show_line_number(line);
+        if is_source_line && prev_was_source_line {
+            line_num += 1;
+        }
+        prev_was_source_line |= is_source_line;
+
+        let mut bytes = line.char_source_bytes.iter().flatten().copied();
+        let Some(first) = bytes.next() else {
+            continue;
         };
-        let rendered_text = format!(
-            "{:>width$}",
-            display_num,
-            width = ctx.state.margins.left_config.width
-        );
-        // Use brighter color for the cursor line
-        let margin_style = if ctx.current_source_line_num == ctx.cursor_line {
-            Style::default().fg(ctx.theme.editor_fg)
+        let last = bytes.last().unwrap_or(first);
+        // Past the last character only counts when it is not a newline
+        let end = if line.ends_with_newline {
+            last
         } else {
-            Style::default().fg(ctx.theme.line_number_fg)
+            last + 1
         };
-        push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
-    } else {
-        let margin_content = ctx.state.margins.render_line(
-            ctx.current_source_line_num,
-            crate::view::margin::MarginPosition::Left,
-            ctx.estimated_lines,
-        );
-        let (rendered_text, style_opt) = margin_content.render(ctx.state.margins.left_config.width);
+        if (first..=end).contains(&cursor) {
+            return Some(line_num);
+        }
+        last_end = Some((last, line.ends_with_newline));
+    }
+    // The empty line after a final newline
+    match last_end {
+        Some((last, true)) if cursor == last + 1 => Some(line_num + 1),
+        _ => None,
+    }
+}
 
-        // Use custom style if provided, otherwise use default theme color
-        let margin_style =
-            style_opt.unwrap_or_else(|| Style::default().fg(ctx.theme.line_number_fg));
+/// Render the left margin (gutter columns + separator) to line_spans
+fn render_left_margin(
+    ctx: &LeftMarginContext,
+    line_spans: &mut Vec<Span<'static>>,
+    line_view_map: &mut Vec<Option<usize>>,
+) {
+    let margins = &ctx.state.margins;
+    if !margins.left_config.enabled || margins.gutter_columns().is_empty() {
+        return;
+    }
 
-        push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
+    for column in margins.gutter_columns() {
+        let width = margins.gutter_column_width(column);
+        match column {
+            // For continuation lines, don't show any signs or numbers
+            _ if ctx.is_continuation => {
+                push_span_with_map(
+                    line_spans,
+                    line_view_map,
+                    " ".repeat(width),
+                    Style::default().fg(ctx.theme.line_number_fg),
+                    None,
+                );
+            }
+            GutterColumn::Signs(components) => {
+                let (symbol, style) = gutter_sign(ctx, components)
+                    .unwrap_or_else(|| (" ".to_string(), Style::default()));
+                push_span_with_map(line_spans, line_view_map, symbol, style, None);
+            }
+            GutterColumn::LineNumbers => {
+                let (rendered_text, margin_style) = render_line_number(ctx, width);
+                push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
+            }
+        }
     }
 
     // Render separator
-    if ctx.state.margins.left_config.show_separator {
+    if margins.left_config.show_separator {
         let separator_style = Style::default().fg(ctx.theme.line_number_fg);
         push_span_with_map(
             line_spans,
             line_view_map,
-            ctx.state.margins.left_config.separator.clone(),
+            margins.left_config.separator.clone(),
             separator_style,
             None,
         );
     }
 }
 
+/// Text and style of the margin line's number, right-aligned to `width`
+fn render_line_number(ctx: &LeftMarginContext, width: usize) -> (String, Style) {
+    let line = ctx.current_source_line_num;
+    let on_cursor_line = line == ctx.cursor_line;
+    let display_num = match ctx.line_number_mode {
+        LineNumberMode::Absolute => {
+            let margin_content = ctx.state.margins.render_line(
+                line,
+                crate::view::margin::MarginPosition::Left,
+                ctx.estimated_lines,
+            );
+            let (rendered_text, style_opt) = margin_content.render(width);
+
+            // Use custom style if provided, otherwise use default theme color
+            let margin_style =
+                style_opt.unwrap_or_else(|| Style::default().fg(ctx.theme.line_number_fg));
+            return (rendered_text, margin_style);
+        }
+        // Show absolute line number for the cursor line (1-indexed)
+        LineNumberMode::Hybrid if on_cursor_line => line + 1,
+        // Show relative distance for other lines
+        LineNumberMode::Relative | LineNumberMode::Hybrid => line.abs_diff(ctx.cursor_line),
+    };
+    // Use brighter color for the cursor line
+    let margin_style = if on_cursor_line {
+        Style::default().fg(ctx.theme.editor_fg)
+    } else {
+        Style::default().fg(ctx.theme.line_number_fg)
+    };
+    (
+        format!("{:>width$}", display_num, width = width),
+        margin_style,
+    )
+}
+
 /// Compute the style for a character by layering: token -> ANSI -> syntax -> semantic -> overlays -> selection -> cursor
 fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
-    use crate::view::overlay::OverlayFace;
-
     // Find highlight color for this byte position
     let highlight_color = ctx.byte_pos.and_then(|bp| {
         ctx.highlight_spans
@@ -384,17 +618,6 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
             .map(|span| span.color)
     });
 
-    // Find overlays for this byte position
-    let overlays: Vec<&crate::view::overlay::Overlay> = if let Some(bp) = ctx.byte_pos {
-        ctx.viewport_overlays
-            .iter()
-            .filter(|(_, range)| range.contains(&bp))
-            .map(|(overlay, _)| overlay)
-            .collect()
-    } else {
-        Vec::new()
-    };
-
     // Start with token style if present (for injected content like annotation headers)
     // Otherwise use ANSI/syntax/theme default
     let mut style = if let Some(ts) = ctx.token_style {
@@ -459,26 +682,8 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
     }
 
     // Apply overlay styles
-    for overlay in &overlays {
-        match &overlay.face {
-            OverlayFace::Underline {
-                color,
-                style: _underline_style,
-            } => {
-                style = style.add_modifier(Modifier::UNDERLINED).fg(*color);
-            }
-            OverlayFace::Background { color } => {
-                style = style.bg(*color);
-            }
-            OverlayFace::Foreground { color } => {
-                style = style.fg(*color);
-            }
-            OverlayFace::Style {
-                style: overlay_style,
-            } => {
-                style = style.patch(*overlay_style);
-            }
-        }
+    if let Some(overlay_style) = ctx.byte_pos.and_then(|bp| ctx.overlay_styles.style_at(bp)) {
+        style = style.patch(overlay_style);
     }
 
     // Apply selection highlighting
@@ -506,6 +711,38 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
     }
 }
 
+/// What the splits are drawn from, besides the buffers and the state kept
+/// between frames
+pub struct SplitRenderContext<'a> {
+    pub split_manager: &'a SplitManager,
+    /// Metadata for buffers (contains display names)
+    pub buffer_metadata: &'a HashMap<BufferId, BufferMetadata>,
+    /// The active theme for colors
+    pub theme: &'a crate::view::theme::Theme,
+    pub ansi_background: Option<&'a AnsiBackground>,
+    pub background_fade: f32,
+    /// Whether LSP is waiting
+    pub lsp_waiting: bool,
+    /// Threshold for using constant scrollbar thumb size
+    pub large_file_threshold_bytes: u64,
+    /// Estimated average line length for large file line estimation
+    pub estimated_line_length: usize,
+    pub highlight_context_bytes: usize,
+    /// Whether to hide the hardware cursor (e.g., when menu is open)
+    pub hide_cursor: bool,
+    /// (buffer_id, split_id, is_close_button) of the hovered tab
+    pub hovered_tab: Option<(BufferId, crate::model::event::SplitId, bool)>,
+    pub hovered_close_split: Option<crate::model::event::SplitId>,
+    pub hovered_maximize_split: Option<crate::model::event::SplitId>,
+    pub is_maximized: bool,
+    pub line_number_mode: LineNumberMode,
+    pub gutter_components: &'a [GutterComponent],
+    pub indent_guides: bool,
+    pub sticky_scroll: bool,
+    /// Text width in zen mode, which hides the tab bars
+    pub zen_width: Option<u16>,
+}
+
 /// Renders split panes and their content
 pub struct SplitRenderer;
 
@@ -515,43 +752,26 @@ impl SplitRenderer {
     /// # Arguments
     /// * `frame` - The ratatui frame to render to
     /// * `area` - The rectangular area to render in
-    /// * `split_manager` - The split manager
+    /// * `ctx` - What the splits are drawn from
     /// * `buffers` - All open buffers
-    /// * `buffer_metadata` - Metadata for buffers (contains display names)
     /// * `event_logs` - Event logs for each buffer
-    /// * `theme` - The active theme for colors
-    /// * `lsp_waiting` - Whether LSP is waiting
-    /// * `large_file_threshold_bytes` - Threshold for using constant scrollbar thumb size
-    /// * `line_wrap` - Whether line wrapping is enabled
-    /// * `estimated_line_length` - Estimated average line length for large file line estimation
-    /// * `hide_cursor` - Whether to hide the hardware cursor (e.g., when menu is open)
+    /// * `split_view_states` - Per-split cursors, viewports and tabs
+    /// * `line_caches` - Lines rendered in the previous frame, per split
+    /// * `damage` - Tab bars drawn in the previous frame
     ///
     /// # Returns
     /// * Vec of (split_id, buffer_id, content_rect, scrollbar_rect, thumb_start, thumb_end) for mouse handling
     pub fn render_content(
         frame: &mut Frame,
         area: Rect,
-        split_manager: &SplitManager,
+        ctx: &SplitRenderContext,
         buffers: &mut HashMap<BufferId, EditorState>,
-        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
         event_logs: &mut HashMap<BufferId, EventLog>,
-        theme: &crate::view::theme::Theme,
-        ansi_background: Option<&AnsiBackground>,
-        background_fade: f32,
-        lsp_waiting: bool,
-        large_file_threshold_bytes: u64,
-        _line_wrap: bool,
-        estimated_line_length: usize,
-        highlight_context_bytes: usize,
         mut split_view_states: Option<
             &mut HashMap<crate::model::event::SplitId, crate::view::split::SplitViewState>,
         >,
-        hide_cursor: bool,
-        hovered_tab: Option<(BufferId, crate::model::event::SplitId, bool)>, // (buffer_id, split_id, is_close_button)
-        hovered_close_split: Option<crate::model::event::SplitId>,
-        hovered_maximize_split: Option<crate::model::event::SplitId>,
-        is_maximized: bool,
-        relative_line_numbers: bool,
+        line_caches: &mut HashMap<crate::model::event::SplitId, LineRenderCache>,
+        damage: &mut DamageTracker,
     ) -> (
         Vec<(
             crate::model::event::SplitId,
@@ -564,13 +784,31 @@ impl SplitRenderer {
         Vec<(crate::model::event::SplitId, BufferId, u16, u16, u16, u16)>,
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // close split button areas
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // maximize split button areas
+        Vec<TabBarArea>, // tab bars with their overflow button and hidden tabs
         HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>>, // view line mappings for mouse clicks
     ) {
         let _span = tracing::trace_span!("render_content").entered();
+        let SplitRenderContext {
+            split_manager,
+            buffer_metadata,
+            theme,
+            large_file_threshold_bytes,
+            hovered_tab,
+            hovered_close_split,
+            hovered_maximize_split,
+            is_maximized,
+            zen_width,
+            ..
+        } = *ctx;
 
         // Get all visible splits with their areas
         let visible_buffers = split_manager.get_visible_buffers(area);
         let active_split_id = split_manager.active_split();
+        line_caches.retain(|split_id, _| visible_buffers.iter().any(|(id, _, _)| id == split_id));
+        damage.retain(|region| match region {
+            DamageRegion::Tabs(split_id) => visible_buffers.iter().any(|(id, _, _)| id == split_id),
+            _ => true,
+        });
         let has_multiple_splits = visible_buffers.len() > 1;
 
         // Collect areas for mouse handling
@@ -578,15 +816,22 @@ impl SplitRenderer {
         let mut all_tab_areas = Vec::new();
         let mut close_split_areas = Vec::new();
         let mut maximize_split_areas = Vec::new();
+        let mut tab_bars = Vec::new();
         let mut view_line_mappings: HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>> =
             HashMap::new();
 
         // Render each split
         for (split_id, buffer_id, split_area) in visible_buffers {
+            // A split squeezed to nothing by a small terminal has nothing to show
+            if split_area.width == 0 || split_area.height == 0 {
+                continue;
+            }
             let is_active = split_id == active_split_id;
 
-            let layout = Self::split_layout(split_area);
-            let (split_buffers, tab_scroll_offset) =
+            // Zen mode hides the tab bar and centers the text
+            let show_tabs = zen_width.is_none();
+            let layout = Self::split_layout(split_area, show_tabs);
+            let (split_buffers, pinned_buffers, tab_scroll_offset) =
                 Self::split_buffers_for_tabs(split_view_states.as_deref(), split_id, buffer_id);
 
             // Determine hover state for this split's tabs
@@ -598,32 +843,57 @@ impl SplitRenderer {
                 }
             });
 
+            // Split control buttons at the right side of the tabs row
+            // Show maximize/unmaximize button when: multiple splits exist OR we're currently maximized
+            // Show close button when: multiple splits exist AND we're not maximized
+            // Both need the split to be wide enough to hold them
+            let fits_buttons = layout.tabs_rect.width >= 4;
+            let show_maximize_btn =
+                show_tabs && fits_buttons && (has_multiple_splits || is_maximized);
+            let show_close_btn = show_tabs && fits_buttons && has_multiple_splits && !is_maximized;
+            // Each button takes its column plus the space after it
+            let buttons_width = 2 * (show_maximize_btn as u16 + show_close_btn as u16);
+
             // Render tabs for this split and collect hit areas
-            let tab_hit_areas = TabsRenderer::render_for_split(
-                frame,
-                layout.tabs_rect,
-                &split_buffers,
-                buffers,
-                buffer_metadata,
-                buffer_id, // The currently displayed buffer in this split
-                theme,
-                is_active,
-                tab_scroll_offset,
-                tab_hover_for_split,
-            );
+            let tab_hit_areas = if show_tabs {
+                let tab_bar = TabBarContext {
+                    split_buffers: &split_buffers,
+                    pinned: &pinned_buffers,
+                    buffers,
+                    buffer_metadata,
+                    active_buffer: buffer_id, // The currently displayed buffer in this split
+                    theme,
+                    is_active_split: is_active,
+                    tab_scroll_offset,
+                    hovered_tab: tab_hover_for_split,
+                    reserved_right: buttons_width,
+                };
+                damage.draw(
+                    frame,
+                    DamageRegion::Tabs(split_id),
+                    layout.tabs_rect,
+                    TabsRenderer::fingerprint(&tab_bar),
+                    |frame| TabsRenderer::render_for_split(frame, layout.tabs_rect, &tab_bar),
+                )
+            } else {
+                TabBarHitAreas::default()
+            };
 
             // Add tab row to hit areas (all tabs share the same row)
             let tab_row = layout.tabs_rect.y;
-            for (buf_id, start_col, end_col, close_start) in tab_hit_areas {
+            for (buf_id, start_col, end_col, close_start) in tab_hit_areas.tabs {
                 all_tab_areas.push((split_id, buf_id, tab_row, start_col, end_col, close_start));
             }
+            if show_tabs {
+                tab_bars.push(TabBarArea {
+                    split_id,
+                    rect: layout.tabs_rect,
+                    overflow_button: tab_hit_areas.overflow_button,
+                    hidden: tab_hit_areas.hidden,
+                });
+            }
 
             // Render split control buttons at the right side of tabs row
-            // Show maximize/unmaximize button when: multiple splits exist OR we're currently maximized
-            // Show close button when: multiple splits exist AND we're not maximized
-            let show_maximize_btn = has_multiple_splits || is_maximized;
-            let show_close_btn = has_multiple_splits && !is_maximized;
-
             if show_maximize_btn || show_close_btn {
                 // Calculate button positions from right edge
                 // Layout: [maximize] [space] [close] |
@@ -707,19 +977,9 @@ impl SplitRenderer {
                     event_log_opt,
                     layout.content_rect,
                     is_active,
-                    theme,
-                    ansi_background,
-                    background_fade,
-                    lsp_waiting,
-                    view_prefs.view_mode,
-                    view_prefs.compose_width,
-                    view_prefs.compose_column_guides,
-                    view_prefs.view_transform,
-                    estimated_line_length,
-                    highlight_context_bytes,
-                    buffer_id,
-                    hide_cursor,
-                    relative_line_numbers,
+                    view_prefs,
+                    line_caches.entry(split_id).or_default(),
+                    ctx,
                 );
 
                 // Store view line mappings for mouse click handling
@@ -789,6 +1049,7 @@ impl SplitRenderer {
             all_tab_areas,
             close_split_areas,
             maximize_split_areas,
+            tab_bars,
             view_line_mappings,
         )
     }
@@ -823,8 +1084,8 @@ impl SplitRenderer {
         }
     }
 
-    fn split_layout(split_area: Rect) -> SplitLayout {
-        let tabs_height = 1u16;
+    fn split_layout(split_area: Rect, show_tabs: bool) -> SplitLayout {
+        let tabs_height = if show_tabs { 1u16 } else { 0 };
         let scrollbar_width = 1u16;
 
         let tabs_rect = Rect::new(split_area.x, split_area.y, split_area.width, tabs_height);
@@ -854,16 +1115,17 @@ impl SplitRenderer {
         >,
         split_id: crate::model::event::SplitId,
         buffer_id: BufferId,
-    ) -> (Vec<BufferId>, usize) {
+    ) -> (Vec<BufferId>, Vec<BufferId>, usize) {
         if let Some(view_states) = split_view_states {
             if let Some(view_state) = view_states.get(&split_id) {
                 return (
                     view_state.open_buffers.clone(),
+                    view_state.pinned_buffers.clone(),
                     view_state.tab_scroll_offset,
                 );
             }
         }
-        (vec![buffer_id], 0)
+        (vec![buffer_id], Vec::new(), 0)
     }
 
     fn temporary_split_state(
@@ -1561,6 +1823,36 @@ impl SplitRenderer {
         )
     }
 
+    /// The rows the source line starting at `line_start` takes when wrapped
+    /// at `wrap_width` columns, as `build_view_data` lays them out
+    pub(crate) fn wrapped_source_line(
+        buffer: &mut Buffer,
+        line_start: usize,
+        wrap_width: usize,
+        tab_size: usize,
+        estimated_line_length: usize,
+    ) -> Vec<ViewLine> {
+        use crate::services::plugins::api::ViewTokenWireKind;
+
+        let line_ending = buffer.line_ending();
+        let mut tokens = Self::build_base_tokens(
+            buffer,
+            line_start,
+            estimated_line_length,
+            0,
+            false,
+            line_ending,
+        );
+        if let Some(newline) = tokens
+            .iter()
+            .position(|token| matches!(token.kind, ViewTokenWireKind::Newline))
+        {
+            tokens.truncate(newline + 1);
+        }
+        let tokens = Self::apply_wrapping_transform(tokens, wrap_width, 0);
+        ViewLineIterator::new(&tokens, false, true, tab_size).collect()
+    }
+
     fn apply_wrapping_transform(
         tokens: Vec<crate::services::plugins::api::ViewTokenWire>,
         content_width: usize,
@@ -1572,8 +1864,10 @@ impl SplitRenderer {
         let mut wrapped = Vec::new();
         let mut current_line_width = 0;
 
-        // Calculate available width (accounting for gutter on first line only)
-        let available_width = content_width.saturating_sub(gutter_width);
+        // Calculate available width (accounting for gutter on first line only).
+        // A split narrower than its gutter still gets one column, so wrapping
+        // always makes progress.
+        let available_width = content_width.saturating_sub(gutter_width).max(1);
 
         for token in tokens {
             match &token.kind {
@@ -1898,13 +2192,28 @@ impl SplitRenderer {
         // Update semantic highlighter color from theme
         state.semantic_highlighter.highlight_color = theme.semantic_highlight_bg;
 
-        let semantic_spans = state.semantic_highlighter.highlight_occurrences(
-            &state.buffer,
-            primary_cursor_position,
-            viewport_start,
-            viewport_end,
-            highlight_context_bytes,
-        );
+        // With a selection, highlight other copies of the selected text;
+        // otherwise highlight occurrences of the word under the cursor
+        let selection = state
+            .cursors
+            .primary()
+            .selection_range()
+            .filter(|range| !range.is_empty());
+        let semantic_spans = match selection {
+            Some(selection) => state.semantic_highlighter.highlight_selection_occurrences(
+                &state.buffer,
+                selection,
+                viewport_start,
+                viewport_end,
+            ),
+            None => state.semantic_highlighter.highlight_occurrences(
+                &state.buffer,
+                primary_cursor_position,
+                viewport_start,
+                viewport_end,
+                highlight_context_bytes,
+            ),
+        };
 
         let viewport_overlays = state
             .overlays
@@ -1912,6 +2221,7 @@ impl SplitRenderer {
             .into_iter()
             .map(|(overlay, range)| (overlay.clone(), range))
             .collect::<Vec<_>>();
+        let overlay_styles = DecorationIndex::build(&viewport_overlays);
 
         // Use the lsp-diagnostic namespace to identify diagnostic overlays
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
@@ -1934,19 +2244,28 @@ impl SplitRenderer {
                 .collect();
 
         // Pre-compute line indicators for the viewport (only query markers in visible range)
-        let line_indicators = state.margins.get_indicators_for_viewport(
+        let line_indicators = state.margins.get_indicators_for_viewport_in(
+            viewport_start,
+            viewport_end,
+            |byte_offset| state.buffer.get_line_number(byte_offset),
+            |namespace| namespace != BOOKMARK_INDICATOR_NAMESPACE,
+        );
+        let bookmark_indicators = state.margins.get_indicators_for_viewport_in(
             viewport_start,
             viewport_end,
             |byte_offset| state.buffer.get_line_number(byte_offset),
+            |namespace| namespace == BOOKMARK_INDICATOR_NAMESPACE,
         );
 
         DecorationContext {
             highlight_spans,
             semantic_spans,
             viewport_overlays,
+            overlay_styles,
             virtual_text_lookup,
             diagnostic_lines,
             line_indicators,
+            bookmark_indicators,
         }
     }
 
@@ -1987,23 +2306,51 @@ impl SplitRenderer {
             line_wrap,
             estimated_lines,
             left_column,
-            relative_line_numbers,
+            line_number_mode,
+            indent_guides,
+            line_cache,
         } = input;
 
+        // Reveal codes show positions, so debug mode never reuses lines
+        let mut line_cache = line_cache.filter(|_| !state.debug_highlight_mode);
+        if let Some(cache) = line_cache.as_deref_mut() {
+            let mut hasher = DefaultHasher::new();
+            (
+                &theme.name,
+                theme.editor_fg,
+                theme.indent_guide_fg,
+                theme.indent_guide_active_fg,
+            )
+                .hash(&mut hasher);
+            (render_area.width, left_column, line_wrap).hash(&mut hasher);
+            (state.show_whitespace_tabs, state.tab_size).hash(&mut hasher);
+            cache.begin_frame(hasher.finish());
+        }
+
         let selection_ranges = &selection.ranges;
         let block_selections = &selection.block_rects;
         let cursor_positions = &selection.cursor_positions;
         let primary_cursor_position = selection.primary_cursor_position;
 
         // Compute cursor line number for relative line numbers display
-        let cursor_line = state.buffer.get_line_number(primary_cursor_position);
+        let cursor_line = view_lines
+            .get(view_anchor.start_line_idx..)
+            .and_then(|lines| {
+                displayed_cursor_line(lines, starting_line_num, primary_cursor_position)
+            })
+            .unwrap_or_else(|| state.buffer.get_line_number(primary_cursor_position));
+
+        let indent_guides = indent_guides
+            .then(|| IndentGuides::compute(view_lines, primary_cursor_position, state.tab_size));
 
         let highlight_spans = &decorations.highlight_spans;
         let semantic_spans = &decorations.semantic_spans;
         let viewport_overlays = &decorations.viewport_overlays;
+        let overlay_styles = &decorations.overlay_styles;
         let virtual_text_lookup = &decorations.virtual_text_lookup;
         let diagnostic_lines = &decorations.diagnostic_lines;
         let line_indicators = &decorations.line_indicators;
+        let bookmark_indicators = &decorations.bookmark_indicators;
 
         let mut lines = Vec::new();
         let mut lines_rendered = 0usize;
@@ -2054,6 +2401,7 @@ impl SplitRenderer {
             let line_visual_to_char = &current_view_line.visual_to_char;
             let line_tab_starts = &current_view_line.tab_starts;
             let _line_start_type = current_view_line.line_start; // Available for future use
+            let view_line_idx = view_iter_idx;
 
             // Helper to get source byte at a visual column using the new O(1) lookup
             let _source_byte_at_col = |vis_col: usize| -> Option<usize> {
@@ -2108,12 +2456,15 @@ impl SplitRenderer {
                     estimated_lines,
                     diagnostic_lines,
                     line_indicators,
+                    bookmark_indicators,
                     cursor_line,
-                    relative_line_numbers,
+                    line_number_mode,
                 },
                 &mut line_spans,
                 &mut line_view_map,
             );
+            let margin_spans = line_spans.len();
+            let margin_columns = line_view_map.len();
 
             // Check if this line has any selected text
             let mut byte_index = 0; // Byte offset in line_content string
@@ -2137,6 +2488,28 @@ impl SplitRenderer {
             };
             let max_chars_to_process = left_col.saturating_add(max_visible_chars);
 
+            // Reuse the line's content from the previous frame when nothing it
+            // depends on changed; lines cut short for speed are not cached
+            let cache_key = line_cache
+                .as_ref()
+                .filter(|_| line_content.len() <= max_chars_to_process)
+                .and_then(|_| {
+                    line_cache_key(
+                        current_view_line,
+                        view_line_idx,
+                        current_source_line_num,
+                        selection,
+                        decorations,
+                        indent_guides.as_ref(),
+                    )
+                });
+            let reused = match (cache_key, line_cache.as_deref_mut()) {
+                (Some((key, base)), Some(cache)) => {
+                    cache.reuse(key, base, &mut line_spans, &mut line_view_map)
+                }
+                _ => false,
+            };
+
             // ANSI parser for this line to handle escape sequences
             // Optimization: only create parser if line contains ESC byte
             let line_has_ansi = line_content.contains('\x1b');
@@ -2155,7 +2528,9 @@ impl SplitRenderer {
                 None
             };
 
-            let mut chars_iterator = line_content.chars().peekable();
+            // A reused line's content is already in place
+            let content_to_render = if reused { "" } else { line_content.as_str() };
+            let mut chars_iterator = content_to_render.chars().peekable();
             while let Some(ch) = chars_iterator.next() {
                 // Get source byte for this character using character index
                 // (char_source_bytes is indexed by character position, not visual column)
@@ -2246,7 +2621,7 @@ impl SplitRenderer {
                         .get(display_char_idx)
                         .and_then(|s| s.as_ref());
                     let CharStyleOutput {
-                        style,
+                        mut style,
                         is_secondary_cursor,
                     } = compute_char_style(&CharStyleContext {
                         byte_pos,
@@ -2257,11 +2632,16 @@ impl SplitRenderer {
                         theme,
                         highlight_spans,
                         semantic_spans,
-                        viewport_overlays,
+                        overlay_styles,
                         primary_cursor_position,
                         is_active,
                     });
 
+                    let indent_guide = indent_guides
+                        .as_ref()
+                        .filter(|_| ch == ' ' && !is_cursor && !is_selected)
+                        .and_then(|guides| guides.guide_at(view_line_idx, col_offset));
+
                     // Determine display character (tabs already expanded in ViewLineIterator)
                     // Show tab indicator (→) at the start of tab expansions (if enabled for this language)
                     let tab_indicator: String;
@@ -2281,6 +2661,13 @@ impl SplitRenderer {
                         // Visual indicator for tab: show → at the first position
                         tab_indicator = "→".to_string();
                         &tab_indicator
+                    } else if let Some(is_active_guide) = indent_guide {
+                        style = style.fg(if is_active_guide {
+                            theme.indent_guide_active_fg
+                        } else {
+                            theme.indent_guide_fg
+                        });
+                        "│"
                     } else {
                         tab_indicator = ch.to_string();
                         &tab_indicator
@@ -2412,6 +2799,17 @@ impl SplitRenderer {
                 visible_char_count += ch_width;
             }
 
+            if let (Some((key, base)), Some(cache), false) =
+                (cache_key, line_cache.as_deref_mut(), reused)
+            {
+                cache.insert(
+                    key,
+                    base,
+                    &line_spans[margin_spans..],
+                    &line_view_map[margin_columns..],
+                );
+            }
+
             // Set last_seg_y early so cursor detection works for both empty and non-empty lines
             // For lines without wrapping, this will be the final y position
             // Also set for empty content lines (regardless of line_wrap) so cursor at EOF can be positioned
@@ -2574,31 +2972,24 @@ impl SplitRenderer {
                 let mut implicit_line_spans = Vec::new();
                 let implicit_line_num = current_source_line_num + 1;
 
-                if state.margins.left_config.enabled {
-                    // Indicator column (space)
-                    implicit_line_spans.push(Span::styled(" ", Style::default()));
-
-                    // Line number
-                    let estimated_lines = (state.buffer.len() / 80).max(1);
-                    let margin_content = state.margins.render_line(
-                        implicit_line_num,
-                        crate::view::margin::MarginPosition::Left,
+                let estimated_lines = (state.buffer.len() / 80).max(1);
+                let mut implicit_line_map = Vec::new();
+                render_left_margin(
+                    &LeftMarginContext {
+                        state,
+                        theme,
+                        is_continuation: false,
+                        current_source_line_num: implicit_line_num,
                         estimated_lines,
-                    );
-                    let (rendered_text, style_opt) =
-                        margin_content.render(state.margins.left_config.width);
-                    let margin_style =
-                        style_opt.unwrap_or_else(|| Style::default().fg(theme.line_number_fg));
-                    implicit_line_spans.push(Span::styled(rendered_text, margin_style));
-
-                    // Separator
-                    if state.margins.left_config.show_separator {
-                        implicit_line_spans.push(Span::styled(
-                            state.margins.left_config.separator.to_string(),
-                            Style::default().fg(theme.line_number_fg),
-                        ));
-                    }
-                }
+                        diagnostic_lines,
+                        line_indicators,
+                        bookmark_indicators,
+                        cursor_line,
+                        line_number_mode,
+                    },
+                    &mut implicit_line_spans,
+                    &mut implicit_line_map,
+                );
 
                 let implicit_y = lines.len() as u16;
                 lines.push(Line::from(implicit_line_spans));
@@ -2621,18 +3012,30 @@ impl SplitRenderer {
         // This also ensures proper clearing in differential rendering because tildes
         // are guaranteed to differ from previous content, forcing ratatui to update.
         // See: https://github.com/ratatui/ratatui/issues/1606
+        // While the rest of the file loads, the first of them says so instead.
         let eof_style = Style::default()
             .fg(theme.line_number_fg)
             .add_modifier(ratatui::style::Modifier::DIM);
+        let mut loading_marker = state.loading;
         while lines.len() < render_area.height as usize {
             // Show tilde with dim styling, padded with spaces to fill the line
+            let marker = if std::mem::take(&mut loading_marker) {
+                "loading…"
+            } else {
+                "~"
+            };
             let tilde_line = format!(
-                "~{}",
-                " ".repeat(render_area.width.saturating_sub(1) as usize)
+                "{}{}",
+                marker,
+                " ".repeat((render_area.width as usize).saturating_sub(marker.chars().count()))
             );
             lines.push(Line::styled(tilde_line, eof_style));
         }
 
+        if let Some(cache) = line_cache {
+            cache.end_frame();
+        }
+
         LineRenderOutput {
             lines,
             cursor: have_cursor.then_some((cursor_screen_x, cursor_screen_y)),
@@ -2675,21 +3078,33 @@ impl SplitRenderer {
         event_log: Option<&mut EventLog>,
         area: Rect,
         is_active: bool,
-        theme: &crate::view::theme::Theme,
-        ansi_background: Option<&AnsiBackground>,
-        background_fade: f32,
-        lsp_waiting: bool,
-        view_mode: ViewMode,
-        compose_width: Option<u16>,
-        compose_column_guides: Option<Vec<u16>>,
-        view_transform: Option<ViewTransformPayload>,
-        estimated_line_length: usize,
-        highlight_context_bytes: usize,
-        _buffer_id: BufferId,
-        hide_cursor: bool,
-        relative_line_numbers: bool,
+        view_prefs: ViewPreferences,
+        line_cache: &mut LineRenderCache,
+        ctx: &SplitRenderContext,
     ) -> Vec<ViewLineMapping> {
         let _span = tracing::trace_span!("render_buffer_in_split").entered();
+        let SplitRenderContext {
+            theme,
+            ansi_background,
+            background_fade,
+            lsp_waiting,
+            estimated_line_length,
+            highlight_context_bytes,
+            hide_cursor,
+            line_number_mode,
+            gutter_components,
+            indent_guides,
+            sticky_scroll,
+            zen_width,
+            ..
+        } = *ctx;
+        let ViewPreferences {
+            view_mode,
+            compose_width,
+            compose_column_guides,
+            view_transform,
+        } = view_prefs;
+        let compose_width = compose_width.or(zen_width);
 
         let line_wrap = viewport.line_wrap_enabled;
 
@@ -2702,11 +3117,15 @@ impl SplitRenderer {
 
         let buffer_len = state.buffer.len();
         let estimated_lines = (buffer_len / 80).max(1);
+        state.margins.set_gutter_components(gutter_components);
         state.margins.update_width_for_buffer(estimated_lines);
         let gutter_width = state.margins.left_total_width();
 
         let compose_layout = Self::calculate_compose_layout(area, &view_mode, compose_width);
         let render_area = compose_layout.render_area;
+        viewport.wrap_width = (render_area.width as usize)
+            .saturating_sub(gutter_width)
+            .max(1);
 
         // Clone view_transform so we can reuse it if scrolling triggers a rebuild
         let view_transform_for_rebuild = view_transform.clone();
@@ -2745,6 +3164,7 @@ impl SplitRenderer {
         };
 
         let view_anchor = Self::calculate_view_anchor(&view_data.lines, viewport.top_byte);
+        let first_rendered_line = view_anchor.start_line_idx;
         Self::render_compose_margins(frame, area, &compose_layout, &view_mode, theme);
 
         let selection = Self::selection_context(state);
@@ -2815,7 +3235,9 @@ impl SplitRenderer {
             line_wrap,
             estimated_lines,
             left_column: viewport.left_column,
-            relative_line_numbers,
+            line_number_mode,
+            indent_guides,
+            line_cache: Some(line_cache),
         });
 
         let mut lines = render_output.lines;
@@ -2840,6 +3262,28 @@ impl SplitRenderer {
             .style(Style::default().bg(theme.editor_bg));
         frame.render_widget(Paragraph::new(lines).block(editor_block), render_area);
 
+        // Tint ruler columns, leaving selections and other highlights alone
+        if !state.rulers.is_empty() {
+            let content_x = render_area.x as usize + gutter_width;
+            let area_end = (render_area.x + render_area.width) as usize;
+            let buf = frame.buffer_mut();
+            for &col in &state.rulers {
+                let Some(x) = (content_x + col).checked_sub(viewport.left_column) else {
+                    continue;
+                };
+                if x < content_x || x >= area_end {
+                    continue;
+                }
+                for y in render_area.y..render_area.y + render_area.height {
+                    if let Some(cell) = buf.cell_mut((x as u16, y)) {
+                        if cell.bg == theme.editor_bg {
+                            cell.set_bg(theme.ruler_bg);
+                        }
+                    }
+                }
+            }
+        }
+
         // Render column guides if present (for tables, etc.)
         if let Some(guides) = compose_column_guides {
             let guide_style = Style::default()
@@ -2881,6 +3325,22 @@ impl SplitRenderer {
             gutter_width,
         );
 
+        let sticky_headers = if sticky_scroll {
+            Self::render_sticky_scroll(
+                frame,
+                state,
+                &view_lines_to_render[first_rendered_line.min(view_lines_to_render.len())..],
+                render_area,
+                gutter_width,
+                viewport.left_column,
+                cursor.map(|(_, y)| y as usize),
+                highlight_context_bytes,
+                theme,
+            )
+        } else {
+            Vec::new()
+        };
+
         if is_active && state.show_cursors && !hide_cursor {
             if let Some((cursor_screen_x, cursor_screen_y)) = cursor {
                 // cursor_screen_x already includes gutter width from line_view_map
@@ -2899,7 +3359,143 @@ impl SplitRenderer {
 
         // Extract view line mappings for mouse click handling
         // This maps screen coordinates to buffer byte positions
-        Self::extract_view_line_mappings(view_lines_to_render)
+        let mut mappings = Self::extract_view_line_mappings(view_lines_to_render);
+
+        // Clicking a sticky scroll row jumps to its header line
+        for (mapping, &header) in mappings.iter_mut().zip(&sticky_headers) {
+            *mapping = ViewLineMapping {
+                char_source_bytes: vec![Some(header)],
+                visual_to_char: Vec::new(),
+                line_end_byte: header,
+            };
+        }
+        mappings
+    }
+
+    /// Draw the sticky scroll band over the top rows of the content area:
+    /// the header lines of the scopes enclosing the first line below it.
+    ///
+    /// Returns the byte offset of the header shown on each band row. Rows at
+    /// and below the cursor are never covered.
+    #[allow(clippy::too_many_arguments)]
+    fn render_sticky_scroll(
+        frame: &mut Frame,
+        state: &EditorState,
+        view_lines: &[ViewLine],
+        render_area: Rect,
+        gutter_width: usize,
+        left_column: usize,
+        cursor_row: Option<usize>,
+        context_bytes: usize,
+        theme: &crate::view::theme::Theme,
+    ) -> Vec<usize> {
+        let max_rows = (render_area.height as usize / 3).min(MAX_STICKY_SCROLL_LINES);
+        let language = state.highlighter.language();
+        let is_markdown = state.highlighter.syntax_name() == Some("Markdown");
+        if max_rows == 0 || (language.is_none() && !is_markdown) {
+            return Vec::new();
+        }
+
+        // Start of the source line shown on a screen row
+        let row_line_start = |row: usize| -> Option<usize> {
+            let line = view_lines
+                .get(..=row)?
+                .iter()
+                .rev()
+                .find(|line| should_show_line_number(line))
+                .or_else(|| view_lines.get(row))?;
+            line.char_source_bytes.iter().flatten().next().copied()
+        };
+
+        // The band hides the rows it covers, so the scopes are those of the
+        // first row below it; iterate until the band height settles
+        let mut headers: Vec<usize> = Vec::new();
+        for _ in 0..=max_rows {
+            let Some(position) = row_line_start(headers.len()) else {
+                break;
+            };
+            let mut next = enclosing_scope_headers(
+                &state.buffer,
+                language,
+                is_markdown,
+                position,
+                context_bytes,
+            );
+            next.truncate(max_rows);
+            let settled = next.len() == headers.len();
+            headers = next;
+            if settled {
+                break;
+            }
+        }
+        if let Some(cursor_row) = cursor_row {
+            headers.truncate(cursor_row);
+        }
+
+        let band_style = Style::default()
+            .fg(theme.editor_fg)
+            .bg(theme.current_line_bg);
+        let text_width = (render_area.width as usize).saturating_sub(gutter_width);
+        for (row, &header) in headers.iter().enumerate() {
+            let mut spans = Vec::new();
+            let margins = &state.margins;
+            if margins.left_total_width() > 0 {
+                let mut gutter = String::new();
+                for column in margins.gutter_columns() {
+                    let width = margins.gutter_column_width(column);
+                    match column {
+                        GutterColumn::Signs(_) => gutter.push_str(&" ".repeat(width)),
+                        GutterColumn::LineNumbers => gutter.push_str(&format!(
+                            "{:>width$}",
+                            state.buffer.get_line_number(header) + 1,
+                            width = width
+                        )),
+                    }
+                }
+                if margins.left_config.show_separator {
+                    gutter.push_str(&margins.left_config.separator);
+                }
+                spans.push(Span::styled(
+                    gutter,
+                    Style::default().fg(theme.line_number_fg),
+                ));
+            }
+
+            let end = (header + STICKY_SCROLL_MAX_LINE_BYTES).min(state.buffer.len());
+            let text = String::from_utf8_lossy(&state.buffer.slice_bytes(header..end)).into_owned();
+            let line = text.split(['\n', '\r']).next().unwrap_or("");
+            let mut expanded = String::new();
+            let mut column = 0;
+            for ch in line.chars() {
+                let width = if ch == '\t' {
+                    state.tab_size.max(1) - column % state.tab_size.max(1)
+                } else {
+                    char_width(ch)
+                };
+                if column >= left_column && column + width <= left_column + text_width {
+                    if ch == '\t' {
+                        expanded.push_str(&" ".repeat(width));
+                    } else {
+                        expanded.push(ch);
+                    }
+                }
+                column += width;
+            }
+            spans.push(Span::raw(expanded));
+
+            let row_area = Rect::new(
+                render_area.x,
+                render_area.y + row as u16,
+                render_area.width,
+                1,
+            );
+            frame.render_widget(Clear, row_area);
+            frame.render_widget(
+                Paragraph::new(Line::from(spans)).style(band_style),
+                row_area,
+            );
+        }
+        headers
     }
 
     /// Extract ViewLineMapping from rendered view lines
@@ -3055,24 +3651,51 @@ mod tests {
         content: &str,
         cursor_pos: usize,
         gutters_enabled: bool,
+    ) -> (LineRenderOutput, usize, bool, usize) {
+        render_output_for_with_options(content, cursor_pos, gutters_enabled, false)
+    }
+
+    fn render_output_for_with_options(
+        content: &str,
+        cursor_pos: usize,
+        gutters_enabled: bool,
+        indent_guides: bool,
     ) -> (LineRenderOutput, usize, bool, usize) {
         let mut state = EditorState::new(20, 6, 1024);
         state.buffer = Buffer::from_str(content, 1024);
         state.cursors.primary_mut().position = cursor_pos.min(state.buffer.len());
-        // Create a standalone viewport (no longer part of EditorState)
-        let viewport = Viewport::new(20, 4);
         // Enable/disable line numbers/gutters based on parameter
         state.margins.left_config.enabled = gutters_enabled;
 
+        let (output, primary_cursor_position) = render_state(&mut state, indent_guides, None);
+        (
+            output,
+            state.buffer.len(),
+            content.ends_with('\n'),
+            primary_cursor_position,
+        )
+    }
+
+    /// Render `state` into a 20x4 area, returning the output and the primary
+    /// cursor position
+    fn render_state(
+        state: &mut EditorState,
+        indent_guides: bool,
+        line_cache: Option<&mut LineRenderCache>,
+    ) -> (LineRenderOutput, usize) {
+        // Create a standalone viewport (no longer part of EditorState)
+        let viewport = Viewport::new(20, 4);
+        let content_len = state.buffer.len();
+
         let render_area = Rect::new(0, 0, 20, 4);
         let visible_count = viewport.visible_line_count();
         let gutter_width = state.margins.left_total_width();
 
         let view_data = SplitRenderer::build_view_data(
-            &mut state,
+            state,
             &viewport,
             None,
-            content.len().max(1),
+            content_len.max(1),
             visible_count,
             false, // line wrap disabled for tests
             render_area.width as usize,
@@ -3084,20 +3707,20 @@ mod tests {
         state.margins.update_width_for_buffer(estimated_lines);
         let gutter_width = state.margins.left_total_width();
 
-        let selection = SplitRenderer::selection_context(&state);
+        let selection = SplitRenderer::selection_context(state);
         let starting_line_num = state
             .buffer
             .populate_line_cache(viewport.top_byte, visible_count);
         let viewport_start = viewport.top_byte;
         let viewport_end = SplitRenderer::calculate_viewport_end(
-            &mut state,
+            state,
             viewport_start,
-            content.len().max(1),
+            content_len.max(1),
             visible_count,
         );
         let theme = Theme::default();
         let decorations = SplitRenderer::decoration_context(
-            &mut state,
+            state,
             viewport_start,
             viewport_end,
             selection.primary_cursor_position,
@@ -3106,7 +3729,7 @@ mod tests {
         );
 
         let output = SplitRenderer::render_view_lines(LineRenderInput {
-            state: &state,
+            state,
             theme: &theme,
             view_lines: &view_data.lines,
             view_anchor,
@@ -3121,15 +3744,38 @@ mod tests {
             line_wrap: viewport.line_wrap_enabled,
             estimated_lines,
             left_column: viewport.left_column,
-            relative_line_numbers: false,
+            line_number_mode: LineNumberMode::Absolute,
+            indent_guides,
+            line_cache,
         });
 
-        (
-            output,
-            state.buffer.len(),
-            content.ends_with('\n'),
-            selection.primary_cursor_position,
-        )
+        (output, selection.primary_cursor_position)
+    }
+
+    #[test]
+    fn line_cache_reuses_unchanged_lines() {
+        let mut state = EditorState::new(20, 6, 1024);
+        state.buffer = Buffer::from_str("one\ntwo\nthree\n", 1024);
+        let mut cache = LineRenderCache::default();
+
+        // The cursor's line is always rebuilt
+        let (first, _) = render_state(&mut state, false, Some(&mut cache));
+        assert_eq!(cache.stats(), (0, 2));
+        let (second, _) = render_state(&mut state, false, Some(&mut cache));
+        assert_eq!(cache.stats(), (2, 0));
+        assert_eq!(first.lines, second.lines);
+
+        // Lines moved by an edit above them are still reused
+        let cursor_id = state.cursors.primary_id();
+        state.apply(&crate::model::event::Event::Insert {
+            position: 0,
+            text: "x".to_string(),
+            cursor_id,
+        });
+        let (edited, _) = render_state(&mut state, false, Some(&mut cache));
+        assert_eq!(cache.stats(), (2, 0));
+        assert_eq!(edited.lines[1..3], first.lines[1..3]);
+        assert_ne!(edited.lines[0], first.lines[0]);
     }
 
     #[test]
@@ -3186,6 +3832,58 @@ mod tests {
         assert_eq!(cursor, Some((3, 0)));
     }
 
+    fn rendered_line_chars(output: &LineRenderOutput, line_idx: usize) -> Vec<(char, Style)> {
+        output.lines[line_idx]
+            .spans
+            .iter()
+            .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+            .collect()
+    }
+
+    #[test]
+    fn indent_guides_drawn_at_each_level() {
+        let content = "fn a() {\n    if x {\n        y\n    }\n";
+        let (output, ..) = render_output_for_with_options(content, 0, false, true);
+        let text = |idx| {
+            rendered_line_chars(&output, idx)
+                .into_iter()
+                .map(|(c, _)| c)
+                .collect::<String>()
+        };
+        assert_eq!(text(0), "fn a() {");
+        assert_eq!(text(1), "│   if x {");
+        assert_eq!(text(2), "│   │   y");
+        assert_eq!(text(3), "│   }");
+
+        let (output, ..) = render_output_for_with_options(content, 0, false, false);
+        assert!(!rendered_line_chars(&output, 2)
+            .iter()
+            .any(|(c, _)| *c == '│'));
+    }
+
+    #[test]
+    fn indent_guide_of_cursor_block_is_highlighted() {
+        let content = "fn a() {\n    if x {\n        y\n    }\n";
+        let theme = Theme::default();
+        let cursor = content.find('y').unwrap();
+        let (output, ..) = render_output_for_with_options(content, cursor, false, true);
+
+        let line = rendered_line_chars(&output, 2);
+        assert_eq!(line[0].1.fg, Some(theme.indent_guide_fg));
+        assert_eq!(line[4].1.fg, Some(theme.indent_guide_active_fg));
+        // The outer guide on other lines stays dim
+        let line = rendered_line_chars(&output, 1);
+        assert_eq!(line[0].1.fg, Some(theme.indent_guide_fg));
+
+        // Cursor on "if x {" highlights the outer guide across its block
+        let cursor = content.find("if").unwrap();
+        let (output, ..) = render_output_for_with_options(content, cursor, false, true);
+        for idx in 1..=3 {
+            let line = rendered_line_chars(&output, idx);
+            assert_eq!(line[0].1.fg, Some(theme.indent_guide_active_fg));
+        }
+    }
+
     // Helper to count all cursor positions in rendered output
     // Cursors can appear as:
     // 1. Primary cursor in output.cursor (hardware cursor position)
@@ -1,12 +1,15 @@
 //! Status bar and prompt/minibuffer rendering
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::path::Path;
 
 use crate::primitives::display_width::{char_width, str_width};
 use crate::state::EditorState;
-use crate::view::prompt::Prompt;
+use crate::view::overlay::priority;
+use crate::view::prompt::{Prompt, PromptType};
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
@@ -147,48 +150,177 @@ pub fn truncate_path(path: &Path, max_len: usize) -> TruncatedPath {
     }
 }
 
+/// Clickable parts of the status bar, each as `(row, start_col, end_col)`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatusBarAreas {
+    /// Language indicator
+    pub language: Option<(u16, u16, u16)>,
+    /// Indentation indicator
+    pub indentation: Option<(u16, u16, u16)>,
+    /// Background service health summary
+    pub health: Option<(u16, u16, u16)>,
+}
+
+/// What the status bar is drawn from, besides the active buffer
+pub struct StatusBarContext<'a> {
+    pub status_message: &'a Option<String>,
+    pub plugin_status_message: &'a Option<String>,
+    /// LSP status indicator
+    pub lsp_status: &'a str,
+    /// Git branch and working tree indicator
+    pub git_status: &'a str,
+    /// Running jobs and failed background services
+    pub health_summary: &'a str,
+    pub theme: &'a crate::view::theme::Theme,
+    /// The display name for the file (project-relative path)
+    pub display_name: &'a str,
+    pub keybindings: &'a crate::input::keybindings::KeybindingResolver,
+    /// Current chord sequence state (for multi-key bindings)
+    pub chord_state: &'a [(crossterm::event::KeyCode, crossterm::event::KeyModifiers)],
+    /// New version string if an update is available
+    pub update_available: Option<&'a str>,
+}
+
+/// Everything the status bar shows. It is drawn from this alone, so an
+/// unchanged status bar is found by comparing fingerprints of it (see
+/// [`DamageTracker`](super::DamageTracker)).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatusBarContent {
+    filename: String,
+    modified: bool,
+    line: usize,
+    col: usize,
+    /// Diagnostics by severity: errors, warnings, others
+    diagnostics: (usize, usize, usize),
+    cursor_count: usize,
+    lsp_status: String,
+    git_status: String,
+    health_summary: String,
+    /// Keys of the chord typed so far
+    chord: String,
+    /// Non-empty status messages, the editor's first
+    messages: Vec<String>,
+    update_available: Option<String>,
+    palette_shortcut: String,
+    language: String,
+    use_tabs: bool,
+    tab_size: usize,
+    colors: StatusBarColors,
+}
+
+/// Theme colors used by the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct StatusBarColors {
+    status_bar_fg: Color,
+    status_bar_bg: Color,
+    diagnostic_warning_fg: Color,
+    menu_highlight_fg: Color,
+    menu_dropdown_bg: Color,
+    help_indicator_fg: Color,
+    help_indicator_bg: Color,
+}
+
+impl StatusBarContent {
+    /// Gather what the status bar shows for the active buffer `state`
+    pub fn new(state: &mut EditorState, ctx: &StatusBarContext) -> Self {
+        let cursor = *state.primary_cursor();
+
+        // Find the start of the line containing the cursor; the line number
+        // is cached in the state
+        let line_start = state
+            .buffer
+            .line_iterator(cursor.position, 80)
+            .current_position();
+        let col = cursor.position.saturating_sub(line_start);
+
+        // Count diagnostics by severity, using the lsp-diagnostic namespace
+        // to identify diagnostic overlays
+        let mut diagnostics = (0, 0, 0);
+        let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
+        for overlay in state.overlays.all() {
+            if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                // The priority tells the severity (see `diagnostic_to_overlay`)
+                match overlay.priority {
+                    priority::DIAGNOSTIC_ERROR => diagnostics.0 += 1,
+                    priority::DIAGNOSTIC_WARNING => diagnostics.1 += 1,
+                    _ => diagnostics.2 += 1,
+                }
+            }
+        }
+
+        let chord = ctx
+            .chord_state
+            .iter()
+            .map(|(code, modifiers)| crate::input::keybindings::format_keybinding(code, modifiers))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let messages = [ctx.status_message, ctx.plugin_status_message]
+            .into_iter()
+            .flatten()
+            .filter(|msg| !msg.is_empty())
+            .cloned()
+            .collect();
+
+        let palette_shortcut = ctx
+            .keybindings
+            .get_keybinding_for_action(
+                &crate::input::keybindings::Action::CommandPalette,
+                crate::input::keybindings::KeyContext::Global,
+            )
+            .unwrap_or_else(|| "?".to_string());
+
+        let theme = ctx.theme;
+        Self {
+            filename: ctx.display_name.to_string(),
+            modified: state.buffer.is_modified(),
+            line: state.primary_cursor_line_number.value(),
+            col,
+            diagnostics,
+            cursor_count: state.cursors.count(),
+            lsp_status: ctx.lsp_status.to_string(),
+            git_status: ctx.git_status.to_string(),
+            health_summary: ctx.health_summary.to_string(),
+            chord,
+            messages,
+            update_available: ctx.update_available.map(str::to_string),
+            palette_shortcut,
+            language: state.syntax_display_name(),
+            use_tabs: state.use_tabs,
+            tab_size: state.tab_size,
+            colors: StatusBarColors {
+                status_bar_fg: theme.status_bar_fg,
+                status_bar_bg: theme.status_bar_bg,
+                diagnostic_warning_fg: theme.diagnostic_warning_fg,
+                menu_highlight_fg: theme.menu_highlight_fg,
+                menu_dropdown_bg: theme.menu_dropdown_bg,
+                help_indicator_fg: theme.help_indicator_fg,
+                help_indicator_bg: theme.help_indicator_bg,
+            },
+        }
+    }
+
+    /// Fingerprint of what the status bar shows
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 /// Renders the status bar and prompt/minibuffer
 pub struct StatusBarRenderer;
 
 impl StatusBarRenderer {
     /// Render only the status bar (without prompt)
     ///
-    /// # Arguments
-    /// * `frame` - The ratatui frame to render to
-    /// * `area` - The rectangular area to render in
-    /// * `state` - The active buffer's editor state
-    /// * `status_message` - Optional status message to display
-    /// * `lsp_status` - LSP status indicator
-    /// * `theme` - The active theme for colors
-    /// * `display_name` - The display name for the file (project-relative path)
-    /// * `chord_state` - Current chord sequence state (for multi-key bindings)
-    /// * `update_available` - Optional new version string if an update is available
+    /// Returns the positions of the clickable indicators that were shown.
     pub fn render_status_bar(
         frame: &mut Frame,
         area: Rect,
-        state: &mut EditorState,
-        status_message: &Option<String>,
-        plugin_status_message: &Option<String>,
-        lsp_status: &str,
-        theme: &crate::view::theme::Theme,
-        display_name: &str,
-        keybindings: &crate::input::keybindings::KeybindingResolver,
-        chord_state: &[(crossterm::event::KeyCode, crossterm::event::KeyModifiers)],
-        update_available: Option<&str>,
-    ) {
-        Self::render_status(
-            frame,
-            area,
-            state,
-            status_message,
-            plugin_status_message,
-            lsp_status,
-            theme,
-            display_name,
-            keybindings,
-            chord_state,
-            update_available,
-        );
+        content: &StatusBarContent,
+    ) -> StatusBarAreas {
+        Self::render_status(frame, area, content)
     }
 
     /// Render the prompt/minibuffer
@@ -203,6 +335,21 @@ impl StatusBarRenderer {
         // Create spans for the prompt
         let mut spans = vec![Span::styled(prompt.message.clone(), base_style)];
 
+        // Passwords are never echoed, only their length
+        if prompt.prompt_type == PromptType::SudoPassword {
+            spans.push(Span::styled(
+                "*".repeat(prompt.input.chars().count()),
+                base_style,
+            ));
+            frame.render_widget(Paragraph::new(Line::from(spans)).style(base_style), area);
+            let masked_cursor = prompt.input[..prompt.cursor_pos].chars().count();
+            let cursor_x = (prompt.message.len() + masked_cursor) as u16;
+            if cursor_x < area.width {
+                frame.set_cursor_position((area.x + cursor_x, area.y));
+            }
+            return;
+        }
+
         // If there's a selection, split the input into parts
         if let Some((sel_start, sel_end)) = prompt.selection_range() {
             let input = &prompt.input;
@@ -340,83 +487,21 @@ impl StatusBarRenderer {
     }
 
     /// Render the normal status bar
-    fn render_status(
-        frame: &mut Frame,
-        area: Rect,
-        state: &mut EditorState,
-        status_message: &Option<String>,
-        plugin_status_message: &Option<String>,
-        lsp_status: &str,
-        theme: &crate::view::theme::Theme,
-        display_name: &str,
-        keybindings: &crate::input::keybindings::KeybindingResolver,
-        chord_state: &[(crossterm::event::KeyCode, crossterm::event::KeyModifiers)],
-        update_available: Option<&str>,
-    ) {
-        // Use the pre-computed display name from buffer metadata
-        let filename = display_name;
-
-        let modified = if state.buffer.is_modified() {
-            " [+]"
-        } else {
-            ""
-        };
+    fn render_status(frame: &mut Frame, area: Rect, content: &StatusBarContent) -> StatusBarAreas {
+        let theme = &content.colors;
+        let filename = &content.filename;
+        let modified = if content.modified { " [+]" } else { "" };
+        let (line, col) = (content.line, content.col);
 
         // Format chord state if present
-        let chord_display = if !chord_state.is_empty() {
-            let chord_str = chord_state
-                .iter()
-                .map(|(code, modifiers)| {
-                    crate::input::keybindings::format_keybinding(code, modifiers)
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
-            format!(" [{}]", chord_str)
+        let chord_display = if !content.chord.is_empty() {
+            format!(" [{}]", content.chord)
         } else {
             String::new()
         };
 
-        // View mode indicator
-        let _mode_label = match state.view_mode {
-            crate::state::ViewMode::Compose => " | Compose",
-            _ => "",
-        };
-
-        let cursor = *state.primary_cursor();
-
-        // Get line number and column efficiently using cached values
-        let (line, col) = {
-            // Find the start of the line containing the cursor
-            let cursor_iter = state.buffer.line_iterator(cursor.position, 80);
-            let line_start = cursor_iter.current_position();
-            let col = cursor.position.saturating_sub(line_start);
-
-            // Use cached line number from state
-            let line_num = state.primary_cursor_line_number.value();
-            (line_num, col)
-        };
-
-        // Count diagnostics by severity
-        let diagnostics = state.overlays.all();
-        let mut error_count = 0;
-        let mut warning_count = 0;
-        let mut info_count = 0;
-
-        // Use the lsp-diagnostic namespace to identify diagnostic overlays
-        let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
-        for overlay in diagnostics {
-            if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                // Check priority to determine severity
-                // Based on lsp_diagnostics.rs: Error=100, Warning=50, Info=30, Hint=10
-                match overlay.priority {
-                    100 => error_count += 1,
-                    50 => warning_count += 1,
-                    _ => info_count += 1,
-                }
-            }
-        }
-
         // Build diagnostics summary if there are any
+        let (error_count, warning_count, info_count) = content.diagnostics;
         let diagnostics_summary = if error_count + warning_count + info_count > 0 {
             let mut parts = Vec::new();
             if error_count > 0 {
@@ -434,61 +519,106 @@ impl StatusBarRenderer {
         };
 
         // Build cursor count indicator (only show if multiple cursors)
-        let cursor_count_indicator = if state.cursors.count() > 1 {
-            format!(" | {} cursors", state.cursors.count())
+        let cursor_count_indicator = if content.cursor_count > 1 {
+            format!(" | {} cursors", content.cursor_count)
         } else {
             String::new()
         };
 
         // Build the status string with optional LSP status and status message
-        let lsp_indicator = if !lsp_status.is_empty() {
-            format!(" | {}", lsp_status)
+        let lsp_indicator = if !content.lsp_status.is_empty() {
+            format!(" | {}", content.lsp_status)
         } else {
             String::new()
         };
 
-        let mut message_parts: Vec<&str> = Vec::new();
-        if let Some(msg) = status_message {
-            if !msg.is_empty() {
-                message_parts.push(msg);
-            }
-        }
-        if let Some(msg) = plugin_status_message {
-            if !msg.is_empty() {
-                message_parts.push(msg);
-            }
-        }
+        let git_indicator = if !content.git_status.is_empty() {
+            format!(" | {}", content.git_status)
+        } else {
+            String::new()
+        };
 
-        let message_suffix = if message_parts.is_empty() {
+        let message_suffix = if content.messages.is_empty() {
             String::new()
         } else {
-            format!(" | {}", message_parts.join(" | "))
+            format!(" | {}", content.messages.join(" | "))
         };
 
         let base_status = format!(
-            "{filename}{modified} | Ln {line}, Col {col}{diagnostics_summary}{cursor_count_indicator}{lsp_indicator}"
+            "{filename}{modified} | Ln {line}, Col {col}{diagnostics_summary}{cursor_count_indicator}{git_indicator}{lsp_indicator}"
         );
         let left_status = format!("{base_status}{chord_display}{message_suffix}");
 
         // Build update indicator for right side (if update available)
-        let update_indicator = update_available.map(|version| format!(" Update: v{} ", version));
+        let update_indicator = content
+            .update_available
+            .as_ref()
+            .map(|version| format!(" Update: v{} ", version));
         let update_width = update_indicator.as_ref().map(|s| s.len()).unwrap_or(0);
 
         // Build Command Palette indicator for right side
         // Always show Command Palette indicator on the right side
-        let cmd_palette_shortcut = keybindings
-            .get_keybinding_for_action(
-                &crate::input::keybindings::Action::CommandPalette,
-                crate::input::keybindings::KeyContext::Global,
-            )
-            .unwrap_or_else(|| "?".to_string());
-        let cmd_palette_indicator = format!("Palette: {}", cmd_palette_shortcut);
+        let cmd_palette_indicator = format!("Palette: {}", content.palette_shortcut);
         let padded_cmd_palette = format!(" {} ", cmd_palette_indicator);
 
         // Calculate available width - reserve space for right side indicators
         let available_width = area.width as usize;
         let cmd_palette_width = padded_cmd_palette.len();
-        let right_side_width = update_width + cmd_palette_width;
+
+        // Build language indicator for right side (clickable to change language mode).
+        // It gives way to the status message when both don't fit.
+        let language_indicator = format!(" {} ", content.language);
+        let language_width = if str_width(&left_status)
+            + str_width(&language_indicator)
+            + update_width
+            + cmd_palette_width
+            < available_width
+        {
+            str_width(&language_indicator)
+        } else {
+            0
+        };
+        let mut areas = StatusBarAreas::default();
+
+        // Build health indicator for right side (clickable to show details),
+        // shown only if it fits as well
+        let health_indicator = format!(" {} ", content.health_summary);
+        let health_width = if !content.health_summary.is_empty()
+            && str_width(&left_status)
+                + str_width(&health_indicator)
+                + language_width
+                + update_width
+                + cmd_palette_width
+                < available_width
+        {
+            str_width(&health_indicator)
+        } else {
+            0
+        };
+
+        // Build indentation indicator for right side (clickable to change it),
+        // shown only if it fits as well; it gives way first
+        let indentation_indicator = if content.use_tabs {
+            format!(" Tabs: {} ", content.tab_size)
+        } else {
+            format!(" Spaces: {} ", content.tab_size)
+        };
+        let indentation_width = if language_width > 0
+            && str_width(&left_status)
+                + str_width(&indentation_indicator)
+                + health_width
+                + language_width
+                + update_width
+                + cmd_palette_width
+                < available_width
+        {
+            str_width(&indentation_indicator)
+        } else {
+            0
+        };
+
+        let right_side_width =
+            health_width + indentation_width + language_width + update_width + cmd_palette_width;
 
         // Only show command palette indicator if there's enough space (at least 15 chars for minimal display)
         let spans = if available_width >= 15 {
@@ -556,6 +686,62 @@ impl StatusBarRenderer {
                 ));
             }
 
+            // Add health indicator
+            if health_width > 0 {
+                let health_start = area.x
+                    + spans
+                        .iter()
+                        .map(|span| str_width(&span.content))
+                        .sum::<usize>() as u16;
+                areas.health = Some((area.y, health_start, health_start + health_width as u16));
+                spans.push(Span::styled(
+                    health_indicator.clone(),
+                    Style::default()
+                        .fg(theme.diagnostic_warning_fg)
+                        .bg(theme.status_bar_bg),
+                ));
+            }
+
+            // Add indentation indicator
+            if indentation_width > 0 {
+                let indentation_start = area.x
+                    + spans
+                        .iter()
+                        .map(|span| str_width(&span.content))
+                        .sum::<usize>() as u16;
+                areas.indentation = Some((
+                    area.y,
+                    indentation_start,
+                    indentation_start + indentation_width as u16,
+                ));
+                spans.push(Span::styled(
+                    indentation_indicator.clone(),
+                    Style::default()
+                        .fg(theme.status_bar_fg)
+                        .bg(theme.status_bar_bg),
+                ));
+            }
+
+            // Add language indicator
+            if language_width > 0 {
+                let language_start = area.x
+                    + spans
+                        .iter()
+                        .map(|span| str_width(&span.content))
+                        .sum::<usize>() as u16;
+                areas.language = Some((
+                    area.y,
+                    language_start,
+                    language_start + language_width as u16,
+                ));
+                spans.push(Span::styled(
+                    language_indicator.clone(),
+                    Style::default()
+                        .fg(theme.status_bar_fg)
+                        .bg(theme.status_bar_bg),
+                ));
+            }
+
             // Add update indicator if available (with highlighted styling)
             if let Some(ref update_text) = update_indicator {
                 spans.push(Span::styled(
@@ -661,6 +847,8 @@ impl StatusBarRenderer {
         let status_line = Paragraph::new(Line::from(spans));
 
         frame.render_widget(status_line, area);
+
+        areas
     }
 
     /// Render the search options bar (shown when search prompt is active)
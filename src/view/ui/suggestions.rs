@@ -1,8 +1,10 @@
 //! Autocomplete suggestions and command palette UI rendering
 
+use crate::input::command_palette;
 use crate::input::commands::CommandSource;
+use crate::input::fuzzy::fuzzy_match;
 use crate::primitives::display_width::{char_width, str_width};
-use crate::view::prompt::Prompt;
+use crate::view::prompt::{Prompt, PromptType};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -81,7 +83,13 @@ impl SuggestionsRenderer {
 
         let visible_suggestions = &prompt.suggestions[start_idx..end_idx];
 
-        // Fixed column layout: "  Name  |  Keybinding  |  Description  |  Source"
+        // Characters of the names matching the palette query are highlighted
+        let highlight_query = match prompt.prompt_type {
+            PromptType::Command => command_palette::parse_query(&prompt.input).1,
+            _ => "",
+        };
+
+        // Fixed column layout: "  Name  |  Description  |  Keybinding  |  Source"
         let left_margin = 2;
         let column_spacing = 2;
         let available_width = inner_area.width as usize;
@@ -158,81 +166,52 @@ impl SuggestionsRenderer {
             } else {
                 name.clone()
             };
-            spans.push(Span::styled(name_text.clone(), base_style));
-            let name_display_width = str_width(&name_text);
-            let name_padding = name_column_width.saturating_sub(name_display_width);
-            if name_padding > 0 {
-                spans.push(Span::styled(" ".repeat(name_padding), base_style));
-            }
-
-            // Spacing before keybinding column
-            spans.push(Span::styled(" ".repeat(column_spacing), base_style));
-
-            // Column 2: Keyboard shortcut (fixed width)
-            let keybinding_style = if suggestion.disabled {
-                base_style
-            } else if is_selected {
-                Style::default()
-                    .fg(theme.help_key_fg)
-                    .bg(theme.suggestion_selected_bg)
-            } else if is_hovered {
-                Style::default()
-                    .fg(theme.help_key_fg)
-                    .bg(theme.menu_hover_bg)
+            let match_positions = if highlight_query.is_empty() {
+                Vec::new()
             } else {
-                Style::default()
-                    .fg(theme.line_number_fg)
-                    .bg(theme.suggestion_bg)
+                fuzzy_match(highlight_query, name).match_positions
             };
-
-            if let Some(keybinding) = &suggestion.keybinding {
-                let kb_visual_width = str_width(keybinding);
-                let kb_text = if kb_visual_width > keybinding_column_width {
-                    // Truncate keybinding by visual width
-                    let mut width = 0;
-                    keybinding
-                        .chars()
-                        .take_while(|ch| {
-                            let w = char_width(*ch);
-                            if width + w <= keybinding_column_width {
-                                width += w;
-                                true
-                            } else {
-                                false
-                            }
-                        })
-                        .collect()
+            if match_positions.is_empty() {
+                spans.push(Span::styled(name_text.clone(), base_style));
+            } else {
+                let match_style = if suggestion.disabled {
+                    base_style
                 } else {
-                    keybinding.clone()
+                    base_style
+                        .fg(theme.help_key_fg)
+                        .add_modifier(Modifier::BOLD)
                 };
-                spans.push(Span::styled(kb_text.clone(), keybinding_style));
-                let kb_display_width = str_width(&kb_text);
-                let kb_padding = keybinding_column_width.saturating_sub(kb_display_width);
-                if kb_padding > 0 {
-                    spans.push(Span::styled(" ".repeat(kb_padding), base_style));
+                let mut run = String::new();
+                let mut run_matched = false;
+                for (idx, ch) in name_text.chars().enumerate() {
+                    let matched = match_positions.contains(&idx);
+                    if matched != run_matched && !run.is_empty() {
+                        let style = if run_matched { match_style } else { base_style };
+                        spans.push(Span::styled(std::mem::take(&mut run), style));
+                    }
+                    run_matched = matched;
+                    run.push(ch);
                 }
-            } else {
-                // No keybinding for this command, pad the column
-                spans.push(Span::styled(
-                    " ".repeat(keybinding_column_width),
-                    base_style,
-                ));
+                let style = if run_matched { match_style } else { base_style };
+                spans.push(Span::styled(run, style));
+            }
+            let name_display_width = str_width(&name_text);
+            let name_padding = name_column_width.saturating_sub(name_display_width);
+            if name_padding > 0 {
+                spans.push(Span::styled(" ".repeat(name_padding), base_style));
             }
 
             // Spacing before description column
             spans.push(Span::styled(" ".repeat(column_spacing), base_style));
 
             // Calculate space used by fixed columns
-            let fixed_columns_width = left_margin
-                + name_column_width
-                + column_spacing
-                + keybinding_column_width
-                + column_spacing;
+            let fixed_columns_width = left_margin + name_column_width + column_spacing;
 
-            // Reserve space for source column at the end
-            let source_reserved = column_spacing + source_column_width;
+            // Reserve space for keybinding and source columns at the end
+            let source_reserved =
+                column_spacing + keybinding_column_width + column_spacing + source_column_width;
 
-            // Column 3: Description (flexible width, leaves room for source)
+            // Column 2: Description (flexible width, leaves room for keybinding and source)
             if let Some(desc) = &suggestion.description {
                 // Only show description if we have enough space
                 if fixed_columns_width + source_reserved < available_width {
@@ -279,6 +258,60 @@ impl SuggestionsRenderer {
                 }
             }
 
+            // Spacing before keybinding column
+            spans.push(Span::styled(" ".repeat(column_spacing), base_style));
+
+            // Column 3: Keyboard shortcut (right-aligned, fixed width)
+            let keybinding_style = if suggestion.disabled {
+                base_style
+            } else if is_selected {
+                Style::default()
+                    .fg(theme.help_key_fg)
+                    .bg(theme.suggestion_selected_bg)
+            } else if is_hovered {
+                Style::default()
+                    .fg(theme.help_key_fg)
+                    .bg(theme.menu_hover_bg)
+            } else {
+                Style::default()
+                    .fg(theme.line_number_fg)
+                    .bg(theme.suggestion_bg)
+            };
+
+            if let Some(keybinding) = &suggestion.keybinding {
+                let kb_visual_width = str_width(keybinding);
+                let kb_text = if kb_visual_width > keybinding_column_width {
+                    // Truncate keybinding by visual width
+                    let mut width = 0;
+                    keybinding
+                        .chars()
+                        .take_while(|ch| {
+                            let w = char_width(*ch);
+                            if width + w <= keybinding_column_width {
+                                width += w;
+                                true
+                            } else {
+                                false
+                            }
+                        })
+                        .collect()
+                } else {
+                    keybinding.clone()
+                };
+                let kb_display_width = str_width(&kb_text);
+                let kb_padding = keybinding_column_width.saturating_sub(kb_display_width);
+                if kb_padding > 0 {
+                    spans.push(Span::styled(" ".repeat(kb_padding), base_style));
+                }
+                spans.push(Span::styled(kb_text, keybinding_style));
+            } else {
+                // No keybinding for this command, pad the column
+                spans.push(Span::styled(
+                    " ".repeat(keybinding_column_width),
+                    base_style,
+                ));
+            }
+
             // Spacing before source column
             spans.push(Span::styled(" ".repeat(column_spacing), base_style));
 
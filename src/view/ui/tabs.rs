@@ -4,16 +4,62 @@ use crate::app::BufferMetadata;
 use crate::model::event::BufferId;
 use crate::primitives::display_width::str_width;
 use crate::state::EditorState;
+use crate::view::controls::{DropdownLayout, DropdownState};
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::{Block, Clear, Paragraph};
 use ratatui::Frame;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 /// Renders the tab bar showing open buffers
 pub struct TabsRenderer;
 
+/// What the tab bar of a split is drawn from
+pub struct TabBarContext<'a> {
+    /// Buffers open in the split, in order
+    pub split_buffers: &'a [BufferId],
+    /// Pinned tabs of the split, drawn with a pin marker
+    pub pinned: &'a [BufferId],
+    /// All open buffers (for accessing state/metadata)
+    pub buffers: &'a HashMap<BufferId, EditorState>,
+    /// Metadata for buffers (contains display names for virtual buffers)
+    pub buffer_metadata: &'a HashMap<BufferId, BufferMetadata>,
+    /// The buffer shown in the split
+    pub active_buffer: BufferId,
+    pub theme: &'a crate::view::theme::Theme,
+    /// Whether the split is the active one
+    pub is_active_split: bool,
+    pub tab_scroll_offset: usize,
+    /// (buffer_id, is_close_button) of the hovered tab
+    pub hovered_tab: Option<(BufferId, bool)>,
+    /// Columns at the right end drawn over by the caller
+    pub reserved_right: u16,
+}
+
+/// Overflow button drawn at the right end of a tab bar that doesn't fit
+const OVERFLOW_BUTTON: &str = " ⋯ ";
+const OVERFLOW_BUTTON_WIDTH: u16 = 3;
+
+/// Width of the close button ("× ") ending each tab
+const TAB_CLOSE_WIDTH: usize = 2;
+
+/// Marker drawn before the name of a pinned tab
+const PIN_MARKER: &str = "⚑ ";
+
+/// Hit areas of a rendered tab bar, as absolute screen columns
+#[derive(Debug, Clone, Default)]
+pub struct TabBarHitAreas {
+    /// (buffer_id, tab_start_col, tab_end_col, close_start_col) for each visible tab
+    pub tabs: Vec<(BufferId, u16, u16, u16)>,
+    /// (start_col, end_col) of the overflow button, when the tabs don't fit
+    pub overflow_button: Option<(u16, u16)>,
+    /// Tabs that are scrolled out of view or cut off, in tab order
+    pub hidden: Vec<BufferId>,
+}
+
 /// Compute a scroll offset that keeps the active tab fully visible.
 /// `tab_widths` should include separators; `active_idx` refers to the tab index (not counting separators).
 pub fn compute_tab_scroll_offset(
@@ -88,34 +134,242 @@ mod tests {
 }
 
 impl TabsRenderer {
+    /// Fingerprint of everything [`Self::render_for_split`] shows for the same
+    /// context, so an unchanged tab bar is not drawn again (see
+    /// [`DamageTracker`](super::DamageTracker))
+    pub fn fingerprint(ctx: &TabBarContext) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (
+            ctx.active_buffer,
+            ctx.is_active_split,
+            ctx.tab_scroll_offset,
+            ctx.hovered_tab,
+            ctx.reserved_right,
+        )
+            .hash(&mut hasher);
+        let theme = ctx.theme;
+        (
+            theme.tab_active_fg,
+            theme.tab_active_bg,
+            theme.tab_inactive_fg,
+            theme.tab_inactive_bg,
+            theme.tab_hover_bg,
+            theme.tab_close_hover_fg,
+            theme.tab_separator_bg,
+        )
+            .hash(&mut hasher);
+        // The tabs as drawn, text included
+        for id in ctx.split_buffers {
+            let pinned = ctx.pinned.contains(id);
+            (
+                id,
+                Self::tab_text(*id, ctx.buffers, ctx.buffer_metadata, pinned),
+            )
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Text shown on the tab of a buffer: ` {pin}{name}{modified}{binary_indicator} `
+    ///
+    /// Returns `None` for a buffer that is not open.
+    fn tab_text(
+        id: BufferId,
+        buffers: &HashMap<BufferId, EditorState>,
+        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
+        pinned: bool,
+    ) -> Option<String> {
+        let state = buffers.get(&id)?;
+        let meta = buffer_metadata.get(&id);
+        let is_terminal = meta
+            .and_then(|m| m.virtual_mode())
+            .map(|mode| mode == "terminal")
+            .unwrap_or(false);
+
+        let name = if is_terminal {
+            meta.map(|m| m.display_name.as_str())
+        } else {
+            state
+                .buffer
+                .file_path()
+                .and_then(|p| p.file_name())
+                .and_then(|n| n.to_str())
+                .or_else(|| meta.map(|m| m.display_name.as_str()))
+        }
+        .unwrap_or("[No Name]");
+
+        let modified = if state.buffer.is_modified() { "*" } else { "" };
+        let binary_indicator = if meta.map(|m| m.binary).unwrap_or(false) {
+            " [BIN]"
+        } else {
+            ""
+        };
+
+        let pin = if pinned { PIN_MARKER } else { "" };
+
+        Some(format!(" {pin}{name}{modified}{binary_indicator} "))
+    }
+
+    /// Name of a buffer as listed in the tab overflow dropdown
+    pub fn tab_title(
+        id: BufferId,
+        buffers: &HashMap<BufferId, EditorState>,
+        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
+    ) -> Option<String> {
+        Self::tab_text(id, buffers, buffer_metadata, false).map(|text| text.trim().to_string())
+    }
+
     /// Render the tab bar for a specific split showing only its open buffers
     ///
-    /// # Arguments
-    /// * `frame` - The ratatui frame to render to
-    /// * `area` - The rectangular area to render the tabs in
-    /// * `split_buffers` - List of buffer IDs open in this split (in order)
-    /// * `buffers` - All open buffers (for accessing state/metadata)
-    /// * `buffer_metadata` - Metadata for buffers (contains display names for virtual buffers)
-    /// * `active_buffer` - The currently active buffer ID for this split
-    /// * `theme` - The active theme for colors
-    /// * `is_active_split` - Whether this split is the active one
-    /// * `hovered_tab` - Optional (buffer_id, is_close_button) if a tab is being hovered
+    /// When the tabs don't fit, the bar scrolls to keep the active tab in view
+    /// and an overflow button ("⋯") is drawn at its right end, just left of the
+    /// `reserved_right` columns the caller keeps for the split buttons.
+    ///
+    /// # Returns
+    /// Hit areas of the visible tabs and the overflow button, as absolute
+    /// screen columns, along with the tabs that are not fully visible.
+    pub fn render_for_split(frame: &mut Frame, area: Rect, ctx: &TabBarContext) -> TabBarHitAreas {
+        let TabBarContext {
+            split_buffers,
+            pinned,
+            buffers,
+            buffer_metadata,
+            theme,
+            reserved_right,
+            ..
+        } = *ctx;
+        // Full width of each tab, counting its close button
+        let tab_widths: Vec<(BufferId, usize)> = split_buffers
+            .iter()
+            .filter_map(|id| {
+                Self::tab_text(*id, buffers, buffer_metadata, pinned.contains(id))
+                    .map(|text| (*id, str_width(&text) + TAB_CLOSE_WIDTH))
+            })
+            .collect();
+        let total_width =
+            tab_widths.iter().map(|(_, w)| w).sum::<usize>() + tab_widths.len().saturating_sub(1);
+
+        let room = area.width.saturating_sub(reserved_right);
+        let overflows = total_width > room as usize && room > OVERFLOW_BUTTON_WIDTH;
+        let tabs_area = if overflows {
+            Rect {
+                width: room - OVERFLOW_BUTTON_WIDTH,
+                ..area
+            }
+        } else {
+            area
+        };
+
+        let tabs = Self::render_tabs(frame, tabs_area, ctx);
+
+        let mut overflow_button = None;
+        if overflows {
+            let button_x = tabs_area.x + tabs_area.width;
+            let style = Style::default()
+                .fg(theme.tab_inactive_fg)
+                .bg(theme.tab_separator_bg);
+            let line = Line::from(vec![
+                Span::styled(OVERFLOW_BUTTON, style),
+                Span::styled(
+                    " ".repeat(reserved_right as usize),
+                    Style::default().bg(theme.tab_separator_bg),
+                ),
+            ]);
+            let rest = Rect::new(button_x, area.y, area.width - tabs_area.width, 1);
+            frame.render_widget(Paragraph::new(line), rest);
+            overflow_button = Some((button_x, button_x + OVERFLOW_BUTTON_WIDTH));
+        }
+
+        let hidden = tab_widths
+            .iter()
+            .filter(|(id, width)| {
+                !tabs
+                    .iter()
+                    .any(|(tab, start, end, _)| tab == id && (end - start) as usize >= *width)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        TabBarHitAreas {
+            tabs,
+            overflow_button,
+            hidden,
+        }
+    }
+
+    /// Render the list of hidden tabs opened from the overflow button,
+    /// right-aligned under the button
+    ///
+    /// # Returns
+    /// Layout information for hit testing
+    pub fn render_overflow_menu(
+        frame: &mut Frame,
+        button_area: Rect,
+        state: &DropdownState,
+        theme: &crate::view::theme::Theme,
+    ) -> DropdownLayout {
+        let screen = frame.area();
+        let text_width = state
+            .options
+            .iter()
+            .map(|option| str_width(option))
+            .max()
+            .unwrap_or(0);
+        let width = ((text_width + 2) as u16).min(screen.width);
+        let x = (button_area.x + button_area.width)
+            .saturating_sub(width)
+            .min(screen.width.saturating_sub(width));
+        let top = button_area.y + 1;
+        let rows = (state.options.len() as u16).min(screen.height.saturating_sub(top));
+
+        let mut option_areas = Vec::new();
+        for (i, option) in state.options.iter().take(rows as usize).enumerate() {
+            let option_area = Rect::new(x, top + i as u16, width, 1);
+            let style = if i == state.selected {
+                Style::default()
+                    .fg(theme.menu_highlight_fg)
+                    .bg(theme.menu_highlight_bg)
+            } else {
+                Style::default()
+                    .fg(theme.menu_dropdown_fg)
+                    .bg(theme.menu_dropdown_bg)
+            };
+            let padding = (width as usize).saturating_sub(str_width(option) + 1);
+            let text = format!(" {option}{}", " ".repeat(padding));
+            frame.render_widget(Clear, option_area);
+            frame.render_widget(Paragraph::new(Span::styled(text, style)), option_area);
+            option_areas.push(option_area);
+        }
+
+        DropdownLayout {
+            button_area,
+            option_areas,
+            full_area: Rect::new(x, button_area.y, width, rows + 1).union(button_area),
+        }
+    }
+
+    /// Render the tabs that fit in `area`, scrolled so the active one is shown
     ///
     /// # Returns
     /// Vec of (buffer_id, tab_start_col, tab_end_col, close_start_col) for each visible tab.
     /// These are absolute screen column positions for hit testing.
-    pub fn render_for_split(
+    fn render_tabs(
         frame: &mut Frame,
         area: Rect,
-        split_buffers: &[BufferId],
-        buffers: &HashMap<BufferId, EditorState>,
-        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
-        active_buffer: BufferId,
-        theme: &crate::view::theme::Theme,
-        is_active_split: bool,
-        tab_scroll_offset: usize,
-        hovered_tab: Option<(BufferId, bool)>, // (buffer_id, is_close_button)
+        ctx: &TabBarContext,
     ) -> Vec<(BufferId, u16, u16, u16)> {
+        let TabBarContext {
+            split_buffers,
+            pinned,
+            buffers,
+            buffer_metadata,
+            active_buffer,
+            theme,
+            is_active_split,
+            tab_scroll_offset,
+            hovered_tab,
+            ..
+        } = *ctx;
         const SCROLL_INDICATOR_LEFT: &str = "<";
         const SCROLL_INDICATOR_RIGHT: &str = ">";
         const SCROLL_INDICATOR_WIDTH: usize = 1; // Width of "<" or ">"
@@ -126,36 +380,13 @@ impl TabsRenderer {
 
         // First, build all spans and calculate their display widths
         for (idx, id) in split_buffers.iter().enumerate() {
-            let Some(state) = buffers.get(id) else {
+            let Some(tab_name_text) =
+                Self::tab_text(*id, buffers, buffer_metadata, pinned.contains(id))
+            else {
                 continue;
             };
             rendered_buffer_ids.push(*id);
 
-            let meta = buffer_metadata.get(id);
-            let is_terminal = meta
-                .and_then(|m| m.virtual_mode())
-                .map(|mode| mode == "terminal")
-                .unwrap_or(false);
-
-            let name = if is_terminal {
-                meta.map(|m| m.display_name.as_str())
-            } else {
-                state
-                    .buffer
-                    .file_path()
-                    .and_then(|p| p.file_name())
-                    .and_then(|n| n.to_str())
-                    .or_else(|| meta.map(|m| m.display_name.as_str()))
-            }
-            .unwrap_or("[No Name]");
-
-            let modified = if state.buffer.is_modified() { "*" } else { "" };
-            let binary_indicator = if buffer_metadata.get(id).map(|m| m.binary).unwrap_or(false) {
-                " [BIN]"
-            } else {
-                ""
-            };
-
             let is_active = *id == active_buffer;
 
             // Check hover state for this tab
@@ -196,8 +427,6 @@ impl TabsRenderer {
                 base_style
             };
 
-            // Build tab content: " {name}{modified}{binary_indicator} "
-            let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
             let tab_name_width = str_width(&tab_name_text);
 
             // Close button: "× "
@@ -433,14 +662,18 @@ impl TabsRenderer {
         Self::render_for_split(
             frame,
             area,
-            &buffer_ids,
-            buffers,
-            buffer_metadata,
-            active_buffer,
-            theme,
-            true, // Legacy behavior: always treat as active
-            0,    // Default tab_scroll_offset for legacy render
-            None, // No hover state for legacy render
+            &TabBarContext {
+                split_buffers: &buffer_ids,
+                pinned: &[],
+                buffers,
+                buffer_metadata,
+                active_buffer,
+                theme,
+                is_active_split: true, // Legacy behavior: always treat as active
+                tab_scroll_offset: 0,
+                hovered_tab: None,
+                reserved_right: 0,
+            },
         );
     }
 }
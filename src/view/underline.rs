@@ -0,0 +1,300 @@
+//! Curly, dotted and dashed underlines.
+//!
+//! ratatui only knows a plain underline. The style of an underline is kept
+//! in modifier bits ratatui doesn't use (see [`underline_modifier`]), so it
+//! survives layout and diffing like any other part of a cell's style; the
+//! color goes in the cell's underline color. [`UnderlineBackend`] wraps the
+//! crossterm backend: after a frame is drawn, it draws the cells with a
+//! styled underline again using the extended underline sequences
+//! (`CSI 4:3 m` for curly and so on).
+//!
+//! Terminals that don't know these sequences may show them as a different
+//! attribute or ignore the whole line, so they are only sent to terminals
+//! known to support them. Elsewhere every style falls back to a plain
+//! underline, still in its own color.
+//!
+//! Can be overridden with the `FRESH_UNDERLINE_STYLES` env var: "on" or
+//! "off".
+
+use crate::view::overlay::UnderlineStyle;
+use ratatui::backend::{Backend, ClearType, CrosstermBackend, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::crossterm::cursor::MoveTo;
+use ratatui::crossterm::queue;
+use ratatui::crossterm::style::{
+    Attribute, Color as CColor, Colors, Print, SetAttribute, SetColors, SetUnderlineColor,
+};
+use ratatui::layout::{Position, Size};
+use ratatui::style::Modifier;
+use std::io::{self, Write};
+
+/// Modifier bits for the underline styles, outside those ratatui defines
+const CURLY: Modifier = Modifier::from_bits_retain(1 << 12);
+const DOTTED: Modifier = Modifier::from_bits_retain(1 << 13);
+const DASHED: Modifier = Modifier::from_bits_retain(1 << 14);
+
+/// Modifiers that draw an underline of `style`
+pub fn underline_modifier(style: UnderlineStyle) -> Modifier {
+    Modifier::UNDERLINED
+        | match style {
+            UnderlineStyle::Straight => Modifier::empty(),
+            UnderlineStyle::Wavy => CURLY,
+            UnderlineStyle::Dotted => DOTTED,
+            UnderlineStyle::Dashed => DASHED,
+        }
+}
+
+/// The underline style drawn for `modifier`, if it is not a plain underline
+fn styled_underline(modifier: Modifier) -> Option<Attribute> {
+    if !modifier.contains(Modifier::UNDERLINED) {
+        return None;
+    }
+    if modifier.contains(CURLY) {
+        Some(Attribute::Undercurled)
+    } else if modifier.contains(DOTTED) {
+        Some(Attribute::Underdotted)
+    } else if modifier.contains(DASHED) {
+        Some(Attribute::Underdashed)
+    } else {
+        None
+    }
+}
+
+/// Whether the terminal draws curly, dotted and dashed underlines
+pub fn detect_underline_styles() -> bool {
+    underline_styles_supported(|name| std::env::var(name).ok())
+}
+
+fn underline_styles_supported(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(mode) = var("FRESH_UNDERLINE_STYLES") {
+        match mode.to_lowercase().as_str() {
+            "on" | "1" | "true" => return true,
+            "off" | "0" | "false" => return false,
+            _ => {} // Fall through to auto-detection
+        }
+    }
+
+    let term = var("TERM").unwrap_or_default().to_lowercase();
+    // Multiplexers only pass the sequences on when configured to
+    if term.starts_with("screen") || term.starts_with("tmux") || term == "linux" {
+        return false;
+    }
+    if [
+        "kitty",
+        "wezterm",
+        "foot",
+        "alacritty",
+        "ghostty",
+        "contour",
+    ]
+    .iter()
+    .any(|name| term.contains(name))
+    {
+        return true;
+    }
+    if let Some(program) = var("TERM_PROGRAM") {
+        if ["WezTerm", "iTerm.app", "ghostty", "vscode"].contains(&program.as_str()) {
+            return true;
+        }
+    }
+    // GNOME Terminal and other VTE-based terminals since VTE 0.51.2
+    var("VTE_VERSION")
+        .and_then(|version| version.parse::<u32>().ok())
+        .is_some_and(|version| version >= 5102)
+}
+
+/// Crossterm backend that also draws curly, dotted and dashed underlines
+pub struct UnderlineBackend<W: Write> {
+    inner: CrosstermBackend<W>,
+    /// Whether to send the styled underline sequences
+    styled_underlines: bool,
+}
+
+impl<W: Write> UnderlineBackend<W> {
+    pub fn new(writer: W, styled_underlines: bool) -> Self {
+        Self {
+            inner: CrosstermBackend::new(writer),
+            styled_underlines,
+        }
+    }
+
+    /// Draw `cells` again with their underline styles
+    fn draw_styled_underlines(
+        &mut self,
+        cells: Vec<(u16, u16, Cell, Attribute)>,
+    ) -> io::Result<()> {
+        let writer = &mut self.inner;
+        for (x, y, cell, underline) in cells {
+            queue!(
+                writer,
+                MoveTo(x, y),
+                SetAttribute(Attribute::Reset),
+                SetColors(Colors::new(cell.fg.into(), cell.bg.into())),
+            )?;
+            for (modifier, attribute) in [
+                (Modifier::BOLD, Attribute::Bold),
+                (Modifier::DIM, Attribute::Dim),
+                (Modifier::ITALIC, Attribute::Italic),
+                (Modifier::REVERSED, Attribute::Reverse),
+                (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+            ] {
+                if cell.modifier.contains(modifier) {
+                    queue!(writer, SetAttribute(attribute))?;
+                }
+            }
+            queue!(
+                writer,
+                SetAttribute(underline),
+                SetUnderlineColor(cell.underline_color.into()),
+                Print(cell.symbol()),
+            )?;
+        }
+        queue!(
+            writer,
+            SetColors(Colors::new(CColor::Reset, CColor::Reset)),
+            SetUnderlineColor(CColor::Reset),
+            SetAttribute(Attribute::Reset),
+        )
+    }
+}
+
+impl<W: Write> Backend for UnderlineBackend<W> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if !self.styled_underlines {
+            return self.inner.draw(content);
+        }
+        let mut styled = Vec::new();
+        self.inner.draw(content.inspect(|(x, y, cell)| {
+            if let Some(underline) = styled_underline(cell.modifier) {
+                styled.push((*x, *y, (*cell).clone(), underline));
+            }
+        }))?;
+        if styled.is_empty() {
+            return Ok(());
+        }
+        self.draw_styled_underlines(styled)
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Style};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Writer whose output can be read while the backend owns it
+    #[derive(Clone, Default)]
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn draw(styled_underlines: bool, style: Style) -> String {
+        let output = Output::default();
+        let mut backend = UnderlineBackend::new(output.clone(), styled_underlines);
+        let mut cell = Cell::new("x");
+        cell.set_style(style);
+        backend.draw([(0, 0, &cell)].into_iter()).unwrap();
+        let bytes = output.0.borrow().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_curly_underline_drawn() {
+        let style = Style::default()
+            .add_modifier(underline_modifier(UnderlineStyle::Wavy))
+            .underline_color(Color::Red);
+        // CSI 4:3 m is a curly underline
+        assert!(draw(true, style).contains("\x1b[4:3m"));
+        // Unsupported: only the plain underline
+        let plain = draw(false, style);
+        assert!(plain.contains("\x1b[4m"));
+        assert!(!plain.contains("\x1b[4:3m"));
+
+        let straight = Style::default().add_modifier(underline_modifier(UnderlineStyle::Straight));
+        assert!(!draw(true, straight).contains("\x1b[4:"));
+    }
+
+    #[test]
+    fn test_underline_style_detection() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(underline_styles_supported(env(&[("TERM", "xterm-kitty")])));
+        assert!(underline_styles_supported(env(&[
+            ("TERM", "xterm-256color"),
+            ("VTE_VERSION", "7200")
+        ])));
+        assert!(!underline_styles_supported(env(&[(
+            "TERM",
+            "xterm-256color"
+        )])));
+        assert!(!underline_styles_supported(env(&[
+            ("TERM", "tmux-256color"),
+            ("TERM_PROGRAM", "WezTerm")
+        ])));
+        assert!(underline_styles_supported(env(&[
+            ("TERM", "tmux-256color"),
+            ("FRESH_UNDERLINE_STYLES", "on")
+        ])));
+        assert!(!underline_styles_supported(env(&[
+            ("TERM", "xterm-kitty"),
+            ("FRESH_UNDERLINE_STYLES", "off")
+        ])));
+    }
+}
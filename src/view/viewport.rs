@@ -35,6 +35,10 @@ pub struct Viewport {
     /// When true, horizontal scrolling is disabled
     pub line_wrap_enabled: bool,
 
+    /// Columns of text per row when wrapping, as of the last render
+    /// (0 before the first one)
+    pub wrap_width: usize,
+
     /// Whether viewport needs synchronization with cursor positions
     /// When true, ensure_visible needs to be called before rendering
     /// This allows batching multiple cursor movements into a single viewport update
@@ -63,6 +67,7 @@ impl Viewport {
             scroll_offset: 3,
             horizontal_scroll_offset: 5,
             line_wrap_enabled: false,
+            wrap_width: 0,
             needs_sync: false,
             skip_resize_sync: false,
             skip_ensure_visible: false,
@@ -622,6 +627,27 @@ impl Viewport {
             self.skip_ensure_visible
         );
 
+        // Document start and end (Ctrl+Home/End) have a known answer, so skip
+        // loading the data around the cursor and counting the lines above it,
+        // which on a huge file can be most of the file. At the end, only the
+        // start of the last line is looked up, to get the cursor's column
+        if cursor.position == 0 {
+            self.top_byte = 0;
+            self.left_column = 0;
+            return;
+        }
+        let buffer_len = buffer.len();
+        if cursor.position == buffer_len && !self.line_wrap_enabled {
+            // The last line ends up at the bottom, as it would after centering
+            // on it and then limiting the scroll to the end of the buffer
+            self.set_top_byte_with_limit(buffer, buffer_len);
+            let cursor_line_start = buffer.line_iterator(buffer_len, 80).current_position();
+            // The cursor is at the end of its line, so its column is the line length
+            let cursor_column = buffer_len - cursor_line_start;
+            self.ensure_column_visible(cursor_column, cursor_column, buffer);
+            return;
+        }
+
         // For large files with lazy loading, ensure data around cursor is loaded
         let viewport_lines = self.visible_line_count().max(1);
 
@@ -638,7 +664,6 @@ impl Viewport {
         let estimated_viewport_bytes = viewport_lines * 200;
         let load_start = cursor.position.saturating_sub(estimated_viewport_bytes * 2);
         // Cap load_length to not go past EOF
-        let buffer_len = buffer.len();
         let remaining_bytes = buffer_len.saturating_sub(load_start);
         let load_length = (estimated_viewport_bytes * 3).min(remaining_bytes);
 
@@ -1290,4 +1315,42 @@ mod tests {
             lines_from_top
         );
     }
+
+    #[test]
+    fn test_ensure_visible_cursor_at_document_start() {
+        let content: String = (1..=50).map(|i| format!("line{}\n", i)).collect();
+        let mut buffer = Buffer::from_str_test(&content);
+        let mut vp = Viewport::new(80, 10);
+        vp.top_byte = buffer.line_start_offset(30).unwrap();
+        vp.left_column = 12;
+
+        vp.ensure_visible(&mut buffer, &Cursor::new(0));
+
+        assert_eq!(vp.top_byte, 0);
+        assert_eq!(vp.left_column, 0);
+    }
+
+    #[test]
+    fn test_ensure_visible_cursor_at_document_end() {
+        let long_last_line = "x".repeat(200);
+        let content: String = (1..=50)
+            .map(|i| format!("line{}\n", i))
+            .chain(std::iter::once(long_last_line))
+            .collect();
+        let mut buffer = Buffer::from_str_test(&content);
+        let mut vp = Viewport::new(80, 10);
+
+        let end = buffer.len();
+        vp.ensure_visible(&mut buffer, &Cursor::new(end));
+
+        // The last line sits on the bottom row of the viewport
+        let top_line = buffer.get_line_number(vp.top_byte);
+        let last_line = buffer.get_line_number(end);
+        assert_eq!(top_line + vp.visible_line_count() - 1, last_line);
+
+        // The cursor's column, at the end of the long last line, is scrolled into view
+        let visible_width = vp.width as usize - vp.gutter_width(&buffer) - 1;
+        assert!(vp.left_column > 0);
+        assert!(200 >= vp.left_column && 200 < vp.left_column + visible_width);
+    }
 }
@@ -25,7 +25,7 @@ use std::collections::HashMap;
 use crate::model::marker::{MarkerId, MarkerList};
 
 /// Position relative to the character at the marker position
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VirtualTextPosition {
     // ─── Inline positions (within a line) ───
     /// Render before the character (e.g., parameter hints: `/*count=*/5`)
@@ -2,7 +2,7 @@
 //! Git test helper - creates hermetic git repositories for testing
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::TempDir;
 
@@ -353,6 +353,39 @@ A sample project for testing.
         let dst = plugins_dir.join("git_gutter.ts");
         fs::copy(&src, &dst)
             .unwrap_or_else(|e| panic!("Failed to copy git_gutter.ts from {:?}: {}", src, e));
+
+        Self::copy_plugin_lib(&project_root, &plugins_dir);
+    }
+
+    /// Set up git status plugin for staging panel tests
+    pub fn setup_git_status_plugin(&self) {
+        let plugins_dir = self.path.join("plugins");
+        fs::create_dir_all(&plugins_dir).expect("Failed to create plugins directory");
+
+        let project_root = std::env::var("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .expect("CARGO_MANIFEST_DIR not set");
+
+        let src = project_root.join("plugins/git_status.ts");
+        let dst = plugins_dir.join("git_status.ts");
+        fs::copy(&src, &dst)
+            .unwrap_or_else(|e| panic!("Failed to copy git_status.ts from {:?}: {}", src, e));
+
+        Self::copy_plugin_lib(&project_root, &plugins_dir);
+    }
+
+    /// Copy the shared plugin library (plugins/lib) that plugins import from
+    fn copy_plugin_lib(project_root: &Path, plugins_dir: &Path) {
+        let lib_src = project_root.join("plugins/lib");
+        let lib_dst = plugins_dir.join("lib");
+        fs::create_dir_all(&lib_dst).expect("Failed to create plugins/lib directory");
+        for entry in fs::read_dir(&lib_src).expect("Failed to read plugins/lib") {
+            let entry = entry.expect("Failed to read plugins/lib entry");
+            if entry.path().extension().is_some_and(|e| e == "ts") {
+                fs::copy(entry.path(), lib_dst.join(entry.file_name()))
+                    .unwrap_or_else(|e| panic!("Failed to copy {:?}: {}", entry.path(), e));
+            }
+        }
     }
 
     /// Set up buffer modified plugin for unsaved changes indicator tests
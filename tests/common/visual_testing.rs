@@ -1,6 +1,7 @@
 #![cfg(test)]
 // Visual regression testing utilities
 
+use crate::common::harness::EditorTestHarness;
 use ratatui::buffer::Buffer;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
@@ -93,6 +94,19 @@ impl VisualFlow {
         Ok(())
     }
 
+    /// Capture the harness's screen as a step
+    /// This takes both a text snapshot (for testing) and generates an SVG (for visualization)
+    pub fn capture_step(
+        &mut self,
+        harness: &mut EditorTestHarness,
+        step_name: &str,
+        description: &str,
+    ) -> io::Result<()> {
+        harness.render()?;
+        let cursor_pos = harness.screen_cursor_position();
+        self.step(harness.buffer(), cursor_pos, step_name, description)
+    }
+
     /// Finalize the flow and write its documentation file
     pub fn finalize(mut self) {
         self.write_documentation_file().ok();
@@ -0,0 +1,124 @@
+//! Tests for abbreviation expansion
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn harness_with_abbreviations(pairs: &[(&str, &str)]) -> EditorTestHarness {
+    let mut config = Config::default();
+    for (typed, expansion) in pairs {
+        config
+            .abbreviations
+            .insert(typed.to_string(), expansion.to_string());
+    }
+    EditorTestHarness::with_config(100, 24, config).unwrap()
+}
+
+#[test]
+fn test_abbreviation_expands_on_word_boundary() {
+    let mut harness = harness_with_abbreviations(&[("teh", "the")]);
+
+    harness.type_text("teh cat, teh.").unwrap();
+    harness.assert_buffer_content("the cat, the.");
+
+    // Not expanded inside a longer word, or before more word characters
+    harness.type_text(" poteh tehx ").unwrap();
+    harness.assert_buffer_content("the cat, the. poteh tehx ");
+}
+
+#[test]
+fn test_abbreviation_backspace_reverts_expansion() {
+    let mut harness = harness_with_abbreviations(&[("teh", "the")]);
+
+    harness.type_text("teh ").unwrap();
+    harness.assert_buffer_content("the ");
+
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("teh ");
+    assert_eq!(harness.cursor_position(), 4);
+
+    // A second Backspace deletes normally
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("teh");
+}
+
+#[test]
+fn test_abbreviation_snippet_places_cursor() {
+    let mut harness = harness_with_abbreviations(&[("fn-", "fn $0() {}")]);
+
+    harness.type_text("fn- ").unwrap();
+    // The boundary character is consumed by the snippet
+    harness.assert_buffer_content("fn () {}");
+    assert_eq!(harness.cursor_position(), 3);
+
+    harness.type_text("main").unwrap();
+    harness.assert_buffer_content("fn main() {}");
+}
+
+#[test]
+fn test_abbreviation_undo_restores_typed_text() {
+    let mut harness = harness_with_abbreviations(&[("teh", "the")]);
+
+    harness.type_text("teh ").unwrap();
+    harness.assert_buffer_content("the ");
+
+    // Undo the space, then the expansion
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("teh");
+}
+
+#[test]
+fn test_language_abbreviations_override_global() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config
+        .abbreviations
+        .insert("pr-".to_string(), "print".to_string());
+    config
+        .languages
+        .get_mut("rust")
+        .unwrap()
+        .abbreviations
+        .insert("pr-".to_string(), "println!(\"$0\");".to_string());
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+
+    let rust_file = temp_dir.path().join("main.rs");
+    std::fs::write(&rust_file, "").unwrap();
+    harness.open_file(&rust_file).unwrap();
+    harness.type_text("pr- hi").unwrap();
+    harness.assert_buffer_content("println!(\"hi\");");
+
+    let text_file = temp_dir.path().join("notes.txt");
+    std::fs::write(&text_file, "").unwrap();
+    harness.open_file(&text_file).unwrap();
+    harness.type_text("pr- ").unwrap();
+    harness.assert_buffer_content("print ");
+}
+
+#[test]
+fn test_list_abbreviations_command() {
+    let mut harness = harness_with_abbreviations(&[("teh", "the"), ("adn", "and")]);
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("List Abbreviations").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Abbreviations*");
+    harness.assert_screen_contains("adn  ->  and  [global]");
+    harness.assert_screen_contains("teh  ->  the  [global]");
+}
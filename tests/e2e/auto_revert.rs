@@ -389,3 +389,131 @@ fn test_auto_revert_with_temp_rename_save() {
         harness.assert_buffer_content(&new_content);
     }
 }
+
+/// Test that auto-revert can be turned off in the config
+#[test]
+fn test_auto_revert_disabled_by_config() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert = false;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("not_reverted.txt");
+
+    write_and_sync(&file_path, "Original content");
+    harness.open_file(&file_path).unwrap();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    write_and_sync(&file_path, "External change");
+
+    // Well past the poll interval
+    for _ in 0..10 {
+        harness.process_async_and_render().unwrap();
+        harness.sleep(Duration::from_millis(300));
+    }
+    harness.assert_buffer_content("Original content");
+}
+
+/// Test that files matching `auto_revert_uncached_paths` are still reverted
+/// when they are polled by opening them
+#[test]
+fn test_auto_revert_uncached_paths() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_uncached_paths = vec!["uncached_*.txt".to_string()];
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("uncached_file.txt");
+
+    write_and_sync(&file_path, "Original content");
+    harness.open_file(&file_path).unwrap();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    write_and_sync(&file_path, "External change");
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "External change")
+        .expect("Auto-revert should update buffer content");
+}
+
+/// Test that the file watcher status lists what was polled
+#[test]
+fn test_file_watcher_status() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("watched.txt");
+    write_and_sync(&file_path, "content");
+    harness.open_file(&file_path).unwrap();
+
+    // Past the poll interval, then delete the file so the next poll can't read it
+    harness.sleep(FILE_CHANGE_DELAY);
+    fs::remove_file(&file_path).unwrap();
+    harness.process_async_and_render().unwrap();
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show File Watcher Status").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Auto-revert: on");
+    harness.assert_screen_contains("Open files: 1 checked every 2.0s");
+    harness.assert_screen_contains("1 can't be read");
+}
+
+/// The status bar reports a watched file that can't be read; clicking the
+/// report opens the System Health popup
+#[test]
+fn test_system_health_indicator() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("watched.txt");
+    write_and_sync(&file_path, "content");
+    harness.open_file(&file_path).unwrap();
+    harness.assert_screen_not_contains("unreadable");
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    fs::remove_file(&file_path).unwrap();
+    harness.process_async_and_render().unwrap();
+
+    let (row, col) = (0..24)
+        .find_map(|row| {
+            let text = harness.get_row_text(row);
+            text.find("1 file unreadable").map(|col| (row, col as u16))
+        })
+        .expect("health indicator in the status bar");
+    harness.mouse_click(col + 1, row).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("System Health");
+    harness.assert_screen_contains("File watcher: 1 file can't be read");
+    harness.assert_screen_contains("Plugins: ");
+}
+
+/// Auto-revert can be turned back on from the System Health popup
+#[test]
+fn test_system_health_enables_auto_revert() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert = false;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(100, 24, config).unwrap();
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show System Health").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("File watcher: auto-revert off");
+
+    // Nothing is running, so the plugins line comes first
+    harness.assert_screen_contains("Plugins: ");
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Auto-revert enabled");
+}
@@ -0,0 +1,127 @@
+//! E2E tests for saving large buffers in the background
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, FormatterConfig};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+/// Harness that saves every buffer in the background, verifying the writes
+fn harness() -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.background_save_threshold_bytes = 1;
+    config.editor.verify_background_saves = true;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+/// Poll until every save in the background is done
+fn wait_for_saves(harness: &mut EditorTestHarness) {
+    for _ in 0..200 {
+        harness.editor_mut().poll_background_saves();
+        if !harness.editor().is_saving_in_background() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("save did not finish");
+}
+
+fn save(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+/// Test that a save in the background shows its progress, writes the file
+/// and leaves the buffer unmodified
+#[test]
+fn test_background_save_writes_file() {
+    let fixture = TestFixture::new("big.txt", "hello\n").unwrap();
+    let mut harness = harness();
+    harness.open_file(&fixture.path).unwrap();
+    harness.type_text("say ").unwrap();
+
+    save(&mut harness);
+    assert!(harness.editor().is_saving_in_background());
+    harness.assert_screen_contains("Saving big.txt...");
+
+    wait_for_saves(&mut harness);
+    harness.render().unwrap();
+    assert_eq!(fs::read_to_string(&fixture.path).unwrap(), "say hello\n");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+    harness.assert_screen_contains("Saved big.txt");
+}
+
+/// Test that edits made while the buffer saves are not written, and leave
+/// the buffer modified
+#[test]
+fn test_edits_during_background_save_stay_modified() {
+    let fixture = TestFixture::new("big.txt", "hello\n").unwrap();
+    let mut harness = harness();
+    harness.open_file(&fixture.path).unwrap();
+    harness.type_text("say ").unwrap();
+
+    save(&mut harness);
+    harness.type_text("x").unwrap();
+    wait_for_saves(&mut harness);
+
+    assert_eq!(fs::read_to_string(&fixture.path).unwrap(), "say hello\n");
+    harness.assert_buffer_content("say xhello\n");
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    // Undoing the edit returns to the saved content
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
+
+/// Test that a quit waits for a save in the background to finish
+#[test]
+fn test_quit_waits_for_background_save() {
+    let fixture = TestFixture::new("big.txt", "hello\n").unwrap();
+    let mut harness = harness();
+    harness.open_file(&fixture.path).unwrap();
+    harness.type_text("say ").unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Save in the quit dialog starts the save
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.editor().is_saving_in_background());
+    assert!(!harness.should_quit());
+
+    wait_for_saves(&mut harness);
+    assert!(harness.should_quit());
+    assert_eq!(fs::read_to_string(&fixture.path).unwrap(), "say hello\n");
+}
+
+/// Test that a large buffer with a formatter saves in the foreground, so
+/// format on save still runs
+#[test]
+#[cfg_attr(not(unix), ignore = "On-save actions require Unix-like environment")]
+fn test_large_buffer_with_format_on_save() {
+    let fixture = TestFixture::new("big.rs", "b\na\n").unwrap();
+    let mut config = Config::default();
+    config.editor.background_save_threshold_bytes = 1;
+    let rust = config.languages.get_mut("rust").unwrap();
+    rust.format_on_save = true;
+    rust.formatter = Some(FormatterConfig {
+        command: "sort".to_string(),
+        args: vec![],
+        stdin: true,
+        timeout_ms: 5000,
+    });
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    save(&mut harness);
+    assert!(!harness.editor().is_saving_in_background());
+    harness.assert_buffer_content("a\nb\n");
+    assert_eq!(fs::read_to_string(&fixture.path).unwrap(), "a\nb\n");
+}
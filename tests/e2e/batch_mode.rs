@@ -0,0 +1,60 @@
+//! Tests for the commands run by headless batch mode (`--batch`)
+
+use crate::common::harness::EditorTestHarness;
+use fresh::input::batch_commands::parse_batch_command;
+use tempfile::TempDir;
+
+fn run(harness: &mut EditorTestHarness, line: &str) -> Result<(), String> {
+    let command = parse_batch_command(line)?.expect("a command");
+    harness.editor_mut().run_batch_command(&command)
+}
+
+#[test]
+fn test_batch_replace_and_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "foo bar\nbar foo\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    run(&mut harness, "replace foo \"a b\"").unwrap();
+    run(&mut harness, "replace missing x").unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "foo bar\nbar foo\n"
+    );
+
+    run(&mut harness, "save").unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "a b bar\nbar a b\n"
+    );
+}
+
+#[test]
+fn test_batch_runs_named_actions() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("lines.txt");
+    std::fs::write(&file_path, "first\nsecond\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    run(&mut harness, "delete_line").unwrap();
+    run(&mut harness, "save").unwrap();
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "second\n");
+}
+
+#[test]
+fn test_batch_command_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("data.unknownext");
+    std::fs::write(&file_path, "x\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    assert!(run(&mut harness, "format").is_err());
+    assert!(run(&mut harness, "frobnicate").is_err());
+}
@@ -0,0 +1,73 @@
+//! Tests for compacting fragmented buffers when the editor is idle
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+/// Type on every line of a 40-line buffer, leaving many small pieces
+fn fragmented_harness() -> EditorTestHarness {
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    let text: String = (0..40).map(|i| format!("line {}\n", i)).collect();
+    harness.type_text(&text).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..40 {
+        harness.type_text("ab").unwrap();
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.render().unwrap();
+    harness
+}
+
+fn piece_count(harness: &EditorTestHarness) -> usize {
+    harness.editor().active_state().buffer.stats().leaf_count
+}
+
+#[test]
+fn test_idle_editor_compacts_fragmented_buffer() {
+    let mut harness = fragmented_harness();
+    let content = harness.get_buffer_content().unwrap();
+    let pieces = piece_count(&harness);
+    assert!(pieces >= 64, "expected a fragmented buffer, got {}", pieces);
+
+    // Not idle yet
+    harness.editor_mut().compact_idle_buffers();
+    assert_eq!(piece_count(&harness), pieces);
+
+    harness.advance_time(Duration::from_secs(2));
+    harness.editor_mut().compact_idle_buffers();
+    assert!(
+        piece_count(&harness) < 8,
+        "{} pieces",
+        piece_count(&harness)
+    );
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+    assert_eq!(harness.editor().compaction_metrics().runs, 1);
+
+    // Editing and undoing still work on the compacted buffer
+    harness.type_text("z").unwrap();
+    assert_ne!(harness.get_buffer_content().unwrap(), content);
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+}
+
+#[test]
+fn test_show_buffer_statistics() {
+    let mut harness = fragmented_harness();
+    harness.advance_time(Duration::from_secs(2));
+    harness.editor_mut().compact_idle_buffers();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show Buffer Statistics").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("compacted 1 times");
+}
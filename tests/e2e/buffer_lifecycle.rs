@@ -43,11 +43,12 @@ fn test_quit_with_modified_buffers_shows_confirmation() {
         .unwrap();
     harness.render().unwrap();
 
-    // Should NOT quit immediately - there's a confirmation prompt
+    // Should NOT quit immediately - there's a confirmation dialog
     assert!(
         !harness.should_quit(),
         "Editor should not quit immediately with unsaved changes"
     );
+    harness.assert_screen_contains("Unsaved Changes");
 }
 
 /// Test that quitting without modified buffers works immediately
@@ -85,9 +86,9 @@ fn test_quit_with_confirmation_discard() {
         .unwrap();
     harness.render().unwrap();
 
-    // Confirm with 'd' (discard) and Enter
+    // Pick Discard on the buffer's row of the dialog
     harness
-        .send_key(KeyCode::Char('d'), KeyModifiers::NONE)
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
         .unwrap();
     harness
         .send_key(KeyCode::Enter, KeyModifiers::NONE)
@@ -113,14 +114,10 @@ fn test_quit_with_confirmation_cancel() {
         .unwrap();
     harness.render().unwrap();
 
-    // Cancel with 'c' and Enter (or any non-'d' key, default is cancel)
-    harness
-        .send_key(KeyCode::Char('c'), KeyModifiers::NONE)
-        .unwrap();
-    harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
-        .unwrap();
+    // Cancel the dialog with Escape
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
     harness.render().unwrap();
+    harness.assert_screen_contains("Quit cancelled");
 
     // Editor should NOT quit
     assert!(
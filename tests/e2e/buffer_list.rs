@@ -0,0 +1,79 @@
+//! Tests for the buffer list (jump to, save and close open buffers)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn press(harness: &mut EditorTestHarness, code: KeyCode, modifiers: KeyModifiers) {
+    harness.send_key(code, modifiers).unwrap();
+    harness.render().unwrap();
+}
+
+/// Open a.txt, b.txt and c.txt, with unsaved changes in c.txt
+fn three_buffers() -> (EditorTestHarness, tempfile::TempDir) {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, name).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.type_text("!").unwrap();
+    (harness, temp_dir)
+}
+
+#[test]
+fn test_mark_and_close_buffers() {
+    let (mut harness, _temp_dir) = three_buffers();
+
+    harness.run_command("Buffers").unwrap();
+    harness.assert_screen_contains("Buffers: 3 open, 1 modified");
+    harness.assert_screen_contains("[ ] * c.txt");
+
+    // Mark a.txt and b.txt, below the two header lines
+    press(&mut harness, KeyCode::Home, KeyModifiers::CONTROL);
+    press(&mut harness, KeyCode::Down, KeyModifiers::NONE);
+    press(&mut harness, KeyCode::Down, KeyModifiers::NONE);
+    press(&mut harness, KeyCode::Char(' '), KeyModifiers::NONE);
+    press(&mut harness, KeyCode::Char(' '), KeyModifiers::NONE);
+    harness.assert_screen_contains("[x]   a.txt");
+    harness.assert_screen_contains("[x]   b.txt");
+
+    press(&mut harness, KeyCode::Char('d'), KeyModifiers::NONE);
+    harness.assert_screen_contains("Closed 2 buffer(s)");
+    harness.assert_screen_contains("Buffers: 1 open, 1 modified");
+    harness.assert_screen_not_contains("a.txt");
+
+    // The modified buffer is kept
+    press(&mut harness, KeyCode::Char('d'), KeyModifiers::NONE);
+    harness.assert_screen_contains("kept 1 with unsaved changes");
+}
+
+#[test]
+fn test_save_all_and_jump() {
+    let (mut harness, temp_dir) = three_buffers();
+
+    harness.run_command("Buffers").unwrap();
+    press(&mut harness, KeyCode::Char('S'), KeyModifiers::SHIFT);
+    harness.assert_screen_contains("Saved 1 buffer(s)");
+    harness.assert_screen_contains("Buffers: 3 open, 0 modified");
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("c.txt")).unwrap(),
+        "!c.txt"
+    );
+
+    // Enter shows a.txt in the split the list was opened from
+    press(&mut harness, KeyCode::Home, KeyModifiers::CONTROL);
+    press(&mut harness, KeyCode::Down, KeyModifiers::NONE);
+    press(&mut harness, KeyCode::Down, KeyModifiers::NONE);
+    press(&mut harness, KeyCode::Enter, KeyModifiers::NONE);
+    harness.assert_buffer_content("a.txt");
+}
+
+#[test]
+fn test_close_all_saved_buffers() {
+    let (mut harness, _temp_dir) = three_buffers();
+
+    harness.run_command("Close All Saved Buffers").unwrap();
+    harness.assert_screen_contains("Closed 2 saved buffer(s)");
+    harness.assert_buffer_content("!c.txt");
+}
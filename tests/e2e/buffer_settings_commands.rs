@@ -1,6 +1,7 @@
 //! Tests for buffer settings commands:
 //! - Set Tab Size
 //! - Toggle Indentation: Spaces ↔ Tabs
+//! - Set Indentation, and detecting it when a file is opened
 //! - Toggle Tab Indicators
 //! - Reset Buffer Settings
 
@@ -9,25 +10,6 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
 use tempfile::TempDir;
 
-/// Helper to run a command from the command palette
-fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
-    // Open command palette with Ctrl+P
-    harness
-        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
-        .unwrap();
-    harness.render().unwrap();
-
-    // Type the command name
-    harness.type_text(command_name).unwrap();
-    harness.render().unwrap();
-
-    // Press Enter to execute
-    harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
-        .unwrap();
-    harness.render().unwrap();
-}
-
 /// Test that "Toggle Indentation" command toggles between spaces and tabs
 #[test]
 fn test_toggle_indentation_command() {
@@ -54,7 +36,7 @@ fn test_toggle_indentation_command() {
     harness.render().unwrap();
 
     // Run "Toggle Indentation" command to switch to spaces
-    run_command(&mut harness, "Toggle Indentation");
+    harness.run_command("Toggle Indentation").unwrap();
 
     // Now Tab should insert spaces
     harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
@@ -76,7 +58,7 @@ fn test_toggle_indentation_command() {
         .unwrap();
     harness.render().unwrap();
 
-    run_command(&mut harness, "Toggle Indentation");
+    harness.run_command("Toggle Indentation").unwrap();
 
     harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
     let content = harness.get_buffer_content().unwrap();
@@ -109,7 +91,7 @@ fn test_toggle_tab_indicators_command() {
     );
 
     // Run "Toggle Tab Indicators" command
-    run_command(&mut harness, "Toggle Tab Indicators");
+    harness.run_command("Toggle Tab Indicators").unwrap();
 
     // Now tab indicators should be visible
     let screen_after = harness.screen_to_string();
@@ -120,7 +102,7 @@ fn test_toggle_tab_indicators_command() {
     );
 
     // Toggle again - should hide them
-    run_command(&mut harness, "Toggle Tab Indicators");
+    harness.run_command("Toggle Tab Indicators").unwrap();
 
     let screen_final = harness.screen_to_string();
     assert!(
@@ -226,8 +208,8 @@ fn test_reset_buffer_settings_command() {
     );
 
     // Modify settings: toggle tab indicators and toggle indentation to spaces
-    run_command(&mut harness, "Toggle Tab Indicators");
-    run_command(&mut harness, "Toggle Indentation"); // Go uses tabs by default, so toggle switches to spaces
+    harness.run_command("Toggle Tab Indicators").unwrap();
+    harness.run_command("Toggle Indentation").unwrap(); // Go uses tabs by default, so toggle switches to spaces
 
     // Verify modifications took effect
     let screen_modified = harness.screen_to_string();
@@ -254,7 +236,7 @@ fn test_reset_buffer_settings_command() {
     harness.render().unwrap();
 
     // Reset buffer settings
-    run_command(&mut harness, "Reset Buffer Settings");
+    harness.run_command("Reset Buffer Settings").unwrap();
 
     // Verify settings are restored to Go defaults
     let screen_reset = harness.screen_to_string();
@@ -276,3 +258,73 @@ fn test_reset_buffer_settings_command() {
         content_reset
     );
 }
+
+/// The indentation of an opened file is detected, shown in the status bar
+/// and changed by clicking it
+#[test]
+fn test_indentation_detected_and_changed_from_status_bar() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "fn main() {\n  if x {\n    y();\n  }\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(140, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Rust defaults to 4 spaces, but the file indents by 2
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.ends_with("}\n  "), "{:?}", content);
+
+    let status_row = (0..24)
+        .rev()
+        .find(|&row| harness.get_screen_row(row).contains("Spaces: 2"))
+        .expect("indentation shown in the status bar");
+    let status = harness.get_screen_row(status_row);
+    let col = status[..status.find("Spaces: 2").unwrap()].chars().count();
+    harness.mouse_click(col as u16, status_row as u16).unwrap();
+    harness.assert_screen_contains("Indentation:");
+
+    // Spaces: 2, Spaces: 4, Spaces: 8, then Tabs: 2
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Tabs: 2");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert!(harness.get_buffer_content().unwrap().ends_with("}\n  \t"));
+}
+
+/// With detection off the language settings apply, until detection is
+/// asked for from the prompt
+#[test]
+fn test_indentation_detection_can_be_turned_off() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "fn main() {\n\tx();\n}\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.detect_indentation = false;
+    let mut harness = EditorTestHarness::with_config(140, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 4");
+
+    // From "Spaces: 4" down to "Detect from file", after the tab widths
+    harness.run_command("Set Indentation").unwrap();
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Tabs: 4");
+}
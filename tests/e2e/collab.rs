@@ -0,0 +1,169 @@
+//! E2E tests for sharing a buffer between two editors
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Run a command that prompts for an address, answering with `address`
+fn run_address_command(harness: &mut EditorTestHarness, name: &str, address: &str) {
+    harness.run_command(name).unwrap();
+    harness
+        .send_key_repeat(KeyCode::Backspace, KeyModifiers::NONE, 32)
+        .unwrap();
+    harness.type_text(address).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Process both editors' messages until `condition` holds, failing after
+/// ten seconds
+fn wait_for_both(
+    host: &mut EditorTestHarness,
+    guest: &mut EditorTestHarness,
+    mut condition: impl FnMut(&EditorTestHarness, &EditorTestHarness) -> bool,
+) {
+    for _ in 0..500 {
+        host.process_async_and_render().unwrap();
+        guest.process_async_and_render().unwrap();
+        if condition(host, guest) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    panic!(
+        "timed out; host:\n{}\nguest:\n{}",
+        host.screen_to_string(),
+        guest.screen_to_string()
+    );
+}
+
+/// Share the host's active buffer on a free port, returning what guests
+/// join with ("ADDRESS TOKEN")
+fn share(host: &mut EditorTestHarness) -> String {
+    run_address_command(host, "Collab: Share Buffer", "127.0.0.1:0");
+    let screen = host.screen_to_string();
+    screen
+        .split("join with: ")
+        .nth(1)
+        .and_then(|rest| {
+            let mut words = rest.split_whitespace();
+            Some(format!("{} {}", words.next()?, words.next()?))
+        })
+        .unwrap_or_else(|| panic!("no address on screen:\n{}", screen))
+}
+
+#[test]
+fn test_share_buffer_and_edit_from_both_sides() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello\n").unwrap();
+
+    let mut host = EditorTestHarness::with_working_dir(120, 24, temp_dir.path().into()).unwrap();
+    host.open_file(&file).unwrap();
+    let join = share(&mut host);
+
+    let mut guest = EditorTestHarness::with_working_dir(120, 24, temp_dir.path().into()).unwrap();
+    run_address_command(&mut guest, "Collab: Join", &join);
+    wait_for_both(&mut host, &mut guest, |_, guest| {
+        guest.get_buffer_content().as_deref() == Some("hello\n")
+    });
+    guest.assert_screen_contains("notes.txt (shared)");
+
+    // An edit on each side shows up on the other
+    guest.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    guest.type_text(" world").unwrap();
+    host.send_key(KeyCode::End, KeyModifiers::CONTROL).unwrap();
+    host.type_text("bye").unwrap();
+    wait_for_both(&mut host, &mut guest, |host, guest| {
+        host.get_buffer_content().as_deref() == Some("hello world\nbye")
+            && guest.get_buffer_content().as_deref() == Some("hello world\nbye")
+    });
+
+    // Leaving keeps the guest's copy
+    host.run_command("Collab: Stop").unwrap();
+    wait_for_both(&mut host, &mut guest, |_, guest| {
+        guest
+            .screen_to_string()
+            .contains("The host ended the shared session")
+    });
+    assert_eq!(
+        guest.get_buffer_content().as_deref(),
+        Some("hello world\nbye")
+    );
+}
+
+#[test]
+fn test_join_needs_session_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello\n").unwrap();
+
+    let mut host = EditorTestHarness::with_working_dir(120, 24, temp_dir.path().into()).unwrap();
+    host.open_file(&file).unwrap();
+    let join = share(&mut host);
+    let address = join.split_whitespace().next().unwrap();
+
+    let mut guest = EditorTestHarness::with_working_dir(120, 24, temp_dir.path().into()).unwrap();
+    run_address_command(&mut guest, "Collab: Join", &format!("{} wrong", address));
+    wait_for_both(&mut host, &mut guest, |_, guest| {
+        guest
+            .screen_to_string()
+            .contains("The host ended the shared session")
+    });
+    guest.assert_screen_not_contains("notes.txt (shared)");
+}
+
+#[test]
+fn test_guest_cannot_insert_as_another_site() {
+    use fresh::model::crdt::{CrdtOp, ElementId};
+    use fresh::services::collab::{CollabMessage, HOST_SITE};
+    use std::io::{BufRead, BufReader, Write};
+
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello\n").unwrap();
+
+    let mut host = EditorTestHarness::with_working_dir(120, 24, temp_dir.path().into()).unwrap();
+    host.open_file(&file).unwrap();
+    let join = share(&mut host);
+    let (address, token) = join.split_once(' ').unwrap();
+
+    // A hand-written guest, so it can send whatever it likes
+    let mut stream = std::net::TcpStream::connect(address).unwrap();
+    writeln!(stream, "{{\"token\":\"{}\"}}", token).unwrap();
+    // The host only answers while the test lets it process messages
+    stream
+        .set_read_timeout(Some(std::time::Duration::from_millis(20)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut line = String::new();
+    let site = loop {
+        host.process_async_and_render().unwrap();
+        if let Ok(n) = reader.read_line(&mut line) {
+            assert!(n > 0, "the host closed the connection");
+            if let Ok(CollabMessage::Welcome { site, .. }) = serde_json::from_str(&line) {
+                break site;
+            }
+            line.clear();
+        }
+    };
+    let mut send_insert = |site: u64, text: &str| {
+        let message = CollabMessage::Ops {
+            site,
+            ops: vec![CrdtOp::Insert {
+                id: ElementId { clock: 100, site },
+                after: None,
+                bytes: text.as_bytes().to_vec(),
+            }],
+        };
+        writeln!(stream, "{}", serde_json::to_string(&message).unwrap()).unwrap();
+    };
+
+    send_insert(HOST_SITE, "forged ");
+    send_insert(site, "mine ");
+    host.wait_until(|h| h.get_buffer_content().unwrap().starts_with("mine "))
+        .unwrap();
+    assert_eq!(host.get_buffer_content().unwrap(), "mine hello\n");
+}
@@ -503,7 +503,7 @@ fn test_command_palette_from_file_explorer() {
     harness.assert_screen_contains("Command:");
 
     // Should show commands
-    harness.assert_screen_contains("Open File");
+    harness.assert_screen_contains("New File");
 
     // Should be able to execute a command
     harness.type_text("toggle hidden").unwrap();
@@ -566,8 +566,9 @@ fn test_command_palette_down_no_wraparound() {
         .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
         .unwrap();
 
-    // Filter to get only two commands
-    harness.type_text("save f").unwrap();
+    // Filter to get only two commands ("save f" also matches "Close All
+    // Saved Buffers")
+    harness.type_text("save fi").unwrap();
     harness.render().unwrap();
 
     // Should match "Save File" and "Save File As"
@@ -703,8 +704,11 @@ fn test_command_palette_shows_shortcuts() {
     // Add Cursor Below should show Ctrl+Alt+↓
     harness.assert_screen_contains("Add Cursor Below");
 
-    // Copy should show Ctrl+C (or ⌘+C on macOS)
+    // Copy should show Ctrl+C (or ⌘+C on macOS); it is below the first page
+    harness.type_text("Copy").unwrap();
+    harness.render().unwrap();
     harness.assert_screen_contains("Copy");
+    let screen = harness.screen_to_string();
     assert!(
         screen.contains("Ctrl+C") || screen.contains("⌘+C"),
         "Should show shortcut for Copy"
@@ -877,3 +881,99 @@ fn test_show_keyboard_shortcuts_open_close_reopen() {
         screen
     );
 }
+
+/// Test that a `/` prefix lists the workspace files and opens the chosen one
+#[test]
+fn test_command_palette_file_mode() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+    std::fs::write(temp_dir.path().join("src/parser.rs"), "fn parse() {}\n").unwrap();
+    std::fs::write(temp_dir.path().join("notes.txt"), "notes\n").unwrap();
+    let mut harness =
+        EditorTestHarness::with_working_dir(120, 30, temp_dir.path().to_path_buf()).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("/prs").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("src/parser.rs");
+    harness.assert_screen_not_contains("notes.txt");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some("fn parse() {}\n")
+    );
+}
+
+/// Test that an `@` prefix lists the symbols of the file and jumps to the chosen one
+#[test]
+fn test_command_palette_symbol_mode() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let fixture = TestFixture::new(
+        "lib.rs",
+        "fn first() {}\n\nstruct Point {\n    x: i32,\n}\n\nfn second() {}\n",
+    )
+    .unwrap();
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("@").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("fn first() {}");
+    harness.assert_screen_contains("struct Point {");
+    harness.assert_screen_contains("line 7");
+
+    harness.type_text("second").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("fn second").unwrap()
+    );
+}
+
+/// Test that a `#` prefix searches the symbols of the whole project
+#[test]
+fn test_command_palette_workspace_symbol_mode() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("shapes.py"),
+        "class Circle:\n    pass\n\n\ndef area_of_square(side):\n    return side * side\n",
+    )
+    .unwrap();
+    let mut harness =
+        EditorTestHarness::with_working_dir(120, 30, temp_dir.path().to_path_buf()).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("#").unwrap();
+    // The index is built in the background
+    harness.wait_for_screen_contains("area_of_square").unwrap();
+    harness.assert_screen_contains("shapes.py:5");
+
+    harness.type_text("square").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.starts_with("class Circle:"));
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("def area_of_square").unwrap()
+    );
+}
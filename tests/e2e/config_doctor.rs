@@ -0,0 +1,47 @@
+//! Tests for the Config Doctor command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[test]
+fn test_config_doctor_reports_errors_with_lines() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::write(
+        project_dir.join("config.json"),
+        "{\n  \"keybindings\": [\n    {\"key\": \"s\", \"action\": \"save_everything\"}\n  ],\n  \"theme\": \"dark\"\n  \"editor\": {}\n}\n",
+    )
+    .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Config Doctor").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // The missing comma is found; the file isn't used, so its keybinding isn't checked
+    harness.assert_screen_contains("config.json:6:3: error: expected `,` or `}`");
+    harness.assert_screen_not_contains("save_everything");
+    harness.assert_screen_contains("Errors: 1");
+
+    // Once the file parses, the ignored keybinding is reported
+    std::fs::write(
+        project_dir.join("config.json"),
+        "{\n  \"keybindings\": [\n    {\"key\": \"s\", \"action\": \"save_everything\"}\n  ]\n}\n",
+    )
+    .unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Config Doctor").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains(
+        "config.json:3: warning: keybinding: unknown action \"save_everything\"",
+    );
+}
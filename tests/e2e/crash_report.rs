@@ -0,0 +1,69 @@
+//! Tests for offering the report of a crash on the next start
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use fresh::services::crash_report;
+use tempfile::TempDir;
+
+/// A harness whose state directory has a pending crash report
+fn crashed_harness(context_temp: &TempDir) -> EditorTestHarness {
+    let dir_context = DirectoryContext::for_testing(context_temp.path());
+    let report_dir = crash_report::report_dir(&dir_context.state_dir);
+    std::fs::create_dir_all(&report_dir).unwrap();
+    let report = report_dir.join("crash-1.txt");
+    std::fs::write(&report, "Fresh crashed\n\nPanic: index out of bounds\n").unwrap();
+    std::fs::write(
+        report_dir.join("pending"),
+        report.to_string_lossy().as_bytes(),
+    )
+    .unwrap();
+
+    EditorTestHarness::with_shared_dir_context(
+        100,
+        30,
+        Default::default(),
+        context_temp.path().to_path_buf(),
+        dir_context,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_crash_report_is_offered_once() {
+    let context_temp = TempDir::new().unwrap();
+    let mut harness = crashed_harness(&context_temp);
+
+    let report = harness.editor().take_pending_crash_report().unwrap();
+    assert!(harness.editor().take_pending_crash_report().is_none());
+
+    harness.editor_mut().offer_crash_report(report, false);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Fresh crashed last time. Open the crash report?");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("crash-1.txt");
+    harness.assert_screen_contains("Panic: index out of bounds");
+}
+
+#[test]
+fn test_declining_crash_report_keeps_it() {
+    let context_temp = TempDir::new().unwrap();
+    let mut harness = crashed_harness(&context_temp);
+
+    let report = harness.editor().take_pending_crash_report().unwrap();
+    harness.editor_mut().offer_crash_report(report, true);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Restore the session and open the crash report?");
+
+    harness.type_text("n").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Crash report saved to");
+    harness.assert_screen_not_contains("Panic: index out of bounds");
+}
@@ -0,0 +1,44 @@
+//! Tests for the diff viewer opened with `--diff`
+
+use crate::common::harness::EditorTestHarness;
+use tempfile::TempDir;
+
+#[test]
+fn test_open_diff_shows_unified_diff() {
+    let temp_dir = TempDir::new().unwrap();
+    let old = temp_dir.path().join("old.txt");
+    let new = temp_dir.path().join("new.txt");
+    std::fs::write(&old, "alpha\nbeta\ngamma\n").unwrap();
+    std::fs::write(&new, "alpha\nBETA\ngamma\ndelta\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.editor_mut().open_diff(&old, &new).unwrap();
+    harness.render().unwrap();
+
+    let content = harness.get_buffer_content().unwrap();
+    let body: Vec<&str> = content.lines().skip(2).collect();
+    assert_eq!(body, vec![" alpha", "-beta", "+BETA", " gamma", "+delta"]);
+    assert!(content.starts_with(&format!("--- {}\n", old.display())));
+
+    harness.assert_screen_contains("*Diff: old.txt vs new.txt*");
+    harness.assert_screen_contains("3 changed line(s)");
+
+    // The diff buffer is read-only
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+}
+
+#[test]
+fn test_open_diff_identical_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let old = temp_dir.path().join("a.txt");
+    let new = temp_dir.path().join("b.txt");
+    std::fs::write(&old, "same\n").unwrap();
+    std::fs::write(&new, "same\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.editor_mut().open_diff(&old, &new).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Files are identical");
+}
@@ -0,0 +1,122 @@
+//! E2E tests for the context menu opened with a right click in the text area
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Screen position of byte `column` of the first line
+fn text_pos(harness: &EditorTestHarness, column: u16) -> (u16, u16) {
+    let gutter_width = harness.editor().active_state().margins.left_total_width() as u16;
+    let (content_first_row, _) = harness.content_area_rows();
+    (gutter_width + column, content_first_row as u16)
+}
+
+/// Pick the `index`th enabled entry of the open context menu from the keyboard
+fn pick(harness: &mut EditorTestHarness, index: usize) {
+    for _ in 0..=index {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that right-clicking text lists the editor actions, and Paste inserts
+/// at the click since Cut and Copy are disabled without a selection
+#[test]
+fn test_right_click_text_pastes_at_click() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("hello world\n").unwrap();
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("big ".to_string());
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Find References");
+
+    let (col, row) = text_pos(&harness, 6);
+    harness.mouse_right_click(col, row).unwrap();
+    for entry in [
+        "Cut",
+        "Copy",
+        "Paste",
+        "Go to Definition",
+        "Find References",
+        "Format Selection",
+    ] {
+        harness.assert_screen_contains(entry);
+    }
+
+    pick(&mut harness, 0);
+    harness.assert_screen_not_contains("Find References");
+    harness.assert_buffer_content("hello big world\n");
+}
+
+/// Test that right-clicking inside a selection keeps it, so Copy copies it
+#[test]
+fn test_right_click_in_selection_copies_it() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("hello world\n").unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.render().unwrap();
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+
+    let (col, row) = text_pos(&harness, 2);
+    harness.mouse_right_click(col, row).unwrap();
+    assert_eq!(harness.get_selected_text(), "hello");
+    // Cut, Copy, then Paste are enabled
+    pick(&mut harness, 1);
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().paste_for_test();
+    harness.assert_buffer_content("hello worldhello\n");
+}
+
+/// Test that plugins can add items to the editor context menu
+#[test]
+#[cfg(feature = "plugins")]
+fn test_plugin_context_menu_item() {
+    use crate::common::fixtures::TestFixture;
+    use std::fs;
+    use std::time::Duration;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    let test_plugin = r#"
+globalThis.context_menu_shout = function(): void {
+    editor.setStatus("Shouted from the context menu");
+};
+
+editor.addContextMenuItem("Shout", "context_menu_shout", "");
+"#;
+    fs::write(plugins_dir.join("context_menu_test.ts"), test_plugin).unwrap();
+
+    let fixture = TestFixture::new("test.txt", "hello world\n").unwrap();
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&fixture.path).unwrap();
+    harness.render().unwrap();
+    for _ in 0..5 {
+        harness.process_async_and_render().unwrap();
+        harness.sleep(Duration::from_millis(50));
+    }
+
+    let (col, row) = text_pos(&harness, 0);
+    harness.mouse_right_click(col, row).unwrap();
+    harness.assert_screen_contains("Shout");
+
+    // Paste, then the plugin's item
+    pick(&mut harness, 1);
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .contains("Shouted from the context menu")
+        })
+        .unwrap();
+}
@@ -0,0 +1,69 @@
+//! E2E tests for the feedback given on rejected keys (`editor.error_feedback`)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, ErrorFeedback};
+use fresh::view::prompt::PromptType;
+use std::time::Duration;
+
+fn harness_with_feedback(feedback: ErrorFeedback) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.error_feedback = feedback;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+/// Test that moving past the start or end of the buffer rings the bell,
+/// and moves that go somewhere don't
+#[test]
+fn test_bell_at_buffer_edges() {
+    let mut harness = harness_with_feedback(ErrorFeedback::Bell);
+    let _fixture = harness.load_buffer_from_text("ab").unwrap();
+
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    assert!(harness.editor_mut().take_bell());
+    assert!(!harness.editor_mut().take_bell());
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    assert!(!harness.editor_mut().take_bell());
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    assert!(harness.editor_mut().take_bell());
+}
+
+/// Test that an unknown key in a prompt rings the bell
+#[test]
+fn test_bell_on_unknown_prompt_key() {
+    let mut harness = harness_with_feedback(ErrorFeedback::Bell);
+    harness
+        .editor_mut()
+        .start_prompt("Go to line: ".to_string(), PromptType::GotoLine);
+
+    harness.send_key(KeyCode::F(5), KeyModifiers::NONE).unwrap();
+    assert!(harness.editor_mut().take_bell());
+    harness.type_text("12").unwrap();
+    assert!(!harness.editor_mut().take_bell());
+}
+
+/// Test that the visual bell flashes the split briefly, and the default
+/// gives no feedback
+#[test]
+fn test_visual_bell_flashes_split() {
+    let mut harness = harness_with_feedback(ErrorFeedback::Visual);
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert!(harness.editor().is_visual_bell_on());
+    assert!(!harness.editor_mut().take_bell());
+
+    assert!(!harness.editor_mut().check_visual_bell());
+    harness.advance_time(Duration::from_millis(200));
+    assert!(harness.editor_mut().check_visual_bell());
+    assert!(!harness.editor().is_visual_bell_on());
+
+    let mut harness = harness_with_feedback(ErrorFeedback::default());
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert!(!harness.editor().is_visual_bell_on());
+    assert!(!harness.editor_mut().take_bell());
+}
@@ -0,0 +1,70 @@
+//! Tests for exporting a buffer as HTML or ANSI
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+fn export(harness: &mut EditorTestHarness, command: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn open_rust_file(harness: &mut EditorTestHarness, temp_dir: &TempDir) -> std::path::PathBuf {
+    let path = temp_dir.path().join("main.rs");
+    std::fs::write(&path, "fn main() {\n    let x = \"<a>\";\n}\n").unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    path
+}
+
+#[test]
+fn test_export_as_html() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let path = open_rust_file(&mut harness, &temp_dir);
+
+    export(&mut harness, "Export as HTML");
+    harness.assert_screen_contains("Export as HTML to:");
+    harness.assert_screen_contains("main.rs.html");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Exported as HTML");
+
+    let html = std::fs::read_to_string(path.with_file_name("main.rs.html")).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>main.rs</title>"));
+    assert!(html.contains("<span style=\"color:"));
+    assert!(html.contains("&lt;a&gt;"));
+}
+
+#[test]
+fn test_export_as_ansi() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let path = open_rust_file(&mut harness, &temp_dir);
+
+    export(&mut harness, "Export as ANSI");
+    harness.assert_screen_contains("main.rs.ans");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let ansi = std::fs::read_to_string(path.with_file_name("main.rs.ans")).unwrap();
+    assert!(ansi.contains("\x1b[0m"));
+    assert!(ansi.lines().next().unwrap().starts_with("\x1b["));
+    assert!(ansi.contains("\"<a>\""));
+    // The buffer's own file is untouched
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "fn main() {\n    let x = \"<a>\";\n}\n"
+    );
+}
@@ -0,0 +1,137 @@
+//! Tests for renaming, duplicating and trashing the current buffer's file,
+//! and undoing those operations
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Replace the prompt input with `text` and confirm
+fn answer_prompt(harness: &mut EditorTestHarness, text: &str) {
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_rename_file_moves_buffer_and_undo_moves_back() {
+    let temp_dir = TempDir::new().unwrap();
+    let old_path = temp_dir.path().join("old.txt");
+    let new_path = temp_dir.path().join("sub/new.txt");
+    std::fs::write(&old_path, "content\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&old_path).unwrap();
+    harness.type_text("more ").unwrap();
+
+    harness.run_command("Rename/Move File").unwrap();
+    harness.assert_screen_contains("Rename/move to:");
+    answer_prompt(&mut harness, new_path.to_str().unwrap());
+
+    assert!(!old_path.exists());
+    assert_eq!(std::fs::read_to_string(&new_path).unwrap(), "content\n");
+    let buffer = &harness.editor().active_state().buffer;
+    assert_eq!(buffer.file_path(), Some(new_path.as_path()));
+    assert!(buffer.is_modified(), "unsaved edits stay in the buffer");
+    harness.assert_screen_contains("new.txt");
+
+    // Saving writes to the new location
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&new_path).unwrap(),
+        "more content\n"
+    );
+
+    harness.run_command("Undo File Operation").unwrap();
+    assert!(!new_path.exists());
+    assert_eq!(
+        std::fs::read_to_string(&old_path).unwrap(),
+        "more content\n"
+    );
+    assert_eq!(
+        harness.editor().active_state().buffer.file_path(),
+        Some(old_path.as_path())
+    );
+}
+
+#[test]
+fn test_rename_file_refuses_to_overwrite() {
+    let temp_dir = TempDir::new().unwrap();
+    let a = temp_dir.path().join("a.txt");
+    let b = temp_dir.path().join("b.txt");
+    std::fs::write(&a, "a").unwrap();
+    std::fs::write(&b, "b").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&a).unwrap();
+    harness.run_command("Rename/Move File").unwrap();
+    answer_prompt(&mut harness, b.to_str().unwrap());
+
+    harness.assert_screen_contains("already exists");
+    assert_eq!(std::fs::read_to_string(&a).unwrap(), "a");
+    assert_eq!(std::fs::read_to_string(&b).unwrap(), "b");
+    assert_eq!(
+        harness.editor().active_state().buffer.file_path(),
+        Some(a.as_path())
+    );
+}
+
+#[test]
+fn test_duplicate_file_opens_copy() {
+    let temp_dir = TempDir::new().unwrap();
+    let original = temp_dir.path().join("notes.md");
+    std::fs::write(&original, "# Notes\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&original).unwrap();
+    harness.run_command("Duplicate File").unwrap();
+    harness.assert_screen_contains("notes copy.md");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let copy = temp_dir.path().join("notes copy.md");
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), "# Notes\n");
+    assert_eq!(
+        harness.editor().active_state().buffer.file_path(),
+        Some(copy.as_path())
+    );
+    assert_eq!(std::fs::read_to_string(&original).unwrap(), "# Notes\n");
+}
+
+#[test]
+fn test_trash_file_cancelled_keeps_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("keep.txt");
+    std::fs::write(&path, "keep").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.run_command("Delete File (Move to Trash)").unwrap();
+    harness.assert_screen_contains("to trash? (y/N)");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Delete cancelled");
+    assert!(path.exists());
+    assert_eq!(
+        harness.editor().active_state().buffer.file_path(),
+        Some(path.as_path())
+    );
+}
+
+#[test]
+fn test_undo_without_file_operation() {
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.run_command("Undo File Operation").unwrap();
+    harness.assert_screen_contains("No file operation to undo");
+}
@@ -0,0 +1,73 @@
+//! Tests for "Find References" without a language server: the project-wide
+//! search and its results buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Project with `helper` in two files; opens `b.rs` with the cursor on it
+fn setup(temp_dir: &TempDir) -> EditorTestHarness {
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("a.rs"),
+        "fn helper() {}\nfn other() {}\nlet y = helper_two;\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("b.rs"), "helper();\nhelper();\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_working_dir(160, 30, root.to_path_buf()).unwrap();
+    harness.open_file(&root.join("b.rs")).unwrap();
+    harness
+}
+
+fn find_references(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Find References").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_find_references_lists_matches_grouped_by_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+    find_references(&mut harness);
+
+    harness.assert_screen_contains("Found 3 textual reference(s) for 'helper'");
+    harness.assert_screen_contains("References to 'helper': 3");
+    let screen = harness.screen_to_string();
+    assert!(
+        !screen.contains("helper_two"),
+        "Partial words must not match"
+    );
+
+    // One heading line per file, in path order
+    let a = screen.find("│ a.rs ").expect("a.rs heading");
+    let b = screen.find("│ b.rs ").expect("b.rs heading");
+    assert!(a < b);
+    harness.assert_screen_contains("1:4: fn helper() {}");
+    harness.assert_screen_contains("2:1: helper();");
+}
+
+#[test]
+fn test_find_references_jumps_to_selected_result() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+    find_references(&mut harness);
+
+    // The cursor starts on the first result, in a.rs
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Jumped to a.rs:1");
+    assert_eq!(harness.cursor_position(), 3);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn helper() {}\nfn other() {}\nlet y = helper_two;\n"
+    );
+}
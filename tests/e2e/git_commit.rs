@@ -0,0 +1,162 @@
+//! E2E tests for committing from the editor
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A repository with one commit and a staged new file `b.txt`
+fn repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    git(dir, &["config", "user.name", "Test"]);
+    std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "First commit"]);
+    std::fs::write(dir.join("b.txt"), "staged line\n").unwrap();
+    git(dir, &["add", "b.txt"]);
+    temp_dir
+}
+
+fn save(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+#[test]
+fn test_commit_staged_changes() {
+    let repo = repo();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, repo.path().to_path_buf()).unwrap();
+
+    harness.run_command("Git: Commit").unwrap();
+    harness.assert_screen_contains("*Commit Message*");
+    harness.assert_screen_contains("*Staged Changes*");
+    harness.assert_screen_contains("+staged line");
+
+    harness.type_text("Add b").unwrap();
+    save(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("] Add b"))
+        .unwrap();
+    harness.assert_screen_not_contains("*Commit Message*");
+    harness.assert_screen_not_contains("*Staged Changes*");
+
+    assert_eq!(git(repo.path(), &["log", "-1", "--format=%s"]), "Add b\n");
+    assert_eq!(git(repo.path(), &["diff", "--cached", "--name-only"]), "");
+}
+
+#[test]
+fn test_nothing_staged() {
+    let repo = repo();
+    git(repo.path(), &["commit", "-q", "-m", "Add b"]);
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, repo.path().to_path_buf()).unwrap();
+
+    harness.run_command("Git: Commit").unwrap();
+    harness.assert_screen_contains("Nothing staged to commit");
+    harness.assert_screen_not_contains("*Commit Message*");
+}
+
+#[test]
+fn test_amend_commit_starts_with_last_message() {
+    let repo = repo();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, repo.path().to_path_buf()).unwrap();
+
+    harness.run_command("Git: Amend Commit").unwrap();
+    harness.assert_buffer_content("First commit\n");
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Body").unwrap();
+    save(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("] First commit"))
+        .unwrap();
+
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%B"]),
+        "First commit\nBody\n\n"
+    );
+    assert_eq!(git(repo.path(), &["rev-list", "--count", "HEAD"]), "1\n");
+    assert_eq!(
+        git(repo.path(), &["show", "--format=", "--name-only"]),
+        "a.txt\nb.txt\n"
+    );
+}
+
+/// A rejecting hook's output is shown and the message is kept for a retry
+#[test]
+fn test_failing_hook_output_is_shown() {
+    let repo = repo();
+    let hook = repo.path().join(".git/hooks/pre-commit");
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\necho 'lint: trailing whitespace in b.txt'\nexit 1\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, repo.path().to_path_buf()).unwrap();
+
+    harness.run_command("Git: Commit").unwrap();
+    harness.type_text("Add b").unwrap();
+    save(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Commit failed"))
+        .unwrap();
+    harness.assert_screen_contains("*Commit Output*");
+    harness.assert_screen_contains("lint: trailing whitespace in b.txt");
+    harness.assert_buffer_content("Add b");
+    assert_eq!(git(repo.path(), &["rev-list", "--count", "HEAD"]), "1\n");
+
+    // Fix the problem and commit again
+    std::fs::remove_file(&hook).unwrap();
+    save(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("] Add b"))
+        .unwrap();
+    harness.assert_screen_not_contains("*Commit Output*");
+}
+
+/// The message buffer tints the ruler columns
+#[test]
+fn test_commit_message_rulers() {
+    let repo = repo();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, repo.path().to_path_buf()).unwrap();
+
+    harness.run_command("Git: Commit").unwrap();
+    harness.type_text("Subject").unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    let (row, line) = screen
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.contains("Subject"))
+        .unwrap();
+    let start = line[..line.find("Subject").unwrap()].chars().count() as u16;
+    let bg = |col: u16| harness.get_cell_style(start + col, row as u16).unwrap().bg;
+    assert_eq!(bg(50), bg(72));
+    assert_ne!(bg(50), bg(60));
+}
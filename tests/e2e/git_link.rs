@@ -0,0 +1,75 @@
+//! E2E tests for links to files on the git remote
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A repository with `src/lib.rs` committed and a GitHub `origin` remote
+fn repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    git(dir, &["config", "user.name", "Test"]);
+    git(
+        dir,
+        &["remote", "add", "origin", "git@github.com:owner/repo.git"],
+    );
+    std::fs::create_dir(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "one\ntwo\nthree\nfour\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "First commit"]);
+    temp_dir
+}
+
+#[test]
+fn test_copy_permalink_to_selection() {
+    let repo = repo();
+    let commit = git(repo.path(), &["rev-parse", "HEAD"]);
+    let mut harness =
+        EditorTestHarness::with_working_dir(200, 24, repo.path().to_path_buf()).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.open_file(&repo.path().join("src/lib.rs")).unwrap();
+
+    // Select lines 2 and 3
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+
+    harness.run_command("Git: Copy Permalink").unwrap();
+    let url = format!(
+        "https://github.com/owner/repo/blob/{}/src/lib.rs#L2-L3",
+        commit.trim()
+    );
+    harness.assert_screen_contains(&format!("Copied {}", url));
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().paste_for_test();
+    assert!(harness.get_buffer_content().unwrap().contains(&url));
+}
+
+#[test]
+fn test_permalink_without_remote() {
+    let repo = repo();
+    git(repo.path(), &["remote", "remove", "origin"]);
+    let mut harness =
+        EditorTestHarness::with_working_dir(200, 24, repo.path().to_path_buf()).unwrap();
+    harness.open_file(&repo.path().join("src/lib.rs")).unwrap();
+
+    harness.run_command("Git: Open on Remote").unwrap();
+    harness.assert_screen_contains("No 'origin' remote");
+}
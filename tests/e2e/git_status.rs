@@ -0,0 +1,99 @@
+//! E2E tests for the git status in the status bar and branch checkout
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+}
+
+/// A repository on `main` with one commit and a `feature` branch
+fn repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    git(dir, &["config", "user.name", "Test"]);
+    std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "first"]);
+    git(dir, &["branch", "feature"]);
+    temp_dir
+}
+
+/// The left part of the status bar, e.g. `[No Name] | Ln 0, Col 0 | main`
+fn status_bar(harness: &EditorTestHarness) -> String {
+    let screen = harness.screen_to_string();
+    let lines: Vec<&str> = screen.lines().collect();
+    let bar = lines[lines.len() - 2];
+    bar.split("  ").next().unwrap_or(bar).to_string()
+}
+
+#[test]
+fn test_status_bar_shows_branch_and_changes() {
+    let repo = repo();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 24, repo.path().to_path_buf()).unwrap();
+    harness
+        .wait_until(|h| status_bar(h).ends_with("| main"))
+        .unwrap();
+
+    std::fs::write(repo.path().join("b.txt"), "b\n").unwrap();
+    harness
+        .wait_until(|h| status_bar(h).ends_with("| main *"))
+        .unwrap();
+}
+
+#[test]
+fn test_checkout_branch() {
+    let repo = repo();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 24, repo.path().to_path_buf()).unwrap();
+    harness
+        .wait_until(|h| status_bar(h).ends_with("| main"))
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Git: Checkout Branch").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("current");
+    harness.type_text("feature").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| status_bar(h).contains("| feature | Switched to branch"))
+        .unwrap();
+}
+
+/// The status bar has no git status outside a repository
+#[test]
+fn test_no_git_status_outside_repository() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    harness.process_async_and_render().unwrap();
+    assert!(!status_bar(&harness).contains("main"));
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Git: Checkout Branch").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Cannot list branches");
+}
@@ -0,0 +1,40 @@
+//! Tests for the "Grammar: Install..." command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Open the install grammar prompt from the command palette
+fn open_install_prompt(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Grammar: Install").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_install_grammar_command_opens_prompt() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    open_install_prompt(&mut harness);
+
+    harness.assert_screen_contains("Install grammar");
+    assert!(harness.editor().is_prompting());
+}
+
+#[test]
+fn test_install_grammar_rejects_invalid_source() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    open_install_prompt(&mut harness);
+
+    harness.type_text("not a grammar").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(!harness.editor().is_prompting());
+    harness.assert_screen_contains("Expected a .vsix URL");
+}
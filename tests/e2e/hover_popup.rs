@@ -0,0 +1,94 @@
+//! Tests for focusing, copying and pinning the hover popup
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::event::{Event, PopupContentData, PopupData, PopupPositionData};
+
+/// Show a hover popup as the LSP hover response does
+fn show_hover(harness: &mut EditorTestHarness) {
+    let lines = (0..20).map(|i| format!("hover doc {:02}", i)).collect();
+    harness
+        .editor_mut()
+        .active_state_mut()
+        .apply(&Event::ShowPopup {
+            popup: PopupData {
+                title: Some("Hover".to_string()),
+                transient: true,
+                content: PopupContentData::Text(lines),
+                position: PopupPositionData::Fixed { x: 10, y: 5 },
+                width: 30,
+                max_height: 6,
+                bordered: true,
+            },
+        });
+    harness.render().unwrap();
+}
+
+/// Pressing the hover key again focuses the popup so it can be scrolled
+#[test]
+fn test_hover_key_focuses_hover_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_hover(&mut harness);
+
+    harness
+        .send_key(KeyCode::Char('k'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    let popup = harness.editor().active_state().popups.top().unwrap();
+    assert!(!popup.transient);
+
+    // Down scrolls the popup instead of dismissing it
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("hover doc 01");
+    harness.assert_screen_not_contains("hover doc 00");
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().active_state().popups.top().is_none());
+}
+
+/// An unfocused hover popup is still dismissed by any other key
+#[test]
+fn test_hover_popup_dismissed_without_focus() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_hover(&mut harness);
+
+    harness.type_text("x").unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().active_state().popups.top().is_none());
+    harness.assert_buffer_content("x");
+}
+
+/// A focused popup can be copied, and pinned to stay while editing
+#[test]
+fn test_copy_and_pin_hover_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_hover(&mut harness);
+    harness
+        .send_key(KeyCode::Char('k'), KeyModifiers::ALT)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_state().popups.pinned().len(), 1);
+
+    // Typing goes to the buffer while the pinned popup stays
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("hover doc 00\nhover doc 01"));
+    harness.assert_screen_contains("Hover");
+
+    harness.run_command("Close Pinned Popups").unwrap();
+    assert!(harness.editor().active_state().popups.pinned().is_empty());
+}
@@ -0,0 +1,54 @@
+//! Tests for ignore files and the global ignore patterns
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn explorer_harness(temp_dir: &TempDir, config: Config) -> EditorTestHarness {
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::write(root.join("notes.txt"), "notes\n").unwrap();
+    std::fs::write(root.join("secret.txt"), "secret\n").unwrap();
+    std::fs::write(root.join("debug.log"), "log\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, root).unwrap();
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("notes.txt").unwrap();
+    harness
+}
+
+#[test]
+fn test_explorer_hides_global_ignore_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        ignore_patterns: vec!["*.log".to_string()],
+        ..Default::default()
+    };
+    let harness = explorer_harness(&temp_dir, config);
+
+    harness.assert_screen_contains("secret.txt");
+    harness.assert_screen_not_contains("debug.log");
+}
+
+#[test]
+fn test_explorer_reloads_changed_ignore_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = explorer_harness(&temp_dir, Config::default());
+    harness.assert_screen_contains("secret.txt");
+    harness.assert_screen_contains("debug.log");
+
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::write(root.join(".ignore"), "secret.txt\n").unwrap();
+    harness
+        .wait_until(|h| !h.screen_to_string().contains("secret.txt"))
+        .unwrap();
+
+    std::fs::write(root.join(".ignore"), "*.log\n").unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("secret.txt") && !screen.contains("debug.log")
+        })
+        .unwrap();
+}
@@ -0,0 +1,77 @@
+//! Tests for keys reported by the kitty keyboard protocol
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, Keybinding};
+use fresh::input::keyboard::KeyboardProtocol;
+
+/// With Shift held the protocol reports the shifted character, which must
+/// still match bindings written with the unshifted key
+#[test]
+fn test_shifted_character_matches_ctrl_shift_binding() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello").unwrap();
+
+    // Ctrl+Shift+1 sets bookmark 1
+    harness
+        .send_key(
+            KeyCode::Char('!'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Bookmark '1' set");
+}
+
+#[test]
+fn test_super_and_ctrl_enter_bindings() {
+    let config = Config {
+        keybindings: vec![
+            Keybinding {
+                key: "l".to_string(),
+                modifiers: vec!["super".to_string()],
+                keys: vec![],
+                action: "toggle_line_numbers".to_string(),
+                args: Default::default(),
+                when: None,
+            },
+            Keybinding {
+                key: "Enter".to_string(),
+                modifiers: vec!["ctrl".to_string()],
+                keys: vec![],
+                action: "select_all".to_string(),
+                args: Default::default(),
+                when: None,
+            },
+        ],
+        ..Default::default()
+    };
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("one two").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("    1 │ one two");
+
+    harness
+        .send_key(KeyCode::Char('l'), KeyModifiers::SUPER)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("1 │ one two");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "one two");
+}
+
+#[test]
+fn test_show_keyboard_protocol_command() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.run_command("Show Keyboard Protocol").unwrap();
+    harness.assert_screen_contains("Keyboard protocol: legacy");
+
+    harness
+        .editor_mut()
+        .set_keyboard_protocol(KeyboardProtocol::Kitty);
+    harness.run_command("Show Keyboard Protocol").unwrap();
+    harness.assert_screen_contains("Keyboard protocol: kitty (CSI-u)");
+}
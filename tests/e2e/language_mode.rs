@@ -0,0 +1,87 @@
+//! Tests for the "Change Language Mode" command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Open a Rust file (test editors only have the "Plain Text" grammar, so
+/// Rust is detected through tree-sitter)
+fn open_rust_file(harness: &mut EditorTestHarness, temp_dir: &TempDir) {
+    let file_path = temp_dir.path().join("main.rs");
+    std::fs::write(&file_path, "fn main() {\n    let x = 1;\n}\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+}
+
+/// Run "Change Language Mode" and pick `language`
+fn change_language(harness: &mut EditorTestHarness, language: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Change Language Mode").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text(language).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_change_language_mode_overrides_syntax() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    open_rust_file(&mut harness, &temp_dir);
+
+    // Detected from the .rs extension
+    harness.assert_screen_contains(" Rust ");
+
+    change_language(&mut harness, "Plain Text");
+
+    assert_eq!(
+        harness.editor().active_state().syntax_override.as_deref(),
+        Some("Plain Text")
+    );
+    assert_eq!(
+        harness.editor().active_state().highlighter.syntax_name(),
+        Some("Plain Text")
+    );
+    harness.assert_screen_contains(" Plain Text ");
+    harness.assert_screen_not_contains(" Rust ");
+}
+
+#[test]
+fn test_change_language_mode_auto_detect_clears_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    open_rust_file(&mut harness, &temp_dir);
+
+    change_language(&mut harness, "Plain Text");
+    change_language(&mut harness, "Auto Detect");
+
+    assert_eq!(harness.editor().active_state().syntax_override, None);
+    harness.assert_screen_contains(" Rust ");
+}
+
+#[test]
+fn test_clicking_status_bar_language_opens_prompt() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    open_rust_file(&mut harness, &temp_dir);
+
+    // Status bar is the second-to-last row
+    let status_row = 22;
+    let status_line = harness.get_screen_row(status_row as usize);
+    let col = status_line
+        .find(" Rust ")
+        .expect("status bar should show the language");
+    let col = status_line[..col].chars().count() as u16;
+
+    harness.mouse_click(col + 1, status_row).unwrap();
+    harness.render().unwrap();
+
+    assert!(harness.editor().is_prompting());
+    harness.assert_screen_contains("Language mode:");
+}
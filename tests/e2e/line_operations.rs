@@ -0,0 +1,143 @@
+//! Tests for the line operation commands (sort, reverse, join, align, ...)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Select `count` lines down from the start of the buffer
+fn select_first_lines(harness: &mut EditorTestHarness, count: usize) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..count {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_sort_selected_lines_is_one_undo_step() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("pear\napple\nfig\nzucchini\n")
+        .unwrap();
+
+    select_first_lines(&mut harness, 3);
+    harness.run_command("Sort Lines Ascending").unwrap();
+    harness.assert_screen_contains("Sorted 3 lines");
+    harness.assert_buffer_content("apple\nfig\npear\nzucchini\n");
+
+    // The lines stay selected, so operations can be chained
+    harness.run_command("Reverse Lines").unwrap();
+    harness.assert_buffer_content("pear\nfig\napple\nzucchini\n");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("apple\nfig\npear\nzucchini\n");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("pear\napple\nfig\nzucchini\n");
+}
+
+#[test]
+fn test_line_operations_on_whole_buffer() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("10 b\n9 a\nb\n10 b\n")
+        .unwrap();
+
+    harness.run_command("Remove Duplicate Lines").unwrap();
+    harness.assert_buffer_content("10 b\n9 a\nb\n");
+
+    harness.run_command("Sort Lines Numerically").unwrap();
+    harness.assert_buffer_content("b\n9 a\n10 b\n");
+
+    harness.run_command("Sort Lines Descending").unwrap();
+    harness.assert_buffer_content("b\n9 a\n10 b\n");
+    harness.assert_screen_contains("3 lines unchanged");
+}
+
+#[test]
+fn test_join_lines() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("call(\n    a,\n    b)\nend\n")
+        .unwrap();
+
+    // Without a selection the cursor line is joined with the next one
+    harness.run_command("Join Lines").unwrap();
+    harness.assert_buffer_content("call( a,\n    b)\nend\n");
+
+    select_first_lines(&mut harness, 2);
+    harness.run_command("Join Lines").unwrap();
+    harness.assert_buffer_content("call( a, b)\nend\n");
+}
+
+#[test]
+fn test_align_on_delimiter_with_preview() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let original = "host = example.org\nport=80\ntimeout   = 5\n";
+    let _fixture = harness.load_buffer_from_text(original).unwrap();
+
+    harness.run_command("Align On...").unwrap();
+    harness.type_text("=").unwrap();
+    harness.render().unwrap();
+    // The aligned lines are previewed before anything changes
+    harness.assert_screen_contains("port    = 80");
+    harness.assert_buffer_content(original);
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Aligned 3 lines on '='");
+    harness.assert_buffer_content("host    = example.org\nport    = 80\ntimeout = 5\n");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(original);
+}
+
+#[test]
+fn test_align_on_invalid_regex() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("a = 1\nbb = 2\n").unwrap();
+
+    harness.run_command("Align On...").unwrap();
+    harness.type_text("/(/").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Invalid regex");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("a = 1\nbb = 2\n");
+}
+
+#[test]
+fn test_reflow_paragraph_keeps_comment_markers() {
+    let mut config = fresh::config::Config::default();
+    config.editor.reflow_width = 24;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    let original = "// one two three four five six seven\n// eight\n\nnext\n";
+    let _fixture = harness.load_buffer_from_text(original).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Reflowed to 24 columns");
+    harness.assert_buffer_content("// one two three four\n// five six seven eight\n\nnext\n");
+
+    // The cursor moves past the paragraph, then back to its start
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.run_command("Next Paragraph").unwrap();
+    harness.type_text("x").unwrap();
+    harness.assert_buffer_content("// one two three four\n// five six seven eight\nx\nnext\n");
+}
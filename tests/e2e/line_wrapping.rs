@@ -206,6 +206,38 @@ fn test_wrapped_line_navigation_up_down() {
     assert!(harness.cursor_position() >= second_line_end - 20);
 }
 
+/// Up/Down move by screen rows through a wrapped line, keeping the column
+#[test]
+fn test_wrapped_line_up_down_moves_by_rows() {
+    let mut harness = EditorTestHarness::new(60, 24).unwrap();
+
+    // 51 columns of text per row: the first line takes rows 0..51 and 51..100
+    let long_line = "0123456789".repeat(10);
+    harness.type_text(&long_line).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("ab").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 5)
+        .unwrap();
+
+    // The second row of the long line, then the end of the short line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 56);
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 103);
+
+    // Back up through the rows, to the remembered column
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 56);
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 5);
+}
+
 /// Test deleting content in wrapped lines
 #[test]
 fn test_wrapped_line_deletion() {
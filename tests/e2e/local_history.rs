@@ -0,0 +1,86 @@
+//! Tests for the local history list (diff against / restore earlier versions)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, EditorConfig};
+
+/// Edit a file to "one", auto-save it, edit it to "one two" and open the
+/// local history list
+fn edited_with_history() -> (EditorTestHarness, tempfile::TempDir) {
+    let config = Config {
+        editor: EditorConfig {
+            auto_save_interval_secs: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut harness = EditorTestHarness::with_config(100, 30, config).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "").unwrap();
+    harness.open_file(&file).unwrap();
+
+    harness.type_text("one").unwrap();
+    let saved = harness.editor_mut().auto_save_dirty_buffers().unwrap();
+    assert_eq!(saved, 1);
+    harness.type_text(" two").unwrap();
+
+    harness.run_command("Local History").unwrap();
+    harness.assert_screen_contains("Local history: notes.txt (3 version(s))");
+    harness.assert_screen_contains("Undo checkpoint");
+    harness.assert_screen_contains("Autosave");
+    harness.assert_screen_contains("as opened");
+    (harness, temp_dir)
+}
+
+/// Move to the n-th version in the list (newest first)
+fn select_version(harness: &mut EditorTestHarness, n: usize) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    // Below the two header lines
+    for _ in 0..n + 2 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+}
+
+#[test]
+fn test_diff_buffer_against_autosave() {
+    let (mut harness, _temp_dir) = edited_with_history();
+
+    select_version(&mut harness, 1);
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("-one");
+    harness.assert_screen_contains("+one two");
+    harness.assert_screen_contains("2 changed line(s)");
+}
+
+#[test]
+fn test_restore_version_is_undoable() {
+    let (mut harness, _temp_dir) = edited_with_history();
+
+    select_version(&mut harness, 2);
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Restored the version as opened");
+    harness.assert_buffer_content("");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("one two");
+}
+
+#[test]
+fn test_local_history_without_edits() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("untouched\n").unwrap();
+
+    harness.run_command("Local History").unwrap();
+    harness.assert_screen_contains("No local history for this buffer");
+}
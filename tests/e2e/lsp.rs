@@ -3569,8 +3569,8 @@ fn test_hover_popup_persists_within_symbol_and_popup() -> std::io::Result<()> {
     harness.render()?;
 
     // Move mouse over the symbol "example_function" to trigger hover state
-    // The gutter takes some columns, so move to column ~10 which should be over the symbol
-    harness.mouse_move(10, 2)?;
+    // The gutter takes 8 columns, so column 11 is over the symbol
+    harness.mouse_move(11, 2)?;
     harness.render()?;
 
     // Force check mouse hover to bypass the 500ms timer and send the request
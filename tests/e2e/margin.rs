@@ -524,3 +524,148 @@ fn test_line_numbers_update_with_navigation_keys() {
     harness.render().unwrap();
     harness.assert_screen_contains("   1 │");
 }
+
+/// Write a file of `Line 1`..`Line n` and open it with `config`
+fn open_lines_with_config(n: usize, config: fresh::config::Config) -> (TempDir, EditorTestHarness) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    let content: String = (1..=n).map(|i| format!("Line {i}\n")).collect();
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (temp_dir, harness)
+}
+
+/// Test that the gutter shows only the configured components, in order
+#[test]
+fn test_gutter_components_follow_config_order() {
+    use fresh::config::GutterComponent;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.gutter = vec![GutterComponent::LineNumbers, GutterComponent::Bookmarks];
+    let (_temp_dir, mut harness) = open_lines_with_config(5, config);
+
+    // Bookmark line 3
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(
+            KeyCode::Char('1'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+
+    // Line numbers first, then the bookmark column
+    harness.assert_screen_contains("   1  │ Line 1");
+    harness.assert_screen_contains("   31 │ Line 3");
+
+    // The sign follows the line when text is inserted above it
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("New\n").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("   3  │ Line 2");
+    harness.assert_screen_contains("   41 │ Line 3");
+}
+
+/// Test that the gutter has no sign column when no sign component is configured
+#[test]
+fn test_gutter_line_numbers_only() {
+    use fresh::config::GutterComponent;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.gutter = vec![GutterComponent::LineNumbers];
+    let (_temp_dir, harness) = open_lines_with_config(3, config);
+
+    let row = (0..24)
+        .map(|y| harness.get_screen_row(y))
+        .find(|row| row.contains("Line 2"))
+        .unwrap();
+    assert!(row.starts_with("   2 │ Line 2"), "row was {row:?}");
+    assert_eq!(
+        harness.editor().active_state().margins.left_total_width(),
+        7
+    );
+}
+
+/// Test relative and hybrid line number modes
+#[test]
+fn test_gutter_line_number_modes() {
+    use fresh::config::LineNumberMode;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.line_number_mode = LineNumberMode::Relative;
+    let (_temp_dir, mut harness) = open_lines_with_config(5, config);
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("   1 │ Line 1");
+    harness.assert_screen_contains("   0 │ Line 2");
+    harness.assert_screen_contains("   2 │ Line 4");
+
+    let mut config = fresh::config::Config::default();
+    config.editor.line_number_mode = LineNumberMode::Hybrid;
+    let (_temp_dir, mut harness) = open_lines_with_config(5, config);
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("   1 │ Line 1");
+    harness.assert_screen_contains("   2 │ Line 2");
+    harness.assert_screen_contains("   2 │ Line 4");
+}
+
+/// Test that relative line numbers count from the cursor line in large file
+/// mode, where the line numbers shown are estimates
+#[test]
+fn test_relative_line_numbers_in_large_file_mode() {
+    use fresh::config::LineNumberMode;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.large_file_threshold_bytes = 500;
+    config.editor.line_number_mode = LineNumberMode::Relative;
+    let (_temp_dir, mut harness) = open_lines_with_config(100, config);
+
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("   0 │ Line 6");
+    harness.assert_screen_contains("   1 │ Line 5");
+    harness.assert_screen_contains("   1 │ Line 7");
+    harness.assert_screen_contains("   3 │ Line 9");
+}
+
+/// Test that wrapped lines count once in relative line numbers
+#[test]
+fn test_relative_line_numbers_with_wrapped_lines() {
+    use fresh::config::LineNumberMode;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    let long_line = format!("Long {}", "word ".repeat(30));
+    std::fs::write(&file_path, format!("Line 1\n{long_line}\nLine 3\nLine 4\n")).unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.line_wrap = true;
+    config.editor.line_number_mode = LineNumberMode::Relative;
+    let mut harness = EditorTestHarness::with_config(60, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    // Down moves over the rows of the long line to "Line 3"
+    let line_3 = "Line 1\n".len() + long_line.len() + 1;
+    for _ in 0..10 {
+        if harness.cursor_position() >= line_3 {
+            break;
+        }
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    assert_eq!(harness.cursor_position(), line_3);
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("   2 │ Line 1");
+    harness.assert_screen_contains("   1 │ Long word");
+    harness.assert_screen_contains("   0 │ Line 3");
+    harness.assert_screen_contains("   1 │ Line 4");
+}
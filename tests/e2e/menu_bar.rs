@@ -311,8 +311,8 @@ fn test_mouse_click_undo_menu_item() {
     // Undo should have reversed the last text insertion
     // Menu should be closed
     harness.assert_screen_not_contains("Undo");
-    // The last character should be undone (type_text inserts char by char)
-    harness.assert_buffer_content("Hello Worl");
+    // The last word typed should be undone
+    harness.assert_buffer_content("Hello ");
 }
 
 /// Test that View menu File Explorer checkbox syncs with actual file explorer state
@@ -702,3 +702,47 @@ fn test_toggle_menu_bar_visibility_and_auto_show() {
     harness.assert_screen_contains("File");
     harness.assert_screen_contains("Edit");
 }
+
+/// Test that the Go menu lists the navigation commands
+#[test]
+fn test_go_menu_lists_navigation_commands() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+
+    for entry in [
+        "Go to Matching Bracket",
+        "Next Error",
+        "Back",
+        "Forward",
+        "Jump to Bookmark...",
+        "Command Palette...",
+    ] {
+        harness.assert_screen_contains(entry);
+    }
+}
+
+/// Test that the File menu's Auto-Revert checkbox follows the setting
+#[test]
+fn test_file_menu_auto_revert_checkbox() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("☑ Auto-Revert");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+
+    harness.editor_mut().toggle_auto_revert();
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("☐ Auto-Revert");
+}
@@ -1,23 +1,47 @@
+pub mod abbreviations;
 pub mod ansi_cursor;
 pub mod auto_indent;
 pub mod auto_revert;
+pub mod background_save;
 pub mod basic;
+pub mod batch_mode;
 pub mod binary_file;
+pub mod buffer_compaction;
 pub mod buffer_lifecycle;
+pub mod buffer_list;
 pub mod buffer_settings_commands;
+pub mod collab;
 pub mod command_palette;
+pub mod config_doctor;
+pub mod crash_report;
 pub mod crlf_rendering;
+pub mod diff_view;
 pub mod document_model;
+pub mod editor_context_menu;
 pub mod emacs_actions;
+pub mod error_feedback;
 pub mod explorer_menu;
+pub mod export;
 pub mod file_browser;
 pub mod file_explorer;
+pub mod file_management;
 pub mod file_permissions;
+pub mod find_references;
+pub mod git_commit;
+pub mod git_link;
+pub mod git_status;
+pub mod grammar_install;
+pub mod hover_popup;
+pub mod ignore_files;
 pub mod indent_dedent;
+pub mod keyboard_protocol;
+pub mod language_mode;
 pub mod large_file_mode;
 pub mod lifecycle;
+pub mod line_operations;
 pub mod line_wrapping;
 pub mod live_grep;
+pub mod local_history;
 pub mod lsp;
 pub mod lsp_order;
 pub mod margin;
@@ -29,24 +53,38 @@ pub mod movement;
 pub mod multi_file_opening;
 pub mod multibyte_characters;
 pub mod multicursor;
+pub mod notifications;
 pub mod on_save_actions;
 pub mod open_folder;
 pub mod paste;
+pub mod persistent_undo;
+pub mod plugin_fetch;
+pub mod plugin_health;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod position_history;
 pub mod position_history_bugs;
 pub mod position_history_debug;
 pub mod position_history_truncate_debug;
+#[cfg(unix)]
+pub mod progressive_load;
 pub mod prompt;
 pub mod prompt_editing;
+pub mod protected_mode;
+pub mod quickfix;
+pub mod quit_dialog;
 pub mod recovery;
+pub mod remote_open;
+pub mod rename_symbol;
 pub mod rendering;
+pub mod repl;
+pub mod scratch_buffers;
 pub mod scroll_clearing;
 pub mod scrolling;
 pub mod search;
 pub mod selection;
 pub mod session;
+pub mod session_recording;
 pub mod settings;
 pub mod shell_command;
 pub mod slow_filesystem;
@@ -54,18 +92,30 @@ pub mod smart_editing;
 pub mod split_tabs;
 pub mod split_view;
 pub mod split_view_expectations;
+pub mod split_working_dir;
+pub mod ssh_files;
 pub mod stdin_input;
+pub mod sticky_scroll;
+pub mod sudo_save;
 pub mod tab_config;
+pub mod tab_context_menu;
 pub mod tab_indent_selection;
 pub mod tab_scrolling;
+pub mod tags;
+pub mod tasks;
 pub mod terminal;
 pub mod terminal_close;
 pub mod terminal_resize;
+pub mod terminal_title;
 pub mod test_scrollbar_keybinds_cursor;
 pub mod theme;
 pub mod undo_redo;
 pub mod unicode_cursor;
 pub mod unicode_prompt_bugs;
 pub mod update_notification;
+pub mod vfs;
 pub mod virtual_lines;
 pub mod visual_regression;
+pub mod workspace_edit;
+pub mod workspace_folders;
+pub mod zen_mode;
@@ -2,7 +2,9 @@
 
 use crate::common::fixtures::TestFixture;
 use crate::common::harness::EditorTestHarness;
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
+use fresh::model::event::{Event, PopupContentData, PopupData, PopupPositionData};
+use fresh::view::popup::PopupPosition;
 use std::fs;
 
 /// Test scrollbar rendering in a single split
@@ -1488,6 +1490,34 @@ fn test_mouse_hover_updates_on_position_change() {
     );
 }
 
+/// Test that only identifiers are hovered
+#[test]
+fn test_mouse_hover_ignores_whitespace() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let content = "let  value = 1;\n";
+    let _fixture = harness.load_buffer_from_text(content).unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+
+    // Over "let" (text starts after the 8-column gutter)
+    harness.mouse_move(9, row).unwrap();
+    assert!(harness.editor().get_mouse_hover_state().is_some());
+
+    // Over the spaces between "let" and "value"
+    harness.mouse_move(12, row).unwrap();
+    assert!(
+        harness.editor().get_mouse_hover_state().is_none(),
+        "Whitespace should not be hovered"
+    );
+
+    // Over "value"
+    harness.mouse_move(14, row).unwrap();
+    assert!(harness.editor().get_mouse_hover_state().is_some());
+}
+
 /// Test that moving mouse to gutter clears hover state
 #[test]
 fn test_mouse_hover_clears_in_gutter() {
@@ -1560,7 +1590,7 @@ fn test_mouse_hover_same_position_preserves_state() {
     harness.render().unwrap();
 
     let (content_first_row, _) = harness.content_area_rows();
-    let col = 12;
+    let col = 10; // Over "test" (whitespace is not hovered)
     let row = content_first_row as u16;
 
     // Move to position
@@ -1739,3 +1769,179 @@ fn test_double_click_requires_same_position() {
         "Double-click at same position SHOULD select a word, but got empty selection"
     );
 }
+
+/// Show a persistent text popup with 20 numbered lines at a fixed position
+/// Outer area is x=10..40, y=5..11; content rows are 6..10
+fn show_doc_popup(harness: &mut EditorTestHarness, transient: bool) {
+    let lines = (0..20).map(|i| format!("doc line {:02}", i)).collect();
+    harness
+        .editor_mut()
+        .active_state_mut()
+        .apply(&Event::ShowPopup {
+            popup: PopupData {
+                title: Some("Docs".to_string()),
+                transient,
+                content: PopupContentData::Text(lines),
+                position: PopupPositionData::Fixed { x: 10, y: 5 },
+                width: 30,
+                max_height: 6,
+                bordered: true,
+            },
+        });
+    harness.render().unwrap();
+}
+
+/// Test that the mouse wheel scrolls the popup under the mouse
+#[test]
+fn test_mouse_wheel_scrolls_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_doc_popup(&mut harness, false);
+    harness.assert_screen_contains("doc line 00");
+
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 15,
+            row: 7,
+            modifiers: KeyModifiers::NONE,
+        })
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_not_contains("doc line 00");
+    harness.assert_screen_contains("doc line 03");
+    assert!(harness.editor().active_state().popups.is_visible());
+
+    // Scrolling back up past the top stops at the first line
+    for _ in 0..3 {
+        harness
+            .send_mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 15,
+                row: 7,
+                modifiers: KeyModifiers::NONE,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+    harness.assert_screen_contains("doc line 00");
+}
+
+/// Test that dragging a popup's title bar moves it
+#[test]
+fn test_mouse_drag_moves_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_doc_popup(&mut harness, false);
+
+    harness.mouse_drag(12, 5, 22, 9).unwrap();
+
+    let popup = harness.editor().active_state().popups.top().unwrap();
+    assert_eq!(popup.position, PopupPosition::Fixed { x: 20, y: 9 });
+    assert!(harness.get_screen_row(9).contains("Docs"));
+}
+
+/// Test that dragging a popup's bottom-right corner resizes it
+#[test]
+fn test_mouse_drag_resizes_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_doc_popup(&mut harness, false);
+
+    harness.mouse_drag(39, 10, 49, 12).unwrap();
+
+    let popup = harness.editor().active_state().popups.top().unwrap();
+    assert_eq!(popup.width, 40);
+    assert_eq!(popup.max_height, 8);
+    // Two more content rows are visible
+    harness.assert_screen_contains("doc line 05");
+}
+
+/// Test that transient popups (hover, signature help) can't be dragged
+#[test]
+fn test_transient_popup_not_draggable() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    show_doc_popup(&mut harness, true);
+
+    harness.mouse_drag(12, 5, 22, 9).unwrap();
+
+    let popup = harness.editor().active_state().popups.top();
+    assert!(popup.is_none_or(|p| p.position == PopupPosition::Fixed { x: 10, y: 5 }));
+}
+
+/// Test that a triple-click selects the whole line
+#[test]
+fn test_triple_click_selects_line() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("hello world goodbye\nsecond line here\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+    for _ in 0..3 {
+        harness.mouse_click(10, row).unwrap();
+    }
+
+    assert_eq!(harness.get_selected_text(), "hello world goodbye\n");
+}
+
+/// Test that Ctrl+click adds a cursor instead of moving the cursor
+#[test]
+fn test_ctrl_click_adds_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("first line\nsecond line\nthird line\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let row = content_first_row as u16;
+    harness.mouse_click(10, row).unwrap();
+    for kind in [
+        MouseEventKind::Down(crossterm::event::MouseButton::Left),
+        MouseEventKind::Up(crossterm::event::MouseButton::Left),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: 10,
+                row: row + 2,
+                modifiers: KeyModifiers::CONTROL,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_count(), 2);
+
+    // Typing goes to both cursors
+    harness.type_text("X").unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    let lines: Vec<_> = content.lines().collect();
+    assert!(lines[0].contains('X'), "{content:?}");
+    assert_eq!(lines[1], "second line");
+    assert!(lines[2].contains('X'), "{content:?}");
+}
+
+/// Test that the mouse wheel scrolls `mouse_scroll_lines` lines per notch
+#[test]
+fn test_mouse_scroll_lines_config() {
+    let mut config = fresh::config::Config::default();
+    config.editor.mouse_scroll_lines = 5;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    let content: String = (1..=100).map(|i| format!("Line {i}\n")).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+
+    let initial_top_line = harness.top_line_number();
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 40,
+            row: 10,
+            modifiers: KeyModifiers::NONE,
+        })
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.top_line_number(), initial_top_line + 5);
+}
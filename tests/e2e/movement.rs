@@ -650,3 +650,56 @@ fn test_movement_through_multiple_empty_lines() {
         "Should be somewhere on Line 6, got position {final_pos}"
     );
 }
+
+/// Home goes to the first non-whitespace character, then to column 0
+#[test]
+fn test_home_toggles_between_indentation_and_line_start() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("    indented").unwrap();
+    assert_eq!(harness.cursor_position(), 12);
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 4, "First Home stops at the text");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 0, "Second Home goes to column 0");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        4,
+        "Third Home goes back to the text"
+    );
+
+    // Shift+Home selects back to the text, not the indentation
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "indented");
+}
+
+/// Ctrl+End and Ctrl+Home on a large file land on the buffer's ends
+#[test]
+fn test_document_start_and_end_on_large_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("large.txt");
+    let content: String = (0..100_000).map(|i| format!("line {i}\n")).collect();
+    std::fs::write(&file_path, &content).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), content.len());
+    harness.assert_screen_contains("line 99999");
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+    harness.assert_screen_contains("line 0");
+    assert_eq!(harness.top_byte(), 0);
+}
@@ -0,0 +1,82 @@
+//! E2E tests for toast notifications and the notification history panel
+
+use crate::common::harness::EditorTestHarness;
+use fresh::view::notification::NotificationLevel;
+use std::time::Duration;
+
+/// Toasts are rendered and disappear once the timeout elapses
+#[test]
+fn test_toast_auto_dismisses() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    harness
+        .editor_mut()
+        .notify(NotificationLevel::Info, "Build finished");
+    harness.render().unwrap();
+    harness.assert_screen_contains("Build finished");
+
+    // Default timeout is 4 seconds
+    harness.advance_time(Duration::from_secs(5));
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_not_contains("Build finished");
+}
+
+/// Error toasts stay on screen longer than info toasts
+#[test]
+fn test_error_toast_lingers() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    harness
+        .editor_mut()
+        .notify(NotificationLevel::Info, "Saved");
+    harness
+        .editor_mut()
+        .notify(NotificationLevel::Error, "Formatter crashed");
+    harness.render().unwrap();
+    harness.assert_screen_contains("Saved");
+    harness.assert_screen_contains("Formatter crashed");
+
+    harness.advance_time(Duration::from_secs(5));
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_not_contains("Saved");
+    harness.assert_screen_contains("Formatter crashed");
+
+    harness.advance_time(Duration::from_secs(4));
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_not_contains("Formatter crashed");
+}
+
+/// Expired notifications can still be reviewed with "Show Notifications"
+#[test]
+fn test_show_notifications_history() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    harness
+        .editor_mut()
+        .notify(NotificationLevel::Warning, "Disk almost full");
+    harness.advance_time(Duration::from_secs(10));
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_not_contains("Disk almost full");
+
+    harness.run_command("Show Notifications").unwrap();
+
+    harness.assert_screen_contains("*Notifications*");
+    harness.assert_screen_contains("Disk almost full");
+    harness.assert_screen_contains("Warning");
+}
+
+/// "Dismiss Notifications" hides visible toasts immediately
+#[test]
+fn test_dismiss_notifications() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    harness
+        .editor_mut()
+        .notify(NotificationLevel::Info, "Indexing workspace");
+    harness.render().unwrap();
+    harness.assert_screen_contains("Indexing workspace");
+
+    harness.run_command("Dismiss Notifications").unwrap();
+    harness.assert_screen_not_contains("Indexing workspace");
+    assert_eq!(harness.editor().notifications().history_len(), 1);
+}
@@ -11,6 +11,7 @@
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::{Config, FormatterConfig, LanguageConfig, OnSaveAction};
+use std::collections::HashMap;
 use tempfile::TempDir;
 
 /// Test format_on_save with formatter (replaces buffer content)
@@ -50,6 +51,8 @@ fn test_format_on_save() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -113,6 +116,8 @@ fn test_on_save_linter_style() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -176,6 +181,8 @@ fn test_on_save_action_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -248,6 +255,8 @@ fn test_on_save_file_placeholder() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -314,6 +323,8 @@ fn test_formatter_stdin_mode() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -387,6 +398,8 @@ fn test_on_save_stops_on_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action1, action2],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
@@ -481,6 +494,8 @@ fn test_formatter_not_found_shows_message() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            abbreviations: HashMap::new(),
+            repl: None,
         },
     );
 
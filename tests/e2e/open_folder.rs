@@ -806,3 +806,94 @@ fn test_session_persistence_across_project_switches() {
         harness.assert_screen_contains("file_a.txt");
     }
 }
+
+/// Switching project with unsaved changes asks first, as quitting does:
+/// cancel keeps the project, save writes the buffers and switches
+#[test]
+fn test_switch_project_with_unsaved_changes_asks_first() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_a = temp_dir.path().join("project_a");
+    let project_b = temp_dir.path().join("project_b");
+    fs::create_dir(&project_a).unwrap();
+    fs::create_dir(&project_b).unwrap();
+    let project_a = project_a.canonicalize().unwrap();
+    let project_b = project_b.canonicalize().unwrap();
+    let file_a = project_a.join("notes.txt");
+    fs::write(&file_a, "saved").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        Default::default(),
+        project_a.clone(),
+    )
+    .unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(" edited").unwrap();
+
+    switch_to_project(&mut harness, &project_b);
+    harness.assert_screen_contains("1 buffer has unsaved changes");
+    assert!(
+        !harness.should_quit(),
+        "Should not switch before the answer"
+    );
+
+    harness.type_text("c").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_screen_contains("Switch project cancelled");
+    assert!(!harness.should_quit());
+    assert_eq!(harness.editor_mut().take_restart_dir(), None);
+
+    switch_to_project(&mut harness, &project_b);
+    harness.type_text("s").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(
+        harness.should_quit(),
+        "Should switch once the buffer is saved"
+    );
+    assert_eq!(
+        harness.editor_mut().take_restart_dir(),
+        Some(project_b.clone())
+    );
+    assert_eq!(fs::read_to_string(&file_a).unwrap(), "saved edited");
+}
+
+/// Discarding unsaved changes switches without writing them
+#[test]
+fn test_switch_project_discarding_unsaved_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_a = temp_dir.path().join("project_a");
+    let project_b = temp_dir.path().join("project_b");
+    fs::create_dir(&project_a).unwrap();
+    fs::create_dir(&project_b).unwrap();
+    let project_a = project_a.canonicalize().unwrap();
+    let project_b = project_b.canonicalize().unwrap();
+    let file_a = project_a.join("notes.txt");
+    fs::write(&file_a, "saved").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        Default::default(),
+        project_a.clone(),
+    )
+    .unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness.type_text("lost ").unwrap();
+
+    switch_to_project(&mut harness, &project_b);
+    harness.type_text("d").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.should_quit());
+    assert_eq!(harness.editor_mut().take_restart_dir(), Some(project_b));
+    assert_eq!(fs::read_to_string(&file_a).unwrap(), "saved");
+}
@@ -830,3 +830,111 @@ fn test_paste_crlf_into_prompt() {
     // Prompt should contain the text (newlines may be shown differently in prompt)
     harness.assert_screen_contains("line1");
 }
+
+/// Test that files dropped on the terminal are offered to open in tabs
+#[test]
+fn test_paste_dropped_files_opens_them() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first file.txt");
+    let second = dir.path().join("second.txt");
+    std::fs::write(&first, "first contents").unwrap();
+    std::fs::write(&second, "second contents").unwrap();
+
+    // Terminals paste dropped files as quoted paths
+    harness.editor_mut().paste_from_terminal(format!(
+        "'{}' '{}' ",
+        first.display(),
+        second.display()
+    ));
+    harness.render().unwrap();
+    harness.assert_screen_contains("Open 2 dropped files?");
+    harness.assert_buffer_content("");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("first file.txt");
+    harness.assert_screen_contains("second.txt");
+    harness.assert_buffer_content("second contents");
+}
+
+/// Test that declining to open a dropped file pastes its path
+#[test]
+fn test_paste_dropped_file_declined_inserts_path() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "").unwrap();
+    let path = file.display().to_string();
+
+    harness.editor_mut().paste_from_terminal(path.clone());
+    harness.render().unwrap();
+    harness.assert_screen_contains("Open dropped file");
+
+    harness.type_text("n").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content(&path);
+
+    // Text that isn't a path is pasted right away
+    harness
+        .editor_mut()
+        .paste_from_terminal(" and more".to_string());
+    harness.assert_buffer_content(&format!("{} and more", path));
+}
+
+/// Test picking an entry from the clipboard history, re-indented and as plain text
+#[test]
+fn test_paste_from_clipboard_history() {
+    let mut harness =
+        EditorTestHarness::with_config(80, 24, fresh::config::Config::default()).unwrap();
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("x();\n        y();".to_string());
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("latest".to_string());
+    harness.type_text("  ").unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('v'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Clipboard History");
+    harness.assert_screen_contains("latest");
+    harness.assert_screen_contains("2 lines");
+
+    // The older entry is re-indented to the cursor's line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("  x();\n  y();");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("  ");
+
+    // As plain text, it's pasted exactly as it was copied
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .type_text("Paste from History as Plain Text")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("  x();\n        y();");
+}
@@ -0,0 +1,99 @@
+//! Tests for undo history kept across sessions
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, EditorConfig};
+use fresh::config_io::DirectoryContext;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn start(dir_context: &DirectoryContext, config: Config, project: &Path) -> EditorTestHarness {
+    EditorTestHarness::with_shared_dir_context(
+        80,
+        24,
+        config,
+        project.to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap()
+}
+
+/// First session: add a line to `file`, save it and close the editor
+fn edit_and_save(dir_context: &DirectoryContext, project: &Path, file: &Path) {
+    let mut harness = start(dir_context, Config::default(), project);
+    harness.open_file(file).unwrap();
+    harness.type_text("first\n").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.editor_mut().save_all_undo_histories();
+    assert_eq!(std::fs::read_to_string(file).unwrap(), "first\nsecond\n");
+}
+
+fn undo(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+#[test]
+fn test_undo_history_restored_in_next_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "second\n").unwrap();
+
+    edit_and_save(&dir_context, temp_dir.path(), &file);
+
+    let mut harness = start(&dir_context, Config::default(), temp_dir.path());
+    harness.open_file(&file).unwrap();
+    harness.assert_buffer_content("first\nsecond\n");
+
+    // "first\n" was typed as one word, and is undone as one step
+    undo(&mut harness);
+    harness.assert_buffer_content("second\n");
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("first\nsecond\n");
+}
+
+#[test]
+fn test_undo_history_ignored_when_file_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "second\n").unwrap();
+
+    edit_and_save(&dir_context, temp_dir.path(), &file);
+    std::fs::write(&file, "rewritten elsewhere\n").unwrap();
+
+    let mut harness = start(&dir_context, Config::default(), temp_dir.path());
+    harness.open_file(&file).unwrap();
+    undo(&mut harness);
+    harness.assert_buffer_content("rewritten elsewhere\n");
+}
+
+#[test]
+fn test_persistent_undo_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "second\n").unwrap();
+
+    edit_and_save(&dir_context, temp_dir.path(), &file);
+
+    let config = Config {
+        editor: EditorConfig {
+            persistent_undo: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut harness = start(&dir_context, config, temp_dir.path());
+    harness.open_file(&file).unwrap();
+    undo(&mut harness);
+    harness.assert_buffer_content("first\nsecond\n");
+}
@@ -0,0 +1,100 @@
+//! E2E tests for HTTP requests made by plugins and their network permission
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::plugins::fetch::FetchRequest;
+use std::time::Duration;
+
+/// Start a server answering one request, returning its URL
+fn start_server() -> (String, tiny_http::Server) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/define/fresh", server.server_addr());
+    (url, server)
+}
+
+/// Make a request as the "dictionary" plugin
+fn fetch_as_dictionary(harness: &mut EditorTestHarness, url: &str, request_id: u64) {
+    harness.editor_mut().handle_plugin_fetch(
+        Some("dictionary".to_string()),
+        FetchRequest {
+            url: url.to_string(),
+            ..Default::default()
+        },
+        request_id,
+    );
+    harness.render().unwrap();
+}
+
+/// Answer the network access prompt
+fn answer(harness: &mut EditorTestHarness, text: &str) {
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// The first request of a plugin asks for network access, and is made once
+/// allowed
+#[test]
+fn test_plugin_fetch_asks_for_network_access() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    let (url, server) = start_server();
+
+    fetch_as_dictionary(&mut harness, &url, 1);
+    harness.assert_screen_contains("Allow plugin 'dictionary' to access the network (127.0.0.1)?");
+    answer(&mut harness, "y");
+
+    assert_eq!(
+        harness
+            .editor()
+            .config()
+            .plugin_network_access
+            .get("dictionary"),
+        Some(&true)
+    );
+    let request = server
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap()
+        .expect("request was not made");
+    assert_eq!(request.url(), "/define/fresh");
+}
+
+/// A denied plugin is not asked again, and its requests are not made
+#[test]
+fn test_plugin_fetch_denied_is_remembered() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    let (url, server) = start_server();
+
+    fetch_as_dictionary(&mut harness, &url, 1);
+    answer(&mut harness, "n");
+    assert_eq!(
+        harness
+            .editor()
+            .config()
+            .plugin_network_access
+            .get("dictionary"),
+        Some(&false)
+    );
+
+    fetch_as_dictionary(&mut harness, &url, 2);
+    assert!(!harness.editor().is_prompting());
+    assert!(server
+        .recv_timeout(Duration::from_millis(200))
+        .unwrap()
+        .is_none());
+}
+
+/// Canceling the prompt fails the request without saving an answer
+#[test]
+fn test_plugin_fetch_prompt_canceled() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    let (url, _server) = start_server();
+
+    fetch_as_dictionary(&mut harness, &url, 1);
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    assert!(!harness.editor().is_prompting());
+    assert!(harness.editor().config().plugin_network_access.is_empty());
+}
@@ -0,0 +1,75 @@
+//! E2E tests for plugin errors, restarts and the plugin log
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::plugins::health::PluginState;
+use std::time::Duration;
+
+/// Record `count` errors thrown by the todo plugin
+fn throw_errors(harness: &mut EditorTestHarness, count: usize) {
+    for _ in 0..count {
+        harness.editor_mut().record_plugin_error(
+            Some("todo".to_string()),
+            "onSave: TypeError: x is undefined".to_string(),
+        );
+    }
+}
+
+/// A plugin that keeps throwing is disabled, and Show Plugins says why
+#[test]
+fn test_plugin_disabled_after_repeated_errors() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    throw_errors(&mut harness, 2);
+    assert_eq!(
+        harness.editor().plugin_health().state("todo"),
+        PluginState::Running
+    );
+
+    throw_errors(&mut harness, 1);
+    assert!(matches!(
+        harness.editor().plugin_health().state("todo"),
+        PluginState::Disabled {
+            restart_at: Some(_),
+            ..
+        }
+    ));
+    harness.render().unwrap();
+    harness.assert_screen_contains("Plugin 'todo' disabled");
+
+    harness.run_command("Show Plugins").unwrap();
+    harness.assert_screen_contains("*Plugins*");
+    harness.assert_screen_contains("todo: disabled, restarting in 1s");
+    harness.assert_screen_contains("TypeError: x is undefined");
+}
+
+/// Restarts back off exponentially while they keep failing
+#[test]
+fn test_plugin_restart_backs_off() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    throw_errors(&mut harness, 3);
+
+    // Without the plugin runtime the reload fails, so the next attempt waits longer
+    harness.advance_time(Duration::from_secs(1));
+    harness.process_async_and_render().unwrap();
+
+    harness.run_command("Show Plugins").unwrap();
+    harness.assert_screen_contains("todo: disabled, restarting in 2s");
+}
+
+/// Show Plugin Log lists what plugins printed and the errors they threw
+#[test]
+fn test_show_plugin_log() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    harness.editor_mut().record_plugin_log(
+        Some("todo".to_string()),
+        "log",
+        "Loaded 3 keywords".to_string(),
+    );
+    throw_errors(&mut harness, 1);
+
+    harness.run_command("Show Plugin Log").unwrap();
+    harness.assert_screen_contains("*Plugin Log*");
+    harness.assert_screen_contains("INFO  [todo] (0s ago) Loaded 3 keywords");
+    harness.assert_screen_contains("ERROR [todo] (0s ago) onSave: TypeError: x is undefined");
+}
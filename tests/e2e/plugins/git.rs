@@ -1,4 +1,4 @@
-//! E2E tests for git features (git grep and git find file)
+//! E2E tests for git features (git grep, find file, log, blame and status)
 
 use crate::common::git_test_helper::{DirGuard, GitTestRepo};
 use crate::common::harness::EditorTestHarness;
@@ -2290,3 +2290,164 @@ fn test_git_blame_original_buffer_not_decorated() {
         "Original file should NOT have blame headers after closing blame"
     );
 }
+
+// =============================================================================
+// Git Status Tests
+// =============================================================================
+
+/// Helper to trigger the git status panel via command palette
+fn trigger_git_status(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("Git Status").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Run git in the repo and return its stdout
+fn git_output(repo: &GitTestRepo, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(&repo.path)
+        .output()
+        .expect("Failed to run git");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// Test the git status panel lists a modified file and stages it with s
+#[test]
+fn test_git_status_stage_file() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    repo.setup_git_status_plugin();
+    repo.modify_file("README.md", "# Test Project\n\nChanged.\n");
+
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    trigger_git_status(&mut harness);
+
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("Unstaged (1):") && screen.contains("M  README.md")
+        })
+        .unwrap();
+
+    // Header, help line, blank line and section header come before the file
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.process_async_and_render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Staged (1):"))
+        .unwrap();
+
+    let staged = git_output(&repo, &["diff", "--cached", "--name-only"]);
+    assert_eq!(staged.trim(), "README.md");
+}
+
+/// Test Enter on a file shows its hunks and s stages a single hunk
+#[test]
+fn test_git_status_stage_hunk() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    repo.setup_git_status_plugin();
+    repo.modify_file(
+        "src/main.rs",
+        r#"fn main() {
+    println!("Hello, hunks!");
+    let config = load_config();
+    start_server(config);
+}
+
+fn load_config() -> Config {
+    Config::default()
+}
+
+fn start_server(config: Config) {
+    println!("Starting server now...");
+}
+"#,
+    );
+
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    trigger_git_status(&mut harness);
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("M  src/main.rs"))
+        .unwrap();
+
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.process_async_and_render().unwrap();
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    // The two changes are far enough apart to be separate hunks
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("Hunks: src/main.rs") && screen.contains("Unstaged (2):")
+        })
+        .unwrap();
+
+    // Move onto the first hunk's header
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.process_async_and_render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("Unstaged (1):") && screen.contains("Staged (1):")
+        })
+        .unwrap();
+
+    let staged = git_output(&repo, &["diff", "--cached"]);
+    assert!(staged.contains("+    println!(\"Hello, hunks!\");"));
+    assert!(!staged.contains("Starting server now"));
+
+    // q goes back to the file list
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Git Status:"))
+        .unwrap();
+}
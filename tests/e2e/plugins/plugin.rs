@@ -1990,3 +1990,99 @@ editor.setStatus("Test diagnostics plugin loaded");
 
     Ok(())
 }
+
+/// Test that commands plugins register are listed in the Plugins menu, and
+/// that plugins can add items to the built-in menus
+#[test]
+fn test_plugin_commands_in_menu_bar() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    let test_plugin = r#"
+globalThis.menu_test_greet = function(): void {
+    editor.setStatus("Greetings from the menu bar");
+};
+
+editor.registerCommand("Menu Test: Greet", "Say hello", "menu_test_greet", "normal");
+editor.addMenuItem("Edit", "Greet Everyone", "menu_test_greet", "");
+"#;
+    fs::write(plugins_dir.join("menu_bar_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+    harness
+        .wait_until(|h| h.get_menu_bar().contains("Plugins"))
+        .unwrap();
+
+    // The item added to the Edit menu
+    harness
+        .send_key(KeyCode::Char('e'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Greet Everyone");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    // The Plugins menu has a submenu per plugin, holding its commands
+    let menu_bar = harness.get_menu_bar();
+    let col = menu_bar[..menu_bar.find("Plugins").unwrap()]
+        .chars()
+        .count() as u16;
+    harness.mouse_click(col, 0).unwrap();
+    let row = (0..30)
+        .find(|&row| harness.get_screen_row(row).contains("menu_bar_test"))
+        .expect("Plugins menu should list the plugin") as u16;
+    harness.mouse_click(col, row).unwrap();
+    harness.assert_screen_contains("Menu Test: Greet");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Greetings from the menu bar"))
+        .unwrap();
+}
+
+/// Test that getBufferText returns a byte range, clamping ranges past the end
+#[test]
+fn test_plugin_get_buffer_text_ranges() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+
+    let test_plugin = r#"
+editor.registerCommand("Read Ranges", "Read buffer ranges", "read_ranges", "normal");
+
+globalThis.read_ranges = async function(): Promise<void> {
+    const id = editor.getActiveBufferId();
+    const inside = await editor.getBufferText(id, 6, 11);
+    const pastEnd = await editor.getBufferText(id, 6, 1000);
+    const outside = await editor.getBufferText(id, 500, 1000);
+    editor.setStatus(`ranges [${inside}] [${pastEnd.trim()}] [${outside}]`);
+};
+"#;
+    fs::write(plugins_dir.join("read_ranges.ts"), test_plugin).unwrap();
+
+    let fixture = TestFixture::new("test.txt", "hello world\n").unwrap();
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&fixture.path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Read Ranges").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("ranges [world] [world] []"))
+        .unwrap();
+}
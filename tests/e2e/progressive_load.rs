@@ -0,0 +1,112 @@
+//! E2E tests for opening files that are slow to read
+//!
+//! A named pipe stands in for a file on a slow filesystem: the editor sees
+//! what the test writes to it, when the test writes it.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Make a named pipe at `dir/name`
+fn make_pipe(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    let status = std::process::Command::new("mkfifo")
+        .arg(&path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "mkfifo failed");
+    path
+}
+
+/// Write `first` to the pipe at `path` at once, and `rest` once told to
+fn write_slowly(path: PathBuf, first: &'static str, rest: &'static str) -> mpsc::Sender<()> {
+    let (go, wait) = mpsc::channel();
+    thread::spawn(move || {
+        let mut pipe = std::fs::OpenOptions::new().write(true).open(path).unwrap();
+        pipe.write_all(first.as_bytes()).unwrap();
+        pipe.flush().unwrap();
+        let _ = wait.recv();
+        pipe.write_all(rest.as_bytes()).unwrap();
+    });
+    go
+}
+
+/// Harness whose file checks don't read the pipe ahead of the loader
+fn harness() -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.minified_line_length = 0;
+    config.editor.line_length_warning_bytes = 0;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+/// Poll until every file finished loading
+fn wait_for_loads(harness: &mut EditorTestHarness) {
+    for _ in 0..200 {
+        harness.editor_mut().poll_progressive_loads();
+        if !harness.editor().is_loading_files() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("file did not finish loading");
+}
+
+/// Test that a slow file shows what has arrived with a loading marker, and
+/// the rest once it arrives
+#[test]
+fn test_slow_file_shows_prefix_then_rest() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = make_pipe(temp_dir.path(), "slow.txt");
+    let go = write_slowly(path.clone(), "first line\n", "second line\n");
+
+    let mut harness = harness();
+    harness.open_file(&path).unwrap();
+    assert!(harness.editor().is_loading_files());
+    harness.assert_screen_contains("first line");
+    harness.assert_screen_contains("loading…");
+    harness.assert_screen_not_contains("second line");
+
+    // Navigation works meanwhile
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), "first line".len());
+
+    go.send(()).unwrap();
+    wait_for_loads(&mut harness);
+    harness.render().unwrap();
+    harness.assert_screen_contains("second line");
+    harness.assert_screen_not_contains("loading…");
+    harness.assert_buffer_content("first line\nsecond line\n");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
+
+/// Test that a slow file can't be edited or saved until it has loaded
+#[test]
+fn test_slow_file_read_only_until_loaded() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = make_pipe(temp_dir.path(), "slow.txt");
+    let go = write_slowly(path.clone(), "abc\n", "def\n");
+
+    let mut harness = harness();
+    harness.open_file(&path).unwrap();
+    harness.type_text("x").unwrap();
+    harness.assert_buffer_content("abc\n");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Can't save while the file is still loading")
+    );
+
+    go.send(()).unwrap();
+    wait_for_loads(&mut harness);
+    harness.type_text("x").unwrap();
+    harness.assert_buffer_content("xabc\ndef\n");
+}
@@ -0,0 +1,217 @@
+//! Tests for opening binary and minified files in protected mode
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+const PNG_DATA: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+];
+
+/// A file with a line over `minified_line_length` bytes opens without highlighting
+#[test]
+fn test_minified_file_opens_without_highlighting() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("app.min.js");
+    let line = "var a=1;".repeat(2000);
+    std::fs::write(&path, format!("{}\n", line)).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("[minified file");
+    let state = harness.editor().active_state();
+    assert!(!state.highlighter.has_highlighting());
+    assert!(!state.semantic_highlighter.enabled);
+
+    // A file with short lines keeps its highlighting
+    let path = temp_dir.path().join("app.js");
+    std::fs::write(&path, "var a = 1;\n").unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    assert!(harness
+        .editor()
+        .active_state()
+        .highlighter
+        .has_highlighting());
+}
+
+/// Setting `minified_line_length` to 0 turns detection off
+#[test]
+fn test_minified_detection_can_be_turned_off() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("app.min.js");
+    std::fs::write(&path, "var a=1;".repeat(2000)).unwrap();
+
+    let mut config = Config::default();
+    config.editor.minified_line_length = 0;
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_not_contains("[minified file");
+    assert!(harness
+        .editor()
+        .active_state()
+        .highlighter
+        .has_highlighting());
+}
+
+/// With `binary_hex_view_prompt` on, opening a binary file offers a hex view
+#[test]
+fn test_binary_file_offers_hex_view() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("image.png");
+    std::fs::write(&path, PNG_DATA).unwrap();
+
+    let mut config = Config::default();
+    config.editor.binary_hex_view_prompt = true;
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Show a hex view?");
+
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Hex: image.png*");
+    harness.assert_screen_contains("00000000  89 50 4e 47 0d 0a 1a 0a  00 00 00 0d 49 48 44 52");
+    harness.assert_screen_contains("|.PNG........IHDR|");
+    assert!(harness.editor().is_editing_disabled());
+}
+
+/// Without the prompt, a binary file only reports that it is read-only
+#[test]
+fn test_binary_file_without_hex_view_prompt() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("image.png");
+    std::fs::write(&path, PNG_DATA).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_not_contains("Show a hex view?");
+    harness.assert_screen_contains("[binary file, read-only]");
+    assert!(!harness
+        .editor()
+        .active_state()
+        .highlighter
+        .has_highlighting());
+}
+
+/// The Show Hex View command works on any file
+#[test]
+fn test_show_hex_view_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "hello\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("Show Hex View").unwrap();
+    harness.assert_screen_contains("*Hex: notes.txt*");
+    harness.assert_screen_contains("68 65 6c 6c 6f 0a");
+    harness.assert_screen_contains("|hello.|");
+}
+
+/// Open `app.js` with `size` bytes of short lines and the given limits
+fn open_large_file(
+    temp_dir: &TempDir,
+    size: usize,
+    max_size: u64,
+    max_line_len: usize,
+) -> EditorTestHarness {
+    let path = temp_dir.path().join("app.js");
+    std::fs::write(&path, "var a = 1;\n".repeat(size / 11)).unwrap();
+
+    let mut config = Config::default();
+    config.editor.file_size_warning_bytes = max_size;
+    config.editor.line_length_warning_bytes = max_line_len;
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// A file over `file_size_warning_bytes` opens with features limited, and
+/// the prompt keeps them limited by default
+#[test]
+fn test_large_file_opens_with_features_limited() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = open_large_file(&temp_dir, 2200, 1000, 0);
+
+    harness.assert_screen_contains("Large file (file of 2.1 KB)");
+    harness.assert_screen_contains("Limit features, read-only");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Highlighting and auto-revert are off");
+    let state = harness.editor().active_state();
+    assert!(!state.highlighter.has_highlighting());
+    assert!(!state.auto_revert);
+    assert!(!harness.editor().is_editing_disabled());
+}
+
+/// The prompt can make the buffer read-only
+#[test]
+fn test_large_file_can_be_made_read_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = open_large_file(&temp_dir, 2200, 1000, 0);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(harness.editor().is_editing_disabled());
+    assert!(!harness.editor().active_state().auto_revert);
+}
+
+/// The prompt can turn all features back on
+#[test]
+fn test_large_file_features_can_be_turned_back_on() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = open_large_file(&temp_dir, 2200, 1000, 0);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("All features turned back on");
+    let state = harness.editor().active_state();
+    assert!(state.highlighter.has_highlighting());
+    assert!(state.semantic_highlighter.enabled);
+    assert!(state.auto_revert);
+}
+
+/// A file with a line over `line_length_warning_bytes` also gets the prompt,
+/// and files within both limits do not
+#[test]
+fn test_long_line_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    let harness = open_large_file(&temp_dir, 2200, 0, 1000);
+    harness.assert_screen_not_contains("Large file");
+
+    let path = temp_dir.path().join("data.txt");
+    std::fs::write(&path, format!("{}\n", "x".repeat(1500))).unwrap();
+    let mut config = Config::default();
+    config.editor.line_length_warning_bytes = 1000;
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Large file (line over 1000 B)");
+}
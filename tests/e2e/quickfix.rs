@@ -0,0 +1,105 @@
+//! Tests for the quickfix list: building it from a log, stepping through it
+//! and jumping from the list split
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+const LOG: &str = "\
+running checks
+src/a.rs:2:5: unused variable
+src/b.rs:1: missing docs
+done
+";
+
+/// Project with two source files and a pasted log, with the log open
+fn setup(temp_dir: &TempDir) -> EditorTestHarness {
+    let root = temp_dir.path();
+    std::fs::create_dir(root.join("src")).unwrap();
+    std::fs::write(root.join("src/a.rs"), "fn a() {\n    let x = 1;\n}\n").unwrap();
+    std::fs::write(root.join("src/b.rs"), "pub fn b() {}\n").unwrap();
+    std::fs::write(root.join("build.log"), LOG).unwrap();
+
+    let mut harness = EditorTestHarness::with_working_dir(120, 30, root.to_path_buf()).unwrap();
+    harness.open_file(&root.join("build.log")).unwrap();
+    harness
+}
+
+#[test]
+fn test_quickfix_from_buffer_and_cycle() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+
+    harness.run_command("Quickfix: Load from Buffer").unwrap();
+    harness.assert_screen_contains("Quickfix: 2 location(s) from build.log");
+
+    harness.run_command("Quickfix: Next Entry").unwrap();
+    harness.assert_screen_contains("1/2 note: unused variable");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn a() {\n    let x = 1;\n}\n"
+    );
+    // Line 2, column 5
+    assert_eq!(harness.cursor_position(), 13);
+
+    harness.run_command("Quickfix: Next Entry").unwrap();
+    harness.assert_screen_contains("2/2 note: missing docs");
+    assert_eq!(harness.get_buffer_content().unwrap(), "pub fn b() {}\n");
+
+    // Both directions wrap around
+    harness.run_command("Quickfix: Next Entry").unwrap();
+    harness.assert_screen_contains("1/2 note: unused variable");
+    harness.run_command("Quickfix: Previous Entry").unwrap();
+    harness.assert_screen_contains("2/2 note: missing docs");
+}
+
+#[test]
+fn test_quickfix_without_locations() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+    harness
+        .open_file(&temp_dir.path().join("src/b.rs"))
+        .unwrap();
+
+    harness.run_command("Quickfix: Load from Buffer").unwrap();
+    harness.assert_screen_contains("No file locations found");
+    harness.run_command("Quickfix: Next Entry").unwrap();
+    harness.assert_screen_contains("Quickfix list is empty");
+}
+
+#[test]
+fn test_quickfix_list_split_jumps_in_source_split() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+
+    harness.run_command("Quickfix: Load from Buffer").unwrap();
+    harness.run_command("Quickfix: Show List").unwrap();
+    harness.assert_screen_contains("Quickfix: build.log (2 location(s))");
+    harness.assert_screen_contains("src/a.rs:2:5: note: unused variable");
+    harness.assert_screen_contains("src/b.rs:1: note: missing docs");
+    // The log stays visible above the list
+    harness.assert_screen_contains("running checks");
+
+    // Header lines are not locations
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Move the cursor to a location line");
+
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("2/2 note: missing docs");
+    assert_eq!(harness.get_buffer_content().unwrap(), "pub fn b() {}\n");
+
+    // The file replaced the log in the upper split, and the list marks the
+    // current entry
+    let screen = harness.screen_to_string();
+    assert!(!screen.contains("running checks"), "{}", screen);
+    harness.assert_screen_contains("> src/b.rs:1: note: missing docs");
+}
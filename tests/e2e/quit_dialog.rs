@@ -0,0 +1,140 @@
+//! E2E tests for the dialog listing unsaved buffers on quit
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Open two files, modify both, and ask to quit
+fn quit_with_two_modified(harness: &mut EditorTestHarness) -> (TestFixture, TestFixture) {
+    let first = TestFixture::new("first.txt", "one\n").unwrap();
+    let second = TestFixture::new("second.txt", "two\n").unwrap();
+    for fixture in [&first, &second] {
+        harness.open_file(&fixture.path).unwrap();
+        harness.type_text("new ").unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    (first, second)
+}
+
+/// Screen position of the first character of `text`
+fn screen_pos(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    let screen = harness.screen_to_string();
+    screen
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            let index = line.find(text)?;
+            Some((line[..index].chars().count() as u16, row as u16))
+        })
+        .unwrap_or_else(|| panic!("{:?} not on screen:\n{}", text, screen))
+}
+
+/// Test that the dialog lists each unsaved buffer, and saving one and
+/// discarding the other quits with only the first saved
+#[test]
+fn test_quit_dialog_save_one_discard_other() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let (first, second) = quit_with_two_modified(&mut harness);
+    harness.assert_screen_contains("Unsaved Changes");
+    harness.assert_screen_contains("2 buffers have unsaved changes");
+    harness.assert_screen_contains("[ Save All ]");
+
+    // Save on the first row
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(fs::read_to_string(&first.path).unwrap(), "new one\n");
+    assert!(!harness.should_quit());
+    harness.assert_screen_contains("1 buffer has unsaved changes");
+
+    // Discard on the row left
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.should_quit());
+    assert_eq!(fs::read_to_string(&second.path).unwrap(), "two\n");
+}
+
+/// Test that Save All saves every buffer and quits
+#[test]
+fn test_quit_dialog_save_all() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let (first, second) = quit_with_two_modified(&mut harness);
+
+    let (col, row) = screen_pos(&harness, "[ Save All ]");
+    harness.mouse_click(col + 2, row).unwrap();
+    assert!(harness.should_quit());
+    assert_eq!(fs::read_to_string(&first.path).unwrap(), "new one\n");
+    assert_eq!(fs::read_to_string(&second.path).unwrap(), "new two\n");
+}
+
+/// Test that Cancel closes the dialog without quitting
+#[test]
+fn test_quit_dialog_cancel_with_mouse() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixtures = quit_with_two_modified(&mut harness);
+
+    let (col, row) = screen_pos(&harness, "[ Cancel ]");
+    harness.mouse_click(col + 2, row).unwrap();
+    harness.render().unwrap();
+    assert!(!harness.should_quit());
+    harness.assert_screen_not_contains("Unsaved Changes");
+    harness.assert_screen_contains("Quit cancelled");
+}
+
+/// Test that saving a buffer without a file keeps its row and says why
+#[test]
+fn test_quit_dialog_save_without_file_keeps_row() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("scribble").unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(!harness.should_quit());
+    harness.assert_screen_contains("has no file or changed on disk");
+    harness.assert_screen_contains("[ Discard ]");
+}
+
+/// Test that a buffer opened from a provider is saved through it from the
+/// dialog, and the quit waits for the write
+#[test]
+fn test_quit_dialog_saves_provider_buffer() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .register_plugin_vfs_provider("mem", true);
+    assert!(harness.editor_mut().open_url("mem://notes"));
+    harness
+        .editor_mut()
+        .complete_vfs_read("mem://notes", Ok("notes".to_string()));
+    harness.type_text("new ").unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("1 buffer has unsaved changes");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_screen_not_contains("has no file");
+    assert!(!harness.should_quit());
+
+    harness
+        .editor_mut()
+        .complete_vfs_write("mem://notes", Ok(()));
+    assert!(harness.should_quit());
+}
@@ -0,0 +1,79 @@
+//! Tests for opening files in a running editor with `--listen`/`--remote`
+
+#![cfg(unix)]
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::remote::{send_open, OpenRequest};
+use tempfile::TempDir;
+
+fn open_request(path: std::path::PathBuf, wait: bool) -> OpenRequest {
+    OpenRequest {
+        path,
+        line: Some(2),
+        column: None,
+        wait,
+    }
+}
+
+#[test]
+fn test_remote_open_adds_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket = temp_dir.path().join("fresh.sock");
+    let file_path = temp_dir.path().join("remote.txt");
+    std::fs::write(&file_path, "first line\nsecond line\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().start_remote_server(&socket).unwrap();
+
+    let request = open_request(file_path, false);
+    let client = std::thread::spawn(move || send_open(&socket, &request));
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap().starts_with("first line"))
+        .unwrap();
+    client.join().unwrap().unwrap();
+
+    harness.assert_screen_contains("remote.txt");
+    assert_eq!(harness.cursor_position(), "first line\n".len());
+}
+
+#[test]
+fn test_remote_wait_until_buffer_closed() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket = temp_dir.path().join("fresh.sock");
+    let file_path = temp_dir.path().join("COMMIT_EDITMSG");
+    std::fs::write(&file_path, "message\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().start_remote_server(&socket).unwrap();
+
+    let request = open_request(file_path, true);
+    let client = std::thread::spawn(move || send_open(&socket, &request));
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "message\n")
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(!client.is_finished());
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(buffer_id).unwrap();
+    client.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_remote_open_directory_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket = temp_dir.path().join("fresh.sock");
+    let file_path = temp_dir.path().join("subdir");
+    std::fs::create_dir(&file_path).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().start_remote_server(&socket).unwrap();
+
+    let request = open_request(file_path, false);
+    let client = std::thread::spawn(move || send_open(&socket, &request));
+    while !client.is_finished() {
+        harness.process_async_and_render().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert!(client.join().unwrap().is_err());
+}
@@ -0,0 +1,121 @@
+//! Tests for "Rename Symbol" without a language server: the project-wide
+//! textual rename with its review buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Project with `old_name` in two files; opens `a.rs` with the cursor on it
+fn setup(temp_dir: &TempDir) -> EditorTestHarness {
+    let root = temp_dir.path();
+    std::fs::write(root.join("a.rs"), "fn old_name() {}\nold_name();\n").unwrap();
+    std::fs::write(
+        root.join("b.rs"),
+        "use old_name;\nlet x = old_name_extra;\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::with_working_dir(160, 30, root.to_path_buf()).unwrap();
+    harness.open_file(&root.join("a.rs")).unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 4)
+        .unwrap();
+    harness
+}
+
+/// Run "Rename Symbol" and answer the prompt with `new_name`
+fn rename_to(harness: &mut EditorTestHarness, new_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Rename Symbol").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Rename 'old_name' in project to:");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(new_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn buffer_text(harness: &mut EditorTestHarness, path: &Path) -> String {
+    harness.open_file(path).unwrap();
+    harness.get_buffer_content().unwrap()
+}
+
+#[test]
+fn test_textual_rename_applies_selected_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+    rename_to(&mut harness, "new_name");
+
+    harness.assert_screen_contains("3 of 3 changes selected");
+    harness.assert_screen_contains("[x] 1: fn old_name() {}");
+    harness.assert_screen_contains("[x] 1: use old_name;");
+    assert!(!harness.screen_to_string().contains("old_name_extra"));
+
+    // Exclude the definition, keep the two uses
+    harness
+        .send_key(KeyCode::Char(' '), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("2 of 3 changes selected");
+    harness.assert_screen_contains("[ ] 1: fn old_name() {}");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Renamed 'old_name' to 'new_name': 2 change(s) in 2 file(s)");
+
+    let root = temp_dir.path();
+    assert_eq!(
+        buffer_text(&mut harness, &root.join("a.rs")),
+        "fn old_name() {}\nnew_name();\n"
+    );
+    assert_eq!(
+        buffer_text(&mut harness, &root.join("b.rs")),
+        "use new_name;\nlet x = old_name_extra;\n"
+    );
+    // Changes are left unsaved for review
+    assert_eq!(
+        std::fs::read_to_string(root.join("b.rs")).unwrap(),
+        "use old_name;\nlet x = old_name_extra;\n"
+    );
+
+    // Each file's changes undo as one step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "use old_name;\nlet x = old_name_extra;\n"
+    );
+}
+
+#[test]
+fn test_textual_rename_cancel_leaves_files_unchanged() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir);
+    rename_to(&mut harness, "new_name");
+    harness.assert_screen_contains("*Rename: old_name*");
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.screen_to_string().contains("*Rename: old_name*"));
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn old_name() {}\nold_name();\n"
+    );
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
@@ -0,0 +1,97 @@
+//! Tests for sending code from a buffer to a REPL in a terminal split
+//!
+//! The REPL tests need a working PTY and return early without one (see
+//! `terminal.rs`). `sh` stands in for a language REPL.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use portable_pty::{native_pty_system, PtySize};
+use tempfile::TempDir;
+
+fn pty_available() -> bool {
+    let available = native_pty_system()
+        .openpty(PtySize {
+            rows: 1,
+            cols: 1,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .is_ok();
+    if !available {
+        eprintln!("Skipping REPL test: PTY not available in this environment");
+    }
+    available
+}
+
+#[test]
+fn test_send_line_to_started_repl() {
+    if !pty_available() {
+        return;
+    }
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("echo $((6 * 7))").unwrap();
+
+    harness.run_command("REPL: Start").unwrap();
+    harness.assert_screen_contains("REPL command:");
+    harness.type_text("sh").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("REPL 'sh' started");
+    harness.assert_screen_contains("*Terminal 0*");
+
+    harness.run_command("REPL: Send Line or Selection").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("42"))
+        .unwrap();
+    // The focus stays on the code
+    assert_eq!(harness.get_buffer_content().unwrap(), "echo $((6 * 7))");
+}
+
+#[test]
+fn test_send_cells_to_language_repl() {
+    if !pty_available() {
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("script.py");
+    std::fs::write(
+        &script,
+        "# %% first\necho $((40 + 2))\n# %% second\necho $((20 + 3))\n",
+    )
+    .unwrap();
+
+    let mut config = Config::default();
+    config.languages.get_mut("python").unwrap().repl = Some("sh".to_string());
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&script).unwrap();
+
+    // The REPL configured for the language starts on the first send
+    harness.run_command("REPL: Send Cell").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("42"))
+        .unwrap();
+    harness.assert_screen_contains("Sent cell (1 line(s)) to the REPL");
+
+    // The cursor moved past the next marker, into the second cell
+    harness.run_command("REPL: Send Cell").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("23"))
+        .unwrap();
+}
+
+#[test]
+fn test_send_without_repl() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("1 + 1").unwrap();
+    harness.run_command("REPL: Send Line or Selection").unwrap();
+    harness.assert_screen_contains("No REPL for this buffer");
+}
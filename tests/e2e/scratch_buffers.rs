@@ -0,0 +1,76 @@
+//! Tests for scratch buffers (new buffers with a chosen language, kept on disk)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use tempfile::TempDir;
+
+fn scratch_harness(context_temp: &TempDir) -> (EditorTestHarness, DirectoryContext) {
+    let dir_context = DirectoryContext::for_testing(context_temp.path());
+    let harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        30,
+        Default::default(),
+        context_temp.path().to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap();
+    (harness, dir_context)
+}
+
+#[test]
+fn test_new_scratch_buffer_is_kept_on_quit() {
+    let context_temp = TempDir::new().unwrap();
+    let (mut harness, dir_context) = scratch_harness(&context_temp);
+
+    harness.run_command("New Scratch Buffer").unwrap();
+    harness.assert_screen_contains("Scratch buffer language:");
+    // Test editors only have the "Plain Text" grammar
+    harness.type_text("Plain").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("scratch-1.txt");
+    harness.assert_screen_contains("New Plain Text scratch buffer");
+
+    harness.type_text("remember the milk").unwrap();
+    harness.render().unwrap();
+
+    // Quitting writes the scratch buffer instead of asking to discard it
+    harness.editor_mut().quit();
+    harness.render().unwrap();
+    assert!(harness.editor().should_quit());
+    let content = std::fs::read_to_string(dir_context.scratch_dir().join("scratch-1.txt")).unwrap();
+    assert_eq!(content, "remember the milk");
+}
+
+#[test]
+fn test_open_existing_scratch_buffer() {
+    let context_temp = TempDir::new().unwrap();
+    let (mut harness, dir_context) = scratch_harness(&context_temp);
+    std::fs::create_dir_all(dir_context.scratch_dir()).unwrap();
+    std::fs::write(
+        dir_context.scratch_dir().join("scratch-2.md"),
+        "\n# Shopping list\n- milk\n",
+    )
+    .unwrap();
+
+    harness.run_command("Open Scratch Buffer").unwrap();
+    harness.assert_screen_contains("scratch-2.md");
+    harness.assert_screen_contains("# Shopping list");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("- milk");
+
+    // The next new scratch buffer gets the following number
+    harness.run_command("New Scratch Buffer").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("scratch-3.txt");
+}
@@ -1428,3 +1428,86 @@ fn test_status_bar_hidden_during_suggestions() {
         "Status bar should show 'Palette:' indicator after closing command palette"
     );
 }
+
+/// Test that Select All Matches puts a cursor on every match and edits them together
+#[test]
+fn test_select_all_matches_edits_every_match() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("select_all.txt");
+    std::fs::write(&file_path, "foo bar\nfoo baz\nqux foo").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("foo").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('l'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.cursor_count(), 3);
+    let mut selections = harness.editor().active_state().cursors.selections();
+    selections.sort_by_key(|r| r.start);
+    assert_eq!(selections, vec![0..3, 8..11, 20..23]);
+    harness.assert_screen_contains("Selected 3 matches");
+
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "x bar\nx baz\nqux x");
+
+    // Undoing the edit and then the selection brings back the single cursor
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_count(), 1);
+}
+
+/// Test that Select All Matches keeps to the selection a search was limited to
+#[test]
+fn test_select_all_matches_in_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("select_all_range.txt");
+    std::fs::write(&file_path, "a a\na a\na a").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Select the first two lines and search inside them
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Find in Selection").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("a").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('l'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    assert_eq!(harness.cursor_count(), 4);
+}
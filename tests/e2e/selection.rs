@@ -1579,3 +1579,54 @@ fn test_select_at_file_boundaries() {
     // Just verify we can get selected text without panicking
     // The test validates that boundary operations don't crash
 }
+
+/// Test that other copies of the selected text get the occurrence highlight
+#[test]
+fn test_selection_highlights_other_occurrences() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("a.b(x) a.b(y)").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let theme = harness.editor().theme();
+    let (selection_bg, occurrence_bg) = (theme.selection_bg, theme.semantic_highlight_bg);
+    let row = harness.content_area_rows().0 as u16;
+
+    // Content starts at column 8 (after the gutter); "a.b" is selected
+    let bg_at = |col: u16| harness.get_cell_style(col, row).and_then(|s| s.bg);
+    assert_eq!(bg_at(8), Some(selection_bg));
+    for col in 15..18 {
+        assert_eq!(bg_at(col), Some(occurrence_bg), "column {col}");
+    }
+    assert_ne!(bg_at(18), Some(occurrence_bg));
+}
+
+/// Test that occurrence highlights wait for the cursor to rest
+#[test]
+fn test_occurrence_highlight_waits_for_cursor_to_rest() {
+    let mut config = fresh::config::Config::default();
+    config.editor.highlight_occurrences_delay_ms = 150;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("foo bar foo").unwrap();
+    harness.render().unwrap();
+
+    let occurrence_bg = harness.editor().theme().semantic_highlight_bg;
+    let row = harness.content_area_rows().0 as u16;
+    let first_foo_bg =
+        |harness: &EditorTestHarness| harness.get_cell_style(8, row).and_then(|s| s.bg);
+    assert_ne!(first_foo_bg(&harness), Some(occurrence_bg));
+
+    harness.advance_time(std::time::Duration::from_millis(150));
+    harness.render().unwrap();
+    assert_eq!(first_foo_bg(&harness), Some(occurrence_bg));
+
+    // Moving hides them again until the cursor settles
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_ne!(first_foo_bg(&harness), Some(occurrence_bg));
+}
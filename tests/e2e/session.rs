@@ -1103,3 +1103,214 @@ fn test_session_restores_splits() {
         harness.assert_buffer_content("Left split content");
     }
 }
+
+/// Test that the quickfix list and its current entry survive a session restore
+#[test]
+fn test_session_restores_quickfix_list() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    std::fs::write(project_dir.join("a.txt"), "one\ntwo\n").unwrap();
+    std::fs::write(project_dir.join("b.txt"), "three\n").unwrap();
+    let log = project_dir.join("check.log");
+    std::fs::write(&log, "a.txt:2: first\nb.txt:1: second\n").unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+    };
+
+    // First session: build the list, go to its first entry and save
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&log).unwrap();
+        run_command(&mut harness, "Quickfix: Load from Buffer");
+        run_command(&mut harness, "Quickfix: Next Entry");
+        harness.assert_screen_contains("1/2 note: first");
+
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    // Second session: stepping continues after the saved entry
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        let restored = harness.editor_mut().try_restore_session().unwrap();
+        assert!(restored, "Session should have been restored");
+
+        run_command(&mut harness, "Quickfix: Next Entry");
+        harness.assert_screen_contains("2/2 note: second");
+        harness.assert_buffer_content("three\n");
+    }
+}
+
+/// Test that a language mode override survives a session restore
+#[test]
+fn test_session_restores_language_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file = project_dir.join("main.rs");
+    std::fs::write(&file, "fn main() {}\n").unwrap();
+
+    // First session: override the language and save
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&file).unwrap();
+
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Change Language Mode").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.type_text("Plain Text").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    // Second session: the override is reapplied
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        let restored = harness.editor_mut().try_restore_session().unwrap();
+        assert!(restored, "Session should have been restored");
+        harness.render().unwrap();
+
+        assert_eq!(
+            harness.editor().active_state().syntax_override.as_deref(),
+            Some("Plain Text")
+        );
+        harness.assert_screen_contains(" Plain Text ");
+    }
+}
+
+/// Test that the session is saved on its own once the tabs change and settle
+#[test]
+fn test_session_auto_saves_after_layout_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("a.txt");
+    std::fs::write(&file, "Auto-saved tab").unwrap();
+    let session_path = get_session_path(&project_dir).unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.editor_mut().enable_session_auto_save();
+        harness.editor_mut().auto_save_session();
+
+        harness.open_file(&file).unwrap();
+        harness.editor_mut().auto_save_session();
+        assert!(
+            !session_path.exists(),
+            "Session should wait for the change to settle"
+        );
+
+        harness.advance_time(std::time::Duration::from_secs(3));
+        harness.editor_mut().auto_save_session();
+        assert!(session_path.exists(), "Session should have been auto-saved");
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        assert!(harness.editor_mut().try_restore_session().unwrap());
+        harness.assert_buffer_content("Auto-saved tab");
+    }
+}
+
+/// Test that the selection and secondary cursors of a file survive a session restore
+#[test]
+fn test_session_restores_selection_and_cursors() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("words.txt");
+    std::fs::write(&file, "foo bar foo baz foo\n").unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&file).unwrap();
+
+        // Select the first "foo" and add cursors at the other two
+        for _ in 0..3 {
+            harness
+                .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+                .unwrap();
+        }
+        harness.editor_mut().add_cursor_at_next_match();
+        harness.editor_mut().add_cursor_at_next_match();
+        assert_eq!(harness.cursor_count(), 3);
+
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        assert!(harness.editor_mut().try_restore_session().unwrap());
+        harness.render().unwrap();
+
+        assert_eq!(harness.cursor_count(), 3);
+        let mut selections = harness.editor().active_state().cursors.selections();
+        selections.sort_by_key(|range| range.start);
+        assert_eq!(selections, vec![0..3, 8..11, 16..19]);
+    }
+}
@@ -0,0 +1,168 @@
+//! Tests for recording input with `--record-session` and replaying it
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use fresh::input::recording::{RecordedEvent, Recording};
+use fresh::services::async_bridge::{AsyncMessage, LspMessageType};
+use fresh::view::prompt::PromptType;
+
+#[test]
+fn test_replayed_recording_reproduces_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+
+    let mut original = EditorTestHarness::new(80, 24).unwrap();
+    original
+        .editor_mut()
+        .start_session_recording(&path)
+        .unwrap();
+    let events = [
+        Event::Key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE)),
+        Event::Key(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE)),
+        Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        Event::Paste("pasted".to_string()),
+        Event::Key(KeyEvent::new(KeyCode::Home, KeyModifiers::SHIFT)),
+        Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)),
+        Event::Resize(70, 20),
+    ];
+    // As the main loop does: record each event, then handle it
+    for event in &events {
+        original.editor_mut().record_terminal_event(event);
+        match event {
+            Event::Key(key) => original.send_key(key.code, key.modifiers).unwrap(),
+            Event::Paste(text) => {
+                original.editor_mut().paste_from_terminal(text.clone());
+                original.render().unwrap();
+            }
+            Event::Resize(width, height) => original.resize(*width, *height).unwrap(),
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(original.get_buffer_content().unwrap(), "hi\n");
+
+    let recording = Recording::load(&path).unwrap();
+    assert_eq!((recording.width, recording.height), (80, 24));
+    // Handling async messages (file watching, plugins) is recorded too
+    let terminal_events = recording
+        .entries
+        .iter()
+        .filter(|entry| !matches!(entry.event, RecordedEvent::Async { .. }))
+        .count();
+    assert_eq!(terminal_events, events.len());
+
+    let mut replayed = EditorTestHarness::new(recording.width, recording.height).unwrap();
+    replayed.replay(&recording).unwrap();
+    assert_eq!(replayed.get_buffer_content(), original.get_buffer_content());
+    assert_eq!(replayed.screen_to_string(), original.screen_to_string());
+}
+
+#[test]
+fn test_replay_injects_recorded_async_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+
+    let mut original = EditorTestHarness::new(80, 24).unwrap();
+    original
+        .editor_mut()
+        .start_session_recording(&path)
+        .unwrap();
+    let sender = original.editor().async_bridge().unwrap().sender();
+    sender
+        .send(AsyncMessage::LspWindowMessage {
+            language: "rust".to_string(),
+            message_type: LspMessageType::Warning,
+            message: "workspace not indexed".to_string(),
+        })
+        .unwrap();
+    original.process_async_and_render().unwrap();
+    original.assert_screen_contains("LSP (rust): workspace not indexed");
+
+    // No language server runs during the replay; the message comes from
+    // the recording
+    let recording = Recording::load(&path).unwrap();
+    let mut replayed = EditorTestHarness::new(recording.width, recording.height).unwrap();
+    replayed.replay(&recording).unwrap();
+    replayed.assert_screen_contains("LSP (rust): workspace not indexed");
+}
+
+#[test]
+fn test_replay_handwritten_recording() {
+    // Recordings are plain JSON lines, so a test can also be written by hand
+    let recording = Recording::parse(
+        r#"{"fresh_recording": 1, "width": 80, "height": 24}
+{"ms": 0, "type": "key", "key": "a", "modifiers": 0}
+{"ms": 10, "type": "key", "key": "B", "modifiers": 1}
+{"ms": 20, "type": "async"}
+{"ms": 30, "type": "key", "key": "Left", "modifiers": 0}
+{"ms": 40, "type": "key", "key": "c", "modifiers": 0}
+"#,
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(recording.width, recording.height).unwrap();
+    harness.replay(&recording).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "acB");
+}
+
+#[test]
+fn test_password_prompt_input_is_not_recorded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().start_session_recording(&path).unwrap();
+    harness
+        .editor_mut()
+        .start_prompt("[sudo] password: ".to_string(), PromptType::SudoPassword);
+    let events = [
+        Event::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE)),
+        Event::Key(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE)),
+        Event::Paste("cr3t-pw".to_string()),
+        Event::Key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE)),
+    ];
+    for event in &events {
+        harness.editor_mut().record_terminal_event(event);
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("cr3t"), "{}", contents);
+    let recording = Recording::load(&path).unwrap();
+    let recorded: Vec<_> = recording.entries.into_iter().map(|e| e.event).collect();
+    assert_eq!(
+        recorded,
+        vec![
+            RecordedEvent::Key {
+                key: "*".to_string(),
+                modifiers: 0
+            },
+            RecordedEvent::Key {
+                key: "*".to_string(),
+                modifiers: 0
+            },
+            RecordedEvent::Paste {
+                text: "*".to_string()
+            },
+            RecordedEvent::Key {
+                key: "Backspace".to_string(),
+                modifiers: 0
+            },
+        ]
+    );
+
+    // Once the prompt is closed, keys are recorded as typed again
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness
+        .editor_mut()
+        .record_terminal_event(&Event::Key(KeyEvent::new(
+            KeyCode::Char('s'),
+            KeyModifiers::NONE,
+        )));
+    let recording = Recording::load(&path).unwrap();
+    assert_eq!(
+        recording.entries.last().unwrap().event,
+        RecordedEvent::Key {
+            key: "s".to_string(),
+            modifiers: 0
+        }
+    );
+}
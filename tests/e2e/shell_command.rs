@@ -474,3 +474,103 @@ fn test_shell_command_replace_clamps_cursor_when_buffer_shrinks() {
         "Cursor should be clamped to new buffer length"
     );
 }
+
+/// "Run Shell Command" hands the command to the event loop, which runs it in
+/// the terminal; its output can then be inserted at the cursor
+#[test]
+fn test_run_shell_command_offers_to_insert_output() {
+    use fresh::app::terminal_handoff::{CommandOutput, TerminalHandoff};
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("before ").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Run Shell Command").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.assert_screen_contains("Run in terminal:");
+    harness.type_text("git log -1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    assert_eq!(
+        harness.editor_mut().take_terminal_handoff(),
+        Some(TerminalHandoff::RunCommand("git log -1".to_string()))
+    );
+    assert_eq!(harness.editor_mut().take_terminal_handoff(), None);
+
+    // What the event loop reports back once the command has run
+    harness.editor_mut().finish_run_shell_command(
+        "git log -1",
+        Ok(CommandOutput {
+            stdout: "commit abc\nAuthor: someone\n".to_string(),
+            success: true,
+        }),
+    );
+    harness.render().unwrap();
+    harness.assert_screen_contains("Insert output of 'git log -1' (2 lines) at cursor?");
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("before commit abc\nAuthor: someone\n");
+}
+
+/// Declining the offer, or a command printing nothing, leaves the buffer alone
+#[test]
+fn test_run_shell_command_output_not_inserted() {
+    use fresh::app::terminal_handoff::CommandOutput;
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("text").unwrap();
+
+    harness.editor_mut().finish_run_shell_command(
+        "make",
+        Ok(CommandOutput {
+            stdout: "error\n".to_string(),
+            success: false,
+        }),
+    );
+    harness.render().unwrap();
+    harness.assert_screen_contains("Insert output of 'make' (failed) (1 line) at cursor?");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("text");
+
+    harness.editor_mut().finish_run_shell_command(
+        "true",
+        Ok(CommandOutput {
+            stdout: String::new(),
+            success: true,
+        }),
+    );
+    harness.render().unwrap();
+    harness.assert_screen_contains("'true' printed nothing");
+    harness.assert_buffer_content("text");
+}
+
+/// The Suspend command asks the event loop to suspend the editor
+#[test]
+#[cfg_attr(not(unix), ignore = "Suspend requires Unix job control")]
+fn test_suspend_command_requests_handoff() {
+    use fresh::app::terminal_handoff::TerminalHandoff;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Suspend").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.editor_mut().take_terminal_handoff(),
+        Some(TerminalHandoff::Suspend)
+    );
+}
@@ -187,7 +187,9 @@ fn test_auto_close_before_whitespace() {
     harness.open_file(&file_path).unwrap();
 
     // Position cursor at beginning (before space)
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
 
     // Type opening paren - should auto-close before whitespace
     harness.type_text("(").unwrap();
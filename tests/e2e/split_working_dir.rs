@@ -0,0 +1,116 @@
+//! Tests for giving a split its own working directory
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, TaskConfig};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Project with `sub/a.txt` and the given config, with `sub/a.txt` open
+fn setup(temp_dir: &TempDir, config: Config) -> EditorTestHarness {
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::create_dir(root.join("sub")).unwrap();
+    std::fs::write(root.join("sub/a.txt"), "a\n").unwrap();
+    std::fs::write(root.join("sub/b.txt"), "b\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 30, config, root.clone()).unwrap();
+    harness.open_file(&root.join("sub/a.txt")).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+#[test]
+fn test_set_and_clear_split_working_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, Config::default());
+    let root = temp_dir.path().canonicalize().unwrap();
+    harness.assert_screen_not_contains("[cwd:");
+
+    harness.run_command("Set Split Working Directory").unwrap();
+    harness.assert_screen_contains("[cwd: sub]");
+    assert_eq!(
+        harness.editor().active_split_working_dir(),
+        root.join("sub")
+    );
+
+    // Relative paths now resolve against the split's directory
+    harness.editor_mut().open_file(Path::new("b.txt")).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "b\n");
+
+    // A new split starts in the same directory
+    harness.run_command("Split Vertical").unwrap();
+    assert_eq!(
+        harness.editor().active_split_working_dir(),
+        root.join("sub")
+    );
+
+    harness
+        .run_command("Clear Split Working Directory")
+        .unwrap();
+    harness.assert_screen_not_contains("[cwd:");
+    assert_eq!(harness.editor().active_split_working_dir(), root);
+
+    // The other split keeps its own
+    harness.run_command("Next Split").unwrap();
+    assert_eq!(
+        harness.editor().active_split_working_dir(),
+        root.join("sub")
+    );
+}
+
+#[test]
+fn test_set_split_working_dir_needs_a_file() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.run_command("Set Split Working Directory").unwrap();
+    harness.assert_screen_contains("Buffer has no folder to use");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_task_runs_in_split_working_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        tasks: vec![TaskConfig {
+            name: "where".to_string(),
+            command: "echo \"in $(basename \"$PWD\")\"".to_string(),
+            working_dir: None,
+        }],
+        ..Default::default()
+    };
+    let mut harness = setup(&temp_dir, config);
+
+    harness.run_command("Set Split Working Directory").unwrap();
+    harness.run_command("Run Task").unwrap();
+    harness.type_text("where").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Task 'where' "))
+        .unwrap();
+    harness.assert_screen_contains("in sub");
+}
+
+#[test]
+fn test_split_working_dir_is_saved_with_the_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    {
+        let mut harness = setup(&temp_dir, Config::default());
+        harness.run_command("Set Split Working Directory").unwrap();
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 30, Config::default(), root.clone())
+            .unwrap();
+    assert!(harness.editor_mut().try_restore_session().unwrap());
+    harness.render().unwrap();
+    harness.assert_screen_contains("[cwd: sub]");
+    assert_eq!(
+        harness.editor().active_split_working_dir(),
+        root.join("sub")
+    );
+}
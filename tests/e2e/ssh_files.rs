@@ -0,0 +1,98 @@
+//! Tests for editing `ssh://` files, with a fake ssh that runs the remote
+//! command locally
+
+#![cfg(unix)]
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::services::ssh::{SshUrl, SSH_PROGRAM_ENV};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Install a fake `ssh` that skips the options and host and runs the
+/// remote command with `sh`
+fn use_fake_ssh(dir: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    let program = dir.join("fake-ssh");
+    std::fs::write(
+        &program,
+        "#!/bin/sh\nwhile [ $# -gt 1 ]; do\n  case \"$1\" in -o|-p) shift 2;; *) shift;; esac\ndone\nexec sh -c \"$1\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var(SSH_PROGRAM_ENV, &program);
+}
+
+#[test]
+fn test_edit_and_save_ssh_file() {
+    let temp_dir = TempDir::new().unwrap();
+    use_fake_ssh(temp_dir.path());
+    let remote_path = temp_dir.path().join("remote notes.txt");
+    std::fs::write(&remote_path, "remote content\n").unwrap();
+    let url = SshUrl::parse(&format!("ssh://user@testhost{}", remote_path.display())).unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.editor_mut().open_ssh_url(url.clone());
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "remote content\n")
+        .unwrap();
+    harness.assert_screen_contains(&url.to_string());
+
+    harness.type_text("edited ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|_| std::fs::read_to_string(&remote_path).unwrap() == "edited remote content\n")
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Saved ssh://"))
+        .unwrap();
+}
+
+/// Test that a remote file large enough to save in the background is still
+/// written back to the host
+#[test]
+fn test_large_ssh_file_is_uploaded() {
+    let temp_dir = TempDir::new().unwrap();
+    use_fake_ssh(temp_dir.path());
+    let remote_path = temp_dir.path().join("big.txt");
+    std::fs::write(&remote_path, "remote content\n").unwrap();
+    let url = SshUrl::parse(&format!("ssh://testhost{}", remote_path.display())).unwrap();
+
+    let mut config = Config::default();
+    config.editor.background_save_threshold_bytes = 1;
+    let mut harness = EditorTestHarness::with_config(160, 24, config).unwrap();
+    harness.editor_mut().open_ssh_url(url);
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "remote content\n")
+        .unwrap();
+
+    harness.type_text("big ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(!harness.editor().is_saving_in_background());
+    harness
+        .wait_until(|_| std::fs::read_to_string(&remote_path).unwrap() == "big remote content\n")
+        .unwrap();
+}
+
+#[test]
+fn test_missing_ssh_file_reports_error() {
+    let temp_dir = TempDir::new().unwrap();
+    use_fake_ssh(temp_dir.path());
+    let url = SshUrl::parse(&format!(
+        "ssh://testhost{}",
+        temp_dir.path().join("missing.txt").display()
+    ))
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.editor_mut().open_ssh_url(url);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Could not fetch"))
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+}
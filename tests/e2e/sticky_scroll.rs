@@ -0,0 +1,73 @@
+//! Tests for the sticky scroll band of enclosing scope headers
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyModifiers, MouseEvent, MouseEventKind};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+/// A Rust file with a function body long enough to scroll through
+fn long_function_source() -> String {
+    let mut source = String::from("impl Foo {\n    fn long_function() {\n");
+    for i in 0..60 {
+        source.push_str(&format!("        let x{i} = {i};\n"));
+    }
+    source.push_str("    }\n}\n");
+    source
+}
+
+fn open_scrolled(sticky_scroll: bool) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("foo.rs");
+    std::fs::write(&file_path, long_function_source()).unwrap();
+
+    let mut config = Config::default();
+    config.editor.sticky_scroll = sticky_scroll;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    for _ in 0..5 {
+        harness
+            .send_mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                column: 20,
+                row: 10,
+                modifiers: KeyModifiers::NONE,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+#[test]
+fn test_sticky_scroll_pins_enclosing_scopes() {
+    let (mut harness, _temp_dir) = open_scrolled(true);
+    let (first_row, _) = harness.content_area_rows();
+
+    assert!(harness.top_line_number() > 2);
+    assert!(harness.get_screen_row(first_row).contains("impl Foo {"));
+    assert!(harness
+        .get_screen_row(first_row + 1)
+        .contains("    fn long_function() {"));
+    assert!(harness.get_screen_row(first_row + 2).contains("let x"));
+}
+
+#[test]
+fn test_sticky_scroll_disabled() {
+    let (harness, _temp_dir) = open_scrolled(false);
+    harness.assert_screen_not_contains("impl Foo {");
+    harness.assert_screen_not_contains("fn long_function");
+}
+
+#[test]
+fn test_click_sticky_header_jumps_to_it() {
+    let (mut harness, _temp_dir) = open_scrolled(true);
+    let (first_row, _) = harness.content_area_rows();
+
+    harness.mouse_click(30, first_row as u16 + 1).unwrap();
+
+    let header = long_function_source().find("    fn long_function").unwrap();
+    assert_eq!(harness.cursor_position(), header);
+    assert!(harness.top_line_number() <= 1);
+}
@@ -0,0 +1,120 @@
+//! Tests for retrying a denied save as root, with a fake sudo that checks
+//! the password and runs the command as the current user
+
+#![cfg(unix)]
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::sudo::SUDO_PROGRAM_ENV;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Install a fake `sudo` that accepts the password "secret" and has no
+/// cached credentials
+fn use_fake_sudo(dir: &Path) {
+    let program = dir.join("fake-sudo");
+    std::fs::write(
+        &program,
+        "#!/bin/sh\n\
+         if [ \"$1\" = \"-n\" ]; then exit 1; fi\n\
+         read -r pw\n\
+         [ \"$pw\" = secret ] || { echo 'Sorry, try again.' >&2; exit 1; }\n\
+         shift 5\n\
+         exec \"$@\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var(SUDO_PROGRAM_ENV, &program);
+}
+
+/// A writable file in a directory the editor cannot create its temporary
+/// file in. Returns `None` when running as root, where nothing is denied.
+fn protected_file(temp_dir: &TempDir) -> Option<PathBuf> {
+    let dir = temp_dir.path().join("etc");
+    std::fs::create_dir(&dir).unwrap();
+    let file = dir.join("app.conf");
+    std::fs::write(&file, "old\n").unwrap();
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+    if std::fs::write(dir.join("probe"), "").is_ok() {
+        return None;
+    }
+    Some(file)
+}
+
+fn press_enter(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+#[test]
+fn test_save_as_root_with_password() {
+    let temp_dir = TempDir::new().unwrap();
+    use_fake_sudo(temp_dir.path());
+    let Some(file) = protected_file(&temp_dir) else {
+        return;
+    };
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.type_text("new ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Retry as root? (y/N)");
+
+    harness.type_text("y").unwrap();
+    press_enter(&mut harness);
+    harness.render().unwrap();
+    harness.assert_screen_contains("[sudo] password:");
+
+    harness.type_text("secret").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[sudo] password: ******");
+    assert!(!harness.screen_to_string().contains("secret"));
+
+    press_enter(&mut harness);
+    harness.render().unwrap();
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "new old\n");
+    harness.assert_screen_contains("as root");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    std::fs::set_permissions(
+        file.parent().unwrap(),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_save_as_root_wrong_password_keeps_file() {
+    let temp_dir = TempDir::new().unwrap();
+    use_fake_sudo(temp_dir.path());
+    let Some(file) = protected_file(&temp_dir) else {
+        return;
+    };
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.type_text("new ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("y").unwrap();
+    press_enter(&mut harness);
+    harness.type_text("guess").unwrap();
+    press_enter(&mut harness);
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Failed to save as root: Sorry, try again.");
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "old\n");
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    std::fs::set_permissions(
+        file.parent().unwrap(),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+}
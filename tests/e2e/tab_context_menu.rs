@@ -0,0 +1,138 @@
+//! E2E tests for the tab context menu opened with a right click
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Open `names` as files with their own name as content, and return the
+/// temp dir holding them
+fn open_files(harness: &mut EditorTestHarness, names: &[&str]) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    for name in names {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, *name).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.render().unwrap();
+    temp_dir
+}
+
+/// Screen column of a tab's name in the tab bar
+fn tab_col(harness: &EditorTestHarness, name: &str) -> u16 {
+    let tab_bar = harness.get_tab_bar();
+    let index = tab_bar
+        .find(name)
+        .unwrap_or_else(|| panic!("Tab {name} not in tab bar: {tab_bar:?}"));
+    tab_bar[..index].chars().count() as u16
+}
+
+/// Pick the `index`th enabled entry of the open context menu from the keyboard
+fn pick(harness: &mut EditorTestHarness, index: usize) {
+    for _ in 0..=index {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that right-clicking a tab lists its actions, and Escape closes the menu
+#[test]
+fn test_right_click_tab_opens_context_menu() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _dir = open_files(&mut harness, &["a.txt", "b.txt"]);
+    harness.assert_screen_not_contains("Close Others");
+
+    harness
+        .mouse_right_click(tab_col(&harness, "a.txt"), 1)
+        .unwrap();
+    for entry in [
+        "Close Others",
+        "Close to the Right",
+        "Pin",
+        "Copy Path",
+        "Reveal in File Explorer",
+        "Split Right",
+    ] {
+        harness.assert_screen_contains(entry);
+    }
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Close Others");
+    harness.assert_buffer_content("b.txt");
+}
+
+/// Test that Close Others keeps the clicked tab and pinned tabs
+#[test]
+fn test_close_others_keeps_pinned_tabs() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _dir = open_files(&mut harness, &["a.txt", "b.txt", "c.txt", "d.txt"]);
+
+    // Pin c.txt: it moves to the front of the tab bar with a pin marker
+    harness
+        .mouse_right_click(tab_col(&harness, "c.txt"), 1)
+        .unwrap();
+    pick(&mut harness, 3);
+    let tab_bar = harness.get_tab_bar();
+    assert!(
+        tab_col(&harness, "c.txt") < tab_col(&harness, "a.txt") && tab_bar.contains('⚑'),
+        "Expected pinned c.txt first: {tab_bar:?}"
+    );
+
+    // Close Others on b.txt
+    harness
+        .mouse_right_click(tab_col(&harness, "b.txt"), 1)
+        .unwrap();
+    pick(&mut harness, 1);
+    let tab_bar = harness.get_tab_bar();
+    assert!(tab_bar.contains("b.txt") && tab_bar.contains("c.txt"));
+    assert!(
+        !tab_bar.contains("a.txt") && !tab_bar.contains("d.txt"),
+        "Expected other tabs closed: {tab_bar:?}"
+    );
+    harness.assert_buffer_content("b.txt");
+}
+
+/// Test that Close to the Right only closes the tabs after the clicked one
+#[test]
+fn test_close_tabs_to_the_right() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _dir = open_files(&mut harness, &["a.txt", "b.txt", "c.txt"]);
+
+    harness
+        .mouse_right_click(tab_col(&harness, "a.txt"), 1)
+        .unwrap();
+    // The menu's border is on row 2, so its third entry is on row 5
+    harness
+        .mouse_click(tab_col(&harness, "a.txt") + 2, 5)
+        .unwrap();
+
+    let tab_bar = harness.get_tab_bar();
+    assert!(tab_bar.contains("a.txt"));
+    assert!(
+        !tab_bar.contains("b.txt") && !tab_bar.contains("c.txt"),
+        "Expected tabs to the right closed: {tab_bar:?}"
+    );
+}
+
+/// Test that Split Right opens the tab's buffer in a new split
+#[test]
+fn test_split_right_from_tab_menu() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _dir = open_files(&mut harness, &["a.txt", "b.txt"]);
+
+    harness
+        .mouse_right_click(tab_col(&harness, "a.txt"), 1)
+        .unwrap();
+    pick(&mut harness, 6);
+
+    harness.assert_buffer_content("a.txt");
+    let tab_bar = harness.get_tab_bar();
+    assert_eq!(
+        tab_bar.matches("a.txt").count(),
+        2,
+        "Expected a.txt in both splits: {tab_bar:?}"
+    );
+}
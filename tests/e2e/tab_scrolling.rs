@@ -158,3 +158,104 @@ fn test_active_tab_visibility_with_scrolling() {
         // Check for indicators based on current position and width.
     }
 }
+
+/// Test that a tab bar too narrow for its tabs gets an overflow button
+/// listing the hidden tabs, and that picking one switches to it
+#[test]
+fn test_tab_overflow_dropdown_lists_hidden_tabs() {
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_dummy_files(&temp_dir);
+
+    let mut harness = EditorTestHarness::new(NARROW_WIDTH, TEST_HEIGHT).unwrap();
+    for file_path in &files {
+        harness.open_file(file_path).unwrap();
+    }
+    harness.render().unwrap();
+
+    // The overflow button sits at the right end of the tab bar (row 1)
+    let tab_bar = harness.get_tab_bar();
+    assert!(
+        tab_bar.trim_end().ends_with('⋯'),
+        "Expected overflow button in tab bar: {tab_bar:?}"
+    );
+
+    // Clicking it lists the tabs scrolled out of view, first tab first
+    harness.mouse_click(NARROW_WIDTH - 2, 1).unwrap();
+    assert!(
+        harness
+            .get_screen_row(2)
+            .contains("long_file_name_number_00.txt"),
+        "Expected first hidden tab at the top of the list. Screen:\n{}",
+        harness.screen_to_string()
+    );
+
+    // Navigate down one entry and pick it
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("Content for file 1");
+    harness.assert_screen_not_contains("long_file_name_number_00.txt");
+}
+
+/// Test that clicking a hidden tab in the overflow list switches to it, and
+/// Escape closes the list without switching
+#[test]
+fn test_tab_overflow_dropdown_mouse_and_escape() {
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_dummy_files(&temp_dir);
+
+    let mut harness = EditorTestHarness::new(NARROW_WIDTH, TEST_HEIGHT).unwrap();
+    for file_path in &files {
+        harness.open_file(file_path).unwrap();
+    }
+    harness.render().unwrap();
+
+    harness.mouse_click(NARROW_WIDTH - 2, 1).unwrap();
+    harness.assert_screen_contains("long_file_name_number_00.txt");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("long_file_name_number_00.txt");
+    harness.assert_buffer_content("Content for file 14");
+
+    harness.mouse_click(NARROW_WIDTH - 2, 1).unwrap();
+    harness.mouse_click(NARROW_WIDTH - 5, 4).unwrap();
+    harness.assert_buffer_content("Content for file 2");
+}
+
+/// Test that the mouse wheel over the tab bar cycles through the tabs
+#[test]
+fn test_mouse_wheel_over_tab_bar_cycles_tabs() {
+    use crossterm::event::{MouseEvent, MouseEventKind};
+
+    let temp_dir = TempDir::new().unwrap();
+    let files = create_dummy_files(&temp_dir);
+
+    let mut harness = EditorTestHarness::new(80, TEST_HEIGHT).unwrap();
+    for file_path in &files[..3] {
+        harness.open_file(file_path).unwrap();
+    }
+    harness.render().unwrap();
+    harness.assert_buffer_content("Content for file 2");
+
+    let wheel = |kind| MouseEvent {
+        kind,
+        column: 10,
+        row: 1,
+        modifiers: KeyModifiers::NONE,
+    };
+
+    harness.send_mouse(wheel(MouseEventKind::ScrollUp)).unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("Content for file 1");
+
+    harness
+        .send_mouse(wheel(MouseEventKind::ScrollDown))
+        .unwrap();
+    harness
+        .send_mouse(wheel(MouseEventKind::ScrollDown))
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("Content for file 0");
+}
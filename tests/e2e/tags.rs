@@ -0,0 +1,82 @@
+//! Tests for the ctags fallback: go to definition and completion from the
+//! project's tags file when no language server is running
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+const TAGS: &str = "\
+!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/
+parse_args\tutil.c\t/^int parse_args(int argc, char **argv)$/;\"\tf
+parse_line\tutil.c\t/^static int parse_line(const char *s)$/;\"\tf
+";
+
+const UTIL: &str = "\
+#include <stdio.h>
+
+static int parse_line(const char *s)
+{
+    return 0;
+}
+
+int parse_args(int argc, char **argv)
+{
+    return parse_line(argv[0]);
+}
+";
+
+/// Project with a tags file, with `main.c` open
+fn setup(temp_dir: &TempDir, main: &str) -> EditorTestHarness {
+    let root = temp_dir.path();
+    std::fs::write(root.join("tags"), TAGS).unwrap();
+    std::fs::write(root.join("util.c"), UTIL).unwrap();
+    std::fs::write(root.join("main.c"), main).unwrap();
+
+    let mut harness = EditorTestHarness::with_working_dir(120, 30, root.to_path_buf()).unwrap();
+    harness.open_file(&root.join("main.c")).unwrap();
+    harness
+}
+
+#[test]
+fn test_goto_definition_from_tags() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(
+        &temp_dir,
+        "int main(int c, char **v) { return parse_args(c, v); }\n",
+    );
+
+    // Cursor inside `parse_args`
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key_repeat(KeyCode::Left, KeyModifiers::NONE, 12)
+        .unwrap();
+
+    harness.run_command("Go to Definition").unwrap();
+    harness.assert_screen_contains("Jumped to definition of 'parse_args' from tags");
+    assert_eq!(harness.get_buffer_content().unwrap(), UTIL);
+    // Start of line 8, found from the tag's search pattern
+    assert_eq!(
+        harness.cursor_position(),
+        UTIL.find("int parse_args").unwrap()
+    );
+}
+
+#[test]
+fn test_goto_definition_missing_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, "unknown_name\n");
+
+    harness.run_command("Go to Definition").unwrap();
+    harness.assert_screen_contains("No tag for 'unknown_name'");
+}
+
+#[test]
+fn test_completion_from_tags() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, "");
+
+    harness.type_text("parse_").unwrap();
+    harness.run_command("Show Completions").unwrap();
+    harness.assert_screen_contains("parse_args");
+    harness.assert_screen_contains("parse_line");
+}
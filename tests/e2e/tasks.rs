@@ -0,0 +1,120 @@
+//! Tests for running project tasks and jumping to the errors they report
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, TaskConfig};
+use tempfile::TempDir;
+
+/// Project with `src/main.c` and the given tasks configured
+fn setup(temp_dir: &TempDir, tasks: &[(&str, &str)]) -> EditorTestHarness {
+    let root = temp_dir.path();
+    std::fs::create_dir(root.join("src")).unwrap();
+    std::fs::write(root.join("src/main.c"), "int main() {\n    return\n}\n").unwrap();
+
+    let config = Config {
+        tasks: tasks
+            .iter()
+            .map(|(name, command)| TaskConfig {
+                name: name.to_string(),
+                command: command.to_string(),
+                working_dir: None,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    EditorTestHarness::with_config_and_working_dir(160, 30, config, root.to_path_buf()).unwrap()
+}
+
+/// Run the task called `name` and wait for it to finish
+fn run_task(harness: &mut EditorTestHarness, name: &str) {
+    harness.run_command("Run Task").unwrap();
+    harness.assert_screen_contains("Run task:");
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(&format!("Task '{}' ", name)))
+        .unwrap();
+}
+
+const BUILD: &str =
+    "echo compiling; echo 'src/main.c:2:11: error: expected expression' >&2; exit 1";
+
+#[test]
+#[cfg(unix)]
+fn test_run_task_streams_output_and_collects_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, &[("test", "true"), ("build", BUILD)]);
+    run_task(&mut harness, "build");
+
+    harness.assert_screen_contains("*Task: build*");
+    harness.assert_screen_contains("compiling");
+    harness.assert_screen_contains("Task 'build' failed (exit code 1): 1 error(s), 0 warning(s)");
+
+    harness.run_command("Quickfix: Next Entry").unwrap();
+    harness.assert_screen_contains("1/1 error: expected expression");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "int main() {\n    return\n}\n"
+    );
+    // Line 2, column 11 (just after "return")
+    assert_eq!(harness.cursor_position(), 23);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_enter_on_task_output_line_jumps_to_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, &[("build", BUILD)]);
+    run_task(&mut harness, "build");
+
+    // Move to the error line in the output buffer
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    let mut found = false;
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        if harness
+            .screen_to_string()
+            .contains("1/1 error: expected expression")
+        {
+            found = true;
+            break;
+        }
+    }
+    assert!(found, "Enter on the error line should jump to it");
+    assert_eq!(harness.cursor_position(), 23);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_stop_task() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, &[("watch", "echo started; sleep 30")]);
+    harness.run_command("Run Task").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("started"))
+        .unwrap();
+
+    harness.run_command("Stop Task").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Task 'watch' was stopped"))
+        .unwrap();
+}
+
+#[test]
+fn test_run_task_without_tasks_configured() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = setup(&temp_dir, &[]);
+    harness.run_command("Run Task").unwrap();
+    harness.assert_screen_contains("No tasks configured");
+}
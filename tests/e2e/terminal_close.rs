@@ -33,19 +33,6 @@ macro_rules! harness_or_return {
     };
 }
 
-/// Helper to run a command via command palette
-fn run_command(harness: &mut EditorTestHarness, command: &str) {
-    harness
-        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
-        .unwrap();
-    harness.render().unwrap();
-    harness.type_text(command).unwrap();
-    harness
-        .send_key(KeyCode::Enter, KeyModifiers::NONE)
-        .unwrap();
-    harness.render().unwrap();
-}
-
 /// Test: Open split, open terminal in split, close all other tabs, then close buffer
 ///
 /// When terminal is the only tab in a split and we close it:
@@ -57,7 +44,7 @@ fn test_close_terminal_as_only_tab_in_split() {
     let mut harness = harness_or_return!(120, 30);
 
     // Create a vertical split - now we have two splits with [No Name]
-    run_command(&mut harness, "split vert");
+    harness.run_command("split vert").unwrap();
 
     // Disable jump_to_end_on_output so terminal output doesn't interfere
     harness
@@ -81,16 +68,16 @@ fn test_close_terminal_as_only_tab_in_split() {
 
     // Close the [No Name] buffer in this split so terminal is the only tab
     // First switch to it
-    run_command(&mut harness, "prev buffer");
+    harness.run_command("prev buffer").unwrap();
     // Then close it
-    run_command(&mut harness, "close tab");
+    harness.run_command("close tab").unwrap();
 
     // Now terminal is the only tab in the right split
     // The left split has [No Name]
     harness.assert_screen_contains("Terminal");
 
     // Close the terminal buffer
-    run_command(&mut harness, "close buffer");
+    harness.run_command("close buffer").unwrap();
 
     // After closing:
     // 1. terminal_mode should be OFF
@@ -157,7 +144,7 @@ fn test_close_terminal_after_switching_back_to_terminal_split() {
     harness.render().unwrap();
 
     // Create a vertical split (focus moves to new split)
-    run_command(&mut harness, "split vert");
+    harness.run_command("split vert").unwrap();
 
     // Now we're in the new (right) split with [No Name]
     assert!(
@@ -166,7 +153,7 @@ fn test_close_terminal_after_switching_back_to_terminal_split() {
     );
 
     // Focus back on the first split (with terminal) - this re-enters terminal_mode
-    run_command(&mut harness, "prev split");
+    harness.run_command("prev split").unwrap();
 
     let active_buffer = harness.editor().active_buffer_id();
     assert!(
@@ -176,7 +163,7 @@ fn test_close_terminal_after_switching_back_to_terminal_split() {
 
     // The bug: terminal_mode becomes true when switching to terminal split
     // Close the terminal buffer - terminal_mode should be turned OFF
-    run_command(&mut harness, "close buffer");
+    harness.run_command("close buffer").unwrap();
 
     // After closing:
     // 1. terminal_mode MUST be OFF (this is the bug being tested)
@@ -239,7 +226,7 @@ fn test_close_buffer_in_one_split_leaves_other_split() {
     harness.render().unwrap();
 
     // Create a vertical split - the new split will also show the terminal
-    run_command(&mut harness, "split vert");
+    harness.run_command("split vert").unwrap();
 
     // Both splits should show Terminal 0
     harness.render().unwrap();
@@ -254,7 +241,7 @@ fn test_close_buffer_in_one_split_leaves_other_split() {
 
     // Now close the terminal in the current split using close_tab
     // This should remove it from this split but leave it in the other split
-    run_command(&mut harness, "close tab");
+    harness.run_command("close tab").unwrap();
 
     harness.render().unwrap();
 
@@ -1,4 +1,5 @@
 use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
 use tempfile::TempDir;
 
 /// Test that viewport uses full available area after terminal resize at startup
@@ -143,3 +144,93 @@ fn test_viewport_updates_after_resize_with_open_file() {
         content_lines_after.len()
     );
 }
+
+/// Below the minimum size the editor shows a notice instead of its layout,
+/// and comes back once the terminal grows again
+#[test]
+fn test_too_small_window_shows_notice() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello world").unwrap();
+
+    harness.resize(18, 4).unwrap();
+    harness.assert_screen_contains("Window too small");
+    harness.assert_screen_not_contains("hello world");
+
+    // Editing keeps working while the notice is up
+    harness.type_text("!").unwrap();
+
+    harness.resize(80, 24).unwrap();
+    harness.assert_screen_not_contains("Window too small");
+    harness.assert_screen_contains("hello world!");
+}
+
+/// Rendering and editing at extreme sizes must not panic
+#[test]
+fn test_extreme_sizes_do_not_panic() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness
+        .type_text("a line long enough to wrap several times in a narrow split\nsecond\nthird")
+        .unwrap();
+    harness.editor_mut().split_pane_vertical();
+    harness.editor_mut().split_pane_vertical();
+    harness.editor_mut().split_pane_horizontal();
+
+    for (width, height) in [(1, 1), (500, 5), (5, 500), (20, 5), (21, 6), (80, 24)] {
+        harness.resize(width, height).unwrap();
+        harness.type_text("x\ny").unwrap();
+        harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        harness
+            .send_key(KeyCode::PageDown, KeyModifiers::NONE)
+            .unwrap();
+
+        // Popups sized for a normal terminal
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("s").unwrap();
+        harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+        harness
+            .send_key(KeyCode::Char('o'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+        harness
+            .send_key(KeyCode::Char(','), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+        harness.render().unwrap();
+    }
+    harness.assert_screen_not_contains("Window too small");
+}
+
+/// Splits keep their proportions when the terminal is resized, and their
+/// viewports take their share of the new size right away
+#[test]
+fn test_splits_relayout_proportionally_on_resize() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("left and right").unwrap();
+    harness.editor_mut().split_pane_vertical();
+    harness.render().unwrap();
+
+    let separator_column = |harness: &EditorTestHarness| {
+        let row = harness.get_screen_row(3);
+        row.chars().position(|c| c == '│' || c == '┃')
+    };
+    let before = separator_column(&harness).expect("separator at 80 columns");
+
+    harness.editor_mut().resize(160, 24);
+    let width = harness.editor().active_viewport().width;
+    assert!(
+        (70..=80).contains(&width),
+        "viewport should take half of the wider terminal before the next render, got {}",
+        width
+    );
+
+    harness.resize(160, 24).unwrap();
+    let after = separator_column(&harness).expect("separator at 160 columns");
+    assert!(
+        after.abs_diff(before * 2) <= 2,
+        "separator moved from column {} to {} instead of keeping its proportion",
+        before,
+        after
+    );
+}
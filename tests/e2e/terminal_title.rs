@@ -0,0 +1,42 @@
+//! E2E tests for the terminal title following the active buffer
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+
+/// Test that the title names the active file and project, marks unsaved
+/// changes, and is only handed out again when it changes
+#[test]
+fn test_terminal_title_follows_active_buffer() {
+    let fixture = TestFixture::new("notes.txt", "hello\n").unwrap();
+    let project = fixture.path.parent().unwrap().to_path_buf();
+    let project_name = project.file_name().unwrap().to_string_lossy().into_owned();
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Config::default(), project).unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    let title = format!("notes.txt — {} — fresh", project_name);
+    assert_eq!(
+        harness.editor_mut().take_terminal_title_change(),
+        Some(title.clone())
+    );
+    assert_eq!(harness.editor_mut().take_terminal_title_change(), None);
+
+    harness.type_text("x").unwrap();
+    assert_eq!(
+        harness.editor_mut().take_terminal_title_change(),
+        Some(format!("notes.txt* — {} — fresh", project_name))
+    );
+
+    harness.editor_mut().forget_terminal_title();
+    assert!(harness.editor_mut().take_terminal_title_change().is_some());
+}
+
+/// Test that an empty format leaves the terminal title alone
+#[test]
+fn test_terminal_title_disabled() {
+    let mut config = Config::default();
+    config.editor.terminal_title = String::new();
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    assert_eq!(harness.editor_mut().take_terminal_title_change(), None);
+}
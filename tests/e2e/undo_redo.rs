@@ -1,5 +1,79 @@
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::time::Duration;
+
+/// Harness that undoes typed text one character at a time
+fn harness_undoing_characters() -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.undo_group_timeout_ms = 0;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+fn undo(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+fn redo(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+/// Test that typed text is undone a word at a time, the spaces after a
+/// word going with it
+#[test]
+fn test_typed_words_undo_as_one_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello big world").unwrap();
+
+    undo(&mut harness);
+    harness.assert_buffer_content("hello big ");
+    undo(&mut harness);
+    harness.assert_buffer_content("hello ");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+
+    redo(&mut harness);
+    harness.assert_buffer_content("hello ");
+    redo(&mut harness);
+    redo(&mut harness);
+    harness.assert_buffer_content("hello big world");
+}
+
+/// Test that a pause in typing longer than the undo group timeout starts a
+/// new undo step within a word
+#[test]
+fn test_typing_pause_starts_new_undo_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let timeout = Duration::from_millis(harness.config().editor.undo_group_timeout_ms);
+    harness.type_text("hel").unwrap();
+    harness.advance_time(timeout + Duration::from_millis(1));
+    harness.type_text("lo").unwrap();
+
+    undo(&mut harness);
+    harness.assert_buffer_content("hel");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
+
+/// Test that a cursor move ends the word being typed, so the text typed
+/// after it is undone on its own
+#[test]
+fn test_cursor_move_ends_typed_undo_step() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("world").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.assert_buffer_content("helloworld");
+
+    undo(&mut harness);
+    harness.assert_buffer_content("world");
+    undo(&mut harness);
+    harness.assert_buffer_content("");
+}
 
 /// Test that undo skips over readonly actions (like cursor movement) and only undoes write actions
 ///
@@ -9,7 +83,7 @@ use crossterm::event::{KeyCode, KeyModifiers};
 /// 3. Undo once should undo the cursor movements AND the last typed character
 #[test]
 fn test_undo_skips_readonly_movement_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness = harness_undoing_characters();
 
     // Type "hello"
     harness.type_text("hello").unwrap();
@@ -52,7 +126,7 @@ fn test_undo_skips_readonly_movement_actions() {
 /// Test that multiple undo steps skip over all readonly actions
 #[test]
 fn test_multiple_undo_skips_all_readonly_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness = harness_undoing_characters();
 
     // Type "abc"
     harness.type_text("abc").unwrap();
@@ -88,7 +162,7 @@ fn test_multiple_undo_skips_all_readonly_actions() {
 /// Test that redo also skips readonly actions
 #[test]
 fn test_redo_skips_readonly_movement_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness = harness_undoing_characters();
 
     // Type "xyz"
     harness.type_text("xyz").unwrap();
@@ -113,7 +187,7 @@ fn test_redo_skips_readonly_movement_actions() {
 /// Test undo/redo with mixed write and readonly actions
 #[test]
 fn test_undo_redo_with_mixed_actions() {
-    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let mut harness = harness_undoing_characters();
 
     // Type "ab"
     harness.type_text("ab").unwrap();
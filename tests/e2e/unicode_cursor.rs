@@ -27,7 +27,9 @@ fn test_cursor_sync_with_non_ascii_box_drawing_chars() {
         expected_buffer_pos, buffer_pos
     );
 
-    // Move cursor to the beginning of the line
+    // Move cursor to the beginning of the line (the first Home stops after
+    // the leading spaces)
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
     harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
 
     // Cursor should now be at position 0
@@ -63,7 +65,9 @@ fn test_cursor_sync_with_non_ascii_box_drawing_chars() {
 
     // Now test: type a character and verify it appears at the visual cursor position
     // Move to somewhere in the middle of the line
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
 
     // Move right 20 characters
     for _ in 0..20 {
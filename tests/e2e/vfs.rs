@@ -0,0 +1,181 @@
+//! E2E tests for buffers backed by file system providers (`git://`,
+//! `cmd://` and plugin schemes) and for opening earlier revisions of a file
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+}
+
+/// Commit `notes.txt` as "old notes" ("first"), then as "new notes"
+/// ("second")
+fn commit_notes_twice(dir: &Path) {
+    git(dir, &["init", "-q"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    git(dir, &["config", "user.name", "Test"]);
+    std::fs::write(dir.join("notes.txt"), "old notes\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "first"]);
+    std::fs::write(dir.join("notes.txt"), "new notes\n").unwrap();
+    git(dir, &["commit", "-q", "-am", "second"]);
+}
+
+/// Choose the commit with `summary` in the revision prompt
+fn choose_revision(harness: &mut EditorTestHarness, summary: &str) {
+    harness.type_text(summary).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A URL typed in the Open File prompt opens the provider's content
+#[cfg(unix)]
+#[test]
+fn test_open_command_output_from_prompt() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("cmd://printf 'from a command'").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "from a command")
+        .unwrap();
+    harness.assert_screen_contains("cmd://printf");
+}
+
+/// `git://REV/path` opens an old revision of a file, which cannot be saved
+#[test]
+fn test_open_git_revision_read_only() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let dir = harness.project_dir().unwrap();
+    commit_notes_twice(&dir);
+
+    assert!(harness.editor_mut().open_url("git://HEAD~1/notes.txt"));
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "old notes\n")
+        .unwrap();
+    harness.assert_screen_contains("notes.txt@HEAD~1");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("git://HEAD~1/notes.txt is read-only");
+    assert_eq!(
+        std::fs::read_to_string(dir.join("notes.txt")).unwrap(),
+        "new notes\n"
+    );
+}
+
+/// "Open File @ Revision..." lists the commits of the file and opens the
+/// chosen one in a `file@hash` buffer
+#[test]
+fn test_open_file_at_revision() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let dir = harness.project_dir().unwrap();
+    commit_notes_twice(&dir);
+    harness.open_file(&dir.join("notes.txt")).unwrap();
+
+    harness.run_command("Open File @ Revision").unwrap();
+    harness.assert_screen_contains("second");
+    harness.assert_screen_contains("Test, ");
+    choose_revision(&mut harness, "first");
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "old notes\n")
+        .unwrap();
+    harness.assert_screen_contains("notes.txt@");
+}
+
+/// "Compare with Revision..." opens the diff viewer from the chosen commit
+/// to the buffer, including unsaved edits
+#[test]
+fn test_compare_with_revision() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let dir = harness.project_dir().unwrap();
+    commit_notes_twice(&dir);
+    harness.open_file(&dir.join("notes.txt")).unwrap();
+    harness.type_text("unsaved ").unwrap();
+
+    harness.run_command("Compare with Revision").unwrap();
+    choose_revision(&mut harness, "first");
+
+    harness.assert_screen_contains("-old notes");
+    harness.assert_screen_contains("+unsaved new notes");
+}
+
+/// Files outside a git repository have no revisions
+#[test]
+fn test_open_file_at_revision_without_git() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let dir = harness.project_dir().unwrap();
+    std::fs::write(dir.join("notes.txt"), "notes\n").unwrap();
+    harness.open_file(&dir.join("notes.txt")).unwrap();
+
+    harness.run_command("Open File @ Revision").unwrap();
+    assert!(!harness.editor().is_prompting());
+    harness.assert_screen_contains("git log failed");
+}
+
+/// A plugin provider answers reads and writes, and the buffer is marked
+/// saved once the write completes
+#[test]
+fn test_plugin_provider_read_and_write() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .register_plugin_vfs_provider("mem", true);
+
+    assert!(harness.editor_mut().open_url("mem://notes"));
+    harness
+        .editor_mut()
+        .complete_vfs_read("mem://notes", Ok("plugin content".to_string()));
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "plugin content");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    harness.type_text("edited ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    harness
+        .editor_mut()
+        .complete_vfs_write("mem://notes", Ok(()));
+    harness.render().unwrap();
+    assert!(!harness.editor().active_state().buffer.is_modified());
+    harness.assert_screen_contains("Saved mem://notes");
+}
+
+/// Read errors are reported without opening a buffer
+#[test]
+fn test_plugin_provider_read_error() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .register_plugin_vfs_provider("mem", false);
+
+    assert!(harness.editor_mut().open_url("mem://missing"));
+    harness
+        .editor_mut()
+        .complete_vfs_read("mem://missing", Err("not found".to_string()));
+    harness.render().unwrap();
+    harness.assert_screen_contains("Error opening mem://missing: not found");
+    assert!(!harness.editor_mut().open_url("s3://bucket/key"));
+}
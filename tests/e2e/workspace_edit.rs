@@ -0,0 +1,73 @@
+//! Tests for applying edits that span several files
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::app::workspace_edit::{EditRange, TextEdit, WorkspaceEdit};
+
+fn replace(range: std::ops::Range<usize>, new_text: &str) -> TextEdit {
+    TextEdit {
+        range: EditRange::Bytes(range),
+        new_text: new_text.to_string(),
+    }
+}
+
+#[test]
+fn test_edits_open_buffer_and_closed_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let open = temp_dir.path().join("open.rs");
+    let closed = temp_dir.path().join("closed.rs");
+    std::fs::write(&open, "fn value() {}\nvalue();\n").unwrap();
+    std::fs::write(&closed, "use value;\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&open).unwrap();
+
+    let mut edit = WorkspaceEdit::default();
+    edit.add(
+        open.clone(),
+        [replace(14..19, "amount"), replace(3..8, "amount")],
+    );
+    edit.add(closed.clone(), [replace(4..9, "amount")]);
+    let applied = harness
+        .editor_mut()
+        .apply_workspace_edit(edit, "Rename")
+        .unwrap();
+    assert_eq!((applied.buffers, applied.files, applied.edits), (1, 1, 3));
+
+    // The open buffer is edited in memory, the closed file on disk
+    harness.assert_buffer_content("fn amount() {}\namount();\n");
+    assert_eq!(
+        std::fs::read_to_string(&open).unwrap(),
+        "fn value() {}\nvalue();\n"
+    );
+    assert_eq!(std::fs::read_to_string(&closed).unwrap(), "use amount;\n");
+
+    // All edits of a buffer undo together
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("fn value() {}\nvalue();\n");
+}
+
+#[test]
+fn test_invalid_edit_changes_nothing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let open = temp_dir.path().join("open.rs");
+    let closed = temp_dir.path().join("closed.rs");
+    std::fs::write(&open, "let a = 1;\n").unwrap();
+    std::fs::write(&closed, "let b = 2;\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&open).unwrap();
+
+    let mut edit = WorkspaceEdit::default();
+    edit.add(open.clone(), [replace(4..5, "x")]);
+    edit.add(closed.clone(), [replace(4..5, "y"), replace(4..6, "z")]);
+    assert!(harness
+        .editor_mut()
+        .apply_workspace_edit(edit, "Rename")
+        .is_err());
+
+    harness.assert_buffer_content("let a = 1;\n");
+    assert_eq!(std::fs::read_to_string(&closed).unwrap(), "let b = 2;\n");
+}
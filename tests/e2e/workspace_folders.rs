@@ -0,0 +1,100 @@
+//! Tests for workspaces with several root folders
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A project folder with `main.txt` and a second folder with `lib.txt`
+fn setup(temp_dir: &TempDir) -> (PathBuf, PathBuf) {
+    let base = temp_dir.path().canonicalize().unwrap();
+    let project = base.join("project");
+    let other = base.join("other");
+    std::fs::create_dir(&project).unwrap();
+    std::fs::create_dir(&other).unwrap();
+    std::fs::write(project.join("main.txt"), "main\n").unwrap();
+    std::fs::write(other.join("lib.txt"), "lib\n").unwrap();
+    (project, other)
+}
+
+fn harness_in(project: &Path) -> EditorTestHarness {
+    EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Config::default(),
+        project.to_path_buf(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_add_workspace_folder_shows_it_in_the_explorer() {
+    let temp_dir = TempDir::new().unwrap();
+    let (project, other) = setup(&temp_dir);
+    let mut harness = harness_in(&project);
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("main.txt").unwrap();
+
+    harness.editor_mut().add_workspace_folder(other.clone());
+    harness.render().unwrap();
+    harness.assert_screen_contains("Added to workspace");
+    harness.wait_for_file_explorer_item("lib.txt").unwrap();
+    harness.assert_screen_contains("main.txt");
+    assert_eq!(
+        harness.editor().workspace_roots(),
+        vec![project.clone(), other.clone()]
+    );
+}
+
+#[test]
+fn test_overlapping_workspace_folder_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let (project, _) = setup(&temp_dir);
+    std::fs::create_dir(project.join("nested")).unwrap();
+    let mut harness = harness_in(&project);
+
+    harness
+        .editor_mut()
+        .add_workspace_folder(project.join("nested"));
+    harness.render().unwrap();
+    harness.assert_screen_contains("overlaps workspace folder");
+    assert!(harness.editor().workspace_folders().is_empty());
+}
+
+#[test]
+fn test_remove_workspace_folder() {
+    let temp_dir = TempDir::new().unwrap();
+    let (project, other) = setup(&temp_dir);
+    let mut harness = harness_in(&project);
+
+    harness.run_command("Remove Folder from Workspace").unwrap();
+    harness.assert_screen_contains("No folders have been added to the workspace");
+
+    harness.editor_mut().add_workspace_folder(other.clone());
+    harness.run_command("Remove Folder from Workspace").unwrap();
+    harness.assert_screen_contains(&other.display().to_string());
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Removed from workspace");
+    assert!(harness.editor().workspace_folders().is_empty());
+}
+
+#[test]
+fn test_workspace_folders_are_saved_with_the_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let (project, other) = setup(&temp_dir);
+    {
+        let mut harness = harness_in(&project);
+        harness.editor_mut().add_workspace_folder(other.clone());
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    let mut harness = harness_in(&project);
+    assert!(harness.editor_mut().try_restore_session().unwrap());
+    assert_eq!(harness.editor().workspace_folders(), [other]);
+}
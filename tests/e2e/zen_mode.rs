@@ -0,0 +1,73 @@
+//! Tests for zen (distraction-free) mode
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn toggle_zen_mode(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Toggle Zen Mode").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_zen_mode_hides_bars_and_centers_text() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "first line\nsecond line\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.zen_mode_width = 40;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("notes.txt ×");
+    harness.assert_screen_contains("    1 │ first line");
+    harness.assert_screen_contains("File   Edit");
+
+    toggle_zen_mode(&mut harness);
+    assert!(harness.editor().is_zen_mode());
+    assert!(harness.editor().config().editor.zen_mode);
+    harness.assert_screen_not_contains("notes.txt ×");
+    harness.assert_screen_not_contains("File   Edit");
+    harness.assert_screen_not_contains("│ first line");
+
+    // The text starts in the top row, in a 40 column wide centered column
+    let first_row = harness.get_screen_row(0);
+    let indent = first_row.find("first line").unwrap();
+    assert_eq!(indent, (99 - 40) / 2, "row: {:?}", first_row);
+
+    // Buffers opened in zen mode have no gutter either
+    let other = temp_dir.path().join("other.txt");
+    std::fs::write(&other, "other file\n").unwrap();
+    harness.open_file(&other).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("│ other file");
+
+    toggle_zen_mode(&mut harness);
+    assert!(!harness.editor().is_zen_mode());
+    assert!(!harness.editor().config().editor.zen_mode);
+    harness.assert_screen_contains("    1 │ other file");
+    harness.assert_screen_contains("other.txt ×");
+    harness.assert_screen_contains("File   Edit");
+}
+
+#[test]
+fn test_zen_mode_from_config() {
+    let mut config = Config::default();
+    config.editor.zen_mode = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+
+    assert!(harness.editor().is_zen_mode());
+    harness.assert_screen_not_contains("[No Name]");
+    harness.assert_screen_not_contains("│ hello");
+    harness.assert_screen_contains("hello");
+}
@@ -553,12 +553,13 @@ fn test_lsp_diagnostic_to_overlay() {
     // Check priority (error should be highest)
     assert_eq!(priority, 100);
 
-    // Check face (should use theme's error background color)
+    // Check face (should use theme's error background, underlined in the error color)
     match face {
-        fresh::view::overlay::OverlayFace::Background { color } => {
-            assert_eq!(color, theme.diagnostic_error_bg);
+        fresh::view::overlay::OverlayFace::Style { style } => {
+            assert_eq!(style.bg, Some(theme.diagnostic_error_bg));
+            assert_eq!(style.underline_color, Some(theme.diagnostic_error_fg));
         }
-        _ => panic!("Expected background face for error diagnostic"),
+        _ => panic!("Expected style face for error diagnostic"),
     }
 }
 